@@ -0,0 +1,129 @@
+//! Battle combat benchmarks
+//!
+//! Compares exchange resolution with and without `ExchangeCache` for two
+//! large armies locked in melee.
+
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use arc_citadel::battle::{resolve_unit_combat, BattleUnit, Element, UnitId, UnitType};
+use arc_citadel::combat::{
+    resolve_exchange, CombatSkill, CombatStance, CombatState, Combatant, ExchangeCache,
+};
+use arc_citadel::core::types::EntityId;
+
+/// Entities per army
+const ARMY_SIZE: usize = 600;
+/// Entities per element
+const ELEMENT_SIZE: usize = 50;
+
+fn army(unit_type: UnitType) -> BattleUnit {
+    let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+    for _ in 0..ARMY_SIZE / ELEMENT_SIZE {
+        let entities = (0..ELEMENT_SIZE).map(|_| EntityId::new()).collect();
+        unit.elements.push(Element::new(entities));
+    }
+    unit
+}
+
+/// Two armies and their entities' combat states, before any fighting
+fn battle() -> (BattleUnit, BattleUnit, HashMap<EntityId, CombatState>) {
+    let attacker = army(UnitType::Spearmen);
+    let defender = army(UnitType::HeavyInfantry);
+    let mut states = HashMap::new();
+    for unit in [&attacker, &defender] {
+        let props = unit.unit_type.default_properties();
+        for &entity in unit.elements.iter().flat_map(|e| &e.entities) {
+            let state = CombatState {
+                skill: CombatSkill::trained(),
+                weapon: props.avg_weapon.clone(),
+                armor: props.avg_armor.clone(),
+                ..CombatState::default()
+            };
+            states.insert(entity, state);
+        }
+    }
+    (attacker, defender, states)
+}
+
+fn combatant(state: &CombatState, stance: CombatStance) -> Combatant {
+    Combatant {
+        weapon: state.weapon.clone(),
+        armor: state.armor.clone(),
+        stance,
+        skill: state.skill,
+        aim: None,
+    }
+}
+
+/// Every attacker against every defender it faces, one exchange each
+fn bench_exchanges(c: &mut Criterion) {
+    let (attacker, defender, states) = battle();
+    let pairs: Vec<(&CombatState, &CombatState)> = attacker
+        .elements
+        .iter()
+        .flat_map(|e| &e.entities)
+        .zip(defender.elements.iter().flat_map(|e| &e.entities))
+        .map(|(a, d)| (&states[a], &states[d]))
+        .collect();
+
+    let mut group = c.benchmark_group("exchanges");
+    group.bench_function("direct", |b| {
+        b.iter(|| {
+            for &(a, d) in &pairs {
+                let attacker = combatant(a, CombatStance::Pressing);
+                let defender = combatant(d, CombatStance::Defensive);
+                black_box(resolve_exchange(&attacker, &defender));
+            }
+        })
+    });
+    group.bench_function("cached", |b| {
+        let mut cache = ExchangeCache::new();
+        b.iter(|| {
+            for &(a, d) in &pairs {
+                let att = cache.loadout(&a.weapon, &a.armor, a.skill.level);
+                let def = cache.loadout(&d.weapon, &d.armor, d.skill.level);
+                black_box(cache.exchange(
+                    (att, CombatStance::Pressing),
+                    (def, CombatStance::Defensive),
+                ));
+            }
+        })
+    });
+    group.finish();
+}
+
+/// One round of melee between the armies, as a battle tick resolves it
+fn bench_unit_combat(c: &mut Criterion) {
+    let (attacker, defender, states) = battle();
+    let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+    let mut group = c.benchmark_group("unit_combat");
+    group.bench_function("fresh_cache", |b| {
+        b.iter_batched(
+            || states.clone(),
+            |mut states| {
+                let mut cache = ExchangeCache::new();
+                resolve_unit_combat(&attacker, &defender, &mut states, &mut cache, &mut rng)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("battle_cache", |b| {
+        let mut cache = ExchangeCache::new();
+        b.iter_batched(
+            || states.clone(),
+            |mut states| {
+                resolve_unit_combat(&attacker, &defender, &mut states, &mut cache, &mut rng)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_exchanges, bench_unit_combat);
+criterion_main!(benches);
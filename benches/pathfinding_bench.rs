@@ -0,0 +1,74 @@
+//! Crowd pathfinding benchmarks
+//!
+//! Compares per-entity A* with `PathCache` (shared flow field and cached
+//! routes) for a thousand villagers converging on one spot behind a wall.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use arc_citadel::core::types::Vec2;
+use arc_citadel::spatial::path_cache::PathCache;
+use arc_citadel::spatial::pathfinding::step_toward;
+use arc_citadel::world::BlockedCells;
+
+/// Villagers heading for the square
+const CROWD: usize = 1000;
+/// Ticks of walking per iteration
+const TICKS: u64 = 10;
+
+/// A wall across x = 40 with a gate at its northern end
+fn town() -> BlockedCells {
+    let mut blocked = BlockedCells::new();
+    for y in -40..30 {
+        blocked.block(40, y);
+    }
+    blocked
+}
+
+/// The crowd, scattered west of the wall
+fn crowd() -> Vec<Vec2> {
+    (0..CROWD)
+        .map(|i| Vec2::new((i % 25) as f32 + 0.5, (i / 25) as f32 - 20.0 + 0.5))
+        .collect()
+}
+
+fn bench_crowd(c: &mut Criterion) {
+    let blocked = town();
+    let square = Vec2::new(60.5, 0.5);
+
+    let mut group = c.benchmark_group("crowd_to_one_destination");
+    group.sample_size(10);
+    group.bench_function("per_entity_a_star", |b| {
+        b.iter_batched(
+            crowd,
+            |mut walkers| {
+                for _ in 0..TICKS {
+                    for pos in &mut walkers {
+                        *pos = step_toward(&blocked, *pos, square, 1.0);
+                    }
+                }
+                black_box(walkers)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("path_cache", |b| {
+        b.iter_batched(
+            crowd,
+            |mut walkers| {
+                let mut cache = PathCache::new();
+                for tick in 0..TICKS {
+                    cache.begin_tick(tick);
+                    for pos in &mut walkers {
+                        *pos = cache.step_toward(&blocked, *pos, square, 1.0);
+                    }
+                }
+                black_box(walkers)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_crowd);
+criterion_main!(benches);
@@ -315,6 +315,8 @@ fn create_gnoll_polity() -> Polity {
         cultural_drift: CulturalDrift::default(),
         relations: HashMap::new(),
         species_state: SpeciesState::Gnoll(GnollState::default()),
+        religion: None,
+        technologies: Vec::new(),
         alive: true,
     }
 }
@@ -338,6 +340,8 @@ fn create_vampire_polity() -> Polity {
         cultural_drift: CulturalDrift::default(),
         relations: HashMap::new(),
         species_state: SpeciesState::Vampire(VampireState::default()),
+        religion: None,
+        technologies: Vec::new(),
         alive: true,
     }
 }
@@ -361,6 +365,8 @@ fn create_kobold_polity() -> Polity {
         cultural_drift: CulturalDrift::default(),
         relations: HashMap::new(),
         species_state: SpeciesState::Kobold(KoboldState::default()),
+        religion: None,
+        technologies: Vec::new(),
         alive: true,
     }
 }
@@ -16,8 +16,14 @@ pub enum ActionId {
     Craft,
     Gather,
     Repair,
+    TendLivestock,
+    Haul,
+    /// Drill a squad in formation on the drill ground
+    Drill,
     TalkTo,
     Help,
+    /// Carry or drag the wounded, the sick, or the dead
+    Carry,
     Trade,
     Attack,
     Defend,
@@ -25,6 +31,9 @@ pub enum ActionId {
     HoldPosition,
     IdleWander,
     IdleObserve,
+    PickUp,
+    Drop,
+    Equip,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -35,21 +44,66 @@ pub enum ActionCategory {
     Social,
     Combat,
     Idle,
+    Items,
 }
 
 impl ActionId {
+    /// Every action, in catalog order
+    pub const ALL: [ActionId; 26] = [
+        ActionId::MoveTo,
+        ActionId::Follow,
+        ActionId::Flee,
+        ActionId::Rest,
+        ActionId::Eat,
+        ActionId::SeekSafety,
+        ActionId::Build,
+        ActionId::Craft,
+        ActionId::Gather,
+        ActionId::Repair,
+        ActionId::TendLivestock,
+        ActionId::Haul,
+        ActionId::Drill,
+        ActionId::TalkTo,
+        ActionId::Help,
+        ActionId::Carry,
+        ActionId::Trade,
+        ActionId::Attack,
+        ActionId::Defend,
+        ActionId::Charge,
+        ActionId::HoldPosition,
+        ActionId::IdleWander,
+        ActionId::IdleObserve,
+        ActionId::PickUp,
+        ActionId::Drop,
+        ActionId::Equip,
+    ];
+
+    /// Look up an action by name ("gather", "TalkTo"; case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| format!("{:?}", action).eq_ignore_ascii_case(name))
+    }
+
     pub fn category(&self) -> ActionCategory {
         match self {
             ActionId::MoveTo | ActionId::Follow | ActionId::Flee => ActionCategory::Movement,
             ActionId::Rest | ActionId::Eat | ActionId::SeekSafety => ActionCategory::Survival,
-            ActionId::Build | ActionId::Craft | ActionId::Gather | ActionId::Repair => {
-                ActionCategory::Work
+            ActionId::Build
+            | ActionId::Craft
+            | ActionId::Gather
+            | ActionId::Repair
+            | ActionId::TendLivestock
+            | ActionId::Haul
+            | ActionId::Drill => ActionCategory::Work,
+            ActionId::TalkTo | ActionId::Help | ActionId::Carry | ActionId::Trade => {
+                ActionCategory::Social
             }
-            ActionId::TalkTo | ActionId::Help | ActionId::Trade => ActionCategory::Social,
             ActionId::Attack | ActionId::Defend | ActionId::Charge | ActionId::HoldPosition => {
                 ActionCategory::Combat
             }
             ActionId::IdleWander | ActionId::IdleObserve => ActionCategory::Idle,
+            ActionId::PickUp | ActionId::Drop | ActionId::Equip => ActionCategory::Items,
         }
     }
 
@@ -60,6 +114,7 @@ impl ActionId {
             ActionId::SeekSafety | ActionId::Flee => vec![(NeedType::Safety, 0.3)],
             ActionId::TalkTo | ActionId::Help => vec![(NeedType::Social, 0.3)],
             ActionId::Build | ActionId::Craft | ActionId::Gather => vec![(NeedType::Purpose, 0.3)],
+            ActionId::TendLivestock | ActionId::Haul => vec![(NeedType::Purpose, 0.2)],
             _ => vec![],
         }
     }
@@ -81,6 +136,8 @@ impl ActionId {
             ActionId::Build => 200, // Substantial work
             ActionId::Craft => 100, // Crafting session
             ActionId::Help => 30,   // Helping someone
+            ActionId::TendLivestock => 60, // Milking, shearing, butchering
+            ActionId::Drill => 120,        // A session on the drill ground
             _ => 0,
         }
     }
@@ -20,6 +20,7 @@ pub fn get_behavior(species: Species) -> Box<dyn PolityBehavior> {
         Species::Gnoll => Box::new(super::species::gnoll::GnollBehavior),
         Species::Vampire => Box::new(super::species::vampire::VampireBehavior),
         Species::Kobold => Box::new(super::species::kobold::KoboldBehavior),
+        Species::Orc => Box::new(super::species::orc::OrcBehavior),
         // Default no-op behavior for other species
         _ => Box::new(DefaultBehavior),
     }
@@ -69,6 +70,8 @@ mod tests {
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         }
     }
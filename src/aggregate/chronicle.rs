@@ -0,0 +1,350 @@
+//! Chronicle - the generated history kept, on disk and queryable
+//!
+//! `simulate` returns a `HistoryLog` of raw events naming polities and
+//! regions by id. A `HistoryChronicle` keeps those events together with the
+//! names they refer to, so the history outlives the simulation run: it saves
+//! as JSON tagged with `CHRONICLE_VERSION`, loads back, and answers queries
+//! by region, by polity, and by years. `describe` renders an event as a line
+//! of prose and `summary` picks out the notable ones, for narrative use.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::aggregate::events::{Event, EventType};
+use crate::aggregate::output::SimulationOutput;
+
+/// Chronicle format version, bumped whenever a saved chronicle changes shape
+pub const CHRONICLE_VERSION: u32 = 1;
+
+/// Errors from saving or loading a chronicle
+#[derive(Error, Debug)]
+pub enum ChronicleError {
+    /// Reading or writing the file failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The chronicle could not be encoded or decoded
+    #[error("Chronicle format error: {0}")]
+    FormatError(#[from] serde_json::Error),
+    /// The chronicle was written by a newer build than this one
+    #[error("Chronicle version {found} is newer than supported ({CHRONICLE_VERSION})")]
+    UnsupportedVersion { found: u32 },
+}
+
+/// A generated history, with the names its events refer to
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HistoryChronicle {
+    pub version: u32,
+    /// The year the history ran to
+    pub final_year: u32,
+    /// Polity names by id, the fallen included
+    pub polities: BTreeMap<u32, String>,
+    /// Region names by id
+    pub regions: BTreeMap<u32, String>,
+    /// Who held each region at the end
+    #[serde(default)]
+    pub holders: BTreeMap<u32, u32>,
+    /// Every event, in the order it happened
+    pub events: Vec<Event>,
+}
+
+impl HistoryChronicle {
+    /// Keep the history of a simulation run
+    pub fn from_output(output: &SimulationOutput) -> Self {
+        let world = &output.final_world;
+        Self {
+            version: CHRONICLE_VERSION,
+            final_year: world.year,
+            polities: world
+                .polities
+                .iter()
+                .map(|p| (p.id.0, p.name.clone()))
+                .collect(),
+            regions: world
+                .regions
+                .iter()
+                .map(|r| (r.id, r.name.clone()))
+                .collect(),
+            holders: world
+                .regions
+                .iter()
+                .filter_map(|r| r.controller.map(|c| (r.id, c)))
+                .collect(),
+            events: output.history.events.clone(),
+        }
+    }
+
+    /// Encode the chronicle as JSON
+    pub fn to_json(&self) -> Result<String, ChronicleError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Decode a chronicle from JSON, refusing one from a newer version
+    pub fn from_json(json: &str) -> Result<Self, ChronicleError> {
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+        let header: Header = serde_json::from_str(json)?;
+        if header.version > CHRONICLE_VERSION {
+            return Err(ChronicleError::UnsupportedVersion {
+                found: header.version,
+            });
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Save the chronicle to a file, replacing it if it exists
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ChronicleError> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Load a chronicle from a file written by `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ChronicleError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Events that took place in a region
+    pub fn events_in_region(&self, region: u32) -> impl Iterator<Item = &Event> {
+        self.events
+            .iter()
+            .filter(move |e| e.location == Some(region) || e.event_type.region() == Some(region))
+    }
+
+    /// Events a polity took part in
+    pub fn events_for_polity(&self, polity: u32) -> impl Iterator<Item = &Event> {
+        self.events.iter().filter(move |e| {
+            e.participants.contains(&polity) || e.event_type.polities().contains(&polity)
+        })
+    }
+
+    /// Events in a span of years
+    pub fn events_between(&self, years: RangeInclusive<u32>) -> impl Iterator<Item = &Event> {
+        self.events.iter().filter(move |e| years.contains(&e.year))
+    }
+
+    fn polity(&self, id: u32) -> &str {
+        self.polities
+            .get(&id)
+            .map_or("an unknown people", |n| n.as_str())
+    }
+
+    fn region(&self, id: u32) -> &str {
+        self.regions
+            .get(&id)
+            .map_or("an unknown land", |n| n.as_str())
+    }
+
+    /// An event as a line of prose, or `None` if it is too minor to tell
+    pub fn describe(&self, event: &Event) -> Option<String> {
+        use EventType::*;
+        let text = match &event.event_type {
+            WarDeclared {
+                aggressor,
+                defender,
+                ..
+            } => format!(
+                "{} declared war on {}",
+                self.polity(*aggressor),
+                self.polity(*defender)
+            ),
+            ReligiousWar {
+                aggressor,
+                defender,
+                holy_site,
+                ..
+            } => format!(
+                "{} began a holy war on {} for {}",
+                self.polity(*aggressor),
+                self.polity(*defender),
+                self.region(*holy_site)
+            ),
+            WarEnded {
+                victor: Some(victor),
+                ..
+            } => format!("{} won its war", self.polity(*victor)),
+            WarEnded { victor: None, .. } => "A war ended with no victor".to_string(),
+            RegionLost {
+                loser,
+                winner,
+                region,
+            } => format!(
+                "{} took {} from {}",
+                self.polity(*winner),
+                self.region(*region),
+                self.polity(*loser)
+            ),
+            Expansion { polity, region } => format!(
+                "{} expanded into {}",
+                self.polity(*polity),
+                self.region(*region)
+            ),
+            Settlement {
+                polity,
+                region,
+                name,
+            } => format!(
+                "{} founded {} in {}",
+                self.polity(*polity),
+                name,
+                self.region(*region)
+            ),
+            AllianceFormed { members } => format!(
+                "An alliance was formed between {}",
+                members
+                    .iter()
+                    .map(|m| self.polity(*m))
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            ),
+            Betrayal { betrayer, victim } => format!(
+                "{} betrayed {}",
+                self.polity(*betrayer),
+                self.polity(*victim)
+            ),
+            CivilWar { polity, .. } => format!("Civil war tore {} apart", self.polity(*polity)),
+            PolityCollapsed { polity, .. } => format!("{} collapsed", self.polity(*polity)),
+            PolityMerged { absorbed, absorber } => format!(
+                "{} was absorbed into {}",
+                self.polity(*absorbed),
+                self.polity(*absorber)
+            ),
+            ReligionFounded { polity, .. } => {
+                format!("A new faith arose among {}", self.polity(*polity))
+            }
+            Schism { polity, .. } => {
+                format!("A schism split the faith of {}", self.polity(*polity))
+            }
+            Innovation { polity, technology } => {
+                format!("{} first learned {}", self.polity(*polity), technology)
+            }
+            GrudgeDeclared {
+                polity, against, ..
+            } => format!(
+                "{} swore a grudge against {}",
+                self.polity(*polity),
+                self.polity(*against)
+            ),
+            Plague { .. } => "Plague swept the land".to_string(),
+            Famine { .. } => "Famine struck".to_string(),
+            _ => return None,
+        };
+        Some(format!("Year {}: {}", event.year, text))
+    }
+
+    /// The last `limit` notable events, as prose, oldest first
+    ///
+    /// With a region, only those that took place there or involved whoever
+    /// held it at the end.
+    pub fn summary(&self, region: Option<u32>, limit: usize) -> Vec<String> {
+        let holder = region.and_then(|r| self.holders.get(&r).copied());
+        let mut lines: Vec<String> = self
+            .events
+            .iter()
+            .rev()
+            .filter(|e| match region {
+                Some(r) => {
+                    e.location == Some(r)
+                        || e.event_type.region() == Some(r)
+                        || holder.is_some_and(|h| {
+                            e.participants.contains(&h) || e.event_type.polities().contains(&h)
+                        })
+                }
+                None => true,
+            })
+            .filter_map(|e| self.describe(e))
+            .take(limit)
+            .collect();
+        lines.reverse();
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::events::HistoryLog;
+
+    fn chronicle() -> HistoryChronicle {
+        let mut history = HistoryLog::new();
+        history.add_event(
+            EventType::Expansion {
+                polity: 1,
+                region: 10,
+            },
+            5,
+            vec![1],
+            Some(10),
+        );
+        history.add_event(
+            EventType::TraditionAdopted {
+                polity: 2,
+                tradition: "Stone songs".to_string(),
+            },
+            12,
+            vec![],
+            None,
+        );
+        history.add_event(
+            EventType::RegionLost {
+                loser: 1,
+                winner: 2,
+                region: 10,
+            },
+            40,
+            vec![1, 2],
+            Some(10),
+        );
+        HistoryChronicle {
+            version: CHRONICLE_VERSION,
+            final_year: 50,
+            polities: [(1, "Aldoria".to_string()), (2, "Khazad".to_string())].into(),
+            regions: [(10, "Greenmere".to_string())].into(),
+            holders: [(10, 2)].into(),
+            events: history.events,
+        }
+    }
+
+    #[test]
+    fn test_chronicle_queries() {
+        let chronicle = chronicle();
+        assert_eq!(chronicle.events_in_region(10).count(), 2);
+        // Found through the event itself, though logged without participants
+        assert_eq!(chronicle.events_for_polity(2).count(), 2);
+        assert_eq!(chronicle.events_between(10..=50).count(), 2);
+        assert_eq!(chronicle.events_between(0..=4).count(), 0);
+    }
+
+    #[test]
+    fn test_chronicle_round_trips_and_refuses_newer_versions() {
+        let chronicle = chronicle();
+        let loaded = HistoryChronicle::from_json(&chronicle.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.events.len(), 3);
+        assert_eq!(loaded.polities[&2], "Khazad");
+
+        let mut newer = chronicle;
+        newer.version = CHRONICLE_VERSION + 1;
+        assert!(matches!(
+            HistoryChronicle::from_json(&newer.to_json().unwrap()),
+            Err(ChronicleError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_chronicle_summary_tells_notable_events() {
+        let chronicle = chronicle();
+        assert_eq!(
+            chronicle.summary(Some(10), 5),
+            vec![
+                "Year 5: Aldoria expanded into Greenmere",
+                "Year 40: Khazad took Greenmere from Aldoria",
+            ]
+        );
+        assert_eq!(chronicle.summary(None, 1).len(), 1);
+    }
+}
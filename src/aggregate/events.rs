@@ -3,7 +3,9 @@
 use serde::{Deserialize, Serialize};
 
 use crate::aggregate::polity::{DecisionType, GrudgeReason, TreatyTerms};
+use crate::aggregate::religion::ReligionId;
 use crate::aggregate::world::WarCause;
+use crate::core::astronomy::CelestialEvent;
 use crate::core::types::PolityId;
 
 /// A historical event
@@ -96,6 +98,33 @@ pub enum EventType {
         value: String,
         direction: f32,
     },
+    Innovation {
+        polity: u32,
+        technology: String,
+    },
+    TechnologyAdopted {
+        polity: u32,
+        technology: String,
+        from: u32,
+    },
+
+    // Religion
+    ReligionFounded {
+        religion: ReligionId,
+        polity: u32,
+        event: CelestialEvent,
+    },
+    ReligiousWar {
+        aggressor: u32,
+        defender: u32,
+        religion: ReligionId,
+        holy_site: u32,
+    },
+    Schism {
+        polity: u32,
+        from: ReligionId,
+        to: ReligionId,
+    },
 
     // Disasters
     Plague {
@@ -190,6 +219,97 @@ pub enum EventType {
     },
 }
 
+impl EventType {
+    /// Every polity the event names
+    pub fn polities(&self) -> Vec<u32> {
+        use EventType::*;
+        match self {
+            WarDeclared {
+                aggressor,
+                defender,
+                ..
+            }
+            | ReligiousWar {
+                aggressor,
+                defender,
+                ..
+            } => vec![*aggressor, *defender],
+            Battle { winner, .. } => vec![*winner],
+            WarEnded { victor, .. } => victor.iter().copied().collect(),
+            AllianceFormed { members } => members.clone(),
+            AllianceBroken { breaker } => vec![*breaker],
+            Treaty { parties, .. } => parties.clone(),
+            Betrayal { betrayer, victim } => vec![*betrayer, *victim],
+            Expansion { polity, .. }
+            | Settlement { polity, .. }
+            | TraditionAdopted { polity, .. }
+            | CulturalDrift { polity, .. }
+            | Innovation { polity, .. }
+            | ReligionFounded { polity, .. }
+            | Schism { polity, .. }
+            | OathSworn { polity, .. }
+            | OathBroken { polity, .. }
+            | GriefEvent { polity, .. }
+            | DeliberationComplete { polity, .. }
+            | Isolation { polity } => vec![*polity],
+            RegionLost { loser, winner, .. } => vec![*loser, *winner],
+            CivilWar {
+                polity,
+                faction_ids,
+            } => std::iter::once(*polity)
+                .chain(faction_ids.iter().copied())
+                .collect(),
+            PolityCollapsed {
+                polity,
+                successor_states,
+            } => std::iter::once(*polity)
+                .chain(successor_states.iter().copied())
+                .collect(),
+            PolityMerged { absorbed, absorber } => vec![*absorbed, *absorber],
+            TechnologyAdopted { polity, from, .. } => vec![*polity, *from],
+            Plague { affected, .. } | Famine { affected } => affected.clone(),
+            GrudgeDeclared {
+                polity, against, ..
+            }
+            | GrudgeSettled { polity, against } => vec![*polity, *against],
+            RaidLaunched { attacker, target } | SpiteRaid { attacker, target } => {
+                vec![attacker.0, target.0]
+            }
+            InfiltrationAttempt {
+                infiltrator,
+                target,
+            }
+            | InfiltrationSuccess {
+                infiltrator,
+                target,
+            } => vec![infiltrator.0, target.0],
+            CorruptionSpreads { polity, .. }
+            | BattleWon { polity }
+            | BattleLost { polity }
+            | TrapConstruction { polity, .. }
+            | DragonTributeOffered { polity }
+            | TrapTriggered { polity, .. } => vec![polity.0],
+            TributeDemanded { from, .. } => vec![from.0],
+            TributePaid { to, .. } => vec![to.0],
+            Siege { .. } => Vec::new(),
+        }
+    }
+
+    /// The region the event took place in, if it names one
+    pub fn region(&self) -> Option<u32> {
+        use EventType::*;
+        match self {
+            Battle { location, .. } => Some(*location),
+            Siege { target, .. } => Some(*target),
+            Expansion { region, .. } | RegionLost { region, .. } | Settlement { region, .. } => {
+                Some(*region)
+            }
+            ReligiousWar { holy_site, .. } => Some(*holy_site),
+            _ => None,
+        }
+    }
+}
+
 /// The complete history log
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct HistoryLog {
@@ -124,6 +124,8 @@ mod tests {
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         }
     }
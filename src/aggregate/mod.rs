@@ -5,21 +5,27 @@
 //! Produces emergent history with species-authentic behavior.
 
 pub mod behavior;
+pub mod chronicle;
 pub mod events;
 pub mod hierarchy;
 pub mod output;
 pub mod polity;
 pub mod region;
+pub mod religion;
 pub mod ruler;
 pub mod simulation;
 pub mod species;
 pub mod systems;
+pub mod technology;
 pub mod world;
 
+pub use chronicle::{ChronicleError, HistoryChronicle, CHRONICLE_VERSION};
 pub use events::{Event, EventType, HistoryLog};
 pub use output::SimulationOutput;
 pub use polity::{Polity, PolityType, SpeciesState};
 pub use region::{Region, ResourceType, Terrain};
+pub use religion::{Deity, Domain, Religion, ReligionId, SettlementFaith};
 pub use ruler::Ruler;
 pub use simulation::{simulate, SimulationConfig};
+pub use technology::{Innovation, SettlementKnowledge, Technology, TechnologyTable};
 pub use world::AggregateWorld;
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::aggregate::events::{EventType, HistoryLog};
+use crate::aggregate::religion::{Religion, SettlementFaith};
+use crate::aggregate::technology::{Innovation, SettlementKnowledge, TechnologyTable};
 use crate::aggregate::world::AggregateWorld;
 
 /// Complete simulation output
@@ -20,6 +22,38 @@ pub struct WorldSnapshot {
     pub year: u32,
     pub regions: Vec<crate::aggregate::region::Region>,
     pub polities: Vec<crate::aggregate::polity::Polity>,
+    #[serde(default)]
+    pub religions: Vec<Religion>,
+    /// The technology table the history was run with
+    #[serde(default)]
+    pub technologies: TechnologyTable,
+    #[serde(default)]
+    pub innovations: Vec<Innovation>,
+}
+
+impl WorldSnapshot {
+    /// The faith a settlement founded in `region` inherits from whoever
+    /// holds it; `None` for unheld land
+    pub fn settlement_faith(&self, region: u32) -> Option<SettlementFaith> {
+        let controller = self.regions.get(region as usize)?.controller?;
+        let polity = self.polities.iter().find(|p| p.id.0 == controller)?;
+        let religion = self
+            .religions
+            .iter()
+            .find(|r| Some(r.id) == polity.religion)?;
+        Some(SettlementFaith::of(religion, region))
+    }
+
+    /// What a settlement founded in `region` can make and field, from the
+    /// technologies of whoever holds it; `None` for unheld land
+    pub fn settlement_knowledge(&self, region: u32) -> Option<SettlementKnowledge> {
+        let controller = self.regions.get(region as usize)?.controller?;
+        let polity = self.polities.iter().find(|p| p.id.0 == controller)?;
+        Some(SettlementKnowledge::of(
+            &self.technologies,
+            &polity.technologies,
+        ))
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -42,7 +76,12 @@ impl SimulationOutput {
         let wars_fought = history
             .events
             .iter()
-            .filter(|e| matches!(e.event_type, EventType::WarDeclared { .. }))
+            .filter(|e| {
+                matches!(
+                    e.event_type,
+                    EventType::WarDeclared { .. } | EventType::ReligiousWar { .. }
+                )
+            })
             .count() as u32;
 
         let total_events = history.events.len() as u32;
@@ -52,6 +91,9 @@ impl SimulationOutput {
                 year: world.year,
                 regions: world.regions,
                 polities: world.polities,
+                religions: world.religions,
+                technologies: world.technologies,
+                innovations: world.innovations,
             },
             history,
             statistics: SimulationStats {
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+use crate::aggregate::religion::ReligionId;
 use crate::core::types::{GovernmentType, PolityId, PolityTier, RulerId, Species};
 
 /// A polity (nation, tribe, hold, grove, etc.)
@@ -36,6 +37,14 @@ pub struct Polity {
     // Species-specific state
     pub species_state: SpeciesState,
 
+    /// The religion it follows
+    #[serde(default)]
+    pub religion: Option<ReligionId>,
+
+    /// Names of the technologies it has
+    #[serde(default)]
+    pub technologies: Vec<String>,
+
     // Alive status
     pub alive: bool,
 }
@@ -945,6 +954,8 @@ mod tests {
             cultural_drift: CulturalDrift::Human(HumanCulturalDrift::default()),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         };
 
@@ -973,6 +984,8 @@ mod tests {
             cultural_drift: CulturalDrift::Human(HumanCulturalDrift::default()),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         };
 
@@ -1005,6 +1018,8 @@ mod tests {
             }),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         };
 
@@ -1030,6 +1045,8 @@ mod tests {
             }),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         };
 
@@ -1060,6 +1077,8 @@ mod tests {
             cultural_drift: CulturalDrift::Human(HumanCulturalDrift::default()),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         };
 
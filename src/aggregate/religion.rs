@@ -0,0 +1,326 @@
+//! Religion - pantheons, holy sites, and the faith a settlement inherits
+//!
+//! Each culture (polities of one species founded under the same celestial
+//! event) worships one pantheon. The founding event names its patron: a
+//! people founded under a blood eclipse keep a god of war, one founded
+//! under both moons full a god of plenty. The patron's domain picks the
+//! kind of land held holy. Schisms split a religion, keeping its gods but
+//! raising a new patron.
+//!
+//! `SettlementFaith` is what a settlement founded in a region takes from
+//! the religion of whoever holds it: handed to worldgen as part of the
+//! founding context, and to a live world as the culture its omens are read
+//! by.
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::aggregate::region::Terrain;
+use crate::core::astronomy::{CelestialEvent, FoundingModifiers, Season};
+use crate::core::types::Species;
+use crate::world::omens::DEFAULT_CULTURE;
+
+/// Unique identifier for a religion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReligionId(pub u32);
+
+/// What a god is the god of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Domain {
+    War,
+    Harvest,
+    Death,
+    Plenty,
+    Secrets,
+    Night,
+    Sky,
+    Hearth,
+    Stone,
+    Forest,
+    Sea,
+}
+
+impl Domain {
+    /// The god a culture founded under `event` takes as its patron
+    pub fn of_event(event: CelestialEvent) -> Self {
+        match event {
+            CelestialEvent::BloodEclipse | CelestialEvent::FullSanguine => Domain::War,
+            CelestialEvent::SilverEclipse | CelestialEvent::FullArgent => Domain::Harvest,
+            CelestialEvent::DoubleEclipse => Domain::Death,
+            CelestialEvent::PerfectDoubleFull | CelestialEvent::NearDoubleFull => Domain::Plenty,
+            CelestialEvent::PerfectDoubleNew => Domain::Secrets,
+            CelestialEvent::NearDoubleNew
+            | CelestialEvent::NewArgent
+            | CelestialEvent::NewSanguine => Domain::Night,
+        }
+    }
+
+    /// The god a species keeps whatever the sky did at its founding
+    pub fn of_species(species: Species) -> Self {
+        match species {
+            Species::Dwarf | Species::StoneGiants | Species::Golem | Species::Kobold => {
+                Domain::Stone
+            }
+            Species::Elf | Species::Dryad | Species::Fey | Species::Satyr | Species::Centaur => {
+                Domain::Forest
+            }
+            Species::Merfolk | Species::Naga | Species::Lizardfolk => Domain::Sea,
+            Species::Harpy => Domain::Sky,
+            _ => Domain::Hearth,
+        }
+    }
+
+    /// The land held holy to this god
+    pub fn sacred_terrain(&self) -> Terrain {
+        match self {
+            Domain::War => Terrain::Hills,
+            Domain::Harvest | Domain::Plenty | Domain::Hearth => Terrain::Plains,
+            Domain::Death | Domain::Night => Terrain::Marsh,
+            Domain::Secrets => Terrain::Desert,
+            Domain::Sky | Domain::Stone => Terrain::Mountain,
+            Domain::Forest => Terrain::Forest,
+            Domain::Sea => Terrain::Coast,
+        }
+    }
+}
+
+const DOMAINS: [Domain; 11] = [
+    Domain::War,
+    Domain::Harvest,
+    Domain::Death,
+    Domain::Plenty,
+    Domain::Secrets,
+    Domain::Night,
+    Domain::Sky,
+    Domain::Hearth,
+    Domain::Stone,
+    Domain::Forest,
+    Domain::Sea,
+];
+
+const NAME_STARTS: [&str; 12] = [
+    "Ar", "Bel", "Cor", "Dae", "Esh", "Hal", "Ith", "Mor", "Nym", "Sar", "Tir", "Vael",
+];
+const NAME_ENDS: [&str; 10] = [
+    "a", "ath", "eon", "is", "or", "oth", "uin", "ara", "ek", "yr",
+];
+
+/// A god in a pantheon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deity {
+    pub name: String,
+    pub domain: Domain,
+}
+
+/// A religion: a pantheon, where it is holy, and whom it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Religion {
+    pub id: ReligionId,
+    pub name: String,
+    pub species: Species,
+    /// The sky its culture was founded under
+    pub founding_event: CelestialEvent,
+    /// The patron first
+    pub pantheon: Vec<Deity>,
+    /// Region IDs
+    pub holy_sites: Vec<u32>,
+    /// Polity that founded it, or broke away to found it
+    pub founder: u32,
+    /// The religion it split from, if it began as a schism
+    pub parent: Option<ReligionId>,
+    pub founded_year: u32,
+}
+
+fn deity_name(rng: &mut ChaCha8Rng) -> String {
+    format!(
+        "{}{}",
+        NAME_STARTS[rng.gen_range(0..NAME_STARTS.len())],
+        NAME_ENDS[rng.gen_range(0..NAME_ENDS.len())]
+    )
+}
+
+impl Religion {
+    /// A new pantheon for a culture of `species` founded under `event`:
+    /// the event's patron, the species' own god, and one or two more
+    pub fn found(
+        id: ReligionId,
+        species: Species,
+        event: CelestialEvent,
+        founder: u32,
+        year: u32,
+        rng: &mut ChaCha8Rng,
+    ) -> Self {
+        let mut domains = vec![Domain::of_event(event), Domain::of_species(species)];
+        for _ in 0..rng.gen_range(1..=2) {
+            domains.push(DOMAINS[rng.gen_range(0..DOMAINS.len())]);
+        }
+        domains.dedup();
+        let mut pantheon: Vec<Deity> = Vec::new();
+        for domain in domains {
+            if pantheon.iter().all(|d| d.domain != domain) {
+                pantheon.push(Deity {
+                    name: deity_name(rng),
+                    domain,
+                });
+            }
+        }
+        let name = format!("Faith of {}", pantheon[0].name);
+        Self {
+            id,
+            name,
+            species,
+            founding_event: event,
+            pantheon,
+            holy_sites: Vec::new(),
+            founder,
+            parent: None,
+            founded_year: year,
+        }
+    }
+
+    /// A religion broken from this one by `founder`: the same gods, with
+    /// another raised to patron
+    pub fn schism(&self, id: ReligionId, founder: u32, founder_name: &str, year: u32) -> Self {
+        let mut pantheon = self.pantheon.clone();
+        if pantheon.len() > 1 {
+            pantheon.rotate_left(1);
+        }
+        Self {
+            id,
+            name: format!("{} Reform of {}", founder_name, pantheon[0].name),
+            species: self.species,
+            founding_event: self.founding_event,
+            pantheon,
+            holy_sites: Vec::new(),
+            founder,
+            parent: Some(self.id),
+            founded_year: year,
+        }
+    }
+
+    pub fn patron(&self) -> &Deity {
+        &self.pantheon[0]
+    }
+
+    /// The culture in `data/omens.toml` whose table reads the sky for this
+    /// faith: the Blood Moon's own for gods of war and death
+    pub fn omen_culture(&self) -> &'static str {
+        match self.patron().domain {
+            Domain::War | Domain::Death => "bloodsworn",
+            _ => DEFAULT_CULTURE,
+        }
+    }
+}
+
+/// The faith a settlement founded in a region inherits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementFaith {
+    pub religion: String,
+    pub patron: String,
+    pub deities: Vec<String>,
+    pub founding_event: CelestialEvent,
+    /// Culture its omens are read by
+    pub omen_culture: String,
+    /// The region is one of the religion's holy sites
+    pub holy_site: bool,
+}
+
+impl SettlementFaith {
+    pub fn of(religion: &Religion, region: u32) -> Self {
+        Self {
+            religion: religion.name.clone(),
+            patron: religion.patron().name.clone(),
+            deities: religion.pantheon.iter().map(|d| d.name.clone()).collect(),
+            founding_event: religion.founding_event,
+            omen_culture: religion.omen_culture().to_string(),
+            holy_site: religion.holy_sites.contains(&region),
+        }
+    }
+
+    /// The founding context for worldgen's hex generation, with the faith
+    /// added; a holy site leans toward sacred hexes
+    pub fn founding_context_json(&self, modifiers: &FoundingModifiers, season: Season) -> String {
+        let json = modifiers.to_founding_context_json(season, Some(self.founding_event));
+        let mut context: serde_json::Value =
+            serde_json::from_str(&json).unwrap_or_else(|_| serde_json::json!({}));
+        if self.holy_site {
+            if let Some(tags) = context["bias_tags"].as_array_mut() {
+                tags.push("sacred".into());
+            }
+        }
+        context["religion"] = self.religion.clone().into();
+        context["patron"] = self.patron.clone().into();
+        context["deities"] = self.deities.clone().into();
+        context["holy_site"] = self.holy_site.into();
+        context.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_founding_event_names_the_patron() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let war = Religion::found(
+            ReligionId(1),
+            Species::Human,
+            CelestialEvent::BloodEclipse,
+            3,
+            0,
+            &mut rng,
+        );
+        assert_eq!(war.patron().domain, Domain::War);
+        assert_eq!(war.omen_culture(), "bloodsworn");
+        assert!(war.pantheon.iter().any(|d| d.domain == Domain::Hearth));
+        assert!(war.name.contains(&war.patron().name));
+
+        let plenty = Religion::found(
+            ReligionId(2),
+            Species::Dwarf,
+            CelestialEvent::PerfectDoubleFull,
+            4,
+            0,
+            &mut rng,
+        );
+        assert_eq!(plenty.omen_culture(), DEFAULT_CULTURE);
+        assert_eq!(plenty.pantheon[1].domain, Domain::Stone);
+
+        let reform = plenty.schism(ReligionId(3), 9, "Kazdun", 120);
+        assert_eq!(reform.parent, Some(plenty.id));
+        assert_eq!(reform.patron().domain, Domain::Stone);
+        assert_eq!(reform.pantheon.len(), plenty.pantheon.len());
+    }
+
+    #[test]
+    fn test_holy_site_settlements_lean_sacred() {
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        let mut religion = Religion::found(
+            ReligionId(1),
+            Species::Human,
+            CelestialEvent::SilverEclipse,
+            1,
+            0,
+            &mut rng,
+        );
+        religion.holy_sites.push(12);
+        let faith = SettlementFaith::of(&religion, 12);
+        assert!(faith.holy_site);
+        assert!(!SettlementFaith::of(&religion, 13).holy_site);
+
+        let modifiers =
+            FoundingModifiers::calculate(100, Season::Spring, &[CelestialEvent::SilverEclipse]);
+        let context: serde_json::Value =
+            serde_json::from_str(&faith.founding_context_json(&modifiers, Season::Spring)).unwrap();
+        assert_eq!(context["astronomical_event"], "silver_eclipse");
+        assert_eq!(context["religion"], faith.religion.as_str());
+        assert!(context["bias_tags"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|t| t == "sacred"));
+    }
+}
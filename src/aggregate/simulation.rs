@@ -82,6 +82,9 @@ pub fn simulate(config: SimulationConfig) -> SimulationOutput {
 
     let mut history = HistoryLog::new();
 
+    // Give every culture its pantheon
+    systems::generate_religions(&mut world, &mut history);
+
     // Main simulation loop
     for year in 0..config.years {
         world.year = year;
@@ -105,7 +108,13 @@ pub fn simulate(config: SimulationConfig) -> SimulationOutput {
         // 4. Process active wars
         systems::resolve_active_wars(&mut world, &mut history, year);
 
-        // 5. End-of-year updates
+        // 5. Holy wars and schisms
+        systems::run_religion(&mut world, &mut history, year);
+
+        // 6. Innovations begin and spread
+        systems::run_technology(&mut world, &mut history, year);
+
+        // 7. End-of-year updates
         systems::update_populations(&mut world);
         systems::decay_relations(&mut world);
         systems::check_polity_viability(&mut world, &mut history, year);
@@ -96,6 +96,8 @@ mod tests {
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Gnoll(GnollState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         }
     }
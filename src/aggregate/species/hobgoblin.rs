@@ -43,6 +43,8 @@ mod tests {
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Hobgoblin(HobgoblinState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         }
     }
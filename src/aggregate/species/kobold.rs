@@ -94,6 +94,8 @@ mod tests {
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Kobold(KoboldState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         }
     }
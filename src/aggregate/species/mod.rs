@@ -20,7 +20,7 @@ mod merfolk;
 mod minotaur;
 mod naga;
 mod ogre;
-mod orc;
+pub mod orc;
 mod revenant;
 mod satyr;
 mod stone_giants;
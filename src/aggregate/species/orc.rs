@@ -1,20 +1,118 @@
-//! Orc-specific polity behavior
+//! Orc-specific polity behavior - Warband archetype
 
+use crate::aggregate::behavior::PolityBehavior;
 use crate::aggregate::events::EventType;
 use crate::aggregate::polity::Polity;
-use crate::aggregate::world::AggregateWorld;
+use crate::aggregate::systems::expansion::find_expansion_targets;
+use crate::aggregate::world::{AggregateWorld, WarCause};
+use crate::core::types::PolityId;
 
-/// Generate Orc-specific events for a polity
-pub fn tick(polity: &Polity, _world: &AggregateWorld, _year: u32) -> Vec<EventType> {
-    let events = Vec::new();
+/// WAAAGH at which the horde goes to war (species/orc.toml behavior_rules)
+const WAAAGH_THRESHOLD: f32 = 0.8;
+/// Tribal strength at which the tribe expands (species/orc.toml behavior_rules)
+const EXPANSION_STRENGTH: f32 = 0.9;
+/// Tribal strength needed to raid weaker neighbors at all
+const RAID_STRENGTH: f32 = 0.4;
 
-    // Access orc-specific state
-    if let Some(_state) = polity.orc_state() {
-        // TODO: Implement Orc-specific behavior
-        // Example: Check conditions, generate events
+pub struct OrcBehavior;
+
+impl PolityBehavior for OrcBehavior {
+    fn tick(&self, polity: &Polity, world: &AggregateWorld, _year: u32) -> Vec<EventType> {
+        let mut events = Vec::new();
+
+        if let Some(state) = polity.orc_state() {
+            // Blood feuds are settled first, whatever the odds
+            if let Some(&target) = state
+                .blood_feuds
+                .iter()
+                .find(|&&id| world.get_polity(id).is_some_and(|p| p.alive))
+            {
+                events.push(EventType::RaidLaunched {
+                    attacker: polity.id,
+                    target: PolityId(target),
+                });
+            } else if state.tribal_strength > RAID_STRENGTH {
+                // Strong tribes raid whoever looks weaker
+                if let Some(target) = find_weaker_neighbor(polity, world) {
+                    events.push(EventType::RaidLaunched {
+                        attacker: polity.id,
+                        target,
+                    });
+                }
+            }
+
+            // The WAAAGH turns raiding into war
+            if state.waaagh_level > WAAAGH_THRESHOLD {
+                if let Some(target) = find_weaker_neighbor(polity, world) {
+                    events.push(EventType::WarDeclared {
+                        aggressor: polity.id.0,
+                        defender: target.0,
+                        cause: WarCause::Expansion,
+                    });
+                }
+            }
+
+            if state.tribal_strength > EXPANSION_STRENGTH {
+                if let Some(&region) = find_expansion_targets(polity, world).unclaimed.first() {
+                    events.push(EventType::Expansion {
+                        polity: polity.id.0,
+                        region,
+                    });
+                }
+            }
+        }
+
+        events
     }
 
-    events
+    fn on_event(&self, polity: &mut Polity, event: &EventType, _world: &AggregateWorld) {
+        let id = polity.id;
+        if let Some(state) = polity.orc_state_mut() {
+            match event {
+                EventType::BattleWon { .. } => {
+                    state.waaagh_level = (state.waaagh_level + 0.2).min(1.0);
+                    state.tribal_strength = (state.tribal_strength + 0.05).min(1.0);
+                }
+                EventType::BattleLost { .. } => {
+                    state.waaagh_level = (state.waaagh_level - 0.3).max(0.0);
+                    state.tribal_strength = (state.tribal_strength - 0.1).max(0.0);
+                }
+                // Whoever raids an orc tribe earns a blood feud
+                EventType::RaidLaunched { attacker, target }
+                    if *target == id && !state.blood_feuds.contains(&attacker.0) =>
+                {
+                    state.blood_feuds.push(attacker.0);
+                }
+                // A raid on a feud target settles the feud
+                EventType::RaidLaunched { attacker, target } if *attacker == id => {
+                    state.blood_feuds.retain(|&feud| feud != target.0);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A neighbor weaker than the horde, not already at war with it or allied
+fn find_weaker_neighbor(polity: &Polity, world: &AggregateWorld) -> Option<PolityId> {
+    world
+        .get_neighbors(polity.id)
+        .into_iter()
+        .find(|&neighbor_id| {
+            let settled = polity
+                .relations
+                .get(&neighbor_id.0)
+                .is_some_and(|rel| rel.at_war || rel.alliance);
+            !settled
+                && world
+                    .get_polity_by_polity_id(neighbor_id)
+                    .is_some_and(|n| n.alive && n.military_strength < polity.military_strength)
+        })
+}
+
+/// Legacy tick function for backward compatibility with species/mod.rs dispatch
+pub fn tick(polity: &Polity, world: &AggregateWorld, year: u32) -> Vec<EventType> {
+    OrcBehavior.tick(polity, world, year)
 }
 
 #[cfg(test)]
@@ -43,14 +141,70 @@ mod tests {
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Orc(OrcState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         }
     }
 
+    fn create_test_world() -> AggregateWorld {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        AggregateWorld::new(vec![], vec![], ChaCha8Rng::seed_from_u64(42))
+    }
+
     #[test]
     fn test_orc_state_accessor() {
         let polity = create_test_polity();
         let state = polity.orc_state();
         assert!(state.is_some());
     }
+
+    #[test]
+    fn test_raided_tribe_swears_blood_feud() {
+        let mut polity = create_test_polity();
+        let world = create_test_world();
+
+        OrcBehavior.on_event(
+            &mut polity,
+            &EventType::RaidLaunched {
+                attacker: PolityId(7),
+                target: PolityId(1),
+            },
+            &world,
+        );
+        assert_eq!(polity.orc_state().unwrap().blood_feuds, vec![7]);
+
+        // Raiding them back settles it
+        OrcBehavior.on_event(
+            &mut polity,
+            &EventType::RaidLaunched {
+                attacker: PolityId(1),
+                target: PolityId(7),
+            },
+            &world,
+        );
+        assert!(polity.orc_state().unwrap().blood_feuds.is_empty());
+    }
+
+    #[test]
+    fn test_victories_feed_the_waaagh() {
+        let mut polity = create_test_polity();
+        let world = create_test_world();
+
+        for _ in 0..5 {
+            OrcBehavior.on_event(
+                &mut polity,
+                &EventType::BattleWon {
+                    polity: PolityId(1),
+                },
+                &world,
+            );
+        }
+        assert!(polity.orc_state().unwrap().waaagh_level > WAAAGH_THRESHOLD);
+
+        // No neighbors, so nothing to raid or declare war on
+        assert!(OrcBehavior.tick(&polity, &world, 1).is_empty());
+    }
 }
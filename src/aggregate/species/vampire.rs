@@ -104,6 +104,8 @@ mod tests {
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Vampire(VampireState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         }
     }
@@ -24,3 +24,25 @@ pub fn decay_relations(world: &mut AggregateWorld) {
         }
     }
 }
+
+/// How well `from` regards `toward`, -100 to +100: opinion, half again of
+/// trust, weighed down by war and grudges and lifted by alliance. Polities
+/// with no relation are neutral.
+pub fn relation_score(world: &AggregateWorld, from: u32, toward: u32) -> i32 {
+    let Some(relation) = world
+        .get_polity(from)
+        .and_then(|polity| polity.relations.get(&toward))
+    else {
+        return 0;
+    };
+
+    let grudges: f32 = relation.grudges.iter().map(|g| g.severity).sum();
+    let mut score = relation.opinion + relation.trust / 2 - (grudges * 20.0) as i32;
+    if relation.at_war {
+        score -= 50;
+    }
+    if relation.alliance {
+        score += 25;
+    }
+    score.clamp(-100, 100)
+}
@@ -799,6 +799,8 @@ fn generate_species_polities(
             cultural_drift,
             relations: HashMap::new(),
             species_state,
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         };
 
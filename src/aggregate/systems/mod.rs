@@ -4,12 +4,16 @@ mod diplomacy;
 pub mod expansion;
 mod generation;
 mod population;
+mod religion;
 mod resolution;
+mod technology;
 mod warfare;
 
-pub use diplomacy::decay_relations;
+pub use diplomacy::{decay_relations, relation_score};
 pub use expansion::{calculate_human_expansion_pressure, find_expansion_targets};
 pub use generation::{generate_map, generate_polities, initialize_relations};
 pub use population::update_populations;
+pub use religion::{generate_religions, piety, run_religion};
 pub use resolution::{apply_cultural_drift, check_polity_viability, event_priority, resolve_event};
+pub use technology::run_technology;
 pub use warfare::resolve_active_wars;
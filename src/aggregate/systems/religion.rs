@@ -0,0 +1,317 @@
+//! Religion - founding pantheons, holy wars, and schisms
+
+use std::collections::{HashSet, VecDeque};
+
+use rand::Rng;
+
+use crate::aggregate::events::{EventType, HistoryLog};
+use crate::aggregate::polity::{CulturalDrift, Polity};
+use crate::aggregate::region::Terrain;
+use crate::aggregate::religion::{Religion, ReligionId};
+use crate::aggregate::systems::resolve_event;
+use crate::aggregate::world::AggregateWorld;
+use crate::core::astronomy::{AstronomicalState, CelestialEvent, YEAR_LENGTH};
+
+/// Tries at a pantheon whose name no other religion has
+const NAMING_DRAWS: u32 = 10;
+/// Founding days are drawn from this many years of sky
+const FOUNDING_YEARS: u32 = 50;
+/// Draws before settling for a plain full moon
+const FOUNDING_DRAWS: u32 = 200;
+/// Opinion polities of one faith start with of each other
+const SHARED_FAITH_OPINION: i32 = 15;
+/// Yearly chance a polity goes to war for a holy site held by another faith
+const HOLY_WAR_CHANCE: f64 = 0.05;
+/// Fewest polities a religion needs before it can split
+const SCHISM_MIN_ADHERENTS: usize = 3;
+/// Yearly chance a large enough religion splits
+const SCHISM_CHANCE: f64 = 0.02;
+/// A polity breaks away only if it thinks less than this of the religion's head
+const SCHISM_OPINION: i32 = 0;
+/// Opinion lost between the head and the polity that broke away
+const SCHISM_OPINION_HIT: i32 = 25;
+
+/// How much a polity's culture leans on faith (-0.5 to 0.5)
+pub fn piety(polity: &Polity) -> f32 {
+    match &polity.cultural_drift {
+        CulturalDrift::Human(d) => d.piety_emphasis,
+        CulturalDrift::Dwarf(d) => d.ancestor_weight,
+        CulturalDrift::Elf(d) => d.memory_weight,
+        CulturalDrift::Generic(d) => d.traditionalism,
+    }
+}
+
+/// The most portentous event in the sky on a day drawn at random
+fn founding_event(world: &mut AggregateWorld) -> CelestialEvent {
+    let significance = |e: &CelestialEvent| match (e.is_rare(), e.is_common()) {
+        (true, _) => 2,
+        (_, true) => 0,
+        _ => 1,
+    };
+    for _ in 0..FOUNDING_DRAWS {
+        let day = world.rng.gen_range(0..FOUNDING_YEARS * YEAR_LENGTH as u32);
+        if let Some(event) = AstronomicalState::events_for_day(day)
+            .into_iter()
+            .max_by_key(significance)
+        {
+            return event;
+        }
+    }
+    CelestialEvent::FullArgent
+}
+
+/// The nearest region to `from` of the land the patron favours, whoever
+/// holds it
+fn nearest_sacred(world: &AggregateWorld, from: u32, sacred: Terrain) -> Option<u32> {
+    let mut seen = HashSet::from([from]);
+    let mut frontier = VecDeque::from([from]);
+    while let Some(id) = frontier.pop_front() {
+        let region = world.get_region(id)?;
+        if id != from && region.terrain == sacred {
+            return Some(id);
+        }
+        for &next in &region.neighbors {
+            if seen.insert(next) {
+                frontier.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Give every culture its pantheon: polities of one species founded under
+/// the same event share a religion, holy at the founder's capital and at
+/// the nearest land its patron favours
+pub fn generate_religions(world: &mut AggregateWorld, history: &mut HistoryLog) {
+    let ids: Vec<u32> = world
+        .polities
+        .iter()
+        .filter(|p| p.alive)
+        .map(|p| p.id.0)
+        .collect();
+
+    for polity_id in ids {
+        let event = founding_event(world);
+        let Some(polity) = world.get_polity(polity_id) else {
+            continue;
+        };
+        let (species, capital) = (polity.species, polity.capital);
+        let existing = world
+            .religions
+            .iter()
+            .find(|r| r.species == species && r.founding_event == event && r.parent.is_none())
+            .map(|r| r.id);
+        let religion = match existing {
+            Some(id) => id,
+            None => {
+                let id = world.next_religion_id();
+                let mut religion =
+                    Religion::found(id, species, event, polity_id, world.year, &mut world.rng);
+                for _ in 0..NAMING_DRAWS {
+                    if world.religions.iter().all(|r| r.name != religion.name) {
+                        break;
+                    }
+                    religion =
+                        Religion::found(id, species, event, polity_id, world.year, &mut world.rng);
+                }
+                religion.holy_sites.push(capital);
+                let sacred = religion.patron().domain.sacred_terrain();
+                religion
+                    .holy_sites
+                    .extend(nearest_sacred(world, capital, sacred));
+                world.religions.push(religion);
+                history.add_event(
+                    EventType::ReligionFounded {
+                        religion: id,
+                        polity: polity_id,
+                        event,
+                    },
+                    world.year,
+                    vec![polity_id],
+                    Some(capital),
+                );
+                id
+            }
+        };
+        if let Some(polity) = world.get_polity_mut(polity_id) {
+            polity.religion = Some(religion);
+        }
+    }
+
+    let faiths: Vec<(u32, Option<ReligionId>)> = world
+        .polities
+        .iter()
+        .map(|p| (p.id.0, p.religion))
+        .collect();
+    for polity in &mut world.polities {
+        for (other, faith) in &faiths {
+            if *other == polity.id.0 || faith.is_none() || *faith != polity.religion {
+                continue;
+            }
+            if let Some(relation) = polity.relations.get_mut(other) {
+                relation.opinion = (relation.opinion + SHARED_FAITH_OPINION).min(100);
+            }
+        }
+    }
+}
+
+/// Go to war for holy sites held by other faiths, and split religions
+/// whose members have turned on their head
+pub fn run_religion(world: &mut AggregateWorld, history: &mut HistoryLog, year: u32) {
+    declare_holy_wars(world, history, year);
+    split_religions(world, history, year);
+}
+
+fn declare_holy_wars(world: &mut AggregateWorld, history: &mut HistoryLog, year: u32) {
+    let mut claims = Vec::new();
+    for polity in world.polities.iter().filter(|p| p.alive) {
+        let Some(religion) = polity.religion.and_then(|id| world.get_religion(id)) else {
+            continue;
+        };
+        let neighbors = world.get_neighbors(polity.id);
+        for &site in &religion.holy_sites {
+            let Some(holder) = world.get_region(site).and_then(|r| r.controller) else {
+                continue;
+            };
+            let Some(holder) = world.get_polity(holder) else {
+                continue;
+            };
+            let at_war = polity.relations.get(&holder.id.0).is_some_and(|r| r.at_war);
+            if holder.id != polity.id
+                && holder.alive
+                && holder.religion != polity.religion
+                && neighbors.contains(&holder.id)
+                && !at_war
+            {
+                let chance = HOLY_WAR_CHANCE * (1.0 + 2.0 * piety(polity) as f64);
+                claims.push((polity.id.0, holder.id.0, religion.id, site, chance));
+                break;
+            }
+        }
+    }
+
+    for (aggressor, defender, religion, holy_site, chance) in claims {
+        if !world.rng.gen_bool(chance.clamp(0.0, 1.0)) {
+            continue;
+        }
+        let at_war = world
+            .get_polity(aggressor)
+            .and_then(|p| p.relations.get(&defender))
+            .is_some_and(|r| r.at_war);
+        if at_war {
+            continue;
+        }
+        resolve_event(
+            world,
+            history,
+            EventType::ReligiousWar {
+                aggressor,
+                defender,
+                religion,
+                holy_site,
+            },
+            year,
+        );
+    }
+}
+
+fn split_religions(world: &mut AggregateWorld, history: &mut HistoryLog, year: u32) {
+    for i in 0..world.religions.len() {
+        let from = world.religions[i].id;
+        let adherents: Vec<&Polity> = world
+            .polities
+            .iter()
+            .filter(|p| p.alive && p.religion == Some(from))
+            .collect();
+        if adherents.len() < SCHISM_MIN_ADHERENTS {
+            continue;
+        }
+        let founder = world.religions[i].founder;
+        let head = if adherents.iter().any(|p| p.id.0 == founder) {
+            founder
+        } else {
+            adherents[0].id.0
+        };
+        let opinion_of_head = |p: &Polity| p.relations.get(&head).map_or(0, |r| r.opinion);
+        let Some(dissenter) = adherents
+            .iter()
+            .filter(|p| p.id.0 != head && opinion_of_head(p) < SCHISM_OPINION)
+            .min_by_key(|p| opinion_of_head(p))
+            .map(|p| (p.id.0, p.name.clone(), p.capital))
+        else {
+            continue;
+        };
+        if !world.rng.gen_bool(SCHISM_CHANCE) {
+            continue;
+        }
+
+        let (polity_id, polity_name, capital) = dissenter;
+        let to = world.next_religion_id();
+        let mut reform = world.religions[i].schism(to, polity_id, &polity_name, year);
+        reform.holy_sites.push(capital);
+        world.religions.push(reform);
+        for polity in &mut world.polities {
+            let follows =
+                polity.id.0 == polity_id || polity.parent.is_some_and(|p| p.0 == polity_id);
+            if follows && polity.religion == Some(from) {
+                polity.religion = Some(to);
+            }
+        }
+        for (a, b) in [(polity_id, head), (head, polity_id)] {
+            if let Some(relation) = world
+                .get_polity_mut(a)
+                .and_then(|p| p.relations.get_mut(&b))
+            {
+                relation.opinion = (relation.opinion - SCHISM_OPINION_HIT).max(-100);
+            }
+        }
+        history.add_event(
+            EventType::Schism {
+                polity: polity_id,
+                from,
+                to,
+            },
+            year,
+            vec![polity_id, head],
+            Some(capital),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::simulation::{simulate, SimulationConfig};
+
+    #[test]
+    fn test_every_polity_is_born_into_a_faith() {
+        let output = simulate(SimulationConfig {
+            years: 0,
+            ..Default::default()
+        });
+        let world = &output.final_world;
+
+        assert!(!world.religions.is_empty());
+        for polity in &world.polities {
+            let id = polity.religion.expect("every polity has a religion");
+            let religion = world.religions.iter().find(|r| r.id == id).unwrap();
+            assert_eq!(religion.species, polity.species);
+        }
+        for religion in &world.religions {
+            assert!(!religion.holy_sites.is_empty());
+            let founder = world
+                .polities
+                .iter()
+                .find(|p| p.id.0 == religion.founder)
+                .unwrap();
+            assert!(religion.holy_sites.contains(&founder.capital));
+        }
+        let founded = output
+            .history
+            .events
+            .iter()
+            .filter(|e| matches!(e.event_type, EventType::ReligionFounded { .. }))
+            .count();
+        assert_eq!(founded, world.religions.len());
+    }
+}
@@ -8,6 +8,7 @@ use crate::aggregate::world::{AggregateWorld, War, WarCause, WarState};
 pub fn event_priority(event: &EventType) -> u32 {
     match event {
         EventType::WarDeclared { .. } => 10,
+        EventType::ReligiousWar { .. } => 10,
         EventType::GrudgeDeclared { .. } => 15,
         EventType::Betrayal { .. } => 20,
         EventType::AllianceFormed { .. } => 30,
@@ -51,6 +52,26 @@ pub fn resolve_event(
             );
         }
 
+        EventType::ReligiousWar {
+            aggressor,
+            defender,
+            religion,
+            holy_site,
+        } => {
+            resolve_war_declaration(world, aggressor, defender, WarCause::Religion, year);
+            history.add_event(
+                EventType::ReligiousWar {
+                    aggressor,
+                    defender,
+                    religion,
+                    holy_site,
+                },
+                year,
+                vec![aggressor, defender],
+                Some(holy_site),
+            );
+        }
+
         EventType::Expansion { polity, region } => {
             resolve_expansion(world, polity, region);
             history.add_event(
@@ -272,7 +293,16 @@ fn resolve_civil_war(
     }
 
     // Collect data before modifying world
-    let (rebel_id, polity_name, polity_species, polity_type, polity_species_state, government) = {
+    let (
+        rebel_id,
+        polity_name,
+        polity_species,
+        polity_type,
+        polity_species_state,
+        government,
+        religion,
+        technologies,
+    ) = {
         let polity = match world.get_polity(polity_id) {
             Some(p) => p,
             None => return,
@@ -285,6 +315,8 @@ fn resolve_civil_war(
             polity.polity_type,
             polity.species_state.clone(),
             polity.government,
+            polity.religion,
+            polity.technologies.clone(),
         )
     };
 
@@ -316,6 +348,9 @@ fn resolve_civil_war(
         cultural_drift: crate::aggregate::polity::CulturalDrift::default(),
         relations: std::collections::HashMap::new(),
         species_state: polity_species_state,
+        // Rebels keep the faith they were raised in, and what they know
+        religion,
+        technologies,
         alive: true,
     };
 
@@ -533,6 +568,27 @@ pub fn accumulate_drift_for_behavior(world: &mut AggregateWorld, event: &EventTy
             }
         }
 
+        // Holy wars deepen the faith of those who wage them
+        EventType::ReligiousWar { aggressor, .. } => {
+            if let Some(polity) = world.get_polity_mut(*aggressor) {
+                match &mut polity.cultural_drift {
+                    CulturalDrift::Human(d) => {
+                        d.martial_tradition = (d.martial_tradition + DRIFT_RATE).min(DRIFT_MAX);
+                        d.piety_emphasis = (d.piety_emphasis + DRIFT_RATE).min(DRIFT_MAX);
+                    }
+                    CulturalDrift::Dwarf(d) => {
+                        d.ancestor_weight = (d.ancestor_weight + DRIFT_RATE * 0.5).min(DRIFT_MAX);
+                    }
+                    CulturalDrift::Elf(d) => {
+                        d.memory_weight = (d.memory_weight + DRIFT_RATE * 0.5).min(DRIFT_MAX);
+                    }
+                    CulturalDrift::Generic(d) => {
+                        d.traditionalism = (d.traditionalism + DRIFT_RATE).min(DRIFT_MAX);
+                    }
+                }
+            }
+        }
+
         // Expansion increases expansionist drive
         EventType::Expansion { polity, .. } => {
             if let Some(p) = world.get_polity_mut(*polity) {
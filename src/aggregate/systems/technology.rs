@@ -0,0 +1,291 @@
+//! Technology - innovations beginning with events and spreading by trade and war
+
+use rand::Rng;
+
+use crate::aggregate::events::{EventType, HistoryLog};
+use crate::aggregate::polity::{Relation, TreatyTerms};
+use crate::aggregate::technology::{Innovation, InnovationOrigin};
+use crate::aggregate::world::AggregateWorld;
+use crate::core::types::PolityId;
+
+/// Yearly chance a technology passes along a trade route
+const TRADE_SPREAD_CHANCE: f64 = 0.08;
+/// Yearly chance a technology passes between polities at war
+const WAR_SPREAD_CHANCE: f64 = 0.04;
+/// Neighbours at peace trade once they think this well of each other
+const TRADE_OPINION: i32 = 20;
+
+/// Begin innovations set off by this year's events, then spread what
+/// polities know one step along their trade routes and wars
+pub fn run_technology(world: &mut AggregateWorld, history: &mut HistoryLog, year: u32) {
+    begin_innovations(world, history, year);
+    spread_technologies(world, history, year);
+}
+
+fn begin_innovations(world: &mut AggregateWorld, history: &mut HistoryLog, year: u32) {
+    let origins: Vec<(InnovationOrigin, Vec<u32>)> = history
+        .events_for_year(year)
+        .filter_map(|e| InnovationOrigin::of_event(&e.event_type))
+        .collect();
+
+    for (origin, polities) in origins {
+        for polity_id in polities {
+            let Some(polity) = world.get_polity(polity_id).filter(|p| p.alive) else {
+                continue;
+            };
+            let capital = polity.capital;
+            let begun = world
+                .technologies
+                .technologies
+                .iter()
+                .filter(|t| t.origin == origin)
+                .filter(|t| world.innovations.iter().all(|i| i.technology != t.name))
+                .map(|t| (t.name.clone(), t.chance))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .find(|&(_, chance)| world.rng.gen_bool(chance.clamp(0.0, 1.0)));
+            let Some((technology, _)) = begun else {
+                continue;
+            };
+            if let Some(polity) = world.get_polity_mut(polity_id) {
+                polity.technologies.push(technology.clone());
+            }
+            world.innovations.push(Innovation {
+                technology: technology.clone(),
+                polity: polity_id,
+                year,
+            });
+            history.add_event(
+                EventType::Innovation {
+                    polity: polity_id,
+                    technology,
+                },
+                year,
+                vec![polity_id],
+                Some(capital),
+            );
+        }
+    }
+}
+
+/// Whether two polities trade: by treaty, as allies, or as neighbours at
+/// peace who think well enough of each other
+fn trades(relation: &Relation, neighbours: bool) -> bool {
+    !relation.at_war
+        && (relation.alliance
+            || relation
+                .treaties
+                .iter()
+                .any(|t| matches!(t.terms, TreatyTerms::Trade))
+            || (neighbours && relation.opinion >= TRADE_OPINION))
+}
+
+fn spread_technologies(world: &mut AggregateWorld, history: &mut HistoryLog, year: u32) {
+    // Each polity learns from what the others knew at the start of the year
+    let mut routes: Vec<(u32, u32, f64)> = Vec::new();
+    for polity in world.polities.iter().filter(|p| p.alive) {
+        let neighbours = world.get_neighbors(polity.id);
+        let mut others: Vec<u32> = polity.relations.keys().copied().collect();
+        others.sort_unstable();
+        for other in others {
+            let relation = &polity.relations[&other];
+            let chance = if relation.at_war {
+                WAR_SPREAD_CHANCE
+            } else if trades(relation, neighbours.contains(&PolityId(other))) {
+                TRADE_SPREAD_CHANCE
+            } else {
+                continue;
+            };
+            if world.get_polity(other).is_some_and(|p| p.alive) {
+                routes.push((polity.id.0, other, chance));
+            }
+        }
+    }
+
+    let mut adoptions: Vec<(u32, String, u32)> = Vec::new();
+    for (polity_id, from, chance) in routes {
+        let (Some(polity), Some(source)) = (world.get_polity(polity_id), world.get_polity(from))
+        else {
+            continue;
+        };
+        let species = polity.species;
+        let new: Vec<String> = source
+            .technologies
+            .iter()
+            .filter(|t| !polity.technologies.contains(t))
+            .filter(|t| !adoptions.iter().any(|(p, a, _)| *p == polity_id && a == *t))
+            .cloned()
+            .collect();
+        for name in new {
+            let Some(technology) = world.technologies.get(&name) else {
+                continue;
+            };
+            let chance = chance * technology.adoption(species) as f64;
+            if world.rng.gen_bool(chance.clamp(0.0, 1.0)) {
+                adoptions.push((polity_id, name, from));
+            }
+        }
+    }
+
+    for (polity_id, technology, from) in adoptions {
+        let Some(polity) = world.get_polity_mut(polity_id) else {
+            continue;
+        };
+        polity.technologies.push(technology.clone());
+        let capital = polity.capital;
+        history.add_event(
+            EventType::TechnologyAdopted {
+                polity: polity_id,
+                technology,
+                from,
+            },
+            year,
+            vec![polity_id, from],
+            Some(capital),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::simulation::{simulate, SimulationConfig};
+    use crate::aggregate::systems::{generate_map, generate_polities, initialize_relations};
+    use crate::aggregate::technology::TechnologyTable;
+    use crate::core::types::Species;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const TABLE: &str = r#"
+[[technologies]]
+name = "masonry"
+origin = "Expansion"
+chance = 1.0
+adoption = { Elf = 0.0 }
+blueprints = ["stone_wall"]
+"#;
+
+    fn world() -> AggregateWorld {
+        let config = SimulationConfig::default();
+        let rng = ChaCha8Rng::seed_from_u64(5);
+        let mut regions = generate_map(&config.map, rng.clone());
+        let (polities, territory) = generate_polities(&regions, &config.polities, rng.clone());
+        for (region, polity) in territory {
+            regions[region as usize].controller = Some(polity);
+        }
+        let mut world = AggregateWorld::new(regions, polities, rng);
+        initialize_relations(&mut world);
+        world.technologies = TechnologyTable::parse(TABLE).unwrap();
+        world
+    }
+
+    fn of_species(world: &AggregateWorld, species: Species) -> u32 {
+        world
+            .polities
+            .iter()
+            .find(|p| p.species == species)
+            .unwrap()
+            .id
+            .0
+    }
+
+    #[test]
+    fn test_innovations_begin_once() {
+        let mut world = world();
+        let mut history = HistoryLog::new();
+        let first = of_species(&world, Species::Human);
+        let second = of_species(&world, Species::Dwarf);
+        for (year, polity) in [(0, first), (1, second)] {
+            history.add_event(
+                EventType::Expansion { polity, region: 0 },
+                year,
+                vec![polity],
+                None,
+            );
+            begin_innovations(&mut world, &mut history, year);
+        }
+
+        assert_eq!(world.innovations.len(), 1);
+        assert_eq!(world.innovations[0].polity, first);
+        let masonry = "masonry".to_string();
+        assert!(world
+            .get_polity(first)
+            .unwrap()
+            .technologies
+            .contains(&masonry));
+        assert!(!world
+            .get_polity(second)
+            .unwrap()
+            .technologies
+            .contains(&masonry));
+    }
+
+    #[test]
+    fn test_technologies_spread_by_trade_to_those_who_take_to_them() {
+        let mut world = world();
+        let mut history = HistoryLog::new();
+        let source = of_species(&world, Species::Dwarf);
+        let human = of_species(&world, Species::Human);
+        let elf = of_species(&world, Species::Elf);
+        world
+            .get_polity_mut(source)
+            .unwrap()
+            .technologies
+            .push("masonry".into());
+        for polity in [human, elf] {
+            let relation = world
+                .get_polity_mut(polity)
+                .unwrap()
+                .relations
+                .entry(source)
+                .or_default();
+            relation.at_war = false;
+            relation.alliance = true;
+        }
+
+        for year in 0..200 {
+            spread_technologies(&mut world, &mut history, year);
+        }
+        let knows = |world: &AggregateWorld, id| {
+            world
+                .get_polity(id)
+                .unwrap()
+                .technologies
+                .iter()
+                .any(|t| t == "masonry")
+        };
+        assert!(knows(&world, human));
+        assert!(!knows(&world, elf));
+        assert!(history.events.iter().any(|e| matches!(
+            e.event_type,
+            EventType::TechnologyAdopted { polity, from, .. } if polity == human && from == source
+        )));
+    }
+
+    #[test]
+    fn test_every_known_technology_began_somewhere() {
+        let output = simulate(SimulationConfig {
+            years: 100,
+            ..Default::default()
+        });
+        let world = &output.final_world;
+        for polity in &world.polities {
+            for technology in &polity.technologies {
+                assert!(world
+                    .innovations
+                    .iter()
+                    .any(|i| &i.technology == technology));
+            }
+        }
+        let begun = output
+            .history
+            .events
+            .iter()
+            .filter(|e| matches!(e.event_type, EventType::Innovation { .. }))
+            .count();
+        assert_eq!(begun, world.innovations.len());
+        assert!(world
+            .settlement_knowledge(world.polities[0].capital)
+            .is_some());
+    }
+}
@@ -0,0 +1,268 @@
+//! Technology - innovations, where they began, and what they unlock
+//!
+//! The technology table (`data/technologies.toml`) lists the innovations a
+//! culture can come by. Each begins once, in one polity, when something in
+//! its history sets it off: taking new land, going to war, conquering,
+//! making an alliance, or founding a faith. From there it spreads to the
+//! polity's trading partners and to its enemies at war, faster to peoples
+//! who take to it (see `systems::run_technology`).
+//!
+//! An innovation unlocks recipes, blueprints, and unit types; anything no
+//! innovation unlocks is known to all. `SettlementKnowledge` is what a
+//! settlement founded in a region inherits from whoever holds it, handed to
+//! worldgen alongside its faith.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::aggregate::events::EventType;
+use crate::battle::UnitType;
+use crate::core::types::Species;
+
+/// Errors from loading the technology table
+#[derive(Error, Debug)]
+pub enum TechnologyError {
+    /// Reading the file failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The file is not valid technology TOML
+    #[error("Parse error: {0}")]
+    ParseError(#[from] toml::de::Error),
+    /// Two technologies share a name
+    #[error("Technology '{0}' is listed twice")]
+    Duplicate(String),
+}
+
+/// The kind of event an innovation can begin with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InnovationOrigin {
+    /// Taking new land
+    Expansion,
+    /// Going to war, on either side
+    War,
+    /// Taking land from another polity
+    Conquest,
+    /// Making an alliance
+    Alliance,
+    /// Founding a faith or breaking away from one
+    Faith,
+}
+
+impl InnovationOrigin {
+    /// The origin an event counts as, and the polities it can happen to
+    pub fn of_event(event: &EventType) -> Option<(Self, Vec<u32>)> {
+        match event {
+            EventType::Expansion { polity, .. } | EventType::Settlement { polity, .. } => {
+                Some((Self::Expansion, vec![*polity]))
+            }
+            EventType::WarDeclared {
+                aggressor,
+                defender,
+                ..
+            }
+            | EventType::ReligiousWar {
+                aggressor,
+                defender,
+                ..
+            } => Some((Self::War, vec![*aggressor, *defender])),
+            EventType::RegionLost { winner, .. } => Some((Self::Conquest, vec![*winner])),
+            EventType::AllianceFormed { members } => Some((Self::Alliance, members.clone())),
+            EventType::ReligionFounded { polity, .. } | EventType::Schism { polity, .. } => {
+                Some((Self::Faith, vec![*polity]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// One innovation in the table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Technology {
+    pub name: String,
+    pub origin: InnovationOrigin,
+    /// Chance it begins each time its origin happens to a polity
+    pub chance: f64,
+    /// How readily each species takes it up from others (1.0 if unlisted)
+    #[serde(default)]
+    pub adoption: HashMap<Species, f32>,
+    /// Recipe IDs it unlocks
+    #[serde(default)]
+    pub recipes: Vec<String>,
+    /// Blueprint IDs it unlocks
+    #[serde(default)]
+    pub blueprints: Vec<String>,
+    #[serde(default)]
+    pub unit_types: Vec<UnitType>,
+}
+
+impl Technology {
+    pub fn adoption(&self, species: Species) -> f32 {
+        self.adoption.get(&species).copied().unwrap_or(1.0)
+    }
+}
+
+/// The technology table
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TechnologyTable {
+    #[serde(default)]
+    pub technologies: Vec<Technology>,
+}
+
+impl TechnologyTable {
+    /// No technologies: everything is known to all
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(content: &str) -> Result<Self, TechnologyError> {
+        let table: Self = toml::from_str(content)?;
+        for (i, technology) in table.technologies.iter().enumerate() {
+            if table.technologies[..i]
+                .iter()
+                .any(|t| t.name == technology.name)
+            {
+                return Err(TechnologyError::Duplicate(technology.name.clone()));
+            }
+        }
+        Ok(table)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TechnologyError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Technology> {
+        self.technologies.iter().find(|t| t.name == name)
+    }
+}
+
+/// Load the technology table from `data/technologies.toml`
+pub(crate) fn load_default_technologies() -> TechnologyTable {
+    TechnologyTable::load("data/technologies.toml").unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load technologies: {}", e);
+        TechnologyTable::new()
+    })
+}
+
+/// Where and when an innovation began
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Innovation {
+    pub technology: String,
+    pub polity: u32,
+    pub year: u32,
+}
+
+/// What a settlement founded in a region can make and field
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettlementKnowledge {
+    /// Technologies its culture has
+    pub technologies: Vec<String>,
+    /// Recipes some technology unlocks that its culture lacks
+    pub withheld_recipes: Vec<String>,
+    pub withheld_blueprints: Vec<String>,
+    pub withheld_unit_types: Vec<UnitType>,
+}
+
+impl SettlementKnowledge {
+    /// What a culture knowing `known` can make and field: anything only
+    /// technologies it lacks unlock is withheld
+    pub fn of(table: &TechnologyTable, known: &[String]) -> Self {
+        let (had, lacking): (Vec<&Technology>, Vec<&Technology>) = table
+            .technologies
+            .iter()
+            .partition(|t| known.contains(&t.name));
+        let mut knowledge = Self {
+            technologies: known.to_vec(),
+            ..Self::default()
+        };
+        for technology in lacking {
+            for recipe in &technology.recipes {
+                if !had.iter().any(|t| t.recipes.contains(recipe)) {
+                    knowledge.withheld_recipes.push(recipe.clone());
+                }
+            }
+            for blueprint in &technology.blueprints {
+                if !had.iter().any(|t| t.blueprints.contains(blueprint)) {
+                    knowledge.withheld_blueprints.push(blueprint.clone());
+                }
+            }
+            for &unit_type in &technology.unit_types {
+                if !had.iter().any(|t| t.unit_types.contains(&unit_type)) {
+                    knowledge.withheld_unit_types.push(unit_type);
+                }
+            }
+        }
+        knowledge
+    }
+
+    pub fn allows_recipe(&self, id: &str) -> bool {
+        !self.withheld_recipes.iter().any(|r| r == id)
+    }
+
+    pub fn allows_blueprint(&self, id: &str) -> bool {
+        !self.withheld_blueprints.iter().any(|b| b == id)
+    }
+
+    pub fn allows_unit_type(&self, unit_type: UnitType) -> bool {
+        !self.withheld_unit_types.contains(&unit_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: &str = r#"
+[[technologies]]
+name = "ironworking"
+origin = "Expansion"
+chance = 0.1
+adoption = { Dwarf = 2.0 }
+recipes = ["smelt_iron", "forge_tools"]
+
+[[technologies]]
+name = "toolmaking"
+origin = "Alliance"
+chance = 0.1
+recipes = ["forge_tools"]
+unit_types = ["Engineers"]
+"#;
+
+    #[test]
+    fn test_table_parses_and_rejects_duplicates() {
+        let table = TechnologyTable::parse(TABLE).unwrap();
+        let iron = table.get("ironworking").unwrap();
+        assert_eq!(iron.origin, InnovationOrigin::Expansion);
+        assert_eq!(iron.adoption(Species::Dwarf), 2.0);
+        assert_eq!(iron.adoption(Species::Elf), 1.0);
+
+        let twice = TABLE.replace("toolmaking", "ironworking");
+        assert!(matches!(
+            TechnologyTable::parse(&twice),
+            Err(TechnologyError::Duplicate(_))
+        ));
+        assert!(!load_default_technologies().technologies.is_empty());
+    }
+
+    #[test]
+    fn test_knowledge_withholds_only_what_is_lacking() {
+        let table = TechnologyTable::parse(TABLE).unwrap();
+        let none = SettlementKnowledge::of(&table, &[]);
+        assert!(!none.allows_recipe("smelt_iron"));
+        assert!(!none.allows_recipe("forge_tools"));
+        assert!(!none.allows_unit_type(UnitType::Engineers));
+        // Nothing unlocks these: everyone has them
+        assert!(none.allows_recipe("farm_food"));
+        assert!(none.allows_blueprint("wooden_house"));
+        assert!(none.allows_unit_type(UnitType::Infantry));
+
+        // Tools come with either technology
+        let tools = SettlementKnowledge::of(&table, &["toolmaking".to_string()]);
+        assert!(tools.allows_recipe("forge_tools"));
+        assert!(!tools.allows_recipe("smelt_iron"));
+        assert!(tools.allows_unit_type(UnitType::Engineers));
+    }
+}
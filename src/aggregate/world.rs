@@ -6,7 +6,9 @@ use std::collections::HashMap;
 
 use crate::aggregate::polity::Polity;
 use crate::aggregate::region::Region;
+use crate::aggregate::religion::{Religion, ReligionId};
 use crate::aggregate::ruler::Ruler;
+use crate::aggregate::technology::{load_default_technologies, Innovation, TechnologyTable};
 use crate::core::types::{PolityId, RulerId};
 
 /// The aggregate world state for history simulation
@@ -19,6 +21,12 @@ pub struct AggregateWorld {
     pub rulers: HashMap<RulerId, Ruler>,
     /// Currently active wars
     pub active_wars: Vec<War>,
+    /// Every religion founded, including those with no followers left
+    pub religions: Vec<Religion>,
+    /// The innovations cultures can come by (`data/technologies.toml`)
+    pub technologies: TechnologyTable,
+    /// Where and when each innovation so far began
+    pub innovations: Vec<Innovation>,
     /// Current simulation year
     pub year: u32,
     /// Random number generator (deterministic)
@@ -67,6 +75,9 @@ impl AggregateWorld {
             polities,
             rulers: HashMap::new(),
             active_wars: Vec::new(),
+            religions: Vec::new(),
+            technologies: load_default_technologies(),
+            innovations: Vec::new(),
             year: 0,
             rng,
             next_polity_id,
@@ -88,6 +99,15 @@ impl AggregateWorld {
         id
     }
 
+    /// Generate a new unique ReligionId
+    pub fn next_religion_id(&self) -> ReligionId {
+        ReligionId(self.religions.iter().map(|r| r.id.0).max().unwrap_or(0) + 1)
+    }
+
+    pub fn get_religion(&self, id: ReligionId) -> Option<&Religion> {
+        self.religions.iter().find(|r| r.id == id)
+    }
+
     /// Get a ruler by ID
     pub fn get_ruler(&self, id: RulerId) -> Option<&Ruler> {
         self.rulers.get(&id)
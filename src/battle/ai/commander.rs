@@ -1,6 +1,17 @@
 //! AI Commander - main battle AI implementation
 //!
 //! Evaluates battle state and issues orders through courier system.
+//!
+//! Commanders read the ground: one holding back takes the nearest high
+//! ground rather than stopping on open slope, and one choosing a target
+//! prefers not to attack up a hill (weighed by `defense_value`).
+//!
+//! An aggressive commander whose target is behind the enemy's walls storms
+//! them: foot soldiers ram a gate nearby, or climb the wall if there is
+//! none (see `siege::siege_order`). Horsemen wait for the way to open.
+//!
+//! Supply wagons are never sent into the fight; their drivers follow the
+//! line on their own (see `supply`).
 
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
@@ -9,8 +20,12 @@ use crate::battle::ai::decision_context::DecisionContext;
 use crate::battle::ai::personality::AiPersonality;
 use crate::battle::ai::phase_plans::PhasePlanManager;
 use crate::battle::ai::BattleAI;
+use crate::battle::constants::HIGH_GROUND_SEARCH_RADIUS;
 use crate::battle::courier::Order;
+use crate::battle::elevation::high_ground_near;
 use crate::battle::execution::BattleEventLog;
+use crate::battle::siege::siege_order;
+use crate::battle::unit_type::UnitType;
 use crate::battle::units::{BattleUnit, UnitId, UnitStance};
 use crate::core::types::Tick;
 
@@ -127,6 +142,11 @@ impl AiCommander {
         context: &DecisionContext,
         aggression: f32,
     ) -> Option<Order> {
+        // Wagons are left to their drivers
+        if unit.unit_type == UnitType::SupplyWagons {
+            return None;
+        }
+
         let visible_enemies = context.visible_enemy_units();
 
         if visible_enemies.is_empty() {
@@ -137,17 +157,29 @@ impl AiCommander {
             return Some(Order::move_to(unit.id, enemy_hq));
         }
 
+        // A stressed commander only weighs the enemies nearest the unit
+        let mut considered = visible_enemies;
+        considered.sort_by_key(|enemy| unit.position.distance(&enemy.position));
+        considered.truncate(context.command.options_considered(considered.len()));
+
         // Find best target based on personality weights
-        let target = self.select_target(unit, &visible_enemies, context)?;
+        let target = self.select_target(unit, &considered, context)?;
 
         // Decide action based on aggression
         if aggression > 0.5 {
-            // Aggressive: attack
-            Some(Order::attack(unit.id, target.id))
+            // Aggressive: attack, storming any walls in the way
+            let assault = context
+                .map
+                .and_then(|map| siege_order(map, unit, target.position, context.hq_position()));
+            assault.or_else(|| Some(Order::attack(unit.id, target.id)))
         } else {
-            // Defensive: move towards but hold
+            // Defensive: move towards but hold, on high ground if there is any
             let halfway = unit.position.lerp(&target.position, 0.5);
-            Some(Order::move_to(unit.id, halfway))
+            let hold = context
+                .map
+                .and_then(|map| high_ground_near(map, halfway, HIGH_GROUND_SEARCH_RADIUS))
+                .unwrap_or(halfway);
+            Some(Order::move_to(unit.id, hold))
         }
     }
 
@@ -156,7 +188,7 @@ impl AiCommander {
         &self,
         unit: &BattleUnit,
         enemies: &[&'a BattleUnit],
-        _context: &DecisionContext,
+        context: &DecisionContext,
     ) -> Option<&'a BattleUnit> {
         if enemies.is_empty() {
             return None;
@@ -190,6 +222,12 @@ impl AiCommander {
                 score += 1.0;
             }
 
+            // Attacking uphill is costly
+            let rise = context.elevation_at(enemy.position) - context.elevation_at(unit.position);
+            if rise > 0 {
+                score -= rise as f32 * weights.defense_value * 0.5;
+            }
+
             if score > best_score {
                 best_score = score;
                 best_target = Some(*enemy);
@@ -200,8 +238,11 @@ impl AiCommander {
     }
 
     /// Check if we should retreat based on strength ratio and casualties
+    ///
+    /// Reinforcements due soon count toward our strength: better to hold
+    /// until they arrive than give up the field.
     fn should_retreat(&self, context: &DecisionContext) -> bool {
-        let ratio = context.strength_ratio();
+        let ratio = context.expected_strength_ratio();
         let casualties = context.own_casualty_percentage();
 
         ratio < self.personality.weights.retreat_threshold
@@ -265,6 +306,7 @@ impl BattleAI for AiCommander {
 mod tests {
     use super::*;
     use crate::battle::ai::personality::AiPersonality;
+    use crate::battle::command_stress::CommandStress;
     use crate::battle::courier::OrderType;
     use crate::battle::hex::BattleHexCoord;
     use crate::battle::unit_type::UnitType;
@@ -350,6 +392,46 @@ mod tests {
         assert!(matches!(orders[0].order_type, OrderType::Attack(_)));
     }
 
+    #[test]
+    fn test_stressed_commander_only_sees_the_nearest_enemy() {
+        let mut personality = AiPersonality::default();
+        personality.behavior.aggression = 0.8;
+        personality.preferences.re_evaluation_interval = 1;
+        personality.difficulty.mistake_chance = 0.0;
+
+        let mut own_army = Army::new(ArmyId::new(), EntityId::new());
+        let mut own_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        own_formation.units.push(create_test_unit_at(
+            BattleHexCoord::new(5, 5),
+            UnitStance::Formed,
+        ));
+        own_army.formations.push(own_formation);
+
+        // A fresh unit close by, and a nearly spent one further off
+        let near = create_test_unit_at(BattleHexCoord::new(7, 5), UnitStance::Formed);
+        let mut far = create_test_unit_at(BattleHexCoord::new(15, 5), UnitStance::Formed);
+        far.casualties = 45;
+        let (near_id, far_id) = (near.id, far.id);
+        let mut enemy_army = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        enemy_formation.units.extend([near, far]);
+        enemy_army.formations.push(enemy_formation);
+
+        let visibility = ArmyVisibility::new();
+        let mut events = BattleEventLog::new();
+
+        let context = DecisionContext::new(&own_army, &enemy_army, &visibility, 0, true);
+        let orders = AiCommander::new(personality.clone()).process_tick(&context, 0, &mut events);
+        assert!(matches!(orders[0].order_type, OrderType::Attack(id) if id == far_id));
+
+        let mut overwhelmed = CommandStress::default();
+        overwhelmed.stress = 1.0;
+        let context = DecisionContext::new(&own_army, &enemy_army, &visibility, 0, true)
+            .with_command(overwhelmed);
+        let orders = AiCommander::new(personality).process_tick(&context, 0, &mut events);
+        assert!(matches!(orders[0].order_type, OrderType::Attack(id) if id == near_id));
+    }
+
     #[test]
     fn test_commander_retreats_when_outnumbered() {
         let mut personality = AiPersonality::default();
@@ -520,4 +602,85 @@ mod tests {
         // Defensive commander should move to halfway point
         assert!(matches!(orders[0].order_type, OrderType::MoveTo(_)));
     }
+
+    #[test]
+    fn test_defensive_commander_takes_the_high_ground() {
+        use crate::battle::battle_map::BattleMap;
+        use crate::battle::elevation::raise_hill;
+
+        let mut personality = AiPersonality::default();
+        personality.behavior.aggression = 0.3;
+        personality.difficulty.mistake_chance = 0.0;
+        let mut commander = AiCommander::new(personality);
+
+        let mut own_army = Army::new(ArmyId::new(), EntityId::new());
+        let mut own_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        own_formation.units.push(create_test_unit_at(
+            BattleHexCoord::new(0, 5),
+            UnitStance::Formed,
+        ));
+        own_army.formations.push(own_formation);
+        let mut enemy_army = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        enemy_formation.units.push(create_test_unit_at(
+            BattleHexCoord::new(10, 5),
+            UnitStance::Formed,
+        ));
+        enemy_army.formations.push(enemy_formation);
+
+        // A knoll just off the halfway point
+        let mut map = BattleMap::new(20, 20);
+        raise_hill(&mut map, BattleHexCoord::new(6, 4), 1, 2);
+
+        let visibility = ArmyVisibility::new();
+        let context =
+            DecisionContext::new(&own_army, &enemy_army, &visibility, 0, true).with_map(&map);
+        let orders = commander.process_tick(&context, 0, &mut BattleEventLog::new());
+
+        assert!(matches!(
+            orders[0].order_type,
+            OrderType::MoveTo(hold) if hold == BattleHexCoord::new(6, 4)
+        ));
+    }
+
+    #[test]
+    fn test_aggressive_commander_storms_the_walls() {
+        use crate::battle::battle_map::BattleMap;
+        use crate::battle::siege::raise_curtain_wall;
+
+        let mut personality = AiPersonality::default();
+        personality.behavior.aggression = 0.8;
+        personality.difficulty.mistake_chance = 0.0;
+        let mut commander = AiCommander::new(personality);
+
+        let mut own_army = Army::new(ArmyId::new(), EntityId::new());
+        own_army.hq_position = BattleHexCoord::new(0, 5);
+        let mut own_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        own_formation.units.push(create_test_unit_at(
+            BattleHexCoord::new(6, 5),
+            UnitStance::Formed,
+        ));
+        own_army.formations.push(own_formation);
+        let mut enemy_army = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        enemy_formation.units.push(create_test_unit_at(
+            BattleHexCoord::new(12, 5),
+            UnitStance::Formed,
+        ));
+        enemy_army.formations.push(enemy_formation);
+
+        // The enemy sits behind a wall with a gate close by
+        let mut map = BattleMap::new(20, 10);
+        raise_curtain_wall(&mut map, 9, 5);
+
+        let visibility = ArmyVisibility::new();
+        let context =
+            DecisionContext::new(&own_army, &enemy_army, &visibility, 0, true).with_map(&map);
+        let orders = commander.process_tick(&context, 0, &mut BattleEventLog::new());
+
+        assert!(matches!(
+            orders[0].order_type,
+            OrderType::RamAssault(gate) if gate == BattleHexCoord::new(9, 5)
+        ));
+    }
 }
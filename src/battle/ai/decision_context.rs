@@ -1,8 +1,16 @@
 //! AI's filtered view of the battle state
 //!
 //! Respects fog of war unless ignores_fog_of_war is true.
+//!
+//! A commander knows the timetable of their own reinforcements, and counts
+//! the ones due soon when weighing whether to stand. The enemy's stay
+//! unknown until they march into view.
 
+use crate::battle::battle_map::BattleMap;
+use crate::battle::command_stress::CommandStress;
+use crate::battle::constants::REINFORCEMENT_HORIZON_TICKS;
 use crate::battle::hex::BattleHexCoord;
+use crate::battle::reinforcements::Reinforcement;
 use crate::battle::units::{Army, BattleUnit, UnitId};
 use crate::battle::visibility::ArmyVisibility;
 use crate::core::types::Tick;
@@ -15,6 +23,12 @@ pub struct DecisionContext<'a> {
     pub enemy_army: &'a Army,
     pub own_visibility: &'a ArmyVisibility,
     pub current_tick: Tick,
+    /// The deciding commander's state of mind
+    pub command: CommandStress,
+    /// The ground, if the commander can read it (hills, ridges)
+    pub map: Option<&'a BattleMap>,
+    /// Own formations still on the march
+    pub reinforcements: &'a [Reinforcement],
     ignores_fog: bool,
 }
 
@@ -31,10 +45,50 @@ impl<'a> DecisionContext<'a> {
             enemy_army,
             own_visibility,
             current_tick,
+            command: CommandStress::default(),
+            map: None,
+            reinforcements: &[],
             ignores_fog,
         }
     }
 
+    /// Decide under the given commander's stress
+    pub fn with_command(mut self, command: CommandStress) -> Self {
+        self.command = command;
+        self
+    }
+
+    /// Decide with the lie of the land in view
+    pub fn with_map(mut self, map: &'a BattleMap) -> Self {
+        self.map = Some(map);
+        self
+    }
+
+    /// Decide knowing which of our own formations are on the way
+    pub fn with_reinforcements(mut self, reinforcements: &'a [Reinforcement]) -> Self {
+        self.reinforcements = reinforcements;
+        self
+    }
+
+    /// Strength of own reinforcements due within `REINFORCEMENT_HORIZON_TICKS`
+    ///
+    /// Ones waiting on a go-code aren't counted: nobody has called them yet.
+    pub fn incoming_strength(&self) -> usize {
+        self.reinforcements
+            .iter()
+            .filter(|r| {
+                r.ticks_until(self.current_tick)
+                    .is_some_and(|t| t <= REINFORCEMENT_HORIZON_TICKS)
+            })
+            .map(|r| r.strength())
+            .sum()
+    }
+
+    /// Elevation at a position (flat ground if the map is unknown)
+    pub fn elevation_at(&self, pos: BattleHexCoord) -> i8 {
+        self.map.map(|map| map.elevation_at(pos)).unwrap_or(0)
+    }
+
     /// Get all own units
     pub fn own_units(&self) -> Vec<&BattleUnit> {
         self.own_army
@@ -87,6 +141,16 @@ impl<'a> DecisionContext<'a> {
         self.own_effective_strength() as f32 / enemy_strength as f32
     }
 
+    /// Strength ratio counting reinforcements due soon
+    /// Returns f32::MAX if no visible enemies
+    pub fn expected_strength_ratio(&self) -> f32 {
+        let enemy_strength = self.visible_enemy_strength();
+        if enemy_strength == 0 {
+            return f32::MAX;
+        }
+        (self.own_effective_strength() + self.incoming_strength()) as f32 / enemy_strength as f32
+    }
+
     /// Find weakest visible enemy unit
     pub fn weakest_enemy(&self) -> Option<&BattleUnit> {
         self.visible_enemy_units()
@@ -385,6 +449,51 @@ mod tests {
         assert_eq!(context.own_casualty_percentage(), 0.0);
     }
 
+    #[test]
+    fn test_reinforcements_due_soon_count_toward_strength() {
+        use crate::battle::planning::GoCodeId;
+        use crate::battle::reinforcements::{ArrivalTrigger, MapEdge};
+
+        let mut own_army = Army::new(ArmyId::new(), EntityId::new());
+        let mut own_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        own_formation
+            .units
+            .push(create_test_unit(BattleHexCoord::new(5, 5)));
+        own_army.formations.push(own_formation);
+
+        let mut enemy_army = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        for q in 6..8 {
+            enemy_formation
+                .units
+                .push(create_test_unit(BattleHexCoord::new(q, 5)));
+        }
+        enemy_army.formations.push(enemy_formation);
+
+        let column = |trigger| {
+            let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+            formation
+                .units
+                .push(create_test_unit(BattleHexCoord::default()));
+            Reinforcement::new(formation, MapEdge::West, trigger)
+        };
+        let reinforcements = vec![
+            column(ArrivalTrigger::AtTick(100)),
+            column(ArrivalTrigger::AtTick(
+                100 + REINFORCEMENT_HORIZON_TICKS + 50,
+            )),
+            column(ArrivalTrigger::GoCode(GoCodeId::new())),
+        ];
+
+        let visibility = ArmyVisibility::new();
+        let context = DecisionContext::new(&own_army, &enemy_army, &visibility, 50, true)
+            .with_reinforcements(&reinforcements);
+
+        assert_eq!(context.incoming_strength(), 50);
+        assert!((context.strength_ratio() - 0.5).abs() < 0.01);
+        assert!((context.expected_strength_ratio() - 1.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_hq_position() {
         let mut own_army = Army::new(ArmyId::new(), EntityId::new());
@@ -1,12 +1,18 @@
 //! Battle map with hex grid, terrain, and line of sight
 //!
 //! Maps are DENSE with terrain - navigation is a puzzle, not optional.
+//! Hills and ridgelines (see `elevation`) hide the ground beyond their crest.
+//! Walls and closed gates bar the way, and walls hide what lies behind them,
+//! until they are breached (see `siege`).
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::battle::constants::LADDER_CLIMB_COST;
 use crate::battle::hex::BattleHexCoord;
-use crate::battle::terrain::{BattleTerrain, TerrainFeature};
+use crate::battle::terrain::{
+    BattleTerrain, BreachState, Fortification, FortificationKind, TerrainFeature,
+};
 use crate::core::types::EntityId;
 
 /// Visibility state for fog of war
@@ -27,6 +33,9 @@ pub struct BattleHex {
     pub features: Vec<TerrainFeature>,
     pub occupants: Vec<EntityId>,
     pub visibility: VisibilityState,
+    /// Wall or gate standing here, if any
+    #[serde(default)]
+    pub fortification: Option<Fortification>,
 }
 
 impl BattleHex {
@@ -38,11 +47,46 @@ impl BattleHex {
             features: Vec::new(),
             occupants: Vec::new(),
             visibility: VisibilityState::Unknown,
+            fortification: None,
         }
     }
 
-    /// Total movement cost including features
+    /// Total movement cost including features (infinite where a wall or
+    /// closed gate bars the way)
     pub fn total_movement_cost(&self) -> f32 {
+        if self.bars_the_way() {
+            return f32::INFINITY;
+        }
+        self.open_ground_cost()
+    }
+
+    /// Cost of climbing over the wall here on assault ladders
+    pub fn climbing_cost(&self) -> f32 {
+        self.open_ground_cost() + LADDER_CLIMB_COST
+    }
+
+    /// Does a wall or closed gate stop anyone walking in?
+    pub fn bars_the_way(&self) -> bool {
+        self.fortification.is_some_and(|f| f.bars_the_way())
+    }
+
+    /// Can assault ladders be put up against a wall here?
+    pub fn can_be_scaled(&self) -> bool {
+        self.fortification.is_some_and(|f| f.can_be_scaled())
+    }
+
+    /// Can horsemen keep up a gallop across this hex? (Open ground or road,
+    /// with no stream, treeline, or wall to check them; a slope is fine)
+    pub fn is_open_ground(&self) -> bool {
+        matches!(self.terrain, BattleTerrain::Open | BattleTerrain::Road)
+            && self.fortification.is_none()
+            && self
+                .features
+                .iter()
+                .all(|f| matches!(f, TerrainFeature::Hill | TerrainFeature::Ridge))
+    }
+
+    fn open_ground_cost(&self) -> f32 {
         let base = self.terrain.movement_cost();
         let feature_cost: f32 = self
             .features
@@ -59,9 +103,18 @@ impl BattleHex {
         (base + feature_cover).min(1.0) // Cap at 1.0
     }
 
-    /// Does this hex block line of sight?
+    /// Does this hex block line of sight? (A breached wall is rubble and
+    /// hides nothing)
     pub fn blocks_los(&self) -> bool {
-        self.terrain.blocks_los() || self.features.iter().any(|f| f.blocks_los())
+        let breached = self.fortification.is_some_and(|f| f.is_breached());
+        self.terrain.blocks_los() || (!breached && self.features.iter().any(|f| f.blocks_los()))
+    }
+
+    /// Does this hex block line of sight for a viewer standing at
+    /// `viewer_elevation`? A ridge hides what lies beyond from anyone below it.
+    pub fn blocks_los_from(&self, viewer_elevation: i8) -> bool {
+        self.blocks_los()
+            || (viewer_elevation < self.elevation && self.features.contains(&TerrainFeature::Ridge))
     }
 }
 
@@ -76,6 +129,7 @@ pub struct Objective {
 /// The full battle map
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleMap {
+    #[serde(with = "crate::battle::save::sorted_hex_map")]
     pub hexes: HashMap<BattleHexCoord, BattleHex>,
     pub width: u32,
     pub height: u32,
@@ -122,13 +176,25 @@ impl BattleMap {
     }
 
     /// Check line of sight between two hexes
+    ///
+    /// Ground rising above the sight line between the two (a crest between
+    /// them) blocks it as surely as a forest does.
     pub fn has_line_of_sight(&self, from: BattleHexCoord, to: BattleHexCoord) -> bool {
         let line = from.line_to(&to);
+        let eye = self.elevation_at(from);
+        let target = self.elevation_at(to);
+        let steps = line.len().saturating_sub(1).max(1) as f32;
 
         // Check all hexes except start and end
-        for coord in line.iter().skip(1).take(line.len().saturating_sub(2)) {
+        for (n, coord) in line
+            .iter()
+            .enumerate()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+        {
             if let Some(hex) = self.get_hex(*coord) {
-                if hex.blocks_los() {
+                let sight = eye as f32 + (target - eye) as f32 * n as f32 / steps;
+                if hex.blocks_los_from(eye) || hex.elevation as f32 > sight {
                     return false;
                 }
             }
@@ -160,11 +226,66 @@ impl BattleMap {
         }
     }
 
+    /// Raise a stretch of wall at a coordinate
+    pub fn build_wall(&mut self, coord: BattleHexCoord) {
+        self.add_feature(coord, TerrainFeature::Wall);
+        if let Some(hex) = self.get_hex_mut(coord) {
+            hex.fortification = Some(Fortification::wall());
+        }
+    }
+
+    /// Hang a closed gate at a coordinate
+    pub fn build_gate(&mut self, coord: BattleHexCoord) {
+        self.add_feature(coord, TerrainFeature::Gate);
+        if let Some(hex) = self.get_hex_mut(coord) {
+            hex.fortification = Some(Fortification::gate());
+        }
+    }
+
+    /// Open or close the gate at a coordinate; false if there is no gate
+    /// standing there
+    pub fn set_gate_open(&mut self, coord: BattleHexCoord, open: bool) -> bool {
+        match self.fortification_mut(coord) {
+            Some(gate) if gate.kind == FortificationKind::Gate && !gate.is_breached() => {
+                gate.open = open;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The wall or gate at a coordinate, if any
+    pub fn fortification(&self, coord: BattleHexCoord) -> Option<&Fortification> {
+        self.get_hex(coord)?.fortification.as_ref()
+    }
+
+    fn fortification_mut(&mut self, coord: BattleHexCoord) -> Option<&mut Fortification> {
+        self.get_hex_mut(coord)?.fortification.as_mut()
+    }
+
+    /// Does a wall or closed gate at a coordinate stop anyone walking in?
+    pub fn is_barred(&self, coord: BattleHexCoord) -> bool {
+        self.get_hex(coord).is_some_and(|h| h.bars_the_way())
+    }
+
+    /// Batter the wall or gate at a coordinate, returning its state
+    /// afterwards (None if nothing stands there)
+    pub fn damage_fortification(
+        &mut self,
+        coord: BattleHexCoord,
+        damage: f32,
+    ) -> Option<BreachState> {
+        self.fortification_mut(coord).map(|f| f.take_damage(damage))
+    }
+
+    /// Elevation at a coordinate (0 off the map)
+    pub fn elevation_at(&self, coord: BattleHexCoord) -> i8 {
+        self.get_hex(coord).map(|h| h.elevation).unwrap_or(0)
+    }
+
     /// Get elevation difference (positive = from is higher)
     pub fn elevation_difference(&self, from: BattleHexCoord, to: BattleHexCoord) -> i8 {
-        let from_elev = self.get_hex(from).map(|h| h.elevation).unwrap_or(0);
-        let to_elev = self.get_hex(to).map(|h| h.elevation).unwrap_or(0);
-        from_elev - to_elev
+        self.elevation_at(from) - self.elevation_at(to)
     }
 
     /// Get all hexes visible from a position with given range
@@ -179,6 +300,7 @@ impl BattleMap {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::battle::constants::WALL_HP;
 
     #[test]
     fn test_battle_hex_creation() {
@@ -226,6 +348,22 @@ mod tests {
         assert!(!map.has_line_of_sight(from, to));
     }
 
+    #[test]
+    fn test_crest_blocks_line_of_sight_from_below() {
+        let mut map = BattleMap::new(10, 10);
+        let below = BattleHexCoord::new(0, 0);
+        let crest = BattleHexCoord::new(3, 0);
+        let beyond = BattleHexCoord::new(6, 0);
+        map.set_elevation(crest, 2);
+        map.add_feature(crest, TerrainFeature::Ridge);
+        assert!(!map.has_line_of_sight(below, beyond));
+        assert!(map.has_line_of_sight(below, crest));
+
+        // From higher ground the crest hides nothing
+        map.set_elevation(below, 5);
+        assert!(map.has_line_of_sight(below, beyond));
+    }
+
     #[test]
     fn test_elevation_difference() {
         let mut map = BattleMap::new(10, 10);
@@ -246,4 +384,44 @@ mod tests {
 
         assert!(with_hill > base_cost);
     }
+
+    #[test]
+    fn test_wall_bars_the_way_and_sight_until_breached() {
+        let mut map = BattleMap::new(10, 10);
+        let wall = BattleHexCoord::new(2, 0);
+        map.build_wall(wall);
+        let from = BattleHexCoord::new(0, 0);
+        let to = BattleHexCoord::new(5, 0);
+
+        assert!(map.is_barred(wall));
+        assert!(map
+            .get_hex(wall)
+            .unwrap()
+            .total_movement_cost()
+            .is_infinite());
+        assert!(!map.has_line_of_sight(from, to));
+        assert!(!map.set_gate_open(wall, true));
+
+        assert_eq!(
+            map.damage_fortification(wall, WALL_HP),
+            Some(BreachState::Breached)
+        );
+        assert!(!map.is_barred(wall));
+        assert!(map.get_hex(wall).unwrap().total_movement_cost().is_finite());
+        assert!(map.has_line_of_sight(from, to));
+    }
+
+    #[test]
+    fn test_open_gate_lets_anyone_through() {
+        let mut map = BattleMap::new(10, 10);
+        let gate = BattleHexCoord::new(2, 0);
+        map.build_gate(gate);
+        assert!(map.is_barred(gate));
+        assert!(map.set_gate_open(gate, true));
+        assert!(!map.is_barred(gate));
+        assert_eq!(
+            map.damage_fortification(BattleHexCoord::new(3, 0), 10.0),
+            None
+        );
+    }
 }
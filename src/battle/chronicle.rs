@@ -0,0 +1,518 @@
+//! Battle chronicle - a finished battle told as a story
+//!
+//! The tick loop logs everything as it happens, one `BattleEventLog` per
+//! tick. `BattleChronicle::compose` reads a finished battle's events back
+//! and keeps what a teller would:
+//!
+//! - A name: after the site it was fought on, else the objective taken.
+//! - Phases: the approach until the first unit engaged, the clash, and the
+//!   rout once the losing side's first unit broke.
+//! - Turning points within them: units breaking, rallying, or cut down,
+//!   charges striking home or thrown back, commanders and officers
+//!   falling, reinforcements arriving, walls breached, scaled, or mined,
+//!   objectives taken.
+//! - A casualty table of every unit on both sides.
+//!
+//! `World::legacy` keeps every chronicle composed (`Legacy::record_battle`),
+//! where the LLM context and the chronicle read them back.
+
+use serde::{Deserialize, Serialize};
+
+use crate::battle::execution::{BattleEvent, BattleEventType, BattleOutcome, BattleState};
+use crate::battle::unit_type::UnitType;
+use crate::battle::units::{Army, FormationId, UnitId};
+use crate::core::types::{EntityId, Tick};
+
+/// Which army something belonged to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    Friendly,
+    Enemy,
+}
+
+impl Side {
+    fn possessive(&self) -> &'static str {
+        match self {
+            Side::Friendly => "our",
+            Side::Enemy => "their",
+        }
+    }
+}
+
+/// The stages a battle is told in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PhaseKind {
+    /// Before any unit engaged
+    Approach,
+    /// Fighting until the beaten side began to break
+    Clash,
+    /// From the beaten side's first unit breaking to the end
+    Rout,
+}
+
+impl PhaseKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            PhaseKind::Approach => "The Approach",
+            PhaseKind::Clash => "The Clash",
+            PhaseKind::Rout => "The Rout",
+        }
+    }
+}
+
+/// A moment the battle turned on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurningPoint {
+    pub tick: Tick,
+    /// Whose moment it was, if it was either side's
+    pub side: Option<Side>,
+    pub text: String,
+}
+
+/// One stage of the battle, and what turned in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChroniclePhase {
+    pub kind: PhaseKind,
+    pub from: Tick,
+    pub to: Tick,
+    pub turning_points: Vec<TurningPoint>,
+}
+
+/// How a unit ended the battle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitFate {
+    /// Still in the field
+    Held,
+    /// Routing at the end
+    Broke,
+    /// None left standing
+    Destroyed,
+}
+
+/// One row of the casualty table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CasualtyLine {
+    pub side: Side,
+    pub unit_type: UnitType,
+    pub strength: usize,
+    pub casualties: u32,
+    pub fate: UnitFate,
+}
+
+/// A finished battle, told
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleChronicle {
+    pub name: String,
+    pub outcome: BattleOutcome,
+    /// Battle ticks it lasted
+    pub length: Tick,
+    pub phases: Vec<ChroniclePhase>,
+    pub casualties: Vec<CasualtyLine>,
+}
+
+fn side_of_unit(battle: &BattleState, unit: UnitId) -> Option<(Side, UnitType)> {
+    let find = |army: &Army| army.get_unit(unit).map(|u| u.unit_type);
+    find(&battle.friendly_army)
+        .map(|t| (Side::Friendly, t))
+        .or_else(|| find(&battle.enemy_army).map(|t| (Side::Enemy, t)))
+}
+
+fn side_of_formation(battle: &BattleState, formation: FormationId) -> Option<Side> {
+    let has = |army: &Army| army.formations.iter().any(|f| f.id == formation);
+    if has(&battle.friendly_army) {
+        Some(Side::Friendly)
+    } else if has(&battle.enemy_army) {
+        Some(Side::Enemy)
+    } else {
+        None
+    }
+}
+
+/// Who someone who fell was, and on which side
+fn fallen(battle: &BattleState, entity: EntityId) -> Option<(Side, String)> {
+    for (side, army) in [
+        (Side::Friendly, &battle.friendly_army),
+        (Side::Enemy, &battle.enemy_army),
+    ] {
+        if army.commander == entity {
+            return Some((side, format!("{} commander", side.possessive())));
+        }
+        let officer = army
+            .formations
+            .iter()
+            .flat_map(|f| f.officers.iter())
+            .find(|o| o.entity == entity);
+        if let Some(officer) = officer {
+            return Some((side, officer.name.clone()));
+        }
+    }
+    None
+}
+
+/// Capitalise the first letter of a telling
+fn sentence(text: String) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => text,
+    }
+}
+
+/// The turning point an event makes, if it is one
+fn turning_point(battle: &BattleState, event: &BattleEvent) -> Option<TurningPoint> {
+    let unit = |id: UnitId, what: &str| {
+        let (side, unit_type) = side_of_unit(battle, id)?;
+        let text = what
+            .replace("{side}", side.possessive())
+            .replace("{unit}", unit_type.describe());
+        Some((Some(side), text))
+    };
+    let (side, text) = match &event.event_type {
+        BattleEventType::UnitBroke { unit_id } => unit(*unit_id, "{side} {unit} broke")?,
+        BattleEventType::UnitRallied { unit_id } => unit(*unit_id, "{side} {unit} rallied")?,
+        BattleEventType::UnitDestroyed { unit_id } => {
+            unit(*unit_id, "{side} {unit} were cut down")?
+        }
+        BattleEventType::ChargeStruckHome { unit_id } => {
+            unit(*unit_id, "a charge of {side} {unit} struck home")?
+        }
+        BattleEventType::ChargeRepulsed { unit_id } => {
+            unit(*unit_id, "a charge of {side} {unit} was thrown back")?
+        }
+        BattleEventType::ChargeCountered { unit_id } => {
+            unit(*unit_id, "{side} {unit} rode out to meet a charge")?
+        }
+        BattleEventType::WallScaled { unit_id } => {
+            unit(*unit_id, "{side} {unit} gained the top of the wall")?
+        }
+        BattleEventType::CommanderKilled { entity_id } => {
+            let (side, who) = fallen(battle, *entity_id)?;
+            (Some(side), format!("{} fell", who))
+        }
+        BattleEventType::ReinforcementsArrived { formation } => {
+            let side = side_of_formation(battle, *formation)?;
+            (
+                Some(side),
+                format!("{} reinforcements arrived", side.possessive()),
+            )
+        }
+        BattleEventType::FortificationBreached { .. } => (None, "the walls were breached".into()),
+        BattleEventType::MineSprung { .. } => (None, "a mine brought the wall down".into()),
+        BattleEventType::ObjectiveCaptured { name } => (None, format!("{} was taken", name)),
+        _ => return None,
+    };
+    Some(TurningPoint {
+        tick: event.tick,
+        side,
+        text: sentence(text),
+    })
+}
+
+/// Which side lost, if either did
+fn beaten(outcome: BattleOutcome) -> Option<Side> {
+    match outcome {
+        BattleOutcome::DecisiveVictory | BattleOutcome::Victory | BattleOutcome::PyrrhicVictory => {
+            Some(Side::Enemy)
+        }
+        BattleOutcome::Defeat | BattleOutcome::DecisiveDefeat => Some(Side::Friendly),
+        _ => None,
+    }
+}
+
+/// What a battle is remembered as
+fn battle_name(battle: &BattleState, events: &[BattleEvent], site: Option<&str>) -> String {
+    if let Some(site) = site {
+        return format!("The Battle of {}", site);
+    }
+    let taken = events.iter().rev().find_map(|e| match &e.event_type {
+        BattleEventType::ObjectiveCaptured { name } => Some(name.clone()),
+        _ => None,
+    });
+    if let Some(objective) = taken.or_else(|| battle.map.objectives.first().map(|o| o.name.clone()))
+    {
+        return format!("The Battle for {}", objective);
+    }
+    let stormed = events.iter().any(|e| {
+        matches!(
+            e.event_type,
+            BattleEventType::FortificationBreached { .. } | BattleEventType::WallScaled { .. }
+        )
+    });
+    if stormed {
+        "The Storming of the Walls".into()
+    } else {
+        "The Battle in the Field".into()
+    }
+}
+
+impl BattleChronicle {
+    /// Tell a finished battle from the events of its ticks (those in the
+    /// battle's own log are included); `site` names the ground, if it has a
+    /// name
+    pub fn compose(battle: &BattleState, events: &[BattleEvent], site: Option<&str>) -> Self {
+        let mut events: Vec<BattleEvent> = battle
+            .battle_log
+            .iter()
+            .chain(events.iter())
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| e.tick);
+
+        let end = battle.tick;
+        let contact = events
+            .iter()
+            .find(|e| {
+                matches!(
+                    e.event_type,
+                    BattleEventType::UnitEngaged { .. } | BattleEventType::ChargeStruckHome { .. }
+                )
+            })
+            .map(|e| e.tick);
+        let loser = beaten(battle.outcome);
+        let rout = events.iter().find_map(|e| match e.event_type {
+            BattleEventType::UnitBroke { unit_id }
+                if loser.is_some()
+                    && side_of_unit(battle, unit_id).map(|(side, _)| side) == loser =>
+            {
+                Some(e.tick)
+            }
+            _ => None,
+        });
+
+        let mut bounds = vec![(PhaseKind::Approach, 0)];
+        if let Some(contact) = contact {
+            bounds.push((PhaseKind::Clash, contact));
+            if let Some(rout) = rout.filter(|&r| r >= contact) {
+                bounds.push((PhaseKind::Rout, rout));
+            }
+        }
+        let mut phases: Vec<ChroniclePhase> = bounds
+            .iter()
+            .enumerate()
+            .map(|(i, &(kind, from))| ChroniclePhase {
+                kind,
+                from,
+                to: bounds.get(i + 1).map_or(end, |&(_, next)| next),
+                turning_points: Vec::new(),
+            })
+            .collect();
+        for point in events.iter().filter_map(|e| turning_point(battle, e)) {
+            if let Some(phase) = phases.iter_mut().rev().find(|p| p.from <= point.tick) {
+                phase.turning_points.push(point);
+            }
+        }
+        // A phase that began and ended on one tick with nothing in it is no phase
+        phases.retain(|p| p.to > p.from || !p.turning_points.is_empty());
+
+        let casualties = [
+            (Side::Friendly, &battle.friendly_army),
+            (Side::Enemy, &battle.enemy_army),
+        ]
+        .into_iter()
+        .flat_map(|(side, army)| {
+            army.formations
+                .iter()
+                .flat_map(|f| f.units.iter())
+                .map(move |unit| CasualtyLine {
+                    side,
+                    unit_type: unit.unit_type,
+                    strength: unit.strength(),
+                    casualties: unit.casualties,
+                    fate: if unit.effective_strength() == 0 {
+                        UnitFate::Destroyed
+                    } else if unit.is_broken() {
+                        UnitFate::Broke
+                    } else {
+                        UnitFate::Held
+                    },
+                })
+        })
+        .collect();
+
+        Self {
+            name: battle_name(battle, &events, site),
+            outcome: battle.outcome,
+            length: end,
+            phases,
+            casualties,
+        }
+    }
+
+    /// Everyone lost on one side
+    pub fn losses(&self, side: Side) -> u32 {
+        self.casualties
+            .iter()
+            .filter(|c| c.side == side)
+            .map(|c| c.casualties)
+            .sum()
+    }
+
+    /// The turning points, in order
+    pub fn turning_points(&self) -> impl Iterator<Item = &TurningPoint> {
+        self.phases.iter().flat_map(|p| p.turning_points.iter())
+    }
+
+    /// The battle in a line
+    pub fn summary(&self) -> String {
+        format!(
+            "{} ({:?}): {} of ours and {} of theirs fell",
+            self.name,
+            self.outcome,
+            self.losses(Side::Friendly),
+            self.losses(Side::Enemy)
+        )
+    }
+
+    /// The battle told in full: its phases and their turning points, then
+    /// the casualty table
+    pub fn narrative(&self) -> String {
+        let mut s = format!("{}\n", self.summary());
+        for phase in &self.phases {
+            s.push_str(&format!(
+                "\n{} (ticks {}-{})\n",
+                phase.kind.title(),
+                phase.from,
+                phase.to
+            ));
+            for point in &phase.turning_points {
+                s.push_str(&format!("- {}: {}\n", point.tick, point.text));
+            }
+        }
+        s.push_str("\nCasualties:\n");
+        for line in &self.casualties {
+            s.push_str(&format!(
+                "- {} {}: {} of {} lost, {:?}\n",
+                sentence(line.side.possessive().to_string()),
+                line.unit_type.describe(),
+                line.casualties,
+                line.strength,
+                line.fate
+            ));
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::battle_map::{BattleMap, Objective};
+    use crate::battle::execution::BattleEventLog;
+    use crate::battle::hex::BattleHexCoord;
+    use crate::battle::officers::Officer;
+    use crate::battle::units::{ArmyId, BattleFormation, BattleUnit, Element, UnitStance};
+    use crate::skills::ChunkLibrary;
+
+    fn army(unit_type: UnitType) -> Army {
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+        unit.elements.push(Element::new(vec![EntityId::new(); 50]));
+        formation.officers.push(Officer::new(
+            EntityId::new(),
+            "Aldric".into(),
+            unit.id,
+            &ChunkLibrary::new(),
+        ));
+        formation.units.push(unit);
+        army.formations.push(formation);
+        army
+    }
+
+    fn first_unit(army: &Army) -> UnitId {
+        army.formations[0].units[0].id
+    }
+
+    /// Our infantry meet their cavalry at tick 10, their officer falls at
+    /// 20, and their cavalry break at 30 and are all but destroyed
+    fn won_battle() -> (BattleState, Vec<BattleEvent>) {
+        let mut battle = BattleState::new(
+            BattleMap::new(20, 20),
+            army(UnitType::Infantry),
+            army(UnitType::Cavalry),
+        );
+        battle.map.objectives.push(Objective {
+            coord: BattleHexCoord::new(5, 5),
+            name: "the Old Mill".into(),
+            required_for_victory: true,
+        });
+        let ours = first_unit(&battle.friendly_army);
+        let theirs = first_unit(&battle.enemy_army);
+        let officer = battle.enemy_army.formations[0].officers[0].entity;
+
+        let mut log = BattleEventLog::new();
+        log.push(
+            BattleEventType::UnitEngaged { unit_id: ours },
+            "".into(),
+            10,
+        );
+        log.push(
+            BattleEventType::CommanderKilled { entity_id: officer },
+            "".into(),
+            20,
+        );
+        log.push(
+            BattleEventType::UnitBroke { unit_id: theirs },
+            "".into(),
+            30,
+        );
+
+        battle.friendly_army.formations[0].units[0].casualties = 5;
+        let cavalry = &mut battle.enemy_army.formations[0].units[0];
+        cavalry.casualties = 40;
+        cavalry.stance = UnitStance::Routing;
+        battle.tick = 40;
+        battle.end_battle(BattleOutcome::Victory);
+        (battle, log.events)
+    }
+
+    #[test]
+    fn test_chronicle_tells_phases_and_turning_points() {
+        let (battle, events) = won_battle();
+        let chronicle = BattleChronicle::compose(&battle, &events, None);
+
+        assert_eq!(chronicle.name, "The Battle for the Old Mill");
+        let kinds: Vec<PhaseKind> = chronicle.phases.iter().map(|p| p.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![PhaseKind::Approach, PhaseKind::Clash, PhaseKind::Rout]
+        );
+        assert_eq!(chronicle.phases[1].from, 10);
+        assert_eq!(chronicle.phases[1].to, 30);
+        assert_eq!(chronicle.phases[2].to, 40);
+
+        let clash: Vec<&str> = chronicle.phases[1]
+            .turning_points
+            .iter()
+            .map(|p| p.text.as_str())
+            .collect();
+        assert_eq!(clash, vec!["Aldric fell"]);
+        let rout = &chronicle.phases[2].turning_points[0];
+        assert_eq!(rout.text, "Their cavalry broke");
+        assert_eq!(rout.side, Some(Side::Enemy));
+
+        assert_eq!(chronicle.losses(Side::Friendly), 5);
+        assert_eq!(chronicle.losses(Side::Enemy), 40);
+        let cavalry = chronicle
+            .casualties
+            .iter()
+            .find(|c| c.side == Side::Enemy)
+            .unwrap();
+        assert_eq!(cavalry.fate, UnitFate::Broke);
+        assert!(chronicle.narrative().contains("The Rout"));
+    }
+
+    #[test]
+    fn test_site_names_the_battle_and_the_legacy_keeps_it() {
+        let (battle, events) = won_battle();
+        let chronicle = BattleChronicle::compose(&battle, &events, Some("Greyford"));
+        assert_eq!(chronicle.name, "The Battle of Greyford");
+
+        let mut legacy = crate::world::legacy::Legacy::new();
+        legacy.record_battle(battle.tick, chronicle);
+        assert!(legacy.battle("The Battle of Greyford").is_some());
+        assert!(legacy.chronicle[0]
+            .text
+            .starts_with("The Battle of Greyford"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Commander stress - pressure degrades command
+//!
+//! Each side's commander accumulates stress as their army takes casualties
+//! and as things go wrong without warning (a unit breaking, a courier taken).
+//! A stressed commander suffers from tunnel vision and hesitation:
+//!
+//! - the AI weighs fewer targets for each unit, only those nearest to it
+//! - every order (the AI's or the player's) takes longer to get out of HQ,
+//!   as couriers wait before setting off
+//!
+//! Leadership mastery blunts each blow and speeds recovery; a quiet spell
+//! with nothing new going wrong lets the commander steady themselves.
+
+use serde::{Deserialize, Serialize};
+
+use crate::battle::constants::{
+    COMMAND_CASUALTY_STRESS, COMMAND_LEADERSHIP_MITIGATION, COMMAND_MAX_ORDER_DELAY,
+    COMMAND_REST_RECOVERY, COMMAND_REST_TICKS, COMMAND_SHAKEN, COMMAND_SURPRISE_STRESS,
+    COMMAND_TUNNEL_VISION,
+};
+use crate::battle::units::Army;
+use crate::core::types::Tick;
+
+/// One commander's state of mind
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct CommandStress {
+    /// 0.0 = calm, 1.0 = overwhelmed
+    pub stress: f32,
+    /// Leadership mastery (0.0-1.0)
+    pub leadership: f32,
+    /// Casualty fraction already taken into account
+    casualties_seen: f32,
+    /// Ticks since anything new went wrong
+    quiet_ticks: Tick,
+}
+
+impl CommandStress {
+    pub fn new(leadership: f32) -> Self {
+        Self {
+            leadership: leadership.clamp(0.0, 1.0),
+            ..Self::default()
+        }
+    }
+
+    fn add(&mut self, amount: f32) {
+        let mitigated = amount * (1.0 - self.leadership * COMMAND_LEADERSHIP_MITIGATION);
+        self.stress = (self.stress + mitigated).min(1.0);
+        self.quiet_ticks = 0;
+    }
+
+    /// Something went wrong without warning
+    pub fn surprise(&mut self) {
+        self.add(COMMAND_SURPRISE_STRESS);
+    }
+
+    /// Take in the army's losses since the last update, or rest if there are none
+    pub fn update(&mut self, army: &Army) {
+        let total = army.total_strength();
+        let casualties = if total == 0 {
+            0.0
+        } else {
+            1.0 - army.effective_strength() as f32 / total as f32
+        };
+
+        let new_losses = casualties - self.casualties_seen;
+        if new_losses > 0.0 {
+            self.casualties_seen = casualties;
+            self.add(new_losses * COMMAND_CASUALTY_STRESS);
+            return;
+        }
+
+        self.quiet_ticks += 1;
+        if self.quiet_ticks >= COMMAND_REST_TICKS {
+            let recovery = COMMAND_REST_RECOVERY * (1.0 + self.leadership);
+            self.stress = (self.stress - recovery).max(0.0);
+        }
+    }
+
+    /// Too stressed to think clearly
+    pub fn is_shaken(&self) -> bool {
+        self.stress >= COMMAND_SHAKEN
+    }
+
+    /// How many of `available` options the commander actually weighs
+    pub fn options_considered(&self, available: usize) -> usize {
+        let considered = (available as f32 * (1.0 - self.stress * COMMAND_TUNNEL_VISION)).ceil();
+        (considered as usize).clamp(available.min(1), available)
+    }
+
+    /// Ticks an order waits at HQ before its courier sets off
+    pub fn order_delay(&self) -> Tick {
+        (self.stress * COMMAND_MAX_ORDER_DELAY as f32).round() as Tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::unit_type::UnitType;
+    use crate::battle::units::{ArmyId, BattleFormation, BattleUnit, Element, FormationId, UnitId};
+    use crate::core::types::EntityId;
+
+    fn army() -> Army {
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        unit.elements.push(Element::new(vec![EntityId::new(); 100]));
+        formation.units.push(unit);
+        army.formations.push(formation);
+        army
+    }
+
+    fn lose(army: &mut Army, casualties: u32) {
+        army.formations[0].units[0].casualties += casualties;
+    }
+
+    #[test]
+    fn test_losses_and_surprises_degrade_command() {
+        let mut army = army();
+        let mut novice = CommandStress::new(0.0);
+        let mut veteran = CommandStress::new(1.0);
+
+        lose(&mut army, 20);
+        novice.update(&army);
+        veteran.update(&army);
+        novice.surprise();
+        veteran.surprise();
+        assert!(novice.stress > veteran.stress);
+        assert!(novice.is_shaken());
+
+        assert!(novice.options_considered(8) < veteran.options_considered(8));
+        assert!(novice.order_delay() > veteran.order_delay());
+        assert_eq!(novice.options_considered(1), 1);
+        assert_eq!(CommandStress::new(0.5).options_considered(8), 8);
+        assert_eq!(CommandStress::new(0.5).order_delay(), 0);
+    }
+
+    #[test]
+    fn test_rest_steadies_the_commander() {
+        let mut army = army();
+        let mut command = CommandStress::new(0.0);
+        lose(&mut army, 20);
+        command.update(&army);
+        let shaken = command.stress;
+
+        // The same losses are not felt twice, and nothing eases before a quiet spell
+        for _ in 1..COMMAND_REST_TICKS {
+            command.update(&army);
+        }
+        assert_eq!(command.stress, shaken);
+
+        for _ in 0..10 {
+            command.update(&army);
+        }
+        assert!(command.stress < shaken);
+
+        // A fresh blow ends the quiet spell
+        command.surprise();
+        let stress = command.stress;
+        command.update(&army);
+        assert_eq!(command.stress, stress);
+    }
+}
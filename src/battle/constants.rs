@@ -36,12 +36,83 @@ pub const SCOUT_VISION_BONUS: u32 = 4;
 pub const ELEVATION_VISION_BONUS: u32 = 2;
 pub const FOREST_VISION_PENALTY: u32 = 4;
 
+// Elevation - per level of height
+pub const UPHILL_MOVEMENT_COST: f32 = 0.5; // Extra hexes' worth of progress per level climbed
+pub const UPHILL_MARCH_FATIGUE: f32 = 0.01; // Per level climbed, times the pace's fatigue
+pub const HIGH_GROUND_STRESS: f32 = 0.02; // Per tick fighting an enemy a level above
+pub const UPHILL_COMBAT_FATIGUE: f32 = 0.01; // Per tick fighting an enemy a level above
+pub const HIGH_GROUND_SEARCH_RADIUS: u32 = 4; // How far a holding commander looks for a hill
+
 // Combat rates (per tick) - ADDITIVE
 pub const BASE_CASUALTY_RATE: f32 = 0.02;
 pub const FATIGUE_RATE_COMBAT: f32 = 0.02;
 pub const FATIGUE_RATE_MARCH: f32 = 0.005;
 pub const FATIGUE_RECOVERY_RATE: f32 = 0.01;
 
+// Engagement pacing - bouts of fighting broken by lulls
+pub const LULL_EXHAUSTION: f32 = 0.7; // Mean fatigue + stress/2 at which a bout dies down
+pub const RESUME_EXHAUSTION: f32 = 0.35; // Recovered enough to fight again
+pub const MAX_BOUT_TICKS: u64 = 60; // No bout lasts longer than a minute
+pub const MIN_LULL_TICKS: u64 = 10;
+pub const MAX_LULL_TICKS: u64 = 45; // Someone always starts it again
+pub const LULL_STRESS_RECOVERY: f32 = 0.005;
+
+// Skirmish doctrine - screen, harass, and never be caught
+pub const SKIRMISH_SCREEN_DISTANCE: u32 = 4; // Hexes ahead of the main line
+pub const SKIRMISH_EVADE_RANGE: u32 = 2; // Formed infantry this close is too close
+pub const SKIRMISH_CHARGE_RANGE: u32 = 4; // An enemy advancing this close is a charge
+pub const SKIRMISH_VOLLEY_TICKS: u64 = 5; // Ticks between harassing volleys
+pub const SKIRMISH_EVADE_FATIGUE: f32 = 0.02; // Per hex given up
+pub const SKIRMISH_EXHAUSTED: f32 = 0.9; // Too tired to keep running
+
+// Ranged fire - volleys loosed at visible enemies before contact
+pub const BOW_VOLLEY_TICKS: u64 = 5; // Ticks between volleys from formed bowmen
+pub const CROSSBOW_VOLLEY_TICKS: u64 = 10; // Spanning a crossbow takes longer
+pub const QUIVER_VOLLEYS: u32 = 24; // Volleys a ranged unit carries ammunition for
+pub const COVER_HIT_PENALTY: f32 = 0.4; // Off the hit chance per point of the target's cover
+
+// Supply in battle - stamina, and the wagons that restore it and the quivers
+pub const MELEE_STAMINA_DRAIN: f32 = 0.002; // Per tick of melee
+pub const WAGON_RESUPPLY_RANGE: u32 = 2; // Hexes from a wagon it can hand stores across
+pub const WAGON_RESUPPLY_TICKS: u64 = 10; // Ticks between handing out stores
+pub const WAGON_STAMINA_RESTORE: f32 = 0.1; // Stamina one store of food and water gives back
+
+// Cavalry charges - shock on contact after a gallop over open ground
+pub const CHARGE_MIN_RUN: u32 = 3; // Hexes galloped over open ground to strike with shock
+pub const CHARGE_REPULSE_LOSSES: u32 = 4; // Riders lost on the points of a braced line
+pub const CHARGE_REPULSE_STRESS: f32 = 0.2; // Stress of a charge thrown back
+pub const CHARGE_REPULSE_COHESION: f32 = 0.4; // Order lost by a charge thrown back
+pub const CHARGE_DISORDER_TICKS: u64 = 20; // Ticks a repulsed charge takes to re-form
+
+// Commander stress - pressure narrows options and slows orders
+pub const COMMAND_CASUALTY_STRESS: f32 = 2.0; // Per fraction of the army lost
+pub const COMMAND_SURPRISE_STRESS: f32 = 0.15; // A unit breaking, a courier taken
+pub const COMMAND_LEADERSHIP_MITIGATION: f32 = 0.6; // Share of each blow a master shrugs off
+pub const COMMAND_REST_TICKS: u64 = 30; // Quiet this long before stress eases
+pub const COMMAND_REST_RECOVERY: f32 = 0.01; // Per quiet tick, doubled for a master
+pub const COMMAND_SHAKEN: f32 = 0.5;
+pub const COMMAND_TUNNEL_VISION: f32 = 0.75; // Share of options ignored when overwhelmed
+pub const COMMAND_MAX_ORDER_DELAY: u64 = 20; // Ticks an overwhelmed HQ takes per order
+
+// Officers - named leaders riding with a formation's units
+pub const OFFICER_COMMAND_RANGE: u32 = 3; // Hexes from their unit an officer can rally
+pub const OFFICER_RALLY_MARGIN: f32 = 0.3; // Extra stress a master can rally units from
+pub const OFFICER_CONTAGION_DAMPING: f32 = 0.5; // Share of spreading panic a master stops
+pub const OFFICER_ORDER_SPEEDUP: f32 = 0.5; // Share of HQ delay a master manager saves
+pub const OFFICER_LOSS_CHANCE: f32 = 0.02; // Per casualty their unit takes
+
+// Drill - standing companies against levies
+pub const DRILL_ORDER_SPEEDUP: f32 = 0.5; // Share of HQ delay a perfectly drilled unit saves
+pub const DRILL_COHESION_HOLD: f32 = 0.5; // Share of a cohesion loss a perfectly drilled unit keeps
+
+// Mounted scouts and courier escorts - the information war
+pub const SCOUT_PROBE_DISTANCE: u32 = 6; // Hexes scouts ride out ahead when reconnoitring
+pub const SCOUT_RIDDEN_DOWN_RANGE: u32 = 2; // Enemy cavalry this close drives the riders off
+pub const SCOUT_RIDDEN_DOWN_LOSSES: u32 = 3;
+pub const SCOUT_RIDDEN_DOWN_STRESS: f32 = 0.2;
+pub const ESCORT_INTERCEPTION_REDUCTION: f32 = 0.35; // Off the chance, unless cavalry intercepts
+pub const ESCORT_LOSSES: u32 = 2; // Riders lost when an escorted courier is taken anyway
+
 // Stress - ADDITIVE thresholds
 pub const CONTAGION_STRESS: f32 = 0.10;
 pub const OFFICER_DEATH_STRESS: f32 = 0.30;
@@ -50,6 +121,32 @@ pub const FLANK_STRESS: f32 = 0.20;
 // Rally - ticks required to transition from Rallying to Formed
 pub const RALLY_TICKS_REQUIRED: u64 = 30;
 
+// Reinforcements
+pub const REINFORCEMENT_HORIZON_TICKS: u64 = 300; // AI counts on columns due within this
+
+// Sieges - walls and gates, and the assault on them
+pub const WALL_HP: f32 = 400.0;
+pub const GATE_HP: f32 = 200.0;
+pub const FORTIFICATION_DAMAGED_SHARE: f32 = 0.5; // Below this share of HP it shows damage
+pub const RAM_DAMAGE_PER_SOLDIER: f32 = 0.1; // Per tick, per soldier swinging the ram
+pub const ENGINEER_RAM_BONUS: f32 = 0.1; // Extra per engineer, who know how to swing one
+pub const LADDER_CLIMB_COST: f32 = 4.0; // Movement progress to scale a wall
+pub const RAM_CREW_LOSSES: u32 = 1; // Per tick, per defending unit on or behind the gate
+pub const LADDER_LOSSES: u32 = 2; // Per tick at the wall foot, per defending unit
+pub const ASSAULT_STRESS: f32 = 0.02; // Per tick, per defending unit
+pub const SIEGE_GATE_SEARCH_RADIUS: u32 = 6; // AI rams a gate this close rather than climb
+
+// Sapping - mines dug under walls, and the defenders listening for them
+pub const SAP_RATE_PER_SOLDIER: f32 = 0.0005; // Hexes of tunnel a tick, per soldier digging
+pub const ENGINEER_SAP_BONUS: f32 = 0.0015; // Extra per engineer, who know how to shore one up
+pub const LISTENING_RANGE: u32 = 4; // Hexes from a listener at the wall digging can be heard
+pub const LISTEN_CHANCE: f32 = 0.02; // Per tick, per listener in range
+pub const ENGINEER_LISTEN_BONUS: f32 = 0.03; // Engineers know the sound of a pick
+pub const MINE_COLLAPSE_LOSSES: u32 = 10; // Per unit on or beside the wall when it comes down
+pub const MINE_COLLAPSE_STRESS: f32 = 0.3;
+pub const BREAK_IN_LOSSES: u32 = 5; // Sappers lost when a countermine breaks into their tunnel
+pub const BREAK_IN_STRESS: f32 = 0.2;
+
 // Courier
 pub const COURIER_INTERCEPTION_RANGE: u32 = 2;
 pub const COURIER_INTERCEPTION_CHANCE_PATROL: f32 = 0.5;
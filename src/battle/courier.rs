@@ -8,6 +8,7 @@ use uuid::Uuid;
 use crate::battle::formation_layout::FormationLineId;
 use crate::battle::hex::{BattleHexCoord, HexDirection};
 use crate::battle::planning::{EngagementRule, GoCodeId};
+use crate::battle::save::Inert;
 use crate::battle::units::{FormationId, FormationShape, UnitId};
 use crate::core::types::{EntityId, Tick};
 
@@ -49,6 +50,24 @@ pub enum OrderType {
     },
     /// Move to assigned slot in a formation line
     MoveToFormationSlot(FormationLineId),
+    /// Scouts ride out to look in a direction
+    Reconnoiter(HexDirection),
+    /// Storm the wall at a hex with scaling ladders
+    LadderAssault(BattleHexCoord),
+    /// Batter down the wall or gate at a hex
+    RamAssault(BattleHexCoord),
+    /// Dig a mine from where the unit stands to under the wall at a hex
+    Sap(BattleHexCoord),
+    /// Stand where the unit is, set to receive a charge
+    Brace,
+    /// An order from a save this build does not recognise; does nothing
+    Unknown(String),
+}
+
+impl Inert for OrderType {
+    fn inert(tag: String) -> Self {
+        OrderType::Unknown(tag)
+    }
 }
 
 /// Target of an order
@@ -61,6 +80,7 @@ pub enum OrderTarget {
 /// An order to be delivered
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
+    #[serde(deserialize_with = "crate::battle::save::or_inert")]
     pub order_type: OrderType,
     pub target: OrderTarget,
     pub issued_at: Tick,
@@ -137,6 +157,14 @@ pub struct CourierInFlight {
     pub path: Vec<BattleHexCoord>,
 
     pub status: CourierStatus,
+
+    /// Ticks before the courier sets off (the order still being drawn up)
+    #[serde(default)]
+    pub delay: Tick,
+
+    /// Escort unit riding with the courier, if one was free
+    #[serde(default)]
+    pub escort: Option<UnitId>,
 }
 
 impl CourierInFlight {
@@ -159,6 +187,8 @@ impl CourierInFlight {
             progress: 0.0,
             path,
             status: CourierStatus::EnRoute,
+            delay: 0,
+            escort: None,
         }
     }
 
@@ -182,6 +212,10 @@ impl CourierInFlight {
         if !self.is_en_route() {
             return;
         }
+        if self.delay > 0 {
+            self.delay -= 1;
+            return;
+        }
 
         self.progress += speed;
 
@@ -214,7 +248,7 @@ impl CourierInFlight {
         }
 
         let remaining_hexes = self.path.len() as f32 + (1.0 - self.progress);
-        (remaining_hexes / speed).ceil() as u32
+        self.delay as u32 + (remaining_hexes / speed).ceil() as u32
     }
 }
 
@@ -238,7 +272,20 @@ impl CourierSystem {
         source: BattleHexCoord,
         destination: BattleHexCoord,
     ) -> CourierId {
-        let courier = CourierInFlight::new(courier_entity, order, source, destination);
+        self.dispatch_after(0, courier_entity, order, source, destination)
+    }
+
+    /// Dispatch a courier that waits `delay` ticks before setting off
+    pub fn dispatch_after(
+        &mut self,
+        delay: Tick,
+        courier_entity: EntityId,
+        order: Order,
+        source: BattleHexCoord,
+        destination: BattleHexCoord,
+    ) -> CourierId {
+        let mut courier = CourierInFlight::new(courier_entity, order, source, destination);
+        courier.delay = delay;
         let id = courier.id;
         self.in_flight.push(courier);
         id
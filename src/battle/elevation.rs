@@ -0,0 +1,134 @@
+//! Elevation - hills, ridgelines, and the high ground
+//!
+//! Height is counted in whole levels on each hex. Climbing a level costs
+//! movement and tires a unit (a charge uphill most of all), and a unit
+//! fighting an enemy above it takes extra stress and fatigue every tick.
+//! Height also lets a unit see further (see `visibility`), and a crest hides
+//! what lies beyond it from anyone below (see `BattleMap::has_line_of_sight`).
+
+use crate::battle::battle_map::BattleMap;
+use crate::battle::constants::{HIGH_GROUND_STRESS, UPHILL_COMBAT_FATIGUE, UPHILL_MOVEMENT_COST};
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::terrain::TerrainFeature;
+
+/// Levels climbed stepping from one hex to another (0 going down or level)
+pub fn climb(map: &BattleMap, from: BattleHexCoord, to: BattleHexCoord) -> u32 {
+    map.elevation_difference(to, from).max(0) as u32
+}
+
+/// Extra movement a step uphill costs, on top of the terrain's own
+pub fn uphill_cost(map: &BattleMap, from: BattleHexCoord, to: BattleHexCoord) -> f32 {
+    climb(map, from, to) as f32 * UPHILL_MOVEMENT_COST
+}
+
+/// Extra stress and fatigue per tick for a unit at `position` fighting an
+/// enemy at `enemy`, if the enemy holds higher ground
+pub fn uphill_penalty(
+    map: &BattleMap,
+    position: BattleHexCoord,
+    enemy: BattleHexCoord,
+) -> (f32, f32) {
+    let levels = climb(map, position, enemy) as f32;
+    (levels * HIGH_GROUND_STRESS, levels * UPHILL_COMBAT_FATIGUE)
+}
+
+/// The highest passable hex within `radius` of `position` that stands above
+/// it, nearest first among equals
+pub fn high_ground_near(
+    map: &BattleMap,
+    position: BattleHexCoord,
+    radius: u32,
+) -> Option<BattleHexCoord> {
+    let base = map.get_hex(position).map(|h| h.elevation).unwrap_or(0);
+    position
+        .hexes_in_range(radius)
+        .into_iter()
+        .filter_map(|coord| map.get_hex(coord))
+        .filter(|hex| hex.elevation > base && !hex.terrain.impassable_for_infantry())
+        .min_by_key(|hex| {
+            (
+                -hex.elevation,
+                position.distance(&hex.coord),
+                hex.coord.q,
+                hex.coord.r,
+            )
+        })
+        .map(|hex| hex.coord)
+}
+
+/// Raise a hill: `peak` levels at the centre, sloping down to the ground at
+/// `radius` hexes out. Ground already higher is left as it is.
+pub fn raise_hill(map: &mut BattleMap, center: BattleHexCoord, radius: u32, peak: i8) {
+    let span = radius as i32 + 1;
+    for coord in center.hexes_in_range(radius) {
+        let rest = span - center.distance(&coord) as i32;
+        let height = ((peak as i32 * rest + span - 1) / span) as i8;
+        if let Some(hex) = map.get_hex_mut(coord) {
+            hex.elevation = hex.elevation.max(height);
+        }
+    }
+}
+
+/// Raise a ridgeline from one hex to another: a crest `height` levels high,
+/// marked with `TerrainFeature::Ridge`, with slopes `flank` hexes wide
+pub fn raise_ridgeline(
+    map: &mut BattleMap,
+    from: BattleHexCoord,
+    to: BattleHexCoord,
+    height: i8,
+    flank: u32,
+) {
+    for crest in from.line_to(&to) {
+        raise_hill(map, crest, flank, height);
+        map.add_feature(crest, TerrainFeature::Ridge);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ridgeline_is_contiguous_and_slopes() {
+        let mut map = BattleMap::new(20, 20);
+        raise_ridgeline(
+            &mut map,
+            BattleHexCoord::new(10, 0),
+            BattleHexCoord::new(10, 19),
+            2,
+            2,
+        );
+
+        for r in 0..20 {
+            let crest = map.get_hex(BattleHexCoord::new(10, r)).unwrap();
+            assert_eq!(crest.elevation, 2);
+            assert!(crest.features.contains(&TerrainFeature::Ridge));
+        }
+        let elevation = |q| map.get_hex(BattleHexCoord::new(q, 10)).unwrap().elevation;
+        assert_eq!(elevation(9), 2);
+        assert_eq!(elevation(8), 1);
+        assert_eq!(elevation(7), 0);
+
+        // Climbing costs; coming down does not
+        let foot = BattleHexCoord::new(7, 10);
+        let slope = BattleHexCoord::new(8, 10);
+        assert_eq!(climb(&map, foot, slope), 1);
+        assert!(uphill_cost(&map, foot, slope) > 0.0);
+        assert_eq!(uphill_cost(&map, slope, foot), 0.0);
+        assert!(uphill_penalty(&map, foot, slope).0 > 0.0);
+        assert_eq!(uphill_penalty(&map, slope, foot), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_high_ground_near_finds_the_summit() {
+        let mut map = BattleMap::new(20, 20);
+        raise_hill(&mut map, BattleHexCoord::new(12, 10), 2, 3);
+
+        assert_eq!(
+            high_ground_near(&map, BattleHexCoord::new(9, 10), 4),
+            Some(BattleHexCoord::new(12, 10))
+        );
+        assert_eq!(high_ground_near(&map, BattleHexCoord::new(2, 2), 4), None);
+        assert_eq!(high_ground_near(&map, BattleHexCoord::new(12, 10), 4), None);
+    }
+}
@@ -4,7 +4,7 @@
 
 use crate::battle::hex::BattleHexCoord;
 use crate::battle::planning::EngagementRule;
-use crate::battle::units::{BattleUnit, UnitId};
+use crate::battle::units::{BattleUnit, UnitId, UnitStance};
 
 /// Potential engagement between two units
 #[derive(Debug, Clone)]
@@ -21,6 +21,11 @@ pub fn detect_engagement(unit_a: &BattleUnit, unit_b: &BattleUnit) -> Option<Pot
         return None;
     }
 
+    // Skirmishers falling back slip away rather than close
+    if unit_a.stance == UnitStance::Evading || unit_b.stance == UnitStance::Evading {
+        return None;
+    }
+
     let distance = unit_a.position.distance(&unit_b.position);
 
     // Must be adjacent (distance 1) for melee engagement
@@ -95,7 +100,7 @@ mod tests {
     use super::*;
     use crate::battle::hex::HexDirection;
     use crate::battle::unit_type::UnitType;
-    use crate::battle::units::{Element, UnitStance};
+    use crate::battle::units::Element;
     use crate::core::types::EntityId;
 
     #[test]
@@ -1,26 +1,86 @@
 //! Battle execution loop
 //!
-//! Each tick: movement -> couriers -> engagement -> combat -> morale -> rout
+//! Each tick: movement -> couriers -> ranged fire -> supply -> skirmish ->
+//! engagement -> combat -> morale -> rout
+//!
+//! Engagements are paced: a bout of fighting lasts until both sides are spent,
+//! then a lull lets them recover before it flares up again. Units in a lull
+//! are no longer pinned, so that is when to withdraw them or send in reserves.
+//!
+//! A unit fighting an enemy on higher ground takes extra stress and fatigue
+//! every tick of the bout (see `elevation`).
+//!
+//! Cavalry that come into contact at the gallop strike with their charge
+//! (see `resolution`) before the first exchange of the fight; a charge
+//! thrown back leaves them disordered until they re-form.
+//!
+//! Skirmishers act before engagement is detected, so one that falls back from
+//! a charge this tick is not caught by it. Formed shooters loose their
+//! volleys just before them, at whatever their army can see (see `ranged`),
+//! and supply wagons hand out arrows, food, and water (see `supply`).
+//!
+//! Each commander's stress is updated at the end of the tick from the army's
+//! losses, units that broke, and couriers that were taken.
+//!
+//! Couriers are intercepted by the other side's patrols; an escort riding
+//! with one makes that less likely, unless cavalry does the intercepting.
+//! Scouts out reconnoitring are driven off by enemy cavalry before fog of war
+//! is updated, so they see nothing that tick.
+//!
+//! Reinforcements due this tick march on at the start of it, before fog of
+//! war is updated, so they see and are seen straight away. An army with
+//! reinforcements still on the way is not beaten just because nothing of it
+//! is left on the field.
+//!
+//! Assaults on walls and gates are pressed straight after movement, so a
+//! unit that reaches the foot of its target starts battering (and being shot
+//! at) that same tick, and one that breaches it marches in on the next.
+//! Mines are dug, listened for, and sprung in the same phase.
+//!
+//! Every roll of the dice in a tick comes from `tick_rng`, seeded by the
+//! battle's seed and the tick, so a battle fought again from the same seed
+//! (or from a save) turns out the same.
 
-use std::collections::HashMap;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::battle::ai::{BattleAI, DecisionContext};
+use crate::combat::cache::ExchangeCache;
 use crate::combat::state::CombatState;
 
 use crate::battle::battle_map::BattleMap;
-use crate::battle::constants::COURIER_SPEED;
-use crate::battle::courier::CourierSystem;
+use crate::battle::command_stress::CommandStress;
+use crate::battle::constants::{
+    COURIER_SPEED, FATIGUE_RECOVERY_RATE, LULL_EXHAUSTION, LULL_STRESS_RECOVERY, MAX_BOUT_TICKS,
+    MAX_LULL_TICKS, MINE_COLLAPSE_LOSSES, MINE_COLLAPSE_STRESS, MIN_LULL_TICKS, RESUME_EXHAUSTION,
+};
+use crate::battle::courier::{CourierId, CourierSystem, Order, OrderTarget};
+use crate::battle::elevation::uphill_penalty;
 use crate::battle::engagement::find_all_engagements;
 use crate::battle::hex::BattleHexCoord;
 use crate::battle::morale::{
-    apply_stress, calculate_contagion_stress, check_morale_break, check_rally, process_morale_break,
+    apply_stress, calculate_contagion_stress, calculate_officer_death_stress, check_morale_break,
+    check_rally, process_morale_break,
 };
 use crate::battle::movement::advance_unit_movement;
+use crate::battle::officers::{
+    commander_fallen, contagion_share, officer_losses, order_delay, rally_skill_near,
+};
 use crate::battle::planning::BattlePlan;
-use crate::battle::resolution::resolve_unit_combat;
+use crate::battle::ranged::run_ranged_fire;
+use crate::battle::recon::{drive_off_scouts, free_escort, interception_chance};
+use crate::battle::reinforcements::{arrive_reinforcements, Reinforcement};
+use crate::battle::resolution::{
+    fall_into_disorder, reform_from_disorder, resolve_charge, resolve_unit_combat, ChargeOutcome,
+};
+use crate::battle::siege::{run_mining, run_siege};
+use crate::battle::skirmish::run_skirmishers;
+use crate::battle::supply::{drain_stamina, out_of_ammo_stress, run_resupply};
+use crate::battle::terrain::FortificationKind;
 use crate::battle::triggers::{evaluate_all_gocodes, UnitPosition};
-use crate::battle::units::{Army, UnitId, UnitStance};
+use crate::battle::units::{Army, BattleFormation, BattleUnit, FormationId, UnitId, UnitStance};
 use crate::battle::visibility::{update_army_visibility, ArmyVisibility};
 use crate::core::types::{EntityId, Tick};
 
@@ -65,10 +125,28 @@ pub struct BattleEvent {
 pub enum BattleEventType {
     BattleStarted,
     UnitEngaged { unit_id: UnitId },
+    LullBegan { units: (UnitId, UnitId) },
+    FightingResumed { units: (UnitId, UnitId) },
+    SkirmishersFellBack { unit_id: UnitId },
+    SkirmishersCornered { unit_id: UnitId },
+    OutOfAmmunition { unit_id: UnitId },
+    ChargeStruckHome { unit_id: UnitId },
+    ChargeCountered { unit_id: UnitId },
+    ChargeRepulsed { unit_id: UnitId },
+    UnitReformed { unit_id: UnitId },
+    WagonsEmptied { unit_id: UnitId },
+    ScoutsDrivenOff { unit_id: UnitId },
     UnitBroke { unit_id: UnitId },
     UnitDestroyed { unit_id: UnitId },
     UnitRallied { unit_id: UnitId },
+    ReinforcementsArrived { formation: FormationId },
+    FortificationBreached { coord: BattleHexCoord },
+    WallScaled { unit_id: UnitId },
+    DiggingHeard { coord: BattleHexCoord },
+    MineSprung { coord: BattleHexCoord },
+    SappersBrokenInto { unit_id: UnitId },
     CommanderKilled { entity_id: EntityId },
+    CommanderShaken { entity_id: EntityId },
     ObjectiveCaptured { name: String },
     CourierIntercepted,
     GoCodeTriggered { name: String },
@@ -102,12 +180,48 @@ pub struct RoutingUnit {
     pub retreat_progress: f32,
 }
 
+/// Rhythm of an engagement: bouts of fighting broken by lulls
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CombatPace {
+    #[default]
+    Bout, // Fighting hand to hand
+    Lull, // Both sides drawn back to recover; units may be withdrawn or relieved
+}
+
 /// Active combat between units
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveCombat {
     pub attacker_unit: UnitId,
     pub defender_unit: UnitId,
+    /// Ticks spent actually fighting (lulls excluded)
     pub ticks_engaged: u32,
+    pub pace: CombatPace,
+    /// Tick the current bout or lull began
+    pub pace_since: Tick,
+    /// Bouts fought so far, including the current one
+    pub bouts: u32,
+}
+
+impl ActiveCombat {
+    pub fn new(attacker_unit: UnitId, defender_unit: UnitId, tick: Tick) -> Self {
+        Self {
+            attacker_unit,
+            defender_unit,
+            ticks_engaged: 0,
+            pace: CombatPace::Bout,
+            pace_since: tick,
+            bouts: 1,
+        }
+    }
+
+    /// Start a bout or a lull
+    pub fn set_pace(&mut self, pace: CombatPace, tick: Tick) {
+        if pace == CombatPace::Bout {
+            self.bouts += 1;
+        }
+        self.pace = pace;
+        self.pace_since = tick;
+    }
 }
 
 /// Complete battle state
@@ -123,6 +237,10 @@ pub struct BattleState {
     pub phase: BattlePhase,
     pub outcome: BattleOutcome,
 
+    /// Seed of the battle's dice (see `tick_rng`)
+    #[serde(default)]
+    pub seed: u64,
+
     /// Current time scale (sim-seconds per tick)
     /// Higher = time moves faster (approach phase)
     /// Lower = time moves slower (combat phase)
@@ -135,6 +253,10 @@ pub struct BattleState {
     // Systems
     pub courier_system: CourierSystem,
 
+    // Commanders' state of mind
+    pub friendly_command: CommandStress,
+    pub enemy_command: CommandStress,
+
     // Visibility (fog of war)
     pub friendly_visibility: ArmyVisibility,
     pub enemy_visibility: ArmyVisibility,
@@ -143,9 +265,19 @@ pub struct BattleState {
     pub active_combats: Vec<ActiveCombat>,
     pub routing_units: Vec<RoutingUnit>,
 
+    // Formations still on the march to the field
+    #[serde(default)]
+    pub friendly_reinforcements: Vec<Reinforcement>,
+    #[serde(default)]
+    pub enemy_reinforcements: Vec<Reinforcement>,
+
     // Entity-level combat state
     pub entity_states: HashMap<EntityId, CombatState>,
 
+    /// Exchange outcomes already resolved this battle (rebuilt on demand)
+    #[serde(skip)]
+    pub exchange_cache: ExchangeCache,
+
     // Log
     pub battle_log: Vec<BattleEvent>,
 
@@ -167,15 +299,21 @@ impl std::fmt::Debug for BattleState {
             .field("tick", &self.tick)
             .field("phase", &self.phase)
             .field("outcome", &self.outcome)
+            .field("seed", &self.seed)
             .field("time_scale", &self.time_scale)
             .field("friendly_plan", &self.friendly_plan)
             .field("enemy_plan", &self.enemy_plan)
             .field("courier_system", &self.courier_system)
+            .field("friendly_command", &self.friendly_command)
+            .field("enemy_command", &self.enemy_command)
             .field("friendly_visibility", &self.friendly_visibility)
             .field("enemy_visibility", &self.enemy_visibility)
             .field("active_combats", &self.active_combats)
             .field("routing_units", &self.routing_units)
+            .field("friendly_reinforcements", &self.friendly_reinforcements)
+            .field("enemy_reinforcements", &self.enemy_reinforcements)
             .field("entity_states_count", &self.entity_states.len())
+            .field("exchange_cache_len", &self.exchange_cache.len())
             .field("battle_log", &self.battle_log)
             .field(
                 "enemy_ai",
@@ -198,15 +336,21 @@ impl Clone for BattleState {
             tick: self.tick,
             phase: self.phase,
             outcome: self.outcome,
+            seed: self.seed,
             time_scale: self.time_scale,
             friendly_plan: self.friendly_plan.clone(),
             enemy_plan: self.enemy_plan.clone(),
             courier_system: self.courier_system.clone(),
+            friendly_command: self.friendly_command,
+            enemy_command: self.enemy_command,
             friendly_visibility: self.friendly_visibility.clone(),
             enemy_visibility: self.enemy_visibility.clone(),
             active_combats: self.active_combats.clone(),
             routing_units: self.routing_units.clone(),
+            friendly_reinforcements: self.friendly_reinforcements.clone(),
+            enemy_reinforcements: self.enemy_reinforcements.clone(),
             entity_states: self.entity_states.clone(),
+            exchange_cache: ExchangeCache::new(),
             battle_log: self.battle_log.clone(),
             enemy_ai: None,    // AI is not cloned - must be re-attached
             friendly_ai: None, // AI is not cloned - must be re-attached
@@ -215,7 +359,13 @@ impl Clone for BattleState {
 }
 
 impl BattleState {
+    /// Set up a battle with a random seed
     pub fn new(map: BattleMap, friendly_army: Army, enemy_army: Army) -> Self {
+        Self::with_seed(map, friendly_army, enemy_army, rand::random())
+    }
+
+    /// Set up a battle whose every roll is reproducible from `seed`
+    pub fn with_seed(map: BattleMap, friendly_army: Army, enemy_army: Army, seed: u64) -> Self {
         use crate::battle::constants::TIME_SCALE_APPROACH;
         Self {
             map,
@@ -224,21 +374,33 @@ impl BattleState {
             tick: 0,
             phase: BattlePhase::Planning,
             outcome: BattleOutcome::Undecided,
+            seed,
             time_scale: TIME_SCALE_APPROACH, // Start in approach phase (fast time)
             friendly_plan: BattlePlan::new(),
             enemy_plan: BattlePlan::new(),
             courier_system: CourierSystem::new(),
+            friendly_command: CommandStress::default(),
+            enemy_command: CommandStress::default(),
             friendly_visibility: ArmyVisibility::new(),
             enemy_visibility: ArmyVisibility::new(),
             active_combats: Vec::new(),
             routing_units: Vec::new(),
+            friendly_reinforcements: Vec::new(),
+            enemy_reinforcements: Vec::new(),
             entity_states: HashMap::new(),
+            exchange_cache: ExchangeCache::new(),
             battle_log: Vec::new(),
             enemy_ai: None,
             friendly_ai: None,
         }
     }
 
+    /// Random number generator for the current tick, seeded by the battle's
+    /// seed and the tick
+    pub fn tick_rng(&self) -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(self.seed ^ self.tick.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
     /// Is the battle finished?
     pub fn is_finished(&self) -> bool {
         matches!(self.phase, BattlePhase::Finished)
@@ -260,6 +422,16 @@ impl BattleState {
         self.friendly_ai = ai;
     }
 
+    /// Send a formation to join the friendly army mid-battle
+    pub fn schedule_friendly_reinforcement(&mut self, reinforcement: Reinforcement) {
+        self.friendly_reinforcements.push(reinforcement);
+    }
+
+    /// Send a formation to join the enemy army mid-battle
+    pub fn schedule_enemy_reinforcement(&mut self, reinforcement: Reinforcement) {
+        self.enemy_reinforcements.push(reinforcement);
+    }
+
     /// Advance the battle by one tick
     pub fn advance_tick(&mut self) {
         if self.is_finished() {
@@ -383,6 +555,7 @@ impl BattleState {
 
         // Update time scale based on current battle state
         self.time_scale = self.calculate_time_scale();
+        let mut rng = self.tick_rng();
 
         // ===== PHASE 0: AI DECISIONS =====
         self.phase_ai(&mut events);
@@ -391,23 +564,43 @@ impl BattleState {
         self.phase_pre_tick(&mut events);
 
         // ===== PHASE 2: MOVEMENT =====
-        self.phase_movement(&mut events);
+        self.phase_movement(&mut events, &mut rng);
+        self.phase_siege(&mut events, &mut rng);
+
+        // ===== PHASE 3: RANGED FIRE AND SKIRMISH =====
+        self.phase_ranged(&mut events, &mut rng);
+        self.phase_supply(&mut events);
+        self.phase_skirmish(&mut events, &mut rng);
 
-        // ===== PHASE 3: COMBAT =====
-        self.phase_combat(&mut events);
+        // ===== PHASE 4: COMBAT =====
+        self.phase_combat(&mut events, &mut rng);
 
-        // ===== PHASE 4: MORALE =====
+        // ===== PHASE 5: MORALE =====
         self.phase_morale(&mut events);
 
-        // ===== PHASE 5: ROUT =====
+        // ===== PHASE 6: ROUT =====
         self.phase_rout(&mut events);
 
-        // ===== PHASE 6: POST-TICK =====
-        self.phase_post_tick(&mut events);
+        // ===== PHASE 7: POST-TICK =====
+        self.phase_post_tick(&mut events, &mut rng);
 
         events
     }
 
+    /// Send one of the player's orders by courier
+    ///
+    /// The courier sets off once the friendly commander has drawn the order
+    /// up, which takes longer the more stressed they are. Returns None if
+    /// the target is gone or no courier is left.
+    pub fn issue_order(&mut self, order: Order) -> Option<CourierId> {
+        send_by_courier(
+            &mut self.friendly_army,
+            &mut self.courier_system,
+            &self.friendly_command,
+            order,
+        )
+    }
+
     fn phase_ai(&mut self, events: &mut BattleEventLog) {
         // Process enemy AI
         if let Some(ref mut ai) = self.enemy_ai {
@@ -418,34 +611,20 @@ impl BattleState {
                 &self.enemy_visibility,
                 self.tick,
                 ai.ignores_fog_of_war(),
-            );
+            )
+            .with_command(self.enemy_command)
+            .with_map(&self.map)
+            .with_reinforcements(&self.enemy_reinforcements);
 
-            // Get AI decisions
+            // Get AI decisions, dispatched via courier system
             let orders = ai.process_tick(&context, self.tick, events);
-
-            // Dispatch orders via courier system
             for order in orders {
-                // Get destination based on order target
-                let destination = match &order.target {
-                    crate::battle::courier::OrderTarget::Unit(unit_id) => {
-                        self.enemy_army.get_unit(*unit_id).map(|u| u.position)
-                    }
-                    crate::battle::courier::OrderTarget::Formation(formation_id) => self
-                        .enemy_army
-                        .formations
-                        .iter()
-                        .find(|f| f.id == *formation_id)
-                        .and_then(|f| f.commander_position()),
-                };
-
-                if let Some(dest) = destination {
-                    // Get courier from pool
-                    if let Some(courier_entity) = self.enemy_army.courier_pool.pop() {
-                        let source = self.enemy_army.hq_position;
-                        self.courier_system
-                            .dispatch(courier_entity, order, source, dest);
-                    }
-                }
+                send_by_courier(
+                    &mut self.enemy_army,
+                    &mut self.courier_system,
+                    &self.enemy_command,
+                    order,
+                );
             }
         }
 
@@ -458,44 +637,50 @@ impl BattleState {
                 &self.friendly_visibility,
                 self.tick,
                 ai.ignores_fog_of_war(),
-            );
+            )
+            .with_command(self.friendly_command)
+            .with_map(&self.map)
+            .with_reinforcements(&self.friendly_reinforcements);
 
-            // Get AI decisions
+            // Get AI decisions, dispatched via courier system
             let orders = ai.process_tick(&context, self.tick, events);
-
-            // Dispatch orders via courier system
             for order in orders {
-                // Get destination based on order target
-                let destination = match &order.target {
-                    crate::battle::courier::OrderTarget::Unit(unit_id) => {
-                        self.friendly_army.get_unit(*unit_id).map(|u| u.position)
-                    }
-                    crate::battle::courier::OrderTarget::Formation(formation_id) => self
-                        .friendly_army
-                        .formations
-                        .iter()
-                        .find(|f| f.id == *formation_id)
-                        .and_then(|f| f.commander_position()),
-                };
-
-                if let Some(dest) = destination {
-                    // Get courier from pool
-                    if let Some(courier_entity) = self.friendly_army.courier_pool.pop() {
-                        let source = self.friendly_army.hq_position;
-                        self.courier_system
-                            .dispatch(courier_entity, order, source, dest);
-                    }
-                }
+                send_by_courier(
+                    &mut self.friendly_army,
+                    &mut self.courier_system,
+                    &self.friendly_command,
+                    order,
+                );
             }
         }
     }
 
     fn phase_pre_tick(&mut self, events: &mut BattleEventLog) {
-        use crate::battle::courier::Order;
         use crate::battle::orders::apply_order;
         use crate::battle::planning::ContingencyResponse;
         use crate::battle::triggers::evaluate_all_contingencies;
 
+        // Reinforcements march on before anyone looks
+        self.phase_reinforcements(events);
+
+        // Scouts meeting enemy cavalry are driven off before they can report
+        let (friendly_units, enemy_units) =
+            (units_of(&self.friendly_army), units_of(&self.enemy_army));
+        let driven_off = drive_off_scouts(&self.map, &mut self.friendly_army, &enemy_units)
+            .into_iter()
+            .chain(drive_off_scouts(
+                &self.map,
+                &mut self.enemy_army,
+                &friendly_units,
+            ));
+        for unit_id in driven_off {
+            events.push(
+                BattleEventType::ScoutsDrivenOff { unit_id },
+                "Scouts driven off by enemy cavalry".to_string(),
+                self.tick,
+            );
+        }
+
         // Update fog of war
         update_army_visibility(
             &mut self.friendly_visibility,
@@ -549,13 +734,15 @@ impl BattleState {
             0.0
         };
 
-        // Check if commander is alive (simplified: check if any formation has units)
+        // Check if commander is alive (simplified: check if any formation has
+        // units, unless the commander rode with one as an officer and fell)
         let commander_alive = !self.friendly_army.formations.is_empty()
             && self
                 .friendly_army
                 .formations
                 .iter()
-                .any(|f| !f.units.is_empty());
+                .any(|f| !f.units.is_empty())
+            && !commander_fallen(&self.friendly_army);
 
         // Get enemy positions
         let enemy_positions: Vec<BattleHexCoord> = self
@@ -617,6 +804,7 @@ impl BattleState {
                         ContingencyResponse::Signal(go_code_id) => {
                             go_codes_to_trigger.push(*go_code_id);
                         }
+                        ContingencyResponse::Unknown(_) => {}
                     }
                 }
             }
@@ -658,45 +846,56 @@ impl BattleState {
         }
     }
 
-    fn phase_movement(&mut self, events: &mut BattleEventLog) {
+    fn phase_movement(&mut self, events: &mut BattleEventLog, rng: &mut impl Rng) {
         use crate::battle::constants::{
             COURIER_INTERCEPTION_CHANCE_ALERT, COURIER_INTERCEPTION_CHANCE_PATROL,
-            COURIER_INTERCEPTION_RANGE,
+            COURIER_INTERCEPTION_RANGE, ESCORT_LOSSES,
         };
         use crate::battle::orders::apply_order;
 
         // ===== COURIER INTERCEPTION CHECK (before advancing) =====
 
-        // Get enemy units that can intercept (Patrol or Alert stance)
-        let interceptors: Vec<(BattleHexCoord, f32)> = self
-            .enemy_army
-            .formations
-            .iter()
-            .flat_map(|f| f.units.iter())
-            .filter(|u| matches!(u.stance, UnitStance::Patrol | UnitStance::Alert))
-            .map(|u| {
-                let chance = if u.stance == UnitStance::Patrol {
-                    COURIER_INTERCEPTION_CHANCE_PATROL
-                } else {
-                    COURIER_INTERCEPTION_CHANCE_ALERT
-                };
-                (u.position, chance)
-            })
-            .collect();
+        // Units that can intercept (Patrol or Alert stance), with their base chance
+        let interceptors_in = |army: &Army| -> Vec<(BattleUnit, f32)> {
+            army.formations
+                .iter()
+                .flat_map(|f| f.units.iter())
+                .filter(|u| matches!(u.stance, UnitStance::Patrol | UnitStance::Alert))
+                .map(|u| {
+                    let chance = if u.stance == UnitStance::Patrol {
+                        COURIER_INTERCEPTION_CHANCE_PATROL
+                    } else {
+                        COURIER_INTERCEPTION_CHANCE_ALERT
+                    };
+                    (u.clone(), chance)
+                })
+                .collect()
+        };
+        let friendly_interceptors = interceptors_in(&self.friendly_army);
+        let enemy_interceptors = interceptors_in(&self.enemy_army);
 
-        // Check each courier against interceptors
+        // Check each courier against the other side's interceptors
+        let mut escorts_hit = Vec::new();
         for courier in &mut self.courier_system.in_flight {
             if !courier.is_en_route() {
                 continue;
             }
 
-            for (interceptor_pos, chance) in &interceptors {
-                let distance = courier.current_position.distance(interceptor_pos);
+            let interceptors = if addresses(&self.friendly_army, &courier.order.target) {
+                &enemy_interceptors
+            } else {
+                &friendly_interceptors
+            };
+            for (interceptor, base_chance) in interceptors {
+                let distance = courier.current_position.distance(&interceptor.position);
                 if distance <= COURIER_INTERCEPTION_RANGE {
                     // Random interception check
-                    let roll: f32 = rand::random();
-                    if roll < *chance {
+                    let chance =
+                        interception_chance(*base_chance, interceptor, courier.escort.is_some());
+                    let roll: f32 = rng.gen();
+                    if roll < chance {
                         courier.intercept();
+                        escorts_hit.extend(courier.escort);
                         events.push(
                             BattleEventType::CourierIntercepted,
                             "Courier intercepted by enemy patrol".to_string(),
@@ -708,6 +907,25 @@ impl BattleState {
             }
         }
 
+        // An escort that failed to save its courier loses riders with it
+        for escort_id in escorts_hit {
+            if let Some(escort) = self.get_unit_mut(escort_id) {
+                escort.casualties += ESCORT_LOSSES;
+            }
+        }
+
+        // A lost courier is a nasty surprise for whoever sent it
+        for courier in &self.courier_system.in_flight {
+            if !courier.was_intercepted() {
+                continue;
+            }
+            if addresses(&self.friendly_army, &courier.order.target) {
+                self.friendly_command.surprise();
+            } else {
+                self.enemy_command.surprise();
+            }
+        }
+
         // Remove intercepted couriers
         self.courier_system
             .in_flight
@@ -778,7 +996,148 @@ impl BattleState {
         }
     }
 
-    fn phase_combat(&mut self, _events: &mut BattleEventLog) {
+    fn phase_ranged(&mut self, events: &mut BattleEventLog, rng: &mut impl Rng) {
+        let (friendly_units, enemy_units) =
+            (units_of(&self.friendly_army), units_of(&self.enemy_army));
+        let friendly = run_ranged_fire(
+            &self.map,
+            &mut self.friendly_army,
+            &self.friendly_plan,
+            &enemy_units,
+            &self.friendly_visibility,
+            self.tick,
+            rng,
+        );
+        let enemy = run_ranged_fire(
+            &self.map,
+            &mut self.enemy_army,
+            &self.enemy_plan,
+            &friendly_units,
+            &self.enemy_visibility,
+            self.tick,
+            rng,
+        );
+
+        for (outcome, target_army) in [
+            (&friendly, &mut self.enemy_army),
+            (&enemy, &mut self.friendly_army),
+        ] {
+            for volley in &outcome.volleys {
+                if let Some(unit) = target_army.get_unit_mut(volley.target) {
+                    unit.casualties += volley.casualties;
+                    unit.stress += volley.stress;
+                }
+            }
+        }
+
+        for unit_id in friendly.out_of_ammo.iter().chain(&enemy.out_of_ammo) {
+            events.push(
+                BattleEventType::OutOfAmmunition { unit_id: *unit_id },
+                "Shooters loose their last volley".to_string(),
+                self.tick,
+            );
+        }
+    }
+
+    fn phase_supply(&mut self, events: &mut BattleEventLog) {
+        let friendly = run_resupply(&mut self.friendly_army, &mut self.friendly_plan, self.tick);
+        let enemy = run_resupply(&mut self.enemy_army, &mut self.enemy_plan, self.tick);
+        for unit_id in friendly.emptied.iter().chain(&enemy.emptied) {
+            events.push(
+                BattleEventType::WagonsEmptied { unit_id: *unit_id },
+                "The wagons hand out their last stores".to_string(),
+                self.tick,
+            );
+        }
+
+        // Empty quivers with the enemy in range
+        let (friendly_units, enemy_units) =
+            (units_of(&self.friendly_army), units_of(&self.enemy_army));
+        for (army, enemies, visibility) in [
+            (
+                &mut self.friendly_army,
+                &enemy_units,
+                &self.friendly_visibility,
+            ),
+            (
+                &mut self.enemy_army,
+                &friendly_units,
+                &self.enemy_visibility,
+            ),
+        ] {
+            for unit in army.formations.iter_mut().flat_map(|f| f.units.iter_mut()) {
+                unit.stress += out_of_ammo_stress(unit, enemies, visibility);
+            }
+        }
+    }
+
+    fn phase_skirmish(&mut self, events: &mut BattleEventLog, rng: &mut impl Rng) {
+        let enemy_units = units_of(&self.enemy_army);
+        let friendly = run_skirmishers(
+            &self.map,
+            &mut self.friendly_army,
+            &mut self.friendly_plan,
+            &enemy_units,
+            self.tick,
+            rng,
+        );
+        let friendly_units = units_of(&self.friendly_army);
+        let enemy = run_skirmishers(
+            &self.map,
+            &mut self.enemy_army,
+            &mut self.enemy_plan,
+            &friendly_units,
+            self.tick,
+            rng,
+        );
+
+        for (outcome, target_army) in [
+            (&friendly, &mut self.enemy_army),
+            (&enemy, &mut self.friendly_army),
+        ] {
+            for volley in &outcome.volleys {
+                if let Some(unit) = target_army.get_unit_mut(volley.target) {
+                    unit.casualties += volley.casualties;
+                    unit.stress += volley.stress;
+                }
+            }
+        }
+
+        for unit_id in friendly.fell_back.iter().chain(&enemy.fell_back) {
+            events.push(
+                BattleEventType::SkirmishersFellBack { unit_id: *unit_id },
+                "Skirmishers fall back".to_string(),
+                self.tick,
+            );
+        }
+        for unit_id in friendly.cornered.iter().chain(&enemy.cornered) {
+            events.push(
+                BattleEventType::SkirmishersCornered { unit_id: *unit_id },
+                "Skirmishers cornered and forced to fight".to_string(),
+                self.tick,
+            );
+        }
+    }
+
+    fn phase_combat(&mut self, events: &mut BattleEventLog, rng: &mut impl Rng) {
+        // Charges thrown back earlier re-form once they have had time to
+        let tick = self.tick;
+        for unit in self
+            .friendly_army
+            .formations
+            .iter_mut()
+            .chain(self.enemy_army.formations.iter_mut())
+            .flat_map(|f| f.units.iter_mut())
+        {
+            if reform_from_disorder(unit, tick) {
+                events.push(
+                    BattleEventType::UnitReformed { unit_id: unit.id },
+                    "Horsemen re-form after their charge was thrown back".to_string(),
+                    tick,
+                );
+            }
+        }
+
         // Collect unit references
         let friendly_units: Vec<&crate::battle::units::BattleUnit> = self
             .friendly_army
@@ -795,33 +1154,242 @@ impl BattleState {
             .collect();
 
         // Detect engagements
-        let engagements = find_all_engagements(&friendly_units, &enemy_units);
+        let engagements: Vec<(UnitId, UnitId)> =
+            find_all_engagements(&friendly_units, &enemy_units)
+                .into_iter()
+                .map(|e| (e.attacker_id, e.defender_id))
+                .collect();
+
+        // Fights whose units have drawn apart (withdrawn, routed, destroyed) are over
+        self.active_combats
+            .retain(|c| engagements.contains(&(c.attacker_unit, c.defender_unit)));
 
         // Process each engagement
-        for engagement in engagements {
-                        // Find units by ID and resolve combat
-                        let friendly_unit = self.friendly_army.get_unit(engagement.attacker_id);
-                        let enemy_unit = self.enemy_army.get_unit(engagement.defender_id);
-            
-                        if let (Some(attacker), Some(defender)) = (friendly_unit, enemy_unit) {
-                            // Resolve combat with entity states
-                            let result = resolve_unit_combat(attacker, defender, &mut self.entity_states);
-            
-                            // Apply results
-                            if let Some(unit) = self.friendly_army.get_unit_mut(engagement.attacker_id) {                    unit.casualties += result.attacker_casualties;
-                    unit.stress += result.attacker_stress_delta;
-                    unit.fatigue = (unit.fatigue + result.attacker_fatigue_delta).min(1.0);
-                    unit.stance = UnitStance::Engaged;
+        for (attacker_id, defender_id) in engagements {
+            let idx = match self
+                .active_combats
+                .iter()
+                .position(|c| c.attacker_unit == attacker_id && c.defender_unit == defender_id)
+            {
+                Some(idx) => idx,
+                None => {
+                    self.active_combats.push(ActiveCombat::new(
+                        attacker_id,
+                        defender_id,
+                        self.tick,
+                    ));
+                    events.push(
+                        BattleEventType::UnitEngaged {
+                            unit_id: attacker_id,
+                        },
+                        "Units engaged".to_string(),
+                        self.tick,
+                    );
+                    self.strike_charges(attacker_id, defender_id, events);
+                    self.active_combats.len() - 1
+                }
+            };
+
+            if self.active_combats[idx].pace == CombatPace::Lull {
+                self.tend_lull(idx, events);
+                continue;
+            }
+
+            // Find units by ID and resolve combat
+            let friendly_unit = self.friendly_army.get_unit(attacker_id);
+            let enemy_unit = self.enemy_army.get_unit(defender_id);
+
+            if let (Some(attacker), Some(defender)) = (friendly_unit, enemy_unit) {
+                // Resolve combat with entity states
+                let result = resolve_unit_combat(
+                    attacker,
+                    defender,
+                    &mut self.entity_states,
+                    &mut self.exchange_cache,
+                    rng,
+                );
+                // Whoever fights up the slope pays for it
+                let (attacker_stress, attacker_fatigue) =
+                    uphill_penalty(&self.map, attacker.position, defender.position);
+                let (defender_stress, defender_fatigue) =
+                    uphill_penalty(&self.map, defender.position, attacker.position);
+
+                // Apply results
+                if let Some(unit) = self.friendly_army.get_unit_mut(attacker_id) {
+                    unit.casualties += result.attacker_casualties;
+                    unit.stress += result.attacker_stress_delta + attacker_stress;
+                    unit.fatigue =
+                        (unit.fatigue + result.attacker_fatigue_delta + attacker_fatigue).min(1.0);
+                    drain_stamina(unit);
+                    if unit.stance != UnitStance::Disordered {
+                        unit.stance = UnitStance::Engaged;
+                    }
                 }
 
-                if let Some(unit) = self.enemy_army.get_unit_mut(engagement.defender_id) {
+                if let Some(unit) = self.enemy_army.get_unit_mut(defender_id) {
                     unit.casualties += result.defender_casualties;
-                    unit.stress += result.defender_stress_delta;
-                    unit.fatigue = (unit.fatigue + result.defender_fatigue_delta).min(1.0);
-                    unit.stance = UnitStance::Engaged;
+                    unit.stress += result.defender_stress_delta + defender_stress;
+                    unit.fatigue =
+                        (unit.fatigue + result.defender_fatigue_delta + defender_fatigue).min(1.0);
+                    drain_stamina(unit);
+                    if unit.stance != UnitStance::Disordered {
+                        unit.stance = UnitStance::Engaged;
+                    }
+                }
+            }
+
+            self.active_combats[idx].ticks_engaged += 1;
+
+            // Spent on both sides, the fighting dies down by mutual consent
+            let bout_ticks = self
+                .tick
+                .saturating_sub(self.active_combats[idx].pace_since);
+            if self.engagement_exhaustion(attacker_id, defender_id) >= LULL_EXHAUSTION
+                || bout_ticks >= MAX_BOUT_TICKS
+            {
+                self.active_combats[idx].set_pace(CombatPace::Lull, self.tick);
+                for unit in [
+                    self.friendly_army.get_unit_mut(attacker_id),
+                    self.enemy_army.get_unit_mut(defender_id),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    if unit.stance == UnitStance::Engaged {
+                        unit.stance = UnitStance::Formed;
+                    }
+                }
+                events.push(
+                    BattleEventType::LullBegan {
+                        units: (attacker_id, defender_id),
+                    },
+                    "The fighting dies down as both sides draw breath".to_string(),
+                    self.tick,
+                );
+            }
+        }
+    }
+
+    /// Whichever of a newly engaged pair came in at the gallop strikes with
+    /// its charge; both charges are spent either way
+    fn strike_charges(
+        &mut self,
+        friendly_id: UnitId,
+        enemy_id: UnitId,
+        events: &mut BattleEventLog,
+    ) {
+        let (Some(friendly), Some(enemy)) = (
+            self.friendly_army.get_unit(friendly_id).cloned(),
+            self.enemy_army.get_unit(enemy_id).cloned(),
+        ) else {
+            return;
+        };
+        let friendly_charge = resolve_charge(&friendly, &enemy);
+        // Two charges meeting head on are one clash, not two
+        let enemy_charge = match friendly_charge {
+            Some(ChargeOutcome::Countercharged) => None,
+            _ => resolve_charge(&enemy, &friendly),
+        };
+
+        for (charger_id, target_id, outcome) in [
+            (friendly_id, enemy_id, friendly_charge),
+            (enemy_id, friendly_id, enemy_charge),
+        ] {
+            match outcome {
+                None => {}
+                Some(ChargeOutcome::Shock(shock)) => {
+                    if let Some(target) = self.get_unit_mut(target_id) {
+                        target.casualties += shock.immediate_casualties;
+                        target.stress += shock.stress_spike;
+                    }
+                    events.push(
+                        BattleEventType::ChargeStruckHome {
+                            unit_id: charger_id,
+                        },
+                        "The charge strikes home".to_string(),
+                        self.tick,
+                    );
+                }
+                Some(ChargeOutcome::Countercharged) => {
+                    events.push(
+                        BattleEventType::ChargeCountered {
+                            unit_id: charger_id,
+                        },
+                        "The charge is met by a countercharge".to_string(),
+                        self.tick,
+                    );
+                }
+                Some(ChargeOutcome::Repulsed { casualties, stress }) => {
+                    let tick = self.tick;
+                    if let Some(charger) = self.get_unit_mut(charger_id) {
+                        charger.casualties += casualties;
+                        charger.stress += stress;
+                        fall_into_disorder(charger, tick);
+                    }
+                    events.push(
+                        BattleEventType::ChargeRepulsed {
+                            unit_id: charger_id,
+                        },
+                        "The charge breaks on a braced line".to_string(),
+                        self.tick,
+                    );
                 }
             }
         }
+
+        for unit_id in [friendly_id, enemy_id] {
+            if let Some(unit) = self.get_unit_mut(unit_id) {
+                unit.charge_run = 0;
+            }
+        }
+    }
+
+    /// Mean exhaustion (fatigue, and half of stress) of an engagement's two units
+    fn engagement_exhaustion(&self, attacker_id: UnitId, defender_id: UnitId) -> f32 {
+        let exhaustion = |unit: Option<&crate::battle::units::BattleUnit>| {
+            unit.map(|u| u.fatigue + u.stress * 0.5).unwrap_or(0.0)
+        };
+        (exhaustion(self.friendly_army.get_unit(attacker_id))
+            + exhaustion(self.enemy_army.get_unit(defender_id)))
+            / 2.0
+    }
+
+    /// One tick of a lull: both sides recover, and the fighting resumes once
+    /// they have (or once the lull has gone on long enough)
+    fn tend_lull(&mut self, idx: usize, events: &mut BattleEventLog) {
+        let ActiveCombat {
+            attacker_unit,
+            defender_unit,
+            pace_since,
+            ..
+        } = self.active_combats[idx];
+
+        for unit in [
+            self.friendly_army.get_unit_mut(attacker_unit),
+            self.enemy_army.get_unit_mut(defender_unit),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            // Spent stamina is not rested off
+            let floor = unit.fatigue_floor().min(unit.fatigue);
+            unit.fatigue = (unit.fatigue - FATIGUE_RECOVERY_RATE).max(floor);
+            unit.stress = (unit.stress - LULL_STRESS_RECOVERY).max(0.0);
+        }
+
+        let lull_ticks = self.tick.saturating_sub(pace_since);
+        let recovered = lull_ticks >= MIN_LULL_TICKS
+            && self.engagement_exhaustion(attacker_unit, defender_unit) <= RESUME_EXHAUSTION;
+        if recovered || lull_ticks >= MAX_LULL_TICKS {
+            self.active_combats[idx].set_pace(CombatPace::Bout, self.tick);
+            events.push(
+                BattleEventType::FightingResumed {
+                    units: (attacker_unit, defender_unit),
+                },
+                "The fighting flares up again".to_string(),
+                self.tick,
+            );
+        }
     }
 
     fn phase_morale(&mut self, events: &mut BattleEventLog) {
@@ -837,7 +1405,8 @@ impl BattleState {
 
         // Check morale for all units
         for formation in &mut self.friendly_army.formations {
-            for unit in &mut formation.units {
+            let shares = contagion_shares(formation);
+            for (unit, share) in formation.units.iter_mut().zip(shares) {
                 // Auto-rout destroyed units (0 effective strength)
                 if unit.effective_strength() == 0 && !unit.is_broken() {
                     process_morale_break(unit);
@@ -855,7 +1424,8 @@ impl BattleState {
                     .filter(|pos| unit.position.distance(pos) <= 2)
                     .count();
 
-                let contagion = calculate_contagion_stress(unit, nearby_routing);
+                // Officers nearby keep the panic from spreading
+                let contagion = calculate_contagion_stress(unit, nearby_routing) * share;
                 if contagion > 0.0 {
                     apply_stress(unit, contagion);
                 }
@@ -884,7 +1454,8 @@ impl BattleState {
             .collect();
 
         for formation in &mut self.enemy_army.formations {
-            for unit in &mut formation.units {
+            let shares = contagion_shares(formation);
+            for (unit, share) in formation.units.iter_mut().zip(shares) {
                 // Auto-rout destroyed units (0 effective strength)
                 if unit.effective_strength() == 0 && !unit.is_broken() {
                     process_morale_break(unit);
@@ -901,7 +1472,8 @@ impl BattleState {
                     .filter(|pos| unit.position.distance(pos) <= 2)
                     .count();
 
-                let contagion = calculate_contagion_stress(unit, nearby_routing);
+                // Officers nearby keep the panic from spreading
+                let contagion = calculate_contagion_stress(unit, nearby_routing) * share;
                 if contagion > 0.0 {
                     apply_stress(unit, contagion);
                 }
@@ -936,7 +1508,12 @@ impl BattleState {
 
         for (formation_idx, formation) in self.friendly_army.formations.iter_mut().enumerate() {
             let commander_pos = commander_positions[formation_idx];
-            for unit in &mut formation.units {
+            let rallying_officers: Vec<Option<f32>> = formation
+                .units
+                .iter()
+                .map(|u| rally_skill_near(formation, u.position))
+                .collect();
+            for (unit, officer_rally) in formation.units.iter_mut().zip(rallying_officers) {
                 // Check Rallying → Formed transition
                 if matches!(unit.stance, UnitStance::Rallying) {
                     if let Some(rally_start) = unit.rallying_since {
@@ -956,8 +1533,9 @@ impl BattleState {
                         .iter()
                         .any(|pos| unit.position.distance(pos) <= 3);
                     let is_near_leader = unit.position.distance(&commander_pos) <= 3;
+                    let leader_rally = officer_rally.or(is_near_leader.then_some(0.0));
 
-                    let result = check_rally(unit, is_near_enemy, is_near_leader);
+                    let result = check_rally(unit, is_near_enemy, leader_rally);
                     if result.rallies {
                         unit.stance = UnitStance::Rallying;
                         unit.rallying_since = Some(self.tick);
@@ -973,7 +1551,183 @@ impl BattleState {
         }
     }
 
-    fn phase_post_tick(&mut self, _events: &mut BattleEventLog) {
+    fn phase_siege(&mut self, events: &mut BattleEventLog, rng: &mut impl Rng) {
+        let enemy_units = units_of(&self.enemy_army);
+        let friendly = run_siege(&mut self.map, &mut self.friendly_army, &enemy_units);
+        let friendly_units = units_of(&self.friendly_army);
+        let enemy = run_siege(&mut self.map, &mut self.enemy_army, &friendly_units);
+
+        for coord in friendly.breached.iter().chain(&enemy.breached) {
+            let what = match self.map.fortification(*coord).map(|f| f.kind) {
+                Some(FortificationKind::Gate) => "Gate",
+                _ => "Wall",
+            };
+            events.push(
+                BattleEventType::FortificationBreached { coord: *coord },
+                format!("{} breached at ({}, {})", what, coord.q, coord.r),
+                self.tick,
+            );
+        }
+        for unit_id in friendly.scaled.iter().chain(&enemy.scaled) {
+            events.push(
+                BattleEventType::WallScaled { unit_id: *unit_id },
+                "Stormers gain the top of the wall".to_string(),
+                self.tick,
+            );
+        }
+
+        // Underneath, the sappers dig and the defenders listen
+        let enemy_units = units_of(&self.enemy_army);
+        let friendly_mining =
+            run_mining(&mut self.map, &mut self.friendly_army, &enemy_units, || {
+                rng.gen()
+            });
+        let friendly_units = units_of(&self.friendly_army);
+        let enemy_mining = run_mining(&mut self.map, &mut self.enemy_army, &friendly_units, || {
+            rng.gen()
+        });
+
+        for (mining, defenders) in [
+            (&friendly_mining, &mut self.enemy_army),
+            (&enemy_mining, &mut self.friendly_army),
+        ] {
+            for coord in &mining.sprung {
+                for unit in defenders
+                    .formations
+                    .iter_mut()
+                    .flat_map(|f| f.units.iter_mut())
+                {
+                    if unit.position.distance(coord) <= 1 {
+                        unit.casualties += MINE_COLLAPSE_LOSSES;
+                        unit.stress += MINE_COLLAPSE_STRESS;
+                    }
+                }
+                events.push(
+                    BattleEventType::MineSprung { coord: *coord },
+                    format!("A mine brings the wall down at ({}, {})", coord.q, coord.r),
+                    self.tick,
+                );
+            }
+            for coord in &mining.heard {
+                events.push(
+                    BattleEventType::DiggingHeard { coord: *coord },
+                    format!("Listeners hear digging under ({}, {})", coord.q, coord.r),
+                    self.tick,
+                );
+            }
+            for unit_id in &mining.broken_into {
+                events.push(
+                    BattleEventType::SappersBrokenInto { unit_id: *unit_id },
+                    "A countermine breaks into the sappers' tunnel".to_string(),
+                    self.tick,
+                );
+            }
+        }
+    }
+
+    fn phase_reinforcements(&mut self, events: &mut BattleEventLog) {
+        let mut occupied: std::collections::HashSet<BattleHexCoord> =
+            [&self.friendly_army, &self.enemy_army]
+                .into_iter()
+                .flat_map(|army| army.formations.iter().flat_map(|f| f.units.iter()))
+                .map(|u| u.position)
+                .collect();
+        for friendly in [true, false] {
+            let (pending, army, plan) = if friendly {
+                (
+                    &mut self.friendly_reinforcements,
+                    &mut self.friendly_army,
+                    &self.friendly_plan,
+                )
+            } else {
+                (
+                    &mut self.enemy_reinforcements,
+                    &mut self.enemy_army,
+                    &self.enemy_plan,
+                )
+            };
+            for arrival in
+                arrive_reinforcements(pending, army, &self.map, plan, &mut occupied, self.tick)
+            {
+                events.push(
+                    BattleEventType::ReinforcementsArrived {
+                        formation: arrival.formation,
+                    },
+                    format!(
+                        "{} arrived from the {:?} edge ({} units)",
+                        if arrival.name.is_empty() {
+                            "Reinforcements"
+                        } else {
+                            &arrival.name
+                        },
+                        arrival.edge,
+                        arrival.units
+                    ),
+                    self.tick,
+                );
+            }
+        }
+    }
+
+    fn phase_post_tick(&mut self, events: &mut BattleEventLog, rng: &mut impl Rng) {
+        // Officers fall among their units' casualties, shaking their formation
+        for (army, command) in [
+            (&mut self.friendly_army, &mut self.friendly_command),
+            (&mut self.enemy_army, &mut self.enemy_command),
+        ] {
+            for fallen in officer_losses(army, || rng.gen()) {
+                let stress = calculate_officer_death_stress(true, true);
+                if let Some(formation) = army
+                    .formations
+                    .iter_mut()
+                    .find(|f| f.id == fallen.formation)
+                {
+                    for unit in &mut formation.units {
+                        apply_stress(unit, stress);
+                    }
+                }
+                command.surprise();
+                events.push(
+                    BattleEventType::CommanderKilled {
+                        entity_id: fallen.entity,
+                    },
+                    format!("{} has fallen", fallen.name),
+                    self.tick,
+                );
+            }
+        }
+
+        // Commanders take in the tick's losses and shocks
+        let broke: Vec<UnitId> = events
+            .events
+            .iter()
+            .filter_map(|e| match e.event_type {
+                BattleEventType::UnitBroke { unit_id } => Some(unit_id),
+                _ => None,
+            })
+            .collect();
+        for (army, command) in [
+            (&self.friendly_army, &mut self.friendly_command),
+            (&self.enemy_army, &mut self.enemy_command),
+        ] {
+            let was_shaken = command.is_shaken();
+            for &unit_id in &broke {
+                if army.get_unit(unit_id).is_some() {
+                    command.surprise();
+                }
+            }
+            command.update(army);
+            if command.is_shaken() && !was_shaken {
+                events.push(
+                    BattleEventType::CommanderShaken {
+                        entity_id: army.commander,
+                    },
+                    "Commander is shaken; orders come slowly".to_string(),
+                    self.tick,
+                );
+            }
+        }
+
         // Check battle end
         if let Some(outcome) = check_battle_end(self) {
             self.end_battle(outcome);
@@ -984,17 +1738,77 @@ impl BattleState {
     }
 }
 
+/// Whether an order is addressed to one of the army's units or formations
+fn addresses(army: &Army, target: &OrderTarget) -> bool {
+    match target {
+        OrderTarget::Unit(unit_id) => army.get_unit(*unit_id).is_some(),
+        OrderTarget::Formation(formation_id) => {
+            army.formations.iter().any(|f| f.id == *formation_id)
+        }
+    }
+}
+
+/// Snapshot of every unit in an army
+fn units_of(army: &Army) -> Vec<BattleUnit> {
+    army.formations
+        .iter()
+        .flat_map(|f| f.units.iter().cloned())
+        .collect()
+}
+
+/// Share of spreading panic that reaches each of a formation's units
+fn contagion_shares(formation: &BattleFormation) -> Vec<f32> {
+    formation
+        .units
+        .iter()
+        .map(|u| contagion_share(formation, u.position))
+        .collect()
+}
+
+/// Send an order from the army's HQ by courier, once the commander has it ready
+fn send_by_courier(
+    army: &mut Army,
+    couriers: &mut CourierSystem,
+    command: &CommandStress,
+    order: Order,
+) -> Option<CourierId> {
+    // Get destination based on order target
+    let destination = match &order.target {
+        OrderTarget::Unit(unit_id) => army.get_unit(*unit_id).map(|u| u.position),
+        OrderTarget::Formation(formation_id) => army
+            .formations
+            .iter()
+            .find(|f| f.id == *formation_id)
+            .and_then(|f| f.commander_position()),
+    }?;
+
+    // Get courier from pool, with an escort if one is free
+    let courier_entity = army.courier_pool.pop()?;
+    let escort = free_escort(army, couriers);
+    let id = couriers.dispatch_after(
+        order_delay(army, &order.target, command.order_delay()),
+        courier_entity,
+        order,
+        army.hq_position,
+        destination,
+    );
+    if let Some(courier) = couriers.get_courier_mut(id) {
+        courier.escort = escort;
+    }
+    Some(id)
+}
+
 /// Check if battle should end
 pub fn check_battle_end(state: &BattleState) -> Option<BattleOutcome> {
     let friendly_effective = state.friendly_army.effective_strength();
     let enemy_effective = state.enemy_army.effective_strength();
 
-    // Check for army destruction
-    if enemy_effective == 0 {
+    // Check for army destruction (not while more of it is on the way)
+    if enemy_effective == 0 && state.enemy_reinforcements.is_empty() {
         return Some(BattleOutcome::DecisiveVictory);
     }
 
-    if friendly_effective == 0 {
+    if friendly_effective == 0 && state.friendly_reinforcements.is_empty() {
         return Some(BattleOutcome::DecisiveDefeat);
     }
 
@@ -1122,6 +1936,104 @@ mod tests {
         assert_eq!(outcome, Some(BattleOutcome::DecisiveVictory));
     }
 
+    #[test]
+    fn test_rammers_breach_the_gate_and_march_in() {
+        use crate::battle::hex::BattleHexCoord;
+        use crate::battle::planning::{Waypoint, WaypointBehavior, WaypointPlan};
+        use crate::battle::siege::{raise_curtain_wall, SiegeAssault};
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+
+        let mut map = BattleMap::new(20, 20);
+        raise_curtain_wall(&mut map, 10, 10);
+        let gate = BattleHexCoord::new(10, 10);
+
+        let army_with = |unit_type: UnitType, position: BattleHexCoord| {
+            let mut army = Army::new(ArmyId::new(), EntityId::new());
+            let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+            let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+            unit.elements.push(Element::new(vec![EntityId::new(); 50]));
+            unit.position = position;
+            formation.units.push(unit);
+            army.formations.push(formation);
+            army
+        };
+        let mut friendly = army_with(UnitType::Engineers, BattleHexCoord::new(7, 10));
+        let enemy = army_with(UnitType::Infantry, BattleHexCoord::new(18, 2));
+        let rammers = &mut friendly.formations[0].units[0];
+        rammers.assault = Some(SiegeAssault::Ram(gate));
+        rammers.stance = UnitStance::Moving;
+        let mut plan = WaypointPlan::new(rammers.id);
+        plan.add_waypoint(Waypoint::new(gate, WaypointBehavior::AttackFrom));
+
+        let mut state = BattleState::new(map, friendly, enemy);
+        state.friendly_plan.waypoint_plans.push(plan);
+        state.start_battle();
+
+        let mut breached = false;
+        for _ in 0..120 {
+            let events = state.run_tick();
+            breached |= events.events.iter().any(|e| {
+                matches!(e.event_type, BattleEventType::FortificationBreached { coord } if coord == gate)
+            });
+            if state.friendly_army.formations[0].units[0].position == gate {
+                break;
+            }
+        }
+        assert!(breached);
+        assert!(!state.map.is_barred(gate));
+        assert_eq!(state.friendly_army.formations[0].units[0].position, gate);
+    }
+
+    #[test]
+    fn test_reinforcements_march_on_and_are_seen() {
+        use crate::battle::hex::BattleHexCoord;
+        use crate::battle::reinforcements::{ArrivalTrigger, MapEdge};
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+
+        let map = BattleMap::new(20, 20);
+        let friendly = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        unit.elements.push(Element::new(vec![EntityId::new(); 50]));
+        unit.position = BattleHexCoord::new(10, 17);
+        formation.units.push(unit);
+        enemy.formations.push(formation);
+
+        // The whole friendly army is still on the road
+        let mut column = BattleFormation::new(FormationId::new(), EntityId::new());
+        column.name = "Northern host".into();
+        let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        unit.elements.push(Element::new(vec![EntityId::new(); 50]));
+        column.units.push(unit);
+
+        let mut state = BattleState::new(map, friendly, enemy);
+        state.schedule_friendly_reinforcement(Reinforcement::new(
+            column,
+            MapEdge::South,
+            ArrivalTrigger::AtTick(3),
+        ));
+        state.start_battle();
+        assert_eq!(check_battle_end(&state), None);
+
+        let mut arrived = false;
+        for _ in 0..5 {
+            let events = state.run_tick();
+            arrived |= events
+                .events
+                .iter()
+                .any(|e| matches!(e.event_type, BattleEventType::ReinforcementsArrived { .. }));
+        }
+        assert!(arrived);
+        assert!(state.friendly_reinforcements.is_empty());
+        assert_eq!(state.friendly_army.formations.len(), 1);
+        let position = state.friendly_army.formations[0].units[0].position;
+        assert_eq!(position.r, 19);
+        assert!(state.enemy_visibility.is_visible(position));
+    }
+
     #[test]
     fn test_full_tick_advances_state() {
         use crate::battle::hex::BattleHexCoord;
@@ -1338,6 +2250,114 @@ mod tests {
         assert_eq!(wp_plan.unwrap().waypoints[0].position, destination);
     }
 
+    #[test]
+    fn test_stressed_commander_is_slow_to_send_orders() {
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+
+        let ticks_to_deliver = |stress: f32| {
+            let mut friendly = Army::new(ArmyId::new(), EntityId::new());
+            friendly.hq_position = BattleHexCoord::new(2, 2);
+            friendly.courier_pool.push(EntityId::new());
+            let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+            let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+            unit.elements.push(Element::new(vec![EntityId::new(); 50]));
+            unit.position = BattleHexCoord::new(5, 5);
+            let unit_id = unit.id;
+            formation.units.push(unit);
+            friendly.formations.push(formation);
+
+            let mut enemy = Army::new(ArmyId::new(), EntityId::new());
+            let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+            let mut enemy_unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+            enemy_unit
+                .elements
+                .push(Element::new(vec![EntityId::new(); 50]));
+            enemy_unit.position = BattleHexCoord::new(18, 18);
+            enemy_formation.units.push(enemy_unit);
+            enemy.formations.push(enemy_formation);
+
+            let mut state = BattleState::new(BattleMap::new(20, 20), friendly, enemy);
+            state.start_battle();
+            state.friendly_command.stress = stress;
+            state
+                .issue_order(Order::move_to(unit_id, BattleHexCoord::new(8, 8)))
+                .unwrap();
+
+            (1..100)
+                .find(|_| {
+                    state.run_tick();
+                    state.friendly_plan.get_waypoint_plan(unit_id).is_some()
+                })
+                .unwrap()
+        };
+
+        let calm = ticks_to_deliver(0.0);
+        let shaken = ticks_to_deliver(0.8);
+        assert_eq!(shaken - calm, 16);
+    }
+
+    #[test]
+    fn test_escorted_order_sends_scouts_out_until_cavalry_drives_them_off() {
+        use crate::battle::courier::OrderType;
+        use crate::battle::hex::HexDirection;
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+
+        let unit_at = |unit_type, q, r| {
+            let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+            unit.elements.push(Element::new(vec![EntityId::new(); 20]));
+            unit.position = BattleHexCoord::new(q, r);
+            unit
+        };
+        let scouts = unit_at(UnitType::MountedScouts, 4, 10);
+        let escort = unit_at(UnitType::CourierEscort, 2, 10);
+        let (scouts_id, escort_id) = (scouts.id, escort.id);
+        let mut friendly = Army::new(ArmyId::new(), EntityId::new());
+        friendly.hq_position = BattleHexCoord::new(2, 10);
+        friendly.courier_pool.push(EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        formation.units = vec![scouts, escort];
+        friendly.formations.push(formation);
+
+        let mut enemy = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        enemy_formation
+            .units
+            .push(unit_at(UnitType::Cavalry, 35, 10));
+        enemy.formations.push(enemy_formation);
+
+        let mut state = BattleState::new(BattleMap::new(40, 20), friendly, enemy);
+        state.start_battle();
+        let order = Order::new(
+            OrderType::Reconnoiter(HexDirection::East),
+            OrderTarget::Unit(scouts_id),
+            0,
+        );
+        let courier = state.issue_order(order).unwrap();
+        let escort = state.courier_system.get_courier(courier).unwrap().escort;
+        assert_eq!(escort, Some(escort_id));
+
+        (1..100)
+            .find(|_| {
+                state.run_tick();
+                state.get_unit(scouts_id).unwrap().scouting.is_some()
+            })
+            .unwrap();
+        state.run_tick();
+        let far_east = BattleHexCoord::new(20, 10);
+        assert!(state.friendly_visibility.is_visible(far_east));
+
+        // Enemy cavalry near the scouts' riders sends them back
+        state.enemy_army.formations[0].units[0].position = BattleHexCoord::new(11, 10);
+        let events = state.run_tick();
+        assert!(events.events.iter().any(|e| matches!(
+            e.event_type,
+            BattleEventType::ScoutsDrivenOff { unit_id } if unit_id == scouts_id
+        )));
+        assert!(!state.friendly_visibility.is_visible(far_east));
+    }
+
     #[test]
     fn test_contingency_triggers_in_phase_pre_tick() {
         use crate::battle::hex::BattleHexCoord;
@@ -1740,6 +2760,61 @@ mod tests {
         let _ = any_interception_event; // Acknowledge the variable was used for tracking
     }
 
+    #[test]
+    fn test_fallen_officer_shakes_formation_and_hq() {
+        use crate::battle::constants::OFFICER_DEATH_STRESS;
+        use crate::battle::hex::BattleHexCoord;
+        use crate::battle::officers::{commander_fallen, Officer};
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+        use crate::skills::ChunkLibrary;
+
+        let map = BattleMap::new(20, 20);
+        let commander = EntityId::new();
+        let mut friendly = Army::new(ArmyId::new(), commander);
+        let mut formation = BattleFormation::new(FormationId::new(), commander);
+        let mut guard = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        guard.elements.push(Element::new(vec![EntityId::new(); 10]));
+        guard.position = BattleHexCoord::new(5, 5);
+        guard.casualties = 10; // Cut down to the last man
+        let mut line = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        line.elements.push(Element::new(vec![EntityId::new(); 50]));
+        line.position = BattleHexCoord::new(10, 5);
+        let line_id = line.id;
+        formation.officers.push(Officer::new(
+            commander,
+            "Aldric".into(),
+            guard.id,
+            &ChunkLibrary::new(),
+        ));
+        formation.units.push(guard);
+        formation.units.push(line);
+        friendly.formations.push(formation);
+
+        let mut enemy = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut enemy_unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        enemy_unit
+            .elements
+            .push(Element::new(vec![EntityId::new(); 50]));
+        enemy_unit.position = BattleHexCoord::new(18, 18);
+        enemy_formation.units.push(enemy_unit);
+        enemy.formations.push(enemy_formation);
+
+        let mut state = BattleState::new(map, friendly, enemy);
+        state.start_battle();
+        let events = state.run_tick();
+
+        assert!(events.events.iter().any(|e| matches!(
+            e.event_type,
+            BattleEventType::CommanderKilled { entity_id } if entity_id == commander
+        )));
+        let line = state.friendly_army.get_unit(line_id).unwrap();
+        assert!(line.stress >= OFFICER_DEATH_STRESS);
+        assert!(state.friendly_command.stress > 0.0);
+        assert!(commander_fallen(&state.friendly_army));
+    }
+
     #[test]
     fn test_rallying_to_formed_transition() {
         use crate::battle::constants::RALLY_TICKS_REQUIRED;
@@ -1810,4 +2885,222 @@ mod tests {
             "Cohesion should be restored after rallying"
         );
     }
+
+    #[test]
+    fn test_engagement_pauses_for_lulls() {
+        use crate::battle::hex::BattleHexCoord;
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+
+        let mut friendly = Army::new(ArmyId::new(), EntityId::new());
+        let mut friendly_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut friendly_unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        friendly_unit
+            .elements
+            .push(Element::new(vec![EntityId::new(); 100]));
+        friendly_unit.position = BattleHexCoord::new(10, 15);
+        friendly_formation.units.push(friendly_unit);
+        friendly.formations.push(friendly_formation);
+
+        let mut enemy = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut enemy_unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        enemy_unit
+            .elements
+            .push(Element::new(vec![EntityId::new(); 100]));
+        enemy_unit.position = BattleHexCoord::new(11, 15); // Adjacent
+        enemy_formation.units.push(enemy_unit);
+        enemy.formations.push(enemy_formation);
+
+        let mut state = BattleState::new(BattleMap::new(30, 30), friendly, enemy);
+        state.start_battle();
+
+        // Fight until both sides are spent
+        let mut lull_began = false;
+        for _ in 0..MAX_BOUT_TICKS + 1 {
+            let events = state.run_tick();
+            if events
+                .events
+                .iter()
+                .any(|e| matches!(e.event_type, BattleEventType::LullBegan { .. }))
+            {
+                lull_began = true;
+                break;
+            }
+        }
+        assert!(lull_began, "Fighting should die down");
+        assert_eq!(state.active_combats[0].pace, CombatPace::Lull);
+        let unit = &state.friendly_army.formations[0].units[0];
+        assert!(!unit.is_engaged(), "A lull frees units to withdraw");
+
+        // Nobody falls in a lull, and both sides get their breath back
+        let casualties = unit.casualties;
+        let fatigue = unit.fatigue;
+        state.run_tick();
+        let unit = &state.friendly_army.formations[0].units[0];
+        assert_eq!(unit.casualties, casualties);
+        assert!(unit.fatigue < fatigue);
+
+        // Until it flares up again
+        let mut resumed = false;
+        for _ in 0..MAX_LULL_TICKS {
+            let events = state.run_tick();
+            if events
+                .events
+                .iter()
+                .any(|e| matches!(e.event_type, BattleEventType::FightingResumed { .. }))
+            {
+                resumed = true;
+                break;
+            }
+        }
+        assert!(resumed, "Fighting should resume after the lull");
+        assert_eq!(state.active_combats[0].bouts, 2);
+    }
+
+    #[test]
+    fn test_skirmishers_slip_away_from_melee() {
+        use crate::battle::hex::BattleHexCoord;
+        use crate::battle::planning::EngagementRule;
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+
+        let mut friendly = Army::new(ArmyId::new(), EntityId::new());
+        let mut friendly_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut archers = BattleUnit::new(UnitId::new(), UnitType::Archers);
+        archers
+            .elements
+            .push(Element::new(vec![EntityId::new(); 40]));
+        archers.position = BattleHexCoord::new(10, 15);
+        let archer_id = archers.id;
+        friendly_formation.units.push(archers);
+        friendly.formations.push(friendly_formation);
+
+        let mut enemy = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut enemy_unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        enemy_unit
+            .elements
+            .push(Element::new(vec![EntityId::new(); 100]));
+        enemy_unit.position = BattleHexCoord::new(11, 15); // Adjacent
+        enemy_formation.units.push(enemy_unit);
+        enemy.formations.push(enemy_formation);
+
+        let mut state = BattleState::new(BattleMap::new(30, 30), friendly, enemy);
+        state
+            .friendly_plan
+            .engagement_rules
+            .push((archer_id, EngagementRule::Skirmish));
+        state.start_battle();
+
+        let events = state.run_tick();
+        assert!(events
+            .events
+            .iter()
+            .any(|e| matches!(e.event_type, BattleEventType::SkirmishersFellBack { .. })));
+        let archers = state.friendly_army.get_unit(archer_id).unwrap();
+        assert_eq!(archers.stance, UnitStance::Evading);
+        assert!(archers.position.distance(&BattleHexCoord::new(11, 15)) > 1);
+        assert!(state.active_combats.is_empty(), "No melee was joined");
+    }
+
+    #[test]
+    fn test_formed_archers_fire_before_contact() {
+        use crate::battle::constants::QUIVER_VOLLEYS;
+        use crate::battle::hex::BattleHexCoord;
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+
+        let mut friendly = Army::new(ArmyId::new(), EntityId::new());
+        let mut friendly_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut archers = BattleUnit::new(UnitId::new(), UnitType::Archers);
+        archers
+            .elements
+            .push(Element::new(vec![EntityId::new(); 40]));
+        archers.position = BattleHexCoord::new(10, 15);
+        let archer_id = archers.id;
+        friendly_formation.units.push(archers);
+        friendly.formations.push(friendly_formation);
+
+        let mut enemy = Army::new(ArmyId::new(), EntityId::new());
+        let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut enemy_unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        enemy_unit
+            .elements
+            .push(Element::new(vec![EntityId::new(); 100]));
+        enemy_unit.position = BattleHexCoord::new(16, 15); // Well within bow range
+        let enemy_id = enemy_unit.id;
+        enemy_formation.units.push(enemy_unit);
+        enemy.formations.push(enemy_formation);
+
+        let mut state = BattleState::new(BattleMap::new(30, 30), friendly, enemy);
+        state.start_battle();
+        for _ in 0..20 {
+            state.run_tick();
+        }
+
+        let archers = state.friendly_army.get_unit(archer_id).unwrap();
+        assert!(archers.volleys_loosed > 0);
+        assert!(archers.ammo() < QUIVER_VOLLEYS);
+        let target = state.enemy_army.get_unit(enemy_id).unwrap();
+        assert!(target.stress > 0.0);
+        assert!(state.active_combats.is_empty(), "No melee was joined");
+    }
+
+    #[test]
+    fn test_charge_strikes_home_or_breaks_on_spears() {
+        use crate::battle::constants::CHARGE_MIN_RUN;
+        use crate::battle::hex::BattleHexCoord;
+        use crate::battle::unit_type::UnitType;
+        use crate::battle::units::{BattleFormation, BattleUnit, Element, FormationId};
+
+        // Knights arriving at the gallop next to a waiting enemy unit
+        let charge_into = |target_type: UnitType| {
+            let mut friendly = Army::new(ArmyId::new(), EntityId::new());
+            let mut friendly_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+            let mut knights = BattleUnit::new(UnitId::new(), UnitType::HeavyCavalry);
+            knights
+                .elements
+                .push(Element::new(vec![EntityId::new(); 40]));
+            knights.position = BattleHexCoord::new(10, 15);
+            knights.stance = UnitStance::Charging;
+            knights.charge_run = CHARGE_MIN_RUN;
+            friendly_formation.units.push(knights);
+            friendly.formations.push(friendly_formation);
+
+            let mut enemy = Army::new(ArmyId::new(), EntityId::new());
+            let mut enemy_formation = BattleFormation::new(FormationId::new(), EntityId::new());
+            let mut target = BattleUnit::new(UnitId::new(), target_type);
+            target
+                .elements
+                .push(Element::new(vec![EntityId::new(); 100]));
+            target.position = BattleHexCoord::new(11, 15);
+            enemy_formation.units.push(target);
+            enemy.formations.push(enemy_formation);
+
+            let mut state = BattleState::new(BattleMap::new(30, 30), friendly, enemy);
+            state.start_battle();
+            let events = state.run_tick();
+            (state, events)
+        };
+
+        let (state, events) = charge_into(UnitType::Levy);
+        assert!(events
+            .events
+            .iter()
+            .any(|e| matches!(e.event_type, BattleEventType::ChargeStruckHome { .. })));
+        let knights = &state.friendly_army.formations[0].units[0];
+        assert_eq!(knights.charge_run, 0);
+        assert_eq!(knights.stance, UnitStance::Engaged);
+        assert!(state.enemy_army.formations[0].units[0].casualties > 0);
+
+        let (state, events) = charge_into(UnitType::Spearmen);
+        assert!(events
+            .events
+            .iter()
+            .any(|e| matches!(e.event_type, BattleEventType::ChargeRepulsed { .. })));
+        let knights = &state.friendly_army.formations[0].units[0];
+        assert_eq!(knights.stance, UnitStance::Disordered);
+        assert!(knights.casualties > 0);
+    }
 }
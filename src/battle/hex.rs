@@ -5,7 +5,9 @@
 use serde::{Deserialize, Serialize};
 
 /// Axial hex coordinate for battle map
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default,
+)]
 pub struct BattleHexCoord {
     pub q: i32,
     pub r: i32,
@@ -10,19 +10,29 @@
 
 pub mod ai;
 pub mod battle_map;
+pub mod chronicle;
+pub mod command_stress;
 pub mod constants;
 pub mod courier;
+pub mod elevation;
 pub mod engagement;
 pub mod execution;
 pub mod formation_layout;
 pub mod hex;
 pub mod morale;
 pub mod movement;
+pub mod officers;
 pub mod orders;
 pub mod pathfinding;
 pub mod planning;
-// ranged.rs - orphaned, depends on unimplemented combat::weapons
+pub mod ranged;
+pub mod recon;
+pub mod reinforcements;
 pub mod resolution;
+pub mod save;
+pub mod siege;
+pub mod skirmish;
+pub mod supply;
 pub mod terrain;
 pub mod triggers;
 pub mod unit_type;
@@ -31,10 +41,17 @@ pub mod visibility;
 
 // Re-exports for convenient access
 pub use battle_map::{BattleHex, BattleMap, Objective, VisibilityState};
+pub use chronicle::{
+    BattleChronicle, CasualtyLine, ChroniclePhase, PhaseKind, Side, TurningPoint, UnitFate,
+};
+pub use command_stress::CommandStress;
 pub use constants::*;
 pub use courier::{
     CourierId, CourierInFlight, CourierStatus, CourierSystem, Order, OrderTarget, OrderType,
 };
+pub use elevation::{
+    climb, high_ground_near, raise_hill, raise_ridgeline, uphill_cost, uphill_penalty,
+};
 pub use engagement::{
     detect_engagement, find_all_engagements, is_flanked, is_surrounded, should_initiate_combat,
     PotentialEngagement,
@@ -44,7 +61,7 @@ pub use formation_layout::{
 };
 pub use execution::{
     check_battle_end, ActiveCombat, BattleEvent, BattleEventLog, BattleEventType, BattleOutcome,
-    BattlePhase, BattleState, RoutingUnit,
+    BattlePhase, BattleState, CombatPace, RoutingUnit,
 };
 pub use hex::{BattleHexCoord, HexDirection};
 pub use morale::{
@@ -52,18 +69,43 @@ pub use morale::{
     check_rally, process_morale_break, process_rally, MoraleCheckResult,
 };
 pub use movement::{advance_unit_movement, move_routing_unit, MovementResult};
+pub use officers::{
+    commander_fallen, contagion_share, officer_losses, order_delay, rally_skill_near,
+    FallenOfficer, Officer,
+};
 pub use orders::{apply_order, ApplyOrderResult};
-pub use pathfinding::{find_path, path_cost};
+pub use pathfinding::{find_assault_path, find_path, path_cost};
 pub use planning::{
     BattlePlan, Contingency, ContingencyResponse, ContingencyTrigger, EngagementRule, GoCode,
     GoCodeId, GoCodeTrigger, MovementPace, UnitDeployment, WaitCondition, Waypoint,
     WaypointBehavior, WaypointPlan,
 };
+pub use ranged::{
+    can_shoot, fire_target, max_range_hexes, min_range_hexes, resolve_unit_ranged_attack,
+    run_ranged_fire, unit_ranged_weapon, RangedAttackResult, RangedOutcome,
+};
+pub use recon::{drive_off_scouts, free_escort, interception_chance, scout_post};
+pub use reinforcements::{arrive_reinforcements, Arrival, ArrivalTrigger, MapEdge, Reinforcement};
 pub use resolution::{
-    determine_combat_lod, resolve_shock_attack, resolve_unit_combat, CombatLOD, ShockResult,
-    UnitCombatResult,
+    can_countercharge, determine_combat_lod, fall_into_disorder, has_charge_impetus,
+    reform_from_disorder, resolve_charge, resolve_shock_attack, resolve_unit_combat,
+    stops_charges, ChargeOutcome, CombatLOD, ShockResult, UnitCombatResult,
+};
+pub use save::{
+    battle_from_str, battle_to_string, load_battle, save_battle, BattleSaveError,
+    BATTLE_SCHEMA_VERSION,
+};
+pub use siege::{
+    can_storm, heard_mines, listening_chance, raise_curtain_wall, run_mining, run_siege, sap_rate,
+    siege_order, Mine, MiningOutcome, SiegeAssault, SiegeOutcome,
+};
+pub use skirmish::{
+    follows_doctrine, run_skirmishers, screen_position, SkirmishAction, SkirmishOutcome, Volley,
+};
+pub use supply::{
+    drain_stamina, out_of_ammo_stress, resupply_destination, run_resupply, supply_need, SupplyOutcome,
 };
-pub use terrain::{BattleTerrain, TerrainFeature};
+pub use terrain::{BattleTerrain, BreachState, Fortification, FortificationKind, TerrainFeature};
 pub use triggers::{
     evaluate_all_contingencies, evaluate_all_gocodes, evaluate_contingency_trigger,
     evaluate_gocode_trigger, TriggerResults, UnitPosition,
@@ -2,7 +2,7 @@
 //!
 //! Stress accumulates from combat. When it exceeds threshold, units break.
 
-use crate::battle::constants::{CONTAGION_STRESS, OFFICER_DEATH_STRESS};
+use crate::battle::constants::{CONTAGION_STRESS, OFFICER_DEATH_STRESS, OFFICER_RALLY_MARGIN};
 use crate::battle::units::{BattleUnit, UnitStance};
 
 /// Result of a morale check
@@ -41,11 +41,12 @@ pub fn check_morale_break(unit: &BattleUnit) -> MoraleCheckResult {
     result
 }
 
-/// Check if a routing unit can rally
+/// Check if a routing unit can rally; `leader_rally` is the rallying skill
+/// of a leader nearby, if there is one
 pub fn check_rally(
     unit: &BattleUnit,
     is_near_enemy: bool,
-    is_near_leader: bool,
+    leader_rally: Option<f32>,
 ) -> MoraleCheckResult {
     let mut result = MoraleCheckResult::default();
 
@@ -68,8 +69,9 @@ pub fn check_rally(
         result.stress_delta = -0.1;
     }
 
-    // Leader nearby helps
-    if is_near_leader && stress_after_recovery < rally_threshold + 0.2 {
+    // Leader nearby helps, a skilled one more
+    let leader_margin = leader_rally.map(|skill| 0.2 + OFFICER_RALLY_MARGIN * skill);
+    if leader_margin.is_some_and(|margin| stress_after_recovery < rally_threshold + margin) {
         result.rallies = true;
         result.stress_delta = -0.15;
     }
@@ -104,7 +106,8 @@ pub fn apply_stress(unit: &mut BattleUnit, stress_delta: f32) {
 /// Process morale break for a unit
 pub fn process_morale_break(unit: &mut BattleUnit) {
     unit.stance = UnitStance::Routing;
-    unit.cohesion = (unit.cohesion * 0.5).max(0.1);
+    unit.lose_cohesion(unit.cohesion * 0.5);
+    unit.cohesion = unit.cohesion.max(0.1);
 }
 
 /// Process rally for a unit
@@ -151,7 +154,7 @@ mod tests {
         unit.stance = UnitStance::Routing;
         unit.stress = 0.3; // Low enough to rally
 
-        let result = check_rally(&unit, false, false);
+        let result = check_rally(&unit, false, None);
 
         assert!(result.rallies);
     }
@@ -163,7 +166,7 @@ mod tests {
         unit.stance = UnitStance::Routing;
         unit.stress = 0.3;
 
-        let result = check_rally(&unit, true, false); // Near enemy
+        let result = check_rally(&unit, true, None); // Near enemy
 
         assert!(!result.rallies);
     }
@@ -198,10 +201,15 @@ mod tests {
         unit.stress = 0.6; // Too high for normal rally
 
         // Without leader: can't rally
-        assert!(!check_rally(&unit, false, false).rallies);
+        assert!(!check_rally(&unit, false, None).rallies);
 
         // With leader: can rally
-        assert!(check_rally(&unit, false, true).rallies);
+        assert!(check_rally(&unit, false, Some(0.0)).rallies);
+
+        // Deeper still, only a skilled rallier brings them back
+        unit.stress = 0.85;
+        assert!(!check_rally(&unit, false, Some(0.0)).rallies);
+        assert!(check_rally(&unit, false, Some(1.0)).rallies);
     }
 
     #[test]
@@ -254,6 +262,18 @@ mod tests {
         assert!(unit.cohesion < 0.5); // Cohesion reduced
     }
 
+    #[test]
+    fn test_drilled_unit_holds_together_when_it_breaks() {
+        let mut levy = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        let mut drilled = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        drilled.drill = 1.0;
+
+        process_morale_break(&mut levy);
+        process_morale_break(&mut drilled);
+
+        assert!(drilled.cohesion > levy.cohesion);
+    }
+
     #[test]
     fn test_process_rally() {
         let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
@@ -281,7 +301,7 @@ mod tests {
         unit.stance = UnitStance::Formed; // Not routing
         unit.stress = 0.1;
 
-        let result = check_rally(&unit, false, true);
+        let result = check_rally(&unit, false, Some(0.0));
 
         assert!(!result.rallies);
     }
@@ -1,15 +1,26 @@
 //! Unit movement along waypoints
 //!
-//! Units follow their waypoint plans, respecting terrain and pace.
+//! Units follow their waypoint plans, respecting terrain and pace. Every
+//! level climbed costs extra progress and fatigue, scaled by pace, so a
+//! charge uphill arrives slow and blown.
+//!
+//! Walls and closed gates stop a unit at their foot. Only a unit storming
+//! one with ladders climbs it, which costs `LADDER_CLIMB_COST` progress more.
+//!
+//! Cavalry moving at the charge pace are `Charging`, and count the hexes of
+//! open ground they gallop over (`charge_run`); anything rougher, a slower
+//! pace, or reaching the waypoint without striking anyone starts it over.
 
 use crate::battle::battle_map::BattleMap;
 use crate::battle::constants::{
     CAVALRY_CHARGE_SPEED, CAVALRY_TROT_SPEED, CAVALRY_WALK_SPEED, FATIGUE_RATE_MARCH,
-    INFANTRY_RUN_SPEED, INFANTRY_WALK_SPEED,
+    INFANTRY_RUN_SPEED, INFANTRY_WALK_SPEED, LADDER_CLIMB_COST, UPHILL_MARCH_FATIGUE,
 };
+use crate::battle::elevation::{climb, uphill_cost};
 use crate::battle::hex::BattleHexCoord;
-use crate::battle::pathfinding::find_path;
+use crate::battle::pathfinding::{find_assault_path, find_path};
 use crate::battle::planning::{MovementPace, WaitCondition, WaypointBehavior, WaypointPlan};
+use crate::battle::siege::SiegeAssault;
 use crate::battle::unit_type::UnitType;
 use crate::battle::units::{BattleUnit, UnitStance};
 
@@ -85,6 +96,8 @@ pub fn is_waiting(plan: &WaypointPlan, current_tick: u64) -> bool {
             // TODO: Check if under attack
             true
         }
+        // Unrecognised conditions from old saves never hold a unit
+        Some(WaitCondition::Unknown(_)) => false,
     }
 }
 
@@ -141,6 +154,7 @@ pub fn is_waiting_with_context(
             // Wait until this unit is under attack
             !units_under_attack.contains(&plan.unit_id)
         }
+        Some(WaitCondition::Unknown(_)) => false,
     }
 }
 
@@ -156,7 +170,10 @@ pub fn advance_unit_movement(
     let mut result = MovementResult::default();
 
     // Can't move if not in moving stance
-    if !matches!(unit.stance, UnitStance::Moving | UnitStance::Formed) {
+    if !matches!(
+        unit.stance,
+        UnitStance::Moving | UnitStance::Formed | UnitStance::Skirmishing | UnitStance::Charging
+    ) {
         return result;
     }
 
@@ -172,6 +189,12 @@ pub fn advance_unit_movement(
     if unit.position == waypoint_position {
         result.reached_waypoint = true;
 
+        // A charge that reaches its mark without striking anyone pulls up
+        unit.charge_run = 0;
+        if unit.stance == UnitStance::Charging {
+            unit.stance = UnitStance::Moving;
+        }
+
         // Apply waypoint behavior
         match waypoint_behavior {
             WaypointBehavior::MoveTo => {
@@ -200,8 +223,14 @@ pub fn advance_unit_movement(
         UnitType::LightCavalry | UnitType::HeavyCavalry
     );
 
-    // Find path to waypoint
-    let Some(path) = find_path(map, unit.position, waypoint_position, is_cavalry) else {
+    // Find path to waypoint (over the wall, for a unit carrying ladders)
+    let ladders = matches!(unit.assault, Some(SiegeAssault::Ladders(_)));
+    let path = if ladders {
+        find_assault_path(map, unit.position, waypoint_position, is_cavalry)
+    } else {
+        find_path(map, unit.position, waypoint_position, is_cavalry)
+    };
+    let Some(path) = path else {
         result.path_blocked = true;
         return result;
     };
@@ -217,19 +246,47 @@ pub fn advance_unit_movement(
     // Accumulate movement progress
     plan.movement_progress += effective_speed;
 
-    // Move along path, consuming progress for each hex crossed
+    // Move along path, consuming progress for each hex crossed (more uphill)
+    let charging = waypoint_pace == MovementPace::Charge && unit.unit_type.can_charge();
     let mut hexes_moved = 0;
-    while plan.movement_progress >= 1.0 && path.len() > 1 + hexes_moved {
-        plan.movement_progress -= 1.0;
-        unit.position = path[1 + hexes_moved];
+    let mut levels_climbed = 0;
+    while path.len() > 1 + hexes_moved {
+        let next = path[1 + hexes_moved];
+        let climbing = map.is_barred(next);
+        if climbing && !ladders {
+            // At the foot of a wall or gate: wait here, not bank the march
+            plan.movement_progress = 0.0;
+            result.path_blocked = true;
+            break;
+        }
+        let climb_cost = if climbing { LADDER_CLIMB_COST } else { 0.0 };
+        let cost = 1.0 + uphill_cost(map, unit.position, next) + climb_cost;
+        if plan.movement_progress < cost {
+            break;
+        }
+        plan.movement_progress -= cost;
+        levels_climbed += climb(map, unit.position, next);
+        let open = map.get_hex(next).is_some_and(|h| h.is_open_ground());
+        unit.charge_run = if charging && open {
+            unit.charge_run + 1
+        } else {
+            0
+        };
+        unit.position = next;
         hexes_moved += 1;
         result.moved = true;
     }
 
     if result.moved {
-        unit.stance = UnitStance::Moving;
+        unit.stance = if charging {
+            UnitStance::Charging
+        } else {
+            UnitStance::Moving
+        };
         // Fatigue scales with time (more sim-time = more fatigue)
         result.fatigue_delta = FATIGUE_RATE_MARCH * waypoint_pace.fatigue_multiplier() * time_scale;
+        result.fatigue_delta +=
+            UPHILL_MARCH_FATIGUE * levels_climbed as f32 * waypoint_pace.fatigue_multiplier();
     }
 
     result
@@ -267,6 +324,7 @@ mod tests {
     use crate::battle::battle_map::BattleMap;
     use crate::battle::hex::BattleHexCoord;
     use crate::battle::planning::{MovementPace, Waypoint, WaypointBehavior, WaypointPlan};
+    use crate::battle::terrain::BattleTerrain;
     use crate::battle::unit_type::UnitType;
     use crate::battle::units::{BattleUnit, UnitId, UnitStance};
 
@@ -505,6 +563,45 @@ mod tests {
         assert!(result_charge.fatigue_delta > result_walk.fatigue_delta);
     }
 
+    #[test]
+    fn test_charge_counts_the_gallop_over_open_ground() {
+        let mut map = BattleMap::new(20, 20);
+        let mut knights = BattleUnit::new(UnitId::new(), UnitType::HeavyCavalry);
+        knights.position = BattleHexCoord::new(0, 5);
+        let mut plan = WaypointPlan::new(knights.id);
+        plan.add_waypoint(
+            Waypoint::new(BattleHexCoord::new(15, 5), WaypointBehavior::MoveTo)
+                .with_pace(MovementPace::Charge),
+        );
+
+        advance_unit_movement(&map, &mut knights, &mut plan, 2.0);
+        assert_eq!(knights.stance, UnitStance::Charging);
+        assert_eq!(knights.charge_run, 2);
+
+        // Rough ground breaks the gallop
+        for q in 0..20 {
+            for r in 0..20 {
+                map.set_terrain(BattleHexCoord::new(q, r), BattleTerrain::Rough);
+            }
+        }
+        assert!(advance_unit_movement(&map, &mut knights, &mut plan, 2.0).moved);
+        assert_eq!(knights.charge_run, 0);
+
+        // Foot running flat out are not charging in this sense
+        let mut footmen = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        footmen.position = BattleHexCoord::new(0, 8);
+        let mut plan = WaypointPlan::new(footmen.id);
+        plan.add_waypoint(
+            Waypoint::new(BattleHexCoord::new(15, 8), WaypointBehavior::MoveTo)
+                .with_pace(MovementPace::Charge),
+        );
+        assert!(
+            advance_unit_movement(&BattleMap::new(20, 20), &mut footmen, &mut plan, 10.0).moved
+        );
+        assert_eq!(footmen.stance, UnitStance::Moving);
+        assert_eq!(footmen.charge_run, 0);
+    }
+
     #[test]
     fn test_all_movement_paces_for_infantry() {
         let walk = base_speed(UnitType::Infantry, MovementPace::Walk);
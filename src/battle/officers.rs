@@ -0,0 +1,264 @@
+//! Officers - named leaders riding with a formation's units
+//!
+//! A formation can carry officers: actual entities, each riding with one of
+//! its units. What they have learned of leadership is read once, when they
+//! are attached:
+//!
+//! - `LeadRallyWavering` lets routed units within `OFFICER_COMMAND_RANGE`
+//!   rally from deeper stress, and keeps panic from spreading to them
+//! - `LeadBattleManagement` gets orders addressed to the formation out of
+//!   HQ sooner
+//!
+//! Officers share their unit's danger: each casualty it takes may be them.
+//! An officer's death fires `CommanderKilled`, shakes every unit of their
+//! formation (`OFFICER_DEATH_STRESS`), and comes as a surprise to HQ.
+
+use serde::{Deserialize, Serialize};
+
+use crate::battle::constants::{
+    DRILL_ORDER_SPEEDUP, OFFICER_COMMAND_RANGE, OFFICER_CONTAGION_DAMPING, OFFICER_LOSS_CHANCE,
+    OFFICER_ORDER_SPEEDUP,
+};
+use crate::battle::courier::OrderTarget;
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::units::{Army, BattleFormation, FormationId, UnitId};
+use crate::core::types::{EntityId, Tick};
+use crate::skills::{ChunkId, ChunkLibrary};
+
+/// A named leader riding with one of a formation's units
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Officer {
+    pub entity: EntityId,
+    pub name: String,
+    /// The unit they ride with
+    pub unit: UnitId,
+    /// Skill at rallying the wavering (0.0-1.0)
+    pub rally: f32,
+    /// Skill at managing a battle (0.0-1.0)
+    pub management: f32,
+    pub killed: bool,
+    /// Casualties of their unit already survived
+    #[serde(default)]
+    casualties_seen: u32,
+}
+
+impl Officer {
+    /// An officer riding with `unit`, skilled as their chunks say
+    pub fn new(entity: EntityId, name: String, unit: UnitId, library: &ChunkLibrary) -> Self {
+        let depth = |id| library.get_chunk(id).map_or(0.0, |c| c.encoding_depth);
+        Self {
+            entity,
+            name,
+            unit,
+            rally: depth(ChunkId::LeadRallyWavering),
+            management: depth(ChunkId::LeadBattleManagement),
+            killed: false,
+            casualties_seen: 0,
+        }
+    }
+}
+
+/// An officer who fell this tick
+#[derive(Debug, Clone, PartialEq)]
+pub struct FallenOfficer {
+    pub formation: FormationId,
+    pub entity: EntityId,
+    pub name: String,
+}
+
+/// Living officers of a formation, with where their unit stands
+fn living_officers(
+    formation: &BattleFormation,
+) -> impl Iterator<Item = (&Officer, BattleHexCoord)> {
+    formation
+        .officers
+        .iter()
+        .filter(|o| !o.killed)
+        .filter_map(|officer| {
+            let unit = formation.units.iter().find(|u| u.id == officer.unit)?;
+            Some((officer, unit.position))
+        })
+}
+
+/// The best rallying skill among a formation's living officers within
+/// `OFFICER_COMMAND_RANGE` of `position`, if any are
+pub fn rally_skill_near(formation: &BattleFormation, position: BattleHexCoord) -> Option<f32> {
+    living_officers(formation)
+        .filter(|(_, at)| at.distance(&position) <= OFFICER_COMMAND_RANGE)
+        .map(|(officer, _)| officer.rally)
+        .max_by(f32::total_cmp)
+}
+
+/// Share of panic from routing friends that reaches a unit at `position`
+pub fn contagion_share(formation: &BattleFormation, position: BattleHexCoord) -> f32 {
+    let skill = rally_skill_near(formation, position).unwrap_or(0.0);
+    1.0 - OFFICER_CONTAGION_DAMPING * skill
+}
+
+/// Ticks an order addressed to `target` waits at HQ, cut short by the best
+/// battle manager among its formation's living officers and by the drill
+/// of the units it is for
+pub fn order_delay(army: &Army, target: &OrderTarget, hq_delay: Tick) -> Tick {
+    let formation = army.formations.iter().find(|f| match target {
+        OrderTarget::Unit(unit_id) => f.units.iter().any(|u| u.id == *unit_id),
+        OrderTarget::Formation(formation_id) => f.id == *formation_id,
+    });
+    let management = formation
+        .into_iter()
+        .flat_map(|f| f.officers.iter())
+        .filter(|o| !o.killed)
+        .map(|o| o.management)
+        .fold(0.0, f32::max);
+    // A formation moves at the pace of its least drilled unit
+    let drill = formation
+        .into_iter()
+        .flat_map(|f| f.units.iter())
+        .filter(|u| match target {
+            OrderTarget::Unit(unit_id) => u.id == *unit_id,
+            OrderTarget::Formation(_) => true,
+        })
+        .map(|u| u.drill)
+        .reduce(f32::min)
+        .unwrap_or(0.0);
+    let delay = hq_delay as f32
+        * (1.0 - OFFICER_ORDER_SPEEDUP * management)
+        * (1.0 - DRILL_ORDER_SPEEDUP * drill);
+    delay.round() as Tick
+}
+
+/// Whether any of the army's officers who fell was its commander
+pub fn commander_fallen(army: &Army) -> bool {
+    army.formations
+        .iter()
+        .flat_map(|f| f.officers.iter())
+        .any(|o| o.killed && o.entity == army.commander)
+}
+
+/// Officers struck down among their unit's casualties since the last tick,
+/// or lost with their unit; `roll` gives uniform rolls in 0.0-1.0
+pub fn officer_losses(army: &mut Army, mut roll: impl FnMut() -> f32) -> Vec<FallenOfficer> {
+    let mut fallen = Vec::new();
+    for formation in &mut army.formations {
+        for officer in formation.officers.iter_mut().filter(|o| !o.killed) {
+            let unit = formation.units.iter().find(|u| u.id == officer.unit);
+            let killed = match unit {
+                None => true,
+                Some(unit) if unit.effective_strength() == 0 => true,
+                Some(unit) => {
+                    let new = unit.casualties.saturating_sub(officer.casualties_seen);
+                    officer.casualties_seen = unit.casualties;
+                    let survive = (1.0 - OFFICER_LOSS_CHANCE).powi(new as i32);
+                    new > 0 && roll() >= survive
+                }
+            };
+            if killed {
+                officer.killed = true;
+                fallen.push(FallenOfficer {
+                    formation: formation.id,
+                    entity: officer.entity,
+                    name: officer.name.clone(),
+                });
+            }
+        }
+    }
+    fallen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::unit_type::UnitType;
+    use crate::battle::units::{ArmyId, BattleUnit, Element};
+    use crate::skills::PersonalChunkState;
+
+    fn master_of(chunks: &[ChunkId]) -> ChunkLibrary {
+        let mut library = ChunkLibrary::new();
+        for &id in chunks {
+            let mut state = PersonalChunkState::new(0);
+            state.encoding_depth = 1.0;
+            library.set_chunk(id, state);
+        }
+        library
+    }
+
+    fn army_with_officer(library: &ChunkLibrary) -> Army {
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        unit.elements.push(Element::new(vec![EntityId::new(); 50]));
+        unit.position = BattleHexCoord::new(0, 0);
+        formation.officers.push(Officer::new(
+            army.commander,
+            "Aldric".into(),
+            unit.id,
+            library,
+        ));
+        formation.units.push(unit);
+        army.formations.push(formation);
+        army
+    }
+
+    #[test]
+    fn test_officers_read_their_leadership_chunks() {
+        let library = master_of(&[ChunkId::LeadRallyWavering]);
+        let army = army_with_officer(&library);
+        let formation = &army.formations[0];
+        assert_eq!(formation.officers[0].rally, 1.0);
+        assert_eq!(formation.officers[0].management, 0.0);
+
+        assert_eq!(
+            rally_skill_near(formation, BattleHexCoord::new(2, 0)),
+            Some(1.0)
+        );
+        assert_eq!(rally_skill_near(formation, BattleHexCoord::new(9, 0)), None);
+        assert!(contagion_share(formation, BattleHexCoord::new(1, 0)) < 1.0);
+        assert_eq!(contagion_share(formation, BattleHexCoord::new(9, 0)), 1.0);
+    }
+
+    #[test]
+    fn test_battle_managers_get_orders_out_sooner() {
+        let novice = army_with_officer(&ChunkLibrary::new());
+        let master = army_with_officer(&master_of(&[ChunkId::LeadBattleManagement]));
+        let to = |army: &Army| OrderTarget::Formation(army.formations[0].id);
+        assert_eq!(order_delay(&novice, &to(&novice), 20), 20);
+        assert!(order_delay(&master, &to(&master), 20) < 20);
+        let unit = OrderTarget::Unit(master.formations[0].units[0].id);
+        assert!(order_delay(&master, &unit, 20) < 20);
+    }
+
+    #[test]
+    fn test_drilled_units_take_their_orders_sooner() {
+        let mut army = army_with_officer(&ChunkLibrary::new());
+        let unit = OrderTarget::Unit(army.formations[0].units[0].id);
+        army.formations[0].units[0].drill = 1.0;
+        assert_eq!(order_delay(&army, &unit, 20), 10);
+
+        // A formation waits on its least drilled unit
+        let mut levy = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        levy.elements.push(Element::new(vec![EntityId::new(); 50]));
+        army.formations[0].units.push(levy);
+        let formation = OrderTarget::Formation(army.formations[0].id);
+        assert_eq!(order_delay(&army, &formation, 20), 20);
+    }
+
+    #[test]
+    fn test_officers_can_fall_with_their_unit() {
+        let mut army = army_with_officer(&ChunkLibrary::new());
+        // No losses, no danger
+        assert!(officer_losses(&mut army, || 0.99).is_empty());
+
+        army.formations[0].units[0].casualties = 10;
+        assert!(officer_losses(&mut army, || 0.0).is_empty());
+        // Casualties already survived are not rolled for again
+        assert!(officer_losses(&mut army, || 0.99).is_empty());
+
+        army.formations[0].units[0].casualties = 11;
+        let fallen = officer_losses(&mut army, || 0.99);
+        assert_eq!(fallen.len(), 1);
+        assert_eq!(fallen[0].name, "Aldric");
+        assert!(commander_fallen(&army));
+        // The dead fall only once
+        army.formations[0].units[0].casualties = 20;
+        assert!(officer_losses(&mut army, || 0.99).is_empty());
+    }
+}
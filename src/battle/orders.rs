@@ -8,6 +8,7 @@ use crate::battle::hex::{BattleHexCoord, HexDirection};
 use crate::battle::planning::{
     BattlePlan, EngagementRule, MovementPace, Waypoint, WaypointBehavior, WaypointPlan,
 };
+use crate::battle::siege::{can_storm, Mine, SiegeAssault};
 use crate::battle::units::{Army, FormationId, UnitId, UnitStance};
 
 /// Result of applying an order
@@ -85,9 +86,10 @@ fn apply_order_to_unit(
                     .with_pace(MovementPace::Quick),
             );
 
-            // Set unit to moving stance
+            // Set unit to moving stance, calling off any assault
             if let Some(unit) = army.get_unit_mut(unit_id) {
                 unit.stance = UnitStance::Moving;
+                unit.assault = None;
             }
 
             ApplyOrderResult {
@@ -110,13 +112,18 @@ fn apply_order_to_unit(
                 }
             };
 
+            // Horsemen go in at the charge
+            let pace = match army.get_unit(unit_id) {
+                Some(unit) if unit.unit_type.can_charge() => MovementPace::Charge,
+                _ => MovementPace::Run,
+            };
+
             let waypoint_plan = get_or_create_waypoint_plan(plan, unit_id);
             waypoint_plan.waypoints.clear();
             waypoint_plan.current_waypoint = 0;
             waypoint_plan.wait_start_tick = None;
             waypoint_plan.add_waypoint(
-                Waypoint::new(target_pos, WaypointBehavior::AttackFrom)
-                    .with_pace(MovementPace::Run),
+                Waypoint::new(target_pos, WaypointBehavior::AttackFrom).with_pace(pace),
             );
 
             // Set engagement rule to aggressive
@@ -153,6 +160,11 @@ fn apply_order_to_unit(
         }
 
         OrderType::Retreat(route) => {
+            if let Some(unit) = army.get_unit_mut(unit_id) {
+                unit.scouting = None;
+                unit.assault = None;
+            }
+
             let waypoint_plan = get_or_create_waypoint_plan(plan, unit_id);
             waypoint_plan.waypoints.clear();
             waypoint_plan.current_waypoint = 0;
@@ -248,6 +260,7 @@ fn apply_order_to_unit(
 
             if let Some(unit) = army.get_unit_mut(unit_id) {
                 unit.stance = UnitStance::Formed;
+                unit.assault = None;
             }
 
             ApplyOrderResult {
@@ -327,6 +340,140 @@ fn apply_order_to_unit(
                 }
             }
         }
+
+        OrderType::Reconnoiter(direction) => match army.get_unit_mut(unit_id) {
+            Some(unit) if unit.unit_type.can_reconnoiter() => {
+                unit.scouting = Some(*direction);
+                unit.facing = *direction;
+
+                ApplyOrderResult {
+                    success: true,
+                    affected_units: vec![unit_id],
+                    message: format!("Scouts ride out {:?}", direction),
+                }
+            }
+            _ => ApplyOrderResult {
+                success: false,
+                affected_units: vec![],
+                message: "Only scouts can reconnoitre".to_string(),
+            },
+        },
+
+        OrderType::LadderAssault(target) | OrderType::RamAssault(target) => {
+            let assault = if matches!(order.order_type, OrderType::LadderAssault(_)) {
+                SiegeAssault::Ladders(*target)
+            } else {
+                SiegeAssault::Ram(*target)
+            };
+            match army.get_unit_mut(unit_id) {
+                Some(unit) if can_storm(unit.unit_type) => {
+                    unit.assault = Some(assault);
+                    unit.scouting = None;
+                    unit.stance = UnitStance::Moving;
+                }
+                _ => {
+                    return ApplyOrderResult {
+                        success: false,
+                        affected_units: vec![],
+                        message: "Horsemen cannot storm walls".to_string(),
+                    };
+                }
+            }
+
+            // To the foot of the wall (or onto it, with ladders)
+            let waypoint_plan = get_or_create_waypoint_plan(plan, unit_id);
+            waypoint_plan.waypoints.clear();
+            waypoint_plan.current_waypoint = 0;
+            waypoint_plan.wait_start_tick = None;
+            waypoint_plan.add_waypoint(
+                Waypoint::new(*target, WaypointBehavior::AttackFrom).with_pace(MovementPace::Quick),
+            );
+
+            plan.engagement_rules.retain(|(id, _)| *id != unit_id);
+            plan.engagement_rules
+                .push((unit_id, EngagementRule::Aggressive));
+
+            ApplyOrderResult {
+                success: true,
+                affected_units: vec![unit_id],
+                message: format!("{:?}", assault),
+            }
+        }
+
+        OrderType::Sap(target) => {
+            let entrance = match army.get_unit_mut(unit_id) {
+                Some(unit) if can_storm(unit.unit_type) => {
+                    unit.assault = None;
+                    unit.scouting = None;
+                    unit.stance = UnitStance::Formed;
+                    unit.position
+                }
+                _ => {
+                    return ApplyOrderResult {
+                        success: false,
+                        affected_units: vec![],
+                        message: "Horsemen cannot dig".to_string(),
+                    };
+                }
+            };
+
+            // They dig where they stand, one tunnel at a time
+            if let Some(waypoint_plan) = plan
+                .waypoint_plans
+                .iter_mut()
+                .find(|p| p.unit_id == unit_id)
+            {
+                waypoint_plan.waypoints.clear();
+                waypoint_plan.current_waypoint = 0;
+            }
+            army.mines.retain(|m| m.sappers != unit_id);
+            army.mines.push(Mine::new(unit_id, entrance, *target));
+
+            ApplyOrderResult {
+                success: true,
+                affected_units: vec![unit_id],
+                message: format!("Sapping toward ({}, {})", target.q, target.r),
+            }
+        }
+
+        OrderType::Brace => {
+            match army.get_unit_mut(unit_id) {
+                Some(unit) if !unit.unit_type.is_mounted() && unit.can_fight() => {
+                    unit.assault = None;
+                    unit.scouting = None;
+                    unit.stance = UnitStance::Braced;
+                }
+                _ => {
+                    return ApplyOrderResult {
+                        success: false,
+                        affected_units: vec![],
+                        message: "Only steady foot can brace".to_string(),
+                    };
+                }
+            }
+
+            // Braced where they stand until ordered on
+            if let Some(waypoint_plan) = plan
+                .waypoint_plans
+                .iter_mut()
+                .find(|p| p.unit_id == unit_id)
+            {
+                waypoint_plan.waypoints.clear();
+                waypoint_plan.current_waypoint = 0;
+            }
+
+            ApplyOrderResult {
+                success: true,
+                affected_units: vec![unit_id],
+                message: "Braced to receive a charge".to_string(),
+            }
+        }
+
+        OrderType::Unknown(tag) => ApplyOrderResult {
+            success: false,
+            affected_units: vec![],
+            message: format!("Unknown order {} ignored", tag),
+        },
     }
 }
 
@@ -458,6 +605,73 @@ mod tests {
         assert_eq!(unit.stance, UnitStance::Moving);
     }
 
+    #[test]
+    fn test_assault_orders_send_foot_to_the_wall() {
+        let (mut army, unit_id) = create_test_army_with_unit();
+        let mut plan = BattlePlan::new();
+        let gate = BattleHexCoord::new(8, 3);
+
+        let order = Order::new(OrderType::RamAssault(gate), OrderTarget::Unit(unit_id), 0);
+        assert!(apply_order(&order, &mut army, &mut plan).success);
+        let unit = army.get_unit(unit_id).unwrap();
+        assert_eq!(unit.assault, Some(SiegeAssault::Ram(gate)));
+        assert_eq!(unit.stance, UnitStance::Moving);
+        let wp_plan = plan.get_waypoint_plan(unit_id).unwrap();
+        assert_eq!(wp_plan.waypoints[0].position, gate);
+        assert_eq!(wp_plan.waypoints[0].behavior, WaypointBehavior::AttackFrom);
+
+        // A new move calls the assault off
+        apply_order(&Order::move_to(unit_id, gate), &mut army, &mut plan);
+        assert!(army.get_unit(unit_id).unwrap().assault.is_none());
+
+        // Horsemen cannot storm walls
+        let riders = UnitId::new();
+        army.formations[0]
+            .units
+            .push(BattleUnit::new(riders, UnitType::Cavalry));
+        let order = Order::new(OrderType::LadderAssault(gate), OrderTarget::Unit(riders), 0);
+        assert!(!apply_order(&order, &mut army, &mut plan).success);
+        assert!(army.get_unit(riders).unwrap().assault.is_none());
+    }
+
+    #[test]
+    fn test_sap_order_starts_a_mine_where_the_unit_stands() {
+        let (mut army, unit_id) = create_test_army_with_unit();
+        let mut plan = BattlePlan::new();
+        let wall = BattleHexCoord::new(8, 3);
+        let entrance = army.get_unit(unit_id).unwrap().position;
+
+        let order = Order::new(OrderType::Sap(wall), OrderTarget::Unit(unit_id), 0);
+        assert!(apply_order(&order, &mut army, &mut plan).success);
+        assert_eq!(army.mines.len(), 1);
+        assert_eq!(army.mines[0].entrance, entrance);
+        assert_eq!(army.mines[0].target, wall);
+
+        // A fresh order to dig abandons the old tunnel
+        assert!(apply_order(&order, &mut army, &mut plan).success);
+        assert_eq!(army.mines.len(), 1);
+    }
+
+    #[test]
+    fn test_brace_order_holds_foot_ready_for_a_charge() {
+        let (mut army, unit_id) = create_test_army_with_unit();
+        let mut plan = BattlePlan::new();
+        let order = Order::new(OrderType::Brace, OrderTarget::Unit(unit_id), 0);
+        assert!(apply_order(&order, &mut army, &mut plan).success);
+        assert_eq!(army.get_unit(unit_id).unwrap().stance, UnitStance::Braced);
+
+        // Horsemen do not brace
+        let riders = UnitId::new();
+        let mut cavalry = BattleUnit::new(riders, UnitType::Cavalry);
+        cavalry
+            .elements
+            .push(Element::new(vec![EntityId::new(); 10]));
+        army.formations[0].units.push(cavalry);
+        let order = Order::new(OrderType::Brace, OrderTarget::Unit(riders), 0);
+        assert!(!apply_order(&order, &mut army, &mut plan).success);
+        assert_eq!(army.get_unit(riders).unwrap().stance, UnitStance::Formed);
+    }
+
     #[test]
     fn test_apply_attack_order() {
         let (mut army, unit_id) = create_test_army_with_unit();
@@ -1,11 +1,14 @@
 //! A* pathfinding for battle maps
 //!
-//! Respects terrain costs and unit type restrictions.
+//! Respects terrain costs, the climb up slopes, and unit type restrictions.
+//! Walls and closed gates bar the way until breached, though a path may end
+//! at one (to batter it), and troops carrying ladders may climb walls.
 
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
 
 use crate::battle::battle_map::BattleMap;
+use crate::battle::elevation::uphill_cost;
 use crate::battle::hex::BattleHexCoord;
 
 /// Node in the A* open set
@@ -43,11 +46,36 @@ impl PartialOrd for PathNode {
 ///
 /// Returns None if no path exists.
 /// `is_cavalry` restricts movement through forests/buildings.
+/// A goal barred by a wall or gate can still be reached (the path ends at
+/// it), but no path passes through one.
 pub fn find_path(
     map: &BattleMap,
     start: BattleHexCoord,
     goal: BattleHexCoord,
     is_cavalry: bool,
+) -> Option<Vec<BattleHexCoord>> {
+    search(map, start, goal, is_cavalry, false)
+}
+
+/// Find a path for troops carrying assault ladders
+///
+/// Like `find_path`, but walls that are still standing may be climbed, at
+/// `LADDER_CLIMB_COST` extra each. Gates cannot be.
+pub fn find_assault_path(
+    map: &BattleMap,
+    start: BattleHexCoord,
+    goal: BattleHexCoord,
+    is_cavalry: bool,
+) -> Option<Vec<BattleHexCoord>> {
+    search(map, start, goal, is_cavalry, true)
+}
+
+fn search(
+    map: &BattleMap,
+    start: BattleHexCoord,
+    goal: BattleHexCoord,
+    is_cavalry: bool,
+    ladders: bool,
 ) -> Option<Vec<BattleHexCoord>> {
     if start == goal {
         return Some(vec![start]);
@@ -84,7 +112,15 @@ pub fn find_path(
                 continue;
             }
 
-            let move_cost = hex.total_movement_cost();
+            let ground = if ladders && hex.can_be_scaled() {
+                hex.climbing_cost()
+            } else if neighbor == goal && hex.bars_the_way() {
+                // Up to the wall's foot, to batter it
+                0.0
+            } else {
+                hex.total_movement_cost()
+            };
+            let move_cost = ground + uphill_cost(map, current.coord, neighbor);
             if move_cost.is_infinite() {
                 continue;
             }
@@ -122,12 +158,18 @@ fn reconstruct_path(
     path
 }
 
-/// Calculate path cost (sum of terrain costs)
+/// Calculate path cost (sum of terrain costs, and of every climb)
 pub fn path_cost(map: &BattleMap, path: &[BattleHexCoord]) -> f32 {
-    path.iter()
+    let terrain: f32 = path
+        .iter()
         .filter_map(|coord| map.get_hex(*coord))
         .map(|hex| hex.total_movement_cost())
-        .sum()
+        .sum();
+    let climbs: f32 = path
+        .windows(2)
+        .map(|step| uphill_cost(map, step[0], step[1]))
+        .sum();
+    terrain + climbs
 }
 
 #[cfg(test)]
@@ -169,6 +211,21 @@ mod tests {
         assert!(!path.contains(&BattleHexCoord::new(2, 0)));
     }
 
+    #[test]
+    fn test_pathfind_skirts_a_steep_climb() {
+        let mut map = BattleMap::new(10, 10);
+        let crag = BattleHexCoord::new(2, 0);
+        map.set_elevation(crag, 3);
+
+        let start = BattleHexCoord::new(0, 0);
+        let goal = BattleHexCoord::new(5, 0);
+        let path = find_path(&map, start, goal, false).unwrap();
+
+        assert!(!path.contains(&crag));
+        let straight: Vec<_> = start.line_to(&goal);
+        assert!(path_cost(&map, &straight) > path_cost(&map, &path));
+    }
+
     #[test]
     fn test_cavalry_cant_enter_forest() {
         let mut map = BattleMap::new(10, 10);
@@ -204,6 +261,35 @@ mod tests {
         assert!(path.is_none());
     }
 
+    #[test]
+    fn test_pathfind_respects_walls_until_breached() {
+        let mut map = BattleMap::new(10, 10);
+        // A wall across the whole map, with a gate in it
+        for r in 0..10 {
+            map.build_wall(BattleHexCoord::new(5, r));
+        }
+        let gate = BattleHexCoord::new(5, 5);
+        map.build_gate(gate);
+
+        let start = BattleHexCoord::new(0, 5);
+        let goal = BattleHexCoord::new(9, 5);
+        assert!(find_path(&map, start, goal, false).is_none());
+
+        // Up to the gate to batter it, but not through
+        let to_gate = find_path(&map, start, gate, false).unwrap();
+        assert_eq!(to_gate.last(), Some(&gate));
+
+        // Ladders go over the wall, never the gate
+        let over = find_assault_path(&map, start, goal, false).unwrap();
+        let climbed: Vec<_> = over.iter().filter(|c| map.is_barred(**c)).collect();
+        assert_eq!(climbed.len(), 1);
+        assert_ne!(*climbed[0], gate);
+
+        map.damage_fortification(gate, f32::MAX);
+        let through = find_path(&map, start, goal, false).unwrap();
+        assert!(through.contains(&gate));
+    }
+
     #[test]
     fn test_pathfind_same_start_goal() {
         let map = BattleMap::new(10, 10);
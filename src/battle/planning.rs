@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::battle::formation_layout::FormationLine;
 use crate::battle::hex::{BattleHexCoord, HexDirection};
+use crate::battle::save::Inert;
 use crate::battle::units::{UnitId, UnitStance};
 use crate::core::types::Tick;
 
@@ -77,6 +78,14 @@ pub enum WaitCondition {
     UnitArrives(UnitId), // Wait for another unit
     EnemySighted,        // Wait until enemy seen
     Attacked,            // Wait until attacked
+    /// A condition from a save this build does not recognise; never holds a unit
+    Unknown(String),
+}
+
+impl Inert for WaitCondition {
+    fn inert(tag: String) -> Self {
+        WaitCondition::Unknown(tag)
+    }
 }
 
 /// A waypoint in a movement plan
@@ -85,6 +94,7 @@ pub struct Waypoint {
     pub position: BattleHexCoord,
     pub behavior: WaypointBehavior,
     pub pace: MovementPace,
+    #[serde(default, deserialize_with = "crate::battle::save::option_or_inert")]
     pub wait_condition: Option<WaitCondition>,
 }
 
@@ -181,6 +191,14 @@ pub enum GoCodeTrigger {
     EnemyInArea {
         area: Vec<BattleHexCoord>,
     },
+    /// A trigger from a save this build does not recognise; never fires
+    Unknown(String),
+}
+
+impl Inert for GoCodeTrigger {
+    fn inert(tag: String) -> Self {
+        GoCodeTrigger::Unknown(tag)
+    }
 }
 
 /// A go-code (coordinated trigger)
@@ -188,6 +206,7 @@ pub enum GoCodeTrigger {
 pub struct GoCode {
     pub id: GoCodeId,
     pub name: String,
+    #[serde(deserialize_with = "crate::battle::save::or_inert")]
     pub trigger: GoCodeTrigger,
     pub subscribers: Vec<UnitId>,
     pub triggered: bool,
@@ -219,6 +238,14 @@ pub enum ContingencyTrigger {
     PositionLost(BattleHexCoord),
     EnemyFlanking,
     CasualtiesExceed(f32), // Percentage
+    /// A trigger from a save this build does not recognise; never fires
+    Unknown(String),
+}
+
+impl Inert for ContingencyTrigger {
+    fn inert(tag: String) -> Self {
+        ContingencyTrigger::Unknown(tag)
+    }
 }
 
 /// Contingency response
@@ -228,12 +255,22 @@ pub enum ContingencyResponse {
     Retreat(Vec<BattleHexCoord>), // Retreat route
     Rally(BattleHexCoord),        // Rally point
     Signal(GoCodeId),             // Trigger a go-code
+    /// A response from a save this build does not recognise; does nothing
+    Unknown(String),
+}
+
+impl Inert for ContingencyResponse {
+    fn inert(tag: String) -> Self {
+        ContingencyResponse::Unknown(tag)
+    }
 }
 
 /// A contingency (pre-planned response)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Contingency {
+    #[serde(deserialize_with = "crate::battle::save::or_inert")]
     pub trigger: ContingencyTrigger,
+    #[serde(deserialize_with = "crate::battle::save::or_inert")]
     pub response: ContingencyResponse,
     pub priority: u8,
     pub activated: bool,
@@ -1,10 +1,23 @@
 //! Ranged combat phase for battle system
 //!
 //! Handles bow, crossbow, and thrown weapon attacks using the chunking skill system.
+//!
+//! Ranged units in formed order (skirmishers shoot in their own phase) loose
+//! a volley every `BOW_VOLLEY_TICKS` (`CROSSBOW_VOLLEY_TICKS` for crossbows)
+//! at the nearest enemy their army can see and reach, until they come to
+//! blows or run out of the `QUIVER_VOLLEYS` they carry. Targets already in
+//! melee are left alone, and cover on the target's hex spoils the aim.
+
+use rand::Rng;
 
+use crate::battle::battle_map::BattleMap;
+use crate::battle::constants::{BOW_VOLLEY_TICKS, COVER_HIT_PENALTY, CROSSBOW_VOLLEY_TICKS};
 use crate::battle::hex::BattleHexCoord;
+use crate::battle::planning::BattlePlan;
+use crate::battle::skirmish::{follows_doctrine, Volley};
 use crate::battle::unit_type::UnitType;
-use crate::battle::units::BattleUnit;
+use crate::battle::units::{Army, BattleUnit, UnitId, UnitStance};
+use crate::battle::visibility::ArmyVisibility;
 use crate::combat::weapons::{Mass, RangeCategory, RangedWeaponProperties};
 
 /// Maximum effective range in hexes for each range category
@@ -68,14 +81,50 @@ pub fn unit_ranged_weapon(unit_type: UnitType) -> Option<RangedWeaponProperties>
     }
 }
 
+/// Ticks between a ranged unit's volleys (None for units that do not shoot)
+pub fn volley_interval(unit_type: UnitType) -> Option<u64> {
+    match unit_type {
+        UnitType::Crossbowmen => Some(CROSSBOW_VOLLEY_TICKS),
+        _ if unit_type.is_ranged() => Some(BOW_VOLLEY_TICKS),
+        _ => None,
+    }
+}
+
+/// Cover the terrain at a hex gives whoever stands there (0.0 to 1.0)
+pub fn target_cover(map: &BattleMap, coord: BattleHexCoord) -> f32 {
+    map.get_hex(coord)
+        .map_or(0.0, |hex| hex.terrain.cover_value())
+}
+
+/// Chance a volley hits, from the distance, sight, and the target's cover
+pub fn hit_chance(distance: u32, range: RangeCategory, has_los: bool, cover: f32) -> f32 {
+    // Base hit chance depends on skill (simplified - use encoding depth later)
+    let base_hit_chance = 0.4;
+
+    // Distance penalty
+    let max_range = max_range_hexes(range);
+    let distance_penalty = (distance as f32 / max_range as f32) * 0.3;
+
+    // Cover bonus for defender
+    let cover_bonus = cover * COVER_HIT_PENALTY;
+
+    // LOS penalty
+    let los_penalty = if has_los { 0.0 } else { 0.5 };
+
+    (base_hit_chance - distance_penalty - cover_bonus - los_penalty).max(0.05)
+}
+
 /// Resolve a ranged attack from one unit to another
 ///
-/// Returns attack result. Does NOT mutate units - caller applies results.
+/// Returns attack result. Does NOT mutate units - caller applies results
+/// (including the ammunition used). The hit is rolled with `rng`.
 pub fn resolve_unit_ranged_attack(
     attacker: &BattleUnit,
     defender: &BattleUnit,
     _tick: u64,
     has_los: bool,
+    cover: f32,
+    rng: &mut impl Rng,
 ) -> RangedAttackResult {
     let mut result = RangedAttackResult::default();
 
@@ -88,31 +137,17 @@ pub fn resolve_unit_ranged_attack(
         }
     };
 
-    // Check range
-    if !can_shoot(attacker.position, defender.position, weapon.range) {
+    // Check range and ammunition
+    if !can_shoot(attacker.position, defender.position, weapon.range) || attacker.ammo() == 0 {
         result.ammo_consumed = 0;
         return result;
     }
 
-    // Base hit chance depends on skill (simplified - use encoding depth later)
-    let base_hit_chance = 0.4;
-
-    // Distance penalty
     let distance = attacker.position.distance(&defender.position);
-    let max_range = max_range_hexes(weapon.range);
-    let distance_penalty = (distance as f32 / max_range as f32) * 0.3;
-
-    // Cover bonus for defender (would come from terrain)
-    let cover_bonus = 0.0; // TODO: terrain lookup
-
-    // LOS penalty
-    let los_penalty = if has_los { 0.0 } else { 0.5 };
-
-    // Final hit chance
-    let hit_chance = (base_hit_chance - distance_penalty - cover_bonus - los_penalty).max(0.05);
+    let hit_chance = hit_chance(distance, weapon.range, has_los, cover);
 
-    // Roll for hit (simplified - use RNG properly in real impl)
-    let roll: f32 = rand::random();
+    // Roll for hit
+    let roll: f32 = rng.gen();
     result.hit = roll < hit_chance;
 
     // Casualties if hit
@@ -137,9 +172,94 @@ pub fn resolve_unit_ranged_attack(
     result
 }
 
+/// What one side's formed shooters did this tick
+#[derive(Debug, Clone, Default)]
+pub struct RangedOutcome {
+    /// Volleys loosed at the other side
+    pub volleys: Vec<Volley>,
+    /// Units that loosed their last volley
+    pub out_of_ammo: Vec<UnitId>,
+}
+
+/// The nearest enemy a unit can see (through its army's eyes) and shoot at,
+/// leaving alone those already in melee
+pub fn fire_target(
+    unit: &BattleUnit,
+    enemies: &[BattleUnit],
+    visibility: &ArmyVisibility,
+) -> Option<UnitId> {
+    let weapon = unit_ranged_weapon(unit.unit_type)?;
+    enemies
+        .iter()
+        .filter(|e| e.can_fight() && !e.is_engaged())
+        .filter(|e| visibility.is_visible(e.position))
+        .filter(|e| can_shoot(unit.position, e.position, weapon.range))
+        .min_by_key(|e| unit.position.distance(&e.position))
+        .map(|e| e.id)
+}
+
+/// Whether a unit shoots in the ranged phase: a shooter with ammunition,
+/// in formed order rather than skirmishing, and not yet come to blows
+fn fires_in_formation(unit: &BattleUnit, plan: &BattlePlan) -> bool {
+    unit.ammo() > 0
+        && unit.can_fight()
+        && !matches!(
+            unit.stance,
+            UnitStance::Engaged | UnitStance::Skirmishing | UnitStance::Evading
+        )
+        && !follows_doctrine(unit, &plan.get_engagement_rule(unit.id))
+}
+
+/// Loose every volley due from an army's formed shooters
+///
+/// Tires the shooters and spends their ammunition; the volleys are returned
+/// for the caller to apply to the enemy. Hits are rolled with `rng`.
+pub fn run_ranged_fire(
+    map: &BattleMap,
+    army: &mut Army,
+    plan: &BattlePlan,
+    enemies: &[BattleUnit],
+    visibility: &ArmyVisibility,
+    tick: u64,
+    rng: &mut impl Rng,
+) -> RangedOutcome {
+    let mut outcome = RangedOutcome::default();
+    for unit in army.formations.iter_mut().flat_map(|f| f.units.iter_mut()) {
+        let due = volley_interval(unit.unit_type).is_some_and(|every| tick.is_multiple_of(every));
+        if !due || !fires_in_formation(unit, plan) {
+            continue;
+        }
+        let Some(target) = fire_target(unit, enemies, visibility) else {
+            continue;
+        };
+        let Some(enemy) = enemies.iter().find(|e| e.id == target) else {
+            continue;
+        };
+        let has_los = map.has_line_of_sight(unit.position, enemy.position);
+        let cover = target_cover(map, enemy.position);
+        let result = resolve_unit_ranged_attack(unit, enemy, tick, has_los, cover, rng);
+        if result.ammo_consumed == 0 {
+            continue;
+        }
+        unit.volleys_loosed += result.ammo_consumed;
+        unit.fatigue = (unit.fatigue + result.fatigue_cost).min(1.0);
+        if unit.ammo() == 0 {
+            outcome.out_of_ammo.push(unit.id);
+        }
+        outcome.volleys.push(Volley {
+            target,
+            casualties: result.casualties,
+            stress: result.stress_inflicted,
+        });
+    }
+    outcome
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
 
     #[test]
     fn test_can_shoot_at_target_in_range() {
@@ -197,7 +317,8 @@ mod tests {
         target.position = BattleHexCoord::new(8, 0);
         target.elements.push(Element::new(vec![EntityId::new(); 50]));
 
-        let result = resolve_unit_ranged_attack(&archer, &target, 0, true);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let result = resolve_unit_ranged_attack(&archer, &target, 0, true, 0.0, &mut rng);
 
         // Should have attempted attack
         assert!(result.ammo_consumed > 0);
@@ -220,7 +341,8 @@ mod tests {
         target.position = BattleHexCoord::new(50, 0);
         target.elements.push(Element::new(vec![EntityId::new(); 50]));
 
-        let result = resolve_unit_ranged_attack(&archer, &target, 0, true);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let result = resolve_unit_ranged_attack(&archer, &target, 0, true, 0.0, &mut rng);
 
         // No ammo consumed if out of range
         assert_eq!(result.ammo_consumed, 0);
@@ -240,10 +362,99 @@ mod tests {
         target.position = BattleHexCoord::new(5, 0);
         target.elements.push(Element::new(vec![EntityId::new(); 50]));
 
-        let result = resolve_unit_ranged_attack(&infantry, &target, 0, true);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let result = resolve_unit_ranged_attack(&infantry, &target, 0, true, 0.0, &mut rng);
 
         // Infantry can't do ranged attacks
         assert_eq!(result.ammo_consumed, 0);
         assert!(!result.hit);
     }
+
+    fn unit_at(unit_type: UnitType, position: BattleHexCoord, soldiers: usize) -> BattleUnit {
+        use crate::battle::units::Element;
+        use crate::core::types::EntityId;
+
+        let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+        unit.elements
+            .push(Element::new(vec![EntityId::new(); soldiers]));
+        unit.position = position;
+        unit
+    }
+
+    fn seeing(hexes: &[BattleHexCoord]) -> ArmyVisibility {
+        let mut visibility = ArmyVisibility::new();
+        visibility.update(hexes.iter().copied().collect());
+        visibility
+    }
+
+    #[test]
+    fn test_cover_lowers_hit_chance() {
+        let open = hit_chance(8, RangeCategory::Medium, true, 0.0);
+        let forest = hit_chance(8, RangeCategory::Medium, true, 0.5);
+        assert!(forest < open);
+        assert!(hit_chance(8, RangeCategory::Medium, true, 1.0) >= 0.0);
+    }
+
+    #[test]
+    fn test_fire_target_needs_sight_and_spares_melee() {
+        let archers = unit_at(UnitType::Archers, BattleHexCoord::new(0, 0), 20);
+        let near = unit_at(UnitType::Infantry, BattleHexCoord::new(4, 0), 50);
+        let far = unit_at(UnitType::Infantry, BattleHexCoord::new(8, 0), 50);
+        let enemies = vec![near.clone(), far.clone()];
+
+        // Only what the army can see is shot at
+        assert_eq!(
+            fire_target(&archers, &enemies, &seeing(&[far.position])),
+            Some(far.id)
+        );
+        assert_eq!(fire_target(&archers, &enemies, &seeing(&[])), None);
+
+        // The nearest first, unless it is already in melee
+        let both = seeing(&[near.position, far.position]);
+        assert_eq!(fire_target(&archers, &enemies, &both), Some(near.id));
+        let mut engaged = near.clone();
+        engaged.stance = UnitStance::Engaged;
+        let enemies = vec![engaged, far.clone()];
+        assert_eq!(fire_target(&archers, &enemies, &both), Some(far.id));
+    }
+
+    #[test]
+    fn test_shooters_run_out_of_ammunition() {
+        use crate::battle::constants::QUIVER_VOLLEYS;
+        use crate::battle::units::{ArmyId, BattleFormation, FormationId};
+        use crate::core::types::EntityId;
+
+        let map = BattleMap::new(20, 20);
+        let archers = unit_at(UnitType::Archers, BattleHexCoord::new(2, 5), 20);
+        let archer_id = archers.id;
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        formation.units.push(archers);
+        army.formations.push(formation);
+
+        let target = unit_at(UnitType::Infantry, BattleHexCoord::new(8, 5), 50);
+        let visibility = seeing(&[target.position]);
+        let enemies = vec![target];
+        let plan = BattlePlan::new();
+
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut volleys = 0;
+        let mut spent = Vec::new();
+        for tick in 0..(QUIVER_VOLLEYS as u64 + 10) * BOW_VOLLEY_TICKS {
+            let outcome = run_ranged_fire(
+                &map,
+                &mut army,
+                &plan,
+                &enemies,
+                &visibility,
+                tick,
+                &mut rng,
+            );
+            volleys += outcome.volleys.len() as u32;
+            spent.extend(outcome.out_of_ammo);
+        }
+        assert_eq!(volleys, QUIVER_VOLLEYS);
+        assert_eq!(spent, vec![archer_id]);
+        assert_eq!(army.get_unit(archer_id).unwrap().ammo(), 0);
+    }
 }
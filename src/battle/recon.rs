@@ -0,0 +1,166 @@
+//! Mounted scouts and courier escorts - the information war
+//!
+//! Vision is scarce and orders travel by courier, so both can be fought
+//! over. Two kinds of light horse exist for it:
+//!
+//! - Mounted scouts, ordered to reconnoitre in a direction, ride out
+//!   `SCOUT_PROBE_DISTANCE` hexes and report what they see from there.
+//! - Courier escorts ride with couriers, one escort unit per courier,
+//!   making an interception less likely.
+//!
+//! Both are light horse and no match for cavalry. Enemy cavalry coming near
+//! the scouts' riders drives them back with losses, and an escort is no
+//! help when cavalry makes the interception; if an escorted courier is taken
+//! anyway, the escort loses riders with it.
+
+use crate::battle::battle_map::BattleMap;
+use crate::battle::constants::{
+    ESCORT_INTERCEPTION_REDUCTION, SCOUT_PROBE_DISTANCE, SCOUT_RIDDEN_DOWN_LOSSES,
+    SCOUT_RIDDEN_DOWN_RANGE, SCOUT_RIDDEN_DOWN_STRESS,
+};
+use crate::battle::courier::CourierSystem;
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::unit_type::UnitType;
+use crate::battle::units::{Army, BattleUnit, UnitId};
+
+/// Where a reconnoitring unit's riders are looking from
+///
+/// As far out as `SCOUT_PROBE_DISTANCE` in the ordered direction, stopping
+/// at the map edge. None if the unit is not out scouting.
+pub fn scout_post(map: &BattleMap, unit: &BattleUnit) -> Option<BattleHexCoord> {
+    let direction = unit.scouting.filter(|_| unit.can_fight())?;
+    let step = direction.offset();
+    let mut post = unit.position;
+    for _ in 0..SCOUT_PROBE_DISTANCE {
+        let next = BattleHexCoord::new(post.q + step.q, post.r + step.r);
+        if !map.in_bounds(next) {
+            break;
+        }
+        post = next;
+    }
+    Some(post)
+}
+
+/// Whether an enemy unit is cavalry that light horse cannot stand up to
+pub fn is_cavalry(unit: &BattleUnit) -> bool {
+    unit.unit_type.is_mounted() && unit.can_fight()
+}
+
+/// An escort unit not already riding with a courier
+pub fn free_escort(army: &Army, couriers: &CourierSystem) -> Option<UnitId> {
+    army.formations
+        .iter()
+        .flat_map(|f| f.units.iter())
+        .filter(|u| u.unit_type == UnitType::CourierEscort && u.can_fight() && !u.is_engaged())
+        .map(|u| u.id)
+        .find(|&id| !couriers.in_flight.iter().any(|c| c.escort == Some(id)))
+}
+
+/// Chance `interceptor` takes a courier, given its base chance by stance
+pub fn interception_chance(base: f32, interceptor: &BattleUnit, escorted: bool) -> f32 {
+    if escorted && !is_cavalry(interceptor) {
+        (base - ESCORT_INTERCEPTION_REDUCTION).max(0.0)
+    } else {
+        base
+    }
+}
+
+/// Drive back scouts with enemy cavalry near their riders
+///
+/// Returns the units driven off; they stop reconnoitring until ordered out
+/// again.
+pub fn drive_off_scouts(map: &BattleMap, army: &mut Army, enemies: &[BattleUnit]) -> Vec<UnitId> {
+    let mut driven_off = Vec::new();
+    for unit in army.formations.iter_mut().flat_map(|f| f.units.iter_mut()) {
+        let Some(post) = scout_post(map, unit) else {
+            continue;
+        };
+        let ridden_down = enemies.iter().filter(|e| is_cavalry(e)).any(|e| {
+            e.position.distance(&post) <= SCOUT_RIDDEN_DOWN_RANGE
+                || e.position.distance(&unit.position) <= SCOUT_RIDDEN_DOWN_RANGE
+        });
+        if ridden_down {
+            unit.scouting = None;
+            unit.casualties += SCOUT_RIDDEN_DOWN_LOSSES;
+            unit.stress += SCOUT_RIDDEN_DOWN_STRESS;
+            driven_off.push(unit.id);
+        }
+    }
+    driven_off
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::constants::COURIER_INTERCEPTION_CHANCE_PATROL;
+    use crate::battle::courier::Order;
+    use crate::battle::hex::HexDirection;
+    use crate::battle::units::{ArmyId, BattleFormation, Element, FormationId};
+    use crate::core::types::EntityId;
+
+    fn unit_at(unit_type: UnitType, q: i32, r: i32) -> BattleUnit {
+        let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+        unit.position = BattleHexCoord::new(q, r);
+        unit.elements.push(Element::new(vec![EntityId::new(); 10]));
+        unit
+    }
+
+    fn army_of(units: Vec<BattleUnit>) -> Army {
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        formation.units = units;
+        army.formations.push(formation);
+        army
+    }
+
+    #[test]
+    fn test_scouts_are_ridden_down_by_cavalry() {
+        let map = BattleMap::new(30, 30);
+        let mut scouts = unit_at(UnitType::MountedScouts, 5, 10);
+        assert_eq!(scout_post(&map, &scouts), None);
+
+        scouts.scouting = Some(HexDirection::East);
+        let post = scout_post(&map, &scouts).unwrap();
+        assert_eq!(
+            post,
+            BattleHexCoord::new(5 + SCOUT_PROBE_DISTANCE as i32, 10)
+        );
+
+        // Infantry near the post does not trouble them; cavalry does
+        let mut army = army_of(vec![scouts]);
+        let infantry = unit_at(UnitType::Infantry, post.q + 1, post.r);
+        assert!(drive_off_scouts(&map, &mut army, &[infantry]).is_empty());
+
+        let cavalry = unit_at(UnitType::Cavalry, post.q + 1, post.r);
+        let driven_off = drive_off_scouts(&map, &mut army, &[cavalry]);
+        assert_eq!(driven_off.len(), 1);
+        let scouts = &army.formations[0].units[0];
+        assert_eq!(scouts.scouting, None);
+        assert_eq!(scouts.casualties, SCOUT_RIDDEN_DOWN_LOSSES);
+    }
+
+    #[test]
+    fn test_escorts_guard_one_courier_each() {
+        let escort = unit_at(UnitType::CourierEscort, 0, 0);
+        let escort_id = escort.id;
+        let army = army_of(vec![escort, unit_at(UnitType::Infantry, 1, 0)]);
+        let mut couriers = CourierSystem::new();
+        assert_eq!(free_escort(&army, &couriers), Some(escort_id));
+
+        let id = couriers.dispatch(
+            EntityId::new(),
+            Order::hold(UnitId::new()),
+            BattleHexCoord::new(0, 0),
+            BattleHexCoord::new(5, 0),
+        );
+        couriers.get_courier_mut(id).unwrap().escort = Some(escort_id);
+        assert_eq!(free_escort(&army, &couriers), None);
+
+        let base = COURIER_INTERCEPTION_CHANCE_PATROL;
+        let patrol = unit_at(UnitType::Infantry, 3, 0);
+        let cavalry = unit_at(UnitType::LightCavalry, 3, 0);
+        assert!(interception_chance(base, &patrol, true) < base);
+        assert_eq!(interception_chance(base, &patrol, false), base);
+        assert_eq!(interception_chance(base, &cavalry, true), base);
+    }
+}
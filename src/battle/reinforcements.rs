@@ -0,0 +1,260 @@
+//! Reinforcements - formations that march onto the field mid-battle
+//!
+//! A reinforcement is a formation held off the map until it arrives at one
+//! edge: on a given tick, or once a go-code in its side's plan has fired
+//! (the tick after, since go-codes are evaluated after arrivals). Its units
+//! are put on the open hexes of that edge nearest the middle, facing in,
+//! and from then on see, are seen, and take orders like any other
+//! formation. This is how armies converging from a campaign join a battle
+//! already under way.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::battle::battle_map::BattleMap;
+use crate::battle::hex::{BattleHexCoord, HexDirection};
+use crate::battle::planning::{BattlePlan, GoCodeId};
+use crate::battle::units::{Army, BattleFormation, FormationId};
+use crate::core::types::Tick;
+
+/// Edge of the battle map a reinforcement marches in from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MapEdge {
+    /// r = 0
+    North,
+    /// r = height - 1
+    South,
+    /// q = width - 1
+    East,
+    /// q = 0
+    West,
+}
+
+impl MapEdge {
+    /// Hexes along this edge, from the middle outward
+    pub fn hexes(&self, map: &BattleMap) -> Vec<BattleHexCoord> {
+        let (w, h) = (map.width as i32, map.height as i32);
+        let mut hexes: Vec<BattleHexCoord> = match self {
+            MapEdge::North => (0..w).map(|q| BattleHexCoord::new(q, 0)).collect(),
+            MapEdge::South => (0..w).map(|q| BattleHexCoord::new(q, h - 1)).collect(),
+            MapEdge::East => (0..h).map(|r| BattleHexCoord::new(w - 1, r)).collect(),
+            MapEdge::West => (0..h).map(|r| BattleHexCoord::new(0, r)).collect(),
+        };
+        let middle = match self {
+            MapEdge::North | MapEdge::South => (w - 1) / 2,
+            MapEdge::East | MapEdge::West => (h - 1) / 2,
+        };
+        hexes.sort_by_key(|hex| match self {
+            MapEdge::North | MapEdge::South => (hex.q - middle).abs(),
+            MapEdge::East | MapEdge::West => (hex.r - middle).abs(),
+        });
+        hexes
+    }
+}
+
+/// What brings a reinforcement onto the field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrivalTrigger {
+    /// Arrives on this tick (or the first one after it)
+    AtTick(Tick),
+    /// Arrives once this go-code in its side's plan has fired
+    GoCode(GoCodeId),
+}
+
+/// A formation on its way to the battle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reinforcement {
+    pub formation: BattleFormation,
+    pub edge: MapEdge,
+    pub trigger: ArrivalTrigger,
+}
+
+impl Reinforcement {
+    pub fn new(formation: BattleFormation, edge: MapEdge, trigger: ArrivalTrigger) -> Self {
+        Self {
+            formation,
+            edge,
+            trigger,
+        }
+    }
+
+    /// Should it arrive on `tick`, given its side's plan?
+    pub fn is_due(&self, tick: Tick, plan: &BattlePlan) -> bool {
+        match self.trigger {
+            ArrivalTrigger::AtTick(at) => tick >= at,
+            ArrivalTrigger::GoCode(id) => plan.go_codes.iter().any(|g| g.id == id && g.triggered),
+        }
+    }
+
+    /// Ticks until it arrives, if it keeps to a timetable (a go-code's
+    /// reinforcement comes when it is called)
+    pub fn ticks_until(&self, tick: Tick) -> Option<Tick> {
+        match self.trigger {
+            ArrivalTrigger::AtTick(at) => Some(at.saturating_sub(tick)),
+            ArrivalTrigger::GoCode(_) => None,
+        }
+    }
+
+    pub fn strength(&self) -> usize {
+        self.formation.effective_strength()
+    }
+}
+
+/// A reinforcement that has reached the field
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arrival {
+    pub formation: FormationId,
+    pub name: String,
+    pub edge: MapEdge,
+    pub units: usize,
+}
+
+/// Bring every due reinforcement onto the field into `army`
+///
+/// Units take the open hexes of their edge nearest the middle, skipping
+/// hexes in `occupied` and ground they cannot cross. If the edge is full
+/// they crowd onto its middle.
+pub fn arrive_reinforcements(
+    pending: &mut Vec<Reinforcement>,
+    army: &mut Army,
+    map: &BattleMap,
+    plan: &BattlePlan,
+    occupied: &mut HashSet<BattleHexCoord>,
+    tick: Tick,
+) -> Vec<Arrival> {
+    let (due, waiting): (Vec<Reinforcement>, Vec<Reinforcement>) =
+        pending.drain(..).partition(|r| r.is_due(tick, plan));
+    *pending = waiting;
+
+    let center = BattleHexCoord::new(map.width as i32 / 2, map.height as i32 / 2);
+    let mut arrivals = Vec::new();
+    for Reinforcement {
+        mut formation,
+        edge,
+        ..
+    } in due
+    {
+        let edge_hexes = edge.hexes(map);
+        for unit in &mut formation.units {
+            let mounted = unit.unit_type.is_mounted();
+            let open = edge_hexes.iter().copied().find(|hex| {
+                !occupied.contains(hex)
+                    && map.get_hex(*hex).is_some_and(|h| {
+                        if mounted {
+                            !h.terrain.impassable_for_cavalry()
+                        } else {
+                            !h.terrain.impassable_for_infantry()
+                        }
+                    })
+            });
+            let Some(position) = open.or_else(|| edge_hexes.first().copied()) else {
+                continue;
+            };
+            occupied.insert(position);
+            unit.position = position;
+            unit.facing = facing_toward(position, center);
+        }
+        arrivals.push(Arrival {
+            formation: formation.id,
+            name: formation.name.clone(),
+            edge,
+            units: formation.units.len(),
+        });
+        army.formations.push(formation);
+    }
+    arrivals
+}
+
+/// The direction from `from` that steps closest to `to`
+fn facing_toward(from: BattleHexCoord, to: BattleHexCoord) -> HexDirection {
+    HexDirection::all()
+        .into_iter()
+        .min_by_key(|d| {
+            let step = d.offset();
+            BattleHexCoord::new(from.q + step.q, from.r + step.r).distance(&to)
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::planning::{GoCode, GoCodeTrigger};
+    use crate::battle::terrain::BattleTerrain;
+    use crate::battle::unit_type::UnitType;
+    use crate::battle::units::{ArmyId, BattleUnit, Element, UnitId};
+    use crate::core::types::EntityId;
+
+    fn column(units: usize) -> BattleFormation {
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        formation.name = "Relief column".into();
+        for _ in 0..units {
+            let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+            unit.elements.push(Element::new(vec![EntityId::new(); 20]));
+            formation.units.push(unit);
+        }
+        formation
+    }
+
+    #[test]
+    fn test_arrives_on_schedule_at_open_edge_hexes() {
+        let mut map = BattleMap::new(11, 11);
+        map.set_terrain(BattleHexCoord::new(5, 10), BattleTerrain::DeepWater);
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let plan = BattlePlan::new();
+        let mut pending = vec![Reinforcement::new(
+            column(3),
+            MapEdge::South,
+            ArrivalTrigger::AtTick(20),
+        )];
+        let mut occupied = HashSet::from([BattleHexCoord::new(4, 10)]);
+
+        let arrivals =
+            arrive_reinforcements(&mut pending, &mut army, &map, &plan, &mut occupied, 19);
+        assert!(arrivals.is_empty());
+        assert_eq!(pending[0].ticks_until(19), Some(1));
+
+        let arrivals =
+            arrive_reinforcements(&mut pending, &mut army, &map, &plan, &mut occupied, 20);
+        assert_eq!(arrivals.len(), 1);
+        assert_eq!(arrivals[0].units, 3);
+        assert!(pending.is_empty());
+
+        let positions: Vec<BattleHexCoord> = army.formations[0]
+            .units
+            .iter()
+            .map(|u| u.position)
+            .collect();
+        assert!(positions.iter().all(|p| p.r == 10));
+        assert!(!positions.contains(&BattleHexCoord::new(5, 10)));
+        assert!(!positions.contains(&BattleHexCoord::new(4, 10)));
+        assert!(positions.contains(&BattleHexCoord::new(6, 10)));
+    }
+
+    #[test]
+    fn test_go_code_calls_them_in() {
+        let map = BattleMap::new(10, 10);
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut plan = BattlePlan::new();
+        let go_code = GoCode::new("HAMMER".into(), GoCodeTrigger::Manual);
+        let id = go_code.id;
+        plan.go_codes.push(go_code);
+        let mut pending = vec![Reinforcement::new(
+            column(1),
+            MapEdge::West,
+            ArrivalTrigger::GoCode(id),
+        )];
+        assert_eq!(pending[0].ticks_until(0), None);
+
+        let mut occupied = HashSet::new();
+        arrive_reinforcements(&mut pending, &mut army, &map, &plan, &mut occupied, 100);
+        assert_eq!(pending.len(), 1);
+
+        plan.go_codes[0].triggered = true;
+        let arrivals =
+            arrive_reinforcements(&mut pending, &mut army, &map, &plan, &mut occupied, 101);
+        assert_eq!(arrivals[0].edge, MapEdge::West);
+        assert_eq!(army.formations[0].units[0].position.q, 0);
+    }
+}
@@ -1,12 +1,27 @@
 //! Mass combat resolution at different LOD levels
 //!
 //! Entity-level simulation using the `combat` crate.
+//!
+//! Cavalry that gallop at least `CHARGE_MIN_RUN` hexes of open ground before
+//! contact strike with the shock of a charge (a worse one from behind). Pikes
+//! and spears standing their ground, or any infantry braced for it, throw the
+//! charge back instead: the riders lose men and order and stay `Disordered`
+//! for `CHARGE_DISORDER_TICKS`. Formed cavalry meet a charge with one of
+//! their own, and neither side's shock tells.
 
 use std::collections::HashMap;
 
-use crate::battle::units::BattleUnit;
+use rand::Rng;
+
+use crate::battle::constants::{
+    CHARGE_DISORDER_TICKS, CHARGE_MIN_RUN, CHARGE_REPULSE_COHESION, CHARGE_REPULSE_LOSSES,
+    CHARGE_REPULSE_STRESS,
+};
+use crate::battle::engagement::is_flanked;
 use crate::battle::unit_type::UnitType;
-use crate::combat::resolution::{resolve_exchange, resolve_hit, select_hit_zone, Combatant};
+use crate::battle::units::{BattleUnit, UnitStance};
+use crate::combat::cache::ExchangeCache;
+use crate::combat::resolution::{resolve_hit, select_hit_zone};
 use crate::combat::state::CombatState;
 use crate::combat::{CombatStance, Edge, Mass, Reach, WeaponProperties, WeaponSpecial};
 use crate::core::types::EntityId;
@@ -40,6 +55,24 @@ pub struct ShockResult {
     pub triggered_break_check: bool,
 }
 
+/// How a charge went home
+#[derive(Debug, Clone)]
+pub enum ChargeOutcome {
+    /// Struck with the full shock of the charge
+    Shock(ShockResult),
+    /// Met at the gallop by the target's own horsemen; neither charge tells
+    Countercharged,
+    /// Thrown back by a braced line; the charger falls into disorder
+    Repulsed { casualties: u32, stress: f32 },
+}
+
+/// What one side has lost so far in a round of combat
+#[derive(Debug, Default)]
+struct RoundLosses {
+    casualties: u32,
+    stress: f32,
+}
+
 /// Helper to get active entities (not dead/incapacitated)
 fn get_active_entities(
     unit: &BattleUnit,
@@ -72,10 +105,16 @@ fn get_active_entities(
 }
 
 /// Resolve unit-level combat using entity simulation
+///
+/// Exchange outcomes are looked up in `cache`, which should live as long as
+/// the battle so repeated matchups across ticks are resolved only once.
+/// Rear-rank shooters pick their targets and roll to hit with `rng`.
 pub fn resolve_unit_combat(
     attacker: &BattleUnit,
     defender: &BattleUnit,
     entity_states: &mut HashMap<EntityId, CombatState>,
+    cache: &mut ExchangeCache,
+    rng: &mut impl Rng,
 ) -> UnitCombatResult {
     // 1. Gather active entities
     let attacker_ids = get_active_entities(attacker, entity_states);
@@ -87,10 +126,8 @@ pub fn resolve_unit_combat(
     let combat_width = attacker_ids.len().min(defender_ids.len()).min(max_width);
 
     // Track results
-    let mut attacker_casualties = 0;
-    let mut defender_casualties = 0;
-    let mut attacker_stress = 0.0;
-    let mut defender_stress = 0.0;
+    let mut attacker_losses = RoundLosses::default();
+    let mut defender_losses = RoundLosses::default();
     
     // Track engaged entities to prevent them from firing ranged
     let mut engaged_attackers = std::collections::HashSet::new();
@@ -108,11 +145,10 @@ pub fn resolve_unit_combat(
             att_id, 
             def_id, 
             entity_states, 
+            cache,
             false, // Not flanking
-            &mut attacker_casualties,
-            &mut defender_casualties,
-            &mut attacker_stress,
-            &mut defender_stress
+            &mut attacker_losses,
+            &mut defender_losses,
         );
     }
 
@@ -145,11 +181,10 @@ pub fn resolve_unit_combat(
                     att_id, 
                     def_id, 
                     entity_states, 
+                    cache,
                     true, // Support attack - safer for attacker
-                    &mut attacker_casualties,
-                    &mut defender_casualties,
-                    &mut attacker_stress,
-                    &mut defender_stress
+                    &mut attacker_losses,
+                    &mut defender_losses,
                 );
             }
         }
@@ -181,11 +216,10 @@ pub fn resolve_unit_combat(
                     def_id, // Defender is attacker in this exchange
                     att_id, 
                     entity_states, 
+                    cache,
                     true,
-                    &mut defender_casualties, // Swapped because func assumes arg1 is attacker
-                    &mut attacker_casualties,
-                    &mut defender_stress,
-                    &mut attacker_stress
+                    &mut defender_losses, // Swapped because func assumes arg1 is attacker
+                    &mut attacker_losses,
                 );
             }
         }
@@ -211,11 +245,10 @@ pub fn resolve_unit_combat(
                 att_id,
                 def_id,
                 entity_states,
+                cache,
                 true, // Flanking/Ganging up
-                &mut attacker_casualties,
-                &mut defender_casualties,
-                &mut attacker_stress,
-                &mut defender_stress
+                &mut attacker_losses,
+                &mut defender_losses,
             );
         }
     }
@@ -228,8 +261,8 @@ pub fn resolve_unit_combat(
             &engaged_attackers, 
             entity_states,
             &attacker.unit_type,
-            &mut defender_casualties,
-            &mut defender_stress
+            &mut defender_losses,
+            rng,
         );
     }
 
@@ -240,25 +273,25 @@ pub fn resolve_unit_combat(
             &engaged_defenders, 
             entity_states,
             &defender.unit_type,
-            &mut attacker_casualties,
-            &mut attacker_stress
+            &mut attacker_losses,
+            rng,
         );
     }
 
     // Determine pressure shift based on casualties
-    let pressure_shift = if defender_casualties > attacker_casualties {
+    let pressure_shift = if defender_losses.casualties > attacker_losses.casualties {
         0.05
-    } else if attacker_casualties > defender_casualties {
+    } else if attacker_losses.casualties > defender_losses.casualties {
         -0.05
     } else {
         0.0
     };
 
     UnitCombatResult {
-        attacker_casualties,
-        defender_casualties,
-        attacker_stress_delta: attacker_stress,
-        defender_stress_delta: defender_stress,
+        attacker_casualties: attacker_losses.casualties,
+        defender_casualties: defender_losses.casualties,
+        attacker_stress_delta: attacker_losses.stress,
+        defender_stress_delta: defender_losses.stress,
         attacker_fatigue_delta: 0.05, // Fixed fatigue per combat round
         defender_fatigue_delta: 0.05,
         pressure_shift,
@@ -270,52 +303,37 @@ fn resolve_entity_exchange(
     att_id: EntityId,
     def_id: EntityId,
     states: &mut HashMap<EntityId, CombatState>,
+    cache: &mut ExchangeCache,
     is_support: bool, // If true, attacker is safer (reach or flank)
-    att_casualties: &mut u32,
-    def_casualties: &mut u32,
-    att_stress: &mut f32,
-    def_stress: &mut f32,
+    att_losses: &mut RoundLosses,
+    def_losses: &mut RoundLosses,
 ) {
-    // Need to extract properties to avoid double mutable borrow
-    // We clone the needed parts of state to create Combatants
-    let (att_combatant, def_combatant) = {
+    // Both sides' loadouts, with the attacker defensive on a support attack
+    // to minimize return hits
+    let result = {
         let att_state = states.get(&att_id).unwrap();
         let def_state = states.get(&def_id).unwrap();
-
-        let mut att_c = Combatant {
-            weapon: att_state.weapon.clone(),
-            armor: att_state.armor.clone(),
-            stance: CombatStance::Pressing, // Attacker presses
-            skill: att_state.skill.clone(),
+        let att_stance = if is_support {
+            CombatStance::Defensive
+        } else {
+            CombatStance::Pressing
         };
-        
-        // If support attack, assume defensive stance for attacker to minimize return hits
-        if is_support {
-            att_c.stance = CombatStance::Defensive;
-        }
-
-        let def_c = Combatant {
-            weapon: def_state.weapon.clone(),
-            armor: def_state.armor.clone(),
-            stance: CombatStance::Defensive, // Defender defends
-            skill: def_state.skill.clone(),
-        };
-
-        (att_c, def_c)
+        let att = cache.loadout(&att_state.weapon, &att_state.armor, att_state.skill.level);
+        let def = cache.loadout(&def_state.weapon, &def_state.armor, def_state.skill.level);
+        cache
+            .exchange((att, att_stance), (def, CombatStance::Defensive))
+            .clone()
     };
 
-    // Resolve
-    let result = resolve_exchange(&att_combatant, &def_combatant);
-
     // Apply results
     if let Some(wound) = result.attacker_wound {
         if let Some(state) = states.get_mut(&att_id) {
             let was_dead = state.is_dead() || state.is_incapacitated();
             state.wounds.push(wound);
             if !was_dead && (state.is_dead() || state.is_incapacitated()) {
-                *att_casualties += 1;
+                att_losses.casualties += 1;
             }
-            *att_stress += 0.01;
+            att_losses.stress += 0.01;
         }
     }
 
@@ -324,9 +342,9 @@ fn resolve_entity_exchange(
             let was_dead = state.is_dead() || state.is_incapacitated();
             state.wounds.push(wound);
             if !was_dead && (state.is_dead() || state.is_incapacitated()) {
-                *def_casualties += 1;
+                def_losses.casualties += 1;
             }
-            *def_stress += 0.01;
+            def_losses.stress += 0.01;
         }
     }
 }
@@ -338,8 +356,8 @@ fn resolve_ranged_attacks(
     engaged: &std::collections::HashSet<EntityId>,
     states: &mut HashMap<EntityId, CombatState>,
     unit_type: &UnitType,
-    casualties: &mut u32,
-    stress: &mut f32,
+    losses: &mut RoundLosses,
+    rng: &mut impl Rng,
 ) {
     if defenders.is_empty() {
         return;
@@ -369,7 +387,7 @@ fn resolve_ranged_attacks(
 
         // Fire!
         // Pick random target
-        let target_idx = rand::random::<usize>() % defenders.len();
+        let target_idx = rng.gen_range(0..defenders.len());
         let target_id = defenders[target_idx];
 
         // Resolve hit
@@ -385,7 +403,7 @@ fn resolve_ranged_attacks(
         // `resolve_hit` assumes a hit. We need a miss chance.
         // Simple accuracy check: 50% base +/- skill
         let hit_chance = 0.5; // Placeholder
-        if rand::random::<f32>() > hit_chance {
+        if rng.gen::<f32>() > hit_chance {
             continue; 
         }
 
@@ -397,9 +415,9 @@ fn resolve_ranged_attacks(
             let was_dead = state.is_dead() || state.is_incapacitated();
             state.wounds.push(wound);
             if !was_dead && (state.is_dead() || state.is_incapacitated()) {
-                *casualties += 1;
+                losses.casualties += 1;
             }
-            *stress += 0.005;
+            losses.stress += 0.005;
         }
     }
 }
@@ -450,6 +468,92 @@ pub fn resolve_shock_attack(
     }
 }
 
+/// Does a unit strike with the shock of a charge? Cavalry at the gallop
+/// with a long enough run over open ground behind them
+pub fn has_charge_impetus(unit: &BattleUnit) -> bool {
+    unit.unit_type.can_charge()
+        && unit.stance == UnitStance::Charging
+        && unit.charge_run >= CHARGE_MIN_RUN
+}
+
+/// Can a unit stop a charge dead? Anyone braced for one, or pikes and
+/// spears standing their ground
+pub fn stops_charges(unit: &BattleUnit) -> bool {
+    let reach = unit.unit_type.default_properties().avg_weapon.reach;
+    match unit.stance {
+        UnitStance::Braced => true,
+        UnitStance::Formed | UnitStance::Alert | UnitStance::Engaged => {
+            matches!(reach, Reach::Long | Reach::Pike)
+        }
+        _ => false,
+    }
+}
+
+/// Can a unit meet a charge with one of its own? Cavalry already at the
+/// gallop, or formed and ready to spur out
+pub fn can_countercharge(unit: &BattleUnit) -> bool {
+    unit.unit_type.can_charge()
+        && matches!(
+            unit.stance,
+            UnitStance::Charging | UnitStance::Formed | UnitStance::Alert
+        )
+}
+
+/// Resolve the moment a charge strikes home
+///
+/// None if the charger has no charge to deliver.
+pub fn resolve_charge(charger: &BattleUnit, target: &BattleUnit) -> Option<ChargeOutcome> {
+    if !has_charge_impetus(charger) {
+        return None;
+    }
+
+    let outcome = if stops_charges(target) {
+        ChargeOutcome::Repulsed {
+            casualties: CHARGE_REPULSE_LOSSES.min(charger.effective_strength() as u32),
+            stress: CHARGE_REPULSE_STRESS,
+        }
+    } else if can_countercharge(target) {
+        ChargeOutcome::Countercharged
+    } else {
+        let shock_type = if is_flanked(target, &[charger.position]) {
+            crate::combat::ShockType::RearCharge
+        } else {
+            crate::combat::ShockType::CavalryCharge
+        };
+        ChargeOutcome::Shock(resolve_shock_attack(charger, target, shock_type))
+    };
+    Some(outcome)
+}
+
+/// Throw a repulsed charger into disorder
+pub fn fall_into_disorder(unit: &mut BattleUnit, tick: u64) {
+    unit.stance = UnitStance::Disordered;
+    unit.disordered_since = Some(tick);
+    unit.lose_cohesion(CHARGE_REPULSE_COHESION);
+    unit.charge_run = 0;
+}
+
+/// Re-form a disordered unit once `CHARGE_DISORDER_TICKS` have passed
+///
+/// Returns true if it re-formed this tick.
+pub fn reform_from_disorder(unit: &mut BattleUnit, tick: u64) -> bool {
+    let Some(since) = unit.disordered_since else {
+        return false;
+    };
+    if unit.stance != UnitStance::Disordered {
+        // Broken, or swept up in the fighting, before it could re-form
+        unit.disordered_since = None;
+        return false;
+    }
+    if tick < since + CHARGE_DISORDER_TICKS {
+        return false;
+    }
+    unit.stance = UnitStance::Formed;
+    unit.disordered_since = None;
+    unit.cohesion = (unit.cohesion + CHARGE_REPULSE_COHESION).min(1.0);
+    true
+}
+
 /// Determine LOD for a combat
 pub fn determine_combat_lod(
     total_combatants: usize,
@@ -470,8 +574,107 @@ pub fn determine_combat_lod(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::battle::units::Element;
+    use crate::battle::hex::{BattleHexCoord, HexDirection};
+    use crate::battle::units::{Element, UnitId};
     use crate::core::types::EntityId;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn unit_in(unit_type: UnitType, stance: UnitStance, soldiers: usize) -> BattleUnit {
+        let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+        unit.elements
+            .push(Element::new(vec![EntityId::new(); soldiers]));
+        unit.stance = stance;
+        unit
+    }
+
+    fn galloping(unit_type: UnitType) -> BattleUnit {
+        let mut unit = unit_in(unit_type, UnitStance::Charging, 40);
+        unit.charge_run = CHARGE_MIN_RUN;
+        unit
+    }
+
+    #[test]
+    fn test_charge_needs_a_run_over_open_ground() {
+        let mut knights = galloping(UnitType::HeavyCavalry);
+        assert!(has_charge_impetus(&knights));
+
+        knights.charge_run = CHARGE_MIN_RUN - 1;
+        assert!(!has_charge_impetus(&knights));
+        let levy = unit_in(UnitType::Levy, UnitStance::Formed, 50);
+        assert!(resolve_charge(&knights, &levy).is_none());
+
+        // Infantry running in is no charge
+        let mut footmen = galloping(UnitType::Infantry);
+        footmen.charge_run = 10;
+        assert!(!has_charge_impetus(&footmen));
+    }
+
+    #[test]
+    fn test_braced_infantry_and_pikes_throw_back_a_charge() {
+        let knights = galloping(UnitType::HeavyCavalry);
+        let repulsed = |target: &BattleUnit| {
+            matches!(
+                resolve_charge(&knights, target),
+                Some(ChargeOutcome::Repulsed { .. })
+            )
+        };
+
+        let spears = unit_in(UnitType::Spearmen, UnitStance::Formed, 50);
+        let braced = unit_in(UnitType::Infantry, UnitStance::Braced, 50);
+        assert!(repulsed(&spears) && repulsed(&braced));
+        // Caught on the move, or unready, they take the shock
+        let marching = unit_in(UnitType::Spearmen, UnitStance::Moving, 50);
+        assert!(!repulsed(&marching));
+        let infantry = unit_in(UnitType::Infantry, UnitStance::Formed, 50);
+        let Some(ChargeOutcome::Shock(shock)) = resolve_charge(&knights, &infantry) else {
+            panic!("formed swordsmen should take the shock of a charge");
+        };
+        assert!(shock.immediate_casualties > 0);
+
+        // Formed cavalry ride out to meet it
+        let lancers = unit_in(UnitType::Cavalry, UnitStance::Formed, 40);
+        assert!(matches!(
+            resolve_charge(&knights, &lancers),
+            Some(ChargeOutcome::Countercharged)
+        ));
+    }
+
+    #[test]
+    fn test_a_charge_from_behind_hits_harder() {
+        let mut knights = galloping(UnitType::HeavyCavalry);
+        let mut levy = unit_in(UnitType::Levy, UnitStance::Formed, 200);
+        levy.position = BattleHexCoord::new(5, 5);
+        levy.facing = HexDirection::default();
+
+        let front = levy.facing.offset();
+        knights.position = BattleHexCoord::new(5 + front.q, 5 + front.r);
+        let Some(ChargeOutcome::Shock(head_on)) = resolve_charge(&knights, &levy) else {
+            panic!("levy should take the shock of a charge");
+        };
+        let rear = levy.facing.opposite().offset();
+        knights.position = BattleHexCoord::new(5 + rear.q, 5 + rear.r);
+        let Some(ChargeOutcome::Shock(from_behind)) = resolve_charge(&knights, &levy) else {
+            panic!("levy should take the shock of a charge");
+        };
+        assert!(from_behind.immediate_casualties > head_on.immediate_casualties);
+        assert!(from_behind.stress_spike > head_on.stress_spike);
+    }
+
+    #[test]
+    fn test_repulsed_charge_reforms_after_a_while() {
+        let mut knights = galloping(UnitType::HeavyCavalry);
+        fall_into_disorder(&mut knights, 100);
+        assert_eq!(knights.stance, UnitStance::Disordered);
+        assert_eq!(knights.charge_run, 0);
+        assert!(knights.cohesion < 1.0);
+
+        assert!(!reform_from_disorder(&mut knights, 100 + CHARGE_DISORDER_TICKS - 1));
+        assert_eq!(knights.stance, UnitStance::Disordered);
+        assert!(reform_from_disorder(&mut knights, 100 + CHARGE_DISORDER_TICKS));
+        assert_eq!(knights.stance, UnitStance::Formed);
+        assert_eq!(knights.cohesion, 1.0);
+    }
 
     #[test]
     fn test_resolve_combat_casualties() {
@@ -494,7 +697,14 @@ mod tests {
         eprintln!("Defender element 0 entities: {:?}", defender.elements[0].entities.len());
 
         // Run combat
-        let _result = resolve_unit_combat(&attacker, &defender, &mut entity_states);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let _result = resolve_unit_combat(
+            &attacker,
+            &defender,
+            &mut entity_states,
+            &mut ExchangeCache::new(),
+            &mut rng,
+        );
 
         eprintln!("Entity states after combat: {}", entity_states.len());
 
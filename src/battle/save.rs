@@ -0,0 +1,250 @@
+//! Battle saves - the whole battle state on disk, under a schema version
+//!
+//! Orders, plans, and go-codes are saved as they are, so their enums are part
+//! of the save format. To let those enums change without breaking old saves,
+//! every save is tagged with `BATTLE_SCHEMA_VERSION`, and the fields holding
+//! order types, wait conditions, go-code triggers, and contingencies decode
+//! tolerantly: a variant this build does not know becomes an inert `Unknown`
+//! (an order that does nothing, a trigger that never fires) and a warning is
+//! logged, instead of the whole save failing to load.
+
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+use crate::battle::execution::BattleState;
+
+/// Battle save schema version, bumped whenever saved battle state changes shape
+pub const BATTLE_SCHEMA_VERSION: u32 = 9;
+
+/// Errors from saving or loading a battle
+#[derive(Error, Debug)]
+pub enum BattleSaveError {
+    /// Reading or writing the file failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The save could not be encoded or decoded
+    #[error("Battle save format error: {0}")]
+    FormatError(#[from] serde_json::Error),
+    /// The save was written by a newer build than this one
+    #[error("Battle save schema {found} is newer than supported ({BATTLE_SCHEMA_VERSION})")]
+    UnsupportedVersion { found: u32 },
+}
+
+#[derive(Serialize)]
+struct BattleSaveRef<'a> {
+    version: u32,
+    battle: &'a BattleState,
+}
+
+#[derive(Deserialize)]
+struct BattleSave {
+    battle: BattleState,
+}
+
+/// Encode a battle as a save string
+pub fn battle_to_string(battle: &BattleState) -> Result<String, BattleSaveError> {
+    Ok(serde_json::to_string(&BattleSaveRef {
+        version: BATTLE_SCHEMA_VERSION,
+        battle,
+    })?)
+}
+
+/// Decode a battle from a save string
+///
+/// Saves from this schema or any older one load; unknown variants in them
+/// are made inert. Saves from a newer schema are refused.
+pub fn battle_from_str(save: &str) -> Result<BattleState, BattleSaveError> {
+    #[derive(Deserialize)]
+    struct Header {
+        // Saves from before versioning carry no tag
+        #[serde(default)]
+        version: u32,
+    }
+    let header: Header = serde_json::from_str(save)?;
+    if header.version > BATTLE_SCHEMA_VERSION {
+        return Err(BattleSaveError::UnsupportedVersion {
+            found: header.version,
+        });
+    }
+    Ok(serde_json::from_str::<BattleSave>(save)?.battle)
+}
+
+/// Save a battle to a file, replacing it if it exists
+pub fn save_battle(battle: &BattleState, path: impl AsRef<Path>) -> Result<(), BattleSaveError> {
+    fs::write(path, battle_to_string(battle)?)?;
+    Ok(())
+}
+
+/// Load a battle from a file written by `save_battle`
+pub fn load_battle(path: impl AsRef<Path>) -> Result<BattleState, BattleSaveError> {
+    battle_from_str(&fs::read_to_string(path)?)
+}
+
+/// An enum that can stand in an inert placeholder for a variant it does not know
+pub trait Inert {
+    /// The placeholder for an unrecognised variant, named `tag` in the save
+    fn inert(tag: String) -> Self;
+}
+
+/// The variant name of an externally tagged enum value
+fn variant_tag(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(tag) => tag.clone(),
+        serde_json::Value::Object(map) if map.len() == 1 => map.keys().next().unwrap().clone(),
+        other => other.to_string(),
+    }
+}
+
+fn decode_or_inert<T: DeserializeOwned + Inert>(value: serde_json::Value) -> T {
+    match T::deserialize(&value) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            let tag = variant_tag(&value);
+            tracing::warn!(
+                "Unknown {} '{}' in battle save, made inert: {}",
+                std::any::type_name::<T>()
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or("variant"),
+                tag,
+                e
+            );
+            T::inert(tag)
+        }
+    }
+}
+
+/// Decode an enum field, making unknown variants inert
+///
+/// Use with `#[serde(deserialize_with = "crate::battle::save::or_inert")]`.
+pub fn or_inert<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: DeserializeOwned + Inert,
+    D: Deserializer<'de>,
+{
+    Ok(decode_or_inert(serde_json::Value::deserialize(
+        deserializer,
+    )?))
+}
+
+/// As `or_inert`, for optional enum fields
+pub fn option_or_inert<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: DeserializeOwned + Inert,
+    D: Deserializer<'de>,
+{
+    Ok(Option::<serde_json::Value>::deserialize(deserializer)?.map(decode_or_inert))
+}
+
+/// Serde adapter for maps keyed by hex: written as a list of `[hex, value]`
+/// pairs sorted by hex, since JSON object keys must be strings
+///
+/// Use with `#[serde(with = "crate::battle::save::sorted_hex_map")]`.
+pub mod sorted_hex_map {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::battle::hex::BattleHexCoord;
+
+    pub fn serialize<V, S>(
+        map: &HashMap<BattleHexCoord, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        V: Serialize,
+        S: Serializer,
+    {
+        let mut pairs: Vec<(&BattleHexCoord, &V)> = map.iter().collect();
+        pairs.sort_by(|a, b| a.0.cmp(b.0));
+        serializer.collect_seq(pairs)
+    }
+
+    pub fn deserialize<'de, V, D>(deserializer: D) -> Result<HashMap<BattleHexCoord, V>, D::Error>
+    where
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(BattleHexCoord, V)>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::battle_map::BattleMap;
+    use crate::battle::courier::{Order, OrderType};
+    use crate::battle::orders::apply_order;
+    use crate::battle::planning::{GoCode, GoCodeTrigger};
+    use crate::battle::triggers::evaluate_gocode_trigger;
+    use crate::battle::units::{Army, ArmyId, UnitId};
+    use crate::core::types::EntityId;
+
+    fn battle() -> BattleState {
+        let map = BattleMap::new(10, 10);
+        let friendly = Army::new(ArmyId::new(), EntityId::new());
+        let enemy = Army::new(ArmyId::new(), EntityId::new());
+        BattleState::new(map, friendly, enemy)
+    }
+
+    #[test]
+    fn test_battle_save_round_trips_and_refuses_newer_schema() {
+        let mut state = battle();
+        state.tick = 42;
+        state
+            .friendly_plan
+            .go_codes
+            .push(GoCode::new("HAMMER".into(), GoCodeTrigger::Time(50)));
+
+        let save = battle_to_string(&state).unwrap();
+        let loaded = battle_from_str(&save).unwrap();
+        assert_eq!(loaded.tick, 42);
+        assert_eq!(loaded.friendly_plan.go_codes[0].name, "HAMMER");
+
+        let newer = save.replacen(
+            &format!("\"version\":{}", BATTLE_SCHEMA_VERSION),
+            &format!("\"version\":{}", BATTLE_SCHEMA_VERSION + 1),
+            1,
+        );
+        assert!(matches!(
+            battle_from_str(&newer),
+            Err(BattleSaveError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_variants_become_inert() {
+        let order: Order = serde_json::from_str(
+            r#"{"order_type":{"Feint":{"q":1,"r":2}},"target":{"Unit":"00000000-0000-0000-0000-000000000001"},"issued_at":3}"#,
+        )
+        .unwrap();
+        assert!(matches!(&order.order_type, OrderType::Unknown(tag) if tag == "Feint"));
+
+        // An unknown order changes nothing
+        let mut state = battle();
+        let result = apply_order(&order, &mut state.friendly_army, &mut state.friendly_plan);
+        assert!(!result.success);
+        assert!(state.friendly_plan.waypoint_plans.is_empty());
+
+        let go_code: GoCode = serde_json::from_str(&format!(
+            r#"{{"id":"{}","name":"ANVIL","trigger":"OnHorn","subscribers":[],"triggered":false}}"#,
+            UnitId::new().0
+        ))
+        .unwrap();
+        assert!(matches!(&go_code.trigger, GoCodeTrigger::Unknown(tag) if tag == "OnHorn"));
+        assert!(!evaluate_gocode_trigger(&go_code, 1_000, &[]));
+
+        // Known variants still decode; inert ones save and load as themselves
+        let again: Order = serde_json::from_str(&serde_json::to_string(&order).unwrap()).unwrap();
+        assert!(matches!(&again.order_type, OrderType::Unknown(tag) if tag == "Feint"));
+        let hold: Order =
+            serde_json::from_str(&serde_json::to_string(&Order::hold(UnitId::new())).unwrap())
+                .unwrap();
+        assert!(matches!(hold.order_type, OrderType::HoldPosition));
+    }
+}
@@ -0,0 +1,525 @@
+//! Sieges - storming walls and battering gates
+//!
+//! Walls and gates (`Fortification`s on the map) bar the way until breached.
+//! There are two ways past them, both ordered by courier like anything else:
+//!
+//! - `RamAssault`: the unit marches to the foot of a wall or gate and
+//!   batters it, `RAM_DAMAGE_PER_SOLDIER` HP a tick for every soldier left
+//!   standing (`ENGINEER_RAM_BONUS` more for engineers). Once it is
+//!   breached the rubble is open to anyone, and the rammers march in.
+//! - `LadderAssault`: the unit climbs a stretch of wall, costing
+//!   `LADDER_CLIMB_COST` movement progress on top of the ground, and stands
+//!   on it. Gates cannot be scaled.
+//!
+//! Either way, the assault is made under the defenders' noses: every enemy
+//! unit on or next to the target costs the stormers losses and stress each
+//! tick they spend at its foot. Horsemen do neither.
+//!
+//! The slow way is underneath. A `Sap` order sets a unit digging a `Mine`
+//! from where it stands to under a wall, `SAP_RATE_PER_SOLDIER` hexes of
+//! tunnel a tick for each soldier (`ENGINEER_SAP_BONUS` more for engineers),
+//! for as long as it stays at the entrance. When the tunnel reaches the
+//! wall, the props are fired: the wall comes down, taking whoever is on or
+//! beside it with it.
+//!
+//! The defenders cannot see a mine. Every unit of theirs on or beside a wall
+//! is a listening post, with a `LISTEN_CHANCE` each tick of hearing a tunnel
+//! head within `LISTENING_RANGE`. Once one is heard, the listeners at the
+//! wall it is dug toward countermine out to meet it; if the tunnels meet
+//! before the mine is sprung, the sappers are broken in on and the mine is
+//! lost. The sappers are not told they were heard.
+
+use serde::{Deserialize, Serialize};
+
+use crate::battle::battle_map::BattleMap;
+use crate::battle::constants::{
+    ASSAULT_STRESS, BREAK_IN_LOSSES, BREAK_IN_STRESS, ENGINEER_LISTEN_BONUS, ENGINEER_RAM_BONUS,
+    ENGINEER_SAP_BONUS, LADDER_LOSSES, LISTENING_RANGE, LISTEN_CHANCE, RAM_CREW_LOSSES,
+    RAM_DAMAGE_PER_SOLDIER, SAP_RATE_PER_SOLDIER, SIEGE_GATE_SEARCH_RADIUS,
+};
+use crate::battle::courier::{Order, OrderTarget, OrderType};
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::pathfinding::{find_assault_path, find_path};
+use crate::battle::terrain::{BreachState, FortificationKind};
+use crate::battle::unit_type::UnitType;
+use crate::battle::units::{Army, BattleUnit, UnitId};
+
+/// A wall or gate a unit has been ordered to storm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SiegeAssault {
+    /// Climb the wall at this hex
+    Ladders(BattleHexCoord),
+    /// Batter down the wall or gate at this hex
+    Ram(BattleHexCoord),
+}
+
+impl SiegeAssault {
+    /// The wall or gate under assault
+    pub fn target(&self) -> BattleHexCoord {
+        match self {
+            SiegeAssault::Ladders(coord) | SiegeAssault::Ram(coord) => *coord,
+        }
+    }
+}
+
+/// What one side's assaults achieved this tick
+#[derive(Debug, Clone, Default)]
+pub struct SiegeOutcome {
+    /// Walls and gates battered down
+    pub breached: Vec<BattleHexCoord>,
+    /// Units that gained the top of a wall
+    pub scaled: Vec<UnitId>,
+}
+
+/// A tunnel dug toward an enemy wall
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mine {
+    /// The unit digging it
+    pub sappers: UnitId,
+    /// Where the tunnel starts
+    pub entrance: BattleHexCoord,
+    /// The wall it is dug under
+    pub target: BattleHexCoord,
+    /// Hexes of tunnel dug so far
+    pub dug: f32,
+    /// Whether the defenders have heard the digging
+    pub detected: bool,
+    /// Hexes of countermine the defenders have dug out to meet it
+    pub countermined: f32,
+}
+
+impl Mine {
+    pub fn new(sappers: UnitId, entrance: BattleHexCoord, target: BattleHexCoord) -> Self {
+        Self {
+            sappers,
+            entrance,
+            target,
+            dug: 0.0,
+            detected: false,
+            countermined: 0.0,
+        }
+    }
+
+    /// Hexes of tunnel from the entrance to under the wall
+    pub fn length(&self) -> f32 {
+        self.entrance.distance(&self.target) as f32
+    }
+
+    /// Where the digging is now
+    pub fn head(&self) -> BattleHexCoord {
+        let length = self.length();
+        if length <= 0.0 {
+            return self.target;
+        }
+        self.entrance
+            .lerp(&self.target, (self.dug / length).min(1.0))
+    }
+}
+
+/// What one side's mines came to this tick
+#[derive(Debug, Clone, Default)]
+pub struct MiningOutcome {
+    /// Walls brought down by a mine
+    pub sprung: Vec<BattleHexCoord>,
+    /// Walls under which the defenders heard digging
+    pub heard: Vec<BattleHexCoord>,
+    /// Sappers broken in on by a countermine
+    pub broken_into: Vec<UnitId>,
+}
+
+/// Whether a unit of this type can storm walls (anyone but horsemen)
+pub fn can_storm(unit_type: UnitType) -> bool {
+    !unit_type.is_mounted()
+}
+
+/// HP a unit knocks off a wall or gate in a tick of battering
+pub fn ram_damage(unit: &BattleUnit) -> f32 {
+    let bonus = if unit.unit_type == UnitType::Engineers {
+        ENGINEER_RAM_BONUS
+    } else {
+        0.0
+    };
+    unit.effective_strength() as f32 * (RAM_DAMAGE_PER_SOLDIER + bonus)
+}
+
+/// Hexes of tunnel a unit digs in a tick
+pub fn sap_rate(unit: &BattleUnit) -> f32 {
+    let bonus = if unit.unit_type == UnitType::Engineers {
+        ENGINEER_SAP_BONUS
+    } else {
+        0.0
+    };
+    unit.effective_strength() as f32 * (SAP_RATE_PER_SOLDIER + bonus)
+}
+
+/// Chance a tick that a listener hears digging at `head`
+pub fn listening_chance(map: &BattleMap, listener: &BattleUnit, head: BattleHexCoord) -> f32 {
+    let at_the_wall = listener
+        .position
+        .hexes_in_range(1)
+        .into_iter()
+        .any(|coord| map.fortification(coord).is_some());
+    if !listener.can_fight() || !at_the_wall || listener.position.distance(&head) > LISTENING_RANGE
+    {
+        return 0.0;
+    }
+    if listener.unit_type == UnitType::Engineers {
+        LISTEN_CHANCE + ENGINEER_LISTEN_BONUS
+    } else {
+        LISTEN_CHANCE
+    }
+}
+
+/// Dig on every mine one side has under way, let the `defenders` listen for
+/// them and countermine the ones they hear, and spring any that reach their
+/// wall
+///
+/// `roll` gives a number in 0..1 for each chance a listener has. Sappers
+/// broken in on take their losses here; the caller deals out the losses of
+/// defenders caught on a wall that comes down.
+pub fn run_mining(
+    map: &mut BattleMap,
+    army: &mut Army,
+    defenders: &[BattleUnit],
+    mut roll: impl FnMut() -> f32,
+) -> MiningOutcome {
+    let mut outcome = MiningOutcome::default();
+    let mut mines = std::mem::take(&mut army.mines);
+    mines.retain_mut(|mine| {
+        let standing = map
+            .fortification(mine.target)
+            .is_some_and(|f| !f.is_breached());
+        if !standing {
+            return false;
+        }
+
+        // Digging goes on only while the sappers keep at it
+        if let Some(sappers) = army.get_unit(mine.sappers) {
+            if sappers.can_fight() && sappers.position.distance(&mine.entrance) <= 1 {
+                mine.dug += sap_rate(sappers);
+            }
+        }
+
+        let head = mine.head();
+        if !mine.detected {
+            mine.detected = defenders
+                .iter()
+                .any(|d| roll() < listening_chance(map, d, head));
+            if mine.detected {
+                outcome.heard.push(mine.target);
+            }
+        }
+
+        if mine.detected {
+            mine.countermined += defenders
+                .iter()
+                .filter(|d| d.can_fight() && d.position.distance(&mine.target) <= 1)
+                .map(sap_rate)
+                .sum::<f32>();
+            if mine.dug + mine.countermined >= mine.length() && mine.dug < mine.length() {
+                if let Some(sappers) = army.get_unit_mut(mine.sappers) {
+                    sappers.casualties += BREAK_IN_LOSSES;
+                    sappers.stress += BREAK_IN_STRESS;
+                }
+                outcome.broken_into.push(mine.sappers);
+                return false;
+            }
+        }
+
+        if mine.dug >= mine.length() {
+            map.damage_fortification(mine.target, f32::MAX);
+            outcome.sprung.push(mine.target);
+            return false;
+        }
+        true
+    });
+    army.mines = mines;
+    outcome
+}
+
+/// The enemy mines a side knows of: those its listeners have heard
+pub fn heard_mines(enemy: &Army) -> impl Iterator<Item = &Mine> {
+    enemy.mines.iter().filter(|m| m.detected)
+}
+
+/// Press every assault one side has under way
+///
+/// Units at the foot of their target take fire from `defenders` on or next
+/// to it; rammers batter it. A unit that has breached its target, stands on
+/// top of it, or can no longer fight gives up the assault.
+pub fn run_siege(map: &mut BattleMap, army: &mut Army, defenders: &[BattleUnit]) -> SiegeOutcome {
+    let mut outcome = SiegeOutcome::default();
+    for unit in army.formations.iter_mut().flat_map(|f| f.units.iter_mut()) {
+        let Some(assault) = unit.assault else {
+            continue;
+        };
+        let target = assault.target();
+        let standing = map.fortification(target).is_some_and(|f| !f.is_breached());
+        if !standing || !unit.can_fight() {
+            unit.assault = None;
+            continue;
+        }
+        if unit.position == target {
+            outcome.scaled.push(unit.id);
+            unit.assault = None;
+            continue;
+        }
+        if unit.position.distance(&target) > 1 {
+            continue; // Still on the way
+        }
+
+        let on_the_walls = defenders
+            .iter()
+            .filter(|d| d.can_fight() && d.position.distance(&target) <= 1)
+            .count() as u32;
+        unit.stress += ASSAULT_STRESS * on_the_walls as f32;
+        match assault {
+            SiegeAssault::Ram(_) => {
+                unit.casualties += RAM_CREW_LOSSES * on_the_walls;
+                let damage = ram_damage(unit);
+                if map.damage_fortification(target, damage) == Some(BreachState::Breached) {
+                    outcome.breached.push(target);
+                    unit.assault = None;
+                }
+            }
+            SiegeAssault::Ladders(_) => {
+                unit.casualties += LADDER_LOSSES * on_the_walls;
+            }
+        }
+    }
+    outcome
+}
+
+/// The assault that would open the way from `unit` to `goal`, if walls bar it
+///
+/// Only walls nearer the goal than `own_hq` are stormed - the others are
+/// the unit's own side's. Rams go to a closed gate within
+/// `SIEGE_GATE_SEARCH_RADIUS` if there is one, and engineers batter the wall
+/// itself if not; anyone else climbs it.
+pub fn siege_order(
+    map: &BattleMap,
+    unit: &BattleUnit,
+    goal: BattleHexCoord,
+    own_hq: BattleHexCoord,
+) -> Option<Order> {
+    if !can_storm(unit.unit_type) || find_path(map, unit.position, goal, false).is_some() {
+        return None;
+    }
+    let wall = find_assault_path(map, unit.position, goal, false)?
+        .into_iter()
+        .find(|coord| map.is_barred(*coord))?;
+    if wall.distance(&own_hq) < wall.distance(&goal) {
+        return None;
+    }
+
+    let gate = unit
+        .position
+        .hexes_in_range(SIEGE_GATE_SEARCH_RADIUS)
+        .into_iter()
+        .filter(|coord| {
+            map.is_barred(*coord)
+                && map
+                    .fortification(*coord)
+                    .is_some_and(|f| f.kind == FortificationKind::Gate)
+        })
+        .min_by_key(|coord| coord.distance(&unit.position));
+    let order_type = match gate {
+        Some(gate) => OrderType::RamAssault(gate),
+        None if unit.unit_type == UnitType::Engineers => OrderType::RamAssault(wall),
+        None => OrderType::LadderAssault(wall),
+    };
+    Some(Order::new(order_type, OrderTarget::Unit(unit.id), 0))
+}
+
+/// Raise a curtain wall down column `q`, the whole height of the map, with
+/// a gate at row `gate_r`
+pub fn raise_curtain_wall(map: &mut BattleMap, q: i32, gate_r: i32) {
+    for r in 0..map.height as i32 {
+        let coord = BattleHexCoord::new(q, r);
+        if r == gate_r {
+            map.build_gate(coord);
+        } else {
+            map.build_wall(coord);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::units::{ArmyId, BattleFormation, Element, FormationId};
+    use crate::core::types::EntityId;
+
+    fn unit_at(unit_type: UnitType, position: BattleHexCoord, soldiers: usize) -> BattleUnit {
+        let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+        unit.elements
+            .push(Element::new(vec![EntityId::new(); soldiers]));
+        unit.position = position;
+        unit
+    }
+
+    fn army_of(unit: BattleUnit) -> Army {
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        formation.units.push(unit);
+        army.formations.push(formation);
+        army
+    }
+
+    #[test]
+    fn test_ram_batters_gate_down_under_fire() {
+        let mut map = BattleMap::new(10, 10);
+        raise_curtain_wall(&mut map, 5, 5);
+        let gate = BattleHexCoord::new(5, 5);
+
+        let mut rammers = unit_at(UnitType::Engineers, BattleHexCoord::new(4, 5), 50);
+        rammers.assault = Some(SiegeAssault::Ram(gate));
+        let mut army = army_of(rammers);
+        let defenders = vec![unit_at(UnitType::Infantry, BattleHexCoord::new(6, 5), 50)];
+
+        let mut breached = false;
+        for _ in 0..100 {
+            let outcome = run_siege(&mut map, &mut army, &defenders);
+            if outcome.breached.contains(&gate) {
+                breached = true;
+                break;
+            }
+        }
+        assert!(breached);
+        assert!(!map.is_barred(gate));
+        let rammers = &army.formations[0].units[0];
+        assert!(rammers.assault.is_none());
+        assert!(rammers.casualties > 0);
+        assert!(rammers.stress > 0.0);
+    }
+
+    #[test]
+    fn test_ladders_gain_the_wall() {
+        let mut map = BattleMap::new(10, 10);
+        raise_curtain_wall(&mut map, 5, 5);
+        let wall = BattleHexCoord::new(5, 2);
+
+        let mut stormers = unit_at(UnitType::Infantry, BattleHexCoord::new(4, 2), 50);
+        stormers.assault = Some(SiegeAssault::Ladders(wall));
+        let mut army = army_of(stormers);
+        let defenders = vec![unit_at(UnitType::Infantry, BattleHexCoord::new(6, 2), 50)];
+
+        // At the foot of the wall: shot at, nothing scaled yet
+        let outcome = run_siege(&mut map, &mut army, &defenders);
+        assert!(outcome.scaled.is_empty());
+        assert_eq!(army.formations[0].units[0].casualties, LADDER_LOSSES);
+
+        army.formations[0].units[0].position = wall;
+        let outcome = run_siege(&mut map, &mut army, &defenders);
+        assert_eq!(outcome.scaled, vec![army.formations[0].units[0].id]);
+        assert!(army.formations[0].units[0].assault.is_none());
+        assert!(map.is_barred(wall));
+    }
+
+    #[test]
+    fn test_unheard_mine_brings_the_wall_down() {
+        let mut map = BattleMap::new(20, 10);
+        raise_curtain_wall(&mut map, 10, 5);
+        let wall = BattleHexCoord::new(10, 2);
+
+        let sappers = unit_at(UnitType::Engineers, BattleHexCoord::new(4, 2), 50);
+        let sapper_id = sappers.id;
+        let mut army = army_of(sappers);
+        army.mines
+            .push(Mine::new(sapper_id, BattleHexCoord::new(4, 2), wall));
+        let defenders = vec![unit_at(UnitType::Infantry, BattleHexCoord::new(11, 2), 50)];
+
+        // Nobody hears a thing
+        let mut sprung = false;
+        for _ in 0..200 {
+            let outcome = run_mining(&mut map, &mut army, &defenders, || 1.0);
+            assert!(outcome.heard.is_empty());
+            if outcome.sprung.contains(&wall) {
+                sprung = true;
+                break;
+            }
+        }
+        assert!(sprung);
+        assert!(!map.is_barred(wall));
+        assert!(army.mines.is_empty());
+    }
+
+    #[test]
+    fn test_heard_mine_is_countermined() {
+        let mut map = BattleMap::new(20, 10);
+        raise_curtain_wall(&mut map, 10, 5);
+        let wall = BattleHexCoord::new(10, 2);
+
+        let sappers = unit_at(UnitType::Infantry, BattleHexCoord::new(4, 2), 50);
+        let sapper_id = sappers.id;
+        let mut army = army_of(sappers);
+        army.mines
+            .push(Mine::new(sapper_id, BattleHexCoord::new(4, 2), wall));
+        let listeners = vec![unit_at(UnitType::Engineers, BattleHexCoord::new(11, 2), 50)];
+
+        // Too far off to hear at first
+        let outcome = run_mining(&mut map, &mut army, &listeners, || 0.0);
+        assert!(outcome.heard.is_empty());
+        assert!(heard_mines(&army).next().is_none());
+
+        let mut broken_into = false;
+        for _ in 0..200 {
+            let outcome = run_mining(&mut map, &mut army, &listeners, || 0.0);
+            if outcome.broken_into.contains(&sapper_id) {
+                broken_into = true;
+                break;
+            }
+        }
+        assert!(broken_into);
+        assert!(map.is_barred(wall), "The wall still stands");
+        assert!(army.mines.is_empty());
+        assert_eq!(army.formations[0].units[0].casualties, BREAK_IN_LOSSES);
+    }
+
+    #[test]
+    fn test_digging_stops_when_the_sappers_leave() {
+        let mut map = BattleMap::new(20, 10);
+        raise_curtain_wall(&mut map, 10, 5);
+        let wall = BattleHexCoord::new(10, 2);
+
+        let sappers = unit_at(UnitType::Infantry, BattleHexCoord::new(4, 2), 50);
+        let sapper_id = sappers.id;
+        let mut army = army_of(sappers);
+        army.mines
+            .push(Mine::new(sapper_id, BattleHexCoord::new(4, 2), wall));
+
+        run_mining(&mut map, &mut army, &[], || 1.0);
+        let dug = army.mines[0].dug;
+        assert!(dug > 0.0);
+        army.formations[0].units[0].position = BattleHexCoord::new(0, 8);
+        run_mining(&mut map, &mut army, &[], || 1.0);
+        assert_eq!(army.mines[0].dug, dug);
+    }
+
+    #[test]
+    fn test_siege_order_storms_only_enemy_walls() {
+        let mut map = BattleMap::new(20, 20);
+        raise_curtain_wall(&mut map, 10, 10);
+        let own_hq = BattleHexCoord::new(0, 10);
+        let goal = BattleHexCoord::new(15, 10);
+
+        // Near the gate: ram it
+        let infantry = unit_at(UnitType::Infantry, BattleHexCoord::new(8, 10), 50);
+        let order = siege_order(&map, &infantry, goal, own_hq).unwrap();
+        assert!(matches!(
+            order.order_type,
+            OrderType::RamAssault(gate) if gate == BattleHexCoord::new(10, 10)
+        ));
+
+        // Far from it: climb
+        let infantry = unit_at(UnitType::Infantry, BattleHexCoord::new(8, 0), 50);
+        let order = siege_order(&map, &infantry, BattleHexCoord::new(15, 0), own_hq).unwrap();
+        assert!(matches!(order.order_type, OrderType::LadderAssault(_)));
+
+        // Horsemen wait; and nobody storms their own walls
+        let cavalry = unit_at(UnitType::Cavalry, BattleHexCoord::new(8, 10), 50);
+        assert!(siege_order(&map, &cavalry, goal, own_hq).is_none());
+        let inside = unit_at(UnitType::Infantry, BattleHexCoord::new(12, 10), 50);
+        assert!(siege_order(&map, &inside, own_hq, goal).is_none());
+    }
+}
@@ -0,0 +1,387 @@
+//! Skirmish doctrine - screening, harassing fire, and refusing melee
+//!
+//! Light troops under a `Skirmish` engagement rule (if their type can
+//! skirmish: archers, light cavalry, horse archers, scouts) do not stand in
+//! the line. They screen ahead of it and loose volleys at whatever comes into
+//! range. When formed troops get close, or anything charges them, they fall
+//! back a hex, loosing a parting shot as they go. They stand and fight only
+//! when cornered with nowhere left to fall back to, or too spent to run.
+//!
+//! A skirmisher already pinned in a bout of melee fights it out; the next
+//! lull is its chance to slip away.
+
+use rand::Rng;
+
+use crate::battle::battle_map::BattleMap;
+use crate::battle::constants::{
+    SKIRMISH_CHARGE_RANGE, SKIRMISH_EVADE_FATIGUE, SKIRMISH_EVADE_RANGE, SKIRMISH_EXHAUSTED,
+    SKIRMISH_SCREEN_DISTANCE, SKIRMISH_VOLLEY_TICKS,
+};
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::planning::{
+    BattlePlan, EngagementRule, Waypoint, WaypointBehavior, WaypointPlan,
+};
+use crate::battle::ranged::{
+    can_shoot, resolve_unit_ranged_attack, target_cover, unit_ranged_weapon,
+};
+use crate::battle::units::{Army, BattleUnit, UnitId, UnitStance};
+use crate::core::types::Tick;
+
+/// What a skirmisher does this tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkirmishAction {
+    /// Nothing close: hold the screen ahead of the main line
+    Screen,
+    /// Loose at an enemy in range
+    Shoot(UnitId),
+    /// Give up a hex, with a parting shot if anyone is in range
+    FallBack {
+        to: BattleHexCoord,
+        shot: Option<UnitId>,
+    },
+    /// Cornered or spent: fight where they stand
+    StandAndFight,
+}
+
+/// Hits landed by one volley
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Volley {
+    pub target: UnitId,
+    pub casualties: u32,
+    pub stress: f32,
+}
+
+/// What one side's skirmishers did this tick
+#[derive(Debug, Clone, Default)]
+pub struct SkirmishOutcome {
+    /// Volleys loosed at the other side
+    pub volleys: Vec<Volley>,
+    /// Skirmishers that fell back
+    pub fell_back: Vec<UnitId>,
+    /// Skirmishers newly caught with nowhere to go
+    pub cornered: Vec<UnitId>,
+}
+
+/// Whether a unit fights by skirmish doctrine
+pub fn follows_doctrine(unit: &BattleUnit, rule: &EngagementRule) -> bool {
+    matches!(rule, EngagementRule::Skirmish)
+        && unit.unit_type.default_properties().can_skirmish
+        && unit.can_fight()
+}
+
+/// Whether an enemy is too close, or coming on too hard, to stay put
+///
+/// Anyone in contact is a threat. So is formed infantry or cavalry within
+/// `SKIRMISH_EVADE_RANGE`, and anyone advancing within
+/// `SKIRMISH_CHARGE_RANGE` - except other skirmishers on foot, which are
+/// shot at rather than run from.
+pub fn is_threat(enemy: &BattleUnit, skirmisher: &BattleUnit) -> bool {
+    if !enemy.can_fight() {
+        return false;
+    }
+    let distance = enemy.position.distance(&skirmisher.position);
+    if distance <= 1 {
+        return true;
+    }
+
+    let loose = enemy.unit_type.default_properties().can_skirmish;
+    let formed = matches!(enemy.stance, UnitStance::Formed | UnitStance::Alert)
+        && distance <= SKIRMISH_EVADE_RANGE;
+    let charging = matches!(enemy.stance, UnitStance::Moving | UnitStance::Charging)
+        && distance <= SKIRMISH_CHARGE_RANGE;
+
+    (formed && !loose) || (charging && (!loose || enemy.unit_type.is_mounted()))
+}
+
+/// The neighbouring hex that best opens the distance to every threat
+///
+/// None if no passable hex gets further from the nearest threat (cornered).
+pub fn fall_back_hex(
+    map: &BattleMap,
+    unit: &BattleUnit,
+    threats: &[&BattleUnit],
+) -> Option<BattleHexCoord> {
+    let nearest = |hex: BattleHexCoord| {
+        threats
+            .iter()
+            .map(|threat| threat.position.distance(&hex))
+            .min()
+            .unwrap_or(u32::MAX)
+    };
+    let here = nearest(unit.position);
+    let mounted = unit.unit_type.is_mounted();
+
+    unit.position
+        .neighbors()
+        .into_iter()
+        .filter(|&hex| {
+            map.in_bounds(hex)
+                && map.get_hex(hex).is_some_and(|h| {
+                    if mounted {
+                        !h.terrain.impassable_for_cavalry()
+                    } else {
+                        !h.terrain.impassable_for_infantry()
+                    }
+                })
+        })
+        .map(|hex| (nearest(hex), hex))
+        .filter(|&(distance, _)| distance > here)
+        .max_by_key(|&(distance, _)| distance)
+        .map(|(_, hex)| hex)
+}
+
+fn centroid(hexes: &[BattleHexCoord]) -> Option<BattleHexCoord> {
+    if hexes.is_empty() {
+        return None;
+    }
+    let n = hexes.len() as f32;
+    let q = hexes.iter().map(|h| h.q as f32).sum::<f32>() / n;
+    let r = hexes.iter().map(|h| h.r as f32).sum::<f32>() / n;
+    Some(BattleHexCoord::new(q.round() as i32, r.round() as i32))
+}
+
+/// Where the screen stands: `SKIRMISH_SCREEN_DISTANCE` ahead of the main
+/// line toward the enemy, but never more than halfway across
+pub fn screen_position(
+    main_line: &[BattleHexCoord],
+    enemies: &[BattleHexCoord],
+) -> Option<BattleHexCoord> {
+    let line = centroid(main_line)?;
+    let enemy = centroid(enemies)?;
+    let gap = line.distance(&enemy);
+    if gap == 0 {
+        return Some(line);
+    }
+    let t = (SKIRMISH_SCREEN_DISTANCE as f32 / gap as f32).min(0.5);
+    Some(line.lerp(&enemy, t))
+}
+
+/// The nearest enemy this skirmisher can shoot at, if it shoots at all
+fn volley_target(unit: &BattleUnit, enemies: &[BattleUnit]) -> Option<UnitId> {
+    let weapon = unit_ranged_weapon(unit.unit_type).filter(|_| unit.ammo() > 0)?;
+    enemies
+        .iter()
+        .filter(|e| e.effective_strength() > 0)
+        .filter(|e| can_shoot(unit.position, e.position, weapon.range))
+        .min_by_key(|e| unit.position.distance(&e.position))
+        .map(|e| e.id)
+}
+
+/// Decide what a skirmisher does, given every enemy on the field
+pub fn decide(map: &BattleMap, unit: &BattleUnit, enemies: &[BattleUnit]) -> SkirmishAction {
+    let threats: Vec<&BattleUnit> = enemies.iter().filter(|e| is_threat(e, unit)).collect();
+    let target = volley_target(unit, enemies);
+
+    if !threats.is_empty() {
+        if unit.fatigue >= SKIRMISH_EXHAUSTED {
+            return SkirmishAction::StandAndFight;
+        }
+        return match fall_back_hex(map, unit, &threats) {
+            Some(to) => SkirmishAction::FallBack { to, shot: target },
+            None => SkirmishAction::StandAndFight,
+        };
+    }
+
+    match target {
+        Some(id) => SkirmishAction::Shoot(id),
+        None => SkirmishAction::Screen,
+    }
+}
+
+/// Loose a volley at a target, rolling the hit with `rng`; the hits are
+/// applied by the caller
+fn loose(
+    map: &BattleMap,
+    unit: &mut BattleUnit,
+    target: UnitId,
+    enemies: &[BattleUnit],
+    tick: Tick,
+    rng: &mut impl Rng,
+) -> Option<Volley> {
+    let enemy = enemies.iter().find(|e| e.id == target)?;
+    let has_los = map.has_line_of_sight(unit.position, enemy.position);
+    let cover = target_cover(map, enemy.position);
+    let result = resolve_unit_ranged_attack(unit, enemy, tick, has_los, cover, rng);
+    if result.ammo_consumed == 0 {
+        return None;
+    }
+    unit.volleys_loosed += result.ammo_consumed;
+    unit.fatigue = (unit.fatigue + result.fatigue_cost).min(1.0);
+    Some(Volley {
+        target,
+        casualties: result.casualties,
+        stress: result.stress_inflicted,
+    })
+}
+
+/// Send a screener out to the screen, unless it already has orders to follow
+fn hold_screen(plan: &mut BattlePlan, unit: &BattleUnit, screen: BattleHexCoord) {
+    if unit.position.distance(&screen) <= 1 {
+        return;
+    }
+    let has_orders = plan
+        .get_waypoint_plan(unit.id)
+        .is_some_and(|p| p.current().is_some());
+    if has_orders {
+        return;
+    }
+    plan.waypoint_plans.retain(|p| p.unit_id != unit.id);
+    let mut route = WaypointPlan::new(unit.id);
+    route.add_waypoint(Waypoint::new(screen, WaypointBehavior::MoveTo));
+    plan.waypoint_plans.push(route);
+}
+
+/// Run skirmish doctrine for every skirmisher in an army
+///
+/// Moves, fatigues, and sets the stance of the army's own skirmishers; the
+/// volleys they loose are returned for the caller to apply to the enemy.
+pub fn run_skirmishers(
+    map: &BattleMap,
+    army: &mut Army,
+    plan: &mut BattlePlan,
+    enemies: &[BattleUnit],
+    tick: Tick,
+    rng: &mut impl Rng,
+) -> SkirmishOutcome {
+    let mut outcome = SkirmishOutcome::default();
+
+    let main_line: Vec<BattleHexCoord> = army
+        .formations
+        .iter()
+        .flat_map(|f| f.units.iter())
+        .filter(|u| u.can_fight() && !follows_doctrine(u, &plan.get_engagement_rule(u.id)))
+        .map(|u| u.position)
+        .collect();
+    let enemy_positions: Vec<BattleHexCoord> = enemies
+        .iter()
+        .filter(|e| e.can_fight())
+        .map(|e| e.position)
+        .collect();
+    let screen = screen_position(&main_line, &enemy_positions);
+    let volley_due = tick.is_multiple_of(SKIRMISH_VOLLEY_TICKS);
+
+    for unit in army.formations.iter_mut().flat_map(|f| f.units.iter_mut()) {
+        if !follows_doctrine(unit, &plan.get_engagement_rule(unit.id)) {
+            // Ordered out of skirmish order (or broken): close up again
+            if matches!(unit.stance, UnitStance::Skirmishing | UnitStance::Evading) {
+                unit.stance = UnitStance::Formed;
+            }
+            continue;
+        }
+        if unit.stance == UnitStance::Engaged {
+            continue;
+        }
+
+        match decide(map, unit, enemies) {
+            SkirmishAction::Screen => {
+                unit.stance = UnitStance::Skirmishing;
+                if let Some(screen) = screen {
+                    hold_screen(plan, unit, screen);
+                }
+            }
+            SkirmishAction::Shoot(target) => {
+                unit.stance = UnitStance::Skirmishing;
+                if volley_due {
+                    outcome
+                        .volleys
+                        .extend(loose(map, unit, target, enemies, tick, rng));
+                }
+            }
+            SkirmishAction::FallBack { to, shot } => {
+                if let Some(target) = shot.filter(|_| volley_due) {
+                    outcome
+                        .volleys
+                        .extend(loose(map, unit, target, enemies, tick, rng));
+                }
+                unit.position = to;
+                unit.fatigue = (unit.fatigue + SKIRMISH_EVADE_FATIGUE).min(1.0);
+                unit.stance = UnitStance::Evading;
+                outcome.fell_back.push(unit.id);
+            }
+            SkirmishAction::StandAndFight => {
+                if matches!(unit.stance, UnitStance::Skirmishing | UnitStance::Evading) {
+                    outcome.cornered.push(unit.id);
+                }
+                unit.stance = UnitStance::Formed;
+            }
+        }
+    }
+
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::terrain::BattleTerrain;
+    use crate::battle::unit_type::UnitType;
+    use crate::battle::units::Element;
+    use crate::core::types::EntityId;
+
+    fn unit_at(unit_type: UnitType, q: i32, r: i32, stance: UnitStance) -> BattleUnit {
+        let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+        unit.position = BattleHexCoord::new(q, r);
+        unit.stance = stance;
+        unit.elements
+            .push(Element::new((0..20).map(|_| EntityId::new()).collect()));
+        unit
+    }
+
+    #[test]
+    fn test_skirmishers_shoot_then_fall_back_from_a_charge() {
+        let map = BattleMap::new(30, 30);
+        let archers = unit_at(UnitType::Archers, 10, 10, UnitStance::Skirmishing);
+
+        // Formed infantry out at bowshot: loose at it
+        let infantry = unit_at(UnitType::Infantry, 16, 10, UnitStance::Formed);
+        assert_eq!(
+            decide(&map, &archers, std::slice::from_ref(&infantry)),
+            SkirmishAction::Shoot(infantry.id)
+        );
+
+        // Cavalry charging in: fall back, away from it, shooting as they go
+        let cavalry = unit_at(UnitType::HeavyCavalry, 13, 10, UnitStance::Moving);
+        let enemies = [infantry.clone(), cavalry.clone()];
+        match decide(&map, &archers, &enemies) {
+            SkirmishAction::FallBack { to, shot } => {
+                assert!(
+                    to.distance(&cavalry.position) > archers.position.distance(&cavalry.position)
+                );
+                assert_eq!(shot, Some(cavalry.id));
+            }
+            other => panic!("expected to fall back, got {:?}", other),
+        }
+
+        // Enemy skirmishers on foot are shot at, not run from
+        let screen = unit_at(UnitType::Archers, 13, 10, UnitStance::Moving);
+        assert!(!is_threat(&screen, &archers));
+    }
+
+    #[test]
+    fn test_cornered_skirmishers_stand_and_fight() {
+        let mut map = BattleMap::new(30, 30);
+        let archers = unit_at(UnitType::Archers, 10, 10, UnitStance::Evading);
+        let infantry = unit_at(UnitType::Infantry, 11, 10, UnitStance::Formed);
+
+        // Deep water behind and to the sides: nowhere further away to go
+        for hex in archers.position.neighbors() {
+            if hex.distance(&infantry.position) > 1 {
+                map.set_terrain(hex, BattleTerrain::DeepWater);
+            }
+        }
+        assert_eq!(
+            decide(&map, &archers, std::slice::from_ref(&infantry)),
+            SkirmishAction::StandAndFight
+        );
+    }
+
+    #[test]
+    fn test_screen_stands_ahead_of_the_line() {
+        let line = [BattleHexCoord::new(0, 10), BattleHexCoord::new(2, 10)];
+        let enemy = [BattleHexCoord::new(1, 30)];
+        let screen = screen_position(&line, &enemy).unwrap();
+        let centre = BattleHexCoord::new(1, 10);
+        assert_eq!(screen.distance(&centre), SKIRMISH_SCREEN_DISTANCE);
+        assert!(screen.distance(&enemy[0]) < centre.distance(&enemy[0]));
+    }
+}
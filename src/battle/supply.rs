@@ -0,0 +1,283 @@
+//! Supply in battle - ammunition, stamina, and the wagons that carry both
+//!
+//! Every shooter carries `QUIVER_VOLLEYS` volleys (see `ranged`), and every
+//! unit a pool of stamina that melee wears down, `MELEE_STAMINA_DRAIN` a
+//! tick. Stamina is what a lull cannot give back: fatigue never rests off
+//! below what the unit's spent stamina leaves it.
+//!
+//! Supply wagons (`UnitType::SupplyWagons`) carry stores to restore both.
+//! Every `WAGON_RESUPPLY_TICKS` a wagon hands one store to each unit within
+//! `WAGON_RESUPPLY_RANGE` that is out of contact and in need: a volley back
+//! in a shooter's quiver, or `WAGON_STAMINA_RESTORE` stamina. Nobody needs
+//! to send the drivers orders: at each handout, a wagon with stores left
+//! sets off after whichever unit most needs it. A campaign army's wagons are
+//! stocked from its supplies (see `campaign::tactical`).
+//!
+//! Shooters with empty quivers and an enemy in sight and in range feel it
+//! (`StressSource::OutOfAmmo`) every tick until they are resupplied.
+
+use crate::battle::constants::{
+    MELEE_STAMINA_DRAIN, QUIVER_VOLLEYS, WAGON_RESUPPLY_RANGE, WAGON_RESUPPLY_TICKS,
+    WAGON_STAMINA_RESTORE,
+};
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::planning::{BattlePlan, Waypoint, WaypointBehavior, WaypointPlan};
+use crate::battle::ranged::{max_range_hexes, unit_ranged_weapon};
+use crate::battle::unit_type::UnitType;
+use crate::battle::units::{Army, BattleUnit, UnitId, UnitStance};
+use crate::battle::visibility::ArmyVisibility;
+use crate::combat::morale::StressSource;
+use crate::core::types::Tick;
+
+/// What one side's wagons handed out this tick
+#[derive(Debug, Clone, Default)]
+pub struct SupplyOutcome {
+    /// Stores handed out, as (wagon, unit served)
+    pub handed_out: Vec<(UnitId, UnitId)>,
+    /// Wagons that handed out their last store
+    pub emptied: Vec<UnitId>,
+}
+
+/// How badly a unit needs a wagon: the share of its quiver spent plus the
+/// share of its stamina, zero for a unit that needs nothing
+pub fn supply_need(unit: &BattleUnit) -> f32 {
+    let quiver = if unit.unit_type.is_ranged() {
+        unit.volleys_loosed as f32 / QUIVER_VOLLEYS as f32
+    } else {
+        0.0
+    };
+    quiver + (1.0 - unit.stamina)
+}
+
+/// Whether a unit can take a store from a wagon: in need, and not in melee
+fn can_take_store(unit: &BattleUnit) -> bool {
+    unit.unit_type != UnitType::SupplyWagons
+        && unit.can_fight()
+        && unit.stance != UnitStance::Engaged
+        && supply_need(unit) > 0.0
+}
+
+/// Hand a unit one store: a volley if it is a shooter short of them,
+/// food and water otherwise
+fn hand_store(unit: &mut BattleUnit) {
+    if unit.unit_type.is_ranged() && unit.volleys_loosed > 0 {
+        unit.volleys_loosed -= 1;
+    } else {
+        unit.stamina = (unit.stamina + WAGON_STAMINA_RESTORE).min(1.0);
+    }
+}
+
+/// Hand out stores from every wagon of an army that is due to, then send
+/// each wagon with stores left after the unit that most needs it
+pub fn run_resupply(army: &mut Army, plan: &mut BattlePlan, tick: Tick) -> SupplyOutcome {
+    let mut outcome = SupplyOutcome::default();
+    if !tick.is_multiple_of(WAGON_RESUPPLY_TICKS) {
+        return outcome;
+    }
+
+    let wagons: Vec<(UnitId, BattleHexCoord)> = army
+        .formations
+        .iter()
+        .flat_map(|f| f.units.iter())
+        .filter(|u| u.unit_type == UnitType::SupplyWagons && u.stores > 0 && u.can_fight())
+        .map(|u| (u.id, u.position))
+        .collect();
+
+    for (wagon_id, at) in wagons {
+        let Some(mut stores) = army.get_unit(wagon_id).map(|w| w.stores) else {
+            continue;
+        };
+        for unit in army.formations.iter_mut().flat_map(|f| f.units.iter_mut()) {
+            if stores == 0 {
+                break;
+            }
+            if unit.position.distance(&at) <= WAGON_RESUPPLY_RANGE && can_take_store(unit) {
+                hand_store(unit);
+                stores -= 1;
+                outcome.handed_out.push((wagon_id, unit.id));
+            }
+        }
+        if stores == 0 {
+            outcome.emptied.push(wagon_id);
+        }
+        if let Some(wagon) = army.get_unit_mut(wagon_id) {
+            wagon.stores = stores;
+        }
+    }
+
+    let units: Vec<&BattleUnit> = army
+        .formations
+        .iter()
+        .flat_map(|f| f.units.iter())
+        .collect();
+    for wagon in units
+        .iter()
+        .filter(|u| u.unit_type == UnitType::SupplyWagons)
+    {
+        if let Some(destination) = resupply_destination(wagon, &units) {
+            plan.waypoint_plans.retain(|p| p.unit_id != wagon.id);
+            let mut route = WaypointPlan::new(wagon.id);
+            route.add_waypoint(Waypoint::new(destination, WaypointBehavior::MoveTo));
+            plan.waypoint_plans.push(route);
+        }
+    }
+    outcome
+}
+
+/// Run down a unit's stamina for a tick of melee
+pub fn drain_stamina(unit: &mut BattleUnit) {
+    unit.stamina = (unit.stamina - MELEE_STAMINA_DRAIN).max(0.0);
+}
+
+/// Stress this tick for a shooter with an empty quiver and an enemy its
+/// army can see within range
+pub fn out_of_ammo_stress(
+    unit: &BattleUnit,
+    enemies: &[BattleUnit],
+    visibility: &ArmyVisibility,
+) -> f32 {
+    let Some(weapon) = unit_ranged_weapon(unit.unit_type) else {
+        return 0.0;
+    };
+    let range = max_range_hexes(weapon.range);
+    let threatened = enemies.iter().any(|e| {
+        e.can_fight()
+            && visibility.is_visible(e.position)
+            && unit.position.distance(&e.position) <= range
+    });
+    if unit.ammo() == 0 && unit.can_fight() && threatened {
+        StressSource::OutOfAmmo.base_stress()
+    } else {
+        0.0
+    }
+}
+
+/// Where a wagon drives to reach whichever of its army's units most needs
+/// it, if any needs it and is not already within reach
+pub fn resupply_destination(
+    wagon: &BattleUnit,
+    own_units: &[&BattleUnit],
+) -> Option<BattleHexCoord> {
+    if wagon.stores == 0 || !wagon.can_fight() {
+        return None;
+    }
+    let neediest = own_units
+        .iter()
+        .filter(|u| can_take_store(u))
+        .max_by(|a, b| supply_need(a).total_cmp(&supply_need(b)))?;
+    if neediest.position.distance(&wagon.position) <= WAGON_RESUPPLY_RANGE {
+        return None;
+    }
+    Some(neediest.position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::units::{ArmyId, BattleFormation, Element, FormationId};
+    use crate::core::types::EntityId;
+
+    fn unit_at(unit_type: UnitType, position: BattleHexCoord, soldiers: usize) -> BattleUnit {
+        let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+        unit.elements
+            .push(Element::new(vec![EntityId::new(); soldiers]));
+        unit.position = position;
+        unit
+    }
+
+    fn army_of(units: Vec<BattleUnit>) -> Army {
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        formation.units = units;
+        army.formations.push(formation);
+        army
+    }
+
+    #[test]
+    fn test_wagons_refill_quivers_and_stamina_nearby() {
+        let mut wagons = unit_at(UnitType::SupplyWagons, BattleHexCoord::new(5, 5), 10);
+        wagons.stores = 3;
+        let wagon_id = wagons.id;
+
+        let mut archers = unit_at(UnitType::Archers, BattleHexCoord::new(6, 5), 40);
+        archers.volleys_loosed = QUIVER_VOLLEYS;
+        let archer_id = archers.id;
+        let mut infantry = unit_at(UnitType::Infantry, BattleHexCoord::new(5, 6), 50);
+        infantry.stamina = 0.5;
+        let infantry_id = infantry.id;
+        let mut far = unit_at(UnitType::Infantry, BattleHexCoord::new(15, 5), 50);
+        far.stamina = 0.5;
+        let far_id = far.id;
+
+        let mut army = army_of(vec![wagons, archers, infantry, far]);
+        let mut plan = BattlePlan::new();
+
+        // Only on the wagon's schedule
+        assert!(run_resupply(&mut army, &mut plan, 1).handed_out.is_empty());
+
+        let outcome = run_resupply(&mut army, &mut plan, 0);
+        assert_eq!(outcome.handed_out.len(), 2);
+        assert_eq!(army.get_unit(archer_id).unwrap().ammo(), 1);
+        assert!((army.get_unit(infantry_id).unwrap().stamina - 0.6).abs() < 1e-6);
+        assert_eq!(army.get_unit(far_id).unwrap().stamina, 0.5);
+        assert_eq!(army.get_unit(wagon_id).unwrap().stores, 1);
+
+        let outcome = run_resupply(&mut army, &mut plan, WAGON_RESUPPLY_TICKS);
+        assert_eq!(outcome.emptied, vec![wagon_id]);
+        assert_eq!(army.get_unit(wagon_id).unwrap().stores, 0);
+    }
+
+    #[test]
+    fn test_empty_quivers_under_threat_cause_stress() {
+        let mut archers = unit_at(UnitType::Archers, BattleHexCoord::new(0, 0), 40);
+        let enemy = unit_at(UnitType::Infantry, BattleHexCoord::new(6, 0), 50);
+        let mut visibility = ArmyVisibility::new();
+        visibility.update([enemy.position].into_iter().collect());
+        let enemies = vec![enemy];
+
+        assert_eq!(out_of_ammo_stress(&archers, &enemies, &visibility), 0.0);
+        archers.volleys_loosed = QUIVER_VOLLEYS;
+        assert_eq!(
+            out_of_ammo_stress(&archers, &enemies, &visibility),
+            StressSource::OutOfAmmo.base_stress()
+        );
+        // Unseen, or out of range, they are no worry yet
+        assert_eq!(
+            out_of_ammo_stress(&archers, &enemies, &ArmyVisibility::new()),
+            0.0
+        );
+        archers.position = BattleHexCoord::new(-30, 0);
+        assert_eq!(out_of_ammo_stress(&archers, &enemies, &visibility), 0.0);
+    }
+
+    #[test]
+    fn test_wagons_drive_to_the_neediest_unit() {
+        let mut wagons = unit_at(UnitType::SupplyWagons, BattleHexCoord::new(0, 0), 10);
+        wagons.stores = 10;
+        let mut tired = unit_at(UnitType::Infantry, BattleHexCoord::new(8, 0), 50);
+        tired.stamina = 0.8;
+        let mut spent = unit_at(UnitType::Archers, BattleHexCoord::new(0, 8), 40);
+        spent.volleys_loosed = QUIVER_VOLLEYS;
+
+        assert_eq!(
+            resupply_destination(&wagons, &[&tired, &spent]),
+            Some(spent.position)
+        );
+
+        // Nobody in need, or an empty wagon: it stays put
+        let fresh = unit_at(UnitType::Infantry, BattleHexCoord::new(8, 0), 50);
+        assert_eq!(resupply_destination(&wagons, &[&fresh]), None);
+
+        // Handing out stores sends the wagon on its way
+        let spent_at = spent.position;
+        let wagon_id = wagons.id;
+        let mut army = army_of(vec![wagons.clone(), tired, spent]);
+        let mut plan = BattlePlan::new();
+        run_resupply(&mut army, &mut plan, 0);
+        let route = plan.get_waypoint_plan(wagon_id).unwrap();
+        assert_eq!(route.current().map(|w| w.position), Some(spent_at));
+
+        wagons.stores = 0;
+        assert_eq!(resupply_destination(&wagons, &[&fresh]), None);
+    }
+}
@@ -1,9 +1,13 @@
 //! Battle terrain types and their effects
 //!
 //! Dense terrain constrains movement - navigation is a puzzle.
+//! Walls and gates are fortifications with HP: they bar the way until they
+//! are breached (see `siege`).
 
 use serde::{Deserialize, Serialize};
 
+use crate::battle::constants::{FORTIFICATION_DAMAGED_SHARE, GATE_HP, WALL_HP};
+
 /// Primary terrain type for a battle hex
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum BattleTerrain {
@@ -78,7 +82,7 @@ impl BattleTerrain {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TerrainFeature {
     Hill,     // Elevation, vision bonus
-    Ridge,    // Blocks LOS from one side
+    Ridge,    // Crest line, hides what lies beyond from anyone below it
     Stream,   // Minor obstacle
     Bridge,   // Chokepoint
     Wall,     // Defensive, can be breached
@@ -116,9 +120,90 @@ impl TerrainFeature {
         }
     }
 
-    /// Does this block LOS?
+    /// Does this block LOS from every side? (A ridge blocks only from below;
+    /// see `BattleHex::blocks_los_from`)
     pub fn blocks_los(&self) -> bool {
-        matches!(self, TerrainFeature::Ridge | TerrainFeature::Wall)
+        matches!(self, TerrainFeature::Wall)
+    }
+}
+
+/// What kind of fortification stands on a hex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FortificationKind {
+    Wall, // Scaled with ladders or battered down
+    Gate, // Battered down, or opened by those inside
+}
+
+/// How much of a fortification is still standing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BreachState {
+    Intact,
+    Damaged,  // Holding, but below `FORTIFICATION_DAMAGED_SHARE` of its HP
+    Breached, // Rubble: open to anyone
+}
+
+/// A destructible wall or gate on a hex
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Fortification {
+    pub kind: FortificationKind,
+    pub hp: f32,
+    pub max_hp: f32,
+    /// Gates only: open gates let anyone through
+    pub open: bool,
+}
+
+impl Fortification {
+    /// A stretch of curtain wall
+    pub fn wall() -> Self {
+        Self {
+            kind: FortificationKind::Wall,
+            hp: WALL_HP,
+            max_hp: WALL_HP,
+            open: false,
+        }
+    }
+
+    /// A closed gate
+    pub fn gate() -> Self {
+        Self {
+            kind: FortificationKind::Gate,
+            hp: GATE_HP,
+            max_hp: GATE_HP,
+            open: false,
+        }
+    }
+
+    /// How much of it is still standing
+    pub fn state(&self) -> BreachState {
+        if self.hp <= 0.0 {
+            BreachState::Breached
+        } else if self.hp < self.max_hp * FORTIFICATION_DAMAGED_SHARE {
+            BreachState::Damaged
+        } else {
+            BreachState::Intact
+        }
+    }
+
+    /// Is it breached?
+    pub fn is_breached(&self) -> bool {
+        self.state() == BreachState::Breached
+    }
+
+    /// Does it stop anyone walking through? (Walls can still be climbed)
+    pub fn bars_the_way(&self) -> bool {
+        let open_gate = self.kind == FortificationKind::Gate && self.open;
+        !(self.is_breached() || open_gate)
+    }
+
+    /// Can assault ladders be put up against it?
+    pub fn can_be_scaled(&self) -> bool {
+        self.kind == FortificationKind::Wall && !self.is_breached()
+    }
+
+    /// Knock `damage` HP off it, returning its state afterwards
+    pub fn take_damage(&mut self, damage: f32) -> BreachState {
+        self.hp = (self.hp - damage).max(0.0);
+        self.state()
     }
 }
 
@@ -163,4 +248,21 @@ mod tests {
     fn test_feature_defense_bonuses() {
         assert!(TerrainFeature::Wall.defense_bonus() > TerrainFeature::Hill.defense_bonus());
     }
+
+    #[test]
+    fn test_fortification_breaks_down() {
+        let mut gate = Fortification::gate();
+        assert!(gate.bars_the_way());
+        assert!(!gate.can_be_scaled());
+        assert_eq!(gate.take_damage(GATE_HP * 0.6), BreachState::Damaged);
+        assert!(gate.bars_the_way());
+        assert_eq!(gate.take_damage(GATE_HP), BreachState::Breached);
+        assert_eq!(gate.hp, 0.0);
+        assert!(!gate.bars_the_way());
+
+        let mut open = Fortification::gate();
+        open.open = true;
+        assert!(!open.bars_the_way());
+        assert!(Fortification::wall().can_be_scaled());
+    }
 }
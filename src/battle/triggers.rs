@@ -50,6 +50,8 @@ pub fn evaluate_gocode_trigger(
             let _ = area;
             false
         }
+
+        GoCodeTrigger::Unknown(_) => false,
     }
 }
 
@@ -98,6 +100,8 @@ pub fn evaluate_contingency_trigger(
         }
 
         ContingencyTrigger::CasualtiesExceed(threshold) => casualties_percent > *threshold,
+
+        ContingencyTrigger::Unknown(_) => false,
     }
 }
 
@@ -142,6 +146,7 @@ pub fn describe_contingency_response(response: &ContingencyResponse) -> String {
         ContingencyResponse::Signal(go_code_id) => {
             format!("Signal go-code {:?}", go_code_id)
         }
+        ContingencyResponse::Unknown(tag) => format!("Unknown response {}", tag),
     }
 }
 
@@ -2,9 +2,7 @@
 //!
 //! Unit properties emerge from equipment aggregation.
 
-use crate::combat::{
-    ArmorProperties, Coverage, Edge, Mass, Padding, Reach, Rigidity, WeaponProperties,
-};
+use crate::combat::{ArmorProperties, Edge, Mass, Reach, WeaponProperties};
 use serde::{Deserialize, Serialize};
 
 /// Type of military unit
@@ -19,15 +17,18 @@ pub enum UnitType {
     Crossbowmen,   // Slower, more punch
 
     // Cavalry
-    LightCavalry, // Fast, scout, skirmish
-    Cavalry,      // Standard mounted
-    HeavyCavalry, // Shock, armored, expensive
-    HorseArchers, // Mobile ranged
+    LightCavalry,  // Fast, scout, skirmish
+    Cavalry,       // Standard mounted
+    HeavyCavalry,  // Shock, armored, expensive
+    HorseArchers,  // Mobile ranged
+    MountedScouts, // Reconnaissance ahead of the army
+    CourierEscort, // Riders guarding couriers
 
     // Special
-    Engineers, // Siege, construction
-    Scouts,    // Reconnaissance
-    Command,   // Officers, messengers
+    Engineers,    // Siege, construction
+    SupplyWagons, // Arrows, food, and water for the line
+    Scouts,       // Reconnaissance
+    Command,      // Officers, messengers
 }
 
 /// Default properties for a unit type
@@ -53,11 +54,7 @@ impl UnitType {
                     reach: Reach::Short,
                     special: vec![],
                 },
-                avg_armor: ArmorProperties {
-                    rigidity: Rigidity::Cloth,
-                    padding: Padding::None,
-                    coverage: Coverage::None,
-                },
+                avg_armor: ArmorProperties::none(),
                 movement_speed: 1.0,
                 vision_range: 6,
                 base_stress_threshold: 1.2, // Break easily (tripled for longer battles)
@@ -180,6 +177,26 @@ impl UnitType {
                 can_skirmish: true,
             },
 
+            UnitType::MountedScouts => UnitProperties {
+                avg_weapon: WeaponProperties::dagger(),
+                avg_armor: ArmorProperties::leather(),
+                movement_speed: 2.0,
+                vision_range: 12,
+                base_stress_threshold: 1.4, // Not there to fight (tripled)
+                can_charge: false,
+                can_skirmish: true,
+            },
+
+            UnitType::CourierEscort => UnitProperties {
+                avg_weapon: WeaponProperties::sword(),
+                avg_armor: ArmorProperties::leather(),
+                movement_speed: 2.0,
+                vision_range: 8,
+                base_stress_threshold: 1.6, // Tripled
+                can_charge: false,
+                can_skirmish: false,
+            },
+
             UnitType::Engineers => UnitProperties {
                 avg_weapon: WeaponProperties::fists(),
                 avg_armor: ArmorProperties::none(),
@@ -190,6 +207,16 @@ impl UnitType {
                 can_skirmish: false,
             },
 
+            UnitType::SupplyWagons => UnitProperties {
+                avg_weapon: WeaponProperties::fists(),
+                avg_armor: ArmorProperties::none(),
+                movement_speed: 0.7, // Laden carts
+                vision_range: 6,
+                base_stress_threshold: 1.2, // Drivers, not soldiers (tripled)
+                can_charge: false,
+                can_skirmish: false,
+            },
+
             UnitType::Scouts => UnitProperties {
                 avg_weapon: WeaponProperties::dagger(),
                 avg_armor: ArmorProperties::leather(),
@@ -220,10 +247,17 @@ impl UnitType {
                 | UnitType::Cavalry
                 | UnitType::HeavyCavalry
                 | UnitType::HorseArchers
+                | UnitType::MountedScouts
+                | UnitType::CourierEscort
                 | UnitType::Command
         )
     }
 
+    /// Can this unit ride out ahead to reconnoitre?
+    pub fn can_reconnoiter(&self) -> bool {
+        matches!(self, UnitType::MountedScouts)
+    }
+
     /// Is this a ranged unit?
     pub fn is_ranged(&self) -> bool {
         matches!(
@@ -236,6 +270,28 @@ impl UnitType {
     pub fn can_charge(&self) -> bool {
         self.default_properties().can_charge
     }
+
+    /// What the unit's soldiers are called in a telling of the battle
+    pub fn describe(&self) -> &'static str {
+        match self {
+            UnitType::Levy => "levies",
+            UnitType::Infantry => "infantry",
+            UnitType::HeavyInfantry => "heavy infantry",
+            UnitType::Spearmen => "spearmen",
+            UnitType::Archers => "archers",
+            UnitType::Crossbowmen => "crossbowmen",
+            UnitType::LightCavalry => "light cavalry",
+            UnitType::Cavalry => "cavalry",
+            UnitType::HeavyCavalry => "heavy cavalry",
+            UnitType::HorseArchers => "horse archers",
+            UnitType::MountedScouts => "mounted scouts",
+            UnitType::CourierEscort => "courier escort",
+            UnitType::Engineers => "engineers",
+            UnitType::SupplyWagons => "wagoners",
+            UnitType::Scouts => "scouts",
+            UnitType::Command => "command",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -8,7 +8,10 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::battle::constants::{DRILL_COHESION_HOLD, QUIVER_VOLLEYS};
 use crate::battle::hex::{BattleHexCoord, HexDirection};
+use crate::battle::officers::Officer;
+use crate::battle::siege::{Mine, SiegeAssault};
 use crate::battle::unit_type::UnitType;
 use crate::core::types::EntityId;
 
@@ -81,13 +84,18 @@ impl Element {
 pub enum UnitStance {
     #[default]
     Formed, // In formation, ready
-    Moving,   // Moving to position
-    Engaged,  // In combat
-    Shaken,   // Morale damaged
-    Routing,  // Fleeing
-    Rallying, // Reforming after rout
-    Patrol,   // Scouting stance
-    Alert,    // High awareness
+    Moving,      // Moving to position
+    Engaged,     // In combat
+    Shaken,      // Morale damaged
+    Routing,     // Fleeing
+    Rallying,    // Reforming after rout
+    Patrol,      // Scouting stance
+    Alert,       // High awareness
+    Skirmishing, // Loose order: screening and harassing at range
+    Evading,     // Skirmishers falling back from a charge
+    Charging,    // Cavalry at the gallop, closing to strike
+    Braced,      // Standing to receive a charge
+    Disordered,  // Re-forming after a charge was thrown back
 }
 
 /// Formation shape for units
@@ -130,6 +138,43 @@ pub struct BattleUnit {
 
     // Casualties
     pub casualties: u32,
+
+    /// Direction scouts have ridden out to look, if reconnoitring
+    #[serde(default)]
+    pub scouting: Option<HexDirection>,
+
+    /// Wall or gate the unit has been ordered to storm, if any
+    #[serde(default)]
+    pub assault: Option<SiegeAssault>,
+
+    /// Volleys loosed so far, out of `QUIVER_VOLLEYS`
+    #[serde(default)]
+    pub volleys_loosed: u32,
+
+    /// 1.0 (fresh) to 0.0 (spent); worn down by melee, restored only by supply
+    #[serde(default = "full_stamina")]
+    pub stamina: f32,
+
+    /// Stores carried for the rest of the army, if supply wagons
+    #[serde(default)]
+    pub stores: u32,
+
+    /// Hexes galloped over open ground in the current charge
+    #[serde(default)]
+    pub charge_run: u32,
+
+    /// Tick a repulsed charge fell into disorder, if re-forming
+    #[serde(default)]
+    pub disordered_since: Option<u64>,
+
+    /// 0.0 (levy) to 1.0 (drilled to perfection); quickens its orders and
+    /// steadies its cohesion
+    #[serde(default)]
+    pub drill: f32,
+}
+
+fn full_stamina() -> f32 {
+    1.0
 }
 
 impl BattleUnit {
@@ -148,6 +193,14 @@ impl BattleUnit {
             stress: 0.0,
             rallying_since: None,
             casualties: 0,
+            scouting: None,
+            assault: None,
+            volleys_loosed: 0,
+            stamina: 1.0,
+            stores: 0,
+            charge_run: 0,
+            disordered_since: None,
+            drill: 0.0,
         }
     }
 
@@ -166,6 +219,20 @@ impl BattleUnit {
         matches!(self.stance, UnitStance::Routing)
     }
 
+    /// Volleys of ammunition left (none for units that do not shoot)
+    pub fn ammo(&self) -> u32 {
+        if self.unit_type.is_ranged() {
+            QUIVER_VOLLEYS.saturating_sub(self.volleys_loosed)
+        } else {
+            0
+        }
+    }
+
+    /// Fatigue the unit can no longer rest off, having spent its stamina
+    pub fn fatigue_floor(&self) -> f32 {
+        1.0 - self.stamina
+    }
+
     /// Can this unit fight?
     pub fn can_fight(&self) -> bool {
         !matches!(self.stance, UnitStance::Routing | UnitStance::Rallying)
@@ -177,6 +244,12 @@ impl BattleUnit {
         matches!(self.stance, UnitStance::Engaged)
     }
 
+    /// Lose cohesion to a shock, less of it the better drilled the unit is
+    pub fn lose_cohesion(&mut self, amount: f32) {
+        let held = DRILL_COHESION_HOLD * self.drill;
+        self.cohesion = (self.cohesion - amount * (1.0 - held)).max(0.0);
+    }
+
     /// Get stress threshold based on unit type and state
     pub fn stress_threshold(&self) -> f32 {
         let base = self.unit_type.default_properties().base_stress_threshold;
@@ -203,6 +276,9 @@ pub struct BattleFormation {
     pub commander: EntityId,
     pub units: Vec<BattleUnit>,
     pub name: String,
+    /// Named leaders riding with its units
+    #[serde(default)]
+    pub officers: Vec<Officer>,
 }
 
 impl BattleFormation {
@@ -212,6 +288,7 @@ impl BattleFormation {
             commander,
             units: Vec::new(),
             name: String::new(),
+            officers: Vec::new(),
         }
     }
 
@@ -261,6 +338,9 @@ pub struct Army {
     pub formations: Vec<BattleFormation>,
     pub hq_position: BattleHexCoord,
     pub courier_pool: Vec<EntityId>,
+    /// Tunnels the army is digging under enemy walls
+    #[serde(default)]
+    pub mines: Vec<Mine>,
 }
 
 impl Army {
@@ -271,6 +351,7 @@ impl Army {
             formations: Vec::new(),
             hq_position: BattleHexCoord::default(),
             courier_pool: Vec::new(),
+            mines: Vec::new(),
         }
     }
 
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::battle::battle_map::BattleMap;
 use crate::battle::constants::{BASE_VISION_RANGE, ELEVATION_VISION_BONUS, SCOUT_VISION_BONUS};
 use crate::battle::hex::BattleHexCoord;
+use crate::battle::recon::scout_post;
 use crate::battle::unit_type::UnitType;
 use crate::battle::units::{Army, BattleUnit};
 
@@ -54,7 +55,10 @@ pub fn unit_vision_range(unit: &BattleUnit, map: &BattleMap) -> u32 {
     let mut range = BASE_VISION_RANGE;
 
     // Scout bonus (LightCavalry acts as scouts)
-    if matches!(unit.unit_type, UnitType::LightCavalry) {
+    if matches!(
+        unit.unit_type,
+        UnitType::LightCavalry | UnitType::MountedScouts
+    ) {
         range += SCOUT_VISION_BONUS;
     }
 
@@ -81,6 +85,11 @@ pub fn calculate_army_visibility(map: &BattleMap, army: &Army) -> ArmyVisibility
             let range = unit_vision_range(unit, map);
             let unit_visible = map.visible_hexes(unit.position, range);
             visible.extend(unit_visible);
+
+            // Scouts out reconnoitring also report what they see from afar
+            if let Some(post) = scout_post(map, unit) {
+                visible.extend(map.visible_hexes(post, range));
+            }
         }
     }
 
@@ -169,6 +178,30 @@ mod tests {
         assert_eq!(scout_range - infantry_range, SCOUT_VISION_BONUS);
     }
 
+    #[test]
+    fn test_reconnoitring_scouts_see_further() {
+        use crate::battle::constants::SCOUT_PROBE_DISTANCE;
+        use crate::battle::hex::HexDirection;
+
+        let map = BattleMap::new(40, 20);
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut scouts = BattleUnit::new(UnitId::new(), UnitType::MountedScouts);
+        scouts.position = BattleHexCoord::new(5, 10);
+        scouts
+            .elements
+            .push(Element::new(vec![EntityId::new(); 10]));
+        formation.units.push(scouts);
+        army.formations.push(formation);
+
+        let range = unit_vision_range(&army.formations[0].units[0], &map);
+        let beyond = BattleHexCoord::new(5 + (range + SCOUT_PROBE_DISTANCE) as i32, 10);
+        assert!(!calculate_army_visibility(&map, &army).is_visible(beyond));
+
+        army.formations[0].units[0].scouting = Some(HexDirection::East);
+        assert!(calculate_army_visibility(&map, &army).is_visible(beyond));
+    }
+
     #[test]
     fn test_elevation_bonus_vision() {
         let mut map = BattleMap::new(20, 20);
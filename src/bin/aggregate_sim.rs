@@ -1,6 +1,6 @@
 //! Aggregate History Simulation binary
 
-use arc_citadel::aggregate::{simulate, SimulationConfig};
+use arc_citadel::aggregate::{simulate, EventType, HistoryChronicle, SimulationConfig};
 use std::time::Instant;
 
 fn main() {
@@ -28,6 +28,12 @@ fn main() {
     std::fs::write("simulation_output.json", &json).expect("Failed to write output");
     println!("\nFull output written to simulation_output.json");
 
+    // Keep the history for the game to tell
+    HistoryChronicle::from_output(&output)
+        .save("history_chronicle.json")
+        .expect("Failed to write chronicle");
+    println!("History chronicle written to history_chronicle.json");
+
     // Print some interesting stats
     println!("\n--- Species Summary ---");
 
@@ -53,4 +59,49 @@ fn main() {
     println!("Humans: {} polities survived", humans_alive);
     println!("Dwarves: {} polities survived", dwarves_alive);
     println!("Elves: {} polities survived", elves_alive);
+
+    println!("\n--- Religion Summary ---");
+    let count = |matches: fn(&EventType) -> bool| {
+        output
+            .history
+            .events
+            .iter()
+            .filter(|e| matches(&e.event_type))
+            .count()
+    };
+    println!(
+        "{} religions, {} from schisms",
+        output.final_world.religions.len(),
+        count(|e| matches!(e, EventType::Schism { .. })),
+    );
+    println!(
+        "{} holy wars",
+        count(|e| matches!(e, EventType::ReligiousWar { .. }))
+    );
+    for religion in &output.final_world.religions {
+        let followers = output
+            .final_world
+            .polities
+            .iter()
+            .filter(|p| p.alive && p.religion == Some(religion.id))
+            .count();
+        println!(
+            "  {} ({:?}, founded under {:?}): {} polities",
+            religion.name, religion.species, religion.founding_event, followers
+        );
+    }
+
+    println!("\n--- Technology Summary ---");
+    for innovation in &output.final_world.innovations {
+        let known = output
+            .final_world
+            .polities
+            .iter()
+            .filter(|p| p.alive && p.technologies.contains(&innovation.technology))
+            .count();
+        println!(
+            "  {} (begun by polity {} in year {}): {} polities",
+            innovation.technology, innovation.polity, innovation.year, known
+        );
+    }
 }
@@ -4,8 +4,8 @@
 
 use arc_citadel::battle::{
     ai::{load_personality, scoring, AiCommander, AiPersonality},
-    Army, ArmyId, BattleFormation, BattleMap, BattleState, BattleUnit, Element, FormationId,
-    UnitId, UnitType,
+    Army, ArmyId, BattleChronicle, BattleFormation, BattleMap, BattleState, BattleUnit, Element,
+    FormationId, UnitId, UnitType,
 };
 use arc_citadel::battle::hex::BattleHexCoord;
 use arc_citadel::core::types::EntityId;
@@ -147,6 +147,7 @@ fn main() {
     }
 
     // Run battle loop
+    let mut events = Vec::new();
     while !state.is_finished() && state.tick < args.max_ticks {
         if args.verbose {
             eprintln!("=== Tick {} (time_scale: {:.1}x) ===", state.tick, state.time_scale);
@@ -182,7 +183,7 @@ fn main() {
         }
 
         let events_before = state.battle_log.len();
-        let _events = state.run_tick();
+        events.extend(state.run_tick().events);
 
         if args.verbose {
             // Print new events
@@ -231,6 +232,8 @@ fn main() {
             println!();
             println!("Personalities: {} vs {}", result.friendly_personality, result.enemy_personality);
             println!("Seed: {}", result.seed);
+            println!();
+            println!("{}", BattleChronicle::compose(&state, &events, None).narrative());
         }
         _ => {
             eprintln!("Unknown format '{}', defaulting to json", args.format);
@@ -0,0 +1,392 @@
+//! Battle viewer - watch an AI vs AI tactical battle as it is fought.
+//!
+//! With `--plan` the friendly side is the player's: the battle opens in a
+//! planning phase where units are deployed, paths drawn and contingencies
+//! set from the side panel and clicks on the map, and starts once the plan
+//! validates.
+//!
+//! Controls:
+//!   WASD / Arrow keys: Pan camera
+//!   +/- / Mouse wheel: Zoom
+//!   Space: Pause / resume
+//!   1-4: Ticks per frame (1, 2, 5, 10)
+//!   P: Toggle waypoint plans
+//!   C: Toggle couriers
+//!   E: Toggle engagement markers
+//!   L: Toggle unit labels
+//!   Left click (planning): Deploy, add a waypoint, or mark a hex
+//!   Escape: Quit
+
+use std::sync::Arc;
+
+use clap::Parser;
+use egui_wgpu::ScreenDescriptor;
+use egui_winit::State as EguiWinitState;
+use glam::Vec2;
+use winit::{
+    event::{ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent},
+    event_loop::EventLoop,
+    keyboard::{KeyCode, PhysicalKey},
+    window::WindowBuilder,
+};
+
+use arc_citadel::battle::hex::BattleHexCoord;
+use arc_citadel::battle::{
+    ai::{load_personality, AiCommander, AiPersonality},
+    Army, ArmyId, BattleFormation, BattleMap, BattleState, BattleUnit, Element, FormationId,
+    UnitId, UnitType,
+};
+use arc_citadel::core::types::EntityId;
+use arc_citadel::renderer::hex::world_to_hex;
+use arc_citadel::renderer::state::{Color, Selection};
+use arc_citadel::renderer::tiles::{hex_center, TileLayer};
+use arc_citadel::renderer::{BattleView, CameraState, RenderState, Renderer};
+use arc_citadel::ui::planner::{self, PlanDraft};
+
+/// Battle viewer - render a live AI vs AI battle
+#[derive(Parser, Debug)]
+#[command(name = "battle_viewer")]
+struct Args {
+    /// Friendly AI personality name (loaded from data/ai_personalities/)
+    #[arg(long, default_value = "default")]
+    friendly: String,
+
+    /// Enemy AI personality name (loaded from data/ai_personalities/)
+    #[arg(long, default_value = "default")]
+    enemy: String,
+
+    /// Map width in hexes
+    #[arg(long, default_value_t = 60)]
+    map_width: u32,
+
+    /// Map height in hexes
+    #[arg(long, default_value_t = 30)]
+    map_height: u32,
+
+    /// Random seed for the AIs
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    /// Plan the friendly side's battle instead of leaving it to the AI
+    #[arg(long)]
+    plan: bool,
+}
+
+/// Ticks run per frame for keys 1-4
+const SPEEDS: [u32; 4] = [1, 2, 5, 10];
+
+fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive(tracing::Level::INFO.into()),
+        )
+        .init();
+
+    let args = Args::parse();
+    let personality = |name: &str| {
+        load_personality(name).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load personality '{}': {}", name, e);
+            AiPersonality::default()
+        })
+    };
+    let friendly_ai = AiCommander::with_seed(personality(&args.friendly), args.seed);
+    let enemy_ai = AiCommander::with_seed(personality(&args.enemy), args.seed.wrapping_add(1));
+
+    let center_r = (args.map_height / 2) as i32;
+    let map = BattleMap::new(args.map_width, args.map_height);
+    let friendly_army = create_army(BattleHexCoord::new(8, center_r), -2);
+    let enemy_army = create_army(BattleHexCoord::new(args.map_width as i32 - 8, center_r), 2);
+    let mut battle = BattleState::new(map, friendly_army, enemy_army);
+    battle.set_enemy_ai(Some(Box::new(enemy_ai)));
+    // While planning, the battle waits for the plan to validate
+    let mut planning = args
+        .plan
+        .then(|| PlanDraft::for_army(&battle.friendly_army));
+    if planning.is_none() {
+        battle.set_friendly_ai(Some(Box::new(friendly_ai)));
+        battle.start_battle();
+    }
+
+    let event_loop = EventLoop::new().expect("Failed to create event loop");
+    let window = Arc::new(
+        WindowBuilder::new()
+            .with_title("Arc Citadel - Battle Viewer")
+            .with_inner_size(winit::dpi::LogicalSize::new(1280, 720))
+            .build(&event_loop)
+            .expect("Failed to create window"),
+    );
+    let mut renderer = pollster::block_on(Renderer::new(window.clone()));
+
+    let egui_ctx = egui::Context::default();
+    let mut egui_winit_state = EguiWinitState::new(
+        egui_ctx.clone(),
+        egui::ViewportId::ROOT,
+        &window,
+        None,
+        None,
+    );
+    let mut egui_renderer =
+        egui_wgpu::Renderer::new(renderer.device(), renderer.surface_format(), None, 1);
+
+    let mut tiles = TileLayer::new();
+    let mut view = BattleView::default();
+    let far_corner = BattleHexCoord::new(args.map_width as i32, args.map_height as i32);
+    let mut camera = CameraState {
+        center: (hex_center(BattleHexCoord::new(0, 0)) + hex_center(far_corner)) * 0.5,
+        zoom: 1.0,
+        viewport_size: Vec2::new(1280.0, 720.0),
+    };
+    let mut paused = false;
+    let mut ticks_per_frame = SPEEDS[0];
+    let mut cursor: Option<Vec2> = None;
+
+    event_loop
+        .run(move |event, elwt| match event {
+            Event::WindowEvent { event, .. } => {
+                let consumed = egui_winit_state.on_window_event(&window, &event).consumed;
+                if consumed
+                    && !matches!(
+                        event,
+                        WindowEvent::RedrawRequested | WindowEvent::Resized(_)
+                    )
+                {
+                    return;
+                }
+                match event {
+                WindowEvent::CloseRequested => elwt.exit(),
+
+                WindowEvent::Resized(size) => {
+                    renderer.resize(size.width, size.height);
+                    camera.set_viewport_size(size.width as f32, size.height as f32);
+                }
+
+                WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed => {
+                    let pan_speed = 20.0 * camera.zoom;
+                    let PhysicalKey::Code(key) = event.physical_key else {
+                        return;
+                    };
+                    match key {
+                        KeyCode::KeyW | KeyCode::ArrowUp => camera.pan(Vec2::new(0.0, pan_speed)),
+                        KeyCode::KeyS | KeyCode::ArrowDown => {
+                            camera.pan(Vec2::new(0.0, -pan_speed))
+                        }
+                        KeyCode::KeyA | KeyCode::ArrowLeft => {
+                            camera.pan(Vec2::new(-pan_speed, 0.0))
+                        }
+                        KeyCode::KeyD | KeyCode::ArrowRight => {
+                            camera.pan(Vec2::new(pan_speed, 0.0))
+                        }
+                        KeyCode::Equal | KeyCode::NumpadAdd => camera.zoom_by(0.9),
+                        KeyCode::Minus | KeyCode::NumpadSubtract => camera.zoom_by(1.1),
+                        KeyCode::Space => paused = !paused,
+                        KeyCode::Digit1 => ticks_per_frame = SPEEDS[0],
+                        KeyCode::Digit2 => ticks_per_frame = SPEEDS[1],
+                        KeyCode::Digit3 => ticks_per_frame = SPEEDS[2],
+                        KeyCode::Digit4 => ticks_per_frame = SPEEDS[3],
+                        KeyCode::KeyP => view.show_plans = !view.show_plans,
+                        KeyCode::KeyC => view.show_couriers = !view.show_couriers,
+                        KeyCode::KeyE => view.show_engagements = !view.show_engagements,
+                        KeyCode::KeyL => view.show_labels = !view.show_labels,
+                        KeyCode::Escape => elwt.exit(),
+                        _ => {}
+                    }
+                }
+
+                WindowEvent::MouseWheel { delta, .. } => {
+                    let zoom_in = match delta {
+                        MouseScrollDelta::LineDelta(_, y) => y > 0.0,
+                        MouseScrollDelta::PixelDelta(pos) => pos.y > 0.0,
+                    };
+                    camera.zoom_by(if zoom_in { 0.9 } else { 1.1 });
+                }
+
+                WindowEvent::CursorMoved { position, .. } => {
+                    cursor = Some(Vec2::new(position.x as f32, position.y as f32));
+                }
+
+                WindowEvent::MouseInput {
+                    state: ElementState::Pressed,
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    if let (Some(draft), Some(at)) = (planning.as_mut(), cursor) {
+                        let hex = world_to_hex(camera.screen_to_world(at));
+                        draft.click_hex(BattleHexCoord::new(hex.q, hex.r));
+                    }
+                }
+
+                WindowEvent::RedrawRequested => {
+                    if let Some(draft) = &planning {
+                        // Show the draft as it stands: units where they
+                        // deploy, paths from there
+                        planner::install(draft.plan.clone(), &mut battle);
+                    } else if !paused {
+                        for _ in 0..ticks_per_frame {
+                            if battle.is_finished() {
+                                break;
+                            }
+                            battle.run_tick();
+                        }
+                    }
+
+                    // The viewer sees the whole field, fog and all
+                    tiles.sync(&battle.map, None);
+                    renderer.upload_tiles(&mut tiles);
+
+                    let mut state = RenderState {
+                        tick: battle.tick,
+                        entities: Vec::new(),
+                        sprites: Vec::new(),
+                        labels: Vec::new(),
+                        camera,
+                    };
+                    view.draw(&battle, &mut state);
+                    if let Some(unit) = planning.as_ref().and_then(|d| d.selected) {
+                        state.add_selection_ring(
+                            Selection::Entity(EntityId(unit.0)),
+                            Color::rgba(1.0, 1.0, 0.0, 1.0),
+                        );
+                    }
+
+                    let raw_input = egui_winit_state.take_egui_input(&window);
+                    egui_ctx.begin_frame(raw_input);
+                    if let Some(draft) = planning.as_mut() {
+                        let mut plan = None;
+                        egui::SidePanel::right("planner")
+                            .min_width(280.0)
+                            .show(&egui_ctx, |ui| {
+                                plan = planner::show(
+                                    ui,
+                                    draft,
+                                    &battle.friendly_army,
+                                    &battle.map,
+                                );
+                            });
+                        if let Some(plan) = plan {
+                            planner::install(plan, &mut battle);
+                            battle.start_battle();
+                            planning = None;
+                        }
+                    }
+                    let full_output = egui_ctx.end_frame();
+                    egui_winit_state.handle_platform_output(&window, full_output.platform_output);
+                    let paint_jobs =
+                        egui_ctx.tessellate(full_output.shapes, full_output.pixels_per_point);
+                    let (width, height) = renderer.size();
+                    let screen_descriptor = ScreenDescriptor {
+                        size_in_pixels: [width, height],
+                        pixels_per_point: full_output.pixels_per_point,
+                    };
+
+                    let render_result =
+                        renderer.render_with_egui(&state, |device, queue, encoder, view| {
+                            for (id, image_delta) in &full_output.textures_delta.set {
+                                egui_renderer.update_texture(device, queue, *id, image_delta);
+                            }
+                            egui_renderer.update_buffers(
+                                device,
+                                queue,
+                                encoder,
+                                &paint_jobs,
+                                &screen_descriptor,
+                            );
+                            {
+                                let mut render_pass =
+                                    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                                        label: Some("egui Render Pass"),
+                                        color_attachments: &[Some(
+                                            wgpu::RenderPassColorAttachment {
+                                                view,
+                                                resolve_target: None,
+                                                ops: wgpu::Operations {
+                                                    load: wgpu::LoadOp::Load,
+                                                    store: wgpu::StoreOp::Store,
+                                                },
+                                            },
+                                        )],
+                                        depth_stencil_attachment: None,
+                                        timestamp_writes: None,
+                                        occlusion_query_set: None,
+                                    });
+                                egui_renderer.render(
+                                    &mut render_pass,
+                                    &paint_jobs,
+                                    &screen_descriptor,
+                                );
+                            }
+                            for id in &full_output.textures_delta.free {
+                                egui_renderer.free_texture(id);
+                            }
+                        });
+
+                    match render_result {
+                        Ok(_) => {}
+                        Err(wgpu::SurfaceError::Lost) => {
+                            let (w, h) = renderer.size();
+                            renderer.resize(w, h);
+                        }
+                        Err(wgpu::SurfaceError::OutOfMemory) => {
+                            tracing::error!("Out of GPU memory!");
+                            elwt.exit();
+                        }
+                        Err(e) => tracing::warn!("Render error: {:?}", e),
+                    }
+
+                    window.set_title(&format!(
+                        "Arc Citadel - Battle | tick {} | {:?}{} | friendly {} vs enemy {} | {} couriers{}",
+                        battle.tick,
+                        battle.phase,
+                        if battle.is_finished() {
+                            format!(" ({:?})", battle.outcome)
+                        } else {
+                            String::new()
+                        },
+                        battle.friendly_army.effective_strength(),
+                        battle.enemy_army.effective_strength(),
+                        battle.courier_system.in_flight.len(),
+                        if paused { " | PAUSED" } else { "" },
+                    ));
+                }
+
+                _ => {}
+                }
+            }
+
+            Event::AboutToWait => window.request_redraw(),
+
+            _ => {}
+        })
+        .expect("Event loop error");
+}
+
+/// An army of one formation: infantry in the centre, spearmen on the
+/// flanks, archers `rear` hexes behind
+fn create_army(base: BattleHexCoord, rear: i32) -> Army {
+    let mut army = Army::new(ArmyId::new(), EntityId::new());
+    army.hq_position = base;
+
+    let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+    let units = [
+        (UnitType::Infantry, BattleHexCoord::new(base.q, base.r)),
+        (UnitType::Spearmen, BattleHexCoord::new(base.q, base.r - 3)),
+        (UnitType::Spearmen, BattleHexCoord::new(base.q, base.r + 3)),
+        (
+            UnitType::Archers,
+            BattleHexCoord::new(base.q + rear, base.r),
+        ),
+    ];
+    for (unit_type, position) in units {
+        let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+        unit.position = position;
+        unit.elements
+            .push(Element::new((0..40).map(|_| EntityId::new()).collect()));
+        formation.units.push(unit);
+    }
+    army.formations.push(formation);
+
+    for _ in 0..5 {
+        army.courier_pool.push(EntityId::new());
+    }
+    army
+}
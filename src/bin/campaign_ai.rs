@@ -1,13 +1,17 @@
 //! Campaign AI Agent
-//! Two AI factions compete in a campaign simulation
+//! Two AI factions compete in a campaign simulation, each played by
+//! `campaign::ai` with a personality of its own
 
+use arc_citadel::battle::ai::load_personality;
 use arc_citadel::campaign::{
-    apply_retreat, campaign_tick, resolve_battle, ArmyId, ArmyStance, BattleOutcome, CampaignMap,
-    CampaignState, HexCoord, RegionalWeather, ScoutId, ScoutSystem, SupplySystem, VisibilitySystem,
+    apply_retreat, campaign_tick, resolve_battle, ArmyId, ArmyStance, BaggageTrain, BattleOutcome,
+    CampaignAi, CampaignMap, CampaignState, FactionAi, HexCoord, Intent, RegionalWeather, ScoutId,
+    ScoutSystem, SupplySystem, VisibilitySystem,
 };
+use arc_citadel::city::vehicle::VehicleKind;
 use arc_citadel::core::types::PolityId;
 use clap::Parser;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Campaign AI - Two factions compete using AI decision-making
 #[derive(Parser, Debug)]
@@ -22,336 +26,71 @@ struct Args {
     #[arg(long, default_value_t = 200)]
     max_days: u32,
 
+    /// Personality playing Blue (from data/ai_personalities)
+    #[arg(long, default_value = "default")]
+    blue: String,
+
+    /// Personality playing Red (from data/ai_personalities)
+    #[arg(long, default_value = "aggressive")]
+    red: String,
+
     /// Print every AI decision
     #[arg(long, default_value_t = false)]
     verbose: bool,
 }
 
-/// What the AI knows about an army (own or enemy)
-#[derive(Debug, Clone)]
-struct ArmyInfo {
-    id: ArmyId,
-    position: HexCoord,
-    unit_count: u32,
-    morale: f32,
-    supplies_days: f32,
-}
-
-/// World model from a faction's perspective (fog of war)
-#[derive(Debug)]
-struct FactionWorldModel {
-    my_armies: Vec<ArmyInfo>,
-    known_enemies: Vec<ArmyInfo>,
-    explored_hexes: HashSet<HexCoord>,
-    enemy_depot: Option<HexCoord>,
-}
-
-/// Orders the AI can issue
-#[derive(Debug, Clone)]
-#[allow(dead_code)] // Attack variant reserved for future explicit attack orders
-enum AIOrder {
-    MoveTo { army: ArmyId, target: HexCoord },
-    Attack { army: ArmyId },
-    Retreat { army: ArmyId },
-    Hold { army: ArmyId },
-    DeployScout { from_army: ArmyId, target: HexCoord },
-}
+/// Scouts each faction may have out at once
+const MAX_SCOUTS: usize = 3;
 
-/// AI decision-maker for a single faction
-struct FactionAI {
+/// Send scouts from a faction's larger armies toward unexplored ground
+/// nearest the enemy seat
+fn deploy_scouts(
     faction: PolityId,
-    depot: HexCoord,
-    scout_ids: Vec<ScoutId>,
-}
-
-impl FactionAI {
-    fn new(faction: PolityId, depot: HexCoord) -> Self {
-        Self {
-            faction,
-            depot,
-            scout_ids: Vec::new(),
+    enemy_seat: HexCoord,
+    state: &CampaignState,
+    visibility: &VisibilitySystem,
+    scouts: &mut ScoutSystem,
+    deployed: &mut Vec<ScoutId>,
+    verbose: bool,
+) {
+    let explored: HashSet<HexCoord> = visibility
+        .get_faction(faction)
+        .map(|f| f.intel.keys().copied().collect())
+        .unwrap_or_default();
+    for army in state
+        .armies
+        .iter()
+        .filter(|a| a.faction == faction && a.unit_count > 100)
+    {
+        if deployed.len() >= MAX_SCOUTS {
+            return;
         }
-    }
-
-    fn perceive(
-        &self,
-        state: &CampaignState,
-        visibility: &VisibilitySystem,
-        supply: &SupplySystem,
-        enemy_depot: HexCoord,
-    ) -> FactionWorldModel {
-        let fv = visibility.get_faction(self.faction);
-
-        let my_armies: Vec<ArmyInfo> = state
-            .armies
-            .iter()
-            .filter(|a| a.faction == self.faction)
-            .map(|a| {
-                let supplies_days = supply
-                    .get_army_supply(a.id)
-                    .map(|s| s.days_until_starvation(a.unit_count))
-                    .unwrap_or(0.0);
-                ArmyInfo {
-                    id: a.id,
-                    position: a.position,
-                    unit_count: a.unit_count,
-                    morale: a.morale,
-                    supplies_days,
-                }
-            })
-            .collect();
-
-        // Use visibility system + direct distance check for nearby enemies
-        // This ensures we detect enemies within tactical range even in low-visibility terrain
-        let mut known_enemies: Vec<ArmyInfo> = visibility
-            .visible_enemies(self.faction, &state.armies)
-            .into_iter()
-            .map(|a| ArmyInfo {
-                id: a.id,
-                position: a.position,
-                unit_count: a.unit_count,
-                morale: a.morale,
-                supplies_days: 10.0,
-            })
-            .collect();
-
-        // Also detect enemies within 5 hexes of any of our armies (tactical proximity)
-        for my_army in &my_armies {
-            for enemy in state.armies.iter().filter(|a| a.faction != self.faction) {
-                let dist = my_army.position.distance(&enemy.position);
-                if dist <= 5 && !known_enemies.iter().any(|e| e.id == enemy.id) {
-                    known_enemies.push(ArmyInfo {
-                        id: enemy.id,
-                        position: enemy.position,
-                        unit_count: enemy.unit_count,
-                        morale: enemy.morale,
-                        supplies_days: 10.0,
-                    });
-                }
-            }
+        let Some(target) = find_scout_target(army.position, &explored, &state.map, enemy_seat)
+        else {
+            continue;
+        };
+        let scout_id = scouts.deploy_scout(army);
+        if let Some(s) = scouts.get_scout_mut(scout_id) {
+            s.assign_recon(target, &state.map);
         }
-
-        let explored_hexes: HashSet<HexCoord> = fv
-            .map(|f| f.intel.keys().copied().collect())
-            .unwrap_or_default();
-
-        let enemy_depot_known = explored_hexes.contains(&enemy_depot);
-
-        FactionWorldModel {
-            my_armies,
-            known_enemies,
-            explored_hexes,
-            enemy_depot: if enemy_depot_known { Some(enemy_depot) } else { None },
+        deployed.push(scout_id);
+        if verbose {
+            println!(
+                "  Scout deployed from {} to ({}, {})",
+                army.name, target.q, target.r
+            );
         }
     }
-
-    fn decide(&self, world: &FactionWorldModel, map: &CampaignMap, enemy_depot: HexCoord) -> Vec<AIOrder> {
-        let mut orders = Vec::new();
-
-        for army in &world.my_armies {
-            let nearest_enemy = world
-                .known_enemies
-                .iter()
-                .min_by_key(|e| army.position.distance(&e.position));
-
-            // CRITICAL: Check for adjacent enemies BEFORE supply check
-            // If enemy is right next to us, we fight regardless of supplies
-            if let Some(enemy) = nearest_enemy {
-                let distance = army.position.distance(&enemy.position);
-
-                // Adjacent or same hex - always engage (no retreat from contact)
-                // We MOVE TO the enemy position to enter the same hex and trigger battle
-                if distance <= 1 {
-                    orders.push(AIOrder::MoveTo {
-                        army: army.id,
-                        target: enemy.position,
-                    });
-                    continue;
-                }
-
-                // PURSUIT: If enemy is routed (low morale), chase aggressively within 6 hexes
-                // This is how battles become decisive - destroy retreating armies
-                if enemy_is_routed(enemy) && distance <= 6 {
-                    orders.push(AIOrder::MoveTo {
-                        army: army.id,
-                        target: enemy.position,
-                    });
-                    continue;
-                }
-
-                // Chase weak enemies (low unit count or morale) within 4 hexes
-                if enemy_is_weak(enemy) && distance <= 4 && army.morale > 0.4 {
-                    orders.push(AIOrder::MoveTo {
-                        army: army.id,
-                        target: enemy.position,
-                    });
-                    continue;
-                }
-
-                // Enemy is close (2-3 hexes) - chase them if we have any advantage
-                // or if we have reasonable supplies
-                if distance <= 3 && (has_advantage(army, enemy) || army.supplies_days > 2.0) {
-                    orders.push(AIOrder::MoveTo {
-                        army: army.id,
-                        target: enemy.position,
-                    });
-                    continue;
-                }
-            }
-
-            // Now check supplies - only retreat if critically low AND not in contact
-            // Threshold lowered from 3.0 to 1.5 days
-            if army.supplies_days < 1.5 {
-                // Only retreat if depot is reachable (within reasonable distance)
-                let depot_distance = army.position.distance(&self.depot);
-                if depot_distance <= 10 {
-                    orders.push(AIOrder::Retreat { army: army.id });
-                    continue;
-                }
-                // If depot too far, keep fighting - we'll die anyway
-            }
-
-            // Check if enemy visible but not adjacent (handled above)
-            if let Some(enemy) = nearest_enemy {
-                let distance = army.position.distance(&enemy.position);
-
-                // If we have advantage, pursue even at medium distance
-                if has_advantage(army, enemy) && distance <= 5 {
-                    orders.push(AIOrder::MoveTo {
-                        army: army.id,
-                        target: enemy.position,
-                    });
-                    continue;
-                }
-
-                // No advantage and enemy visible but not too close - continue toward objective
-                // (Don't retreat just because we see an enemy)
-            }
-
-            // Deploy scouts if we have spare capacity
-            if army.unit_count > 100 && army.supplies_days > 7.0 {
-                if let Some(scout_target) = find_scout_target(army.position, &world.explored_hexes, map, enemy_depot) {
-                    orders.push(AIOrder::DeployScout {
-                        from_army: army.id,
-                        target: scout_target,
-                    });
-                    // Don't continue - army can still move while scouts deploy
-                }
-            }
-
-            // Advance toward enemy depot if known
-            if let Some(enemy_depot) = world.enemy_depot {
-                if army.supplies_days > 5.0 && army.position != enemy_depot {
-                    orders.push(AIOrder::MoveTo {
-                        army: army.id,
-                        target: enemy_depot,
-                    });
-                    continue;
-                }
-            }
-
-            // If depot not known, advance toward enemy side of map to explore
-            // Blue (PolityId 1) goes toward (17, 17), Red (PolityId 2) goes toward (2, 2)
-            if world.enemy_depot.is_none() && army.supplies_days > 5.0 {
-                // Keep advancing until we find the enemy depot
-                let target = enemy_depot;
-                if army.position.distance(&target) > 2 {
-                    orders.push(AIOrder::MoveTo {
-                        army: army.id,
-                        target,
-                    });
-                    continue;
-                }
-            }
-
-            // Even if at enemy depot (holding it), don't need to hold order
-            orders.push(AIOrder::Hold { army: army.id });
-        }
-
-        orders
-    }
-
-    fn execute(
-        &mut self,
-        orders: Vec<AIOrder>,
-        state: &mut CampaignState,
-        scouts: &mut ScoutSystem,
-        verbose: bool,
-    ) {
-        for order in orders {
-            match order {
-                AIOrder::MoveTo { army, target } => {
-                    let map_clone = state.map.clone();
-                    if let Some(a) = state.get_army_mut(army) {
-                        a.order_move_to(target, &map_clone);
-                        a.stance = ArmyStance::Aggressive;
-                        if verbose {
-                            println!("  {} advances toward ({}, {})", a.name, target.q, target.r);
-                        }
-                    }
-                }
-                AIOrder::Attack { army } => {
-                    if let Some(a) = state.get_army_mut(army) {
-                        a.stance = ArmyStance::Aggressive;
-                        if verbose {
-                            println!("  {} set to aggressive, seeking engagement", a.name);
-                        }
-                    }
-                }
-                AIOrder::Retreat { army } => {
-                    let depot = self.depot;
-                    let map_clone = state.map.clone();
-                    if let Some(a) = state.get_army_mut(army) {
-                        a.order_move_to(depot, &map_clone);
-                        a.stance = ArmyStance::Evasive;
-                        if verbose {
-                            println!("  {} retreating to depot", a.name);
-                        }
-                    }
-                }
-                AIOrder::Hold { army } => {
-                    if let Some(a) = state.get_army_mut(army) {
-                        a.order_halt();
-                        a.stance = ArmyStance::Defensive;
-                        if verbose {
-                            println!("  {} holding position", a.name);
-                        }
-                    }
-                }
-                AIOrder::DeployScout { from_army, target } => {
-                    if let Some(a) = state.get_army(from_army) {
-                        if self.scout_ids.len() < 3 {
-                            let scout_id = scouts.deploy_scout(a);
-                            let map_clone = state.map.clone();
-                            if let Some(s) = scouts.get_scout_mut(scout_id) {
-                                s.assign_recon(target, &map_clone);
-                            }
-                            self.scout_ids.push(scout_id);
-                            if verbose {
-                                println!("  Scout deployed from {} to ({}, {})", a.name, target.q, target.r);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-}
-
-fn has_advantage(my_army: &ArmyInfo, enemy: &ArmyInfo) -> bool {
-    let my_strength = my_army.unit_count as f32 * my_army.morale;
-    let enemy_strength = enemy.unit_count as f32 * enemy.morale;
-    my_strength > enemy_strength * 1.2
 }
 
-/// Check if enemy is routed (broken morale) and should be pursued
-fn enemy_is_routed(enemy: &ArmyInfo) -> bool {
-    enemy.morale < 0.3
-}
-
-/// Check if enemy is weak and vulnerable to attack
-fn enemy_is_weak(enemy: &ArmyInfo) -> bool {
-    enemy.unit_count < 100 || enemy.morale < 0.5
+fn describe(intent: Intent) -> String {
+    let at = intent.target();
+    match intent {
+        Intent::Invade(_) => format!("marches to give battle at ({}, {})", at.q, at.r),
+        Intent::Defend(_) => format!("holds ({}, {})", at.q, at.r),
+        Intent::Raid(_) => format!("slips off to raid ({}, {})", at.q, at.r),
+        Intent::Retreat(_) => format!("falls back to ({}, {})", at.q, at.r),
+    }
 }
 
 fn find_scout_target(
@@ -548,15 +287,18 @@ fn main() {
     state.get_army_mut(blue_army1).unwrap().stance = ArmyStance::Aggressive;
     supply.register_army(blue_army1);
     supply.get_army_supply_mut(blue_army1).unwrap().foraging = true;
-    supply.get_army_supply_mut(blue_army1).unwrap().supplies = 30.0; // Extra supplies for campaign
-    supply.get_army_supply_mut(blue_army1).unwrap().max_supplies = 40.0;
+    supply.get_army_supply_mut(blue_army1).unwrap().supplies = 30.0;
+    // Wagons carry supplies enough for the march on the enemy seat
+    state.get_army_mut(blue_army1).unwrap().baggage =
+        Some(BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 4));
 
     let blue_army2 = state.spawn_army("Blue Guard".to_string(), PolityId(1), HexCoord::new(5, 5));
     state.get_army_mut(blue_army2).unwrap().unit_count = 200;
     supply.register_army(blue_army2);
     supply.get_army_supply_mut(blue_army2).unwrap().foraging = true;
     supply.get_army_supply_mut(blue_army2).unwrap().supplies = 30.0;
-    supply.get_army_supply_mut(blue_army2).unwrap().max_supplies = 40.0;
+    state.get_army_mut(blue_army2).unwrap().baggage =
+        Some(BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 2));
 
     let red_army1 = state.spawn_army("Red Host".to_string(), PolityId(2), red_depot);
     state.get_army_mut(red_army1).unwrap().unit_count = 350;
@@ -564,17 +306,39 @@ fn main() {
     supply.register_army(red_army1);
     supply.get_army_supply_mut(red_army1).unwrap().foraging = true;
     supply.get_army_supply_mut(red_army1).unwrap().supplies = 30.0;
-    supply.get_army_supply_mut(red_army1).unwrap().max_supplies = 40.0;
+    state.get_army_mut(red_army1).unwrap().baggage =
+        Some(BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 4));
 
     let red_army2 = state.spawn_army("Red Vanguard".to_string(), PolityId(2), HexCoord::new(15, 15));
     state.get_army_mut(red_army2).unwrap().unit_count = 150;
     supply.register_army(red_army2);
     supply.get_army_supply_mut(red_army2).unwrap().foraging = true;
     supply.get_army_supply_mut(red_army2).unwrap().supplies = 30.0;
-    supply.get_army_supply_mut(red_army2).unwrap().max_supplies = 40.0;
-
-    let mut blue_ai = FactionAI::new(PolityId(1), blue_depot);
-    let mut red_ai = FactionAI::new(PolityId(2), red_depot);
+    state.get_army_mut(red_army2).unwrap().baggage =
+        Some(BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 2));
+
+    let personality = |name: &str| {
+        load_personality(name).unwrap_or_else(|e| {
+            eprintln!("{}; playing the default personality", e);
+            Default::default()
+        })
+    };
+    println!("Blue plays {}, Red plays {}", args.blue, args.red);
+    println!();
+    let mut ai = CampaignAi::new();
+    ai.add_faction(FactionAi::with_seed(
+        PolityId(1),
+        blue_depot,
+        personality(&args.blue),
+        args.seed,
+    ));
+    ai.add_faction(FactionAi::with_seed(
+        PolityId(2),
+        red_depot,
+        personality(&args.red),
+        args.seed + 1,
+    ));
+    let mut scouts_out: HashMap<PolityId, Vec<ScoutId>> = HashMap::new();
 
     let mut stats = SimulationStats::new();
     let mut occupation = DepotOccupation::new();
@@ -590,41 +354,43 @@ fn main() {
         let scout_armies = scouts.armies_with_scouts();
         visibility.update(&state.armies, &scout_armies, &map, &weather, state.current_day);
 
-        let blue_world = blue_ai.perceive(&state, &visibility, &supply, red_depot);
-        let red_world = red_ai.perceive(&state, &visibility, &supply, blue_depot);
-
         if args.verbose {
             // Print army positions with morale
-            for army_info in &blue_world.my_armies {
-                let morale = state.get_army(army_info.id).map(|a| a.morale).unwrap_or(0.0);
-                println!("  Blue {}: at ({}, {}), {} units, morale {:.2}",
-                    state.get_army(army_info.id).map(|a| a.name.as_str()).unwrap_or("?"),
-                    army_info.position.q, army_info.position.r, army_info.unit_count, morale);
-            }
-            for army_info in &red_world.my_armies {
-                let morale = state.get_army(army_info.id).map(|a| a.morale).unwrap_or(0.0);
-                println!("  Red {}: at ({}, {}), {} units, morale {:.2}",
-                    state.get_army(army_info.id).map(|a| a.name.as_str()).unwrap_or("?"),
-                    army_info.position.q, army_info.position.r, army_info.unit_count, morale);
-            }
-
-            if !blue_world.known_enemies.is_empty() || !red_world.known_enemies.is_empty() {
-                println!("  Blue sees {} enemies, Red sees {} enemies",
-                    blue_world.known_enemies.len(), red_world.known_enemies.len());
+            for army in &state.armies {
+                let side = if army.faction == PolityId(1) {
+                    "Blue"
+                } else {
+                    "Red"
+                };
+                println!(
+                    "  {} {}: at ({}, {}), {} units, morale {:.2}",
+                    side, army.name, army.position.q, army.position.r, army.unit_count, army.morale
+                );
             }
         }
 
-        let blue_orders = blue_ai.decide(&blue_world, &map, red_depot);
-        let red_orders = red_ai.decide(&red_world, &map, blue_depot);
-
-        if args.verbose {
-            println!("Blue AI orders:");
+        for (faction, enemy_seat) in [(PolityId(1), red_depot), (PolityId(2), blue_depot)] {
+            let deployed = scouts_out.entry(faction).or_default();
+            deploy_scouts(
+                faction,
+                enemy_seat,
+                &state,
+                &visibility,
+                &mut scouts,
+                deployed,
+                args.verbose,
+            );
         }
-        blue_ai.execute(blue_orders, &mut state, &mut scouts, args.verbose);
-        if args.verbose {
-            println!("Red AI orders:");
+
+        for directive in ai.tick(&mut state, &visibility, &supply, &weather) {
+            if args.verbose {
+                let name = state
+                    .get_army(directive.army)
+                    .map(|a| a.name.as_str())
+                    .unwrap_or("?");
+                println!("  {} {}", name, describe(directive.intent));
+            }
         }
-        red_ai.execute(red_orders, &mut state, &mut scouts, args.verbose);
 
         supply.tick(&mut state.armies, &map, 1.0);
         scouts.tick(&state.armies, &map, 1.0, state.current_day);
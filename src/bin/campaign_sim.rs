@@ -1,12 +1,16 @@
 //! Campaign layer simulation test
 //! Tests army movement, supply, weather, visibility, battle resolution, and scouts
 
+use arc_citadel::aggregate::{AggregateWorld, Event, EventType};
 use arc_citadel::campaign::{
-    apply_retreat, campaign_tick, resolve_battle, ArmyStance, BattleOutcome, CampaignEvent,
-    CampaignMap, CampaignState, HexCoord, RegionalWeather, ScoutSystem, SupplySystem,
-    VisibilitySystem,
+    apply_retreat, campaign_tick, hex_road_work, load_default_lairs, resolve_battle,
+    settle_commanders, ArmyStance, BattleOutcome, CampaignEvent, CampaignMap, CampaignState,
+    HexCoord, Offer, RaidTarget, RegionalWeather, ScoutSystem, SupplySystem, VisibilitySystem,
 };
+use arc_citadel::city::road::RoadGrade;
 use arc_citadel::core::types::PolityId;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use std::collections::HashSet;
 use std::time::Instant;
 
@@ -89,6 +93,38 @@ fn main() {
     supply_system.register_army(army4);
     supply_system.get_army_supply_mut(army4).unwrap().foraging = true;
 
+    // The dark turns of the world's history left lairs in the wild
+    let history = [
+        Event {
+            id: 0,
+            year: 112,
+            event_type: EventType::PolityCollapsed {
+                polity: 3,
+                successor_states: vec![],
+            },
+            participants: vec![3],
+            location: None,
+        },
+        Event {
+            id: 1,
+            year: 140,
+            event_type: EventType::CorruptionSpreads {
+                polity: PolityId(4),
+                intensity: 0.6,
+            },
+            participants: vec![4],
+            location: None,
+        },
+    ];
+    state.lairs.seed(&map, &load_default_lairs(), &history, 42);
+    for lair in &state.lairs.lairs {
+        println!(
+            "Lair of {} ({}) at ({},{})",
+            lair.creature, lair.strength, lair.position.q, lair.position.r
+        );
+    }
+    println!();
+
     // Deploy scouts
     let scout1 = scouts.deploy_scout(state.get_army(army1).unwrap());
     let scout2 = scouts.deploy_scout(state.get_army(army3).unwrap());
@@ -120,14 +156,46 @@ fn main() {
     println!("  Eastern Legion: Move to (10, 10)");
     state.get_army_mut(army3).unwrap().order_move_to(HexCoord::new(10, 10), &map);
     println!("  Imperial Host: Move to (10, 10)");
-    state.get_army_mut(army4).unwrap().order_move_to(HexCoord::new(10, 15), &map);
-    println!("  Imperial Scouts: Move to (10, 15)");
+    let scouts_at = state.get_army(army4).unwrap().position;
+    let nearest_lair = state
+        .lairs
+        .lairs
+        .iter()
+        .min_by_key(|l| l.position.distance(&scouts_at))
+        .cloned();
+    if let Some(lair) = nearest_lair {
+        state.get_army_mut(army4).unwrap().order_expedition(&lair, &map);
+        println!(
+            "  Imperial Scouts: Clear the {} lair at ({}, {})",
+            lair.creature, lair.position.q, lair.position.r
+        );
+    } else {
+        state.get_army_mut(army4).unwrap().order_move_to(HexCoord::new(10, 15), &map);
+        println!("  Imperial Scouts: Move to (10, 15)");
+    }
+    state.get_army_mut(army2).unwrap().order_build_road(RoadGrade::DirtRoad);
+    println!("  Eastern Guard: Build a dirt road at (2, 2)");
+    state.road_works.plan(
+        &map,
+        PolityId(2),
+        HexCoord::new(19, 19),
+        HexCoord::new(15, 15),
+        RoadGrade::Trail,
+        hex_road_work(RoadGrade::Trail) / 4.0,
+    );
+    println!("  Western Empire: Lay a trail from its depot toward (15, 15)");
+    // With no history between them the two factions are strangers, neutral to each other
+    let strangers = AggregateWorld::new(vec![], vec![], ChaCha8Rng::seed_from_u64(42));
+    state.diplomacy.propose(PolityId(2), PolityId(1), Offer::Passage);
+    println!("  Western Empire: Ask the Eastern Kingdom for passage");
     println!();
 
     // Simulate campaign
     let sim_start = Instant::now();
     let mut total_events = 0;
     let mut arrivals = 0;
+    let mut roads_built = 0;
+    let mut lair_events = 0;
     let mut engagements = 0;
     let mut battles_resolved = 0;
     let mut supply_events = 0;
@@ -187,7 +255,8 @@ fn main() {
         scout_events += sc_events.len();
 
         // Process army movement
-        let events = campaign_tick(&mut state, 1.0);
+        let mut events = campaign_tick(&mut state, 1.0);
+        events.extend(state.diplomacy.resolve(&strangers, state.current_day));
 
         for event in &events {
             match event {
@@ -226,14 +295,22 @@ fn main() {
                         b_name, result.defender_casualties
                     );
 
+                    for fallen in settle_commanders(&result, &mut attacker, &mut defender, day as u64) {
+                        if let CampaignEvent::CommanderLost { commander, fate, .. } = fallen {
+                            println!("  {} was {:?}", commander.name, fate);
+                        }
+                    }
+
                     // Apply battle results back to state
                     if let Some(a) = state.get_army_mut(*army_a) {
                         a.unit_count = attacker.unit_count;
                         a.morale = attacker.morale;
+                        a.commander = attacker.commander;
                     }
                     if let Some(b) = state.get_army_mut(*army_b) {
                         b.unit_count = defender.unit_count;
                         b.morale = defender.morale;
+                        b.commander = defender.commander;
                     }
 
                     // Apply retreat to routed army
@@ -271,6 +348,105 @@ fn main() {
                     battles_resolved += 1;
                     engagements += 1;
                 }
+                CampaignEvent::CommanderLost { .. } => {}
+                CampaignEvent::ArmyForded { army, losses, .. } => {
+                    let army_data = state.get_army(*army).unwrap();
+                    println!(
+                        "Day {}: {} lost {} fording a river",
+                        day, army_data.name, losses
+                    );
+                }
+                CampaignEvent::RoadBuilt {
+                    polity,
+                    position,
+                    infrastructure,
+                    ..
+                } => {
+                    println!(
+                        "Day {}: Faction {} finished {:?} at ({}, {})",
+                        day, polity.0, infrastructure, position.q, position.r
+                    );
+                    roads_built += 1;
+                }
+                CampaignEvent::LairRaided {
+                    lair,
+                    target,
+                    losses,
+                } => {
+                    let creature = &state.lairs.get(*lair).unwrap().creature;
+                    match target {
+                        RaidTarget::Army(army) => println!(
+                            "Day {}: {} raided {}, killing {}",
+                            day,
+                            creature,
+                            state.get_army(*army).unwrap().name,
+                            losses
+                        ),
+                        RaidTarget::Settlement(at) => println!(
+                            "Day {}: {} raided the settlement at ({}, {})",
+                            day, creature, at.q, at.r
+                        ),
+                    }
+                    lair_events += 1;
+                }
+                CampaignEvent::LairCleared { lair, army, losses } => {
+                    println!(
+                        "Day {}: {} cleared the {} lair for {} dead",
+                        day,
+                        state.get_army(*army).unwrap().name,
+                        state.lairs.get(*lair).unwrap().creature,
+                        losses
+                    );
+                    lair_events += 1;
+                }
+                CampaignEvent::ExpeditionRepulsed { lair, army, losses } => {
+                    println!(
+                        "Day {}: {} was thrown back from the {} lair, losing {}",
+                        day,
+                        state.get_army(*army).unwrap().name,
+                        state.lairs.get(*lair).unwrap().creature,
+                        losses
+                    );
+                    lair_events += 1;
+                }
+                CampaignEvent::SiteExplored { army, loot, .. } => {
+                    if let Some(supply) = supply_system.get_army_supply_mut(*army) {
+                        supply.supplies += loot;
+                    }
+                    println!(
+                        "Day {}: {} explored the lair and carried off {:.0} days of supplies",
+                        day,
+                        state.get_army(*army).unwrap().name,
+                        loot
+                    );
+                    lair_events += 1;
+                }
+                CampaignEvent::OfferAccepted { from, to, offer } => {
+                    if let Offer::Tribute { days } = offer {
+                        // The payer's armies hand the supplies to the first of the receiver's
+                        let payer: Vec<_> = state.armies.iter().filter(|a| a.faction == *from).map(|a| a.id).collect();
+                        let mut paid = 0.0;
+                        for id in payer {
+                            if let Some(supply) = supply_system.get_army_supply_mut(id) {
+                                let share = supply.supplies.min(days - paid);
+                                supply.supplies -= share;
+                                paid += share;
+                            }
+                        }
+                        if let Some(receiver) = state.armies.iter().find(|a| a.faction == *to) {
+                            if let Some(supply) = supply_system.get_army_supply_mut(receiver.id) {
+                                supply.supplies += paid;
+                            }
+                        }
+                    }
+                    println!("Day {}: Faction {} accepted {:?} from Faction {}", day, to.0, offer, from.0);
+                }
+                CampaignEvent::OfferRefused { from, to, offer } => {
+                    println!("Day {}: Faction {} refused {:?} from Faction {}", day, to.0, offer, from.0);
+                }
+                CampaignEvent::AccordLapsed { from, to, offer } => {
+                    println!("Day {}: {:?} between Factions {} and {} lapsed", day, offer, from.0, to.0);
+                }
                 CampaignEvent::ArmyMoved { army, position } => {
                     // Log weather changes and occasional movement
                     if day % 10 == 0 {
@@ -321,6 +497,8 @@ fn main() {
     println!("  Arrivals: {}", arrivals);
     println!("  Engagements: {}", engagements);
     println!("  Battles resolved: {}", battles_resolved);
+    println!("  Roads and bridges built: {}", roads_built);
+    println!("  Lair raids and expeditions: {}", lair_events);
     println!("  Supply events: {}", supply_events);
     println!("  Scout events: {}", scout_events);
     println!("  Simulation time: {:?}", elapsed);
@@ -0,0 +1,82 @@
+//! Decision Trace Reader
+//!
+//! Reads a decision trace written by a headless run with --trace and
+//! explains what a traced human decided, or why they stopped doing
+//! something.
+//!
+//! ```text
+//! decision_trace decisions.jsonl Elena
+//! decision_trace decisions.jsonl Elena --why-stopped gather
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use arc_citadel::actions::catalog::ActionId;
+use arc_citadel::simulation::decision_trace::{decisions_of, explain, load_records, why_stopped};
+use clap::Parser;
+
+/// Explain the decisions recorded in a decision trace
+#[derive(Parser, Debug)]
+#[command(name = "decision_trace")]
+struct Args {
+    /// Trace file (JSON lines)
+    trace: PathBuf,
+
+    /// Name of the traced human
+    name: String,
+
+    /// Explain why they last stopped this action (e.g. gather, build)
+    #[arg(long)]
+    why_stopped: Option<String>,
+
+    /// Only decisions from this tick on
+    #[arg(long, default_value_t = 0)]
+    since: u64,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let records = match load_records(&args.trace) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", args.trace.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(action_name) = &args.why_stopped else {
+        let mut shown = 0;
+        for record in decisions_of(&records, &args.name).filter(|r| r.tick >= args.since) {
+            println!("{}", explain(record));
+            shown += 1;
+        }
+        if shown == 0 {
+            eprintln!("No decisions recorded for {}", args.name);
+            return ExitCode::FAILURE;
+        }
+        return ExitCode::SUCCESS;
+    };
+
+    let Some(action) = ActionId::from_name(action_name) else {
+        eprintln!("Unknown action {}", action_name);
+        return ExitCode::FAILURE;
+    };
+    match why_stopped(&records, &args.name, action) {
+        None => {
+            eprintln!("{} never chose {:?} in this trace", args.name, action);
+            ExitCode::FAILURE
+        }
+        Some((started, next)) => {
+            println!("Started: {}", explain(started));
+            match next {
+                Some(next) if next.replacing == Some(action) => {
+                    println!("Abandoned it: {}", explain(next))
+                }
+                Some(next) => println!("Finished or dropped it, then: {}", explain(next)),
+                None => println!("Still at it when the trace ended"),
+            }
+            ExitCode::SUCCESS
+        }
+    }
+}
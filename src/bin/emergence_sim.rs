@@ -230,6 +230,46 @@ fn main() {
                     println!("[DISPOSITION_CHANGE] tick={} e{} {} -> {} changed from {} to {} (trigger: {})",
                         tick, entity_idx, entity_name, target_name, old_disposition, new_disposition, trigger);
                 }
+                SimulationEvent::OathKept { swearer, tick } => {
+                    println!("[OATH] tick={} {} kept an oath", tick, swearer);
+                }
+                SimulationEvent::OathBroken { swearer, tick } => {
+                    println!("[OATH] tick={} {} broke an oath", tick, swearer);
+                }
+                SimulationEvent::RequestMade { asker, wish, tick } => {
+                    println!("[REQUEST] tick={} {} asks for {}", tick, asker, wish);
+                }
+                SimulationEvent::RequestGranted { asker, wish, tick } => {
+                    println!("[REQUEST] tick={} {} was granted {}", tick, asker, wish);
+                }
+                SimulationEvent::RequestLapsed { asker, wish, tick } => {
+                    println!("[REQUEST] tick={} {} asked in vain for {}", tick, asker, wish);
+                }
+                SimulationEvent::OmenSeen { reading, tick } => {
+                    println!("[OMEN] tick={} {}", tick, reading);
+                }
+                SimulationEvent::OmenUnheeded { rite, tick } => {
+                    println!("[OMEN] tick={} the omen passed with no {} held", tick, rite);
+                }
+                SimulationEvent::EraBegan { era, tick } => {
+                    println!("[ERA] tick={} here begins {}", tick, era);
+                }
+                SimulationEvent::Born { name, tick } => {
+                    println!("[BIRTH] tick={} {} was born", tick, name);
+                }
+                SimulationEvent::DiedOfOldAge { name, tick } => {
+                    println!("[DEATH] tick={} {} died of old age", tick, name);
+                }
+                SimulationEvent::WarDeclared {
+                    faction,
+                    against,
+                    tick,
+                } => {
+                    println!("[WAR] tick={} {} declared war on {}", tick, faction, against);
+                }
+                SimulationEvent::PlanProgress { tick, message, .. } => {
+                    println!("[PLAN] tick={} {}", tick, message);
+                }
                 SimulationEvent::GameOver { tick, outcome } => {
                     println!("[GAME_OVER] tick={} outcome={:?}", tick, outcome);
                 }
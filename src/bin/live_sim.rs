@@ -3,9 +3,15 @@
 //! Connects wgpu renderer to running simulation.
 //! Controls:
 //!   WASD / Arrow keys: Pan camera
+//!   Right drag: Pan camera
 //!   +/-: Zoom in/out
-//!   Mouse wheel: Zoom
+//!   Mouse wheel: Zoom toward the cursor
+//!   Left click: Select an entity to inspect
+//!   F: Follow the selection with the camera
 //!   Space: Pause/resume simulation
+//!   M: Show/hide the almanac
+//!   R: Show/hide settlers' requests
+//!   F3: Show/hide the debug overlay (each settler's action and fatigue)
 //!   Enter: Focus command input
 //!   Escape: Quit / Cancel command
 //!
@@ -19,6 +25,7 @@
 //!   save <filename>      - Save game state
 //!   load <filename>      - Load game state
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::sync::Arc;
@@ -35,13 +42,24 @@ use winit::{
 };
 
 use arc_citadel::actions::catalog::ActionId;
-use arc_citadel::core::types::{EntityId, Vec2 as SimVec2};
+use arc_citadel::city::building::{BuildingId, BuildingState};
+use arc_citadel::core::types::{EntityId, Species, Vec2 as SimVec2};
 use arc_citadel::ecs::world::{Abundance, World};
+use arc_citadel::entity::emotion::read_emotion;
 use arc_citadel::entity::tasks::{Task, TaskPriority, TaskSource};
-use arc_citadel::renderer::{CameraState, Color, RenderEntity, RenderState, Renderer, ShapeType};
-use arc_citadel::simulation::tick::run_simulation_tick;
+use arc_citadel::renderer::sprites::{
+    AnimationController, AnimationState, EmotionIndicator, EmotionOverlay,
+};
+use arc_citadel::renderer::text::FloatingTexts;
+use arc_citadel::renderer::{
+    CameraController, CameraState, Color, RenderEntity, RenderState, Renderer, Selection,
+    ShapeType, TextLabel,
+};
+use arc_citadel::simulation::almanac::{almanac, ALMANAC_DAYS};
+use arc_citadel::simulation::requests;
+use arc_citadel::simulation::tick::{run_simulation_tick, TICKS_PER_DAY};
 use arc_citadel::simulation::SimulationEvent;
-use arc_citadel::ui::{GameUI, LogCategory};
+use arc_citadel::ui::{inspector, GameUI, Inspection, LogCategory, SelectionEvent};
 
 /// Convert simulation Vec2 to renderer Vec2
 fn to_render_pos(v: SimVec2) -> Vec2 {
@@ -49,7 +67,16 @@ fn to_render_pos(v: SimVec2) -> Vec2 {
 }
 
 const ENTITY_COUNT: usize = 50;
+/// Drawn size of a building per unit of its footprint
+const BUILDING_DRAW_SCALE: f32 = 4.0;
 const WORLD_SIZE: f32 = 200.0;
+/// Names are drawn over entities once zoomed in this far (world units per pixel)
+const NAME_LABEL_ZOOM: f32 = 0.75;
+/// Label heights in screen pixels
+const NAME_LABEL_SIZE: f32 = 13.0;
+const DEBUG_LABEL_SIZE: f32 = 11.0;
+const DAMAGE_LABEL_SIZE: f32 = 16.0;
+const PLACE_LABEL_SIZE: f32 = 15.0;
 
 fn main() {
     // Initialize tracing
@@ -75,6 +102,12 @@ fn main() {
     // Create renderer
     let mut renderer = pollster::block_on(Renderer::new(window.clone()));
 
+    // Creature and building sprites; without the atlas everything stays a shape
+    let sprite_catalog = renderer
+        .load_atlas("data/sprites/atlas.toml")
+        .map_err(|e| tracing::warn!("No sprite atlas: {}", e))
+        .ok();
+
     // Create egui context and state
     let egui_ctx = egui::Context::default();
     let mut egui_winit_state = EguiWinitState::new(
@@ -145,12 +178,14 @@ fn main() {
         }
     }
 
-    // Camera centered on world
-    let mut camera = CameraState {
+    // Camera centered on world, kept over it
+    let mut camera = CameraController::new(CameraState {
         center: Vec2::new(WORLD_SIZE / 2.0, WORLD_SIZE / 2.0),
         zoom: 2.0,
         viewport_size: Vec2::new(1280.0, 720.0),
-    };
+    })
+    .with_bounds(Vec2::ZERO, Vec2::splat(WORLD_SIZE));
+    let mut last_frame_time = Instant::now();
 
     // Simulation state
     let mut paused = false;
@@ -161,8 +196,21 @@ fn main() {
     // Battle state for win/lose tracking
     let mut battle_state = BattleState::new(&world);
 
+    // Damage numbers rising over struck combatants
+    let mut floating_text = FloatingTexts::new();
+
+    // What each settler visibly feels, drawn over them and in how they stand
+    let emotion_overlay = EmotionOverlay::default();
+    let mut emotions: HashMap<EntityId, EmotionIndicator> = HashMap::new();
+
+    // How each settler's sprite moves: walking, working, fighting, asleep
+    let mut gaits: HashMap<EntityId, Gait> = HashMap::new();
+    let mut animated_tick = sim_ticks;
+
     // Mouse position tracking for entity selection
     let mut mouse_pos: Option<(f32, f32)> = None;
+    let mut pending_click: Option<Vec2> = None;
+    let mut dragging = false;
 
     // Pending command to execute (from egui)
     let mut pending_command: Option<String> = None;
@@ -191,24 +239,32 @@ fn main() {
                                 event: key_event, ..
                             } => {
                                 if key_event.state == ElementState::Pressed {
-                                    let pan_speed = 20.0 * camera.zoom;
-                                    match key_event.physical_key {
+                                    let pan_speed = 20.0 * camera.camera.zoom;
+                                    let pan = match key_event.physical_key {
                                         PhysicalKey::Code(KeyCode::KeyW)
                                         | PhysicalKey::Code(KeyCode::ArrowUp) => {
-                                            camera.pan(Vec2::new(0.0, pan_speed));
+                                            Some(Vec2::new(0.0, pan_speed))
                                         }
                                         PhysicalKey::Code(KeyCode::KeyS)
                                         | PhysicalKey::Code(KeyCode::ArrowDown) => {
-                                            camera.pan(Vec2::new(0.0, -pan_speed));
+                                            Some(Vec2::new(0.0, -pan_speed))
                                         }
                                         PhysicalKey::Code(KeyCode::KeyA)
                                         | PhysicalKey::Code(KeyCode::ArrowLeft) => {
-                                            camera.pan(Vec2::new(-pan_speed, 0.0));
+                                            Some(Vec2::new(-pan_speed, 0.0))
                                         }
                                         PhysicalKey::Code(KeyCode::KeyD)
                                         | PhysicalKey::Code(KeyCode::ArrowRight) => {
-                                            camera.pan(Vec2::new(pan_speed, 0.0));
+                                            Some(Vec2::new(pan_speed, 0.0))
                                         }
+                                        _ => None,
+                                    };
+                                    if let Some(delta) = pan {
+                                        // Steering the camera by hand stops following
+                                        game_ui.following = false;
+                                        camera.pan(delta);
+                                    }
+                                    match key_event.physical_key {
                                         PhysicalKey::Code(KeyCode::Equal)
                                         | PhysicalKey::Code(KeyCode::NumpadAdd) => {
                                             camera.zoom_by(0.9);
@@ -224,6 +280,18 @@ fn main() {
                                                 if paused { "PAUSED" } else { "RUNNING" }
                                             );
                                         }
+                                        PhysicalKey::Code(KeyCode::KeyM) => {
+                                            game_ui.show_almanac = !game_ui.show_almanac;
+                                        }
+                                        PhysicalKey::Code(KeyCode::KeyF) => {
+                                            game_ui.following = !game_ui.following;
+                                        }
+                                        PhysicalKey::Code(KeyCode::KeyR) => {
+                                            game_ui.show_requests = !game_ui.show_requests;
+                                        }
+                                        PhysicalKey::Code(KeyCode::F3) => {
+                                            game_ui.show_debug_labels = !game_ui.show_debug_labels;
+                                        }
                                         PhysicalKey::Code(KeyCode::Enter) => {
                                             // Focus command input
                                             game_ui.command_focused = true;
@@ -258,11 +326,29 @@ fn main() {
                                         }
                                     }
                                 };
-                                camera.zoom_by(zoom_factor);
+                                match mouse_pos {
+                                    Some((mx, my)) => {
+                                        camera.zoom_toward(Vec2::new(mx, my), zoom_factor)
+                                    }
+                                    None => camera.zoom_by(zoom_factor),
+                                }
                             }
 
                             WindowEvent::CursorMoved { position, .. } => {
-                                mouse_pos = Some((position.x as f32, position.y as f32));
+                                let now = (position.x as f32, position.y as f32);
+                                if let (true, Some((mx, my))) = (dragging, mouse_pos) {
+                                    game_ui.following = false;
+                                    camera.drag(Vec2::new(now.0 - mx, now.1 - my));
+                                }
+                                mouse_pos = Some(now);
+                            }
+
+                            WindowEvent::MouseInput {
+                                state,
+                                button: winit::event::MouseButton::Right,
+                                ..
+                            } => {
+                                dragging = *state == ElementState::Pressed;
                             }
 
                             WindowEvent::MouseInput {
@@ -270,33 +356,8 @@ fn main() {
                                 button: winit::event::MouseButton::Left,
                                 ..
                             } => {
-                                if let Some((mx, my)) = mouse_pos {
-                                    // Convert screen to world coordinates
-                                    let world_pos = camera.screen_to_world(Vec2::new(mx, my));
-
-                                    // Find entity near click (within 10 units, scaled by zoom)
-                                    let click_radius = 10.0 * camera.zoom;
-
-                                    let mut closest: Option<(arc_citadel::core::types::EntityId, f32)> = None;
-                                    for i in 0..world.humans.ids.len() {
-                                        if !world.humans.alive[i] {
-                                            continue;
-                                        }
-                                        let pos = world.humans.positions[i];
-                                        let dist = ((pos.x - world_pos.x).powi(2) + (pos.y - world_pos.y).powi(2)).sqrt();
-                                        if dist < click_radius {
-                                            if closest.is_none() || dist < closest.unwrap().1 {
-                                                closest = Some((world.humans.ids[i], dist));
-                                            }
-                                        }
-                                    }
-
-                                    if let Some((entity_id, _)) = closest {
-                                        game_ui.toggle_select(entity_id);
-                                    } else {
-                                        game_ui.deselect();
-                                    }
-                                }
+                                // Picked against the next frame's shapes
+                                pending_click = mouse_pos.map(|(mx, my)| Vec2::new(mx, my));
                             }
 
                             _ => {}
@@ -318,6 +379,7 @@ fn main() {
 
                         // Run simulation tick if not paused
                         if !paused {
+                            let fatigue_before = fatigue_snapshot(&world);
                             let events = run_simulation_tick(&mut world);
                             sim_ticks += 1;
 
@@ -334,7 +396,31 @@ fn main() {
                                         (format!("{} completed {:?}", entity_name, action), LogCategory::Action)
                                     }
                                     SimulationEvent::CombatHit { attacker, defender } => {
-                                        (format!("{} hit {}", attacker, defender), LogCategory::Combat)
+                                        if let Some((position, worn)) =
+                                            blow_landed(&world, &fatigue_before, &defender)
+                                        {
+                                            let (text, color) = if worn > 0.001 {
+                                                (
+                                                    format!("-{:.0}", worn * 100.0),
+                                                    Color::rgba(1.0, 0.3, 0.2, 1.0),
+                                                )
+                                            } else {
+                                                (
+                                                    "miss".to_string(),
+                                                    Color::rgba(0.8, 0.8, 0.8, 1.0),
+                                                )
+                                            };
+                                            floating_text.spawn(
+                                                text,
+                                                to_render_pos(position) + Vec2::new(0.0, 4.0),
+                                                color,
+                                                DAMAGE_LABEL_SIZE,
+                                            );
+                                        }
+                                        (
+                                            format!("{} hit {}", attacker, defender),
+                                            LogCategory::Combat,
+                                        )
                                     }
                                     SimulationEvent::ProductionComplete { recipe, .. } => {
                                         (format!("Produced: {}", recipe), LogCategory::Production)
@@ -344,6 +430,48 @@ fn main() {
                                     | SimulationEvent::ThoughtGenerated { .. }
                                     | SimulationEvent::SocialMemoryUpdate { .. }
                                     | SimulationEvent::DispositionChange { .. } => continue,
+                                    SimulationEvent::OathKept { swearer, .. } => {
+                                        (format!("{} kept an oath", swearer), LogCategory::System)
+                                    }
+                                    SimulationEvent::OathBroken { swearer, .. } => {
+                                        (format!("{} broke an oath", swearer), LogCategory::System)
+                                    }
+                                    SimulationEvent::RequestMade { asker, wish, .. } => {
+                                        (format!("{} asks for {}", asker, wish), LogCategory::System)
+                                    }
+                                    SimulationEvent::RequestGranted { asker, wish, .. } => (
+                                        format!("{} was granted {}", asker, wish),
+                                        LogCategory::System,
+                                    ),
+                                    SimulationEvent::RequestLapsed { asker, wish, .. } => (
+                                        format!("{} asked in vain for {}", asker, wish),
+                                        LogCategory::System,
+                                    ),
+                                    SimulationEvent::OmenSeen { reading, .. } => {
+                                        (reading.clone(), LogCategory::System)
+                                    }
+                                    SimulationEvent::OmenUnheeded { rite, .. } => (
+                                        format!("The omen passed with no {} held", rite),
+                                        LogCategory::System,
+                                    ),
+                                    SimulationEvent::EraBegan { era, .. } => {
+                                        (format!("Here begins {}", era), LogCategory::System)
+                                    }
+                                    SimulationEvent::Born { name, .. } => {
+                                        (format!("{} was born", name), LogCategory::System)
+                                    }
+                                    SimulationEvent::DiedOfOldAge { name, .. } => {
+                                        (format!("{} died of old age", name), LogCategory::System)
+                                    }
+                                    SimulationEvent::WarDeclared {
+                                        faction, against, ..
+                                    } => (
+                                        format!("{} declared war on {}", faction, against),
+                                        LogCategory::Combat,
+                                    ),
+                                    SimulationEvent::PlanProgress { message, .. } => {
+                                        (message, LogCategory::System)
+                                    }
                                     SimulationEvent::GameOver { outcome, .. } => {
                                         (format!("Game Over: {:?}", outcome), LogCategory::Combat)
                                     }
@@ -352,6 +480,9 @@ fn main() {
                             }
                         }
 
+                        let dt = last_frame_time.elapsed().as_secs_f32();
+                        last_frame_time = Instant::now();
+
                         // Extract entities for rendering
                         let mut entities =
                             Vec::with_capacity(world.humans.count() + world.food_zones.len() + 1);
@@ -359,7 +490,7 @@ fn main() {
                         // Render food zones as green hexagons
                         for zone in &world.food_zones {
                             entities.push(RenderEntity {
-                                id: arc_citadel::core::types::EntityId::new(),
+                                id: arc_citadel::core::types::EntityId::new().into(),
                                 position: to_render_pos(zone.position),
                                 facing: 0.0,
                                 shape: ShapeType::Hexagon,
@@ -369,57 +500,174 @@ fn main() {
                             });
                         }
 
-                        // Render humans
-                        for i in world.humans.iter_living() {
-                            let pos = world.humans.positions[i];
-                            let id = world.humans.ids[i];
-
-                            // Check if this entity is selected
-                            let is_selected = game_ui.selected_entity == Some(id);
-
-                            // Color: yellow highlight if selected, otherwise by need level
-                            let color = if is_selected {
-                                Color::rgba(1.0, 1.0, 0.0, 1.0) // Yellow highlight
-                            } else {
-                                // Color by need level (redder = more urgent needs)
-                                let needs = &world.humans.needs[i];
-                                let urgency = (needs.food + needs.rest + needs.social) / 3.0;
-                                Color::rgba(0.3 + urgency * 0.7, 0.7 - urgency * 0.5, 0.3, 1.0)
+                        // Render buildings as squares, faint while still going up. Where
+                        // the atlas has a sprite it is drawn over the shape, which
+                        // still answers clicks
+                        let mut sprites = Vec::new();
+                        let buildings = &world.buildings;
+                        for b in 0..buildings.count() {
+                            let (width, height) = buildings.building_types[b].size();
+                            let color = match buildings.states[b] {
+                                BuildingState::UnderConstruction => Color::rgba(0.6, 0.5, 0.3, 0.4),
+                                BuildingState::Complete => Color::rgba(0.6, 0.45, 0.25, 1.0),
+                                BuildingState::Damaged => Color::rgba(0.4, 0.3, 0.25, 1.0),
                             };
-
+                            let position = to_render_pos(buildings.positions[b]);
+                            let scale = width.max(height) * BUILDING_DRAW_SCALE;
                             entities.push(RenderEntity {
-                                id,
-                                position: to_render_pos(pos),
+                                id: buildings.ids[b].into(),
+                                position,
                                 facing: 0.0,
-                                shape: ShapeType::Circle,
+                                shape: ShapeType::Rectangle,
                                 color,
-                                scale: 3.0,
+                                scale,
                                 z_order: 1,
                             });
+                            if let Some(mut sprite) = sprite_catalog.as_ref().and_then(|c| {
+                                c.building_sprite(buildings.building_types[b], position, scale)
+                            }) {
+                                sprite.color[3] = (color.a * 255.0) as u8;
+                                sprites.push(sprite);
+                            }
                         }
 
-                        // Render orcs as red triangles
-                        for i in world.orcs.iter_living() {
-                            let pos = world.orcs.positions[i];
-                            let id = world.orcs.ids[i];
+                        // Render humans, posed by what they feel, with its overlay above,
+                        // animated by what they are doing
+                        let ticked = sim_ticks != animated_tick;
+                        animated_tick = sim_ticks;
+                        let human_sheet = sprite_catalog
+                            .as_ref()
+                            .and_then(|c| c.animation(Species::Human));
+                        let mut shown = HashMap::with_capacity(emotions.len());
+                        let mut walked = HashMap::with_capacity(gaits.len());
+                        for i in world.humans.iter_living() {
+                            let id = world.humans.ids[i];
+                            let pos = to_render_pos(world.humans.positions[i]);
+
+                            // Color by need level (redder = more urgent needs)
+                            let needs = &world.humans.needs[i];
+                            let urgency = (needs.food + needs.rest + needs.social) / 3.0;
+                            let color =
+                                Color::rgba(0.3 + urgency * 0.7, 0.7 - urgency * 0.5, 0.3, 1.0);
+
+                            let mut indicator = emotions.remove(&id).unwrap_or_default();
+                            indicator.set_emotion(read_emotion(
+                                needs,
+                                &world.humans.body_states[i],
+                                &world.humans.thoughts[i],
+                            ));
+                            indicator.update(dt, &emotion_overlay);
+                            let (position, scale, facing) =
+                                indicator.posture().apply(pos, 3.0, 0.0);
+
+                            let mut gait = gaits.remove(&id).unwrap_or_else(|| Gait::new(pos));
+                            if ticked {
+                                gait.step(pos);
+                            }
+                            if let Some(sheet) = human_sheet {
+                                let action = world.humans.task_queues[i]
+                                    .current()
+                                    .map(|task| task.action);
+                                let state = AnimationState::for_action(action, gait.moving);
+                                gait.animation.drive(state, sheet);
+                                gait.animation.update(dt, sheet);
+                            }
+                            sprites.extend(sprite_catalog.as_ref().and_then(|c| {
+                                c.animated_species_sprite(
+                                    id,
+                                    Species::Human,
+                                    &gait.animation,
+                                    position,
+                                    scale * 2.0,
+                                )
+                            }));
+                            walked.insert(id, gait);
+                            sprites.extend(indicator.sprite(id, pos, 3.0, &emotion_overlay));
+                            shown.insert(id, indicator);
 
                             entities.push(RenderEntity {
-                                id,
-                                position: to_render_pos(pos),
-                                facing: 0.0,
-                                shape: ShapeType::Triangle,
-                                color: Color::rgba(0.8, 0.2, 0.2, 1.0),
-                                scale: 4.0,
+                                id: id.into(),
+                                position,
+                                facing,
+                                shape: ShapeType::Circle,
+                                color,
+                                scale,
                                 z_order: 1,
                             });
                         }
+                        // The dead and departed feel nothing more
+                        emotions = shown;
+                        gaits = walked;
+
+                        // Render other species as triangles, orcs in red
+                        for archetype in world.archetypes() {
+                            let species = archetype.species();
+                            let color = match species {
+                                Species::Human => continue,
+                                Species::Orc => Color::rgba(0.8, 0.2, 0.2, 1.0),
+                                _ => Color::rgba(0.5, 0.5, 0.8, 1.0),
+                            };
+                            let parts = archetype.components();
+                            for i in parts.living() {
+                                let position = to_render_pos(parts.positions[i]);
+                                sprites.extend(sprite_catalog.as_ref().and_then(|c| {
+                                    c.species_sprite(parts.ids[i], species, position, 4.0)
+                                }));
+                                entities.push(RenderEntity {
+                                    id: parts.ids[i].into(),
+                                    position,
+                                    facing: 0.0,
+                                    shape: ShapeType::Triangle,
+                                    color,
+                                    scale: 4.0,
+                                    z_order: 1,
+                                });
+                            }
+                        }
+
+                        // Ease the camera, tracking the selection if following it
+                        floating_text.update(dt);
+                        let follow = game_ui
+                            .selection
+                            .filter(|_| game_ui.following)
+                            .and_then(|selection| selection_position(&world, selection))
+                            .map(to_render_pos);
 
-                        let state = RenderState {
+                        let mut state = RenderState {
                             tick: frame_count,
                             entities,
-                            sprites: vec![], // No sprites yet - using shapes for entities
-                            camera,
+                            sprites,
+                            labels: vec![],
+                            camera: camera.update(dt, follow),
                         };
+                        let zoom = state.camera.zoom;
+                        state.labels = place_labels(&world);
+                        state.labels.extend(entity_labels(&world, &game_ui, zoom));
+                        state.labels.extend(floating_text.labels(zoom));
+
+                        // Picking pass: select whatever was clicked on this frame
+                        if let Some(click) = pending_click.take() {
+                            if let Some(event) = game_ui.click(state.pick(click)) {
+                                let msg = match event {
+                                    SelectionEvent::Selected(selection) => {
+                                        format!(
+                                            "Selected {}",
+                                            describe_selection(&world, selection)
+                                        )
+                                    }
+                                    SelectionEvent::Deselected(selection) => {
+                                        format!(
+                                            "Deselected {}",
+                                            describe_selection(&world, selection)
+                                        )
+                                    }
+                                };
+                                game_ui.log(sim_ticks, msg, LogCategory::System);
+                            }
+                        }
+                        if let Some(selection) = game_ui.selection {
+                            state.add_selection_ring(selection, Color::rgba(1.0, 1.0, 0.0, 1.0));
+                        }
 
                         // Begin egui frame
                         let raw_input = egui_winit_state.take_egui_input(&window);
@@ -530,6 +778,202 @@ fn main() {
         .expect("Event loop error");
 }
 
+/// A settler's sprite animation, and whether they moved over the last tick
+struct Gait {
+    animation: AnimationController,
+    last_position: Vec2,
+    moving: bool,
+}
+
+impl Gait {
+    fn new(position: Vec2) -> Self {
+        Self {
+            animation: AnimationController::new(),
+            last_position: position,
+            moving: false,
+        }
+    }
+
+    /// Note where the settler stands after a tick
+    fn step(&mut self, position: Vec2) {
+        self.moving = position.distance_squared(self.last_position) > 1e-6;
+        self.last_position = position;
+    }
+}
+
+/// Fatigue of every human and orc, to tell afterwards how hard each blow landed
+fn fatigue_snapshot(world: &World) -> (Vec<f32>, Vec<f32>) {
+    (
+        world.humans.body_states.iter().map(|b| b.fatigue).collect(),
+        world.orcs.body_states.iter().map(|b| b.fatigue).collect(),
+    )
+}
+
+/// Where the defender named in a combat event stands, and the fatigue the
+/// blow cost them since `before` was taken
+fn blow_landed(
+    world: &World,
+    before: &(Vec<f32>, Vec<f32>),
+    defender: &str,
+) -> Option<(SimVec2, f32)> {
+    if let Some(i) = world.humans.names.iter().position(|n| n == defender) {
+        let was = before.0.get(i).copied().unwrap_or(0.0);
+        return Some((
+            world.humans.positions[i],
+            world.humans.body_states[i].fatigue - was,
+        ));
+    }
+    let i = world.orcs.names.iter().position(|n| n == defender)?;
+    let was = before.1.get(i).copied().unwrap_or(0.0);
+    Some((
+        world.orcs.positions[i],
+        world.orcs.body_states[i].fatigue - was,
+    ))
+}
+
+/// The names of the settlement, its districts, and its battle sites, over
+/// where they lie at every zoom
+fn place_labels(world: &World) -> Vec<TextLabel> {
+    world
+        .places
+        .iter()
+        .map(|place| {
+            TextLabel::new(
+                place.name.clone(),
+                to_render_pos(place.position),
+                Color::rgba(1.0, 0.85, 0.5, 0.9),
+                PLACE_LABEL_SIZE,
+            )
+        })
+        .collect()
+}
+
+/// Names over every creature once zoomed in, and under each settler their
+/// current action and fatigue while the debug overlay is on
+fn entity_labels(world: &World, game_ui: &GameUI, zoom: f32) -> Vec<TextLabel> {
+    let mut labels = Vec::new();
+    let names = zoom <= NAME_LABEL_ZOOM;
+    // Clear of the drawn shape, a few pixels either side
+    let above = Vec2::new(0.0, 4.0 + 3.0 * zoom);
+    let below = Vec2::new(0.0, 4.0 + (DEBUG_LABEL_SIZE + 3.0) * zoom);
+
+    for i in world.humans.iter_living() {
+        let position = to_render_pos(world.humans.positions[i]);
+        if names {
+            labels.push(TextLabel::new(
+                world.humans.names[i].clone(),
+                position + above,
+                Color::WHITE,
+                NAME_LABEL_SIZE,
+            ));
+        }
+        if game_ui.show_debug_labels {
+            let action = world.humans.task_queues[i]
+                .current()
+                .map(|task| format!("{:?}", task.action))
+                .unwrap_or_else(|| "Idle".to_string());
+            labels.push(TextLabel::new(
+                format!("{} f{:.2}", action, world.humans.body_states[i].fatigue),
+                position - below,
+                Color::CYAN,
+                DEBUG_LABEL_SIZE,
+            ));
+        }
+    }
+
+    if names {
+        for archetype in world.archetypes() {
+            if archetype.species() == Species::Human {
+                continue;
+            }
+            let parts = archetype.components();
+            for i in parts.living() {
+                labels.push(TextLabel::new(
+                    parts.names[i].clone(),
+                    to_render_pos(parts.positions[i]) + above,
+                    Color::rgba(1.0, 0.8, 0.8, 1.0),
+                    NAME_LABEL_SIZE,
+                ));
+            }
+        }
+    }
+    labels
+}
+
+/// Where a selection is in the world, if it is still there
+fn selection_position(world: &World, selection: Selection) -> Option<SimVec2> {
+    match selection {
+        Selection::Entity(id) => world.archetypes().into_iter().find_map(|archetype| {
+            let parts = archetype.components();
+            let i = parts.index_of(id)?;
+            parts.alive[i].then(|| parts.positions[i])
+        }),
+        Selection::Building(id) => world
+            .buildings
+            .index_of(id)
+            .map(|b| world.buildings.positions[b]),
+    }
+}
+
+/// Who or what a selection is, for the action log
+fn describe_selection(world: &World, selection: Selection) -> String {
+    match selection {
+        Selection::Entity(id) => world
+            .identities
+            .get(id)
+            .map(|identity| identity.known_as())
+            .unwrap_or_else(|| "a creature".to_string()),
+        Selection::Building(id) => match world.buildings.index_of(id) {
+            Some(b) => format!("{:?}", world.buildings.building_types[b]).to_lowercase(),
+            None => "a ruin".to_string(),
+        },
+    }
+}
+
+/// Building details for the side panel
+fn show_building(panel: &mut egui::Ui, world: &World, id: BuildingId) {
+    let buildings = &world.buildings;
+    let Some(b) = buildings.index_of(id) else {
+        panel.label("This building is gone");
+        return;
+    };
+    let kind = buildings.building_types[b];
+    panel.heading(format!("{:?}", kind));
+    match buildings.states[b] {
+        BuildingState::UnderConstruction => {
+            let progress = buildings.construction_progress[b] / kind.work_required();
+            panel.add(
+                egui::ProgressBar::new(progress.clamp(0.0, 1.0))
+                    .text(format!("Under construction, {:.0}%", progress * 100.0)),
+            );
+            panel.label(format!("Builders: {}", buildings.assigned_workers[b]));
+        }
+        BuildingState::Complete => {
+            panel.label("Complete");
+        }
+        BuildingState::Damaged => {
+            panel.label("Damaged");
+        }
+    }
+    if let Some(recipe) = &buildings.active_recipes[b] {
+        panel.label(format!(
+            "Producing {} ({:.0}%), {} workers",
+            recipe,
+            buildings.production_progress[b] * 100.0,
+            buildings.production_workers[b]
+        ));
+    }
+    let capacity = kind.housing_capacity();
+    if capacity > 0 {
+        let residents = world
+            .humans
+            .iter_living()
+            .filter(|&i| world.humans.assigned_houses[i] == Some(id))
+            .count();
+        panel.label(format!("Residents: {}/{}", residents, capacity));
+    }
+}
+
 /// Draw the game UI using egui
 fn draw_ui(ctx: &egui::Context, ui: &mut GameUI, world: &World, battle_state: &BattleState) {
     // Entity panel (right side)
@@ -539,32 +983,53 @@ fn draw_ui(ctx: &egui::Context, ui: &mut GameUI, world: &World, battle_state: &B
             .show(ctx, |panel| {
                 panel.heading("Entity");
 
-                if let Some(entity_id) = ui.selected_entity {
-                    if let Some(idx) = world.humans.index_of(entity_id) {
-                        panel.label(format!("Name: {}", world.humans.names[idx]));
-                        panel.label(format!(
-                            "Fatigue: {:.0}%",
-                            world.humans.body_states[idx].fatigue * 100.0
-                        ));
-
-                        panel.separator();
-                        panel.label("Needs:");
-                        let needs = &world.humans.needs[idx];
-                        panel.label(format!("  Food: {:.0}%", needs.food * 100.0));
-                        panel.label(format!("  Rest: {:.0}%", needs.rest * 100.0));
-                        panel.label(format!("  Safety: {:.0}%", needs.safety * 100.0));
-                        panel.label(format!("  Social: {:.0}%", needs.social * 100.0));
-                        panel.label(format!("  Purpose: {:.0}%", needs.purpose * 100.0));
-
-                        panel.separator();
-                        if let Some(task) = world.humans.task_queues[idx].current() {
-                            panel.label(format!("Task: {:?}", task.action));
-                        } else {
-                            panel.label("Task: Idle");
+                match ui.selection {
+                    Some(Selection::Entity(entity_id)) => match Inspection::of(world, entity_id) {
+                        Some(inspection) => inspector::show(panel, &inspection),
+                        None => {
+                            panel.label("Only settlers can be inspected");
                         }
+                    },
+                    Some(Selection::Building(building_id)) => {
+                        show_building(panel, world, building_id);
+                    }
+                    None => {
+                        panel.label("Click an entity or building to select");
                     }
-                } else {
-                    panel.label("Click an entity to select");
+                }
+            });
+    }
+
+    // Almanac (left side)
+    if ui.show_almanac {
+        egui::SidePanel::left("almanac_panel")
+            .default_width(250.0)
+            .show(ctx, |panel| {
+                panel.heading(format!("Almanac - Day {}", world.astronomy.current_day));
+                for line in almanac(world, ALMANAC_DAYS).lines() {
+                    panel.label(line);
+                }
+            });
+    }
+
+    // Requests (floating)
+    if ui.show_requests {
+        egui::Window::new("Requests")
+            .default_width(300.0)
+            .show(ctx, |window| {
+                let mut any = false;
+                for request in world.requests.open() {
+                    let days_left =
+                        request.deadline_tick.saturating_sub(world.current_tick) / TICKS_PER_DAY;
+                    window.label(format!(
+                        "{} ({} days left)",
+                        requests::describe(world, request),
+                        days_left
+                    ));
+                    any = true;
+                }
+                if !any {
+                    window.label("No one is asking for anything");
                 }
             });
     }
@@ -833,25 +1298,17 @@ fn execute_command(world: &mut World, command: &str, tick: u64) -> String {
     }
 }
 
-/// Find an entity by name (searches humans and orcs)
+/// Find an entity by name (searches every species, humans first)
 fn find_entity_by_name(world: &World, name: &str) -> Option<EntityId> {
     let name_lower = name.to_lowercase();
 
-    // Search humans
-    for i in world.humans.iter_living() {
-        if world.humans.names[i].to_lowercase().contains(&name_lower) {
-            return Some(world.humans.ids[i]);
-        }
-    }
-
-    // Search orcs
-    for i in world.orcs.iter_living() {
-        if world.orcs.names[i].to_lowercase().contains(&name_lower) {
-            return Some(world.orcs.ids[i]);
-        }
-    }
-
-    None
+    world.archetypes().into_iter().find_map(|archetype| {
+        let parts = archetype.components();
+        parts
+            .living()
+            .find(|&i| parts.names[i].to_lowercase().contains(&name_lower))
+            .map(|i| parts.ids[i])
+    })
 }
 
 /// Simplified save state for MVP
@@ -230,6 +230,12 @@ fn main() {
         GameOutcome::Draw => {
             println!("Result: DRAW (mutual annihilation)");
         }
+        GameOutcome::Contented { happiness } => {
+            println!("Result: CONTENTED (happiness {:.0}%)", happiness * 100.0);
+        }
+        GameOutcome::Overthrown { happiness } => {
+            println!("Result: OVERTHROWN (happiness {:.0}%)", happiness * 100.0);
+        }
         GameOutcome::InProgress => {
             println!("Result: IN PROGRESS (simulation ended before conclusion)");
         }
@@ -272,6 +272,7 @@ fn select_actions(world: &mut World) {
             perceived_dispositions: vec![],
             building_skill: world.humans.building_skills[i],
             nearest_building_site: None,
+            aspiration: None,
         };
 
         if let Some(task) = select_action_human(&ctx) {
@@ -68,7 +68,7 @@ fn main() {
             );
 
             entities.push(RenderEntity {
-                id: EntityId::new(),
+                id: EntityId::new().into(),
                 position: Vec2::new(i as f32 * spacing, j as f32 * spacing),
                 facing: 0.0,
                 shape,
@@ -155,6 +155,7 @@ fn main() {
                         tick: frame_count,
                         entities: entities.clone(),
                         sprites: vec![],
+                        labels: vec![],
                         camera,
                     };
 
@@ -0,0 +1,67 @@
+//! Soak test - a founded settlement left to live for a century
+//! Fails (exit code 1) if the settlement dies out, overcrowds, piles up
+//! memory, or leaves no history
+
+use arc_citadel::simulation::soak::{found_settlement, run_soak, SoakConfig};
+use clap::Parser;
+
+/// Soak test - run a settlement for generations at compressed time
+#[derive(Parser, Debug)]
+#[command(name = "soak_test")]
+#[command(about = "Run a founded settlement for a simulated century")]
+struct Args {
+    /// Years to run
+    #[arg(long, default_value_t = 100)]
+    years: u32,
+
+    /// Simulation steps per day (must divide 1000; fewer is faster)
+    #[arg(long, default_value_t = 1)]
+    steps_per_day: u64,
+
+    /// Years between orc raids (0 for none)
+    #[arg(long, default_value_t = 10)]
+    raid_every: u32,
+
+    /// World seed
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+}
+
+fn main() {
+    let args = Args::parse();
+    println!("=== SOAK TEST: {} years ===\n", args.years);
+
+    let mut world = found_settlement(args.seed);
+    let config = SoakConfig {
+        years: args.years,
+        steps_per_day: args.steps_per_day,
+        raid_every_years: args.raid_every,
+    };
+    let result = run_soak(&mut world, config, |year| {
+        println!(
+            "Year {:3}: {:3} living, {:4} blows, {:6} KB snapshot, {:4} chronicle lines ({:.1?})",
+            year.year,
+            year.population,
+            year.blows,
+            year.snapshot_bytes / 1024,
+            year.chronicle_entries,
+            year.elapsed
+        );
+    });
+
+    match result {
+        Ok(report) => {
+            println!("\n=== Passed ===");
+            println!("  Raids: {}", report.raids);
+            println!("  Blows landed: {}", report.blows());
+            println!("  Everyone who lived there: {}", report.settlers);
+            println!("  Chronicle lines: {}", world.legacy.chronicle.len());
+            println!("  Eras begun: {}", world.eras.begun.len());
+            println!("  Time: {:.1?}", report.elapsed());
+        }
+        Err(e) => {
+            println!("\n=== FAILED: {} ===", e);
+            std::process::exit(1);
+        }
+    }
+}
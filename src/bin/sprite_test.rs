@@ -19,7 +19,7 @@ use winit::{
 };
 
 use arc_citadel::core::types::EntityId;
-use arc_citadel::renderer::{CameraState, RenderState, Renderer, SpriteEntity};
+use arc_citadel::renderer::{AtlasId, CameraState, RenderState, Renderer, SpriteEntity};
 
 const GRID_SIZE: usize = 10;
 const SPRITE_SPACING: f32 = 20.0;
@@ -55,6 +55,7 @@ fn main() {
             (0..GRID_SIZE).map(move |j| SpriteEntity {
                 id: EntityId::new(),
                 position: Vec2::new(i as f32 * SPRITE_SPACING, j as f32 * SPRITE_SPACING),
+                atlas: AtlasId::WHITE,
                 uv_rect: [0.0, 0.0, 1.0, 1.0],
                 color: [(i * 25) as u8, (j * 25) as u8, 128, 255],
                 rotation: (i + j) as f32 * 0.3,
@@ -166,6 +167,7 @@ fn main() {
                             tick: frame_count,
                             entities: Vec::new(),
                             sprites: sprites.clone(),
+                            labels: Vec::new(),
                             camera,
                         };
 
@@ -5,16 +5,17 @@
 //! properties computed at spawn time, along with mutable state that changes during gameplay.
 
 use glam::Vec2;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::schema::{BlueprintId, PropertyOverrides};
 
 /// Unique identifier for an instance
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct InstanceId(pub u64);
 
 /// Tracking who placed this instance and when
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PlacedBy {
     /// Terrain generation during worldgen
     TerrainGen,
@@ -25,7 +26,7 @@ pub enum PlacedBy {
 }
 
 /// Evaluated military properties (cached at instantiation)
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MilitaryProperties {
     pub max_hp: f32,
     pub hardness: f32,
@@ -38,7 +39,7 @@ pub struct MilitaryProperties {
 }
 
 /// Evaluated civilian properties (cached at instantiation)
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CivilianProperties {
     pub pedestrian_capacity: u32,
     pub cart_accessible: bool,
@@ -52,7 +53,7 @@ pub struct CivilianProperties {
 }
 
 /// Evaluated geometry (cached at instantiation)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvaluatedGeometry {
     pub width: f32,
     pub depth: f32,
@@ -61,7 +62,7 @@ pub struct EvaluatedGeometry {
 }
 
 /// A resolved anchor point
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResolvedAnchor {
     pub name: String,
     pub position: glam::Vec3,
@@ -70,14 +71,14 @@ pub struct ResolvedAnchor {
 }
 
 /// Breach in a damaged structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Breach {
     pub position: Vec2,
     pub width: f32,
 }
 
 /// A spawned instance of a blueprint
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlueprintInstance {
     pub id: InstanceId,
     pub blueprint_id: BlueprintId,
@@ -0,0 +1,586 @@
+//! Strategic AI for the campaign layer
+//!
+//! Each faction the AI plays has a `FactionAi`: its seat and an
+//! `AiPersonality`, the same TOML presets the battle AI uses. Every
+//! `difficulty.reaction_delay` days it reviews its armies against what it
+//! knows: enemy armies it has seen lately and enemy depots it has explored
+//! (`FactionVisibility`, or everything if it ignores the fog of war), the
+//! seats of the other factions, each army's days of supply (stretched by
+//! foraging), and the weather where the army stands. Each army is given
+//! one `Intent`, issued as an `ArmyOrder` and a stance:
+//!
+//! - **Retreat** to the nearest depot when supplies would not last the march
+//!   to the army's target or back, morale breaks, or an enemy close by is too
+//!   strong
+//! - **Defend** the seat when an enemy comes near it, or hold in foul weather
+//! - **Invade**: march on an enemy army, depot, or seat the odds favour
+//! - **Raid** an unguarded enemy depot, slipping past its armies
+//!
+//! Caution, aggression, and `attack_value` set the odds an army wants
+//! before it gives battle; initiative decides whether a faction marches on
+//! depots and seats or waits for enemy armies, and cunning whether it raids. On
+//! each review an army may be overlooked (`difficulty.mistake_chance`) and
+//! keep its old orders. Factions at peace (`Diplomacy::at_peace`) leave each
+//! other be.
+
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::map::{CampaignMap, HexCoord};
+use super::route::{Army, ArmyId, ArmyStance, CampaignState};
+use super::supply::{calculate_forage_yield, SupplySystem};
+use super::visibility::{HexVisibility, VisibilitySystem};
+use super::weather::RegionalWeather;
+use crate::battle::ai::AiPersonality;
+use crate::core::types::PolityId;
+
+/// Hexes from a seat or army within which an enemy is a threat to it
+pub const THREAT_RANGE: i32 = 4;
+
+/// Hexes around a depot or seat an enemy army must be within to guard it
+pub const GUARD_RANGE: i32 = 2;
+
+/// Days of supply an army keeps in hand beyond the march, scaled up by caution
+pub const SUPPLY_MARGIN_DAYS: f32 = 2.0;
+
+/// Weather movement modifier below which armies hold where they stand
+pub const FOUL_WEATHER: f32 = 0.5;
+
+/// Least favourable odds any personality will give battle at
+pub const MIN_ODDS: f32 = 0.5;
+
+/// Initiative a faction needs to march on depots and seats, rather than wait
+/// for enemy armies to come
+pub const TAKE_FIELD_INITIATIVE: f32 = 0.5;
+
+/// Cunning a faction needs to raid rather than invade
+pub const RAID_CUNNING: f32 = 0.4;
+
+/// What an army is set to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intent {
+    /// March on an enemy army, depot, or seat to give battle
+    Invade(HexCoord),
+    /// Hold a hex against those who come
+    Defend(HexCoord),
+    /// Strike at an unguarded enemy depot, avoiding battle on the way
+    Raid(HexCoord),
+    /// Fall back to a depot
+    Retreat(HexCoord),
+}
+
+impl Intent {
+    pub fn target(&self) -> HexCoord {
+        match *self {
+            Intent::Invade(at) | Intent::Defend(at) | Intent::Raid(at) | Intent::Retreat(at) => at,
+        }
+    }
+
+    pub fn stance(&self) -> ArmyStance {
+        match self {
+            Intent::Invade(_) => ArmyStance::Aggressive,
+            Intent::Defend(_) => ArmyStance::Defensive,
+            Intent::Raid(_) | Intent::Retreat(_) => ArmyStance::Evasive,
+        }
+    }
+
+    /// Give the army the orders and stance this intent calls for
+    pub fn issue(&self, army: &mut Army, map: &CampaignMap) {
+        army.stance = self.stance();
+        match *self {
+            Intent::Defend(at) => army.order_guard(at),
+            Intent::Invade(to) | Intent::Raid(to) | Intent::Retreat(to) => {
+                army.order_move_to(to, map)
+            }
+        }
+    }
+}
+
+/// An enemy army as a faction knows it: where it was last seen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sighting {
+    pub army: ArmyId,
+    pub faction: PolityId,
+    pub position: HexCoord,
+    pub strength: f32,
+}
+
+/// An army the AI has given new intent to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AiDirective {
+    pub faction: PolityId,
+    pub army: ArmyId,
+    pub intent: Intent,
+}
+
+/// Fighting strength of an army: its soldiers, as far as their heart holds
+pub fn strength(army: &Army) -> f32 {
+    army.unit_count as f32 * army.morale
+}
+
+/// The AI playing one faction
+pub struct FactionAi {
+    pub faction: PolityId,
+    /// Where the faction rules from; defended when threatened
+    pub home: HexCoord,
+    pub personality: AiPersonality,
+    last_review: Option<f32>,
+    intents: HashMap<ArmyId, Intent>,
+    rng: StdRng,
+}
+
+impl FactionAi {
+    pub fn new(faction: PolityId, home: HexCoord, personality: AiPersonality) -> Self {
+        Self::with_seed(faction, home, personality, faction.0 as u64)
+    }
+
+    /// Create with a specific RNG seed for its mistakes
+    pub fn with_seed(
+        faction: PolityId,
+        home: HexCoord,
+        personality: AiPersonality,
+        seed: u64,
+    ) -> Self {
+        Self {
+            faction,
+            home,
+            personality,
+            last_review: None,
+            intents: HashMap::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// What an army was last set to do
+    pub fn intent(&self, army: ArmyId) -> Option<Intent> {
+        self.intents.get(&army).copied()
+    }
+
+    /// Whether enough days have passed to review the armies again
+    pub fn due(&self, day: f32) -> bool {
+        let delay = self.personality.difficulty.reaction_delay.max(1) as f32;
+        self.last_review.is_none_or(|last| day >= last + delay)
+    }
+
+    /// Odds (own strength over theirs) wanted before giving battle
+    pub fn odds_needed(&self) -> f32 {
+        let behavior = &self.personality.behavior;
+        let attack_value = self.personality.weights.attack_value.max(0.1);
+        ((1.0 + behavior.caution - behavior.aggression) / attack_value).max(MIN_ODDS)
+    }
+
+    fn hostile(&self, other: PolityId, state: &CampaignState) -> bool {
+        other != self.faction
+            && !state
+                .diplomacy
+                .at_peace(self.faction, other, state.current_day)
+    }
+
+    /// The hostile armies this faction knows of, where it last saw them
+    pub fn sightings(&self, state: &CampaignState, visibility: &VisibilitySystem) -> Vec<Sighting> {
+        let sighting = |army: &Army, position: HexCoord| Sighting {
+            army: army.id,
+            faction: army.faction,
+            position,
+            strength: strength(army),
+        };
+        if self.personality.difficulty.ignores_fog_of_war {
+            return state
+                .armies
+                .iter()
+                .filter(|a| a.unit_count > 0 && self.hostile(a.faction, state))
+                .map(|a| sighting(a, a.position))
+                .collect();
+        }
+        let Some(fv) = visibility.get_faction(self.faction) else {
+            return Vec::new();
+        };
+
+        let mut seen: Vec<(Sighting, f32)> = Vec::new();
+        for (&hex, intel) in &fv.intel {
+            if !intel.is_recent(state.current_day) {
+                continue;
+            }
+            for &id in &intel.known_armies {
+                if let Some(army) = state.get_army(id) {
+                    if army.unit_count > 0 && self.hostile(army.faction, state) {
+                        seen.push((sighting(army, hex), intel.last_seen_day));
+                    }
+                }
+            }
+        }
+        // Each army where it was seen last
+        seen.sort_by(|a, b| a.0.army.0.cmp(&b.0.army.0).then(b.1.total_cmp(&a.1)));
+        seen.dedup_by_key(|(s, _)| s.army);
+        seen.into_iter().map(|(s, _)| s).collect()
+    }
+
+    /// Hostile depots this faction has explored, and the seats of hostile
+    /// factions, which everyone knows
+    pub fn strongholds(
+        &self,
+        state: &CampaignState,
+        seats: &HashMap<PolityId, HexCoord>,
+        visibility: &VisibilitySystem,
+        supply: &SupplySystem,
+    ) -> Vec<HexCoord> {
+        let all_seen = self.personality.difficulty.ignores_fog_of_war;
+        let fv = visibility.get_faction(self.faction);
+        let mut holds: Vec<HexCoord> = supply
+            .depots
+            .iter()
+            .filter(|d| self.hostile(d.owner, state))
+            .filter(|d| {
+                all_seen
+                    || fv.is_some_and(|fv| fv.get_visibility(&d.position) != HexVisibility::Unknown)
+            })
+            .map(|d| d.position)
+            .collect();
+        let mut seated: Vec<(&PolityId, &HexCoord)> = seats.iter().collect();
+        seated.sort_by_key(|(faction, _)| faction.0);
+        for (&faction, &seat) in seated {
+            if self.hostile(faction, state) && !holds.contains(&seat) {
+                holds.push(seat);
+            }
+        }
+        holds
+    }
+
+    /// The nearest of the faction's depots to fall back to, or its seat
+    fn fallback(&self, from: HexCoord, supply: &SupplySystem) -> HexCoord {
+        supply
+            .depots
+            .iter()
+            .filter(|d| d.owner == self.faction)
+            .map(|d| d.position)
+            .min_by_key(|at| from.distance(at))
+            .unwrap_or(self.home)
+    }
+
+    /// Choose what one army should do
+    #[allow(clippy::too_many_arguments)]
+    pub fn decide(
+        &self,
+        army: &Army,
+        defends_home: bool,
+        sightings: &[Sighting],
+        strongholds: &[HexCoord],
+        supply: &SupplySystem,
+        weather: &RegionalWeather,
+        map: &CampaignMap,
+    ) -> Intent {
+        let behavior = &self.personality.behavior;
+        let weights = &self.personality.weights;
+        let pace = weather.movement_modifier_at(&army.position, map).max(0.1);
+        let march = |to: &HexCoord| army.position.distance(to) as f32 / pace;
+        // Living off the land stretches what the army carries
+        let supply_days = supply
+            .get_army_supply(army.id)
+            .map(|s| {
+                let forage = map
+                    .get(&army.position)
+                    .filter(|_| s.foraging)
+                    .map(|tile| calculate_forage_yield(tile.terrain))
+                    .unwrap_or(0.0);
+                s.days_until_starvation(army.unit_count) / (1.0 - forage).max(0.1)
+            })
+            .unwrap_or(f32::INFINITY);
+        let margin = SUPPLY_MARGIN_DAYS * (1.0 + behavior.caution);
+        let own = strength(army);
+        let odds = |against: f32| {
+            if against > 0.0 {
+                own / against
+            } else {
+                f32::INFINITY
+            }
+        };
+        let needed = self.odds_needed();
+
+        // Fall back when the supplies, the men's nerve, or the odds give out.
+        // An army on campaign needs only enough to reach its target, where
+        // it means to live off what it takes.
+        let depot = self.fallback(army.position, supply);
+        let onward = match self.intent(army.id) {
+            Some(Intent::Invade(to) | Intent::Raid(to)) => march(&to).min(march(&depot)),
+            _ => march(&depot),
+        };
+        let overmatched = sightings.iter().any(|s| {
+            s.position.distance(&army.position) <= THREAT_RANGE
+                && odds(s.strength) < weights.retreat_threshold
+        });
+        if army.morale < weights.retreat_threshold || supply_days < onward + margin || overmatched {
+            if army.position == depot {
+                return Intent::Defend(depot);
+            }
+            return Intent::Retreat(depot);
+        }
+
+        // Meet an enemy nearing the seat, or hold the seat against it
+        if defends_home {
+            let threat = sightings
+                .iter()
+                .filter(|s| s.position.distance(&self.home) <= THREAT_RANGE)
+                .max_by(|a, b| a.strength.total_cmp(&b.strength));
+            if let Some(threat) = threat {
+                if odds(threat.strength) >= needed {
+                    return Intent::Invade(threat.position);
+                }
+                return Intent::Defend(self.home);
+            }
+        }
+
+        // Wait out a blizzard or sandstorm
+        if pace < FOUL_WEATHER {
+            return Intent::Defend(army.position);
+        }
+
+        // Of what can be reached on the supplies in hand, the nearest worth
+        // taking on
+        let in_reach = |to: &HexCoord| march(to) + margin <= supply_days;
+        let mut targets: Vec<(Intent, i32)> = Vec::new();
+        for s in sightings {
+            if in_reach(&s.position) && odds(s.strength) >= needed {
+                targets.push((
+                    Intent::Invade(s.position),
+                    army.position.distance(&s.position),
+                ));
+            }
+        }
+        for hold in strongholds.iter().filter(|at| in_reach(at)) {
+            let guard: f32 = sightings
+                .iter()
+                .filter(|s| s.position.distance(hold) <= GUARD_RANGE)
+                .map(|s| s.strength)
+                .sum();
+            let distance = army.position.distance(hold);
+            if guard == 0.0 && behavior.cunning >= RAID_CUNNING {
+                targets.push((Intent::Raid(*hold), distance));
+            } else if behavior.initiative >= TAKE_FIELD_INITIATIVE && odds(guard) >= needed {
+                targets.push((Intent::Invade(*hold), distance));
+            }
+        }
+        targets
+            .into_iter()
+            .min_by_key(|(intent, distance)| (*distance, matches!(intent, Intent::Raid(_))))
+            .map(|(intent, _)| intent)
+            .unwrap_or(Intent::Defend(self.home))
+    }
+
+    /// Review every army of the faction not locked in battle, and issue
+    /// orders to those whose intent changed
+    pub fn review(
+        &mut self,
+        state: &mut CampaignState,
+        seats: &HashMap<PolityId, HexCoord>,
+        visibility: &VisibilitySystem,
+        supply: &SupplySystem,
+        weather: &RegionalWeather,
+    ) -> Vec<AiDirective> {
+        self.last_review = Some(state.current_day);
+        let sightings = self.sightings(state, visibility);
+        let strongholds = self.strongholds(state, seats, visibility, supply);
+        // The army nearest the seat answers threats to it
+        let defender = state
+            .armies
+            .iter()
+            .filter(|a| a.faction == self.faction && a.unit_count > 0)
+            .min_by_key(|a| (a.position.distance(&self.home), a.id.0))
+            .map(|a| a.id);
+
+        let mut chosen = Vec::new();
+        for army in &state.armies {
+            if army.faction != self.faction || army.unit_count == 0 || army.engaged_with.is_some() {
+                continue;
+            }
+            let previous = self.intent(army.id);
+            if previous.is_some()
+                && self.rng.gen::<f32>() < self.personality.difficulty.mistake_chance
+            {
+                continue; // Overlooked this time
+            }
+            let intent = self.decide(
+                army,
+                defender == Some(army.id),
+                &sightings,
+                &strongholds,
+                supply,
+                weather,
+                &state.map,
+            );
+            if previous != Some(intent) {
+                chosen.push((army.id, intent));
+            }
+        }
+
+        let mut directives = Vec::new();
+        for (id, intent) in chosen {
+            if let Some(army) = state.armies.iter_mut().find(|a| a.id == id) {
+                intent.issue(army, &state.map);
+            }
+            self.intents.insert(id, intent);
+            directives.push(AiDirective {
+                faction: self.faction,
+                army: id,
+                intent,
+            });
+        }
+        directives
+    }
+}
+
+/// The AI for every faction it plays
+#[derive(Default)]
+pub struct CampaignAi {
+    pub factions: Vec<FactionAi>,
+    /// Each faction's seat, as every faction knows them
+    pub seats: HashMap<PolityId, HexCoord>,
+}
+
+impl CampaignAi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Have the AI play a faction
+    pub fn add_faction(&mut self, faction: FactionAi) {
+        self.seats.insert(faction.faction, faction.home);
+        self.factions.push(faction);
+    }
+
+    /// Make a seat known to the AI factions, such as a player's capital
+    pub fn know_seat(&mut self, faction: PolityId, seat: HexCoord) {
+        self.seats.insert(faction, seat);
+    }
+
+    pub fn get(&self, faction: PolityId) -> Option<&FactionAi> {
+        self.factions.iter().find(|f| f.faction == faction)
+    }
+
+    /// Review the armies of each faction that is due. Returns the new
+    /// intents given.
+    pub fn tick(
+        &mut self,
+        state: &mut CampaignState,
+        visibility: &VisibilitySystem,
+        supply: &SupplySystem,
+        weather: &RegionalWeather,
+    ) -> Vec<AiDirective> {
+        let mut directives = Vec::new();
+        for faction in &mut self.factions {
+            if faction.due(state.current_day) {
+                directives.extend(faction.review(state, &self.seats, visibility, supply, weather));
+            }
+        }
+        directives
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::ai::load_personality;
+    use crate::campaign::diplomacy::{Offer, Proposal};
+    use crate::campaign::route::ArmyOrder;
+
+    const BLUE: PolityId = PolityId(1);
+    const RED: PolityId = PolityId(2);
+    const BLUE_SEAT: HexCoord = HexCoord { q: 2, r: 2 };
+    const RED_SEAT: HexCoord = HexCoord { q: 8, r: 5 };
+
+    /// A Blue army of `blue` soldiers, well supplied, three hexes from the
+    /// Red seat and its depot, where a Red army of `red` stands guard
+    fn skirmish(blue: u32, red: u32) -> (CampaignState, SupplySystem, ArmyId) {
+        let mut state = CampaignState::new(CampaignMap::generate_simple(20, 20, 42));
+        let mut supply = SupplySystem::new();
+        supply.create_depot(BLUE_SEAT, BLUE);
+        supply.create_depot(RED_SEAT, RED);
+        let ours = state.spawn_army("Blue Legion".into(), BLUE, HexCoord::new(5, 5));
+        state.get_army_mut(ours).unwrap().unit_count = blue;
+        supply.register_army(ours);
+        supply.get_army_supply_mut(ours).unwrap().supplies = 60.0;
+        let theirs = state.spawn_army("Red Host".into(), RED, RED_SEAT);
+        state.get_army_mut(theirs).unwrap().unit_count = red;
+        (state, supply, ours)
+    }
+
+    fn playing(personality: &str) -> FactionAi {
+        let mut personality = load_personality(personality).unwrap();
+        personality.difficulty.ignores_fog_of_war = true;
+        personality.difficulty.mistake_chance = 0.0; // No mistakes for deterministic test
+        FactionAi::new(BLUE, BLUE_SEAT, personality)
+    }
+
+    fn review(ai: &mut FactionAi, state: &mut CampaignState, supply: &SupplySystem) -> Intent {
+        let seats = HashMap::from([(BLUE, BLUE_SEAT), (RED, RED_SEAT)]);
+        ai.review(
+            state,
+            &seats,
+            &VisibilitySystem::new(),
+            supply,
+            &RegionalWeather::new(),
+        );
+        ai.intent(state.armies[0].id).unwrap()
+    }
+
+    #[test]
+    fn test_the_bold_attack_what_the_cautious_wait_out() {
+        let (mut state, supply, ours) = skirmish(300, 240);
+        let mut bold = playing("aggressive");
+        assert_eq!(
+            review(&mut bold, &mut state, &supply),
+            Intent::Invade(RED_SEAT)
+        );
+        let army = state.get_army(ours).unwrap();
+        assert!(matches!(army.orders, Some(ArmyOrder::MoveTo(_))));
+        assert_eq!(army.stance, ArmyStance::Aggressive);
+
+        let (mut state, supply, _) = skirmish(300, 240);
+        let mut wary = playing("cautious");
+        assert_eq!(
+            review(&mut wary, &mut state, &supply),
+            Intent::Defend(BLUE_SEAT)
+        );
+    }
+
+    #[test]
+    fn test_short_supplies_send_an_army_home() {
+        let (mut state, mut supply, ours) = skirmish(300, 100);
+        supply.get_army_supply_mut(ours).unwrap().supplies = 2.0;
+        let mut ai = playing("aggressive");
+        assert_eq!(
+            review(&mut ai, &mut state, &supply),
+            Intent::Retreat(BLUE_SEAT)
+        );
+        assert_eq!(state.get_army(ours).unwrap().stance, ArmyStance::Evasive);
+    }
+
+    #[test]
+    fn test_unguarded_depot_is_raided_until_peace() {
+        let (mut state, supply, _) = skirmish(300, 300);
+        state.armies[1].position = HexCoord::new(15, 15);
+        let mut ai = playing("cunning");
+        assert_eq!(review(&mut ai, &mut state, &supply), Intent::Raid(RED_SEAT));
+
+        let ceasefire = Proposal {
+            from: RED,
+            to: BLUE,
+            offer: Offer::Ceasefire,
+        };
+        state.diplomacy.accept(ceasefire, state.current_day);
+        assert_eq!(
+            review(&mut ai, &mut state, &supply),
+            Intent::Defend(BLUE_SEAT)
+        );
+    }
+
+    #[test]
+    fn test_reviews_wait_on_reaction_delay() {
+        let mut easy = playing("easy");
+        assert!(easy.due(0.0));
+        easy.last_review = Some(0.0);
+        assert!(!easy.due(4.0));
+        assert!(easy.due(5.0));
+    }
+}
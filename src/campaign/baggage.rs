@@ -0,0 +1,130 @@
+//! Baggage trains - the campaign-scale use of carts and wagons
+//!
+//! An army's baggage train extends how many days of supplies it can carry,
+//! using the same vehicle kinds the settlement builds. Wheels need clear
+//! ground: rough terrain slows the whole army down and wears the vehicles,
+//! and mountains or swamps are barely passable at all.
+
+use serde::{Deserialize, Serialize};
+
+use super::map::CampaignTerrain;
+use crate::city::vehicle::{VehicleKind, BREAKDOWN_CONDITION, HAND_CARRY_CAPACITY};
+
+/// Resource units that make up one supply-day for 100 soldiers
+pub const SUPPLY_UNITS_PER_DAY: f32 = 10.0;
+
+/// Condition lost per day of travel through rough terrain
+pub const ROUGH_TERRAIN_WEAR: f32 = 0.05;
+
+/// A column of vehicles travelling with an army
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaggageTrain {
+    /// Vehicle kind and how many of them
+    pub vehicles: Vec<(VehicleKind, u32)>,
+    /// Average condition of the column (0.0-1.0)
+    pub condition: f32,
+}
+
+impl BaggageTrain {
+    pub fn new() -> Self {
+        Self {
+            vehicles: Vec::new(),
+            condition: 1.0,
+        }
+    }
+
+    pub fn with_vehicles(mut self, kind: VehicleKind, count: u32) -> Self {
+        self.add_vehicles(kind, count);
+        self
+    }
+
+    pub fn add_vehicles(&mut self, kind: VehicleKind, count: u32) {
+        if let Some(entry) = self.vehicles.iter_mut().find(|(k, _)| *k == kind) {
+            entry.1 += count;
+        } else {
+            self.vehicles.push((kind, count));
+        }
+    }
+
+    pub fn vehicle_count(&self) -> u32 {
+        self.vehicles.iter().map(|(_, n)| *n).sum()
+    }
+
+    /// Whether the train is too worn to haul
+    pub fn is_broken_down(&self) -> bool {
+        self.condition <= BREAKDOWN_CONDITION
+    }
+
+    /// Extra supply-days (normalized to 100 soldiers) the train can carry
+    pub fn supply_capacity(&self) -> f32 {
+        if self.is_broken_down() {
+            return 0.0;
+        }
+        let units: f32 = self
+            .vehicles
+            .iter()
+            .map(|(kind, n)| HAND_CARRY_CAPACITY as f32 * kind.capacity_multiplier() * *n as f32)
+            .sum();
+        units / SUPPLY_UNITS_PER_DAY
+    }
+
+    /// Movement cost multiplier for entering terrain with this train
+    pub fn terrain_multiplier(terrain: CampaignTerrain) -> f32 {
+        match terrain {
+            CampaignTerrain::Plains
+            | CampaignTerrain::Desert
+            | CampaignTerrain::Coast
+            | CampaignTerrain::River => 1.0,
+            CampaignTerrain::Forest | CampaignTerrain::Hills => 1.5,
+            CampaignTerrain::Mountains | CampaignTerrain::Swamp => 3.0,
+        }
+    }
+
+    /// Wear the train after travelling through terrain for a number of days
+    pub fn apply_travel_wear(&mut self, terrain: CampaignTerrain, days: f32) {
+        let multiplier = Self::terrain_multiplier(terrain);
+        if multiplier > 1.0 {
+            self.condition = (self.condition - ROUGH_TERRAIN_WEAR * multiplier * days).max(0.0);
+        }
+    }
+
+    /// Repair the train (e.g. while resting at a depot)
+    pub fn repair(&mut self, amount: f32) {
+        self.condition = (self.condition + amount).min(1.0);
+    }
+}
+
+impl Default for BaggageTrain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supply_capacity_scales_with_vehicles() {
+        let carts = BaggageTrain::new().with_vehicles(VehicleKind::Cart, 5);
+        let wagons = BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 5);
+
+        assert!((carts.supply_capacity() - 30.0).abs() < 0.01);
+        assert!(wagons.supply_capacity() > carts.supply_capacity());
+    }
+
+    #[test]
+    fn test_rough_terrain_wears_train() {
+        let mut train = BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 2);
+
+        train.apply_travel_wear(CampaignTerrain::Plains, 3.0);
+        assert_eq!(train.condition, 1.0);
+
+        train.apply_travel_wear(CampaignTerrain::Mountains, 7.0);
+        assert!(train.is_broken_down());
+        assert_eq!(train.supply_capacity(), 0.0);
+
+        train.repair(0.5);
+        assert!(!train.is_broken_down());
+    }
+}
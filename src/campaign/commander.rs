@@ -0,0 +1,304 @@
+//! Army commanders - the people who lead armies on campaign
+//!
+//! An army is led by an actual entity. What they have learned of command
+//! (their Leadership chunks) sets how hard they can march the army, how well
+//! it forages, and how far it sees; what they value sets the odds at which
+//! they will give battle. A commander can fall in battle - killed or taken -
+//! and a leaderless army loses heart and marches without their skill.
+
+use serde::{Deserialize, Serialize};
+
+use super::battle::{BattleOutcome, BattleResult};
+use super::route::{Army, CampaignEvent};
+use crate::core::types::EntityId;
+use crate::entity::species::human::HumanValues;
+use crate::skills::{ChunkId, ChunkLibrary};
+
+/// Chunks for keeping an army fed, ordered, and on the road
+const LOGISTICS_CHUNKS: [ChunkId; 4] = [
+    ChunkId::LeadCampaignPlanning,
+    ChunkId::LeadOrganizationBuilding,
+    ChunkId::LeadClearOrder,
+    ChunkId::LeadDirectFormation,
+];
+
+/// Chunks for setting foraging parties to work
+const FORAGING_CHUNKS: [ChunkId; 3] = [
+    ChunkId::LeadDelegateTask,
+    ChunkId::LeadAssessUnitState,
+    ChunkId::LeadOrganizationBuilding,
+];
+
+/// Chunks for reading the ground and the enemy
+const SCOUTING_CHUNKS: [ChunkId; 3] = [
+    ChunkId::LeadSituationalRead,
+    ChunkId::LeadReadBattleFlow,
+    ChunkId::LeadStrategicIntuition,
+];
+
+/// Extra march rate from a master of logistics
+pub const MARCH_BONUS: f32 = 0.25;
+
+/// Extra forage yield from a master of foraging
+pub const FORAGE_BONUS: f32 = 0.5;
+
+/// Extra hexes of sight from a master scout
+pub const SCOUTING_BONUS_HEXES: f32 = 2.0;
+
+/// Strength ratio the most cautious commander wants before giving battle
+pub const TIMID_BATTLE_ODDS: f32 = 1.5;
+
+/// Strength ratio the boldest commander will still give battle at
+pub const BOLD_BATTLE_ODDS: f32 = 0.6;
+
+/// Chance per casualty fraction that the commander is among the dead
+pub const COMMANDER_DEATH_RATE: f32 = 0.5;
+
+/// Chance a routed army's commander is taken
+pub const COMMANDER_CAPTURE_CHANCE: f32 = 0.25;
+
+/// Morale an army loses when its commander is killed
+pub const COMMANDER_KILLED_MORALE_LOSS: f32 = 0.3;
+
+/// Morale an army loses when its commander is taken
+pub const COMMANDER_CAPTURED_MORALE_LOSS: f32 = 0.2;
+
+/// The person leading an army, and what they bring to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Commander {
+    pub entity: EntityId,
+    pub name: String,
+    /// Skill at keeping the army moving (0.0-1.0)
+    pub logistics: f32,
+    /// Skill at feeding the army off the land (0.0-1.0)
+    pub foraging: f32,
+    /// Skill at reading the ground and the enemy (0.0-1.0)
+    pub scouting: f32,
+    /// Willingness to fight at poor odds (0.0-1.0)
+    pub boldness: f32,
+}
+
+/// Mean encoding depth of `chunks`, counting any not formed as zero
+fn skill(library: &ChunkLibrary, chunks: &[ChunkId]) -> f32 {
+    let total: f32 = chunks
+        .iter()
+        .filter_map(|&id| library.get_chunk(id))
+        .map(|state| state.encoding_depth)
+        .sum();
+    total / chunks.len() as f32
+}
+
+impl Commander {
+    /// Read a commander from who they are: their chunks and their values
+    pub fn new(
+        entity: EntityId,
+        name: String,
+        library: &ChunkLibrary,
+        values: &HumanValues,
+    ) -> Self {
+        let boldness = (values.honor + values.ambition + (1.0 - values.safety)) / 3.0;
+        Self {
+            entity,
+            name,
+            logistics: skill(library, &LOGISTICS_CHUNKS),
+            foraging: skill(library, &FORAGING_CHUNKS),
+            scouting: skill(library, &SCOUTING_CHUNKS),
+            boldness: boldness.clamp(0.0, 1.0),
+        }
+    }
+
+    /// How much faster than a leaderless army this one marches
+    pub fn march_rate(&self) -> f32 {
+        1.0 + self.logistics * MARCH_BONUS
+    }
+
+    /// Multiplier on what the army forages
+    pub fn forage_efficiency(&self) -> f32 {
+        1.0 + self.foraging * FORAGE_BONUS
+    }
+
+    /// Extra hexes the army sees
+    pub fn scouting_bonus(&self) -> i32 {
+        (self.scouting * SCOUTING_BONUS_HEXES).round() as i32
+    }
+
+    /// Strength ratio (own over enemy) this commander wants before fighting
+    pub fn battle_odds(&self) -> f32 {
+        TIMID_BATTLE_ODDS + (BOLD_BATTLE_ODDS - TIMID_BATTLE_ODDS) * self.boldness
+    }
+
+    /// Whether they will give battle with `own` men against `enemy`
+    pub fn gives_battle(&self, own: u32, enemy: u32) -> bool {
+        enemy == 0 || own as f32 / enemy as f32 >= self.battle_odds()
+    }
+}
+
+/// What befell a commander in battle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommanderFate {
+    Killed,
+    Captured,
+}
+
+fn roll(seed: u64) -> f32 {
+    let mut h = seed;
+    h = h.wrapping_mul(6364136223846793005);
+    h = h.wrapping_add(1442695040888963407);
+    ((h ^ (h >> 32)) % 1000) as f32 / 1000.0
+}
+
+/// Whether `army`'s commander fell in a battle that cost it `casualties`:
+/// the bloodier the day, the likelier they died, and a routed army may
+/// leave them behind
+pub fn commander_fate(
+    army: &Army,
+    routed: bool,
+    casualties: u32,
+    seed: u64,
+) -> Option<CommanderFate> {
+    army.commander.as_ref()?;
+    let engaged = (army.unit_count + casualties).max(1);
+    let death_chance = casualties as f32 / engaged as f32 * COMMANDER_DEATH_RATE;
+    if roll(seed) < death_chance {
+        Some(CommanderFate::Killed)
+    } else if routed && roll(seed.wrapping_add(1)) < COMMANDER_CAPTURE_CHANCE {
+        Some(CommanderFate::Captured)
+    } else {
+        None
+    }
+}
+
+/// Settle what became of both commanders after a battle, taking the fallen
+/// from their armies
+pub fn settle_commanders(
+    result: &BattleResult,
+    attacker: &mut Army,
+    defender: &mut Army,
+    seed: u64,
+) -> Vec<CampaignEvent> {
+    if matches!(result.outcome, BattleOutcome::Ongoing) {
+        return Vec::new();
+    }
+    let mut events = Vec::new();
+    let (attacker_faction, defender_faction) = (attacker.faction, defender.faction);
+    let sides = [
+        (
+            attacker,
+            result.attacker_routed,
+            result.attacker_casualties,
+            defender_faction,
+        ),
+        (
+            defender,
+            result.defender_routed,
+            result.defender_casualties,
+            attacker_faction,
+        ),
+    ];
+    for (army, routed, casualties, enemy) in sides {
+        let seed = seed.wrapping_mul(31).wrapping_add(army.id.0 as u64);
+        let Some(fate) = commander_fate(army, routed, casualties, seed) else {
+            continue;
+        };
+        if let Some(commander) = army.lose_commander(fate) {
+            events.push(CampaignEvent::CommanderLost {
+                army: army.id,
+                commander,
+                fate,
+                captor: (fate == CommanderFate::Captured).then_some(enemy),
+            });
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaign::map::HexCoord;
+    use crate::campaign::route::ArmyId;
+    use crate::core::types::PolityId;
+    use crate::skills::PersonalChunkState;
+
+    fn veteran() -> Commander {
+        let mut library = ChunkLibrary::new();
+        for id in LOGISTICS_CHUNKS.iter().chain(&SCOUTING_CHUNKS) {
+            let mut state = PersonalChunkState::new(0);
+            state.encoding_depth = 1.0;
+            library.set_chunk(*id, state);
+        }
+        let values = HumanValues {
+            honor: 0.9,
+            ambition: 0.9,
+            safety: 0.1,
+            ..Default::default()
+        };
+        Commander::new(EntityId::new(), "Aldric".into(), &library, &values)
+    }
+
+    #[test]
+    fn test_leadership_and_values_shape_command() {
+        let aldric = veteran();
+        assert_eq!(aldric.logistics, 1.0);
+        assert_eq!(aldric.march_rate(), 1.0 + MARCH_BONUS);
+        assert_eq!(aldric.scouting_bonus(), 2);
+        // No foraging chunks of their own beyond organisation
+        assert!(aldric.forage_efficiency() < 1.0 + FORAGE_BONUS);
+        assert!(aldric.gives_battle(80, 100));
+
+        let green = Commander::new(
+            EntityId::new(),
+            "Pell".into(),
+            &ChunkLibrary::new(),
+            &HumanValues {
+                safety: 1.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(green.march_rate(), 1.0);
+        assert!(!green.gives_battle(100, 100));
+        assert!(green.gives_battle(200, 100));
+    }
+
+    #[test]
+    fn test_a_massacre_can_cost_the_commander() {
+        let mut attacker = Army::new(ArmyId(1), "A".into(), PolityId(1), HexCoord::new(0, 0))
+            .with_commander(veteran());
+        let mut defender = Army::new(ArmyId(2), "B".into(), PolityId(2), HexCoord::new(0, 0));
+        attacker.unit_count = 0;
+        let result = BattleResult {
+            outcome: BattleOutcome::DefenderVictory,
+            attacker_id: attacker.id,
+            defender_id: defender.id,
+            position: HexCoord::new(0, 0),
+            attacker_casualties: 100,
+            defender_casualties: 5,
+            attacker_routed: true,
+            defender_routed: false,
+            rounds_fought: 4,
+        };
+
+        // Some seed sees the commander fall
+        let (seed, events) = (0..64u64)
+            .map(|seed| {
+                let (mut a, mut d) = (attacker.clone(), defender.clone());
+                (seed, settle_commanders(&result, &mut a, &mut d, seed))
+            })
+            .find(|(_, events)| !events.is_empty())
+            .unwrap();
+        let morale = attacker.morale;
+        let again = settle_commanders(&result, &mut attacker, &mut defender, seed);
+        assert_eq!(again.len(), events.len());
+        assert!(attacker.commander.is_none());
+        assert!(attacker.morale < morale);
+        match &again[0] {
+            CampaignEvent::CommanderLost {
+                army, fate, captor, ..
+            } => {
+                assert_eq!(*army, ArmyId(1));
+                assert_eq!(captor.is_some(), *fate == CommanderFate::Captured);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Diplomacy between factions on campaign
+//!
+//! A faction proposes an `Offer` to another (`Diplomacy::propose`), and when
+//! proposals are resolved (`Diplomacy::resolve`) the other accepts if its
+//! regard for the proposer (`aggregate::systems::relation_score`) reaches the
+//! offer's threshold. Accepted offers become accords:
+//! - a ceasefire holds for `CEASEFIRE_DAYS`, then lapses
+//! - an alliance holds until broken
+//! - tribute buys a ceasefire with days of supplies; each day offered is
+//!   worth `TRIBUTE_REGARD_PER_DAY` of regard
+//! - passage rights let the guest's armies cross the host's ground
+//!
+//! Factions at peace (ceasefire, alliance, or tribute, either way) are not
+//! hostile: their armies share a hex without engaging. Nor are a host's
+//! armies and its guest's on a hex the host controls. `Diplomacy::seek_terms`
+//! has factions propose for themselves: friends offer alliance, and the side
+//! outnumbered by `OUTNUMBERED_RATIO` sues for a ceasefire, paying tribute
+//! if its enemy would not take one for nothing.
+
+use serde::{Deserialize, Serialize};
+
+use super::map::CampaignMap;
+use super::route::{Army, CampaignEvent};
+use crate::aggregate::systems::relation_score;
+use crate::aggregate::world::AggregateWorld;
+use crate::core::types::PolityId;
+
+/// Days a ceasefire (or tribute) holds
+pub const CEASEFIRE_DAYS: f32 = 30.0;
+
+/// Regard a faction needs for its enemy to accept a ceasefire
+pub const CEASEFIRE_THRESHOLD: i32 = -30;
+
+/// Regard a host needs for a guest to grant it passage
+pub const PASSAGE_THRESHOLD: i32 = 0;
+
+/// Regard a faction needs for another to accept an alliance
+pub const ALLIANCE_THRESHOLD: i32 = 50;
+
+/// Regard each day of supplies offered in tribute is worth
+pub const TRIBUTE_REGARD_PER_DAY: f32 = 2.0;
+
+/// Soldiers a side must be outnumbered by before it sues for peace
+pub const OUTNUMBERED_RATIO: f32 = 1.5;
+
+/// Terms one faction offers another
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Offer {
+    Ceasefire,
+    Alliance,
+    /// A ceasefire bought with `days` of supplies, paid by the proposer
+    Tribute {
+        days: f32,
+    },
+    /// The proposer's armies may cross the other's ground
+    Passage,
+}
+
+impl Offer {
+    /// Regard the receiving faction needs for the proposer to accept
+    pub fn threshold(&self) -> i32 {
+        match self {
+            Offer::Ceasefire | Offer::Tribute { .. } => CEASEFIRE_THRESHOLD,
+            Offer::Alliance => ALLIANCE_THRESHOLD,
+            Offer::Passage => PASSAGE_THRESHOLD,
+        }
+    }
+
+    /// Regard the offer itself is worth to the receiving faction
+    fn sweetener(&self) -> i32 {
+        match self {
+            Offer::Tribute { days } => (days * TRIBUTE_REGARD_PER_DAY) as i32,
+            _ => 0,
+        }
+    }
+
+    /// Whether the offer ends the fighting between the two factions
+    fn makes_peace(&self) -> bool {
+        !matches!(self, Offer::Passage)
+    }
+}
+
+/// An offer awaiting an answer
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Proposal {
+    pub from: PolityId,
+    pub to: PolityId,
+    pub offer: Offer,
+}
+
+/// An accepted offer
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Accord {
+    /// Who proposed it (the guest, for passage; the payer, for tribute)
+    pub from: PolityId,
+    pub to: PolityId,
+    pub offer: Offer,
+    /// Day it lapses; `None` holds until broken
+    pub until: Option<f32>,
+}
+
+impl Accord {
+    /// Whether the accord is between `a` and `b`, either way round
+    pub fn between(&self, a: PolityId, b: PolityId) -> bool {
+        (self.from == a && self.to == b) || (self.from == b && self.to == a)
+    }
+
+    pub fn in_force(&self, day: f32) -> bool {
+        self.until.is_none_or(|until| day < until)
+    }
+}
+
+/// Every accord and open proposal in the campaign
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Diplomacy {
+    pub accords: Vec<Accord>,
+    pub proposals: Vec<Proposal>,
+}
+
+impl Diplomacy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put an offer to another faction, to be answered on `resolve`
+    pub fn propose(&mut self, from: PolityId, to: PolityId, offer: Offer) {
+        if from != to {
+            self.proposals.push(Proposal { from, to, offer });
+        }
+    }
+
+    /// Whether the receiving faction would accept a proposal
+    pub fn considers(&self, proposal: &Proposal, world: &AggregateWorld) -> bool {
+        let regard = relation_score(world, proposal.to.0, proposal.from.0);
+        regard + proposal.offer.sweetener() >= proposal.offer.threshold()
+    }
+
+    /// Make an offer an accord, from `day`
+    pub fn accept(&mut self, proposal: Proposal, day: f32) {
+        let until = match proposal.offer {
+            Offer::Ceasefire | Offer::Tribute { .. } => Some(day + CEASEFIRE_DAYS),
+            Offer::Alliance | Offer::Passage => None,
+        };
+        self.accords.retain(|accord| {
+            !(accord.from == proposal.from
+                && accord.to == proposal.to
+                && accord.offer == proposal.offer)
+        });
+        self.accords.push(Accord {
+            from: proposal.from,
+            to: proposal.to,
+            offer: proposal.offer,
+            until,
+        });
+    }
+
+    /// Answer every open proposal and let lapsed accords go
+    pub fn resolve(&mut self, world: &AggregateWorld, day: f32) -> Vec<CampaignEvent> {
+        let mut events = Vec::new();
+
+        let (lapsed, kept) = std::mem::take(&mut self.accords)
+            .into_iter()
+            .partition(|accord| !accord.in_force(day));
+        self.accords = kept;
+        for accord in lapsed {
+            events.push(CampaignEvent::AccordLapsed {
+                from: accord.from,
+                to: accord.to,
+                offer: accord.offer,
+            });
+        }
+
+        for proposal in std::mem::take(&mut self.proposals) {
+            let Proposal { from, to, offer } = proposal;
+            if self.considers(&proposal, world) {
+                self.accept(proposal, day);
+                events.push(CampaignEvent::OfferAccepted { from, to, offer });
+            } else {
+                events.push(CampaignEvent::OfferRefused { from, to, offer });
+            }
+        }
+
+        events
+    }
+
+    /// Whether `a` and `b` have an accord in force that ends the fighting
+    pub fn at_peace(&self, a: PolityId, b: PolityId, day: f32) -> bool {
+        self.accords.iter().any(|accord| {
+            accord.between(a, b) && accord.offer.makes_peace() && accord.in_force(day)
+        })
+    }
+
+    pub fn allied(&self, a: PolityId, b: PolityId) -> bool {
+        self.accords
+            .iter()
+            .any(|accord| accord.between(a, b) && accord.offer == Offer::Alliance)
+    }
+
+    /// Whether `host` lets `guest`'s armies cross its ground
+    pub fn grants_passage(&self, host: PolityId, guest: PolityId) -> bool {
+        self.accords.iter().any(|accord| {
+            accord.from == guest && accord.to == host && accord.offer == Offer::Passage
+        })
+    }
+
+    /// Whether two armies would fight if they met: different factions, not
+    /// at peace, and neither a guest on its host's ground
+    pub fn hostile(&self, a: &Army, b: &Army, map: &CampaignMap, day: f32) -> bool {
+        if a.faction == b.faction || self.at_peace(a.faction, b.faction, day) {
+            return false;
+        }
+        let host = map.get(&a.position).and_then(|tile| tile.controller);
+        let passing = (host == Some(a.faction) && self.grants_passage(a.faction, b.faction))
+            || (host == Some(b.faction) && self.grants_passage(b.faction, a.faction));
+        !passing
+    }
+
+    /// Have each faction with armies in the field propose what suits it:
+    /// alliance to those it regards well enough, and a ceasefire (with
+    /// tribute if needed) to an enemy that outnumbers it. Returns number of
+    /// proposals made.
+    pub fn seek_terms(&mut self, armies: &[Army], world: &AggregateWorld, day: f32) -> usize {
+        let mut factions: Vec<PolityId> = armies.iter().map(|a| a.faction).collect();
+        factions.sort_by_key(|f| f.0);
+        factions.dedup();
+        let soldiers = |faction: PolityId| -> u32 {
+            armies
+                .iter()
+                .filter(|a| a.faction == faction)
+                .map(|a| a.unit_count)
+                .sum()
+        };
+
+        let before = self.proposals.len();
+        for (i, &a) in factions.iter().enumerate() {
+            for &b in &factions[i + 1..] {
+                let pending = self
+                    .proposals
+                    .iter()
+                    .any(|p| (p.from == a && p.to == b) || (p.from == b && p.to == a));
+                if pending {
+                    continue;
+                }
+
+                if !self.allied(a, b) {
+                    if relation_score(world, b.0, a.0) >= ALLIANCE_THRESHOLD {
+                        self.propose(a, b, Offer::Alliance);
+                        continue;
+                    }
+                    if relation_score(world, a.0, b.0) >= ALLIANCE_THRESHOLD {
+                        self.propose(b, a, Offer::Alliance);
+                        continue;
+                    }
+                }
+                if self.at_peace(a, b, day) {
+                    continue;
+                }
+
+                let (weak, strong) = if soldiers(a) < soldiers(b) {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                if (soldiers(strong) as f32) < soldiers(weak) as f32 * OUTNUMBERED_RATIO {
+                    continue;
+                }
+                let shortfall = CEASEFIRE_THRESHOLD - relation_score(world, strong.0, weak.0);
+                let offer = if shortfall <= 0 {
+                    Offer::Ceasefire
+                } else {
+                    Offer::Tribute {
+                        days: (shortfall as f32 / TRIBUTE_REGARD_PER_DAY).ceil(),
+                    }
+                };
+                self.propose(weak, strong, offer);
+            }
+        }
+        self.proposals.len() - before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::simulation::SimulationConfig;
+    use crate::aggregate::systems::{generate_map, generate_polities, initialize_relations};
+    use crate::campaign::map::HexCoord;
+    use crate::campaign::route::{ArmyId, ArmyStance, CampaignState};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// A generated world whose first two polities regard each other `opinion`
+    fn regarding(opinion: i32) -> (AggregateWorld, PolityId, PolityId) {
+        let config = SimulationConfig::default();
+        let rng = ChaCha8Rng::seed_from_u64(5);
+        let regions = generate_map(&config.map, rng.clone());
+        let (polities, _) = generate_polities(&regions, &config.polities, rng.clone());
+        let mut world = AggregateWorld::new(regions, polities, rng);
+        initialize_relations(&mut world);
+        let (a, b) = (world.polities[0].id, world.polities[1].id);
+        for (from, toward) in [(a, b), (b, a)] {
+            let polity = world.get_polity_by_polity_id_mut(from).unwrap();
+            polity.relations.get_mut(&toward.0).unwrap().opinion = opinion;
+        }
+        (world, a, b)
+    }
+
+    #[test]
+    fn test_outnumbered_side_buys_a_ceasefire_and_armies_stop_engaging() {
+        let (world, a, b) = regarding(-60);
+        let mut state = CampaignState::new(CampaignMap::generate_simple(10, 10, 42));
+        let at = HexCoord::new(5, 5);
+        let few = state.spawn_army("Few".into(), a, at);
+        state.get_army_mut(few).unwrap().unit_count = 40;
+        let many = state.spawn_army("Many".into(), b, at);
+        state.get_army_mut(many).unwrap().stance = ArmyStance::Aggressive;
+        assert_eq!(state.check_interceptions(at).len(), 1);
+
+        // Too ill-regarded for a plain ceasefire, so tribute is offered
+        assert_eq!(state.diplomacy.seek_terms(&state.armies, &world, 0.0), 1);
+        let proposal = state.diplomacy.proposals[0];
+        assert_eq!((proposal.from, proposal.to), (a, b));
+        assert!(matches!(proposal.offer, Offer::Tribute { .. }));
+
+        let events = state.diplomacy.resolve(&world, 0.0);
+        assert!(matches!(events[0], CampaignEvent::OfferAccepted { .. }));
+        assert!(state.check_interceptions(at).is_empty());
+
+        // Once it lapses, the fighting resumes
+        let events = state.diplomacy.resolve(&world, CEASEFIRE_DAYS);
+        assert!(matches!(events[0], CampaignEvent::AccordLapsed { .. }));
+        state.current_day = CEASEFIRE_DAYS;
+        assert_eq!(state.check_interceptions(at).len(), 1);
+    }
+
+    #[test]
+    fn test_passage_and_alliance_need_regard() {
+        let (world, a, b) = regarding(-20);
+        let mut diplomacy = Diplomacy::new();
+        diplomacy.propose(a, b, Offer::Passage);
+        diplomacy.propose(a, b, Offer::Alliance);
+        diplomacy.resolve(&world, 0.0);
+        assert!(!diplomacy.grants_passage(b, a));
+        assert!(!diplomacy.allied(a, b));
+
+        let (world, a, b) = regarding(60);
+        diplomacy.propose(a, b, Offer::Passage);
+        diplomacy.resolve(&world, 0.0);
+        assert!(diplomacy.grants_passage(b, a));
+        assert!(!diplomacy.grants_passage(a, b));
+
+        // Guests cross the host's ground unchallenged, but not elsewhere
+        let mut map = CampaignMap::generate_simple(10, 10, 42);
+        let (home, abroad) = (HexCoord::new(2, 2), HexCoord::new(7, 7));
+        map.get_mut(&home).unwrap().controller = Some(b);
+        let mut host = Army::new(ArmyId(1), "Host".into(), b, home);
+        let mut guest = Army::new(ArmyId(2), "Guest".into(), a, home);
+        assert!(!diplomacy.hostile(&host, &guest, &map, 0.0));
+        host.position = abroad;
+        guest.position = abroad;
+        assert!(diplomacy.hostile(&host, &guest, &map, 0.0));
+    }
+}
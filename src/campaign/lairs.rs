@@ -0,0 +1,494 @@
+//! Monster lairs on the campaign map
+//!
+//! The creatures that den in the wild are listed in `data/lairs.toml`. Lairs
+//! are seeded from the dark turns of a world's history (`Lairs::seed`): each
+//! polity that collapses or falls into civil war, each plague, famine, or
+//! spreading corruption leaves a lair of a creature of that origin, on wild
+//! ground (no settlement, no controller) of a terrain it likes.
+//!
+//! A lair builds raid pressure every day, at its creature's `growth`. At 1.0
+//! it raids: the nearest army within `LAIR_RAID_RANGE` loses soldiers and
+//! heart, or failing one the nearest settlement is raided. Either is a
+//! `CampaignEvent::LairRaided`.
+//!
+//! An army sent on an expedition (`ArmyOrder::Expedition`) marches to the
+//! lair and storms it, its soldiers (weighed by morale) against the lair's
+//! power (creatures by ferocity):
+//! - the stronger army clears it (`CampaignEvent::LairCleared`), losing
+//!   `LAIR_ASSAULT_LOSS_RATE` soldiers per point of the lair's power
+//! - a weaker one is thrown back (`CampaignEvent::ExpeditionRepulsed`) with
+//!   twice the losses and shaken, though it takes some of the creatures
+//!   with it
+//!
+//! A cleared lair is a site to explore. Once the expedition has searched it
+//! the next day, it carries off the lair's loot, in days of supplies
+//! (`CampaignEvent::SiteExplored`), and its order is done.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::map::{CampaignMap, CampaignTerrain, HexCoord};
+use super::route::{Army, ArmyId, ArmyOrder, CampaignEvent};
+use crate::aggregate::events::{Event, EventType};
+
+/// Hexes from its lair a raid reaches
+pub const LAIR_RAID_RANGE: i32 = 3;
+
+/// Soldiers a raided army loses per point of the lair's power
+pub const LAIR_RAID_LOSS_RATE: f32 = 0.2;
+
+/// Morale a raided army loses
+pub const LAIR_RAID_MORALE_LOSS: f32 = 0.05;
+
+/// Soldiers an expedition loses storming a lair, per point of its power
+pub const LAIR_ASSAULT_LOSS_RATE: f32 = 0.3;
+
+/// Morale an expedition loses when thrown back
+pub const LAIR_REPULSE_MORALE_LOSS: f32 = 0.2;
+
+/// Share of the creatures a thrown-back expedition could match that it kills
+pub const LAIR_REPULSE_KILL_SHARE: f32 = 0.5;
+
+/// Errors from loading the creature table
+#[derive(Error, Debug)]
+pub enum LairError {
+    /// Reading the file failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The file is not valid creature TOML
+    #[error("Parse error: {0}")]
+    ParseError(#[from] toml::de::Error),
+}
+
+/// The kind of dark event a lair can be left by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LairOrigin {
+    /// A polity falling, or tearing itself apart
+    Collapse,
+    Plague,
+    Famine,
+    /// A taint spreading through a people
+    Corruption,
+}
+
+impl LairOrigin {
+    /// The origin an event counts as, if it is a dark one
+    pub fn of_event(event: &EventType) -> Option<Self> {
+        match event {
+            EventType::PolityCollapsed { .. } | EventType::CivilWar { .. } => Some(Self::Collapse),
+            EventType::Plague { .. } => Some(Self::Plague),
+            EventType::Famine { .. } => Some(Self::Famine),
+            EventType::CorruptionSpreads { .. } => Some(Self::Corruption),
+            _ => None,
+        }
+    }
+}
+
+/// One kind of creature in the table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Creature {
+    pub name: String,
+    pub origin: LairOrigin,
+    /// Terrain it dens in
+    pub terrain: Vec<CampaignTerrain>,
+    /// Creatures in a new lair
+    pub strength: u32,
+    /// Soldiers each creature is worth in a fight
+    pub ferocity: f32,
+    /// Raid pressure a lair builds a day
+    pub growth: f32,
+    /// Days of supplies left in a cleared lair
+    pub loot: f32,
+}
+
+/// The creature table
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LairTable {
+    #[serde(default)]
+    pub creatures: Vec<Creature>,
+}
+
+impl LairTable {
+    /// No creatures: nothing dens anywhere
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(content: &str) -> Result<Self, LairError> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LairError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Creatures a dark event of `origin` can leave behind
+    pub fn of_origin(&self, origin: LairOrigin) -> impl Iterator<Item = &Creature> {
+        self.creatures.iter().filter(move |c| c.origin == origin)
+    }
+}
+
+/// Load the creature table from `data/lairs.toml`
+pub fn load_default_lairs() -> LairTable {
+    LairTable::load("data/lairs.toml").unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load lairs: {}", e);
+        LairTable::new()
+    })
+}
+
+/// Unique identifier for a lair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LairId(pub u32);
+
+/// Whether a lair is still a threat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LairState {
+    /// Occupied, and raiding
+    Active,
+    /// Emptied by an expedition, its loot still to be found
+    Cleared,
+    /// Searched and stripped
+    Explored,
+}
+
+/// A den of creatures on the campaign map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lair {
+    pub id: LairId,
+    pub creature: String,
+    pub position: HexCoord,
+    /// Creatures left in it
+    pub strength: u32,
+    pub ferocity: f32,
+    pub growth: f32,
+    /// Builds toward the next raid at 1.0
+    pub pressure: f32,
+    /// Days of supplies waiting for whoever explores it
+    pub loot: f32,
+    pub state: LairState,
+}
+
+impl Lair {
+    /// What the lair is worth in a fight, in soldiers
+    pub fn power(&self) -> f32 {
+        self.strength as f32 * self.ferocity
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.state == LairState::Active
+    }
+}
+
+/// What a lair raided
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaidTarget {
+    Army(ArmyId),
+    Settlement(HexCoord),
+}
+
+/// Every lair on the map
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Lairs {
+    pub lairs: Vec<Lair>,
+    next_id: u32,
+}
+
+fn mix(seed: u64) -> u64 {
+    let h = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    h ^ (h >> 32)
+}
+
+impl Lairs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: LairId) -> Option<&Lair> {
+        self.lairs.iter().find(|l| l.id == id)
+    }
+
+    /// The lair on a hex, if any
+    pub fn at(&self, position: HexCoord) -> Option<&Lair> {
+        self.lairs.iter().find(|l| l.position == position)
+    }
+
+    /// Den a fresh lair of `creature` on a hex
+    pub fn spawn(&mut self, creature: &Creature, position: HexCoord) -> LairId {
+        let id = LairId(self.next_id);
+        self.next_id += 1;
+        self.lairs.push(Lair {
+            id,
+            creature: creature.name.clone(),
+            position,
+            strength: creature.strength,
+            ferocity: creature.ferocity,
+            growth: creature.growth,
+            pressure: 0.0,
+            loot: creature.loot,
+            state: LairState::Active,
+        });
+        id
+    }
+
+    /// Seed a lair for each dark event in a world's history, on wild ground
+    /// its creature likes. Returns how many were seeded.
+    pub fn seed(
+        &mut self,
+        map: &CampaignMap,
+        table: &LairTable,
+        events: &[Event],
+        seed: u64,
+    ) -> usize {
+        let mut seeded = 0;
+        for event in events {
+            let Some(origin) = LairOrigin::of_event(&event.event_type) else {
+                continue;
+            };
+            let kinds: Vec<&Creature> = table.of_origin(origin).collect();
+            if kinds.is_empty() {
+                continue;
+            }
+            let pick = mix(seed ^ event.id as u64);
+            let creature = kinds[(pick % kinds.len() as u64) as usize];
+
+            let mut wild: Vec<HexCoord> = map
+                .hexes
+                .values()
+                .filter(|tile| {
+                    creature.terrain.contains(&tile.terrain)
+                        && !tile.has_settlement
+                        && tile.controller.is_none()
+                        && self.at(tile.coord).is_none()
+                })
+                .map(|tile| tile.coord)
+                .collect();
+            if wild.is_empty() {
+                continue;
+            }
+            wild.sort_by_key(|c| (c.q, c.r));
+            let position = wild[(mix(pick) % wild.len() as u64) as usize];
+            self.spawn(creature, position);
+            seeded += 1;
+        }
+        seeded
+    }
+
+    /// Storm, explore, and raid: expeditions at their lairs take them on,
+    /// then every active lair builds pressure and raids
+    pub fn tick(
+        &mut self,
+        armies: &mut [Army],
+        map: &CampaignMap,
+        dt_days: f32,
+    ) -> Vec<CampaignEvent> {
+        let mut events = Vec::new();
+
+        for army in armies.iter_mut() {
+            let Some(ArmyOrder::Expedition { lair, site }) = army.orders else {
+                continue;
+            };
+            if army.position != site || army.engaged_with.is_some() {
+                continue;
+            }
+            let Some(lair) = self.lairs.iter_mut().find(|l| l.id == lair) else {
+                army.orders = None;
+                continue;
+            };
+            match lair.state {
+                LairState::Active => events.push(storm(lair, army)),
+                LairState::Cleared => {
+                    lair.state = LairState::Explored;
+                    army.orders = None;
+                    events.push(CampaignEvent::SiteExplored {
+                        lair: lair.id,
+                        army: army.id,
+                        loot: std::mem::take(&mut lair.loot),
+                    });
+                }
+                LairState::Explored => army.orders = None,
+            }
+        }
+
+        for lair in self.lairs.iter_mut().filter(|l| l.is_active()) {
+            lair.pressure += lair.growth * dt_days;
+            if lair.pressure < 1.0 {
+                continue;
+            }
+            lair.pressure -= 1.0;
+            if let Some(event) = raid(lair, armies, map) {
+                events.push(event);
+            }
+        }
+
+        events
+    }
+}
+
+/// An expedition storms a lair
+fn storm(lair: &mut Lair, army: &mut Army) -> CampaignEvent {
+    let army_power = army.unit_count as f32 * army.morale;
+    let lair_power = lair.power();
+    if army_power >= lair_power {
+        let losses = ((lair_power * LAIR_ASSAULT_LOSS_RATE).ceil() as u32).min(army.unit_count);
+        army.unit_count -= losses;
+        lair.strength = 0;
+        lair.state = LairState::Cleared;
+        CampaignEvent::LairCleared {
+            lair: lair.id,
+            army: army.id,
+            losses,
+        }
+    } else {
+        let losses =
+            ((lair_power * LAIR_ASSAULT_LOSS_RATE * 2.0).ceil() as u32).min(army.unit_count);
+        army.unit_count -= losses;
+        army.morale = (army.morale - LAIR_REPULSE_MORALE_LOSS).max(0.0);
+        army.orders = None;
+        let slain = (army_power / lair.ferocity * LAIR_REPULSE_KILL_SHARE) as u32;
+        lair.strength = lair.strength.saturating_sub(slain).max(1);
+        CampaignEvent::ExpeditionRepulsed {
+            lair: lair.id,
+            army: army.id,
+            losses,
+        }
+    }
+}
+
+/// A lair raids the nearest army in reach, or else the nearest settlement
+fn raid(lair: &Lair, armies: &mut [Army], map: &CampaignMap) -> Option<CampaignEvent> {
+    let in_reach = |at: &HexCoord| lair.position.distance(at) <= LAIR_RAID_RANGE;
+    let army = armies
+        .iter_mut()
+        .filter(|a| a.unit_count > 0 && in_reach(&a.position))
+        .min_by_key(|a| (lair.position.distance(&a.position), a.id.0));
+    if let Some(army) = army {
+        let losses = ((lair.power() * LAIR_RAID_LOSS_RATE).ceil() as u32).min(army.unit_count);
+        army.unit_count -= losses;
+        army.morale = (army.morale - LAIR_RAID_MORALE_LOSS).max(0.0);
+        return Some(CampaignEvent::LairRaided {
+            lair: lair.id,
+            target: RaidTarget::Army(army.id),
+            losses,
+        });
+    }
+
+    let settlement = map
+        .hexes
+        .values()
+        .filter(|tile| tile.has_settlement && in_reach(&tile.coord))
+        .map(|tile| tile.coord)
+        .min_by_key(|c| (lair.position.distance(c), c.q, c.r))?;
+    Some(CampaignEvent::LairRaided {
+        lair: lair.id,
+        target: RaidTarget::Settlement(settlement),
+        losses: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaign::map::HexTile;
+    use crate::core::types::PolityId;
+
+    fn trolls() -> Creature {
+        Creature {
+            name: "trolls".into(),
+            origin: LairOrigin::Collapse,
+            terrain: vec![CampaignTerrain::Hills],
+            strength: 5,
+            ferocity: 10.0,
+            growth: 0.5,
+            loot: 20.0,
+        }
+    }
+
+    fn plains(width: i32) -> CampaignMap {
+        let mut map = CampaignMap::new(width, 1);
+        for q in 0..width {
+            let coord = HexCoord::new(q, 0);
+            map.hexes
+                .insert(coord, HexTile::new(coord, CampaignTerrain::Plains));
+        }
+        map
+    }
+
+    #[test]
+    fn test_dark_events_seed_lairs_on_wild_ground() {
+        let table = LairTable::parse(include_str!("../../data/lairs.toml")).unwrap();
+        assert!(table.of_origin(LairOrigin::Collapse).count() > 0);
+
+        let mut map = plains(6);
+        map.get_mut(&HexCoord::new(4, 0)).unwrap().terrain = CampaignTerrain::Hills;
+        let mut hold = HexTile::new(HexCoord::new(5, 0), CampaignTerrain::Hills);
+        hold.has_settlement = true;
+        map.hexes.insert(hold.coord, hold);
+
+        let collapse = Event {
+            id: 0,
+            year: 10,
+            event_type: EventType::PolityCollapsed {
+                polity: 1,
+                successor_states: vec![],
+            },
+            participants: vec![1],
+            location: None,
+        };
+        let mut lairs = Lairs::new();
+        assert_eq!(lairs.seed(&map, &table, &[collapse], 7), 1);
+        assert_eq!(lairs.lairs[0].creature, "trolls");
+        assert_eq!(lairs.lairs[0].position, HexCoord::new(4, 0));
+    }
+
+    #[test]
+    fn test_lair_raids_then_falls_to_an_expedition_and_is_explored() {
+        let mut map = plains(6);
+        map.hexes.insert(
+            HexCoord::new(3, 0),
+            HexTile::new(HexCoord::new(3, 0), CampaignTerrain::Plains).with_settlement("Ford"),
+        );
+        let mut lairs = Lairs::new();
+        let id = lairs.spawn(&trolls(), HexCoord::new(0, 0));
+
+        // With no army near, the settlement is raided
+        let mut armies: Vec<Army> = Vec::new();
+        let raids = lairs.tick(&mut armies, &map, 2.0);
+        assert!(matches!(
+            raids[..],
+            [CampaignEvent::LairRaided {
+                target: RaidTarget::Settlement(_),
+                ..
+            }]
+        ));
+
+        // Too few are thrown back
+        let site = HexCoord::new(0, 0);
+        let mut band = Army::new(ArmyId(1), "Band".into(), PolityId(1), site).with_units(20);
+        band.orders = Some(ArmyOrder::Expedition { lair: id, site });
+        let events = lairs.tick(std::slice::from_mut(&mut band), &map, 0.1);
+        assert!(matches!(
+            events[0],
+            CampaignEvent::ExpeditionRepulsed { .. }
+        ));
+        assert!(band.orders.is_none());
+        assert!(lairs.get(id).unwrap().is_active());
+
+        // Enough clear it, and explore it the next day
+        let mut host = Army::new(ArmyId(2), "Host".into(), PolityId(1), site).with_units(200);
+        host.orders = Some(ArmyOrder::Expedition { lair: id, site });
+        let events = lairs.tick(std::slice::from_mut(&mut host), &map, 0.1);
+        assert!(matches!(events[0], CampaignEvent::LairCleared { .. }));
+        assert!(host.unit_count < 200);
+        let events = lairs.tick(std::slice::from_mut(&mut host), &map, 1.0);
+        assert!(matches!(
+            events[..],
+            [CampaignEvent::SiteExplored { loot, .. }] if loot == 20.0
+        ));
+        assert_eq!(lairs.get(id).unwrap().state, LairState::Explored);
+        assert!(host.orders.is_none());
+    }
+}
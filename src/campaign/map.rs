@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
+use super::roads::Infrastructure;
+use crate::city::road::RoadGrade;
 use crate::core::types::PolityId;
 
 /// Axial hex coordinate (q, r system)
@@ -120,6 +122,12 @@ pub struct HexTile {
     pub controller: Option<PolityId>,
     pub has_settlement: bool,
     pub settlement_name: Option<String>,
+    #[serde(default)]
+    pub road: Option<RoadGrade>, // Built, or a settlement's best from its local map
+    #[serde(default)]
+    pub bridge: bool, // A river hex crossed without fording
+    #[serde(default)]
+    pub road_work: f32, // Labour put toward the next bridge or road grade
 }
 
 impl HexTile {
@@ -131,6 +139,9 @@ impl HexTile {
             controller: None,
             has_settlement: false,
             settlement_name: None,
+            road: None,
+            bridge: false,
+            road_work: 0.0,
         }
     }
 
@@ -144,6 +155,44 @@ impl HexTile {
         self.settlement_name = Some(name.to_string());
         self
     }
+
+    /// Cost to enter this hex, reduced by any road through it
+    pub fn movement_cost(&self) -> f32 {
+        let road_speed = self.road.map(|g| g.speed_multiplier()).unwrap_or(1.0);
+        self.terrain.movement_cost() / road_speed
+    }
+
+    /// Whether crossing this hex means fording a river
+    pub fn needs_fording(&self) -> bool {
+        self.terrain == CampaignTerrain::River && !self.bridge
+    }
+
+    /// What building toward a road of `grade` here works on next, if
+    /// anything is left to build
+    pub fn next_infrastructure(&self, grade: RoadGrade) -> Option<Infrastructure> {
+        if self.needs_fording() {
+            Some(Infrastructure::Bridge)
+        } else if self.road.is_none_or(|road| road < grade) {
+            Some(Infrastructure::Road(grade))
+        } else {
+            None
+        }
+    }
+
+    /// Put labour toward a road of `grade`; returns what was finished
+    pub fn build(&mut self, grade: RoadGrade, work: f32) -> Option<Infrastructure> {
+        let next = self.next_infrastructure(grade)?;
+        self.road_work += work;
+        if self.road_work < next.work_required(self.road) {
+            return None;
+        }
+        self.road_work = 0.0;
+        match next {
+            Infrastructure::Road(grade) => self.road = Some(grade),
+            Infrastructure::Bridge => self.bridge = true,
+        }
+        Some(next)
+    }
 }
 
 /// The campaign map containing all hex tiles
@@ -207,6 +256,21 @@ impl CampaignMap {
         self.hexes.get_mut(coord)
     }
 
+    /// Record the road grade a settlement's local network offers
+    ///
+    /// Only settlement hexes have a local map to take one from; roads
+    /// elsewhere are built (`campaign::roads`). Returns false if the hex is
+    /// missing or has no settlement.
+    pub fn set_settlement_road(&mut self, coord: &HexCoord, grade: Option<RoadGrade>) -> bool {
+        match self.hexes.get_mut(coord) {
+            Some(tile) if tile.has_settlement => {
+                tile.road = grade;
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Check if a coordinate is within the map bounds
     pub fn contains(&self, coord: &HexCoord) -> bool {
         self.hexes.contains_key(coord)
@@ -221,6 +285,40 @@ impl CampaignMap {
             .collect()
     }
 
+    /// Days to march along a path, counting every hex entered after the first
+    pub fn path_cost(&self, path: &[HexCoord]) -> f32 {
+        path.iter()
+            .skip(1)
+            .map(|coord| self.get(coord).map_or(f32::INFINITY, |t| t.movement_cost()))
+            .sum()
+    }
+
+    /// Days to march from one hex to another along the cheapest path
+    pub fn travel_days(&self, from: HexCoord, to: HexCoord) -> Option<f32> {
+        self.find_path(from, to).map(|path| self.path_cost(&path))
+    }
+
+    /// Every hex within `days` of march from `from`, with the days to reach it
+    pub fn within_days(&self, from: HexCoord, days: f32) -> HashMap<HexCoord, f32> {
+        let mut reached = HashMap::new();
+        if !self.contains(&from) {
+            return reached;
+        }
+        reached.insert(from, 0.0);
+        let mut frontier = vec![from];
+        while let Some(current) = frontier.pop() {
+            let so_far = reached[&current];
+            for neighbor in self.passable_neighbors(&current) {
+                let cost = so_far + self.get(&neighbor).unwrap().movement_cost();
+                if cost <= days && reached.get(&neighbor).is_none_or(|&c| cost < c) {
+                    reached.insert(neighbor, cost);
+                    frontier.push(neighbor);
+                }
+            }
+        }
+        reached
+    }
+
     /// A* pathfinding from start to goal
     pub fn find_path(&self, start: HexCoord, goal: HexCoord) -> Option<Vec<HexCoord>> {
         if !self.contains(&start) || !self.contains(&goal) {
@@ -259,6 +357,13 @@ impl CampaignMap {
             }
         }
 
+        // The cheapest hex bounds the heuristic, so roads are never overlooked
+        let cheapest = self
+            .hexes
+            .values()
+            .map(|t| t.movement_cost())
+            .fold(f32::INFINITY, f32::min);
+
         let mut open_set = BinaryHeap::new();
         let mut came_from: HashMap<HexCoord, HexCoord> = HashMap::new();
         let mut g_score: HashMap<HexCoord, f32> = HashMap::new();
@@ -268,7 +373,7 @@ impl CampaignMap {
         open_set.push(Node {
             coord: start,
             g_cost: 0.0,
-            f_cost: start.distance(&goal) as f32,
+            f_cost: start.distance(&goal) as f32 * cheapest,
         });
 
         while let Some(current) = open_set.pop() {
@@ -295,14 +400,14 @@ impl CampaignMap {
                 }
 
                 let neighbor_tile = self.get(&neighbor).unwrap();
-                let movement_cost = neighbor_tile.terrain.movement_cost();
+                let movement_cost = neighbor_tile.movement_cost();
                 let tentative_g = g_score.get(&current.coord).unwrap_or(&f32::INFINITY)
                     + movement_cost;
 
                 if tentative_g < *g_score.get(&neighbor).unwrap_or(&f32::INFINITY) {
                     came_from.insert(neighbor, current.coord);
                     g_score.insert(neighbor, tentative_g);
-                    let h = neighbor.distance(&goal) as f32;
+                    let h = neighbor.distance(&goal) as f32 * cheapest;
                     open_set.push(Node {
                         coord: neighbor,
                         g_cost: tentative_g,
@@ -377,6 +482,29 @@ mod tests {
         assert_eq!(path.last(), Some(&goal));
     }
 
+    #[test]
+    fn test_pathfinding_follows_roads() {
+        let mut map = CampaignMap::new(5, 3);
+        for q in 0..5 {
+            for r in 0..3 {
+                let coord = HexCoord::new(q, r);
+                map.hexes
+                    .insert(coord, HexTile::new(coord, CampaignTerrain::Plains));
+            }
+        }
+        // A stone road looping out through row 2
+        let road = [(0, 2), (1, 2), (2, 2), (3, 2), (4, 1)];
+        for (q, r) in road {
+            map.get_mut(&HexCoord::new(q, r)).unwrap().road = Some(RoadGrade::StoneRoad);
+        }
+
+        let start = HexCoord::new(0, 1);
+        let goal = HexCoord::new(4, 1);
+        let path = map.find_path(start, goal).unwrap();
+        assert!(path.contains(&HexCoord::new(2, 2)));
+        assert!(map.travel_days(start, goal).unwrap() < 4.0);
+    }
+
     #[test]
     fn test_pathfinding_same_hex() {
         let map = CampaignMap::generate_simple(5, 5, 42);
@@ -1,14 +1,39 @@
+pub mod ai;
+pub mod baggage;
 pub mod battle;
+pub mod commander;
+pub mod diplomacy;
+pub mod lairs;
 pub mod location;
 pub mod map;
+pub mod roads;
 pub mod route;
 pub mod scouts;
 pub mod supply;
+pub mod tactical;
 pub mod visibility;
 pub mod weather;
 
+pub use ai::{
+    strength, AiDirective, CampaignAi, FactionAi, Intent, Sighting, FOUL_WEATHER, GUARD_RANGE,
+    MIN_ODDS, RAID_CUNNING, SUPPLY_MARGIN_DAYS, TAKE_FIELD_INITIATIVE, THREAT_RANGE,
+};
+pub use baggage::{BaggageTrain, SUPPLY_UNITS_PER_DAY};
+pub use commander::{settle_commanders, commander_fate, Commander, CommanderFate};
+pub use diplomacy::{
+    Accord, Diplomacy, Offer, Proposal, ALLIANCE_THRESHOLD, CEASEFIRE_DAYS, CEASEFIRE_THRESHOLD,
+    OUTNUMBERED_RATIO, PASSAGE_THRESHOLD, TRIBUTE_REGARD_PER_DAY,
+};
+pub use lairs::{
+    load_default_lairs, Creature, Lair, LairId, LairOrigin, LairState, LairTable, Lairs,
+    RaidTarget, LAIR_RAID_RANGE,
+};
 pub use location::Location;
 pub use map::{CampaignMap, CampaignTerrain, HexCoord, HexTile};
+pub use roads::{
+    hex_road_work, Infrastructure, RoadBuilt, RoadProject, RoadWorks, BRIDGE_WORK,
+    FORD_LOSS_RATE, SUPPLY_LINE_DAYS,
+};
 pub use route::{
     Army, ArmyId, ArmyOrder, ArmyStance, CampaignEvent, CampaignState, MovementResult, campaign_tick,
 };
@@ -31,6 +56,10 @@ pub use battle::{
     calculate_combat_strength,
     BASE_CASUALTY_RATE, ROUT_THRESHOLD,
 };
+pub use tactical::{
+    fight_engagement, resolve_tactical_battle, tactical_composition, tactical_scale,
+    MAX_TACTICAL_SOLDIERS,
+};
 pub use scouts::{
     IntelType, Scout, ScoutEvent, ScoutId, ScoutIntel, ScoutMission, ScoutSystem,
     SCOUT_DETECTION_RANGE, SCOUT_EVASION_CHANCE, SCOUT_SPEED_MULTIPLIER,
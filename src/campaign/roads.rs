@@ -0,0 +1,225 @@
+//! Roads and bridges on the campaign map
+//!
+//! Any hex can carry a road (`HexTile::road`), which divides the cost of
+//! entering it by the grade's speed and carries wagons through rough ground
+//! without slowing or wearing them. A river hex also wants a bridge: an army
+//! crossing one without fords it, losing `FORD_LOSS_RATE` of its soldiers and
+//! dragging its wagons through at `FORD_BAGGAGE_MULTIPLIER`.
+//!
+//! Roads are built a hex at a time, from labour put into the tile
+//! (`HexTile::build`). On a river hex the bridge comes first. Labour comes
+//! from two places:
+//! - An army ordered to `ArmyOrder::BuildRoad` works the hex it stands on,
+//!   `ROAD_WORK_PER_SOLDIER` a day per soldier
+//! - A polity plans a `RoadProject` between two hexes and works along it at
+//!   its own daily rate, but only on ground it holds or no one does
+
+use serde::{Deserialize, Serialize};
+
+use super::map::{CampaignMap, HexCoord};
+use crate::city::road::RoadGrade;
+use crate::core::types::PolityId;
+
+/// Soldier-days to build a campaign hex of road per unit of a local road
+/// cell's `RoadGrade::work_required`
+pub const HEX_ROAD_WORK_SCALE: f32 = 100.0;
+
+/// Soldier-days to bridge a river hex
+pub const BRIDGE_WORK: f32 = 3000.0;
+
+/// Labour one soldier puts into a road in a day
+pub const ROAD_WORK_PER_SOLDIER: f32 = 1.0;
+
+/// Fraction of an army lost fording an unbridged river hex
+pub const FORD_LOSS_RATE: f32 = 0.01;
+
+/// Movement cost multiplier for a baggage train fording a river
+pub const FORD_BAGGAGE_MULTIPLIER: f32 = 2.0;
+
+/// Travel days from a depot within which an army can still draw on it
+pub const SUPPLY_LINE_DAYS: f32 = 2.0;
+
+/// Labour to build a hex of road of a grade from nothing
+pub fn hex_road_work(grade: RoadGrade) -> f32 {
+    grade.work_required() * HEX_ROAD_WORK_SCALE
+}
+
+/// Something built on a hex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Infrastructure {
+    Road(RoadGrade),
+    Bridge,
+}
+
+impl Infrastructure {
+    /// Labour to build it on a hex that already has `road`
+    pub fn work_required(&self, road: Option<RoadGrade>) -> f32 {
+        match self {
+            Infrastructure::Bridge => BRIDGE_WORK,
+            Infrastructure::Road(grade) => {
+                hex_road_work(*grade) - road.map(hex_road_work).unwrap_or(0.0)
+            }
+        }
+    }
+}
+
+/// A road a polity is building between two hexes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoadProject {
+    pub owner: PolityId,
+    pub path: Vec<HexCoord>,
+    pub grade: RoadGrade,
+    /// Labour the polity puts in each day
+    pub labour: f32,
+}
+
+impl RoadProject {
+    /// The first hex along the path still to be built
+    fn next_hex(&self, map: &CampaignMap) -> Option<HexCoord> {
+        self.path.iter().copied().find(|coord| {
+            map.get(coord)
+                .is_some_and(|tile| tile.next_infrastructure(self.grade).is_some())
+        })
+    }
+}
+
+/// Something a road project finished
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoadBuilt {
+    pub owner: PolityId,
+    pub position: HexCoord,
+    pub infrastructure: Infrastructure,
+}
+
+/// Every polity's road projects
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoadWorks {
+    pub projects: Vec<RoadProject>,
+}
+
+impl RoadWorks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plan a road along the cheapest path between two hexes. Returns false
+    /// if there is no path.
+    pub fn plan(
+        &mut self,
+        map: &CampaignMap,
+        owner: PolityId,
+        from: HexCoord,
+        to: HexCoord,
+        grade: RoadGrade,
+        labour: f32,
+    ) -> bool {
+        let Some(path) = map.find_path(from, to) else {
+            return false;
+        };
+        self.projects.push(RoadProject {
+            owner,
+            path,
+            grade,
+            labour,
+        });
+        true
+    }
+
+    /// Work each project's next hex, and drop the projects that are done.
+    /// A project whose next hex another polity holds waits.
+    pub fn tick(&mut self, map: &mut CampaignMap, dt_days: f32) -> Vec<RoadBuilt> {
+        let mut built = Vec::new();
+        for project in &self.projects {
+            let Some(position) = project.next_hex(map) else {
+                continue;
+            };
+            let Some(tile) = map.get_mut(&position) else {
+                continue;
+            };
+            if tile.controller.is_some_and(|c| c != project.owner) {
+                continue;
+            }
+            if let Some(infrastructure) = tile.build(project.grade, project.labour * dt_days) {
+                built.push(RoadBuilt {
+                    owner: project.owner,
+                    position,
+                    infrastructure,
+                });
+            }
+        }
+        self.projects.retain(|p| p.next_hex(map).is_some());
+        built
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaign::map::{CampaignTerrain, HexTile};
+
+    #[test]
+    fn test_river_is_bridged_before_the_road_is_laid() {
+        let mut tile = HexTile::new(HexCoord::new(0, 0), CampaignTerrain::River);
+        assert!(tile.needs_fording());
+        assert_eq!(tile.build(RoadGrade::DirtRoad, BRIDGE_WORK - 1.0), None);
+        assert_eq!(
+            tile.build(RoadGrade::DirtRoad, 1.0),
+            Some(Infrastructure::Bridge)
+        );
+        assert!(!tile.needs_fording());
+
+        let dirt = hex_road_work(RoadGrade::DirtRoad);
+        assert_eq!(
+            tile.build(RoadGrade::DirtRoad, dirt),
+            Some(Infrastructure::Road(RoadGrade::DirtRoad))
+        );
+        assert_eq!(tile.next_infrastructure(RoadGrade::Trail), None);
+
+        // Paving a dirt road costs only the difference
+        let paving = hex_road_work(RoadGrade::StoneRoad) - dirt;
+        assert_eq!(
+            tile.build(RoadGrade::StoneRoad, paving),
+            Some(Infrastructure::Road(RoadGrade::StoneRoad))
+        );
+    }
+
+    #[test]
+    fn test_polity_builds_along_its_project_on_its_own_ground() {
+        let mut map = CampaignMap::new(4, 1);
+        for q in 0..4 {
+            let coord = HexCoord::new(q, 0);
+            map.hexes
+                .insert(coord, HexTile::new(coord, CampaignTerrain::Plains));
+        }
+        map.get_mut(&HexCoord::new(2, 0)).unwrap().controller = Some(PolityId(2));
+
+        let mut works = RoadWorks::new();
+        let trail = hex_road_work(RoadGrade::Trail);
+        assert!(works.plan(
+            &map,
+            PolityId(1),
+            HexCoord::new(0, 0),
+            HexCoord::new(3, 0),
+            RoadGrade::Trail,
+            trail,
+        ));
+
+        let built: Vec<HexCoord> = (0..5)
+            .flat_map(|_| works.tick(&mut map, 1.0))
+            .map(|b| b.position)
+            .collect();
+        // The foreign hex holds the road up
+        assert_eq!(built, vec![HexCoord::new(0, 0), HexCoord::new(1, 0)]);
+        assert_eq!(works.projects.len(), 1);
+
+        map.get_mut(&HexCoord::new(2, 0)).unwrap().controller = None;
+        for _ in 0..2 {
+            works.tick(&mut map, 1.0);
+        }
+        assert!(works.projects.is_empty());
+        assert_eq!(
+            map.get(&HexCoord::new(3, 0)).unwrap().road,
+            Some(RoadGrade::Trail)
+        );
+    }
+}
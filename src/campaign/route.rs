@@ -4,7 +4,17 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::baggage::BaggageTrain;
+use super::commander::{
+    Commander, CommanderFate, COMMANDER_CAPTURED_MORALE_LOSS, COMMANDER_KILLED_MORALE_LOSS,
+};
+use super::diplomacy::{Diplomacy, Offer};
+use super::lairs::{Lair, LairId, Lairs, RaidTarget};
 use super::map::{CampaignMap, HexCoord};
+use super::roads::{
+    Infrastructure, RoadWorks, FORD_BAGGAGE_MULTIPLIER, FORD_LOSS_RATE, ROAD_WORK_PER_SOLDIER,
+};
+use crate::city::road::RoadGrade;
 use crate::core::types::PolityId;
 
 /// Unique identifier for an army
@@ -32,6 +42,10 @@ pub enum ArmyOrder {
     Patrol(Vec<HexCoord>),
     Guard(HexCoord),
     Halt,
+    /// Stay put and build a road of this grade through the hex
+    BuildRoad(RoadGrade),
+    /// March to a lair, storm it, and explore it once cleared
+    Expedition { lair: LairId, site: HexCoord },
 }
 
 /// Result of movement execution
@@ -58,6 +72,13 @@ pub struct Army {
     pub movement_points: f32,      // Accumulated movement progress
     pub path_cache: Option<Vec<HexCoord>>, // Cached path to destination
     pub engaged_with: Option<ArmyId>, // Currently engaged in battle with this army
+    #[serde(default)]
+    pub baggage: Option<BaggageTrain>, // Carts and wagons hauling supplies
+    #[serde(default)]
+    pub commander: Option<Commander>, // Who leads it; leaderless if None
+    /// 0.0 (levy) to 1.0 (standing army drilled to perfection)
+    #[serde(default)]
+    pub drill: f32,
 }
 
 impl Army {
@@ -74,6 +95,9 @@ impl Army {
             movement_points: 0.0,
             path_cache: None,
             engaged_with: None,
+            baggage: None,
+            commander: None,
+            drill: 0.0,
         }
     }
 
@@ -87,6 +111,32 @@ impl Army {
         self
     }
 
+    pub fn with_baggage(mut self, baggage: BaggageTrain) -> Self {
+        self.baggage = Some(baggage);
+        self
+    }
+
+    pub fn with_drill(mut self, drill: f32) -> Self {
+        self.drill = drill.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_commander(mut self, commander: Commander) -> Self {
+        self.commander = Some(commander);
+        self
+    }
+
+    /// The commander falls; the army loses heart. Returns who was lost.
+    pub fn lose_commander(&mut self, fate: CommanderFate) -> Option<Commander> {
+        let commander = self.commander.take()?;
+        let loss = match fate {
+            CommanderFate::Killed => COMMANDER_KILLED_MORALE_LOSS,
+            CommanderFate::Captured => COMMANDER_CAPTURED_MORALE_LOSS,
+        };
+        self.morale = (self.morale - loss).max(0.0);
+        Some(commander)
+    }
+
     /// Give movement orders to the army
     pub fn order_move_to(&mut self, destination: HexCoord, map: &CampaignMap) {
         self.orders = Some(ArmyOrder::MoveTo(destination));
@@ -106,13 +156,46 @@ impl Army {
         self.path_cache = None;
     }
 
+    /// Set the army to building a road where it stands
+    pub fn order_build_road(&mut self, grade: RoadGrade) {
+        self.orders = Some(ArmyOrder::BuildRoad(grade));
+        self.path_cache = None;
+    }
+
+    /// Send the army on an expedition against a lair
+    pub fn order_expedition(&mut self, lair: &Lair, map: &CampaignMap) {
+        self.orders = Some(ArmyOrder::Expedition {
+            lair: lair.id,
+            site: lair.position,
+        });
+        self.path_cache = map.find_path(self.position, lair.position);
+    }
+
+    /// Work on the road through the army's hex, if ordered to. Returns what
+    /// was finished; the order is done once the road is.
+    pub fn build_road(&mut self, map: &mut CampaignMap, dt_days: f32) -> Option<Infrastructure> {
+        let Some(ArmyOrder::BuildRoad(grade)) = self.orders else {
+            return None;
+        };
+        if self.engaged_with.is_some() {
+            return None;
+        }
+        let tile = map.get_mut(&self.position)?;
+        let work = self.unit_count as f32 * ROAD_WORK_PER_SOLDIER * dt_days;
+        let built = tile.build(grade, work);
+        if tile.next_infrastructure(grade).is_none() {
+            self.orders = None;
+        }
+        built
+    }
+
     /// Calculate movement cost to enter a hex
     pub fn movement_cost_to(&self, map: &CampaignMap, to: &HexCoord) -> f32 {
         let Some(tile) = map.get(to) else {
             return f32::INFINITY;
         };
 
-        let base_cost = tile.terrain.movement_cost();
+        let base_cost = tile.movement_cost();
 
         // Larger armies move slower
         let size_penalty = 1.0 + (self.unit_count as f32 / 1000.0).min(0.5);
@@ -120,7 +203,22 @@ impl Army {
         // Low morale slows movement
         let morale_penalty = if self.morale < 0.3 { 1.5 } else { 1.0 };
 
-        base_cost * size_penalty * morale_penalty
+        // Wheeled baggage needs clear ground or a road, and a bridge over rivers
+        let road_carries_wheels = tile.road.map(|g| g.allows_wheels()).unwrap_or(false);
+        let baggage_penalty = match &self.baggage {
+            Some(train) if !train.is_broken_down() && tile.needs_fording() => {
+                FORD_BAGGAGE_MULTIPLIER
+            }
+            Some(train) if !train.is_broken_down() && !road_carries_wheels => {
+                BaggageTrain::terrain_multiplier(tile.terrain)
+            }
+            _ => 1.0,
+        };
+
+        // A skilled commander keeps the column moving
+        let march_rate = self.commander.as_ref().map(|c| c.march_rate()).unwrap_or(1.0);
+
+        base_cost * size_penalty * morale_penalty * baggage_penalty / march_rate
     }
 
     /// Execute movement for this tick
@@ -145,13 +243,17 @@ impl Army {
                     return MovementResult::NoOrders;
                 }
             }
-            ArmyOrder::Halt => return MovementResult::NoOrders,
+            ArmyOrder::Expedition { site, .. } => {
+                if self.position == *site {
+                    return MovementResult::NoOrders; // At the lair
+                }
+                *site
+            }
+            ArmyOrder::Halt | ArmyOrder::BuildRoad(_) => return MovementResult::NoOrders,
         };
 
         if self.position == destination {
-            self.orders = None;
-            self.path_cache = None;
-            return MovementResult::Arrived;
+            return self.arrive();
         }
 
         // Ensure we have a valid path
@@ -167,9 +269,7 @@ impl Army {
         let current_idx = path.iter().position(|&h| h == self.position).unwrap_or(0);
         let Some(&next_hex) = path.get(current_idx + 1) else {
             // Reached end of path
-            self.orders = None;
-            self.path_cache = None;
-            return MovementResult::Arrived;
+            return self.arrive();
         };
 
         // Accumulate movement points
@@ -180,10 +280,21 @@ impl Army {
             self.movement_points -= cost;
             self.position = next_hex;
 
+            if let Some(tile) = map.get(&next_hex) {
+                // Some are swept away at a ford
+                if tile.needs_fording() {
+                    let lost = (self.unit_count as f32 * FORD_LOSS_RATE).ceil() as u32;
+                    self.unit_count = self.unit_count.saturating_sub(lost);
+                }
+                // A road spares the wagons
+                let road_carries_wheels = tile.road.is_some_and(|g| g.allows_wheels());
+                if let (Some(train), false) = (self.baggage.as_mut(), road_carries_wheels) {
+                    train.apply_travel_wear(tile.terrain, cost);
+                }
+            }
+
             if self.position == destination {
-                self.orders = None;
-                self.path_cache = None;
-                return MovementResult::Arrived;
+                return self.arrive();
             }
 
             MovementResult::Moving
@@ -192,6 +303,16 @@ impl Army {
         }
     }
 
+    /// The army has reached its destination. Its orders are done, unless it
+    /// has a lair to take on there.
+    fn arrive(&mut self) -> MovementResult {
+        if !matches!(self.orders, Some(ArmyOrder::Expedition { .. })) {
+            self.orders = None;
+        }
+        self.path_cache = None;
+        MovementResult::Arrived
+    }
+
     /// Check if this army should intercept another
     pub fn should_intercept(&self, other: &Army) -> bool {
         if self.faction == other.faction {
@@ -208,6 +329,13 @@ impl Army {
             return false;
         }
 
+        // Nor will a commander who doesn't like the odds
+        if let Some(commander) = &self.commander {
+            if !commander.gives_battle(self.unit_count, other.unit_count) {
+                return false;
+            }
+        }
+
         match self.stance {
             ArmyStance::Aggressive => true,
             ArmyStance::Defensive => self.position == other.position,
@@ -227,6 +355,12 @@ pub struct CampaignState {
     pub map: CampaignMap,
     pub armies: Vec<Army>,
     pub current_day: f32,
+    #[serde(default)]
+    pub road_works: RoadWorks, // Roads polities are building
+    #[serde(default)]
+    pub lairs: Lairs, // Monster lairs, active and cleared
+    #[serde(default)]
+    pub diplomacy: Diplomacy, // Accords and open offers between factions
     next_army_id: u32,
 }
 
@@ -236,6 +370,9 @@ impl CampaignState {
             map,
             armies: Vec::new(),
             current_day: 0.0,
+            road_works: RoadWorks::new(),
+            lairs: Lairs::new(),
+            diplomacy: Diplomacy::new(),
             next_army_id: 1,
         }
     }
@@ -264,13 +401,16 @@ impl CampaignState {
         self.armies.iter().filter(|a| a.position == position).collect()
     }
 
-    /// Check for interceptions at a position
+    /// Check for interceptions at a position, between hostile armies
     pub fn check_interceptions(&self, position: HexCoord) -> Vec<(ArmyId, ArmyId)> {
         let armies_here: Vec<_> = self.armies_at(position);
         let mut interceptions = Vec::new();
 
         for (i, army_a) in armies_here.iter().enumerate() {
             for army_b in armies_here.iter().skip(i + 1) {
+                if !self.diplomacy.hostile(army_a, army_b, &self.map, self.current_day) {
+                    continue;
+                }
                 if army_a.should_intercept(army_b) || army_b.should_intercept(army_a) {
                     interceptions.push((army_a.id, army_b.id));
                 }
@@ -290,7 +430,15 @@ pub fn campaign_tick(state: &mut CampaignState, dt_days: f32) -> Vec<CampaignEve
 
     // Process army movement
     for army in &mut state.armies {
+        let before = army.unit_count;
         let result = army.execute_movement(&map, dt_days);
+        if army.unit_count < before {
+            events.push(CampaignEvent::ArmyForded {
+                army: army.id,
+                position: army.position,
+                losses: before - army.unit_count,
+            });
+        }
         match result {
             MovementResult::Arrived => {
                 events.push(CampaignEvent::ArmyArrived {
@@ -308,6 +456,29 @@ pub fn campaign_tick(state: &mut CampaignState, dt_days: f32) -> Vec<CampaignEve
         }
     }
 
+    // Armies and polities build roads
+    for army in &mut state.armies {
+        if let Some(infrastructure) = army.build_road(&mut state.map, dt_days) {
+            events.push(CampaignEvent::RoadBuilt {
+                polity: army.faction,
+                army: Some(army.id),
+                position: army.position,
+                infrastructure,
+            });
+        }
+    }
+    for built in state.road_works.tick(&mut state.map, dt_days) {
+        events.push(CampaignEvent::RoadBuilt {
+            polity: built.owner,
+            army: None,
+            position: built.position,
+            infrastructure: built.infrastructure,
+        });
+    }
+
+    // Expeditions take on lairs, and lairs raid
+    events.extend(state.lairs.tick(&mut state.armies, &state.map, dt_days));
+
     // Check for interceptions
     let positions: Vec<_> = state.armies.iter().map(|a| a.position).collect();
     let mut new_engagements = Vec::new();
@@ -344,6 +515,40 @@ pub enum CampaignEvent {
     ArmyMoved { army: ArmyId, position: HexCoord },
     ArmyArrived { army: ArmyId, position: HexCoord },
     ArmiesEngaged { army_a: ArmyId, army_b: ArmyId, position: HexCoord },
+    /// An army lost men fording a river
+    ArmyForded { army: ArmyId, position: HexCoord, losses: u32 },
+    /// A road or bridge was finished, by an army or by its polity's labourers
+    RoadBuilt {
+        polity: PolityId,
+        army: Option<ArmyId>,
+        position: HexCoord,
+        infrastructure: Infrastructure,
+    },
+    /// A lair raided an army (which lost `losses` soldiers) or a settlement
+    LairRaided {
+        lair: LairId,
+        target: RaidTarget,
+        losses: u32,
+    },
+    /// An expedition cleared a lair
+    LairCleared { lair: LairId, army: ArmyId, losses: u32 },
+    /// An expedition was thrown back from a lair
+    ExpeditionRepulsed { lair: LairId, army: ArmyId, losses: u32 },
+    /// An expedition explored a cleared lair, carrying off `loot` days of supplies
+    SiteExplored { lair: LairId, army: ArmyId, loot: f32 },
+    /// A faction accepted another's offer
+    OfferAccepted { from: PolityId, to: PolityId, offer: Offer },
+    /// A faction turned down another's offer
+    OfferRefused { from: PolityId, to: PolityId, offer: Offer },
+    /// A ceasefire (or the one tribute bought) ran out
+    AccordLapsed { from: PolityId, to: PolityId, offer: Offer },
+    /// An army's commander was killed or taken in battle
+    CommanderLost {
+        army: ArmyId,
+        commander: Commander,
+        fate: CommanderFate,
+        captor: Option<PolityId>,
+    },
 }
 
 #[cfg(test)]
@@ -366,6 +571,96 @@ mod tests {
         assert_eq!(army.morale, 1.0);
     }
 
+    #[test]
+    fn test_baggage_slows_rough_terrain() {
+        use crate::campaign::map::CampaignTerrain;
+        use crate::city::vehicle::VehicleKind;
+
+        let mut map = test_map();
+        let forest = HexCoord::new(1, 0);
+        map.get_mut(&forest).unwrap().terrain = CampaignTerrain::Forest;
+
+        let light = Army::new(ArmyId(1), "Light".into(), PolityId(1), HexCoord::new(0, 0));
+        let laden = Army::new(ArmyId(2), "Laden".into(), PolityId(1), HexCoord::new(0, 0))
+            .with_baggage(BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 3));
+
+        assert!(laden.movement_cost_to(&map, &forest) > light.movement_cost_to(&map, &forest));
+    }
+
+    #[test]
+    fn test_settlement_roads_speed_armies() {
+        use crate::campaign::map::{CampaignTerrain, HexTile};
+        use crate::city::road::RoadGrade;
+        use crate::city::vehicle::VehicleKind;
+
+        let mut map = test_map();
+        let town = HexCoord::new(1, 0);
+        map.hexes.insert(
+            town,
+            HexTile::new(town, CampaignTerrain::Hills).with_settlement("Hillford"),
+        );
+
+        // Only settlement hexes take roads
+        assert!(!map.set_settlement_road(&HexCoord::new(2, 0), Some(RoadGrade::Trail)));
+
+        let laden = Army::new(ArmyId(1), "Laden".into(), PolityId(1), HexCoord::new(0, 0))
+            .with_baggage(BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 3));
+        let before = laden.movement_cost_to(&map, &town);
+
+        assert!(map.set_settlement_road(&town, Some(RoadGrade::DirtRoad)));
+        let after = laden.movement_cost_to(&map, &town);
+
+        // Faster road plus no rough-terrain penalty for the wagons
+        assert!(after < before / 1.5);
+    }
+
+    #[test]
+    fn test_army_builds_road_and_bridges_spare_fording() {
+        use crate::campaign::map::{CampaignTerrain, HexTile};
+        use crate::campaign::roads::{hex_road_work, BRIDGE_WORK};
+
+        let mut map = test_map();
+        let ford = HexCoord::new(1, 0);
+        map.hexes
+            .insert(ford, HexTile::new(ford, CampaignTerrain::River));
+        let mut state = CampaignState::new(map);
+
+        let wading = state.spawn_army("Wading".into(), PolityId(1), HexCoord::new(0, 0));
+        let map = state.map.clone();
+        state
+            .get_army_mut(wading)
+            .unwrap()
+            .order_move_to(ford, &map);
+        let events = campaign_tick(&mut state, 1.0);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, CampaignEvent::ArmyForded { losses: 1, .. })));
+        assert_eq!(state.get_army(wading).unwrap().unit_count, 99);
+
+        // Digging in builds the bridge, then the road over it
+        let builders = state.spawn_army("Builders".into(), PolityId(1), ford);
+        let army = state.get_army_mut(builders).unwrap();
+        army.unit_count = 1000;
+        army.order_build_road(RoadGrade::Trail);
+        let days = ((BRIDGE_WORK + hex_road_work(RoadGrade::Trail)) / 1000.0).ceil() as usize;
+        let built: Vec<Infrastructure> = (0..days)
+            .flat_map(|_| campaign_tick(&mut state, 1.0))
+            .filter_map(|e| match e {
+                CampaignEvent::RoadBuilt { infrastructure, .. } => Some(infrastructure),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            built,
+            vec![
+                Infrastructure::Bridge,
+                Infrastructure::Road(RoadGrade::Trail)
+            ]
+        );
+        assert!(state.get_army(builders).unwrap().orders.is_none());
+        assert!(!state.map.get(&ford).unwrap().needs_fording());
+    }
+
     #[test]
     fn test_army_movement() {
         let map = test_map();
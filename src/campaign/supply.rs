@@ -1,11 +1,15 @@
 //! Supply system for campaign layer
 //!
 //! Armies consume supplies and must forage or maintain supply lines.
-//! Running out of supplies causes attrition and morale loss.
+//! Running out of supplies causes attrition and morale loss. An army draws
+//! on a friendly depot within `SUPPLY_LINE_DAYS` of march, so roads out of a
+//! depot stretch its reach.
 
 use serde::{Deserialize, Serialize};
 
+use super::baggage::BaggageTrain;
 use super::map::{CampaignMap, CampaignTerrain, HexCoord};
+use super::roads::SUPPLY_LINE_DAYS;
 use super::route::{Army, ArmyId};
 use crate::core::types::PolityId;
 
@@ -128,6 +132,15 @@ impl ArmySupply {
     pub fn add_supplies(&mut self, amount: f32) {
         self.supplies = (self.supplies + amount).min(self.max_supplies);
     }
+
+    /// Recompute carrying capacity from the army's baggage train
+    ///
+    /// Supplies above the new capacity are lost (abandoned with broken wagons).
+    pub fn update_capacity(&mut self, baggage: Option<&BaggageTrain>) {
+        let hauled = baggage.map(|b| b.supply_capacity()).unwrap_or(0.0);
+        self.max_supplies = BASE_SUPPLY_DAYS * 1.5 + hauled;
+        self.supplies = self.supplies.min(self.max_supplies);
+    }
 }
 
 /// Calculate foraging yield for a hex
@@ -203,6 +216,25 @@ impl SupplySystem {
             .find(|d| d.position == position && d.owner == faction)
     }
 
+    /// The nearest depot of a faction's within supply line reach of a
+    /// position that has supplies to give
+    pub fn depot_in_reach(
+        &self,
+        map: &CampaignMap,
+        position: HexCoord,
+        faction: PolityId,
+    ) -> Option<&SupplyDepot> {
+        self.depots
+            .iter()
+            .filter(|d| d.owner == faction && d.supplies > 0.0)
+            .filter_map(|d| {
+                let reach = map.within_days(d.position, SUPPLY_LINE_DAYS);
+                Some((d, *reach.get(&position)?))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(d, _)| d)
+    }
+
     /// Get mutable depot by ID
     pub fn get_depot_mut(&mut self, id: DepotId) -> Option<&mut SupplyDepot> {
         self.depots.iter_mut().find(|d| d.id == id)
@@ -222,6 +254,22 @@ impl SupplySystem {
             depot.generate_supplies(dt_days);
         }
 
+        // Baggage trains set carrying capacity; depots are where wagons get mended
+        for army in armies.iter_mut() {
+            let at_depot = self
+                .depots
+                .iter()
+                .any(|d| d.position == army.position && d.owner == army.faction);
+            if at_depot {
+                if let Some(train) = army.baggage.as_mut() {
+                    train.repair(0.1 * dt_days);
+                }
+            }
+            if let Some(supply) = self.get_army_supply_mut(army.id) {
+                supply.update_capacity(army.baggage.as_ref());
+            }
+        }
+
         // Collect army data needed for processing to avoid borrow conflicts
         let army_data: Vec<_> = armies
             .iter()
@@ -230,10 +278,9 @@ impl SupplySystem {
 
         // Process resupply from depots first (separate pass)
         for (army_id, position, faction, _unit_count) in &army_data {
-            // Find depot at army position owned by same faction
-            let depot_transfer: Option<(DepotId, f32, f32)> = self.depots
-                .iter()
-                .find(|d| d.position == *position && d.owner == *faction)
+            // Find the nearest depot in reach owned by same faction
+            let depot_transfer: Option<(DepotId, f32, f32)> = self
+                .depot_in_reach(map, *position, *faction)
                 .and_then(|d| {
                     let supply_idx = self.army_supplies.iter().position(|s| s.army_id == *army_id)?;
                     let needed = self.army_supplies[supply_idx].max_supplies - self.army_supplies[supply_idx].supplies;
@@ -248,10 +295,11 @@ impl SupplySystem {
                 // Transfer supplies from depot to army
                 if let Some(depot) = self.depots.iter_mut().find(|d| d.id == depot_id) {
                     let transferred = depot.transfer_to_army(needed.min(available));
+                    let from = depot.position;
                     if transferred > 0.0 {
                         if let Some(supply) = self.get_army_supply_mut(*army_id) {
                             supply.add_supplies(transferred);
-                            supply.last_resupply = *position;
+                            supply.last_resupply = from;
                         }
                         events.push(SupplyEvent::ArmyResupplied {
                             army: *army_id,
@@ -273,7 +321,8 @@ impl SupplySystem {
             if supply.foraging {
                 if let Some(tile) = map.get(&army.position) {
                     let forage_yield = calculate_forage_yield(tile.terrain) * dt_days;
-                    let effective_yield = forage_yield * (army.unit_count as f32 / 100.0);
+                    let efficiency = army.commander.as_ref().map(|c| c.forage_efficiency()).unwrap_or(1.0);
+                    let effective_yield = forage_yield * (army.unit_count as f32 / 100.0) * efficiency;
                     supply.add_supplies(effective_yield);
                     events.push(SupplyEvent::ArmyForaged {
                         army: army.id,
@@ -344,6 +393,24 @@ mod tests {
         assert!((supply.days_until_starvation(500) - 2.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_baggage_extends_capacity() {
+        use crate::city::vehicle::VehicleKind;
+
+        let mut supply = ArmySupply::new(ArmyId(1));
+        let base = supply.max_supplies;
+
+        let train = BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 2);
+        supply.update_capacity(Some(&train));
+        assert!(supply.max_supplies > base);
+
+        // Losing the train drops excess supplies
+        supply.supplies = supply.max_supplies;
+        supply.update_capacity(None);
+        assert_eq!(supply.max_supplies, base);
+        assert_eq!(supply.supplies, base);
+    }
+
     #[test]
     fn test_forage_yield() {
         let forest = calculate_forage_yield(CampaignTerrain::Forest);
@@ -394,4 +461,30 @@ mod tests {
         // Should have resupplied
         assert!(events.iter().any(|e| matches!(e, SupplyEvent::ArmyResupplied { .. })));
     }
+
+    #[test]
+    fn test_roads_stretch_supply_lines() {
+        use crate::campaign::map::HexTile;
+        use crate::city::road::RoadGrade;
+
+        let mut map = CampaignMap::new(4, 1);
+        for q in 0..4 {
+            let coord = HexCoord::new(q, 0);
+            map.hexes
+                .insert(coord, HexTile::new(coord, CampaignTerrain::Plains));
+        }
+        let mut system = SupplySystem::new();
+        system.create_depot(HexCoord::new(0, 0), PolityId(1));
+        let far = HexCoord::new(3, 0);
+
+        // Three days' march is beyond reach, and another's depot never serves
+        assert!(system.depot_in_reach(&map, HexCoord::new(2, 0), PolityId(1)).is_some());
+        assert!(system.depot_in_reach(&map, far, PolityId(1)).is_none());
+        assert!(system.depot_in_reach(&map, HexCoord::new(1, 0), PolityId(2)).is_none());
+
+        for q in 1..4 {
+            map.get_mut(&HexCoord::new(q, 0)).unwrap().road = Some(RoadGrade::StoneRoad);
+        }
+        assert!(system.depot_in_reach(&map, far, PolityId(1)).is_some());
+    }
 }
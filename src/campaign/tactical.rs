@@ -0,0 +1,605 @@
+//! Fighting campaign engagements on the tactical map
+//!
+//! `resolve_battle` settles an engagement with a few abstract rounds. This
+//! bridge instead raises each campaign army as a tactical `battle::Army`,
+//! lets two AI commanders fight it out on a map laid out from the campaign
+//! hex, and writes the casualties, morale, and outcome back.
+//!
+//! Large armies are fought at a common scale so the tactical battle stays a
+//! manageable size: with a scale of 3, every tactical soldier stands for
+//! three on the campaign map, and each casualty counts three times.
+//!
+//! What the armies bring with them:
+//! - stance sets the mix of troops (cavalry for aggressive armies, spears
+//!   for defensive ones, light horse for evasive ones)
+//! - bad weather wets bowstrings (fewer archers) and hides the enemy (the
+//!   armies deploy closer together)
+//! - hunger and marching in bad weather start the troops fatigued, and
+//!   starving troops with their stamina already half spent
+//! - a baggage train in working order follows the line as supply wagons,
+//!   stocked from the army's supplies; whatever they hand out is taken off
+//!   those supplies afterwards
+//! - low morale starts them already stressed
+//! - a standing army's drill carries over to every company it fields
+//! - hills and mountains put the defender on a ridgeline
+//! - a defender holding a settlement fights behind a curtain wall with one
+//!   gate, which the attacker has to batter down or climb
+
+use crate::battle::ai::{AiCommander, AiPersonality};
+use crate::battle::elevation::{raise_hill, raise_ridgeline};
+use crate::battle::execution::BattleOutcome as TacticalOutcome;
+use crate::battle::siege::raise_curtain_wall;
+use crate::battle::{
+    Army as TacticalArmy, ArmyId as TacticalArmyId, BattleFormation, BattleHexCoord, BattleMap,
+    BattleState, BattleTerrain, BattleUnit, Element, FormationId, UnitId, UnitType,
+};
+use crate::core::types::EntityId;
+
+use super::battle::{apply_retreat, BattleOutcome, BattleResult};
+use super::map::{CampaignMap, CampaignTerrain};
+use super::route::{Army, ArmyId, ArmyStance, CampaignState};
+use super::supply::{ArmySupply, SupplySystem, BASE_SUPPLY_DAYS};
+use super::weather::Weather;
+
+/// Most tactical soldiers raised for either side
+pub const MAX_TACTICAL_SOLDIERS: u32 = 600;
+
+/// Tactical soldiers per unit
+pub const COMPANY_SIZE: u32 = 50;
+
+/// Soldiers per element within a unit
+pub const ELEMENT_SIZE: usize = 10;
+
+/// Share of an army fielded as archers in clear weather
+pub const ARCHER_SHARE: f32 = 0.2;
+
+/// Share of an army fielded in its stance's speciality
+pub const STANCE_SHARE: f32 = 0.25;
+
+/// Tactical map size (hexes)
+pub const TACTICAL_MAP_WIDTH: u32 = 60;
+pub const TACTICAL_MAP_HEIGHT: u32 = 30;
+
+/// Distance between the armies when they deploy in clear weather
+pub const DEPLOYMENT_GAP: i32 = 40;
+
+/// Hexes in front of a besieged defender's line that its walls stand
+pub const WALL_STANDOFF: i32 = 3;
+
+/// Starting fatigue for a starving army
+pub const STARVING_FATIGUE: f32 = 0.5;
+
+/// Starting fatigue from a march at a standstill in the worst weather
+pub const WEATHER_FATIGUE: f32 = 0.3;
+
+/// Starting stamina for a starving army
+pub const STARVING_STAMINA: f32 = 0.5;
+
+/// Wagon stores (a volley, or a meal and water for a unit) per day of supplies
+pub const STORES_PER_SUPPLY_DAY: f32 = 4.0;
+
+/// Hexes behind its line an army's supply wagons deploy
+pub const WAGON_STANDOFF: i32 = 2;
+
+/// Morale lost at the loss of the whole army, or the rout of all of it
+pub const CASUALTY_MORALE_LOSS: f32 = 0.5;
+pub const ROUT_MORALE_LOSS: f32 = 0.5;
+
+/// Morale regained by the winning side
+pub const VICTORY_MORALE_GAIN: f32 = 0.1;
+
+/// Couriers each commander has for sending orders
+const COURIERS: usize = 5;
+
+/// The common scale two armies are fought at
+pub fn tactical_scale(a: &Army, b: &Army) -> u32 {
+    a.unit_count
+        .max(b.unit_count)
+        .div_ceil(MAX_TACTICAL_SOLDIERS)
+        .max(1)
+}
+
+/// The unit types an army fields, with how many tactical soldiers each
+pub fn tactical_composition(army: &Army, soldiers: u32, weather: Weather) -> Vec<(UnitType, u32)> {
+    let archers = (soldiers as f32 * ARCHER_SHARE * weather.ranged_combat_modifier()) as u32;
+    let speciality = (soldiers as f32 * STANCE_SHARE) as u32;
+    let special_type = match army.stance {
+        ArmyStance::Aggressive => UnitType::Cavalry,
+        ArmyStance::Defensive => UnitType::Spearmen,
+        ArmyStance::Evasive => UnitType::LightCavalry,
+    };
+    let infantry = soldiers.saturating_sub(archers + speciality);
+
+    [
+        (UnitType::Infantry, infantry),
+        (special_type, speciality),
+        (UnitType::Archers, archers),
+    ]
+    .into_iter()
+    .filter(|&(_, count)| count > 0)
+    .collect()
+}
+
+/// Fatigue the troops bring onto the field
+pub fn starting_fatigue(supply: Option<&ArmySupply>, weather: Weather) -> f32 {
+    let hunger = match supply {
+        Some(supply) if supply.is_starving() => STARVING_FATIGUE,
+        // Short rations tire the troops before they run out altogether
+        Some(supply) => {
+            STARVING_FATIGUE * 0.5 * (1.0 - supply.supplies / BASE_SUPPLY_DAYS).clamp(0.0, 1.0)
+        }
+        None => 0.0,
+    };
+    let march = WEATHER_FATIGUE * (1.0 - weather.movement_modifier());
+    (hunger + march).min(1.0)
+}
+
+/// Stores an army's supply wagons take onto the field: as many of its
+/// supplies as its baggage train can haul, none without a working train
+pub fn wagon_stores(army: &Army, supply: Option<&ArmySupply>) -> u32 {
+    let (Some(train), Some(supply)) = (&army.baggage, supply) else {
+        return 0;
+    };
+    let hauled = supply.supplies.min(train.supply_capacity()).max(0.0);
+    (hauled * STORES_PER_SUPPLY_DAY) as u32
+}
+
+/// Stores a tactical army's wagons handed out, of the `stocked` they started with
+fn stores_spent(tactical: &TacticalArmy, stocked: u32) -> u32 {
+    let left: u32 = tactical
+        .formations
+        .iter()
+        .flat_map(|f| f.units.iter())
+        .filter(|u| u.unit_type == UnitType::SupplyWagons)
+        .map(|u| u.stores)
+        .sum();
+    stocked.saturating_sub(left)
+}
+
+/// Raise a campaign army as a tactical army deployed along column `q`, its
+/// wagons `rear` columns away
+fn raise_army(
+    army: &Army,
+    scale: u32,
+    q: i32,
+    rear: i32,
+    supply: Option<&ArmySupply>,
+    weather: Weather,
+) -> TacticalArmy {
+    let soldiers = army.unit_count.div_ceil(scale);
+    let fatigue = starting_fatigue(supply, weather);
+    let stamina = if supply.is_some_and(|s| s.is_starving()) {
+        STARVING_STAMINA
+    } else {
+        1.0
+    };
+    let center = TACTICAL_MAP_HEIGHT as i32 / 2;
+
+    let mut tactical = TacticalArmy::new(TacticalArmyId::new(), EntityId::new());
+    tactical.hq_position = BattleHexCoord::new(q, center);
+    let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+    formation.name = army.name.clone();
+
+    let mut slot = 0i32;
+    for (unit_type, count) in tactical_composition(army, soldiers, weather) {
+        let mut remaining = count;
+        while remaining > 0 {
+            let size = remaining.min(COMPANY_SIZE);
+            remaining -= size;
+
+            let mut unit = BattleUnit::new(UnitId::new(), unit_type);
+            // Alternate either side of the centre of the line
+            let offset = if slot % 2 == 0 { slot } else { -slot - 1 };
+            unit.position = BattleHexCoord::new(q, center + offset);
+            let entities: Vec<EntityId> = (0..size).map(|_| EntityId::new()).collect();
+            for chunk in entities.chunks(ELEMENT_SIZE) {
+                unit.elements.push(Element::new(chunk.to_vec()));
+            }
+            unit.fatigue = fatigue;
+            unit.stamina = stamina;
+            unit.drill = army.drill;
+            unit.stress = (1.0 - army.morale) * unit.stress_threshold() * 0.5;
+            formation.units.push(unit);
+            slot += 1;
+        }
+    }
+
+    let stores = wagon_stores(army, supply);
+    if stores > 0 {
+        let mut wagons = BattleUnit::new(UnitId::new(), UnitType::SupplyWagons);
+        wagons.position = BattleHexCoord::new(q + rear, center);
+        let drivers = army.baggage.as_ref().map_or(1, |train| {
+            train.vehicle_count().clamp(1, ELEMENT_SIZE as u32)
+        });
+        wagons.elements.push(Element::new(
+            (0..drivers).map(|_| EntityId::new()).collect(),
+        ));
+        wagons.stores = stores;
+        formation.units.push(wagons);
+    }
+
+    tactical.formations.push(formation);
+    tactical.courier_pool = (0..COURIERS).map(|_| EntityId::new()).collect();
+    tactical
+}
+
+/// Lay out the tactical map from the campaign hex the armies meet on
+fn battlefield(
+    terrain: CampaignTerrain,
+    attacker_q: i32,
+    defender_q: i32,
+    walled: bool,
+) -> BattleMap {
+    let mut map = BattleMap::new(TACTICAL_MAP_WIDTH, TACTICAL_MAP_HEIGHT);
+    let patch = match terrain {
+        CampaignTerrain::Forest => Some(BattleTerrain::Forest),
+        CampaignTerrain::Hills | CampaignTerrain::Mountains => Some(BattleTerrain::Rough),
+        CampaignTerrain::Swamp | CampaignTerrain::River | CampaignTerrain::Coast => {
+            Some(BattleTerrain::ShallowWater)
+        }
+        CampaignTerrain::Plains | CampaignTerrain::Desert => None,
+    };
+
+    for q in 0..TACTICAL_MAP_WIDTH as i32 {
+        for r in 0..TACTICAL_MAP_HEIGHT as i32 {
+            let coord = BattleHexCoord::new(q, r);
+            // Scattered patches, kept off the deployment lines
+            let on_a_line = (q - attacker_q).abs() <= 1 || (q - defender_q).abs() <= 1;
+            if let Some(ground) = patch {
+                if (q * 7 + r * 13).rem_euclid(5) == 0 && !on_a_line {
+                    map.set_terrain(coord, ground);
+                }
+            }
+        }
+    }
+
+    if walled {
+        raise_curtain_wall(
+            &mut map,
+            defender_q - WALL_STANDOFF,
+            TACTICAL_MAP_HEIGHT as i32 / 2,
+        );
+    }
+
+    // In hill country the defender holds a ridgeline, with a knoll on the
+    // flank for whoever reaches it first
+    let (crest, flank) = match terrain {
+        CampaignTerrain::Hills => (1, 2),
+        CampaignTerrain::Mountains => (2, 3),
+        _ => return map,
+    };
+    let bottom = TACTICAL_MAP_HEIGHT as i32 - 1;
+    raise_ridgeline(
+        &mut map,
+        BattleHexCoord::new(defender_q, 0),
+        BattleHexCoord::new(defender_q, bottom),
+        crest,
+        flank,
+    );
+    raise_hill(
+        &mut map,
+        BattleHexCoord::new((attacker_q + defender_q) / 2, bottom / 5),
+        flank,
+        crest,
+    );
+    map
+}
+
+/// Campaign outcome for the attacker fighting as the friendly side
+fn campaign_outcome(outcome: TacticalOutcome) -> BattleOutcome {
+    match outcome {
+        TacticalOutcome::DecisiveVictory
+        | TacticalOutcome::Victory
+        | TacticalOutcome::PyrrhicVictory => BattleOutcome::AttackerVictory,
+        TacticalOutcome::Defeat | TacticalOutcome::DecisiveDefeat => BattleOutcome::DefenderVictory,
+        TacticalOutcome::Draw | TacticalOutcome::MutualRout => BattleOutcome::Draw,
+        TacticalOutcome::Undecided => BattleOutcome::Ongoing,
+    }
+}
+
+/// Write a tactical army's losses back onto its campaign army, returning
+/// the casualties and whether it routed
+fn write_back(army: &mut Army, tactical: &TacticalArmy, scale: u32, won: bool) -> (u32, bool) {
+    let total = tactical.total_strength().max(1);
+    let lost = total - tactical.effective_strength();
+    let casualties = (lost as u32 * scale).min(army.unit_count);
+    let casualty_share = lost as f32 / total as f32;
+    let routing = tactical.percentage_routing();
+
+    army.unit_count -= casualties;
+    army.morale -= CASUALTY_MORALE_LOSS * casualty_share + ROUT_MORALE_LOSS * routing;
+    if won {
+        army.morale += VICTORY_MORALE_GAIN;
+    }
+    army.morale = army.morale.clamp(0.0, 1.0);
+    (casualties, routing > 0.5 || army.unit_count == 0)
+}
+
+/// Fight a battle between two campaign armies on the tactical map
+/// Returns battle result and modifies armies in place
+///
+/// The attacker fights as the friendly side. `seed` drives both commanders
+/// and the battle's dice, so the same armies and seed give the same battle.
+pub fn resolve_tactical_battle(
+    attacker: &mut Army,
+    defender: &mut Army,
+    map: &CampaignMap,
+    weather: Weather,
+    supply: &mut SupplySystem,
+    seed: u64,
+) -> BattleResult {
+    let position = defender.position;
+    let terrain = map.get(&position).map(|t| t.terrain).unwrap_or_default();
+    let walled = map.get(&position).is_some_and(|t| t.has_settlement);
+
+    // Poor visibility lets the armies blunder close before they see each other
+    let gap = ((DEPLOYMENT_GAP as f32 * weather.visibility_modifier()) as i32).max(6);
+    let attacker_q = (TACTICAL_MAP_WIDTH as i32 - gap) / 2;
+    let defender_q = attacker_q + gap;
+
+    let scale = tactical_scale(attacker, defender);
+    let friendly = raise_army(
+        attacker,
+        scale,
+        attacker_q,
+        -WAGON_STANDOFF,
+        supply.get_army_supply(attacker.id),
+        weather,
+    );
+    let enemy = raise_army(
+        defender,
+        scale,
+        defender_q,
+        WAGON_STANDOFF,
+        supply.get_army_supply(defender.id),
+        weather,
+    );
+    let attacker_stores = wagon_stores(attacker, supply.get_army_supply(attacker.id));
+    let defender_stores = wagon_stores(defender, supply.get_army_supply(defender.id));
+
+    let mut state = BattleState::with_seed(
+        battlefield(terrain, attacker_q, defender_q, walled),
+        friendly,
+        enemy,
+        seed,
+    );
+    state.set_friendly_ai(Some(Box::new(AiCommander::with_seed(
+        AiPersonality::default(),
+        seed,
+    ))));
+    state.set_enemy_ai(Some(Box::new(AiCommander::with_seed(
+        AiPersonality::default(),
+        seed.wrapping_add(1),
+    ))));
+    state.start_battle();
+    while !state.is_finished() {
+        state.run_tick();
+    }
+
+    let outcome = campaign_outcome(state.outcome);
+    let attacker_won = matches!(outcome, BattleOutcome::AttackerVictory);
+    let defender_won = matches!(outcome, BattleOutcome::DefenderVictory);
+    let (attacker_casualties, attacker_routed) =
+        write_back(attacker, &state.friendly_army, scale, attacker_won);
+    let (defender_casualties, defender_routed) =
+        write_back(defender, &state.enemy_army, scale, defender_won);
+
+    // What the wagons handed out came out of the army's supplies
+    for (army_id, tactical, stocked) in [
+        (attacker.id, &state.friendly_army, attacker_stores),
+        (defender.id, &state.enemy_army, defender_stores),
+    ] {
+        if let Some(army_supply) = supply.get_army_supply_mut(army_id) {
+            let spent = stores_spent(tactical, stocked) as f32 / STORES_PER_SUPPLY_DAY;
+            army_supply.supplies = (army_supply.supplies - spent).max(0.0);
+        }
+    }
+
+    if attacker_routed {
+        apply_retreat(attacker, position, map);
+    }
+    if defender_routed {
+        apply_retreat(defender, position, map);
+    }
+
+    BattleResult {
+        outcome,
+        attacker_id: attacker.id,
+        defender_id: defender.id,
+        position,
+        attacker_casualties,
+        defender_casualties,
+        attacker_routed,
+        defender_routed,
+        rounds_fought: state.tick as u32,
+    }
+}
+
+/// Fight out an engagement in the campaign, disengaging both armies
+/// afterwards
+///
+/// Returns None if either army is missing.
+pub fn fight_engagement(
+    state: &mut CampaignState,
+    attacker: ArmyId,
+    defender: ArmyId,
+    weather: Weather,
+    supply: &mut SupplySystem,
+    seed: u64,
+) -> Option<BattleResult> {
+    let mut attacking = state.get_army(attacker)?.clone();
+    let mut defending = state.get_army(defender)?.clone();
+    let result = resolve_tactical_battle(
+        &mut attacking,
+        &mut defending,
+        &state.map,
+        weather,
+        supply,
+        seed,
+    );
+    attacking.disengage();
+    defending.disengage();
+
+    for army in [attacking, defending] {
+        if let Some(slot) = state.get_army_mut(army.id) {
+            *slot = army;
+        }
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaign::map::HexCoord;
+    use crate::core::types::PolityId;
+
+    #[test]
+    fn test_composition_follows_stance_and_weather() {
+        let army = Army::new(ArmyId(1), "Host".into(), PolityId(1), HexCoord::new(0, 0))
+            .with_stance(ArmyStance::Aggressive);
+        let clear = tactical_composition(&army, 200, Weather::Clear);
+        assert_eq!(clear.iter().map(|(_, n)| n).sum::<u32>(), 200);
+        assert!(clear.contains(&(UnitType::Cavalry, 50)));
+        assert!(clear.contains(&(UnitType::Archers, 40)));
+
+        // Wet bowstrings put fewer archers in the field
+        let storm = tactical_composition(&army, 200, Weather::HeavyRain);
+        assert!(storm.contains(&(UnitType::Archers, 16)));
+
+        // Huge armies are fought at scale
+        let huge = army.clone().with_units(1500);
+        assert_eq!(tactical_scale(&huge, &army), 3);
+
+        // A standing army fields drilled companies
+        let standing = army.clone().with_drill(0.8);
+        let tactical = raise_army(&standing, 1, 0, -1, None, Weather::Clear);
+        assert!(tactical.formations[0].units.iter().all(|u| u.drill == 0.8));
+
+        let mut hungry = ArmySupply::new(ArmyId(1));
+        assert_eq!(starting_fatigue(Some(&hungry), Weather::Clear), 0.0);
+        hungry.supplies = 0.0;
+        assert!(starting_fatigue(Some(&hungry), Weather::Blizzard) > STARVING_FATIGUE);
+    }
+
+    #[test]
+    fn test_engagement_is_fought_and_written_back() {
+        let mut state = CampaignState::new(CampaignMap::generate_simple(5, 5, 42));
+        let here = HexCoord::new(0, 0);
+        let big = state.spawn_army("Host".into(), PolityId(1), here);
+        let small = state.spawn_army("Band".into(), PolityId(2), here);
+        state.get_army_mut(big).unwrap().unit_count = 120;
+        state.get_army_mut(small).unwrap().unit_count = 30;
+        state.get_army_mut(big).unwrap().engaged_with = Some(small);
+
+        let result = fight_engagement(
+            &mut state,
+            big,
+            small,
+            Weather::Clear,
+            &mut SupplySystem::new(),
+            9,
+        )
+        .unwrap();
+        assert!(matches!(result.outcome, BattleOutcome::AttackerVictory));
+        assert!(result.defender_casualties > 0);
+
+        let host = state.get_army(big).unwrap();
+        let band = state.get_army(small).unwrap();
+        assert_eq!(host.unit_count, 120 - result.attacker_casualties);
+        assert_eq!(band.unit_count, 30 - result.defender_casualties);
+        assert!(band.morale < host.morale);
+        assert!(host.engaged_with.is_none());
+    }
+
+    #[test]
+    fn test_wagons_are_stocked_from_supplies_and_drawn_down() {
+        use crate::campaign::baggage::BaggageTrain;
+        use crate::city::vehicle::VehicleKind;
+
+        let mut state = CampaignState::new(CampaignMap::generate_simple(5, 5, 42));
+        let here = HexCoord::new(0, 0);
+        let host = state.spawn_army("Host".into(), PolityId(1), here);
+        let band = state.spawn_army("Band".into(), PolityId(2), here);
+        {
+            let army = state.get_army_mut(host).unwrap();
+            army.unit_count = 200;
+            army.baggage = Some(BaggageTrain::new().with_vehicles(VehicleKind::Wagon, 4));
+        }
+        state.get_army_mut(band).unwrap().unit_count = 150;
+
+        let mut supply = SupplySystem::new();
+        supply.register_army(host);
+        supply.register_army(band);
+
+        // No train, no wagons; a train hauls no more than the army has
+        let host_army = state.get_army(host).unwrap().clone();
+        let band_army = state.get_army(band).unwrap().clone();
+        let stocked = wagon_stores(&host_army, supply.get_army_supply(host));
+        assert!(stocked > 0);
+        assert_eq!(wagon_stores(&band_army, supply.get_army_supply(band)), 0);
+        let tactical = raise_army(
+            &host_army,
+            1,
+            10,
+            -WAGON_STANDOFF,
+            supply.get_army_supply(host),
+            Weather::Clear,
+        );
+        let wagons = tactical.formations[0]
+            .units
+            .iter()
+            .find(|u| u.unit_type == UnitType::SupplyWagons)
+            .unwrap();
+        assert_eq!(wagons.stores, stocked);
+        assert_eq!(wagons.position.q, 10 - WAGON_STANDOFF);
+
+        // Whatever they hand out is counted against the stock
+        let mut drawn = tactical.clone();
+        for unit in drawn.formations[0].units.iter_mut() {
+            if unit.unit_type == UnitType::SupplyWagons {
+                unit.stores -= 5;
+            }
+        }
+        assert_eq!(stores_spent(&tactical, stocked), 0);
+        assert_eq!(stores_spent(&drawn, stocked), 5);
+
+        // and taken off the army's supplies; an army without wagons spends none
+        let before = supply.get_army_supply(host).unwrap().supplies;
+        fight_engagement(&mut state, host, band, Weather::Clear, &mut supply, 3).unwrap();
+        assert!(supply.get_army_supply(host).unwrap().supplies <= before);
+        assert_eq!(
+            supply.get_army_supply(band).unwrap().supplies,
+            BASE_SUPPLY_DAYS
+        );
+
+        // A starving army starts spent
+        let mut starving = ArmySupply::new(host);
+        starving.supplies = 0.0;
+        let tactical = raise_army(&host_army, 1, 10, -1, Some(&starving), Weather::Clear);
+        let unit = &tactical.formations[0].units[0];
+        assert_eq!(unit.stamina, STARVING_STAMINA);
+        assert!(tactical.formations[0]
+            .units
+            .iter()
+            .all(|u| u.unit_type != UnitType::SupplyWagons));
+    }
+
+    #[test]
+    fn test_settlement_defender_fights_behind_walls() {
+        let defender_q = 40;
+        let map = battlefield(CampaignTerrain::Plains, 10, defender_q, true);
+        let wall_q = defender_q - WALL_STANDOFF;
+        let gate = BattleHexCoord::new(wall_q, TACTICAL_MAP_HEIGHT as i32 / 2);
+        assert!(
+            (0..TACTICAL_MAP_HEIGHT as i32).all(|r| map.is_barred(BattleHexCoord::new(wall_q, r)))
+        );
+        assert_eq!(
+            map.fortification(gate).map(|f| f.kind),
+            Some(crate::battle::FortificationKind::Gate)
+        );
+
+        let open = battlefield(CampaignTerrain::Plains, 10, defender_q, false);
+        assert!(!open.is_barred(gate));
+    }
+}
@@ -154,7 +154,7 @@ impl FactionVisibility {
 
 /// Calculate visibility range for an army
 pub fn calculate_visibility_range(
-    _army: &Army,
+    army: &Army,
     terrain: CampaignTerrain,
     weather: Weather,
     has_scouts: bool,
@@ -177,7 +177,10 @@ pub fn calculate_visibility_range(
         _ => 0,
     };
 
-    let effective_range = (base as f32 * terrain_mod * weather_mod) as i32 + scout_bonus + elevation_bonus;
+    // A commander with an eye for ground sees further
+    let commander_bonus = army.commander.as_ref().map(|c| c.scouting_bonus()).unwrap_or(0);
+
+    let effective_range = (base as f32 * terrain_mod * weather_mod) as i32 + scout_bonus + elevation_bonus + commander_bonus;
     effective_range.max(1) // Always see at least your own hex
 }
 
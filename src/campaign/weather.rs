@@ -90,6 +90,11 @@ impl Weather {
             Self::Sandstorm => 0.05,
         }
     }
+
+    /// Whether rain or snow is falling
+    pub fn is_wet(&self) -> bool {
+        matches!(self, Self::Rain | Self::HeavyRain | Self::Snow | Self::Blizzard)
+    }
 }
 
 impl Default for Weather {
@@ -98,6 +103,17 @@ impl Default for Weather {
     }
 }
 
+/// Weathers in the order of `Season::weather_weights`
+pub const SEASONAL_WEATHER: [Weather; 7] = [
+    Weather::Clear,
+    Weather::Cloudy,
+    Weather::Rain,
+    Weather::HeavyRain,
+    Weather::Snow,
+    Weather::Blizzard,
+    Weather::Fog,
+];
+
 /// Season affecting weather probabilities
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Season {
@@ -152,6 +168,9 @@ pub struct WeatherState {
     pub current_season: Season,
     pub days_with_current: u32,   // How long current weather has persisted
     pub forecast_days: u32,       // Days until weather changes
+    /// Days in a row without rain or snow
+    #[serde(default)]
+    pub dry_days: u32,
 }
 
 impl WeatherState {
@@ -161,6 +180,7 @@ impl WeatherState {
             current_season: Season::Spring,
             days_with_current: 0,
             forecast_days: 3,
+            dry_days: 0,
         }
     }
 
@@ -175,6 +195,29 @@ impl WeatherState {
             self.days_with_current = 0;
             self.forecast_days = self.roll_duration(rng_seed);
         }
+
+        if self.current_weather.is_wet() {
+            self.dry_days = 0;
+        } else {
+            self.dry_days += dt_days as u32;
+        }
+    }
+
+    /// The weather actually coming over the next `days` days, starting
+    /// tomorrow, when day `d` is rolled from `seed_for_day(d)`
+    pub fn outlook(
+        &self,
+        today: u32,
+        days: u32,
+        seed_for_day: impl Fn(u32) -> u64,
+    ) -> Vec<Weather> {
+        let mut state = self.clone();
+        (today + 1..=today + days)
+            .map(|day| {
+                state.update(1.0, day % 360, seed_for_day(day));
+                state.current_weather
+            })
+            .collect()
     }
 
     fn roll_weather(&self, seed: u64) -> Weather {
@@ -185,16 +228,7 @@ impl WeatherState {
         for (i, &w) in weights.iter().enumerate() {
             cumulative += w;
             if roll < cumulative {
-                return match i {
-                    0 => Weather::Clear,
-                    1 => Weather::Cloudy,
-                    2 => Weather::Rain,
-                    3 => Weather::HeavyRain,
-                    4 => Weather::Snow,
-                    5 => Weather::Blizzard,
-                    6 => Weather::Fog,
-                    _ => Weather::Clear,
-                };
+                return SEASONAL_WEATHER[i];
             }
         }
         Weather::Clear
@@ -14,9 +14,26 @@ pub enum BuildingType {
     Granary,
     Wall,
     Gate,
+    Bathhouse,
+    MarketSquare,
+    Watchtower,
+    Well,
+    Shrine,
 }
 
 impl BuildingType {
+    /// The building a constructed blueprint becomes, if it is one
+    pub fn for_blueprint(name: &str) -> Option<Self> {
+        match name {
+            "wooden_house" => Some(BuildingType::House),
+            "stone_wall" => Some(BuildingType::Wall),
+            "watchtower" => Some(BuildingType::Watchtower),
+            "well" => Some(BuildingType::Well),
+            "shrine" => Some(BuildingType::Shrine),
+            _ => None,
+        }
+    }
+
     /// Base work required to construct this building type
     pub fn work_required(&self) -> f32 {
         match self {
@@ -26,6 +43,11 @@ impl BuildingType {
             BuildingType::Granary => 120.0,
             BuildingType::Wall => 80.0,
             BuildingType::Gate => 60.0,
+            BuildingType::Bathhouse => 120.0,
+            BuildingType::MarketSquare => 90.0,
+            BuildingType::Watchtower => 250.0,
+            BuildingType::Well => 60.0,
+            BuildingType::Shrine => 80.0,
         }
     }
 
@@ -38,6 +60,11 @@ impl BuildingType {
             BuildingType::Granary => 4,
             BuildingType::Wall => 6,
             BuildingType::Gate => 4,
+            BuildingType::Bathhouse => 4,
+            BuildingType::MarketSquare => 5,
+            BuildingType::Watchtower => 6,
+            BuildingType::Well => 2,
+            BuildingType::Shrine => 3,
         }
     }
 
@@ -50,6 +77,11 @@ impl BuildingType {
             BuildingType::Granary => (3.0, 3.0),
             BuildingType::Wall => (1.0, 1.0),
             BuildingType::Gate => (2.0, 1.0),
+            BuildingType::Bathhouse => (3.0, 3.0),
+            BuildingType::MarketSquare => (5.0, 5.0),
+            BuildingType::Watchtower => (4.0, 4.0),
+            BuildingType::Well => (2.0, 2.0),
+            BuildingType::Shrine => (3.0, 3.0),
         }
     }
 
@@ -74,6 +106,15 @@ impl BuildingType {
             BuildingType::Granary => vec![(ResourceType::Wood, 50), (ResourceType::Stone, 30)],
             BuildingType::Wall => vec![(ResourceType::Stone, 25)],
             BuildingType::Gate => vec![(ResourceType::Wood, 15), (ResourceType::Iron, 10)],
+            BuildingType::Bathhouse => vec![(ResourceType::Wood, 30), (ResourceType::Stone, 30)],
+            BuildingType::MarketSquare => vec![(ResourceType::Wood, 20), (ResourceType::Stone, 40)],
+            BuildingType::Watchtower => vec![
+                (ResourceType::Stone, 60),
+                (ResourceType::Wood, 30),
+                (ResourceType::Iron, 10),
+            ],
+            BuildingType::Well => vec![(ResourceType::Stone, 20)],
+            BuildingType::Shrine => vec![(ResourceType::Stone, 30)],
         }
     }
 }
@@ -107,7 +148,7 @@ impl Default for BuildingId {
 }
 
 /// Structure of Arrays for building entities
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BuildingArchetype {
     /// Unique identifiers
     pub ids: Vec<BuildingId>,
@@ -371,6 +412,11 @@ mod tests {
             BuildingType::Granary,
             BuildingType::Wall,
             BuildingType::Gate,
+            BuildingType::Bathhouse,
+            BuildingType::MarketSquare,
+            BuildingType::Watchtower,
+            BuildingType::Well,
+            BuildingType::Shrine,
         ];
 
         for bt in building_types {
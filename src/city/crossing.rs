@@ -0,0 +1,405 @@
+//! Crossings - bridges and ramps over terrain obstacles
+//!
+//! A crossing opens a path through a stream, gully, or ledge. Its size,
+//! materials, work, and movement cost all come from the `bridge` and `ramp`
+//! blueprints. Builders commit whatever materials the stockpile holds, down
+//! to half the blueprint's bill; a crossing built short of materials stands
+//! but may collapse, closing the path again.
+
+use std::collections::HashMap;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::blueprints::{
+    eval_expr_str, get_labor_cap, get_required_materials, BlueprintError, BlueprintRegistry,
+    PlacedBy,
+};
+use crate::city::stockpile::Stockpile;
+use crate::core::types::Vec2;
+use crate::simulation::resource_zone::ResourceType;
+use crate::world::{BlockedCells, TerrainFeature, TerrainFeatureKind};
+
+/// Smallest fraction of the blueprint's materials builders will work with
+pub const MIN_BUILD_QUALITY: f32 = 0.5;
+
+/// Daily collapse chance of a crossing built with no materials at all
+pub const COLLAPSE_RATE: f32 = 0.1;
+
+/// Kind of crossing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CrossingKind {
+    /// Spans a stream or gully
+    Bridge,
+    /// Climbs a ledge
+    Ramp,
+}
+
+impl CrossingKind {
+    /// The crossing that gets past a kind of terrain feature
+    pub fn for_feature(kind: TerrainFeatureKind) -> Self {
+        match kind {
+            TerrainFeatureKind::Stream | TerrainFeatureKind::Gully => CrossingKind::Bridge,
+            TerrainFeatureKind::Ledge => CrossingKind::Ramp,
+        }
+    }
+
+    /// Name of the blueprint that defines this crossing
+    pub fn blueprint_name(&self) -> &'static str {
+        match self {
+            CrossingKind::Bridge => "bridge",
+            CrossingKind::Ramp => "ramp",
+        }
+    }
+}
+
+/// Unique identifier for a crossing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CrossingId(pub u32);
+
+/// Lifecycle of a crossing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrossingState {
+    UnderConstruction,
+    Complete,
+    Collapsed,
+}
+
+/// Why a crossing could not be planned
+#[derive(Debug)]
+pub enum CrossingError {
+    /// The crossing's blueprint is not loaded
+    UnknownBlueprint(String),
+    /// The blueprint rejected the crossing's dimensions
+    Blueprint(BlueprintError),
+    /// The stockpile holds less than `MIN_BUILD_QUALITY` of the materials
+    InsufficientMaterials,
+    /// There is no terrain feature at the position
+    NoObstacle,
+}
+
+impl std::fmt::Display for CrossingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrossingError::UnknownBlueprint(name) => write!(f, "Unknown blueprint: {}", name),
+            CrossingError::Blueprint(e) => write!(f, "Blueprint error: {}", e),
+            CrossingError::InsufficientMaterials => write!(f, "Insufficient materials"),
+            CrossingError::NoObstacle => write!(f, "No terrain feature to cross"),
+        }
+    }
+}
+
+impl std::error::Error for CrossingError {}
+
+impl From<BlueprintError> for CrossingError {
+    fn from(e: BlueprintError) -> Self {
+        CrossingError::Blueprint(e)
+    }
+}
+
+/// A bridge or ramp over a terrain feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Crossing {
+    pub id: CrossingId,
+    pub kind: CrossingKind,
+    pub position: Vec2,
+    /// Feature cells this crossing opens when complete
+    pub cells: Vec<(i32, i32)>,
+    pub state: CrossingState,
+    /// Work applied so far (0.0 to work_required)
+    pub progress: f32,
+    pub work_required: f32,
+    /// Most builders that can work at once
+    pub labor_cap: u32,
+    /// Movement cost multiplier while crossing
+    pub movement_cost: f32,
+    /// Fraction of the blueprint's materials actually used (0.5-1.0)
+    pub quality: f32,
+}
+
+impl Crossing {
+    /// Plan a crossing over a feature at a position
+    ///
+    /// Evaluates the blueprint for the feature's dimensions, then consumes
+    /// as much of the material bill as the stockpile can cover.
+    pub fn plan(
+        id: CrossingId,
+        feature: &TerrainFeature,
+        position: Vec2,
+        width: f32,
+        registry: &BlueprintRegistry,
+        stockpile: &mut Stockpile,
+        current_tick: u64,
+    ) -> Result<Self, CrossingError> {
+        let kind = CrossingKind::for_feature(feature.kind);
+        let blueprint_id = registry
+            .id_by_name(kind.blueprint_name())
+            .ok_or_else(|| CrossingError::UnknownBlueprint(kind.blueprint_name().to_string()))?;
+
+        let mut params = HashMap::new();
+        params.insert("width".to_string(), width);
+        match kind {
+            CrossingKind::Bridge => params.insert("span".to_string(), feature.width),
+            CrossingKind::Ramp => params.insert("rise".to_string(), feature.elevation),
+        };
+
+        let instance = registry.instantiate(
+            blueprint_id,
+            params,
+            glam::Vec2::new(position.x, position.y),
+            0.0,
+            PlacedBy::Gameplay { tick: current_tick },
+            None,
+        )?;
+        let blueprint = registry
+            .get(blueprint_id)
+            .ok_or_else(|| CrossingError::UnknownBlueprint(kind.blueprint_name().to_string()))?;
+
+        // Commit what the stockpile can cover
+        let required: Vec<(ResourceType, u32)> =
+            get_required_materials(blueprint, &instance.parameters)
+                .into_iter()
+                .filter_map(|(name, n)| ResourceType::from_name(&name).map(|r| (r, n)))
+                .collect();
+        let total_required: u32 = required.iter().map(|(_, n)| n).sum();
+        let supplied: Vec<(ResourceType, u32)> = required
+            .iter()
+            .map(|&(r, n)| (r, n.min(stockpile.get(r))))
+            .collect();
+        let total_supplied: u32 = supplied.iter().map(|(_, n)| n).sum();
+        let quality = if total_required == 0 {
+            1.0
+        } else {
+            total_supplied as f32 / total_required as f32
+        };
+        if quality < MIN_BUILD_QUALITY {
+            return Err(CrossingError::InsufficientMaterials);
+        }
+        stockpile.consume_materials(&supplied);
+
+        let work_required = blueprint
+            .construction
+            .as_ref()
+            .map(|c| eval_expr_str(&c.base_time, &instance.parameters))
+            .transpose()?
+            .unwrap_or(0.0);
+
+        // Feature cells within half the crossing's width of the crossing line
+        let cells = feature.cells_across(position, width * 0.5 + feature.cell_size * 0.5);
+
+        Ok(Self {
+            id,
+            kind,
+            position,
+            cells,
+            state: CrossingState::UnderConstruction,
+            progress: 0.0,
+            work_required,
+            labor_cap: get_labor_cap(blueprint, &instance.parameters),
+            movement_cost: instance.military.movement_cost,
+            quality,
+        })
+    }
+
+    /// Apply construction work. Returns true when the crossing opens.
+    pub fn apply_work(&mut self, amount: f32, blocked: &mut BlockedCells) -> bool {
+        if self.state != CrossingState::UnderConstruction {
+            return self.state == CrossingState::Complete;
+        }
+        self.progress += amount;
+        if self.progress < self.work_required {
+            return false;
+        }
+
+        self.state = CrossingState::Complete;
+        for &(x, y) in &self.cells {
+            blocked.unblock(x, y);
+            blocked.set_movement_cost(x, y, self.movement_cost);
+        }
+        true
+    }
+
+    /// Daily chance that an under-built crossing gives way
+    pub fn collapse_chance(&self) -> f32 {
+        if self.state != CrossingState::Complete {
+            return 0.0;
+        }
+        (1.0 - self.quality).max(0.0) * 2.0 * COLLAPSE_RATE
+    }
+
+    /// Roll for collapse. Returns true if the crossing collapsed.
+    pub fn check_collapse<R: Rng>(&mut self, blocked: &mut BlockedCells, rng: &mut R) -> bool {
+        let chance = self.collapse_chance();
+        if chance <= 0.0 || rng.gen::<f32>() >= chance {
+            return false;
+        }
+        self.collapse(blocked);
+        true
+    }
+
+    /// The crossing gives way and the obstacle blocks the path again
+    pub fn collapse(&mut self, blocked: &mut BlockedCells) {
+        self.state = CrossingState::Collapsed;
+        for &(x, y) in &self.cells {
+            blocked.set_movement_cost(x, y, 1.0);
+            blocked.block(x, y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::TerrainFeatures;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::path::Path;
+
+    fn registry() -> BlueprintRegistry {
+        let mut registry = BlueprintRegistry::new();
+        registry
+            .load_directory(Path::new("data/blueprints"))
+            .unwrap();
+        registry
+    }
+
+    fn stream(blocked: &mut BlockedCells) -> TerrainFeature {
+        let mut features = TerrainFeatures::new();
+        let id = features.add(
+            TerrainFeatureKind::Stream,
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 20.0),
+            3.0,
+            1.0,
+            blocked,
+        );
+        features.get(id).unwrap().clone()
+    }
+
+    #[test]
+    fn test_bridge_opens_stream_when_complete() {
+        let registry = registry();
+        let mut blocked = BlockedCells::new();
+        let feature = stream(&mut blocked);
+        let mut stockpile = Stockpile::new();
+        stockpile.add(ResourceType::Wood, 100);
+        stockpile.add(ResourceType::Stone, 100);
+
+        let mut bridge = Crossing::plan(
+            CrossingId(0),
+            &feature,
+            Vec2::new(10.0, 10.0),
+            2.0,
+            &registry,
+            &mut stockpile,
+            0,
+        )
+        .unwrap();
+
+        // span 3 x width 2: wood 36, stone 12, work 90
+        assert_eq!(bridge.kind, CrossingKind::Bridge);
+        assert_eq!(stockpile.get(ResourceType::Wood), 64);
+        assert_eq!(stockpile.get(ResourceType::Stone), 88);
+        assert_eq!(bridge.quality, 1.0);
+        assert!((bridge.work_required - 90.0).abs() < 0.01);
+
+        let on_bridge = glam::Vec2::new(10.0, 10.0);
+        assert!(blocked.is_position_blocked(on_bridge));
+        assert!(!bridge.apply_work(50.0, &mut blocked));
+        assert!(bridge.apply_work(50.0, &mut blocked));
+        assert!(!blocked.is_position_blocked(on_bridge));
+
+        // Only the crossing is opened, not the whole stream
+        assert!(blocked.is_position_blocked(glam::Vec2::new(10.0, 2.0)));
+
+        // Fully supplied bridges never collapse
+        assert_eq!(bridge.collapse_chance(), 0.0);
+    }
+
+    #[test]
+    fn test_ramp_uses_ledge_height_and_slows_movement() {
+        let registry = registry();
+        let mut blocked = BlockedCells::new();
+        let mut features = TerrainFeatures::new();
+        let id = features.add(
+            TerrainFeatureKind::Ledge,
+            Vec2::new(0.0, 5.0),
+            Vec2::new(20.0, 5.0),
+            1.0,
+            2.0,
+            &mut blocked,
+        );
+        let mut stockpile = Stockpile::new();
+        stockpile.add(ResourceType::Wood, 100);
+        stockpile.add(ResourceType::Stone, 100);
+
+        let mut ramp = Crossing::plan(
+            CrossingId(0),
+            features.get(id).unwrap(),
+            Vec2::new(10.0, 5.0),
+            2.0,
+            &registry,
+            &mut stockpile,
+            0,
+        )
+        .unwrap();
+        assert_eq!(ramp.kind, CrossingKind::Ramp);
+        assert_eq!(stockpile.get(ResourceType::Stone), 100 - 32);
+
+        ramp.apply_work(ramp.work_required, &mut blocked);
+        assert_eq!(
+            blocked.position_movement_cost(glam::Vec2::new(10.0, 5.0)),
+            1.5
+        );
+    }
+
+    #[test]
+    fn test_underbuilt_bridge_can_collapse() {
+        let registry = registry();
+        let mut blocked = BlockedCells::new();
+        let feature = stream(&mut blocked);
+
+        // Nothing to build with
+        let mut empty = Stockpile::new();
+        let result = Crossing::plan(
+            CrossingId(0),
+            &feature,
+            Vec2::new(10.0, 10.0),
+            2.0,
+            &registry,
+            &mut empty,
+            0,
+        );
+        assert!(matches!(result, Err(CrossingError::InsufficientMaterials)));
+
+        // Enough wood, no stone: 36 of 48 units
+        let mut stockpile = Stockpile::new();
+        stockpile.add(ResourceType::Wood, 100);
+        let mut bridge = Crossing::plan(
+            CrossingId(0),
+            &feature,
+            Vec2::new(10.0, 10.0),
+            2.0,
+            &registry,
+            &mut stockpile,
+            0,
+        )
+        .unwrap();
+        assert!((bridge.quality - 0.75).abs() < 0.01);
+
+        bridge.apply_work(bridge.work_required, &mut blocked);
+        assert!(bridge.collapse_chance() > 0.0);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut collapsed = false;
+        for _ in 0..200 {
+            if bridge.check_collapse(&mut blocked, &mut rng) {
+                collapsed = true;
+                break;
+            }
+        }
+        assert!(collapsed);
+        assert_eq!(bridge.state, CrossingState::Collapsed);
+        assert!(blocked.is_position_blocked(glam::Vec2::new(10.0, 10.0)));
+    }
+}
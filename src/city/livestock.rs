@@ -0,0 +1,943 @@
+//! Livestock husbandry - domesticated animals, pastures, breeding, and products
+//!
+//! Livestock form a secondary food economy alongside farms:
+//! - Animals graze on pastures; pasture forage regrows by season
+//! - When forage runs short (always in winter) animals eat fodder from the stockpile
+//! - Mature females breed during their breeding season and give birth after gestation
+//! - Recurring products (milk, wool, eggs) accumulate on the pasture until tended
+//! - Slaughter decisions cull surplus animals for meat and leather
+//!
+//! Animals use the same SoA layout as entity archetypes. The daily update is
+//! pure over the herd, pastures, and stockpile so it can be tested in isolation;
+//! `simulation::husbandry` wires it into the world tick and task system.
+
+use crate::city::stockpile::Stockpile;
+use crate::core::astronomy::Season;
+use crate::core::types::Vec2;
+use crate::simulation::resource_zone::ResourceType;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Days of winter the settlement must be able to feed its herd through
+pub const WINTER_DAYS: u32 = 90;
+
+/// Hunger level (0.0-1.0) above which animals stop producing
+const PRODUCTIVE_HUNGER_LIMIT: f32 = 0.5;
+
+/// Hunger gained per day when an animal gets nothing to eat
+const STARVATION_HUNGER_PER_DAY: f32 = 0.25;
+
+/// Health lost per day while hunger is maxed out
+const STARVATION_DAMAGE_PER_DAY: f32 = 0.2;
+
+/// Daily chance a receptive female conceives when a mate is present
+const CONCEPTION_CHANCE: f32 = 0.1;
+
+/// Kind of domesticated animal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LivestockKind {
+    Cattle,
+    Sheep,
+    Goat,
+    Pig,
+    Chicken,
+}
+
+impl LivestockKind {
+    /// Recurring product: (resource, amount, interval in days), if any
+    pub fn product(&self) -> Option<(ResourceType, u32, u32)> {
+        match self {
+            LivestockKind::Cattle => Some((ResourceType::Milk, 2, 1)),
+            LivestockKind::Goat => Some((ResourceType::Milk, 1, 1)),
+            LivestockKind::Sheep => Some((ResourceType::Wool, 4, 180)),
+            LivestockKind::Chicken => Some((ResourceType::Eggs, 1, 1)),
+            LivestockKind::Pig => None,
+        }
+    }
+
+    /// Whether only females yield the recurring product (milk, eggs)
+    pub fn product_is_female_only(&self) -> bool {
+        matches!(
+            self,
+            LivestockKind::Cattle | LivestockKind::Goat | LivestockKind::Chicken
+        )
+    }
+
+    /// (meat, leather) yielded when slaughtered as an adult
+    pub fn slaughter_yield(&self) -> (u32, u32) {
+        match self {
+            LivestockKind::Cattle => (20, 4),
+            LivestockKind::Sheep => (8, 2),
+            LivestockKind::Goat => (6, 2),
+            LivestockKind::Pig => (15, 2),
+            LivestockKind::Chicken => (1, 0),
+        }
+    }
+
+    /// Age in days at which the animal can breed and produce
+    pub fn maturity_days(&self) -> u32 {
+        match self {
+            LivestockKind::Cattle => 720,
+            LivestockKind::Sheep => 360,
+            LivestockKind::Goat => 300,
+            LivestockKind::Pig => 240,
+            LivestockKind::Chicken => 150,
+        }
+    }
+
+    /// Age in days after which the animal is past its useful life
+    pub fn old_age_days(&self) -> u32 {
+        match self {
+            LivestockKind::Cattle => 5400,
+            LivestockKind::Sheep => 2880,
+            LivestockKind::Goat => 3240,
+            LivestockKind::Pig => 2160,
+            LivestockKind::Chicken => 1440,
+        }
+    }
+
+    /// Days between conception and birth
+    pub fn gestation_days(&self) -> u32 {
+        match self {
+            LivestockKind::Cattle => 270,
+            LivestockKind::Sheep => 150,
+            LivestockKind::Goat => 150,
+            LivestockKind::Pig => 115,
+            LivestockKind::Chicken => 21,
+        }
+    }
+
+    /// Number of young born per birth
+    pub fn litter_size(&self) -> u32 {
+        match self {
+            LivestockKind::Cattle | LivestockKind::Sheep => 1,
+            LivestockKind::Goat => 2,
+            LivestockKind::Pig => 6,
+            LivestockKind::Chicken => 4,
+        }
+    }
+
+    /// Whether animals of this kind breed in the given season
+    pub fn breeds_in(&self, season: Season) -> bool {
+        match self {
+            LivestockKind::Chicken | LivestockKind::Pig => season != Season::Winter,
+            LivestockKind::Sheep | LivestockKind::Goat => season == Season::Autumn,
+            LivestockKind::Cattle => matches!(season, Season::Spring | Season::Summer),
+        }
+    }
+
+    /// Forage eaten per day from a pasture
+    pub fn forage_per_day(&self) -> f32 {
+        match self {
+            LivestockKind::Cattle => 1.0,
+            LivestockKind::Sheep => 0.4,
+            LivestockKind::Goat => 0.3,
+            LivestockKind::Pig => 0.5,
+            LivestockKind::Chicken => 0.05,
+        }
+    }
+
+    /// Pasture capacity units consumed by one animal
+    pub fn grazing_units(&self) -> f32 {
+        match self {
+            LivestockKind::Cattle => 1.0,
+            LivestockKind::Sheep | LivestockKind::Goat | LivestockKind::Pig => 0.5,
+            LivestockKind::Chicken => 0.1,
+        }
+    }
+
+    /// Draft power for pulling vehicles (an entity pulls 1.0, 0.0 = cannot pull)
+    pub fn draft_power(&self) -> f32 {
+        match self {
+            LivestockKind::Cattle => 3.0,
+            LivestockKind::Goat => 0.5,
+            LivestockKind::Sheep | LivestockKind::Pig | LivestockKind::Chicken => 0.0,
+        }
+    }
+}
+
+/// Sex of an animal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LivestockSex {
+    Female,
+    Male,
+}
+
+/// Unique livestock identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LivestockId(pub u64);
+
+/// Unique pasture identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PastureId(pub u32);
+
+/// A fenced grazing zone that holds livestock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pasture {
+    pub id: PastureId,
+    pub position: Vec2,
+    pub radius: f32,
+    /// Grazing units the pasture can support
+    pub capacity: f32,
+    /// Forage currently available
+    pub forage: f32,
+    /// Maximum standing forage
+    pub max_forage: f32,
+    /// Forage regrowth per day at full (spring) rate
+    pub regrowth: f32,
+    /// Products waiting to be collected by a tending worker
+    pub pending_products: Vec<(ResourceType, u32)>,
+}
+
+impl Pasture {
+    pub fn new(id: PastureId, position: Vec2, radius: f32, capacity: f32) -> Self {
+        Self {
+            id,
+            position,
+            radius,
+            capacity,
+            forage: capacity * 10.0,
+            max_forage: capacity * 10.0,
+            regrowth: capacity * 1.2,
+            pending_products: Vec::new(),
+        }
+    }
+
+    /// Check if a position is inside this pasture
+    pub fn contains(&self, pos: Vec2) -> bool {
+        self.position.distance(&pos) <= self.radius
+    }
+
+    /// Seasonal multiplier on forage regrowth
+    pub fn regrowth_multiplier(season: Season) -> f32 {
+        match season {
+            Season::Spring => 1.0,
+            Season::Summer => 0.8,
+            Season::Autumn => 0.4,
+            Season::Winter => 0.0,
+        }
+    }
+
+    /// Regrow forage for one day
+    pub fn regrow(&mut self, season: Season) {
+        let growth = self.regrowth * Self::regrowth_multiplier(season);
+        self.forage = (self.forage + growth).min(self.max_forage);
+    }
+
+    /// Graze up to `amount` forage, returns amount eaten
+    pub fn graze(&mut self, amount: f32) -> f32 {
+        let eaten = amount.min(self.forage);
+        self.forage -= eaten;
+        eaten
+    }
+
+    /// Add products awaiting collection
+    pub fn add_product(&mut self, resource: ResourceType, amount: u32) {
+        if let Some(entry) = self
+            .pending_products
+            .iter_mut()
+            .find(|(r, _)| *r == resource)
+        {
+            entry.1 += amount;
+        } else {
+            self.pending_products.push((resource, amount));
+        }
+    }
+
+    /// Whether anything is waiting to be collected
+    pub fn has_pending_products(&self) -> bool {
+        self.pending_products.iter().any(|(_, amount)| *amount > 0)
+    }
+}
+
+/// Summary of one day of husbandry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HusbandryReport {
+    pub births: u32,
+    pub starved: u32,
+    pub died_of_age: u32,
+    pub fodder_consumed: u32,
+    pub products: u32,
+}
+
+/// Result of tending a pasture
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TendResult {
+    /// Products moved into the stockpile
+    pub collected: Vec<(ResourceType, u32)>,
+    /// Animals slaughtered
+    pub slaughtered: u32,
+    /// Meat and leather added to the stockpile
+    pub meat: u32,
+    pub leather: u32,
+}
+
+/// Structure of Arrays for livestock
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LivestockArchetype {
+    pub ids: Vec<LivestockId>,
+    pub kinds: Vec<LivestockKind>,
+    pub sexes: Vec<LivestockSex>,
+    /// Pasture each animal is kept in
+    pub pastures: Vec<PastureId>,
+    pub ages_days: Vec<u32>,
+    /// 0.0 = fed, 1.0 = starving
+    pub hunger: Vec<f32>,
+    /// 0.0 = dead, 1.0 = healthy
+    pub health: Vec<f32>,
+    /// Days remaining until birth (None if not pregnant)
+    pub gestation_remaining: Vec<Option<u32>>,
+    /// Days since the recurring product was last yielded
+    pub days_since_product: Vec<u32>,
+    /// Marked by the slaughter planner, processed when the pasture is tended
+    pub marked_for_slaughter: Vec<bool>,
+    pub alive: Vec<bool>,
+    next_id: u64,
+}
+
+impl LivestockArchetype {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn living_count(&self) -> usize {
+        self.alive.iter().filter(|&&a| a).count()
+    }
+
+    /// Spawn an animal in a pasture, returns its index
+    pub fn spawn(
+        &mut self,
+        kind: LivestockKind,
+        sex: LivestockSex,
+        pasture: PastureId,
+        age_days: u32,
+    ) -> usize {
+        let index = self.ids.len();
+        self.ids.push(LivestockId(self.next_id));
+        self.next_id += 1;
+        self.kinds.push(kind);
+        self.sexes.push(sex);
+        self.pastures.push(pasture);
+        self.ages_days.push(age_days);
+        self.hunger.push(0.0);
+        self.health.push(1.0);
+        self.gestation_remaining.push(None);
+        self.days_since_product.push(0);
+        self.marked_for_slaughter.push(false);
+        self.alive.push(true);
+        index
+    }
+
+    pub fn index_of(&self, id: LivestockId) -> Option<usize> {
+        self.ids.iter().position(|&l| l == id)
+    }
+
+    pub fn iter_living(&self) -> impl Iterator<Item = usize> + '_ {
+        self.alive
+            .iter()
+            .enumerate()
+            .filter(|(_, &alive)| alive)
+            .map(|(i, _)| i)
+    }
+
+    /// Living animals kept in a pasture
+    pub fn iter_in_pasture(&self, pasture: PastureId) -> impl Iterator<Item = usize> + '_ {
+        self.iter_living()
+            .filter(move |&i| self.pastures[i] == pasture)
+    }
+
+    pub fn is_mature(&self, idx: usize) -> bool {
+        self.ages_days[idx] >= self.kinds[idx].maturity_days()
+    }
+
+    /// Grazing units currently used in a pasture
+    pub fn grazing_load(&self, pasture: PastureId) -> f32 {
+        self.iter_in_pasture(pasture)
+            .map(|i| self.kinds[i].grazing_units())
+            .sum()
+    }
+
+    /// Fodder needed to carry the living herd through a full winter
+    pub fn winter_fodder_requirement(&self) -> u32 {
+        let per_day: f32 = self
+            .iter_living()
+            .map(|i| self.kinds[i].forage_per_day())
+            .sum();
+        (per_day * WINTER_DAYS as f32).ceil() as u32
+    }
+
+    /// Advance the herd by one day
+    ///
+    /// Order: regrow pastures, feed (forage then fodder), age and health,
+    /// births, conception, products.
+    pub fn tick_daily<R: Rng>(
+        &mut self,
+        pastures: &mut [Pasture],
+        stockpile: &mut Stockpile,
+        season: Season,
+        rng: &mut R,
+    ) -> HusbandryReport {
+        let mut report = HusbandryReport::default();
+
+        for pasture in pastures.iter_mut() {
+            pasture.regrow(season);
+        }
+
+        // Feeding: graze first, make up the shortfall from stockpiled fodder
+        let mut fodder_debt = 0.0_f32;
+        let mut shortfalls: Vec<(usize, f32)> = Vec::new();
+        for i in self.iter_living().collect::<Vec<_>>() {
+            let need = self.kinds[i].forage_per_day();
+            let grazed = pastures
+                .iter_mut()
+                .find(|p| p.id == self.pastures[i])
+                .map(|p| p.graze(need))
+                .unwrap_or(0.0);
+            let shortfall = need - grazed;
+            if shortfall > 0.0 {
+                fodder_debt += shortfall;
+                shortfalls.push((i, shortfall));
+            } else {
+                self.hunger[i] = (self.hunger[i] - 0.2).max(0.0);
+            }
+        }
+
+        if fodder_debt > 0.0 {
+            let wanted = fodder_debt.ceil() as u32;
+            let taken = stockpile.remove(ResourceType::Fodder, wanted);
+            report.fodder_consumed = taken;
+            let fed_fraction = (taken as f32 / fodder_debt).min(1.0);
+            for (i, shortfall) in shortfalls {
+                let unfed = shortfall * (1.0 - fed_fraction) / self.kinds[i].forage_per_day();
+                if unfed > 0.0 {
+                    self.hunger[i] = (self.hunger[i] + unfed * STARVATION_HUNGER_PER_DAY).min(1.0);
+                } else {
+                    self.hunger[i] = (self.hunger[i] - 0.2).max(0.0);
+                }
+            }
+        }
+
+        // Aging and health
+        for i in self.iter_living().collect::<Vec<_>>() {
+            self.ages_days[i] += 1;
+            if self.hunger[i] >= 1.0 {
+                self.health[i] -= STARVATION_DAMAGE_PER_DAY;
+            } else if self.hunger[i] < PRODUCTIVE_HUNGER_LIMIT {
+                self.health[i] = (self.health[i] + 0.05).min(1.0);
+            }
+
+            if self.health[i] <= 0.0 {
+                self.alive[i] = false;
+                report.starved += 1;
+            } else if self.ages_days[i] > self.kinds[i].old_age_days() * 5 / 4 {
+                self.alive[i] = false;
+                report.died_of_age += 1;
+            }
+        }
+
+        // Births
+        for i in self.iter_living().collect::<Vec<_>>() {
+            if let Some(remaining) = self.gestation_remaining[i] {
+                if remaining <= 1 {
+                    self.gestation_remaining[i] = None;
+                    let kind = self.kinds[i];
+                    let pasture = self.pastures[i];
+                    for _ in 0..kind.litter_size() {
+                        let sex = if rng.gen::<bool>() {
+                            LivestockSex::Female
+                        } else {
+                            LivestockSex::Male
+                        };
+                        self.spawn(kind, sex, pasture, 0);
+                        report.births += 1;
+                    }
+                } else {
+                    self.gestation_remaining[i] = Some(remaining - 1);
+                }
+            }
+        }
+
+        // Conception: receptive females with a mature male of their kind in the same pasture
+        for i in self.iter_living().collect::<Vec<_>>() {
+            let kind = self.kinds[i];
+            if self.sexes[i] != LivestockSex::Female
+                || self.gestation_remaining[i].is_some()
+                || !self.is_mature(i)
+                || !kind.breeds_in(season)
+                || self.hunger[i] >= PRODUCTIVE_HUNGER_LIMIT
+            {
+                continue;
+            }
+            let pasture_id = self.pastures[i];
+            let has_mate = self.iter_in_pasture(pasture_id).any(|j| {
+                self.kinds[j] == kind && self.sexes[j] == LivestockSex::Male && self.is_mature(j)
+            });
+            if !has_mate {
+                continue;
+            }
+            // Overcrowded pastures suppress breeding
+            let crowded = pastures
+                .iter()
+                .find(|p| p.id == pasture_id)
+                .map(|p| self.grazing_load(pasture_id) >= p.capacity)
+                .unwrap_or(true);
+            if crowded {
+                continue;
+            }
+            if rng.gen::<f32>() < CONCEPTION_CHANCE {
+                self.gestation_remaining[i] = Some(kind.gestation_days());
+            }
+        }
+
+        // Recurring products accumulate on the pasture until tended
+        for i in self.iter_living().collect::<Vec<_>>() {
+            let kind = self.kinds[i];
+            let Some((resource, amount, interval)) = kind.product() else {
+                continue;
+            };
+            self.days_since_product[i] += 1;
+            let eligible = self.is_mature(i)
+                && self.hunger[i] < PRODUCTIVE_HUNGER_LIMIT
+                && (!kind.product_is_female_only() || self.sexes[i] == LivestockSex::Female);
+            if eligible && self.days_since_product[i] >= interval {
+                self.days_since_product[i] = 0;
+                if let Some(pasture) = pastures.iter_mut().find(|p| p.id == self.pastures[i]) {
+                    pasture.add_product(resource, amount);
+                    report.products += amount;
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Decide which animals to slaughter, returns how many were newly marked
+    ///
+    /// Culls, in order of preference:
+    /// 1. Animals well past their useful age
+    /// 2. Surplus mature males (one breeding male per kind per pasture is kept)
+    /// 3. Animals beyond pasture capacity
+    /// 4. In autumn, animals the stockpiled fodder cannot carry through winter
+    ///
+    /// A breeding pair of each kind in each pasture is never culled for reasons 2-4.
+    pub fn plan_slaughter(
+        &mut self,
+        pastures: &[Pasture],
+        stockpile: &Stockpile,
+        season: Season,
+    ) -> u32 {
+        let mut marked = 0;
+
+        for i in self.iter_living().collect::<Vec<_>>() {
+            if !self.marked_for_slaughter[i] && self.ages_days[i] > self.kinds[i].old_age_days() {
+                self.marked_for_slaughter[i] = true;
+                marked += 1;
+            }
+        }
+
+        for pasture in pastures {
+            // Candidates sorted so that the least valuable animals go first:
+            // surplus males, then the oldest.
+            let mut kept_male: Vec<LivestockKind> = Vec::new();
+            let mut kept_female: Vec<LivestockKind> = Vec::new();
+            let mut candidates: Vec<usize> = Vec::new();
+            let mut members: Vec<usize> = self
+                .iter_in_pasture(pasture.id)
+                .filter(|&i| !self.marked_for_slaughter[i])
+                .collect();
+            // Youngest mature animals are kept as breeders
+            members.sort_by_key(|&i| (!self.is_mature(i), self.ages_days[i]));
+            for i in members {
+                let kind = self.kinds[i];
+                let mature = self.is_mature(i);
+                match self.sexes[i] {
+                    LivestockSex::Male if mature && !kept_male.contains(&kind) => {
+                        kept_male.push(kind)
+                    }
+                    LivestockSex::Female if mature && !kept_female.contains(&kind) => {
+                        kept_female.push(kind)
+                    }
+                    _ => candidates.push(i),
+                }
+            }
+
+            let (mut males, mut others): (Vec<usize>, Vec<usize>) = candidates
+                .into_iter()
+                .partition(|&i| self.sexes[i] == LivestockSex::Male && self.is_mature(i));
+            others.sort_by_key(|&i| std::cmp::Reverse(self.ages_days[i]));
+            males.sort_by_key(|&i| std::cmp::Reverse(self.ages_days[i]));
+
+            for &i in &males {
+                self.marked_for_slaughter[i] = true;
+                marked += 1;
+            }
+
+            let mut load = self.grazing_load_unmarked(pasture.id);
+            for &i in &others {
+                if load <= pasture.capacity {
+                    break;
+                }
+                self.marked_for_slaughter[i] = true;
+                load -= self.kinds[i].grazing_units();
+                marked += 1;
+            }
+        }
+
+        if season == Season::Autumn {
+            let available = stockpile.get(ResourceType::Fodder);
+            let mut required = self.winter_fodder_requirement_unmarked();
+            let mut by_age: Vec<usize> = self
+                .iter_living()
+                .filter(|&i| !self.marked_for_slaughter[i] && !self.is_mature(i))
+                .collect();
+            by_age.sort_by_key(|&i| std::cmp::Reverse(self.ages_days[i]));
+            for i in by_age {
+                if required <= available {
+                    break;
+                }
+                self.marked_for_slaughter[i] = true;
+                required = required
+                    .saturating_sub((self.kinds[i].forage_per_day() * WINTER_DAYS as f32) as u32);
+                marked += 1;
+            }
+        }
+
+        marked
+    }
+
+    fn grazing_load_unmarked(&self, pasture: PastureId) -> f32 {
+        self.iter_in_pasture(pasture)
+            .filter(|&i| !self.marked_for_slaughter[i])
+            .map(|i| self.kinds[i].grazing_units())
+            .sum()
+    }
+
+    fn winter_fodder_requirement_unmarked(&self) -> u32 {
+        let per_day: f32 = self
+            .iter_living()
+            .filter(|&i| !self.marked_for_slaughter[i])
+            .map(|i| self.kinds[i].forage_per_day())
+            .sum();
+        (per_day * WINTER_DAYS as f32).ceil() as u32
+    }
+
+    /// Whether a pasture has marked animals waiting to be slaughtered
+    pub fn has_pending_slaughter(&self, pasture: PastureId) -> bool {
+        self.iter_in_pasture(pasture)
+            .any(|i| self.marked_for_slaughter[i])
+    }
+
+    /// Tend a pasture: collect waiting products and slaughter marked animals
+    ///
+    /// `skill` (0.0-1.0+) scales the meat and leather recovered from carcasses.
+    pub fn tend_pasture(
+        &mut self,
+        pasture: &mut Pasture,
+        stockpile: &mut Stockpile,
+        skill: f32,
+    ) -> TendResult {
+        let mut result = TendResult::default();
+
+        for (resource, amount) in pasture.pending_products.drain(..) {
+            let added = stockpile.add(resource, amount);
+            if added > 0 {
+                result.collected.push((resource, added));
+            }
+        }
+
+        let marked: Vec<usize> = self
+            .iter_in_pasture(pasture.id)
+            .filter(|&i| self.marked_for_slaughter[i])
+            .collect();
+        let skill_mult = 0.5 + 0.5 * skill.clamp(0.0, 1.0);
+        for i in marked {
+            let (meat, leather) = self.kinds[i].slaughter_yield();
+            // Young animals yield proportionally less
+            let growth = (self.ages_days[i] as f32 / self.kinds[i].maturity_days() as f32).min(1.0);
+            let meat = (meat as f32 * growth * skill_mult).round() as u32;
+            let leather = (leather as f32 * growth * skill_mult).round() as u32;
+            result.meat += stockpile.add(ResourceType::Meat, meat);
+            result.leather += stockpile.add(ResourceType::Leather, leather);
+            self.alive[i] = false;
+            self.marked_for_slaughter[i] = false;
+            result.slaughtered += 1;
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn pasture() -> Pasture {
+        Pasture::new(PastureId(0), Vec2::new(0.0, 0.0), 10.0, 10.0)
+    }
+
+    #[test]
+    fn test_pasture_regrowth_by_season() {
+        let mut p = pasture();
+        p.forage = 0.0;
+        p.regrow(Season::Spring);
+        assert!(p.forage > 0.0);
+
+        let before = p.forage;
+        p.regrow(Season::Winter);
+        assert_eq!(p.forage, before, "No forage grows in winter");
+    }
+
+    #[test]
+    fn test_grazing_feeds_herd() {
+        let mut herd = LivestockArchetype::new();
+        let mut pastures = vec![pasture()];
+        let mut stockpile = Stockpile::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Female,
+            PastureId(0),
+            1000,
+        );
+        let report = herd.tick_daily(&mut pastures, &mut stockpile, Season::Summer, &mut rng);
+
+        assert_eq!(report.fodder_consumed, 0);
+        assert_eq!(herd.hunger[0], 0.0);
+    }
+
+    #[test]
+    fn test_winter_consumes_fodder() {
+        let mut herd = LivestockArchetype::new();
+        let mut pastures = vec![pasture()];
+        pastures[0].forage = 0.0;
+        let mut stockpile = Stockpile::new();
+        stockpile.add(ResourceType::Fodder, 10);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Female,
+            PastureId(0),
+            1000,
+        );
+        let report = herd.tick_daily(&mut pastures, &mut stockpile, Season::Winter, &mut rng);
+
+        assert_eq!(report.fodder_consumed, 1);
+        assert_eq!(stockpile.get(ResourceType::Fodder), 9);
+        assert_eq!(herd.hunger[0], 0.0);
+    }
+
+    #[test]
+    fn test_starvation_without_fodder() {
+        let mut herd = LivestockArchetype::new();
+        let mut pastures = vec![pasture()];
+        pastures[0].forage = 0.0;
+        let mut stockpile = Stockpile::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        herd.spawn(
+            LivestockKind::Sheep,
+            LivestockSex::Female,
+            PastureId(0),
+            1000,
+        );
+        let mut starved = 0;
+        for _ in 0..30 {
+            starved += herd
+                .tick_daily(&mut pastures, &mut stockpile, Season::Winter, &mut rng)
+                .starved;
+        }
+
+        assert_eq!(starved, 1, "Unfed animal should eventually die");
+        assert_eq!(herd.living_count(), 0);
+    }
+
+    #[test]
+    fn test_products_accumulate_on_pasture() {
+        let mut herd = LivestockArchetype::new();
+        let mut pastures = vec![pasture()];
+        let mut stockpile = Stockpile::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Female,
+            PastureId(0),
+            1000,
+        );
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Male,
+            PastureId(0),
+            1000,
+        );
+        herd.spawn(
+            LivestockKind::Chicken,
+            LivestockSex::Female,
+            PastureId(0),
+            10,
+        );
+
+        herd.tick_daily(&mut pastures, &mut stockpile, Season::Winter, &mut rng);
+
+        // Only the mature cow yields milk; bull and chick yield nothing
+        assert_eq!(pastures[0].pending_products, vec![(ResourceType::Milk, 2)]);
+        assert_eq!(
+            stockpile.get(ResourceType::Milk),
+            0,
+            "Products wait for tending"
+        );
+    }
+
+    #[test]
+    fn test_breeding_produces_young() {
+        let mut herd = LivestockArchetype::new();
+        let mut pastures = vec![pasture()];
+        let mut stockpile = Stockpile::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+        herd.spawn(
+            LivestockKind::Chicken,
+            LivestockSex::Female,
+            PastureId(0),
+            200,
+        );
+        herd.spawn(
+            LivestockKind::Chicken,
+            LivestockSex::Male,
+            PastureId(0),
+            200,
+        );
+
+        let mut births = 0;
+        for _ in 0..90 {
+            births += herd
+                .tick_daily(&mut pastures, &mut stockpile, Season::Spring, &mut rng)
+                .births;
+        }
+
+        assert!(births >= LivestockKind::Chicken.litter_size());
+        assert!(herd.living_count() > 2);
+    }
+
+    #[test]
+    fn test_no_breeding_out_of_season() {
+        let mut herd = LivestockArchetype::new();
+        let mut pastures = vec![pasture()];
+        let mut stockpile = Stockpile::new();
+        stockpile.add(ResourceType::Fodder, 100);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+        herd.spawn(
+            LivestockKind::Sheep,
+            LivestockSex::Female,
+            PastureId(0),
+            1000,
+        );
+        herd.spawn(LivestockKind::Sheep, LivestockSex::Male, PastureId(0), 1000);
+
+        for _ in 0..60 {
+            herd.tick_daily(&mut pastures, &mut stockpile, Season::Spring, &mut rng);
+        }
+
+        assert!(herd.gestation_remaining[0].is_none());
+    }
+
+    #[test]
+    fn test_plan_slaughter_keeps_breeding_pair() {
+        let mut herd = LivestockArchetype::new();
+        let pastures = vec![pasture()];
+        let stockpile = Stockpile::new();
+
+        herd.spawn(LivestockKind::Pig, LivestockSex::Female, PastureId(0), 500);
+        herd.spawn(LivestockKind::Pig, LivestockSex::Male, PastureId(0), 500);
+        herd.spawn(LivestockKind::Pig, LivestockSex::Male, PastureId(0), 800);
+
+        let marked = herd.plan_slaughter(&pastures, &stockpile, Season::Summer);
+
+        assert_eq!(marked, 1, "Only the surplus boar should be culled");
+        assert!(!herd.marked_for_slaughter[0]);
+        assert!(!herd.marked_for_slaughter[1]);
+        assert!(herd.marked_for_slaughter[2]);
+    }
+
+    #[test]
+    fn test_autumn_cull_for_fodder_shortage() {
+        let mut herd = LivestockArchetype::new();
+        let pastures = vec![pasture()];
+        let stockpile = Stockpile::new();
+
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Female,
+            PastureId(0),
+            1000,
+        );
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Male,
+            PastureId(0),
+            1000,
+        );
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Female,
+            PastureId(0),
+            100,
+        );
+
+        let summer = herd.plan_slaughter(&pastures, &stockpile, Season::Summer);
+        assert_eq!(summer, 0);
+
+        let autumn = herd.plan_slaughter(&pastures, &stockpile, Season::Autumn);
+        assert_eq!(autumn, 1, "Young stock is culled when no fodder is stored");
+        assert!(herd.marked_for_slaughter[2]);
+    }
+
+    #[test]
+    fn test_tend_pasture_collects_and_slaughters() {
+        let mut herd = LivestockArchetype::new();
+        let mut p = pasture();
+        let mut stockpile = Stockpile::new();
+
+        p.add_product(ResourceType::Eggs, 3);
+        herd.spawn(LivestockKind::Pig, LivestockSex::Male, PastureId(0), 500);
+        herd.marked_for_slaughter[0] = true;
+
+        let result = herd.tend_pasture(&mut p, &mut stockpile, 1.0);
+
+        assert_eq!(result.collected, vec![(ResourceType::Eggs, 3)]);
+        assert_eq!(result.slaughtered, 1);
+        assert_eq!(stockpile.get(ResourceType::Eggs), 3);
+        assert_eq!(stockpile.get(ResourceType::Meat), 15);
+        assert_eq!(stockpile.get(ResourceType::Leather), 2);
+        assert!(!p.has_pending_products());
+        assert_eq!(herd.living_count(), 0);
+    }
+
+    #[test]
+    fn test_winter_fodder_requirement() {
+        let mut herd = LivestockArchetype::new();
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Female,
+            PastureId(0),
+            1000,
+        );
+        herd.spawn(
+            LivestockKind::Cattle,
+            LivestockSex::Male,
+            PastureId(0),
+            1000,
+        );
+
+        assert_eq!(herd.winter_fodder_requirement(), 180);
+    }
+}
@@ -2,15 +2,27 @@
 
 pub mod building;
 pub mod construction;
+pub mod crossing;
+pub mod livestock;
 pub mod production;
 pub mod recipe;
+pub mod road;
+pub mod site;
 pub mod stockpile;
+pub mod vehicle;
 
 pub use building::{BuildingArchetype, BuildingId, BuildingState, BuildingType};
 pub use construction::{
     apply_construction_work, calculate_team_contribution, calculate_worker_contribution,
     ContributionResult,
 };
-pub use production::{tick_production, ProductionResult};
+pub use crossing::{Crossing, CrossingError, CrossingId, CrossingKind, CrossingState};
+pub use livestock::{
+    LivestockArchetype, LivestockId, LivestockKind, LivestockSex, Pasture, PastureId,
+};
+pub use production::{tick_production, tick_production_over, ProductionResult};
 pub use recipe::{Recipe, RecipeCatalog, RecipeLoadError};
+pub use road::{RoadGrade, RoadNetwork};
+pub use site::{ConstructionSite, SiteError};
 pub use stockpile::Stockpile;
+pub use vehicle::{DraftSource, VehicleArchetype, VehicleId, VehicleKind, VehicleState};
@@ -4,11 +4,13 @@
 //! advances production progress based on worker count, and on completion:
 //! - Consumes input resources from the stockpile
 //! - Adds output resources to the stockpile
+//! - Hands back any crafted items, for workers' inventories or the stockpile
 //! - Resets progress for the next cycle
 
 use crate::city::building::BuildingArchetype;
 use crate::city::recipe::RecipeCatalog;
 use crate::city::stockpile::Stockpile;
+use crate::entity::inventory::Item;
 
 /// Result of a single production cycle completion
 #[derive(Debug, Clone, PartialEq)]
@@ -17,8 +19,10 @@ pub struct ProductionResult {
     pub building_idx: usize,
     /// ID of the recipe that completed
     pub recipe_id: String,
-    /// Number of cycles completed (always 1 per tick, but kept for future batch processing)
+    /// Number of cycles completed (more than one when a step covers many ticks)
     pub cycles_completed: u32,
+    /// Crafted items made, not yet handed to anyone
+    pub items: Vec<Item>,
 }
 
 /// Process production for all active buildings
@@ -30,6 +34,7 @@ pub struct ProductionResult {
 ///    - Checks if stockpile has required inputs (skips if not)
 ///    - Consumes inputs from stockpile
 ///    - Adds outputs to stockpile
+///    - Returns crafted items in the result (the caller decides who gets them)
 ///    - Resets progress for next cycle
 ///
 /// Returns a list of completed production cycles for tracking/events.
@@ -37,6 +42,17 @@ pub fn tick_production(
     buildings: &mut BuildingArchetype,
     recipes: &RecipeCatalog,
     stockpile: &mut Stockpile,
+) -> Vec<ProductionResult> {
+    tick_production_over(buildings, recipes, stockpile, 1.0)
+}
+
+/// Process `ticks` ticks of production at once; a building completes every
+/// cycle the ticks cover, for as long as its inputs last
+pub fn tick_production_over(
+    buildings: &mut BuildingArchetype,
+    recipes: &RecipeCatalog,
+    stockpile: &mut Stockpile,
+    ticks: f32,
 ) -> Vec<ProductionResult> {
     let mut results = Vec::new();
 
@@ -66,14 +82,22 @@ pub fn tick_production(
         let workers = buildings.production_workers[i];
         let rate = recipe.production_rate(workers);
         // Progress is rate / work_required (normalized to 0.0-1.0 range)
-        let progress = if recipe.work_required > 0 {
-            rate / recipe.work_required as f32
+        let mut progress = if recipe.work_required > 0 {
+            rate * ticks / recipe.work_required as f32
         } else {
-            1.0 // Instant completion if no work required
+            ticks // Instant completion, a cycle a tick, if no work required
         };
 
-        // Advance production and check for completion
-        if buildings.advance_production(i, progress) {
+        // Advance production, completing each cycle the progress reaches
+        let mut cycles_completed = 0;
+        let mut items = Vec::new();
+        while progress > 0.0 && stockpile.has_materials(&recipe.inputs) {
+            let needed = 1.0 - buildings.production_progress[i];
+            let advance = if progress >= needed { 1.0 } else { progress };
+            progress -= needed.min(progress);
+            if !buildings.advance_production(i, advance) {
+                break;
+            }
             // Production cycle complete!
 
             // Consume inputs from stockpile
@@ -83,11 +107,16 @@ pub fn tick_production(
             for (resource, amount) in &recipe.outputs {
                 stockpile.add(*resource, *amount);
             }
+            items.extend(recipe.items.iter().cloned());
+            cycles_completed += 1;
+        }
 
+        if cycles_completed > 0 {
             results.push(ProductionResult {
                 building_idx: i,
                 recipe_id,
-                cycles_completed: 1,
+                cycles_completed,
+                items,
             });
         }
     }
@@ -439,4 +468,47 @@ mod tests {
             buildings.production_progress[0]
         );
     }
+
+    #[test]
+    fn test_tick_production_over_completes_every_cycle_it_covers() {
+        let mut buildings = BuildingArchetype::new();
+        let id = BuildingId::new();
+        buildings.spawn(id, BuildingType::Farm, Vec2::new(0.0, 0.0), 0);
+        buildings.states[0] = BuildingState::Complete;
+        buildings.start_production(0, "farm_food".into());
+        buildings.production_workers[0] = 2;
+
+        let recipes = RecipeCatalog::with_defaults();
+        let mut stockpile = Stockpile::new();
+        stockpile.set_capacity(ResourceType::Food, 1000);
+
+        // A day's step: 1000 ticks of a 100-tick cycle
+        let results = tick_production_over(&mut buildings, &recipes, &mut stockpile, 1000.0);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].cycles_completed, 10);
+        assert_eq!(stockpile.get(ResourceType::Food), 50);
+    }
+
+    #[test]
+    fn test_tick_production_over_stops_when_inputs_run_out() {
+        let mut buildings = BuildingArchetype::new();
+        let id = BuildingId::new();
+        buildings.spawn(id, BuildingType::Workshop, Vec2::new(0.0, 0.0), 0);
+        buildings.states[0] = BuildingState::Complete;
+        buildings.start_production(0, "weave_cloth".into());
+        buildings.production_workers[0] = 1;
+
+        let recipes = RecipeCatalog::with_defaults();
+        let mut stockpile = Stockpile::new();
+        stockpile.set_capacity(ResourceType::Cloth, 1000);
+        stockpile.set_capacity(ResourceType::Wood, 1000);
+        // Wood for three cycles of the ten 400 ticks would cover
+        stockpile.add(ResourceType::Wood, 6);
+
+        let results = tick_production_over(&mut buildings, &recipes, &mut stockpile, 400.0);
+
+        assert_eq!(results[0].cycles_completed, 3);
+        assert_eq!(stockpile.get(ResourceType::Wood), 0);
+    }
 }
@@ -1,9 +1,10 @@
 //! Production recipes - define what buildings produce
 //!
-//! Recipes specify input resources, output resources, work required,
-//! and which building type can execute them.
+//! Recipes specify input resources, output resources (and any crafted
+//! items), work required, and which building type can execute them.
 
 use crate::city::building::BuildingType;
+use crate::entity::inventory::Item;
 use crate::simulation::resource_zone::ResourceType;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +21,9 @@ pub struct Recipe {
     pub inputs: Vec<(ResourceType, u32)>,
     /// Output resources produced
     pub outputs: Vec<(ResourceType, u32)>,
+    /// Crafted items produced, one of each per cycle
+    #[serde(default)]
+    pub items: Vec<Item>,
     /// Work units required to complete one production cycle
     pub work_required: u32,
     /// Workers needed for full speed
@@ -59,6 +63,19 @@ impl RecipeCatalog {
             building_type: BuildingType::Farm,
             inputs: vec![],
             outputs: vec![(ResourceType::Food, 5)],
+            items: vec![],
+            work_required: 100,
+            workers_needed: 2,
+        });
+
+        // Farm grows hay for livestock to eat through winter
+        catalog.add(Recipe {
+            id: "farm_fodder".into(),
+            name: "Grow Fodder".into(),
+            building_type: BuildingType::Farm,
+            inputs: vec![],
+            outputs: vec![(ResourceType::Fodder, 8)],
+            items: vec![],
             work_required: 100,
             workers_needed: 2,
         });
@@ -70,6 +87,7 @@ impl RecipeCatalog {
             building_type: BuildingType::Workshop,
             inputs: vec![(ResourceType::Ore, 3)],
             outputs: vec![(ResourceType::Iron, 1)],
+            items: vec![],
             work_required: 50,
             workers_needed: 1,
         });
@@ -81,10 +99,35 @@ impl RecipeCatalog {
             building_type: BuildingType::Workshop,
             inputs: vec![(ResourceType::Wood, 2)],
             outputs: vec![(ResourceType::Cloth, 1)],
+            items: vec![],
             work_required: 40,
             workers_needed: 1,
         });
 
+        // Workshop: iron + wood -> sword
+        catalog.add(Recipe {
+            id: "forge_sword".into(),
+            name: "Forge Sword".into(),
+            building_type: BuildingType::Workshop,
+            inputs: vec![(ResourceType::Iron, 2), (ResourceType::Wood, 1)],
+            outputs: vec![],
+            items: Item::named("sword").into_iter().collect(),
+            work_required: 80,
+            workers_needed: 1,
+        });
+
+        // Workshop: leather -> leather armor
+        catalog.add(Recipe {
+            id: "stitch_armor".into(),
+            name: "Stitch Leather Armor".into(),
+            building_type: BuildingType::Workshop,
+            inputs: vec![(ResourceType::Leather, 3)],
+            outputs: vec![],
+            items: Item::named("leather_armor").into_iter().collect(),
+            work_required: 60,
+            workers_needed: 1,
+        });
+
         catalog
     }
 
@@ -137,6 +180,7 @@ pub enum RecipeLoadError {
     ParseError(String),
     InvalidBuildingType(String),
     InvalidResourceType(String),
+    InvalidItem(String),
 }
 
 impl std::fmt::Display for RecipeLoadError {
@@ -146,6 +190,7 @@ impl std::fmt::Display for RecipeLoadError {
             RecipeLoadError::ParseError(e) => write!(f, "Parse error: {}", e),
             RecipeLoadError::InvalidBuildingType(e) => write!(f, "Invalid building type: {}", e),
             RecipeLoadError::InvalidResourceType(e) => write!(f, "Invalid resource type: {}", e),
+            RecipeLoadError::InvalidItem(e) => write!(f, "Invalid item: {}", e),
         }
     }
 }
@@ -166,7 +211,10 @@ struct TomlRecipe {
     building_type: String,
     #[serde(default)]
     inputs: Vec<TomlResourceAmount>,
+    #[serde(default)]
     outputs: Vec<TomlResourceAmount>,
+    #[serde(default)]
+    items: Vec<String>,
     work_required: u32,
     workers_needed: u32,
 }
@@ -187,6 +235,11 @@ impl TomlRecipe {
             "granary" => BuildingType::Granary,
             "wall" => BuildingType::Wall,
             "gate" => BuildingType::Gate,
+            "bathhouse" => BuildingType::Bathhouse,
+            "market_square" | "market" => BuildingType::MarketSquare,
+            "watchtower" => BuildingType::Watchtower,
+            "well" => BuildingType::Well,
+            "shrine" => BuildingType::Shrine,
             _ => return Err(RecipeLoadError::InvalidBuildingType(self.building_type)),
         };
 
@@ -202,12 +255,19 @@ impl TomlRecipe {
             .map(|ra| ra.into_resource_amount())
             .collect::<Result<Vec<_>, _>>()?;
 
+        let items = self
+            .items
+            .into_iter()
+            .map(|name| Item::named(&name).ok_or(RecipeLoadError::InvalidItem(name)))
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(Recipe {
             id: self.id,
             name: self.name,
             building_type,
             inputs,
             outputs,
+            items,
             work_required: self.work_required,
             workers_needed: self.workers_needed,
         })
@@ -216,15 +276,8 @@ impl TomlRecipe {
 
 impl TomlResourceAmount {
     fn into_resource_amount(self) -> Result<(ResourceType, u32), RecipeLoadError> {
-        let resource = match self.resource.to_lowercase().as_str() {
-            "wood" => ResourceType::Wood,
-            "stone" => ResourceType::Stone,
-            "ore" => ResourceType::Ore,
-            "iron" => ResourceType::Iron,
-            "cloth" => ResourceType::Cloth,
-            "food" => ResourceType::Food,
-            _ => return Err(RecipeLoadError::InvalidResourceType(self.resource)),
-        };
+        let resource = ResourceType::from_name(&self.resource)
+            .ok_or_else(|| RecipeLoadError::InvalidResourceType(self.resource.clone()))?;
         Ok((resource, self.amount))
     }
 }
@@ -241,6 +294,7 @@ mod tests {
             building_type: BuildingType::Farm,
             inputs: vec![],
             outputs: vec![(ResourceType::Food, 1)],
+            items: vec![],
             work_required: 100,
             workers_needed: 2,
         };
@@ -265,6 +319,7 @@ mod tests {
             building_type: BuildingType::Granary,
             inputs: vec![],
             outputs: vec![(ResourceType::Food, 1)],
+            items: vec![],
             work_required: 10,
             workers_needed: 0, // No workers needed
         };
@@ -299,11 +354,17 @@ mod tests {
         let catalog = RecipeCatalog::with_defaults();
 
         let farm_recipes: Vec<_> = catalog.for_building(BuildingType::Farm).collect();
-        assert_eq!(farm_recipes.len(), 1);
+        assert_eq!(farm_recipes.len(), 2);
+        assert!(
+            catalog.get("farm_fodder").is_some(),
+            "Should have farm_fodder recipe"
+        );
         assert_eq!(farm_recipes[0].id, "farm_food");
 
         let workshop_recipes: Vec<_> = catalog.for_building(BuildingType::Workshop).collect();
-        assert_eq!(workshop_recipes.len(), 2);
+        assert_eq!(workshop_recipes.len(), 4);
+        let sword = catalog.get("forge_sword").unwrap();
+        assert_eq!(sword.items, vec![Item::named("sword").unwrap()]);
 
         // House has no recipes in defaults
         let house_recipes: Vec<_> = catalog.for_building(BuildingType::House).collect();
@@ -327,6 +388,7 @@ mod tests {
             building_type: BuildingType::Granary,
             inputs: vec![(ResourceType::Food, 10)],
             outputs: vec![(ResourceType::Food, 5)], // Preservation - some loss
+            items: vec![],
             work_required: 20,
             workers_needed: 1,
         });
@@ -493,10 +555,16 @@ amount = 1
 
         // Verify recipe counts by building type
         let farm_recipes: Vec<_> = catalog.for_building(BuildingType::Farm).collect();
-        assert_eq!(farm_recipes.len(), 1);
+        assert_eq!(farm_recipes.len(), 2);
+        assert!(
+            catalog.get("farm_fodder").is_some(),
+            "Should have farm_fodder recipe"
+        );
 
         let workshop_recipes: Vec<_> = catalog.for_building(BuildingType::Workshop).collect();
-        assert_eq!(workshop_recipes.len(), 3);
+        assert_eq!(workshop_recipes.len(), 5);
+        let armor = catalog.get("stitch_armor").unwrap();
+        assert_eq!(armor.items, vec![Item::named("leather_armor").unwrap()]);
 
         let granary_recipes: Vec<_> = catalog.for_building(BuildingType::Granary).collect();
         assert_eq!(granary_recipes.len(), 1);
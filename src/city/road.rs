@@ -0,0 +1,396 @@
+//! Roads - built and worn paths on the local map
+//!
+//! Roads come from two places. Frequently travelled ground wears into a
+//! trail and then a dirt road on its own; unused paths fade back over time.
+//! Roads can also be planned and built cell by cell, which is the only way
+//! to get a stone road and never fades.
+//!
+//! Every grade speeds up walking. Dirt and stone roads also carry wheels
+//! through rough terrain that vehicles otherwise cannot cross.
+
+use ahash::AHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::city::stockpile::Stockpile;
+use crate::core::types::Vec2;
+use crate::simulation::resource_zone::ResourceType;
+
+/// Side length of one road cell in world units
+pub const ROAD_CELL_SIZE: f32 = 2.0;
+
+/// Traffic at which bare ground wears into a trail
+pub const TRAIL_TRAFFIC: f32 = 50.0;
+
+/// Traffic at which a trail wears into a dirt road
+pub const DIRT_ROAD_TRAFFIC: f32 = 200.0;
+
+/// Fraction of accumulated traffic that fades each day
+pub const TRAFFIC_DECAY_PER_DAY: f32 = 0.05;
+
+/// Cells of a grade a settlement needs before the campaign map counts it
+pub const SETTLEMENT_ROAD_CELLS: usize = 10;
+
+/// Quality of a road, from worn footpath to paved road
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RoadGrade {
+    /// Footpath worn into the ground
+    Trail,
+    /// Packed earth, wide enough for carts
+    DirtRoad,
+    /// Paved with stone, only by construction
+    StoneRoad,
+}
+
+impl RoadGrade {
+    /// Movement speed multiplier while on this grade
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            RoadGrade::Trail => 1.2,
+            RoadGrade::DirtRoad => 1.4,
+            RoadGrade::StoneRoad => 1.6,
+        }
+    }
+
+    /// Whether vehicles can use this grade to cross rough terrain
+    pub fn allows_wheels(&self) -> bool {
+        matches!(self, RoadGrade::DirtRoad | RoadGrade::StoneRoad)
+    }
+
+    /// Work needed to build one cell of this grade
+    pub fn work_required(&self) -> f32 {
+        match self {
+            RoadGrade::Trail => 10.0,
+            RoadGrade::DirtRoad => 25.0,
+            RoadGrade::StoneRoad => 60.0,
+        }
+    }
+
+    /// Materials consumed per cell when construction is planned
+    pub fn materials_per_cell(&self) -> Vec<(ResourceType, u32)> {
+        match self {
+            RoadGrade::Trail | RoadGrade::DirtRoad => vec![],
+            RoadGrade::StoneRoad => vec![(ResourceType::Stone, 2)],
+        }
+    }
+}
+
+/// Road state of a single cell
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RoadCell {
+    /// Accumulated foot traffic, fading daily
+    pub traffic: f32,
+    /// Grade worn in by traffic
+    pub worn: Option<RoadGrade>,
+    /// Grade built by construction
+    pub built: Option<RoadGrade>,
+}
+
+impl RoadCell {
+    /// Effective grade - the better of worn and built
+    pub fn grade(&self) -> Option<RoadGrade> {
+        self.worn.max(self.built)
+    }
+}
+
+/// A planned road cell awaiting construction
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RoadSite {
+    pub grade: RoadGrade,
+    /// Work applied so far (0.0 to work_required)
+    pub progress: f32,
+}
+
+/// All roads, worn paths, and planned road work on the local map
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoadNetwork {
+    #[serde(with = "crate::ecs::persistence::sorted_map")]
+    pub cells: AHashMap<(i32, i32), RoadCell>,
+    #[serde(with = "crate::ecs::persistence::sorted_map")]
+    pub sites: AHashMap<(i32, i32), RoadSite>,
+}
+
+impl RoadNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grid cell containing a position
+    pub fn cell_of(pos: Vec2) -> (i32, i32) {
+        (
+            (pos.x / ROAD_CELL_SIZE).floor() as i32,
+            (pos.y / ROAD_CELL_SIZE).floor() as i32,
+        )
+    }
+
+    /// World position at the centre of a cell
+    pub fn cell_center(cell: (i32, i32)) -> Vec2 {
+        Vec2::new(
+            (cell.0 as f32 + 0.5) * ROAD_CELL_SIZE,
+            (cell.1 as f32 + 0.5) * ROAD_CELL_SIZE,
+        )
+    }
+
+    /// Road grade at a position, if any
+    pub fn grade_at(&self, pos: Vec2) -> Option<RoadGrade> {
+        self.cells
+            .get(&Self::cell_of(pos))
+            .and_then(|cell| cell.grade())
+    }
+
+    /// Movement speed multiplier at a position (1.0 off-road)
+    pub fn speed_multiplier_at(&self, pos: Vec2) -> f32 {
+        self.grade_at(pos)
+            .map(|g| g.speed_multiplier())
+            .unwrap_or(1.0)
+    }
+
+    /// Whether a road at this position lets vehicles through rough terrain
+    pub fn allows_wheels_at(&self, pos: Vec2) -> bool {
+        self.grade_at(pos)
+            .map(|g| g.allows_wheels())
+            .unwrap_or(false)
+    }
+
+    /// Record one step of traffic, wearing the ground into a path
+    pub fn record_traffic(&mut self, pos: Vec2) {
+        let cell = self.cells.entry(Self::cell_of(pos)).or_default();
+        cell.traffic += 1.0;
+        if cell.traffic >= DIRT_ROAD_TRAFFIC {
+            cell.worn = Some(RoadGrade::DirtRoad);
+        } else if cell.traffic >= TRAIL_TRAFFIC && cell.worn.is_none() {
+            cell.worn = Some(RoadGrade::Trail);
+        }
+    }
+
+    /// Fade traffic for a day; unused worn paths revert
+    ///
+    /// Worn grades drop once traffic falls below half the threshold that
+    /// created them, so a path in steady use does not flicker.
+    pub fn decay_daily(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.traffic *= 1.0 - TRAFFIC_DECAY_PER_DAY;
+            if cell.worn == Some(RoadGrade::DirtRoad) && cell.traffic < DIRT_ROAD_TRAFFIC * 0.5 {
+                cell.worn = Some(RoadGrade::Trail);
+            }
+            if cell.worn == Some(RoadGrade::Trail) && cell.traffic < TRAIL_TRAFFIC * 0.5 {
+                cell.worn = None;
+            }
+        }
+        self.cells
+            .retain(|_, cell| cell.built.is_some() || cell.worn.is_some() || cell.traffic >= 1.0);
+    }
+
+    /// Plan a straight road between two points
+    ///
+    /// Materials for every new cell are consumed up front. Cells that already
+    /// have this grade or better (built) or are already planned are skipped.
+    /// Returns the number of cells planned, or 0 if materials are short.
+    pub fn plan_road(
+        &mut self,
+        from: Vec2,
+        to: Vec2,
+        grade: RoadGrade,
+        stockpile: &mut Stockpile,
+    ) -> usize {
+        let distance = from.distance(&to);
+        let steps = (distance / (ROAD_CELL_SIZE * 0.5)).ceil().max(1.0) as usize;
+
+        let mut new_cells: Vec<(i32, i32)> = Vec::new();
+        for s in 0..=steps {
+            let t = s as f32 / steps as f32;
+            let cell = Self::cell_of(from + (to - from) * t);
+            let already_built = self
+                .cells
+                .get(&cell)
+                .and_then(|c| c.built)
+                .map(|b| b >= grade)
+                .unwrap_or(false);
+            if already_built || self.sites.contains_key(&cell) || new_cells.contains(&cell) {
+                continue;
+            }
+            new_cells.push(cell);
+        }
+
+        let materials: Vec<(ResourceType, u32)> = grade
+            .materials_per_cell()
+            .into_iter()
+            .map(|(r, n)| (r, n * new_cells.len() as u32))
+            .collect();
+        if !stockpile.consume_materials(&materials) {
+            return 0;
+        }
+
+        for &cell in &new_cells {
+            self.sites.insert(
+                cell,
+                RoadSite {
+                    grade,
+                    progress: 0.0,
+                },
+            );
+        }
+        new_cells.len()
+    }
+
+    /// Nearest planned road cell whose centre is within `range` of a position
+    pub fn site_near(&self, pos: Vec2, range: f32) -> Option<(i32, i32)> {
+        self.sites
+            .keys()
+            .map(|&cell| (cell, Self::cell_center(cell).distance(&pos)))
+            .filter(|&(_, d)| d <= range)
+            .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+            .map(|(cell, _)| cell)
+    }
+
+    /// Apply construction work to a planned cell. Returns true when the cell is finished.
+    pub fn apply_work(&mut self, cell: (i32, i32), amount: f32) -> bool {
+        let Some(site) = self.sites.get_mut(&cell) else {
+            return true;
+        };
+        site.progress += amount;
+        if site.progress < site.grade.work_required() {
+            return false;
+        }
+
+        let grade = site.grade;
+        self.sites.remove(&cell);
+        let road = self.cells.entry(cell).or_default();
+        road.built = road.built.max(Some(grade));
+        true
+    }
+
+    /// Best grade the settlement's network offers to passing armies
+    ///
+    /// A grade counts once at least `SETTLEMENT_ROAD_CELLS` cells are that
+    /// grade or better.
+    pub fn settlement_grade(&self) -> Option<RoadGrade> {
+        [RoadGrade::StoneRoad, RoadGrade::DirtRoad, RoadGrade::Trail]
+            .into_iter()
+            .find(|&g| {
+                self.cells
+                    .values()
+                    .filter(|c| c.grade().map(|cg| cg >= g).unwrap_or(false))
+                    .count()
+                    >= SETTLEMENT_ROAD_CELLS
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traffic_wears_trail_then_dirt_road() {
+        let mut roads = RoadNetwork::new();
+        let pos = Vec2::new(5.0, 5.0);
+
+        for _ in 0..TRAIL_TRAFFIC as usize {
+            roads.record_traffic(pos);
+        }
+        assert_eq!(roads.grade_at(pos), Some(RoadGrade::Trail));
+        assert!(roads.speed_multiplier_at(pos) > 1.0);
+        assert!(!roads.allows_wheels_at(pos));
+
+        for _ in 0..DIRT_ROAD_TRAFFIC as usize {
+            roads.record_traffic(pos);
+        }
+        assert_eq!(roads.grade_at(pos), Some(RoadGrade::DirtRoad));
+        assert!(roads.allows_wheels_at(pos));
+        assert_eq!(roads.speed_multiplier_at(Vec2::new(50.0, 50.0)), 1.0);
+    }
+
+    #[test]
+    fn test_unused_paths_fade() {
+        let mut roads = RoadNetwork::new();
+        let pos = Vec2::new(1.0, 1.0);
+        for _ in 0..DIRT_ROAD_TRAFFIC as usize {
+            roads.record_traffic(pos);
+        }
+
+        for _ in 0..150 {
+            roads.decay_daily();
+        }
+        assert_eq!(roads.grade_at(pos), None);
+        assert!(roads.cells.is_empty());
+    }
+
+    #[test]
+    fn test_planned_road_is_built_and_never_fades() {
+        let mut roads = RoadNetwork::new();
+        let mut stockpile = Stockpile::new();
+        stockpile.add(ResourceType::Stone, 100);
+
+        let planned = roads.plan_road(
+            Vec2::new(0.0, 1.0),
+            Vec2::new(10.0, 1.0),
+            RoadGrade::StoneRoad,
+            &mut stockpile,
+        );
+        assert_eq!(planned, 6);
+        assert_eq!(stockpile.get(ResourceType::Stone), 100 - 12);
+
+        // Planning the same stretch again adds nothing
+        let again = roads.plan_road(
+            Vec2::new(0.0, 1.0),
+            Vec2::new(10.0, 1.0),
+            RoadGrade::StoneRoad,
+            &mut stockpile,
+        );
+        assert_eq!(again, 0);
+
+        let cell = roads.site_near(Vec2::new(0.0, 0.0), 3.0).unwrap();
+        assert!(!roads.apply_work(cell, 30.0));
+        assert!(roads.apply_work(cell, 30.0));
+        assert_eq!(roads.sites.len(), 5);
+
+        let center = RoadNetwork::cell_center(cell);
+        for _ in 0..100 {
+            roads.decay_daily();
+        }
+        assert_eq!(roads.grade_at(center), Some(RoadGrade::StoneRoad));
+    }
+
+    #[test]
+    fn test_stone_road_needs_stone() {
+        let mut roads = RoadNetwork::new();
+        let mut stockpile = Stockpile::new();
+
+        let planned = roads.plan_road(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(20.0, 0.0),
+            RoadGrade::StoneRoad,
+            &mut stockpile,
+        );
+        assert_eq!(planned, 0);
+        assert!(roads.sites.is_empty());
+
+        // Dirt roads cost only labor
+        let planned = roads.plan_road(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(20.0, 0.0),
+            RoadGrade::DirtRoad,
+            &mut stockpile,
+        );
+        assert_eq!(planned, 11);
+    }
+
+    #[test]
+    fn test_settlement_grade_needs_enough_cells() {
+        let mut roads = RoadNetwork::new();
+        let mut stockpile = Stockpile::new();
+        assert_eq!(roads.settlement_grade(), None);
+
+        roads.plan_road(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(40.0, 0.0),
+            RoadGrade::DirtRoad,
+            &mut stockpile,
+        );
+        let cells: Vec<_> = roads.sites.keys().copied().collect();
+        for cell in cells {
+            roads.apply_work(cell, 100.0);
+        }
+        assert_eq!(roads.settlement_grade(), Some(RoadGrade::DirtRoad));
+    }
+}
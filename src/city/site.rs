@@ -0,0 +1,245 @@
+//! Construction sites - buildings placed from blueprints
+//!
+//! Placing a blueprint marks out a site for a building under construction.
+//! The blueprint sets its material bill, work, and crew size. Nothing is taken
+//! from the stockpile up front: haulers carry materials to the site a load at
+//! a time, and builders start once the whole bill has arrived. Materials the
+//! stockpile does not track (nails, mortar) are left off the bill.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::blueprints::{
+    eval_expr_str, get_labor_cap, get_required_materials, BlueprintError, BlueprintInstance,
+    BlueprintRegistry, PlacedBy,
+};
+use crate::city::building::{BuildingId, BuildingType};
+use crate::city::stockpile::Stockpile;
+use crate::core::types::Vec2;
+use crate::simulation::resource_zone::ResourceType;
+
+/// Most of one material a hauler carries per trip
+pub const HAUL_LOAD: u32 = 20;
+
+/// Blueprints that can be placed as buildings
+pub const BUILDING_BLUEPRINTS: [&str; 5] =
+    ["wooden_house", "stone_wall", "watchtower", "well", "shrine"];
+
+/// The building blueprint a player's word for it names ("house", "stone wall")
+pub fn blueprint_named(target: &str) -> Option<&'static str> {
+    let target = target.trim().to_lowercase().replace(' ', "_");
+    if target.is_empty() {
+        return None;
+    }
+    BUILDING_BLUEPRINTS
+        .into_iter()
+        .find(|name| name.contains(target.as_str()))
+}
+
+/// Why a blueprint could not be placed
+#[derive(Debug, Error)]
+pub enum SiteError {
+    /// The blueprint is not loaded
+    #[error("Unknown blueprint: {0}")]
+    UnknownBlueprint(String),
+    /// The blueprint is not a building (a tree, a bridge)
+    #[error("{0} is not a building")]
+    NotABuilding(String),
+    /// No era the settlement has reached unlocks the blueprint
+    #[error("{0} has not been unlocked yet")]
+    Locked(String),
+    /// The blueprint could not be evaluated
+    #[error("Blueprint error: {0}")]
+    Blueprint(#[from] BlueprintError),
+}
+
+/// A building going up from a placed blueprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstructionSite {
+    /// The building under construction here
+    pub building: BuildingId,
+    pub building_type: BuildingType,
+    pub position: Vec2,
+    /// The evaluated blueprint; its progress and stage follow the work done
+    pub instance: BlueprintInstance,
+    /// Construction stages (id, progress threshold), in order
+    pub stages: Vec<(String, f32)>,
+    /// Materials the blueprint calls for
+    pub required: Vec<(ResourceType, u32)>,
+    /// Amount of each required material hauled to the site so far
+    pub delivered: Vec<u32>,
+    /// Work applied so far (0.0 to work_required)
+    pub progress: f32,
+    pub work_required: f32,
+    /// Most haulers or builders that can work at once
+    pub labor_cap: u32,
+}
+
+impl ConstructionSite {
+    /// Plan a building from a blueprint at a position, with the blueprint's
+    /// default dimensions
+    pub fn plan(
+        building: BuildingId,
+        name: &str,
+        position: Vec2,
+        registry: &BlueprintRegistry,
+        current_tick: u64,
+    ) -> Result<Self, SiteError> {
+        let building_type = BuildingType::for_blueprint(name)
+            .ok_or_else(|| SiteError::NotABuilding(name.to_string()))?;
+        let blueprint_id = registry
+            .id_by_name(name)
+            .ok_or_else(|| SiteError::UnknownBlueprint(name.to_string()))?;
+        let blueprint = registry
+            .get(blueprint_id)
+            .ok_or_else(|| SiteError::UnknownBlueprint(name.to_string()))?;
+
+        let instance = registry.instantiate(
+            blueprint_id,
+            Default::default(),
+            glam::Vec2::new(position.x, position.y),
+            0.0,
+            PlacedBy::Gameplay { tick: current_tick },
+            None,
+        )?;
+
+        // Sorted, so haulers fetch materials in the same order every run
+        let mut required: Vec<(ResourceType, u32)> =
+            get_required_materials(blueprint, &instance.parameters)
+                .into_iter()
+                .filter_map(|(name, n)| ResourceType::from_name(&name).map(|r| (r, n)))
+                .filter(|&(_, n)| n > 0)
+                .collect();
+        required.sort_unstable();
+
+        let (work_required, stages) = match &blueprint.construction {
+            Some(c) => (
+                eval_expr_str(&c.base_time, &instance.parameters)?,
+                c.stages
+                    .iter()
+                    .map(|stage| (stage.id.clone(), stage.progress_threshold))
+                    .collect(),
+            ),
+            None => (0.0, Vec::new()),
+        };
+
+        Ok(Self {
+            building,
+            building_type,
+            position,
+            delivered: vec![0; required.len()],
+            required,
+            stages,
+            progress: 0.0,
+            work_required,
+            labor_cap: get_labor_cap(blueprint, &instance.parameters).max(1),
+            instance,
+        })
+    }
+
+    /// Materials still to be hauled, and how much of each
+    pub fn missing(&self) -> impl Iterator<Item = (ResourceType, u32)> + '_ {
+        self.required
+            .iter()
+            .zip(&self.delivered)
+            .map(|(&(resource, n), &delivered)| (resource, n.saturating_sub(delivered)))
+            .filter(|&(_, n)| n > 0)
+    }
+
+    /// Whether the whole material bill has arrived
+    pub fn materials_ready(&self) -> bool {
+        self.missing().next().is_none()
+    }
+
+    /// Whether the stockpile holds any material still to be hauled
+    pub fn can_haul_from(&self, stockpile: &Stockpile) -> bool {
+        self.missing()
+            .any(|(resource, _)| stockpile.get(resource) > 0)
+    }
+
+    /// Haul one load of a missing material from the stockpile, returning
+    /// the material and how much arrived
+    pub fn haul_from(&mut self, stockpile: &mut Stockpile) -> Option<(ResourceType, u32)> {
+        let (resource, needed) = self
+            .missing()
+            .find(|&(resource, _)| stockpile.get(resource) > 0)?;
+        let m = self.required.iter().position(|&(r, _)| r == resource)?;
+        let hauled = stockpile.remove(resource, needed.min(HAUL_LOAD));
+        self.delivered[m] += hauled;
+        Some((resource, hauled))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.materials_ready() && self.progress >= self.work_required
+    }
+
+    /// Apply construction work. Returns true when the building is finished.
+    ///
+    /// Work does nothing until the materials have arrived.
+    pub fn apply_work(&mut self, amount: f32) -> bool {
+        if !self.materials_ready() {
+            return false;
+        }
+        self.progress = (self.progress + amount).min(self.work_required);
+        let fraction = if self.work_required > 0.0 {
+            self.progress / self.work_required
+        } else {
+            1.0
+        };
+        self.instance.construction_progress = fraction;
+        self.instance.construction_stage = self
+            .stages
+            .iter()
+            .rev()
+            .find(|(_, threshold)| fraction >= *threshold)
+            .map(|(id, _)| id.clone());
+        self.is_complete()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_site_hauls_then_builds() {
+        let mut registry = BlueprintRegistry::new();
+        registry
+            .load_directory(Path::new("data/blueprints"))
+            .unwrap();
+        let mut site =
+            ConstructionSite::plan(BuildingId(1), "well", Vec2::new(5.0, 5.0), &registry, 0)
+                .unwrap();
+
+        // diameter 1.5, depth 10: 75 stone and 300 work
+        assert_eq!(site.required, vec![(ResourceType::Stone, 75)]);
+        assert_eq!(site.work_required, 300.0);
+        assert_eq!(site.building_type, BuildingType::Well);
+        assert!(!site.apply_work(1000.0));
+
+        let mut stockpile = Stockpile::new();
+        assert!(!site.can_haul_from(&stockpile));
+        stockpile.add(ResourceType::Stone, 100);
+        for load in [20, 20, 20, 15] {
+            assert_eq!(
+                site.haul_from(&mut stockpile),
+                Some((ResourceType::Stone, load))
+            );
+        }
+        assert!(site.materials_ready());
+        assert_eq!(stockpile.get(ResourceType::Stone), 25);
+        assert_eq!(site.haul_from(&mut stockpile), None);
+
+        assert!(!site.apply_work(150.0));
+        assert_eq!(site.instance.construction_progress, 0.5);
+        assert!(site.apply_work(150.0));
+
+        assert_eq!(blueprint_named("Stone Wall"), Some("stone_wall"));
+        assert_eq!(blueprint_named("house"), Some("wooden_house"));
+        assert!(matches!(
+            ConstructionSite::plan(BuildingId(2), "bridge", Vec2::new(0.0, 0.0), &registry, 0),
+            Err(SiteError::NotABuilding(_))
+        ));
+    }
+}
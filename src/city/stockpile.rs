@@ -1,14 +1,21 @@
 //! Stockpile - settlement-level resource storage
 
+use crate::entity::inventory::Item;
 use crate::simulation::resource_zone::ResourceType;
 use ahash::AHashMap;
 use serde::{Deserialize, Serialize};
 
+/// Capacity of a resource the stockpile has not been set up for
+const DEFAULT_CAPACITY: u32 = 100;
+
 /// A stockpile holding resources for a settlement
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Stockpile {
     /// Resources stored: type -> (current, capacity)
     resources: AHashMap<ResourceType, (u32, u32)>,
+    /// Crafted items waiting to be picked up, oldest first
+    #[serde(default)]
+    items: Vec<Item>,
 }
 
 impl Stockpile {
@@ -35,9 +42,20 @@ impl Stockpile {
             .unwrap_or(0)
     }
 
+    /// Room left for a resource
+    pub fn space(&self, resource: ResourceType) -> u32 {
+        self.resources
+            .get(&resource)
+            .map(|(current, cap)| cap.saturating_sub(*current))
+            .unwrap_or(DEFAULT_CAPACITY)
+    }
+
     /// Try to add resources, returns amount actually added
     pub fn add(&mut self, resource: ResourceType, amount: u32) -> u32 {
-        let entry = self.resources.entry(resource).or_insert((0, 100)); // Default capacity 100
+        let entry = self
+            .resources
+            .entry(resource)
+            .or_insert((0, DEFAULT_CAPACITY));
         let space = entry.1.saturating_sub(entry.0);
         let added = amount.min(space);
         entry.0 += added;
@@ -55,6 +73,21 @@ impl Stockpile {
         }
     }
 
+    /// Store a crafted item
+    pub fn store_item(&mut self, item: Item) {
+        self.items.push(item);
+    }
+
+    /// Take out the oldest stored item
+    pub fn take_item(&mut self) -> Option<Item> {
+        (!self.items.is_empty()).then(|| self.items.remove(0))
+    }
+
+    /// Crafted items in store
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
     /// Check if stockpile has enough of all required materials
     pub fn has_materials(&self, requirements: &[(ResourceType, u32)]) -> bool {
         requirements
@@ -0,0 +1,564 @@
+//! Vehicles - carts and wagons for bulk hauling
+//!
+//! Vehicles multiply how much a hauler can move in one trip, but only on
+//! clear ground: blocked cells stop them outright and rough terrain (forest
+//! and rocky resource zones) is impassable to wheels unless a road runs
+//! through it. Roads also speed vehicles up. Every unit travelled
+//! wears the vehicle down; a worn-out vehicle breaks and must be repaired.
+//!
+//! Vehicles are constructed like buildings (materials up front, then work)
+//! and are pulled either by an entity or by a draft animal from the herd.
+
+use crate::city::livestock::{LivestockId, LivestockKind};
+use crate::city::road::RoadNetwork;
+use crate::city::stockpile::Stockpile;
+use crate::core::types::{EntityId, Vec2};
+use crate::simulation::resource_zone::{ResourceType, ResourceZone};
+use crate::world::BlockedCells;
+use serde::{Deserialize, Serialize};
+
+/// Resource units one entity can carry by hand
+pub const HAND_CARRY_CAPACITY: u32 = 10;
+
+/// Condition below which a vehicle breaks down
+pub const BREAKDOWN_CONDITION: f32 = 0.1;
+
+/// Kind of vehicle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VehicleKind {
+    /// Light two-wheeled cart pushed by one entity
+    Handcart,
+    /// Two-wheeled cart, pulled by an entity or an animal
+    Cart,
+    /// Four-wheeled wagon, needs a draft animal
+    Wagon,
+}
+
+impl VehicleKind {
+    /// Cargo multiplier over hand carrying at full draft power
+    pub fn capacity_multiplier(&self) -> f32 {
+        match self {
+            VehicleKind::Handcart => 3.0,
+            VehicleKind::Cart => 6.0,
+            VehicleKind::Wagon => 15.0,
+        }
+    }
+
+    /// Draft power needed to move a full load (entity = 1.0)
+    pub fn draft_required(&self) -> f32 {
+        match self {
+            VehicleKind::Handcart => 1.0,
+            VehicleKind::Cart => 2.0,
+            VehicleKind::Wagon => 4.0,
+        }
+    }
+
+    /// Whether an entity alone can pull this vehicle
+    pub fn entity_can_pull(&self) -> bool {
+        !matches!(self, VehicleKind::Wagon)
+    }
+
+    /// Work units to build
+    pub fn work_required(&self) -> f32 {
+        match self {
+            VehicleKind::Handcart => 30.0,
+            VehicleKind::Cart => 60.0,
+            VehicleKind::Wagon => 120.0,
+        }
+    }
+
+    /// Materials consumed when construction starts
+    pub fn required_materials(&self) -> Vec<(ResourceType, u32)> {
+        match self {
+            VehicleKind::Handcart => vec![(ResourceType::Wood, 10)],
+            VehicleKind::Cart => vec![(ResourceType::Wood, 20), (ResourceType::Iron, 2)],
+            VehicleKind::Wagon => vec![
+                (ResourceType::Wood, 40),
+                (ResourceType::Iron, 6),
+                (ResourceType::Leather, 4),
+            ],
+        }
+    }
+
+    /// Condition lost per unit of distance travelled
+    pub fn wear_per_distance(&self) -> f32 {
+        match self {
+            VehicleKind::Handcart => 0.0010,
+            VehicleKind::Cart => 0.0007,
+            VehicleKind::Wagon => 0.0005,
+        }
+    }
+}
+
+/// What is pulling a vehicle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DraftSource {
+    Entity(EntityId),
+    Livestock(LivestockId, LivestockKind),
+}
+
+impl DraftSource {
+    pub fn power(&self) -> f32 {
+        match self {
+            DraftSource::Entity(_) => 1.0,
+            DraftSource::Livestock(_, kind) => kind.draft_power(),
+        }
+    }
+}
+
+/// Unique vehicle identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VehicleId(pub u32);
+
+/// Lifecycle state of a vehicle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VehicleState {
+    UnderConstruction,
+    Ready,
+    Broken,
+}
+
+/// Outcome of moving a vehicle one step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VehicleMove {
+    Moved,
+    Arrived,
+    /// Next step is blocked or rough terrain
+    Obstructed,
+    /// Vehicle is not ready or has no draft
+    Immobile,
+}
+
+/// Whether wheels can roll over a position
+///
+/// Blocked cells are impassable; so is the interior of forest (wood) and
+/// rocky (stone, ore) resource zones, unless a dirt or stone road runs through.
+pub fn is_clear_terrain(
+    pos: Vec2,
+    blocked: &BlockedCells,
+    zones: &[ResourceZone],
+    roads: &RoadNetwork,
+) -> bool {
+    if blocked.is_position_blocked(glam::Vec2::new(pos.x, pos.y)) {
+        return false;
+    }
+    if roads.allows_wheels_at(pos) {
+        return true;
+    }
+    !zones.iter().any(|z| {
+        matches!(
+            z.resource_type,
+            ResourceType::Wood | ResourceType::Stone | ResourceType::Ore
+        ) && z.contains(pos)
+    })
+}
+
+/// Movement cost multiplier of passable ground under a vehicle (e.g. ramps)
+fn terrain_cost(pos: Vec2, blocked: &BlockedCells) -> f32 {
+    let cost = blocked.position_movement_cost(glam::Vec2::new(pos.x, pos.y));
+    if cost.is_finite() {
+        cost.max(1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Structure of Arrays for vehicles
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VehicleArchetype {
+    pub ids: Vec<VehicleId>,
+    pub kinds: Vec<VehicleKind>,
+    pub states: Vec<VehicleState>,
+    pub positions: Vec<Vec2>,
+    /// Work applied so far (0.0 to work_required)
+    pub construction_progress: Vec<f32>,
+    /// 0.0 = wrecked, 1.0 = new
+    pub condition: Vec<f32>,
+    pub drafts: Vec<Option<DraftSource>>,
+    pub cargo: Vec<Vec<(ResourceType, u32)>>,
+    next_id: u32,
+}
+
+impl VehicleArchetype {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn index_of(&self, id: VehicleId) -> Option<usize> {
+        self.ids.iter().position(|&v| v == id)
+    }
+
+    /// Start building a vehicle, consuming materials from the stockpile
+    ///
+    /// Returns None if the stockpile lacks materials.
+    pub fn commission(
+        &mut self,
+        kind: VehicleKind,
+        position: Vec2,
+        stockpile: &mut Stockpile,
+    ) -> Option<VehicleId> {
+        if !stockpile.consume_materials(&kind.required_materials()) {
+            return None;
+        }
+        let id = VehicleId(self.next_id);
+        self.next_id += 1;
+        self.ids.push(id);
+        self.kinds.push(kind);
+        self.states.push(VehicleState::UnderConstruction);
+        self.positions.push(position);
+        self.construction_progress.push(0.0);
+        self.condition.push(1.0);
+        self.drafts.push(None);
+        self.cargo.push(Vec::new());
+        Some(id)
+    }
+
+    /// Nearest vehicle under construction within `range` of a position
+    pub fn site_near(&self, pos: Vec2, range: f32) -> Option<usize> {
+        self.nearest_where(pos, range, |s| s == VehicleState::UnderConstruction)
+    }
+
+    /// Nearest ready or broken vehicle within `range` that is below full condition
+    pub fn worn_near(&self, pos: Vec2, range: f32) -> Option<usize> {
+        self.nearest_where(pos, range, |s| s != VehicleState::UnderConstruction)
+            .filter(|&i| self.condition[i] < 1.0)
+    }
+
+    fn nearest_where(
+        &self,
+        pos: Vec2,
+        range: f32,
+        pred: impl Fn(VehicleState) -> bool,
+    ) -> Option<usize> {
+        (0..self.count())
+            .filter(|&i| pred(self.states[i]))
+            .map(|i| (i, self.positions[i].distance(&pos)))
+            .filter(|(_, d)| *d <= range)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    /// Apply construction work, returns true when the vehicle is finished
+    pub fn apply_work(&mut self, idx: usize, work: f32) -> bool {
+        if self.states[idx] != VehicleState::UnderConstruction {
+            return true;
+        }
+        self.construction_progress[idx] += work;
+        if self.construction_progress[idx] >= self.kinds[idx].work_required() {
+            self.states[idx] = VehicleState::Ready;
+            return true;
+        }
+        false
+    }
+
+    /// Restore condition, returns true when fully repaired
+    pub fn repair(&mut self, idx: usize, amount: f32) -> bool {
+        self.condition[idx] = (self.condition[idx] + amount).min(1.0);
+        if self.states[idx] == VehicleState::Broken && self.condition[idx] > BREAKDOWN_CONDITION {
+            self.states[idx] = VehicleState::Ready;
+        }
+        self.condition[idx] >= 1.0
+    }
+
+    /// Hitch a draft source, returns false if it cannot pull this vehicle
+    pub fn hitch(&mut self, idx: usize, draft: DraftSource) -> bool {
+        let kind = self.kinds[idx];
+        let allowed = match draft {
+            DraftSource::Entity(_) => kind.entity_can_pull(),
+            DraftSource::Livestock(_, animal) => animal.draft_power() > 0.0,
+        };
+        if allowed {
+            self.drafts[idx] = Some(draft);
+        }
+        allowed
+    }
+
+    pub fn unhitch(&mut self, idx: usize) {
+        self.drafts[idx] = None;
+    }
+
+    /// Cargo capacity with the current draft
+    ///
+    /// Underpowered drafts can still move the vehicle but only with a partial load.
+    pub fn capacity(&self, idx: usize) -> u32 {
+        let Some(draft) = self.drafts[idx] else {
+            return 0;
+        };
+        let kind = self.kinds[idx];
+        let power_ratio = (draft.power() / kind.draft_required()).min(1.0);
+        (HAND_CARRY_CAPACITY as f32 * kind.capacity_multiplier() * power_ratio) as u32
+    }
+
+    pub fn cargo_total(&self, idx: usize) -> u32 {
+        self.cargo[idx].iter().map(|(_, a)| *a).sum()
+    }
+
+    /// Load cargo, returns amount actually loaded
+    pub fn load(&mut self, idx: usize, resource: ResourceType, amount: u32) -> u32 {
+        let space = self.capacity(idx).saturating_sub(self.cargo_total(idx));
+        let loaded = amount.min(space);
+        if loaded == 0 {
+            return 0;
+        }
+        if let Some(entry) = self.cargo[idx].iter_mut().find(|(r, _)| *r == resource) {
+            entry.1 += loaded;
+        } else {
+            self.cargo[idx].push((resource, loaded));
+        }
+        loaded
+    }
+
+    /// Unload all cargo into a stockpile, returns total units delivered
+    pub fn unload_into(&mut self, idx: usize, stockpile: &mut Stockpile) -> u32 {
+        let mut delivered = 0;
+        let mut leftover = Vec::new();
+        for (resource, amount) in self.cargo[idx].drain(..) {
+            let added = stockpile.add(resource, amount);
+            delivered += added;
+            if added < amount {
+                leftover.push((resource, amount - added));
+            }
+        }
+        self.cargo[idx] = leftover;
+        delivered
+    }
+
+    /// Move one step toward a target over clear terrain
+    ///
+    /// Speed scales with draft power over draft required; heavier loads and
+    /// worn vehicles move slower. Travel wears the vehicle and may break it.
+    pub fn step_toward(
+        &mut self,
+        idx: usize,
+        target: Vec2,
+        base_speed: f32,
+        blocked: &BlockedCells,
+        zones: &[ResourceZone],
+        roads: &RoadNetwork,
+    ) -> VehicleMove {
+        if self.states[idx] != VehicleState::Ready {
+            return VehicleMove::Immobile;
+        }
+        let Some(draft) = self.drafts[idx] else {
+            return VehicleMove::Immobile;
+        };
+
+        let kind = self.kinds[idx];
+        let current = self.positions[idx];
+        let distance = current.distance(&target);
+        if distance < 0.01 {
+            return VehicleMove::Arrived;
+        }
+
+        let capacity = self.capacity(idx).max(1) as f32;
+        let load_ratio = self.cargo_total(idx) as f32 / capacity;
+        let power_ratio = (draft.power() / kind.draft_required()).min(1.5);
+        let speed = base_speed
+            * power_ratio
+            * (1.0 - 0.3 * load_ratio)
+            * (0.5 + 0.5 * self.condition[idx])
+            * roads.speed_multiplier_at(current)
+            / terrain_cost(current, blocked);
+        let step = speed.min(distance);
+        let next = current + (target - current).normalize() * step;
+
+        if !is_clear_terrain(next, blocked, zones, roads) {
+            return VehicleMove::Obstructed;
+        }
+
+        self.positions[idx] = next;
+        self.condition[idx] -= step * kind.wear_per_distance() * (1.0 + load_ratio);
+        if self.condition[idx] <= BREAKDOWN_CONDITION {
+            self.condition[idx] = self.condition[idx].max(0.0);
+            self.states[idx] = VehicleState::Broken;
+        }
+
+        if step >= distance {
+            VehicleMove::Arrived
+        } else {
+            VehicleMove::Moved
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::road::RoadGrade;
+
+    fn stocked() -> Stockpile {
+        let mut stockpile = Stockpile::new();
+        stockpile.add(ResourceType::Wood, 100);
+        stockpile.add(ResourceType::Iron, 20);
+        stockpile.add(ResourceType::Leather, 10);
+        stockpile
+    }
+
+    fn ready_cart(vehicles: &mut VehicleArchetype, stockpile: &mut Stockpile) -> usize {
+        let id = vehicles
+            .commission(VehicleKind::Cart, Vec2::new(0.0, 0.0), stockpile)
+            .unwrap();
+        let idx = vehicles.index_of(id).unwrap();
+        vehicles.apply_work(idx, 100.0);
+        idx
+    }
+
+    #[test]
+    fn test_commission_consumes_materials() {
+        let mut vehicles = VehicleArchetype::new();
+        let mut stockpile = stocked();
+
+        let id = vehicles.commission(VehicleKind::Cart, Vec2::new(0.0, 0.0), &mut stockpile);
+        assert!(id.is_some());
+        assert_eq!(stockpile.get(ResourceType::Wood), 80);
+        assert_eq!(stockpile.get(ResourceType::Iron), 18);
+        assert_eq!(vehicles.states[0], VehicleState::UnderConstruction);
+
+        let mut empty = Stockpile::new();
+        assert!(vehicles
+            .commission(VehicleKind::Wagon, Vec2::new(0.0, 0.0), &mut empty)
+            .is_none());
+    }
+
+    #[test]
+    fn test_construction_completes() {
+        let mut vehicles = VehicleArchetype::new();
+        let mut stockpile = stocked();
+        vehicles.commission(VehicleKind::Handcart, Vec2::new(0.0, 0.0), &mut stockpile);
+
+        assert!(!vehicles.apply_work(0, 20.0));
+        assert!(vehicles.apply_work(0, 10.0));
+        assert_eq!(vehicles.states[0], VehicleState::Ready);
+    }
+
+    #[test]
+    fn test_capacity_multiplies_hauling() {
+        let mut vehicles = VehicleArchetype::new();
+        let mut stockpile = stocked();
+        let idx = ready_cart(&mut vehicles, &mut stockpile);
+
+        assert_eq!(vehicles.capacity(idx), 0, "Unhitched carts carry nothing");
+
+        // One entity only half-powers a cart
+        vehicles.hitch(idx, DraftSource::Entity(EntityId::new()));
+        assert_eq!(vehicles.capacity(idx), 30);
+
+        // An ox pulls a full load
+        vehicles.hitch(
+            idx,
+            DraftSource::Livestock(LivestockId(0), LivestockKind::Cattle),
+        );
+        assert_eq!(vehicles.capacity(idx), 60);
+        assert!(vehicles.capacity(idx) > HAND_CARRY_CAPACITY);
+    }
+
+    #[test]
+    fn test_wagon_needs_animal() {
+        let mut vehicles = VehicleArchetype::new();
+        let mut stockpile = stocked();
+        vehicles.commission(VehicleKind::Wagon, Vec2::new(0.0, 0.0), &mut stockpile);
+
+        assert!(!vehicles.hitch(0, DraftSource::Entity(EntityId::new())));
+        assert!(!vehicles.hitch(
+            0,
+            DraftSource::Livestock(LivestockId(0), LivestockKind::Sheep)
+        ));
+        assert!(vehicles.hitch(
+            0,
+            DraftSource::Livestock(LivestockId(0), LivestockKind::Cattle)
+        ));
+    }
+
+    #[test]
+    fn test_load_and_unload() {
+        let mut vehicles = VehicleArchetype::new();
+        let mut stockpile = stocked();
+        let idx = ready_cart(&mut vehicles, &mut stockpile);
+        vehicles.hitch(idx, DraftSource::Entity(EntityId::new()));
+
+        assert_eq!(vehicles.load(idx, ResourceType::Stone, 50), 30);
+        assert_eq!(vehicles.load(idx, ResourceType::Stone, 5), 0);
+
+        let mut depot = Stockpile::new();
+        assert_eq!(vehicles.unload_into(idx, &mut depot), 30);
+        assert_eq!(depot.get(ResourceType::Stone), 30);
+        assert_eq!(vehicles.cargo_total(idx), 0);
+    }
+
+    #[test]
+    fn test_rough_terrain_obstructs() {
+        let mut vehicles = VehicleArchetype::new();
+        let mut stockpile = stocked();
+        let idx = ready_cart(&mut vehicles, &mut stockpile);
+        vehicles.hitch(idx, DraftSource::Entity(EntityId::new()));
+
+        let blocked = BlockedCells::new();
+        let forest = vec![ResourceZone::new(
+            Vec2::new(5.0, 0.0),
+            ResourceType::Wood,
+            3.0,
+        )];
+
+        let mut roads = RoadNetwork::new();
+
+        let mut result = VehicleMove::Moved;
+        for _ in 0..10 {
+            result =
+                vehicles.step_toward(idx, Vec2::new(10.0, 0.0), 1.0, &blocked, &forest, &roads);
+            if result != VehicleMove::Moved {
+                break;
+            }
+        }
+        assert_eq!(result, VehicleMove::Obstructed);
+        assert!(vehicles.positions[idx].x < 2.0 + 0.01);
+
+        // A dirt road cut through the forest lets the cart pass
+        roads.plan_road(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            RoadGrade::DirtRoad,
+            &mut stockpile,
+        );
+        let cells: Vec<_> = roads.sites.keys().copied().collect();
+        for cell in cells {
+            roads.apply_work(cell, 100.0);
+        }
+        for _ in 0..20 {
+            result =
+                vehicles.step_toward(idx, Vec2::new(10.0, 0.0), 1.0, &blocked, &forest, &roads);
+            if result != VehicleMove::Moved {
+                break;
+            }
+        }
+        assert_eq!(result, VehicleMove::Arrived);
+    }
+
+    #[test]
+    fn test_travel_wears_and_breaks() {
+        let mut vehicles = VehicleArchetype::new();
+        let mut stockpile = stocked();
+        let idx = ready_cart(&mut vehicles, &mut stockpile);
+        vehicles.hitch(
+            idx,
+            DraftSource::Livestock(LivestockId(0), LivestockKind::Cattle),
+        );
+        let blocked = BlockedCells::new();
+        let roads = RoadNetwork::new();
+
+        vehicles.condition[idx] = 0.12;
+        vehicles.step_toward(idx, Vec2::new(100.0, 0.0), 50.0, &blocked, &[], &roads);
+        assert_eq!(vehicles.states[idx], VehicleState::Broken);
+        assert_eq!(
+            vehicles.step_toward(idx, Vec2::new(100.0, 0.0), 1.0, &blocked, &[], &roads),
+            VehicleMove::Immobile
+        );
+
+        // Maintenance puts it back in service
+        assert!(vehicles.repair(idx, 1.0));
+        assert_eq!(vehicles.states[idx], VehicleState::Ready);
+    }
+}
@@ -1,10 +1,14 @@
 //! Armor properties for categorical combat resolution
 //!
-//! Armor has exactly three properties: Rigidity, Padding, Coverage.
+//! Body armor has three properties: Rigidity, Padding, Coverage.
 //! These determine outcomes via lookup tables against weapon properties.
+//! A helmet stands in for the body armor on the head; a shield blocks
+//! whichever zones the wearer's stance puts it in front of.
 
 use serde::{Deserialize, Serialize};
 
+use crate::combat::{BodyZone, CombatStance};
+
 /// Material hardness - determines if edge can penetrate
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Rigidity {
@@ -40,12 +44,80 @@ pub enum Coverage {
     Full,
 }
 
+/// What protects the head
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Helmet {
+    /// Bare head: body armor never covers it
+    #[default]
+    None,
+    /// Open-faced steel cap: turns edges, rings under heavy blows
+    Cap,
+    /// Closed helm over padding: turns edges and soaks blows
+    Great,
+}
+
+impl Helmet {
+    /// Protection at the head
+    pub fn protection(&self) -> (Rigidity, Padding) {
+        match self {
+            Helmet::None => (Rigidity::Cloth, Padding::None),
+            Helmet::Cap => (Rigidity::Plate, Padding::None),
+            Helmet::Great => (Rigidity::Plate, Padding::Heavy),
+        }
+    }
+}
+
+/// Shield carried on the off (left) arm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Shield {
+    #[default]
+    None,
+    /// Small fist shield, only worth anything on guard
+    Buckler,
+    /// Arm-length round shield
+    Round,
+    /// Shoulder-to-shin shield
+    Tower,
+}
+
+impl Shield {
+    /// Zones this shield stands in front of in `stance`
+    ///
+    /// On guard a shield is raised high; pressing, it swings wide with the
+    /// blow. Recovering or broken, it is out of position.
+    pub fn covered_zones(&self, stance: CombatStance) -> &'static [BodyZone] {
+        use BodyZone::*;
+        use CombatStance::*;
+
+        match (self, stance) {
+            (Shield::None, _) | (_, Recovering) | (_, Broken) => &[],
+            (Shield::Buckler, Defensive) => &[Torso, ArmLeft, HandLeft],
+            (Shield::Buckler, _) => &[HandLeft],
+            (Shield::Round, Defensive) => &[Head, Torso, ArmLeft, HandLeft],
+            (Shield::Round, Neutral) => &[Torso, ArmLeft, HandLeft],
+            (Shield::Round, Pressing) => &[ArmLeft, HandLeft],
+            (Shield::Tower, Defensive) => &[Head, Neck, Torso, ArmLeft, HandLeft, LegLeft],
+            (Shield::Tower, Neutral) => &[Torso, ArmLeft, HandLeft, LegLeft],
+            (Shield::Tower, Pressing) => &[Torso, ArmLeft, HandLeft],
+        }
+    }
+
+    /// Does this shield stand in front of `zone` in `stance`?
+    pub fn covers(&self, zone: BodyZone, stance: CombatStance) -> bool {
+        self.covered_zones(stance).contains(&zone)
+    }
+}
+
 /// Complete armor properties
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArmorProperties {
     pub rigidity: Rigidity,
     pub padding: Padding,
     pub coverage: Coverage,
+    #[serde(default)]
+    pub helmet: Helmet,
+    #[serde(default)]
+    pub shield: Shield,
 }
 
 impl ArmorProperties {
@@ -55,6 +127,8 @@ impl ArmorProperties {
             rigidity: Rigidity::Cloth,
             padding: Padding::None,
             coverage: Coverage::None,
+            helmet: Helmet::None,
+            shield: Shield::None,
         }
     }
 
@@ -64,6 +138,8 @@ impl ArmorProperties {
             rigidity: Rigidity::Leather,
             padding: Padding::Light,
             coverage: Coverage::Partial,
+            helmet: Helmet::None,
+            shield: Shield::None,
         }
     }
 
@@ -73,6 +149,8 @@ impl ArmorProperties {
             rigidity: Rigidity::Mail,
             padding: Padding::Light,
             coverage: Coverage::Partial,
+            helmet: Helmet::Cap,
+            shield: Shield::None,
         }
     }
 
@@ -82,6 +160,28 @@ impl ArmorProperties {
             rigidity: Rigidity::Plate,
             padding: Padding::Heavy,
             coverage: Coverage::Full,
+            helmet: Helmet::Great,
+            shield: Shield::None,
+        }
+    }
+
+    /// The same armor with a shield on the off arm
+    pub fn with_shield(mut self, shield: Shield) -> Self {
+        self.shield = shield;
+        self
+    }
+
+    /// What actually protects `zone`: the helmet on the head, nothing in
+    /// the gaps partial coverage leaves (neck, hands, feet), the body
+    /// armor everywhere else
+    pub fn at(&self, zone: BodyZone) -> (Rigidity, Padding) {
+        let bare = (Rigidity::Cloth, Padding::None);
+        let gap = matches!(zone, BodyZone::Neck) || zone.is_hand() || zone.is_foot();
+        match (zone, self.coverage) {
+            (BodyZone::Head, _) => self.helmet.protection(),
+            (_, Coverage::None) => bare,
+            (_, Coverage::Partial) if gap => bare,
+            _ => (self.rigidity, self.padding),
         }
     }
 }
@@ -102,6 +202,8 @@ mod tests {
             rigidity: Rigidity::Plate,
             padding: Padding::Heavy,
             coverage: Coverage::Full,
+            helmet: Helmet::Great,
+            shield: Shield::None,
         };
         assert_eq!(plate.rigidity, Rigidity::Plate);
     }
@@ -126,4 +228,33 @@ mod tests {
         assert_eq!(plate.rigidity, Rigidity::Plate);
         assert_eq!(plate.padding, Padding::Heavy);
     }
+
+    #[test]
+    fn test_protection_by_zone() {
+        let mail = ArmorProperties::mail();
+        assert_eq!(mail.at(BodyZone::Torso), (Rigidity::Mail, Padding::Light));
+        assert_eq!(mail.at(BodyZone::Head), (Rigidity::Plate, Padding::None));
+        assert_eq!(mail.at(BodyZone::Neck), (Rigidity::Cloth, Padding::None));
+
+        let plate = ArmorProperties::plate();
+        assert_eq!(
+            plate.at(BodyZone::HandLeft),
+            (Rigidity::Plate, Padding::Heavy)
+        );
+        assert_eq!(
+            ArmorProperties::leather().at(BodyZone::Head).0,
+            Rigidity::Cloth
+        );
+    }
+
+    #[test]
+    fn test_shield_follows_stance() {
+        let round = Shield::Round;
+        assert!(round.covers(BodyZone::Head, CombatStance::Defensive));
+        assert!(round.covers(BodyZone::Torso, CombatStance::Neutral));
+        assert!(!round.covers(BodyZone::Torso, CombatStance::Pressing));
+        assert!(!round.covers(BodyZone::ArmLeft, CombatStance::Recovering));
+        assert!(!round.covers(BodyZone::LegRight, CombatStance::Defensive));
+        assert!(Shield::Tower.covers(BodyZone::LegLeft, CombatStance::Neutral));
+    }
 }
@@ -4,6 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::combat::SkillLevel;
+
 /// Wound severity categories (not f32)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum WoundSeverity {
@@ -98,6 +100,58 @@ impl BodyZone {
     pub fn is_hand(&self) -> bool {
         matches!(self, BodyZone::HandLeft | BodyZone::HandRight)
     }
+
+    /// Is this a foot zone?
+    pub fn is_foot(&self) -> bool {
+        matches!(self, BodyZone::FootLeft | BodyZone::FootRight)
+    }
+
+    /// Least skill that lands a called shot here
+    ///
+    /// Big targets take a trained hand; small ones a veteran's; the gap at
+    /// the throat a master's.
+    pub fn aim_skill(&self) -> SkillLevel {
+        match self {
+            BodyZone::Torso
+            | BodyZone::ArmLeft
+            | BodyZone::ArmRight
+            | BodyZone::LegLeft
+            | BodyZone::LegRight => SkillLevel::Trained,
+            BodyZone::Head
+            | BodyZone::HandLeft
+            | BodyZone::HandRight
+            | BodyZone::FootLeft
+            | BodyZone::FootRight => SkillLevel::Veteran,
+            BodyZone::Neck => SkillLevel::Master,
+        }
+    }
+
+    /// Where a called shot lands when it falls just short: the bigger
+    /// target next to this one
+    pub fn near_miss(&self) -> BodyZone {
+        match self {
+            BodyZone::HandLeft => BodyZone::ArmLeft,
+            BodyZone::HandRight => BodyZone::ArmRight,
+            BodyZone::FootLeft => BodyZone::LegLeft,
+            BodyZone::FootRight => BodyZone::LegRight,
+            _ => BodyZone::Torso,
+        }
+    }
+
+    /// Where a called shot at this zone lands for someone of `skill`
+    ///
+    /// Steps down to the next bigger target until the skill is enough.
+    /// Novices can't call shots at all: `None`.
+    pub fn aimed_by(&self, skill: SkillLevel) -> Option<BodyZone> {
+        if !skill.can_target_specific_zone() {
+            return None;
+        }
+        let mut zone = *self;
+        while skill < zone.aim_skill() {
+            zone = zone.near_miss();
+        }
+        Some(zone)
+    }
 }
 
 #[cfg(test)]
@@ -145,5 +199,27 @@ mod tests {
         assert!(!BodyZone::HandRight.is_arm());
 
         assert!(BodyZone::HandLeft.is_hand());
+        assert!(BodyZone::FootLeft.is_foot());
+    }
+
+    #[test]
+    fn test_called_shots_gated_by_skill() {
+        assert_eq!(BodyZone::Head.aimed_by(SkillLevel::Novice), None);
+        assert_eq!(
+            BodyZone::LegLeft.aimed_by(SkillLevel::Trained),
+            Some(BodyZone::LegLeft)
+        );
+        assert_eq!(
+            BodyZone::Neck.aimed_by(SkillLevel::Veteran),
+            Some(BodyZone::Torso)
+        );
+        assert_eq!(
+            BodyZone::HandRight.aimed_by(SkillLevel::Trained),
+            Some(BodyZone::ArmRight)
+        );
+        assert_eq!(
+            BodyZone::Neck.aimed_by(SkillLevel::Master),
+            Some(BodyZone::Neck)
+        );
     }
 }
@@ -0,0 +1,166 @@
+//! Memoized exchange outcomes
+//!
+//! `resolve_exchange` is a pure function of each side's weapon, armor, skill
+//! level, and stance, and a battle pits the same few loadouts against each
+//! other thousands of times a tick. `ExchangeCache` resolves each distinct
+//! matchup once and hands back the stored outcome after that.
+//!
+//! Loadouts (weapon, armor, skill level) are interned by comparing them with
+//! the ones already seen, so a combatant who changes weapon, armor, or skill
+//! simply looks up a different matchup: an entry never goes stale. Stance is
+//! part of the key too, which covers the battle contexts (front rank, reach,
+//! flanking support) that change how a side fights.
+
+use ahash::AHashMap;
+
+use crate::combat::resolution::{resolve_exchange, Combatant, ExchangeResult};
+use crate::combat::{ArmorProperties, CombatSkill, CombatStance, SkillLevel, WeaponProperties};
+
+/// Index of an interned loadout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LoadoutId(u32);
+
+/// What a combatant fights with: everything but stance
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Loadout {
+    weapon: WeaponProperties,
+    armor: ArmorProperties,
+    skill: SkillLevel,
+}
+
+/// One side of a matchup
+type Side = (LoadoutId, CombatStance);
+
+/// Exchange outcomes by matchup
+#[derive(Debug, Default)]
+pub struct ExchangeCache {
+    loadouts: Vec<Loadout>,
+    outcomes: AHashMap<(Side, Side), ExchangeResult>,
+    hits: u64,
+    misses: u64,
+}
+
+impl ExchangeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern a loadout, returning its id
+    ///
+    /// Battles field a handful of distinct loadouts, so a scan beats hashing
+    /// (and cloning) the weapon and armor every exchange.
+    pub fn loadout(
+        &mut self,
+        weapon: &WeaponProperties,
+        armor: &ArmorProperties,
+        skill: SkillLevel,
+    ) -> LoadoutId {
+        let found = self
+            .loadouts
+            .iter()
+            .position(|l| l.skill == skill && l.armor == *armor && l.weapon == *weapon);
+        let index = found.unwrap_or_else(|| {
+            self.loadouts.push(Loadout {
+                weapon: weapon.clone(),
+                armor: armor.clone(),
+                skill,
+            });
+            self.loadouts.len() - 1
+        });
+        LoadoutId(index as u32)
+    }
+
+    /// The outcome of an attacker on one side pressing a defender on the
+    /// other, resolving it the first time the matchup is seen
+    pub fn exchange(&mut self, attacker: Side, defender: Side) -> &ExchangeResult {
+        let loadouts = &self.loadouts;
+        let mut resolved = false;
+        let result = self
+            .outcomes
+            .entry((attacker, defender))
+            .or_insert_with(|| {
+                resolved = true;
+                let combatant = |(id, stance): Side| {
+                    let loadout = &loadouts[id.0 as usize];
+                    Combatant {
+                        weapon: loadout.weapon.clone(),
+                        armor: loadout.armor.clone(),
+                        stance,
+                        skill: CombatSkill {
+                            level: loadout.skill,
+                        },
+                        aim: None,
+                    }
+                };
+                resolve_exchange(&combatant(attacker), &combatant(defender))
+            });
+        if resolved {
+            self.misses += 1;
+        } else {
+            self.hits += 1;
+        }
+        result
+    }
+
+    /// Resolve an exchange between two combatants through the cache
+    pub fn resolve(&mut self, attacker: &Combatant, defender: &Combatant) -> ExchangeResult {
+        let a = self.loadout(&attacker.weapon, &attacker.armor, attacker.skill.level);
+        let d = self.loadout(&defender.weapon, &defender.armor, defender.skill.level);
+        self.exchange((a, attacker.stance), (d, defender.stance))
+            .clone()
+    }
+
+    /// Forget every loadout and outcome (for when resolution rules change)
+    pub fn clear(&mut self) {
+        self.loadouts.clear();
+        self.outcomes.clear();
+    }
+
+    /// Number of distinct matchups resolved
+    pub fn len(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+
+    /// Lookups answered from the cache, and lookups that had to resolve
+    pub fn stats(&self) -> (u64, u64) {
+        (self.hits, self.misses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_exchange_matches_direct_resolution() {
+        let mut cache = ExchangeCache::new();
+        let spearman = Combatant::test_spearman();
+        let mut knight = Combatant::test_plate_knight();
+        knight.stance = CombatStance::Defensive;
+
+        for _ in 0..3 {
+            let cached = cache.resolve(&spearman, &knight);
+            let direct = resolve_exchange(&spearman, &knight);
+            assert_eq!(cached.defender_wound, direct.defender_wound);
+            assert_eq!(cached.attacker_wound, direct.attacker_wound);
+            assert_eq!(cached.attacker_struck_first, direct.attacker_struck_first);
+        }
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.stats(), (2, 1));
+
+        // A changed stance or weapon is a different matchup, never a stale one
+        knight.stance = CombatStance::Recovering;
+        assert!(!cache.resolve(&spearman, &knight).attacker_hit);
+        knight.weapon = WeaponProperties::spear();
+        cache.resolve(&spearman, &knight);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.loadouts.len(), 3);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -10,6 +10,7 @@
 pub mod adapter;
 pub mod armor;
 pub mod body_zone;
+pub mod cache;
 pub mod constants;
 pub mod equipment;
 pub mod formation;
@@ -24,16 +25,19 @@ pub mod weapons;
 pub mod wounds;
 
 pub use adapter::{CombatAdapter, CombatResult};
-pub use armor::{ArmorProperties, Coverage, Padding, Rigidity};
+pub use armor::{ArmorProperties, Coverage, Helmet, Padding, Rigidity, Shield};
 pub use equipment::{armor_for_role, combat_state_for_role, weapon_for_role};
 pub use body_zone::{BodyZone, WoundSeverity};
+pub use cache::{ExchangeCache, LoadoutId};
 pub use formation::{FormationState, PressureCategory, ShockType};
 pub use morale::{BreakResult, MoraleState, StressSource};
 pub use penetration::{resolve_penetration, PenetrationResult};
-pub use resolution::{resolve_exchange, Combatant, ExchangeResult};
+pub use resolution::{resolve_exchange, strike_zone, Combatant, ExchangeResult};
 pub use skill::{CombatSkill, SkillLevel};
 pub use stance::{CombatStance, StanceTransitions, TransitionTrigger};
 pub use state::CombatState;
 pub use trauma::{resolve_trauma, TraumaResult};
-pub use weapons::{Edge, Mass, Reach, WeaponProperties, WeaponSpecial};
-pub use wounds::{combine_results, Wound};
+pub use weapons::{
+    Edge, Mass, RangeCategory, RangedWeaponProperties, Reach, WeaponProperties, WeaponSpecial,
+};
+pub use wounds::{combine_results, Gait, Wound};
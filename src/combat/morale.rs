@@ -29,6 +29,7 @@ pub enum StressSource {
     NoResponse,
     OverwatchFire,
     ProlongedCombat,
+    OutOfAmmo,
 
     // Social stress
     AlliesBreaking,
@@ -54,6 +55,7 @@ impl StressSource {
             StressSource::NoResponse,
             StressSource::OverwatchFire,
             StressSource::ProlongedCombat,
+            StressSource::OutOfAmmo,
             StressSource::AlliesBreaking,
             StressSource::AloneExposed,
         ]
@@ -82,6 +84,7 @@ impl StressSource {
             StressSource::NoResponse => 0.02,
             StressSource::OverwatchFire => 0.02,
             StressSource::ProlongedCombat => 0.005,
+            StressSource::OutOfAmmo => 0.01,
 
             // Social stress
             StressSource::AlliesBreaking => 0.10,
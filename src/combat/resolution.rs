@@ -2,12 +2,26 @@
 //!
 //! An exchange occurs when PRESSING meets any other stance.
 //! NO PERCENTAGE MODIFIERS. Property comparisons only.
+//!
+//! Where a blow lands is categorical too: a called shot lands where the
+//! attacker's skill allows, an uncalled one where their skill habitually
+//! goes. A shield in front of that zone takes the blow instead.
 
 use crate::combat::{
     combine_results, resolve_penetration, resolve_trauma, ArmorProperties, BodyZone, CombatSkill,
-    CombatStance, SkillLevel, WeaponProperties, WeaponSpecial, Wound,
+    CombatStance, PenetrationResult, SkillLevel, WeaponProperties, WeaponSpecial, Wound,
+    WoundSeverity,
 };
 
+/// Where a veteran looks for a way around a shield, in order
+const SHIELD_GAPS: [BodyZone; 5] = [
+    BodyZone::Torso,
+    BodyZone::ArmRight,
+    BodyZone::LegRight,
+    BodyZone::LegLeft,
+    BodyZone::Head,
+];
+
 /// A combatant in an exchange
 #[derive(Debug, Clone)]
 pub struct Combatant {
@@ -15,6 +29,8 @@ pub struct Combatant {
     pub armor: ArmorProperties,
     pub stance: CombatStance,
     pub skill: CombatSkill,
+    /// Called shot: the zone this combatant tries to hit
+    pub aim: Option<BodyZone>,
 }
 
 impl Combatant {
@@ -25,6 +41,7 @@ impl Combatant {
             armor: ArmorProperties::none(),
             stance: CombatStance::Pressing,
             skill: CombatSkill::trained(),
+            aim: None,
         }
     }
 
@@ -35,6 +52,7 @@ impl Combatant {
             armor: ArmorProperties::none(),
             stance: CombatStance::Pressing,
             skill: CombatSkill::trained(),
+            aim: None,
         }
     }
 
@@ -45,6 +63,7 @@ impl Combatant {
             armor: ArmorProperties::plate(),
             stance: CombatStance::Neutral,
             skill: CombatSkill::veteran(),
+            aim: None,
         }
     }
 
@@ -55,8 +74,15 @@ impl Combatant {
             armor: ArmorProperties::none(),
             stance: CombatStance::Neutral,
             skill: CombatSkill::novice(),
+            aim: None,
         }
     }
+
+    /// The same combatant calling a shot at `zone`
+    pub fn aiming_at(mut self, zone: BodyZone) -> Self {
+        self.aim = Some(zone);
+        self
+    }
 }
 
 /// Result of an exchange
@@ -86,14 +112,64 @@ pub fn select_hit_zone(skill: SkillLevel) -> BodyZone {
     }
 }
 
-/// Resolve a single hit
+/// Where `attacker`'s blow lands on `defender`
+///
+/// A called shot lands where the attacker's skill carries it
+/// (`BodyZone::aimed_by`). Otherwise the blow goes where their skill
+/// habitually sends it, unless a veteran or better sees that zone behind
+/// the defender's shield and takes the first gap instead.
+pub fn strike_zone(attacker: &Combatant, defender: &Combatant) -> BodyZone {
+    let skill = attacker.skill.level;
+    if let Some(zone) = attacker.aim.and_then(|z| z.aimed_by(skill)) {
+        return zone;
+    }
+
+    let zone = select_hit_zone(skill);
+    let shield = defender.armor.shield;
+    if skill >= SkillLevel::Veteran && shield.covers(zone, defender.stance) {
+        if let Some(&gap) = SHIELD_GAPS
+            .iter()
+            .find(|z| !shield.covers(**z, defender.stance) && skill >= z.aim_skill())
+        {
+            return gap;
+        }
+    }
+    zone
+}
+
+/// Resolve a single hit against the protection at `zone`
 pub fn resolve_hit(weapon: &WeaponProperties, armor: &ArmorProperties, zone: BodyZone) -> Wound {
+    let (rigidity, padding) = armor.at(zone);
     let has_piercing = weapon.has_special(WeaponSpecial::Piercing);
-    let pen = resolve_penetration(weapon.edge, armor.rigidity, has_piercing);
-    let trauma = resolve_trauma(weapon.mass, armor.padding);
+    let pen = resolve_penetration(weapon.edge, rigidity, has_piercing);
+    let trauma = resolve_trauma(weapon.mass, padding);
     combine_results(pen, trauma, zone)
 }
 
+/// Resolve `attacker` striking `defender`
+///
+/// A blow at a zone the defender's shield covers lands on the shield: the
+/// edge is wasted and only the weight carries through to the shield arm.
+/// Shieldbreakers hew through as if it weren't there.
+fn resolve_strike(attacker: &Combatant, defender: &Combatant) -> Wound {
+    let zone = strike_zone(attacker, defender);
+    let blocked = defender.armor.shield.covers(zone, defender.stance)
+        && !attacker.weapon.has_special(WeaponSpecial::Shieldbreaker);
+    if !blocked {
+        return resolve_hit(&attacker.weapon, &defender.armor, zone);
+    }
+
+    let shield_arm = BodyZone::ArmLeft;
+    let (_, padding) = defender.armor.at(shield_arm);
+    let trauma = resolve_trauma(attacker.weapon.mass, padding);
+    let wound = combine_results(PenetrationResult::NoPenetrationAttempt, trauma, shield_arm);
+    if wound.severity == WoundSeverity::None {
+        Wound::none(shield_arm)
+    } else {
+        wound
+    }
+}
+
 /// Resolve an exchange between attacker and defender
 ///
 /// # Arguments
@@ -108,8 +184,7 @@ pub fn resolve_exchange(attacker: &Combatant, defender: &Combatant) -> ExchangeR
 
     if !defender_can_respond {
         // Free hit - defender is recovering or broken
-        let zone = select_hit_zone(attacker.skill.level);
-        let wound = resolve_hit(&attacker.weapon, &defender.armor, zone);
+        let wound = resolve_strike(attacker, defender);
 
         return ExchangeResult {
             defender_hit: true,
@@ -131,14 +206,11 @@ pub fn resolve_exchange(attacker: &Combatant, defender: &Combatant) -> ExchangeR
     };
 
     // Step 3: Resolve attacker's hit
-    let attacker_zone = select_hit_zone(attacker.skill.level);
-    let defender_wound = resolve_hit(&attacker.weapon, &defender.armor, attacker_zone);
+    let defender_wound = resolve_strike(attacker, defender);
 
     // Step 4: Resolve defender's counter (if they can attack)
     let (attacker_hit, attacker_wound) = if defender.stance.can_attack() && both_hit {
-        let defender_zone = select_hit_zone(defender.skill.level);
-        let wound = resolve_hit(&defender.weapon, &attacker.armor, defender_zone);
-        (true, Some(wound))
+        (true, Some(resolve_strike(defender, attacker)))
     } else {
         (false, None)
     };
@@ -155,7 +227,7 @@ pub fn resolve_exchange(attacker: &Combatant, defender: &Combatant) -> ExchangeR
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::combat::body_zone::WoundSeverity;
+    use crate::combat::{Helmet, Shield};
 
     #[test]
     fn test_pressing_vs_recovering_is_free_hit() {
@@ -210,4 +282,52 @@ mod tests {
         assert!(result.defender_hit);
         assert!(result.attacker_hit);
     }
+
+    #[test]
+    fn test_called_shot_goes_around_shield() {
+        let mut defender = Combatant::test_swordsman();
+        defender.armor = ArmorProperties::leather().with_shield(Shield::Round);
+        defender.stance = CombatStance::Defensive;
+
+        // Trained swordsman's blow at the torso lands on the shield
+        let attacker = Combatant::test_swordsman();
+        assert_eq!(strike_zone(&attacker, &defender), BodyZone::Torso);
+        let blocked = resolve_exchange(&attacker, &defender);
+        let wound = blocked.defender_wound.unwrap();
+        assert_eq!(wound.zone, BodyZone::ArmLeft);
+        assert_eq!(wound.severity, WoundSeverity::None);
+
+        // Calling a shot at the leg cuts under it and lames
+        let aimed = Combatant::test_swordsman().aiming_at(BodyZone::LegRight);
+        let wound = resolve_exchange(&aimed, &defender).defender_wound.unwrap();
+        assert_eq!(wound.zone, BodyZone::LegRight);
+        assert!(wound.severity > WoundSeverity::None);
+        assert!(wound.mobility_impact);
+
+        // A novice can't call shots; a trained hand aiming at the throat
+        // lands on the body
+        let mut novice = Combatant::test_unarmored().aiming_at(BodyZone::LegRight);
+        novice.stance = CombatStance::Pressing;
+        assert_eq!(strike_zone(&novice, &defender), BodyZone::Torso);
+        let throat = Combatant::test_swordsman().aiming_at(BodyZone::Neck);
+        assert_eq!(strike_zone(&throat, &defender), BodyZone::Torso);
+    }
+
+    #[test]
+    fn test_helmet_decides_head_blows() {
+        let mut master = Combatant::test_swordsman();
+        master.skill = CombatSkill::master();
+
+        let mut bareheaded = Combatant::test_plate_knight();
+        bareheaded.armor.helmet = Helmet::None;
+        let wound = resolve_exchange(&master, &bareheaded)
+            .defender_wound
+            .unwrap();
+        assert_eq!(wound.zone, BodyZone::Head);
+        assert!(wound.severity >= WoundSeverity::Serious);
+
+        let helmed = Combatant::test_plate_knight();
+        let wound = resolve_exchange(&master, &helmed).defender_wound.unwrap();
+        assert_eq!(wound.severity, WoundSeverity::None);
+    }
 }
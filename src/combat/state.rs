@@ -3,7 +3,7 @@
 //! Every entity has combat state (mandatory but minimal).
 
 use crate::combat::{ArmorProperties, CombatSkill, CombatStance, MoraleState, WeaponProperties};
-use crate::combat::wounds::Wound;
+use crate::combat::wounds::{Gait, Wound};
 use crate::combat::body_zone::WoundSeverity;
 use serde::{Deserialize, Serialize};
 
@@ -91,6 +91,11 @@ impl CombatState {
         total_severity >= 10
     }
 
+    /// How well this entity walks on its leg wounds
+    pub fn gait(&self) -> Gait {
+        Gait::of(&self.wounds)
+    }
+
     /// Check if entity is dead
     pub fn is_dead(&self) -> bool {
         // Dead if destroyed or if head/neck/torso critical
@@ -151,6 +151,72 @@ impl Default for WeaponProperties {
     }
 }
 
+/// Range category for ranged weapons
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RangeCategory {
+    /// Thrown weapons (3-5 hexes effective)
+    Close,
+    /// Shortbows, light crossbows (8-12 hexes)
+    Medium,
+    /// Longbows, heavy crossbows (15-20+ hexes)
+    Long,
+}
+
+/// Properties specific to ranged weapons
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangedWeaponProperties {
+    /// Effective range category
+    pub range: RangeCategory,
+    /// Physical strength required to draw/throw (affects fatigue)
+    pub draw_strength: Mass,
+    /// Ticks required to reload (0 for bows, >0 for crossbows)
+    pub reload_ticks: u8,
+    /// Ammunition consumed per shot
+    pub ammo_per_shot: u8,
+}
+
+impl RangedWeaponProperties {
+    /// Shortbow - medium range, moderate draw
+    pub fn shortbow() -> Self {
+        Self {
+            range: RangeCategory::Medium,
+            draw_strength: Mass::Medium,
+            reload_ticks: 0,
+            ammo_per_shot: 1,
+        }
+    }
+
+    /// Longbow - long range, heavy draw
+    pub fn longbow() -> Self {
+        Self {
+            range: RangeCategory::Long,
+            draw_strength: Mass::Heavy,
+            reload_ticks: 0,
+            ammo_per_shot: 1,
+        }
+    }
+
+    /// Light crossbow - medium range, easy to use, slow reload
+    pub fn light_crossbow() -> Self {
+        Self {
+            range: RangeCategory::Medium,
+            draw_strength: Mass::Light,
+            reload_ticks: 2,
+            ammo_per_shot: 1,
+        }
+    }
+
+    /// Javelin - close range, throwable
+    pub fn javelin() -> Self {
+        Self {
+            range: RangeCategory::Close,
+            draw_strength: Mass::Medium,
+            reload_ticks: 0,
+            ammo_per_shot: 1,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,4 +263,18 @@ mod tests {
         let spear = WeaponProperties::spear();
         assert!(spear.has_special(WeaponSpecial::Piercing));
     }
+
+    #[test]
+    fn test_common_ranged_weapons() {
+        assert!(RangeCategory::Long > RangeCategory::Medium);
+
+        let bow = RangedWeaponProperties::shortbow();
+        assert_eq!(bow.range, RangeCategory::Medium);
+
+        let crossbow = RangedWeaponProperties::light_crossbow();
+        assert!(crossbow.reload_ticks > 0);
+
+        let javelin = RangedWeaponProperties::javelin();
+        assert_eq!(javelin.range, RangeCategory::Close);
+    }
 }
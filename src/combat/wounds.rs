@@ -30,6 +30,46 @@ impl Wound {
     }
 }
 
+/// How well someone walks on their worst leg wound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Gait {
+    Sound,
+    /// Minor leg wound
+    Limping,
+    /// Serious leg wound
+    Hobbled,
+    /// Critical or worse: dragging the leg
+    Crippled,
+}
+
+impl Gait {
+    /// Gait given every wound carried
+    pub fn of(wounds: &[Wound]) -> Self {
+        let worst = wounds
+            .iter()
+            .filter(|w| w.zone.is_leg())
+            .map(|w| w.severity)
+            .max()
+            .unwrap_or(WoundSeverity::None);
+        match worst {
+            WoundSeverity::None | WoundSeverity::Scratch => Gait::Sound,
+            WoundSeverity::Minor => Gait::Limping,
+            WoundSeverity::Serious => Gait::Hobbled,
+            WoundSeverity::Critical | WoundSeverity::Destroyed => Gait::Crippled,
+        }
+    }
+
+    /// Share of a sound walking pace this gait manages
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            Gait::Sound => 1.0,
+            Gait::Limping => 0.75,
+            Gait::Hobbled => 0.5,
+            Gait::Crippled => 0.2,
+        }
+    }
+}
+
 /// Combine penetration and trauma results into a wound
 ///
 /// Takes the WORSE of the two results (no multiplication).
@@ -130,4 +170,28 @@ mod tests {
         );
         assert_eq!(wound.severity, WoundSeverity::None);
     }
+
+    #[test]
+    fn test_leg_wounds_set_gait() {
+        let arm = combine_results(
+            PenetrationResult::Cut,
+            TraumaResult::Negligible,
+            BodyZone::ArmLeft,
+        );
+        assert_eq!(Gait::of(&[arm.clone()]), Gait::Sound);
+
+        let foot = combine_results(
+            PenetrationResult::ShallowCut,
+            TraumaResult::Negligible,
+            BodyZone::FootRight,
+        );
+        let thigh = combine_results(
+            PenetrationResult::DeepCut,
+            TraumaResult::Negligible,
+            BodyZone::LegLeft,
+        );
+        assert_eq!(Gait::of(&[arm.clone(), foot.clone()]), Gait::Limping);
+        assert_eq!(Gait::of(&[foot, thigh, arm]), Gait::Crippled);
+        assert!(Gait::Crippled.speed_multiplier() < Gait::Limping.speed_multiplier());
+    }
 }
@@ -0,0 +1,278 @@
+//! Clarification - asking the player before acting on a doubtful order
+//!
+//! When the parser could not tell what was meant (an unknown action, a
+//! confidence below `CLARIFY_BELOW_CONFIDENCE`, or ambiguous concepts), the
+//! executor does not guess. It hands back a `Clarification` holding the
+//! order and one follow-up question. The player's answer is merged into the
+//! order, which is then either ready to carry out or has another question.
+
+use crate::ecs::world::World;
+use crate::llm::parser::{IntentAction, ParsedIntent};
+
+/// Confidence below which the player is asked to confirm an order
+pub const CLARIFY_BELOW_CONFIDENCE: f32 = 0.6;
+
+/// What the follow-up question is about
+#[derive(Debug, Clone, PartialEq)]
+pub enum Question {
+    /// The parser could not tell what should be done
+    Action,
+    /// A word that could mean many things ("brave", "beautiful")
+    Concept(String),
+    /// The parser was unsure of the whole reading
+    Confirm,
+}
+
+/// An order waiting on the player's answer
+#[derive(Debug, Clone)]
+pub struct Clarification {
+    pub intent: ParsedIntent,
+    /// The step of the order the question is about (0 unless multi-step)
+    pub step: usize,
+    pub question: Question,
+}
+
+/// What came of an answer
+#[derive(Debug, Clone)]
+pub enum Clarified {
+    /// The order is clear and can be carried out
+    Resolved(ParsedIntent),
+    /// There is more to ask
+    Ask(Clarification),
+    /// The player dropped the order
+    Cancelled,
+}
+
+/// The question to ask about one step, if any
+fn question_for(step: &ParsedIntent) -> Option<Question> {
+    if step.action == IntentAction::Unknown {
+        Some(Question::Action)
+    } else if let Some(concept) = step.ambiguous_concepts.first() {
+        Some(Question::Concept(concept.clone()))
+    } else if step.confidence < CLARIFY_BELOW_CONFIDENCE {
+        Some(Question::Confirm)
+    } else {
+        None
+    }
+}
+
+/// The first question to ask about an order, or None if it is clear
+pub fn needs_clarification(intent: &ParsedIntent) -> Option<Clarification> {
+    intent
+        .steps()
+        .iter()
+        .enumerate()
+        .find_map(|(step, s)| question_for(s).map(|question| (step, question)))
+        .map(|(step, question)| Clarification {
+            intent: intent.clone(),
+            step,
+            question,
+        })
+}
+
+/// How an order step reads back to the player, e.g. "build wall (east)"
+fn describe(step: &ParsedIntent) -> String {
+    let mut text = format!("{:?}", step.action).to_lowercase();
+    if let Some(target) = &step.target {
+        text = format!("{} {}", text, target);
+    }
+    if let Some(location) = &step.location {
+        text = format!("{} ({})", text, location);
+    }
+    if let Some(subjects) = &step.subjects {
+        text = format!("{} by {}", text, subjects.join(", "));
+    }
+    text
+}
+
+/// The action named by an answer like "build it" or "go gather"
+pub(crate) fn action_named(answer: &str) -> Option<IntentAction> {
+    answer.split_whitespace().find_map(|word| match word {
+        "build" | "construct" | "raise" => Some(IntentAction::Build),
+        "craft" | "make" | "forge" => Some(IntentAction::Craft),
+        "assign" | "guard" | "post" => Some(IntentAction::Assign),
+        "fight" | "attack" | "defend" => Some(IntentAction::Combat),
+        "gather" | "collect" | "chop" | "harvest" | "mine" => Some(IntentAction::Gather),
+        "move" | "go" | "walk" => Some(IntentAction::Move),
+        "rest" | "sleep" => Some(IntentAction::Rest),
+        "talk" | "chat" | "visit" => Some(IntentAction::Social),
+        _ => None,
+    })
+}
+
+/// The answer split into names, if every one is someone living
+fn named_people(world: &World, answer: &str) -> Option<Vec<String>> {
+    let names: Vec<String> = answer
+        .replace(" and ", ",")
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect();
+    let known = |name: &String| {
+        let name = name.to_lowercase();
+        world
+            .humans
+            .iter_living()
+            .any(|i| world.humans.names[i].to_lowercase().starts_with(&name))
+    };
+    (!names.is_empty() && names.iter().all(known)).then_some(names)
+}
+
+/// Replace `concept` in `text`, ignoring case; None if it is not there
+fn replace_concept(text: &str, concept: &str, answer: &str) -> Option<String> {
+    let at = text.to_lowercase().find(&concept.to_lowercase())?;
+    let before = text.get(..at)?;
+    let after = text.get(at + concept.len()..)?;
+    Some(format!("{}{}{}", before, answer, after))
+}
+
+/// Fold what the player meant by `concept` into the step
+///
+/// Names of settlers become the step's subjects; otherwise the answer
+/// stands in for the word wherever it appears, or qualifies the target.
+fn merge_concept(world: &World, step: &mut ParsedIntent, concept: &str, answer: &str) {
+    step.ambiguous_concepts.retain(|c| c != concept);
+    if let Some(names) = named_people(world, answer) {
+        step.subjects = Some(names);
+        return;
+    }
+    if let Some(subjects) = &mut step.subjects {
+        for subject in subjects.iter_mut() {
+            if let Some(merged) = replace_concept(subject, concept, answer) {
+                *subject = merged;
+                return;
+            }
+        }
+    }
+    for field in [&mut step.target, &mut step.location] {
+        if let Some(merged) = field
+            .as_deref()
+            .and_then(|text| replace_concept(text, concept, answer))
+        {
+            *field = Some(merged);
+            return;
+        }
+    }
+    step.target = Some(match step.target.take() {
+        Some(target) => format!("{} ({})", target, answer),
+        None => answer.to_string(),
+    });
+}
+
+impl Clarification {
+    /// The question to put to the player
+    pub fn question(&self) -> String {
+        let step = &self.intent.steps()[self.step];
+        let question = match &self.question {
+            Question::Action => {
+                "What should be done - build, craft, gather, move, fight, rest, or talk?"
+                    .to_string()
+            }
+            Question::Concept(concept) => format!("What do you mean by \"{}\"?", concept),
+            Question::Confirm => format!("Did you mean: {}? (yes / no)", describe(step)),
+        };
+        match self.intent.is_compound() {
+            true => format!("Step {}: {}", self.step + 1, question),
+            false => question,
+        }
+    }
+
+    /// Merge the player's answer into the order
+    ///
+    /// "cancel" (or "no" to a confirmation) drops it; an answer that does
+    /// not fit the question asks it again. Answering a question counts as
+    /// confirming the step.
+    pub fn answer(mut self, world: &World, answer: &str) -> Clarified {
+        let answer = answer.trim();
+        let lower = answer.to_lowercase();
+        if lower == "cancel" || (self.question == Question::Confirm && lower == "no") {
+            return Clarified::Cancelled;
+        }
+        let step = self.intent.step_mut(self.step).expect("step in range");
+        match &self.question {
+            Question::Action => match action_named(&lower) {
+                Some(action) => step.action = action,
+                None => return Clarified::Ask(self),
+            },
+            Question::Concept(concept) => merge_concept(world, step, concept, answer),
+            Question::Confirm if matches!(lower.as_str(), "yes" | "y") => {}
+            Question::Confirm => return Clarified::Ask(self),
+        }
+        step.confidence = 1.0;
+        match needs_clarification(&self.intent) {
+            Some(next) => Clarified::Ask(next),
+            None => Clarified::Resolved(self.intent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::executor::CommandExecutor;
+
+    #[test]
+    fn test_ambiguous_order_waits_for_an_answer() {
+        let mut world = World::with_seed(8);
+        world.spawn_human("Marcus".into());
+        world.spawn_human("Ada".into());
+        let intent = ParsedIntent {
+            action: IntentAction::Assign,
+            target: Some("scouting".into()),
+            ambiguous_concepts: vec!["brave".into()],
+            confidence: 0.7,
+            ..Default::default()
+        };
+
+        let result = CommandExecutor::execute(&mut world, &intent, 0);
+        assert_eq!(result.tasks_created, 0);
+        let clarification = result.clarification.unwrap();
+        assert_eq!(clarification.question(), "What do you mean by \"brave\"?");
+
+        let Clarified::Resolved(clear) = clarification.answer(&world, "Marcus and Ada") else {
+            panic!("one answer should settle it");
+        };
+        assert_eq!(clear.subjects, Some(vec!["Marcus".into(), "Ada".into()]));
+        assert!(clear.ambiguous_concepts.is_empty());
+        assert!(needs_clarification(&clear).is_none());
+    }
+
+    #[test]
+    fn test_unsure_step_is_confirmed_or_dropped() {
+        let world = World::with_seed(9);
+        let mut intent = ParsedIntent {
+            action: IntentAction::Gather,
+            target: Some("wood".into()),
+            confidence: 0.9,
+            ..Default::default()
+        };
+        intent.then = vec![ParsedIntent {
+            confidence: 0.3,
+            ..Default::default()
+        }];
+
+        let clarification = needs_clarification(&intent).unwrap();
+        assert_eq!(clarification.step, 1);
+        assert_eq!(clarification.question, Question::Action);
+        // An answer naming no action asks again
+        let Clarified::Ask(again) = clarification.answer(&world, "hmm") else {
+            panic!("should ask again");
+        };
+        let Clarified::Resolved(clear) = again.answer(&world, "build it") else {
+            panic!("the action was named");
+        };
+        assert_eq!(clear.steps()[1].action, IntentAction::Build);
+
+        let unsure = ParsedIntent {
+            confidence: 0.4,
+            ..clear
+        };
+        let confirm = needs_clarification(&unsure).unwrap();
+        assert_eq!(
+            confirm.question(),
+            "Step 1: Did you mean: gather wood? (yes / no)"
+        );
+        assert!(matches!(confirm.answer(&world, "no"), Clarified::Cancelled));
+    }
+}
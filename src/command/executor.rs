@@ -1,37 +1,126 @@
 //! Command execution - converts resolved intents to tasks
+//!
+//! An order to build a known structure somewhere ("build a house in the
+//! east") places its blueprint there. Anyone named in the order is put to
+//! work on the site; the rest of the work is claimed by idle humans.
+//!
+//! While someone is challenging the player's leadership, no order is
+//! heeded; while an omen forbids fighting, no order to fight is. An order the parser was unsure of is not carried out at all; the result
+//! holds a `Clarification` with a question for the player instead.
+//!
+//! An order of several steps ("gather wood then build a wall") starts its
+//! first step and leaves the rest to a `CommandPlan` (see `command::plan`).
 
 use crate::actions::catalog::ActionId;
-use crate::command::resolver::{IntentResolution, IntentResolver};
+use crate::city::building::BuildingId;
+use crate::city::site::blueprint_named;
+use crate::command::clarify::{needs_clarification, Clarification};
+use crate::command::plan::{start_step, PlanId};
+use crate::command::resolver::{IntentResolution, IntentResolver, MatchReason, SubjectMatch};
 use crate::core::types::{EntityId, Tick};
 use crate::ecs::world::World;
 use crate::entity::tasks::{Task, TaskPriority, TaskSource};
 use crate::llm::parser::{IntentAction, IntentPriority, ParsedIntent};
+use crate::simulation::omens::fighting_forbidden;
+use crate::simulation::sites::place_blueprint;
+use crate::simulation::unrest::orders_refused;
 
 /// Executes commands by creating tasks for entities
 pub struct CommandExecutor;
 
 impl CommandExecutor {
     /// Execute a parsed intent, returning created tasks
+    ///
+    /// A multi-step intent becomes a plan; only its first step is carried
+    /// out now, and the result says which plan the rest wait in.
     pub fn execute(world: &mut World, intent: &ParsedIntent, tick: Tick) -> ExecutionResult {
+        if let Some(refusal) = orders_refused(world) {
+            return ExecutionResult::failed(refusal);
+        }
+        if intent
+            .steps()
+            .iter()
+            .any(|step| step.action == IntentAction::Combat)
+        {
+            if let Some(refusal) = fighting_forbidden(world) {
+                return ExecutionResult::failed(refusal);
+            }
+        }
+        if let Some(clarification) = needs_clarification(intent) {
+            return ExecutionResult::unclear(clarification);
+        }
+        if !intent.is_compound() {
+            return Self::execute_step(world, intent, &[], tick);
+        }
+        let id = world.plans.add(intent.steps(), tick);
+        let (progress, mut result) = start_step(world, id, 0, tick);
+        if result.error.is_some() {
+            world.plans.remove(id);
+            return result;
+        }
+        tracing::debug!("{}", progress.message);
+        result.plan = Some(id);
+        result
+    }
+
+    /// Execute one step of an order
+    ///
+    /// A step naming no subjects falls to `inherited`, those who did the
+    /// step before, if any of them are still alive.
+    pub fn execute_step(
+        world: &mut World,
+        intent: &ParsedIntent,
+        inherited: &[EntityId],
+        tick: Tick,
+    ) -> ExecutionResult {
         let resolver = IntentResolver::new(world);
-        let resolution = resolver.resolve(intent);
+        let mut resolution = resolver.resolve(intent);
+        if intent.subjects.is_none() {
+            resolution.subjects.extend(
+                inherited
+                    .iter()
+                    .filter_map(|&id| world.humans.index_of(id))
+                    .filter(|&i| world.humans.alive[i])
+                    .map(|i| SubjectMatch {
+                        entity_id: world.humans.ids[i],
+                        name: world.humans.names[i].clone(),
+                        match_reason: MatchReason::PreviousStep,
+                    }),
+            );
+        }
 
-        if resolution.subjects.is_empty() && needs_subjects(&intent.action) {
-            return ExecutionResult {
-                tasks_created: 0,
-                assigned_to: Vec::new(),
-                error: Some("No matching entities found for command".to_string()),
-            };
+        // Building a known structure at a known place marks out a site for it
+        let blueprint = match (&intent.action, &intent.target, resolution.location) {
+            (IntentAction::Build, Some(target), Some(position)) => {
+                blueprint_named(target).map(|name| (name, position))
+            }
+            _ => None,
+        };
+        let placed = match blueprint {
+            Some((name, position)) => match place_blueprint(world, name, position) {
+                Ok(building) => Some(building),
+                Err(e) => return ExecutionResult::failed(format!("Cannot place {}: {}", name, e)),
+            },
+            None => None,
+        };
+
+        if resolution.subjects.is_empty() && placed.is_none() && needs_subjects(&intent.action) {
+            return ExecutionResult::failed("No matching entities found for command");
         }
 
         let priority = convert_priority(intent.priority);
         let mut tasks_created = 0;
         let mut assigned_to = Vec::new();
+        let mut action = None;
 
         for subject in &resolution.subjects {
-            if let Some(task) = create_task(intent, &resolution, subject.entity_id, priority, tick)
-            {
+            let task = match placed {
+                Some(building) => Some(site_task(world, building, priority, tick)),
+                None => create_task(intent, &resolution, subject.entity_id, priority, tick),
+            };
+            if let Some(task) = task {
                 if let Some(idx) = world.humans.index_of(subject.entity_id) {
+                    action = Some(task.action);
                     world.humans.task_queues[idx].push(task);
                     tasks_created += 1;
                     assigned_to.push((subject.entity_id, subject.name.clone()));
@@ -42,6 +131,10 @@ impl CommandExecutor {
         ExecutionResult {
             tasks_created,
             assigned_to,
+            action,
+            placed,
+            plan: None,
+            clarification: None,
             error: None,
         }
     }
@@ -52,9 +145,43 @@ impl CommandExecutor {
 pub struct ExecutionResult {
     pub tasks_created: usize,
     pub assigned_to: Vec<(EntityId, String)>,
+    /// Action of the tasks created, if any
+    pub action: Option<ActionId>,
+    /// The building whose blueprint the command placed, if any
+    pub placed: Option<BuildingId>,
+    /// The plan holding the rest of a multi-step order
+    pub plan: Option<PlanId>,
+    /// The question to ask before the order can be carried out
+    pub clarification: Option<Clarification>,
     pub error: Option<String>,
 }
 
+impl ExecutionResult {
+    fn failed(error: impl Into<String>) -> Self {
+        Self {
+            tasks_created: 0,
+            assigned_to: Vec::new(),
+            action: None,
+            placed: None,
+            plan: None,
+            clarification: None,
+            error: Some(error.into()),
+        }
+    }
+
+    fn unclear(clarification: Clarification) -> Self {
+        Self {
+            tasks_created: 0,
+            assigned_to: Vec::new(),
+            action: None,
+            placed: None,
+            plan: None,
+            clarification: Some(clarification),
+            error: None,
+        }
+    }
+}
+
 fn needs_subjects(action: &IntentAction) -> bool {
     !matches!(action, IntentAction::Query)
 }
@@ -68,6 +195,19 @@ fn convert_priority(priority: IntentPriority) -> TaskPriority {
     }
 }
 
+/// Put someone to work on a newly placed site: hauling, unless it needs nothing
+fn site_task(world: &World, building: BuildingId, priority: TaskPriority, tick: Tick) -> Task {
+    let site = world.sites.iter().find(|site| site.building == building);
+    let action = match site {
+        Some(site) if !site.materials_ready() => ActionId::Haul,
+        _ => ActionId::Build,
+    };
+    let mut task = Task::new(action, priority, tick).with_building(building);
+    task.target_position = site.map(|site| site.position);
+    task.source = TaskSource::PlayerCommand;
+    task
+}
+
 fn create_task(
     intent: &ParsedIntent,
     resolution: &IntentResolution,
@@ -119,6 +259,7 @@ mod tests {
             priority: IntentPriority::Normal,
             ambiguous_concepts: Vec::new(),
             confidence: 0.9,
+            then: Vec::new(),
         };
 
         let result = CommandExecutor::execute(&mut world, &intent, 0);
@@ -131,4 +272,34 @@ mod tests {
         assert_eq!(task.action, ActionId::MoveTo);
         assert!(task.target_position.is_some());
     }
+
+    #[test]
+    fn test_build_command_places_blueprint() {
+        let mut world = World::new();
+        let marcus_id = world.spawn_human("Marcus".into());
+
+        let intent = ParsedIntent {
+            action: IntentAction::Build,
+            target: Some("stone wall".to_string()),
+            location: Some("east".to_string()),
+            subjects: Some(vec!["Marcus".to_string()]),
+            priority: IntentPriority::Normal,
+            ambiguous_concepts: Vec::new(),
+            confidence: 0.9,
+            then: Vec::new(),
+        };
+
+        let result = CommandExecutor::execute(&mut world, &intent, 0);
+
+        assert!(result.error.is_none());
+        let building = result.placed.unwrap();
+        assert_eq!(world.sites[0].building, building);
+        assert_eq!(world.sites[0].instance.blueprint_name, "stone_wall");
+
+        // The wall needs stone hauled before Marcus can build it
+        let idx = world.humans.index_of(marcus_id).unwrap();
+        let task = world.humans.task_queues[idx].current().unwrap();
+        assert_eq!(task.action, ActionId::Haul);
+        assert_eq!(task.target_building, Some(building));
+    }
 }
@@ -2,9 +2,22 @@
 //!
 //! Converts LLM ParsedIntent into executable Tasks:
 //! ParsedIntent -> IntentResolver -> IntentResolution -> TaskCreator -> Vec<Task>
+//!
+//! Orders the parser was unsure of wait on a Clarification from the player.
+//! Multi-step orders are tracked as CommandPlans and advanced each tick.
+//! Questions are answered from the world by a Query instead of executed.
+//! A Script line gives the same orders in a structured form, without the LLM.
 
+pub mod clarify;
 pub mod executor;
+pub mod plan;
+pub mod query;
 pub mod resolver;
+pub mod script;
 
+pub use clarify::{needs_clarification, Clarification, Clarified, Question};
 pub use executor::CommandExecutor;
+pub use plan::{advance_plans, CommandPlan, CommandPlans, PlanId, PlanProgress};
+pub use query::Query;
 pub use resolver::{IntentResolution, IntentResolver, SubjectMatch};
+pub use script::{parse_line, run_script, ScriptError};
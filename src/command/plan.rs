@@ -0,0 +1,374 @@
+//! Command plans - multi-step orders carried out one step after another
+//!
+//! "Gather wood then build a wall on the east side" is parsed as one intent
+//! with the steps after the first in `then`. The first step is executed at
+//! once; the rest wait in a `CommandPlan` until the step before is done:
+//! its subjects have no more of its tasks, and any site it placed is built.
+//! A later step with no subjects of its own falls to whoever did the step
+//! before. Each step started, finished, or failed is reported back to the
+//! player.
+
+use serde::{Deserialize, Serialize};
+
+use crate::actions::catalog::ActionId;
+use crate::city::building::{BuildingId, BuildingState};
+use crate::command::executor::{CommandExecutor, ExecutionResult};
+use crate::core::types::{EntityId, Tick};
+use crate::ecs::world::World;
+use crate::entity::tasks::TaskSource;
+use crate::llm::parser::ParsedIntent;
+
+/// Identifier of a command plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PlanId(pub u32);
+
+/// Where a step of a plan stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepStatus {
+    /// Waiting for the step before
+    Pending,
+    /// Its tasks have been handed out
+    Active,
+    Done,
+    /// It could not be carried out; the plan stops here
+    Failed,
+}
+
+/// One step of a plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanStep {
+    pub intent: ParsedIntent,
+    pub status: StepStatus,
+    /// Who was given the step's tasks
+    pub assigned: Vec<EntityId>,
+    /// Action of the tasks handed out, if any
+    pub action: Option<ActionId>,
+    /// Site the step placed, if any
+    pub placed: Option<BuildingId>,
+    /// Why the step failed
+    pub error: Option<String>,
+}
+
+impl PlanStep {
+    fn new(intent: ParsedIntent) -> Self {
+        Self {
+            intent,
+            status: StepStatus::Pending,
+            assigned: Vec::new(),
+            action: None,
+            placed: None,
+            error: None,
+        }
+    }
+
+    /// Short description, e.g. "build wall"
+    pub fn describe(&self) -> String {
+        let action = format!("{:?}", self.intent.action).to_lowercase();
+        match &self.intent.target {
+            Some(target) => format!("{} {}", action, target),
+            None => action,
+        }
+    }
+}
+
+/// An order of several steps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandPlan {
+    pub id: PlanId,
+    pub issued: Tick,
+    pub steps: Vec<PlanStep>,
+}
+
+impl CommandPlan {
+    /// Index of the step under way (or next to start), if the plan is not over
+    pub fn current_step(&self) -> Option<usize> {
+        if self.is_failed() {
+            return None;
+        }
+        self.steps
+            .iter()
+            .position(|step| step.status != StepStatus::Done)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|step| step.status == StepStatus::Failed)
+    }
+
+    /// Whether every step is done, or one failed
+    pub fn is_finished(&self) -> bool {
+        self.current_step().is_none()
+    }
+
+    /// One-line progress, e.g. "Plan 2: step 2/3, build wall"
+    pub fn progress(&self) -> String {
+        match self.current_step() {
+            Some(i) => format!(
+                "Plan {}: step {}/{}, {}",
+                self.id.0,
+                i + 1,
+                self.steps.len(),
+                self.steps[i].describe()
+            ),
+            None if self.is_failed() => format!("Plan {}: failed", self.id.0),
+            None => format!("Plan {}: done", self.id.0),
+        }
+    }
+}
+
+/// Plans under way
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommandPlans {
+    plans: Vec<CommandPlan>,
+    next_id: u32,
+}
+
+impl CommandPlans {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a plan for these steps; returns its id
+    pub fn add(&mut self, steps: Vec<ParsedIntent>, tick: Tick) -> PlanId {
+        let id = PlanId(self.next_id);
+        self.next_id += 1;
+        self.plans.push(CommandPlan {
+            id,
+            issued: tick,
+            steps: steps.into_iter().map(PlanStep::new).collect(),
+        });
+        id
+    }
+
+    /// Stop tracking a plan
+    pub fn remove(&mut self, id: PlanId) -> Option<CommandPlan> {
+        let index = self.plans.iter().position(|plan| plan.id == id)?;
+        Some(self.plans.remove(index))
+    }
+
+    pub fn get(&self, id: PlanId) -> Option<&CommandPlan> {
+        self.plans.iter().find(|plan| plan.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: PlanId) -> Option<&mut CommandPlan> {
+        self.plans.iter_mut().find(|plan| plan.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CommandPlan> {
+        self.plans.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plans.is_empty()
+    }
+}
+
+/// A change in a plan worth telling the player
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanProgress {
+    pub plan: PlanId,
+    /// Step the news is about (0-based)
+    pub step: usize,
+    pub steps: usize,
+    pub message: String,
+}
+
+/// Whether everything the step handed out has been done
+///
+/// Err if there is nobody left alive to do it.
+fn step_done(world: &World, step: &PlanStep) -> Result<bool, String> {
+    if let Some(building) = step.placed {
+        return match world.buildings.index_of(building) {
+            Some(b) => Ok(world.buildings.states[b] == BuildingState::Complete),
+            None => Err("its site is gone".into()),
+        };
+    }
+    let Some(action) = step.action else {
+        return Ok(true);
+    };
+    let living: Vec<usize> = step
+        .assigned
+        .iter()
+        .filter_map(|&id| world.humans.index_of(id))
+        .filter(|&i| world.humans.alive[i])
+        .collect();
+    if living.is_empty() && !step.assigned.is_empty() {
+        return Err("everyone given it has died".into());
+    }
+    Ok(!living.iter().any(|&i| {
+        world.humans.task_queues[i]
+            .iter()
+            .any(|t| t.action == action && t.source == TaskSource::PlayerCommand)
+    }))
+}
+
+/// Carry out the step at `index` of a plan, recording what it handed out
+pub(crate) fn start_step(
+    world: &mut World,
+    id: PlanId,
+    index: usize,
+    tick: Tick,
+) -> (PlanProgress, ExecutionResult) {
+    let plan = world.plans.get(id).expect("plan exists");
+    let steps = plan.steps.len();
+    let intent = plan.steps[index].intent.clone();
+    let inherited = match index {
+        0 => Vec::new(),
+        _ => plan.steps[index - 1].assigned.clone(),
+    };
+
+    let result = CommandExecutor::execute_step(world, &intent, &inherited, tick);
+    let step = &mut world.plans.get_mut(id).expect("plan exists").steps[index];
+    let message = match &result.error {
+        Some(error) => {
+            step.status = StepStatus::Failed;
+            step.error = Some(error.clone());
+            format!(
+                "Plan {}: step {}/{} ({}) failed: {}",
+                id.0,
+                index + 1,
+                steps,
+                step.describe(),
+                error
+            )
+        }
+        None => {
+            step.status = StepStatus::Active;
+            step.assigned = result.assigned_to.iter().map(|(id, _)| *id).collect();
+            step.action = result.action;
+            step.placed = result.placed;
+            format!(
+                "Plan {}: step {}/{} ({}) begun by {} worker(s)",
+                id.0,
+                index + 1,
+                steps,
+                step.describe(),
+                step.assigned.len()
+            )
+        }
+    };
+    let progress = PlanProgress {
+        plan: id,
+        step: index,
+        steps,
+        message,
+    };
+    (progress, result)
+}
+
+/// Move every plan along: finish steps whose work is done and start the
+/// next. Finished and failed plans are dropped once reported.
+pub fn advance_plans(world: &mut World) -> Vec<PlanProgress> {
+    let tick = world.current_tick;
+    let mut progress = Vec::new();
+    let ids: Vec<PlanId> = world.plans.iter().map(|plan| plan.id).collect();
+    for id in ids {
+        while let Some(index) = world.plans.get(id).and_then(CommandPlan::current_step) {
+            let plan = world.plans.get(id).expect("plan exists");
+            let steps = plan.steps.len();
+            let step = &plan.steps[index];
+            if step.status == StepStatus::Pending {
+                progress.push(start_step(world, id, index, tick).0);
+                continue;
+            }
+            let done = step_done(world, step);
+            let step = &mut world.plans.get_mut(id).expect("plan exists").steps[index];
+            match done {
+                Ok(false) => break,
+                Ok(true) => {
+                    step.status = StepStatus::Done;
+                    progress.push(PlanProgress {
+                        plan: id,
+                        step: index,
+                        steps,
+                        message: format!(
+                            "Plan {}: step {}/{} ({}) done",
+                            id.0,
+                            index + 1,
+                            steps,
+                            step.describe()
+                        ),
+                    });
+                }
+                Err(error) => {
+                    step.status = StepStatus::Failed;
+                    step.error = Some(error.clone());
+                    progress.push(PlanProgress {
+                        plan: id,
+                        step: index,
+                        steps,
+                        message: format!(
+                            "Plan {}: step {}/{} ({}) failed: {}",
+                            id.0,
+                            index + 1,
+                            steps,
+                            step.describe(),
+                            error
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    world.plans.plans.retain(|plan| !plan.is_finished());
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::parser::{IntentAction, IntentPriority};
+
+    fn intent(action: IntentAction, target: &str, location: Option<&str>) -> ParsedIntent {
+        ParsedIntent {
+            action,
+            target: Some(target.into()),
+            location: location.map(Into::into),
+            subjects: None,
+            priority: IntentPriority::Normal,
+            ambiguous_concepts: Vec::new(),
+            confidence: 0.9,
+            then: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_steps_run_in_order_by_the_same_workers() {
+        let mut world = World::with_seed(5);
+        let marcus = world.spawn_human("Marcus".into());
+        world.spawn_human("Ada".into());
+        let mut order = intent(IntentAction::Gather, "wood", None);
+        order.subjects = Some(vec!["Marcus".into()]);
+        order.then = vec![intent(IntentAction::Move, "the gate", Some("east"))];
+        assert_eq!(order.steps().len(), 2);
+
+        let result = CommandExecutor::execute(&mut world, &order, 0);
+        assert!(result.error.is_none());
+        let id = result.plan.unwrap();
+        let marcus_idx = world.humans.index_of(marcus).unwrap();
+        let current = |world: &World| world.humans.task_queues[marcus_idx].current().cloned();
+        assert_eq!(current(&world).unwrap().action, ActionId::Gather);
+        // Nothing of the second step is handed out yet
+        assert!(advance_plans(&mut world).is_empty());
+        assert_eq!(world.plans.get(id).unwrap().current_step(), Some(0));
+
+        world.humans.task_queues[marcus_idx].complete_current();
+        let progress = advance_plans(&mut world);
+        assert_eq!(progress.len(), 2);
+        assert!(progress[0].message.contains("step 1/2 (gather wood) done"));
+        assert!(progress[1].message.contains("begun by 1 worker"));
+        assert_eq!(current(&world).unwrap().action, ActionId::MoveTo);
+        assert_eq!(
+            world.plans.get(id).unwrap().progress(),
+            "Plan 0: step 2/2, move the gate"
+        );
+
+        world.humans.task_queues[marcus_idx].complete_current();
+        let progress = advance_plans(&mut world);
+        assert!(progress[0]
+            .message
+            .contains("step 2/2 (move the gate) done"));
+        assert!(world.plans.is_empty());
+    }
+}
@@ -0,0 +1,265 @@
+//! Queries - answering the player's questions about the settlement
+//!
+//! A question parsed as `IntentAction::Query` ("who is hungriest", "how
+//! much wood do we have", "what is Marcus doing") is read into a `Query` by
+//! its key words and answered from the world: needs, the stockpile, task
+//! queues, and the latest poll. Answers are plain text; the LLM may retell
+//! them in the steward's voice (see `llm::narrator::polish_answer`).
+
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::needs::{NeedType, Needs};
+use crate::simulation::resource_zone::ResourceType;
+
+/// What the player asked about
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    /// Who has the greatest need of one kind ("who is hungriest")
+    Neediest(NeedType),
+    /// How much of a resource is stored ("how much wood do we have")
+    Stock(ResourceType),
+    /// What someone is doing
+    Doing(EntityId),
+    /// Where someone is
+    Whereabouts(EntityId),
+    /// How many live in the settlement
+    Population,
+    /// How happy the settlement is
+    Mood,
+}
+
+/// Words naming each need, and how the neediest are described
+const NEED_WORDS: [(&str, NeedType, &str); 10] = [
+    ("hungr", NeedType::Food, "hungriest"),
+    ("starv", NeedType::Food, "hungriest"),
+    ("tired", NeedType::Rest, "most tired"),
+    ("exhaust", NeedType::Rest, "most tired"),
+    ("sleep", NeedType::Rest, "most tired"),
+    ("lonel", NeedType::Social, "loneliest"),
+    ("afraid", NeedType::Safety, "most afraid"),
+    ("scared", NeedType::Safety, "most afraid"),
+    ("danger", NeedType::Safety, "most afraid"),
+    ("bored", NeedType::Purpose, "most aimless"),
+];
+
+fn need_level(needs: &Needs, need: NeedType) -> f32 {
+    match need {
+        NeedType::Rest => needs.rest,
+        NeedType::Food => needs.food,
+        NeedType::Safety => needs.safety,
+        NeedType::Social => needs.social,
+        NeedType::Purpose => needs.purpose,
+    }
+}
+
+fn superlative(need: NeedType) -> &'static str {
+    NEED_WORDS
+        .iter()
+        .find(|(_, n, _)| *n == need)
+        .map(|(_, _, word)| *word)
+        .unwrap_or("neediest")
+}
+
+/// Someone named in the question, living or not
+fn named_in(world: &World, words: &[String]) -> Option<EntityId> {
+    words.iter().find_map(|word| {
+        let word = word.trim_end_matches("'s");
+        (0..world.humans.ids.len())
+            .find(|&i| world.humans.names[i].eq_ignore_ascii_case(word))
+            .map(|i| world.humans.ids[i])
+    })
+}
+
+impl Query {
+    /// Read a question by its key words; None if it is not understood
+    pub fn parse(world: &World, question: &str) -> Option<Query> {
+        let lower = question.to_lowercase();
+        let words: Vec<String> = lower
+            .split(|c: char| !c.is_alphanumeric() && c != '\'')
+            .filter(|word| !word.is_empty())
+            .map(String::from)
+            .collect();
+
+        if let Some(id) = named_in(world, &words) {
+            return Some(match lower.contains("where") {
+                true => Query::Whereabouts(id),
+                false => Query::Doing(id),
+            });
+        }
+        if let Some(resource) = words
+            .iter()
+            .find_map(|word| ResourceType::from_name(word.trim_end_matches('s')))
+        {
+            return Some(Query::Stock(resource));
+        }
+        if let Some(&(_, need, _)) = NEED_WORDS.iter().find(|(w, _, _)| lower.contains(w)) {
+            return Some(Query::Neediest(need));
+        }
+        let about = |keys: &[&str]| keys.iter().any(|key| lower.contains(key));
+        if about(&["how many", "population", "people", "settlers"]) {
+            Some(Query::Population)
+        } else if about(&["happ", "mood", "morale", "content", "unrest"]) {
+            Some(Query::Mood)
+        } else {
+            None
+        }
+    }
+
+    /// Answer the question from the world as it stands
+    pub fn answer(&self, world: &World) -> String {
+        match self {
+            Query::Neediest(need) => {
+                let neediest = world.humans.iter_living().max_by(|&a, &b| {
+                    let level = |i: usize| need_level(&world.humans.needs[i], *need);
+                    level(a).total_cmp(&level(b))
+                });
+                match neediest {
+                    Some(i) => format!(
+                        "{} is the {} ({:.0}%).",
+                        world.humans.names[i],
+                        superlative(*need),
+                        need_level(&world.humans.needs[i], *need) * 100.0
+                    ),
+                    None => "There is no one in the settlement.".to_string(),
+                }
+            }
+            Query::Stock(resource) => {
+                let stored = world.stockpile.get(*resource);
+                let carried: u32 = world
+                    .humans
+                    .iter_living()
+                    .map(|i| world.humans.possessions[i].get(*resource))
+                    .sum();
+                let name = format!("{:?}", resource).to_lowercase();
+                match carried {
+                    0 => format!("We have {} {} in the stockpile.", stored, name),
+                    _ => format!(
+                        "We have {} {} in the stockpile, and settlers hold {} more.",
+                        stored, name, carried
+                    ),
+                }
+            }
+            Query::Doing(id) => describe_doing(world, *id),
+            Query::Whereabouts(id) => describe_whereabouts(world, *id),
+            Query::Population => {
+                let living = world.humans.iter_living().count();
+                let away = world.exiles.away().count();
+                match away {
+                    0 => format!("{} settlers live here.", living),
+                    _ => format!("{} settlers live here; {} are in exile.", living, away),
+                }
+            }
+            Query::Mood => match &world.happiness.latest {
+                Some(poll) => {
+                    let mut text = format!(
+                        "Happiness is {:.0}%, with {:.0}% of settlers content.",
+                        poll.happiness * 100.0,
+                        poll.approval * 100.0
+                    );
+                    if let Some(factor) = poll.worst_factor() {
+                        text.push_str(&format!(" The worst grievance is {}.", factor.name()));
+                    }
+                    if world.happiness.unrest {
+                        text.push_str(" The settlement is in unrest.");
+                    }
+                    text
+                }
+                None => "No one has been asked yet; the first poll is at dawn.".to_string(),
+            },
+        }
+    }
+}
+
+/// Why someone cannot be doing anything, if they cannot
+fn absent(world: &World, i: usize) -> Option<String> {
+    let name = &world.humans.names[i];
+    let id = world.humans.ids[i];
+    if world.exiles.is_away(id) {
+        Some(format!("{} is in exile.", name))
+    } else if !world.humans.alive[i] {
+        Some(format!("{} is dead.", name))
+    } else {
+        None
+    }
+}
+
+fn describe_doing(world: &World, id: EntityId) -> String {
+    let Some(i) = world.humans.index_of(id) else {
+        return "No one by that name.".to_string();
+    };
+    if let Some(absent) = absent(world, i) {
+        return absent;
+    }
+    let name = &world.humans.names[i];
+    let queue = &world.humans.task_queues[i];
+    let Some(task) = queue.current() else {
+        return format!("{} is idle.", name);
+    };
+    let mut text = format!("{} is doing {:?}", name, task.action);
+    if let Some(target) = task
+        .target_entity
+        .and_then(|target| world.humans.index_of(target))
+    {
+        text.push_str(&format!(" with {}", world.humans.names[target]));
+    } else if let Some(pos) = task.target_position {
+        text.push_str(&format!(" at ({:.0}, {:.0})", pos.x, pos.y));
+    }
+    text.push_str(&format!(" ({:.0}% done)", task.progress * 100.0));
+    match queue.queued_len() {
+        0 => text.push('.'),
+        n => text.push_str(&format!(", with {} more task(s) waiting.", n)),
+    }
+    text
+}
+
+fn describe_whereabouts(world: &World, id: EntityId) -> String {
+    let Some(i) = world.humans.index_of(id) else {
+        return "No one by that name.".to_string();
+    };
+    if let Some(absent) = absent(world, i) {
+        return absent;
+    }
+    let pos = world.humans.positions[i];
+    format!(
+        "{} is at ({:.0}, {:.0}).",
+        world.humans.names[i], pos.x, pos.y
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::catalog::ActionId;
+    use crate::entity::tasks::{Task, TaskPriority};
+
+    #[test]
+    fn test_questions_are_answered_from_the_world() {
+        let mut world = World::with_seed(31);
+        world.spawn_human("Marcus".into());
+        world.spawn_human("Ada".into());
+        world.humans.needs[1].food = 0.9;
+        world.stockpile.add(ResourceType::Wood, 40);
+        world.humans.task_queues[0].push(Task::new(ActionId::Gather, TaskPriority::Normal, 0));
+
+        let ask = |world: &World, question: &str| {
+            Query::parse(world, question)
+                .map(|query| query.answer(world))
+                .unwrap_or_default()
+        };
+        assert_eq!(
+            ask(&world, "who is hungriest?"),
+            "Ada is the hungriest (90%)."
+        );
+        assert_eq!(
+            ask(&world, "How much wood do we have?"),
+            "We have 40 wood in the stockpile."
+        );
+        assert!(ask(&world, "what is Marcus doing").starts_with("Marcus is doing Gather"));
+        assert_eq!(ask(&world, "what is ada doing?"), "Ada is idle.");
+        assert_eq!(
+            ask(&world, "how many people live here"),
+            "2 settlers live here."
+        );
+        assert_eq!(Query::parse(&world, "why is the sky blue"), None);
+    }
+}
@@ -31,6 +31,7 @@ pub enum MatchReason {
     PartialName,
     Qualification { skill: String, level: f32 },
     Everyone,
+    PreviousStep,
 }
 
 /// Resolves ParsedIntent subjects and locations to concrete entities/positions
@@ -190,7 +191,12 @@ impl<'a> IntentResolver<'a> {
         let loc = location.as_ref()?;
         let loc_lower = loc.to_lowercase();
 
-        // Named locations (expand as needed)
+        // Places the settlers have named ("Redford Field")
+        if let Some(place) = self.world.places.mentioned_in(loc) {
+            return Some(place.position);
+        }
+
+        // Compass directions
         if loc_lower.contains("center") || loc_lower.contains("middle") {
             return Some(Vec2::new(100.0, 100.0));
         }
@@ -207,9 +213,11 @@ impl<'a> IntentResolver<'a> {
             return Some(Vec2::new(100.0, 20.0));
         }
 
-        // TODO: Parse coordinates like "50, 100"
-
-        None
+        // Coordinates like "50, 100" or "(50, 100)"
+        let (x, y) = loc
+            .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+            .split_once(',')?;
+        Some(Vec2::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
     }
 
     fn resolve_target(&self, _target: &Option<String>) -> Option<EntityId> {
@@ -222,6 +230,7 @@ impl<'a> IntentResolver<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::world::{Place, PlaceKind};
 
     #[test]
     fn test_resolve_by_name() {
@@ -235,6 +244,27 @@ mod tests {
         assert_eq!(matches[0].entity_id, marcus_id);
     }
 
+    #[test]
+    fn test_resolve_named_place() {
+        let mut world = World::new();
+        world.places.places.push(Place {
+            name: "Northmere Field".into(),
+            kind: PlaceKind::BattleSite,
+            position: Vec2::new(120.0, 80.0),
+            named: 0,
+        });
+
+        let resolver = IntentResolver::new(&world);
+        let to = |loc: &str| {
+            resolver
+                .resolve_location(&Some(loc.to_string()))
+                .map(|p| (p.x, p.y))
+        };
+        // The name wins over the compass word inside it
+        assert_eq!(to("northmere field"), Some((120.0, 80.0)));
+        assert_eq!(to("north"), Some((100.0, 180.0)));
+    }
+
     #[test]
     fn test_resolve_everyone() {
         let mut world = World::new();
@@ -0,0 +1,300 @@
+//! Script - a small structured command language
+//!
+//! Everything the LLM path can order is also reachable by typed commands,
+//! with no API key needed, and whole scripts can be run in tests:
+//!
+//! ```text
+//! # comments and blank lines are skipped
+//! spawn Marcus
+//! spawn dwarf Thorin
+//! place wooden_house 50 60
+//! assign Marcus gather wood @(12,30) !high
+//! assign Marcus,Elena build wall @east then rest
+//! assign everyone move @center
+//! priority Elena critical
+//! ```
+//!
+//! `assign` builds the same `ParsedIntent` the LLM would, with full
+//! confidence; steps after `then` with no one named fall to those who did
+//! the step before. `priority` changes the priority of someone's orders.
+//! `spawn` brings in a human, or a dwarf or elf when the species is named
+//! first.
+
+use thiserror::Error;
+
+use crate::command::clarify::action_named;
+use crate::core::types::Vec2;
+use crate::ecs::world::World;
+use crate::entity::tasks::TaskPriority;
+use crate::llm::parser::{IntentPriority, ParsedIntent};
+use crate::simulation::replay::{apply_command, CommandOutcome, PlayerCommand};
+
+/// Why a script line could not be read
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ScriptError {
+    #[error("unknown command \"{0}\"")]
+    UnknownCommand(String),
+    #[error("unknown action \"{0}\"")]
+    UnknownAction(String),
+    #[error("no one living is named {0}")]
+    UnknownPerson(String),
+    #[error("unknown priority \"{0}\" (critical, high, normal, or low)")]
+    UnknownPriority(String),
+    #[error("bad position \"{0}\"")]
+    BadPosition(String),
+    #[error("usage: {0}")]
+    Usage(&'static str),
+    #[error("line {line}: {error}")]
+    AtLine {
+        line: usize,
+        error: Box<ScriptError>,
+    },
+}
+
+const ASSIGN_USAGE: &str =
+    "assign <who>[,<who>] <action> [target] [@place|@(x,y)] [!priority] [then ...]";
+const PRIORITY_USAGE: &str = "priority <name> <critical|high|normal|low>";
+const SPAWN_USAGE: &str = "spawn [dwarf|elf] <name>";
+const PLACE_USAGE: &str = "place <blueprint> <x> <y>";
+
+fn priority_named(word: &str) -> Result<IntentPriority, ScriptError> {
+    match word.to_lowercase().as_str() {
+        "critical" => Ok(IntentPriority::Critical),
+        "high" => Ok(IntentPriority::High),
+        "normal" => Ok(IntentPriority::Normal),
+        "low" => Ok(IntentPriority::Low),
+        _ => Err(ScriptError::UnknownPriority(word.to_string())),
+    }
+}
+
+/// The living human with this name, case-insensitively
+fn living_named(world: &World, name: &str) -> Result<usize, ScriptError> {
+    world
+        .humans
+        .iter_living()
+        .find(|&i| world.humans.names[i].eq_ignore_ascii_case(name))
+        .ok_or_else(|| ScriptError::UnknownPerson(name.to_string()))
+}
+
+/// Split words, keeping a position like "@(12, 30)" together
+fn tokens(line: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut open = false;
+    for word in line.split_whitespace() {
+        match tokens.last_mut() {
+            Some(last) if open => last.push_str(word),
+            _ => tokens.push(word.to_string()),
+        }
+        let last = tokens.last().expect("just pushed");
+        open = last.starts_with("@(") && !last.ends_with(')');
+    }
+    tokens
+}
+
+/// One step of an assignment: `<action> [target] [@place] [!priority]`
+fn parse_step(words: &[String]) -> Result<ParsedIntent, ScriptError> {
+    let (action, rest) = words
+        .split_first()
+        .ok_or(ScriptError::Usage(ASSIGN_USAGE))?;
+    let action = action_named(&action.to_lowercase())
+        .ok_or_else(|| ScriptError::UnknownAction(action.clone()))?;
+    let mut step = ParsedIntent {
+        action,
+        confidence: 1.0,
+        ..Default::default()
+    };
+    let mut target = Vec::new();
+    for word in rest {
+        if let Some(place) = word.strip_prefix('@') {
+            if place.starts_with('(') {
+                let inner = place.trim_start_matches('(').trim_end_matches(')');
+                let valid = inner
+                    .split_once(',')
+                    .is_some_and(|(x, y)| x.parse::<f32>().is_ok() && y.parse::<f32>().is_ok());
+                if !valid {
+                    return Err(ScriptError::BadPosition(word.clone()));
+                }
+                step.location = Some(inner.to_string());
+            } else {
+                step.location = Some(place.to_string());
+            }
+        } else if let Some(priority) = word.strip_prefix('!') {
+            step.priority = priority_named(priority)?;
+        } else {
+            target.push(word.as_str());
+        }
+    }
+    if !target.is_empty() {
+        step.target = Some(target.join(" "));
+    }
+    Ok(step)
+}
+
+/// `assign <who> <step> [then <step>]...`
+fn parse_assign(world: &World, words: &[String]) -> Result<ParsedIntent, ScriptError> {
+    let (who, rest) = words
+        .split_first()
+        .ok_or(ScriptError::Usage(ASSIGN_USAGE))?;
+    let subjects: Vec<String> = who
+        .split(',')
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect();
+    for name in &subjects {
+        if !matches!(name.to_lowercase().as_str(), "everyone" | "all") {
+            living_named(world, name)?;
+        }
+    }
+    let mut steps = rest
+        .split(|word| word.eq_ignore_ascii_case("then"))
+        .map(parse_step);
+    let mut intent = steps.next().ok_or(ScriptError::Usage(ASSIGN_USAGE))??;
+    intent.subjects = Some(subjects);
+    intent.then = steps.collect::<Result<_, _>>()?;
+    Ok(intent)
+}
+
+/// Read one line of script; None for a blank line or a comment
+pub fn parse_line(world: &World, line: &str) -> Result<Option<PlayerCommand>, ScriptError> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let words = tokens(line);
+    let (verb, args) = words.split_first().expect("line is not empty");
+    let command = match verb.to_lowercase().as_str() {
+        "assign" => PlayerCommand::Intent(parse_assign(world, args)?),
+        "priority" => {
+            let [name, level] = args else {
+                return Err(ScriptError::Usage(PRIORITY_USAGE));
+            };
+            let entity = world.humans.ids[living_named(world, name)?];
+            let priority = match priority_named(level)? {
+                IntentPriority::Critical => TaskPriority::Critical,
+                IntentPriority::High => TaskPriority::High,
+                IntentPriority::Normal => TaskPriority::Normal,
+                IntentPriority::Low => TaskPriority::Low,
+            };
+            PlayerCommand::Prioritize { entity, priority }
+        }
+        "spawn" => {
+            let species = args.first().map(|word| word.to_lowercase());
+            match (species.as_deref(), args) {
+                (_, []) | (Some("dwarf" | "elf"), [_]) => {
+                    return Err(ScriptError::Usage(SPAWN_USAGE))
+                }
+                (Some("dwarf"), [_, names @ ..]) => PlayerCommand::SpawnDwarf {
+                    name: names.join(" "),
+                },
+                (Some("elf"), [_, names @ ..]) => PlayerCommand::SpawnElf {
+                    name: names.join(" "),
+                },
+                (_, names) => PlayerCommand::SpawnHuman {
+                    name: names.join(" "),
+                },
+            }
+        }
+        "place" => {
+            let [blueprint, x, y] = args else {
+                return Err(ScriptError::Usage(PLACE_USAGE));
+            };
+            let bad = || ScriptError::BadPosition(format!("{} {}", x, y));
+            PlayerCommand::PlaceBlueprint {
+                blueprint: blueprint.clone(),
+                position: Vec2::new(x.parse().map_err(|_| bad())?, y.parse().map_err(|_| bad())?),
+            }
+        }
+        _ => return Err(ScriptError::UnknownCommand(verb.clone())),
+    };
+    Ok(Some(command))
+}
+
+/// Run a whole script, line by line, stopping at the first line that cannot
+/// be read
+pub fn run_script(world: &mut World, script: &str) -> Result<Vec<CommandOutcome>, ScriptError> {
+    let mut outcomes = Vec::new();
+    for (n, line) in script.lines().enumerate() {
+        let command = parse_line(world, line).map_err(|error| ScriptError::AtLine {
+            line: n + 1,
+            error: Box::new(error),
+        })?;
+        if let Some(command) = command {
+            outcomes.push(apply_command(world, &command));
+        }
+    }
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::catalog::ActionId;
+    use crate::llm::parser::IntentAction;
+
+    #[test]
+    fn test_assign_reads_like_a_parsed_order() {
+        let mut world = World::with_seed(51);
+        world.spawn_human("Marcus".into());
+        world.spawn_human("Elena".into());
+
+        let Some(PlayerCommand::Intent(intent)) = parse_line(
+            &world,
+            "assign Marcus gather wood @(12, 30) !high then build wall @east",
+        )
+        .unwrap() else {
+            panic!("assign makes an order");
+        };
+        assert_eq!(intent.action, IntentAction::Gather);
+        assert_eq!(intent.target.as_deref(), Some("wood"));
+        assert_eq!(intent.location.as_deref(), Some("12,30"));
+        assert_eq!(intent.priority, IntentPriority::High);
+        assert_eq!(intent.subjects, Some(vec!["Marcus".to_string()]));
+        assert_eq!(intent.then.len(), 1);
+        assert_eq!(intent.then[0].action, IntentAction::Build);
+        assert_eq!(intent.then[0].location.as_deref(), Some("east"));
+
+        assert_eq!(
+            parse_line(&world, "assign Nobody rest").unwrap_err(),
+            ScriptError::UnknownPerson("Nobody".into())
+        );
+        assert_eq!(
+            parse_line(&world, "assign Marcus dance").unwrap_err(),
+            ScriptError::UnknownAction("dance".into())
+        );
+    }
+
+    #[test]
+    fn test_script_runs_orders_and_priorities() {
+        let mut world = World::with_seed(52);
+        let script = "# a morning's work\nspawn Marcus\n\nassign Marcus move @(40,50)\npriority Marcus critical\n";
+        let outcomes = run_script(&mut world, script).unwrap();
+        assert_eq!(outcomes.len(), 3);
+        assert!(matches!(outcomes[2], CommandOutcome::Prioritized(1)));
+        let task = world.humans.task_queues[0].current().unwrap();
+        assert_eq!(task.action, ActionId::MoveTo);
+        assert_eq!(task.priority, TaskPriority::Critical);
+        let pos = task.target_position.unwrap();
+        assert_eq!((pos.x, pos.y), (40.0, 50.0));
+
+        let error = run_script(&mut world, "spawn Ada\nfly away").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: unknown command \"fly\"");
+    }
+
+    #[test]
+    fn test_spawn_names_the_species_first() {
+        let mut world = World::with_seed(53);
+        run_script(
+            &mut world,
+            "spawn dwarf Thorin Oakenshield\nspawn Elf Aelwen\nspawn Ada",
+        )
+        .unwrap();
+        assert_eq!(world.dwarves.names, vec!["Thorin Oakenshield"]);
+        assert_eq!(world.elves.names, vec!["Aelwen"]);
+        assert_eq!(world.humans.names, vec!["Ada"]);
+
+        assert_eq!(
+            parse_line(&world, "spawn dwarf").unwrap_err(),
+            ScriptError::Usage(SPAWN_USAGE)
+        );
+    }
+}
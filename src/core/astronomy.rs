@@ -13,8 +13,8 @@ use serde::{Deserialize, Serialize};
 /// Number of days in a year
 pub const YEAR_LENGTH: u16 = 360;
 
-/// Number of simulation ticks per day
-pub const TICKS_PER_DAY: u64 = 1000;
+// Number of simulation ticks per day, from the clock every layer shares
+pub use crate::core::time::TICKS_PER_DAY;
 
 /// Silver Moon (Argent) orbital period in days
 pub const ARGENT_PERIOD: u16 = 29;
@@ -126,6 +126,28 @@ impl Season {
             _ => Season::Winter, // 271-360
         }
     }
+
+    /// First day of the season within the year
+    pub fn first_day(&self) -> u16 {
+        match self {
+            Season::Spring => 1,
+            Season::Summer => 91,
+            Season::Autumn => 181,
+            Season::Winter => 271,
+        }
+    }
+
+    /// Parse a season from its name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let season = match name.to_lowercase().as_str() {
+            "spring" => Season::Spring,
+            "summer" => Season::Summer,
+            "autumn" | "fall" => Season::Autumn,
+            "winter" => Season::Winter,
+            _ => return None,
+        };
+        Some(season)
+    }
 }
 
 /// Celestial events that can occur
@@ -339,7 +361,12 @@ impl AstronomicalState {
 
     /// Advance simulation by one tick
     pub fn advance_tick(&mut self) {
-        self.tick += 1;
+        self.advance_ticks(1);
+    }
+
+    /// Advance by `ticks` ticks at once
+    pub fn advance_ticks(&mut self, ticks: u64) {
+        self.tick += ticks;
 
         let new_day = (self.tick / self.ticks_per_day) as u32;
 
@@ -452,6 +479,17 @@ impl AstronomicalState {
         ((tick_in_day * 24) / self.ticks_per_day) as u32
     }
 
+    /// Days until a season next begins (a full year if it begins today)
+    pub fn days_until(&self, season: Season) -> u32 {
+        let first = season.first_day() as u32;
+        let today = self.day_of_year as u32;
+        if first > today {
+            first - today
+        } else {
+            first + YEAR_LENGTH as u32 - today
+        }
+    }
+
     /// Get TimePeriod for backward compatibility with expectations system
     pub fn time_period(&self) -> TimePeriod {
         TimePeriod::from(self.solar_phase)
@@ -482,64 +520,71 @@ impl AstronomicalState {
         let start_day = self.current_day;
 
         for day in start_day..(start_day + days) {
-            let argent = MoonState::new(day, ARGENT_PERIOD, ARGENT_NODE_PRECESSION);
-            let sanguine = MoonState::new(day, SANGUINE_PERIOD, SANGUINE_NODE_PRECESSION);
-
-            let mut day_events = Vec::new();
+            let day_events = Self::events_for_day(day);
 
-            // Check moon phases
-            if argent.is_full() {
-                day_events.push(CelestialEvent::FullArgent);
-            }
-            if argent.is_new() {
-                day_events.push(CelestialEvent::NewArgent);
-            }
-            if sanguine.is_full() {
-                day_events.push(CelestialEvent::FullSanguine);
-            }
-            if sanguine.is_new() {
-                day_events.push(CelestialEvent::NewSanguine);
+            // Only store non-empty event lists
+            if !day_events.is_empty() {
+                self.event_calendar.insert(day, day_events);
             }
+        }
+    }
 
-            // Check double events
-            if argent.is_full() && sanguine.is_full() {
-                if (argent.phase - 0.5).abs() < 0.02 && (sanguine.phase - 0.5).abs() < 0.02 {
-                    day_events.push(CelestialEvent::PerfectDoubleFull);
-                } else {
-                    day_events.push(CelestialEvent::NearDoubleFull);
-                }
-            }
-            if argent.is_new() && sanguine.is_new() {
-                if argent.phase < 0.02 && sanguine.phase < 0.02 {
-                    day_events.push(CelestialEvent::PerfectDoubleNew);
-                } else {
-                    day_events.push(CelestialEvent::NearDoubleNew);
-                }
-            }
+    /// Celestial events on a given day, worked out from the moons' cycles
+    pub fn events_for_day(day: u32) -> Vec<CelestialEvent> {
+        let argent = MoonState::new(day, ARGENT_PERIOD, ARGENT_NODE_PRECESSION);
+        let sanguine = MoonState::new(day, SANGUINE_PERIOD, SANGUINE_NODE_PRECESSION);
+
+        let mut day_events = Vec::new();
 
-            // Check eclipses
-            let day_of_year = ((day % YEAR_LENGTH as u32) + 1) as u16;
-            let sun_longitude = (day_of_year as f32 / YEAR_LENGTH as f32) * 360.0;
+        // Check moon phases
+        if argent.is_full() {
+            day_events.push(CelestialEvent::FullArgent);
+        }
+        if argent.is_new() {
+            day_events.push(CelestialEvent::NewArgent);
+        }
+        if sanguine.is_full() {
+            day_events.push(CelestialEvent::FullSanguine);
+        }
+        if sanguine.is_new() {
+            day_events.push(CelestialEvent::NewSanguine);
+        }
 
-            if argent.is_new() && argent.eclipse_possible(sun_longitude) {
-                day_events.push(CelestialEvent::SilverEclipse);
+        // Check double events
+        if argent.is_full() && sanguine.is_full() {
+            if (argent.phase - 0.5).abs() < 0.02 && (sanguine.phase - 0.5).abs() < 0.02 {
+                day_events.push(CelestialEvent::PerfectDoubleFull);
+            } else {
+                day_events.push(CelestialEvent::NearDoubleFull);
             }
-            if sanguine.is_new() && sanguine.eclipse_possible(sun_longitude) {
-                day_events.push(CelestialEvent::BloodEclipse);
+        }
+        if argent.is_new() && sanguine.is_new() {
+            if argent.phase < 0.02 && sanguine.phase < 0.02 {
+                day_events.push(CelestialEvent::PerfectDoubleNew);
+            } else {
+                day_events.push(CelestialEvent::NearDoubleNew);
             }
+        }
 
-            // Check for double eclipse
-            if day_events.contains(&CelestialEvent::SilverEclipse)
-                && day_events.contains(&CelestialEvent::BloodEclipse)
-            {
-                day_events.push(CelestialEvent::DoubleEclipse);
-            }
+        // Check eclipses
+        let day_of_year = ((day % YEAR_LENGTH as u32) + 1) as u16;
+        let sun_longitude = (day_of_year as f32 / YEAR_LENGTH as f32) * 360.0;
 
-            // Only store non-empty event lists
-            if !day_events.is_empty() {
-                self.event_calendar.insert(day, day_events);
-            }
+        if argent.is_new() && argent.eclipse_possible(sun_longitude) {
+            day_events.push(CelestialEvent::SilverEclipse);
         }
+        if sanguine.is_new() && sanguine.eclipse_possible(sun_longitude) {
+            day_events.push(CelestialEvent::BloodEclipse);
+        }
+
+        // Check for double eclipse
+        if day_events.contains(&CelestialEvent::SilverEclipse)
+            && day_events.contains(&CelestialEvent::BloodEclipse)
+        {
+            day_events.push(CelestialEvent::DoubleEclipse);
+        }
+
+        day_events
     }
 
     /// Find the next occurrence of a specific event type
@@ -954,6 +999,21 @@ mod tests {
         assert_eq!(Season::from_day_of_year(270), Season::Autumn);
         assert_eq!(Season::from_day_of_year(271), Season::Winter);
         assert_eq!(Season::from_day_of_year(360), Season::Winter);
+        for season in [Season::Spring, Season::Summer, Season::Autumn, Season::Winter] {
+            assert_eq!(Season::from_day_of_year(season.first_day()), season);
+        }
+        assert_eq!(Season::from_name("Winter"), Some(Season::Winter));
+        assert_eq!(Season::from_name("monsoon"), None);
+    }
+
+    #[test]
+    fn test_days_until_season() {
+        let mut state = AstronomicalState::new(1000);
+        state.day_of_year = 250;
+        assert_eq!(state.days_until(Season::Winter), 21);
+        assert_eq!(state.days_until(Season::Spring), 111);
+        state.day_of_year = 271;
+        assert_eq!(state.days_until(Season::Winter), 360);
     }
 
     #[test]
@@ -14,6 +14,10 @@ pub enum ArcError {
     #[error("LLM error: {0}")]
     LlmError(String),
 
+    /// The LLM service could not be reached or is overloaded; worth retrying
+    #[error("LLM unavailable: {0}")]
+    LlmUnavailable(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -22,6 +26,9 @@ pub enum ArcError {
 
     #[error("Navigation error: {0}")]
     NavigationError(String),
+
+    #[error("Replay error: {0}")]
+    ReplayError(#[from] crate::simulation::replay::ReplayError),
 }
 
 pub type Result<T> = std::result::Result<T, ArcError>;
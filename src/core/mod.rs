@@ -2,6 +2,8 @@ pub mod astronomy;
 pub mod calendar;
 pub mod config;
 pub mod error;
+pub mod time;
 pub mod types;
 
 pub use calendar::{Calendar, TimePeriod};
+pub use time::{ClockError, Timestamp, WorldClock};
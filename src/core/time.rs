@@ -0,0 +1,197 @@
+//! World time - one clock shared by every layer
+//!
+//! Each layer counts time its own way: the settlement and the sky in ticks
+//! (`TICKS_PER_DAY` to the day), the campaign in fractional days, and a
+//! battle in ticks of `BATTLE_TICK_SIM_SECONDS` each. A `Timestamp` is the
+//! canonical instant - settlement ticks since the world began - and converts
+//! to and from each layer's count, so events from any layer can be stamped
+//! on the one timeline the chronicle keeps.
+//!
+//! `WorldClock` is the synchronization contract. While another layer holds
+//! the clock (a battle being fought) the settlement stands still; when that
+//! layer lets go it reports how long it ran, and the settlement lives
+//! through that time before going on (see `simulation::time_sync`).
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::battle::constants::BATTLE_TICK_SIM_SECONDS;
+use crate::core::types::Tick;
+use crate::world::identity::Layer;
+
+/// Settlement ticks in a day, for every layer that counts in them
+pub const TICKS_PER_DAY: u64 = 1000;
+
+/// Seconds in a day
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Seconds one settlement tick covers
+pub const SECONDS_PER_TICK: f32 = SECONDS_PER_DAY as f32 / TICKS_PER_DAY as f32;
+
+/// Why the clock could not be held or released
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockError {
+    #[error("The clock is already held by the {0:?} layer")]
+    AlreadyHeld(Layer),
+    #[error("The clock is not held by the {0:?} layer")]
+    NotHeld(Layer),
+}
+
+/// Days, as the campaign counts them, in `ticks` settlement ticks
+pub fn ticks_to_days(ticks: Tick) -> f32 {
+    ticks as f32 / TICKS_PER_DAY as f32
+}
+
+/// Settlement ticks in `days` days, to the tick below
+pub fn days_to_ticks(days: f32) -> Tick {
+    (days.max(0.0) * TICKS_PER_DAY as f32) as Tick
+}
+
+/// Settlement ticks a battle of `battle_ticks` takes, to the tick above:
+/// a fight however short takes up some of the settlement's day
+pub fn battle_ticks_to_ticks(battle_ticks: u64) -> Tick {
+    (battle_ticks as f32 * BATTLE_TICK_SIM_SECONDS / SECONDS_PER_TICK).ceil() as Tick
+}
+
+/// Battle ticks in `ticks` settlement ticks
+pub fn ticks_to_battle_ticks(ticks: Tick) -> u64 {
+    (ticks as f32 * SECONDS_PER_TICK / BATTLE_TICK_SIM_SECONDS) as u64
+}
+
+/// An instant on the shared timeline: settlement ticks since the world began
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Timestamp(pub Tick);
+
+impl Timestamp {
+    pub fn tick(self) -> Tick {
+        self.0
+    }
+
+    /// The day it falls on, counting from 0
+    pub fn day(self) -> u32 {
+        (self.0 / TICKS_PER_DAY) as u32
+    }
+
+    /// Hour of the day (0-23)
+    pub fn hour(self) -> u32 {
+        ((self.0 % TICKS_PER_DAY) * 24 / TICKS_PER_DAY) as u32
+    }
+
+    /// The campaign day it falls in, with the time of day as a fraction
+    pub fn campaign_day(self) -> f32 {
+        ticks_to_days(self.0)
+    }
+
+    pub fn from_campaign_day(day: f32) -> Self {
+        Self(days_to_ticks(day))
+    }
+
+    /// The instant `battle_tick` ticks into a battle that began at `self`
+    pub fn at_battle_tick(self, battle_tick: u64) -> Self {
+        let seconds = battle_tick as f32 * BATTLE_TICK_SIM_SECONDS;
+        Self(self.0 + (seconds / SECONDS_PER_TICK) as Tick)
+    }
+
+    /// The instant `ticks` settlement ticks later
+    pub fn after(self, ticks: Tick) -> Self {
+        Self(self.0 + ticks)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minutes = (self.0 % TICKS_PER_DAY) * 24 * 60 / TICKS_PER_DAY;
+        write!(
+            f,
+            "day {}, {:02}:{:02}",
+            self.day() + 1,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+}
+
+/// Which layer, if any, has stopped the settlement's clock, and since when
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorldClock {
+    held: Option<(Layer, Timestamp)>,
+}
+
+impl WorldClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop the settlement's clock at `now` while `layer` runs
+    pub fn hold(&mut self, layer: Layer, now: Timestamp) -> Result<(), ClockError> {
+        if let Some((holder, _)) = self.held {
+            return Err(ClockError::AlreadyHeld(holder));
+        }
+        self.held = Some((layer, now));
+        Ok(())
+    }
+
+    /// `layer` lets go after running `elapsed` settlement ticks; returns the
+    /// instant the settlement must catch up to
+    pub fn release(&mut self, layer: Layer, elapsed: Tick) -> Result<Timestamp, ClockError> {
+        match self.held {
+            Some((holder, since)) if holder == layer => {
+                self.held = None;
+                Ok(since.after(elapsed))
+            }
+            _ => Err(ClockError::NotHeld(layer)),
+        }
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.held.is_some()
+    }
+
+    /// The layer holding the clock and the instant it stopped, if held
+    pub fn held(&self) -> Option<(Layer, Timestamp)> {
+        self.held
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layers_convert_through_one_timeline() {
+        let noon_day_three = Timestamp(2 * TICKS_PER_DAY + TICKS_PER_DAY / 2);
+        assert_eq!(noon_day_three.day(), 2);
+        assert_eq!(noon_day_three.hour(), 12);
+        assert_eq!(noon_day_three.campaign_day(), 2.5);
+        assert_eq!(Timestamp::from_campaign_day(2.5), noon_day_three);
+        assert_eq!(noon_day_three.to_string(), "day 3, 12:00");
+
+        // A five-minute battle takes four settlement ticks (86.4 s each)
+        assert_eq!(battle_ticks_to_ticks(300), 4);
+        assert_eq!(battle_ticks_to_ticks(0), 0);
+        assert_eq!(ticks_to_battle_ticks(TICKS_PER_DAY), SECONDS_PER_DAY);
+        // Events within the battle fall inside its span
+        assert_eq!(noon_day_three.at_battle_tick(100), noon_day_three.after(1));
+        assert!(noon_day_three.at_battle_tick(300) <= noon_day_three.after(4));
+    }
+
+    #[test]
+    fn test_clock_is_held_by_one_layer_at_a_time() {
+        let mut clock = WorldClock::new();
+        clock.hold(Layer::Battle, Timestamp(50)).unwrap();
+        assert_eq!(
+            clock.hold(Layer::Campaign, Timestamp(50)),
+            Err(ClockError::AlreadyHeld(Layer::Battle))
+        );
+        assert_eq!(
+            clock.release(Layer::Campaign, 3),
+            Err(ClockError::NotHeld(Layer::Campaign))
+        );
+        assert_eq!(clock.release(Layer::Battle, 3), Ok(Timestamp(53)));
+        assert!(!clock.is_held());
+    }
+}
@@ -0,0 +1,5 @@
+//! Debugging aids for chasing down emergent behaviour
+
+pub mod snapshot;
+
+pub use snapshot::{diff, Change, SnapshotDiff, WorldSnapshot};
@@ -0,0 +1,360 @@
+//! World snapshots - what every settler was doing at one tick, and what
+//! changed between two
+//!
+//! `WorldSnapshot::capture` records each human's needs, position, current
+//! task, and how they feel about everyone they know. `diff` compares two
+//! snapshots and says, settler by settler, what changed: who moved, whose
+//! needs rose or fell past `NEED_THRESHOLD`, who took up a new task, whose
+//! feelings about someone turned. Taking one snapshot before a stretch of
+//! ticks and another after pins down when emergent behaviour went astray.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::actions::catalog::ActionId;
+use crate::core::types::{EntityId, Tick, Vec2};
+use crate::ecs::world::World;
+use crate::entity::needs::Needs;
+use crate::entity::social::Disposition;
+
+/// Smallest change in a need worth reporting
+pub const NEED_THRESHOLD: f32 = 0.05;
+
+/// Smallest move worth reporting, in world units
+pub const MOVE_THRESHOLD: f32 = 1.0;
+
+/// One settler as they were when the snapshot was taken
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlerSnapshot {
+    pub id: EntityId,
+    pub name: String,
+    pub alive: bool,
+    pub position: Vec2,
+    pub needs: Needs,
+    pub task: Option<ActionId>,
+    /// How they feel about each entity they remember
+    pub relationships: Vec<(EntityId, Disposition)>,
+}
+
+/// Every settler in the world at one tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub tick: Tick,
+    pub settlers: Vec<SettlerSnapshot>,
+}
+
+impl WorldSnapshot {
+    pub fn capture(world: &World) -> Self {
+        let humans = &world.humans;
+        let settlers = (0..humans.ids.len())
+            .map(|i| SettlerSnapshot {
+                id: humans.ids[i],
+                name: humans.names[i].clone(),
+                alive: humans.alive[i],
+                position: humans.positions[i],
+                needs: humans.needs[i].clone(),
+                task: humans.task_queues[i].current().map(|task| task.action),
+                relationships: humans.social_memories[i]
+                    .slots
+                    .iter()
+                    .map(|slot| (slot.target_id, slot.get_disposition()))
+                    .collect(),
+            })
+            .collect();
+        Self {
+            tick: world.current_tick,
+            settlers,
+        }
+    }
+
+    pub fn settler(&self, id: EntityId) -> Option<&SettlerSnapshot> {
+        self.settlers.iter().find(|s| s.id == id)
+    }
+}
+
+/// One thing that changed about a settler
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// Born, or arrived, since the first snapshot
+    Appeared,
+    /// No longer in the world at all
+    Vanished,
+    Died,
+    Moved {
+        from: Vec2,
+        to: Vec2,
+    },
+    Need {
+        need: &'static str,
+        from: f32,
+        to: f32,
+    },
+    Task {
+        from: Option<ActionId>,
+        to: Option<ActionId>,
+    },
+    /// Their feelings about `other` turned; `from` is `None` for someone new
+    Relationship {
+        other: EntityId,
+        other_name: String,
+        from: Option<Disposition>,
+        to: Disposition,
+    },
+    /// They no longer remember `other`
+    Forgot {
+        other: EntityId,
+        other_name: String,
+    },
+}
+
+/// Everything that changed about one settler
+#[derive(Debug, Clone)]
+pub struct SettlerChanges {
+    pub id: EntityId,
+    pub name: String,
+    pub changes: Vec<Change>,
+}
+
+/// What changed between two snapshots, for whom
+#[derive(Debug, Clone)]
+pub struct SnapshotDiff {
+    pub from_tick: Tick,
+    pub to_tick: Tick,
+    pub settlers: Vec<SettlerChanges>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.settlers.is_empty()
+    }
+
+    /// What changed about `id`, if anything did
+    pub fn for_settler(&self, id: EntityId) -> &[Change] {
+        self.settlers
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.changes.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+fn needs_of(needs: &Needs) -> [(&'static str, f32); 6] {
+    [
+        ("rest", needs.rest),
+        ("food", needs.food),
+        ("safety", needs.safety),
+        ("social", needs.social),
+        ("purpose", needs.purpose),
+        ("hygiene", needs.hygiene),
+    ]
+}
+
+fn settler_changes(
+    before: &SettlerSnapshot,
+    after: &SettlerSnapshot,
+    names: &HashMap<EntityId, &str>,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+    if before.alive && !after.alive {
+        changes.push(Change::Died);
+    }
+    if before.position.distance(&after.position) >= MOVE_THRESHOLD {
+        changes.push(Change::Moved {
+            from: before.position,
+            to: after.position,
+        });
+    }
+    for ((need, from), (_, to)) in needs_of(&before.needs)
+        .into_iter()
+        .zip(needs_of(&after.needs))
+    {
+        if (to - from).abs() >= NEED_THRESHOLD {
+            changes.push(Change::Need { need, from, to });
+        }
+    }
+    if before.task != after.task {
+        changes.push(Change::Task {
+            from: before.task,
+            to: after.task,
+        });
+    }
+
+    let name_of = |id: EntityId| names.get(&id).map_or("someone", |n| *n).to_string();
+    let was: HashMap<_, _> = before.relationships.iter().copied().collect();
+    for &(other, to) in &after.relationships {
+        let from = was.get(&other).copied();
+        if from != Some(to) {
+            changes.push(Change::Relationship {
+                other,
+                other_name: name_of(other),
+                from,
+                to,
+            });
+        }
+    }
+    for &(other, _) in &before.relationships {
+        if !after.relationships.iter().any(|&(o, _)| o == other) {
+            changes.push(Change::Forgot {
+                other,
+                other_name: name_of(other),
+            });
+        }
+    }
+    changes
+}
+
+/// What changed between `before` and `after`
+pub fn diff(before: &WorldSnapshot, after: &WorldSnapshot) -> SnapshotDiff {
+    let names: HashMap<EntityId, &str> = before
+        .settlers
+        .iter()
+        .chain(&after.settlers)
+        .map(|s| (s.id, s.name.as_str()))
+        .collect();
+
+    let mut settlers = Vec::new();
+    for now in &after.settlers {
+        let changes = match before.settler(now.id) {
+            Some(then) => settler_changes(then, now, &names),
+            None => vec![Change::Appeared],
+        };
+        if !changes.is_empty() {
+            settlers.push(SettlerChanges {
+                id: now.id,
+                name: now.name.clone(),
+                changes,
+            });
+        }
+    }
+    for then in &before.settlers {
+        if after.settler(then.id).is_none() {
+            settlers.push(SettlerChanges {
+                id: then.id,
+                name: then.name.clone(),
+                changes: vec![Change::Vanished],
+            });
+        }
+    }
+    SnapshotDiff {
+        from_tick: before.tick,
+        to_tick: after.tick,
+        settlers,
+    }
+}
+
+fn task_name(task: Option<ActionId>) -> String {
+    task.map_or_else(|| "nothing".to_string(), |action| format!("{:?}", action))
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Appeared => write!(f, "appeared"),
+            Change::Vanished => write!(f, "vanished"),
+            Change::Died => write!(f, "died"),
+            Change::Moved { from, to } => write!(
+                f,
+                "moved ({:.1}, {:.1}) -> ({:.1}, {:.1})",
+                from.x, from.y, to.x, to.y
+            ),
+            Change::Need { need, from, to } => write!(f, "{} {:.2} -> {:.2}", need, from, to),
+            Change::Task { from, to } => {
+                write!(f, "task {} -> {}", task_name(*from), task_name(*to))
+            }
+            Change::Relationship {
+                other_name,
+                from: Some(from),
+                to,
+                ..
+            } => write!(f, "{:?} towards {} (was {:?})", to, other_name, from),
+            Change::Relationship {
+                other_name,
+                from: None,
+                to,
+                ..
+            } => write!(f, "came to know {} ({:?})", other_name, to),
+            Change::Forgot { other_name, .. } => write!(f, "forgot {}", other_name),
+        }
+    }
+}
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(
+                f,
+                "Tick {} -> {}: nothing changed",
+                self.from_tick, self.to_tick
+            );
+        }
+        write!(
+            f,
+            "Tick {} -> {}: {} settler(s) changed",
+            self.from_tick,
+            self.to_tick,
+            self.settlers.len()
+        )?;
+        for settler in &self.settlers {
+            write!(f, "\n{}", settler.name)?;
+            for change in &settler.changes {
+                write!(f, "\n  {}", change)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::social::EventType;
+
+    #[test]
+    fn test_diff_names_who_changed_and_how() {
+        let mut world = World::new();
+        let marcus = world.spawn_human("Marcus".into());
+        let lucia = world.spawn_human("Lucia".into());
+        let before = WorldSnapshot::capture(&world);
+
+        world.current_tick = 50;
+        world.humans.positions[0] = Vec2::new(30.0, 0.0);
+        world.humans.needs[0].food = before.settlers[0].needs.food + 0.5;
+        world.humans.social_memories[0].record_encounter(lucia, EventType::AidReceived, 1.0, 50);
+        world.humans.social_memories[0].promote_encounter(lucia, 50);
+        world.humans.alive[1] = false;
+        let after = WorldSnapshot::capture(&world);
+
+        let changes = diff(&before, &after);
+        assert_eq!(changes.settlers.len(), 2);
+        let marcus_changes = changes.for_settler(marcus);
+        assert!(marcus_changes
+            .iter()
+            .any(|c| matches!(c, Change::Moved { .. })));
+        assert!(marcus_changes
+            .iter()
+            .any(|c| matches!(c, Change::Need { need: "food", .. })));
+        assert!(marcus_changes.iter().any(|c| matches!(
+            c,
+            Change::Relationship { other, from: None, .. } if *other == lucia
+        )));
+        assert!(matches!(changes.for_settler(lucia), [Change::Died]));
+
+        let text = changes.to_string();
+        assert!(text.starts_with("Tick 0 -> 50: 2 settler(s) changed"));
+        assert!(text.contains("came to know Lucia"));
+        assert!(text.contains("Lucia\n  died"));
+    }
+
+    #[test]
+    fn test_quiet_world_diffs_empty() {
+        let mut world = World::new();
+        world.spawn_human("Marcus".into());
+        let snapshot = WorldSnapshot::capture(&world);
+        let again = WorldSnapshot::capture(&world);
+        let changes = diff(&snapshot, &again);
+        assert!(changes.is_empty());
+        assert_eq!(changes.to_string(), "Tick 0 -> 0: nothing changed");
+    }
+}
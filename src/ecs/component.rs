@@ -0,0 +1,468 @@
+//! Component registration and typed queries over the SoA archetypes
+//!
+//! Two ways to reach entity data without touching `World` or the archetypes:
+//!
+//! - `World::query` walks the components every species shares, across every
+//!   archetype, with the borrows spelled in the type:
+//!   `world.query::<(&EntityId, &Needs, &mut TaskQueue)>()` yields one tuple
+//!   per living entity. Each column is still a contiguous slice underneath.
+//! - `ComponentRegistry` (`world.components`) holds extra components that
+//!   subsystems attach to entities at runtime. Each registered type gets its
+//!   own dense column (values packed in a `Vec`, with an id index), so a new
+//!   subsystem can keep per-entity data without adding a field to an
+//!   archetype and every spawn function.
+//!
+//! Registered components are saved with the world under their
+//! `Component::NAME`. A loaded column stays encoded until its subsystem
+//! registers (or first inserts) the type, since only the subsystem knows
+//! it. An entity's components are removed when it dies or is exiled.
+
+use std::any::{Any, TypeId};
+use std::collections::BTreeMap;
+use std::slice;
+
+use ahash::AHashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core::types::EntityId;
+use crate::entity::body::BodyState;
+use crate::entity::needs::Needs;
+use crate::entity::social::SocialMemory;
+use crate::entity::species::ComponentsMut;
+use crate::entity::tasks::TaskQueue;
+use crate::entity::thoughts::ThoughtBuffer;
+
+/// A component every species' archetype carries a column of
+pub trait SharedComponent: Sized + 'static {
+    /// Move this component's column out of `parts`, leaving an empty slice
+    fn take_column<'a>(parts: &mut ComponentsMut<'a>) -> &'a mut [Self];
+}
+
+macro_rules! shared_component {
+    ($component:ty, $column:ident) => {
+        impl SharedComponent for $component {
+            fn take_column<'a>(parts: &mut ComponentsMut<'a>) -> &'a mut [Self] {
+                std::mem::take(&mut parts.$column)
+            }
+        }
+    };
+}
+
+shared_component!(Needs, needs);
+shared_component!(ThoughtBuffer, thoughts);
+shared_component!(TaskQueue, task_queues);
+shared_component!(BodyState, body_states);
+shared_component!(SocialMemory, social_memories);
+
+/// One column of a query: `&T`, `&mut T`, or `&EntityId`, or a tuple of them
+pub trait Fetch<'a> {
+    type Item;
+    type Iter: Iterator<Item = Self::Item>;
+
+    /// Borrow this query's columns out of one archetype
+    ///
+    /// Panics if the same component is asked for twice in one query.
+    fn fetch(parts: &mut ComponentsMut<'a>) -> Self::Iter;
+}
+
+fn take_column<'a, T: SharedComponent>(parts: &mut ComponentsMut<'a>) -> &'a mut [T] {
+    let column = T::take_column(parts);
+    assert!(
+        column.len() == parts.ids.len(),
+        "{} requested twice in one query",
+        std::any::type_name::<T>()
+    );
+    column
+}
+
+impl<'a, T: SharedComponent> Fetch<'a> for &'a T {
+    type Item = &'a T;
+    type Iter = slice::Iter<'a, T>;
+
+    fn fetch(parts: &mut ComponentsMut<'a>) -> Self::Iter {
+        let column: &'a [T] = take_column(parts);
+        column.iter()
+    }
+}
+
+impl<'a, T: SharedComponent> Fetch<'a> for &'a mut T {
+    type Item = &'a mut T;
+    type Iter = slice::IterMut<'a, T>;
+
+    fn fetch(parts: &mut ComponentsMut<'a>) -> Self::Iter {
+        take_column(parts).iter_mut()
+    }
+}
+
+impl<'a> Fetch<'a> for &'a EntityId {
+    type Item = &'a EntityId;
+    type Iter = slice::Iter<'a, EntityId>;
+
+    fn fetch(parts: &mut ComponentsMut<'a>) -> Self::Iter {
+        parts.ids.iter()
+    }
+}
+
+/// Steps several column iterators together
+pub struct Zipped<I>(I);
+
+macro_rules! fetch_tuple {
+    ($($fetch:ident),+) => {
+        impl<'a, $($fetch: Fetch<'a>),+> Fetch<'a> for ($($fetch,)+) {
+            type Item = ($($fetch::Item,)+);
+            type Iter = Zipped<($($fetch::Iter,)+)>;
+
+            fn fetch(parts: &mut ComponentsMut<'a>) -> Self::Iter {
+                Zipped(($($fetch::fetch(parts),)+))
+            }
+        }
+
+        impl<$($fetch: Iterator),+> Iterator for Zipped<($($fetch,)+)> {
+            type Item = ($($fetch::Item,)+);
+
+            #[allow(non_snake_case)]
+            fn next(&mut self) -> Option<Self::Item> {
+                let ($($fetch,)+) = &mut self.0;
+                Some(($($fetch.next()?,)+))
+            }
+        }
+    };
+}
+
+fetch_tuple!(A);
+fetch_tuple!(A, B);
+fetch_tuple!(A, B, C);
+fetch_tuple!(A, B, C, D);
+
+/// Query the living members of one archetype
+pub fn query_archetype<'a, Q: Fetch<'a>>(
+    mut parts: ComponentsMut<'a>,
+) -> impl Iterator<Item = Q::Item> + 'a
+where
+    Q::Iter: 'a,
+{
+    let alive = parts.alive;
+    Q::fetch(&mut parts)
+        .zip(alive)
+        .filter_map(|(item, &alive)| alive.then_some(item))
+}
+
+/// A type a subsystem attaches to entities through the `ComponentRegistry`
+pub trait Component: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Name the column is saved under, unique among components
+    const NAME: &'static str;
+}
+
+/// One registered component type's column
+struct Column<T> {
+    ids: Vec<EntityId>,
+    values: Vec<T>,
+    index: AHashMap<EntityId, usize>,
+}
+
+impl<T> Default for Column<T> {
+    fn default() -> Self {
+        Self {
+            ids: Vec::new(),
+            values: Vec::new(),
+            index: AHashMap::new(),
+        }
+    }
+}
+
+impl<T> Column<T> {
+    fn insert(&mut self, entity: EntityId, value: T) -> Option<T> {
+        match self.index.get(&entity) {
+            Some(&i) => Some(std::mem::replace(&mut self.values[i], value)),
+            None => {
+                self.index.insert(entity, self.ids.len());
+                self.ids.push(entity);
+                self.values.push(value);
+                None
+            }
+        }
+    }
+
+    fn remove(&mut self, entity: EntityId) -> Option<T> {
+        let i = self.index.remove(&entity)?;
+        self.ids.swap_remove(i);
+        if let Some(&moved) = self.ids.get(i) {
+            self.index.insert(moved, i);
+        }
+        Some(self.values.swap_remove(i))
+    }
+}
+
+/// A column as saved: each entity with its encoded value
+type SavedColumn = Vec<(EntityId, serde_json::Value)>;
+
+/// Type-erased column, so entities can be removed from every column at once
+trait AnyColumn: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn remove_entity(&mut self, entity: EntityId);
+    fn name(&self) -> &'static str;
+    fn save(&self) -> Result<SavedColumn, serde_json::Error>;
+}
+
+impl<T: Component> AnyColumn for Column<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn remove_entity(&mut self, entity: EntityId) {
+        self.remove(entity);
+    }
+
+    fn name(&self) -> &'static str {
+        T::NAME
+    }
+
+    fn save(&self) -> Result<SavedColumn, serde_json::Error> {
+        self.ids
+            .iter()
+            .zip(&self.values)
+            .map(|(&id, value)| Ok((id, serde_json::to_value(value)?)))
+            .collect()
+    }
+}
+
+/// Extra components attached to entities at runtime, one column per type
+#[derive(Default)]
+pub struct ComponentRegistry {
+    columns: AHashMap<TypeId, Box<dyn AnyColumn>>,
+    /// Loaded columns whose type has not been registered since, by name
+    saved: BTreeMap<String, SavedColumn>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give component type `T` a column (does nothing if it has one),
+    /// decoding any values for it that were loaded with the world
+    ///
+    /// A loaded value that no longer decodes as a `T` is dropped with a
+    /// warning.
+    pub fn register<T: Component>(&mut self) {
+        if self.is_registered::<T>() {
+            return;
+        }
+        let mut column = Column::<T>::default();
+        for (entity, value) in self.saved.remove(T::NAME).unwrap_or_default() {
+            match serde_json::from_value(value) {
+                Ok(value) => {
+                    column.insert(entity, value);
+                }
+                Err(e) => tracing::warn!("Dropped saved {} component: {}", T::NAME, e),
+            }
+        }
+        self.columns.insert(TypeId::of::<T>(), Box::new(column));
+    }
+
+    pub fn is_registered<T: 'static>(&self) -> bool {
+        self.columns.contains_key(&TypeId::of::<T>())
+    }
+
+    fn column<T: 'static>(&self) -> Option<&Column<T>> {
+        self.columns
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref()
+    }
+
+    fn column_mut<T: 'static>(&mut self) -> Option<&mut Column<T>> {
+        self.columns
+            .get_mut(&TypeId::of::<T>())?
+            .as_any_mut()
+            .downcast_mut()
+    }
+
+    /// Attach a component to an entity (registering `T` if needed),
+    /// returning the value it replaces
+    pub fn insert<T: Component>(&mut self, entity: EntityId, value: T) -> Option<T> {
+        self.register::<T>();
+        self.column_mut::<T>()?.insert(entity, value)
+    }
+
+    pub fn get<T: 'static>(&self, entity: EntityId) -> Option<&T> {
+        let column = self.column::<T>()?;
+        column.index.get(&entity).map(|&i| &column.values[i])
+    }
+
+    pub fn get_mut<T: 'static>(&mut self, entity: EntityId) -> Option<&mut T> {
+        let column = self.column_mut::<T>()?;
+        let i = *column.index.get(&entity)?;
+        Some(&mut column.values[i])
+    }
+
+    /// Detach a component from an entity
+    pub fn remove<T: 'static>(&mut self, entity: EntityId) -> Option<T> {
+        self.column_mut::<T>()?.remove(entity)
+    }
+
+    /// Detach every component an entity has
+    pub fn remove_entity(&mut self, entity: EntityId) {
+        for column in self.columns.values_mut() {
+            column.remove_entity(entity);
+        }
+        for column in self.saved.values_mut() {
+            column.retain(|(id, _)| *id != entity);
+        }
+    }
+
+    /// Every entity with a `T`, and its value
+    pub fn iter<T: 'static>(&self) -> impl Iterator<Item = (EntityId, &T)> {
+        self.column::<T>()
+            .into_iter()
+            .flat_map(|column| column.ids.iter().copied().zip(&column.values))
+    }
+
+    /// Every entity with a `T`, and its value, writable
+    pub fn iter_mut<T: 'static>(&mut self) -> impl Iterator<Item = (EntityId, &mut T)> {
+        self.column_mut::<T>()
+            .into_iter()
+            .flat_map(|column| column.ids.iter().copied().zip(&mut column.values))
+    }
+
+    /// How many entities have a `T`
+    pub fn count<T: 'static>(&self) -> usize {
+        self.column::<T>().map_or(0, |column| column.ids.len())
+    }
+}
+
+impl Serialize for ComponentRegistry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Sorted by name, so the same components always save the same way
+        let mut columns: BTreeMap<&str, SavedColumn> = self
+            .saved
+            .iter()
+            .map(|(name, column)| (name.as_str(), column.clone()))
+            .collect();
+        for column in self.columns.values() {
+            let saved = column.save().map_err(serde::ser::Error::custom)?;
+            columns.insert(column.name(), saved);
+        }
+        columns.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ComponentRegistry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            columns: AHashMap::new(),
+            saved: BTreeMap::deserialize(deserializer)?,
+        })
+    }
+}
+
+impl std::fmt::Debug for ComponentRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComponentRegistry")
+            .field("columns", &self.columns.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::catalog::ActionId;
+    use crate::ecs::world::World;
+    use crate::entity::tasks::{Task, TaskPriority};
+
+    #[test]
+    fn test_query_spans_species() {
+        let mut world = World::with_seed(11);
+        let ada = world.spawn_human("Ada".into());
+        world.spawn_orc("Grok".into());
+        let dead = world.spawn_dwarf("Durin".into());
+        world.dwarves.alive[0] = false;
+
+        for (needs, tasks) in world.query::<(&mut Needs, &mut TaskQueue)>() {
+            needs.food = 0.7;
+            tasks.push(Task::new(ActionId::Rest, TaskPriority::Normal, 0));
+        }
+
+        let fed: Vec<EntityId> = world
+            .query::<(&EntityId, &Needs)>()
+            .filter(|(_, needs)| needs.food == 0.7)
+            .map(|(&id, _)| id)
+            .collect();
+        assert_eq!(fed.len(), 2);
+        assert!(fed.contains(&ada));
+        assert!(!fed.contains(&dead));
+        assert!(world.humans.task_queues[0].current().is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "requested twice")]
+    fn test_query_rejects_aliasing() {
+        let mut world = World::with_seed(11);
+        world.spawn_human("Ada".into());
+        let _ = world.query::<(&mut Needs, &Needs)>().count();
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Grudge(f32);
+
+    impl Component for Grudge {
+        const NAME: &'static str = "grudge";
+    }
+
+    #[test]
+    fn test_registered_components() {
+        let mut registry = ComponentRegistry::new();
+        let (a, b) = (EntityId::new(), EntityId::new());
+        assert!(!registry.is_registered::<Grudge>());
+        assert_eq!(registry.insert(a, Grudge(0.2)), None);
+        registry.insert(b, Grudge(0.5));
+        assert_eq!(registry.insert(a, Grudge(0.3)), Some(Grudge(0.2)));
+
+        for (_, grudge) in registry.iter_mut::<Grudge>() {
+            grudge.0 *= 2.0;
+        }
+        assert_eq!(registry.get::<Grudge>(b), Some(&Grudge(1.0)));
+
+        registry.remove_entity(a);
+        assert_eq!(registry.count::<Grudge>(), 1);
+        assert_eq!(registry.get::<Grudge>(a), None);
+        assert_eq!(registry.iter::<Grudge>().next().unwrap().0, b);
+        assert_eq!(registry.remove::<Grudge>(b), Some(Grudge(1.0)));
+        assert_eq!(registry.get::<u32>(b), None);
+    }
+
+    #[test]
+    fn test_components_survive_a_save_and_leave_with_the_dead() {
+        use crate::ecs::persistence::{snapshot_from_str, snapshot_to_string};
+
+        let mut world = World::with_seed(11);
+        let ada = world.spawn_human("Ada".into());
+        let bram = world.spawn_human("Bram".into());
+        world.components.insert(ada, Grudge(0.4));
+        world.components.insert(bram, Grudge(0.9));
+
+        let mut loaded = snapshot_from_str(&snapshot_to_string(&world).unwrap()).unwrap();
+        // Still encoded until the subsystem that owns it registers the type
+        assert_eq!(loaded.components.get::<Grudge>(ada), None);
+        loaded.components.register::<Grudge>();
+        assert_eq!(loaded.components.get::<Grudge>(ada), Some(&Grudge(0.4)));
+        // Saving it again gives the same snapshot (as values; hash maps
+        // serialize in no fixed order)
+        let value = |world: &World| {
+            serde_json::from_str::<serde_json::Value>(&snapshot_to_string(world).unwrap()).unwrap()
+        };
+        assert_eq!(value(&loaded), value(&world));
+
+        loaded.humans.alive[1] = false;
+        loaded.record_deaths();
+        assert_eq!(loaded.components.count::<Grudge>(), 1);
+        assert_eq!(loaded.components.get::<Grudge>(bram), None);
+    }
+}
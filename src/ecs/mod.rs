@@ -1 +1,3 @@
+pub mod component;
 pub mod world;
+pub mod persistence;
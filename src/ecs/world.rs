@@ -1,22 +1,52 @@
 //! ECS World - manages all entities and their components
 
 use crate::blueprints::BlueprintRegistry;
+use crate::campaign::weather::WeatherState;
 use crate::city::building::{BuildingArchetype, BuildingId, BuildingType};
+use crate::city::crossing::{Crossing, CrossingError, CrossingId};
+use crate::city::livestock::{LivestockArchetype, Pasture, PastureId};
+use crate::city::road::RoadNetwork;
+use crate::city::site::{ConstructionSite, SiteError};
 use crate::city::stockpile::Stockpile;
+use crate::city::vehicle::VehicleArchetype;
+use crate::command::plan::CommandPlans;
 use crate::core::astronomy::AstronomicalState;
+use crate::core::time::{Timestamp, WorldClock, TICKS_PER_DAY};
 use crate::core::types::{EntityId, Species, Vec2};
+use crate::ecs::component::{query_archetype, ComponentRegistry, Fetch};
+use crate::entity::inventory::DroppedItem;
 use crate::entity::species::dwarf::DwarfArchetype;
 use crate::entity::species::elf::ElfArchetype;
 use crate::entity::species::human::HumanArchetype;
 use crate::entity::species::orc::OrcArchetype;
-use crate::rules::SpeciesRules;
+use crate::entity::species::Archetype;
+use crate::rules::{SpeciesRules, ValueDynamicsRules};
+use crate::simulation::decision_trace::DecisionTrace;
+use crate::simulation::eras::{blueprint_locked, load_default_era_rules, EraRules};
+use crate::simulation::jobs::JobBoard;
+use crate::simulation::omens::{load_default_omen_rules, OmenRules};
+use crate::simulation::regrowth::{load_default_regrowth_rules, RegrowthRules};
 use crate::simulation::resource_zone::ResourceZone;
-use crate::world::{BlockedCells, LoadError, PlacementLoader, WorldObjects};
+use crate::simulation::scheduler::Scheduler;
+use crate::simulation::time_scale::TimeScale;
+use crate::simulation::transformations::{load_default_transformation_rules, TransformationRules};
+use crate::spatial::path_cache::PathCache;
+use crate::world::{
+    Avatar, BlockedCells, ContentSettings, Despair, Drill, Eras, Exiles, FactionId, Factions, Fate,
+    Feats, FeatureId, Happiness, Identity, IdentityRegistry, Legacy, LightId, LightKind,
+    LightSources, LoadError, OathId, OathTerms, Oaths, Omens, PlacementLoader, Places, Requests,
+    SituationLog, TerrainFeatureKind, TerrainFeatures, Transformations, Whereabouts, WorldObjects,
+    Zone, ZoneDesignation, ZoneId, Zones,
+};
 use ahash::AHashMap;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
+use uuid::Uuid;
 
 /// Abundance level of a food zone
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Abundance {
     /// Infinite food - never depletes
     Unlimited,
@@ -25,7 +55,7 @@ pub enum Abundance {
 }
 
 /// A static zone where entities can find food
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FoodZone {
     pub id: u32,
     pub position: Vec2,
@@ -53,18 +83,42 @@ impl FoodZone {
 
     /// Regenerate food for scarce zones
     pub fn regenerate(&mut self) {
+        self.regenerate_by(1.0);
+    }
+
+    /// Regenerate at `factor` times the zone's own rate (seasons, drought)
+    pub fn regenerate_by(&mut self, factor: f32) {
         if let Abundance::Scarce {
             current,
             max,
             regen,
         } = &mut self.abundance
         {
-            *current = (*current + *regen).min(*max);
+            *current = (*current + *regen * factor).min(*max);
         }
     }
 }
 
+/// Load species rules from the TOML files in `species/`
+pub(crate) fn load_default_species_rules() -> SpeciesRules {
+    let species_dir = std::path::Path::new("species");
+    crate::rules::load_species_rules(species_dir).unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load species rules: {}", e);
+        SpeciesRules::new()
+    })
+}
+
+/// Load species value dynamics from the TOML files in `species/`
+pub(crate) fn load_default_species_dynamics() -> ValueDynamicsRules {
+    let species_dir = std::path::Path::new("species");
+    crate::rules::load_species_dynamics(species_dir).unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load species dynamics: {}", e);
+        ValueDynamicsRules::new()
+    })
+}
+
 /// The game world containing all entities
+#[derive(Serialize, Deserialize)]
 pub struct World {
     pub current_tick: u64,
     entity_registry: AHashMap<EntityId, (Species, usize)>,
@@ -77,32 +131,133 @@ pub struct World {
     next_food_zone_id: u32,
     pub resource_zones: Vec<ResourceZone>,
     pub astronomy: AstronomicalState,
-    /// Runtime-loaded species action rules
+    /// Runtime-loaded species action rules (reloaded from disk, never saved)
+    #[serde(skip)]
     pub species_rules: SpeciesRules,
+    /// Runtime-loaded species value dynamics (reloaded from disk, never saved)
+    #[serde(skip)]
+    pub species_dynamics: ValueDynamicsRules,
+    /// Runtime-loaded seasonal regrowth factors (reloaded from disk, never saved)
+    #[serde(skip)]
+    pub regrowth: RegrowthRules,
+    /// Runtime-loaded omen tables for every culture (reloaded from disk, never saved)
+    #[serde(skip)]
+    pub omen_rules: OmenRules,
+    /// Runtime-loaded campaign arc of eras (reloaded from disk, never saved)
+    #[serde(skip)]
+    pub era_rules: EraRules,
+    /// Runtime-loaded transformation table (reloaded from disk, never saved)
+    #[serde(skip)]
+    pub transformation_rules: TransformationRules,
+    /// How often each system runs, and how long it takes (runtime only, never saved)
+    #[serde(skip)]
+    pub scheduler: Scheduler,
+    /// Components attached to entities by subsystems
+    pub components: ComponentRegistry,
+    /// Decisions recorded for watched entities, when tracing (never saved)
+    #[serde(skip)]
+    pub decision_trace: Option<DecisionTrace>,
     /// All buildings in the world
     pub buildings: BuildingArchetype,
     /// Global stockpile for resources (MVP - later per-settlement)
     pub stockpile: Stockpile,
     /// World objects (walls, trees, etc.)
     pub world_objects: WorldObjects,
+    /// Items lying on the ground
+    pub dropped_items: Vec<DroppedItem>,
     /// Blocked cells for pathfinding
     pub blocked_cells: BlockedCells,
+    /// Flow fields and routes shared by walkers (runtime only, never saved)
+    #[serde(skip)]
+    pub paths: PathCache,
+    /// Domesticated animals
+    pub livestock: LivestockArchetype,
+    /// Grazing zones that hold livestock
+    pub pastures: Vec<Pasture>,
+    next_pasture_id: u32,
+    /// Carts and wagons for bulk hauling
+    pub vehicles: VehicleArchetype,
+    /// Built roads, worn paths, and planned road work
+    pub roads: RoadNetwork,
+    /// Streams, gullies, and ledges on the local map
+    pub terrain_features: TerrainFeatures,
+    /// Bridges and ramps over terrain features
+    pub crossings: Vec<Crossing>,
+    next_crossing_id: u32,
+    /// Buildings going up from placed blueprints, and their finished blueprints
+    pub sites: Vec<ConstructionSite>,
+    /// Designated work waiting for, or reserved by, a worker
+    pub jobs: JobBoard,
+    /// Stockpile, farm, dormitory, and no-entry areas the player has marked out
+    pub zones: Zones,
+    /// Torches, lanterns, braziers, and campfires
+    pub lights: LightSources,
+    /// Family trees, memorials to the dead, and the chronicle
+    pub legacy: Legacy,
+    /// Promises and contracts, open and settled
+    pub oaths: Oaths,
+    /// The settlement's weather, rolled once a day
+    pub weather: WeatherState,
+    /// The entity the player is embodying, if any
+    pub avatar: Option<Avatar>,
+    /// The settlement at the last situation report, and the fighting since
+    pub situation: SituationLog,
+    /// The latest opinion poll, recent daily happiness, and unrest
+    pub happiness: Happiness,
+    /// Multi-step player orders still under way
+    pub plans: CommandPlans,
+    /// Everyone banished from the settlement, away or returned
+    pub exiles: Exiles,
+    /// Notable feats, and the famine under way if any
+    pub feats: Feats,
+    /// How many ticks each simulation step covers
+    pub time_scale: TimeScale,
+    /// The compressed scale put off while fighting, to take up again after
+    pub held_time_scale: Option<TimeScale>,
+    /// Which layer, if any, has stopped the settlement's clock
+    pub clock: WorldClock,
+    /// What settlers have asked of the player, and how it went
+    pub requests: Requests,
+    /// The settlement's culture and the omens it is living under
+    pub omens: Omens,
+    /// The eras begun so far, and the rules they put in force
+    pub eras: Eras,
+    /// How much of settlers' darker lives the player has chosen to see
+    pub content: ContentSettings,
+    /// Settlers whose misery has lasted too long
+    pub despair: Despair,
+    /// The drill ground and how well each settler has been drilled
+    pub drill: Drill,
+    /// The settlement, its districts, and other places settlers have named
+    pub places: Places,
+    /// Turns in settlers' lives that may change them, and those that did
+    pub transformations: Transformations,
+    /// Who everyone is and which layer they are in, living or dead
+    pub identities: IdentityRegistry,
+    /// Who belongs to which faction, and how the factions stand
+    pub factions: Factions,
+    /// Seed behind every random roll and every id this world issues
+    pub seed: u64,
+    /// Ids issued so far (position in the seeded id sequence)
+    ids_issued: u64,
 }
 
 impl World {
+    /// Create an empty world with a random seed
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    /// Create an empty world whose simulation is reproducible from `seed`
+    pub fn with_seed(seed: u64) -> Self {
         let mut next_indices = AHashMap::new();
         next_indices.insert(Species::Human, 0);
         next_indices.insert(Species::Dwarf, 0);
         next_indices.insert(Species::Elf, 0);
         next_indices.insert(Species::Orc, 0);
 
-        // Load species rules from TOML files
-        let species_dir = std::path::Path::new("species");
-        let species_rules = crate::rules::load_species_rules(species_dir).unwrap_or_else(|e| {
-            eprintln!("Warning: Failed to load species rules: {}", e);
-            SpeciesRules::new()
-        });
+        let species_rules = load_default_species_rules();
+        let omen_rules = load_default_omen_rules();
 
         Self {
             current_tick: 0,
@@ -117,13 +272,85 @@ impl World {
             resource_zones: Vec::new(),
             astronomy: AstronomicalState::default(),
             species_rules,
+            species_dynamics: load_default_species_dynamics(),
+            regrowth: load_default_regrowth_rules(),
+            omens: Omens {
+                culture: omen_rules.default_culture.clone(),
+                ..Omens::new()
+            },
+            omen_rules,
+            era_rules: load_default_era_rules(),
+            transformation_rules: load_default_transformation_rules(),
+            scheduler: Scheduler::new(),
+            components: ComponentRegistry::new(),
+            decision_trace: None,
             buildings: BuildingArchetype::new(),
             stockpile: Stockpile::new(),
             world_objects: WorldObjects::new(),
+            dropped_items: Vec::new(),
             blocked_cells: BlockedCells::new(),
+            paths: PathCache::new(),
+            livestock: LivestockArchetype::new(),
+            pastures: Vec::new(),
+            next_pasture_id: 0,
+            vehicles: VehicleArchetype::new(),
+            roads: RoadNetwork::new(),
+            terrain_features: TerrainFeatures::new(),
+            crossings: Vec::new(),
+            next_crossing_id: 0,
+            sites: Vec::new(),
+            jobs: JobBoard::new(),
+            zones: Zones::new(),
+            lights: LightSources::new(),
+            legacy: Legacy::new(),
+            oaths: Oaths::new(),
+            weather: WeatherState::new(),
+            avatar: None,
+            situation: SituationLog::new(),
+            happiness: Happiness::new(),
+            plans: CommandPlans::new(),
+            exiles: Exiles::new(),
+            feats: Feats::new(),
+            time_scale: TimeScale::Detailed,
+            held_time_scale: None,
+            clock: WorldClock::new(),
+            requests: Requests::new(),
+            eras: Eras::new(),
+            content: ContentSettings::default(),
+            despair: Despair::new(),
+            drill: Drill::new(),
+            places: Places::new(),
+            transformations: Transformations::new(),
+            identities: IdentityRegistry::new(),
+            factions: Factions::with_settlement(),
+            seed,
+            ids_issued: 0,
         }
     }
 
+    /// Seed for the current tick's random rolls
+    pub fn tick_seed(&self) -> u64 {
+        self.seed ^ self.current_tick.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Random number generator for the current tick, seeded by `tick_seed`
+    pub fn tick_rng(&self) -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(self.tick_seed())
+    }
+
+    /// Next value in this world's id sequence, so that replaying the same
+    /// spawns gives the same ids
+    fn issue_id(&mut self) -> u128 {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(self.ids_issued);
+        self.ids_issued += 1;
+        rng.gen()
+    }
+
+    fn issue_entity_id(&mut self) -> EntityId {
+        EntityId(Uuid::from_u128(self.issue_id()))
+    }
+
     pub fn add_food_zone(&mut self, position: Vec2, radius: f32, abundance: Abundance) -> u32 {
         let id = self.next_food_zone_id;
         self.next_food_zone_id += 1;
@@ -136,11 +363,142 @@ impl World {
         id
     }
 
+    /// Designate a pasture for livestock
+    pub fn add_pasture(&mut self, position: Vec2, radius: f32, capacity: f32) -> PastureId {
+        let id = PastureId(self.next_pasture_id);
+        self.next_pasture_id += 1;
+        self.pastures.push(Pasture::new(id, position, radius, capacity));
+        id
+    }
+
+    /// Lay down a stream, gully, or ledge, blocking the cells it covers
+    pub fn add_terrain_feature(
+        &mut self,
+        kind: TerrainFeatureKind,
+        from: Vec2,
+        to: Vec2,
+        width: f32,
+        elevation: f32,
+    ) -> FeatureId {
+        self.terrain_features
+            .add(kind, from, to, width, elevation, &mut self.blocked_cells)
+    }
+
+    /// Designate a zone; a no-entry zone is closed to pathfinding at once
+    pub fn designate_zone(&mut self, designation: ZoneDesignation) -> ZoneId {
+        self.zones.designate(designation, &mut self.blocked_cells)
+    }
+
+    /// Lift a zone designation
+    pub fn remove_zone(&mut self, id: ZoneId) -> Option<Zone> {
+        self.zones.remove(id, &mut self.blocked_cells)
+    }
+
+    /// Plan a bridge or ramp over the feature at a position
+    ///
+    /// Materials are drawn from the stockpile now; builders are assigned by
+    /// the daily crossing system.
+    pub fn plan_crossing(
+        &mut self,
+        registry: &BlueprintRegistry,
+        position: Vec2,
+        width: f32,
+    ) -> Result<CrossingId, CrossingError> {
+        let feature_id = self
+            .terrain_features
+            .feature_at(position, &self.blocked_cells)
+            .ok_or(CrossingError::NoObstacle)?;
+        let feature = self.terrain_features.get(feature_id).unwrap();
+
+        let id = CrossingId(self.next_crossing_id);
+        let crossing = Crossing::plan(
+            id,
+            feature,
+            position,
+            width,
+            registry,
+            &mut self.stockpile,
+            self.current_tick,
+        )?;
+        self.next_crossing_id += 1;
+        self.crossings.push(crossing);
+        Ok(id)
+    }
+
+    /// Place a blueprint for a building at a position
+    ///
+    /// The building starts under construction; haulers and builders are sent
+    /// to it by the construction site system. Blueprints an era has yet to
+    /// unlock cannot be placed.
+    pub fn place_blueprint(
+        &mut self,
+        registry: &BlueprintRegistry,
+        name: &str,
+        position: Vec2,
+    ) -> Result<BuildingId, SiteError> {
+        if blueprint_locked(self, name) {
+            return Err(SiteError::Locked(name.to_string()));
+        }
+        let building = BuildingId(self.issue_id() as u64);
+        let site = ConstructionSite::plan(building, name, position, registry, self.current_tick)?;
+        self.buildings
+            .spawn(building, site.building_type, position, self.current_tick);
+        self.sites.push(site);
+        Ok(building)
+    }
+
+    /// Place a fixed light on the map
+    pub fn place_light(&mut self, kind: LightKind, position: Vec2) -> LightId {
+        self.lights.place(kind, position)
+    }
+
+    /// Hand a torch or lantern to a human
+    ///
+    /// Returns None if the light cannot be carried, the entity is not a
+    /// living human, or they already carry a light.
+    pub fn give_light(&mut self, entity: EntityId, kind: LightKind) -> Option<LightId> {
+        let idx = self.humans.index_of(entity)?;
+        if !self.humans.alive[idx] {
+            return None;
+        }
+        self.lights.give(kind, entity, self.humans.positions[idx])
+    }
+
+    /// Have a human swear an oath, due in `days` days
+    ///
+    /// The beneficiary is who the oath is sworn to (None = the settlement).
+    /// Returns None if the swearer is not a living human.
+    pub fn swear_oath(
+        &mut self,
+        swearer: EntityId,
+        beneficiary: Option<EntityId>,
+        terms: OathTerms,
+        days: u32,
+    ) -> Option<OathId> {
+        let idx = self.humans.index_of(swearer)?;
+        if !self.humans.alive[idx] {
+            return None;
+        }
+        let baseline = match terms {
+            OathTerms::Deliver { resource, .. } => self.stockpile.get(resource),
+            OathTerms::Defend { .. } => 0,
+        };
+        let deadline = self.current_tick + days as u64 * self.astronomy.ticks_per_day;
+        let oath = self
+            .oaths
+            .swear(swearer, beneficiary, terms, self.current_tick, deadline);
+        oath.baseline = baseline;
+        Some(oath.id)
+    }
+
     pub fn spawn_human(&mut self, name: String) -> EntityId {
-        let entity_id = EntityId::new();
+        let entity_id = self.issue_entity_id();
         let index = *self.next_indices.get(&Species::Human).unwrap();
 
+        self.identities
+            .register(entity_id, name.clone(), Species::Human, self.current_tick);
         self.humans.spawn(entity_id, name, self.current_tick);
+        self.enlist(entity_id, self.factions.settlement());
 
         self.entity_registry
             .insert(entity_id, (Species::Human, index));
@@ -149,11 +507,42 @@ impl World {
         entity_id
     }
 
+    /// Spawn a human into the slot of someone dead a day or more, if there
+    /// is one, so the human columns hold only the living and the newly dead
+    /// however many generations pass
+    pub fn spawn_human_in_freed_slot(&mut self, name: String) -> EntityId {
+        let entity_id = self.spawn_human(name);
+        if let Some(slot) = self.freed_human_slot() {
+            let buried = self.humans.ids[slot];
+            self.humans.swap_remove(slot);
+            self.entity_registry.remove(&buried);
+            self.entity_registry
+                .insert(entity_id, (Species::Human, slot));
+            *self.next_indices.get_mut(&Species::Human).unwrap() -= 1;
+        }
+        entity_id
+    }
+
+    /// A human slot whose occupant died at least a day ago
+    fn freed_human_slot(&self) -> Option<usize> {
+        let tick = self.current_tick;
+        (0..self.humans.count()).find(|&i| {
+            !self.humans.alive[i]
+                && matches!(
+                    self.identities.get(self.humans.ids[i]).map(|identity| identity.fate),
+                    Some(Fate::Dead { tick: died }) if died + TICKS_PER_DAY <= tick
+                )
+        })
+    }
+
     pub fn spawn_orc(&mut self, name: String) -> EntityId {
-        let entity_id = EntityId::new();
+        let entity_id = self.issue_entity_id();
         let index = *self.next_indices.get(&Species::Orc).unwrap();
 
+        self.identities
+            .register(entity_id, name.clone(), Species::Orc, self.current_tick);
         self.orcs.spawn(entity_id, name, self.current_tick);
+        self.enlist(entity_id, self.factions.warband());
 
         self.entity_registry
             .insert(entity_id, (Species::Orc, index));
@@ -163,10 +552,13 @@ impl World {
     }
 
     pub fn spawn_dwarf(&mut self, name: String) -> EntityId {
-        let entity_id = EntityId::new();
+        let entity_id = self.issue_entity_id();
         let index = *self.next_indices.get(&Species::Dwarf).unwrap();
 
+        self.identities
+            .register(entity_id, name.clone(), Species::Dwarf, self.current_tick);
         self.dwarves.spawn(entity_id, name, self.current_tick);
+        self.enlist(entity_id, self.factions.settlement());
 
         self.entity_registry
             .insert(entity_id, (Species::Dwarf, index));
@@ -176,10 +568,13 @@ impl World {
     }
 
     pub fn spawn_elf(&mut self, name: String) -> EntityId {
-        let entity_id = EntityId::new();
+        let entity_id = self.issue_entity_id();
         let index = *self.next_indices.get(&Species::Elf).unwrap();
 
+        self.identities
+            .register(entity_id, name.clone(), Species::Elf, self.current_tick);
         self.elves.spawn(entity_id, name, self.current_tick);
+        self.enlist(entity_id, self.factions.settlement());
 
         self.entity_registry
             .insert(entity_id, (Species::Elf, index));
@@ -188,24 +583,138 @@ impl World {
         entity_id
     }
 
+    fn enlist(&mut self, entity_id: EntityId, faction: Option<FactionId>) {
+        if let Some(faction) = faction {
+            self.factions
+                .join(entity_id, faction)
+                .expect("faction was just looked up");
+        }
+    }
+
+    /// A faction's stockpile (the settlement's own is the world stockpile)
+    pub fn faction_stockpile(&self, faction: FactionId) -> Option<&Stockpile> {
+        if self.factions.settlement() == Some(faction) {
+            return Some(&self.stockpile);
+        }
+        self.factions.get(faction).map(|f| &f.stockpile)
+    }
+
+    pub fn faction_stockpile_mut(&mut self, faction: FactionId) -> Option<&mut Stockpile> {
+        if self.factions.settlement() == Some(faction) {
+            return Some(&mut self.stockpile);
+        }
+        self.factions.get_mut(faction).map(|f| &mut f.stockpile)
+    }
+
     /// Spawn a new building at the given position
     pub fn spawn_building(&mut self, building_type: BuildingType, position: Vec2) -> BuildingId {
-        let id = BuildingId::new();
+        let id = BuildingId(self.issue_id() as u64);
         self.buildings
             .spawn(id, building_type, position, self.current_tick);
         id
     }
 
+    /// Every species archetype in the world, seen through their shared columns
+    pub fn archetypes(&self) -> [&dyn Archetype; 4] {
+        [&self.humans, &self.orcs, &self.dwarves, &self.elves]
+    }
+
+    /// The living entities of every species, one tuple of components each
+    ///
+    /// `world.query::<(&EntityId, &Needs, &mut TaskQueue)>()`; see
+    /// `ecs::component` for what can be queried.
+    pub fn query<'w, Q: Fetch<'w>>(&'w mut self) -> impl Iterator<Item = Q::Item> + 'w
+    where
+        Q::Iter: 'w,
+    {
+        self.archetypes_mut()
+            .into_iter()
+            .flat_map(|archetype| query_archetype::<Q>(archetype.components_mut()))
+    }
+
+    pub fn archetypes_mut(&mut self) -> [&mut dyn Archetype; 4] {
+        [
+            &mut self.humans,
+            &mut self.orcs,
+            &mut self.dwarves,
+            &mut self.elves,
+        ]
+    }
+
+    /// The archetype holding a species, if the world has one for it
+    pub fn archetype(&self, species: Species) -> Option<&dyn Archetype> {
+        self.archetypes()
+            .into_iter()
+            .find(|archetype| archetype.species() == species)
+    }
+
+    pub fn archetype_mut(&mut self, species: Species) -> Option<&mut dyn Archetype> {
+        self.archetypes_mut()
+            .into_iter()
+            .find(|archetype| archetype.species() == species)
+    }
+
     pub fn get_entity_info(&self, entity_id: EntityId) -> Option<(Species, usize)> {
         self.entity_registry.get(&entity_id).copied()
     }
 
+    /// Who someone is and where, with their position if in the settlement
+    pub fn where_is(&self, entity_id: EntityId) -> Option<(&Identity, Option<Vec2>)> {
+        let identity = self.identities.get(entity_id)?;
+        let position = match identity.whereabouts {
+            Whereabouts::Settlement => self
+                .get_entity_info(entity_id)
+                .and_then(|(species, i)| Some(self.archetype(species)?.components().positions[i])),
+            _ => None,
+        };
+        Some((identity, position))
+    }
+
+    /// Mark dead in the identity registry everyone their archetype says has died
+    pub fn record_deaths(&mut self) {
+        let tick = self.current_tick;
+        let dead: Vec<EntityId> = self
+            .archetypes()
+            .into_iter()
+            .flat_map(|archetype| {
+                let parts = archetype.components();
+                (0..parts.len())
+                    .filter(move |&i| !parts.alive[i])
+                    .map(move |i| parts.ids[i])
+            })
+            // Exiles have left the map, not died
+            .filter(|&id| !self.exiles.is_away(id))
+            .collect();
+        for id in dead {
+            self.identities.record_death(id, tick);
+            self.components.remove_entity(id);
+        }
+    }
+
     pub fn entity_count(&self) -> usize {
-        self.humans.count() + self.orcs.count() + self.dwarves.count() + self.elves.count()
+        self.archetypes()
+            .iter()
+            .map(|archetype| archetype.components().len())
+            .sum()
     }
 
+    /// The current instant on the timeline every layer shares
+    pub fn now(&self) -> Timestamp {
+        Timestamp(self.current_tick)
+    }
+
+    /// Set the time scale the player asked for, returning the one it
+    /// replaces; while anyone is fighting, steps stay detailed until the
+    /// fight is over
+    pub fn set_time_scale(&mut self, scale: TimeScale) -> TimeScale {
+        let previous = self.held_time_scale.take().unwrap_or(self.time_scale);
+        self.time_scale = scale;
+        previous
+    }
+
+    /// Advance time by one step of the current time scale
     pub fn tick(&mut self) {
-        self.current_tick += 1;
+        self.current_tick += self.time_scale.span();
     }
 
     pub fn human_entities(&self) -> impl Iterator<Item = EntityId> + '_ {
@@ -215,7 +724,8 @@ impl World {
             .map(|(id, _)| *id)
     }
 
-    /// Load world objects from JSON placement data
+    /// Load world objects (and the zones designated with them) from JSON
+    /// placement data
     pub fn load_world_objects_json(&mut self, json: &str) -> Result<usize, LoadError> {
         // Create a temporary registry for loading
         let mut registry = BlueprintRegistry::new();
@@ -227,7 +737,7 @@ impl World {
         }
 
         let loader = PlacementLoader::new(&registry);
-        let objects = loader.load_from_json(json)?;
+        let (objects, zones) = loader.load_with_zones_from_json(json)?;
 
         let count = objects.len();
 
@@ -254,6 +764,10 @@ impl World {
         }
 
         self.world_objects = objects;
+        self.zones = Zones::new();
+        for designation in zones {
+            self.designate_zone(designation);
+        }
         Ok(count)
     }
 
@@ -330,6 +844,33 @@ mod tests {
         assert_eq!(world.buildings.count(), 3);
     }
 
+    #[test]
+    fn test_spawns_join_factions() {
+        use crate::simulation::resource_zone::ResourceType;
+
+        let mut world = World::new();
+        let settler = world.spawn_human("Settler".into());
+        let dwarf = world.spawn_dwarf("Dwarf".into());
+        let orc = world.spawn_orc("Orc".into());
+        let settlement = world.factions.settlement().unwrap();
+
+        assert_eq!(world.factions.faction_of(settler), Some(settlement));
+        assert_eq!(world.factions.faction_of(dwarf), Some(settlement));
+        assert!(world.factions.are_hostile(settler, orc));
+
+        world
+            .faction_stockpile_mut(settlement)
+            .unwrap()
+            .add(ResourceType::Food, 10);
+        assert_eq!(world.stockpile.get(ResourceType::Food), 10);
+        let warband = world.factions.warband().unwrap();
+        world
+            .faction_stockpile_mut(warband)
+            .unwrap()
+            .add(ResourceType::Food, 5);
+        assert_eq!(world.stockpile.get(ResourceType::Food), 10);
+    }
+
     #[test]
     fn test_world_has_stockpile() {
         use crate::simulation::resource_zone::ResourceType;
@@ -0,0 +1,127 @@
+//! Long-term aspirations
+//!
+//! An aspiration is what someone works toward over months rather than this
+//! hour: mastering a craft, founding a family, avenging a death, or earning
+//! a title. It tracks how far along they are (0.0-1.0) and how many days it
+//! has been since they last got any closer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{EntityId, Tick};
+
+/// Days without progress before frustration sets in (and again each time
+/// this many more pass)
+pub const FRUSTRATION_DAYS: u32 = 30;
+
+/// What someone aspires to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AspirationKind {
+    MasterCraft,
+    FoundFamily,
+    /// See the killer of `victim` answer for it
+    Avenge {
+        victim: EntityId,
+        wrongdoer: EntityId,
+    },
+    EarnTitle,
+}
+
+impl AspirationKind {
+    /// Short description ("master a craft")
+    pub fn describe(&self) -> &'static str {
+        match self {
+            AspirationKind::MasterCraft => "master a craft",
+            AspirationKind::FoundFamily => "found a family",
+            AspirationKind::Avenge { .. } => "avenge a death",
+            AspirationKind::EarnTitle => "earn a title",
+        }
+    }
+}
+
+/// How a day's progress changed an aspiration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Milestone {
+    /// Nothing worth remarking on
+    Unchanged,
+    Closer,
+    Achieved,
+    /// Another `FRUSTRATION_DAYS` passed with no progress
+    Frustrated,
+}
+
+/// A long-term aspiration and how it is going
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Aspiration {
+    pub kind: AspirationKind,
+    pub formed_tick: Tick,
+    /// How far along (0.0-1.0)
+    pub progress: f32,
+    pub days_stalled: u32,
+    pub achieved: bool,
+}
+
+impl Aspiration {
+    pub fn new(kind: AspirationKind, formed_tick: Tick, progress: f32) -> Self {
+        Self {
+            kind,
+            formed_tick,
+            progress: progress.clamp(0.0, 1.0),
+            days_stalled: 0,
+            achieved: false,
+        }
+    }
+
+    /// The aspiration if it is still being pursued
+    pub fn pursued(&self) -> Option<&AspirationKind> {
+        (!self.achieved).then_some(&self.kind)
+    }
+
+    /// Record a day's measure of progress
+    pub fn record_progress(&mut self, progress: f32) -> Milestone {
+        if self.achieved {
+            return Milestone::Unchanged;
+        }
+        let progress = progress.clamp(0.0, 1.0);
+        if progress >= 1.0 {
+            self.progress = 1.0;
+            self.achieved = true;
+            return Milestone::Achieved;
+        }
+
+        let closer = progress > self.progress + f32::EPSILON;
+        self.progress = progress;
+        if closer {
+            self.days_stalled = 0;
+            return Milestone::Closer;
+        }
+        self.days_stalled += 1;
+        if self.days_stalled.is_multiple_of(FRUSTRATION_DAYS) {
+            Milestone::Frustrated
+        } else {
+            Milestone::Unchanged
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_frustration_and_achievement() {
+        let mut aspiration = Aspiration::new(AspirationKind::MasterCraft, 0, 0.2);
+        assert_eq!(aspiration.record_progress(0.3), Milestone::Closer);
+
+        let stalled: Vec<Milestone> = (0..FRUSTRATION_DAYS)
+            .map(|_| aspiration.record_progress(0.3))
+            .collect();
+        assert_eq!(stalled.last(), Some(&Milestone::Frustrated));
+        assert!(stalled[..stalled.len() - 1]
+            .iter()
+            .all(|m| *m == Milestone::Unchanged));
+
+        assert_eq!(aspiration.record_progress(1.0), Milestone::Achieved);
+        assert!(aspiration.pursued().is_none());
+        assert_eq!(aspiration.record_progress(0.0), Milestone::Unchanged);
+    }
+}
@@ -2,8 +2,20 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Work ticks per day a body can sustain without building up exhaustion
+pub const SUSTAINABLE_WORK_TICKS: u32 = 500;
+
+/// Exhaustion gained per work tick beyond the sustainable amount
+pub const OVERWORK_PER_TICK: f32 = 0.0005;
+
+/// Exhaustion shed by a full day without work (scaled down for part days)
+pub const REST_DAY_RECOVERY: f32 = 0.3;
+
+/// Exhaustion at which a body is too worn down to take on more work
+pub const OVERWORKED_THRESHOLD: f32 = 0.6;
+
 /// Physical state of an entity's body
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BodyState {
     /// 0.0 = fresh, 1.0 = exhausted
     pub fatigue: f32,
@@ -13,6 +25,22 @@ pub struct BodyState {
     pub pain: f32,
     /// Computed from wounds
     pub overall_health: f32,
+    /// Chronic overwork built up over days: 0.0 = fresh, 1.0 = collapse
+    #[serde(default)]
+    pub exhaustion: f32,
+    /// Ticks spent working since the last daily exhaustion update
+    #[serde(default)]
+    pub work_ticks_today: u32,
+    /// Wear of old age: 0.0 = hale, 1.0 = body gives out
+    #[serde(default)]
+    pub frailty: f32,
+}
+
+impl Default for BodyState {
+    /// A fresh, healthy body
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BodyState {
@@ -22,6 +50,9 @@ impl BodyState {
             hunger: 0.0,
             pain: 0.0,
             overall_health: 1.0,
+            exhaustion: 0.0,
+            work_ticks_today: 0,
+            frailty: 0.0,
         }
     }
 
@@ -35,15 +66,59 @@ impl BodyState {
         self.can_act() && self.fatigue < 0.9
     }
 
-    /// Apply fatigue from activity
+    /// Apply fatigue from activity (frail bodies tire faster)
     pub fn add_fatigue(&mut self, amount: f32) {
-        self.fatigue = (self.fatigue + amount).min(1.0);
+        self.fatigue = (self.fatigue + amount * (1.0 + self.frailty)).min(1.0);
+    }
+
+    /// Wear from another day of old age; health can no longer rise above
+    /// what frailty leaves
+    pub fn add_frailty(&mut self, amount: f32) {
+        self.frailty = (self.frailty + amount).clamp(0.0, 1.0);
+        self.overall_health = self.overall_health.min(1.0 - self.frailty);
     }
 
     /// Recover fatigue from rest
     pub fn recover_fatigue(&mut self, amount: f32) {
         self.fatigue = (self.fatigue - amount).max(0.0);
     }
+
+    /// Close out the day's work: overwork builds exhaustion, lighter days
+    /// shed it. Resets the work counter and returns the change in exhaustion.
+    pub fn end_workday(&mut self) -> f32 {
+        let worked = self.work_ticks_today;
+        self.work_ticks_today = 0;
+
+        let before = self.exhaustion;
+        if worked > SUSTAINABLE_WORK_TICKS {
+            self.exhaustion += (worked - SUSTAINABLE_WORK_TICKS) as f32 * OVERWORK_PER_TICK;
+        } else {
+            let idle = (SUSTAINABLE_WORK_TICKS - worked) as f32 / SUSTAINABLE_WORK_TICKS as f32;
+            self.exhaustion -= idle * REST_DAY_RECOVERY;
+        }
+        self.exhaustion = self.exhaustion.clamp(0.0, 1.0);
+        self.exhaustion - before
+    }
+
+    /// Too worn down to take on more work
+    pub fn is_overworked(&self) -> bool {
+        self.exhaustion >= OVERWORKED_THRESHOLD
+    }
+
+    /// Worked past the point of collapse
+    pub fn has_collapsed(&self) -> bool {
+        self.exhaustion >= 1.0
+    }
+
+    /// Chance per work tick of fumbling and wasting the effort
+    pub fn fumble_chance(&self) -> f32 {
+        (self.exhaustion - 0.3).max(0.0) * 0.5
+    }
+
+    /// Daily chance of injury for someone who worked while exhausted
+    pub fn injury_chance(&self) -> f32 {
+        self.exhaustion * self.exhaustion * 0.2
+    }
 }
 
 /// Individual wound on a body part
@@ -0,0 +1,153 @@
+//! Emotion - what an entity visibly feels, read from its needs, body, and
+//! recent thoughts
+//!
+//! This is the outward face of the mood system: the one feeling strong
+//! enough to show in how someone stands. When several apply, the most
+//! alarming wins (rage over fear over exhaustion over sorrow over content).
+
+use crate::entity::body::BodyState;
+use crate::entity::needs::Needs;
+use crate::entity::thoughts::{ThoughtBuffer, Valence};
+
+/// Intensity of angry thoughts (anger, injustice, betrayal, unrest) that shows as rage
+pub const ENRAGED_THRESHOLD: f32 = 0.6;
+
+/// Safety need, or intensity of fearful thoughts, that shows as fear
+pub const AFRAID_THRESHOLD: f32 = 0.7;
+
+/// Fatigue, or rest need, that shows as exhaustion
+pub const EXHAUSTED_THRESHOLD: f32 = 0.8;
+
+/// Chronic exhaustion that shows even when rested for the moment
+pub const CHRONIC_EXHAUSTION_THRESHOLD: f32 = 0.6;
+
+/// Net thought intensity below which someone looks downcast (and above
+/// which, negated, they look content)
+pub const MOOD_THRESHOLD: f32 = 0.5;
+
+/// Thought concepts that stoke anger
+const ANGRY_CONCEPTS: [&str; 4] = ["anger", "injustice", "betrayal", "unrest"];
+
+/// Thought concepts that stoke fear
+const FEARFUL_CONCEPTS: [&str; 2] = ["fear", "danger"];
+
+/// What an entity visibly feels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Emotion {
+    /// Nothing strong enough to show
+    #[default]
+    Calm,
+    Content,
+    Downcast,
+    Exhausted,
+    Afraid,
+    Enraged,
+}
+
+impl Emotion {
+    /// Every emotion that shows, in overlay atlas row order
+    pub const SHOWN: [Emotion; 5] = [
+        Emotion::Content,
+        Emotion::Downcast,
+        Emotion::Exhausted,
+        Emotion::Afraid,
+        Emotion::Enraged,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Emotion::Calm => "calm",
+            Emotion::Content => "content",
+            Emotion::Downcast => "downcast",
+            Emotion::Exhausted => "exhausted",
+            Emotion::Afraid => "afraid",
+            Emotion::Enraged => "enraged",
+        }
+    }
+}
+
+/// Summed intensity of negative thoughts about any of `concepts`
+fn negative_intensity(thoughts: &ThoughtBuffer, concepts: &[&str]) -> f32 {
+    thoughts
+        .negative()
+        .filter(|t| {
+            concepts
+                .iter()
+                .any(|c| t.concept_category.eq_ignore_ascii_case(c))
+        })
+        .map(|t| t.intensity)
+        .sum()
+}
+
+/// The emotion that shows, from needs, body, and thoughts
+pub fn read_emotion(needs: &Needs, body: &BodyState, thoughts: &ThoughtBuffer) -> Emotion {
+    if negative_intensity(thoughts, &ANGRY_CONCEPTS) >= ENRAGED_THRESHOLD {
+        return Emotion::Enraged;
+    }
+    if needs.safety >= AFRAID_THRESHOLD
+        || negative_intensity(thoughts, &FEARFUL_CONCEPTS) >= AFRAID_THRESHOLD
+    {
+        return Emotion::Afraid;
+    }
+    if body.fatigue >= EXHAUSTED_THRESHOLD
+        || needs.rest >= EXHAUSTED_THRESHOLD
+        || body.exhaustion >= CHRONIC_EXHAUSTION_THRESHOLD
+    {
+        return Emotion::Exhausted;
+    }
+
+    let net: f32 = thoughts
+        .iter()
+        .map(|t| match t.valence {
+            Valence::Positive => t.intensity,
+            Valence::Negative => -t.intensity,
+        })
+        .sum();
+    if net <= -MOOD_THRESHOLD {
+        Emotion::Downcast
+    } else if net >= MOOD_THRESHOLD {
+        Emotion::Content
+    } else {
+        Emotion::Calm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::thoughts::{CauseType, Thought};
+
+    #[test]
+    fn test_most_alarming_feeling_shows() {
+        let mut needs = Needs::default();
+        let mut body = BodyState::new();
+        let mut thoughts = ThoughtBuffer::new();
+        assert_eq!(read_emotion(&needs, &body, &thoughts), Emotion::Calm);
+
+        thoughts.add(Thought::new(
+            Valence::Positive,
+            0.8,
+            "festival",
+            "danced at the harvest feast",
+            CauseType::Event,
+            0,
+        ));
+        assert_eq!(read_emotion(&needs, &body, &thoughts), Emotion::Content);
+
+        body.fatigue = 0.9;
+        assert_eq!(read_emotion(&needs, &body, &thoughts), Emotion::Exhausted);
+
+        needs.safety = 0.8;
+        assert_eq!(read_emotion(&needs, &body, &thoughts), Emotion::Afraid);
+
+        thoughts.add(Thought::new(
+            Valence::Negative,
+            0.7,
+            "BETRAYAL",
+            "a sworn oath was broken",
+            CauseType::Entity,
+            0,
+        ));
+        assert_eq!(read_emotion(&needs, &body, &thoughts), Emotion::Enraged);
+    }
+}
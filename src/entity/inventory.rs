@@ -0,0 +1,229 @@
+//! Items a person carries: weapons, armor, food, and crafted goods
+//!
+//! Bulk resources (wood, ore, sacks of grain) are counted in `Possessions`.
+//! An inventory holds individual items instead, each with properties of its
+//! own: a sword is not interchangeable with a spear. One weapon and one suit
+//! of armor can be equipped at a time, and what is equipped is what combat
+//! uses.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::combat::{ArmorProperties, WeaponProperties};
+use crate::core::types::Vec2;
+
+/// Items one person can carry, not counting what they have equipped
+pub const INVENTORY_CAPACITY: usize = 8;
+
+/// What an item is, and the properties that make it useful
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ItemKind {
+    Weapon(WeaponProperties),
+    Armor(ArmorProperties),
+    /// Something to eat, and how much hunger it sates
+    Food {
+        nourishment: f32,
+    },
+    /// Crafted goods, kept for their worth
+    Goods,
+}
+
+/// A single item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub name: String,
+    pub kind: ItemKind,
+}
+
+impl Item {
+    pub fn new(name: impl Into<String>, kind: ItemKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+        }
+    }
+
+    /// A standard item by name ("sword", "mail", "ration"; case-insensitive)
+    pub fn named(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        let kind = match name.as_str() {
+            "sword" => ItemKind::Weapon(WeaponProperties::sword()),
+            "mace" => ItemKind::Weapon(WeaponProperties::mace()),
+            "spear" => ItemKind::Weapon(WeaponProperties::spear()),
+            "dagger" => ItemKind::Weapon(WeaponProperties::dagger()),
+            "axe" => ItemKind::Weapon(WeaponProperties::axe()),
+            "club" => ItemKind::Weapon(WeaponProperties::club()),
+            "leather_armor" => ItemKind::Armor(ArmorProperties::leather()),
+            "mail" => ItemKind::Armor(ArmorProperties::mail()),
+            "plate" => ItemKind::Armor(ArmorProperties::plate()),
+            "ration" => ItemKind::Food { nourishment: 0.4 },
+            "tools" | "pottery" | "jewelry" => ItemKind::Goods,
+            _ => return None,
+        };
+        Some(Self { name, kind })
+    }
+
+    pub fn is_equippable(&self) -> bool {
+        matches!(self.kind, ItemKind::Weapon(_) | ItemKind::Armor(_))
+    }
+}
+
+/// An item lying on the ground
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DroppedItem {
+    pub position: Vec2,
+    pub item: Item,
+}
+
+/// Errors from handling an inventory
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InventoryError {
+    #[error("no item in slot {0}")]
+    NoSuchItem(usize),
+    #[error("{0} cannot be equipped")]
+    NotEquippable(String),
+}
+
+/// What one person carries and has equipped
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Inventory {
+    /// Carried items, oldest first
+    pub items: Vec<Item>,
+    pub weapon: Option<Item>,
+    pub armor: Option<Item>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.items.len() >= INVENTORY_CAPACITY
+    }
+
+    /// Carry an item, handing it back if there is no room
+    pub fn add(&mut self, item: Item) -> Result<(), Item> {
+        if self.is_full() {
+            return Err(item);
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    /// Take a carried item out of the inventory
+    pub fn take(&mut self, index: usize) -> Option<Item> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+
+    /// Take the most recently acquired carried item
+    pub fn take_newest(&mut self) -> Option<Item> {
+        self.items.pop()
+    }
+
+    /// Equip a carried weapon or armor, putting what it replaces back in the pack
+    pub fn equip(&mut self, index: usize) -> Result<(), InventoryError> {
+        let item = self
+            .items
+            .get(index)
+            .ok_or(InventoryError::NoSuchItem(index))?;
+        let slot = match item.kind {
+            ItemKind::Weapon(_) => &mut self.weapon,
+            ItemKind::Armor(_) => &mut self.armor,
+            _ => return Err(InventoryError::NotEquippable(item.name.clone())),
+        };
+        let item = self.items.remove(index);
+        if let Some(replaced) = slot.replace(item) {
+            self.items.push(replaced);
+        }
+        Ok(())
+    }
+
+    /// Equip the newest weapon and newest armor carried, returning whether
+    /// anything changed hands
+    pub fn equip_newest(&mut self) -> bool {
+        let newest = |items: &[Item], weapon: bool| {
+            items.iter().rposition(|item| match item.kind {
+                ItemKind::Weapon(_) => weapon,
+                ItemKind::Armor(_) => !weapon,
+                _ => false,
+            })
+        };
+        let mut changed = false;
+        for weapon in [true, false] {
+            if let Some(index) = newest(&self.items, weapon) {
+                changed |= self.equip(index).is_ok();
+            }
+        }
+        changed
+    }
+
+    /// Eat the oldest food carried, returning how much hunger it sates
+    pub fn take_food(&mut self) -> Option<f32> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| matches!(item.kind, ItemKind::Food { .. }))?;
+        match self.items.remove(index).kind {
+            ItemKind::Food { nourishment } => Some(nourishment),
+            _ => None,
+        }
+    }
+
+    /// Properties of the equipped weapon, if any
+    pub fn weapon_properties(&self) -> Option<&WeaponProperties> {
+        match &self.weapon.as_ref()?.kind {
+            ItemKind::Weapon(properties) => Some(properties),
+            _ => None,
+        }
+    }
+
+    /// Properties of the equipped armor, if any
+    pub fn armor_properties(&self) -> Option<&ArmorProperties> {
+        match &self.armor.as_ref()?.kind {
+            ItemKind::Armor(properties) => Some(properties),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_carry_and_equip() {
+        let mut inventory = Inventory::new();
+        inventory.add(Item::named("club").unwrap()).unwrap();
+        inventory.add(Item::named("ration").unwrap()).unwrap();
+        inventory.add(Item::named("Sword").unwrap()).unwrap();
+        inventory.add(Item::named("mail").unwrap()).unwrap();
+
+        assert!(inventory.equip_newest());
+        assert_eq!(
+            inventory.weapon_properties(),
+            Some(&WeaponProperties::sword())
+        );
+        assert_eq!(inventory.armor_properties(), Some(&ArmorProperties::mail()));
+        assert_eq!(inventory.items.len(), 2);
+
+        // Swapping weapons puts the sword back in the pack
+        assert_eq!(inventory.equip(0), Ok(()));
+        assert_eq!(
+            inventory.weapon_properties(),
+            Some(&WeaponProperties::club())
+        );
+        assert_eq!(inventory.items.last().unwrap().name, "sword");
+        assert_eq!(
+            inventory.equip(0),
+            Err(InventoryError::NotEquippable("ration".into()))
+        );
+        assert_eq!(inventory.take_food(), Some(0.4));
+        assert_eq!(inventory.equip(5), Err(InventoryError::NoSuchItem(5)));
+
+        for _ in inventory.items.len()..INVENTORY_CAPACITY {
+            inventory.add(Item::named("tools").unwrap()).unwrap();
+        }
+        assert!(inventory.add(Item::named("dagger").unwrap()).is_err());
+    }
+}
@@ -5,6 +5,10 @@
 //! - `thoughts` - Thought generation and memory
 //! - `tasks` - Task queue and execution
 //! - `body` - Physical state (fatigue, wounds)
+//! - `emotion` - The feeling that shows, read from needs, body, and thoughts
+//! - `possessions` - Personal goods and favors owed
+//! - `inventory` - Carried and equipped items (weapons, armor, food, goods)
+//! - `aspirations` - Long-term aspirations and progress toward them
 //! - `species/` - Species-specific values and archetypes
 //! - `relationships` - Inter-entity relationships
 //! - `social/` - Social memory and group dynamics
@@ -28,14 +32,18 @@
 //! Access by index: `archetype.needs[idx]`, `archetype.values[idx]`
 
 pub mod archetype;
+pub mod aspirations;
 pub mod body;
+pub mod emotion;
 
 // Re-export deprecated types for backwards compatibility
 // These will be removed once migration to skills::Role is complete
 #[allow(deprecated)]
 pub use archetype::{CraftSpecialty, EntityArchetype, TrainingLevel};
 pub mod identity;
+pub mod inventory;
 pub mod needs;
+pub mod possessions;
 pub mod relationships;
 pub mod social;
 pub mod species;
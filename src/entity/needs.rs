@@ -15,6 +15,9 @@ pub struct Needs {
     pub social: f32,
     /// 0.0 = has purpose, 1.0 = aimless
     pub purpose: f32,
+    /// 0.0 = clean, 1.0 = filthy (micro-need: never the most pressing)
+    #[serde(default)]
+    pub hygiene: f32,
 }
 
 impl Default for Needs {
@@ -25,6 +28,7 @@ impl Default for Needs {
             safety: 0.1,
             social: 0.3,
             purpose: 0.3,
+            hygiene: 0.1,
         }
     }
 }
@@ -85,6 +89,7 @@ impl Needs {
         self.food += 0.0003 * dt;                   // ~1200 ticks to critical
         self.social += 0.00015 * dt;                // ~2300 ticks to critical (slow per E5)
         self.purpose += 0.0001 * dt;                // ~3500 ticks to critical (slowest)
+        self.hygiene += 0.0002 * dt * activity_mult; // grubby in about a day of work
 
         // Safety DECREASES when no threats (opposite of other needs)
         // Fast decay ensures safety returns to baseline after combat
@@ -95,6 +100,12 @@ impl Needs {
         self.food = self.food.min(1.0);
         self.social = self.social.min(1.0);
         self.purpose = self.purpose.min(1.0);
+        self.hygiene = self.hygiene.min(1.0);
+    }
+
+    /// Wash off some dirt
+    pub fn wash(&mut self, amount: f32) {
+        self.hygiene = (self.hygiene - amount).max(0.0);
     }
 
     /// Satisfy a need
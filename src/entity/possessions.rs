@@ -0,0 +1,112 @@
+//! Personal goods and favors owed
+//!
+//! Apart from the settlement stockpile, each person carries goods of their
+//! own (kept back from gathering or won in trade) and keeps track of who
+//! owes them a favor for goods given on credit.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::EntityId;
+use crate::simulation::resource_zone::ResourceType;
+
+/// A debt of goodwill: `debtor` owes goods worth `value`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Favor {
+    pub debtor: EntityId,
+    pub value: f32,
+}
+
+/// What one person owns and is owed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Possessions {
+    pub goods: BTreeMap<ResourceType, u32>,
+    /// Favors owed to this person, oldest first
+    pub favors: Vec<Favor>,
+}
+
+impl Possessions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, resource: ResourceType) -> u32 {
+        self.goods.get(&resource).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, resource: ResourceType, amount: u32) {
+        if amount > 0 {
+            *self.goods.entry(resource).or_insert(0) += amount;
+        }
+    }
+
+    /// Remove up to `amount`, returning how much was actually removed
+    pub fn remove(&mut self, resource: ResourceType, amount: u32) -> u32 {
+        let held = self.get(resource);
+        let taken = held.min(amount);
+        if taken == held {
+            self.goods.remove(&resource);
+        } else {
+            self.goods.insert(resource, held - taken);
+        }
+        taken
+    }
+
+    pub fn has_goods(&self) -> bool {
+        !self.goods.is_empty()
+    }
+
+    /// Record that `debtor` owes a favor worth `value`
+    pub fn grant_favor(&mut self, debtor: EntityId, value: f32) {
+        match self.favors.iter_mut().find(|f| f.debtor == debtor) {
+            Some(favor) => favor.value += value,
+            None => self.favors.push(Favor { debtor, value }),
+        }
+    }
+
+    /// Total value `debtor` owes this person
+    pub fn owed_by(&self, debtor: EntityId) -> f32 {
+        self.favors
+            .iter()
+            .filter(|f| f.debtor == debtor)
+            .map(|f| f.value)
+            .sum()
+    }
+
+    /// Write off up to `value` of what `debtor` owes, returning the amount
+    /// settled
+    pub fn settle_favor(&mut self, debtor: EntityId, value: f32) -> f32 {
+        let Some(pos) = self.favors.iter().position(|f| f.debtor == debtor) else {
+            return 0.0;
+        };
+        let settled = self.favors[pos].value.min(value);
+        self.favors[pos].value -= settled;
+        if self.favors[pos].value <= f32::EPSILON {
+            self.favors.remove(pos);
+        }
+        settled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_goods_and_favors() {
+        let mut possessions = Possessions::new();
+        possessions.add(ResourceType::Wood, 3);
+        assert_eq!(possessions.remove(ResourceType::Wood, 5), 3);
+        assert!(!possessions.has_goods());
+
+        let debtor = EntityId::new();
+        possessions.grant_favor(debtor, 1.0);
+        possessions.grant_favor(debtor, 0.5);
+        assert_eq!(possessions.favors.len(), 1);
+        assert_eq!(possessions.settle_favor(debtor, 1.0), 1.0);
+        assert!((possessions.owed_by(debtor) - 0.5).abs() < 1e-6);
+        assert_eq!(possessions.settle_favor(debtor, 2.0), 0.5);
+        assert!(possessions.favors.is_empty());
+    }
+}
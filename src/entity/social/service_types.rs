@@ -24,8 +24,14 @@ impl ServiceType {
             ActionId::Craft => Some(ServiceType::Crafting),
             ActionId::Trade => Some(ServiceType::Trading),
             ActionId::Help => Some(ServiceType::Helping),
-            ActionId::Gather | ActionId::Build | ActionId::Repair => Some(ServiceType::Labor),
-            ActionId::Defend | ActionId::HoldPosition => Some(ServiceType::Protection),
+            ActionId::Gather
+            | ActionId::Build
+            | ActionId::Repair
+            | ActionId::TendLivestock
+            | ActionId::Haul => Some(ServiceType::Labor),
+            ActionId::Defend | ActionId::HoldPosition | ActionId::Drill => {
+                Some(ServiceType::Protection)
+            }
             // Teaching and Healing would map to future actions
             _ => None,
         }
@@ -0,0 +1,160 @@
+//! Uniform access to the components every species archetype shares
+//!
+//! Each species keeps its own SoA archetype, with its own value vocabulary
+//! and whatever extra columns it needs (humans have many). Underneath, every
+//! one of them carries the same core columns: ids, names, positions, bodies,
+//! needs, thoughts, tasks, and social memory. The `Archetype` trait exposes
+//! those columns as slices, so systems that only touch the shared parts can
+//! run over every species in one loop instead of one copy per species.
+//!
+//! `components_mut` hands out every column at once, so a system can read
+//! one column while writing another without fighting the borrow checker.
+//! Species implement the trait with `impl_archetype!`.
+
+use crate::core::types::{EntityId, Species, Vec2};
+use crate::entity::body::BodyState;
+use crate::entity::needs::Needs;
+use crate::entity::social::SocialMemory;
+use crate::entity::tasks::TaskQueue;
+use crate::entity::thoughts::ThoughtBuffer;
+
+/// The shared columns of an archetype, read-only
+#[derive(Clone, Copy)]
+pub struct Components<'a> {
+    pub ids: &'a [EntityId],
+    pub names: &'a [String],
+    pub alive: &'a [bool],
+    pub positions: &'a [Vec2],
+    pub velocities: &'a [Vec2],
+    pub body_states: &'a [BodyState],
+    pub needs: &'a [Needs],
+    pub thoughts: &'a [ThoughtBuffer],
+    pub task_queues: &'a [TaskQueue],
+    pub social_memories: &'a [SocialMemory],
+}
+
+impl<'a> Components<'a> {
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn index_of(&self, id: EntityId) -> Option<usize> {
+        self.ids.iter().position(|&e| e == id)
+    }
+
+    /// Indices of the living
+    pub fn living(&self) -> impl Iterator<Item = usize> + 'a {
+        let alive = self.alive;
+        (0..alive.len()).filter(move |&i| alive[i])
+    }
+}
+
+/// The shared columns of an archetype, with the mutable ones writable
+///
+/// Identity and liveness stay read-only: spawning and death go through the
+/// species' own archetype and the world.
+pub struct ComponentsMut<'a> {
+    pub ids: &'a [EntityId],
+    pub names: &'a [String],
+    pub alive: &'a [bool],
+    pub positions: &'a mut [Vec2],
+    pub velocities: &'a mut [Vec2],
+    pub body_states: &'a mut [BodyState],
+    pub needs: &'a mut [Needs],
+    pub thoughts: &'a mut [ThoughtBuffer],
+    pub task_queues: &'a mut [TaskQueue],
+    pub social_memories: &'a mut [SocialMemory],
+}
+
+/// A species' SoA archetype, seen through the columns all species share
+pub trait Archetype {
+    fn species(&self) -> Species;
+
+    fn components(&self) -> Components<'_>;
+
+    fn components_mut(&mut self) -> ComponentsMut<'_>;
+}
+
+/// Implement `Archetype` for a species archetype with the standard columns
+///
+/// `impl_archetype!(GoblinArchetype, Goblin)` for the `Species::Goblin` archetype.
+macro_rules! impl_archetype {
+    ($archetype:ty, $species:ident) => {
+        impl $crate::entity::species::archetype::Archetype for $archetype {
+            fn species(&self) -> $crate::core::types::Species {
+                $crate::core::types::Species::$species
+            }
+
+            fn components(&self) -> $crate::entity::species::archetype::Components<'_> {
+                $crate::entity::species::archetype::Components {
+                    ids: &self.ids,
+                    names: &self.names,
+                    alive: &self.alive,
+                    positions: &self.positions,
+                    velocities: &self.velocities,
+                    body_states: &self.body_states,
+                    needs: &self.needs,
+                    thoughts: &self.thoughts,
+                    task_queues: &self.task_queues,
+                    social_memories: &self.social_memories,
+                }
+            }
+
+            fn components_mut(&mut self) -> $crate::entity::species::archetype::ComponentsMut<'_> {
+                $crate::entity::species::archetype::ComponentsMut {
+                    ids: &self.ids,
+                    names: &self.names,
+                    alive: &self.alive,
+                    positions: &mut self.positions,
+                    velocities: &mut self.velocities,
+                    body_states: &mut self.body_states,
+                    needs: &mut self.needs,
+                    thoughts: &mut self.thoughts,
+                    task_queues: &mut self.task_queues,
+                    social_memories: &mut self.social_memories,
+                }
+            }
+        }
+    };
+}
+pub(crate) use impl_archetype;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::species::goblin::{GoblinArchetype, GoblinValues};
+    use crate::entity::species::orc::OrcArchetype;
+
+    #[test]
+    fn test_species_share_components() {
+        let mut orcs = OrcArchetype::new();
+        orcs.spawn(EntityId::new(), "Grok".into(), 0);
+        orcs.spawn(EntityId::new(), "Mog".into(), 0);
+        orcs.alive[0] = false;
+        let mut goblins = GoblinArchetype::new();
+        let snik = goblins.spawn("Snik".into(), Vec2::new(3.0, 4.0), GoblinValues::new());
+
+        let mut archetypes: [&mut dyn Archetype; 2] = [&mut orcs, &mut goblins];
+        for archetype in archetypes.iter_mut() {
+            let parts = archetype.components_mut();
+            for (i, needs) in parts.needs.iter_mut().enumerate() {
+                if parts.alive[i] {
+                    needs.food = 0.9;
+                }
+            }
+        }
+
+        assert_eq!(orcs.species(), Species::Orc);
+        assert_eq!(orcs.components().living().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(orcs.needs[0].food, Needs::default().food);
+        assert_eq!(orcs.needs[1].food, 0.9);
+        let goblin_parts = goblins.components();
+        assert_eq!(goblin_parts.index_of(snik), Some(0));
+        assert_eq!(goblin_parts.positions[0].x, 3.0);
+        assert_eq!(goblin_parts.needs[0].food, 0.9);
+    }
+}
@@ -4,6 +4,7 @@ use crate::core::types::{EntityId, Vec2};
 use crate::entity::body::BodyState;
 use crate::entity::needs::Needs;
 use crate::entity::social::SocialMemory;
+use crate::entity::species::archetype::impl_archetype;
 use crate::entity::tasks::TaskQueue;
 use crate::entity::thoughts::ThoughtBuffer;
 use serde::{Deserialize, Serialize};
@@ -93,6 +94,8 @@ impl DryadArchetype {
     }
 }
 
+impl_archetype!(DryadArchetype, Dryad);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -7,14 +7,22 @@ use crate::core::types::{EntityId, Tick, Vec2};
 use crate::entity::body::BodyState;
 use crate::entity::needs::Needs;
 use crate::entity::social::SocialMemory;
+use crate::entity::species::archetype::impl_archetype;
 use crate::entity::tasks::TaskQueue;
 use crate::entity::thoughts::ThoughtBuffer;
+use crate::skills::{
+    generate_chunks_from_history, generate_history_for_role, ChunkLibrary, CraftSpecialty, Role,
+    SpeciesChunkModifiers,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 
 /// Dwarf-specific value vocabulary
 ///
 /// Dwarves prioritize tradition, craftsmanship, and clan honor over
 /// human concepts like beauty and curiosity.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DwarfValues {
     /// Respect for ancestral ways
     pub tradition: f32,
@@ -65,7 +73,56 @@ impl DwarfValues {
     }
 }
 
+impl crate::entity::species::value_access::ValueAccessor for DwarfValues {
+    fn get_value(&self, field_name: &str) -> Option<f32> {
+        match field_name {
+            "tradition" => Some(self.tradition),
+            "craftsmanship" => Some(self.craftsmanship),
+            "clan_honor" => Some(self.clan_honor),
+            "greed" => Some(self.greed),
+            "stubbornness" => Some(self.stubbornness),
+            "fortification" => Some(self.fortification),
+            "grudge" => Some(self.grudge),
+            _ => None,
+        }
+    }
+
+    fn set_value(&mut self, field_name: &str, value: f32) -> bool {
+        let field = match field_name {
+            "tradition" => &mut self.tradition,
+            "craftsmanship" => &mut self.craftsmanship,
+            "clan_honor" => &mut self.clan_honor,
+            "greed" => &mut self.greed,
+            "stubbornness" => &mut self.stubbornness,
+            "fortification" => &mut self.fortification,
+            "grudge" => &mut self.grudge,
+            _ => return false,
+        };
+        *field = value;
+        true
+    }
+
+    fn field_names() -> &'static [&'static str] {
+        &[
+            "tradition",
+            "craftsmanship",
+            "clan_honor",
+            "greed",
+            "stubbornness",
+            "fortification",
+            "grudge",
+        ]
+    }
+}
+
+/// Role whose life history a spawned dwarf has lived
+const SPAWN_ROLE: Role = Role::Craftsman(CraftSpecialty::Smithing);
+
+/// Age in years of a spawned dwarf
+const SPAWN_AGE: u32 = 60;
+
 /// Structure of Arrays for dwarf entities
+#[derive(Serialize, Deserialize)]
 pub struct DwarfArchetype {
     pub ids: Vec<EntityId>,
     pub names: Vec<String>,
@@ -79,6 +136,8 @@ pub struct DwarfArchetype {
     pub task_queues: Vec<TaskQueue>,
     pub alive: Vec<bool>,
     pub social_memories: Vec<SocialMemory>,
+    /// Skill chunk libraries, learned at the species' rate
+    pub chunk_libraries: Vec<ChunkLibrary>,
 }
 
 impl DwarfArchetype {
@@ -96,6 +155,7 @@ impl DwarfArchetype {
             task_queues: Vec::new(),
             alive: Vec::new(),
             social_memories: Vec::new(),
+            chunk_libraries: Vec::new(),
         }
     }
 
@@ -103,7 +163,13 @@ impl DwarfArchetype {
         self.ids.len()
     }
 
+    /// Spawn a new dwarf, with the skills of a life in the species' usual role
     pub fn spawn(&mut self, id: EntityId, name: String, tick: Tick) {
+        let mut rng = ChaCha8Rng::seed_from_u64(id.0.as_u128() as u64);
+        let history = generate_history_for_role(SPAWN_ROLE, SPAWN_AGE, &mut rng);
+        let mut chunks = generate_chunks_from_history(&history, tick, &mut rng);
+        SpeciesChunkModifiers::dwarf().shape(&mut chunks);
+
         self.ids.push(id);
         self.names.push(name);
         self.birth_ticks.push(tick);
@@ -116,6 +182,7 @@ impl DwarfArchetype {
         self.task_queues.push(TaskQueue::new());
         self.alive.push(true);
         self.social_memories.push(SocialMemory::new());
+        self.chunk_libraries.push(chunks);
     }
 
     pub fn index_of(&self, id: EntityId) -> Option<usize> {
@@ -131,6 +198,8 @@ impl DwarfArchetype {
     }
 }
 
+impl_archetype!(DwarfArchetype, Dwarf);
+
 impl Default for DwarfArchetype {
     fn default() -> Self {
         Self::new()
@@ -159,6 +228,13 @@ mod tests {
         assert_eq!(archetype.count(), 1);
         assert_eq!(archetype.index_of(id), Some(0));
         assert!(archetype.alive[0]);
+        // Spawned with a life's worth of skill
+        assert!(
+            archetype.chunk_libraries[0]
+                .domain_summary(crate::skills::ChunkDomain::Craft)
+                .chunk_count
+                > 0
+        );
     }
 
     #[test]
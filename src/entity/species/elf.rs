@@ -7,14 +7,22 @@ use crate::core::types::{EntityId, Tick, Vec2};
 use crate::entity::body::BodyState;
 use crate::entity::needs::Needs;
 use crate::entity::social::SocialMemory;
+use crate::entity::species::archetype::impl_archetype;
 use crate::entity::tasks::TaskQueue;
 use crate::entity::thoughts::ThoughtBuffer;
+use crate::skills::{
+    generate_chunks_from_history, generate_history_for_role, ChunkLibrary, Role,
+    SpeciesChunkModifiers,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 
 /// Elf-specific value vocabulary
 ///
 /// Elves prioritize beauty, wisdom, and nature over
 /// human concepts like ambition and comfort.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ElfValues {
     /// Appreciation for aesthetic perfection
     pub beauty: f32,
@@ -65,7 +73,56 @@ impl ElfValues {
     }
 }
 
+impl crate::entity::species::value_access::ValueAccessor for ElfValues {
+    fn get_value(&self, field_name: &str) -> Option<f32> {
+        match field_name {
+            "beauty" => Some(self.beauty),
+            "wisdom" => Some(self.wisdom),
+            "nature" => Some(self.nature),
+            "grace" => Some(self.grace),
+            "aloofness" => Some(self.aloofness),
+            "archery" => Some(self.archery),
+            "arcane_sense" => Some(self.arcane_sense),
+            _ => None,
+        }
+    }
+
+    fn set_value(&mut self, field_name: &str, value: f32) -> bool {
+        let field = match field_name {
+            "beauty" => &mut self.beauty,
+            "wisdom" => &mut self.wisdom,
+            "nature" => &mut self.nature,
+            "grace" => &mut self.grace,
+            "aloofness" => &mut self.aloofness,
+            "archery" => &mut self.archery,
+            "arcane_sense" => &mut self.arcane_sense,
+            _ => return false,
+        };
+        *field = value;
+        true
+    }
+
+    fn field_names() -> &'static [&'static str] {
+        &[
+            "beauty",
+            "wisdom",
+            "nature",
+            "grace",
+            "aloofness",
+            "archery",
+            "arcane_sense",
+        ]
+    }
+}
+
+/// Role whose life history a spawned elf has lived
+const SPAWN_ROLE: Role = Role::Scholar;
+
+/// Age in years of a spawned elf
+const SPAWN_AGE: u32 = 120;
+
 /// Structure of Arrays for elf entities
+#[derive(Serialize, Deserialize)]
 pub struct ElfArchetype {
     pub ids: Vec<EntityId>,
     pub names: Vec<String>,
@@ -79,6 +136,8 @@ pub struct ElfArchetype {
     pub task_queues: Vec<TaskQueue>,
     pub alive: Vec<bool>,
     pub social_memories: Vec<SocialMemory>,
+    /// Skill chunk libraries, learned at the species' rate
+    pub chunk_libraries: Vec<ChunkLibrary>,
 }
 
 impl ElfArchetype {
@@ -96,6 +155,7 @@ impl ElfArchetype {
             task_queues: Vec::new(),
             alive: Vec::new(),
             social_memories: Vec::new(),
+            chunk_libraries: Vec::new(),
         }
     }
 
@@ -103,7 +163,13 @@ impl ElfArchetype {
         self.ids.len()
     }
 
+    /// Spawn a new elf, with the skills of a life in the species' usual role
     pub fn spawn(&mut self, id: EntityId, name: String, tick: Tick) {
+        let mut rng = ChaCha8Rng::seed_from_u64(id.0.as_u128() as u64);
+        let history = generate_history_for_role(SPAWN_ROLE, SPAWN_AGE, &mut rng);
+        let mut chunks = generate_chunks_from_history(&history, tick, &mut rng);
+        SpeciesChunkModifiers::elf().shape(&mut chunks);
+
         self.ids.push(id);
         self.names.push(name);
         self.birth_ticks.push(tick);
@@ -116,6 +182,7 @@ impl ElfArchetype {
         self.task_queues.push(TaskQueue::new());
         self.alive.push(true);
         self.social_memories.push(SocialMemory::new());
+        self.chunk_libraries.push(chunks);
     }
 
     pub fn index_of(&self, id: EntityId) -> Option<usize> {
@@ -131,6 +198,8 @@ impl ElfArchetype {
     }
 }
 
+impl_archetype!(ElfArchetype, Elf);
+
 impl Default for ElfArchetype {
     fn default() -> Self {
         Self::new()
@@ -158,6 +227,13 @@ mod tests {
         assert_eq!(archetype.count(), 1);
         assert_eq!(archetype.index_of(id), Some(0));
         assert!(archetype.alive[0]);
+        // Spawned with a life's worth of skill
+        assert!(
+            archetype.chunk_libraries[0]
+                .domain_summary(crate::skills::ChunkDomain::Knowledge)
+                .chunk_count
+                > 0
+        );
     }
 
     #[test]
@@ -2,10 +2,15 @@
 
 use crate::city::BuildingId;
 use crate::combat::{combat_state_for_role, CombatState};
+use crate::core::astronomy::YEAR_LENGTH;
 use crate::core::types::{EntityId, Tick, Vec2};
+use crate::entity::aspirations::Aspiration;
 use crate::entity::body::BodyState;
+use crate::entity::inventory::Inventory;
 use crate::entity::needs::Needs;
+use crate::entity::possessions::Possessions;
 use crate::entity::social::{EventBuffer, SocialMemory};
+use crate::entity::species::archetype::impl_archetype;
 use crate::entity::tasks::TaskQueue;
 use crate::entity::thoughts::ThoughtBuffer;
 #[allow(deprecated)]
@@ -16,9 +21,10 @@ use crate::skills::{
 };
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 
 /// Human-specific value vocabulary
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HumanValues {
     pub honor: f32,
     pub beauty: f32,
@@ -53,11 +59,65 @@ impl HumanValues {
     }
 }
 
+impl crate::entity::species::value_access::ValueAccessor for HumanValues {
+    fn get_value(&self, field_name: &str) -> Option<f32> {
+        match field_name {
+            "honor" => Some(self.honor),
+            "beauty" => Some(self.beauty),
+            "comfort" => Some(self.comfort),
+            "ambition" => Some(self.ambition),
+            "loyalty" => Some(self.loyalty),
+            "love" => Some(self.love),
+            "justice" => Some(self.justice),
+            "curiosity" => Some(self.curiosity),
+            "safety" => Some(self.safety),
+            "piety" => Some(self.piety),
+            _ => None,
+        }
+    }
+
+    fn set_value(&mut self, field_name: &str, value: f32) -> bool {
+        let field = match field_name {
+            "honor" => &mut self.honor,
+            "beauty" => &mut self.beauty,
+            "comfort" => &mut self.comfort,
+            "ambition" => &mut self.ambition,
+            "loyalty" => &mut self.loyalty,
+            "love" => &mut self.love,
+            "justice" => &mut self.justice,
+            "curiosity" => &mut self.curiosity,
+            "safety" => &mut self.safety,
+            "piety" => &mut self.piety,
+            _ => return false,
+        };
+        *field = value;
+        true
+    }
+
+    fn field_names() -> &'static [&'static str] {
+        &[
+            "honor",
+            "beauty",
+            "comfort",
+            "ambition",
+            "loyalty",
+            "love",
+            "justice",
+            "curiosity",
+            "safety",
+            "piety",
+        ]
+    }
+}
+
 /// Structure of Arrays for human entities
+#[derive(Serialize, Deserialize)]
 pub struct HumanArchetype {
     pub ids: Vec<EntityId>,
     pub names: Vec<String>,
     pub birth_ticks: Vec<Tick>,
+    /// Age in days
+    pub ages: Vec<u32>,
     pub positions: Vec<Vec2>,
     pub velocities: Vec<Vec2>,
     pub body_states: Vec<BodyState>,
@@ -76,6 +136,14 @@ pub struct HumanArchetype {
     pub assigned_houses: Vec<Option<BuildingId>>,
     /// Skill chunk libraries for each entity
     pub chunk_libraries: Vec<crate::skills::ChunkLibrary>,
+    /// Personal goods and favors owed
+    pub possessions: Vec<Possessions>,
+    /// Carried and equipped items
+    pub inventories: Vec<Inventory>,
+    /// Days spent watching the sky (weather lore)
+    pub sky_observations: Vec<u32>,
+    /// What each person is working toward over months, if anything
+    pub aspirations: Vec<Option<Aspiration>>,
 }
 
 impl HumanArchetype {
@@ -84,6 +152,7 @@ impl HumanArchetype {
             ids: Vec::new(),
             names: Vec::new(),
             birth_ticks: Vec::new(),
+            ages: Vec::new(),
             positions: Vec::new(),
             velocities: Vec::new(),
             body_states: Vec::new(),
@@ -98,6 +167,10 @@ impl HumanArchetype {
             combat_states: Vec::new(),
             assigned_houses: Vec::new(),
             chunk_libraries: Vec::new(),
+            possessions: Vec::new(),
+            inventories: Vec::new(),
+            sky_observations: Vec::new(),
+            aspirations: Vec::new(),
         }
     }
 
@@ -128,6 +201,7 @@ impl HumanArchetype {
         // Calculate birth tick from age (approximate: 365 days * 24 ticks/day)
         self.birth_ticks
             .push(tick.saturating_sub((age as u64) * 365 * 24));
+        self.ages.push(age * YEAR_LENGTH as u32);
         self.positions.push(Vec2::default());
         self.velocities.push(Vec2::default());
         self.body_states.push(BodyState::default());
@@ -143,6 +217,10 @@ impl HumanArchetype {
         self.assigned_houses.push(None);
         self.chunk_libraries
             .push(generate_spawn_chunks(archetype, age, tick, &mut rng));
+        self.possessions.push(Possessions::new());
+        self.inventories.push(Inventory::new());
+        self.sky_observations.push(0);
+        self.aspirations.push(None);
     }
 
     /// Spawn a new entity with chunks based on role and age.
@@ -170,6 +248,7 @@ impl HumanArchetype {
         self.names.push(name);
         self.birth_ticks
             .push(tick.saturating_sub((age as u64) * 365 * 24));
+        self.ages.push(age * YEAR_LENGTH as u32);
         self.positions.push(Vec2::default());
         self.velocities.push(Vec2::default());
         self.body_states.push(BodyState::default());
@@ -185,6 +264,10 @@ impl HumanArchetype {
         self.combat_states.push(combat_state_for_role(role));
         self.assigned_houses.push(None);
         self.chunk_libraries.push(chunks);
+        self.possessions.push(Possessions::new());
+        self.inventories.push(Inventory::new());
+        self.sky_observations.push(0);
+        self.aspirations.push(None);
     }
 
     /// Spawn with explicit history (for important NPCs)
@@ -203,6 +286,7 @@ impl HumanArchetype {
         self.names.push(name);
         self.birth_ticks
             .push(tick.saturating_sub((age as u64) * 365 * 24));
+        self.ages.push(age * YEAR_LENGTH as u32);
         self.positions.push(Vec2::default());
         self.velocities.push(Vec2::default());
         self.body_states.push(BodyState::default());
@@ -217,6 +301,10 @@ impl HumanArchetype {
         self.combat_states.push(CombatState::default());
         self.assigned_houses.push(None);
         self.chunk_libraries.push(chunks);
+        self.possessions.push(Possessions::new());
+        self.inventories.push(Inventory::new());
+        self.sky_observations.push(0);
+        self.aspirations.push(None);
     }
 
     pub fn index_of(&self, id: EntityId) -> Option<usize> {
@@ -231,6 +319,56 @@ impl HumanArchetype {
             .map(|(i, _)| i)
     }
 
+    /// Remove the entity at `index`, moving the last entity into its slot
+    pub fn swap_remove(&mut self, index: usize) {
+        let Self {
+            ids,
+            names,
+            birth_ticks,
+            ages,
+            positions,
+            velocities,
+            body_states,
+            needs,
+            thoughts,
+            values,
+            task_queues,
+            alive,
+            social_memories,
+            event_buffers,
+            building_skills,
+            combat_states,
+            assigned_houses,
+            chunk_libraries,
+            possessions,
+            inventories,
+            sky_observations,
+            aspirations,
+        } = self;
+        ids.swap_remove(index);
+        names.swap_remove(index);
+        birth_ticks.swap_remove(index);
+        ages.swap_remove(index);
+        positions.swap_remove(index);
+        velocities.swap_remove(index);
+        body_states.swap_remove(index);
+        needs.swap_remove(index);
+        thoughts.swap_remove(index);
+        values.swap_remove(index);
+        task_queues.swap_remove(index);
+        alive.swap_remove(index);
+        social_memories.swap_remove(index);
+        event_buffers.swap_remove(index);
+        building_skills.swap_remove(index);
+        combat_states.swap_remove(index);
+        assigned_houses.swap_remove(index);
+        chunk_libraries.swap_remove(index);
+        possessions.swap_remove(index);
+        inventories.swap_remove(index);
+        sky_observations.swap_remove(index);
+        aspirations.swap_remove(index);
+    }
+
     pub fn iter_homeless(&self) -> impl Iterator<Item = usize> + '_ {
         self.alive
             .iter()
@@ -240,6 +378,8 @@ impl HumanArchetype {
     }
 }
 
+impl_archetype!(HumanArchetype, Human);
+
 impl Default for HumanArchetype {
     fn default() -> Self {
         Self::new()
@@ -1,4 +1,6 @@
+pub mod archetype;
 pub mod value_access;
+pub use archetype::{Archetype, Components, ComponentsMut};
 pub use value_access::ValueAccessor;
 
 pub mod abyssal_demons;
@@ -5,7 +5,7 @@
 //! - **Naming**: `OrcArchetype` uses ECS terminology (SoA container), not game design
 //!   terminology (character class). Consider renaming to `OrcStorage` or `Orcs` for clarity.
 //!
-//! - **Initial values**: Currently spawns with `OrcValues::default()` (all 0.0). A proper
+//! - **Initial values**: Currently spawns with `OrcValues::default()` (aggressive). A proper
 //!   spawner should roll initial values within species-appropriate ranges.
 //!
 //! - **Character classes**: Warrior/Shaman/etc. archetypes are a separate concept not yet
@@ -18,14 +18,16 @@ use crate::core::types::{EntityId, Tick, Vec2};
 use crate::entity::body::BodyState;
 use crate::entity::needs::Needs;
 use crate::entity::social::SocialMemory;
+use crate::entity::species::archetype::impl_archetype;
 use crate::entity::tasks::TaskQueue;
 use crate::entity::thoughts::ThoughtBuffer;
+use serde::{Deserialize, Serialize};
 
 /// Orc-specific value vocabulary
 ///
 /// Orcs prioritize strength, dominance, and clan loyalty over
 /// human concepts like honor, beauty, and piety.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrcValues {
     pub rage: f32,
     pub strength: f32,
@@ -69,7 +71,50 @@ impl OrcValues {
     }
 }
 
+impl crate::entity::species::value_access::ValueAccessor for OrcValues {
+    fn get_value(&self, field_name: &str) -> Option<f32> {
+        match field_name {
+            "rage" => Some(self.rage),
+            "strength" => Some(self.strength),
+            "dominance" => Some(self.dominance),
+            "clan_loyalty" => Some(self.clan_loyalty),
+            "blood_debt" => Some(self.blood_debt),
+            "territory" => Some(self.territory),
+            "combat_prowess" => Some(self.combat_prowess),
+            _ => None,
+        }
+    }
+
+    fn set_value(&mut self, field_name: &str, value: f32) -> bool {
+        let field = match field_name {
+            "rage" => &mut self.rage,
+            "strength" => &mut self.strength,
+            "dominance" => &mut self.dominance,
+            "clan_loyalty" => &mut self.clan_loyalty,
+            "blood_debt" => &mut self.blood_debt,
+            "territory" => &mut self.territory,
+            "combat_prowess" => &mut self.combat_prowess,
+            _ => return false,
+        };
+        *field = value;
+        true
+    }
+
+    fn field_names() -> &'static [&'static str] {
+        &[
+            "rage",
+            "strength",
+            "dominance",
+            "clan_loyalty",
+            "blood_debt",
+            "territory",
+            "combat_prowess",
+        ]
+    }
+}
+
 /// Structure of Arrays for orc entities
+#[derive(Serialize, Deserialize)]
 pub struct OrcArchetype {
     pub ids: Vec<EntityId>,
     pub names: Vec<String>,
@@ -135,6 +180,8 @@ impl OrcArchetype {
     }
 }
 
+impl_archetype!(OrcArchetype, Orc);
+
 impl Default for OrcArchetype {
     fn default() -> Self {
         Self::new()
@@ -148,13 +195,24 @@ mod tests {
     #[test]
     fn test_orc_default_values() {
         let values = OrcValues::default();
-        assert_eq!(values.rage, 0.0);
-        assert_eq!(values.strength, 0.0);
-        assert_eq!(values.dominance, 0.0);
-        assert_eq!(values.clan_loyalty, 0.0);
+        assert_eq!(values.rage, 0.6);
+        assert_eq!(values.strength, 0.7);
+        assert_eq!(values.dominance, 0.5);
+        assert_eq!(values.clan_loyalty, 0.5);
         assert_eq!(values.blood_debt, 0.0);
-        assert_eq!(values.territory, 0.0);
-        assert_eq!(values.combat_prowess, 0.0);
+        assert_eq!(values.territory, 0.4);
+        assert_eq!(values.combat_prowess, 0.6);
+    }
+
+    #[test]
+    fn test_orc_values_by_name() {
+        use crate::entity::species::value_access::ValueAccessor;
+
+        let mut values = OrcValues::default();
+        assert!(values.set_value("blood_debt", 0.8));
+        assert_eq!(values.get_value("blood_debt"), Some(0.8));
+        assert!(!values.set_value("honor", 0.5));
+        assert_eq!(OrcValues::field_names().len(), 7);
     }
 
     #[test]
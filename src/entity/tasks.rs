@@ -75,7 +75,7 @@ impl Task {
 }
 
 /// Queue of tasks for an entity
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TaskQueue {
     current: Option<Task>,
     queued: VecDeque<Task>,
@@ -126,6 +126,36 @@ impl TaskQueue {
     pub fn is_idle(&self) -> bool {
         self.current.is_none() && self.queued.is_empty()
     }
+
+    /// Number of tasks waiting behind the current one
+    pub fn queued_len(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Give every task `which` picks out a new priority, and re-order those
+    /// waiting behind the current one. Returns how many were changed.
+    pub fn reprioritize(&mut self, priority: TaskPriority, which: impl Fn(&Task) -> bool) -> usize {
+        let mut changed = 0;
+        let tasks: Vec<Task> = self
+            .current
+            .take()
+            .into_iter()
+            .chain(self.queued.drain(..))
+            .collect();
+        for mut task in tasks {
+            if which(&task) {
+                task.priority = priority;
+                changed += 1;
+            }
+            self.push(task);
+        }
+        changed
+    }
+
+    /// The current task, then those waiting behind it in order
+    pub fn iter(&self) -> impl Iterator<Item = &Task> {
+        self.current.iter().chain(self.queued.iter())
+    }
 }
 
 #[cfg(test)]
@@ -60,7 +60,12 @@ impl Thought {
     }
 
     pub fn decay(&mut self) {
-        self.intensity = (self.intensity - self.decay_rate).max(0.0);
+        self.decay_over(1);
+    }
+
+    /// Decay by `ticks` ticks' worth at once
+    pub fn decay_over(&mut self, ticks: u32) {
+        self.intensity = (self.intensity - self.decay_rate * ticks as f32).max(0.0);
     }
 
     pub fn is_faded(&self) -> bool {
@@ -69,7 +74,7 @@ impl Thought {
 }
 
 /// Buffer of active thoughts
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ThoughtBuffer {
     thoughts: Vec<Thought>,
     max_thoughts: usize,
@@ -114,8 +119,13 @@ impl ThoughtBuffer {
     }
 
     pub fn decay_all(&mut self) {
+        self.decay_all_over(1);
+    }
+
+    /// Decay every thought by `ticks` ticks' worth at once
+    pub fn decay_all_over(&mut self, ticks: u32) {
         for thought in &mut self.thoughts {
-            thought.decay();
+            thought.decay_over(ticks);
         }
         self.thoughts.retain(|t| !t.is_faded());
     }
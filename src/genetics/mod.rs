@@ -2,3 +2,5 @@ pub mod genome;
 pub mod personality;
 pub mod phenotype;
 pub mod values;
+
+pub use phenotype::Phenotype;
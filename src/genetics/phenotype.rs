@@ -1 +1,49 @@
-//! Phenotype - placeholder
+//! Phenotype - physical and mental traits that cap skill
+//!
+//! Traits are multipliers around 1.0 (an average adult). Genomes are not yet
+//! expressed, so every entity currently has the default phenotype.
+
+use serde::{Deserialize, Serialize};
+
+use crate::skills::ChunkDomain;
+
+/// Expressed traits of one entity
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Phenotype {
+    pub strength: f32,
+    pub agility: f32,
+    pub endurance: f32,
+    pub dexterity: f32,
+    pub intellect: f32,
+    pub perception: f32,
+    pub voice_quality: f32,
+}
+
+impl Default for Phenotype {
+    fn default() -> Self {
+        Self {
+            strength: 1.0,
+            agility: 1.0,
+            endurance: 1.0,
+            dexterity: 1.0,
+            intellect: 1.0,
+            perception: 1.0,
+            voice_quality: 1.0,
+        }
+    }
+}
+
+impl Phenotype {
+    /// How far the body lets practice go in a domain, scaling chunk encoding
+    pub fn domain_ceiling(&self, domain: ChunkDomain) -> f32 {
+        match domain {
+            ChunkDomain::Combat => (self.strength + self.agility) / 2.0,
+            ChunkDomain::Craft => (self.dexterity + self.strength) / 2.0,
+            ChunkDomain::Social => (self.voice_quality + self.perception) / 2.0,
+            ChunkDomain::Medicine => (self.dexterity + self.intellect) / 2.0,
+            ChunkDomain::Leadership => (self.voice_quality + self.intellect) / 2.0,
+            ChunkDomain::Knowledge => self.intellect,
+            ChunkDomain::Physical => (self.strength + self.agility + self.endurance) / 3.0,
+        }
+    }
+}
@@ -1 +1,61 @@
-//! Values - placeholder
+//! Values - inherited from parents
+//!
+//! Until genomes exist, a child's values are drawn straight from its
+//! parents: each value lands between the two parents' and drifts a little
+//! either way, so families share a temperament without being copies.
+
+use rand::Rng;
+
+use crate::entity::species::human::HumanValues;
+
+/// Largest drift of an inherited value away from the parents' blend
+pub const VALUE_MUTATION: f32 = 0.1;
+
+fn inherit<R: Rng>(a: f32, b: f32, rng: &mut R) -> f32 {
+    let blend = a + (b - a) * rng.gen::<f32>();
+    (blend + rng.gen_range(-VALUE_MUTATION..=VALUE_MUTATION)).clamp(0.0, 1.0)
+}
+
+/// Values for a child of two humans
+pub fn inherit_human_values<R: Rng>(a: &HumanValues, b: &HumanValues, rng: &mut R) -> HumanValues {
+    HumanValues {
+        honor: inherit(a.honor, b.honor, rng),
+        beauty: inherit(a.beauty, b.beauty, rng),
+        comfort: inherit(a.comfort, b.comfort, rng),
+        ambition: inherit(a.ambition, b.ambition, rng),
+        loyalty: inherit(a.loyalty, b.loyalty, rng),
+        love: inherit(a.love, b.love, rng),
+        justice: inherit(a.justice, b.justice, rng),
+        curiosity: inherit(a.curiosity, b.curiosity, rng),
+        safety: inherit(a.safety, b.safety, rng),
+        piety: inherit(a.piety, b.piety, rng),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_child_values_stay_near_parents() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let a = HumanValues {
+            honor: 0.9,
+            curiosity: 0.2,
+            ..Default::default()
+        };
+        let b = HumanValues {
+            honor: 0.7,
+            curiosity: 0.4,
+            ..Default::default()
+        };
+        for _ in 0..50 {
+            let child = inherit_human_values(&a, &b, &mut rng);
+            assert!((0.55..=1.0).contains(&child.honor));
+            assert!((0.05..=0.55).contains(&child.curiosity));
+            assert!(child.piety <= VALUE_MUTATION);
+        }
+    }
+}
@@ -10,6 +10,7 @@ pub mod combat;
 pub mod command;
 pub mod core;
 pub mod data;
+pub mod debug;
 pub mod ecs;
 pub mod entity;
 pub mod genetics;
@@ -21,3 +22,4 @@ pub mod skills;
 pub mod spatial;
 pub mod ui;
 pub mod world;
+pub mod worldgen;
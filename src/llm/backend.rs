@@ -0,0 +1,513 @@
+//! LLM backends - the APIs a completion can be sent to
+//!
+//! `LlmBackend` is one way of turning a system prompt and a user message
+//! into a reply. There are backends for OpenAI-compatible chat endpoints
+//! (OpenAI, DeepSeek, and local llama.cpp servers), Anthropic's messages API,
+//! and Ollama's chat API, so commands can be parsed by a hosted model or by a
+//! local one with no network connection at all. `LlmClient` picks one from
+//! its `LlmConfig`.
+//!
+//! Backends whose API can be made to call a function (Anthropic tool use,
+//! OpenAI function calling) answer `complete_tool` with the arguments the
+//! model called it with. The rest answer in prose, and the caller digs the
+//! JSON out of the reply.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{ArcError, Result};
+
+/// A reply on its way from a backend
+pub type Completion<'a> = Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>>;
+
+/// A function the model is made to call, with a JSON Schema for its
+/// arguments
+#[derive(Debug, Clone)]
+pub struct ToolSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub parameters: serde_json::Value,
+}
+
+/// Something that can complete a prompt
+pub trait LlmBackend: Send + Sync {
+    /// Short name for logs ("anthropic", "ollama", ...)
+    fn name(&self) -> &'static str;
+
+    /// Send a system prompt and a user message, and return the reply text
+    fn complete<'a>(&'a self, system: &'a str, user: &'a str) -> Completion<'a>;
+
+    /// Whether `complete_tool` really makes the model call the tool
+    fn supports_tools(&self) -> bool {
+        false
+    }
+
+    /// Make the model call `tool`, and return the arguments it was called
+    /// with as JSON; backends without tools return the plain reply
+    fn complete_tool<'a>(
+        &'a self,
+        system: &'a str,
+        user: &'a str,
+        _tool: &'a ToolSpec,
+    ) -> Completion<'a> {
+        self.complete(system, user)
+    }
+}
+
+/// Largest reply to ask for from a model
+fn max_tokens_for(model: &str) -> u32 {
+    if model.contains("reasoner") {
+        32768 // DeepSeek R1 supports up to 32K-64K output
+    } else {
+        8192 // DeepSeek V3/chat max is 8192
+    }
+}
+
+/// Post a JSON request and decode the JSON reply, reporting HTTP errors
+///
+/// Network failures, rate limiting (429), and server errors (5xx) are
+/// `LlmUnavailable`, so callers know a retry may succeed.
+async fn post_json<Req: Serialize, Resp: for<'de> Deserialize<'de>>(
+    request: reqwest::RequestBuilder,
+    body: &Req,
+) -> Result<Resp> {
+    let response = request
+        .header("content-type", "application/json")
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| ArcError::LlmUnavailable(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        let message = format!("API error ({}): {}", status, error_text);
+        return Err(
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                ArcError::LlmUnavailable(message)
+            } else {
+                ArcError::LlmError(message)
+            },
+        );
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ArcError::LlmError(e.to_string()))
+}
+
+/// An OpenAI-compatible chat completions endpoint (OpenAI, DeepSeek, or a
+/// local llama.cpp server, which needs no key)
+pub struct OpenAiBackend {
+    client: Client,
+    api_key: Option<String>,
+    api_url: String,
+    model: String,
+    tools: bool,
+}
+
+impl OpenAiBackend {
+    pub fn new(api_key: Option<String>, api_url: String, model: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            api_url,
+            model,
+            tools: true,
+        }
+    }
+
+    /// Send tool definitions with `complete_tool`, or only the plain prompt
+    /// to a server that rejects them
+    pub fn with_tools(mut self, tools: bool) -> Self {
+        self.tools = tools;
+        self
+    }
+}
+
+impl LlmBackend for OpenAiBackend {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn complete<'a>(&'a self, system: &'a str, user: &'a str) -> Completion<'a> {
+        Box::pin(async move {
+            let request = OpenAIRequest {
+                model: self.model.clone(),
+                max_tokens: max_tokens_for(&self.model),
+                messages: vec![Message::new("system", system), Message::new("user", user)],
+                tools: Vec::new(),
+                tool_choice: None,
+            };
+            let completion = self.send(&request).await?;
+
+            completion
+                .choices
+                .first()
+                .and_then(|c| c.message.content.clone())
+                .ok_or_else(|| ArcError::LlmError("Empty response".into()))
+        })
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.tools
+    }
+
+    fn complete_tool<'a>(
+        &'a self,
+        system: &'a str,
+        user: &'a str,
+        tool: &'a ToolSpec,
+    ) -> Completion<'a> {
+        if !self.tools {
+            return self.complete(system, user);
+        }
+        Box::pin(async move {
+            let request = OpenAIRequest {
+                model: self.model.clone(),
+                max_tokens: max_tokens_for(&self.model),
+                messages: vec![Message::new("system", system), Message::new("user", user)],
+                tools: vec![serde_json::json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.name,
+                        "description": tool.description,
+                        "parameters": tool.parameters,
+                    }
+                })],
+                tool_choice: Some(serde_json::json!({
+                    "type": "function",
+                    "function": { "name": tool.name }
+                })),
+            };
+            let completion = self.send(&request).await?;
+
+            let message = completion
+                .choices
+                .into_iter()
+                .next()
+                .map(|c| c.message)
+                .ok_or_else(|| ArcError::LlmError("Empty response".into()))?;
+            // A model that answers in prose instead is still worth reading
+            match message.tool_calls.into_iter().next() {
+                Some(call) => Ok(call.function.arguments),
+                None => message
+                    .content
+                    .ok_or_else(|| ArcError::LlmError("No tool call in response".into())),
+            }
+        })
+    }
+}
+
+impl OpenAiBackend {
+    async fn send(&self, request: &OpenAIRequest) -> Result<OpenAIResponse> {
+        let mut builder = self.client.post(&self.api_url);
+        if let Some(key) = &self.api_key {
+            builder = builder.header("Authorization", format!("Bearer {}", key));
+        }
+        post_json(builder, request).await
+    }
+}
+
+/// Anthropic's messages API
+pub struct AnthropicBackend {
+    client: Client,
+    api_key: String,
+    api_url: String,
+    model: String,
+}
+
+impl AnthropicBackend {
+    pub fn new(api_key: String, api_url: String, model: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            api_url,
+            model,
+        }
+    }
+}
+
+impl LlmBackend for AnthropicBackend {
+    fn name(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn complete<'a>(&'a self, system: &'a str, user: &'a str) -> Completion<'a> {
+        Box::pin(async move {
+            let request = AnthropicRequest {
+                model: self.model.clone(),
+                max_tokens: 8192,
+                system: system.into(),
+                messages: vec![Message::new("user", user)],
+                tools: Vec::new(),
+                tool_choice: None,
+            };
+            let completion = self.send(&request).await?;
+
+            completion
+                .content
+                .into_iter()
+                .find_map(|c| c.text)
+                .ok_or_else(|| ArcError::LlmError("Empty response".into()))
+        })
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn complete_tool<'a>(
+        &'a self,
+        system: &'a str,
+        user: &'a str,
+        tool: &'a ToolSpec,
+    ) -> Completion<'a> {
+        Box::pin(async move {
+            let request = AnthropicRequest {
+                model: self.model.clone(),
+                max_tokens: 8192,
+                system: system.into(),
+                messages: vec![Message::new("user", user)],
+                tools: vec![serde_json::json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "input_schema": tool.parameters,
+                })],
+                tool_choice: Some(serde_json::json!({ "type": "tool", "name": tool.name })),
+            };
+            let completion = self.send(&request).await?;
+
+            let mut text = None;
+            for block in completion.content {
+                if let Some(input) = block.input {
+                    return Ok(input.to_string());
+                }
+                text = text.or(block.text);
+            }
+            text.ok_or_else(|| ArcError::LlmError("No tool call in response".into()))
+        })
+    }
+}
+
+impl AnthropicBackend {
+    async fn send(&self, request: &AnthropicRequest) -> Result<AnthropicResponse> {
+        let builder = self
+            .client
+            .post(&self.api_url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01");
+        post_json(builder, request).await
+    }
+}
+
+/// A local Ollama server's chat API
+pub struct OllamaBackend {
+    client: Client,
+    api_url: String,
+    model: String,
+}
+
+impl OllamaBackend {
+    pub fn new(api_url: String, model: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_url,
+            model,
+        }
+    }
+}
+
+impl LlmBackend for OllamaBackend {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn complete<'a>(&'a self, system: &'a str, user: &'a str) -> Completion<'a> {
+        Box::pin(async move {
+            let request = OllamaRequest {
+                model: self.model.clone(),
+                messages: vec![Message::new("system", system), Message::new("user", user)],
+                stream: false,
+            };
+            let completion: OllamaResponse =
+                post_json(self.client.post(&self.api_url), &request).await?;
+            completion
+                .message
+                .content
+                .ok_or_else(|| ArcError::LlmError("Empty response".into()))
+        })
+    }
+}
+
+// Anthropic API format
+#[derive(Serialize)]
+struct AnthropicRequest {
+    model: String,
+    max_tokens: u32,
+    system: String,
+    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicResponse {
+    content: Vec<ContentBlock>,
+}
+
+/// A text block has `text`, a tool use block `input`
+#[derive(Deserialize)]
+struct ContentBlock {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    input: Option<serde_json::Value>,
+}
+
+// OpenAI-compatible API format (DeepSeek, OpenAI, llama.cpp, etc.)
+#[derive(Serialize)]
+struct OpenAIRequest {
+    model: String,
+    max_tokens: u32,
+    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+/// `content` is null when the model called a tool instead
+#[derive(Deserialize)]
+struct ChoiceMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Deserialize)]
+struct ToolCall {
+    function: FunctionCall,
+}
+
+/// The arguments arrive as a string of JSON
+#[derive(Deserialize)]
+struct FunctionCall {
+    arguments: String,
+}
+
+// Ollama chat API format
+#[derive(Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<Message>,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponse {
+    message: ChoiceMessage,
+}
+
+// Shared
+#[derive(Serialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+impl Message {
+    fn new(role: &str, content: &str) -> Self {
+        Self {
+            role: role.into(),
+            content: content.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_ollama_backend_talks_to_a_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api/chat", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read until the whole JSON body has arrived
+            while !String::from_utf8_lossy(&request).trim_end().ends_with('}') {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let body = r#"{"message":{"role":"assistant","content":"build a wall"},"done":true}"#;
+            let reply = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(reply.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let backend = OllamaBackend::new(url, "llama3.1".into());
+        let reply = backend.complete("Parse commands", "wall please").await;
+        let request = server.await.unwrap();
+
+        assert_eq!(reply.unwrap(), "build a wall");
+        assert!(request.starts_with("POST /api/chat"));
+        assert!(request.contains(r#""stream":false"#));
+        assert!(request.contains(r#""model":"llama3.1""#));
+    }
+
+    #[test]
+    fn test_tool_calls_are_read_from_replies() {
+        let openai: OpenAIResponse = serde_json::from_str(
+            r#"{"choices":[{"message":{"role":"assistant","content":null,
+                "tool_calls":[{"id":"1","type":"function",
+                "function":{"name":"parse_intent","arguments":"{\"action\":\"BUILD\"}"}}]}}]}"#,
+        )
+        .unwrap();
+        let message = &openai.choices[0].message;
+        assert!(message.content.is_none());
+        assert_eq!(
+            message.tool_calls[0].function.arguments,
+            r#"{"action":"BUILD"}"#
+        );
+
+        let anthropic: AnthropicResponse = serde_json::from_str(
+            r#"{"content":[{"type":"text","text":"Parsing."},
+                {"type":"tool_use","id":"t","name":"parse_intent","input":{"action":"REST"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(anthropic.content[0].text.as_deref(), Some("Parsing."));
+        assert_eq!(
+            anthropic.content[1].input.as_ref().unwrap()["action"],
+            "REST"
+        );
+    }
+}
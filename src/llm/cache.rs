@@ -0,0 +1,138 @@
+//! On-disk cache of parsed intents
+//!
+//! Parsing the same command in the same situation gives the same intent, so
+//! there is no need to pay for (or wait on) the LLM twice. Entries are keyed
+//! by a stable hash of the system prompt, the command with case and spacing
+//! normalised, and the game context digest, and kept in memory and, if the
+//! cache has a directory, as one JSON file per entry that later runs reuse.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::core::error::Result;
+use crate::llm::parser::ParsedIntent;
+
+/// Cache of parsed intents by prompt and context
+#[derive(Default)]
+pub struct IntentCache {
+    dir: Option<PathBuf>,
+    entries: Mutex<HashMap<u64, ParsedIntent>>,
+}
+
+/// 64-bit FNV-1a, stable across runs and platforms
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+impl IntentCache {
+    /// A cache that lasts only as long as the process
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// A cache kept in `dir`, created if missing
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: Some(dir.as_ref().to_path_buf()),
+            entries: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The cache key for a command parsed with this prompt in this context
+    pub fn key(system: &str, input: &str, context_digest: &str) -> u64 {
+        let command = input
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        [system, command.as_str(), context_digest]
+            .iter()
+            // Separate the parts so ("ab", "c") and ("a", "bc") differ
+            .fold(0xcbf2_9ce4_8422_2325, |hash, part| {
+                fnv1a(fnv1a(hash, part.as_bytes()), &[0xff])
+            })
+    }
+
+    fn path(&self, key: u64) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{:016x}.json", key)))
+    }
+
+    /// The cached intent for a key, from memory or disk
+    pub fn get(&self, key: u64) -> Option<ParsedIntent> {
+        if let Some(intent) = self.entries.lock().unwrap().get(&key) {
+            return Some(intent.clone());
+        }
+        let content = fs::read_to_string(self.path(key)?).ok()?;
+        let intent: ParsedIntent = serde_json::from_str(&content).ok()?;
+        self.entries.lock().unwrap().insert(key, intent.clone());
+        Some(intent)
+    }
+
+    /// Remember an intent; failing to write it to disk only logs a warning
+    pub fn insert(&self, key: u64, intent: &ParsedIntent) {
+        self.entries.lock().unwrap().insert(key, intent.clone());
+        if let Some(path) = self.path(key) {
+            let written = serde_json::to_string_pretty(intent)
+                .map_err(std::io::Error::from)
+                .and_then(|json| fs::write(&path, json));
+            if let Err(e) = written {
+                tracing::warn!("Could not write LLM cache entry {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Number of entries loaded in memory
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::parser::IntentAction;
+
+    #[test]
+    fn test_cache_survives_a_restart() {
+        let dir =
+            std::env::temp_dir().join(format!("arc_citadel_llm_cache_{}", std::process::id()));
+        let key = IntentCache::key("prompt", "Build a  wall", "3 settlers");
+        // Case and spacing do not matter; the context does
+        assert_eq!(
+            key,
+            IntentCache::key("prompt", "build a wall ", "3 settlers")
+        );
+        assert_ne!(
+            key,
+            IntentCache::key("prompt", "build a wall", "4 settlers")
+        );
+
+        let intent = ParsedIntent {
+            action: IntentAction::Build,
+            target: Some("wall".into()),
+            confidence: 0.9,
+            ..Default::default()
+        };
+        IntentCache::open(&dir).unwrap().insert(key, &intent);
+
+        let reopened = IntentCache::open(&dir).unwrap();
+        assert!(reopened.is_empty());
+        let cached = reopened.get(key).unwrap();
+        assert_eq!(cached.action, IntentAction::Build);
+        assert_eq!(cached.target.as_deref(), Some("wall"));
+        assert!(reopened.get(key ^ 1).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
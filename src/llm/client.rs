@@ -1,13 +1,24 @@
 //! Async LLM client for command parsing
 //!
-//! This is a model-agnostic HTTP client for calling LLM APIs.
-//! Supports both Anthropic and OpenAI-compatible APIs (DeepSeek, etc).
+//! This is a model-agnostic client for calling LLMs. The API it talks to is
+//! a `LlmBackend` chosen by `LlmConfig`: Anthropic, any OpenAI-compatible
+//! endpoint (DeepSeek, OpenAI, a local llama.cpp server), or a local Ollama
+//! server, so natural-language commands work offline too. Calls are held to
+//! the configured rate and retried with backoff when the service is
+//! unavailable, and parsed intents can be cached (see `IntentCache`).
 //! Key principle: LLMs parse COMMANDS only, not entity behavior
 //! (entities use rules-based systems for emergent behavior).
 
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
 use crate::core::error::{ArcError, Result};
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use crate::llm::backend::{
+    AnthropicBackend, Completion, LlmBackend, OllamaBackend, OpenAiBackend, ToolSpec,
+};
+use crate::llm::cache::IntentCache;
+use crate::llm::rate_limit::{RateLimiter, RetryPolicy};
 
 /// API format type
 #[derive(Debug, Clone, PartialEq)]
@@ -16,28 +27,288 @@ pub enum ApiFormat {
     OpenAI,
 }
 
+/// Which LLM service to talk to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    /// Anthropic's hosted messages API
+    Anthropic,
+    /// An OpenAI-compatible hosted API (OpenAI, DeepSeek, ...)
+    OpenAi,
+    /// A local Ollama server
+    Ollama,
+    /// A local llama.cpp server (OpenAI-compatible, no key)
+    LlamaCpp,
+}
+
+impl LlmProvider {
+    /// Parse a provider name as given in `LLM_PROVIDER`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name
+            .trim()
+            .to_lowercase()
+            .replace(['-', '_', '.'], "")
+            .as_str()
+        {
+            "anthropic" => Some(Self::Anthropic),
+            "openai" | "deepseek" => Some(Self::OpenAi),
+            "ollama" => Some(Self::Ollama),
+            "llamacpp" => Some(Self::LlamaCpp),
+            _ => None,
+        }
+    }
+
+    /// Whether this provider runs on this machine and needs no API key
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Ollama | Self::LlamaCpp)
+    }
+
+    /// Whether the provider's API takes tool definitions by default
+    ///
+    /// A llama.cpp server only does with a model and template that support
+    /// them, and rejects the request otherwise.
+    pub fn supports_tools(&self) -> bool {
+        matches!(self, Self::Anthropic | Self::OpenAi)
+    }
+
+    /// Endpoint used when none is configured
+    pub fn default_url(&self) -> &'static str {
+        match self {
+            Self::Anthropic => "https://api.anthropic.com/v1/messages",
+            Self::OpenAi => "https://api.openai.com/v1/chat/completions",
+            Self::Ollama => "http://localhost:11434/api/chat",
+            Self::LlamaCpp => "http://localhost:8080/v1/chat/completions",
+        }
+    }
+
+    /// Model used when none is configured
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            Self::Anthropic => "claude-3-haiku-20240307",
+            Self::OpenAi => "gpt-4o-mini",
+            Self::Ollama => "llama3.1",
+            // llama.cpp serves whatever model it was started with
+            Self::LlamaCpp => "local",
+        }
+    }
+}
+
+/// Which LLM to use and how to reach it
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LlmConfig {
+    pub provider: LlmProvider,
+    /// Endpoint URL (provider default if unset)
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Model name (provider default if unset)
+    #[serde(default)]
+    pub model: Option<String>,
+    /// API key; required by hosted providers only
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Most calls a minute (unlimited if unset)
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+    /// Retries of a call to an unavailable service (`RetryPolicy` default if unset)
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+    /// Directory to cache parsed intents in (no cache if unset)
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+    /// Whether to make the model call tools (provider default if unset)
+    #[serde(default)]
+    pub tools: Option<bool>,
+}
+
+impl LlmConfig {
+    /// A config for this provider with all its defaults
+    pub fn new(provider: LlmProvider) -> Self {
+        Self {
+            provider,
+            api_url: None,
+            model: None,
+            api_key: None,
+            requests_per_minute: None,
+            max_retries: None,
+            cache_dir: None,
+            tools: None,
+        }
+    }
+
+    /// Parse a config from TOML
+    pub fn parse(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|e| ArcError::LlmError(e.to_string()))
+    }
+
+    /// Load a config from a TOML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Build a config from environment variables
+    ///
+    /// Optional: LLM_PROVIDER (anthropic, openai, ollama, llamacpp; detected
+    /// from LLM_API_URL if unset, defaulting to Anthropic)
+    /// Optional: LLM_API_URL, LLM_MODEL (provider defaults)
+    /// Optional: LLM_REQUESTS_PER_MINUTE, LLM_MAX_RETRIES, LLM_CACHE_DIR
+    /// Optional: LLM_TOOLS (true or false; provider default if unset)
+    /// Required for hosted providers: LLM_API_KEY
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let api_url = var("LLM_API_URL");
+        let provider = match var("LLM_PROVIDER") {
+            Some(name) => LlmProvider::parse(&name)
+                .ok_or_else(|| ArcError::LlmError(format!("Unknown LLM_PROVIDER '{}'", name)))?,
+            None => api_url
+                .as_deref()
+                .map(LlmClient::detect_provider)
+                .unwrap_or(LlmProvider::Anthropic),
+        };
+        let number = |name: &str| -> Result<Option<u32>> {
+            var(name)
+                .map(|v| {
+                    v.parse().map_err(|_| {
+                        ArcError::LlmError(format!("{} is not a number: '{}'", name, v))
+                    })
+                })
+                .transpose()
+        };
+        let tools = var("LLM_TOOLS")
+            .map(|v| {
+                v.parse().map_err(|_| {
+                    ArcError::LlmError(format!("LLM_TOOLS is not true or false: '{}'", v))
+                })
+            })
+            .transpose()?;
+        let config = Self {
+            provider,
+            api_url,
+            model: var("LLM_MODEL"),
+            api_key: var("LLM_API_KEY"),
+            requests_per_minute: number("LLM_REQUESTS_PER_MINUTE")?,
+            max_retries: number("LLM_MAX_RETRIES")?,
+            cache_dir: var("LLM_CACHE_DIR").map(PathBuf::from),
+            tools,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that a hosted provider has a key
+    pub fn validate(&self) -> Result<()> {
+        if !self.provider.is_local() && self.api_key.is_none() {
+            return Err(ArcError::LlmError("LLM_API_KEY not set".into()));
+        }
+        Ok(())
+    }
+
+    /// The endpoint URL to use
+    pub fn url(&self) -> &str {
+        self.api_url
+            .as_deref()
+            .unwrap_or_else(|| self.provider.default_url())
+    }
+
+    /// The model name to use
+    pub fn model_name(&self) -> &str {
+        self.model
+            .as_deref()
+            .unwrap_or_else(|| self.provider.default_model())
+    }
+
+    /// Whether the model is to be made to call tools
+    pub fn use_tools(&self) -> bool {
+        self.tools.unwrap_or_else(|| self.provider.supports_tools())
+    }
+
+    /// The backend this config describes
+    pub fn backend(&self) -> Result<Box<dyn LlmBackend>> {
+        self.validate()?;
+        let (url, model) = (self.url().to_string(), self.model_name().to_string());
+        Ok(match self.provider {
+            LlmProvider::Anthropic => Box::new(AnthropicBackend::new(
+                self.api_key.clone().unwrap_or_default(),
+                url,
+                model,
+            )),
+            LlmProvider::OpenAi | LlmProvider::LlamaCpp => Box::new(
+                OpenAiBackend::new(self.api_key.clone(), url, model).with_tools(self.use_tools()),
+            ),
+            LlmProvider::Ollama => Box::new(OllamaBackend::new(url, model)),
+        })
+    }
+
+    /// How failed calls are retried
+    pub fn retry_policy(&self) -> RetryPolicy {
+        let default = RetryPolicy::default();
+        RetryPolicy {
+            max_retries: self.max_retries.unwrap_or(default.max_retries),
+            ..default
+        }
+    }
+}
+
 /// Async LLM client for making API calls
 pub struct LlmClient {
-    client: Client,
-    api_key: String,
-    api_url: String,
-    model: String,
-    api_format: ApiFormat,
+    config: LlmConfig,
+    backend: Box<dyn LlmBackend>,
+    limiter: Option<RateLimiter>,
+    retry: RetryPolicy,
+    cache: Option<IntentCache>,
 }
 
 impl LlmClient {
     /// Create a new LLM client with explicit configuration
+    ///
+    /// The API format is detected from the URL.
     pub fn new(api_key: String, api_url: String, model: String) -> Self {
-        let api_format = Self::detect_api_format(&api_url);
+        let config = LlmConfig {
+            api_url: Some(api_url.clone()),
+            model: Some(model),
+            api_key: Some(api_key),
+            ..LlmConfig::new(Self::detect_provider(&api_url))
+        };
+        Self::from_config(config).expect("config with an API key is valid")
+    }
+
+    /// Create a client for a provider configuration
+    ///
+    /// Fails if a hosted provider has no key or the cache directory cannot
+    /// be created.
+    pub fn from_config(config: LlmConfig) -> Result<Self> {
+        let backend = config.backend()?;
+        let cache = config
+            .cache_dir
+            .as_ref()
+            .map(IntentCache::open)
+            .transpose()?;
+        Ok(Self::with_backend(config, backend).with_cache(cache))
+    }
+
+    /// Create a client around any backend, with the config's rate limit and
+    /// retries but no cache
+    pub fn with_backend(config: LlmConfig, backend: Box<dyn LlmBackend>) -> Self {
         Self {
-            client: Client::new(),
-            api_key,
-            api_url,
-            model,
-            api_format,
+            limiter: config.requests_per_minute.map(RateLimiter::per_minute),
+            retry: config.retry_policy(),
+            config,
+            backend,
+            cache: None,
         }
     }
 
+    /// Use this intent cache (or none)
+    pub fn with_cache(mut self, cache: Option<IntentCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Use this retry policy
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Detect API format from URL
     fn detect_api_format(url: &str) -> ApiFormat {
         if url.contains("anthropic.com") {
@@ -48,19 +319,36 @@ impl LlmClient {
         }
     }
 
-    /// Create a client from environment variables
-    ///
-    /// Required: LLM_API_KEY
-    /// Optional: LLM_API_URL (defaults to Anthropic API)
-    /// Optional: LLM_MODEL (defaults to claude-3-haiku-20240307)
+    /// Detect the provider from an endpoint URL
+    fn detect_provider(url: &str) -> LlmProvider {
+        if url.contains("/api/chat") || url.contains(":11434") {
+            LlmProvider::Ollama
+        } else {
+            match Self::detect_api_format(url) {
+                ApiFormat::Anthropic => LlmProvider::Anthropic,
+                ApiFormat::OpenAI => LlmProvider::OpenAi,
+            }
+        }
+    }
+
+    /// Create a client from environment variables (see `LlmConfig::from_env`)
     pub fn from_env() -> Result<Self> {
-        let api_key = std::env::var("LLM_API_KEY")
-            .map_err(|_| ArcError::LlmError("LLM_API_KEY not set".into()))?;
-        let api_url = std::env::var("LLM_API_URL")
-            .unwrap_or_else(|_| "https://api.anthropic.com/v1/messages".into());
-        let model = std::env::var("LLM_MODEL").unwrap_or_else(|_| "claude-3-haiku-20240307".into());
+        Self::from_config(LlmConfig::from_env()?)
+    }
+
+    /// The configuration this client was built from
+    pub fn config(&self) -> &LlmConfig {
+        &self.config
+    }
 
-        Ok(Self::new(api_key, api_url, model))
+    /// Name of the backend in use
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// The intent cache, if there is one
+    pub fn cache(&self) -> Option<&IntentCache> {
+        self.cache.as_ref()
     }
 
     /// Send a completion request to the LLM
@@ -71,152 +359,48 @@ impl LlmClient {
     ///
     /// # Returns
     /// The LLM's text response
+    ///
+    /// Waits for the rate limiter, and retries with exponential backoff
+    /// while the service is unavailable.
     pub async fn complete(&self, system: &str, user: &str) -> Result<String> {
-        match self.api_format {
-            ApiFormat::Anthropic => self.complete_anthropic(system, user).await,
-            ApiFormat::OpenAI => self.complete_openai(system, user).await,
-        }
+        self.call(|| self.backend.complete(system, user)).await
     }
 
-    async fn complete_anthropic(&self, system: &str, user: &str) -> Result<String> {
-        let request = AnthropicRequest {
-            model: self.model.clone(),
-            max_tokens: 8192,
-            system: system.into(),
-            messages: vec![Message {
-                role: "user".into(),
-                content: user.into(),
-            }],
-        };
-
-        let response = self
-            .client
-            .post(&self.api_url)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| ArcError::LlmError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(ArcError::LlmError(format!("API error: {}", error_text)));
-        }
-
-        let completion: AnthropicResponse = response
-            .json()
+    /// Make the model call `tool`, returning the JSON arguments it called it
+    /// with (or, from a backend without tools, its plain reply)
+    ///
+    /// Rate limited and retried like `complete`.
+    pub async fn complete_tool(&self, system: &str, user: &str, tool: &ToolSpec) -> Result<String> {
+        self.call(|| self.backend.complete_tool(system, user, tool))
             .await
-            .map_err(|e| ArcError::LlmError(e.to_string()))?;
-
-        completion
-            .content
-            .first()
-            .map(|c| c.text.clone())
-            .ok_or_else(|| ArcError::LlmError("Empty response".into()))
     }
 
-    async fn complete_openai(&self, system: &str, user: &str) -> Result<String> {
-        // Model-specific max_tokens limits
-        let max_tokens = if self.model.contains("reasoner") {
-            32768 // DeepSeek R1 supports up to 32K-64K output
-        } else {
-            8192 // DeepSeek V3/chat max is 8192
-        };
-
-        let request = OpenAIRequest {
-            model: self.model.clone(),
-            max_tokens,
-            messages: vec![
-                Message {
-                    role: "system".into(),
-                    content: system.into(),
-                },
-                Message {
-                    role: "user".into(),
-                    content: user.into(),
-                },
-            ],
-        };
-
-        let response = self
-            .client
-            .post(&self.api_url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("content-type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| ArcError::LlmError(e.to_string()))?;
+    /// Whether the backend can be made to call a tool
+    pub fn supports_tools(&self) -> bool {
+        self.backend.supports_tools()
+    }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(ArcError::LlmError(format!("API error: {}", error_text)));
+    /// Send a request, waiting for the rate limiter and retrying with
+    /// exponential backoff while the service is unavailable
+    async fn call<'a>(&'a self, send: impl Fn() -> Completion<'a>) -> Result<String> {
+        let mut retry = 0;
+        loop {
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire().await;
+            }
+            match send().await {
+                Err(ArcError::LlmUnavailable(e)) if retry < self.retry.max_retries => {
+                    let delay = self.retry.delay(retry);
+                    tracing::warn!("LLM unavailable ({}), retrying in {:?}", e, delay);
+                    tokio::time::sleep(delay).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
         }
-
-        let completion: OpenAIResponse = response
-            .json()
-            .await
-            .map_err(|e| ArcError::LlmError(e.to_string()))?;
-
-        completion
-            .choices
-            .first()
-            .map(|c| c.message.content.clone())
-            .ok_or_else(|| ArcError::LlmError("Empty response".into()))
     }
 }
 
-// Anthropic API format
-#[derive(Serialize)]
-struct AnthropicRequest {
-    model: String,
-    max_tokens: u32,
-    system: String,
-    messages: Vec<Message>,
-}
-
-#[derive(Deserialize)]
-struct AnthropicResponse {
-    content: Vec<ContentBlock>,
-}
-
-#[derive(Deserialize)]
-struct ContentBlock {
-    text: String,
-}
-
-// OpenAI-compatible API format (DeepSeek, OpenAI, etc.)
-#[derive(Serialize)]
-struct OpenAIRequest {
-    model: String,
-    max_tokens: u32,
-    messages: Vec<Message>,
-}
-
-#[derive(Deserialize)]
-struct OpenAIResponse {
-    choices: Vec<Choice>,
-}
-
-#[derive(Deserialize)]
-struct Choice {
-    message: ChoiceMessage,
-}
-
-#[derive(Deserialize)]
-struct ChoiceMessage {
-    content: String,
-}
-
-// Shared
-#[derive(Serialize)]
-struct Message {
-    role: String,
-    content: String,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,18 +412,106 @@ mod tests {
             "https://api.example.com".into(),
             "test-model".into(),
         );
-        assert_eq!(client.api_key, "test-key");
-        assert_eq!(client.api_url, "https://api.example.com");
-        assert_eq!(client.model, "test-model");
+        assert_eq!(client.config.api_key.as_deref(), Some("test-key"));
+        assert_eq!(client.config.url(), "https://api.example.com");
+        assert_eq!(client.config.model_name(), "test-model");
+        assert_eq!(client.backend_name(), "openai");
     }
 
     #[test]
     fn test_from_env_missing_key() {
         // Temporarily clear the env var if set
         let result = LlmClient::from_env();
-        // Should fail if LLM_API_KEY is not set
-        if std::env::var("LLM_API_KEY").is_err() {
+        // Should fail if LLM_API_KEY is not set, unless a local provider is chosen
+        if std::env::var("LLM_API_KEY").is_err() && std::env::var("LLM_PROVIDER").is_err() {
             assert!(result.is_err());
         }
     }
+
+    /// Fails with `LlmUnavailable` a set number of times, then answers
+    struct Flaky {
+        failures: u32,
+        calls: std::sync::atomic::AtomicU32,
+    }
+
+    impl LlmBackend for Flaky {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn complete<'a>(
+            &'a self,
+            _system: &'a str,
+            _user: &'a str,
+        ) -> crate::llm::backend::Completion<'a> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Box::pin(async move {
+                if call < self.failures {
+                    Err(ArcError::LlmUnavailable("503".into()))
+                } else {
+                    Ok("ok".into())
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_calls_are_retried() {
+        let retry = RetryPolicy {
+            max_retries: 2,
+            base_delay: std::time::Duration::from_millis(1),
+            max_delay: std::time::Duration::from_millis(2),
+        };
+        let flaky = |failures| Flaky {
+            failures,
+            calls: Default::default(),
+        };
+        let config = LlmConfig::new(LlmProvider::Ollama);
+
+        let client = LlmClient::with_backend(config.clone(), Box::new(flaky(2))).with_retry(retry);
+        assert_eq!(client.complete("s", "u").await.unwrap(), "ok");
+
+        let client = LlmClient::with_backend(config, Box::new(flaky(3))).with_retry(retry);
+        assert!(matches!(
+            client.complete("s", "u").await,
+            Err(ArcError::LlmUnavailable(_))
+        ));
+    }
+
+    #[test]
+    fn test_local_providers_need_no_key() {
+        let config = LlmConfig::parse("provider = \"ollama\"").unwrap();
+        let client = LlmClient::from_config(config).unwrap();
+        assert_eq!(client.backend_name(), "ollama");
+        assert_eq!(client.config().url(), "http://localhost:11434/api/chat");
+
+        let llama = LlmClient::from_config(LlmConfig::new(LlmProvider::LlamaCpp)).unwrap();
+        assert_eq!(llama.backend_name(), "openai");
+        assert_eq!(LlmProvider::parse("llama.cpp"), Some(LlmProvider::LlamaCpp));
+
+        // Hosted providers still need a key
+        assert!(LlmClient::from_config(LlmConfig::new(LlmProvider::Anthropic)).is_err());
+    }
+
+    #[test]
+    fn test_tools_follow_the_provider_unless_configured() {
+        let llama = LlmClient::from_config(LlmConfig::new(LlmProvider::LlamaCpp)).unwrap();
+        assert!(!llama.supports_tools());
+
+        let config = LlmConfig::parse("provider = \"llamacpp\"\ntools = true").unwrap();
+        assert!(LlmClient::from_config(config).unwrap().supports_tools());
+
+        let openai = LlmConfig {
+            api_key: Some("key".into()),
+            ..LlmConfig::new(LlmProvider::OpenAi)
+        };
+        assert!(LlmClient::from_config(openai.clone())
+            .unwrap()
+            .supports_tools());
+        let config = LlmConfig {
+            tools: Some(false),
+            ..openai
+        };
+        assert!(!LlmClient::from_config(config).unwrap().supports_tools());
+    }
 }
@@ -3,11 +3,15 @@
 //! This module builds world state summaries that help the LLM parser
 //! understand the current game situation for better command disambiguation.
 //! The context includes information about entities, resources, threats,
-//! and recent events.
+//! recent events, and the places settlers have named.
 
-use crate::core::types::Species;
+use crate::aggregate::chronicle::HistoryChronicle;
+use crate::core::types::{Species, Vec2};
 use crate::ecs::world::World;
 
+/// Lines of world history a context carries
+pub const HISTORY_LINES: usize = 8;
+
 /// Game context for LLM prompts
 ///
 /// Contains a summary of the current game state that helps the LLM
@@ -23,8 +27,14 @@ pub struct GameContext {
     pub recent_events: Vec<String>,
     /// Named entities the player might reference
     pub named_entities: Vec<NamedEntity>,
+    /// Named places the player might send someone to
+    pub named_places: Vec<NamedPlace>,
+    /// The last few battles fought, a line each
+    pub battles: Vec<String>,
     /// Current threats or dangers
     pub threats: Vec<String>,
+    /// Notable events of the world's history, oldest first
+    pub world_history: Vec<String>,
     /// Current game tick
     pub current_tick: u64,
 }
@@ -41,6 +51,16 @@ pub struct NamedEntity {
     pub status: String,
 }
 
+/// A named place that can be referenced in commands
+pub struct NamedPlace {
+    /// The place's name
+    pub name: String,
+    /// What sort of place it is (settlement, district, battle site)
+    pub kind: String,
+    /// Where it is
+    pub position: Vec2,
+}
+
 impl GameContext {
     /// Build a game context from the current world state
     ///
@@ -98,13 +118,38 @@ impl GameContext {
             .map(|_| "danger nearby".to_string())
             .collect();
 
+        let named_places = world
+            .places
+            .iter()
+            .map(|place| NamedPlace {
+                name: place.name.clone(),
+                kind: place.kind.describe().into(),
+                position: place.position,
+            })
+            .collect();
+
         Self {
-            location_name: "Main Camp".into(),
+            location_name: world
+                .places
+                .settlement()
+                .map(|place| place.name.clone())
+                .unwrap_or_else(|| "Main Camp".into()),
             entity_count: world.entity_count(),
             available_resources: vec!["wood".into(), "stone".into(), "food".into()],
             recent_events: vec![],
             named_entities,
+            named_places,
+            battles: world
+                .legacy
+                .battles
+                .iter()
+                .rev()
+                .take(3)
+                .rev()
+                .map(|battle| battle.summary())
+                .collect(),
             threats,
+            world_history: vec![],
             current_tick: world.current_tick,
         }
     }
@@ -132,6 +177,17 @@ impl GameContext {
             }
         }
 
+        // Named places
+        if !self.named_places.is_empty() {
+            s.push_str("\nPlaces:\n");
+            for place in &self.named_places {
+                s.push_str(&format!(
+                    "- {} ({} at {:.0}, {:.0})\n",
+                    place.name, place.kind, place.position.x, place.position.y
+                ));
+            }
+        }
+
         // Resources
         if !self.available_resources.is_empty() {
             s.push_str(&format!(
@@ -148,11 +204,52 @@ impl GameContext {
             }
         }
 
+        // Battles
+        if !self.battles.is_empty() {
+            s.push_str("\nBattles:\n");
+            for battle in &self.battles {
+                s.push_str(&format!("- {}\n", battle));
+            }
+        }
+
         // Threats
         if !self.threats.is_empty() {
             s.push_str(&format!("\nThreats: {}\n", self.threats.join(", ")));
         }
 
+        // World history
+        if !self.world_history.is_empty() {
+            s.push_str("\nWorld History:\n");
+            for line in &self.world_history {
+                s.push_str(&format!("- {}\n", line));
+            }
+        }
+
+        s
+    }
+
+    /// The parts of the context a command's meaning can turn on, for keying
+    /// cached intents
+    ///
+    /// Leaves out the tick and whatever else moves from one tick to the next
+    /// (what each person is doing and how they are, recent events, threats),
+    /// so the same order in the same settlement is parsed once.
+    pub fn digest(&self) -> String {
+        let mut s = format!("{}\n", self.location_name);
+        for entity in &self.named_entities {
+            s.push_str(&format!("{} ({:?})\n", entity.name, entity.species));
+        }
+        for place in &self.named_places {
+            s.push_str(&format!("{} ({})\n", place.name, place.kind));
+        }
+        for line in self
+            .available_resources
+            .iter()
+            .chain(&self.battles)
+            .chain(&self.world_history)
+        {
+            s.push_str(&format!("{}\n", line));
+        }
         s
     }
 
@@ -164,7 +261,10 @@ impl GameContext {
             available_resources: vec![],
             recent_events: vec![],
             named_entities: vec![],
+            named_places: vec![],
+            battles: vec![],
             threats: vec![],
+            world_history: vec![],
             current_tick: 0,
         }
     }
@@ -178,6 +278,12 @@ impl GameContext {
         }
     }
 
+    /// Tell the context the world's history, as it bears on `region` if the
+    /// settlement was founded in one
+    pub fn add_history(&mut self, chronicle: &HistoryChronicle, region: Option<u32>) {
+        self.world_history = chronicle.summary(region, HISTORY_LINES);
+    }
+
     /// Add a threat to the context
     pub fn add_threat(&mut self, threat: impl Into<String>) {
         self.threats.push(threat.into());
@@ -243,6 +349,31 @@ mod tests {
         assert!(names.contains(&&"Bob".to_string()));
     }
 
+    #[test]
+    fn test_context_names_the_settlement_and_its_places() {
+        use crate::world::{Place, PlaceKind};
+
+        let mut world = World::new();
+        assert_eq!(GameContext::from_world(&world).location_name, "Main Camp");
+        for (name, kind) in [
+            ("Redford", PlaceKind::Settlement),
+            ("Redford Field", PlaceKind::BattleSite),
+        ] {
+            world.places.places.push(Place {
+                name: name.into(),
+                kind,
+                position: Vec2::new(120.0, 80.0),
+                named: 0,
+            });
+        }
+
+        let ctx = GameContext::from_world(&world);
+        assert_eq!(ctx.location_name, "Redford");
+        assert!(ctx
+            .summary()
+            .contains("- Redford Field (battle site at 120, 80)"));
+    }
+
     #[test]
     fn test_context_summary() {
         let mut ctx = GameContext::empty();
@@ -345,6 +476,36 @@ mod tests {
         assert_eq!(healthy[0].name, "Marcus");
     }
 
+    #[test]
+    fn test_context_tells_world_history() {
+        use crate::aggregate::events::{EventType, HistoryLog};
+
+        let mut history = HistoryLog::new();
+        for year in 0..20 {
+            history.add_event(
+                EventType::Expansion {
+                    polity: 1,
+                    region: year,
+                },
+                year,
+                vec![1],
+                Some(year),
+            );
+        }
+        let chronicle = HistoryChronicle {
+            polities: [(1, "Aldoria".to_string())].into(),
+            events: history.events,
+            ..HistoryChronicle::default()
+        };
+
+        let mut ctx = GameContext::empty();
+        ctx.add_history(&chronicle, None);
+        assert_eq!(ctx.world_history.len(), HISTORY_LINES);
+        assert!(ctx
+            .summary()
+            .contains("World History:\n- Year 12: Aldoria expanded into an unknown land"));
+    }
+
     #[test]
     fn test_has_threats() {
         let mut ctx = GameContext::empty();
@@ -0,0 +1,214 @@
+//! Keyword parser - reads a command without the LLM
+//!
+//! Used when the model's replies never fit the intent schema. It finds the
+//! action from its verb, takes what follows as the target and whatever
+//! comes after "in", "at", "on" or "near" as the place, picks up names the
+//! way "have Marcus ..." or "Marcus and Ada, ..." give them, and hears
+//! urgency in "now" or "immediately". It never fails; a command it cannot read
+//! is `Unknown`. Its confidence is low, so the executor asks before acting
+//! on what it made of the order.
+
+use crate::llm::parser::{IntentAction, IntentPriority, ParsedIntent};
+
+/// Confidence of a command read by keyword
+pub const FALLBACK_CONFIDENCE: f32 = 0.4;
+
+/// Verbs for each action, checked in order
+const VERBS: &[(IntentAction, &[&str])] = &[
+    (
+        IntentAction::Build,
+        &["build", "construct", "erect", "raise", "put up"],
+    ),
+    (
+        IntentAction::Craft,
+        &["craft", "make", "forge", "carve", "brew"],
+    ),
+    (
+        IntentAction::Combat,
+        &["attack", "fight", "defend", "kill", "charge", "repel"],
+    ),
+    (
+        IntentAction::Gather,
+        &[
+            "gather", "collect", "chop", "mine", "harvest", "fetch", "forage",
+        ],
+    ),
+    (
+        IntentAction::Assign,
+        &["assign", "guard", "patrol", "scout", "send", "station"],
+    ),
+    (
+        IntentAction::Social,
+        &["talk", "chat", "speak", "befriend", "negotiate", "greet"],
+    ),
+    (IntentAction::Rest, &["rest", "sleep", "relax"]),
+    (
+        IntentAction::Move,
+        &["go", "move", "walk", "travel", "head"],
+    ),
+    (
+        IntentAction::Query,
+        &["who", "what", "where", "how", "why", "which", "when"],
+    ),
+];
+
+/// Words that introduce a place
+const PLACE_WORDS: &[&str] = &["in", "at", "on", "near", "by", "to"];
+
+/// Words that introduce who should act
+const SUBJECT_WORDS: &[&str] = &["have", "let", "tell", "order", "get"];
+
+/// Words to pass over in targets and places
+const FILLER: &[&str] = &["a", "an", "the", "some", "more", "of", "please"];
+
+/// Words that say how urgent the order is, by priority
+const URGENCY: &[(IntentPriority, &[&str])] = &[
+    (IntentPriority::Critical, &["emergency", "immediately"]),
+    (
+        IntentPriority::High,
+        &["now", "quickly", "urgently", "hurry", "asap"],
+    ),
+    (IntentPriority::Low, &["later", "eventually", "sometime"]),
+];
+
+fn is_urgency(word: &str) -> bool {
+    URGENCY.iter().any(|(_, words)| words.contains(&word))
+}
+
+fn words(input: &str) -> Vec<String> {
+    input
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn lower(word: &str) -> String {
+    word.to_lowercase()
+}
+
+/// Index and action of the first verb, trying two-word verbs too
+fn find_verb(words: &[String]) -> Option<(usize, usize, IntentAction)> {
+    for (i, word) in words.iter().enumerate() {
+        let one = lower(word);
+        let two = words
+            .get(i + 1)
+            .map(|next| format!("{} {}", one, lower(next)));
+        for (action, verbs) in VERBS {
+            for verb in verbs.iter() {
+                if two.as_deref() == Some(*verb) {
+                    return Some((i, 2, action.clone()));
+                }
+                if one == *verb {
+                    return Some((i, 1, action.clone()));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Words up to the next place word, without filler; `None` if none remain
+fn phrase(words: &[String]) -> Option<String> {
+    let phrase: Vec<&str> = words
+        .iter()
+        .take_while(|w| !PLACE_WORDS.contains(&lower(w).as_str()))
+        .filter(|w| !FILLER.contains(&lower(w).as_str()) && !is_urgency(&lower(w)))
+        .map(String::as_str)
+        .collect();
+    (!phrase.is_empty()).then(|| phrase.join(" "))
+}
+
+/// Capitalised words before the verb, or after "have"/"tell"
+fn subjects(words: &[String], verb_at: usize) -> Option<Vec<String>> {
+    let is_name = |w: &String| {
+        w.chars().next().is_some_and(char::is_uppercase)
+            && !SUBJECT_WORDS.contains(&lower(w).as_str())
+            && !FILLER.contains(&lower(w).as_str())
+            && lower(w) != "and"
+            && lower(w) != "everyone"
+    };
+    let names: Vec<String> = words[..verb_at]
+        .iter()
+        .filter(|w| is_name(w))
+        .cloned()
+        .collect();
+    (!names.is_empty()).then_some(names)
+}
+
+fn priority(words: &[String]) -> IntentPriority {
+    URGENCY
+        .iter()
+        .find(|(_, urgent)| words.iter().any(|w| urgent.contains(&lower(w).as_str())))
+        .map_or(IntentPriority::Normal, |(priority, _)| *priority)
+}
+
+/// Read a command by its key words
+pub fn fallback_parse(input: &str) -> ParsedIntent {
+    let words = words(input);
+    let Some((verb_at, verb_len, action)) = find_verb(&words) else {
+        return ParsedIntent {
+            priority: priority(&words),
+            ..ParsedIntent::default()
+        };
+    };
+    if action == IntentAction::Query || input.trim_end().ends_with('?') {
+        return ParsedIntent {
+            action: IntentAction::Query,
+            target: Some(input.trim().to_string()),
+            confidence: FALLBACK_CONFIDENCE,
+            ..ParsedIntent::default()
+        };
+    }
+
+    let rest = &words[verb_at + verb_len..];
+    let place_at = rest
+        .iter()
+        .position(|w| PLACE_WORDS.contains(&lower(w).as_str()));
+    ParsedIntent {
+        target: phrase(rest),
+        location: place_at.and_then(|i| phrase(&rest[i + 1..])),
+        subjects: subjects(&words, verb_at),
+        priority: priority(&words),
+        confidence: FALLBACK_CONFIDENCE,
+        action,
+        ..ParsedIntent::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_orders_by_keyword() {
+        let intent = fallback_parse("Have Marcus build a wall near the east gate now");
+        assert_eq!(intent.action, IntentAction::Build);
+        assert_eq!(intent.target.as_deref(), Some("wall"));
+        assert_eq!(intent.location.as_deref(), Some("east gate"));
+        assert_eq!(intent.subjects, Some(vec!["Marcus".to_string()]));
+        assert_eq!(intent.priority, IntentPriority::High);
+        assert_eq!(intent.confidence, FALLBACK_CONFIDENCE);
+
+        let intent = fallback_parse("put up a well in the north");
+        assert_eq!(intent.action, IntentAction::Build);
+        assert_eq!(intent.target.as_deref(), Some("well"));
+        assert_eq!(intent.location.as_deref(), Some("north"));
+        assert_eq!(intent.subjects, None);
+
+        assert_eq!(
+            fallback_parse("who is hungriest?").action,
+            IntentAction::Query
+        );
+        assert_eq!(fallback_parse("gather wood").action, IntentAction::Gather);
+    }
+
+    #[test]
+    fn test_unreadable_commands_are_unknown() {
+        for input in ["", "blorp", "!!!", "   "] {
+            let intent = fallback_parse(input);
+            assert_eq!(intent.action, IntentAction::Unknown);
+            assert_eq!(intent.confidence, 0.0);
+        }
+    }
+}
@@ -1,5 +1,10 @@
+pub mod backend;
+pub mod cache;
 pub mod client;
 pub mod context;
+pub mod fallback;
+pub mod narrator;
 pub mod parser;
 pub mod prompts;
+pub mod rate_limit;
 pub mod species_interpret;
@@ -0,0 +1,49 @@
+//! Narrate situation reports and answers to the player's questions
+//!
+//! The report itself is plain data drawn from the simulation; the LLM only
+//! retells it as a few lines of chronicle prose for the returning player.
+//! Answers to queries are likewise retold in the steward's voice. Without a
+//! client the plain text serves instead.
+
+use crate::core::error::Result;
+use crate::llm::client::LlmClient;
+use crate::simulation::situation::SituationReport;
+
+/// Retell a situation report as a short narrative
+pub async fn narrate_situation(client: &LlmClient, report: &SituationReport) -> Result<String> {
+    let user_prompt = format!(
+        "WHAT HAPPENED:\n{}\n\nTell the returning lord what happened:",
+        report.lines().join("\n")
+    );
+    let response = client.complete(NARRATE_SYSTEM_PROMPT, &user_prompt).await?;
+    Ok(response.trim().to_string())
+}
+
+/// Retell the plain answer to a question in the steward's voice
+pub async fn polish_answer(client: &LlmClient, question: &str, answer: &str) -> Result<String> {
+    let user_prompt = format!(
+        "QUESTION: {}\nFACTS: {}\n\nAnswer the lord:",
+        question, answer
+    );
+    let response = client.complete(ANSWER_SYSTEM_PROMPT, &user_prompt).await?;
+    Ok(response.trim().to_string())
+}
+
+/// System prompt for situation narration
+const NARRATE_SYSTEM_PROMPT: &str = r#"You are the steward of a medieval settlement, reporting to its lord on their return.
+Retell the events given to you in three to six plain sentences, most important first.
+
+RULES:
+- Mention only what is in the events; invent no names, deaths, or buildings
+- Deaths and fighting come before births and building
+- If nothing of note happened, say so in one sentence
+- No lists, headings, or tick numbers; speak of days instead"#;
+
+/// System prompt for answering the lord's questions
+const ANSWER_SYSTEM_PROMPT: &str = r#"You are the steward of a medieval settlement, answering a question from its lord.
+Answer in one or two plain sentences using only the facts given.
+
+RULES:
+- Keep every name and number in the facts exactly as given
+- Add nothing that is not in the facts
+- No lists or headings"#;
@@ -4,14 +4,32 @@
 //! The LLM parses player commands into structured intents, but does NOT
 //! control entity behavior - entities interpret commands through their
 //! own values and personality.
+//!
+//! Where the provider supports it the model is made to call `parse_intent`
+//! (see `intent_tool`), whose arguments must fit the schema exactly; other
+//! providers answer in prose and the JSON is dug out of the reply. A reply
+//! that does not fit is sent back to the model with the error, up to
+//! `PARSE_ATTEMPTS` times in all, and if it never fits the command is read
+//! by keyword instead (`fallback::fallback_parse`), so malformed output
+//! never stops a command being handled. A request the service rejects is
+//! asked again in prose, and if that is rejected too the command is read by
+//! keyword; only an unavailable service is reported as an error.
 
-use crate::core::error::Result;
+use crate::core::error::{ArcError, Result};
+use crate::llm::backend::ToolSpec;
+use crate::llm::cache::IntentCache;
 use crate::llm::client::LlmClient;
 use crate::llm::context::GameContext;
+use crate::llm::fallback::fallback_parse;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Times the model is asked before the command is read by keyword
+pub const PARSE_ATTEMPTS: usize = 3;
 
 /// Parsed intent from a natural language command
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ParsedIntent {
     /// The type of action requested
     pub action: IntentAction,
@@ -27,6 +45,63 @@ pub struct ParsedIntent {
     pub ambiguous_concepts: Vec<String>,
     /// Parser's confidence in the interpretation (0.0 - 1.0)
     pub confidence: f32,
+    /// Steps to carry out after this one, in order ("... then ...")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub then: Vec<ParsedIntent>,
+}
+
+impl ParsedIntent {
+    /// Every step of the command in order: this one, then those after it
+    ///
+    /// Each step is returned without steps of its own.
+    pub fn steps(&self) -> Vec<ParsedIntent> {
+        let mut first = self.clone();
+        let rest = std::mem::take(&mut first.then);
+        std::iter::once(first)
+            .chain(rest.iter().flat_map(ParsedIntent::steps))
+            .collect()
+    }
+
+    /// Whether the command has more than one step
+    pub fn is_compound(&self) -> bool {
+        !self.then.is_empty()
+    }
+
+    fn step_count(&self) -> usize {
+        1 + self
+            .then
+            .iter()
+            .map(ParsedIntent::step_count)
+            .sum::<usize>()
+    }
+
+    /// Check what the schema cannot: confidence is between 0 and 1, in
+    /// every step
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if !(0.0..=1.0).contains(&self.confidence) {
+            return Err(format!(
+                "confidence must be between 0.0 and 1.0, not {}",
+                self.confidence
+            ));
+        }
+        self.then.iter().try_for_each(ParsedIntent::validate)
+    }
+
+    /// Step `n` of the command, counted as in `steps()`
+    pub fn step_mut(&mut self, n: usize) -> Option<&mut ParsedIntent> {
+        if n == 0 {
+            return Some(self);
+        }
+        let mut n = n - 1;
+        for step in &mut self.then {
+            let count = step.step_count();
+            if n < count {
+                return step.step_mut(n);
+            }
+            n -= count;
+        }
+        None
+    }
 }
 
 /// Types of actions the player can command
@@ -85,6 +160,7 @@ impl Default for ParsedIntent {
             priority: IntentPriority::Normal,
             ambiguous_concepts: Vec::new(),
             confidence: 0.0,
+            then: Vec::new(),
         }
     }
 }
@@ -104,25 +180,127 @@ pub async fn parse_command(
     context: &GameContext,
 ) -> Result<ParsedIntent> {
     let system_prompt = PARSE_SYSTEM_PROMPT;
+    let summary = context.summary();
+    let key = IntentCache::key(system_prompt, input, &context.digest());
+    if let Some(intent) = client.cache().and_then(|cache| cache.get(key)) {
+        tracing::debug!("Intent for '{}' served from cache", input);
+        return Ok(intent);
+    }
+
     let user_prompt = format!(
         "CONTEXT:\n{}\n\nPLAYER INPUT:\n{}\n\nParse this command into JSON:",
-        context.summary(),
-        input
+        summary, input
     );
 
-    let response = client.complete(system_prompt, &user_prompt).await?;
-    let json_str = extract_json(&response)?;
+    let tool = intent_tool();
+    let mut prompt = user_prompt.clone();
+    let mut use_tools = true;
+    for attempt in 1..=PARSE_ATTEMPTS {
+        let reply = if use_tools {
+            client.complete_tool(system_prompt, &prompt, &tool).await
+        } else {
+            client.complete(system_prompt, &prompt).await
+        };
+        let response = match reply {
+            Ok(response) => response,
+            // A server that refuses the tool may still answer in prose
+            Err(ArcError::LlmError(error)) if use_tools => {
+                tracing::warn!(
+                    "Tool call for '{}' refused ({}), asking in prose",
+                    input,
+                    error
+                );
+                use_tools = false;
+                match client.complete(system_prompt, &prompt).await {
+                    Ok(response) => response,
+                    Err(ArcError::LlmError(error)) => {
+                        tracing::warn!("LLM could not parse '{}': {}", input, error);
+                        break;
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+            Err(ArcError::LlmError(error)) => {
+                tracing::warn!("LLM could not parse '{}': {}", input, error);
+                break;
+            }
+            Err(error) => return Err(error),
+        };
+        match read_intent(&response) {
+            Ok(intent) => {
+                // An unparseable command might parse next time
+                if let Some(cache) = client
+                    .cache()
+                    .filter(|_| intent.action != IntentAction::Unknown)
+                {
+                    cache.insert(key, &intent);
+                }
+                return Ok(intent);
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "Unusable intent for '{}' (attempt {}/{}): {}",
+                    input,
+                    attempt,
+                    PARSE_ATTEMPTS,
+                    error
+                );
+                prompt = format!(
+                    "{}\n\nYour previous answer could not be used: {}\nPrevious answer: {}\n\
+                     Answer again, following the format exactly.",
+                    user_prompt, error, response
+                );
+            }
+        }
+    }
 
-    let intent: ParsedIntent = serde_json::from_str(json_str).map_err(|e| {
-        crate::core::error::ArcError::LlmError(format!(
-            "Failed to parse intent: {} - Response: {}",
-            e, response
-        ))
-    })?;
+    tracing::warn!("Reading '{}' by keyword instead", input);
+    Ok(fallback_parse(input))
+}
 
+/// Read an intent from a reply, or say what is wrong with it
+fn read_intent(response: &str) -> std::result::Result<ParsedIntent, String> {
+    let json_str = extract_json(response).map_err(|e| e.to_string())?;
+    let intent: ParsedIntent =
+        serde_json::from_str(json_str).map_err(|e| format!("invalid intent: {}", e))?;
+    intent.validate()?;
     Ok(intent)
 }
 
+/// The `parse_intent` function the model is made to call
+pub fn intent_tool() -> ToolSpec {
+    let step = json!({
+        "type": "object",
+        "properties": {
+            "action": {
+                "type": "string",
+                "enum": ["BUILD", "CRAFT", "ASSIGN", "COMBAT", "GATHER", "MOVE", "QUERY",
+                         "SOCIAL", "REST", "UNKNOWN"]
+            },
+            "target": { "type": ["string", "null"] },
+            "location": { "type": ["string", "null"] },
+            "subjects": { "type": ["array", "null"], "items": { "type": "string" } },
+            "priority": { "type": "string", "enum": ["CRITICAL", "HIGH", "NORMAL", "LOW"] },
+            "ambiguous_concepts": { "type": "array", "items": { "type": "string" } },
+            "confidence": { "type": "number", "minimum": 0.0, "maximum": 1.0 }
+        },
+        "required": ["action", "target", "location", "subjects", "priority",
+                     "ambiguous_concepts", "confidence"],
+        "additionalProperties": false
+    });
+    let mut parameters = step.clone();
+    parameters["properties"]["then"] = json!({
+        "type": "array",
+        "description": "Steps to do afterwards, in order",
+        "items": step
+    });
+    ToolSpec {
+        name: "parse_intent",
+        description: "Record the structured intent of a player's command",
+        parameters,
+    }
+}
+
 /// Extract JSON object from LLM response (handles surrounding text)
 fn extract_json(response: &str) -> Result<&str> {
     let start = response.find('{').ok_or_else(|| {
@@ -139,7 +317,7 @@ const PARSE_SYSTEM_PROMPT: &str = r#"You are parsing player commands for a medie
 Convert natural language orders into structured JSON.
 
 AVAILABLE ACTIONS:
-- BUILD: Construct structures (walls, buildings, fortifications)
+- BUILD: Construct structures (houses, walls, watchtowers, wells, shrines, fortifications)
 - CRAFT: Create items (weapons, tools, goods)
 - ASSIGN: Move personnel to roles/locations
 - COMBAT: Engage enemies or prepare defenses
@@ -163,15 +341,27 @@ OUTPUT FORMAT (JSON only, no explanation):
   "subjects": ["who should do this"] or null if unspecified,
   "priority": "CRITICAL|HIGH|NORMAL|LOW",
   "ambiguous_concepts": ["terms that might be interpreted differently by non-humans"],
-  "confidence": 0.0-1.0
+  "confidence": 0.0-1.0,
+  "then": [steps to do afterwards, in order, each in this same format] or []
 }
 
+MULTI-STEP ORDERS: If the player orders several things in sequence ("X then Y",
+"first X, after that Y"), describe the first step at the top level and list the
+following steps in order in "then". A later step with no subjects is carried out
+by whoever did the step before it.
+
+PLACES: If the player names a place listed under Places in the context, give
+its name exactly as listed as the location.
+
 Examples:
 "build a wall" -> {"action": "BUILD", "target": "wall", "location": null, "subjects": null, "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.9}
+"put up a well in the north" -> {"action": "BUILD", "target": "well", "location": "north", "subjects": null, "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.9}
 "have Marcus guard the east" -> {"action": "ASSIGN", "target": "guard duty", "location": "east", "subjects": ["Marcus"], "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.85}
 "make it beautiful" -> {"action": "CRAFT", "target": null, "location": null, "subjects": null, "priority": "NORMAL", "ambiguous_concepts": ["beautiful"], "confidence": 0.6}
 "everyone rest now" -> {"action": "REST", "target": null, "location": null, "subjects": null, "priority": "HIGH", "ambiguous_concepts": [], "confidence": 0.95}
 "send the brave ones to scout" -> {"action": "ASSIGN", "target": "scouting", "location": null, "subjects": null, "priority": "NORMAL", "ambiguous_concepts": ["brave"], "confidence": 0.7}
+"send Ada and Bram to Redford Field" -> {"action": "MOVE", "target": null, "location": "Redford Field", "subjects": ["Ada", "Bram"], "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.9}
+"gather wood then build a wall on the east side" -> {"action": "GATHER", "target": "wood", "location": null, "subjects": null, "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.85, "then": [{"action": "BUILD", "target": "wall", "location": "east", "subjects": null, "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.85}]}
 "#;
 
 #[cfg(test)]
@@ -248,4 +438,156 @@ Let me know if you need anything else."#;
         assert_eq!(intent.ambiguous_concepts, vec!["east".to_string()]);
         assert!((intent.confidence - 0.85).abs() < 0.001);
     }
+
+    #[test]
+    fn test_read_intent_is_strict() {
+        let good = r#"{"action": "BUILD", "target": "wall", "location": null, "subjects": null, "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.9}"#;
+        assert!(read_intent(good).is_ok());
+
+        let extra = good.replace("\"confidence\"", "\"mood\": \"happy\", \"confidence\"");
+        assert!(read_intent(&extra).unwrap_err().contains("unknown field"));
+        let sure = good.replace("0.9", "9");
+        assert!(read_intent(&sure).unwrap_err().contains("confidence"));
+        assert!(read_intent("no idea").is_err());
+    }
+
+    /// Answers with each reply in turn, recording the prompts it was sent
+    struct Scripted {
+        replies: std::sync::Mutex<Vec<&'static str>>,
+        prompts: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl crate::llm::backend::LlmBackend for Scripted {
+        fn name(&self) -> &'static str {
+            "scripted"
+        }
+
+        fn complete<'a>(
+            &'a self,
+            _system: &'a str,
+            user: &'a str,
+        ) -> crate::llm::backend::Completion<'a> {
+            self.prompts.lock().unwrap().push(user.to_string());
+            let reply = self.replies.lock().unwrap().remove(0);
+            Box::pin(async move { Ok(reply.to_string()) })
+        }
+    }
+
+    fn scripted(replies: Vec<&'static str>) -> LlmClient {
+        let backend = Scripted {
+            replies: std::sync::Mutex::new(replies),
+            prompts: Default::default(),
+        };
+        LlmClient::with_backend(
+            crate::llm::client::LlmConfig::new(crate::llm::client::LlmProvider::Ollama),
+            Box::new(backend),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_bad_replies_are_retried_then_read_by_keyword() {
+        let context = GameContext::empty();
+        let client = scripted(vec![
+            "Sure! The player wants a wall.",
+            r#"{"action": "BUILD", "target": "wall", "location": null, "subjects": null, "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.9}"#,
+        ]);
+        let intent = parse_command(&client, "build a wall", &context)
+            .await
+            .unwrap();
+        assert_eq!(intent.action, IntentAction::Build);
+        assert_eq!(intent.confidence, 0.9);
+
+        let client = scripted(vec!["{not json", "{\"action\": \"DANCE\"}", "?"]);
+        let intent = parse_command(&client, "gather wood now", &context)
+            .await
+            .unwrap();
+        assert_eq!(intent.action, IntentAction::Gather);
+        assert_eq!(intent.target.as_deref(), Some("wood"));
+        assert_eq!(intent.confidence, crate::llm::fallback::FALLBACK_CONFIDENCE);
+    }
+
+    /// Rejects every tool call, as a llama.cpp server without tool support
+    /// does, and answers in prose if it has a reply
+    struct RefusesTools {
+        prose: Option<&'static str>,
+    }
+
+    impl crate::llm::backend::LlmBackend for RefusesTools {
+        fn name(&self) -> &'static str {
+            "refuses tools"
+        }
+
+        fn complete<'a>(
+            &'a self,
+            _system: &'a str,
+            _user: &'a str,
+        ) -> crate::llm::backend::Completion<'a> {
+            let reply = self
+                .prose
+                .map(str::to_string)
+                .ok_or_else(|| ArcError::LlmError("API error (400)".into()));
+            Box::pin(async move { reply })
+        }
+
+        fn complete_tool<'a>(
+            &'a self,
+            _system: &'a str,
+            _user: &'a str,
+            _tool: &'a ToolSpec,
+        ) -> crate::llm::backend::Completion<'a> {
+            Box::pin(async { Err(ArcError::LlmError("API error (400): tools".into())) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_tool_calls_fall_back_to_prose_then_keywords() {
+        let context = GameContext::empty();
+        let client = |prose| {
+            LlmClient::with_backend(
+                crate::llm::client::LlmConfig::new(crate::llm::client::LlmProvider::LlamaCpp),
+                Box::new(RefusesTools { prose }),
+            )
+        };
+
+        let prose = client(Some(
+            r#"{"action": "BUILD", "target": "wall", "location": null, "subjects": null, "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.9}"#,
+        ));
+        let intent = parse_command(&prose, "build a wall", &context)
+            .await
+            .unwrap();
+        assert_eq!(intent.action, IntentAction::Build);
+        assert_eq!(intent.confidence, 0.9);
+
+        let intent = parse_command(&client(None), "gather wood now", &context)
+            .await
+            .unwrap();
+        assert_eq!(intent.action, IntentAction::Gather);
+        assert_eq!(intent.confidence, crate::llm::fallback::FALLBACK_CONFIDENCE);
+    }
+
+    #[tokio::test]
+    async fn test_cached_intent_outlasts_the_tick() {
+        let mut world = crate::ecs::world::World::with_seed(2);
+        world.spawn_human("Marcus".into());
+        // One reply only: a second call to the model would panic
+        let client = scripted(vec![
+            r#"{"action": "ASSIGN", "target": "guard duty", "location": "east", "subjects": ["Marcus"], "priority": "NORMAL", "ambiguous_concepts": [], "confidence": 0.85}"#,
+        ])
+        .with_cache(Some(IntentCache::in_memory()));
+
+        let context = GameContext::from_world(&world);
+        parse_command(&client, "have Marcus guard the east", &context)
+            .await
+            .unwrap();
+
+        world.current_tick += 250;
+        world.humans.needs[0].food = 0.9;
+        let later = GameContext::from_world(&world);
+        assert_ne!(later.summary(), context.summary());
+        let intent = parse_command(&client, "have Marcus guard the east", &later)
+            .await
+            .unwrap();
+        assert_eq!(intent.action, IntentAction::Assign);
+        assert_eq!(intent.subjects, Some(vec!["Marcus".to_string()]));
+    }
 }
@@ -0,0 +1,113 @@
+//! Rate limiting and retry for LLM calls
+//!
+//! `RateLimiter` is a token bucket: it holds up to a minute's worth of
+//! requests and refills continuously, so short bursts go straight through
+//! and sustained use is held to the configured rate. `RetryPolicy` says how
+//! often, and how long apart, a call that failed with `LlmUnavailable`
+//! (network trouble, 429, 5xx) is tried again.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting how often the LLM is called
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `requests` calls per minute, in bursts of up to that many
+    pub fn per_minute(requests: u32) -> Self {
+        let capacity = requests.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: capacity / 60.0,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take a token if one is available, or say how long until one is
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+
+    /// Wait until a token is available, then take it
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            tracing::debug!("LLM rate limit reached, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// How failed LLM calls are retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 = never retry)
+    pub max_retries: u32,
+    /// Wait before the first retry; doubles with each one after
+    pub base_delay: Duration,
+    /// Longest wait between retries
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (0 = the first retry)
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_a_burst_then_waits() {
+        let limiter = RateLimiter::per_minute(2);
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_ok());
+        let wait = limiter.try_acquire().unwrap_err();
+        // One request refills every 30 seconds
+        assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+
+        let retry = RetryPolicy::default();
+        assert_eq!(retry.delay(0), Duration::from_millis(500));
+        assert_eq!(retry.delay(2), Duration::from_secs(2));
+        assert_eq!(retry.delay(10), retry.max_delay);
+    }
+}
@@ -4,18 +4,98 @@
 //! It sets up the async runtime, spawns test entities, runs simulation ticks,
 //! and provides a basic game loop for interacting with the simulation.
 
-use arc_citadel::command::CommandExecutor;
+use arc_citadel::actions::catalog::ActionId;
+use arc_citadel::aggregate::HistoryChronicle;
+use arc_citadel::city::site::blueprint_named;
+use arc_citadel::command::clarify::{Clarification, Clarified};
+use arc_citadel::command::query::Query;
+use arc_citadel::command::script::parse_line;
+use arc_citadel::core::astronomy::Season;
 use arc_citadel::core::error::Result;
+use arc_citadel::core::types::{EntityId, Vec2};
+use arc_citadel::debug::snapshot::{diff, WorldSnapshot};
+use arc_citadel::ecs::persistence::{load_world, save_world};
 use arc_citadel::ecs::world::World;
 use arc_citadel::llm::client::LlmClient;
 use arc_citadel::llm::context::GameContext;
-use arc_citadel::llm::parser::{parse_command, IntentAction};
-use arc_citadel::simulation::tick::run_simulation_tick;
+use arc_citadel::llm::narrator::{narrate_situation, polish_answer};
+use arc_citadel::llm::parser::{parse_command, IntentAction, ParsedIntent};
+use arc_citadel::simulation::almanac::{almanac, ALMANAC_DAYS};
+use arc_citadel::simulation::avatar::available_actions;
+use arc_citadel::simulation::decision_trace::DecisionTrace;
+use arc_citadel::simulation::epithets::known_as;
+use arc_citadel::simulation::headless::{run_headless, HeadlessConfig, TelemetryFormat};
+use arc_citadel::simulation::replay::{replay, CommandOutcome, PlayerCommand, Recorder, ReplayLog};
+use arc_citadel::simulation::requests;
+use arc_citadel::simulation::resource_zone::ResourceType;
+use arc_citadel::simulation::scheduler::{Frequency, SystemId};
+use arc_citadel::simulation::situation::{mark_situation, take_situation_report, FAST_FORWARD_TICKS};
+use arc_citadel::simulation::tick::{SimulationEvent, TICKS_PER_DAY};
+use arc_citadel::simulation::time_scale::{fighting, TimeScale, COMPRESSED_TICKS_PER_DAY};
+use arc_citadel::simulation::unrest::orders_refused;
+use arc_citadel::world::{DialogueChoice, Fate, OathTerms, Whereabouts};
 
+use clap::Parser;
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 
+/// Arc Citadel - interactive by default, or a batch run with --headless
+#[derive(Parser, Debug)]
+#[command(name = "arc-citadel")]
+struct Args {
+    /// Run without a prompt, writing telemetry samples to --out
+    #[arg(long)]
+    headless: bool,
+
+    /// Ticks to run in headless mode
+    #[arg(long, default_value_t = 10_000)]
+    ticks: u64,
+
+    /// Ticks between telemetry samples
+    #[arg(long, default_value_t = 100)]
+    sample_every: u64,
+
+    /// Telemetry format: csv or json
+    #[arg(long, default_value = "csv")]
+    format: String,
+
+    /// Telemetry output file (default: telemetry.csv or telemetry.json)
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// World seed for reproducible runs
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Start from a world saved with the save command
+    #[arg(long)]
+    world: Option<PathBuf>,
+
+    /// Record every decision of the named human in headless mode (repeatable)
+    #[arg(long)]
+    trace: Vec<String>,
+
+    /// Decision trace output file
+    #[arg(long, default_value = "decisions.jsonl")]
+    trace_out: PathBuf,
+
+    /// Simulation steps per day in headless mode (fewer is faster; 1000 is full detail)
+    #[arg(long, default_value_t = TICKS_PER_DAY)]
+    ticks_per_day: u64,
+
+    /// World history for narrative commands to draw on (written by aggregate_sim)
+    #[arg(long)]
+    history: Option<PathBuf>,
+}
+
 fn main() -> Result<()> {
+    let args = Args::parse();
+    if args.headless {
+        return run_headless_mode(&args);
+    }
+
     // Initialize tracing for logging
     tracing_subscriber::fmt()
         .with_env_filter("arc_citadel=debug")
@@ -27,16 +107,39 @@ fn main() -> Result<()> {
     let rt = Runtime::new()?;
 
     // Create the game world
-    let mut world = World::new();
+    let mut world = args.seed.map(World::with_seed).unwrap_or_default();
+
+    // Record the session from here on so it can be replayed
+    let mut recorder = Recorder::start(&world)?;
+    let mut snapshot: Option<WorldSnapshot> = None;
 
     // Spawn initial test population
-    spawn_initial_population(&mut world);
+    spawn_initial_population(&mut world, &mut recorder);
+    mark_situation(&mut world);
+
+    // The world's history, if one was generated for it
+    let history = args
+        .history
+        .as_ref()
+        .and_then(|path| match HistoryChronicle::load(path) {
+            Ok(chronicle) => Some(chronicle),
+            Err(e) => {
+                tracing::warn!("Could not load history {}: {}", path.display(), e);
+                None
+            }
+        });
 
     // Try to create LLM client (optional - works without it)
-    let llm_client = LlmClient::from_env().ok();
-    if llm_client.is_none() {
-        tracing::warn!("LLM_API_KEY not set - running without natural language commands");
-    }
+    let llm_client = match LlmClient::from_env() {
+        Ok(client) => {
+            tracing::info!("Using {} LLM backend", client.backend_name());
+            Some(client)
+        }
+        Err(e) => {
+            tracing::warn!("{} - running without natural language commands", e);
+            None
+        }
+    };
 
     // Display welcome message
     println!("\n=== ARC CITADEL ===");
@@ -45,14 +148,51 @@ fn main() -> Result<()> {
     println!("Commands:");
     println!("  tick / t        - Advance simulation by one tick");
     println!("  spawn <name>    - Spawn a new human entity");
+    println!("  spawn dwarf|elf <name> - Spawn a new dwarf or elf");
+    println!("  place <blueprint> <x> <y> - Mark out a building site (house, wall, well...)");
     println!("  status / s      - Show detailed status");
+    println!("  almanac         - Forecast the coming days' weather and skies");
+    println!("  requests        - What settlers are asking of you");
+    println!("  omens           - The omens the settlement is living under");
+    println!("  rite            - Hold the rites the omens call for");
+    println!("  era             - The era the settlement is in, and the chronicle's chapters");
     println!("  run <n>         - Run n simulation ticks");
+    println!("  time [detailed|compressed [n]] - Show or set steps a day (fewer to fast-forward)");
+    println!("  report          - What happened since the last report");
+    println!("  where <name>    - Where someone is, in whatever layer");
+    println!("  timings         - How long each system has taken per tick");
+    println!("  budget <ms|off> - Time a tick may take before detail is shed");
+    println!("  snapshot        - Remember everyone's needs, place, task and feelings now");
+    println!("  diff            - What has changed for whom since the snapshot");
+    println!("  save <file>     - Save the world to a file");
+    println!("  load <file>     - Load a world saved with save");
+    println!("  record <file>   - Save this session's command log");
+    println!("  replay <file>   - Re-run a recorded command log");
+    println!("  oath <name> deliver <n> <resource> by <days|season>");
+    println!("  oath <name> defend <name> by <days|season>");
+    println!("                  - Have someone swear an oath");
+    println!("  rename <name> <new name> - Give someone a new name");
+    println!("  exile <name>    - Banish someone from the settlement");
+    println!("  ask <question>  - Ask about the settlement (\"who is hungriest\")");
+    println!("  assign <who> <action> [target] [@place|@(x,y)] [!priority] [then ...]");
+    println!("                  - Give an order without the LLM");
+    println!("  priority <name> <critical|high|normal|low> - Change the priority of their orders");
+    println!("  script <file>   - Run a file of commands (spawn, place, assign, priority)");
+    println!("  embody <name>   - Take control of someone");
+    println!("  actions         - List what they know how to do");
+    println!("  go <x> <y>      - Walk them somewhere");
+    println!("  do <action> [<name>] - Have them take an action");
+    println!("  say <name> <chat|compliment|insult> - Speak to someone nearby");
+    println!("  release         - Hand them back to their own judgement");
     println!("  quit / q        - Exit the game");
     if llm_client.is_some() {
         println!("  <any text>      - Natural language command (parsed by LLM)");
     }
     println!();
 
+    // An order waiting on the player's answer to a question about it
+    let mut pending: Option<Clarification> = None;
+
     // Main game loop
     loop {
         // Display current status
@@ -77,9 +217,25 @@ fn main() -> Result<()> {
             break;
         }
 
+        // Anything else answers the question asked about the last order
+        if let Some(clarification) = pending.take() {
+            match clarification.answer(&world, input) {
+                Clarified::Resolved(intent) => {
+                    pending = issue_intent(&mut world, &mut recorder, intent)
+                }
+                Clarified::Ask(next) => {
+                    println!("{}", next.question());
+                    pending = Some(next);
+                }
+                Clarified::Cancelled => println!("Order dropped."),
+            }
+            continue;
+        }
+
         // Handle tick command
         if input == "tick" || input == "t" {
-            run_simulation_tick(&mut world);
+            let events = recorder.tick(&mut world);
+            report_plan_progress(&events);
             println!("Tick {} complete.", world.current_tick);
             continue;
         }
@@ -90,35 +246,379 @@ fn main() -> Result<()> {
             continue;
         }
 
+        // Handle almanac command
+        if input == "almanac" {
+            println!();
+            println!("=== Almanac (Day {}) ===", world.astronomy.current_day);
+            for line in almanac(&world, ALMANAC_DAYS).lines() {
+                println!("{}", line);
+            }
+            println!();
+            continue;
+        }
+
+        // Handle requests command
+        if input == "requests" {
+            display_requests(&world);
+            continue;
+        }
+
+        if input == "omens" {
+            display_omens(&world);
+            continue;
+        }
+
+        if input == "era" {
+            display_era(&world);
+            continue;
+        }
+
+        if input == "rite" {
+            match recorder.apply(&mut world, PlayerCommand::HoldRite) {
+                CommandOutcome::RiteHeld(Ok(n)) => {
+                    println!("The rite is held; {} omen(s) answered.", n)
+                }
+                CommandOutcome::RiteHeld(Err(e)) => println!("{}", e),
+                _ => {}
+            }
+            continue;
+        }
+
         // Handle run <n> command
         if input.starts_with("run ") {
             if let Ok(n) = input.strip_prefix("run ").unwrap().parse::<u32>() {
                 println!("Running {} ticks...", n);
+                let start = world.current_tick;
                 for _ in 0..n {
-                    run_simulation_tick(&mut world);
+                    let events = recorder.tick(&mut world);
+                    report_plan_progress(&events);
                 }
                 println!("Completed {} ticks. Now at tick {}.", n, world.current_tick);
+                if world.current_tick - start >= FAST_FORWARD_TICKS {
+                    show_situation(&mut world, llm_client.as_ref(), &rt);
+                }
             } else {
                 println!("Usage: run <number>");
             }
             continue;
         }
 
-        // Handle spawn command
+        // Handle time [detailed|compressed [steps a day]] command
+        if input == "time" || input.starts_with("time ") {
+            let mut words = input.split_whitespace().skip(1);
+            let scale = match (words.next(), words.next()) {
+                (None, _) => match world.held_time_scale {
+                    Some(held) => {
+                        println!("Time scale: detailed while fighting, then {}.", held.name());
+                        continue;
+                    }
+                    None => {
+                        println!("Time scale: {}.", world.time_scale.name());
+                        continue;
+                    }
+                },
+                (Some("detailed"), None) => Ok(TimeScale::Detailed),
+                (Some("compressed"), None) => TimeScale::compressed(COMPRESSED_TICKS_PER_DAY),
+                (Some("compressed"), Some(n)) => match n.parse() {
+                    Ok(n) => TimeScale::compressed(n),
+                    Err(_) => {
+                        println!("Usage: time [detailed|compressed [steps a day]]");
+                        continue;
+                    }
+                },
+                _ => {
+                    println!("Usage: time [detailed|compressed [steps a day]]");
+                    continue;
+                }
+            };
+            match scale {
+                Ok(scale) => {
+                    recorder.apply(&mut world, PlayerCommand::SetTimeScale(scale));
+                    println!("Time scale: {}.", scale.name());
+                    if fighting(&world) {
+                        println!("Steps stay detailed until the fighting is over.");
+                    }
+                }
+                Err(e) => println!("{}", e),
+            }
+            continue;
+        }
+
+        // Handle report command
+        if input == "report" {
+            show_situation(&mut world, llm_client.as_ref(), &rt);
+            continue;
+        }
+
+        // Handle timings command
+        if input == "timings" {
+            show_timings(&world);
+            continue;
+        }
+
+        // Handle snapshot command
+        if input == "snapshot" {
+            let taken = WorldSnapshot::capture(&world);
+            println!(
+                "Snapshot of {} settlers at tick {}.",
+                taken.settlers.len(),
+                taken.tick
+            );
+            snapshot = Some(taken);
+            continue;
+        }
+
+        // Handle diff command
+        if input == "diff" {
+            match &snapshot {
+                Some(before) => println!("{}", diff(before, &WorldSnapshot::capture(&world))),
+                None => println!("No snapshot yet; take one with snapshot."),
+            }
+            continue;
+        }
+
+        // Handle budget <ms|off> command
+        if let Some(arg) = input.strip_prefix("budget ") {
+            match arg.trim() {
+                "off" => {
+                    recorder.apply(&mut world, PlayerCommand::SetBudget(None));
+                    println!("Tick budget off.");
+                }
+                ms => match ms.parse::<u64>() {
+                    Ok(ms) if ms > 0 => {
+                        let budget = Some(Duration::from_millis(ms));
+                        recorder.apply(&mut world, PlayerCommand::SetBudget(budget));
+                        println!("Tick budget set to {} ms.", ms);
+                    }
+                    _ => println!("Usage: budget <milliseconds|off>"),
+                },
+            }
+            continue;
+        }
+
+        // Handle where <name> command
+        if let Some(name) = input.strip_prefix("where ") {
+            show_whereabouts(&world, name.trim());
+            continue;
+        }
+
+        // Handle save <file> command
+        if let Some(path) = input.strip_prefix("save ") {
+            match save_world(&world, path.trim()) {
+                Ok(()) => println!("Saved tick {} to {}.", world.current_tick, path.trim()),
+                Err(e) => println!("Could not save: {}", e),
+            }
+            continue;
+        }
+
+        // Handle load <file> command
+        if let Some(path) = input.strip_prefix("load ") {
+            match load_world(path.trim()) {
+                Ok(loaded) => {
+                    world = loaded;
+                    recorder = Recorder::start(&world)?;
+                    println!("Loaded {} at tick {}.", path.trim(), world.current_tick);
+                    show_situation(&mut world, llm_client.as_ref(), &rt);
+                }
+                Err(e) => println!("Could not load: {}", e),
+            }
+            continue;
+        }
+
+        // Handle record <file> command
+        if let Some(path) = input.strip_prefix("record ") {
+            match recorder.log().save(path.trim()) {
+                Ok(()) => println!(
+                    "Saved {} logged steps to {}.",
+                    recorder.log().entries.len(),
+                    path.trim()
+                ),
+                Err(e) => println!("Could not save log: {}", e),
+            }
+            continue;
+        }
+
+        // Handle replay <file> command
+        if let Some(path) = input.strip_prefix("replay ") {
+            match ReplayLog::load(path.trim()).and_then(|log| Ok((replay(&log)?, log))) {
+                Ok((replayed, log)) => {
+                    world = replayed;
+                    recorder = Recorder::resume(log);
+                    println!("Replayed {} to tick {}.", path.trim(), world.current_tick);
+                }
+                Err(e) => println!("Could not replay: {}", e),
+            }
+            continue;
+        }
+
+        // Handle oath command
+        if let Some(args) = input.strip_prefix("oath ") {
+            match parse_oath(&world, args) {
+                Ok(command) => match recorder.apply(&mut world, command) {
+                    CommandOutcome::Sworn(Some(_)) => println!("The oath is sworn."),
+                    _ => println!("They cannot swear that oath."),
+                },
+                Err(usage) => println!("{}", usage),
+            }
+            continue;
+        }
+
+        if let Some(question) = input.strip_prefix("ask ") {
+            answer_question(&world, llm_client.as_ref(), &rt, question, None);
+            continue;
+        }
+
+        if let Some(args) = input.strip_prefix("rename ") {
+            let Some((old, new)) = args.trim().split_once(' ') else {
+                println!("Usage: rename <name> <new name>");
+                continue;
+            };
+            match find_human(&world, old) {
+                Some(entity) => {
+                    let name = new.trim().to_string();
+                    match recorder.apply(&mut world, PlayerCommand::Rename { entity, name }) {
+                        CommandOutcome::Renamed(Ok(())) => {
+                            println!("{} is now {}.", old, new.trim())
+                        }
+                        CommandOutcome::Renamed(Err(e)) => println!("{}", e),
+                        _ => {}
+                    }
+                }
+                None => println!("No one named {}", old),
+            }
+            continue;
+        }
+
+        if let Some(name) = input.strip_prefix("exile ") {
+            match find_human(&world, name.trim()) {
+                Some(entity) => {
+                    let command = PlayerCommand::Exile {
+                        entity,
+                        reason: "by your decree".into(),
+                    };
+                    match recorder.apply(&mut world, command) {
+                        CommandOutcome::Exiled(Ok(())) => {
+                            println!("{} is banished from the settlement.", name.trim())
+                        }
+                        CommandOutcome::Exiled(Err(e)) => println!("{}", e),
+                        _ => {}
+                    }
+                }
+                None => println!("No one named {}", name.trim()),
+            }
+            continue;
+        }
+
+        // Structured orders, no LLM needed
+        if input.starts_with("assign ") || input.starts_with("priority ") {
+            match parse_line(&world, input) {
+                Ok(Some(PlayerCommand::Intent(intent))) => {
+                    pending = issue_intent(&mut world, &mut recorder, intent)
+                }
+                Ok(Some(command)) => {
+                    if let CommandOutcome::Prioritized(n) = recorder.apply(&mut world, command) {
+                        println!("Reprioritized {} order(s).", n);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => println!("{}", e),
+            }
+            continue;
+        }
+
+        if let Some(path) = input.strip_prefix("script ") {
+            run_script_file(&mut world, &mut recorder, path.trim());
+            continue;
+        }
+
+        // Handle avatar commands
+        if let Some(name) = input.strip_prefix("embody ") {
+            match find_human(&world, name.trim()) {
+                Some(entity) => match recorder.apply(&mut world, PlayerCommand::Embody { entity }) {
+                    CommandOutcome::Avatar(Ok(())) => println!("You are now {}.", name.trim()),
+                    CommandOutcome::Avatar(Err(e)) => println!("{}", e),
+                    _ => {}
+                },
+                None => println!("No one named {}", name.trim()),
+            }
+            continue;
+        }
+        if input == "release" {
+            match recorder.apply(&mut world, PlayerCommand::Release) {
+                CommandOutcome::Released(Ok(deeds)) => {
+                    println!("Released. They remember {} deed(s).", deeds)
+                }
+                CommandOutcome::Released(Err(e)) => println!("{}", e),
+                _ => {}
+            }
+            continue;
+        }
+        if input == "actions" {
+            match world
+                .avatar
+                .as_ref()
+                .and_then(|a| world.humans.index_of(a.entity))
+            {
+                Some(i) => {
+                    let actions: Vec<String> = available_actions(&world, i)
+                        .iter()
+                        .map(|a| format!("{:?}", a))
+                        .collect();
+                    println!("Available: {}", actions.join(", "));
+                }
+                None => println!("No one is being embodied"),
+            }
+            continue;
+        }
+        if input.starts_with("go ") || input.starts_with("do ") || input.starts_with("say ") {
+            match parse_avatar_command(&world, input) {
+                Ok(command) => {
+                    if let CommandOutcome::Avatar(Err(e)) = recorder.apply(&mut world, command) {
+                        println!("{}", e);
+                    }
+                }
+                Err(usage) => println!("{}", usage),
+            }
+            continue;
+        }
+
+        // Handle spawn command: a human, or a dwarf or elf named first
         if input.starts_with("spawn ") {
-            let name = input.strip_prefix("spawn ").unwrap();
-            if name.is_empty() {
-                println!("Usage: spawn <name>");
-            } else {
-                let id = world.spawn_human(name.into());
-                println!("Spawned {} (ID: {:?})", name, id);
+            match parse_line(&world, input) {
+                Ok(Some(command)) => {
+                    if let CommandOutcome::Spawned(id) = recorder.apply(&mut world, command) {
+                        if let Some(identity) = world.identities.get(id) {
+                            println!("Spawned {} (ID: {:?})", identity.name, id);
+                        }
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => println!("{}", e),
+            }
+            continue;
+        }
+
+        // Handle place command
+        if let Some(args) = input.strip_prefix("place ") {
+            match parse_place(args) {
+                Ok(command) => match recorder.apply(&mut world, command) {
+                    CommandOutcome::Placed(Ok(_)) => {
+                        println!("Site marked out. Idle hands will haul and build.")
+                    }
+                    CommandOutcome::Placed(Err(e)) => println!("Cannot place it: {}", e),
+                    _ => {}
+                },
+                Err(usage) => println!("{}", usage),
             }
             continue;
         }
 
         // Try LLM command parsing if available
         if let Some(ref client) = llm_client {
-            let context = GameContext::from_world(&world);
+            let mut context = GameContext::from_world(&world);
+            if let Some(chronicle) = &history {
+                context.add_history(chronicle, None);
+            }
 
             match rt.block_on(parse_command(client, input, &context)) {
                 Ok(intent) => {
@@ -140,30 +640,22 @@ fn main() -> Result<()> {
                         println!("  Ambiguous concepts: {:?}", intent.ambiguous_concepts);
                         println!("  (These may be interpreted differently by different species)");
                     }
+                    if intent.is_compound() {
+                        println!("  Steps:");
+                        for (n, step) in intent.steps().iter().enumerate() {
+                            let target = step.target.as_deref().unwrap_or("");
+                            println!("    {}. {:?} {}", n + 1, step.action, target);
+                        }
+                    }
 
                     // Execute the command
                     match &intent.action {
                         IntentAction::Query => {
-                            // Handle queries (status, info) without creating tasks
-                            println!("Query: {:?}", intent.target);
-                        }
-                        _ => {
-                            // Execute command
-                            let current_tick = world.current_tick;
-                            let result =
-                                CommandExecutor::execute(&mut world, &intent, current_tick);
-
-                            if let Some(error) = &result.error {
-                                println!("Command failed: {}", error);
-                            } else if result.tasks_created > 0 {
-                                println!("Assigned {} task(s) to:", result.tasks_created);
-                                for (_, name) in &result.assigned_to {
-                                    println!("  - {}", name);
-                                }
-                            } else {
-                                println!("No tasks created");
-                            }
+                            // Questions are answered, not carried out
+                            let target = intent.target.as_deref();
+                            answer_question(&world, Some(client), &rt, input, target);
                         }
+                        _ => pending = issue_intent(&mut world, &mut recorder, intent),
                     }
                 }
                 Err(e) => {
@@ -171,7 +663,7 @@ fn main() -> Result<()> {
                 }
             }
         } else {
-            println!("Unknown command. Available: tick, spawn <name>, status, run <n>, quit");
+            println!("Unknown command. Available: tick, spawn [dwarf|elf] <name>, place <blueprint> <x> <y>, status, almanac, requests, omens, rite, run <n>, time ..., save <file>, load <file>, record <file>, replay <file>, report, where <name>, ask <question>, assign ..., priority <name> <level>, script <file>, timings, budget <ms|off>, oath ..., rename <name> <new name>, exile <name>, embody <name>, actions, go, do, say, release, quit");
         }
     }
 
@@ -183,11 +675,322 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Run the simulation with no prompt and write telemetry samples
+fn run_headless_mode(args: &Args) -> Result<()> {
+    // Warnings only, and on stderr, so that long runs stay fast and quiet
+    tracing_subscriber::fmt()
+        .with_env_filter("arc_citadel=warn")
+        .with_writer(io::stderr)
+        .init();
+
+    let Some(format) = TelemetryFormat::from_name(&args.format) else {
+        eprintln!("Unknown format {} (expected csv or json)", args.format);
+        return Ok(());
+    };
+    let out = args.out.clone().unwrap_or_else(|| match format {
+        TelemetryFormat::Csv => PathBuf::from("telemetry.csv"),
+        TelemetryFormat::Json => PathBuf::from("telemetry.json"),
+    });
+
+    let mut world = match &args.world {
+        Some(path) => match load_world(path) {
+            Ok(mut world) => {
+                // Reseed so that one saved world can start many different runs
+                if let Some(seed) = args.seed {
+                    world.seed = seed;
+                }
+                world
+            }
+            Err(e) => {
+                eprintln!("Could not load {}: {}", path.display(), e);
+                return Ok(());
+            }
+        },
+        None => {
+            let mut world = args.seed.map(World::with_seed).unwrap_or_default();
+            let mut recorder = Recorder::start(&world)?;
+            spawn_initial_population(&mut world, &mut recorder);
+            world
+        }
+    };
+
+    if !args.trace.is_empty() {
+        let mut trace = DecisionTrace::new();
+        for name in &args.trace {
+            match find_human(&world, name) {
+                Some(id) => trace.watch(id),
+                None => eprintln!("No living human named {} to trace", name),
+            }
+        }
+        world.decision_trace = Some(trace);
+    }
+
+    match TimeScale::compressed(args.ticks_per_day) {
+        Ok(scale) => {
+            world.set_time_scale(scale);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return Ok(());
+        }
+    }
+
+    let config = HeadlessConfig {
+        ticks: args.ticks,
+        sample_every: args.sample_every,
+    };
+    let telemetry = run_headless(&mut world, config);
+    match telemetry.save(&out, format) {
+        Ok(()) => println!(
+            "Ran {} ticks (seed {}), wrote {} samples to {}",
+            args.ticks,
+            world.seed,
+            telemetry.samples.len(),
+            out.display()
+        ),
+        Err(e) => eprintln!("Could not write telemetry: {}", e),
+    }
+    if let Some(trace) = &world.decision_trace {
+        match trace.save(&args.trace_out) {
+            Ok(()) => println!(
+                "Wrote {} decisions to {}",
+                trace.records().len(),
+                args.trace_out.display()
+            ),
+            Err(e) => eprintln!("Could not write decision trace: {}", e),
+        }
+    }
+    Ok(())
+}
+
+/// Find a living human by name (case-insensitive)
+fn find_human(world: &World, name: &str) -> Option<EntityId> {
+    world
+        .humans
+        .iter_living()
+        .find(|&i| world.humans.names[i].eq_ignore_ascii_case(name))
+        .map(|i| world.humans.ids[i])
+}
+
+/// Parse the arguments of an oath command into a player command
+///
+/// `<name> deliver <n> <resource> by <deadline>` is sworn to the settlement;
+/// `<name> defend <ward> by <deadline>` is sworn to the ward. The deadline is
+/// a number of days or the start of a season.
+fn parse_oath(world: &World, args: &str) -> std::result::Result<PlayerCommand, String> {
+    const USAGE: &str = "Usage: oath <name> deliver <n> <resource> by <days|season>\n       oath <name> defend <name> by <days|season>";
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let (terms, deadline) = match words.as_slice() {
+        [_, "deliver", amount, resource, "by", deadline] => {
+            let amount = amount.parse().map_err(|_| USAGE.to_string())?;
+            let resource = ResourceType::from_name(resource)
+                .ok_or_else(|| format!("Unknown resource: {}", resource))?;
+            (OathTerms::Deliver { resource, amount }, *deadline)
+        }
+        [_, "defend", ward, "by", deadline] => {
+            let ward = find_human(world, ward).ok_or_else(|| format!("No one named {}", ward))?;
+            (OathTerms::Defend { ward }, *deadline)
+        }
+        _ => return Err(USAGE.to_string()),
+    };
+    let swearer =
+        find_human(world, words[0]).ok_or_else(|| format!("No one named {}", words[0]))?;
+    let days = match Season::from_name(deadline) {
+        Some(season) => world.astronomy.days_until(season),
+        None => deadline.parse().map_err(|_| USAGE.to_string())?,
+    };
+    let beneficiary = match terms {
+        OathTerms::Defend { ward } => Some(ward),
+        OathTerms::Deliver { .. } => None,
+    };
+    Ok(PlayerCommand::SwearOath {
+        swearer,
+        beneficiary,
+        terms,
+        days,
+    })
+}
+
+/// Show what happened since the last report, narrated if an LLM is available
+fn show_situation(world: &mut World, llm_client: Option<&LlmClient>, rt: &Runtime) {
+    let report = take_situation_report(world);
+    println!();
+    println!("=== While you were away ===");
+    let narrated = llm_client.and_then(|client| {
+        rt.block_on(narrate_situation(client, &report))
+            .map_err(|e| tracing::warn!("Could not narrate report: {}", e))
+            .ok()
+    });
+    match narrated {
+        Some(text) if !report.is_quiet() => println!("{}", text),
+        _ => {
+            for line in report.lines() {
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+/// Answer a question about the settlement, in the steward's voice if an
+/// LLM is available; the parsed target is tried if the question is not
+/// understood as asked
+fn answer_question(
+    world: &World,
+    llm_client: Option<&LlmClient>,
+    rt: &Runtime,
+    question: &str,
+    target: Option<&str>,
+) {
+    let query = Query::parse(world, question).or_else(|| Query::parse(world, target?));
+    let Some(query) = query else {
+        println!("I cannot answer that. Ask who is hungriest, how much wood we have, what someone is doing, where someone is, how many live here, or how happy we are.");
+        return;
+    };
+    let answer = query.answer(world);
+    let polished = llm_client.and_then(|client| {
+        rt.block_on(polish_answer(client, question, &answer))
+            .map_err(|e| tracing::warn!("Could not polish answer: {}", e))
+            .ok()
+    });
+    println!("{}", polished.unwrap_or(answer));
+}
+
+/// Say where everyone by a name is, and whether they can be called on
+fn show_whereabouts(world: &World, name: &str) {
+    let ids: Vec<EntityId> = world.identities.find_by_name(name).collect();
+    if ids.is_empty() {
+        println!("No one named {}", name);
+    }
+    for id in ids {
+        let Some((identity, position)) = world.where_is(id) else {
+            continue;
+        };
+        let place = match (identity.whereabouts, position) {
+            _ if matches!(identity.fate, Fate::Exiled { .. }) => "beyond the map".to_string(),
+            (_, Some(pos)) => format!("in the settlement at ({:.0}, {:.0})", pos.x, pos.y),
+            (Whereabouts::Battle { hex, .. }, _) => {
+                format!("in battle at hex ({}, {})", hex.q, hex.r)
+            }
+            (Whereabouts::Campaign { army, hex }, _) => {
+                format!("with army {} at hex ({}, {})", army.0, hex.q, hex.r)
+            }
+            (Whereabouts::Court { polity }, _) => format!("at the court of polity {}", polity.0),
+            (Whereabouts::Settlement, None) => "in the settlement".to_string(),
+        };
+        let fate = match identity.fate {
+            Fate::Active => String::new(),
+            Fate::Captured { since, .. } => format!(", held captive since tick {}", since),
+            Fate::Dead { tick } => format!(", dead since tick {}", tick),
+            Fate::Exiled { since } => format!(", in exile since tick {}", since),
+        };
+        println!(
+            "{} ({:?}, {:?}): {}{}",
+            identity.known_as(),
+            identity.species,
+            identity.role,
+            place,
+            fate
+        );
+    }
+}
+
+/// Show how long each system has taken, and how often it runs
+fn show_timings(world: &World) {
+    println!();
+    println!("=== System Timings (tick {}) ===", world.current_tick);
+    for system in SystemId::ALL {
+        let timing = world.scheduler.timing(system);
+        let frequency = match world.scheduler.frequency(system) {
+            Frequency::EveryTick => "every tick".to_string(),
+            Frequency::Every(n) => format!("every {} ticks", n),
+            Frequency::Amortized(n) => format!("1/{} each tick", n),
+        };
+        println!(
+            "  {:<22} {:>6} runs  mean {:>9.3?}  max {:>9.3?}  ({})",
+            system.name(),
+            timing.runs,
+            timing.mean(),
+            timing.max,
+            frequency
+        );
+    }
+    let budget = match world.scheduler.budget() {
+        Some(budget) => format!("{:?}", budget),
+        None => "none".to_string(),
+    };
+    println!(
+        "  tick budget {}, fidelity {}",
+        budget,
+        world.scheduler.fidelity().name()
+    );
+    println!();
+}
+
+/// Parse a place command: a blueprint and where to put it
+fn parse_place(args: &str) -> std::result::Result<PlayerCommand, String> {
+    const USAGE: &str = "Usage: place <house|wall|watchtower|well|shrine> <x> <y>";
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let [name, x, y] = words.as_slice() else {
+        return Err(USAGE.to_string());
+    };
+    let (x, y) = x
+        .parse()
+        .ok()
+        .zip(y.parse().ok())
+        .ok_or_else(|| USAGE.to_string())?;
+    Ok(PlayerCommand::PlaceBlueprint {
+        blueprint: blueprint_named(name).unwrap_or(name).to_string(),
+        position: Vec2::new(x, y),
+    })
+}
+
+/// Parse a go, do, or say command for the embodied human
+fn parse_avatar_command(world: &World, input: &str) -> std::result::Result<PlayerCommand, String> {
+    const USAGE: &str = "Usage: go <x> <y>\n       do <action> [<name>]\n       say <name> <chat|compliment|insult>";
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let named = |name: &str| find_human(world, name).ok_or_else(|| format!("No one named {}", name));
+    match words.as_slice() {
+        ["go", x, y] => {
+            let (x, y) = x
+                .parse()
+                .ok()
+                .zip(y.parse().ok())
+                .ok_or_else(|| USAGE.to_string())?;
+            Ok(PlayerCommand::AvatarAct {
+                action: ActionId::MoveTo,
+                target: None,
+                position: Some(Vec2::new(x, y)),
+            })
+        }
+        ["do", action, rest @ ..] if rest.len() <= 1 => {
+            let action =
+                ActionId::from_name(action).ok_or_else(|| format!("Unknown action: {}", action))?;
+            let target = rest.first().map(|name| named(name)).transpose()?;
+            Ok(PlayerCommand::AvatarAct {
+                action,
+                target,
+                position: None,
+            })
+        }
+        ["say", listener, choice] => {
+            let choice = DialogueChoice::from_name(choice).ok_or_else(|| USAGE.to_string())?;
+            Ok(PlayerCommand::AvatarSay {
+                listener: named(listener)?,
+                choice,
+            })
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
 /// Spawn the initial population of test entities
-fn spawn_initial_population(world: &mut World) {
+fn spawn_initial_population(world: &mut World, recorder: &mut Recorder) {
     let names = ["Marcus", "Elena", "Thomas", "Sarah", "William"];
     for name in names {
-        let id = world.spawn_human(name.into());
+        let command = PlayerCommand::SpawnHuman { name: name.into() };
+        let CommandOutcome::Spawned(id) = recorder.apply(world, command) else {
+            continue;
+        };
 
         // Set some varied values for testing emergent behavior
         if let Some(idx) = world.humans.index_of(id) {
@@ -238,8 +1041,13 @@ fn display_status(world: &World) {
         world.entity_count()
     );
 
+    let avatar = world.avatar.as_ref().map(|a| a.entity);
     for i in world.humans.iter_living().take(5) {
-        let name = &world.humans.names[i];
+        let name = if Some(world.humans.ids[i]) == avatar {
+            format!("{} (you)", known_as(world, i))
+        } else {
+            known_as(world, i)
+        };
         let body = &world.humans.body_states[i];
         let needs = &world.humans.needs[i];
         let (top_need, level) = needs.most_pressing();
@@ -262,6 +1070,158 @@ fn display_status(world: &World) {
     if world.entity_count() > 5 {
         println!("  ... and {} more", world.entity_count() - 5);
     }
+    if let Some(poll) = &world.happiness.latest {
+        let unrest = if world.happiness.unrest {
+            " - UNREST"
+        } else {
+            ""
+        };
+        println!("  {}{}", poll.summary(), unrest);
+    }
+    if let Some(refusal) = orders_refused(world) {
+        println!("  {}", refusal);
+    }
+    for plan in world.plans.iter() {
+        println!("  {}", plan.progress());
+    }
+    println!();
+}
+
+/// Carry out a parsed order and report what came of it
+///
+/// Returns the order back, with a question, if it was too unclear to act on.
+fn issue_intent(
+    world: &mut World,
+    recorder: &mut Recorder,
+    intent: ParsedIntent,
+) -> Option<Clarification> {
+    let target = intent.target.clone();
+    let CommandOutcome::Executed(result) = recorder.apply(world, PlayerCommand::Intent(intent))
+    else {
+        return None;
+    };
+
+    if let Some(clarification) = result.clarification {
+        println!("{}", clarification.question());
+        println!("(Answer, or say \"cancel\" to drop the order.)");
+        return Some(clarification);
+    }
+    if let Some(error) = &result.error {
+        println!("Command failed: {}", error);
+        return None;
+    }
+    if result.placed.is_some() {
+        println!("Site marked out for the {:?}.", target);
+    }
+    if let Some(plan) = result.plan {
+        println!(
+            "Plan {} started; later steps follow once each is done.",
+            plan.0
+        );
+    }
+    if result.tasks_created > 0 {
+        println!("Assigned {} task(s) to:", result.tasks_created);
+        for (_, name) in &result.assigned_to {
+            println!("  - {}", name);
+        }
+    } else {
+        println!("No tasks created");
+    }
+    None
+}
+
+/// Run each line of a script file as if typed, stopping at the first
+/// line that cannot be read
+fn run_script_file(world: &mut World, recorder: &mut Recorder, path: &str) {
+    let script = match std::fs::read_to_string(path) {
+        Ok(script) => script,
+        Err(e) => {
+            println!("Could not read {}: {}", path, e);
+            return;
+        }
+    };
+    let mut applied = 0;
+    for (n, line) in script.lines().enumerate() {
+        match parse_line(world, line) {
+            Ok(Some(command)) => {
+                recorder.apply(world, command);
+                applied += 1;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                println!("{}: line {}: {}", path, n + 1, e);
+                break;
+            }
+        }
+    }
+    println!("Ran {} command(s) from {}.", applied, path);
+}
+
+/// Print how multi-step orders are coming along
+fn report_plan_progress(events: &[SimulationEvent]) {
+    for event in events {
+        if let SimulationEvent::PlanProgress { message, .. } = event {
+            println!("{}", message);
+        }
+    }
+}
+
+/// List the open requests, with the days left to grant each
+fn display_requests(world: &World) {
+    println!();
+    println!("=== Requests (Day {}) ===", world.astronomy.current_day);
+    let mut any = false;
+    for request in world.requests.open() {
+        let days_left = request.deadline_tick.saturating_sub(world.current_tick) / TICKS_PER_DAY;
+        println!(
+            "  {} ({} days left)",
+            requests::describe(world, request),
+            days_left
+        );
+        any = true;
+    }
+    if !any {
+        println!("  No one is asking for anything.");
+    }
+    println!();
+}
+
+/// Show the settlement's culture and the omens it is living under
+fn display_omens(world: &World) {
+    println!();
+    println!(
+        "=== Omens (Day {}, {}) ===",
+        world.astronomy.current_day, world.omens.culture
+    );
+    for omen in &world.omens.active {
+        println!("  {} (until day {})", omen.reading, omen.until_day);
+        if let Some(rite) = &omen.awaits_rite {
+            println!("    The settlers wait for {}", rite);
+        }
+    }
+    if world.omens.active.is_empty() {
+        println!("  The sky says nothing today.");
+    }
+    println!();
+}
+
+fn display_era(world: &World) {
+    println!();
+    match world.eras.current() {
+        Some(era) => println!("=== {} (since day {}) ===", era.name, era.day),
+        None => println!("=== No era has begun ==="),
+    }
+    for (n, chapter) in world.legacy.chapters.iter().enumerate() {
+        println!(
+            "  {}. {} ({} lines)",
+            n + 1,
+            chapter.title,
+            world.legacy.chapter_entries(n).len()
+        );
+    }
+    if !world.eras.unlocked.is_empty() {
+        println!("  Unlocked: {}", world.eras.unlocked.join(", "));
+    }
     println!();
 }
 
@@ -312,6 +1272,16 @@ fn display_detailed_status(world: &World) {
             );
         }
 
+        let id = world.humans.ids[i];
+        let open_oaths = world.oaths.sworn_by(id).count();
+        if open_oaths > 0 || world.oaths.reputation(id) != 0.0 {
+            println!(
+                "  Oaths: {} open, reputation {:+.2}",
+                open_oaths,
+                world.oaths.reputation(id)
+            );
+        }
+
         if let Some(task) = task_queue.current() {
             println!(
                 "  Current task: {:?} (progress: {:.0}%)",
@@ -0,0 +1,460 @@
+//! Battle view - draws a live `BattleState` so a tactical battle can be
+//! watched rather than read from the log.
+//!
+//! Units are counters in their side's colour, pointed the way they face,
+//! with a strength bar and a morale bar beneath. Couriers are drawn where
+//! they ride, between hexes; each unit's waypoint plan is traced from where
+//! it stands; a marker sits between every pair of units locked in combat.
+//!
+//! Bars, couriers and engagement markers are tinted quads from the white
+//! atlas, so they draw over the shape batches whatever their type.
+
+use glam::Vec2;
+
+use crate::battle::courier::{CourierInFlight, CourierStatus, OrderTarget};
+use crate::battle::execution::BattleState;
+use crate::battle::hex::{BattleHexCoord, HexDirection};
+use crate::battle::planning::WaypointPlan;
+use crate::battle::units::{Army, BattleUnit, UnitId};
+use crate::core::types::EntityId;
+use crate::renderer::hex::HEX_SIZE;
+use crate::renderer::state::{
+    AtlasId, Color, RenderEntity, RenderState, Selection, ShapeType, SpriteEntity, TextLabel,
+};
+use crate::renderer::tiles::hex_center;
+
+/// Scale of a unit counter.
+pub const COUNTER_SCALE: f32 = HEX_SIZE * 1.2;
+
+/// Pips in a strength or morale bar.
+pub const BAR_PIPS: usize = 10;
+
+/// Scale of one bar pip.
+const PIP_SCALE: f32 = 1.1;
+
+/// Spacing between pip centres.
+const PIP_SPACING: f32 = 1.3;
+
+/// Scale of a courier marker.
+const COURIER_SCALE: f32 = 3.0;
+
+/// Scale of an engagement marker.
+const ENGAGEMENT_SCALE: f32 = 4.0;
+
+/// Spacing between the dots tracing a plan.
+const PLAN_DOT_SPACING: f32 = HEX_SIZE * 0.6;
+
+pub const FRIENDLY_COLOR: Color = Color::rgb(0.25, 0.45, 0.9);
+pub const ENEMY_COLOR: Color = Color::rgb(0.85, 0.25, 0.2);
+const COURIER_COLOR: Color = Color::rgb(0.95, 0.85, 0.3);
+const ENEMY_COURIER_COLOR: Color = Color::rgb(0.95, 0.55, 0.25);
+const INTERCEPTED_COLOR: Color = Color::rgb(0.45, 0.05, 0.05);
+const ENGAGEMENT_COLOR: Color = Color::rgb(1.0, 0.1, 0.1);
+const STRENGTH_COLOR: Color = Color::rgb(0.3, 0.85, 0.3);
+const MORALE_COLOR: Color = Color::rgb(0.95, 0.75, 0.2);
+const EMPTY_PIP_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.8);
+
+/// Which side of the battle something belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Friendly,
+    Enemy,
+}
+
+impl Side {
+    pub fn color(self) -> Color {
+        match self {
+            Side::Friendly => FRIENDLY_COLOR,
+            Side::Enemy => ENEMY_COLOR,
+        }
+    }
+}
+
+/// What the battle view draws; each overlay can be toggled.
+#[derive(Debug, Clone, Copy)]
+pub struct BattleView {
+    pub show_plans: bool,
+    pub show_couriers: bool,
+    pub show_engagements: bool,
+    pub show_labels: bool,
+}
+
+impl Default for BattleView {
+    fn default() -> Self {
+        Self {
+            show_plans: true,
+            show_couriers: true,
+            show_engagements: true,
+            show_labels: true,
+        }
+    }
+}
+
+/// Fraction of a unit's men still standing.
+pub fn strength_fraction(unit: &BattleUnit) -> f32 {
+    let strength = unit.strength();
+    if strength == 0 {
+        return 0.0;
+    }
+    unit.effective_strength() as f32 / strength as f32
+}
+
+/// How far a unit is from breaking: 1.0 unstressed, 0.0 broken.
+pub fn morale_fraction(unit: &BattleUnit) -> f32 {
+    if unit.is_broken() {
+        return 0.0;
+    }
+    let threshold = unit.stress_threshold();
+    if threshold <= 0.0 {
+        return 1.0;
+    }
+    (1.0 - unit.stress / threshold).clamp(0.0, 1.0)
+}
+
+/// Pips lit for `fraction` of a bar; any remainder lights one.
+pub fn lit_pips(fraction: f32) -> usize {
+    ((fraction.clamp(0.0, 1.0) * BAR_PIPS as f32).ceil() as usize).min(BAR_PIPS)
+}
+
+/// Angle in radians of a hex direction, as drawn.
+fn facing_angle(direction: HexDirection) -> f32 {
+    let offset = direction.offset();
+    let to = hex_center(offset) - hex_center(BattleHexCoord::new(0, 0));
+    to.y.atan2(to.x)
+}
+
+/// Where a courier is now, between its current hex and the next on its path.
+pub fn courier_position(courier: &CourierInFlight) -> Vec2 {
+    let here = hex_center(courier.current_position);
+    let next = courier
+        .path
+        .iter()
+        .position(|&hex| hex == courier.current_position)
+        .and_then(|i| courier.path.get(i + 1));
+    match next {
+        Some(&next) => here.lerp(hex_center(next), courier.progress.clamp(0.0, 1.0)),
+        None => here,
+    }
+}
+
+fn color_u8(color: Color) -> [u8; 4] {
+    [
+        (color.r * 255.0) as u8,
+        (color.g * 255.0) as u8,
+        (color.b * 255.0) as u8,
+        (color.a * 255.0) as u8,
+    ]
+}
+
+fn quad(id: EntityId, position: Vec2, color: Color, scale: f32, z_order: i32) -> SpriteEntity {
+    SpriteEntity {
+        id,
+        position,
+        atlas: AtlasId::WHITE,
+        uv_rect: [0.0, 0.0, 1.0, 1.0],
+        color: color_u8(color),
+        rotation: 0.0,
+        scale,
+        flip_x: false,
+        flip_y: false,
+        z_order,
+    }
+}
+
+fn unit_entity(id: UnitId) -> EntityId {
+    EntityId(id.0)
+}
+
+fn side_of(state: &BattleState, unit: UnitId) -> Option<Side> {
+    if state.friendly_army.get_unit(unit).is_some() {
+        Some(Side::Friendly)
+    } else if state.enemy_army.get_unit(unit).is_some() {
+        Some(Side::Enemy)
+    } else {
+        None
+    }
+}
+
+fn unit_in(state: &BattleState, unit: UnitId) -> Option<&BattleUnit> {
+    state
+        .friendly_army
+        .get_unit(unit)
+        .or_else(|| state.enemy_army.get_unit(unit))
+}
+
+impl BattleView {
+    /// Draw `state` into `out`, after whatever it already holds.
+    pub fn draw(&self, state: &BattleState, out: &mut RenderState) {
+        out.tick = state.tick;
+        if self.show_plans {
+            self.draw_plans(state, &state.friendly_plan.waypoint_plans, out);
+            self.draw_plans(state, &state.enemy_plan.waypoint_plans, out);
+        }
+        self.draw_army(&state.friendly_army, Side::Friendly, out);
+        self.draw_army(&state.enemy_army, Side::Enemy, out);
+        if self.show_couriers {
+            for courier in &state.courier_system.in_flight {
+                self.draw_courier(state, courier, out);
+            }
+        }
+        if self.show_engagements {
+            for combat in &state.active_combats {
+                let (Some(a), Some(d)) = (
+                    unit_in(state, combat.attacker_unit),
+                    unit_in(state, combat.defender_unit),
+                ) else {
+                    continue;
+                };
+                let between = (hex_center(a.position) + hex_center(d.position)) * 0.5;
+                // Swells with each bout fought
+                let scale = ENGAGEMENT_SCALE * (1.0 + 0.1 * combat.bouts.min(5) as f32);
+                let mut marker = quad(unit_entity(a.id), between, ENGAGEMENT_COLOR, scale, 3);
+                marker.rotation = std::f32::consts::FRAC_PI_4;
+                out.sprites.push(marker);
+            }
+        }
+    }
+
+    fn draw_army(&self, army: &Army, side: Side, out: &mut RenderState) {
+        for unit in army.formations.iter().flat_map(|f| f.units.iter()) {
+            let position = hex_center(unit.position);
+            let mut color = side.color();
+            if unit.is_broken() {
+                color.a = 0.45;
+            }
+            let id = Selection::Entity(unit_entity(unit.id));
+            out.entities.push(RenderEntity {
+                id,
+                position,
+                facing: 0.0,
+                shape: ShapeType::Rectangle,
+                color,
+                scale: COUNTER_SCALE,
+                z_order: 1,
+            });
+            let angle = facing_angle(unit.facing);
+            out.entities.push(RenderEntity {
+                id,
+                position: position + Vec2::from_angle(angle) * COUNTER_SCALE * 0.5,
+                facing: angle,
+                shape: ShapeType::Triangle,
+                color: Color::WHITE,
+                scale: COUNTER_SCALE * 0.35,
+                z_order: 2,
+            });
+
+            let below = position.y - COUNTER_SCALE * 0.5;
+            self.draw_bar(
+                unit,
+                Vec2::new(position.x, below - PIP_SPACING),
+                strength_fraction(unit),
+                STRENGTH_COLOR,
+                out,
+            );
+            self.draw_bar(
+                unit,
+                Vec2::new(position.x, below - PIP_SPACING * 2.0),
+                morale_fraction(unit),
+                MORALE_COLOR,
+                out,
+            );
+
+            if self.show_labels {
+                out.labels.push(TextLabel::new(
+                    format!("{:?} {}", unit.unit_type, unit.effective_strength()),
+                    Vec2::new(position.x, position.y + COUNTER_SCALE * 0.6),
+                    Color::WHITE,
+                    12.0,
+                ));
+            }
+        }
+    }
+
+    fn draw_bar(
+        &self,
+        unit: &BattleUnit,
+        center: Vec2,
+        fraction: f32,
+        color: Color,
+        out: &mut RenderState,
+    ) {
+        let lit = lit_pips(fraction);
+        let left = center.x - PIP_SPACING * (BAR_PIPS as f32 - 1.0) * 0.5;
+        for i in 0..BAR_PIPS {
+            let pip = if i < lit { color } else { EMPTY_PIP_COLOR };
+            out.sprites.push(quad(
+                unit_entity(unit.id),
+                Vec2::new(left + PIP_SPACING * i as f32, center.y),
+                pip,
+                PIP_SCALE,
+                1,
+            ));
+        }
+    }
+
+    fn draw_courier(&self, state: &BattleState, courier: &CourierInFlight, out: &mut RenderState) {
+        let color = match courier.status {
+            CourierStatus::EnRoute if courier.delay > 0 => return,
+            CourierStatus::EnRoute => match courier.order.target {
+                OrderTarget::Unit(unit) => match side_of(state, unit) {
+                    Some(Side::Enemy) => ENEMY_COURIER_COLOR,
+                    _ => COURIER_COLOR,
+                },
+                OrderTarget::Formation(_) => COURIER_COLOR,
+            },
+            CourierStatus::Intercepted => INTERCEPTED_COLOR,
+            CourierStatus::Arrived | CourierStatus::Lost => return,
+        };
+        let mut marker = quad(
+            courier.courier_entity,
+            courier_position(courier),
+            color,
+            COURIER_SCALE,
+            2,
+        );
+        marker.rotation = std::f32::consts::FRAC_PI_4;
+        out.sprites.push(marker);
+    }
+
+    fn draw_plans(&self, state: &BattleState, plans: &[WaypointPlan], out: &mut RenderState) {
+        for plan in plans {
+            let Some(unit) = unit_in(state, plan.unit_id) else {
+                continue;
+            };
+            let Some(side) = side_of(state, plan.unit_id) else {
+                continue;
+            };
+            let mut color = side.color();
+            color.a = 0.6;
+            let id = Selection::Entity(unit_entity(unit.id));
+            let mut from = hex_center(unit.position);
+            for waypoint in plan.waypoints.iter().skip(plan.current_waypoint) {
+                let to = hex_center(waypoint.position);
+                let steps = (from.distance(to) / PLAN_DOT_SPACING) as usize;
+                for step in 1..steps {
+                    out.entities.push(RenderEntity {
+                        id,
+                        position: from.lerp(to, step as f32 / steps as f32),
+                        facing: 0.0,
+                        shape: ShapeType::Circle,
+                        color,
+                        scale: 0.8,
+                        z_order: 0,
+                    });
+                }
+                out.entities.push(RenderEntity {
+                    id,
+                    position: to,
+                    facing: 0.0,
+                    shape: ShapeType::Ring,
+                    color,
+                    scale: HEX_SIZE * 0.4,
+                    z_order: 0,
+                });
+                from = to;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::battle_map::BattleMap;
+    use crate::battle::courier::Order;
+    use crate::battle::execution::ActiveCombat;
+    use crate::battle::planning::{Waypoint, WaypointBehavior};
+    use crate::battle::unit_type::UnitType;
+    use crate::battle::units::{ArmyId, BattleFormation, Element, FormationId};
+    use crate::renderer::state::CameraState;
+
+    fn army_with_unit(position: BattleHexCoord) -> (Army, UnitId) {
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        unit.position = position;
+        unit.elements
+            .push(Element::new((0..20).map(|_| EntityId::new()).collect()));
+        let id = unit.id;
+        formation.units.push(unit);
+        army.formations.push(formation);
+        (army, id)
+    }
+
+    fn empty_state() -> RenderState {
+        RenderState {
+            tick: 0,
+            entities: Vec::new(),
+            sprites: Vec::new(),
+            labels: Vec::new(),
+            camera: CameraState::default(),
+        }
+    }
+
+    #[test]
+    fn test_bars_follow_losses_and_stress() {
+        let (mut army, id) = army_with_unit(BattleHexCoord::new(0, 0));
+        let unit = army.get_unit_mut(id).unwrap();
+        assert_eq!(lit_pips(strength_fraction(unit)), BAR_PIPS);
+        assert_eq!(lit_pips(morale_fraction(unit)), BAR_PIPS);
+
+        unit.casualties = 11;
+        unit.stress = unit.stress_threshold() * 0.5;
+        assert_eq!(lit_pips(strength_fraction(unit)), 5);
+        assert_eq!(lit_pips(morale_fraction(unit)), 5);
+
+        unit.stance = crate::battle::units::UnitStance::Routing;
+        assert_eq!(lit_pips(morale_fraction(unit)), 0);
+    }
+
+    #[test]
+    fn test_draws_counters_couriers_plans_and_engagements() {
+        let (friendly, ours) = army_with_unit(BattleHexCoord::new(2, 2));
+        let (enemy, theirs) = army_with_unit(BattleHexCoord::new(3, 2));
+        let mut battle = BattleState::new(BattleMap::new(10, 10), friendly, enemy);
+        battle
+            .active_combats
+            .push(ActiveCombat::new(ours, theirs, 0));
+
+        let mut courier = CourierInFlight::new(
+            EntityId::new(),
+            Order::hold(ours),
+            BattleHexCoord::new(0, 0),
+            BattleHexCoord::new(4, 0),
+        );
+        courier.progress = 0.5;
+        let midway = (hex_center(courier.path[0]) + hex_center(courier.path[1])) * 0.5;
+        assert!(courier_position(&courier).distance(midway) < 1e-3);
+        battle.courier_system.in_flight.push(courier);
+
+        let mut plan = WaypointPlan::new(ours);
+        plan.add_waypoint(Waypoint::new(
+            BattleHexCoord::new(2, 6),
+            WaypointBehavior::HoldAt,
+        ));
+        battle.friendly_plan.waypoint_plans.push(plan);
+
+        let mut out = empty_state();
+        BattleView::default().draw(&battle, &mut out);
+        let count = |shape| out.entities.iter().filter(|e| e.shape == shape).count();
+        assert_eq!(count(ShapeType::Rectangle), 2);
+        assert_eq!(count(ShapeType::Triangle), 2);
+        assert_eq!(count(ShapeType::Ring), 1);
+        assert!(count(ShapeType::Circle) > 0);
+        // Two bars per unit, then the courier and the engagement
+        assert_eq!(out.sprites.len(), 2 * 2 * BAR_PIPS + 2);
+        assert_eq!(out.labels.len(), 2);
+
+        let mut bare = empty_state();
+        BattleView {
+            show_plans: false,
+            show_couriers: false,
+            show_engagements: false,
+            show_labels: false,
+        }
+        .draw(&battle, &mut bare);
+        assert_eq!(bare.entities.len(), 4);
+        assert_eq!(bare.sprites.len(), 2 * 2 * BAR_PIPS);
+        assert!(bare.labels.is_empty());
+    }
+}
@@ -1,8 +1,23 @@
 //! Camera system with coordinate transformations.
+//!
+//! `CameraState` is the camera as one frame draws it. `CameraController`
+//! owns where the player wants the camera to be, kept within the world's
+//! bounds, and eases the drawn camera toward it each frame, following an
+//! entity when asked to.
 
 use super::state::CameraState;
 use glam::{Mat4, Vec2};
 
+/// Closest zoom, in world units per screen pixel
+pub const MIN_ZOOM: f32 = 0.1;
+
+/// Farthest zoom when the world has no bounds
+pub const MAX_ZOOM: f32 = 100.0;
+
+/// How quickly the camera closes on its target; the gap shrinks by a factor
+/// of e every `1 / CAMERA_SMOOTHING` seconds
+pub const CAMERA_SMOOTHING: f32 = 10.0;
+
 impl CameraState {
     /// Create a new camera centered at origin.
     pub fn new(viewport_width: f32, viewport_height: f32) -> Self {
@@ -63,7 +78,7 @@ impl CameraState {
     /// factor < 1.0 zooms in, factor > 1.0 zooms out.
     pub fn zoom_by(&mut self, factor: f32) {
         self.zoom *= factor;
-        self.zoom = self.zoom.clamp(0.1, 100.0);
+        self.zoom = self.zoom.clamp(MIN_ZOOM, MAX_ZOOM);
     }
 
     /// Zoom toward a specific screen position (e.g., mouse cursor).
@@ -71,7 +86,7 @@ impl CameraState {
     pub fn zoom_toward(&mut self, screen_pos: Vec2, factor: f32) {
         let world_before = self.screen_to_world(screen_pos);
         self.zoom *= factor;
-        self.zoom = self.zoom.clamp(0.1, 100.0);
+        self.zoom = self.zoom.clamp(MIN_ZOOM, MAX_ZOOM);
         let world_after = self.screen_to_world(screen_pos);
         self.center += world_before - world_after;
     }
@@ -107,6 +122,130 @@ impl CameraState {
     }
 }
 
+/// Eases a camera toward where the player wants it, within the world.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraController {
+    /// The camera as drawn this frame
+    pub camera: CameraState,
+    target_center: Vec2,
+    target_zoom: f32,
+    /// World area the view is kept over, as (min, max)
+    bounds: Option<(Vec2, Vec2)>,
+}
+
+impl CameraController {
+    /// Control a camera, starting where it is.
+    pub fn new(camera: CameraState) -> Self {
+        Self {
+            camera,
+            target_center: camera.center,
+            target_zoom: camera.zoom,
+            bounds: None,
+        }
+    }
+
+    /// Keep the view over a world area: it zooms out no further than the
+    /// whole area, and stops panning at its edges.
+    pub fn with_bounds(mut self, min: Vec2, max: Vec2) -> Self {
+        self.bounds = Some((min, max));
+        self.clamp_target();
+        self
+    }
+
+    /// Where the camera is heading
+    pub fn target_center(&self) -> Vec2 {
+        self.target_center
+    }
+
+    /// Pan the target by delta in world units.
+    pub fn pan(&mut self, delta: Vec2) {
+        self.target_center += delta;
+        self.clamp_target();
+    }
+
+    /// Pan by a mouse drag, so the world moves with the cursor.
+    pub fn drag(&mut self, screen_delta: Vec2) {
+        let world_delta = Vec2::new(-screen_delta.x, screen_delta.y) * self.target_zoom;
+        self.pan(world_delta);
+    }
+
+    /// Zoom the target by factor (< 1.0 zooms in).
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.target_zoom *= factor;
+        self.clamp_target();
+    }
+
+    /// Zoom the target toward a screen position, so the world point under
+    /// it is still there once the camera settles.
+    pub fn zoom_toward(&mut self, screen_pos: Vec2, factor: f32) {
+        let mut target = self.camera;
+        target.center = self.target_center;
+        target.zoom = self.target_zoom;
+        let world_before = target.screen_to_world(screen_pos);
+        self.target_zoom *= factor;
+        self.clamp_target();
+        target.zoom = self.target_zoom;
+        let world_after = target.screen_to_world(screen_pos);
+        self.target_center += world_before - world_after;
+        self.clamp_target();
+    }
+
+    /// Update viewport size (call on window resize).
+    pub fn set_viewport_size(&mut self, width: f32, height: f32) {
+        self.camera.set_viewport_size(width, height);
+        self.clamp_target();
+    }
+
+    /// Move the drawn camera `dt` seconds closer to its target, first
+    /// re-centring the target on `follow` if there is someone to follow.
+    pub fn update(&mut self, dt: f32, follow: Option<Vec2>) -> CameraState {
+        if let Some(position) = follow {
+            self.target_center = position;
+            self.clamp_target();
+        }
+        let closing = 1.0 - (-CAMERA_SMOOTHING * dt.max(0.0)).exp();
+        self.camera.center = self.camera.center.lerp(self.target_center, closing);
+        self.camera.zoom += (self.target_zoom - self.camera.zoom) * closing;
+        self.camera
+    }
+
+    /// Farthest zoom allowed: the whole world fits in the view
+    fn max_zoom(&self) -> f32 {
+        match self.bounds {
+            Some((min, max)) => {
+                let fit = (max - min) / self.camera.viewport_size.max(Vec2::ONE);
+                fit.max_element().clamp(MIN_ZOOM, MAX_ZOOM)
+            }
+            None => MAX_ZOOM,
+        }
+    }
+
+    fn clamp_target(&mut self) {
+        self.target_zoom = self.target_zoom.clamp(MIN_ZOOM, self.max_zoom());
+        let Some((min, max)) = self.bounds else {
+            return;
+        };
+        // Keep the view's edges inside the world, or centre a view wider
+        // than the world on it
+        let half_view = self.camera.viewport_size * self.target_zoom / 2.0;
+        let low = min + half_view;
+        let high = max - half_view;
+        let middle = (min + max) / 2.0;
+        self.target_center = Vec2::new(
+            if low.x <= high.x {
+                self.target_center.x.clamp(low.x, high.x)
+            } else {
+                middle.x
+            },
+            if low.y <= high.y {
+                self.target_center.y.clamp(low.y, high.y)
+            } else {
+                middle.y
+            },
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +316,46 @@ mod tests {
         assert!(camera.is_visible(Vec2::new(40.0, 40.0)));
         assert!(!camera.is_visible(Vec2::new(100.0, 0.0)));
     }
+
+    fn bounded_controller() -> CameraController {
+        let camera = CameraState {
+            center: Vec2::new(100.0, 100.0),
+            zoom: 0.5,
+            viewport_size: Vec2::new(100.0, 100.0),
+        };
+        CameraController::new(camera).with_bounds(Vec2::ZERO, Vec2::new(200.0, 200.0))
+    }
+
+    #[test]
+    fn test_controller_eases_toward_target_within_bounds() {
+        let mut controller = bounded_controller();
+
+        // Panning far past the edge stops with the view at the edge
+        controller.pan(Vec2::new(1000.0, 0.0));
+        assert_eq!(controller.target_center(), Vec2::new(175.0, 100.0));
+
+        // The drawn camera closes on the target a step at a time
+        let first = controller.update(0.05, None);
+        assert!(first.center.x > 100.0 && first.center.x < 175.0);
+        for _ in 0..100 {
+            controller.update(0.05, None);
+        }
+        assert!((controller.camera.center.x - 175.0).abs() < 0.01);
+
+        // Zooming out stops once the whole world is in view
+        controller.zoom_by(100.0);
+        assert_eq!(controller.update(10.0, None).zoom, 2.0);
+        assert_eq!(controller.target_center(), Vec2::new(100.0, 100.0));
+    }
+
+    #[test]
+    fn test_controller_follows_a_position() {
+        let mut controller = bounded_controller();
+
+        controller.update(10.0, Some(Vec2::new(60.0, 140.0)));
+        assert!((controller.camera.center - Vec2::new(60.0, 140.0)).length() < 0.01);
+        // Followed into a corner, the view still stays over the world
+        controller.update(10.0, Some(Vec2::new(5.0, 195.0)));
+        assert!((controller.camera.center - Vec2::new(25.0, 175.0)).length() < 0.01);
+    }
 }
@@ -2,7 +2,7 @@
 
 use super::context::GpuContext;
 use crate::renderer::shapes::vertex::{
-    circle_geometry, hexagon_geometry, rectangle_geometry, triangle_geometry,
+    circle_geometry, hexagon_geometry, rectangle_geometry, ring_geometry, triangle_geometry,
 };
 use crate::renderer::shapes::ShapeInstance;
 use wgpu::util::DeviceExt;
@@ -20,6 +20,7 @@ pub struct ShapeBuffers {
     pub rectangle: ShapeGeometry,
     pub triangle: ShapeGeometry,
     pub hexagon: ShapeGeometry,
+    pub ring: ShapeGeometry,
 
     // Dynamic instance buffer (shared across all shapes)
     pub instance_buffer: wgpu::Buffer,
@@ -34,6 +35,7 @@ impl ShapeBuffers {
             rectangle: Self::create_geometry(ctx, "Rectangle", rectangle_geometry()),
             triangle: Self::create_geometry(ctx, "Triangle", triangle_geometry()),
             hexagon: Self::create_geometry(ctx, "Hexagon", hexagon_geometry()),
+            ring: Self::create_geometry(ctx, "Ring", ring_geometry()),
             instance_buffer: ctx.device.create_buffer(&wgpu::BufferDescriptor {
                 label: Some("Instance Buffer"),
                 size: (initial_capacity * std::mem::size_of::<ShapeInstance>()) as u64,
@@ -106,8 +108,10 @@ impl ShapeBuffers {
         rectangles: &[ShapeInstance],
         triangles: &[ShapeInstance],
         hexagons: &[ShapeInstance],
+        rings: &[ShapeInstance],
     ) -> BatchedInstances {
-        let total = circles.len() + rectangles.len() + triangles.len() + hexagons.len();
+        let total =
+            circles.len() + rectangles.len() + triangles.len() + hexagons.len() + rings.len();
 
         // Grow if needed
         self.ensure_capacity(ctx, total);
@@ -131,6 +135,10 @@ impl ShapeBuffers {
         all_instances.extend_from_slice(hexagons);
         let hexagon_end = all_instances.len() as u32;
 
+        let ring_start = hexagon_end;
+        all_instances.extend_from_slice(rings);
+        let ring_end = all_instances.len() as u32;
+
         // Single upload
         if !all_instances.is_empty() {
             ctx.queue.write_buffer(
@@ -145,6 +153,7 @@ impl ShapeBuffers {
             rectangle_range: rectangle_start..rectangle_end,
             triangle_range: triangle_start..triangle_end,
             hexagon_range: hexagon_start..hexagon_end,
+            ring_range: ring_start..ring_end,
         }
     }
 }
@@ -156,4 +165,5 @@ pub struct BatchedInstances {
     pub rectangle_range: std::ops::Range<u32>,
     pub triangle_range: std::ops::Range<u32>,
     pub hexagon_range: std::ops::Range<u32>,
+    pub ring_range: std::ops::Range<u32>,
 }
@@ -5,11 +5,17 @@ pub mod context;
 pub mod pipeline;
 pub mod sprite_buffers;
 pub mod sprite_pipeline;
+pub mod text_buffers;
+pub mod text_pipeline;
 pub mod texture;
+pub mod tile_buffers;
 
 pub use buffers::{BatchedInstances, ShapeBuffers, ShapeGeometry};
 pub use context::GpuContext;
 pub use pipeline::ShapePipeline;
 pub use sprite_buffers::SpriteBuffers;
 pub use sprite_pipeline::SpritePipeline;
+pub use text_buffers::TextBuffers;
+pub use text_pipeline::TextPipeline;
 pub use texture::{Texture, TextureError};
+pub use tile_buffers::{ChunkBuffer, TileBuffers};
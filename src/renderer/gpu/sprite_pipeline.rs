@@ -3,7 +3,7 @@
 use super::context::GpuContext;
 use super::texture::Texture;
 use crate::renderer::shapes::Vertex;
-use crate::renderer::sprites::SpriteInstance;
+use crate::renderer::sprites::{SpriteInstance, TextureAtlas};
 
 /// Sprite rendering pipeline with camera and texture bind groups.
 pub struct SpritePipeline {
@@ -154,6 +154,26 @@ impl SpritePipeline {
         device: &wgpu::Device,
         texture: &Texture,
         label: Option<&str>,
+    ) -> wgpu::BindGroup {
+        self.create_bind_group(device, &texture.view, &texture.sampler, label)
+    }
+
+    /// Create a texture bind group for a sprite atlas.
+    pub fn create_atlas_bind_group(
+        &self,
+        device: &wgpu::Device,
+        atlas: &TextureAtlas,
+        label: Option<&str>,
+    ) -> wgpu::BindGroup {
+        self.create_bind_group(device, &atlas.view, &atlas.sampler, label)
+    }
+
+    fn create_bind_group(
+        &self,
+        device: &wgpu::Device,
+        view: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+        label: Option<&str>,
     ) -> wgpu::BindGroup {
         device.create_bind_group(&wgpu::BindGroupDescriptor {
             label,
@@ -161,11 +181,11 @@ impl SpritePipeline {
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&texture.view),
+                    resource: wgpu::BindingResource::TextureView(view),
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&texture.sampler),
+                    resource: wgpu::BindingResource::Sampler(sampler),
                 },
             ],
         })
@@ -0,0 +1,79 @@
+//! Glyph instance buffer management.
+
+use wgpu::util::DeviceExt;
+
+use super::GpuContext;
+use crate::renderer::shapes::vertex::{unit_quad_indices, unit_quad_vertices};
+use crate::renderer::text::TextInstance;
+
+/// Buffers for text rendering: a unit quad and one instance per glyph.
+pub struct TextBuffers {
+    /// Unit quad vertex buffer
+    pub quad_vertex_buffer: wgpu::Buffer,
+    /// Unit quad index buffer
+    pub quad_index_buffer: wgpu::Buffer,
+    /// Number of indices in quad
+    pub quad_index_count: u32,
+    /// Dynamic glyph instance buffer
+    pub instance_buffer: wgpu::Buffer,
+    /// Current instance buffer capacity
+    pub instance_capacity: usize,
+}
+
+impl TextBuffers {
+    /// Create text buffers with initial glyph capacity.
+    pub fn new(ctx: &GpuContext, initial_capacity: usize) -> Self {
+        let quad_verts = unit_quad_vertices();
+        let quad_indices = unit_quad_indices();
+
+        let quad_vertex_buffer = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Text Quad Vertex Buffer"),
+                contents: bytemuck::cast_slice(&quad_verts),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
+        let quad_index_buffer = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Text Quad Index Buffer"),
+                contents: bytemuck::cast_slice(&quad_indices),
+                usage: wgpu::BufferUsages::INDEX,
+            });
+
+        Self {
+            quad_vertex_buffer,
+            quad_index_buffer,
+            quad_index_count: quad_indices.len() as u32,
+            instance_buffer: Self::create_instance_buffer(ctx, initial_capacity),
+            instance_capacity: initial_capacity,
+        }
+    }
+
+    fn create_instance_buffer(ctx: &GpuContext, capacity: usize) -> wgpu::Buffer {
+        ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Text Instance Buffer"),
+            size: (capacity * std::mem::size_of::<TextInstance>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Upload glyph instances, growing buffer if needed.
+    pub fn upload_instances(&mut self, ctx: &GpuContext, instances: &[TextInstance]) {
+        if instances.is_empty() {
+            return;
+        }
+
+        if instances.len() > self.instance_capacity {
+            let new_capacity = (instances.len() * 2).max(1024);
+            self.instance_buffer = Self::create_instance_buffer(ctx, new_capacity);
+            self.instance_capacity = new_capacity;
+            tracing::debug!("Grew text instance buffer to {} capacity", new_capacity);
+        }
+
+        ctx.queue
+            .write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(instances));
+    }
+}
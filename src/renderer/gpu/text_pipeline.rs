@@ -0,0 +1,187 @@
+//! Text rendering pipeline: glyph quads sampled from a glyph atlas.
+
+use super::context::GpuContext;
+use super::texture::{Texture, TextureError};
+use crate::renderer::shapes::Vertex;
+use crate::renderer::text::{GlyphAtlas, TextInstance};
+
+/// Text rendering pipeline, owning the glyph atlas texture it samples.
+pub struct TextPipeline {
+    pub render_pipeline: wgpu::RenderPipeline,
+    pub camera_buffer: wgpu::Buffer,
+    pub camera_bind_group: wgpu::BindGroup,
+    pub atlas_bind_group: wgpu::BindGroup,
+    #[allow(dead_code)] // Stored to keep texture alive for bind group
+    atlas_texture: Texture,
+    #[allow(dead_code)] // Stored to keep sampler alive for bind group
+    atlas_sampler: wgpu::Sampler,
+}
+
+impl TextPipeline {
+    /// Create a text pipeline and upload `atlas` to the GPU.
+    pub fn new(ctx: &GpuContext, atlas: &GlyphAtlas) -> Result<Self, TextureError> {
+        // Load shader
+        let shader = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Text Shader"),
+                source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/text.wgsl").into()),
+            });
+
+        // Camera uniform bind group layout (group 0)
+        let camera_bind_group_layout =
+            ctx.device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("Text Camera Bind Group Layout"),
+                    entries: &[wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    }],
+                });
+
+        // Glyph atlas bind group layout (group 1)
+        let atlas_bind_group_layout =
+            ctx.device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("Text Atlas Bind Group Layout"),
+                    entries: &[
+                        // texture2d
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        // sampler
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                            count: None,
+                        },
+                    ],
+                });
+
+        // Camera uniform buffer (mat4x4 = 64 bytes)
+        let camera_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Text Camera Buffer"),
+            size: 64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let camera_bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Text Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        // Glyph atlas texture and its bind group
+        let atlas_texture = Texture::from_rgba(
+            &ctx.device,
+            &ctx.queue,
+            &atlas.pixels,
+            (atlas.width, atlas.height),
+            Some("Glyph Atlas"),
+        )?;
+        // Glyphs are scaled to the label size, so filter rather than snap
+        let atlas_sampler = ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Glyph Atlas Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        let atlas_bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Text Atlas Bind Group"),
+            layout: &atlas_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&atlas_texture.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&atlas_sampler),
+                },
+            ],
+        });
+
+        // Pipeline layout
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Text Pipeline Layout"),
+                bind_group_layouts: &[&camera_bind_group_layout, &atlas_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+
+        // Render pipeline
+        let render_pipeline = ctx
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Text Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    buffers: &[Vertex::desc(), TextInstance::desc()],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: ctx.format(),
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None, // No culling for 2D glyphs
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            });
+
+        Ok(Self {
+            render_pipeline,
+            camera_buffer,
+            camera_bind_group,
+            atlas_bind_group,
+            atlas_texture,
+            atlas_sampler,
+        })
+    }
+
+    /// Update camera uniform buffer.
+    pub fn update_camera(&self, queue: &wgpu::Queue, view_proj: glam::Mat4) {
+        queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::cast_slice(&view_proj.to_cols_array()),
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Per-chunk instance buffers for the tile layer.
+
+use std::collections::BTreeMap;
+
+use super::GpuContext;
+use crate::renderer::shapes::ShapeInstance;
+use crate::renderer::tiles::{ChunkCoord, TileLayer};
+
+/// One chunk's hex instances on the GPU.
+pub struct ChunkBuffer {
+    pub instance_buffer: wgpu::Buffer,
+    pub instance_capacity: usize,
+    pub instance_count: u32,
+}
+
+/// GPU copies of the tile layer's chunks, kept between frames.
+#[derive(Default)]
+pub struct TileBuffers {
+    chunks: BTreeMap<ChunkCoord, ChunkBuffer>,
+}
+
+impl TileBuffers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn create_instance_buffer(ctx: &GpuContext, capacity: usize) -> wgpu::Buffer {
+        ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Tile Chunk Instance Buffer"),
+            size: (capacity * std::mem::size_of::<ShapeInstance>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Upload the chunks `layer` has marked dirty, dropping those it no
+    /// longer has; returns how many buffers were written.
+    pub fn upload_dirty(&mut self, ctx: &GpuContext, layer: &mut TileLayer) -> usize {
+        let mut uploads = 0;
+        for coord in layer.take_dirty() {
+            let Some(chunk) = layer.chunk(coord) else {
+                self.chunks.remove(&coord);
+                continue;
+            };
+            let needed = chunk.instances.len();
+            if needed == 0 {
+                self.chunks.remove(&coord);
+                continue;
+            }
+            let buffer = self.chunks.entry(coord).or_insert_with(|| ChunkBuffer {
+                instance_buffer: Self::create_instance_buffer(ctx, needed),
+                instance_capacity: needed,
+                instance_count: 0,
+            });
+            if needed > buffer.instance_capacity {
+                buffer.instance_buffer = Self::create_instance_buffer(ctx, needed);
+                buffer.instance_capacity = needed;
+            }
+            ctx.queue.write_buffer(
+                &buffer.instance_buffer,
+                0,
+                bytemuck::cast_slice(&chunk.instances),
+            );
+            buffer.instance_count = needed as u32;
+            uploads += 1;
+        }
+        uploads
+    }
+
+    /// Forget every chunk, e.g. when the battle ends.
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = &ChunkBuffer> {
+        self.chunks.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
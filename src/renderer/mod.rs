@@ -4,20 +4,34 @@
 //! live simulation data directly. This ensures thread safety and
 //! clean separation of concerns.
 
+pub mod battle_view;
 pub mod camera;
 pub mod gpu;
 pub mod hex;
 pub mod metrics;
+pub mod picking;
 pub mod shapes;
 pub mod sprites;
 pub mod state;
+pub mod text;
+pub mod tiles;
 
+use std::ops::Range;
+use std::path::Path;
 use std::sync::Arc;
 use winit::window::Window;
 
-use gpu::{GpuContext, ShapeBuffers, ShapePipeline, SpriteBuffers, SpritePipeline, Texture};
+use gpu::{
+    GpuContext, ShapeBuffers, ShapePipeline, SpriteBuffers, SpritePipeline, TextBuffers,
+    TextPipeline, Texture, TileBuffers,
+};
 use shapes::ShapeInstance;
-use sprites::SpriteInstance;
+use sprites::{AtlasDefinition, AtlasError, SpriteCatalog, SpriteInstance, TextureAtlas};
+use text::{layout_label, GlyphAtlas, TextInstance};
+use tiles::TileLayer;
+
+/// Pixel size glyphs are rasterized at; labels are scaled from it.
+const GLYPH_PIXEL_SIZE: f32 = 32.0;
 
 /// Main renderer struct.
 pub struct Renderer {
@@ -30,6 +44,10 @@ pub struct Renderer {
     rectangle_instances: Vec<ShapeInstance>,
     triangle_instances: Vec<ShapeInstance>,
     hexagon_instances: Vec<ShapeInstance>,
+    ring_instances: Vec<ShapeInstance>,
+
+    // Battle map tiles, drawn beneath everything
+    tile_buffers: TileBuffers,
 
     // Sprite rendering
     sprite_pipeline: SpritePipeline,
@@ -38,6 +56,16 @@ pub struct Renderer {
     #[allow(dead_code)] // Stored to keep texture alive for bind group
     default_texture: Texture,
     default_texture_bind_group: wgpu::BindGroup,
+    // Loaded atlases; `AtlasId(n)` is entry n - 1, `AtlasId::WHITE` the default
+    sprite_atlases: Vec<(TextureAtlas, wgpu::BindGroup)>,
+    // Sprite instances per atlas this frame, in draw order
+    sprite_runs: Vec<(AtlasId, Range<u32>)>,
+
+    // Text rendering
+    text_pipeline: TextPipeline,
+    text_buffers: TextBuffers,
+    glyph_atlas: GlyphAtlas,
+    text_instances: Vec<TextInstance>,
 
     // Performance tracking
     metrics: RenderMetrics,
@@ -77,6 +105,15 @@ impl Renderer {
             Some("Default Texture Bind Group"),
         );
 
+        // Text rendering setup
+        let glyph_atlas = GlyphAtlas::bundled(GLYPH_PIXEL_SIZE).unwrap_or_else(|e| {
+            tracing::warn!("No font for text rendering: {}", e);
+            GlyphAtlas::empty(GLYPH_PIXEL_SIZE)
+        });
+        let text_pipeline =
+            TextPipeline::new(&ctx, &glyph_atlas).expect("Failed to create glyph atlas texture");
+        let text_buffers = TextBuffers::new(&ctx, 1024);
+
         Self {
             ctx,
             pipeline,
@@ -85,11 +122,19 @@ impl Renderer {
             rectangle_instances: Vec::with_capacity(1000),
             triangle_instances: Vec::with_capacity(1000),
             hexagon_instances: Vec::with_capacity(1000),
+            ring_instances: Vec::with_capacity(16),
+            tile_buffers: TileBuffers::new(),
             sprite_pipeline,
             sprite_buffers,
             sprite_instances: Vec::with_capacity(1000),
             default_texture,
             default_texture_bind_group,
+            sprite_atlases: Vec::new(),
+            sprite_runs: Vec::new(),
+            text_pipeline,
+            text_buffers,
+            glyph_atlas,
+            text_instances: Vec::with_capacity(1024),
             metrics: RenderMetrics::new(),
         }
     }
@@ -114,22 +159,114 @@ impl Renderer {
         &self.metrics
     }
 
+    /// Make `atlas` available to sprites; they sample it by the returned id.
+    pub fn add_atlas(&mut self, atlas: TextureAtlas) -> AtlasId {
+        let bind_group = self.sprite_pipeline.create_atlas_bind_group(
+            &self.ctx.device,
+            &atlas,
+            Some("Sprite Atlas Bind Group"),
+        );
+        self.sprite_atlases.push((atlas, bind_group));
+        AtlasId(self.sprite_atlases.len() as u32)
+    }
+
+    /// Load the atlas a TOML definition describes and catalog its species
+    /// and building sprites.
+    pub fn load_atlas(&mut self, path: impl AsRef<Path>) -> Result<SpriteCatalog, AtlasError> {
+        let definition = AtlasDefinition::load(path)?;
+        let atlas = TextureAtlas::from_definition(&self.ctx.device, &self.ctx.queue, &definition)?;
+        let (width, height) = (atlas.width, atlas.height);
+        let id = self.add_atlas(atlas);
+        Ok(SpriteCatalog::new(id, width, height, &definition))
+    }
+
+    /// Order sprites by layer, then by atlas, and upload them; records the
+    /// run of instances drawn from each atlas.
+    fn prepare_sprites(&mut self, state: &RenderState) {
+        self.sprite_instances.clear();
+        self.sprite_runs.clear();
+        if state.sprites.is_empty() {
+            return;
+        }
+
+        let mut order: Vec<&SpriteEntity> = state.sprites.iter().collect();
+        order.sort_by_key(|sprite| (sprite.z_order, sprite.atlas));
+        for sprite in order {
+            let index = self.sprite_instances.len() as u32;
+            match self.sprite_runs.last_mut() {
+                Some((atlas, run)) if *atlas == sprite.atlas => run.end = index + 1,
+                _ => self.sprite_runs.push((sprite.atlas, index..index + 1)),
+            }
+            self.sprite_instances.push(SpriteInstance::new(
+                [sprite.position.x, sprite.position.y],
+                [sprite.uv_rect[0], sprite.uv_rect[1]],
+                [sprite.uv_rect[2], sprite.uv_rect[3]],
+                sprite.color,
+                sprite.rotation,
+                sprite.scale,
+                sprite.flip_x,
+                sprite.flip_y,
+            ));
+        }
+
+        self.sprite_buffers
+            .upload_instances(&self.ctx, &self.sprite_instances);
+        self.metrics.record_buffer_upload();
+    }
+
+    /// Upload the tile chunks `layer` has marked dirty since the last call.
+    /// Chunks already on the GPU stay there, so an unchanged map costs
+    /// nothing to draw again.
+    pub fn upload_tiles(&mut self, layer: &mut TileLayer) {
+        let uploads = self.tile_buffers.upload_dirty(&self.ctx, layer);
+        for _ in 0..uploads {
+            self.metrics.record_buffer_upload();
+        }
+    }
+
+    /// Stop drawing the tile layer.
+    pub fn clear_tiles(&mut self) {
+        self.tile_buffers.clear();
+    }
+
+    /// Lay out every label as glyph quads and upload them; returns how
+    /// many glyphs to draw.
+    fn prepare_text(&mut self, state: &RenderState) -> u32 {
+        self.text_instances.clear();
+        for label in &state.labels {
+            layout_label(
+                &self.glyph_atlas,
+                label,
+                state.camera.zoom,
+                &mut self.text_instances,
+            );
+        }
+        if !self.text_instances.is_empty() {
+            self.text_buffers
+                .upload_instances(&self.ctx, &self.text_instances);
+            self.metrics.record_buffer_upload();
+        }
+        self.text_instances.len() as u32
+    }
+
     /// Render a frame from the given state.
     pub fn render(&mut self, state: &RenderState) -> Result<(), wgpu::SurfaceError> {
         self.metrics.begin_frame();
         self.metrics.entity_count = state.entities.len();
 
-        // Update camera uniform for both pipelines
+        // Update camera uniform for every pipeline
         let view_proj = state.camera.view_projection_matrix();
         self.pipeline.update_camera(&self.ctx.queue, view_proj);
         self.sprite_pipeline
             .update_camera(&self.ctx.queue, view_proj);
+        self.text_pipeline.update_camera(&self.ctx.queue, view_proj);
 
         // Batch entities by shape type
         self.circle_instances.clear();
         self.rectangle_instances.clear();
         self.triangle_instances.clear();
         self.hexagon_instances.clear();
+        self.ring_instances.clear();
 
         for entity in &state.entities {
             let instance = ShapeInstance::new(
@@ -145,6 +282,7 @@ impl Renderer {
                 ShapeType::Rectangle => self.rectangle_instances.push(instance),
                 ShapeType::Triangle => self.triangle_instances.push(instance),
                 ShapeType::Hexagon => self.hexagon_instances.push(instance),
+                ShapeType::Ring => self.ring_instances.push(instance),
             }
         }
 
@@ -155,9 +293,13 @@ impl Renderer {
             &self.rectangle_instances,
             &self.triangle_instances,
             &self.hexagon_instances,
+            &self.ring_instances,
         );
         self.metrics.record_buffer_upload();
 
+        self.prepare_sprites(state);
+        let glyph_count = self.prepare_text(state);
+
         // Get surface texture
         let output = self.ctx.surface.get_current_texture()?;
         let view = output
@@ -195,6 +337,24 @@ impl Renderer {
             render_pass.set_pipeline(&self.pipeline.render_pipeline);
             render_pass.set_bind_group(0, &self.pipeline.camera_bind_group, &[]);
 
+            // Draw tiles first, one chunk at a time, beneath every unit
+            if !self.tile_buffers.is_empty() {
+                render_pass.set_vertex_buffer(0, self.buffers.hexagon.vertex_buffer.slice(..));
+                render_pass.set_index_buffer(
+                    self.buffers.hexagon.index_buffer.slice(..),
+                    wgpu::IndexFormat::Uint16,
+                );
+                for chunk in self.tile_buffers.chunks() {
+                    render_pass.set_vertex_buffer(1, chunk.instance_buffer.slice(..));
+                    render_pass.draw_indexed(
+                        0..self.buffers.hexagon.index_count,
+                        0,
+                        0..chunk.instance_count,
+                    );
+                    self.metrics.record_draw_call();
+                }
+            }
+
             // All shapes share the same instance buffer, use ranges from batched upload
             render_pass.set_vertex_buffer(1, self.buffers.instance_buffer.slice(..));
 
@@ -258,40 +418,63 @@ impl Renderer {
                 self.metrics.record_draw_call();
             }
 
-            // Render sprites
-            if !state.sprites.is_empty() {
-                self.sprite_instances.clear();
-                for sprite in &state.sprites {
-                    self.sprite_instances.push(SpriteInstance::new(
-                        [sprite.position.x, sprite.position.y],
-                        [sprite.uv_rect[0], sprite.uv_rect[1]],
-                        [sprite.uv_rect[2], sprite.uv_rect[3]],
-                        sprite.color,
-                        sprite.rotation,
-                        sprite.scale,
-                        sprite.flip_x,
-                        sprite.flip_y,
-                    ));
-                }
-
-                self.sprite_buffers
-                    .upload_instances(&self.ctx, &self.sprite_instances);
-                self.metrics.record_buffer_upload();
+            // Draw rings last, so outlines sit over the shapes they surround
+            if !batched.ring_range.is_empty() {
+                render_pass.set_vertex_buffer(0, self.buffers.ring.vertex_buffer.slice(..));
+                render_pass.set_index_buffer(
+                    self.buffers.ring.index_buffer.slice(..),
+                    wgpu::IndexFormat::Uint16,
+                );
+                render_pass.draw_indexed(
+                    0..self.buffers.ring.index_count,
+                    0,
+                    batched.ring_range.clone(),
+                );
+                self.metrics.record_draw_call();
+            }
 
+            // Render sprites, one draw per run of instances sharing an atlas
+            if !self.sprite_runs.is_empty() {
                 render_pass.set_pipeline(&self.sprite_pipeline.render_pipeline);
                 render_pass.set_bind_group(0, &self.sprite_pipeline.camera_bind_group, &[]);
-                render_pass.set_bind_group(1, &self.default_texture_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, self.sprite_buffers.quad_vertex_buffer.slice(..));
                 render_pass.set_vertex_buffer(1, self.sprite_buffers.instance_buffer.slice(..));
                 render_pass.set_index_buffer(
                     self.sprite_buffers.quad_index_buffer.slice(..),
                     wgpu::IndexFormat::Uint16,
                 );
-                render_pass.draw_indexed(
-                    0..self.sprite_buffers.quad_index_count,
-                    0,
-                    0..self.sprite_instances.len() as u32,
+                for (atlas, run) in &self.sprite_runs {
+                    // Unknown atlases fall back to plain tinted quads
+                    let bind_group = match atlas.0.checked_sub(1) {
+                        Some(i) => self
+                            .sprite_atlases
+                            .get(i as usize)
+                            .map(|(_, bind_group)| bind_group)
+                            .unwrap_or(&self.default_texture_bind_group),
+                        None => &self.default_texture_bind_group,
+                    };
+                    render_pass.set_bind_group(1, bind_group, &[]);
+                    render_pass.draw_indexed(
+                        0..self.sprite_buffers.quad_index_count,
+                        0,
+                        run.clone(),
+                    );
+                    self.metrics.record_draw_call();
+                }
+            }
+
+            // Draw text last, over every shape and sprite
+            if glyph_count > 0 {
+                render_pass.set_pipeline(&self.text_pipeline.render_pipeline);
+                render_pass.set_bind_group(0, &self.text_pipeline.camera_bind_group, &[]);
+                render_pass.set_bind_group(1, &self.text_pipeline.atlas_bind_group, &[]);
+                render_pass.set_vertex_buffer(0, self.text_buffers.quad_vertex_buffer.slice(..));
+                render_pass.set_vertex_buffer(1, self.text_buffers.instance_buffer.slice(..));
+                render_pass.set_index_buffer(
+                    self.text_buffers.quad_index_buffer.slice(..),
+                    wgpu::IndexFormat::Uint16,
                 );
+                render_pass.draw_indexed(0..self.text_buffers.quad_index_count, 0, 0..glyph_count);
                 self.metrics.record_draw_call();
             }
         }
@@ -319,17 +502,19 @@ impl Renderer {
         self.metrics.begin_frame();
         self.metrics.entity_count = state.entities.len();
 
-        // Update camera uniform for both pipelines
+        // Update camera uniform for every pipeline
         let view_proj = state.camera.view_projection_matrix();
         self.pipeline.update_camera(&self.ctx.queue, view_proj);
         self.sprite_pipeline
             .update_camera(&self.ctx.queue, view_proj);
+        self.text_pipeline.update_camera(&self.ctx.queue, view_proj);
 
         // Batch entities by shape type
         self.circle_instances.clear();
         self.rectangle_instances.clear();
         self.triangle_instances.clear();
         self.hexagon_instances.clear();
+        self.ring_instances.clear();
 
         for entity in &state.entities {
             let instance = ShapeInstance::new(
@@ -345,6 +530,7 @@ impl Renderer {
                 ShapeType::Rectangle => self.rectangle_instances.push(instance),
                 ShapeType::Triangle => self.triangle_instances.push(instance),
                 ShapeType::Hexagon => self.hexagon_instances.push(instance),
+                ShapeType::Ring => self.ring_instances.push(instance),
             }
         }
 
@@ -355,9 +541,13 @@ impl Renderer {
             &self.rectangle_instances,
             &self.triangle_instances,
             &self.hexagon_instances,
+            &self.ring_instances,
         );
         self.metrics.record_buffer_upload();
 
+        self.prepare_sprites(state);
+        let glyph_count = self.prepare_text(state);
+
         // Get surface texture
         let output = self.ctx.surface.get_current_texture()?;
         let view = output
@@ -395,6 +585,24 @@ impl Renderer {
             render_pass.set_pipeline(&self.pipeline.render_pipeline);
             render_pass.set_bind_group(0, &self.pipeline.camera_bind_group, &[]);
 
+            // Draw tiles first, one chunk at a time, beneath every unit
+            if !self.tile_buffers.is_empty() {
+                render_pass.set_vertex_buffer(0, self.buffers.hexagon.vertex_buffer.slice(..));
+                render_pass.set_index_buffer(
+                    self.buffers.hexagon.index_buffer.slice(..),
+                    wgpu::IndexFormat::Uint16,
+                );
+                for chunk in self.tile_buffers.chunks() {
+                    render_pass.set_vertex_buffer(1, chunk.instance_buffer.slice(..));
+                    render_pass.draw_indexed(
+                        0..self.buffers.hexagon.index_count,
+                        0,
+                        0..chunk.instance_count,
+                    );
+                    self.metrics.record_draw_call();
+                }
+            }
+
             // All shapes share the same instance buffer, use ranges from batched upload
             render_pass.set_vertex_buffer(1, self.buffers.instance_buffer.slice(..));
 
@@ -458,40 +666,63 @@ impl Renderer {
                 self.metrics.record_draw_call();
             }
 
-            // Render sprites
-            if !state.sprites.is_empty() {
-                self.sprite_instances.clear();
-                for sprite in &state.sprites {
-                    self.sprite_instances.push(SpriteInstance::new(
-                        [sprite.position.x, sprite.position.y],
-                        [sprite.uv_rect[0], sprite.uv_rect[1]],
-                        [sprite.uv_rect[2], sprite.uv_rect[3]],
-                        sprite.color,
-                        sprite.rotation,
-                        sprite.scale,
-                        sprite.flip_x,
-                        sprite.flip_y,
-                    ));
-                }
-
-                self.sprite_buffers
-                    .upload_instances(&self.ctx, &self.sprite_instances);
-                self.metrics.record_buffer_upload();
+            // Draw rings last, so outlines sit over the shapes they surround
+            if !batched.ring_range.is_empty() {
+                render_pass.set_vertex_buffer(0, self.buffers.ring.vertex_buffer.slice(..));
+                render_pass.set_index_buffer(
+                    self.buffers.ring.index_buffer.slice(..),
+                    wgpu::IndexFormat::Uint16,
+                );
+                render_pass.draw_indexed(
+                    0..self.buffers.ring.index_count,
+                    0,
+                    batched.ring_range.clone(),
+                );
+                self.metrics.record_draw_call();
+            }
 
+            // Render sprites, one draw per run of instances sharing an atlas
+            if !self.sprite_runs.is_empty() {
                 render_pass.set_pipeline(&self.sprite_pipeline.render_pipeline);
                 render_pass.set_bind_group(0, &self.sprite_pipeline.camera_bind_group, &[]);
-                render_pass.set_bind_group(1, &self.default_texture_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, self.sprite_buffers.quad_vertex_buffer.slice(..));
                 render_pass.set_vertex_buffer(1, self.sprite_buffers.instance_buffer.slice(..));
                 render_pass.set_index_buffer(
                     self.sprite_buffers.quad_index_buffer.slice(..),
                     wgpu::IndexFormat::Uint16,
                 );
-                render_pass.draw_indexed(
-                    0..self.sprite_buffers.quad_index_count,
-                    0,
-                    0..self.sprite_instances.len() as u32,
+                for (atlas, run) in &self.sprite_runs {
+                    // Unknown atlases fall back to plain tinted quads
+                    let bind_group = match atlas.0.checked_sub(1) {
+                        Some(i) => self
+                            .sprite_atlases
+                            .get(i as usize)
+                            .map(|(_, bind_group)| bind_group)
+                            .unwrap_or(&self.default_texture_bind_group),
+                        None => &self.default_texture_bind_group,
+                    };
+                    render_pass.set_bind_group(1, bind_group, &[]);
+                    render_pass.draw_indexed(
+                        0..self.sprite_buffers.quad_index_count,
+                        0,
+                        run.clone(),
+                    );
+                    self.metrics.record_draw_call();
+                }
+            }
+
+            // Draw text last, over every shape and sprite
+            if glyph_count > 0 {
+                render_pass.set_pipeline(&self.text_pipeline.render_pipeline);
+                render_pass.set_bind_group(0, &self.text_pipeline.camera_bind_group, &[]);
+                render_pass.set_bind_group(1, &self.text_pipeline.atlas_bind_group, &[]);
+                render_pass.set_vertex_buffer(0, self.text_buffers.quad_vertex_buffer.slice(..));
+                render_pass.set_vertex_buffer(1, self.text_buffers.instance_buffer.slice(..));
+                render_pass.set_index_buffer(
+                    self.text_buffers.quad_index_buffer.slice(..),
+                    wgpu::IndexFormat::Uint16,
                 );
+                render_pass.draw_indexed(0..self.text_buffers.quad_index_count, 0, 0..glyph_count);
                 self.metrics.record_draw_call();
             }
         }
@@ -509,6 +740,12 @@ impl Renderer {
 }
 
 // Re-export commonly used types
+pub use battle_view::{BattleView, Side};
+pub use camera::CameraController;
 pub use hex::{world_to_hex, HexCoord, HEX_SIZE};
 pub use metrics::RenderMetrics;
-pub use state::{CameraState, Color, RenderEntity, RenderState, ShapeType, SpriteEntity};
+pub use picking::{PICK_SLOP_PIXELS, SELECTION_RING_MARGIN};
+pub use state::{
+    AtlasId, CameraState, Color, RenderEntity, RenderState, Selection, ShapeType, SpriteEntity,
+    TextLabel,
+};
@@ -0,0 +1,126 @@
+//! Picking - which drawn entity or building is under the cursor.
+//!
+//! Picking runs against the same `RenderState` the frame draws, so what can
+//! be clicked is exactly what is on screen. Shapes on the ground layer
+//! (`z_order` 0 or below: food zones, terrain) are never picked. Whatever is
+//! selected is outlined by a ring added to the frame.
+
+use super::state::{Color, RenderEntity, RenderState, Selection, ShapeType};
+use glam::Vec2;
+
+/// Screen pixels of slack around a shape that still count as a hit
+pub const PICK_SLOP_PIXELS: f32 = 4.0;
+
+/// World units between a selected shape's edge and its highlight ring
+pub const SELECTION_RING_MARGIN: f32 = 2.0;
+
+impl RenderState {
+    /// The entity or building drawn under a screen position, if any.
+    ///
+    /// Among overlapping hits the highest layer wins, then the shape whose
+    /// centre is nearest the cursor.
+    pub fn pick(&self, screen_pos: Vec2) -> Option<Selection> {
+        let cursor = self.camera.screen_to_world(screen_pos);
+        let slop = PICK_SLOP_PIXELS * self.camera.zoom;
+        self.entities
+            .iter()
+            .filter(|e| e.z_order > 0)
+            .filter_map(|e| {
+                let distance = e.position.distance(cursor);
+                (distance <= e.scale * e.shape.radius() + slop).then_some((e, distance))
+            })
+            .min_by(|(a, da), (b, db)| b.z_order.cmp(&a.z_order).then(da.total_cmp(db)))
+            .map(|(e, _)| e.id)
+    }
+
+    /// Outline the selected shape with a ring above everything else.
+    /// Returns false if the selection is not drawn this frame.
+    pub fn add_selection_ring(&mut self, selected: Selection, color: Color) -> bool {
+        let Some(shape) = self.entities.iter().find(|e| e.id == selected) else {
+            return false;
+        };
+        let top = self.entities.iter().map(|e| e.z_order).max().unwrap_or(0);
+        let ring = RenderEntity {
+            id: selected,
+            position: shape.position,
+            facing: 0.0,
+            shape: ShapeType::Ring,
+            color,
+            scale: shape.scale * shape.shape.radius() + SELECTION_RING_MARGIN,
+            z_order: top + 1,
+        };
+        self.entities.push(ring);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::building::BuildingId;
+    use crate::core::types::EntityId;
+    use crate::renderer::state::CameraState;
+
+    fn shape(id: Selection, position: Vec2, scale: f32, z_order: i32) -> RenderEntity {
+        RenderEntity {
+            id,
+            position,
+            facing: 0.0,
+            shape: match id {
+                Selection::Entity(_) => ShapeType::Circle,
+                Selection::Building(_) => ShapeType::Rectangle,
+            },
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+            scale,
+            z_order,
+        }
+    }
+
+    fn state(entities: Vec<RenderEntity>) -> RenderState {
+        RenderState {
+            tick: 0,
+            entities,
+            sprites: vec![],
+            labels: vec![],
+            camera: CameraState {
+                center: Vec2::ZERO,
+                zoom: 1.0,
+                viewport_size: Vec2::new(800.0, 600.0),
+            },
+        }
+    }
+
+    #[test]
+    fn test_pick_prefers_top_layer_then_nearest() {
+        let zone = shape(EntityId::new().into(), Vec2::new(0.0, 0.0), 20.0, 0);
+        let near = shape(EntityId::new().into(), Vec2::new(1.0, 0.0), 3.0, 1);
+        let far = shape(EntityId::new().into(), Vec2::new(5.0, 0.0), 3.0, 1);
+        let workshop = shape(BuildingId::new().into(), Vec2::new(-30.0, 0.0), 12.0, 1);
+        let state = state(vec![zone, far, near, workshop]);
+
+        // Screen centre is world origin
+        assert_eq!(state.pick(Vec2::new(400.0, 300.0)), Some(near.id));
+        assert_eq!(state.pick(Vec2::new(406.0, 300.0)), Some(far.id));
+        assert_eq!(state.pick(Vec2::new(370.0, 300.0)), Some(workshop.id));
+        // Inside the zone but clear of everyone: nothing is picked
+        assert_eq!(state.pick(Vec2::new(385.0, 300.0)), None);
+    }
+
+    #[test]
+    fn test_selection_ring_surrounds_the_selected_shape() {
+        let marcus = shape(EntityId::new().into(), Vec2::new(10.0, 5.0), 3.0, 1);
+        let mut state = state(vec![marcus]);
+        let yellow = Color::rgba(1.0, 1.0, 0.0, 1.0);
+
+        assert!(state.add_selection_ring(marcus.id, yellow));
+        let ring = state.entities[1];
+        assert_eq!(ring.shape, ShapeType::Ring);
+        assert_eq!(ring.position, marcus.position);
+        assert_eq!(ring.scale, 3.0 + SELECTION_RING_MARGIN);
+        assert!(ring.z_order > marcus.z_order);
+
+        // Someone off screen gets no ring
+        assert!(!state.add_selection_ring(EntityId::new().into(), yellow));
+        assert_eq!(state.entities.len(), 2);
+    }
+}
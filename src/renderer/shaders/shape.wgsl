@@ -1,5 +1,5 @@
 // Shape rendering shader
-// Supports instanced rendering of circles, rectangles, triangles, and hexagons.
+// Supports instanced rendering of circles, rectangles, triangles, hexagons, and rings.
 
 struct CameraUniform {
     view_proj: mat4x4<f32>,
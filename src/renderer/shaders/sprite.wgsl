@@ -54,19 +54,9 @@ fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
     let s = sin(rotation);
     let rot = mat2x2<f32>(c, -s, s, c);
 
-    // Transform vertex position
-    var local_pos = vertex.position;
-
-    // Apply flip before rotation
-    if flip_x {
-        local_pos.x = -local_pos.x;
-    }
-    if flip_y {
-        local_pos.y = -local_pos.y;
-    }
-
-    // Apply rotation and scale
-    local_pos = rot * local_pos * scale;
+    // Apply rotation and scale (the quad is symmetric, so flips only
+    // change which texels it samples, below)
+    let local_pos = rot * vertex.position * scale;
 
     // Transform to world space
     let world = vec4<f32>(local_pos + instance.world_position, 0.0, 1.0);
@@ -75,8 +65,9 @@ fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
     output.clip_position = camera.view_proj * world;
 
     // Calculate UV coordinates
-    // Vertex position is in range [-0.5, 0.5], map to [0, 1]
-    var uv = vertex.position + vec2<f32>(0.5, 0.5);
+    // Vertex position is in range [-0.5, 0.5], map to [0, 1]; image rows
+    // run down while world y runs up
+    var uv = vec2<f32>(vertex.position.x + 0.5, 0.5 - vertex.position.y);
 
     // Apply flip to UV
     if flip_x {
@@ -0,0 +1,77 @@
+// Text shader: glyph quads sampled from the glyph atlas and tinted.
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var glyph_texture: texture_2d<f32>;
+
+@group(1) @binding(1)
+var glyph_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,  // Unit quad vertex
+}
+
+struct InstanceInput {
+    @location(1) world_position: vec2<f32>, // Quad centre
+    @location(2) size: vec2<f32>,           // Quad width and height
+    @location(3) uv_offset: u32,            // Packed 16-bit u, v offset
+    @location(4) uv_size: u32,              // Packed 16-bit u, v size
+    @location(5) color: u32,                // RGBA8 packed
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    // Unpack UV offset and size (normalized 0-65535 to 0-1)
+    let uv_off = vec2<f32>(
+        f32(instance.uv_offset & 0xFFFFu),
+        f32((instance.uv_offset >> 16u) & 0xFFFFu),
+    ) / 65535.0;
+    let uv_size = vec2<f32>(
+        f32(instance.uv_size & 0xFFFFu),
+        f32((instance.uv_size >> 16u) & 0xFFFFu),
+    ) / 65535.0;
+
+    let world = vec4<f32>(vertex.position * instance.size + instance.world_position, 0.0, 1.0);
+
+    var output: VertexOutput;
+    output.clip_position = camera.view_proj * world;
+
+    // Vertex position is in range [-0.5, 0.5]; the atlas runs top-down
+    // while world y runs up, so flip v
+    let uv = vec2<f32>(vertex.position.x + 0.5, 0.5 - vertex.position.y);
+    output.uv = uv_off + uv * uv_size;
+
+    // Unpack color
+    let r = f32((instance.color >> 24u) & 0xFFu) / 255.0;
+    let g = f32((instance.color >> 16u) & 0xFFu) / 255.0;
+    let b = f32((instance.color >> 8u) & 0xFFu) / 255.0;
+    let a = f32(instance.color & 0xFFu) / 255.0;
+    output.color = vec4<f32>(r, g, b, a);
+
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    // Glyphs are white; coverage is in alpha
+    let coverage = textureSample(glyph_texture, glyph_sampler, input.uv).a;
+    let final_color = vec4<f32>(input.color.rgb, input.color.a * coverage);
+
+    if final_color.a < 0.01 {
+        discard;
+    }
+
+    return final_color;
+}
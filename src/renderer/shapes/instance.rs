@@ -15,7 +15,7 @@ pub struct ShapeInstance {
     pub scale: f32, // 4 bytes
     /// Packed RGBA color (see Color::to_u32).
     pub color: u32, // 4 bytes
-    /// Shape type (0=Circle, 1=Rectangle, 2=Triangle, 3=Hexagon).
+    /// Shape type (0=Circle, 1=Rectangle, 2=Triangle, 3=Hexagon, 4=Ring).
     pub shape_type: u32, // 4 bytes
 }
 
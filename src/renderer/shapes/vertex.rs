@@ -120,6 +120,39 @@ pub fn hexagon_geometry() -> (Vec<Vertex>, Vec<u16>) {
     (vertices, indices)
 }
 
+/// Inner radius of the unit ring, as a fraction of its outer radius.
+pub const RING_INNER_RADIUS: f32 = 0.8;
+
+/// Generate unit ring (annulus) as triangle list (32 segments).
+/// Used to outline a shape, such as the selection highlight.
+pub fn ring_geometry() -> (Vec<Vertex>, Vec<u16>) {
+    const SEGMENTS: usize = 32;
+    let mut vertices = Vec::with_capacity(SEGMENTS * 2);
+    let mut indices = Vec::with_capacity(SEGMENTS * 6);
+
+    // Alternating outer and inner vertices around the ring
+    for i in 0..SEGMENTS {
+        let angle = (i as f32 / SEGMENTS as f32) * std::f32::consts::TAU;
+        let (sin, cos) = angle.sin_cos();
+        vertices.push(Vertex::new(cos, sin));
+        vertices.push(Vertex::new(
+            cos * RING_INNER_RADIUS,
+            sin * RING_INNER_RADIUS,
+        ));
+    }
+
+    // Two triangles per segment, joining it to the next
+    for i in 0..SEGMENTS {
+        let outer = (i * 2) as u16;
+        let inner = outer + 1;
+        let next_outer = (((i + 1) % SEGMENTS) * 2) as u16;
+        let next_inner = next_outer + 1;
+        indices.extend_from_slice(&[outer, next_outer, inner, inner, next_outer, next_inner]);
+    }
+
+    (vertices, indices)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +200,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ring_geometry() {
+        let (verts, indices) = ring_geometry();
+        assert_eq!(verts.len(), 64); // 32 outer + 32 inner
+        assert_eq!(indices.len(), 192); // 64 triangles * 3
+
+        // Outer vertices at distance 1, inner at the inner radius
+        for (i, v) in verts.iter().enumerate() {
+            let dist = (v.position[0].powi(2) + v.position[1].powi(2)).sqrt();
+            let expected = if i % 2 == 0 { 1.0 } else { RING_INNER_RADIUS };
+            assert!((dist - expected).abs() < 0.001);
+        }
+        assert!(indices.iter().all(|&i| (i as usize) < verts.len()));
+    }
+
     #[test]
     fn test_unit_quad() {
         let verts = unit_quad_vertices();
@@ -1,5 +1,9 @@
 //! Animation state machine for sprites.
 
+use serde::Deserialize;
+
+use crate::actions::catalog::{ActionCategory, ActionId};
+
 /// Animation states for entities.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum AnimationState {
@@ -10,6 +14,45 @@ pub enum AnimationState {
     Hit,
     Die,
     Rout,
+    Work,
+    Sleep,
+}
+
+impl AnimationState {
+    /// Every state, in sprite sheet order.
+    pub const ALL: [AnimationState; 8] = [
+        AnimationState::Idle,
+        AnimationState::Move,
+        AnimationState::Attack,
+        AnimationState::Hit,
+        AnimationState::Die,
+        AnimationState::Rout,
+        AnimationState::Work,
+        AnimationState::Sleep,
+    ];
+
+    /// The state an entity doing `action` is drawn in. `moving` is whether
+    /// it changed position since the last frame: someone walking to a job
+    /// site walks, and only works once there.
+    pub fn for_action(action: Option<ActionId>, moving: bool) -> Self {
+        let Some(action) = action else {
+            return if moving {
+                AnimationState::Move
+            } else {
+                AnimationState::Idle
+            };
+        };
+        match action {
+            ActionId::Flee => AnimationState::Rout,
+            ActionId::Attack | ActionId::Charge if !moving => AnimationState::Attack,
+            ActionId::Rest if !moving => AnimationState::Sleep,
+            _ if moving => AnimationState::Move,
+            _ => match action.category() {
+                ActionCategory::Work | ActionCategory::Items => AnimationState::Work,
+                _ => AnimationState::Idle,
+            },
+        }
+    }
 }
 
 /// Controls animation playback for a single entity.
@@ -89,6 +132,19 @@ impl AnimationController {
         }
     }
 
+    /// Play `state`, letting a one-shot animation (a swing, a flinch) finish
+    /// first. A finished swing asked for again starts over.
+    pub fn drive(&mut self, state: AnimationState, animation_data: &AnimationData) {
+        if animation_data.is_looping(self.current_state) || self.finished {
+            if self.current_state == state && self.finished {
+                self.current_frame = 0;
+                self.frame_timer = 0.0;
+                self.finished = false;
+            }
+            self.set_state(state);
+        }
+    }
+
     /// Set facing direction from an angle in radians.
     /// 0 = East, counter-clockwise: 1=NE, 2=N, 3=NW, 4=W, 5=SW, 6=S, 7=SE.
     pub fn set_direction_from_angle(&mut self, angle: f32) {
@@ -100,7 +156,11 @@ impl AnimationController {
     /// Get the frame index in the sprite atlas.
     pub fn atlas_frame(&self, animation_data: &AnimationData) -> u32 {
         let base = animation_data.base_frame(self.current_state);
-        let direction_offset = self.direction as u32 * animation_data.frames_per_direction() as u32;
+        let direction_offset = if animation_data.directions > 1 {
+            self.direction as u32 * animation_data.frames_per_direction() as u32
+        } else {
+            0
+        };
         base + direction_offset + self.current_frame as u32
     }
 
@@ -112,7 +172,11 @@ impl AnimationController {
 }
 
 /// Animation data for a sprite type (e.g., "human_soldier").
-#[derive(Clone, Debug)]
+///
+/// Frames are laid out state by state in `AnimationState::ALL` order,
+/// `atlas_columns` to a row, starting at `origin` in the atlas.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
 pub struct AnimationData {
     /// Frames per animation state.
     pub idle_frames: u8,
@@ -121,6 +185,8 @@ pub struct AnimationData {
     pub hit_frames: u8,
     pub die_frames: u8,
     pub rout_frames: u8,
+    pub work_frames: u8,
+    pub sleep_frames: u8,
 
     /// Number of directions (typically 1, 4, or 8).
     pub directions: u8,
@@ -135,6 +201,9 @@ pub struct AnimationData {
     pub sprite_height: u32,
     pub atlas_width: u32,
     pub atlas_height: u32,
+    /// Top-left of the first frame, in pixels.
+    pub origin_x: u32,
+    pub origin_y: u32,
 }
 
 impl Default for AnimationData {
@@ -146,6 +215,8 @@ impl Default for AnimationData {
             hit_frames: 2,
             die_frames: 4,
             rout_frames: 4,
+            work_frames: 4,
+            sleep_frames: 2,
             directions: 8,
             frame_duration: 0.1,
             atlas_columns: 8,
@@ -154,6 +225,8 @@ impl Default for AnimationData {
             sprite_height: 32,
             atlas_width: 256,
             atlas_height: 256,
+            origin_x: 0,
+            origin_y: 0,
         }
     }
 }
@@ -168,6 +241,8 @@ impl AnimationData {
             AnimationState::Hit => self.hit_frames,
             AnimationState::Die => self.die_frames,
             AnimationState::Rout => self.rout_frames,
+            AnimationState::Work => self.work_frames,
+            AnimationState::Sleep => self.sleep_frames,
         }
     }
 
@@ -179,49 +254,23 @@ impl AnimationData {
     /// Whether the animation loops.
     pub fn is_looping(&self, state: AnimationState) -> bool {
         match state {
-            AnimationState::Idle | AnimationState::Move | AnimationState::Rout => true,
+            AnimationState::Idle
+            | AnimationState::Move
+            | AnimationState::Rout
+            | AnimationState::Work
+            | AnimationState::Sleep => true,
             AnimationState::Attack | AnimationState::Hit | AnimationState::Die => false,
         }
     }
 
     /// Get base frame index for a state.
     pub fn base_frame(&self, state: AnimationState) -> u32 {
-        let mut offset = 0u32;
         let frames_per_dir = self.frames_per_direction() as u32;
-
-        match state {
-            AnimationState::Idle => offset,
-            AnimationState::Move => {
-                offset += self.idle_frames as u32 * frames_per_dir;
-                offset
-            }
-            AnimationState::Attack => {
-                offset += self.idle_frames as u32 * frames_per_dir;
-                offset += self.move_frames as u32 * frames_per_dir;
-                offset
-            }
-            AnimationState::Hit => {
-                offset += self.idle_frames as u32 * frames_per_dir;
-                offset += self.move_frames as u32 * frames_per_dir;
-                offset += self.attack_frames as u32 * frames_per_dir;
-                offset
-            }
-            AnimationState::Die => {
-                offset += self.idle_frames as u32 * frames_per_dir;
-                offset += self.move_frames as u32 * frames_per_dir;
-                offset += self.attack_frames as u32 * frames_per_dir;
-                offset += self.hit_frames as u32 * frames_per_dir;
-                offset
-            }
-            AnimationState::Rout => {
-                offset += self.idle_frames as u32 * frames_per_dir;
-                offset += self.move_frames as u32 * frames_per_dir;
-                offset += self.attack_frames as u32 * frames_per_dir;
-                offset += self.hit_frames as u32 * frames_per_dir;
-                offset += self.die_frames as u32 * frames_per_dir;
-                offset
-            }
-        }
+        AnimationState::ALL
+            .iter()
+            .take_while(|&&s| s != state)
+            .map(|&s| self.frame_count(s) as u32 * frames_per_dir)
+            .sum()
     }
 
     /// Frames per direction (1 if no directional sprites).
@@ -238,10 +287,13 @@ impl AnimationData {
         let col = frame % self.atlas_columns;
         let row = frame / self.atlas_columns;
 
-        let u0 = (col * self.sprite_width) as f32 / self.atlas_width as f32;
-        let v0 = (row * self.sprite_height) as f32 / self.atlas_height as f32;
-        let u1 = ((col + 1) * self.sprite_width) as f32 / self.atlas_width as f32;
-        let v1 = ((row + 1) * self.sprite_height) as f32 / self.atlas_height as f32;
+        let x = self.origin_x + col * self.sprite_width;
+        let y = self.origin_y + row * self.sprite_height;
+
+        let u0 = x as f32 / self.atlas_width as f32;
+        let v0 = y as f32 / self.atlas_height as f32;
+        let u1 = (x + self.sprite_width) as f32 / self.atlas_width as f32;
+        let v1 = (y + self.sprite_height) as f32 / self.atlas_height as f32;
 
         ([u0, v0], [u1 - u0, v1 - v0])
     }
@@ -352,4 +404,48 @@ mod tests {
         assert_eq!(offset, [0.25, 0.25]); // col 1, row 1
         assert_eq!(size, [0.25, 0.25]);
     }
+
+    #[test]
+    fn test_actions_map_to_walk_work_fight_sleep() {
+        use AnimationState as S;
+        assert_eq!(S::for_action(None, false), S::Idle);
+        assert_eq!(S::for_action(None, true), S::Move);
+        assert_eq!(S::for_action(Some(ActionId::Build), false), S::Work);
+        // Walking to the job site
+        assert_eq!(S::for_action(Some(ActionId::Build), true), S::Move);
+        assert_eq!(S::for_action(Some(ActionId::Attack), false), S::Attack);
+        assert_eq!(S::for_action(Some(ActionId::Charge), true), S::Move);
+        assert_eq!(S::for_action(Some(ActionId::Rest), false), S::Sleep);
+        assert_eq!(S::for_action(Some(ActionId::Flee), false), S::Rout);
+        assert_eq!(S::for_action(Some(ActionId::TalkTo), false), S::Idle);
+    }
+
+    #[test]
+    fn test_drive_lets_a_swing_finish_then_swings_again() {
+        let data = AnimationData {
+            attack_frames: 2,
+            directions: 1,
+            ..Default::default()
+        };
+        let mut controller = AnimationController::new();
+        controller.drive(AnimationState::Attack, &data);
+        controller.update(0.1, &data);
+
+        // Mid-swing, a step away does not cut it short
+        controller.drive(AnimationState::Move, &data);
+        assert_eq!(controller.current_state, AnimationState::Attack);
+        controller.update(0.1, &data);
+        assert!(controller.finished);
+
+        controller.drive(AnimationState::Attack, &data);
+        assert_eq!(controller.current_frame, 0);
+        assert!(!controller.finished);
+
+        // Work and sleep sit after rout in the sheet
+        assert_eq!(data.base_frame(AnimationState::Work), 4 + 6 + 2 + 2 + 4 + 4);
+        assert_eq!(
+            data.base_frame(AnimationState::Sleep),
+            data.base_frame(AnimationState::Work) + 4
+        );
+    }
 }
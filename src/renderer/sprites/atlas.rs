@@ -1,9 +1,36 @@
 //! Texture atlas for sprite rendering.
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::animation::{AnimationData, AnimationState};
+use crate::city::building::BuildingType;
+use crate::core::types::Species;
+
+/// Errors loading a texture atlas.
+#[derive(Error, Debug)]
+pub enum AtlasError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Atlas definition error: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+    #[error("Unknown sprite region: {0}")]
+    UnknownRegion(String),
+    #[error("Sprite region {name} lies outside the {width}x{height} atlas")]
+    RegionOutOfBounds {
+        name: String,
+        width: u32,
+        height: u32,
+    },
+}
 
 /// A region within a texture atlas.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
 pub struct SpriteRegion {
     /// X position in pixels.
     pub x: u32,
@@ -36,6 +63,94 @@ impl SpriteRegion {
     }
 }
 
+/// An atlas as described on disk: the image, its named regions, which
+/// region each species and building type is drawn with, and the animation
+/// sheets of species that have them.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AtlasDefinition {
+    /// The PNG, relative to the definition file once loaded.
+    pub image: PathBuf,
+    #[serde(default)]
+    pub regions: HashMap<String, SpriteRegion>,
+    #[serde(default)]
+    pub species: HashMap<Species, String>,
+    #[serde(default)]
+    pub buildings: HashMap<BuildingType, String>,
+    /// Frame layouts; atlas size is filled in when the image is loaded.
+    #[serde(default)]
+    pub animations: HashMap<Species, AnimationData>,
+}
+
+impl AtlasDefinition {
+    /// Parse a definition, checking every species and building names a
+    /// region it defines.
+    pub fn parse(content: &str) -> Result<Self, AtlasError> {
+        let definition: Self = toml::from_str(content)?;
+        for name in definition
+            .species
+            .values()
+            .chain(definition.buildings.values())
+        {
+            if !definition.regions.contains_key(name) {
+                return Err(AtlasError::UnknownRegion(name.clone()));
+            }
+        }
+        Ok(definition)
+    }
+
+    /// Load a definition from a TOML file; its image path is resolved
+    /// against the file's directory.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AtlasError> {
+        let path = path.as_ref();
+        let mut definition = Self::parse(&std::fs::read_to_string(path)?)?;
+        if let Some(dir) = path.parent() {
+            definition.image = dir.join(&definition.image);
+        }
+        Ok(definition)
+    }
+
+    /// The region `species` is drawn with, if it has one.
+    pub fn species_region(&self, species: Species) -> Option<SpriteRegion> {
+        self.regions.get(self.species.get(&species)?).copied()
+    }
+
+    /// The region `building` is drawn with, if it has one.
+    pub fn building_region(&self, building: BuildingType) -> Option<SpriteRegion> {
+        self.regions.get(self.buildings.get(&building)?).copied()
+    }
+
+    /// Check every region fits inside a `width` x `height` image.
+    pub fn check_bounds(&self, width: u32, height: u32) -> Result<(), AtlasError> {
+        for (name, region) in &self.regions {
+            if region.x + region.width > width || region.y + region.height > height {
+                return Err(AtlasError::RegionOutOfBounds {
+                    name: name.clone(),
+                    width,
+                    height,
+                });
+            }
+        }
+        for (species, sheet) in &self.animations {
+            let frames: u32 = AnimationState::ALL
+                .iter()
+                .map(|&s| sheet.frame_count(s) as u32)
+                .sum();
+            let columns = sheet.atlas_columns.max(1);
+            let rows = frames.div_ceil(columns);
+            let right = sheet.origin_x + frames.min(columns) * sheet.sprite_width;
+            let bottom = sheet.origin_y + rows * sheet.sprite_height;
+            if right > width || bottom > height {
+                return Err(AtlasError::RegionOutOfBounds {
+                    name: format!("{species:?} animation"),
+                    width,
+                    height,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A texture atlas containing multiple sprites.
 pub struct TextureAtlas {
     /// GPU texture.
@@ -121,6 +236,25 @@ impl TextureAtlas {
         })
     }
 
+    /// Load the image a definition names and register its regions.
+    pub fn from_definition(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        definition: &AtlasDefinition,
+    ) -> Result<Self, AtlasError> {
+        let bytes = std::fs::read(&definition.image)?;
+        let label = definition.image.display().to_string();
+        let mut atlas = Self::from_bytes(device, queue, &bytes, &label)?;
+        definition.check_bounds(atlas.width, atlas.height)?;
+        atlas.sprites.extend(
+            definition
+                .regions
+                .iter()
+                .map(|(name, region)| (name.clone(), *region)),
+        );
+        Ok(atlas)
+    }
+
     /// Create a placeholder 1x1 white texture for testing.
     pub fn placeholder(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
         let bytes = [255u8, 255, 255, 255]; // Single white pixel
@@ -246,6 +380,39 @@ mod tests {
         assert_eq!(size[1], 0.125); // 32/256
     }
 
+    #[test]
+    fn test_bundled_definition_maps_every_species_and_building() {
+        let definition = AtlasDefinition::load("data/sprites/atlas.toml").unwrap();
+        assert!(definition.image.ends_with("data/sprites/atlas.png"));
+        let image = image::open(&definition.image).unwrap();
+        definition
+            .check_bounds(image.width(), image.height())
+            .unwrap();
+
+        assert_eq!(
+            definition.species_region(Species::Orc),
+            definition.regions.get("orc").copied()
+        );
+        assert!(definition.building_region(BuildingType::Well).is_some());
+        assert_eq!(definition.species.len(), 26);
+        assert_eq!(definition.buildings.len(), 11);
+        assert!(definition.animations.contains_key(&Species::Human));
+
+        // A sheet running off the image is caught like any region
+        let mut tall = definition.clone();
+        tall.animations
+            .get_mut(&Species::Human)
+            .unwrap()
+            .sleep_frames = 9;
+        assert!(tall.check_bounds(image.width(), image.height()).is_err());
+
+        let broken = "image = \"a.png\"\n[species]\nHuman = \"nobody\"\n";
+        assert!(matches!(
+            AtlasDefinition::parse(broken),
+            Err(AtlasError::UnknownRegion(name)) if name == "nobody"
+        ));
+    }
+
     #[test]
     fn test_grid_uv() {
         // Simulate a 4x4 grid atlas of 32x32 sprites in a 128x128 texture
@@ -0,0 +1,175 @@
+//! Sprite catalog: which sprite each species and building type is drawn
+//! with, for building `RenderState`s.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use super::animation::{AnimationController, AnimationData};
+use super::atlas::{AtlasDefinition, SpriteRegion};
+use crate::city::building::BuildingType;
+use crate::core::types::{EntityId, Species};
+use crate::renderer::state::{AtlasId, SpriteEntity};
+
+/// Sprites for species and buildings from one loaded atlas.
+#[derive(Clone, Debug)]
+pub struct SpriteCatalog {
+    atlas: AtlasId,
+    width: u32,
+    height: u32,
+    species: HashMap<Species, SpriteRegion>,
+    buildings: HashMap<BuildingType, SpriteRegion>,
+    animations: HashMap<Species, AnimationData>,
+}
+
+impl SpriteCatalog {
+    /// Catalog `definition`'s mappings for an atlas `width` x `height`
+    /// pixels, loaded into the renderer as `atlas`.
+    pub fn new(atlas: AtlasId, width: u32, height: u32, definition: &AtlasDefinition) -> Self {
+        Self {
+            atlas,
+            width,
+            height,
+            species: definition
+                .species
+                .keys()
+                .filter_map(|&s| Some((s, definition.species_region(s)?)))
+                .collect(),
+            buildings: definition
+                .buildings
+                .keys()
+                .filter_map(|&b| Some((b, definition.building_region(b)?)))
+                .collect(),
+            animations: definition
+                .animations
+                .iter()
+                .map(|(&species, sheet)| {
+                    let sheet = AnimationData {
+                        atlas_width: width,
+                        atlas_height: height,
+                        ..sheet.clone()
+                    };
+                    (species, sheet)
+                })
+                .collect(),
+        }
+    }
+
+    fn sprite(
+        &self,
+        id: EntityId,
+        region: SpriteRegion,
+        position: Vec2,
+        scale: f32,
+    ) -> SpriteEntity {
+        self.sprite_with_uv(id, region.uv(self.width, self.height), position, scale)
+    }
+
+    fn sprite_with_uv(
+        &self,
+        id: EntityId,
+        (uv_offset, uv_size): ([f32; 2], [f32; 2]),
+        position: Vec2,
+        scale: f32,
+    ) -> SpriteEntity {
+        SpriteEntity {
+            id,
+            position,
+            atlas: self.atlas,
+            uv_rect: [uv_offset[0], uv_offset[1], uv_size[0], uv_size[1]],
+            color: [255, 255, 255, 255],
+            rotation: 0.0,
+            scale,
+            flip_x: false,
+            flip_y: false,
+            z_order: 1,
+        }
+    }
+
+    /// The sprite a creature of `species` is drawn as, if the atlas has one.
+    pub fn species_sprite(
+        &self,
+        id: EntityId,
+        species: Species,
+        position: Vec2,
+        scale: f32,
+    ) -> Option<SpriteEntity> {
+        let region = *self.species.get(&species)?;
+        Some(self.sprite(id, region, position, scale))
+    }
+
+    /// The animation sheet of `species`, if the atlas has one.
+    pub fn animation(&self, species: Species) -> Option<&AnimationData> {
+        self.animations.get(&species)
+    }
+
+    /// The sprite a creature of `species` is drawn as, showing the frame
+    /// `controller` is on if the species is animated and its still sprite
+    /// otherwise.
+    pub fn animated_species_sprite(
+        &self,
+        id: EntityId,
+        species: Species,
+        controller: &AnimationController,
+        position: Vec2,
+        scale: f32,
+    ) -> Option<SpriteEntity> {
+        match self.animations.get(&species) {
+            Some(sheet) => {
+                Some(self.sprite_with_uv(id, controller.uv_coords(sheet), position, scale))
+            }
+            None => self.species_sprite(id, species, position, scale),
+        }
+    }
+
+    /// The sprite a `building` is drawn as, if the atlas has one. Buildings
+    /// have no `EntityId`, so the sprite carries a fresh one.
+    pub fn building_sprite(
+        &self,
+        building: BuildingType,
+        position: Vec2,
+        scale: f32,
+    ) -> Option<SpriteEntity> {
+        let region = *self.buildings.get(&building)?;
+        Some(self.sprite(EntityId::new(), region, position, scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_species_and_buildings_draw_from_their_regions() {
+        let definition = AtlasDefinition::parse(
+            r#"
+            image = "atlas.png"
+            [regions]
+            orc = { x = 16, y = 0, width = 16, height = 16 }
+            well = { x = 0, y = 16, width = 16, height = 16 }
+            [species]
+            Orc = "orc"
+            [buildings]
+            Well = "well"
+            "#,
+        )
+        .unwrap();
+        let catalog = SpriteCatalog::new(AtlasId(1), 64, 32, &definition);
+
+        let id = EntityId::new();
+        let orc = catalog
+            .species_sprite(id, Species::Orc, Vec2::new(5.0, 5.0), 4.0)
+            .unwrap();
+        assert_eq!(orc.id, id);
+        assert_eq!(orc.atlas, AtlasId(1));
+        assert_eq!(orc.uv_rect, [0.25, 0.0, 0.25, 0.5]);
+        assert!(catalog
+            .species_sprite(id, Species::Human, Vec2::ZERO, 4.0)
+            .is_none());
+
+        let well = catalog
+            .building_sprite(BuildingType::Well, Vec2::ZERO, 8.0)
+            .unwrap();
+        assert_eq!(well.uv_rect, [0.0, 0.5, 0.25, 0.5]);
+    }
+}
@@ -0,0 +1,235 @@
+//! Emotion overlays: frames drawn over an entity, and the posture its body
+//! takes, showing what it feels without opening the inspector.
+
+use glam::Vec2;
+
+use crate::core::types::EntityId;
+use crate::entity::emotion::Emotion;
+use crate::renderer::state::{AtlasId, SpriteEntity};
+
+/// How a body stands when it feels something.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Posture {
+    /// Tilt off upright, in radians; positive slumps back, negative leans in.
+    pub lean: f32,
+    /// How far the body sinks, as a fraction of its drawn size.
+    pub sink: f32,
+    /// Drawn size relative to upright: hunched is smaller, squared up larger.
+    pub scale: f32,
+}
+
+impl Posture {
+    pub const UPRIGHT: Posture = Posture {
+        lean: 0.0,
+        sink: 0.0,
+        scale: 1.0,
+    };
+
+    /// The posture `emotion` shows in: slumped when exhausted, squared up
+    /// and leaning in when enraged.
+    pub fn of(emotion: Emotion) -> Self {
+        match emotion {
+            Emotion::Calm | Emotion::Content => Posture::UPRIGHT,
+            Emotion::Downcast => Posture {
+                lean: 0.15,
+                sink: 0.1,
+                scale: 0.95,
+            },
+            Emotion::Exhausted => Posture {
+                lean: 0.35,
+                sink: 0.25,
+                scale: 0.9,
+            },
+            Emotion::Afraid => Posture {
+                lean: 0.1,
+                sink: 0.15,
+                scale: 0.85,
+            },
+            Emotion::Enraged => Posture {
+                lean: -0.2,
+                sink: 0.0,
+                scale: 1.15,
+            },
+        }
+    }
+
+    /// Apply to a body drawn at `position` with `scale` and `facing`.
+    pub fn apply(&self, position: Vec2, scale: f32, facing: f32) -> (Vec2, f32, f32) {
+        (
+            position - Vec2::new(0.0, self.sink * scale),
+            scale * self.scale,
+            facing + self.lean,
+        )
+    }
+}
+
+/// Tint an emotion's overlay is drawn in, so it reads even on a plain quad.
+pub fn emotion_tint(emotion: Emotion) -> [u8; 4] {
+    match emotion {
+        Emotion::Calm => [255, 255, 255, 0],
+        Emotion::Content => [120, 220, 120, 220],
+        Emotion::Downcast => [110, 130, 200, 220],
+        Emotion::Exhausted => [170, 170, 170, 220],
+        Emotion::Afraid => [230, 230, 120, 220],
+        Emotion::Enraged => [230, 60, 50, 230],
+    }
+}
+
+/// Layout of the emotion overlay atlas: one row per shown emotion, in
+/// `Emotion::SHOWN` order, `frames` frames across.
+#[derive(Clone, Debug)]
+pub struct EmotionOverlay {
+    pub frames: u8,
+    /// Seconds per frame.
+    pub frame_duration: f32,
+    pub sprite_width: u32,
+    pub sprite_height: u32,
+    pub atlas_width: u32,
+    pub atlas_height: u32,
+}
+
+impl Default for EmotionOverlay {
+    fn default() -> Self {
+        Self {
+            frames: 4,
+            frame_duration: 0.25,
+            sprite_width: 16,
+            sprite_height: 16,
+            atlas_width: 64,
+            atlas_height: 80,
+        }
+    }
+}
+
+impl EmotionOverlay {
+    /// Atlas row of `emotion`, or `None` if it has no overlay.
+    pub fn row(&self, emotion: Emotion) -> Option<u32> {
+        Emotion::SHOWN
+            .iter()
+            .position(|&e| e == emotion)
+            .map(|row| row as u32)
+    }
+
+    /// UV offset and size of `frame` of `emotion`'s overlay.
+    pub fn frame_uv(&self, emotion: Emotion, frame: u8) -> Option<([f32; 2], [f32; 2])> {
+        let row = self.row(emotion)?;
+        let col = (frame % self.frames.max(1)) as u32;
+        let u = (col * self.sprite_width) as f32 / self.atlas_width as f32;
+        let v = (row * self.sprite_height) as f32 / self.atlas_height as f32;
+        let width = self.sprite_width as f32 / self.atlas_width as f32;
+        let height = self.sprite_height as f32 / self.atlas_height as f32;
+        Some(([u, v], [width, height]))
+    }
+}
+
+/// Plays one entity's emotion overlay, looping its frames.
+#[derive(Clone, Debug, Default)]
+pub struct EmotionIndicator {
+    pub emotion: Emotion,
+    pub current_frame: u8,
+    pub frame_timer: f32,
+}
+
+impl EmotionIndicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show `emotion`, restarting the overlay if it changed.
+    pub fn set_emotion(&mut self, emotion: Emotion) {
+        if self.emotion != emotion {
+            self.emotion = emotion;
+            self.current_frame = 0;
+            self.frame_timer = 0.0;
+        }
+    }
+
+    /// Advance by `dt` seconds; returns true if the frame changed.
+    pub fn update(&mut self, dt: f32, overlay: &EmotionOverlay) -> bool {
+        if overlay.row(self.emotion).is_none() || overlay.frames == 0 {
+            return false;
+        }
+        self.frame_timer += dt;
+        if self.frame_timer < overlay.frame_duration {
+            return false;
+        }
+        self.frame_timer -= overlay.frame_duration;
+        self.current_frame = (self.current_frame + 1) % overlay.frames;
+        true
+    }
+
+    pub fn posture(&self) -> Posture {
+        Posture::of(self.emotion)
+    }
+
+    /// The overlay sprite to draw over a body at `position` with `scale`,
+    /// or `None` while calm.
+    pub fn sprite(
+        &self,
+        id: EntityId,
+        position: Vec2,
+        scale: f32,
+        overlay: &EmotionOverlay,
+    ) -> Option<SpriteEntity> {
+        let (uv_offset, uv_size) = overlay.frame_uv(self.emotion, self.current_frame)?;
+        Some(SpriteEntity {
+            id,
+            // Just above the head
+            position: position + Vec2::new(0.0, scale * 1.6),
+            atlas: AtlasId::WHITE,
+            uv_rect: [uv_offset[0], uv_offset[1], uv_size[0], uv_size[1]],
+            color: emotion_tint(self.emotion),
+            rotation: 0.0,
+            scale,
+            flip_x: false,
+            flip_y: false,
+            z_order: 2,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indicator_loops_frames_and_restarts_on_change() {
+        let overlay = EmotionOverlay::default();
+        let mut indicator = EmotionIndicator::new();
+        // Calm shows nothing
+        assert!(!indicator.update(1.0, &overlay));
+        assert!(indicator
+            .sprite(EntityId::new(), Vec2::ZERO, 3.0, &overlay)
+            .is_none());
+
+        indicator.set_emotion(Emotion::Exhausted);
+        for _ in 0..overlay.frames {
+            assert!(indicator.update(overlay.frame_duration, &overlay));
+        }
+        assert_eq!(indicator.current_frame, 0);
+        indicator.update(overlay.frame_duration, &overlay);
+
+        let sprite = indicator
+            .sprite(EntityId::new(), Vec2::ZERO, 3.0, &overlay)
+            .unwrap();
+        assert!(sprite.position.y > 0.0);
+        // Third row, second column
+        assert_eq!(sprite.uv_rect[0], 0.25);
+        assert_eq!(sprite.uv_rect[1], 2.0 * 16.0 / 80.0);
+
+        indicator.set_emotion(Emotion::Enraged);
+        assert_eq!(indicator.current_frame, 0);
+    }
+
+    #[test]
+    fn test_exhaustion_slumps_and_rage_squares_up() {
+        let slumped = Posture::of(Emotion::Exhausted);
+        let (position, scale, facing) = slumped.apply(Vec2::new(0.0, 10.0), 3.0, 0.0);
+        assert!(position.y < 10.0 && scale < 3.0 && facing > 0.0);
+
+        let enraged = Posture::of(Emotion::Enraged);
+        let (_, scale, facing) = enraged.apply(Vec2::ZERO, 3.0, 0.0);
+        assert!(scale > 3.0 && facing < 0.0);
+        assert_eq!(Posture::of(Emotion::Calm), Posture::UPRIGHT);
+    }
+}
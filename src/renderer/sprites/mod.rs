@@ -1,9 +1,13 @@
-//! Sprite rendering with texture atlases and animation.
+//! Sprite rendering with texture atlases, animation, and emotion overlays.
 
 pub mod animation;
 pub mod atlas;
+pub mod catalog;
+pub mod emotion;
 pub mod instance;
 
 pub use animation::{AnimationController, AnimationData, AnimationState};
-pub use atlas::{SpriteRegion, TextureAtlas};
+pub use atlas::{AtlasDefinition, AtlasError, SpriteRegion, TextureAtlas};
+pub use catalog::SpriteCatalog;
+pub use emotion::{emotion_tint, EmotionIndicator, EmotionOverlay, Posture};
 pub use instance::SpriteInstance;
@@ -1,5 +1,6 @@
 //! Render state types - frozen snapshots for rendering.
 
+use crate::city::building::BuildingId;
 use crate::core::types::EntityId;
 use glam::Vec2;
 
@@ -10,14 +11,26 @@ pub struct RenderState {
     pub tick: u64,
     pub entities: Vec<RenderEntity>, // Shape-based entities
     pub sprites: Vec<SpriteEntity>,  // Textured sprites
+    pub labels: Vec<TextLabel>,      // Text drawn over everything else
     pub camera: CameraState,
 }
 
+/// Which loaded texture atlas a sprite samples (see `Renderer::add_atlas`).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct AtlasId(pub u32);
+
+impl AtlasId {
+    /// The built-in single white pixel: sprites drawn from it are plain
+    /// tinted quads.
+    pub const WHITE: AtlasId = AtlasId(0);
+}
+
 /// Sprite render data (for textured entities).
 #[derive(Clone, Copy)]
 pub struct SpriteEntity {
     pub id: EntityId,
     pub position: Vec2,
+    pub atlas: AtlasId,
     pub uv_rect: [f32; 4], // [u, v, width, height] normalized
     pub color: [u8; 4],    // RGBA tint
     pub rotation: f32,
@@ -33,6 +46,7 @@ impl SpriteEntity {
         Self {
             id,
             position,
+            atlas: AtlasId::WHITE,
             uv_rect: [0.0, 0.0, 1.0, 1.0],
             color: [255, 255, 255, 255],
             rotation: 0.0,
@@ -44,10 +58,49 @@ impl SpriteEntity {
     }
 }
 
+/// A line of text anchored in the world.
+#[derive(Clone, Debug)]
+pub struct TextLabel {
+    pub text: String,
+    pub position: Vec2, // Centre of the baseline, in world units
+    pub color: Color,
+    pub size: f32, // Height in screen pixels, whatever the zoom
+}
+
+impl TextLabel {
+    pub fn new(text: impl Into<String>, position: Vec2, color: Color, size: f32) -> Self {
+        Self {
+            text: text.into(),
+            position,
+            color,
+            size,
+        }
+    }
+}
+
+/// What a drawn shape stands for, and so what clicking it selects.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Selection {
+    Entity(EntityId),
+    Building(BuildingId),
+}
+
+impl From<EntityId> for Selection {
+    fn from(id: EntityId) -> Self {
+        Selection::Entity(id)
+    }
+}
+
+impl From<BuildingId> for Selection {
+    fn from(id: BuildingId) -> Self {
+        Selection::Building(id)
+    }
+}
+
 /// Minimal render data per entity.
 #[derive(Clone, Copy)]
 pub struct RenderEntity {
-    pub id: EntityId,
+    pub id: Selection,
     pub position: Vec2,
     pub facing: f32,
     pub shape: ShapeType,
@@ -63,6 +116,18 @@ pub enum ShapeType {
     Rectangle = 1,
     Triangle = 2,
     Hexagon = 3,
+    Ring = 4,
+}
+
+impl ShapeType {
+    /// Distance from the centre of the unit shape to its edge, roughly;
+    /// times `scale` it is the drawn shape's radius for hit-testing.
+    pub fn radius(self) -> f32 {
+        match self {
+            ShapeType::Circle | ShapeType::Hexagon | ShapeType::Ring => 1.0,
+            ShapeType::Rectangle | ShapeType::Triangle => 0.5,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -0,0 +1,189 @@
+//! Glyph atlas: a font rasterized once into a single texture.
+
+use std::collections::HashMap;
+
+use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
+use thiserror::Error;
+
+use crate::renderer::sprites::SpriteRegion;
+
+/// Width of the atlas texture in pixels; rows are added as needed.
+const ATLAS_WIDTH: u32 = 256;
+
+/// Empty pixels kept between glyphs so sampling never bleeds.
+const GLYPH_PADDING: u32 = 1;
+
+/// Name of egui's bundled monospace font, used when no other is given.
+const BUNDLED_FONT: &str = "Hack";
+
+/// Errors building a glyph atlas.
+#[derive(Error, Debug)]
+pub enum TextError {
+    #[error("Font not available: {0}")]
+    MissingFont(String),
+    #[error("Invalid font data")]
+    InvalidFont,
+}
+
+/// Where one character sits in the atlas and how to place it.
+#[derive(Clone, Copy, Debug)]
+pub struct Glyph {
+    /// Region of the atlas holding the glyph's pixels.
+    pub region: SpriteRegion,
+    /// Offset from the pen position on the baseline to the glyph's
+    /// top-left corner, in atlas pixels (y down).
+    pub offset: [f32; 2],
+    /// How far the pen moves after this glyph, in atlas pixels.
+    pub advance: f32,
+}
+
+/// Printable ASCII rasterized at one pixel size, as white RGBA with the
+/// glyph coverage in alpha so it can be tinted like a sprite.
+pub struct GlyphAtlas {
+    /// Size the font was rasterized at, in pixels.
+    pub pixel_size: f32,
+    /// Distance from the baseline to the top of the tallest glyph.
+    pub ascent: f32,
+    /// Atlas width in pixels.
+    pub width: u32,
+    /// Atlas height in pixels.
+    pub height: u32,
+    /// RGBA8 pixels, `width * height * 4` bytes.
+    pub pixels: Vec<u8>,
+    glyphs: HashMap<char, Glyph>,
+    fallback_advance: f32,
+}
+
+impl GlyphAtlas {
+    /// Rasterize egui's bundled monospace font.
+    pub fn bundled(pixel_size: f32) -> Result<Self, TextError> {
+        let fonts = egui::FontDefinitions::default();
+        let data = fonts
+            .font_data
+            .get(BUNDLED_FONT)
+            .ok_or_else(|| TextError::MissingFont(BUNDLED_FONT.to_string()))?;
+        let font = FontArc::try_from_vec(data.font.to_vec()).map_err(|_| TextError::InvalidFont)?;
+        Ok(Self::from_font(&font, pixel_size))
+    }
+
+    /// Rasterize printable ASCII from `font` at `pixel_size`.
+    pub fn from_font(font: &FontArc, pixel_size: f32) -> Self {
+        let scaled = font.as_scaled(PxScale::from(pixel_size));
+        let mut pixels = Vec::new();
+        let mut glyphs = HashMap::new();
+        let (mut x, mut y, mut row_height) = (GLYPH_PADDING, GLYPH_PADDING, 0);
+
+        for c in (' '..='~').chain(std::iter::once('\u{FFFD}')) {
+            let id = font.glyph_id(c);
+            let advance = scaled.h_advance(id);
+            let Some(outline) = font.outline_glyph(id.with_scale(pixel_size)) else {
+                // Nothing to draw (a space): it only moves the pen
+                glyphs.insert(
+                    c,
+                    Glyph {
+                        region: SpriteRegion::new(0, 0, 0, 0),
+                        offset: [0.0, 0.0],
+                        advance,
+                    },
+                );
+                continue;
+            };
+            let bounds = outline.px_bounds();
+            let (w, h) = (bounds.width() as u32, bounds.height() as u32);
+            if x + w + GLYPH_PADDING > ATLAS_WIDTH {
+                x = GLYPH_PADDING;
+                y += row_height + GLYPH_PADDING;
+                row_height = 0;
+            }
+            let rows = (y + h + GLYPH_PADDING) as usize;
+            if pixels.len() < rows * ATLAS_WIDTH as usize * 4 {
+                pixels.resize(rows * ATLAS_WIDTH as usize * 4, 0);
+            }
+            outline.draw(|gx, gy, coverage| {
+                let i = (((y + gy) * ATLAS_WIDTH + x + gx) * 4) as usize;
+                pixels[i..i + 3].copy_from_slice(&[255, 255, 255]);
+                pixels[i + 3] = (coverage.clamp(0.0, 1.0) * 255.0) as u8;
+            });
+            glyphs.insert(
+                c,
+                Glyph {
+                    region: SpriteRegion::new(x, y, w, h),
+                    offset: [bounds.min.x, bounds.min.y],
+                    advance,
+                },
+            );
+            x += w + GLYPH_PADDING;
+            row_height = row_height.max(h);
+        }
+
+        let height = (pixels.len() / (ATLAS_WIDTH as usize * 4)).max(1) as u32;
+        pixels.resize((ATLAS_WIDTH * height * 4) as usize, 0);
+        Self {
+            pixel_size,
+            ascent: scaled.ascent(),
+            width: ATLAS_WIDTH,
+            height,
+            pixels,
+            glyphs,
+            fallback_advance: scaled.h_advance(font.glyph_id(' ')),
+        }
+    }
+
+    /// An atlas with no glyphs: text laid out from it draws nothing.
+    pub fn empty(pixel_size: f32) -> Self {
+        Self {
+            pixel_size,
+            ascent: pixel_size,
+            width: 1,
+            height: 1,
+            pixels: vec![0; 4],
+            glyphs: HashMap::new(),
+            fallback_advance: pixel_size / 2.0,
+        }
+    }
+
+    /// The glyph for `c`, or the replacement glyph for characters the
+    /// atlas does not hold.
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.get(&c).or_else(|| self.glyphs.get(&'\u{FFFD}'))
+    }
+
+    /// How far the pen moves after `c`, in atlas pixels.
+    pub fn advance(&self, c: char) -> f32 {
+        self.glyph(c)
+            .map(|g| g.advance)
+            .unwrap_or(self.fallback_advance)
+    }
+
+    /// Width of `text` on one line, in atlas pixels.
+    pub fn measure(&self, text: &str) -> f32 {
+        text.chars().map(|c| self.advance(c)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_atlas_holds_printable_ascii() {
+        let atlas = GlyphAtlas::bundled(16.0).unwrap();
+        assert_eq!(
+            atlas.pixels.len(),
+            (atlas.width * atlas.height * 4) as usize
+        );
+
+        let digit = atlas.glyph('7').unwrap();
+        assert!(digit.region.width > 0 && digit.region.height > 0);
+        assert!(digit.region.x + digit.region.width <= atlas.width);
+        assert!(digit.region.y + digit.region.height <= atlas.height);
+        // Glyphs sit above the baseline
+        assert!(digit.offset[1] < 0.0);
+        // Monospace: every character advances the same
+        assert_eq!(atlas.advance('i'), atlas.advance('W'));
+        assert_eq!(atlas.measure("-12"), 3.0 * atlas.advance('1'));
+
+        // Something was drawn
+        assert!(atlas.pixels.chunks(4).any(|p| p[3] > 128));
+    }
+}
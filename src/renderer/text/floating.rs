@@ -0,0 +1,101 @@
+//! Floating text: short-lived labels that rise and fade, such as the
+//! damage numbers over a struck combatant.
+
+use glam::Vec2;
+
+use crate::renderer::state::{Color, TextLabel};
+
+/// Seconds a floating label stays on screen.
+pub const FLOAT_LIFETIME: f32 = 1.2;
+
+/// Screen pixels a floating label rises per second.
+pub const FLOAT_RISE: f32 = 24.0;
+
+/// A label rising from where it was spawned.
+#[derive(Clone, Debug)]
+pub struct FloatingText {
+    pub text: String,
+    pub origin: Vec2,
+    pub color: Color,
+    pub size: f32,
+    /// Seconds since it was spawned.
+    pub age: f32,
+}
+
+/// Every floating label still on screen.
+#[derive(Default)]
+pub struct FloatingTexts {
+    texts: Vec<FloatingText>,
+}
+
+impl FloatingTexts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a label rising from `origin` (world units).
+    pub fn spawn(&mut self, text: impl Into<String>, origin: Vec2, color: Color, size: f32) {
+        self.texts.push(FloatingText {
+            text: text.into(),
+            origin,
+            color,
+            size,
+            age: 0.0,
+        });
+    }
+
+    /// Age every label by `dt` seconds, dropping those that have faded.
+    pub fn update(&mut self, dt: f32) {
+        for text in &mut self.texts {
+            text.age += dt;
+        }
+        self.texts.retain(|text| text.age < FLOAT_LIFETIME);
+    }
+
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    /// The labels to draw this frame, risen and faded by age. `zoom`
+    /// (world units per pixel) keeps the rise steady on screen.
+    pub fn labels(&self, zoom: f32) -> impl Iterator<Item = TextLabel> + '_ {
+        self.texts.iter().map(move |text| {
+            let fade = 1.0 - text.age / FLOAT_LIFETIME;
+            let color = Color::rgba(
+                text.color.r,
+                text.color.g,
+                text.color.b,
+                text.color.a * fade,
+            );
+            TextLabel::new(
+                text.text.clone(),
+                text.origin + Vec2::new(0.0, text.age * FLOAT_RISE * zoom),
+                color,
+                text.size,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_floating_text_rises_fades_and_expires() {
+        let mut texts = FloatingTexts::new();
+        texts.spawn("-12", Vec2::new(10.0, 10.0), Color::RED, 14.0);
+
+        texts.update(FLOAT_LIFETIME / 2.0);
+        let label = texts.labels(2.0).next().unwrap();
+        assert!(label.position.y > 10.0);
+        assert!((label.color.a - 0.5).abs() < 1e-4);
+
+        texts.update(FLOAT_LIFETIME);
+        assert!(texts.is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+//! GPU glyph instance data.
+
+use bytemuck::{Pod, Zeroable};
+
+/// One glyph quad. 32 bytes, 16-byte aligned.
+#[repr(C, align(16))]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TextInstance {
+    /// World position of the quad's centre (x, y).
+    pub position: [f32; 2],
+    /// Quad width and height in world units.
+    pub size: [f32; 2],
+    /// Packed UV offset of the glyph's top-left (16-bit u, 16-bit v).
+    pub uv_offset: u32,
+    /// Packed UV size (16-bit width, 16-bit height normalized).
+    pub uv_size: u32,
+    /// Color as RGBA8 packed.
+    pub color: u32,
+    /// Padding for 16-byte alignment.
+    pub _padding: u32,
+}
+
+/// Pack a normalized pair into two 16-bit halves.
+fn pack_uv(uv: [f32; 2]) -> u32 {
+    let u = (uv[0].clamp(0.0, 1.0) * 65535.0) as u32;
+    let v = (uv[1].clamp(0.0, 1.0) * 65535.0) as u32;
+    u | (v << 16)
+}
+
+impl TextInstance {
+    /// Create a glyph instance.
+    pub fn new(
+        position: [f32; 2],
+        size: [f32; 2],
+        uv_offset: [f32; 2],
+        uv_size: [f32; 2],
+        color: u32,
+    ) -> Self {
+        Self {
+            position,
+            size,
+            uv_offset: pack_uv(uv_offset),
+            uv_size: pack_uv(uv_size),
+            color,
+            _padding: 0,
+        }
+    }
+
+    /// Vertex buffer layout descriptor.
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: 32, // Fixed 32 bytes
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                // position: vec2<f32>
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                // size: vec2<f32>
+                wgpu::VertexAttribute {
+                    offset: 8,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                // uv_offset: u32
+                wgpu::VertexAttribute {
+                    offset: 16,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Uint32,
+                },
+                // uv_size: u32
+                wgpu::VertexAttribute {
+                    offset: 20,
+                    shader_location: 4,
+                    format: wgpu::VertexFormat::Uint32,
+                },
+                // color: u32
+                wgpu::VertexAttribute {
+                    offset: 24,
+                    shader_location: 5,
+                    format: wgpu::VertexFormat::Uint32,
+                },
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_instance_layout() {
+        assert_eq!(std::mem::size_of::<TextInstance>(), 32);
+        assert_eq!(std::mem::align_of::<TextInstance>(), 16);
+
+        let glyph = TextInstance::new([1.0, 2.0], [8.0, 12.0], [0.5, 0.25], [0.0, 1.0], 0);
+        assert!((glyph.uv_offset & 0xFFFF) > 32000 && (glyph.uv_offset & 0xFFFF) < 33000);
+        assert_eq!(glyph.uv_size >> 16, 0xFFFF);
+    }
+}
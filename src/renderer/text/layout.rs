@@ -0,0 +1,66 @@
+//! Laying labels out as glyph quads.
+
+use super::atlas::GlyphAtlas;
+use super::instance::TextInstance;
+use crate::renderer::state::TextLabel;
+
+/// Append one glyph quad per visible character of `label`.
+///
+/// Labels are sized in screen pixels, so `zoom` (world units per pixel)
+/// turns that into world units: text stays the same size on screen however
+/// far the camera is zoomed. The label is centred on its position, which
+/// sits on the baseline.
+pub fn layout_label(atlas: &GlyphAtlas, label: &TextLabel, zoom: f32, out: &mut Vec<TextInstance>) {
+    let scale = label.size * zoom / atlas.pixel_size;
+    let color = label.color.to_u32();
+    let mut pen_x = label.position.x - atlas.measure(&label.text) * scale / 2.0;
+    let baseline = label.position.y;
+
+    for c in label.text.chars() {
+        if let Some(glyph) = atlas.glyph(c) {
+            if glyph.region.width > 0 && glyph.region.height > 0 {
+                let width = glyph.region.width as f32 * scale;
+                let height = glyph.region.height as f32 * scale;
+                // Atlas y runs down, world y runs up
+                let center = [
+                    pen_x + glyph.offset[0] * scale + width / 2.0,
+                    baseline - glyph.offset[1] * scale - height / 2.0,
+                ];
+                let (uv_offset, uv_size) = glyph.region.uv(atlas.width, atlas.height);
+                out.push(TextInstance::new(
+                    center,
+                    [width, height],
+                    uv_offset,
+                    uv_size,
+                    color,
+                ));
+            }
+        }
+        pen_x += atlas.advance(c) * scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::state::Color;
+    use glam::Vec2;
+
+    #[test]
+    fn test_label_is_centred_and_scales_with_zoom() {
+        let atlas = GlyphAtlas::bundled(16.0).unwrap();
+        let label = TextLabel::new("A B", Vec2::new(100.0, 50.0), Color::WHITE, 16.0);
+
+        let mut near = Vec::new();
+        layout_label(&atlas, &label, 1.0, &mut near);
+        // The space moves the pen but draws nothing
+        assert_eq!(near.len(), 2);
+        let middle = (near[0].position[0] + near[1].position[0]) / 2.0;
+        assert!((middle - 100.0).abs() < 2.0);
+        assert!(near.iter().all(|g| g.position[1] > 50.0));
+
+        let mut far = Vec::new();
+        layout_label(&atlas, &label, 2.0, &mut far);
+        assert!((far[0].size[0] - 2.0 * near[0].size[0]).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,11 @@
+//! Text rendering from a glyph atlas: labels, floating numbers, overlays.
+
+pub mod atlas;
+pub mod floating;
+pub mod instance;
+pub mod layout;
+
+pub use atlas::{Glyph, GlyphAtlas, TextError};
+pub use floating::{FloatingText, FloatingTexts};
+pub use instance::TextInstance;
+pub use layout::layout_label;
@@ -0,0 +1,173 @@
+//! The tile layer: battle hexes grouped into chunks, each rebuilt and
+//! re-uploaded only when something in it changes.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::battle::battle_map::BattleMap;
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::visibility::ArmyVisibility;
+use crate::renderer::hex::{HexCoord, HEX_SIZE};
+use crate::renderer::shapes::ShapeInstance;
+use crate::renderer::state::ShapeType;
+
+use super::style::{fog_at, tile_color};
+
+/// Hexes along each side of a chunk.
+pub const CHUNK_SIZE: i32 = 16;
+
+/// Which chunk a hex falls in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChunkCoord {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl ChunkCoord {
+    pub fn of(hex: BattleHexCoord) -> Self {
+        Self {
+            q: hex.q.div_euclid(CHUNK_SIZE),
+            r: hex.r.div_euclid(CHUNK_SIZE),
+        }
+    }
+}
+
+/// World position of a battle hex's centre.
+pub fn hex_center(hex: BattleHexCoord) -> glam::Vec2 {
+    HexCoord::new(hex.q, hex.r).to_world()
+}
+
+/// One chunk's hexes, ready to draw with the hexagon geometry.
+#[derive(Debug, Clone, Default)]
+pub struct TileChunk {
+    pub instances: Vec<ShapeInstance>,
+}
+
+/// The battle map as chunks of hex tiles, drawn beneath units.
+///
+/// `sync` restyles every hex from the map and an army's fog of war, but
+/// only chunks whose tiles came out different are marked dirty; the
+/// renderer uploads those and leaves the rest on the GPU as they were.
+#[derive(Debug, Default)]
+pub struct TileLayer {
+    chunks: BTreeMap<ChunkCoord, TileChunk>,
+    dirty: BTreeSet<ChunkCoord>,
+}
+
+impl TileLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restyle the layer from `map` as `visibility` sees it (everything
+    /// visible if `None`); returns how many chunks changed.
+    pub fn sync(&mut self, map: &BattleMap, visibility: Option<&ArmyVisibility>) -> usize {
+        let mut rebuilt: BTreeMap<ChunkCoord, Vec<(BattleHexCoord, ShapeInstance)>> =
+            BTreeMap::new();
+        for (&coord, hex) in &map.hexes {
+            let center = hex_center(coord);
+            let color = tile_color(hex, fog_at(visibility, coord));
+            rebuilt.entry(ChunkCoord::of(coord)).or_default().push((
+                coord,
+                ShapeInstance::new(
+                    [center.x, center.y],
+                    0.0,
+                    HEX_SIZE,
+                    color.to_u32(),
+                    ShapeType::Hexagon as u32,
+                ),
+            ));
+        }
+
+        let mut changed = 0;
+        // Chunks the map no longer covers are dropped
+        let gone: Vec<ChunkCoord> = self
+            .chunks
+            .keys()
+            .filter(|c| !rebuilt.contains_key(c))
+            .copied()
+            .collect();
+        for chunk in gone {
+            self.chunks.remove(&chunk);
+            self.dirty.insert(chunk);
+            changed += 1;
+        }
+
+        for (chunk, mut tiles) in rebuilt {
+            // Hash map order is arbitrary; sort so unchanged chunks compare equal
+            tiles.sort_by_key(|(coord, _)| *coord);
+            let instances: Vec<ShapeInstance> = tiles.into_iter().map(|(_, i)| i).collect();
+            let unchanged = self.chunks.get(&chunk).is_some_and(|old| {
+                bytemuck::cast_slice::<_, u8>(&old.instances)
+                    == bytemuck::cast_slice::<_, u8>(&instances)
+            });
+            if !unchanged {
+                self.chunks.insert(chunk, TileChunk { instances });
+                self.dirty.insert(chunk);
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    pub fn chunk(&self, coord: ChunkCoord) -> Option<&TileChunk> {
+        self.chunks.get(&coord)
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = (ChunkCoord, &TileChunk)> {
+        self.chunks.iter().map(|(&coord, chunk)| (coord, chunk))
+    }
+
+    pub fn is_dirty(&self, coord: ChunkCoord) -> bool {
+        self.dirty.contains(&coord)
+    }
+
+    /// Chunks changed or removed since the last call, clearing the marks.
+    pub fn take_dirty(&mut self) -> Vec<ChunkCoord> {
+        std::mem::take(&mut self.dirty).into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::terrain::BattleTerrain;
+
+    #[test]
+    fn test_only_changed_chunks_are_dirty() {
+        let mut map = BattleMap::new(40, 20);
+        let mut layer = TileLayer::new();
+        // 40 x 20 hexes in chunks of 16: three by two
+        assert_eq!(layer.sync(&map, None), 6);
+        assert_eq!(layer.take_dirty().len(), 6);
+        let tiles: usize = layer.chunks().map(|(_, c)| c.instances.len()).sum();
+        assert_eq!(tiles, 800);
+
+        // Nothing moved: nothing to upload
+        assert_eq!(layer.sync(&map, None), 0);
+
+        map.set_terrain(BattleHexCoord::new(20, 3), BattleTerrain::Forest);
+        map.set_elevation(BattleHexCoord::new(20, 4), 2);
+        assert_eq!(layer.sync(&map, None), 1);
+        assert_eq!(layer.take_dirty(), vec![ChunkCoord { q: 1, r: 0 }]);
+    }
+
+    #[test]
+    fn test_fog_of_war_dirties_chunks_it_covers() {
+        let map = BattleMap::new(32, 16);
+        let mut layer = TileLayer::new();
+        layer.sync(&map, None);
+        layer.take_dirty();
+
+        // The army sees all of the first chunk; only the second goes dark
+        let mut visibility = ArmyVisibility::new();
+        let seen = map.hexes.keys().filter(|c| c.q < CHUNK_SIZE).copied();
+        visibility.update(seen.collect());
+        assert_eq!(layer.sync(&map, Some(&visibility)), 1);
+        assert!(layer.is_dirty(ChunkCoord { q: 1, r: 0 }));
+
+        // Shrinking the map drops the chunk it left
+        layer.take_dirty();
+        assert_eq!(layer.sync(&BattleMap::new(16, 16), None), 1);
+        assert!(layer.chunk(ChunkCoord { q: 1, r: 0 }).is_none());
+    }
+}
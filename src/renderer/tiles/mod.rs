@@ -0,0 +1,7 @@
+//! Tile layer: the hex battle map drawn in chunks beneath units.
+
+pub mod layer;
+pub mod style;
+
+pub use layer::{hex_center, ChunkCoord, TileChunk, TileLayer, CHUNK_SIZE};
+pub use style::{fog_at, terrain_color, tile_color};
@@ -0,0 +1,86 @@
+//! How a battle hex looks: terrain color, shaded by elevation, dimmed by fog.
+
+use crate::battle::battle_map::{BattleHex, VisibilityState};
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::terrain::BattleTerrain;
+use crate::battle::visibility::ArmyVisibility;
+use crate::renderer::state::Color;
+
+/// Brightness gained per level of elevation (lost per level below zero).
+pub const ELEVATION_SHADE_STEP: f32 = 0.08;
+
+/// Brightness of a hex seen before but not watched now.
+pub const REMEMBERED_DIM: f32 = 0.55;
+
+/// Brightness of a hex never seen.
+pub const UNKNOWN_DIM: f32 = 0.15;
+
+/// Base color of a terrain type.
+pub fn terrain_color(terrain: BattleTerrain) -> Color {
+    match terrain {
+        BattleTerrain::Open => Color::rgb(0.45, 0.6, 0.3),
+        BattleTerrain::Rough => Color::rgb(0.55, 0.5, 0.35),
+        BattleTerrain::Forest => Color::rgb(0.15, 0.4, 0.18),
+        BattleTerrain::ShallowWater => Color::rgb(0.35, 0.55, 0.7),
+        BattleTerrain::DeepWater => Color::rgb(0.12, 0.25, 0.5),
+        BattleTerrain::Cliff => Color::rgb(0.4, 0.38, 0.36),
+        BattleTerrain::Road => Color::rgb(0.7, 0.62, 0.45),
+        BattleTerrain::Building => Color::rgb(0.5, 0.35, 0.3),
+    }
+}
+
+/// What an army sees of `coord`; with no army given, everything is in view.
+pub fn fog_at(visibility: Option<&ArmyVisibility>, coord: BattleHexCoord) -> VisibilityState {
+    match visibility {
+        None => VisibilityState::Observed,
+        Some(v) if v.is_visible(coord) => VisibilityState::Observed,
+        Some(v) if v.is_remembered(coord) => VisibilityState::Remembered,
+        Some(_) => VisibilityState::Unknown,
+    }
+}
+
+/// The color `hex` is drawn in: its terrain, lighter on high ground and
+/// darker in hollows, then dimmed by how much of it the army can see.
+pub fn tile_color(hex: &BattleHex, fog: VisibilityState) -> Color {
+    let base = terrain_color(hex.terrain);
+    let shade = (1.0 + hex.elevation as f32 * ELEVATION_SHADE_STEP).max(0.0);
+    let dim = match fog {
+        VisibilityState::Observed => 1.0,
+        VisibilityState::Remembered => REMEMBERED_DIM,
+        VisibilityState::Unknown => UNKNOWN_DIM,
+    };
+    Color::rgb(
+        base.r * shade * dim,
+        base.g * shade * dim,
+        base.b * shade * dim,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_high_ground_is_lighter_and_fog_dims() {
+        let coord = BattleHexCoord::new(2, 3);
+        let mut hex = BattleHex::new(coord, BattleTerrain::Open);
+        let flat = tile_color(&hex, VisibilityState::Observed);
+        hex.elevation = 2;
+        let hill = tile_color(&hex, VisibilityState::Observed);
+        assert!(hill.g > flat.g);
+
+        let remembered = tile_color(&hex, VisibilityState::Remembered);
+        let unknown = tile_color(&hex, VisibilityState::Unknown);
+        assert!(unknown.g < remembered.g && remembered.g < hill.g);
+
+        let mut visibility = ArmyVisibility::new();
+        assert_eq!(fog_at(None, coord), VisibilityState::Observed);
+        assert_eq!(fog_at(Some(&visibility), coord), VisibilityState::Unknown);
+        visibility.update([coord].into_iter().collect());
+        visibility.update(Default::default());
+        assert_eq!(
+            fog_at(Some(&visibility), coord),
+            VisibilityState::Remembered
+        );
+    }
+}
@@ -2,9 +2,13 @@
 
 use crate::actions::catalog::ActionId;
 use crate::core::types::Species;
+use crate::entity::social::Disposition;
 use crate::entity::tasks::TaskPriority;
 use std::collections::HashMap;
 
+/// How far a species sees when its rules do not say
+pub const DEFAULT_PERCEPTION_RANGE: f32 = 50.0;
+
 /// A single action rule loaded from TOML
 #[derive(Debug, Clone)]
 pub struct ActionRule {
@@ -26,11 +30,21 @@ pub struct IdleBehavior {
     pub description: String,
 }
 
+/// How a species takes in those around it
+#[derive(Debug, Clone, Default)]
+pub struct PerceptionRules {
+    /// How far it sees, if not `DEFAULT_PERCEPTION_RANGE`
+    pub range: Option<f32>,
+    /// How it regards other species on sight, whatever it remembers of them
+    pub dispositions: HashMap<Species, Disposition>,
+}
+
 /// All rules for a single species
 #[derive(Debug, Clone, Default)]
 pub struct SpeciesRuleSet {
     pub action_rules: Vec<ActionRule>,
     pub idle_behaviors: Vec<IdleBehavior>,
+    pub perception: PerceptionRules,
 }
 
 /// Central storage for all species rules
@@ -60,6 +74,25 @@ impl SpeciesRules {
             .unwrap_or(&[])
     }
 
+    /// How far a species sees
+    pub fn perception_range(&self, species: Species) -> f32 {
+        self.rules
+            .get(&species)
+            .and_then(|r| r.perception.range)
+            .unwrap_or(DEFAULT_PERCEPTION_RANGE)
+    }
+
+    /// How a species regards another on sight, or None to judge each one
+    /// by memory
+    pub fn sighted_disposition(&self, species: Species, other: Species) -> Option<Disposition> {
+        self.rules
+            .get(&species)?
+            .perception
+            .dispositions
+            .get(&other)
+            .copied()
+    }
+
     /// Insert rules for a species
     pub fn insert(&mut self, species: Species, rules: SpeciesRuleSet) {
         self.rules.insert(species, rules);
@@ -127,6 +160,7 @@ mod tests {
                 description: "Attack when bloodlust is high".to_string(),
             }],
             idle_behaviors: vec![],
+            perception: PerceptionRules::default(),
         };
 
         rules.insert(Species::Gnoll, rule_set);
@@ -136,6 +170,39 @@ mod tests {
         assert_eq!(action_rules[0].trigger_value, "bloodlust");
     }
 
+    #[test]
+    fn test_perception_falls_back_to_defaults() {
+        let mut rules = SpeciesRules::new();
+        let mut perception = PerceptionRules {
+            range: Some(60.0),
+            ..Default::default()
+        };
+        perception
+            .dispositions
+            .insert(Species::Orc, Disposition::Hostile);
+        rules.insert(
+            Species::Gnoll,
+            SpeciesRuleSet {
+                perception,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(rules.perception_range(Species::Gnoll), 60.0);
+        assert_eq!(
+            rules.perception_range(Species::Kobold),
+            DEFAULT_PERCEPTION_RANGE
+        );
+        assert_eq!(
+            rules.sighted_disposition(Species::Gnoll, Species::Orc),
+            Some(Disposition::Hostile)
+        );
+        assert_eq!(
+            rules.sighted_disposition(Species::Gnoll, Species::Human),
+            None
+        );
+    }
+
     #[test]
     fn test_validate_valid_rules() {
         let mut rules = SpeciesRules::new();
@@ -150,6 +217,7 @@ mod tests {
                 description: "Attack".to_string(),
             }],
             idle_behaviors: vec![],
+            perception: PerceptionRules::default(),
         };
 
         rules.insert(Species::Gnoll, rule_set);
@@ -171,6 +239,7 @@ mod tests {
                 description: "Attack".to_string(),
             }],
             idle_behaviors: vec![],
+            perception: PerceptionRules::default(),
         };
 
         rules.insert(Species::Gnoll, rule_set);
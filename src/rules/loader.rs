@@ -3,7 +3,9 @@
 use crate::actions::catalog::ActionId;
 use crate::core::types::Species;
 use crate::entity::tasks::TaskPriority;
-use crate::rules::action_rules::{ActionRule, IdleBehavior, SpeciesRuleSet, SpeciesRules};
+use crate::rules::action_rules::{
+    ActionRule, IdleBehavior, PerceptionRules, SpeciesRuleSet, SpeciesRules,
+};
 use crate::rules::value_dynamics::{SpeciesDynamics, TickDelta, ValueDynamicsRules, ValueEvent};
 use std::fs;
 use std::path::Path;
@@ -18,6 +20,9 @@ pub fn load_species_rules(species_dir: &Path) -> Result<SpeciesRules, String> {
         ("vampire_llm.toml", Species::Vampire),
         ("kobold.toml", Species::Kobold),
         ("human.toml", Species::Human),
+        ("orc.toml", Species::Orc),
+        ("dwarf.toml", Species::Dwarf),
+        ("elf.toml", Species::Elf),
         ("lizardfolk.toml", Species::Lizardfolk),
         ("hobgoblin.toml", Species::Hobgoblin),
         ("ogre.toml", Species::Ogre),
@@ -58,6 +63,9 @@ pub fn load_species_dynamics(species_dir: &Path) -> Result<ValueDynamicsRules, S
         ("vampire_llm.toml", Species::Vampire),
         ("kobold.toml", Species::Kobold),
         ("human.toml", Species::Human),
+        ("orc.toml", Species::Orc),
+        ("dwarf.toml", Species::Dwarf),
+        ("elf.toml", Species::Elf),
         ("lizardfolk.toml", Species::Lizardfolk),
         ("hobgoblin.toml", Species::Hobgoblin),
         ("ogre.toml", Species::Ogre),
@@ -161,9 +169,40 @@ fn parse_species_toml(content: &str, species: Species) -> Result<SpeciesRuleSet,
         }
     }
 
+    // Parse perception
+    if let Some(perception) = toml.get("perception") {
+        rule_set.perception = parse_perception(perception, species)?;
+    }
+
     Ok(rule_set)
 }
 
+fn parse_perception(value: &toml::Value, species: Species) -> Result<PerceptionRules, String> {
+    let range = value
+        .get("range")
+        .and_then(|v| v.as_float())
+        .map(|r| r as f32);
+
+    let mut dispositions = std::collections::HashMap::new();
+    if let Some(table) = value.get("dispositions").and_then(|v| v.as_table()) {
+        for (other, disposition) in table {
+            let other: Species = toml::Value::String(other.clone())
+                .try_into()
+                .map_err(|_| format!("{:?}: Unknown species '{}' in perception", species, other))?;
+            let disposition = disposition
+                .clone()
+                .try_into()
+                .map_err(|_| format!("{:?}: Unknown disposition for {:?}", species, other))?;
+            dispositions.insert(other, disposition);
+        }
+    }
+
+    Ok(PerceptionRules {
+        range,
+        dispositions,
+    })
+}
+
 fn parse_action_rule(value: &toml::Value, species: Species) -> Result<ActionRule, String> {
     let trigger_value = value
         .get("trigger_value")
@@ -272,6 +311,9 @@ fn parse_action_id(s: &str) -> Option<ActionId> {
         "Craft" => Some(ActionId::Craft),
         "Gather" => Some(ActionId::Gather),
         "Repair" => Some(ActionId::Repair),
+        "TendLivestock" => Some(ActionId::TendLivestock),
+        "Haul" => Some(ActionId::Haul),
+        "Drill" => Some(ActionId::Drill),
         "TalkTo" => Some(ActionId::TalkTo),
         "Help" => Some(ActionId::Help),
         "Trade" => Some(ActionId::Trade),
@@ -351,6 +393,33 @@ description = "Prowl territory seeking prey."
             // Check kobold rules loaded
             let kobold_rules = rules.get_action_rules(Species::Kobold);
             assert!(!kobold_rules.is_empty(), "Kobold should have action rules");
+
+            // Check orc rules and dynamics loaded
+            assert!(!rules.get_action_rules(Species::Orc).is_empty());
+            let dynamics = load_species_dynamics(species_dir).unwrap();
+            assert!(!dynamics.get_tick_deltas(Species::Orc).is_empty());
+            assert!(!dynamics
+                .get_events_for_type(Species::Orc, "clan_death")
+                .is_empty());
+
+            // Dwarf and elf rules and dynamics loaded
+            assert!(!rules.get_action_rules(Species::Dwarf).is_empty());
+            assert!(!rules.get_idle_behaviors(Species::Elf).is_empty());
+            assert_eq!(rules.perception_range(Species::Elf), 60.0);
+            assert_eq!(
+                rules.sighted_disposition(Species::Dwarf, Species::Orc),
+                Some(crate::entity::social::Disposition::Hostile)
+            );
+            assert_eq!(
+                rules.sighted_disposition(Species::Dwarf, Species::Human),
+                None
+            );
+            assert!(!dynamics
+                .get_events_for_type(Species::Dwarf, "kin_death")
+                .is_empty());
+            assert!(!dynamics
+                .get_events_for_type(Species::Elf, "kin_death")
+                .is_empty());
         }
     }
 
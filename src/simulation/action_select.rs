@@ -3,6 +3,7 @@
 use crate::actions::catalog::ActionId;
 use crate::city::building::BuildingId;
 use crate::core::types::{EntityId, Tick, Vec2};
+use crate::entity::aspirations::AspirationKind;
 use crate::entity::body::BodyState;
 use crate::entity::needs::{NeedType, Needs};
 use crate::entity::social::Disposition;
@@ -39,6 +40,7 @@ use crate::entity::tasks::{Task, TaskPriority, TaskSource};
 use crate::entity::thoughts::ThoughtBuffer;
 use crate::rules::SpeciesRules;
 use crate::simulation::rule_eval::{evaluate_action_rules, select_idle_behavior};
+use serde::{Deserialize, Serialize};
 
 /// Pick a talk target from perceived entities with disposition preference (E5)
 ///
@@ -227,6 +229,8 @@ pub struct SelectionContext<'a> {
     pub building_skill: f32,
     /// Nearest building under construction: (building_id, position, distance)
     pub nearest_building_site: Option<(BuildingId, Vec2, f32)>,
+    /// Long-term aspiration being pursued, if any
+    pub aspiration: Option<&'a AspirationKind>,
 }
 
 /// Main action selection function for humans
@@ -302,6 +306,101 @@ pub fn select_action_human(ctx: &SelectionContext) -> Option<Task> {
     Some(select_idle_action(ctx))
 }
 
+/// Stage of the human decision cascade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DecisionStage {
+    /// A threat in sight: fight or flee
+    Threat,
+    /// A need past its critical threshold
+    CriticalNeed,
+    /// Reacting to someone nearby by how they are regarded
+    Disposition,
+    /// A strong thought that touches a held value
+    ValueImpulse,
+    /// Purpose driving construction work
+    BuildingWork,
+    /// The most pressing need, before it turns critical
+    ModerateNeed,
+    /// Nothing pressing: aspiration or values pick a pastime
+    Idle,
+}
+
+/// What one stage of the cascade would have done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candidate {
+    pub stage: DecisionStage,
+    /// The action the stage proposes, if it proposes one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<ActionId>,
+    /// How strongly the stage is driven: the need level or thought
+    /// intensity behind it, 1.0 for a threat or someone nearby, 0.0 when idle
+    pub score: f32,
+}
+
+/// Evaluate every stage of `select_action_human`, in cascade order
+///
+/// The first candidate with an action is the one `select_action_human`
+/// chooses (for an entity with no current task); the rest show what it
+/// passed over. Used for decision traces, not on the hot path.
+pub fn evaluate_human(ctx: &SelectionContext) -> Vec<Candidate> {
+    let action = |task: Option<Task>| task.map(|t| t.action);
+    let threat = if ctx.threat_nearby {
+        if ctx.values.honor > 0.5 {
+            Some(ActionId::Attack)
+        } else {
+            action(select_critical_response(NeedType::Safety, ctx))
+        }
+    } else {
+        None
+    };
+    let critical = ctx.needs.has_critical();
+    let (_, level) = ctx.needs.most_pressing();
+
+    vec![
+        Candidate {
+            stage: DecisionStage::Threat,
+            action: threat,
+            score: if ctx.threat_nearby { 1.0 } else { 0.0 },
+        },
+        Candidate {
+            stage: DecisionStage::CriticalNeed,
+            action: critical.and_then(|need| action(select_critical_response(need, ctx))),
+            score: critical
+                .map(|need| match need {
+                    NeedType::Safety => ctx.needs.safety,
+                    NeedType::Food => ctx.needs.food,
+                    _ => ctx.needs.rest,
+                })
+                .unwrap_or(0.0),
+        },
+        Candidate {
+            stage: DecisionStage::Disposition,
+            action: action(check_disposition_response(ctx)),
+            score: if ctx.entity_nearby { 1.0 } else { 0.0 },
+        },
+        Candidate {
+            stage: DecisionStage::ValueImpulse,
+            action: action(check_value_impulses(ctx)),
+            score: ctx.thoughts.strongest().map(|t| t.intensity).unwrap_or(0.0),
+        },
+        Candidate {
+            stage: DecisionStage::BuildingWork,
+            action: action(should_seek_building_work(ctx)),
+            score: ctx.needs.purpose,
+        },
+        Candidate {
+            stage: DecisionStage::ModerateNeed,
+            action: action(address_moderate_need(ctx)),
+            score: level,
+        },
+        Candidate {
+            stage: DecisionStage::Idle,
+            action: Some(select_idle_action(ctx).action),
+            score: 0.0,
+        },
+    ]
+}
+
 /// Handle critical needs (> 0.8) with immediate responses
 fn select_critical_response(need: NeedType, ctx: &SelectionContext) -> Option<Task> {
     match need {
@@ -579,6 +678,11 @@ fn should_seek_building_work(ctx: &SelectionContext) -> Option<Task> {
         return None;
     }
 
+    // Overworked entities stay off the job until they have rested
+    if ctx.body.is_overworked() {
+        return None;
+    }
+
     // Need a building site within range
     let (building_id, pos, _distance) = ctx.nearest_building_site?;
 
@@ -604,15 +708,66 @@ fn should_seek_building_work(ctx: &SelectionContext) -> Option<Task> {
     }
 }
 
-/// Select an idle action based on the entity's values
+/// Purpose need above which idle entities work toward their aspiration
+const ASPIRATION_DRIVE: f32 = 0.3;
+
+/// Work toward a long-term aspiration, if there is a way to right now
+///
+/// - Master a craft: work the nearest building site
+/// - Found a family: talk to someone friendly nearby
+/// - Avenge a death: attack the killer if they are in sight
+/// - Earn a title: gather for the settlement
+fn pursue_aspiration(ctx: &SelectionContext) -> Option<Task> {
+    if ctx.needs.purpose < ASPIRATION_DRIVE {
+        return None;
+    }
+
+    match ctx.aspiration? {
+        AspirationKind::MasterCraft => {
+            let (building_id, pos, _) = ctx.nearest_building_site?;
+            Some(
+                Task::new(ActionId::Build, TaskPriority::Low, ctx.current_tick)
+                    .with_building(building_id)
+                    .with_position(pos),
+            )
+        }
+        AspirationKind::FoundFamily => ctx
+            .perceived_dispositions
+            .iter()
+            .find(|(_, d)| matches!(d, Disposition::Favorable | Disposition::Friendly))
+            .map(|&(target, _)| {
+                Task::new(ActionId::TalkTo, TaskPriority::Low, ctx.current_tick).with_entity(target)
+            }),
+        AspirationKind::Avenge { wrongdoer, .. } => ctx
+            .perceived_dispositions
+            .iter()
+            .any(|(id, _)| id == wrongdoer)
+            .then(|| {
+                Task::new(ActionId::Attack, TaskPriority::Normal, ctx.current_tick)
+                    .with_entity(*wrongdoer)
+            }),
+        AspirationKind::EarnTitle => Some(Task::new(
+            ActionId::Gather,
+            TaskPriority::Low,
+            ctx.current_tick,
+        )),
+    }
+}
+
+/// Select an idle action based on the entity's aspiration and values
 ///
-/// Different values lead to different idle behaviors:
+/// An aspiration comes first whenever there is a way to work toward it.
+/// Otherwise different values lead to different idle behaviors:
 /// - High curiosity (> 0.6): observe surroundings (E5 behavior)
 /// - High ambition/purpose: gather resources or work
 /// - High comfort: stay put and observe
 /// - High social + entity nearby + social need > 0.4: talk
 /// - Default: wander
 fn select_idle_action(ctx: &SelectionContext) -> Task {
+    if let Some(task) = pursue_aspiration(ctx) {
+        return task;
+    }
+
     // E5: High curiosity entities (> 0.6) should strongly prefer IdleObserve
     // This creates clear correlation between curiosity and exploration
     if ctx.values.curiosity > 0.6 {
@@ -5461,6 +5616,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5493,6 +5649,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5524,6 +5681,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5561,6 +5719,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5594,6 +5753,7 @@ mod tests {
             perceived_dispositions: vec![(EntityId::new(), Disposition::Neutral)],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5629,6 +5789,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5665,6 +5826,7 @@ mod tests {
             perceived_dispositions: vec![(EntityId::new(), Disposition::Friendly)],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5696,6 +5858,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5736,6 +5899,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5768,6 +5932,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         // Critical needs should still trigger even with existing task
@@ -5803,6 +5968,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5843,6 +6009,7 @@ mod tests {
             perceived_dispositions: vec![(hostile_entity, Disposition::Hostile)],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5879,6 +6046,7 @@ mod tests {
             perceived_dispositions: vec![(suspicious_entity, Disposition::Suspicious)],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5915,6 +6083,7 @@ mod tests {
             perceived_dispositions: vec![(friendly_entity, Disposition::Friendly)],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5952,6 +6121,7 @@ mod tests {
             perceived_dispositions: vec![(favorable_entity, Disposition::Favorable)],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -5993,6 +6163,7 @@ mod tests {
             ],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6028,6 +6199,7 @@ mod tests {
             perceived_dispositions: vec![(hostile_entity, Disposition::Hostile)],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6063,6 +6235,7 @@ mod tests {
             perceived_dispositions: vec![(friendly_entity, Disposition::Friendly)],
             building_skill: 0.0,
             nearest_building_site: None,
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6104,6 +6277,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.5,
             nearest_building_site: Some((building_id, building_pos, 20.0)),
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6143,6 +6317,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.5,
             nearest_building_site: Some((building_id, building_pos, 20.0)),
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6175,6 +6350,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.5,
             nearest_building_site: None, // No building site
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6212,6 +6388,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0, // Zero skill
             nearest_building_site: Some((building_id, building_pos, 20.0)),
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6249,6 +6426,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 0.0, // Zero skill
             nearest_building_site: Some((building_id, building_pos, 20.0)),
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6286,6 +6464,7 @@ mod tests {
             perceived_dispositions: vec![],
             building_skill: 1.0, // Max skill
             nearest_building_site: Some((building_id, building_pos, 20.0)),
+            aspiration: None,
         };
 
         let task = select_action_human(&ctx);
@@ -6296,6 +6475,41 @@ mod tests {
         assert_eq!(task.action, ActionId::Build);
     }
 
+    #[test]
+    fn test_aspiring_crafter_builds_when_idle() {
+        let body = BodyState::new();
+        let mut needs = Needs::default();
+        needs.purpose = 0.4; // Too low to seek building work on its own
+        let thoughts = ThoughtBuffer::new();
+        let values = HumanValues::default();
+        let building_id = BuildingId::new();
+        let aspiration = AspirationKind::MasterCraft;
+
+        let mut ctx = SelectionContext {
+            body: &body,
+            needs: &needs,
+            thoughts: &thoughts,
+            values: &values,
+            has_current_task: false,
+            threat_nearby: false,
+            food_available: true,
+            safe_location: true,
+            entity_nearby: false,
+            current_tick: 0,
+            nearest_food_zone: None,
+            perceived_dispositions: vec![],
+            building_skill: 0.0,
+            nearest_building_site: Some((building_id, Vec2::new(10.0, 10.0), 20.0)),
+            aspiration: None,
+        };
+        assert_ne!(select_action_human(&ctx).unwrap().action, ActionId::Build);
+
+        ctx.aspiration = Some(&aspiration);
+        let task = select_action_human(&ctx).unwrap();
+        assert_eq!(task.action, ActionId::Build);
+        assert_eq!(task.target_building, Some(building_id));
+    }
+
     // ========================================================================
     // ORC ACTION SELECTION TESTS
     // ========================================================================
@@ -6328,7 +6542,8 @@ mod tests {
         let task = select_action_orc(&ctx);
         assert!(task.is_some());
         let task = task.unwrap();
-        assert_eq!(task.action, ActionId::Defend);
+        assert_eq!(task.action, ActionId::Attack);
+        assert_eq!(task.target_entity, Some(target_entity));
         assert_eq!(task.priority, TaskPriority::High);
     }
 
@@ -6338,6 +6553,7 @@ mod tests {
         let needs = Needs::default();
         let thoughts = ThoughtBuffer::new();
         let mut values = OrcValues::default();
+        values.rage = 0.0; // Calm, so only the debt drives it
         values.blood_debt = 0.8; // High blood debt
 
         let hostile_entity = EntityId::new();
@@ -6360,8 +6576,9 @@ mod tests {
         let task = select_action_orc(&ctx);
         assert!(task.is_some());
         let task = task.unwrap();
-        assert_eq!(task.action, ActionId::Defend);
+        assert_eq!(task.action, ActionId::Attack);
         assert_eq!(task.target_entity, Some(hostile_entity));
+        assert_eq!(task.priority, TaskPriority::High);
     }
 
     #[test]
@@ -6433,6 +6650,7 @@ mod tests {
         let needs = Needs::default();
         let thoughts = ThoughtBuffer::new();
         let mut values = OrcValues::default();
+        values.rage = 0.0; // Calm, so only loyalty drives it
         values.clan_loyalty = 0.9; // High clan loyalty
 
         let friendly_entity = EntityId::new();
@@ -6459,9 +6677,10 @@ mod tests {
         let task = select_action_orc(&ctx);
         assert!(task.is_some());
         let task = task.unwrap();
-        // Loyal orc defends clan member
-        assert_eq!(task.action, ActionId::Defend);
+        // Loyal orc strikes the enemy of a clan member
+        assert_eq!(task.action, ActionId::Attack);
         assert_eq!(task.target_entity, Some(hostile_entity));
+        assert_eq!(task.priority, TaskPriority::High);
     }
 
     #[test]
@@ -0,0 +1,486 @@
+//! Aftermath - bringing a finished battle home to the settlement
+//!
+//! The tactical battle only counts casualties per unit. Once it is over
+//! (`battle::check_battle_end` has decided it), `bring_home` turns those
+//! counts into what happened to the people in the settlement's army, the
+//! friendly side:
+//!
+//! - Each unit's casualties fall on members picked at random. Some of them
+//!   die as any settler does; the rest come home with a wound.
+//! - Survivors learn from the fight: each records experiences of the
+//!   skills their kind of unit used, leaders and officers of command as
+//!   well, and `skills::learning` consolidates them. The beaten learn less.
+//! - The winner takes prisoners from the loser's routed units. Settlers
+//!   taken are held by the enemy; enemies taken are held by the settlement.
+//! - After a win, the gear of enemy dead and prisoners goes to the
+//!   stockpile.
+//! - Everyone else is recalled home, and the chronicle records the toll.
+//!
+//! Soldiers raised for the battle who are not settlers are only counted.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::battle::{BattleOutcome, BattleState, BattleUnit, UnitType};
+use crate::combat::resolution::resolve_hit;
+use crate::combat::{BodyZone, WeaponProperties, WoundSeverity};
+use crate::core::types::{EntityId, PolityId};
+use crate::ecs::world::World;
+use crate::entity::inventory::Item;
+use crate::simulation::legacy::record_death;
+use crate::simulation::transformations::note_head_wound;
+use crate::skills::learning::process_learning;
+use crate::skills::{ChunkId, Experience};
+
+/// Share of a unit's casualties who died; the rest were wounded
+pub const CASUALTY_DEATH_SHARE: f32 = 0.4;
+
+/// Experiences of each battle skill a survivor takes home
+pub const BATTLE_EXPERIENCES: u32 = 6;
+
+/// Share of a beaten side's routed survivors taken prisoner
+pub const PRISONER_SHARE: f32 = 0.5;
+
+/// Chance each enemy dead or prisoner's gear is carried home after a win
+pub const SPOILS_CHANCE: f32 = 0.5;
+
+/// What a battle meant for the settlement
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aftermath {
+    /// Settlers killed
+    pub fallen: Vec<EntityId>,
+    /// Settlers who came home wounded
+    pub wounded: Vec<EntityId>,
+    /// Settlers taken prisoner by the enemy
+    pub taken: Vec<EntityId>,
+    /// Enemies taken prisoner
+    pub prisoners: Vec<EntityId>,
+    /// Casualties among soldiers who are not settlers
+    pub unnamed_casualties: u32,
+    /// Settlers who learned from the fight
+    pub veterans: usize,
+    /// Items carried home to the stockpile
+    pub spoils: usize,
+}
+
+/// Which side, if either, won
+fn victor(outcome: BattleOutcome) -> Option<bool> {
+    match outcome {
+        BattleOutcome::DecisiveVictory | BattleOutcome::Victory | BattleOutcome::PyrrhicVictory => {
+            Some(true)
+        }
+        BattleOutcome::Defeat | BattleOutcome::DecisiveDefeat => Some(false),
+        _ => None,
+    }
+}
+
+/// The skills a kind of unit fights with
+fn battle_skills(unit_type: UnitType) -> &'static [ChunkId] {
+    if unit_type.is_ranged() {
+        &[ChunkId::DrawBow, ChunkId::BasicAim, ChunkId::LooseArrow]
+    } else if unit_type.is_mounted() {
+        &[ChunkId::BasicSwing, ChunkId::PhysMountedCombat]
+    } else {
+        &[
+            ChunkId::BasicSwing,
+            ChunkId::BasicBlock,
+            ChunkId::EngageMelee,
+        ]
+    }
+}
+
+/// The skills of those leading in battle
+const COMMAND_SKILLS: &[ChunkId] = &[ChunkId::LeadRallyWavering, ChunkId::LeadBattleManagement];
+
+/// The gear a kind of unit carries, by item name
+fn gear(unit_type: UnitType) -> &'static [&'static str] {
+    match unit_type {
+        UnitType::HeavyInfantry | UnitType::HeavyCavalry => &["sword", "mail"],
+        UnitType::Spearmen => &["spear", "leather_armor"],
+        UnitType::Levy => &["club"],
+        UnitType::Engineers => &["axe"],
+        UnitType::Archers
+        | UnitType::Crossbowmen
+        | UnitType::HorseArchers
+        | UnitType::Scouts
+        | UnitType::MountedScouts
+        | UnitType::SupplyWagons => &["dagger"],
+        _ => &["sword", "leather_armor"],
+    }
+}
+
+/// Tiredness a wound brings
+fn wound_fatigue(severity: WoundSeverity) -> f32 {
+    match severity {
+        WoundSeverity::None => 0.0,
+        WoundSeverity::Scratch => 0.05,
+        WoundSeverity::Minor => 0.1,
+        WoundSeverity::Serious => 0.2,
+        WoundSeverity::Critical => 0.4,
+        WoundSeverity::Destroyed => 0.6,
+    }
+}
+
+/// A unit's members split into its casualties and its survivors
+fn split_casualties<R: Rng>(unit: &BattleUnit, rng: &mut R) -> (Vec<EntityId>, Vec<EntityId>) {
+    let mut members: Vec<EntityId> = unit
+        .elements
+        .iter()
+        .flat_map(|e| e.entities.iter().copied())
+        .collect();
+    members.shuffle(rng);
+    let survivors = members.split_off((unit.casualties as usize).min(members.len()));
+    (members, survivors)
+}
+
+/// Those of a unit's survivors taken prisoner, if it broke on the losing side
+fn prisoners_from(unit: &BattleUnit, survivors: &[EntityId]) -> Vec<EntityId> {
+    if !unit.is_broken() {
+        return Vec::new();
+    }
+    let taken = (survivors.len() as f32 * PRISONER_SHARE).round() as usize;
+    survivors[..taken].to_vec()
+}
+
+/// Settler `i` came out of the fight wounded by an enemy's weapon
+fn wound_settler<R: Rng>(
+    world: &mut World,
+    i: usize,
+    unit_type: UnitType,
+    weapon: &WeaponProperties,
+    rng: &mut R,
+) {
+    let zones = BodyZone::all();
+    let zone = zones[rng.gen_range(0..zones.len())];
+    let armor = unit_type.default_properties().avg_armor;
+    let mut wound = resolve_hit(weapon, &armor, zone);
+    // Counted among the casualties: whatever hit them did harm
+    wound.severity = wound
+        .severity
+        .clamp(WoundSeverity::Minor, WoundSeverity::Critical);
+    let body = &mut world.humans.body_states[i];
+    body.fatigue = (body.fatigue + wound_fatigue(wound.severity)).min(1.0);
+    let id = world.humans.ids[i];
+    note_head_wound(&mut world.transformations, id, &wound, world.current_tick);
+    world.humans.combat_states[i].wounds.push(wound);
+}
+
+/// Record what settler `i` practised in the fight, and let it sink in
+fn learn_from_battle(world: &mut World, i: usize, skills: &[ChunkId], won: bool) {
+    let tick = world.current_tick;
+    let library = &mut world.humans.chunk_libraries[i];
+    for &chunk_id in skills {
+        for n in 0..BATTLE_EXPERIENCES {
+            library.record_experience(Experience {
+                chunk_id,
+                // The beaten got half of it right
+                success: won || n % 2 == 0,
+                tick,
+            });
+        }
+    }
+    process_learning(library, tick);
+}
+
+/// Bring a finished battle home; `foe` holds the settlers it took
+pub fn bring_home<R: Rng>(
+    world: &mut World,
+    battle: &BattleState,
+    foe: Option<PolityId>,
+    rng: &mut R,
+) -> Aftermath {
+    let tick = world.current_tick;
+    let won = victor(battle.outcome);
+    let learned_well = won != Some(false);
+    let mut aftermath = Aftermath::default();
+
+    let enemy_weapons: Vec<WeaponProperties> = battle
+        .enemy_army
+        .formations
+        .iter()
+        .flat_map(|f| f.units.iter())
+        .map(|u| u.unit_type.default_properties().avg_weapon)
+        .collect();
+
+    for formation in &battle.friendly_army.formations {
+        for officer in formation.officers.iter().filter(|o| o.killed) {
+            match world.humans.index_of(officer.entity) {
+                Some(i) if world.humans.alive[i] => {
+                    world.humans.alive[i] = false;
+                    record_death(world, i, None);
+                    aftermath.fallen.push(officer.entity);
+                }
+                _ => {
+                    world.identities.record_death(officer.entity, tick);
+                }
+            }
+        }
+
+        for unit in &formation.units {
+            let (casualties, survivors) = split_casualties(unit, rng);
+            for id in casualties {
+                let Some(i) = living(world, id) else {
+                    aftermath.unnamed_casualties += 1;
+                    continue;
+                };
+                if rng.gen::<f32>() < CASUALTY_DEATH_SHARE {
+                    world.humans.alive[i] = false;
+                    record_death(world, i, None);
+                    aftermath.fallen.push(id);
+                } else {
+                    let weapon = enemy_weapons
+                        .choose(rng)
+                        .cloned()
+                        .unwrap_or_else(WeaponProperties::sword);
+                    wound_settler(world, i, unit.unit_type, &weapon, rng);
+                    aftermath.wounded.push(id);
+                }
+            }
+
+            let taken = if won == Some(false) {
+                prisoners_from(unit, &survivors)
+            } else {
+                Vec::new()
+            };
+            for &id in &taken {
+                // Already dead or held: nothing more to take
+                let _ = world.identities.capture(id, foe, tick);
+            }
+            let free = survivors.into_iter().filter(|id| !taken.contains(id));
+            for id in free {
+                if let Some(i) = living(world, id) {
+                    learn_from_battle(world, i, battle_skills(unit.unit_type), learned_well);
+                    aftermath.veterans += 1;
+                }
+            }
+            aftermath.taken.extend(taken);
+        }
+
+        let leaders = formation
+            .units
+            .iter()
+            .filter_map(|u| u.leader)
+            .chain(formation.officers.iter().map(|o| o.entity));
+        let mut led: Vec<EntityId> = Vec::new();
+        for id in leaders {
+            if led.contains(&id) {
+                continue;
+            }
+            led.push(id);
+            if let Some(i) = living(world, id) {
+                learn_from_battle(world, i, COMMAND_SKILLS, learned_well);
+            }
+        }
+    }
+
+    // Everyone else deployed comes home; the dead and held stay where they are
+    for formation in &battle.friendly_army.formations {
+        let officers = formation.officers.iter().map(|o| o.entity);
+        for unit in &formation.units {
+            let members = unit
+                .elements
+                .iter()
+                .flat_map(|e| e.entities.iter().copied());
+            for id in unit.leader.into_iter().chain(members) {
+                let _ = world.identities.recall(id, tick);
+            }
+        }
+        for id in officers {
+            let _ = world.identities.recall(id, tick);
+        }
+    }
+
+    if won == Some(true) {
+        for unit in battle
+            .enemy_army
+            .formations
+            .iter()
+            .flat_map(|f| f.units.iter())
+        {
+            let (_, survivors) = split_casualties(unit, rng);
+            let taken = prisoners_from(unit, &survivors);
+            for &id in &taken {
+                // Held by the settlement, which is no polity to name
+                let _ = world.identities.capture(id, None, tick);
+            }
+
+            let stripped = (unit.casualties as usize).min(unit.strength()) + taken.len();
+            for _ in 0..stripped {
+                if rng.gen::<f32>() >= SPOILS_CHANCE {
+                    continue;
+                }
+                for item in gear(unit.unit_type)
+                    .iter()
+                    .filter_map(|name| Item::named(name))
+                {
+                    world.stockpile.store_item(item);
+                    aftermath.spoils += 1;
+                }
+            }
+            aftermath.prisoners.extend(taken);
+        }
+    }
+
+    let verdict = match won {
+        Some(true) => "won",
+        Some(false) => "lost",
+        None => "over",
+    };
+    world.legacy.record(
+        tick,
+        format!(
+            "The battle was {}, at a cost of {} dead and {} wounded; {} of ours were taken, and {} of theirs",
+            verdict,
+            aftermath.fallen.len(),
+            aftermath.wounded.len(),
+            aftermath.taken.len(),
+            aftermath.prisoners.len()
+        ),
+    );
+    aftermath
+}
+
+/// Index of the living settler `id`, if they are one
+fn living(world: &World, id: EntityId) -> Option<usize> {
+    world.humans.index_of(id).filter(|&i| world.humans.alive[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::{
+        Army, ArmyId, BattleFormation, BattleMap, Element, FormationId, UnitId, UnitStance,
+    };
+    use crate::skills::PersonalChunkState;
+    use crate::world::{Fate, Layer};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn army(unit: BattleUnit) -> Army {
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        formation.units.push(unit);
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        army.formations.push(formation);
+        army
+    }
+
+    /// Four settlers in a unit of infantry, deployed, each knowing how to swing
+    fn settlers_unit(world: &mut World) -> BattleUnit {
+        let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        let ids: Vec<EntityId> = ["Ada", "Bram", "Cole", "Dara"]
+            .into_iter()
+            .map(|name| world.spawn_human(name.into()))
+            .collect();
+        for i in 0..ids.len() {
+            world.humans.chunk_libraries[i]
+                .set_chunk(ChunkId::BasicSwing, PersonalChunkState::new(0));
+        }
+        unit.elements.push(Element::new(ids));
+        world.identities.deploy(&unit, 0);
+        unit
+    }
+
+    fn swings(world: &World, id: EntityId) -> u32 {
+        let i = world.humans.index_of(id).unwrap();
+        world.humans.chunk_libraries[i]
+            .get_chunk(ChunkId::BasicSwing)
+            .unwrap()
+            .repetition_count
+    }
+
+    #[test]
+    fn test_victory_brings_home_wounds_lessons_prisoners_and_spoils() {
+        let mut world = World::with_seed(7);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut ours = settlers_unit(&mut world);
+        ours.casualties = 2;
+        let members = ours.elements[0].entities.clone();
+
+        let orcs: Vec<EntityId> = (0..6)
+            .map(|n| world.spawn_orc(format!("Orc {}", n)))
+            .collect();
+        let mut theirs = BattleUnit::new(UnitId::new(), UnitType::HeavyInfantry);
+        theirs.elements.push(Element::new(orcs));
+        theirs.casualties = 2;
+        theirs.stance = UnitStance::Routing;
+
+        let mut battle = BattleState::new(BattleMap::new(10, 10), army(ours), army(theirs));
+        battle.outcome = BattleOutcome::Victory;
+        let aftermath = bring_home(&mut world, &battle, None, &mut rng);
+
+        // The two casualties died or came home hurt; the other two learned
+        assert_eq!(aftermath.fallen.len() + aftermath.wounded.len(), 2);
+        assert_eq!(aftermath.veterans, 2);
+        for &id in &aftermath.wounded {
+            let i = world.humans.index_of(id).unwrap();
+            assert!(world.humans.alive[i]);
+            assert_eq!(world.humans.combat_states[i].wounds.len(), 1);
+        }
+        for &id in &aftermath.fallen {
+            assert!(matches!(
+                world.identities.get(id).unwrap().fate,
+                Fate::Dead { .. }
+            ));
+        }
+        let veterans: Vec<EntityId> = members
+            .iter()
+            .copied()
+            .filter(|id| !aftermath.fallen.contains(id) && !aftermath.wounded.contains(id))
+            .collect();
+        for &id in &veterans {
+            assert_eq!(swings(&world, id), 1 + BATTLE_EXPERIENCES);
+            assert_eq!(world.identities.get(id).unwrap().layer(), Layer::Settlement);
+        }
+
+        // Half the routed survivors were taken, and stripped with the dead
+        assert_eq!(aftermath.prisoners.len(), 2);
+        for &id in &aftermath.prisoners {
+            assert!(matches!(
+                world.identities.get(id).unwrap().fate,
+                Fate::Captured { captor: None, .. }
+            ));
+        }
+        assert!(aftermath.taken.is_empty());
+        assert_eq!(world.stockpile.items().len(), aftermath.spoils);
+        assert!(world
+            .legacy
+            .chronicle
+            .iter()
+            .any(|e| e.text.starts_with("The battle was won")));
+    }
+
+    #[test]
+    fn test_defeat_leaves_settlers_captive_and_teaches_less() {
+        let mut world = World::with_seed(8);
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        let mut ours = settlers_unit(&mut world);
+        ours.stance = UnitStance::Routing;
+        let members = ours.elements[0].entities.clone();
+        let theirs = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+
+        let mut battle = BattleState::new(BattleMap::new(10, 10), army(ours), army(theirs));
+        battle.outcome = BattleOutcome::Defeat;
+        let foe = Some(PolityId(3));
+        let aftermath = bring_home(&mut world, &battle, foe, &mut rng);
+
+        assert_eq!(aftermath.taken.len(), 2);
+        assert!(aftermath.prisoners.is_empty());
+        assert_eq!(aftermath.spoils, 0);
+        for id in members {
+            let identity = world.identities.get(id).unwrap();
+            if aftermath.taken.contains(&id) {
+                assert_eq!(
+                    identity.fate,
+                    Fate::Captured {
+                        captor: foe,
+                        since: 0
+                    }
+                );
+                assert_eq!(swings(&world, id), 1);
+            } else {
+                assert_eq!(identity.layer(), Layer::Settlement);
+                assert_eq!(swings(&world, id), 1 + BATTLE_EXPERIENCES / 2);
+            }
+        }
+    }
+}
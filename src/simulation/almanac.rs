@@ -0,0 +1,416 @@
+//! Weather lore, forecasts, and the almanac
+//!
+//! The settlement's weather is rolled once a day from the world seed, so the
+//! coming days are already settled - the question is who can read them.
+//! Everyone builds up lore from the days they spend watching the sky, and
+//! scholars (Knowledge chunks) read the signs better, up to the depth their
+//! species can master Knowledge at all. A forecast holds with its stated
+//! confidence, which falls off the further ahead it looks; when it misses,
+//! it names the season's likeliest other weather instead. Moon phases are
+//! plain to anyone who keeps count, but double moons and eclipses only make
+//! the almanac of a skilled forecaster.
+//!
+//! Storms flatten half-grown crops. If the settlement's best forecaster saw
+//! a storm coming the day before, the farms are made ready and nothing is
+//! lost.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::actions::catalog::ActionId;
+use crate::campaign::weather::{Season, Weather, SEASONAL_WEATHER};
+use crate::city::building::{BuildingState, BuildingType};
+use crate::core::astronomy::{AstronomicalState, CelestialEvent, YEAR_LENGTH};
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::skills::{ChunkDomain, ChunkLibrary, SpeciesChunkModifiers};
+
+/// Forecast accuracy with no lore or learning at all
+pub const NAIVE_ACCURACY: f32 = 0.3;
+
+/// Accuracy added by deep Knowledge chunks
+pub const KNOWLEDGE_WEIGHT: f32 = 0.35;
+
+/// Accuracy added by a lifetime of watching the sky
+pub const LORE_WEIGHT: f32 = 0.3;
+
+/// Days of sky-watching for full lore
+pub const LORE_DAYS: u32 = 720;
+
+/// Confidence kept for each further day ahead
+pub const LEAD_DECAY: f32 = 0.85;
+
+/// Accuracy needed to foretell double moons and eclipses
+pub const ECLIPSE_LORE: f32 = 0.6;
+
+/// Accuracy needed to foretell the rare alignments
+pub const RARE_LORE: f32 = 0.8;
+
+/// Confidence at which the settlement acts on a storm warning
+pub const HEED_CONFIDENCE: f32 = 0.5;
+
+/// Share of a farm's growing crop lost to an unforeseen storm
+pub const STORM_CROP_LOSS: f32 = 0.5;
+
+/// Days shown in the almanac
+pub const ALMANAC_DAYS: u32 = 5;
+
+/// Whether a weather flattens crops
+pub fn is_storm(weather: Weather) -> bool {
+    matches!(weather, Weather::HeavyRain | Weather::Blizzard)
+}
+
+/// Seed for the weather roll on a given day
+pub fn weather_seed(world_seed: u64, day: u32) -> u64 {
+    world_seed ^ (day as u64 + 1).wrapping_mul(0xD1B5_4A32_D192_ED03)
+}
+
+/// How reliably someone reads the weather (0.0-1.0)
+pub fn forecast_accuracy(
+    library: &ChunkLibrary,
+    observations: u32,
+    species: &SpeciesChunkModifiers,
+) -> f32 {
+    // Sorted so the sum does not depend on map order
+    let mut depths: Vec<f32> = library
+        .chunks()
+        .iter()
+        .filter(|(id, _)| id.domain() == ChunkDomain::Knowledge)
+        .map(|(_, state)| state.encoding_depth)
+        .collect();
+    depths.sort_by(f32::total_cmp);
+    let knowledge = depths.iter().sum::<f32>().min(1.0);
+    let lore = (observations as f32 / LORE_DAYS as f32).min(1.0);
+    let ceiling = species.get_domain(ChunkDomain::Knowledge).max_encoding;
+    (NAIVE_ACCURACY + KNOWLEDGE_WEIGHT * knowledge + LORE_WEIGHT * lore).min(ceiling)
+}
+
+/// The season's likeliest weather other than `not`
+fn likeliest_other(season: Season, not: Weather) -> Weather {
+    SEASONAL_WEATHER
+        .iter()
+        .zip(season.weather_weights())
+        .filter(|(w, _)| **w != not)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(w, _)| *w)
+        .unwrap_or(Weather::Clear)
+}
+
+/// One forecast weather, with how sure of it the forecaster is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeatherForecast {
+    pub weather: Weather,
+    pub confidence: f32,
+}
+
+/// Forecast `truth` (the coming days' weather, starting tomorrow)
+///
+/// `seed` identifies the forecaster: the same forecaster forecasting the
+/// same day gets it right or wrong the same way every time they look.
+pub fn forecast_weather(
+    truth: &[Weather],
+    today: u32,
+    accuracy: f32,
+    seed: u64,
+) -> Vec<WeatherForecast> {
+    truth
+        .iter()
+        .enumerate()
+        .map(|(lead, &actual)| {
+            let day = today + 1 + lead as u32;
+            let confidence = accuracy * LEAD_DECAY.powi(lead as i32);
+            let mut rng = ChaCha8Rng::seed_from_u64(weather_seed(seed, day));
+            let weather = if rng.gen::<f32>() < confidence {
+                actual
+            } else {
+                likeliest_other(Season::from_day(day % 360), actual)
+            };
+            WeatherForecast {
+                weather,
+                confidence,
+            }
+        })
+        .collect()
+}
+
+/// How sure a forecaster of this accuracy is of a celestial event, or None
+/// if it is beyond them
+pub fn event_confidence(event: CelestialEvent, accuracy: f32) -> Option<f32> {
+    if event.is_common() {
+        Some(1.0)
+    } else if event.is_rare() {
+        (accuracy >= RARE_LORE).then_some(accuracy)
+    } else {
+        (accuracy >= ECLIPSE_LORE).then_some(accuracy)
+    }
+}
+
+/// One day of the almanac
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlmanacDay {
+    /// Days from today
+    pub ahead: u32,
+    pub weather: WeatherForecast,
+    pub events: Vec<(CelestialEvent, f32)>,
+}
+
+/// The settlement's best reading of the days ahead
+#[derive(Debug, Clone, PartialEq)]
+pub struct Almanac {
+    /// Who wrote it (None with nobody to ask)
+    pub forecaster: Option<(EntityId, String)>,
+    pub accuracy: f32,
+    pub today: Weather,
+    pub days: Vec<AlmanacDay>,
+}
+
+impl Almanac {
+    /// The almanac as text, one line per day
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![match &self.forecaster {
+            Some((_, name)) => format!(
+                "Kept by {} (reads the sky {:.0}% true)",
+                name,
+                self.accuracy * 100.0
+            ),
+            None => "Nobody keeps the almanac".to_string(),
+        }];
+        lines.push(format!("Today: {:?}", self.today));
+        for day in &self.days {
+            let mut line = format!(
+                "+{}d: {:?} ({:.0}% sure)",
+                day.ahead,
+                day.weather.weather,
+                day.weather.confidence * 100.0
+            );
+            for (event, confidence) in &day.events {
+                line.push_str(&format!(", {:?} ({:.0}%)", event, confidence * 100.0));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+/// The living human who reads the weather best, with their accuracy
+pub fn best_forecaster(world: &World) -> Option<(usize, f32)> {
+    let species = SpeciesChunkModifiers::human();
+    world
+        .humans
+        .iter_living()
+        .map(|i| {
+            let accuracy = forecast_accuracy(
+                &world.humans.chunk_libraries[i],
+                world.humans.sky_observations[i],
+                &species,
+            );
+            (i, accuracy)
+        })
+        .fold(
+            None,
+            |best: Option<(usize, f32)>, (i, accuracy)| match best {
+                Some((_, best_accuracy)) if best_accuracy >= accuracy => best,
+                _ => Some((i, accuracy)),
+            },
+        )
+}
+
+/// Forecaster seed for a human
+fn forecaster_seed(world: &World, i: usize) -> u64 {
+    world.seed ^ world.humans.ids[i].0.as_u64_pair().0
+}
+
+/// The coming days' weather, starting tomorrow
+fn outlook(world: &World, days: u32) -> Vec<Weather> {
+    let seed = world.seed;
+    world
+        .weather
+        .outlook(world.astronomy.current_day, days, |day| {
+            weather_seed(seed, day)
+        })
+}
+
+/// Write the almanac for the next `days` days
+pub fn almanac(world: &World, days: u32) -> Almanac {
+    let today = world.astronomy.current_day;
+    let truth = outlook(world, days);
+    let forecaster = best_forecaster(world);
+    let (weather, accuracy) = match forecaster {
+        Some((i, accuracy)) => (
+            forecast_weather(&truth, today, accuracy, forecaster_seed(world, i)),
+            accuracy,
+        ),
+        // With nobody to ask, all there is to go on is the season
+        None => (
+            (1..=days)
+                .map(|ahead| {
+                    let season = Season::from_day((today + ahead) % 360);
+                    let (weather, weight) = SEASONAL_WEATHER
+                        .iter()
+                        .zip(season.weather_weights())
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                        .unwrap();
+                    WeatherForecast {
+                        weather: *weather,
+                        confidence: weight,
+                    }
+                })
+                .collect(),
+            0.0,
+        ),
+    };
+
+    let days = weather
+        .into_iter()
+        .zip(1..)
+        .map(|(weather, ahead)| AlmanacDay {
+            ahead,
+            weather,
+            events: AstronomicalState::events_for_day(today + ahead)
+                .into_iter()
+                .filter_map(|event| event_confidence(event, accuracy).map(|c| (event, c)))
+                .collect(),
+        })
+        .collect();
+
+    Almanac {
+        forecaster: forecaster.map(|(i, _)| (world.humans.ids[i], world.humans.names[i].clone())),
+        accuracy,
+        today: world.weather.current_weather,
+        days,
+    }
+}
+
+/// What one day of weather did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WeatherReport {
+    pub storm: bool,
+    /// Whether the storm was foreseen and the farms made ready
+    pub foreseen: bool,
+    pub farms_damaged: usize,
+}
+
+/// Roll today's weather, add a day of sky-watching for everyone, and let
+/// any storm loose on the crops
+pub fn run_weather(world: &mut World) -> WeatherReport {
+    let mut report = WeatherReport::default();
+    let today = world.astronomy.current_day;
+    let seed = weather_seed(world.seed, today);
+    world.weather.update(1.0, today % YEAR_LENGTH as u32, seed);
+
+    // Idle watchers learn twice as much as those busy at something else
+    let living: Vec<usize> = world.humans.iter_living().collect();
+    for &i in &living {
+        let watching = world.humans.task_queues[i]
+            .current()
+            .map(|t| t.action == ActionId::IdleObserve)
+            .unwrap_or(false);
+        world.humans.sky_observations[i] += if watching { 2 } else { 1 };
+    }
+
+    if !is_storm(world.weather.current_weather) {
+        return report;
+    }
+    report.storm = true;
+
+    // Did yesterday's almanac see it coming?
+    let warned_by = best_forecaster(world).filter(|&(i, accuracy)| {
+        let yesterday = today.saturating_sub(1);
+        let forecast = forecast_weather(
+            &[world.weather.current_weather],
+            yesterday,
+            accuracy,
+            forecaster_seed(world, i),
+        );
+        is_storm(forecast[0].weather) && forecast[0].confidence >= HEED_CONFIDENCE
+    });
+    if let Some((i, _)) = warned_by {
+        report.foreseen = true;
+        world.humans.thoughts[i].add(Thought::new(
+            Valence::Positive,
+            0.5,
+            "foresight",
+            format!("foresaw the {:?}", world.weather.current_weather),
+            CauseType::Event,
+            world.current_tick,
+        ));
+        return report;
+    }
+
+    for b in 0..world.buildings.count() {
+        let growing = world.buildings.building_types[b] == BuildingType::Farm
+            && world.buildings.states[b] == BuildingState::Complete
+            && world.buildings.production_progress[b] > 0.0;
+        if growing {
+            world.buildings.production_progress[b] *= 1.0 - STORM_CROP_LOSS;
+            report.farms_damaged += 1;
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::{ChunkId, PersonalChunkState};
+
+    #[test]
+    fn test_accuracy_grows_with_knowledge_and_lore() {
+        let human = SpeciesChunkModifiers::human();
+        let mut library = ChunkLibrary::new();
+        let novice = forecast_accuracy(&library, 0, &human);
+        assert_eq!(novice, NAIVE_ACCURACY);
+
+        let mut state = PersonalChunkState::new(0);
+        state.encoding_depth = 0.9;
+        library.set_chunk(ChunkId::KnowArithmetic, state.clone());
+        library.set_chunk(ChunkId::KnowMemorization, state);
+        let scholar = forecast_accuracy(&library, 0, &human);
+        let sage = forecast_accuracy(&library, LORE_DAYS, &human);
+        assert!(novice < scholar && scholar < sage);
+        assert!(sage <= human.get_domain(ChunkDomain::Knowledge).max_encoding);
+
+        // Orcs cannot master Knowledge as deeply
+        let orc_sage = forecast_accuracy(&library, LORE_DAYS, &SpeciesChunkModifiers::orc());
+        assert!(orc_sage < sage);
+    }
+
+    #[test]
+    fn test_forecasts_are_right_about_as_often_as_they_claim() {
+        let truth = vec![Weather::Rain; 400];
+        let sharp = forecast_weather(&truth[..1], 0, 0.9, 1);
+        assert!((sharp[0].confidence - 0.9).abs() < 1e-6);
+
+        let far = forecast_weather(&truth[..3], 0, 0.9, 1);
+        assert!(far[2].confidence < far[0].confidence);
+
+        let hits = (0..400)
+            .filter(|&day| forecast_weather(&truth[..1], day, 0.7, 7)[0].weather == Weather::Rain)
+            .count();
+        assert!((230..330).contains(&hits), "{} hits", hits);
+
+        // Only the learned foretell eclipses
+        assert!(event_confidence(CelestialEvent::SilverEclipse, 0.4).is_none());
+        assert!(event_confidence(CelestialEvent::SilverEclipse, 0.7).is_some());
+        assert_eq!(event_confidence(CelestialEvent::FullArgent, 0.3), Some(1.0));
+    }
+
+    #[test]
+    fn test_almanac_matches_the_weather_that_comes() {
+        let mut world = World::with_seed(11);
+        world.spawn_human("Ada".into());
+        let forecast = almanac(&world, ALMANAC_DAYS);
+        assert_eq!(forecast.days.len(), ALMANAC_DAYS as usize);
+        assert_eq!(forecast.lines().len(), ALMANAC_DAYS as usize + 2);
+
+        // The weather that arrives is the weather the outlook held
+        let coming = outlook(&world, 3);
+        for expected in coming {
+            world.astronomy.current_day += 1;
+            run_weather(&mut world);
+            assert_eq!(world.weather.current_weather, expected);
+        }
+        assert_eq!(world.humans.sky_observations[0], 3);
+    }
+}
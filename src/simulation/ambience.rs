@@ -0,0 +1,192 @@
+//! Ambient stimuli - the world itself, not anyone in it, giving people pause
+//!
+//! Perception reacts to entities and zones; this reacts to the sky and the
+//! wild. When the solar phase turns, the moment may carry something worth
+//! noticing: birdsong at dawn outside winter, a sunset at dusk under a clear
+//! sky, the Blood Moon rising full. Each gives every living human a faint
+//! thought, felt in proportion to the value it speaks to, so those who
+//! value beauty are lifted by the dawn chorus and the pious are unsettled by
+//! the red moon.
+
+use crate::campaign::weather::Weather;
+use crate::core::astronomy::{AstronomicalState, CelestialEvent, Season, SolarPhase};
+use crate::ecs::world::World;
+use crate::entity::species::human::HumanValues;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+
+/// Something in the surroundings that can stir a thought
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbientStimulus {
+    /// Birds singing as the sun rises
+    Birdsong,
+    /// The sun going down in a clear sky
+    Sunset,
+    /// The full Blood Moon coming up at dusk
+    BloodMoonRising,
+}
+
+impl AmbientStimulus {
+    /// The thought this stimulus gives: (valence, base intensity, concept, cause)
+    fn thought(&self) -> (Valence, f32, &'static str, &'static str) {
+        match self {
+            Self::Birdsong => (Valence::Positive, 0.15, "beauty", "heard birdsong at dawn"),
+            Self::Sunset => (
+                Valence::Positive,
+                0.2,
+                "beauty",
+                "watched a beautiful sunset",
+            ),
+            Self::BloodMoonRising => (
+                Valence::Negative,
+                0.25,
+                "omen",
+                "saw the Blood Moon rise, red and full",
+            ),
+        }
+    }
+
+    /// How much this stimulus speaks to someone with these values (0.0-1.0)
+    fn resonance(&self, values: &HumanValues) -> f32 {
+        match self {
+            Self::Birdsong | Self::Sunset => values.beauty,
+            Self::BloodMoonRising => values.piety,
+        }
+    }
+}
+
+/// Whether the solar phase turned to `phase` on this tick
+fn phase_began(astronomy: &AstronomicalState, phase: SolarPhase) -> bool {
+    if astronomy.solar_phase != phase || astronomy.tick == 0 {
+        return false;
+    }
+    let previous = (astronomy.tick - 1) % astronomy.ticks_per_day;
+    let previous_hour = ((previous * 24) / astronomy.ticks_per_day) as u32;
+    SolarPhase::from_hour(previous_hour) != phase
+}
+
+/// The ambient stimuli of this tick, if the phase of the day just turned
+pub fn ambient_stimuli(astronomy: &AstronomicalState, weather: Weather) -> Vec<AmbientStimulus> {
+    let mut stimuli = Vec::new();
+    let storm = matches!(
+        weather,
+        Weather::HeavyRain | Weather::Blizzard | Weather::Sandstorm
+    );
+    if phase_began(astronomy, SolarPhase::Dawn) && astronomy.season != Season::Winter && !storm {
+        stimuli.push(AmbientStimulus::Birdsong);
+    }
+    if phase_began(astronomy, SolarPhase::Dusk) {
+        if weather == Weather::Clear {
+            stimuli.push(AmbientStimulus::Sunset);
+        }
+        // Cloud cannot hide what everyone already knows is up there
+        if astronomy.has_event(CelestialEvent::FullSanguine) {
+            stimuli.push(AmbientStimulus::BloodMoonRising);
+        }
+    }
+    stimuli
+}
+
+/// Give every living human a thought for each ambient stimulus of this tick
+///
+/// Intensity scales with the value the stimulus speaks to; those it barely
+/// touches do not notice at all. Returns the number of thoughts.
+pub fn react_to_surroundings(world: &mut World) -> usize {
+    let stimuli = ambient_stimuli(&world.astronomy, world.weather.current_weather);
+    if stimuli.is_empty() {
+        return 0;
+    }
+    let living: Vec<usize> = world.humans.iter_living().collect();
+    let mut count = 0;
+    for stimulus in stimuli {
+        let (valence, intensity, concept, cause) = stimulus.thought();
+        for &i in &living {
+            let felt = intensity * (0.5 + stimulus.resonance(&world.humans.values[i]));
+            let thought = Thought::new(
+                valence,
+                felt.min(1.0),
+                concept,
+                cause,
+                CauseType::Event,
+                world.current_tick,
+            );
+            if thought.is_faded() {
+                continue;
+            }
+            world.humans.thoughts[i].add(thought);
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::astronomy::TICKS_PER_DAY;
+
+    /// Astronomy at the first tick of the given hour on day `day`
+    fn at_hour(day: u64, hour: u64) -> AstronomicalState {
+        let mut astronomy = AstronomicalState::new(TICKS_PER_DAY);
+        let target = day * TICKS_PER_DAY + (hour * TICKS_PER_DAY).div_ceil(24);
+        while astronomy.tick < target {
+            astronomy.advance_tick();
+        }
+        astronomy
+    }
+
+    #[test]
+    fn test_dawn_and_dusk_stimuli() {
+        let dawn = at_hour(10, 6);
+        assert_eq!(dawn.season, Season::Spring);
+        assert_eq!(
+            ambient_stimuli(&dawn, Weather::Clear),
+            vec![AmbientStimulus::Birdsong]
+        );
+        assert!(ambient_stimuli(&dawn, Weather::Blizzard).is_empty());
+
+        // Only the turn of the phase counts, not every tick of it
+        let mut later = dawn.clone();
+        later.advance_tick();
+        assert!(ambient_stimuli(&later, Weather::Clear).is_empty());
+
+        let mut dusk = at_hour(10, 17);
+        assert_eq!(
+            ambient_stimuli(&dusk, Weather::Clear),
+            vec![AmbientStimulus::Sunset]
+        );
+        dusk.active_events.push(CelestialEvent::FullSanguine);
+        assert_eq!(
+            ambient_stimuli(&dusk, Weather::Rain),
+            vec![AmbientStimulus::BloodMoonRising]
+        );
+    }
+
+    #[test]
+    fn test_blood_moon_troubles_the_pious() {
+        let mut world = World::with_seed(4);
+        world.spawn_human("Ada".into());
+        world.spawn_human("Bram".into());
+        world.humans.values[0].piety = 0.0;
+        world.humans.values[1].piety = 1.0;
+        world.astronomy = at_hour(10, 17);
+        world
+            .astronomy
+            .active_events
+            .push(CelestialEvent::FullSanguine);
+        world.weather.current_weather = Weather::Cloudy;
+
+        assert_eq!(react_to_surroundings(&mut world), 2);
+        let omen = |i: usize| {
+            world.humans.thoughts[i]
+                .iter()
+                .find(|t| t.concept_category == "omen")
+                .map(|t| t.intensity)
+                .unwrap()
+        };
+        assert!(omen(1) > omen(0));
+        assert_eq!(
+            world.humans.thoughts[1].strongest().unwrap().valence,
+            Valence::Negative
+        );
+    }
+}
@@ -0,0 +1,392 @@
+//! Aspiration system
+//!
+//! Once a day everyone without an aspiration weighs what they might live
+//! for, from their values and their past: the ambitious and curious, more so
+//! with a craft already in their hands, set out to master one; the loving
+//! and loyal to found a family; the honorable and ambitious, more so from a
+//! line of renown, to earn a title. The just and honorable who lost someone
+//! close to a killer still walking free drop everything to avenge them.
+//!
+//! Progress is measured each day: the depth of their best craft, a home, a
+//! partner and a child, the killer answering for it, a record of kept oaths.
+//! Getting closer is a quiet satisfaction, a month with none a frustration,
+//! and fulfilment a lasting one (and a line in the chronicle). While idle,
+//! people work toward their aspiration when they can (see action
+//! selection).
+
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::aspirations::{Aspiration, AspirationKind, Milestone};
+use crate::entity::needs::NeedType;
+use crate::entity::social::Disposition;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::legacy::mourners;
+use crate::skills::ChunkDomain;
+use crate::world::{OathStatus, TransformationTrigger};
+
+/// Score an aspiration needs before anyone takes it up
+pub const FORM_THRESHOLD: f32 = 0.5;
+
+/// Score (closeness times justice or honor) that sets someone on vengeance
+pub const AVENGE_THRESHOLD: f32 = 0.3;
+
+/// Encoding depth of a mastered craft chunk
+pub const MASTERY_DEPTH: f32 = 0.8;
+
+/// Net kept oaths that earn the title of Oathkeeper
+pub const TITLE_OATHS: u32 = 3;
+
+/// Intensity of the thought on getting closer
+pub const PROGRESS_INTENSITY: f32 = 0.4;
+
+/// Intensity of the thought on a month without progress
+pub const FRUSTRATION_INTENSITY: f32 = 0.6;
+
+/// Intensity of the thought on fulfilment
+pub const FULFILMENT_INTENSITY: f32 = 0.9;
+
+/// What one day of aspirations did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AspirationReport {
+    pub formed: usize,
+    pub advanced: usize,
+    pub frustrated: usize,
+    pub achieved: usize,
+}
+
+/// Depth of someone's best craft (0.0-1.0)
+fn craft_depth(world: &World, i: usize) -> f32 {
+    world.humans.chunk_libraries[i]
+        .chunks()
+        .iter()
+        .filter(|(id, _)| id.domain() == ChunkDomain::Craft)
+        .map(|(_, state)| state.encoding_depth)
+        .fold(0.0, f32::max)
+}
+
+fn has_child(world: &World, i: usize) -> bool {
+    let id = world.humans.ids[i];
+    world
+        .humans
+        .ids
+        .iter()
+        .any(|&child| world.legacy.parents_of(child).contains(&id))
+}
+
+fn has_partner(world: &World, i: usize) -> bool {
+    world.humans.iter_living().filter(|&j| j != i).any(|j| {
+        world.humans.social_memories[i].get_disposition(world.humans.ids[j])
+            == Disposition::Favorable
+    })
+}
+
+fn is_alive(world: &World, id: EntityId) -> bool {
+    world
+        .humans
+        .index_of(id)
+        .map(|i| world.humans.alive[i])
+        .or_else(|| world.orcs.index_of(id).map(|i| world.orcs.alive[i]))
+        .unwrap_or(false)
+}
+
+/// How far along someone is toward an aspiration (0.0-1.0)
+pub fn measure_progress(world: &World, i: usize, kind: AspirationKind) -> f32 {
+    match kind {
+        AspirationKind::MasterCraft => craft_depth(world, i) / MASTERY_DEPTH,
+        AspirationKind::FoundFamily => {
+            if has_child(world, i) {
+                1.0
+            } else {
+                let home = world.humans.assigned_houses[i].is_some() as u8 as f32;
+                let partner = has_partner(world, i) as u8 as f32;
+                0.25 * home + 0.25 * partner
+            }
+        }
+        AspirationKind::Avenge { victim, wrongdoer } => {
+            let settled = world
+                .legacy
+                .memorial_of(victim)
+                .map(|m| m.settled)
+                .unwrap_or(false);
+            if settled || !is_alive(world, wrongdoer) {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        AspirationKind::EarnTitle => {
+            let id = world.humans.ids[i];
+            let net_kept =
+                world
+                    .oaths
+                    .oaths
+                    .iter()
+                    .filter(|o| o.swearer == id)
+                    .fold(0i32, |net, o| match o.status {
+                        OathStatus::Kept => net + 1,
+                        OathStatus::Broken => net - 1,
+                        _ => net,
+                    });
+            net_kept.max(0) as f32 / TITLE_OATHS as f32
+        }
+    }
+}
+
+/// A death someone is moved to avenge: the closest victim whose killer is
+/// still alive and unanswered for
+fn vengeance(world: &World, i: usize) -> Option<(AspirationKind, f32)> {
+    let values = &world.humans.values[i];
+    let resolve = values.justice.max(values.honor);
+    world
+        .legacy
+        .memorials
+        .iter()
+        .filter_map(|m| {
+            let wrongdoer = m.grievance().filter(|&w| is_alive(world, w))?;
+            let (_, closeness) = mourners(world, m.deceased)
+                .into_iter()
+                .find(|&(mourner, _)| mourner == i)?;
+            let kind = AspirationKind::Avenge {
+                victim: m.deceased,
+                wrongdoer,
+            };
+            Some((kind, closeness * resolve))
+        })
+        .filter(|&(_, score)| score >= AVENGE_THRESHOLD)
+        .fold(
+            None,
+            |best: Option<(AspirationKind, f32)>, candidate| match best {
+                Some(b) if b.1 >= candidate.1 => Some(b),
+                _ => Some(candidate),
+            },
+        )
+}
+
+/// The aspiration someone would take up now, if any
+pub fn choose_aspiration(world: &World, i: usize) -> Option<AspirationKind> {
+    if let Some((kind, _)) = vengeance(world, i) {
+        return Some(kind);
+    }
+
+    let values = &world.humans.values[i];
+    let id = world.humans.ids[i];
+    let partner = if has_partner(world, i) { 0.25 } else { 0.0 };
+    let candidates = [
+        (
+            AspirationKind::MasterCraft,
+            (values.ambition + values.curiosity) / 2.0 + craft_depth(world, i) * 0.5,
+        ),
+        (
+            AspirationKind::FoundFamily,
+            (values.love + values.loyalty) / 2.0 + partner,
+        ),
+        (
+            AspirationKind::EarnTitle,
+            (values.honor + values.ambition) / 2.0 + world.legacy.standing(id) * 0.5,
+        ),
+    ];
+    candidates
+        .into_iter()
+        // Nobody aspires to what they already have
+        .filter(|&(kind, score)| score >= FORM_THRESHOLD && measure_progress(world, i, kind) < 1.0)
+        .fold(
+            None,
+            |best: Option<(AspirationKind, f32)>, candidate| match best {
+                Some(b) if b.1 >= candidate.1 => Some(b),
+                _ => Some(candidate),
+            },
+        )
+        .map(|(kind, _)| kind)
+}
+
+/// Run one day of aspirations
+pub fn run_aspirations(world: &mut World) -> AspirationReport {
+    let mut report = AspirationReport::default();
+    let tick = world.current_tick;
+    let living: Vec<usize> = world.humans.iter_living().collect();
+
+    for i in living {
+        // Vengeance displaces any other unfinished aspiration
+        let pursuing_vengeance = matches!(
+            world.humans.aspirations[i]
+                .as_ref()
+                .and_then(Aspiration::pursued),
+            Some(AspirationKind::Avenge { .. })
+        );
+        let open = match &world.humans.aspirations[i] {
+            None => true,
+            Some(a) => !a.achieved && !pursuing_vengeance && vengeance(world, i).is_some(),
+        };
+        if open {
+            if let Some(kind) = choose_aspiration(world, i) {
+                let progress = measure_progress(world, i, kind);
+                world.humans.aspirations[i] = Some(Aspiration::new(kind, tick, progress));
+                report.formed += 1;
+                continue;
+            }
+        }
+
+        let Some(kind) = world.humans.aspirations[i]
+            .as_ref()
+            .and_then(|a| a.pursued().copied())
+        else {
+            continue;
+        };
+        let progress = measure_progress(world, i, kind);
+        let milestone = world.humans.aspirations[i]
+            .as_mut()
+            .unwrap()
+            .record_progress(progress);
+
+        let (valence, intensity, concept, description) = match milestone {
+            Milestone::Unchanged => continue,
+            Milestone::Closer => {
+                report.advanced += 1;
+                (
+                    Valence::Positive,
+                    PROGRESS_INTENSITY,
+                    "aspiration",
+                    format!("closer to the dream to {}", kind.describe()),
+                )
+            }
+            Milestone::Frustrated => {
+                report.frustrated += 1;
+                // A stalled dream of mastery may turn someone to a new path
+                if kind == AspirationKind::MasterCraft {
+                    world.transformations.note(
+                        world.humans.ids[i],
+                        TransformationTrigger::Retraining,
+                        tick,
+                    );
+                }
+                (
+                    Valence::Negative,
+                    FRUSTRATION_INTENSITY,
+                    "frustration",
+                    format!("no closer to the dream to {}", kind.describe()),
+                )
+            }
+            Milestone::Achieved => {
+                report.achieved += 1;
+                world.humans.needs[i].satisfy(NeedType::Purpose, 1.0);
+                let name = world.humans.names[i].clone();
+                let deed = match kind {
+                    AspirationKind::EarnTitle => "earned the title of Oathkeeper".to_string(),
+                    AspirationKind::Avenge { victim, .. } => {
+                        let victim_name = world
+                            .legacy
+                            .memorial_of(victim)
+                            .map(|m| m.name.clone())
+                            .unwrap_or_else(|| "the dead".to_string());
+                        format!("saw {} avenged", victim_name)
+                    }
+                    AspirationKind::MasterCraft => "mastered a craft".to_string(),
+                    AspirationKind::FoundFamily => "founded a family".to_string(),
+                };
+                world.legacy.record(tick, format!("{} {}", name, deed));
+                (
+                    Valence::Positive,
+                    FULFILMENT_INTENSITY,
+                    "fulfilment",
+                    format!("fulfilled the dream to {}", kind.describe()),
+                )
+            }
+        };
+        world.humans.thoughts[i].add(Thought::new(
+            valence,
+            intensity,
+            concept,
+            description,
+            CauseType::Event,
+            tick,
+        ));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::building::BuildingId;
+    use crate::core::types::Vec2;
+    use crate::skills::{ChunkId, PersonalChunkState};
+    use crate::world::OathTerms;
+
+    #[test]
+    fn test_aspirations_follow_values_and_biography() {
+        let mut world = World::with_seed(5);
+        let crafter = world.spawn_human("Ada".into());
+        let parent = world.spawn_human("Bram".into());
+        let drifter = world.spawn_human("Cora".into());
+        world.humans.values[0].ambition = 0.6;
+        world.humans.values[0].curiosity = 0.3;
+        let mut chunk = PersonalChunkState::new(0);
+        chunk.encoding_depth = 0.4;
+        world.humans.chunk_libraries[0].set_chunk(ChunkId::CraftBasicMeasure, chunk);
+        world.humans.values[1].love = 0.8;
+        world.humans.values[1].loyalty = 0.6;
+
+        let report = run_aspirations(&mut world);
+        assert_eq!(report.formed, 2);
+        let kind_of = |world: &World, id| {
+            world.humans.aspirations[world.humans.index_of(id).unwrap()]
+                .as_ref()
+                .map(|a| a.kind)
+        };
+        assert_eq!(kind_of(&world, crafter), Some(AspirationKind::MasterCraft));
+        assert_eq!(kind_of(&world, parent), Some(AspirationKind::FoundFamily));
+        assert_eq!(kind_of(&world, drifter), None);
+
+        // A home brings Bram closer; a child fulfils the dream
+        world.humans.assigned_houses[1] = Some(BuildingId::new());
+        assert_eq!(run_aspirations(&mut world).advanced, 1);
+        let child = world.spawn_human("Dell".into());
+        world.legacy.record_birth(child, &[parent]);
+        assert_eq!(run_aspirations(&mut world).achieved, 1);
+        assert!(world.humans.thoughts[1]
+            .iter()
+            .any(|t| t.concept_category == "fulfilment"));
+        assert!(world.legacy.chronicle.last().unwrap().text.contains("Bram"));
+    }
+
+    #[test]
+    fn test_vengeance_and_titles() {
+        let mut world = World::with_seed(6);
+        world.spawn_human("Ada".into());
+        let child = world.spawn_human("Bram".into());
+        let killer = world.spawn_orc("Grusk".into());
+        world.humans.values[1].justice = 0.9;
+        world.humans.values[1].honor = 0.6;
+        world.humans.values[1].ambition = 0.6;
+
+        // Bram's oaths would earn him a title...
+        let bram = world.humans.ids[1];
+        for _ in 0..TITLE_OATHS {
+            let ward = world.humans.ids[0];
+            world
+                .oaths
+                .swear(bram, None, OathTerms::Defend { ward }, 0, 10)
+                .status = OathStatus::Kept;
+        }
+        assert_eq!(measure_progress(&world, 1, AspirationKind::EarnTitle), 1.0);
+        // ...which he already has, so he does not aspire to one
+        assert_eq!(choose_aspiration(&world, 1), None);
+
+        // ...but his father's killer still walks
+        let father = world.humans.ids[0];
+        world.legacy.record_birth(child, &[father]);
+        world.humans.alive[0] = false;
+        world
+            .legacy
+            .raise_memorial(father, "Ada".into(), Vec2::default(), 0.5, Some(killer), 0);
+        run_aspirations(&mut world);
+        let expected = AspirationKind::Avenge {
+            victim: father,
+            wrongdoer: killer,
+        };
+        assert_eq!(world.humans.aspirations[1].as_ref().unwrap().kind, expected);
+
+        world.orcs.alive[0] = false;
+        assert_eq!(run_aspirations(&mut world).achieved, 1);
+    }
+}
@@ -0,0 +1,317 @@
+//! Avatar mode
+//!
+//! The player can embody one human at a time. While embodied, that person
+//! is left out of autonomous action selection: the player walks them around,
+//! picks their actions from what their skill chunks allow (anything that
+//! needs no skill, or draws on at least one chunk they have), and chooses
+//! what they say. The rest of the settlement carries on by itself, and
+//! those spoken to remember it as they would any other encounter.
+//!
+//! Releasing control hands the person back to their own judgement, with
+//! memories of what they did: a thought for each deed, and for each deed
+//! toward someone, a memory of that person to colour how they feel.
+
+use thiserror::Error;
+
+use crate::actions::catalog::{ActionCategory, ActionId};
+use crate::core::types::{EntityId, Vec2};
+use crate::ecs::world::World;
+use crate::entity::needs::NeedType;
+use crate::entity::social::EventType;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::skills::action_mapping::get_chunks_for_action;
+use crate::world::{Avatar, Deed, DialogueChoice};
+
+/// Distance within which the avatar can speak to someone
+pub const TALK_RANGE: f32 = 5.0;
+
+/// Intensity of what the listener remembers of a conversation
+pub const DIALOGUE_INTENSITY: f32 = 0.5;
+
+/// Social need met by speaking to someone
+pub const DIALOGUE_SOCIAL: f32 = 0.2;
+
+/// Intensity of each deed remembered on release
+pub const DEED_MEMORY_INTENSITY: f32 = 0.5;
+
+/// Why an avatar command could not be carried out
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvatarError {
+    #[error("No one is being embodied")]
+    NotEmbodied,
+    #[error("Only living humans can be embodied")]
+    NotEmbodiable,
+    #[error("They do not know how to {0:?}")]
+    Unskilled(ActionId),
+    #[error("That needs a target")]
+    MissingTarget,
+    #[error("They are not close enough to talk to")]
+    OutOfReach,
+}
+
+/// Index of the embodied human, if they are still alive
+fn avatar_index(world: &World) -> Result<usize, AvatarError> {
+    let entity = world
+        .avatar
+        .as_ref()
+        .ok_or(AvatarError::NotEmbodied)?
+        .entity;
+    world
+        .humans
+        .index_of(entity)
+        .filter(|&i| world.humans.alive[i])
+        .ok_or(AvatarError::NotEmbodied)
+}
+
+/// Actions a human's skills allow them to take
+pub fn available_actions(world: &World, i: usize) -> Vec<ActionId> {
+    let library = &world.humans.chunk_libraries[i];
+    ActionId::ALL
+        .into_iter()
+        .filter(|&action| {
+            let chunks = get_chunks_for_action(action);
+            chunks.is_empty() || chunks.iter().any(|&chunk| library.has_chunk(chunk))
+        })
+        .collect()
+}
+
+/// Take control of a living human, handing back anyone embodied before
+pub fn embody(world: &mut World, entity: EntityId) -> Result<(), AvatarError> {
+    let i = world
+        .humans
+        .index_of(entity)
+        .filter(|&i| world.humans.alive[i])
+        .ok_or(AvatarError::NotEmbodiable)?;
+    if world.avatar.is_some() {
+        release(world)?;
+    }
+    world.humans.task_queues[i].clear();
+    world.avatar = Some(Avatar::new(entity, world.current_tick));
+    Ok(())
+}
+
+fn record_deed(
+    world: &mut World,
+    action: ActionId,
+    target: Option<EntityId>,
+    said: Option<DialogueChoice>,
+) {
+    let tick = world.current_tick;
+    if let Some(avatar) = world.avatar.as_mut() {
+        avatar.deeds.push(Deed {
+            tick,
+            action,
+            target,
+            said,
+        });
+    }
+}
+
+/// Have the avatar drop what they are doing and take an action
+///
+/// Movement and work at a place take a position; social and combat
+/// actions take someone to act toward.
+pub fn act(
+    world: &mut World,
+    action: ActionId,
+    target: Option<EntityId>,
+    position: Option<Vec2>,
+) -> Result<(), AvatarError> {
+    let i = avatar_index(world)?;
+    if !available_actions(world, i).contains(&action) {
+        return Err(AvatarError::Unskilled(action));
+    }
+    let needs_target = matches!(
+        action.category(),
+        ActionCategory::Social | ActionCategory::Combat
+    ) && action != ActionId::HoldPosition;
+    if (needs_target && target.is_none()) || (action == ActionId::MoveTo && position.is_none()) {
+        return Err(AvatarError::MissingTarget);
+    }
+
+    let mut task = Task::new(action, TaskPriority::High, world.current_tick).from_player();
+    if let Some(target) = target {
+        task = task.with_entity(target);
+    }
+    if let Some(position) = position {
+        task = task.with_position(position);
+    }
+    world.humans.task_queues[i].clear();
+    world.humans.task_queues[i].push(task);
+    record_deed(world, action, target, None);
+    Ok(())
+}
+
+/// Have the avatar say something to someone nearby
+pub fn say(
+    world: &mut World,
+    listener: EntityId,
+    choice: DialogueChoice,
+) -> Result<(), AvatarError> {
+    let i = avatar_index(world)?;
+    let l = world
+        .humans
+        .index_of(listener)
+        .filter(|&l| l != i && world.humans.alive[l])
+        .ok_or(AvatarError::OutOfReach)?;
+    if world.humans.positions[i].distance(&world.humans.positions[l]) > TALK_RANGE {
+        return Err(AvatarError::OutOfReach);
+    }
+
+    let speaker = world.humans.ids[i];
+    let tick = world.current_tick;
+    let heard = match choice {
+        DialogueChoice::Chat => EventType::SharedExperience,
+        DialogueChoice::Compliment => EventType::Compliment,
+        DialogueChoice::Insult => EventType::Insult,
+    };
+    world.humans.social_memories[l].record_encounter(speaker, heard, DIALOGUE_INTENSITY, tick);
+    world.humans.needs[i].satisfy(NeedType::Social, DIALOGUE_SOCIAL);
+    if choice != DialogueChoice::Insult {
+        world.humans.needs[l].satisfy(NeedType::Social, DIALOGUE_SOCIAL);
+    }
+    record_deed(world, ActionId::TalkTo, Some(listener), Some(choice));
+    Ok(())
+}
+
+/// How someone remembers a deed of their own toward another
+fn remembered_as(deed: &Deed) -> EventType {
+    match (deed.action, deed.said) {
+        (_, Some(DialogueChoice::Insult)) => EventType::HarmGiven,
+        (ActionId::Attack | ActionId::Charge, _) => EventType::HarmGiven,
+        (ActionId::Help, _) => EventType::AidGiven,
+        (ActionId::Trade, _) => EventType::Transaction,
+        _ => EventType::SharedExperience,
+    }
+}
+
+/// Hand the avatar back to their own judgement
+///
+/// Returns how many deeds they remember.
+pub fn release(world: &mut World) -> Result<usize, AvatarError> {
+    let avatar = world.avatar.take().ok_or(AvatarError::NotEmbodied)?;
+    let Some(i) = world
+        .humans
+        .index_of(avatar.entity)
+        .filter(|&i| world.humans.alive[i])
+    else {
+        // The dead remember nothing
+        return Ok(0);
+    };
+
+    let tick = world.current_tick;
+    for deed in &avatar.deeds {
+        let event = remembered_as(deed);
+        let target_name = deed
+            .target
+            .and_then(|t| world.humans.index_of(t))
+            .map(|t| world.humans.names[t].clone());
+        let description = match (&target_name, deed.said) {
+            (Some(name), Some(DialogueChoice::Chat)) => {
+                format!("remembered chatting with {}", name)
+            }
+            (Some(name), Some(DialogueChoice::Compliment)) => {
+                format!("remembered complimenting {}", name)
+            }
+            (Some(name), Some(DialogueChoice::Insult)) => format!("remembered insulting {}", name),
+            (Some(name), None) => format!("remembered {:?} with {}", deed.action, name),
+            (None, _) => format!("remembered {:?}", deed.action),
+        };
+        let valence = match event {
+            EventType::HarmGiven => Valence::Negative,
+            _ => Valence::Positive,
+        };
+        world.humans.thoughts[i].add(Thought::new(
+            valence,
+            DEED_MEMORY_INTENSITY,
+            "memory",
+            description,
+            CauseType::Event,
+            tick,
+        ));
+        if let Some(target) = deed.target {
+            world.humans.social_memories[i].record_encounter(
+                target,
+                event,
+                DEED_MEMORY_INTENSITY,
+                deed.tick,
+            );
+        }
+    }
+    Ok(avatar.deeds.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::social::Disposition;
+
+    #[test]
+    fn test_avatar_acts_only_within_its_skills() {
+        let mut world = World::with_seed(11);
+        let ada = world.spawn_human("Ada".into());
+        assert_eq!(
+            act(&mut world, ActionId::Rest, None, None),
+            Err(AvatarError::NotEmbodied)
+        );
+        embody(&mut world, ada).unwrap();
+
+        // A fresh peasant can gather and walk, but has never learned to fight
+        let actions = available_actions(&world, 0);
+        assert!(actions.contains(&ActionId::Gather));
+        assert!(!actions.contains(&ActionId::Attack));
+        let target = Some(world.spawn_orc("Grusk".into()));
+        assert_eq!(
+            act(&mut world, ActionId::Attack, target, None),
+            Err(AvatarError::Unskilled(ActionId::Attack))
+        );
+        assert_eq!(
+            act(&mut world, ActionId::MoveTo, None, None),
+            Err(AvatarError::MissingTarget)
+        );
+
+        act(
+            &mut world,
+            ActionId::MoveTo,
+            None,
+            Some(Vec2::new(10.0, 0.0)),
+        )
+        .unwrap();
+        let task = world.humans.task_queues[0].current().unwrap();
+        assert_eq!(task.action, ActionId::MoveTo);
+        assert_eq!(task.target_position.map(|p| p.x), Some(10.0));
+    }
+
+    #[test]
+    fn test_released_avatar_remembers_what_it_did() {
+        let mut world = World::with_seed(12);
+        let ada = world.spawn_human("Ada".into());
+        let bram = world.spawn_human("Bram".into());
+        world.humans.positions[1] = Vec2::new(3.0, 0.0);
+        embody(&mut world, ada).unwrap();
+
+        say(&mut world, bram, DialogueChoice::Compliment).unwrap();
+        say(&mut world, bram, DialogueChoice::Chat).unwrap();
+        assert_ne!(
+            world.humans.social_memories[1].get_disposition(ada),
+            Disposition::Unknown
+        );
+        world.humans.positions[1] = Vec2::new(30.0, 0.0);
+        assert_eq!(
+            say(&mut world, bram, DialogueChoice::Chat),
+            Err(AvatarError::OutOfReach)
+        );
+
+        assert_eq!(release(&mut world), Ok(2));
+        assert!(world.avatar.is_none());
+        assert!(world.humans.thoughts[0]
+            .iter()
+            .any(|t| t.cause_description.contains("Bram")));
+        assert_ne!(
+            world.humans.social_memories[0].get_disposition(bram),
+            Disposition::Unknown
+        );
+        assert_eq!(release(&mut world), Err(AvatarError::NotEmbodied));
+    }
+}
@@ -0,0 +1,528 @@
+//! Barter between individuals, and market days
+//!
+//! Everyone values goods by their own lights: food by how hungry they are,
+//! building materials by ambition, cloth and hides by their taste for
+//! comfort and beauty, and each further unit of something already held for
+//! less. Two traders swap when both come out ahead, and the better haggler
+//! (by their Social chunks) can press for two units for one. When there is
+//! nothing to swap, a friend gives on credit and is owed a favor, which the
+//! debtor pays back in goods at a later trade.
+//!
+//! Every few days each finished market square holds a market: idle people
+//! with goods to sell gather there and pair off with whoever they can strike
+//! a deal with. Anyone left without a partner sells to their faction's
+//! stockpile instead, if it has something they want (see `economy`).
+
+use crate::actions::catalog::ActionId;
+use crate::city::building::{BuildingState, BuildingType};
+use crate::core::types::{EntityId, Vec2};
+use crate::ecs::world::World;
+use crate::entity::needs::Needs;
+use crate::entity::possessions::Possessions;
+use crate::entity::social::{Disposition, EventType};
+use crate::entity::species::human::HumanValues;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::simulation::economy::propose_for;
+use crate::simulation::resource_zone::ResourceType;
+use crate::simulation::tick::TICKS_PER_DAY;
+use crate::skills::skill_check;
+
+/// Value of a first unit to someone with no particular want for it
+pub const BASE_WANT: f32 = 0.3;
+
+/// Skill lead needed to haggle two units out of the other side for one
+pub const HAGGLE_MARGIN: f32 = 0.2;
+
+/// Days between market days
+pub const MARKET_INTERVAL_DAYS: u64 = 3;
+
+/// What someone wants from a resource, before counting what they hold
+fn want(resource: ResourceType, needs: &Needs, values: &HumanValues) -> f32 {
+    let want = match resource {
+        ResourceType::Food | ResourceType::Milk | ResourceType::Eggs | ResourceType::Meat => {
+            needs.food
+        }
+        ResourceType::Wood | ResourceType::Stone | ResourceType::Ore | ResourceType::Iron => {
+            values.ambition
+        }
+        ResourceType::Cloth | ResourceType::Wool | ResourceType::Leather => {
+            (values.comfort + values.beauty) * 0.5
+        }
+        ResourceType::Fodder => values.ambition * 0.5,
+    };
+    BASE_WANT + want
+}
+
+/// Value of one more unit of a resource to someone already holding `held`
+pub fn unit_value(resource: ResourceType, held: u32, needs: &Needs, values: &HumanValues) -> f32 {
+    want(resource, needs, values) / (1 + held) as f32
+}
+
+/// One side of a negotiation
+#[derive(Debug, Clone, Copy)]
+pub struct Trader<'a> {
+    pub id: EntityId,
+    pub possessions: &'a Possessions,
+    pub needs: &'a Needs,
+    pub values: &'a HumanValues,
+    /// Trade skill modifier from Social chunks
+    pub skill: f32,
+    /// Whether this side is Friendly or better toward the other
+    pub trusts_partner: bool,
+}
+
+impl Trader<'_> {
+    /// Value of receiving `amount` more of a resource
+    pub fn gain(&self, resource: ResourceType, amount: u32) -> f32 {
+        let held = self.possessions.get(resource);
+        (0..amount)
+            .map(|n| unit_value(resource, held + n, self.needs, self.values))
+            .sum()
+    }
+
+    /// Value lost by handing over `amount` of a resource (None if not held)
+    pub fn loss(&self, resource: ResourceType, amount: u32) -> Option<f32> {
+        let held = self.possessions.get(resource);
+        (held >= amount).then(|| {
+            (1..=amount)
+                .map(|n| unit_value(resource, held - n, self.needs, self.values))
+                .sum()
+        })
+    }
+}
+
+/// How a deal was struck
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DealKind {
+    /// Goods for goods
+    Swap,
+    /// Goods given on credit; `debtor` now owes a favor worth `value`
+    Credit { debtor: EntityId, value: f32 },
+    /// Goods given to pay off `value` of a favor `debtor` owed
+    Repayment { debtor: EntityId, value: f32 },
+}
+
+/// Goods that change hands between traders `a` and `b`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deal {
+    pub a_gives: Option<(ResourceType, u32)>,
+    pub b_gives: Option<(ResourceType, u32)>,
+    pub kind: DealKind,
+}
+
+/// The good `debtor` can best spare to pay `creditor`, and what it is worth
+/// to the creditor
+fn repayment(debtor: &Trader, creditor: &Trader) -> Option<(ResourceType, f32)> {
+    if creditor.possessions.owed_by(debtor.id) <= 0.0 {
+        return None;
+    }
+    debtor
+        .possessions
+        .goods
+        .keys()
+        .filter_map(|&r| {
+            let worth = creditor.gain(r, 1);
+            let surplus = worth - debtor.loss(r, 1)?;
+            Some((r, worth, surplus))
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(r, worth, _)| (r, worth))
+}
+
+/// The best one-for-one swap both sides gain from, as (a's good, b's good)
+fn best_swap(a: &Trader, b: &Trader) -> Option<(ResourceType, ResourceType)> {
+    let mut best: Option<(ResourceType, ResourceType, f32)> = None;
+    for &ra in a.possessions.goods.keys() {
+        for &rb in b.possessions.goods.keys() {
+            if ra == rb {
+                continue;
+            }
+            let (Some(a_loss), Some(b_loss)) = (a.loss(ra, 1), b.loss(rb, 1)) else {
+                continue;
+            };
+            let a_net = a.gain(rb, 1) - a_loss;
+            let b_net = b.gain(ra, 1) - b_loss;
+            if a_net > 0.0 && b_net > 0.0 && best.is_none_or(|(_, _, s)| a_net + b_net > s) {
+                best = Some((ra, rb, a_net + b_net));
+            }
+        }
+    }
+    best.map(|(ra, rb, _)| (ra, rb))
+}
+
+/// Work out what `a` and `b` will trade, if anything
+///
+/// Debts are settled first. Failing that, the two swap one for one on the
+/// deal that suits them best, with the better haggler taking two units for
+/// one when the other side would still come out ahead. With nothing worth
+/// swapping, a trusting side hands over something the other wants on credit.
+pub fn negotiate(a: &Trader, b: &Trader) -> Option<Deal> {
+    if let Some((resource, value)) = repayment(a, b) {
+        return Some(Deal {
+            a_gives: Some((resource, 1)),
+            b_gives: None,
+            kind: DealKind::Repayment {
+                debtor: a.id,
+                value,
+            },
+        });
+    }
+    if let Some((resource, value)) = repayment(b, a) {
+        return Some(Deal {
+            a_gives: None,
+            b_gives: Some((resource, 1)),
+            kind: DealKind::Repayment {
+                debtor: b.id,
+                value,
+            },
+        });
+    }
+
+    if let Some((ra, rb)) = best_swap(a, b) {
+        let mut deal = Deal {
+            a_gives: Some((ra, 1)),
+            b_gives: Some((rb, 1)),
+            kind: DealKind::Swap,
+        };
+        if a.skill - b.skill >= HAGGLE_MARGIN
+            && b.loss(rb, 2).is_some_and(|loss| b.gain(ra, 1) > loss)
+        {
+            deal.b_gives = Some((rb, 2));
+        } else if b.skill - a.skill >= HAGGLE_MARGIN
+            && a.loss(ra, 2).is_some_and(|loss| a.gain(rb, 1) > loss)
+        {
+            deal.a_gives = Some((ra, 2));
+        }
+        return Some(deal);
+    }
+
+    credit(b, a)
+        .map(|(resource, value)| Deal {
+            a_gives: None,
+            b_gives: Some((resource, 1)),
+            kind: DealKind::Credit {
+                debtor: a.id,
+                value,
+            },
+        })
+        .or_else(|| {
+            credit(a, b).map(|(resource, value)| Deal {
+                a_gives: Some((resource, 1)),
+                b_gives: None,
+                kind: DealKind::Credit {
+                    debtor: b.id,
+                    value,
+                },
+            })
+        })
+}
+
+/// What a trusting `giver` would hand `taker` on credit, and the favor owed
+fn credit(giver: &Trader, taker: &Trader) -> Option<(ResourceType, f32)> {
+    if !giver.trusts_partner {
+        return None;
+    }
+    giver
+        .possessions
+        .goods
+        .keys()
+        .filter_map(|&r| {
+            let loss = giver.loss(r, 1)?;
+            let gain = taker.gain(r, 1);
+            (gain > loss).then_some((r, loss, gain - loss))
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(r, loss, _)| (r, loss))
+}
+
+/// Build the negotiating side for human `i` dealing with `partner`
+pub fn trader(world: &World, i: usize, partner: EntityId) -> Trader<'_> {
+    Trader {
+        id: world.humans.ids[i],
+        possessions: &world.humans.possessions[i],
+        needs: &world.humans.needs[i],
+        values: &world.humans.values[i],
+        skill: skill_check(&world.humans.chunk_libraries[i], ActionId::Trade).skill_modifier,
+        trusts_partner: matches!(
+            world.humans.social_memories[i].get_disposition(partner),
+            Disposition::Friendly | Disposition::Favorable
+        ),
+    }
+}
+
+/// What humans `a` and `b` would trade right now
+pub fn propose_trade(world: &World, a: usize, b: usize) -> Option<Deal> {
+    let a_side = trader(world, a, world.humans.ids[b]);
+    let b_side = trader(world, b, world.humans.ids[a]);
+    negotiate(&a_side, &b_side)
+}
+
+/// Strike a deal between humans `a` and `b` and hand the goods over
+pub fn conclude_trade(world: &mut World, a: usize, b: usize) -> Option<Deal> {
+    let deal = propose_trade(world, a, b)?;
+    let tick = world.current_tick;
+
+    if let Some((resource, amount)) = deal.a_gives {
+        let moved = world.humans.possessions[a].remove(resource, amount);
+        world.humans.possessions[b].add(resource, moved);
+    }
+    if let Some((resource, amount)) = deal.b_gives {
+        let moved = world.humans.possessions[b].remove(resource, amount);
+        world.humans.possessions[a].add(resource, moved);
+    }
+
+    let (a_id, b_id) = (world.humans.ids[a], world.humans.ids[b]);
+    match deal.kind {
+        DealKind::Swap => {}
+        DealKind::Credit { debtor, value } => {
+            let (creditor, creditor_id, debtor_idx) = if debtor == a_id {
+                (b, b_id, a)
+            } else {
+                (a, a_id, b)
+            };
+            world.humans.possessions[creditor].grant_favor(debtor, value);
+            world.humans.social_memories[creditor].record_encounter(
+                debtor,
+                EventType::AidGiven,
+                EventType::AidGiven.base_intensity(),
+                tick,
+            );
+            world.humans.social_memories[debtor_idx].record_encounter(
+                creditor_id,
+                EventType::AidReceived,
+                EventType::AidReceived.base_intensity(),
+                tick,
+            );
+        }
+        DealKind::Repayment { debtor, value } => {
+            let creditor = if debtor == a_id { b } else { a };
+            world.humans.possessions[creditor].settle_favor(debtor, value);
+            world.humans.social_memories[creditor].record_encounter(
+                debtor,
+                EventType::PromiseKept,
+                EventType::PromiseKept.base_intensity(),
+                tick,
+            );
+        }
+    }
+    Some(deal)
+}
+
+/// What one market day did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarketReport {
+    pub markets: usize,
+    pub attendees: usize,
+    pub pairs: usize,
+    /// Attendees trading with their stockpile for want of a partner
+    pub stockpile_trades: usize,
+}
+
+/// Finished market squares
+fn market_squares(world: &World) -> Vec<Vec2> {
+    (0..world.buildings.count())
+        .filter(|&b| {
+            world.buildings.building_types[b] == BuildingType::MarketSquare
+                && world.buildings.states[b] == BuildingState::Complete
+        })
+        .map(|b| world.buildings.positions[b])
+        .collect()
+}
+
+/// Hold the markets if today is market day
+///
+/// Idle people with goods go to their nearest market square, and each pairs
+/// off with the first other attendee they can strike a deal with. Those left
+/// over trade with the stockpile if they can.
+pub fn run_market(world: &mut World) -> MarketReport {
+    let mut report = MarketReport::default();
+    let tick = world.current_tick;
+    if !(tick / TICKS_PER_DAY).is_multiple_of(MARKET_INTERVAL_DAYS) {
+        return report;
+    }
+    let squares = market_squares(world);
+    if squares.is_empty() {
+        return report;
+    }
+
+    let mut attendees: Vec<Vec<usize>> = vec![Vec::new(); squares.len()];
+    for i in world.humans.iter_living() {
+        let idle = world.humans.task_queues[i]
+            .current()
+            .map(|t| matches!(t.action, ActionId::IdleWander | ActionId::IdleObserve))
+            .unwrap_or(true);
+        if !idle || !world.humans.possessions[i].has_goods() {
+            continue;
+        }
+        let pos = world.humans.positions[i];
+        let nearest = (0..squares.len())
+            .min_by(|&a, &b| {
+                squares[a]
+                    .distance(&pos)
+                    .total_cmp(&squares[b].distance(&pos))
+            })
+            .unwrap();
+        attendees[nearest].push(i);
+    }
+
+    for (square, people) in squares.iter().zip(attendees) {
+        if people.is_empty() {
+            continue;
+        }
+        report.markets += 1;
+        report.attendees += people.len();
+
+        let mut partners: Vec<Option<usize>> = vec![None; people.len()];
+        for x in 0..people.len() {
+            if partners[x].is_some() {
+                continue;
+            }
+            let found = (x + 1..people.len()).find(|&y| {
+                partners[y].is_none() && propose_trade(world, people[x], people[y]).is_some()
+            });
+            if let Some(y) = found {
+                partners[x] = Some(y);
+                partners[y] = Some(x);
+                report.pairs += 1;
+            }
+        }
+
+        for (x, &i) in people.iter().enumerate() {
+            let queue = &mut world.humans.task_queues[i];
+            queue.clear();
+            queue.push(
+                Task::new(ActionId::MoveTo, TaskPriority::Normal, tick).with_position(*square),
+            );
+            if let Some(y) = partners[x] {
+                let partner = world.humans.ids[people[y]];
+                world.humans.task_queues[i].push(
+                    Task::new(ActionId::Trade, TaskPriority::Normal, tick).with_entity(partner),
+                );
+            } else if propose_for(world, i).is_some() {
+                // A trade with no partner is a trade with the stockpile
+                world.humans.task_queues[i].push(Task::new(
+                    ActionId::Trade,
+                    TaskPriority::Normal,
+                    tick,
+                ));
+                report.stockpile_trades += 1;
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side<'a>(
+        possessions: &'a Possessions,
+        needs: &'a Needs,
+        values: &'a HumanValues,
+    ) -> Trader<'a> {
+        Trader {
+            id: EntityId::new(),
+            possessions,
+            needs,
+            values,
+            skill: 0.5,
+            trusts_partner: false,
+        }
+    }
+
+    #[test]
+    fn test_hungry_builder_and_fed_builder_swap() {
+        // A holds food but wants to build; B holds wood but is hungry
+        let mut a_goods = Possessions::new();
+        a_goods.add(ResourceType::Food, 3);
+        let mut b_goods = Possessions::new();
+        b_goods.add(ResourceType::Wood, 3);
+        let a_needs = Needs::default();
+        let b_needs = Needs {
+            food: 0.9,
+            ..Needs::default()
+        };
+        let a_values = HumanValues {
+            ambition: 0.8,
+            ..HumanValues::default()
+        };
+        let b_values = HumanValues::default();
+
+        let mut a = side(&a_goods, &a_needs, &a_values);
+        let b = side(&b_goods, &b_needs, &b_values);
+        let deal = negotiate(&a, &b).unwrap();
+        assert_eq!(deal.kind, DealKind::Swap);
+        assert_eq!(deal.a_gives, Some((ResourceType::Food, 1)));
+        assert_eq!(deal.b_gives, Some((ResourceType::Wood, 1)));
+
+        // A skilled haggler gets two for one while B still gains
+        a.skill = 0.9;
+        let deal = negotiate(&a, &b).unwrap();
+        assert_eq!(deal.b_gives, Some((ResourceType::Wood, 2)));
+
+        // Someone with nothing to offer gets nothing from a stranger
+        let empty = Possessions::new();
+        let broke = side(&empty, &b_needs, &b_values);
+        assert!(negotiate(&broke, &a).is_none());
+    }
+
+    #[test]
+    fn test_credit_is_repaid_at_a_later_trade() {
+        let mut world = World::with_seed(5);
+        world.spawn_human("Ada".into());
+        world.spawn_human("Bram".into());
+        let (ada, bram) = (world.humans.ids[0], world.humans.ids[1]);
+        world.humans.possessions[0].add(ResourceType::Food, 2);
+        world.humans.needs[1].food = 0.9;
+        for _ in 0..3 {
+            world.humans.social_memories[0].record_encounter(bram, EventType::AidReceived, 0.9, 0);
+        }
+
+        // Bram has nothing to offer, so his friend Ada gives on credit
+        let deal = conclude_trade(&mut world, 1, 0).unwrap();
+        assert!(matches!(deal.kind, DealKind::Credit { debtor, .. } if debtor == bram));
+        assert_eq!(world.humans.possessions[1].get(ResourceType::Food), 1);
+        assert!(world.humans.possessions[0].owed_by(bram) > 0.0);
+
+        // Once Bram has goods he pays the favor back before anything else
+        world.humans.possessions[1].add(ResourceType::Wood, 1);
+        let deal = conclude_trade(&mut world, 0, 1).unwrap();
+        assert!(matches!(deal.kind, DealKind::Repayment { debtor, .. } if debtor == bram));
+        assert_eq!(world.humans.possessions[0].get(ResourceType::Wood), 1);
+        assert_eq!(world.humans.possessions[0].owed_by(bram), 0.0);
+        assert!(matches!(
+            world.humans.social_memories[1].get_disposition(ada),
+            Disposition::Friendly | Disposition::Favorable
+        ));
+    }
+
+    #[test]
+    fn test_market_day_gathers_traders() {
+        let mut world = World::with_seed(9);
+        let market = world.spawn_building(BuildingType::MarketSquare, Vec2::new(20.0, 20.0));
+        let market_idx = world.buildings.index_of(market).unwrap();
+        world.buildings.states[market_idx] = BuildingState::Complete;
+        for name in ["Ada", "Bram", "Cora"] {
+            world.spawn_human(name.into());
+        }
+        world.humans.possessions[0].add(ResourceType::Food, 3);
+        world.humans.possessions[1].add(ResourceType::Wood, 3);
+        world.humans.needs[1].food = 0.9;
+        world.humans.values[0].ambition = 0.8;
+
+        // Not a market day
+        world.current_tick = TICKS_PER_DAY;
+        assert_eq!(run_market(&mut world), MarketReport::default());
+
+        world.current_tick = TICKS_PER_DAY * MARKET_INTERVAL_DAYS;
+        let report = run_market(&mut world);
+        assert_eq!(report.markets, 1);
+        assert_eq!(report.attendees, 2, "Cora has nothing to sell");
+        assert_eq!(report.pairs, 1);
+        let queue = &world.humans.task_queues[0];
+        assert_eq!(queue.current().unwrap().action, ActionId::MoveTo);
+        assert_eq!(queue.queued_len(), 1);
+        assert!(world.humans.task_queues[2].is_idle());
+    }
+}
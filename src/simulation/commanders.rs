@@ -0,0 +1,201 @@
+//! Commanders - settlers leading armies on campaign
+//!
+//! `appoint_commander` puts a settler at the head of a campaign army: what
+//! they know of command and what they value become the army's commander,
+//! and the identity registry has them away on campaign as its leader.
+//!
+//! When a battle costs an army its commander, `commanders_lost` brings the
+//! news home. The chronicle records it and those close to them grieve. The
+//! killed die as any settler does, memorial and all; the taken are held by
+//! their captor until freed, and their kin fear for them meanwhile.
+
+use thiserror::Error;
+
+use crate::campaign::commander::{Commander, CommanderFate};
+use crate::campaign::route::{Army, CampaignEvent};
+use crate::core::types::{EntityId, PolityId};
+use crate::ecs::world::World;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::epithets::known_as;
+use crate::simulation::legacy::{mourners, record_death};
+use crate::world::{IdentityError, Role, Whereabouts};
+
+/// Grief of those closest to a commander killed in battle
+pub const FALLEN_GRIEF_INTENSITY: f32 = 0.7;
+
+/// Fear of those closest to a commander taken captive
+pub const CAPTIVE_FEAR_INTENSITY: f32 = 0.5;
+
+/// Why someone could not be given command
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandError {
+    #[error("{0:?} is not among the living settlers")]
+    NotLiving(EntityId),
+    #[error(transparent)]
+    Identity(#[from] IdentityError),
+}
+
+/// Put settler `id` at the head of `army`
+pub fn appoint_commander(
+    world: &mut World,
+    army: &mut Army,
+    id: EntityId,
+) -> Result<(), CommandError> {
+    let i = world
+        .humans
+        .index_of(id)
+        .filter(|&i| world.humans.alive[i])
+        .ok_or(CommandError::NotLiving(id))?;
+    let tick = world.current_tick;
+    world.identities.move_to(
+        id,
+        Whereabouts::Campaign {
+            army: army.id,
+            hex: army.position,
+        },
+        Role::Leader,
+        tick,
+    )?;
+
+    let name = known_as(world, i);
+    world
+        .legacy
+        .record(tick, format!("{} took command of {}", name, army.name));
+    army.commander = Some(Commander::new(
+        id,
+        name,
+        &world.humans.chunk_libraries[i],
+        &world.humans.values[i],
+    ));
+    Ok(())
+}
+
+/// Those close to a lost commander take the news
+fn mourn(world: &mut World, commander: EntityId, fate: CommanderFate) {
+    let tick = world.current_tick;
+    let (concept, intensity, description) = match fate {
+        CommanderFate::Killed => ("grief", FALLEN_GRIEF_INTENSITY, "fell leading an army"),
+        CommanderFate::Captured => ("fear", CAPTIVE_FEAR_INTENSITY, "was taken captive"),
+    };
+    for (j, closeness) in mourners(world, commander) {
+        if world.humans.ids[j] == commander {
+            continue;
+        }
+        let mut thought = Thought::new(
+            Valence::Negative,
+            intensity * closeness,
+            concept,
+            format!("someone dear {}", description),
+            CauseType::Entity,
+            tick,
+        );
+        thought.cause_entity = Some(commander);
+        world.humans.thoughts[j].add(thought);
+    }
+}
+
+/// A commander was killed or taken; `captor` holds the taken
+pub fn commander_lost(
+    world: &mut World,
+    commander: &Commander,
+    fate: CommanderFate,
+    captor: Option<PolityId>,
+) {
+    let tick = world.current_tick;
+    let id = commander.entity;
+    mourn(world, id, fate);
+    match fate {
+        CommanderFate::Killed => {
+            world
+                .legacy
+                .record(tick, format!("{} fell in battle", commander.name));
+            match world.humans.index_of(id) {
+                Some(i) if world.humans.alive[i] => {
+                    world.humans.alive[i] = false;
+                    record_death(world, i, None);
+                }
+                _ => {
+                    world.identities.record_death(id, tick);
+                }
+            }
+        }
+        CommanderFate::Captured => {
+            world
+                .legacy
+                .record(tick, format!("{} was taken captive", commander.name));
+            // Already dead or held: nothing more to take
+            let _ = world.identities.capture(id, captor, tick);
+        }
+    }
+}
+
+/// Bring home the news of every commander lost in `events`; returns how many
+pub fn commanders_lost(world: &mut World, events: &[CampaignEvent]) -> usize {
+    let mut lost = 0;
+    for event in events {
+        if let CampaignEvent::CommanderLost {
+            commander,
+            fate,
+            captor,
+            ..
+        } = event
+        {
+            commander_lost(world, commander, *fate, *captor);
+            lost += 1;
+        }
+    }
+    lost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaign::map::HexCoord;
+    use crate::campaign::route::ArmyId;
+    use crate::core::types::Vec2;
+    use crate::entity::social::EventType;
+    use crate::world::{Fate, Layer};
+
+    #[test]
+    fn test_settler_leads_and_falls() {
+        let mut world = World::new();
+        let marcus = world.spawn_human("Marcus".into());
+        let lucia = world.spawn_human("Lucia".into());
+        world.humans.positions[1] = Vec2::new(50.0, 50.0);
+        world.humans.social_memories[1].record_encounter(marcus, EventType::AidReceived, 1.0, 0);
+
+        let mut army = Army::new(ArmyId(4), "Host".into(), PolityId(1), HexCoord::new(2, 3));
+        appoint_commander(&mut world, &mut army, marcus).unwrap();
+        let commander = army.commander.clone().unwrap();
+        assert_eq!(commander.entity, marcus);
+        let identity = world.identities.get(marcus).unwrap();
+        assert_eq!(identity.layer(), Layer::Campaign);
+        assert_eq!(identity.role, Role::Leader);
+
+        let lost = commanders_lost(
+            &mut world,
+            &[CampaignEvent::CommanderLost {
+                army: army.id,
+                commander,
+                fate: CommanderFate::Killed,
+                captor: None,
+            }],
+        );
+        assert_eq!(lost, 1);
+        assert!(!world.humans.alive[0]);
+        assert!(matches!(
+            world.identities.get(marcus).unwrap().fate,
+            Fate::Dead { .. }
+        ));
+        assert!(world.humans.thoughts[1]
+            .iter()
+            .any(|t| t.concept_category == "grief"));
+
+        // The dead lead no one
+        assert_eq!(
+            appoint_commander(&mut world, &mut army, marcus),
+            Err(CommandError::NotLiving(marcus))
+        );
+        assert!(appoint_commander(&mut world, &mut army, lucia).is_ok());
+    }
+}
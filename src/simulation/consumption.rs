@@ -1,21 +1,40 @@
 //! Food consumption system
 //!
-//! Living entities consume food from the stockpile daily.
+//! Living settlers - humans, dwarves, and elves - consume food from the
+//! stockpile daily, except those refusing it in despair. When grain runs
+//! short, livestock products (meat, milk, eggs) make up the difference.
 
 use crate::ecs::world::World;
 use crate::simulation::resource_zone::ResourceType;
 
-/// Consume food for all living entities
+/// Consume food for all living entities who will eat
 /// Returns number of entities that went hungry
 pub fn consume_food(world: &mut World) -> u32 {
-    let living_count = world.humans.iter_living().count() as u32;
+    let humans = world
+        .humans
+        .iter_living()
+        .filter(|&i| !world.despair.refuses_food(world.humans.ids[i]))
+        .count();
+    // Dwarves and elves who settle alongside them eat from the same stores
+    let kin = world.dwarves.iter_living().count() + world.elves.iter_living().count();
+    let living_count = (humans + kin) as u32;
 
     if living_count == 0 {
         return 0;
     }
 
     let food_needed = living_count; // 1 food per entity
-    let food_consumed = world.stockpile.remove(ResourceType::Food, food_needed);
+    let mut food_consumed = world.stockpile.remove(ResourceType::Food, food_needed);
+
+    // Secondary food economy: fall back to livestock products
+    for resource in [ResourceType::Meat, ResourceType::Milk, ResourceType::Eggs] {
+        if food_consumed >= food_needed {
+            break;
+        }
+        food_consumed += world
+            .stockpile
+            .remove(resource, food_needed - food_consumed);
+    }
 
     // Return number who went hungry
     living_count - food_consumed
@@ -68,6 +87,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_consume_food_falls_back_to_livestock_products() {
+        let mut world = World::new();
+
+        world.stockpile.add(ResourceType::Food, 1);
+        world.stockpile.add(ResourceType::Milk, 1);
+        world.stockpile.add(ResourceType::Eggs, 5);
+
+        world.spawn_human("Alice".into());
+        world.spawn_human("Bob".into());
+        world.spawn_human("Charlie".into());
+
+        let hungry = consume_food(&mut world);
+
+        assert_eq!(hungry, 0, "Milk and eggs cover the grain shortfall");
+        assert_eq!(world.stockpile.get(ResourceType::Food), 0);
+        assert_eq!(world.stockpile.get(ResourceType::Milk), 0);
+        assert_eq!(world.stockpile.get(ResourceType::Eggs), 4);
+    }
+
+    #[test]
+    fn test_consume_food_feeds_dwarves_and_elves() {
+        let mut world = World::new();
+        world.stockpile.add(ResourceType::Food, 10);
+        world.spawn_human("Alice".into());
+        world.spawn_dwarf("Thorin".into());
+        world.spawn_elf("Aelwen".into());
+
+        assert_eq!(consume_food(&mut world), 0);
+        assert_eq!(world.stockpile.get(ResourceType::Food), 7);
+    }
+
     #[test]
     fn test_consume_food_empty_stockpile() {
         let mut world = World::new();
@@ -0,0 +1,138 @@
+//! Bridge and ramp system
+//!
+//! Once a day, under-built crossings roll for collapse and idle humans are
+//! sent to crossings still under construction. Like road work, crossing work
+//! is a Build task aimed at the crossing's position.
+
+use rand::Rng;
+
+use crate::actions::catalog::ActionId;
+use crate::city::crossing::CrossingState;
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::simulation::sites::nearest_idle_worker;
+
+/// Builders within this distance of a crossing count as working on it
+pub const CROSSING_WORK_RANGE: f32 = 2.0;
+
+/// Run one day of crossing upkeep. Returns number of crossings that collapsed.
+pub fn run_crossings<R: Rng>(world: &mut World, rng: &mut R) -> usize {
+    let collapsed = check_collapses(world, rng);
+    assign_crossing_tasks(world);
+    collapsed
+}
+
+/// Roll every complete crossing for collapse
+pub fn check_collapses<R: Rng>(world: &mut World, rng: &mut R) -> usize {
+    let mut collapsed = 0;
+    for crossing in world.crossings.iter_mut() {
+        if crossing.check_collapse(&mut world.blocked_cells, rng) {
+            tracing::debug!("Crossing {:?} collapsed", crossing.id);
+            collapsed += 1;
+        }
+    }
+    collapsed
+}
+
+/// Send idle humans to crossings under construction
+///
+/// Each crossing takes up to its blueprint's labor cap. Returns number of tasks assigned.
+pub fn assign_crossing_tasks(world: &mut World) -> usize {
+    let mut assigned = 0;
+
+    for c in 0..world.crossings.len() {
+        if world.crossings[c].state != CrossingState::UnderConstruction {
+            continue;
+        }
+
+        let site_pos = world.crossings[c].position;
+        let working = world
+            .humans
+            .iter_living()
+            .filter(|&i| {
+                world.humans.task_queues[i]
+                    .current()
+                    .map(|t| {
+                        t.action == ActionId::Build
+                            && t.target_building.is_none()
+                            && t.target_position
+                                .map(|pos| pos.distance(&site_pos) <= CROSSING_WORK_RANGE)
+                                .unwrap_or(false)
+                    })
+                    .unwrap_or(false)
+            })
+            .count() as u32;
+
+        for _ in working..world.crossings[c].labor_cap.max(1) {
+            let Some(i) = nearest_idle_worker(world, site_pos) else {
+                return assigned; // Nobody left to assign
+            };
+            world.humans.task_queues[i].clear();
+            let task = Task::new(ActionId::Build, TaskPriority::Normal, world.current_tick)
+                .with_position(site_pos);
+            world.humans.task_queues[i].push(task);
+            assigned += 1;
+        }
+    }
+
+    assigned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blueprints::BlueprintRegistry;
+    use crate::core::types::Vec2;
+    use crate::simulation::resource_zone::ResourceType;
+    use crate::world::TerrainFeatureKind;
+    use std::path::Path;
+
+    fn world_with_bridge_site() -> World {
+        let mut registry = BlueprintRegistry::new();
+        registry
+            .load_directory(Path::new("data/blueprints"))
+            .unwrap();
+
+        let mut world = World::new();
+        world.add_terrain_feature(
+            TerrainFeatureKind::Gully,
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 20.0),
+            4.0,
+            2.0,
+        );
+        world.stockpile.add(ResourceType::Wood, 200);
+        world.stockpile.add(ResourceType::Stone, 200);
+        world
+            .plan_crossing(&registry, Vec2::new(10.0, 10.0), 2.0)
+            .unwrap();
+        world
+    }
+
+    #[test]
+    fn test_assign_crossing_tasks_respects_labor_cap() {
+        let mut world = world_with_bridge_site();
+        for n in 0..5 {
+            world.spawn_human(format!("Builder {}", n));
+        }
+
+        // span 4: labor cap ceil(4 / 2) = 2
+        assert_eq!(world.crossings[0].labor_cap, 2);
+        assert_eq!(assign_crossing_tasks(&mut world), 2);
+        assert_eq!(assign_crossing_tasks(&mut world), 0);
+    }
+
+    #[test]
+    fn test_plan_crossing_needs_obstacle() {
+        let mut registry = BlueprintRegistry::new();
+        registry
+            .load_directory(Path::new("data/blueprints"))
+            .unwrap();
+        let mut world = World::new();
+
+        assert!(world
+            .plan_crossing(&registry, Vec2::new(0.0, 0.0), 2.0)
+            .is_err());
+        assert!(world.crossings.is_empty());
+    }
+}
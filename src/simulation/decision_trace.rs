@@ -0,0 +1,285 @@
+//! Decision traces - why watched entities chose what they did
+//!
+//! Tracing is opt-in per entity: put a `DecisionTrace` on the world, watch
+//! the entities of interest, and every action selection for them is
+//! recorded - what each stage of the cascade proposed and how strongly it
+//! was driven, the action chosen, the need and thought behind it, and the
+//! task it displaced. Records save as JSON lines, one decision per line, and
+//! `why_stopped` reads them back to answer questions like "why did Elena
+//! abandon the harvest?" long after the run.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use ahash::AHashSet;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::actions::catalog::ActionId;
+use crate::core::types::{EntityId, Tick};
+use crate::ecs::world::World;
+use crate::entity::needs::NeedType;
+use crate::entity::thoughts::Valence;
+use crate::simulation::action_select::{Candidate, DecisionStage};
+
+/// Errors from saving or loading a decision trace
+#[derive(Error, Debug)]
+pub enum TraceError {
+    /// Reading or writing the log failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// A record could not be encoded or decoded
+    #[error("Format error: {0}")]
+    FormatError(#[from] serde_json::Error),
+}
+
+/// The thought on an entity's mind when they decided
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TracedThought {
+    pub category: String,
+    pub cause: String,
+    pub intensity: f32,
+    pub valence: Valence,
+}
+
+/// One action selection for a watched entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub tick: Tick,
+    pub entity: EntityId,
+    pub name: String,
+    /// Task in hand when deciding (an idle task the decision may interrupt)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacing: Option<ActionId>,
+    /// Most pressing need and its level
+    pub need: (NeedType, f32),
+    /// Strongest thought, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thought: Option<TracedThought>,
+    /// Every stage of the cascade, in order
+    pub candidates: Vec<Candidate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chosen: Option<ActionId>,
+    /// Stage that made the choice
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage: Option<DecisionStage>,
+}
+
+/// Which entities are watched, and what they decided
+#[derive(Debug, Clone, Default)]
+pub struct DecisionTrace {
+    watched: AHashSet<EntityId>,
+    records: Vec<DecisionRecord>,
+}
+
+impl DecisionTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record this entity's decisions from now on
+    pub fn watch(&mut self, entity: EntityId) {
+        self.watched.insert(entity);
+    }
+
+    pub fn unwatch(&mut self, entity: EntityId) {
+        self.watched.remove(&entity);
+    }
+
+    pub fn is_watched(&self, entity: EntityId) -> bool {
+        self.watched.contains(&entity)
+    }
+
+    /// Decisions in the order they were made
+    pub fn records(&self) -> &[DecisionRecord] {
+        &self.records
+    }
+
+    /// Write the records as JSON lines
+    pub fn write(&self, out: &mut impl Write) -> Result<(), TraceError> {
+        for record in &self.records {
+            serde_json::to_writer(&mut *out, record)?;
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Write the records to a file, replacing it if it exists
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), TraceError> {
+        let mut file = std::io::BufWriter::new(fs::File::create(path)?);
+        self.write(&mut file)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Read the records of a saved trace
+pub fn load_records(path: impl AsRef<Path>) -> Result<Vec<DecisionRecord>, TraceError> {
+    let file = BufReader::new(fs::File::open(path)?);
+    let mut records = Vec::new();
+    for line in file.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            records.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(records)
+}
+
+/// Record a decision for human `i`, if the world is tracing them
+///
+/// Call before the chosen task is queued, so the task in hand is the one
+/// the decision displaces.
+pub fn record_decision(
+    world: &mut World,
+    i: usize,
+    candidates: Vec<Candidate>,
+    chosen: Option<ActionId>,
+) {
+    let humans = &world.humans;
+    let Some(trace) = world
+        .decision_trace
+        .as_mut()
+        .filter(|trace| trace.is_watched(humans.ids[i]))
+    else {
+        return;
+    };
+    let stage = candidates
+        .iter()
+        .find(|candidate| candidate.action.is_some())
+        .map(|candidate| candidate.stage);
+    trace.records.push(DecisionRecord {
+        tick: world.current_tick,
+        entity: humans.ids[i],
+        name: humans.names[i].clone(),
+        replacing: humans.task_queues[i].current().map(|t| t.action),
+        need: humans.needs[i].most_pressing(),
+        thought: humans.thoughts[i].strongest().map(|t| TracedThought {
+            category: t.concept_category.clone(),
+            cause: t.cause_description.clone(),
+            intensity: t.intensity,
+            valence: t.valence,
+        }),
+        candidates,
+        chosen,
+        stage,
+    });
+}
+
+/// The decisions of one entity, by name (case-insensitive)
+pub fn decisions_of<'a>(
+    records: &'a [DecisionRecord],
+    name: &'a str,
+) -> impl Iterator<Item = &'a DecisionRecord> {
+    records
+        .iter()
+        .filter(move |record| record.name.eq_ignore_ascii_case(name))
+}
+
+/// Why an entity last stopped doing something
+///
+/// Finds the last decision to take up `action` and the decision that came
+/// after it. Returns (started, next); `next` is None if they were still at
+/// it when the trace ended.
+pub fn why_stopped<'a>(
+    records: &'a [DecisionRecord],
+    name: &'a str,
+    action: ActionId,
+) -> Option<(&'a DecisionRecord, Option<&'a DecisionRecord>)> {
+    let decisions: Vec<&DecisionRecord> = decisions_of(records, name).collect();
+    let started = decisions
+        .iter()
+        .rposition(|record| record.chosen == Some(action))?;
+    Some((decisions[started], decisions.get(started + 1).copied()))
+}
+
+/// One decision in a line or two of plain words
+pub fn explain(record: &DecisionRecord) -> String {
+    let chosen = record
+        .chosen
+        .map(|action| format!("{:?}", action))
+        .unwrap_or_else(|| "nothing".into());
+    let stage = record
+        .stage
+        .map(|stage| format!("{:?}", stage))
+        .unwrap_or_else(|| "no stage".into());
+    let (need, level) = record.need;
+    let mut text = format!(
+        "tick {}: {} chose {} ({}), most pressing need {:?} at {:.2}",
+        record.tick, record.name, chosen, stage, need, level
+    );
+    if let Some(thought) = &record.thought {
+        text.push_str(&format!(
+            ", thinking of {} ({:.2}: {})",
+            thought.category, thought.intensity, thought.cause
+        ));
+    }
+    if let Some(replacing) = record.replacing {
+        text.push_str(&format!(", with {:?} in hand", replacing));
+    }
+    let passed_over: Vec<String> = record
+        .candidates
+        .iter()
+        .filter(|candidate| Some(candidate.stage) != record.stage)
+        .filter_map(|candidate| {
+            candidate.action.map(|action| {
+                format!(
+                    "{:?} ({:?} {:.2})",
+                    action, candidate.stage, candidate.score
+                )
+            })
+        })
+        .collect();
+    if !passed_over.is_empty() {
+        text.push_str(&format!("; passed over {}", passed_over.join(", ")));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Vec2;
+    use crate::ecs::world::Abundance;
+    use crate::simulation::tick::run_simulation_tick;
+
+    #[test]
+    fn test_trace_records_watched_entities_and_round_trips() {
+        let mut world = World::with_seed(4);
+        world.add_food_zone(Vec2::new(10.0, 10.0), 10.0, Abundance::Unlimited);
+        let elena = world.spawn_human("Elena".into());
+        world.spawn_human("Bram".into());
+        let mut trace = DecisionTrace::new();
+        trace.watch(elena);
+        world.decision_trace = Some(trace);
+
+        for _ in 0..300 {
+            run_simulation_tick(&mut world);
+        }
+
+        let trace = world.decision_trace.as_ref().unwrap();
+        assert!(!trace.records().is_empty());
+        assert!(trace.records().iter().all(|r| r.entity == elena));
+        for record in trace.records() {
+            // The recorded stage is the one that chose
+            let first = record.candidates.iter().find(|c| c.action.is_some());
+            assert_eq!(first.and_then(|c| c.action), record.chosen);
+        }
+
+        let mut log = Vec::new();
+        trace.write(&mut log).unwrap();
+        let path = std::env::temp_dir().join("arc_citadel_decision_trace_test.jsonl");
+        fs::write(&path, &log).unwrap();
+        let records = load_records(&path).unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!(records.len(), trace.records().len());
+
+        let last = records.last().unwrap().chosen.unwrap();
+        let (started, next) = why_stopped(&records, "elena", last).unwrap();
+        assert_eq!(started.chosen, Some(last));
+        assert!(next.is_none());
+        assert!(explain(started).contains("Elena chose"));
+        assert!(why_stopped(&records, "Bram", last).is_none());
+    }
+}
@@ -0,0 +1,485 @@
+//! Despair - what lasting misery does to a settler, and who can reach them
+//!
+//! Gated by `world.content.despair`; with it off, nobody despairs. A
+//! settler whose mood stays at or below `DESPAIR_MOOD` for
+//! `WITHDRAW_DAYS` withdraws: each day they go off alone, and turn away
+//! from company. After `REFUSAL_DAYS` they refuse food as well, and grow
+//! hungrier by the day. At `BREAKING_DAYS` the disloyal leave the
+//! settlement; under `DespairContent::Full` the rest reach a crisis, and
+//! die that day unless someone reaches them.
+//!
+//! Every day each withdrawn settler's closest friend or best healer may
+//! reach them, the more likely the closer the friend or the more the
+//! healer knows of medicine. Being reached lifts their despair and leaves
+//! them a comforting memory.
+
+use rand::Rng;
+
+use crate::actions::catalog::{ActionCategory, ActionId};
+use crate::core::types::{EntityId, Vec2};
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority, TaskSource};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::epithets::known_as;
+use crate::simulation::happiness::in_settlement;
+use crate::simulation::legacy::{closeness, record_death};
+use crate::simulation::unrest::settlement_centre;
+use crate::skills::ChunkDomain;
+use crate::world::{DespairContent, DespairStage, Despairing, TransformationTrigger};
+
+/// Mood at or below which a settler is in the depths
+pub const DESPAIR_MOOD: f32 = -0.6;
+
+/// Days in the depths before a settler withdraws
+pub const WITHDRAW_DAYS: u32 = 3;
+
+/// Days in the depths before a settler refuses food
+pub const REFUSAL_DAYS: u32 = 6;
+
+/// Days in the depths before a settler leaves or reaches a crisis
+pub const BREAKING_DAYS: u32 = 10;
+
+/// Hunger a settler refusing food gains each day
+pub const REFUSAL_HUNGER: f32 = 0.15;
+
+/// How far from the settlement's centre the withdrawn go to be alone
+pub const ISOLATION_DISTANCE: f32 = 20.0;
+
+/// Loyalty below which a settler at the breaking point leaves
+pub const ABANDON_LOYALTY: f32 = 0.4;
+
+/// How far from the settlement those who abandon it go
+pub const ABANDON_DISTANCE: f32 = 80.0;
+
+/// Daily chance a friend reaches the withdrawn, per unit of closeness
+pub const FRIEND_REACH: f32 = 0.4;
+
+/// Daily chance a healer reaches the withdrawn, before their skill
+pub const HEALER_REACH: f32 = 0.15;
+
+/// Further daily chance a healer reaches the withdrawn, per unit of
+/// medicine encoding
+pub const HEALER_SKILL_REACH: f32 = 0.35;
+
+/// How much more likely anyone is to reach a settler in crisis
+pub const CRISIS_VIGILANCE: f32 = 1.5;
+
+/// Intensity of the memory of being reached
+pub const COMFORT_INTENSITY: f32 = 0.6;
+
+/// Net feeling of human `i`'s thoughts (-1.0 to 1.0)
+pub fn mood(world: &World, i: usize) -> f32 {
+    let net: f32 = world.humans.thoughts[i]
+        .iter()
+        .map(|t| match t.valence {
+            Valence::Positive => t.intensity,
+            Valence::Negative => -t.intensity,
+        })
+        .sum();
+    net.clamp(-1.0, 1.0)
+}
+
+/// How far into despair `days` in the depths take a settler, short of the
+/// breaking point
+fn stage_after(days: u32) -> DespairStage {
+    if days >= REFUSAL_DAYS {
+        DespairStage::RefusingFood
+    } else if days >= WITHDRAW_DAYS {
+        DespairStage::Withdrawn
+    } else {
+        DespairStage::Low
+    }
+}
+
+/// Turn a withdrawn settler's own choice of company, or of food once they
+/// refuse it, into sitting alone; the player's orders still stand
+pub fn withdraw_in_despair(world: &World, i: usize, task: &mut Task) {
+    if task.source == TaskSource::PlayerCommand {
+        return;
+    }
+    let id = world.humans.ids[i];
+    let shunned = match task.action {
+        ActionId::Eat => world.despair.refuses_food(id),
+        action => action.category() == ActionCategory::Social && world.despair.is_withdrawn(id),
+    };
+    if shunned {
+        task.action = ActionId::IdleObserve;
+        task.target_entity = None;
+    }
+}
+
+/// Who might reach human `i`, and how likely they are to today: their
+/// closest friend or their best healer, whichever is likelier
+fn best_reach(world: &World, i: usize) -> Option<(usize, f32)> {
+    world
+        .humans
+        .iter_living()
+        .filter(|&j| j != i && in_settlement(world, j))
+        .filter(|&j| !world.despair.is_withdrawn(world.humans.ids[j]))
+        .map(|j| {
+            let friend = FRIEND_REACH * closeness(world, i, world.humans.ids[j]);
+            let medicine = world.humans.chunk_libraries[j].domain_summary(ChunkDomain::Medicine);
+            let healer = if medicine.chunk_count > 0 {
+                HEALER_REACH + HEALER_SKILL_REACH * medicine.average_encoding()
+            } else {
+                0.0
+            };
+            (j, friend.max(healer))
+        })
+        .filter(|&(_, chance)| chance > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Send human `i` off alone for a while, ahead of whatever they were doing,
+/// unless they have yet to sit alone from an earlier day
+fn go_off_alone(world: &mut World, i: usize) {
+    let sitting_alone =
+        |task: &Task| task.action == ActionId::IdleObserve && task.source == TaskSource::Reaction;
+    if world.humans.task_queues[i].iter().any(sitting_alone) {
+        return;
+    }
+    let tick = world.current_tick;
+    let centre = settlement_centre(world);
+    let away = world.humans.positions[i] - centre;
+    let direction = if away.length() > 0.0 {
+        away.normalize()
+    } else {
+        Vec2::new(1.0, 0.0)
+    };
+    let mut go = Task::new(ActionId::MoveTo, TaskPriority::High, tick)
+        .with_position(centre + direction * ISOLATION_DISTANCE);
+    go.source = TaskSource::Reaction;
+    let mut sit = Task::new(ActionId::IdleObserve, TaskPriority::High, tick);
+    sit.source = TaskSource::Reaction;
+
+    let queue = &mut world.humans.task_queues[i];
+    let kept: Vec<Task> = queue.iter().cloned().collect();
+    queue.clear();
+    for task in [go, sit].into_iter().chain(kept) {
+        queue.push(task);
+    }
+}
+
+/// Human `i` leaves the settlement for good
+fn abandon(world: &mut World, i: usize) {
+    let tick = world.current_tick;
+    let name = known_as(world, i);
+    let faction = world
+        .factions
+        .found(format!("{}'s Band", world.humans.names[i]));
+    world
+        .factions
+        .join(world.humans.ids[i], faction)
+        .expect("faction was just founded");
+    let centre = settlement_centre(world);
+    let away = world.humans.positions[i] - centre;
+    let direction = if away.length() > 0.0 {
+        away.normalize()
+    } else {
+        Vec2::new(1.0, 0.0)
+    };
+    world.humans.assigned_houses[i] = None;
+    let queue = &mut world.humans.task_queues[i];
+    queue.clear();
+    let mut leave = Task::new(ActionId::MoveTo, TaskPriority::Critical, tick)
+        .with_position(centre + direction * ABANDON_DISTANCE);
+    leave.source = TaskSource::Reaction;
+    queue.push(leave);
+    world
+        .legacy
+        .record(tick, format!("{} leaves the settlement in despair", name));
+}
+
+/// Human `helper` reaches human `i` and lifts their despair
+fn reach(world: &mut World, i: usize, helper: usize, crisis: bool) {
+    let tick = world.current_tick;
+    world.despair.lift(world.humans.ids[i]);
+    let helper_name = known_as(world, helper);
+    world.humans.thoughts[i].add(Thought::new(
+        Valence::Positive,
+        COMFORT_INTENSITY,
+        "comfort",
+        format!("was reached by {} in a dark time", helper_name),
+        CauseType::Event,
+        tick,
+    ));
+    if crisis {
+        world.transformations.note(
+            world.humans.ids[i],
+            TransformationTrigger::SpiritualCrisis,
+            tick,
+        );
+        let name = known_as(world, i);
+        world.legacy.record(
+            tick,
+            format!(
+                "{} stays with {} through their darkest hour",
+                helper_name, name
+            ),
+        );
+    }
+}
+
+/// Deepen or lift every settler's despair, and give friends and healers
+/// their chance to reach the withdrawn; run daily after the settlement is
+/// polled
+pub fn run_despair<R: Rng>(world: &mut World, rng: &mut R) {
+    let setting = world.content.despair;
+    if !setting.enabled() {
+        world.despair.despairing.clear();
+        return;
+    }
+    let tick = world.current_tick;
+
+    // Those who died, left, or whose mood lifted are out of the depths
+    let living: Vec<usize> = world
+        .humans
+        .iter_living()
+        .filter(|&i| in_settlement(world, i))
+        .collect();
+    let mut despairing = std::mem::take(&mut world.despair.despairing);
+    despairing.retain(|d| {
+        world
+            .humans
+            .index_of(d.id)
+            .is_some_and(|i| living.contains(&i) && mood(world, i) <= DESPAIR_MOOD)
+    });
+    for &i in &living {
+        let id = world.humans.ids[i];
+        if mood(world, i) <= DESPAIR_MOOD && despairing.iter().all(|d| d.id != id) {
+            despairing.push(Despairing {
+                id,
+                since: tick,
+                days: 0,
+                stage: DespairStage::Low,
+            });
+        }
+    }
+    world.despair.despairing = despairing;
+
+    let ids: Vec<EntityId> = world.despair.despairing.iter().map(|d| d.id).collect();
+    for id in ids {
+        let Some(i) = world.humans.index_of(id) else {
+            continue;
+        };
+        let Some(record) = world.despair.despairing.iter_mut().find(|d| d.id == id) else {
+            continue;
+        };
+        let was = record.stage;
+        if was != DespairStage::Crisis {
+            record.days += 1;
+            record.stage = stage_after(record.days);
+        }
+        let days = record.days;
+        let name = known_as(world, i);
+
+        if was < DespairStage::Withdrawn && days >= WITHDRAW_DAYS {
+            world
+                .legacy
+                .record(tick, format!("{} has withdrawn from everyone", name));
+        }
+        if was < DespairStage::RefusingFood && days >= REFUSAL_DAYS {
+            world
+                .legacy
+                .record(tick, format!("{} has stopped eating", name));
+        }
+        if was != DespairStage::Crisis && days >= BREAKING_DAYS {
+            if world.humans.values[i].loyalty < ABANDON_LOYALTY {
+                world.despair.lift(id);
+                abandon(world, i);
+                continue;
+            }
+            if setting == DespairContent::Full {
+                if let Some(record) = world.despair.despairing.iter_mut().find(|d| d.id == id) {
+                    record.stage = DespairStage::Crisis;
+                }
+                world.legacy.record(tick, format!("{} is in crisis", name));
+            }
+        }
+
+        let stage = world.despair.stage(id).unwrap_or(DespairStage::Low);
+        if stage < DespairStage::Withdrawn {
+            continue;
+        }
+        if stage >= DespairStage::RefusingFood {
+            let needs = &mut world.humans.needs[i];
+            needs.food = (needs.food + REFUSAL_HUNGER).min(1.0);
+        }
+        let crisis = stage == DespairStage::Crisis;
+        let reached = best_reach(world, i).filter(|&(_, chance)| {
+            let chance = if crisis {
+                chance * CRISIS_VIGILANCE
+            } else {
+                chance
+            };
+            rng.gen::<f32>() < chance
+        });
+        match reached {
+            Some((helper, _)) => reach(world, i, helper, crisis),
+            None if crisis => {
+                world.despair.lift(id);
+                world
+                    .legacy
+                    .record(tick, format!("No one reached {} in time", name));
+                world.humans.alive[i] = false;
+                world.humans.assigned_houses[i] = None;
+                world.humans.task_queues[i].clear();
+                record_death(world, i, None);
+            }
+            None => go_off_alone(world, i),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::social::EventType;
+    use crate::skills::{ChunkId, ChunkLibrary, PersonalChunkState};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// A settlement of three, with Ada in the depths and nobody to reach her
+    fn grieving_world(setting: DespairContent) -> World {
+        let mut world = World::with_seed(21);
+        for name in ["Ada", "Bram", "Cora"] {
+            world.spawn_human(name.into());
+        }
+        for i in 0..3 {
+            world.humans.chunk_libraries[i] = ChunkLibrary::new();
+            world.humans.values[i].loyalty = 0.8;
+        }
+        world.humans.thoughts[0].add(Thought::new(
+            Valence::Negative,
+            1.0,
+            "grief",
+            "lost everything",
+            CauseType::Event,
+            0,
+        ));
+        world.content.despair = setting;
+        world
+    }
+
+    fn run_days(world: &mut World, days: u32, rng: &mut ChaCha8Rng) {
+        for _ in 0..days {
+            run_despair(world, rng);
+        }
+    }
+
+    #[test]
+    fn test_despair_is_off_unless_the_content_setting_allows_it() {
+        let mut world = grieving_world(DespairContent::Off);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        run_days(&mut world, BREAKING_DAYS, &mut rng);
+        assert!(world.despair.despairing.is_empty());
+        assert!(world.humans.alive[0]);
+    }
+
+    #[test]
+    fn test_lasting_despair_brings_withdrawal_and_refusing_food() {
+        let mut world = grieving_world(DespairContent::Withdrawal);
+        let ada = world.humans.ids[0];
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+
+        run_days(&mut world, WITHDRAW_DAYS, &mut rng);
+        assert_eq!(world.despair.stage(ada), Some(DespairStage::Withdrawn));
+        let tasks: Vec<ActionId> = world.humans.task_queues[0]
+            .iter()
+            .map(|t| t.action)
+            .collect();
+        assert_eq!(tasks, [ActionId::MoveTo, ActionId::IdleObserve]);
+        let mut talk = Task::new(ActionId::TalkTo, TaskPriority::Normal, 0);
+        withdraw_in_despair(&world, 0, &mut talk);
+        assert_eq!(talk.action, ActionId::IdleObserve);
+
+        run_days(&mut world, REFUSAL_DAYS - WITHDRAW_DAYS, &mut rng);
+        assert!(world.despair.refuses_food(ada));
+        let mut eat = Task::new(ActionId::Eat, TaskPriority::High, 0);
+        withdraw_in_despair(&world, 0, &mut eat);
+        assert_eq!(eat.action, ActionId::IdleObserve);
+        // Nobody else is affected, and the player's orders still stand
+        let mut eat = Task::new(ActionId::Eat, TaskPriority::High, 0);
+        withdraw_in_despair(&world, 1, &mut eat);
+        assert_eq!(eat.action, ActionId::Eat);
+        let mut eat = Task::new(ActionId::Eat, TaskPriority::High, 0).from_player();
+        withdraw_in_despair(&world, 0, &mut eat);
+        assert_eq!(eat.action, ActionId::Eat);
+        assert!(world.humans.needs[0].food > 0.0);
+
+        // Without the full setting the loyal never reach a crisis
+        run_days(&mut world, BREAKING_DAYS, &mut rng);
+        assert_eq!(world.despair.stage(ada), Some(DespairStage::RefusingFood));
+        assert!(world.humans.alive[0]);
+        // Days spent without getting away don't stack up more walks
+        assert_eq!(world.humans.task_queues[0].iter().count(), 2);
+    }
+
+    #[test]
+    fn test_the_disloyal_leave_at_the_breaking_point() {
+        let mut world = grieving_world(DespairContent::Withdrawal);
+        world.humans.values[0].loyalty = 0.1;
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        run_days(&mut world, BREAKING_DAYS, &mut rng);
+        assert!(!in_settlement(&world, 0));
+        assert!(world.despair.despairing.is_empty());
+        assert!(world
+            .legacy
+            .chronicle
+            .iter()
+            .any(|entry| entry.text.ends_with("leaves the settlement in despair")));
+    }
+
+    #[test]
+    fn test_a_crisis_ends_in_death_unless_someone_reaches_them() {
+        let mut world = grieving_world(DespairContent::Full);
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        run_days(&mut world, BREAKING_DAYS - 1, &mut rng);
+        assert!(world.humans.alive[0]);
+        run_days(&mut world, 1, &mut rng);
+        assert!(!world.humans.alive[0]);
+        assert!(world.despair.despairing.is_empty());
+        let chronicle: Vec<&str> = world
+            .legacy
+            .chronicle
+            .iter()
+            .map(|entry| entry.text.as_str())
+            .collect();
+        assert!(chronicle.contains(&"Ada is in crisis"));
+        assert!(chronicle.contains(&"No one reached Ada in time"));
+    }
+
+    #[test]
+    fn test_friends_and_healers_reach_the_withdrawn() {
+        // A devoted friend
+        let mut world = grieving_world(DespairContent::Full);
+        let ada = world.humans.ids[0];
+        let bram = world.humans.ids[1];
+        for _ in 0..20 {
+            world.humans.social_memories[0].record_encounter(bram, EventType::AidReceived, 1.0, 0);
+        }
+        assert!(closeness(&world, 0, bram) > 0.5);
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        run_days(&mut world, BREAKING_DAYS + 1, &mut rng);
+        assert!(world.humans.alive[0]);
+        assert!(world
+            .despair
+            .stage(ada)
+            .is_none_or(|s| s != DespairStage::Crisis));
+        assert!(world.humans.thoughts[0]
+            .iter()
+            .any(|t| t.concept_category == "comfort"));
+
+        // A skilled healer
+        let mut world = grieving_world(DespairContent::Full);
+        let mut state = PersonalChunkState::new(0);
+        state.encoding_depth = 1.0;
+        world.humans.chunk_libraries[2].set_chunk(ChunkId::MedVitalCheck, state);
+        let mut rng = ChaCha8Rng::seed_from_u64(6);
+        run_days(&mut world, BREAKING_DAYS + 1, &mut rng);
+        assert!(world.humans.alive[0]);
+        assert!(world.humans.thoughts[0]
+            .iter()
+            .any(|t| t.cause_description.contains("Cora")));
+    }
+}
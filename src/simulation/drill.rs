@@ -0,0 +1,204 @@
+//! Drill - standing companies trained on the drill ground in peacetime
+//!
+//! While the settlement has a drill ground (`world.drill.ground`) and is at
+//! peace, `DRILL_SQUAD` drill jobs stay posted there. Whoever claims one
+//! walks to the ground and drills; those who know how to give an order and
+//! place a formation suit the work best. A finished session raises each
+//! driller's rating by `DRILL_GAIN`, scaled by the best drillmaster drilling
+//! alongside them, so a squad with nobody to lead it learns nothing. A
+//! settler who goes `DRILL_GRACE_DAYS` without a session loses
+//! `DRILL_DECAY` of their rating a day.
+//!
+//! In battle a unit's drill (the mean of its soldiers') cuts the time its
+//! orders wait at HQ and how much cohesion it loses when shaken; see
+//! `battle::units::BattleUnit::drill`.
+
+use crate::actions::catalog::ActionId;
+use crate::core::time::TICKS_PER_DAY;
+use crate::core::types::Vec2;
+use crate::ecs::world::World;
+use crate::entity::tasks::TaskPriority;
+use crate::simulation::jobs::Job;
+use crate::skills::{ChunkId, ChunkLibrary};
+use crate::world::Layer;
+
+/// Drill jobs kept posted at the drill ground
+pub const DRILL_SQUAD: usize = 8;
+
+/// Settlers within this distance of the ground drill together
+pub const DRILL_RANGE: f32 = 6.0;
+
+/// Rating a session under a master drillmaster gives each driller
+pub const DRILL_GAIN: f32 = 0.05;
+
+/// Days without a session before a settler's drill starts to fade
+pub const DRILL_GRACE_DAYS: u64 = 7;
+
+/// Rating lost each day once drill starts to fade
+pub const DRILL_DECAY: f32 = 0.02;
+
+/// Chunks for running a squad through its drill
+const DRILLMASTER_CHUNKS: [ChunkId; 3] = [
+    ChunkId::LeadClearOrder,
+    ChunkId::LeadIssueCommand,
+    ChunkId::LeadDirectFormation,
+];
+
+/// How well someone can run a drill: mean encoding depth of
+/// `DRILLMASTER_CHUNKS`, counting any not formed as zero
+pub fn drillmaster_skill(library: &ChunkLibrary) -> f32 {
+    let total: f32 = DRILLMASTER_CHUNKS
+        .iter()
+        .filter_map(|&id| library.get_chunk(id))
+        .map(|state| state.encoding_depth)
+        .sum();
+    total / DRILLMASTER_CHUNKS.len() as f32
+}
+
+/// Whether the settlement is at peace: nobody away at a battle or on
+/// campaign, and no blow struck in the last day
+pub fn at_peace(world: &World) -> bool {
+    let away = world.identities.active_in(Layer::Battle).next().is_some()
+        || world.identities.active_in(Layer::Campaign).next().is_some();
+    let fighting =
+        world.situation.fights.last().is_some_and(|fight| {
+            world.current_tick.saturating_sub(fight.last_blow) < TICKS_PER_DAY
+        });
+    !away && !fighting
+}
+
+/// Keep `DRILL_SQUAD` drill jobs posted at the ground while at peace, and
+/// withdraw them otherwise. Returns number of jobs posted.
+pub fn post_drill_jobs(world: &mut World) -> usize {
+    let ground = world.drill.ground.filter(|_| at_peace(world));
+    world.jobs.retain(|job| {
+        job.action != ActionId::Drill
+            || ground.is_some_and(|at| job.position.distance(&at) <= DRILL_RANGE)
+    });
+    let Some(ground) = ground else {
+        return 0;
+    };
+
+    let posted = world
+        .jobs
+        .jobs()
+        .iter()
+        .filter(|job| job.action == ActionId::Drill)
+        .count();
+    for _ in posted..DRILL_SQUAD {
+        let job = Job::new(ActionId::Drill, ground, TaskPriority::Low);
+        world.jobs.post(job, world.current_tick);
+    }
+    DRILL_SQUAD.saturating_sub(posted)
+}
+
+/// Best drillmaster among the living drilling within `DRILL_RANGE` of `ground`
+fn best_drillmaster(world: &World, ground: Vec2) -> f32 {
+    world
+        .humans
+        .iter_living()
+        .filter(|&i| {
+            world.humans.task_queues[i]
+                .current()
+                .is_some_and(|task| task.action == ActionId::Drill)
+                && world.humans.positions[i].distance(&ground) <= DRILL_RANGE
+        })
+        .map(|i| drillmaster_skill(&world.humans.chunk_libraries[i]))
+        .fold(0.0, f32::max)
+}
+
+/// Human `i` has finished a session on the drill ground. Returns the rating
+/// they gained.
+pub fn finish_drill(world: &mut World, i: usize) -> f32 {
+    let Some(ground) = world.drill.ground else {
+        return 0.0;
+    };
+    let gain = DRILL_GAIN * best_drillmaster(world, ground);
+    if gain > 0.0 {
+        let id = world.humans.ids[i];
+        world.drill.practise(id, gain, world.current_tick);
+    }
+    gain
+}
+
+/// Fade the drill of everyone who has gone too long without a session,
+/// forgetting the dead and those with nothing left
+pub fn fade_drill(world: &mut World) {
+    let tick = world.current_tick;
+    let humans = &world.humans;
+    world.drill.drilled.retain_mut(|drilled| {
+        let idle_days = tick.saturating_sub(drilled.last_drilled) / TICKS_PER_DAY;
+        if idle_days > DRILL_GRACE_DAYS {
+            drilled.rating -= DRILL_DECAY;
+        }
+        let living = humans.index_of(drilled.id).is_some_and(|i| humans.alive[i]);
+        living && drilled.rating > 0.0
+    });
+}
+
+/// Run one day of drill: fade unpractised drill, keep the ground's jobs
+/// posted. Returns number of jobs posted.
+pub fn run_drill(world: &mut World) -> usize {
+    fade_drill(world);
+    post_drill_jobs(world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::tasks::Task;
+    use crate::skills::{ChunkLibrary, PersonalChunkState};
+
+    /// A drillmaster and a recruit on the drill ground
+    fn drill_ground() -> World {
+        let mut world = World::with_seed(7);
+        for name in ["Sergeant", "Recruit"] {
+            world.spawn_human(name.into());
+        }
+        let ground = Vec2::new(10.0, 10.0);
+        world.drill.ground = Some(ground);
+        for i in 0..2 {
+            world.humans.chunk_libraries[i] = ChunkLibrary::new();
+            world.humans.positions[i] = ground;
+            world.humans.task_queues[i]
+                .push(Task::new(ActionId::Drill, TaskPriority::Low, 0).with_position(ground));
+        }
+        let mut mastered = PersonalChunkState::new(0);
+        mastered.encoding_depth = 1.0;
+        for id in DRILLMASTER_CHUNKS {
+            world.humans.chunk_libraries[0].set_chunk(id, mastered.clone());
+        }
+        world
+    }
+
+    #[test]
+    fn test_drill_needs_a_drillmaster_and_fades_without_practice() {
+        let mut world = drill_ground();
+        assert_eq!(finish_drill(&mut world, 1), DRILL_GAIN);
+        let recruit = world.humans.ids[1];
+        assert_eq!(world.drill.rating(recruit), DRILL_GAIN);
+
+        // Without the sergeant, the squad drills to no purpose
+        world.humans.task_queues[0].clear();
+        assert_eq!(finish_drill(&mut world, 1), 0.0);
+
+        world.current_tick += DRILL_GRACE_DAYS * TICKS_PER_DAY;
+        fade_drill(&mut world);
+        assert_eq!(world.drill.rating(recruit), DRILL_GAIN);
+        world.current_tick += TICKS_PER_DAY;
+        fade_drill(&mut world);
+        assert!(world.drill.rating(recruit) < DRILL_GAIN);
+    }
+
+    #[test]
+    fn test_drill_jobs_are_posted_only_in_peacetime() {
+        let mut world = drill_ground();
+        assert_eq!(post_drill_jobs(&mut world), DRILL_SQUAD);
+        assert_eq!(post_drill_jobs(&mut world), 0);
+
+        world.situation.note_blows(world.current_tick, 1);
+        assert!(!at_peace(&world));
+        post_drill_jobs(&mut world);
+        assert!(world.jobs.is_empty());
+    }
+}
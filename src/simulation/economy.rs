@@ -0,0 +1,314 @@
+//! Prices from scarcity, trading with stockpiles, and how deals feel
+//!
+//! Barter (`barter.rs`) values goods by each trader's own wants. This module
+//! adds what the goods would fetch at market. A good is dear when its
+//! stockpile is near empty and the resource zones it comes from are worked
+//! out, and cheap when both are full, so prices rise and fall with the
+//! settlement's fortunes without being set by anyone.
+//!
+//! People can also swap surplus goods with their faction's stockpile at those
+//! prices: on market day, attendees with nobody to trade with sell to the
+//! stockpile instead. After a swap, each side weighs what they got against
+//! what they gave at market prices and thinks well or badly of the deal.
+
+use crate::city::stockpile::Stockpile;
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::barter::{trader, Deal, DealKind, Trader};
+use crate::simulation::resource_zone::{ResourceType, ResourceZone};
+use std::collections::BTreeMap;
+
+/// Price of a good with a full stockpile and untouched zones
+pub const BASE_PRICE: f32 = 1.0;
+
+/// Got this much more than was given (at market prices) to be pleased
+pub const GOOD_DEAL_RATIO: f32 = 1.25;
+
+/// Got this little of what was given (at market prices) to feel cheated
+pub const BAD_DEAL_RATIO: f32 = 0.8;
+
+/// How empty the stockpile is of a resource, from 0 (full) to 1 (empty)
+pub fn scarcity(stockpile: &Stockpile, resource: ResourceType) -> f32 {
+    let held = stockpile.get(resource);
+    let room = held + stockpile.space(resource);
+    if room == 0 {
+        return 1.0;
+    }
+    1.0 - held as f32 / room as f32
+}
+
+/// How worked out the zones yielding a resource are, from 0 to 1 (0 if none)
+fn depletion(zones: &[ResourceZone], resource: ResourceType) -> f32 {
+    let (left, count) = zones
+        .iter()
+        .filter(|z| z.resource_type == resource && z.max > 0.0)
+        .fold((0.0, 0), |(left, count), z| {
+            (left + z.current / z.max, count + 1)
+        });
+    if count == 0 {
+        return 0.0;
+    }
+    1.0 - left / count as f32
+}
+
+/// What each good fetches at market
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prices {
+    prices: BTreeMap<ResourceType, f32>,
+}
+
+impl Prices {
+    /// Prices set by a stockpile's stores and the land's resource zones
+    pub fn new(stockpile: &Stockpile, zones: &[ResourceZone]) -> Self {
+        let prices = ResourceType::ALL
+            .into_iter()
+            .map(|r| {
+                let price = BASE_PRICE * (1.0 + scarcity(stockpile, r) + depletion(zones, r));
+                (r, price)
+            })
+            .collect();
+        Self { prices }
+    }
+
+    /// Prices at the market human `i` trades in
+    pub fn for_human(world: &World, i: usize) -> Self {
+        Self::new(home_stockpile(world, i), &world.resource_zones)
+    }
+
+    pub fn price(&self, resource: ResourceType) -> f32 {
+        self.prices.get(&resource).copied().unwrap_or(BASE_PRICE)
+    }
+
+    /// Market value of some goods
+    pub fn value(&self, goods: Option<(ResourceType, u32)>) -> f32 {
+        goods.map_or(0.0, |(r, amount)| self.price(r) * amount as f32)
+    }
+}
+
+/// The stockpile of human `i`'s faction (the settlement's if they have none)
+fn home_stockpile(world: &World, i: usize) -> &Stockpile {
+    world
+        .factions
+        .faction_of(world.humans.ids[i])
+        .and_then(|f| world.faction_stockpile(f))
+        .unwrap_or(&world.stockpile)
+}
+
+/// Goods swapped with a stockpile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StockpileTrade {
+    /// What the trader hands in
+    pub gives: (ResourceType, u32),
+    /// What the trader takes out
+    pub takes: (ResourceType, u32),
+}
+
+/// The swap with a stockpile that suits `trader` best, if any suits them
+///
+/// The stockpile trades at market prices, rounded to whole units: a dear
+/// good fetches several cheap ones, and several cheap goods buy a dear one.
+/// It only takes what it has room for and gives what it holds.
+pub fn propose_stockpile_trade(
+    trader: &Trader,
+    stockpile: &Stockpile,
+    prices: &Prices,
+) -> Option<StockpileTrade> {
+    let mut best: Option<(StockpileTrade, f32)> = None;
+    for &sold in trader.possessions.goods.keys() {
+        for bought in ResourceType::ALL {
+            if bought == sold {
+                continue;
+            }
+            let ratio = prices.price(sold) / prices.price(bought);
+            let (give, take) = if ratio >= 1.0 {
+                (1, ratio.round() as u32)
+            } else {
+                ((1.0 / ratio).round() as u32, 1)
+            };
+            if stockpile.get(bought) < take || stockpile.space(sold) < give {
+                continue;
+            }
+            let Some(loss) = trader.loss(sold, give) else {
+                continue;
+            };
+            let net = trader.gain(bought, take) - loss;
+            if net > 0.0 && best.is_none_or(|(_, n)| net > n) {
+                let trade = StockpileTrade {
+                    gives: (sold, give),
+                    takes: (bought, take),
+                };
+                best = Some((trade, net));
+            }
+        }
+    }
+    best.map(|(trade, _)| trade)
+}
+
+/// What human `i` would swap with their faction's stockpile right now
+pub fn propose_for(world: &World, i: usize) -> Option<StockpileTrade> {
+    let side = trader(world, i, world.humans.ids[i]);
+    let stockpile = home_stockpile(world, i);
+    propose_stockpile_trade(
+        &side,
+        stockpile,
+        &Prices::new(stockpile, &world.resource_zones),
+    )
+}
+
+/// Swap goods between human `i` and their faction's stockpile
+pub fn trade_with_stockpile(world: &mut World, i: usize) -> Option<StockpileTrade> {
+    let trade = propose_for(world, i)?;
+    let prices = Prices::for_human(world, i);
+    let faction = world
+        .factions
+        .faction_of(world.humans.ids[i])
+        .filter(|&f| world.faction_stockpile(f).is_some());
+    let stockpile = match faction {
+        Some(f) => world
+            .faction_stockpile_mut(f)
+            .expect("faction has a stockpile"),
+        None => &mut world.stockpile,
+    };
+
+    let (sold, give) = trade.gives;
+    let (bought, take) = trade.takes;
+    let taken = stockpile.remove(bought, take);
+    stockpile.add(sold, give);
+    world.humans.possessions[i].remove(sold, give);
+    world.humans.possessions[i].add(bought, taken);
+
+    let tick = world.current_tick;
+    if let Some(thought) = deal_thought(&prices, Some(trade.gives), Some(trade.takes), None, tick) {
+        world.humans.thoughts[i].add(thought);
+    }
+    Some(trade)
+}
+
+/// How someone feels about giving `gave` for `got`, if it was far from fair
+pub fn deal_thought(
+    prices: &Prices,
+    gave: Option<(ResourceType, u32)>,
+    got: Option<(ResourceType, u32)>,
+    partner: Option<EntityId>,
+    tick: u64,
+) -> Option<Thought> {
+    let (gave, got) = (prices.value(gave), prices.value(got));
+    if gave <= 0.0 || got <= 0.0 {
+        return None;
+    }
+    let ratio = got / gave;
+    let (valence, description) = if ratio >= GOOD_DEAL_RATIO {
+        (Valence::Positive, "got a good deal")
+    } else if ratio <= BAD_DEAL_RATIO {
+        (Valence::Negative, "was cheated in a trade")
+    } else {
+        return None;
+    };
+    let intensity = (0.3 + ratio.ln().abs() * 0.5).min(0.8);
+    let mut thought = Thought::new(
+        valence,
+        intensity,
+        "trade",
+        description,
+        CauseType::Action,
+        tick,
+    );
+    thought.cause_entity = partner;
+    Some(thought)
+}
+
+/// Give humans `a` and `b` their thoughts on a swap they just made
+///
+/// Gifts on credit and repaid favors are not weighed as deals.
+pub fn judge_deal(world: &mut World, a: usize, b: usize, deal: &Deal) {
+    if deal.kind != DealKind::Swap {
+        return;
+    }
+    let prices = Prices::for_human(world, a);
+    let tick = world.current_tick;
+    let (a_id, b_id) = (world.humans.ids[a], world.humans.ids[b]);
+    if let Some(thought) = deal_thought(&prices, deal.a_gives, deal.b_gives, Some(b_id), tick) {
+        world.humans.thoughts[a].add(thought);
+    }
+    if let Some(thought) = deal_thought(&prices, deal.b_gives, deal.a_gives, Some(a_id), tick) {
+        world.humans.thoughts[b].add(thought);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Vec2;
+    use crate::entity::needs::Needs;
+    use crate::entity::possessions::Possessions;
+    use crate::entity::species::human::HumanValues;
+
+    #[test]
+    fn test_prices_follow_scarcity() {
+        let mut stockpile = Stockpile::new();
+        stockpile.add(ResourceType::Wood, 90);
+        stockpile.add(ResourceType::Stone, 10);
+        let prices = Prices::new(&stockpile, &[]);
+        assert!(prices.price(ResourceType::Stone) > prices.price(ResourceType::Wood));
+
+        // A worked-out forest makes wood dear again
+        let mut forest = ResourceZone::new(Vec2::new(0.0, 0.0), ResourceType::Wood, 5.0);
+        forest.current = 0.1;
+        let logged = Prices::new(&stockpile, &[forest]);
+        assert!(logged.price(ResourceType::Wood) > prices.price(ResourceType::Wood));
+
+        // Selling scarce stone to the stockpile fetches several planks
+        let mut goods = Possessions::new();
+        goods.add(ResourceType::Stone, 3);
+        let needs = Needs::default();
+        let values = HumanValues {
+            ambition: 0.8,
+            ..HumanValues::default()
+        };
+        let side = Trader {
+            id: EntityId::new(),
+            possessions: &goods,
+            needs: &needs,
+            values: &values,
+            skill: 0.5,
+            trusts_partner: false,
+        };
+        let trade = propose_stockpile_trade(&side, &stockpile, &prices).unwrap();
+        assert_eq!(trade.gives, (ResourceType::Stone, 1));
+        assert_eq!(trade.takes.0, ResourceType::Wood);
+        assert!(trade.takes.1 > 1);
+    }
+
+    #[test]
+    fn test_lopsided_deals_are_remembered() {
+        let mut world = World::with_seed(3);
+        world.spawn_human("Ada".into());
+        world.spawn_human("Bram".into());
+        world.stockpile.add(ResourceType::Food, 100);
+        let deal = Deal {
+            a_gives: Some((ResourceType::Food, 1)),
+            b_gives: Some((ResourceType::Iron, 1)),
+            kind: DealKind::Swap,
+        };
+        judge_deal(&mut world, 0, 1, &deal);
+
+        // Plentiful food for scarce iron: Ada did well, Bram did not
+        let ada = world.humans.thoughts[0].iter().next().unwrap();
+        assert_eq!(ada.valence, Valence::Positive);
+        assert_eq!(ada.cause_entity, Some(world.humans.ids[1]));
+        let bram = world.humans.thoughts[1].iter().next().unwrap();
+        assert_eq!(bram.valence, Valence::Negative);
+
+        // Ada sells her iron to the stockpile for food
+        world.humans.possessions[0].add(ResourceType::Iron, 1);
+        world.humans.needs[0].food = 0.9;
+        let trade = trade_with_stockpile(&mut world, 0).unwrap();
+        assert_eq!(trade.gives, (ResourceType::Iron, 1));
+        assert_eq!(world.stockpile.get(ResourceType::Iron), 1);
+        assert_eq!(
+            world.humans.possessions[0].get(ResourceType::Food),
+            trade.takes.1
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Epithets - naming settlers for what they have done
+//!
+//! `perform` records a feat and, the first time someone does something
+//! notable, gives them its epithet and chronicles it. Slayings are reported
+//! from combat, masterworks from crafting (by those who know how to forge
+//! one), and famines are tracked here: a run of at least `FAMINE_DAYS`
+//! days on which settlers went hungry, survived by everyone who was alive
+//! when it began. The player can also rename anyone.
+
+use crate::ecs::world::World;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::tick::TICKS_PER_DAY;
+use crate::world::{Feat, IdentityError};
+
+/// Hungry days in a row that make a famine
+pub const FAMINE_DAYS: u64 = 5;
+
+/// How human `i` is known: their name and epithet, e.g. "Sarah Wolfsbane"
+pub fn known_as(world: &World, i: usize) -> String {
+    world
+        .identities
+        .get(world.humans.ids[i])
+        .map(|identity| identity.known_as())
+        .unwrap_or_else(|| world.humans.names[i].clone())
+}
+
+/// Human `i` performed a feat. Each kind of feat is remembered once per
+/// person; returns whether it earned them an epithet.
+pub fn perform(world: &mut World, i: usize, feat: Feat) -> bool {
+    let id = world.humans.ids[i];
+    if world.feats.of(id).any(|done| done == feat) {
+        return false;
+    }
+    let tick = world.current_tick;
+    world.feats.record.push((tick, id, feat));
+    world.humans.thoughts[i].add(Thought::new(
+        Valence::Positive,
+        0.6,
+        "pride",
+        feat.describe(),
+        CauseType::Action,
+        tick,
+    ));
+    if !world
+        .identities
+        .grant_epithet(id, feat.epithet())
+        .unwrap_or(false)
+    {
+        return false;
+    }
+    let text = format!(
+        "{} {} and is called {} from now on",
+        world.humans.names[i],
+        feat.describe(),
+        known_as(world, i)
+    );
+    world.legacy.record(tick, text);
+    true
+}
+
+/// Follow a famine from the day's hungry count, naming those who lived
+/// through it once it ends
+pub fn track_famine(world: &mut World, hungry: u32) {
+    let tick = world.current_tick;
+    if hungry > 0 {
+        world.feats.famine_since.get_or_insert(tick);
+        return;
+    }
+    let Some(since) = world.feats.famine_since.take() else {
+        return;
+    };
+    let days = (tick - since) / TICKS_PER_DAY;
+    if days < FAMINE_DAYS {
+        return;
+    }
+    world
+        .legacy
+        .record(tick, format!("The famine ends after {} days", days));
+    let survivors: Vec<usize> = world
+        .humans
+        .iter_living()
+        .filter(|&i| world.humans.birth_ticks[i] <= since)
+        .collect();
+    for i in survivors {
+        perform(world, i, Feat::SurvivedFamine);
+    }
+}
+
+/// Give human `i` a new name, keeping any epithet
+pub fn rename(world: &mut World, i: usize, name: &str) -> Result<(), IdentityError> {
+    let id = world.humans.ids[i];
+    let before = known_as(world, i);
+    world.identities.rename(id, name.to_string())?;
+    world.humans.names[i] = name.to_string();
+    let text = format!("{} is henceforth known as {}", before, known_as(world, i));
+    world.legacy.record(world.current_tick, text);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_famine_survivors_earn_an_epithet_once() {
+        let mut world = World::with_seed(41);
+        world.spawn_human("Sarah".into());
+        world.spawn_human("Tom".into());
+        world
+            .identities
+            .grant_epithet(world.humans.ids[1], "the Tall")
+            .unwrap();
+
+        for day in 0..=FAMINE_DAYS {
+            world.current_tick = day * TICKS_PER_DAY;
+            track_famine(&mut world, 2);
+        }
+        world.current_tick += TICKS_PER_DAY;
+        track_famine(&mut world, 0);
+
+        assert_eq!(known_as(&world, 0), "Sarah the Lean");
+        // An epithet already earned is kept
+        assert_eq!(known_as(&world, 1), "Tom the Tall");
+        assert_eq!(world.feats.record.len(), 2);
+        assert!(!perform(&mut world, 0, Feat::SurvivedFamine));
+        assert!(world.legacy.chronicle.iter().any(|entry| entry.text
+            == "Sarah lived through the famine and is called Sarah the Lean from now on"));
+
+        rename(&mut world, 0, "Sal").unwrap();
+        assert_eq!(known_as(&world, 0), "Sal the Lean");
+        assert_eq!(world.humans.names[0], "Sal");
+    }
+}
@@ -0,0 +1,371 @@
+//! Eras - milestones, the ceremonies that mark them, and each era's rules
+//!
+//! A scenario's campaign arc is read from `data/eras.toml` into
+//! `world.era_rules`: the eras in order, each with the milestone that begins
+//! it (years survived, a rival faction defeated, settlers counted). Once a
+//! day `run_eras` checks the next era's milestone. When it is met the era
+//! begins (`begin_era`):
+//!
+//! - The chronicle opens a new chapter.
+//! - The settlers gather for the era's ceremony, if it has one: each walks to
+//!   the gathering place and takes a `"celebration"` thought from it, which
+//!   the opinion poll counts among festivals.
+//! - The era's rules take hold: the blueprints it unlocks can be placed, and
+//!   work goes at its pace.
+//!
+//! A blueprint some era unlocks cannot be placed before that era begins
+//! (`blueprint_locked`); every other blueprint is open from the start.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::actions::catalog::ActionId;
+use crate::core::astronomy::YEAR_LENGTH;
+use crate::core::types::{EntityId, Vec2};
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::happiness::in_settlement;
+use crate::world::{EraBegun, Stance};
+
+/// Strength of the thought each settler takes from an era's ceremony
+pub const CEREMONY_INTENSITY: f32 = 0.6;
+
+/// Loyalty each settler gains at an era's ceremony
+pub const CEREMONY_LOYALTY: f32 = 0.02;
+
+/// Errors from loading the era table
+#[derive(Error, Debug)]
+pub enum EraError {
+    /// Reading the file failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The file is not valid era TOML
+    #[error("Parse error: {0}")]
+    ParseError(#[from] toml::de::Error),
+}
+
+/// What must hold for an era to begin (every condition given, or at once
+/// if none is)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Milestone {
+    /// Full years the settlement has stood
+    #[serde(default)]
+    pub years: Option<u32>,
+    /// A faction at war with the settlement has no one left alive
+    #[serde(default)]
+    pub rival_defeated: bool,
+    /// Living settlers
+    #[serde(default)]
+    pub settlers: Option<usize>,
+}
+
+/// One era of the campaign arc
+#[derive(Debug, Clone, Deserialize)]
+pub struct EraRule {
+    pub name: String,
+    #[serde(default)]
+    pub milestone: Milestone,
+    /// Title of the chronicle chapter it opens (the era's name if not given)
+    #[serde(default)]
+    pub chapter: Option<String>,
+    /// The ceremony the settlers gather for, if any
+    #[serde(default)]
+    pub ceremony: Option<String>,
+    /// Blueprints that cannot be placed until it begins
+    #[serde(default)]
+    pub unlocks: Vec<String>,
+    /// Factor on the pace of work from now on, if it changes
+    #[serde(default)]
+    pub work_pace: Option<f32>,
+}
+
+/// The campaign arc: every era, in the order they come
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EraRules {
+    #[serde(default)]
+    pub eras: Vec<EraRule>,
+}
+
+impl EraRules {
+    /// No eras: the settlement's story is one long chapter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(content: &str) -> Result<Self, EraError> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EraError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EraRule> {
+        self.eras.iter().find(|era| era.name == name)
+    }
+
+    /// Whether some era has to unlock this blueprint
+    pub fn gates(&self, blueprint: &str) -> bool {
+        self.eras
+            .iter()
+            .any(|era| era.unlocks.iter().any(|b| b == blueprint))
+    }
+}
+
+/// Load the campaign arc from `data/eras.toml`
+pub(crate) fn load_default_era_rules() -> EraRules {
+    EraRules::load("data/eras.toml").unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load eras: {}", e);
+        EraRules::new()
+    })
+}
+
+/// Full years since the settlement was founded
+pub fn years_survived(world: &World) -> u32 {
+    world.astronomy.current_day / YEAR_LENGTH as u32
+}
+
+/// Whether the entity is alive in the settlement's world
+fn is_alive(world: &World, id: EntityId) -> bool {
+    world
+        .get_entity_info(id)
+        .and_then(|(species, i)| world.archetype(species)?.components().alive.get(i).copied())
+        .unwrap_or(false)
+}
+
+/// Whether some faction at war with the settlement has members, none alive
+pub fn rival_defeated(world: &World) -> bool {
+    let Some(settlement) = world.factions.settlement() else {
+        return false;
+    };
+    world.factions.iter().any(|faction| {
+        if world.factions.stance(settlement, faction.id) != Stance::Hostile {
+            return false;
+        }
+        let mut members = world.factions.members(faction.id).peekable();
+        members.peek().is_some() && members.all(|id| !is_alive(world, id))
+    })
+}
+
+/// Whether every condition of a milestone holds
+pub fn milestone_reached(world: &World, milestone: &Milestone) -> bool {
+    let settlers = || {
+        world
+            .humans
+            .iter_living()
+            .filter(|&i| in_settlement(world, i))
+            .count()
+    };
+    milestone
+        .years
+        .is_none_or(|years| years_survived(world) >= years)
+        && (!milestone.rival_defeated || rival_defeated(world))
+        && milestone.settlers.is_none_or(|n| settlers() >= n)
+}
+
+/// Begin the next era if its milestone has been reached; returns its name
+pub fn run_eras(world: &mut World) -> Option<String> {
+    let next = world
+        .era_rules
+        .eras
+        .iter()
+        .find(|era| !world.eras.has_begun(&era.name))?;
+    if !milestone_reached(world, &next.milestone) {
+        return None;
+    }
+    let era = next.clone();
+    begin_era(world, &era);
+    Some(era.name)
+}
+
+/// Begin an era now, whatever its milestone: open its chapter, hold its
+/// ceremony, and put its rules in force
+pub fn begin_era(world: &mut World, era: &EraRule) {
+    let tick = world.current_tick;
+    world.eras.begun.push(EraBegun {
+        name: era.name.clone(),
+        tick,
+        day: world.astronomy.current_day,
+    });
+    for blueprint in &era.unlocks {
+        if !world.eras.has_unlocked(blueprint) {
+            world.eras.unlocked.push(blueprint.clone());
+        }
+    }
+    if let Some(pace) = era.work_pace {
+        world.eras.work_pace = pace;
+    }
+
+    let title = era.chapter.as_deref().unwrap_or(&era.name);
+    world.legacy.begin_chapter(tick, title);
+    world
+        .legacy
+        .record(tick, format!("Here begins {}", era.name));
+    if let Some(ceremony) = &era.ceremony {
+        hold_ceremony(world, ceremony);
+    }
+    if !era.unlocks.is_empty() {
+        world.legacy.record(
+            tick,
+            format!("The settlers learned to build: {}", era.unlocks.join(", ")),
+        );
+    }
+}
+
+/// Where the settlers gather: the first building raised, or else their midst
+fn gathering_place(world: &World) -> Option<Vec2> {
+    if let Some(&position) = world.buildings.positions.first() {
+        return Some(position);
+    }
+    let settlers: Vec<Vec2> = world
+        .humans
+        .iter_living()
+        .filter(|&i| in_settlement(world, i))
+        .map(|i| world.humans.positions[i])
+        .collect();
+    if settlers.is_empty() {
+        return None;
+    }
+    let sum = settlers.iter().fold(Vec2::new(0.0, 0.0), |sum, &p| {
+        Vec2::new(sum.x + p.x, sum.y + p.y)
+    });
+    let n = settlers.len() as f32;
+    Some(Vec2::new(sum.x / n, sum.y / n))
+}
+
+/// Gather every settler for a ceremony, which gladdens them and binds them
+/// closer to the settlement
+fn hold_ceremony(world: &mut World, ceremony: &str) {
+    let tick = world.current_tick;
+    let place = gathering_place(world);
+    for i in world.humans.iter_living().collect::<Vec<_>>() {
+        if !in_settlement(world, i) {
+            continue;
+        }
+        if let Some(place) = place {
+            world.humans.task_queues[i]
+                .push(Task::new(ActionId::MoveTo, TaskPriority::Normal, tick).with_position(place));
+        }
+        let values = &mut world.humans.values[i];
+        values.loyalty = (values.loyalty + CEREMONY_LOYALTY).min(1.0);
+        world.humans.thoughts[i].add(Thought::new(
+            Valence::Positive,
+            CEREMONY_INTENSITY,
+            "celebration",
+            format!("gathered for {}", ceremony),
+            CauseType::Event,
+            tick,
+        ));
+    }
+    world
+        .legacy
+        .record(tick, format!("The settlers gathered for {}", ceremony));
+}
+
+/// Whether a blueprint waits on an era that has not begun
+pub fn blueprint_locked(world: &World, blueprint: &str) -> bool {
+    world.era_rules.gates(blueprint) && !world.eras.has_unlocked(blueprint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::site::SiteError;
+    use crate::simulation::sites::place_blueprint;
+
+    fn arc() -> EraRules {
+        EraRules::parse(
+            r#"
+            [[eras]]
+            name = "The First Century"
+            chapter = "A Hundred Winters"
+            ceremony = "the jubilee of the founding"
+            unlocks = ["watchtower"]
+            work_pace = 1.1
+            milestone = { years = 100 }
+
+            [[eras]]
+            name = "The Reckoning"
+            milestone = { rival_defeated = true }
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_shipped_arc_loads() {
+        let rules = EraRules::load("data/eras.toml").unwrap();
+        assert!(!rules.eras.is_empty());
+        assert!(rules.eras.iter().any(|era| era.milestone.years.is_some()));
+        assert!(rules.eras.iter().any(|era| era.milestone.rival_defeated));
+    }
+
+    #[test]
+    fn test_a_century_brings_a_new_era() {
+        let mut world = World::with_seed(70);
+        world.era_rules = arc();
+        world.spawn_human("Thomas".into());
+        world.humans.values[0].loyalty = 0.5;
+
+        assert_eq!(run_eras(&mut world), None);
+        world.astronomy.current_day = 100 * YEAR_LENGTH as u32;
+        assert_eq!(run_eras(&mut world).as_deref(), Some("The First Century"));
+        // Only once; the next era waits on its own milestone
+        assert_eq!(run_eras(&mut world), None);
+
+        assert_eq!(world.eras.current().unwrap().name, "The First Century");
+        assert_eq!(world.eras.work_pace, 1.1);
+        assert_eq!(world.legacy.chapters[0].title, "A Hundred Winters");
+        assert!(world
+            .legacy
+            .chapter_entries(0)
+            .iter()
+            .any(|entry| entry.text.contains("jubilee")));
+        assert!(world.humans.thoughts[0]
+            .iter()
+            .any(|t| t.concept_category == "celebration" && t.valence == Valence::Positive));
+        assert!((world.humans.values[0].loyalty - 0.5 - CEREMONY_LOYALTY).abs() < 1e-6);
+        assert!(world.humans.task_queues[0].current().is_some());
+    }
+
+    #[test]
+    fn test_defeating_a_rival_begins_an_era() {
+        let mut world = World::with_seed(71);
+        world.era_rules = arc();
+        world.spawn_human("Thomas".into());
+        let era = world.era_rules.eras[0].clone();
+        begin_era(&mut world, &era);
+
+        let orc = world.spawn_orc("Grak".into());
+        assert!(!rival_defeated(&world));
+        assert_eq!(run_eras(&mut world), None);
+
+        let (_, i) = world.get_entity_info(orc).unwrap();
+        world.orcs.alive[i] = false;
+        assert!(rival_defeated(&world));
+        assert_eq!(run_eras(&mut world).as_deref(), Some("The Reckoning"));
+        // No chapter title given: the era's name
+        assert_eq!(world.legacy.chapters[1].title, "The Reckoning");
+    }
+
+    #[test]
+    fn test_eras_unlock_blueprints() {
+        let mut world = World::with_seed(72);
+        world.era_rules = arc();
+
+        assert!(blueprint_locked(&world, "watchtower"));
+        assert!(!blueprint_locked(&world, "wooden_house"));
+        assert!(matches!(
+            place_blueprint(&mut world, "watchtower", Vec2::new(5.0, 5.0)),
+            Err(SiteError::Locked(_))
+        ));
+
+        let era = world.era_rules.eras[0].clone();
+        begin_era(&mut world, &era);
+        assert!(!blueprint_locked(&world, "watchtower"));
+        assert!(place_blueprint(&mut world, "watchtower", Vec2::new(5.0, 5.0)).is_ok());
+    }
+}
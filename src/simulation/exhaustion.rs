@@ -0,0 +1,126 @@
+//! Chronic exhaustion system
+//!
+//! Once a day, each human's work ticks are turned into exhaustion (see
+//! `BodyState::end_workday`). Exhausted workers brood over it, risk injury,
+//! and past the limit collapse into forced rest. Light days and rest days
+//! wear exhaustion back down.
+
+use rand::Rng;
+
+use crate::actions::catalog::ActionId;
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+
+/// Exhaustion above which workers resent their workload
+pub const RESENTMENT_THRESHOLD: f32 = 0.5;
+
+/// Pain added by an overwork injury
+pub const INJURY_PAIN: f32 = 0.2;
+
+/// What one day of exhaustion upkeep did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExhaustionReport {
+    pub injured: usize,
+    pub collapsed: usize,
+}
+
+/// Settle the day's work for every living human
+pub fn update_exhaustion<R: Rng>(world: &mut World, rng: &mut R) -> ExhaustionReport {
+    let mut report = ExhaustionReport::default();
+    let living: Vec<usize> = world.humans.iter_living().collect();
+
+    for i in living {
+        let body = &mut world.humans.body_states[i];
+        let worked = body.work_ticks_today > 0;
+        body.end_workday();
+        let exhaustion = body.exhaustion;
+
+        if worked && rng.gen::<f32>() < body.injury_chance() {
+            body.pain = (body.pain + INJURY_PAIN).min(1.0);
+            report.injured += 1;
+        }
+
+        if exhaustion > RESENTMENT_THRESHOLD {
+            world.humans.thoughts[i].add(Thought::new(
+                Valence::Negative,
+                exhaustion,
+                "overwork",
+                "worked to exhaustion",
+                CauseType::Need,
+                world.current_tick,
+            ));
+        }
+
+        if world.humans.body_states[i].has_collapsed() {
+            // Collapse: drop everything and rest until fatigue clears
+            world.humans.body_states[i].fatigue = 1.0;
+            world.humans.task_queues[i].clear();
+            world.humans.task_queues[i].push(Task::new(
+                ActionId::Rest,
+                TaskPriority::Critical,
+                world.current_tick,
+            ));
+            tracing::debug!("{} collapsed from overwork", world.humans.names[i]);
+            report.collapsed += 1;
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::body::SUSTAINABLE_WORK_TICKS;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_overwork_builds_to_collapse_and_rest_restores() {
+        let mut world = World::new();
+        world.spawn_human("Drudge".into());
+        let mut rng = StdRng::seed_from_u64(3);
+
+        // Working every tick of every day
+        let mut days = 0;
+        loop {
+            world.humans.body_states[0].work_ticks_today = 1000;
+            days += 1;
+            if update_exhaustion(&mut world, &mut rng).collapsed > 0 {
+                break;
+            }
+            assert!(days < 10, "Never collapsed");
+        }
+        assert_eq!(days, 4);
+        assert_eq!(
+            world.humans.task_queues[0].current().map(|t| t.action),
+            Some(ActionId::Rest)
+        );
+        assert!(world.humans.thoughts[0]
+            .iter()
+            .any(|t| t.concept_category == "overwork"));
+
+        // Rest days bring them back below the overwork line
+        for _ in 0..2 {
+            update_exhaustion(&mut world, &mut rng);
+        }
+        assert!(!world.humans.body_states[0].is_overworked());
+    }
+
+    #[test]
+    fn test_sustainable_pace_holds_steady() {
+        let mut world = World::new();
+        world.spawn_human("Steady".into());
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for _ in 0..30 {
+            world.humans.body_states[0].work_ticks_today = SUSTAINABLE_WORK_TICKS;
+            assert_eq!(
+                update_exhaustion(&mut world, &mut rng),
+                ExhaustionReport::default()
+            );
+        }
+        assert_eq!(world.humans.body_states[0].exhaustion, 0.0);
+    }
+}
@@ -0,0 +1,391 @@
+//! Exile - banishing settlers, and their return
+//!
+//! An exile is taken off the map (they keep their goods, which leave with
+//! them) and out of the settlement faction. Those close to them grieve, and
+//! bear a grudge against whoever banished them. Away, life is hard or kind
+//! to them day by day; the hardship can kill them, and it sours the
+//! impious. When their time is up the survivors come back to the edge of
+//! the map: the bitter as raiders at the head of a hostile band, the
+//! humbled (or the pious and honorable) as penitents asking to be let back
+//! in, and the rest as traders with goods to sell.
+
+use rand::Rng;
+
+use crate::actions::catalog::ActionId;
+use crate::core::types::{EntityId, Vec2};
+use crate::ecs::world::World;
+use crate::entity::social::EventType;
+use crate::entity::tasks::{Task, TaskPriority, TaskSource};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::epithets::known_as;
+use crate::simulation::legacy::mourners;
+use crate::simulation::resource_zone::ResourceType;
+use crate::simulation::tick::TICKS_PER_DAY;
+use crate::simulation::unrest::settlement_centre;
+use crate::world::{Exile, ExileError, ExileStatus, ReturnRole, Stance};
+
+/// Shortest time away
+pub const EXILE_MIN_DAYS: u64 = 20;
+
+/// Longest time away (for the most curious, who wander furthest)
+pub const EXILE_MAX_DAYS: u64 = 60;
+
+/// How far from the settlement exiles leave and come back
+pub const EXILE_DISTANCE: f32 = 90.0;
+
+/// Grief of those closest to an exile
+pub const GRIEF_INTENSITY: f32 = 0.6;
+
+/// Grudge of those closest to an exile against whoever banished them
+pub const ANGER_INTENSITY: f32 = 0.7;
+
+/// Largest daily change in an exile's hardship
+pub const HARDSHIP_SWING: f32 = 0.1;
+
+/// Daily chance of dying abroad at full hardship
+pub const DEATH_ABROAD_CHANCE: f32 = 0.02;
+
+/// Daily change in bitterness per point of hardship above the exile's piety
+pub const BITTERNESS_DRIFT: f32 = 0.02;
+
+/// Bitterness at which an exile comes back to raid
+pub const RAIDER_BITTERNESS: f32 = 0.6;
+
+/// Hardship that humbles an exile into coming back a penitent
+pub const PENITENT_HARDSHIP: f32 = 0.5;
+
+/// Piety or honor that brings an exile back a penitent regardless
+pub const PENITENT_VIRTUE: f32 = 0.7;
+
+/// Most goods a trader brings back of each kind (fewer after hardship)
+pub const TRADER_GOODS: u32 = 10;
+
+/// Banish human `i` from the settlement
+///
+/// `banished_by` is whoever passed the sentence (None for the player).
+pub fn exile(
+    world: &mut World,
+    i: usize,
+    reason: &str,
+    banished_by: Option<EntityId>,
+) -> Result<(), ExileError> {
+    let id = world.humans.ids[i];
+    if !world.humans.alive[i] {
+        return Err(ExileError::NotLiving(id));
+    }
+    let tick = world.current_tick;
+    world.identities.exile(id, tick)?;
+
+    // Those left behind grieve, and resent whoever sent them away
+    for (j, closeness) in mourners(world, id) {
+        if j == i {
+            continue;
+        }
+        world.humans.thoughts[j].add(Thought::new(
+            Valence::Negative,
+            GRIEF_INTENSITY * closeness,
+            "exile",
+            "someone dear was sent into exile",
+            CauseType::Event,
+            tick,
+        ));
+        let Some(judge) = banished_by.filter(|&judge| judge != world.humans.ids[j]) else {
+            continue;
+        };
+        world.humans.social_memories[j].record_encounter(
+            judge,
+            EventType::HarmReceived,
+            ANGER_INTENSITY * closeness,
+            tick,
+        );
+        let mut thought = Thought::new(
+            Valence::Negative,
+            ANGER_INTENSITY * closeness,
+            "anger",
+            "they banished someone dear",
+            CauseType::Entity,
+            tick,
+        );
+        thought.cause_entity = Some(judge);
+        world.humans.thoughts[j].add(thought);
+    }
+
+    let centre = settlement_centre(world);
+    let away = world.humans.positions[i] - centre;
+    let direction = if away.length() > 0.0 {
+        away.normalize()
+    } else {
+        Vec2::new(1.0, 0.0)
+    };
+    let edge = centre + direction * EXILE_DISTANCE;
+
+    // The just accept a fair sentence; the ambitious never forgive it
+    let values = &world.humans.values[i];
+    let bitterness = (0.2 + 0.4 * (1.0 - values.justice) + 0.4 * values.ambition).min(1.0);
+    let days = EXILE_MIN_DAYS
+        + ((EXILE_MAX_DAYS - EXILE_MIN_DAYS) as f32 * values.curiosity).round() as u64;
+
+    world.humans.task_queues[i].clear();
+    world.humans.assigned_houses[i] = None;
+    world.humans.positions[i] = edge;
+    world.humans.alive[i] = false;
+    world.factions.leave(id);
+    world.components.remove_entity(id);
+
+    let name = known_as(world, i);
+    let sentence = match banished_by
+        .and_then(|judge| world.humans.index_of(judge))
+        .map(|j| known_as(world, j))
+    {
+        Some(judge) => format!("{} was exiled by {}: {}", name, judge, reason),
+        None => format!("{} was banished from the settlement: {}", name, reason),
+    };
+    world.legacy.record(tick, sentence);
+    world.exiles.exiles.push(Exile {
+        id,
+        name,
+        reason: reason.to_string(),
+        banished_by,
+        since: tick,
+        returns_at: tick + days * TICKS_PER_DAY,
+        edge,
+        bitterness,
+        hardship: 0.0,
+        status: ExileStatus::Away,
+    });
+    Ok(())
+}
+
+/// What an exile comes back as, by how life away has left them
+pub fn return_role(world: &World, exile: &Exile) -> ReturnRole {
+    let values = world
+        .humans
+        .index_of(exile.id)
+        .map(|i| &world.humans.values[i]);
+    let virtuous = values.is_some_and(|v| v.piety >= PENITENT_VIRTUE || v.honor >= PENITENT_VIRTUE);
+    if exile.bitterness >= RAIDER_BITTERNESS {
+        ReturnRole::Raider
+    } else if exile.hardship >= PENITENT_HARDSHIP || virtuous {
+        ReturnRole::Penitent
+    } else {
+        ReturnRole::Trader
+    }
+}
+
+/// Bring exile `e` back to the edge of the map in `role`
+fn return_home(world: &mut World, e: usize, role: ReturnRole) {
+    let tick = world.current_tick;
+    let exile = world.exiles.exiles[e].clone();
+    let Some(i) = world.humans.index_of(exile.id) else {
+        return;
+    };
+    world.exiles.exiles[e].status = ExileStatus::Returned { role, tick };
+    world.humans.alive[i] = true;
+    world.humans.positions[i] = exile.edge;
+    world
+        .identities
+        .end_exile(exile.id, tick)
+        .expect("exile is registered");
+
+    let text = match role {
+        ReturnRole::Raider => {
+            let band = world.factions.found(format!("{}'s Raiders", exile.name));
+            world
+                .factions
+                .join(exile.id, band)
+                .expect("band was just founded");
+            if let Some(settlement) = world.factions.settlement() {
+                world
+                    .factions
+                    .set_stance(band, settlement, Stance::Hostile)
+                    .expect("both factions exist");
+            }
+            format!("{} returns from exile at the head of raiders", exile.name)
+        }
+        ReturnRole::Trader => {
+            let caravan = world.factions.found(format!("{}'s Caravan", exile.name));
+            world
+                .factions
+                .join(exile.id, caravan)
+                .expect("caravan was just founded");
+            let amount = (TRADER_GOODS as f32 * (1.0 - exile.hardship)).round() as u32;
+            for resource in [ResourceType::Cloth, ResourceType::Iron] {
+                world.humans.possessions[i].add(resource, amount.max(1));
+            }
+            format!("{} returns from exile as a trader", exile.name)
+        }
+        ReturnRole::Penitent => {
+            if let Some(settlement) = world.factions.settlement() {
+                world
+                    .factions
+                    .join(exile.id, settlement)
+                    .expect("settlement faction exists");
+            }
+            world.humans.thoughts[i].add(Thought::new(
+                Valence::Positive,
+                0.6,
+                "homecoming",
+                "was taken back after exile",
+                CauseType::Event,
+                tick,
+            ));
+            format!("{} returns from exile begging forgiveness", exile.name)
+        }
+    };
+    if role != ReturnRole::Raider {
+        for (j, closeness) in mourners(world, exile.id) {
+            if j != i {
+                world.humans.thoughts[j].add(Thought::new(
+                    Valence::Positive,
+                    GRIEF_INTENSITY * closeness,
+                    "reunion",
+                    "someone dear came back from exile",
+                    CauseType::Event,
+                    tick,
+                ));
+            }
+        }
+    }
+
+    let centre = settlement_centre(world);
+    let mut approach = Task::new(ActionId::MoveTo, TaskPriority::High, tick).with_position(centre);
+    approach.source = TaskSource::Reaction;
+    world.humans.task_queues[i].push(approach);
+    world.legacy.record(tick, text);
+}
+
+/// Life away for everyone in exile, and the return of those whose time is
+/// up; run daily
+pub fn run_exiles<R: Rng>(world: &mut World, rng: &mut R) {
+    let tick = world.current_tick;
+    for e in 0..world.exiles.exiles.len() {
+        if world.exiles.exiles[e].status != ExileStatus::Away {
+            continue;
+        }
+        let piety = world
+            .humans
+            .index_of(world.exiles.exiles[e].id)
+            .map(|i| world.humans.values[i].piety)
+            .unwrap_or(0.0);
+        let exile = &mut world.exiles.exiles[e];
+        exile.hardship =
+            (exile.hardship + rng.gen_range(-HARDSHIP_SWING..HARDSHIP_SWING)).clamp(0.0, 1.0);
+        exile.bitterness =
+            (exile.bitterness + BITTERNESS_DRIFT * (exile.hardship - piety)).clamp(0.0, 1.0);
+
+        if rng.gen::<f32>() < DEATH_ABROAD_CHANCE * exile.hardship {
+            exile.status = ExileStatus::DiedAbroad { tick };
+            let (id, name) = (exile.id, exile.name.clone());
+            world.identities.record_death(id, tick);
+            world
+                .legacy
+                .record(tick, format!("Word comes that {} died in exile", name));
+            continue;
+        }
+        if tick >= exile.returns_at {
+            let role = return_role(world, &world.exiles.exiles[e]);
+            return_home(world, e, role);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::world::IdentityChange;
+
+    #[test]
+    fn test_exile_leaves_the_map_and_grieves_friends() {
+        let mut world = World::with_seed(21);
+        let thief = world.spawn_human("Thief".into());
+        world.spawn_human("Friend".into());
+        let elder = world.spawn_human("Elder".into());
+        world.humans.social_memories[1].record_encounter(thief, EventType::AidReceived, 0.9, 0);
+        world.humans.task_queues[0].push(Task::new(ActionId::Build, TaskPriority::Normal, 0));
+        world.humans.possessions[0].add(ResourceType::Wood, 4);
+
+        exile(&mut world, 0, "stole from the stores", Some(elder)).unwrap();
+        assert!(!world.humans.alive[0]);
+        assert!(world.humans.task_queues[0].is_idle());
+        assert!(world.exiles.is_away(thief));
+        assert_eq!(world.factions.faction_of(thief), None);
+        // They keep what they own, and are not taken for dead
+        assert_eq!(world.humans.possessions[0].get(ResourceType::Wood), 4);
+        world.record_deaths();
+        assert!(!world.identities.is_active(thief));
+        assert!(!world
+            .identities
+            .drain_changes()
+            .iter()
+            .any(|&(_, _, change)| change == IdentityChange::Died));
+        assert!(world.humans.thoughts[1]
+            .iter()
+            .any(|t| t.concept_category == "exile"));
+        let anger = world.humans.thoughts[1]
+            .iter()
+            .find(|t| t.concept_category == "anger")
+            .unwrap();
+        assert_eq!(anger.cause_entity, Some(elder));
+        assert!(world
+            .legacy
+            .chronicle
+            .iter()
+            .any(|entry| entry.text == "Thief was exiled by Elder: stole from the stores"));
+        // No second sentence for the same exile
+        assert!(exile(&mut world, 0, "again", None).is_err());
+    }
+
+    #[test]
+    fn test_exiles_return_by_temperament() {
+        let mut world = World::with_seed(22);
+        let mut rng = ChaCha8Rng::seed_from_u64(22);
+        for name in ["Grim", "Meek", "Settler"] {
+            world.spawn_human(name.into());
+        }
+        let values = &mut world.humans.values;
+        (values[0].justice, values[0].ambition, values[0].curiosity) = (0.0, 1.0, 0.0);
+        (values[1].justice, values[1].ambition, values[1].curiosity) = (1.0, 0.0, 0.0);
+        values[1].piety = 0.9;
+        exile(&mut world, 0, "murder", None).unwrap();
+        exile(&mut world, 1, "theft", None).unwrap();
+
+        for day in 1..=EXILE_MIN_DAYS {
+            world.current_tick = day * TICKS_PER_DAY;
+            run_exiles(&mut world, &mut rng);
+        }
+        let status =
+            |world: &World, i: usize| world.exiles.get(world.humans.ids[i]).unwrap().status;
+        for i in [0, 1] {
+            // Neither should die abroad at this seed
+            assert!(matches!(status(&world, i), ExileStatus::Returned { .. }));
+            assert!(world.humans.alive[i]);
+        }
+        assert!(matches!(
+            status(&world, 0),
+            ExileStatus::Returned {
+                role: ReturnRole::Raider,
+                ..
+            }
+        ));
+        assert!(matches!(
+            status(&world, 1),
+            ExileStatus::Returned {
+                role: ReturnRole::Penitent,
+                ..
+            }
+        ));
+        let (grim, meek, settler) = (
+            world.humans.ids[0],
+            world.humans.ids[1],
+            world.humans.ids[2],
+        );
+        assert!(world.factions.are_hostile(grim, settler));
+        assert_eq!(
+            world.factions.faction_of(meek),
+            world.factions.faction_of(settler)
+        );
+    }
+}
@@ -10,6 +10,7 @@ use crate::entity::social::{
     BehaviorPattern, EventType, PatternType, RecentEvent, ServiceType, TraitIndicator,
 };
 use crate::simulation::perception::Perception;
+use crate::simulation::scheduler::Share;
 
 /// Record an observation and form expectations
 ///
@@ -111,11 +112,11 @@ pub fn infer_patterns_from_action(action: ActionId, tick: u64) -> Vec<BehaviorPa
     patterns
 }
 
-/// Process all observations from perception data
+/// Process observations from perception data
 ///
-/// Iterates through perceptions and forms expectations based on
-/// the current actions of observed entities.
-pub fn process_observations(world: &mut World, perceptions: &[Perception]) {
+/// Iterates through the perceptions of observers in `share` and forms
+/// expectations based on the current actions of observed entities.
+pub fn process_observations(world: &mut World, perceptions: &[Perception], share: Share) {
     let current_tick = world.current_tick;
 
     // Build O(1) lookup map once instead of O(N) index_of per perceived entity
@@ -128,6 +129,9 @@ pub fn process_observations(world: &mut World, perceptions: &[Perception]) {
         .collect();
 
     for (observer_idx, perception) in perceptions.iter().enumerate() {
+        if !share.includes(observer_idx) {
+            continue;
+        }
         for perceived in &perception.perceived_entities {
             // Get observed entity's current action (O(1) lookup)
             if let Some(&observed_idx) = id_to_idx.get(&perceived.entity) {
@@ -388,7 +392,7 @@ mod tests {
             },
         ];
 
-        process_observations(&mut world, &perceptions);
+        process_observations(&mut world, &perceptions, Share::ALL);
 
         // Observer should now have expectations about actor (Trading service)
         let slot = world.humans.social_memories[observer_idx].find_slot(actor_id);
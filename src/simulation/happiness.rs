@@ -0,0 +1,356 @@
+//! Opinion polling - how the settlement feels, and what comes of it
+//!
+//! Once a day every living settler gives an opinion from 0.0 (wretched) to
+//! 1.0 (delighted), starting from indifference (0.5) and moved by their
+//! mood, hunger, other unmet needs, overcrowding, the recently dead,
+//! festivals, and how their requests to the player were answered. The mean
+//! is the settlement's happiness, kept with a breakdown by factor in
+//! `world.happiness` for the UI. When happiness stays low for days the
+//! settlement falls into unrest; a scenario can also be won or lost on it
+//! (`HappinessGoal`).
+
+use std::collections::HashMap;
+
+use crate::city::building::BuildingId;
+use crate::core::astronomy::TICKS_PER_DAY;
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::legacy::closeness;
+use crate::simulation::requests::request_opinion;
+use crate::simulation::tick::GameOutcome;
+use crate::world::{Fate, HappinessFactor, Poll};
+
+/// Weight of mood (net thought intensity, clamped to -1.0..1.0)
+pub const MOOD_WEIGHT: f32 = 0.3;
+
+/// Weight of hunger
+pub const HUNGER_WEIGHT: f32 = 0.25;
+
+/// Weight of the mean of the other needs
+pub const NEEDS_WEIGHT: f32 = 0.2;
+
+/// Opinion lost to having no house, or a house full to double its room
+pub const OVERCROWDING_PENALTY: f32 = 0.15;
+
+/// Days a death weighs on the settlement
+pub const DEATH_MEMORY_DAYS: u64 = 7;
+
+/// Opinion lost to each recent death, for everyone
+pub const GRIEF_PER_DEATH: f32 = 0.03;
+
+/// Further opinion lost to a recent death, times closeness to the dead
+pub const GRIEF_CLOSENESS: f32 = 0.2;
+
+/// Most opinion that grief can take
+pub const GRIEF_CAP: f32 = 0.3;
+
+/// Weight of festival thought intensity
+pub const FESTIVAL_WEIGHT: f32 = 0.3;
+
+/// Most opinion that festivals can give
+pub const FESTIVAL_CAP: f32 = 0.2;
+
+/// Happiness below which the settlement grows restless
+pub const UNREST_THRESHOLD: f32 = 0.3;
+
+/// Days below the threshold before unrest breaks out
+pub const UNREST_DAYS: usize = 3;
+
+/// Whether a thought is about a festival or celebration
+fn is_festive(thought: &Thought) -> bool {
+    thought.valence == Valence::Positive
+        && (thought.concept_category.contains("festival")
+            || thought.concept_category.contains("celebration"))
+}
+
+/// Humans who died within `DEATH_MEMORY_DAYS`
+fn recent_dead(world: &World) -> Vec<EntityId> {
+    let since = world
+        .current_tick
+        .saturating_sub(DEATH_MEMORY_DAYS * TICKS_PER_DAY);
+    (0..world.humans.ids.len())
+        .filter(|&j| !world.humans.alive[j])
+        .map(|j| world.humans.ids[j])
+        .filter(|&id| {
+            world.identities.get(id).is_some_and(
+                |identity| matches!(identity.fate, Fate::Dead { tick } if tick >= since),
+            )
+        })
+        .collect()
+}
+
+/// People living in each house, and its room for them
+fn house_occupancy(world: &World) -> HashMap<BuildingId, (u32, u32)> {
+    let mut houses: HashMap<BuildingId, (u32, u32)> = HashMap::new();
+    for i in world.humans.iter_living() {
+        let Some(house) = world.humans.assigned_houses[i] else {
+            continue;
+        };
+        let capacity = world
+            .buildings
+            .index_of(house)
+            .map(|b| world.buildings.building_types[b].housing_capacity())
+            .unwrap_or(0);
+        houses.entry(house).or_insert((0, capacity)).0 += 1;
+    }
+    houses
+}
+
+/// Human `i`'s opinion and what moved it, in `HappinessFactor::ALL` order
+pub fn opinion(
+    world: &World,
+    i: usize,
+    dead: &[EntityId],
+    houses: &HashMap<BuildingId, (u32, u32)>,
+) -> (f32, [f32; 7]) {
+    let (mut net_mood, mut festive) = (0.0, 0.0);
+    for thought in world.humans.thoughts[i].iter() {
+        if is_festive(thought) {
+            festive += thought.intensity;
+        } else if thought.valence == Valence::Positive {
+            net_mood += thought.intensity;
+        } else {
+            net_mood -= thought.intensity;
+        }
+    }
+    let mood = MOOD_WEIGHT * f32::clamp(net_mood, -1.0, 1.0);
+
+    let needs = &world.humans.needs[i];
+    let hunger = -HUNGER_WEIGHT * needs.food;
+    let other_needs =
+        -NEEDS_WEIGHT * (needs.rest + needs.safety + needs.social + needs.purpose) / 4.0;
+
+    let overcrowding = match world.humans.assigned_houses[i].and_then(|h| houses.get(&h)) {
+        None => -OVERCROWDING_PENALTY,
+        Some(&(occupants, capacity)) if occupants > capacity => {
+            let excess = (occupants - capacity) as f32 / capacity.max(1) as f32;
+            -OVERCROWDING_PENALTY * excess.min(1.0)
+        }
+        Some(_) => 0.0,
+    };
+
+    let grief = dead
+        .iter()
+        .map(|&d| GRIEF_PER_DEATH + GRIEF_CLOSENESS * closeness(world, i, d))
+        .sum::<f32>();
+    let festivals = (FESTIVAL_WEIGHT * festive).min(FESTIVAL_CAP);
+
+    let factors = [
+        mood,
+        hunger,
+        other_needs,
+        overcrowding,
+        -grief.min(GRIEF_CAP),
+        festivals,
+        request_opinion(world, i),
+    ];
+    let score = (0.5 + factors.iter().sum::<f32>()).clamp(0.0, 1.0);
+    (score, factors)
+}
+
+/// Whether human `i` still belongs to the settlement (has not left it)
+pub fn in_settlement(world: &World, i: usize) -> bool {
+    match world.factions.faction_of(world.humans.ids[i]) {
+        Some(faction) => Some(faction) == world.factions.settlement(),
+        None => true,
+    }
+}
+
+/// Every living settler's opinion, by index
+pub fn opinions(world: &World) -> Vec<(usize, f32)> {
+    let dead = recent_dead(world);
+    let houses = house_occupancy(world);
+    world
+        .humans
+        .iter_living()
+        .filter(|&i| in_settlement(world, i))
+        .map(|i| (i, opinion(world, i, &dead, &houses).0))
+        .collect()
+}
+
+/// Ask every living settler how things are
+pub fn poll_settlement(world: &World) -> Poll {
+    let dead = recent_dead(world);
+    let houses = house_occupancy(world);
+    let mut respondents = 0;
+    let (mut total, mut approving) = (0.0, 0);
+    let mut sums = [0.0; 7];
+    for i in world.humans.iter_living() {
+        if !in_settlement(world, i) {
+            continue;
+        }
+        let (score, factors) = opinion(world, i, &dead, &houses);
+        respondents += 1;
+        total += score;
+        if score >= 0.5 {
+            approving += 1;
+        }
+        for (sum, factor) in sums.iter_mut().zip(factors) {
+            *sum += factor;
+        }
+    }
+    let n = respondents.max(1) as f32;
+    Poll {
+        tick: world.current_tick,
+        respondents,
+        happiness: if respondents == 0 { 0.5 } else { total / n },
+        approval: if respondents == 0 {
+            1.0
+        } else {
+            approving as f32 / n
+        },
+        factors: HappinessFactor::ALL
+            .iter()
+            .zip(sums)
+            .map(|(&factor, sum)| (factor, sum / n))
+            .collect(),
+    }
+}
+
+/// Poll the settlement and record the result, starting or ending unrest
+///
+/// Unrest breaks out after `UNREST_DAYS` days below `UNREST_THRESHOLD`:
+/// it is chronicled and everyone is troubled by it. It ends, and is
+/// chronicled again, on the first day happiness is back above the threshold.
+pub fn run_polling(world: &mut World) -> Poll {
+    let poll = poll_settlement(world);
+    world.happiness.record(poll.clone());
+
+    let restless = world
+        .happiness
+        .held_for(UNREST_DAYS, |h| h < UNREST_THRESHOLD);
+    if restless && !world.happiness.unrest {
+        world.happiness.unrest = true;
+        let cause = poll
+            .worst_factor()
+            .map(|factor| format!(" over {}", factor.name()))
+            .unwrap_or_default();
+        world.legacy.record(
+            world.current_tick,
+            format!("Unrest spreads through the settlement{}", cause),
+        );
+        let living: Vec<usize> = world.humans.iter_living().collect();
+        for i in living {
+            world.humans.thoughts[i].add(Thought::new(
+                Valence::Negative,
+                0.4,
+                "unrest",
+                "felt the settlement's discontent",
+                CauseType::Event,
+                world.current_tick,
+            ));
+        }
+    } else if world.happiness.unrest && poll.happiness >= UNREST_THRESHOLD {
+        world.happiness.unrest = false;
+        world
+            .legacy
+            .record(world.current_tick, "The unrest in the settlement subsides");
+    }
+    poll
+}
+
+/// A scenario goal on settlement happiness
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HappinessGoal {
+    /// Won when happiness stays at or above this for `days` days
+    pub win_above: Option<f32>,
+    /// Lost when happiness stays below this for `days` days
+    pub lose_below: Option<f32>,
+    pub days: usize,
+}
+
+/// Whether a happiness goal has been won or lost
+pub fn check_happiness_goal(world: &World, goal: &HappinessGoal) -> GameOutcome {
+    let happiness = world
+        .happiness
+        .latest
+        .as_ref()
+        .map(|poll| poll.happiness)
+        .unwrap_or(0.5);
+    let happiness_record = &world.happiness;
+    if goal
+        .lose_below
+        .is_some_and(|floor| happiness_record.held_for(goal.days, |h| h < floor))
+    {
+        GameOutcome::Overthrown { happiness }
+    } else if goal
+        .win_above
+        .is_some_and(|target| happiness_record.held_for(goal.days, |h| h >= target))
+    {
+        GameOutcome::Contented { happiness }
+    } else {
+        GameOutcome::InProgress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hunger_and_grief_lower_happiness() {
+        let mut world = World::with_seed(6);
+        for name in ["Ada", "Bram", "Cora"] {
+            world.spawn_human(name.into());
+        }
+        let calm = poll_settlement(&world);
+        assert_eq!(calm.respondents, 3);
+
+        for i in 0..3 {
+            world.humans.needs[i].food = 1.0;
+        }
+        let hungry = poll_settlement(&world);
+        assert!(hungry.happiness < calm.happiness);
+        assert_eq!(hungry.worst_factor(), Some(HappinessFactor::Hunger));
+
+        world.humans.alive[2] = false;
+        world.record_deaths();
+        let grieving = poll_settlement(&world);
+        assert_eq!(grieving.respondents, 2);
+        assert!(grieving.factor(HappinessFactor::RecentDeaths) < 0.0);
+        assert!(grieving.summary().contains("recent deaths"));
+    }
+
+    #[test]
+    fn test_lasting_misery_brings_unrest_and_loses_the_scenario() {
+        let mut world = World::with_seed(7);
+        world.spawn_human("Ada".into());
+        world.humans.needs[0].food = 1.0;
+        world.humans.needs[0].safety = 1.0;
+        world.humans.needs[0].rest = 1.0;
+        world.humans.thoughts[0].add(Thought::new(
+            Valence::Negative,
+            1.0,
+            "grief",
+            "lost everything",
+            CauseType::Event,
+            0,
+        ));
+        let goal = HappinessGoal {
+            win_above: Some(0.8),
+            lose_below: Some(UNREST_THRESHOLD),
+            days: UNREST_DAYS,
+        };
+
+        for _ in 0..UNREST_DAYS - 1 {
+            run_polling(&mut world);
+        }
+        assert!(!world.happiness.unrest);
+        assert_eq!(check_happiness_goal(&world, &goal), GameOutcome::InProgress);
+
+        run_polling(&mut world);
+        assert!(world.happiness.unrest);
+        assert!(world
+            .legacy
+            .chronicle
+            .last()
+            .unwrap()
+            .text
+            .contains("Unrest"));
+        assert!(matches!(
+            check_happiness_goal(&world, &goal),
+            GameOutcome::Overthrown { .. }
+        ));
+    }
+}
@@ -22,12 +22,11 @@ pub fn assign_housing(world: &mut World) {
 
         let building_id = world.buildings.ids[idx];
 
-        // Count current occupants
+        // Count current living occupants
         let current_occupants = world
             .humans
-            .assigned_houses
-            .iter()
-            .filter(|&&h| h == Some(building_id))
+            .iter_living()
+            .filter(|&i| world.humans.assigned_houses[i] == Some(building_id))
             .count() as u32;
 
         let remaining = capacity.saturating_sub(current_occupants);
@@ -140,4 +139,23 @@ mod tests {
         // Should still be homeless (building not complete)
         assert!(world.humans.assigned_houses[0].is_none());
     }
+
+    #[test]
+    fn test_assign_housing_gives_the_place_of_the_dead_to_the_homeless() {
+        let mut world = World::new();
+
+        // A full house (capacity 4), one of whom has died
+        let house_id = world.spawn_building(BuildingType::House, Vec2::new(0.0, 0.0));
+        world.buildings.states[0] = BuildingState::Complete;
+        for i in 0..4 {
+            world.spawn_human(format!("Human {}", i));
+        }
+        assign_housing(&mut world);
+        world.humans.alive[2] = false;
+
+        world.spawn_human("Newcomer".into());
+        assign_housing(&mut world);
+
+        assert_eq!(world.humans.assigned_houses[4], Some(house_id));
+    }
 }
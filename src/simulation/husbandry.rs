@@ -0,0 +1,124 @@
+//! Livestock husbandry system
+//!
+//! Runs the daily herd update and hands out tending work. Tending is a
+//! recurring task: whenever a pasture has products waiting or animals marked
+//! for slaughter, an idle human is sent to milk, shear, collect, or butcher.
+
+use crate::actions::catalog::ActionId;
+use crate::city::livestock::HusbandryReport;
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::simulation::sites::nearest_idle_worker;
+use rand::Rng;
+
+/// Run one day of husbandry: feed, breed, produce, plan slaughter, assign tending
+pub fn run_husbandry<R: Rng>(world: &mut World, rng: &mut R) -> HusbandryReport {
+    let season = world.astronomy.season;
+
+    let report =
+        world
+            .livestock
+            .tick_daily(&mut world.pastures, &mut world.stockpile, season, rng);
+    world
+        .livestock
+        .plan_slaughter(&world.pastures, &world.stockpile, season);
+    assign_tending_tasks(world);
+
+    report
+}
+
+/// Send idle humans to pastures that need tending
+///
+/// Each pasture gets at most one tender at a time. Returns number of tasks assigned.
+pub fn assign_tending_tasks(world: &mut World) -> usize {
+    let mut assigned = 0;
+
+    for p in 0..world.pastures.len() {
+        let pasture_id = world.pastures[p].id;
+        let needs_tending = world.pastures[p].has_pending_products()
+            || world.livestock.has_pending_slaughter(pasture_id);
+        if !needs_tending {
+            continue;
+        }
+
+        let pasture_pos = world.pastures[p].position;
+        let already_tended = world.humans.iter_living().any(|i| {
+            world.humans.task_queues[i]
+                .current()
+                .map(|t| {
+                    t.action == ActionId::TendLivestock
+                        && t.target_position
+                            .map(|pos| world.pastures[p].contains(pos))
+                            .unwrap_or(false)
+                })
+                .unwrap_or(false)
+        });
+        if already_tended {
+            continue;
+        }
+
+        let tender = nearest_idle_worker(world, pasture_pos);
+
+        if let Some(i) = tender {
+            world.humans.task_queues[i].clear();
+            let task = Task::new(
+                ActionId::TendLivestock,
+                TaskPriority::Normal,
+                world.current_tick,
+            )
+            .with_position(pasture_pos);
+            world.humans.task_queues[i].push(task);
+            assigned += 1;
+        }
+    }
+
+    assigned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::livestock::{LivestockKind, LivestockSex};
+    use crate::core::types::Vec2;
+    use crate::simulation::resource_zone::ResourceType;
+
+    #[test]
+    fn test_assign_tending_when_products_wait() {
+        let mut world = World::new();
+        let pasture = world.add_pasture(Vec2::new(10.0, 10.0), 5.0, 10.0);
+        world.spawn_human("Herder".into());
+
+        // Nothing to do yet
+        assert_eq!(assign_tending_tasks(&mut world), 0);
+
+        world.pastures[0].add_product(ResourceType::Milk, 2);
+        assert_eq!(assign_tending_tasks(&mut world), 1);
+
+        let task = world.humans.task_queues[0].current().unwrap();
+        assert_eq!(task.action, ActionId::TendLivestock);
+        assert_eq!(task.target_position.map(|p| p.x), Some(10.0));
+
+        // Same pasture is not double-booked
+        world
+            .livestock
+            .spawn(LivestockKind::Pig, LivestockSex::Male, pasture, 500);
+        world.livestock.marked_for_slaughter[0] = true;
+        assert_eq!(assign_tending_tasks(&mut world), 0);
+    }
+
+    #[test]
+    fn test_run_husbandry_produces_and_assigns() {
+        let mut world = World::new();
+        let pasture = world.add_pasture(Vec2::new(0.0, 0.0), 5.0, 10.0);
+        world.spawn_human("Herder".into());
+        world
+            .livestock
+            .spawn(LivestockKind::Goat, LivestockSex::Female, pasture, 1000);
+
+        let report = run_husbandry(&mut world, &mut rand::thread_rng());
+
+        assert_eq!(report.products, 1);
+        assert!(world.pastures[0].has_pending_products());
+        assert!(world.humans.task_queues[0].current().is_some());
+    }
+}
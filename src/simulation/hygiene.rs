@@ -0,0 +1,344 @@
+//! Hygiene system
+//!
+//! Hygiene is a micro-need: it never becomes an entity's most pressing need,
+//! but filth makes people easier to sicken and worse company. Anyone not at
+//! work washes while near water - on a stream bank, at a well, or (faster)
+//! in a bathhouse. Once a day, grubby idlers are sent to the nearest wash
+//! site and everyone rolls for illness.
+
+use rand::Rng;
+
+use crate::actions::catalog::{ActionCategory, ActionId};
+use crate::city::building::{BuildingState, BuildingType};
+use crate::core::types::Vec2;
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::world::TerrainFeatureKind;
+
+/// Above this, people go looking for a wash and are less pleasant company
+pub const GRUBBY_THRESHOLD: f32 = 0.5;
+
+/// Above this, people disgust those around them
+pub const FILTHY_THRESHOLD: f32 = 0.7;
+
+/// Distance at which filth is noticed
+pub const DISGUST_RANGE: f32 = 4.0;
+
+/// Distance from water at which someone can wash
+pub const WASH_RANGE: f32 = 3.0;
+
+/// Daily illness chance at a susceptibility of 1.0
+pub const ILLNESS_CHANCE: f32 = 0.01;
+
+/// Fatigue added by falling ill
+pub const ILLNESS_FATIGUE: f32 = 0.4;
+
+/// Hygiene restored per tick washing in a stream or at a well
+pub const WATER_WASH_RATE: f32 = 0.02;
+
+/// Hygiene restored per tick in a bathhouse
+pub const BATHHOUSE_WASH_RATE: f32 = 0.05;
+
+/// Somewhere to wash: a stretch of stream, or a single well or bathhouse
+#[derive(Debug, Clone, Copy)]
+pub struct WashSite {
+    pub from: Vec2,
+    pub to: Vec2,
+    /// Distance from the from-to line within which people can wash
+    pub reach: f32,
+    /// Hygiene restored per tick
+    pub rate: f32,
+}
+
+impl WashSite {
+    fn point(position: Vec2, rate: f32) -> Self {
+        Self {
+            from: position,
+            to: position,
+            reach: WASH_RANGE,
+            rate,
+        }
+    }
+
+    /// Closest point on the site's centre line
+    pub fn nearest_point(&self, pos: Vec2) -> Vec2 {
+        let line = self.to - self.from;
+        let length_sq = line.x * line.x + line.y * line.y;
+        if length_sq == 0.0 {
+            return self.from;
+        }
+        let offset = pos - self.from;
+        let t = ((offset.x * line.x + offset.y * line.y) / length_sq).clamp(0.0, 1.0);
+        self.from + line * t
+    }
+
+    /// Whether someone at `pos` is close enough to wash
+    pub fn in_reach(&self, pos: Vec2) -> bool {
+        self.nearest_point(pos).distance(&pos) <= self.reach
+    }
+
+    /// Spot to walk to for a wash: the near edge of the site
+    pub fn approach(&self, pos: Vec2) -> Vec2 {
+        let center = self.nearest_point(pos);
+        let edge = self.reach - WASH_RANGE;
+        let toward = (pos - center).normalize();
+        if edge <= 0.0 || toward.length() == 0.0 {
+            center
+        } else {
+            center + toward * edge
+        }
+    }
+}
+
+/// How much more easily a person falls ill at a hygiene level (0.5 clean to 2.5 filthy)
+pub fn disease_susceptibility(hygiene: f32) -> f32 {
+    0.5 + 2.0 * hygiene.clamp(0.0, 1.0)
+}
+
+/// Share of normal social satisfaction someone this dirty gives their company
+pub fn social_quality(hygiene: f32) -> f32 {
+    if hygiene <= GRUBBY_THRESHOLD {
+        1.0
+    } else {
+        1.0 - (hygiene.min(1.0) - GRUBBY_THRESHOLD)
+    }
+}
+
+/// Thought of someone who has to stand next to a filthy neighbour
+pub fn disgust_thought(name: &str, hygiene: f32, tick: u64) -> Thought {
+    Thought::new(
+        Valence::Negative,
+        (hygiene * 0.5).min(1.0),
+        "disgust",
+        format!("{} reeks", name),
+        CauseType::Entity,
+        tick,
+    )
+}
+
+/// Every place on the map to wash
+pub fn wash_sites(world: &World) -> Vec<WashSite> {
+    let streams = world
+        .terrain_features
+        .features
+        .iter()
+        .filter(|f| f.kind == TerrainFeatureKind::Stream)
+        .map(|f| WashSite {
+            from: f.from,
+            to: f.to,
+            reach: f.width * 0.5 + WASH_RANGE,
+            rate: WATER_WASH_RATE,
+        });
+    let wells = world
+        .world_objects
+        .iter()
+        .filter(|o| o.blueprint_name.eq_ignore_ascii_case("well"))
+        .map(|o| WashSite::point(Vec2::new(o.position.x, o.position.y), WATER_WASH_RATE));
+    let bathhouses = (0..world.buildings.count())
+        .filter(|&b| {
+            world.buildings.building_types[b] == BuildingType::Bathhouse
+                && world.buildings.states[b] == BuildingState::Complete
+        })
+        .map(|b| WashSite::point(world.buildings.positions[b], BATHHOUSE_WASH_RATE));
+
+    streams.chain(wells).chain(bathhouses).collect()
+}
+
+/// Let everyone who is near water and not at work wash. Returns number washed.
+pub fn wash_people(world: &mut World) -> usize {
+    let sites = wash_sites(world);
+    if sites.is_empty() {
+        return 0;
+    }
+
+    let mut washed = 0;
+    let living: Vec<usize> = world.humans.iter_living().collect();
+    for i in living {
+        let at_work = world.humans.task_queues[i]
+            .current()
+            .map(|t| t.action.category() == ActionCategory::Work)
+            .unwrap_or(false);
+        if at_work || world.humans.needs[i].hygiene <= 0.0 {
+            continue;
+        }
+        let pos = world.humans.positions[i];
+        let best_rate = sites
+            .iter()
+            .filter(|s| s.in_reach(pos))
+            .map(|s| s.rate)
+            .fold(0.0, f32::max);
+        if best_rate > 0.0 {
+            world.humans.needs[i].wash(world.time_scale.rate(best_rate));
+            washed += 1;
+        }
+    }
+    washed
+}
+
+/// What one day of hygiene upkeep did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HygieneReport {
+    pub fell_ill: usize,
+    pub sent_to_wash: usize,
+}
+
+/// Run one day of hygiene upkeep
+pub fn run_hygiene<R: Rng>(world: &mut World, rng: &mut R) -> HygieneReport {
+    HygieneReport {
+        fell_ill: check_illness(world, rng),
+        sent_to_wash: send_to_wash(world),
+    }
+}
+
+/// Roll every living human for illness, weighted by how dirty they are
+pub fn check_illness<R: Rng>(world: &mut World, rng: &mut R) -> usize {
+    let mut fell_ill = 0;
+    let living: Vec<usize> = world.humans.iter_living().collect();
+    for i in living {
+        let chance = ILLNESS_CHANCE * disease_susceptibility(world.humans.needs[i].hygiene);
+        if rng.gen::<f32>() < chance {
+            world.humans.body_states[i].add_fatigue(ILLNESS_FATIGUE);
+            world.humans.thoughts[i].add(Thought::new(
+                Valence::Negative,
+                0.6,
+                "illness",
+                "fell ill",
+                CauseType::Need,
+                world.current_tick,
+            ));
+            fell_ill += 1;
+        }
+    }
+    fell_ill
+}
+
+/// Send grubby idlers to the nearest wash site. Returns number of tasks assigned.
+pub fn send_to_wash(world: &mut World) -> usize {
+    let sites = wash_sites(world);
+    if sites.is_empty() {
+        return 0;
+    }
+
+    let mut assigned = 0;
+    let living: Vec<usize> = world.humans.iter_living().collect();
+    for i in living {
+        let idle = world.humans.task_queues[i]
+            .current()
+            .map(|t| matches!(t.action, ActionId::IdleWander | ActionId::IdleObserve))
+            .unwrap_or(true);
+        if !idle || world.humans.needs[i].hygiene <= GRUBBY_THRESHOLD {
+            continue;
+        }
+
+        let pos = world.humans.positions[i];
+        let nearest = sites.iter().min_by(|a, b| {
+            let da = a.nearest_point(pos).distance(&pos);
+            let db = b.nearest_point(pos).distance(&pos);
+            da.total_cmp(&db)
+        });
+        let Some(site) = nearest else {
+            continue;
+        };
+        if site.in_reach(pos) {
+            continue; // Already washing
+        }
+
+        world.humans.task_queues[i].clear();
+        let task = Task::new(ActionId::MoveTo, TaskPriority::Normal, world.current_tick)
+            .with_position(site.approach(pos));
+        world.humans.task_queues[i].push(task);
+        assigned += 1;
+    }
+    assigned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_wash_at_stream_and_faster_in_bathhouse() {
+        let mut world = World::new();
+        world.add_terrain_feature(
+            TerrainFeatureKind::Stream,
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 40.0),
+            2.0,
+            1.0,
+        );
+        let bath = world.spawn_building(BuildingType::Bathhouse, Vec2::new(50.0, 10.0));
+        let bath_idx = world.buildings.index_of(bath).unwrap();
+        world.buildings.states[bath_idx] = BuildingState::Complete;
+
+        for name in ["Bank", "Bather", "Farmhand"] {
+            world.spawn_human(name.into());
+        }
+        world.humans.positions[0] = Vec2::new(13.0, 20.0);
+        world.humans.positions[1] = Vec2::new(51.0, 10.0);
+        world.humans.positions[2] = Vec2::new(30.0, 20.0);
+        for i in 0..3 {
+            world.humans.needs[i].hygiene = 0.9;
+        }
+
+        assert_eq!(wash_people(&mut world), 2);
+        let hygiene: Vec<f32> = (0..3).map(|i| world.humans.needs[i].hygiene).collect();
+        assert!(hygiene[1] < hygiene[0], "Bathhouse should wash faster");
+        assert_eq!(hygiene[2], 0.9);
+    }
+
+    #[test]
+    fn test_grubby_idlers_sent_to_wash() {
+        let mut world = World::new();
+        world.add_terrain_feature(
+            TerrainFeatureKind::Stream,
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 40.0),
+            2.0,
+            1.0,
+        );
+        world.spawn_human("Grubby".into());
+        world.spawn_human("Clean".into());
+        world.humans.positions[0] = Vec2::new(30.0, 20.0);
+        world.humans.needs[0].hygiene = 0.8;
+
+        assert_eq!(send_to_wash(&mut world), 1);
+        let task = world.humans.task_queues[0].current().unwrap();
+        assert_eq!(task.action, ActionId::MoveTo);
+        let target = task.target_position.unwrap();
+        assert!(WashSite {
+            from: Vec2::new(10.0, 0.0),
+            to: Vec2::new(10.0, 40.0),
+            reach: 1.0 + WASH_RANGE,
+            rate: WATER_WASH_RATE,
+        }
+        .in_reach(target));
+        assert!(target.x > 10.0, "Should stop on the near bank");
+    }
+
+    #[test]
+    fn test_filth_raises_illness_and_sours_company() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut clean = World::new();
+        let mut filthy = World::new();
+        for n in 0..200 {
+            clean.spawn_human(format!("Clean {}", n));
+            filthy.spawn_human(format!("Filthy {}", n));
+            clean.humans.needs[n].hygiene = 0.0;
+            filthy.humans.needs[n].hygiene = 1.0;
+        }
+
+        let mut clean_ill = 0;
+        let mut filthy_ill = 0;
+        for _ in 0..10 {
+            clean_ill += check_illness(&mut clean, &mut rng);
+            filthy_ill += check_illness(&mut filthy, &mut rng);
+        }
+        assert!(filthy_ill > clean_ill * 2);
+
+        assert_eq!(social_quality(0.3), 1.0);
+        assert!(social_quality(1.0) < social_quality(0.6));
+    }
+}
@@ -0,0 +1,182 @@
+//! Picking up, dropping, and equipping items, and where crafted items go
+//!
+//! Item actions (`PickUp`, `Drop`, `Equip`) run as tasks in `execute_tasks`
+//! and take effect here when they finish. Equipping is what puts an item in
+//! someone's hands for a fight: the equipped weapon and armor replace the
+//! ones in their combat state.
+//!
+//! Workshops that craft items (a forged sword, stitched armor) hand each item
+//! to someone crafting at the workshop, if they have room for it, and store
+//! it in the stockpile otherwise. A crafter who knows how to forge a
+//! masterwork makes one, and is named for it.
+
+use crate::actions::catalog::ActionId;
+use crate::ecs::world::World;
+use crate::entity::inventory::{DroppedItem, Item};
+use crate::simulation::epithets::perform;
+use crate::skills::ChunkId;
+use crate::world::Feat;
+
+/// How close an item on the ground must be to pick it up
+pub const PICKUP_RANGE: f32 = 2.0;
+
+/// Carry out a finished item action for human `i`, returning whether it did
+/// anything
+pub fn use_items(world: &mut World, i: usize, action: ActionId) -> bool {
+    match action {
+        ActionId::PickUp => pick_up(world, i).is_some(),
+        ActionId::Drop => drop_item(world, i).is_some(),
+        ActionId::Equip => equip(world, i),
+        _ => false,
+    }
+}
+
+/// Pick up the nearest item on the ground within reach of human `i`, or the
+/// oldest item in the stockpile if nothing lies nearby
+pub fn pick_up(world: &mut World, i: usize) -> Option<Item> {
+    if world.humans.inventories[i].is_full() {
+        return None;
+    }
+    let pos = world.humans.positions[i];
+    let nearest = world
+        .dropped_items
+        .iter()
+        .enumerate()
+        .map(|(d, dropped)| (d, dropped.position.distance(&pos)))
+        .filter(|&(_, distance)| distance <= PICKUP_RANGE)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(d, _)| d);
+    let item = match nearest {
+        Some(d) => world.dropped_items.swap_remove(d).item,
+        None => world.stockpile.take_item()?,
+    };
+    world.humans.inventories[i].add(item.clone()).ok()?;
+    Some(item)
+}
+
+/// Set down the item human `i` acquired most recently where they stand
+pub fn drop_item(world: &mut World, i: usize) -> Option<Item> {
+    let item = world.humans.inventories[i].take_newest()?;
+    world.dropped_items.push(DroppedItem {
+        position: world.humans.positions[i],
+        item: item.clone(),
+    });
+    Some(item)
+}
+
+/// Equip the newest weapon and armor human `i` carries, arming them for
+/// combat with it
+pub fn equip(world: &mut World, i: usize) -> bool {
+    let inventory = &mut world.humans.inventories[i];
+    if !inventory.equip_newest() {
+        return false;
+    }
+    let combat_state = &mut world.humans.combat_states[i];
+    if let Some(weapon) = inventory.weapon_properties() {
+        combat_state.weapon = weapon.clone();
+    }
+    if let Some(armor) = inventory.armor_properties() {
+        combat_state.armor = armor.clone();
+    }
+    true
+}
+
+/// Hand items crafted at building `building_idx` to its crafters, storing
+/// what they cannot carry in the stockpile
+pub fn deliver_crafted(world: &mut World, building_idx: usize, items: Vec<Item>) {
+    let building = world.buildings.ids[building_idx];
+    let crafters: Vec<usize> = world
+        .humans
+        .iter_living()
+        .filter(|&i| {
+            world.humans.task_queues[i]
+                .current()
+                .is_some_and(|t| t.action == ActionId::Craft && t.target_building == Some(building))
+        })
+        .collect();
+
+    if !items.is_empty() {
+        for &i in &crafters {
+            if world.humans.chunk_libraries[i].has_chunk(ChunkId::CraftForgeMasterwork) {
+                perform(world, i, Feat::Masterwork);
+            }
+        }
+    }
+    for item in items {
+        let crafter = crafters
+            .iter()
+            .copied()
+            .find(|&i| !world.humans.inventories[i].is_full());
+        let unclaimed = match crafter {
+            Some(i) => world.humans.inventories[i].add(item),
+            None => Err(item),
+        };
+        if let Err(item) = unclaimed {
+            world.stockpile.store_item(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::building::{BuildingId, BuildingType};
+    use crate::combat::{ArmorProperties, WeaponProperties};
+    use crate::core::types::Vec2;
+    use crate::entity::tasks::{Task, TaskPriority};
+
+    #[test]
+    fn test_crafted_items_reach_hands_and_stockpile() {
+        let mut world = World::with_seed(5);
+        world.spawn_human("Ada".into());
+        world.spawn_human("Bram".into());
+        let building = BuildingId::new();
+        let origin = Vec2::new(0.0, 0.0);
+        world
+            .buildings
+            .spawn(building, BuildingType::Workshop, origin, 0);
+        let task = Task::new(ActionId::Craft, TaskPriority::Normal, 0).with_building(building);
+        world.humans.task_queues[0].push(task);
+
+        // Ada is crafting at the workshop, so both items are hers
+        let items = vec![
+            Item::named("sword").unwrap(),
+            Item::named("leather_armor").unwrap(),
+        ];
+        deliver_crafted(&mut world, 0, items);
+        assert_eq!(world.humans.inventories[0].items.len(), 2);
+        assert!(world.stockpile.items().is_empty());
+
+        assert!(use_items(&mut world, 0, ActionId::Equip));
+        assert_eq!(
+            world.humans.combat_states[0].weapon,
+            WeaponProperties::sword()
+        );
+        assert_eq!(
+            world.humans.combat_states[0].armor,
+            ArmorProperties::leather()
+        );
+
+        world.humans.inventories[0]
+            .add(Item::named("ration").unwrap())
+            .unwrap();
+        world.humans.positions[0] = Vec2::new(10.0, 10.0);
+        assert!(use_items(&mut world, 0, ActionId::Drop));
+        world.humans.positions[1] = Vec2::new(11.0, 10.0);
+        assert_eq!(
+            pick_up(&mut world, 1).map(|item| item.name),
+            Some("ration".into())
+        );
+        assert!(world.dropped_items.is_empty());
+
+        // With nobody at the workshop, crafted items wait in the stockpile
+        world.humans.task_queues[0].complete_current();
+        deliver_crafted(&mut world, 0, vec![Item::named("mace").unwrap()]);
+        assert_eq!(world.stockpile.items().len(), 1);
+        assert_eq!(
+            pick_up(&mut world, 1).map(|item| item.name),
+            Some("mace".into())
+        );
+        assert!(pick_up(&mut world, 1).is_none());
+    }
+}
@@ -0,0 +1,330 @@
+//! Job board - designated work that idle workers claim
+//!
+//! Work the settlement wants done (gather at a zone, haul to or build at a
+//! site) is posted to `world.jobs`. Each tick `assign_jobs` lets idle humans
+//! claim open jobs: higher priority jobs first, then whichever suits the
+//! worker best - their skill at the work, how far they would walk, and
+//! whether the work speaks to their values. A claim reserves the job, so two
+//! workers never take the same one. The claim is released when the worker
+//! stops working the job (finished, interrupted, or dead); the job stays
+//! posted until it is withdrawn.
+
+use serde::{Deserialize, Serialize};
+
+use crate::actions::catalog::ActionId;
+use crate::city::building::BuildingId;
+use crate::core::types::{EntityId, Tick, Vec2};
+use crate::ecs::world::World;
+use crate::entity::species::human::HumanValues;
+use crate::entity::tasks::{Task, TaskPriority, TaskSource};
+use crate::skills::skill_check;
+
+/// Walking this far costs a job as much as a full point of fit
+pub const JOB_DISTANCE_SCALE: f32 = 50.0;
+
+/// Unique identifier for a posted job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JobId(pub u32);
+
+/// A piece of designated work
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: JobId,
+    pub action: ActionId,
+    /// Where the work is done
+    pub position: Vec2,
+    /// The building the work is for, if any
+    pub building: Option<BuildingId>,
+    pub priority: TaskPriority,
+    pub posted_tick: Tick,
+    /// The worker who has reserved this job
+    pub claimed_by: Option<EntityId>,
+}
+
+impl Job {
+    /// A job to post; the board gives it its id
+    pub fn new(action: ActionId, position: Vec2, priority: TaskPriority) -> Self {
+        Self {
+            id: JobId(0),
+            action,
+            position,
+            building: None,
+            priority,
+            posted_tick: 0,
+            claimed_by: None,
+        }
+    }
+
+    pub fn with_building(mut self, building: BuildingId) -> Self {
+        self.building = Some(building);
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.claimed_by.is_none()
+    }
+
+    /// The task a worker who claims this job carries out
+    pub fn task(&self, current_tick: Tick) -> Task {
+        let mut task =
+            Task::new(self.action, self.priority, current_tick).with_position(self.position);
+        task.target_building = self.building;
+        task.source = TaskSource::Autonomous;
+        task
+    }
+
+    /// Whether a task is the work of this job
+    pub fn matches(&self, task: &Task) -> bool {
+        task.action == self.action
+            && task
+                .target_position
+                .is_some_and(|pos| pos.x == self.position.x && pos.y == self.position.y)
+            && task.target_building == self.building
+    }
+}
+
+/// Every job posted and not yet withdrawn
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobBoard {
+    jobs: Vec<Job>,
+    next_id: u32,
+}
+
+impl JobBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Post a job, returning its id
+    pub fn post(&mut self, mut job: Job, current_tick: Tick) -> JobId {
+        job.id = JobId(self.next_id);
+        job.posted_tick = current_tick;
+        job.claimed_by = None;
+        self.next_id += 1;
+        self.jobs.push(job);
+        JobId(self.next_id - 1)
+    }
+
+    /// Take a job off the board
+    pub fn withdraw(&mut self, id: JobId) -> Option<Job> {
+        let index = self.jobs.iter().position(|job| job.id == id)?;
+        Some(self.jobs.remove(index))
+    }
+
+    /// Keep only the jobs `keep` returns true for
+    pub fn retain(&mut self, keep: impl FnMut(&Job) -> bool) {
+        self.jobs.retain(keep);
+    }
+
+    pub fn get(&self, id: JobId) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    /// Jobs in the order they were posted
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    /// Jobs nobody has claimed
+    pub fn open(&self) -> impl Iterator<Item = &Job> {
+        self.jobs.iter().filter(|job| job.is_open())
+    }
+
+    /// Reserve a job for a worker. Fails if the job is gone or someone else
+    /// holds it.
+    pub fn claim(&mut self, id: JobId, worker: EntityId) -> bool {
+        match self.jobs.iter_mut().find(|job| job.id == id) {
+            Some(job) if job.claimed_by.is_none_or(|w| w == worker) => {
+                job.claimed_by = Some(worker);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Give up the reservation on a job
+    pub fn release(&mut self, id: JobId) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.claimed_by = None;
+        }
+    }
+
+    /// The job a worker holds
+    pub fn claimed_by(&self, worker: EntityId) -> Option<JobId> {
+        self.jobs
+            .iter()
+            .find(|job| job.claimed_by == Some(worker))
+            .map(|job| job.id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+}
+
+/// How much a kind of work speaks to someone's values (0.0 to 1.0)
+///
+/// The ambitious and beauty-loving take to building, the loyal to fetching
+/// and carrying for others, those who want comfort and safety to filling
+/// the stores, and the honourable to the drill ground.
+pub fn value_affinity(values: &HumanValues, action: ActionId) -> f32 {
+    match action {
+        ActionId::Build => (values.ambition + values.beauty) / 2.0,
+        ActionId::Haul => values.loyalty,
+        ActionId::Drill => values.honor,
+        ActionId::Gather => (values.comfort + values.safety) / 2.0,
+        _ => 0.0,
+    }
+}
+
+/// How well a job suits human `i`: skill, plus value affinity, less distance
+pub fn job_fit(world: &World, i: usize, job: &Job) -> f32 {
+    let skill = skill_check(&world.humans.chunk_libraries[i], job.action).skill_modifier;
+    let affinity = value_affinity(&world.humans.values[i], job.action);
+    let distance = world.humans.positions[i].distance(&job.position);
+    skill + affinity - distance / JOB_DISTANCE_SCALE
+}
+
+/// Release claims on jobs their workers no longer work
+pub fn release_stale_claims(world: &mut World) {
+    let humans = &world.humans;
+    for job in world.jobs.jobs.iter_mut() {
+        let Some(worker) = job.claimed_by else {
+            continue;
+        };
+        let working = humans.index_of(worker).is_some_and(|i| {
+            humans.alive[i]
+                && humans.task_queues[i]
+                    .current()
+                    .is_some_and(|task| job.matches(task))
+        });
+        if !working {
+            job.claimed_by = None;
+        }
+    }
+}
+
+/// Let idle humans claim open jobs
+///
+/// Returns number of jobs claimed.
+pub fn assign_jobs(world: &mut World) -> usize {
+    release_stale_claims(world);
+
+    // Humans who are idle or only idling, and not worn out
+    let workers: Vec<usize> = world
+        .humans
+        .iter_living()
+        .filter(|&i| {
+            !world.humans.body_states[i].is_overworked()
+                && world.humans.task_queues[i]
+                    .current()
+                    .map(|t| matches!(t.action, ActionId::IdleWander | ActionId::IdleObserve))
+                    .unwrap_or(true)
+        })
+        .collect();
+
+    let mut claimed = 0;
+    for i in workers {
+        let best = world
+            .jobs
+            .open()
+            .map(|job| (job, job_fit(world, i, job)))
+            .max_by(|(a, fit_a), (b, fit_b)| {
+                (a.priority as u8)
+                    .cmp(&(b.priority as u8))
+                    .then(fit_a.total_cmp(fit_b))
+            })
+            .map(|(job, _)| job.clone());
+        let Some(job) = best else {
+            break; // Every job is taken
+        };
+
+        if world.jobs.claim(job.id, world.humans.ids[i]) {
+            world.humans.task_queues[i].clear();
+            world.humans.task_queues[i].push(job.task(world.current_tick));
+            claimed += 1;
+        }
+    }
+    claimed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn idle_world() -> World {
+        let mut world = World::with_seed(21);
+        for name in ["Ada", "Bram", "Cora"] {
+            world.spawn_human(name.into());
+        }
+        world.humans.positions[0] = Vec2::new(0.0, 0.0);
+        world.humans.positions[1] = Vec2::new(40.0, 0.0);
+        world.humans.positions[2] = Vec2::new(80.0, 0.0);
+        world
+    }
+
+    #[test]
+    fn test_jobs_are_reserved_for_one_worker() {
+        let mut world = idle_world();
+        let job = Job::new(ActionId::Gather, Vec2::new(80.0, 0.0), TaskPriority::Normal);
+        let gather = world.jobs.post(job, 0);
+
+        // Everyone is idle, but only one of them gets the job
+        assert_eq!(assign_jobs(&mut world), 1);
+        let worker = world.jobs.get(gather).unwrap().claimed_by.unwrap();
+        assert!(!world.jobs.claim(gather, world.humans.ids[1]));
+        assert_eq!(world.jobs.claimed_by(worker), Some(gather));
+        let holders = (0..3)
+            .filter(|&i| world.humans.task_queues[i].current().is_some())
+            .count();
+        assert_eq!(holders, 1);
+        assert_eq!(assign_jobs(&mut world), 0);
+
+        // Interrupted work frees the job for someone else
+        let i = world.humans.index_of(worker).unwrap();
+        world.humans.task_queues[i].clear();
+        world.humans.task_queues[i].push(Task::new(ActionId::Rest, TaskPriority::High, 1));
+        assert_eq!(assign_jobs(&mut world), 1);
+        assert_ne!(world.jobs.get(gather).unwrap().claimed_by, Some(worker));
+
+        assert!(world.jobs.withdraw(gather).is_some());
+        assert!(world.jobs.is_empty());
+    }
+
+    #[test]
+    fn test_workers_claim_by_priority_then_fit() {
+        let mut world = idle_world();
+        // Bram loves building
+        world.humans.values[1].ambition = 1.0;
+        world.humans.values[1].beauty = 1.0;
+
+        let urgent = world.jobs.post(
+            Job::new(ActionId::Gather, Vec2::new(80.0, 0.0), TaskPriority::High),
+            0,
+        );
+        let gather = world.jobs.post(
+            Job::new(ActionId::Gather, Vec2::new(30.0, 0.0), TaskPriority::Low),
+            0,
+        );
+        let build = world.jobs.post(
+            Job::new(ActionId::Build, Vec2::new(50.0, 0.0), TaskPriority::Low),
+            0,
+        );
+
+        assert_eq!(assign_jobs(&mut world), 3);
+        let holder = |id| world.jobs.get(id).unwrap().claimed_by;
+        // Ada takes the urgent job despite the walk
+        assert_eq!(holder(urgent), Some(world.humans.ids[0]));
+        // Both other jobs are a short walk for Bram, who would rather build
+        assert_eq!(holder(build), Some(world.humans.ids[1]));
+        assert_eq!(holder(gather), Some(world.humans.ids[2]));
+        assert_eq!(
+            world.humans.task_queues[1].current().unwrap().action,
+            ActionId::Build
+        );
+    }
+}
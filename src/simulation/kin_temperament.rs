@@ -0,0 +1,149 @@
+//! Dwarf and elf temperament
+//!
+//! Dwarf and elf values move with time and grief, as orc values do in
+//! `orc_temperament`. Each tick they drift per the `value_dynamics` in
+//! `species/dwarf.toml` and `species/elf.toml`: a dwarf's urge to fortify
+//! eases back once the danger has passed, though a grudge never fades, and an
+//! elf's aloofness slowly cools. Seeing one of their own fall nearby is a
+//! `kin_death`, per their `value_events` - it hardens a dwarf's grudge and
+//! walls and withdraws an elf from others - and leaves a thought.
+
+use crate::core::types::{Species, Tick, Vec2};
+use crate::ecs::world::World;
+use crate::entity::species::value_access::ValueAccessor;
+use crate::entity::thoughts::{CauseType, Thought, ThoughtBuffer, Valence};
+use crate::rules::ValueDynamicsRules;
+use crate::simulation::value_dynamics::{apply_event, apply_tick_dynamics_over};
+
+/// Distance within which a dwarf or elf sees one of their own fall
+pub const KIN_RANGE: f32 = 30.0;
+
+/// Who among the dwarves and elves was alive before this tick's tasks
+#[derive(Debug, Clone, Default)]
+pub struct KinCondition {
+    dwarves: Vec<bool>,
+    elves: Vec<bool>,
+}
+
+/// Note who is alive, to compare against after the tick's tasks
+pub fn kin_condition(world: &World) -> KinCondition {
+    KinCondition {
+        dwarves: world.dwarves.alive.clone(),
+        elves: world.elves.alive.clone(),
+    }
+}
+
+/// The columns of one species that temperament reads and moves
+struct Kin<'a, V> {
+    species: Species,
+    names: &'a [String],
+    alive: &'a [bool],
+    positions: &'a [Vec2],
+    values: &'a mut [V],
+    thoughts: &'a mut [ThoughtBuffer],
+}
+
+fn temper<V: ValueAccessor>(
+    kin: Kin<'_, V>,
+    before: &[bool],
+    dynamics: &ValueDynamicsRules,
+    span: f32,
+    tick: Tick,
+) {
+    let fallen: Vec<usize> = (0..before.len())
+        .filter(|&i| before[i] && !kin.alive[i])
+        .collect();
+    let deltas = dynamics.get_tick_deltas(kin.species);
+
+    for i in (0..kin.alive.len()).filter(|&i| kin.alive[i]) {
+        apply_tick_dynamics_over(&mut kin.values[i], deltas, span);
+
+        for &f in &fallen {
+            if kin.positions[i].distance(&kin.positions[f]) <= KIN_RANGE {
+                apply_event(&mut kin.values[i], "kin_death", dynamics, kin.species);
+                kin.thoughts[i].add(Thought::new(
+                    Valence::Negative,
+                    0.7,
+                    "kin_death",
+                    format!("saw {} fall", kin.names[f]),
+                    CauseType::Event,
+                    tick,
+                ));
+            }
+        }
+    }
+}
+
+/// Move dwarf and elf values with this tick's losses and the passing of time
+pub fn update_kin_temperament(world: &mut World, before: &KinCondition) {
+    let span = world.time_scale.span() as f32;
+    let tick = world.current_tick;
+    let dwarves = &mut world.dwarves;
+    temper(
+        Kin {
+            species: Species::Dwarf,
+            names: &dwarves.names,
+            alive: &dwarves.alive,
+            positions: &dwarves.positions,
+            values: &mut dwarves.values,
+            thoughts: &mut dwarves.thoughts,
+        },
+        &before.dwarves,
+        &world.species_dynamics,
+        span,
+        tick,
+    );
+    let elves = &mut world.elves;
+    temper(
+        Kin {
+            species: Species::Elf,
+            names: &elves.names,
+            alive: &elves.alive,
+            positions: &elves.positions,
+            values: &mut elves.values,
+            thoughts: &mut elves.thoughts,
+        },
+        &before.elves,
+        &world.species_dynamics,
+        span,
+        tick,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::species::dwarf::DwarfValues;
+
+    #[test]
+    fn test_kin_harden_and_withdraw_when_their_own_fall() {
+        let mut world = World::with_seed(21);
+        world.spawn_dwarf("Thorin".into());
+        world.spawn_dwarf("Balin".into());
+        world.spawn_dwarf("Nori".into());
+        world.spawn_elf("Aelwen".into());
+        world.spawn_elf("Celeborn".into());
+        world.dwarves.positions[2] = Vec2::new(100.0, 0.0);
+        world.elves.values[1].aloofness = 0.9;
+
+        let before = kin_condition(&world);
+        world.dwarves.alive[1] = false;
+        update_kin_temperament(&mut world, &before);
+
+        let thorin = &world.dwarves.values[0];
+        assert!(thorin.grudge > DwarfValues::default().grudge);
+        assert!(thorin.fortification > DwarfValues::default().fortification);
+        assert!(world.dwarves.thoughts[0]
+            .iter()
+            .any(|t| t.cause_description == "saw Balin fall"));
+
+        // Too far away to see
+        assert_eq!(
+            world.dwarves.values[2].grudge,
+            DwarfValues::default().grudge
+        );
+        // A dwarf's death is not an elf's grief, but time still cools them
+        assert_eq!(world.elves.values[0].aloofness, 0.5);
+        assert!(world.elves.values[1].aloofness < 0.9);
+    }
+}
@@ -0,0 +1,287 @@
+//! Legacy system
+//!
+//! The dead cast long shadows. When a human dies the chronicle records it,
+//! and those who were widely known or left children behind get a memorial.
+//! Once a day, descendants and close friends standing at a memorial remember
+//! the dead, idle mourners visit on anniversaries, and the kin of anyone
+//! killed keep nursing a grudge against the killer and the killer's line
+//! until neither is left alive.
+
+use crate::actions::catalog::ActionId;
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::social::{Disposition, EventType};
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::epithets::known_as;
+use crate::simulation::tick::TICKS_PER_DAY;
+use crate::world::legacy::MemorialId;
+
+/// Renown needed for a memorial
+pub const NOTABLE_RENOWN: f32 = 0.2;
+
+/// Renown earned per living friend or descendant at death
+pub const RENOWN_PER_MOURNER: f32 = 0.1;
+
+/// Distance from a memorial at which mourners remember the dead
+pub const REMEMBRANCE_RANGE: f32 = 5.0;
+
+/// Days between visits to a memorial
+pub const VISIT_INTERVAL_DAYS: u64 = 7;
+
+/// Grudge recorded each day by a child of the murdered against the killer
+pub const FEUD_INTENSITY: f32 = 0.8;
+
+/// Extra relationship intensity from a partner with full ancestral standing
+pub const STANDING_BONUS: f32 = 0.2;
+
+/// Living humans who mourn a person, with how close they were (0.0-1.0)
+///
+/// Descendants mourn by blood, halved per generation; friends mourn by
+/// disposition.
+pub fn mourners(world: &World, deceased: EntityId) -> Vec<(usize, f32)> {
+    world
+        .humans
+        .iter_living()
+        .filter_map(|i| {
+            let closeness = closeness(world, i, deceased);
+            (closeness > 0.0).then_some((i, closeness))
+        })
+        .collect()
+}
+
+/// How close human `i` is to `other` (0.0-1.0): by blood for their
+/// descendants, halved per generation, and by disposition for friends
+pub fn closeness(world: &World, i: usize, other: EntityId) -> f32 {
+    let kin = world
+        .legacy
+        .generations_between(world.humans.ids[i], other)
+        .map(|g| 0.5f32.powi(g as i32 - 1))
+        .unwrap_or(0.0);
+    let friend = match world.humans.social_memories[i].get_disposition(other) {
+        Disposition::Favorable => 1.0,
+        Disposition::Friendly => 0.5,
+        _ => 0.0,
+    };
+    f32::max(kin, friend)
+}
+
+/// Chronicle a human's death and raise a memorial if they were notable
+pub fn record_death(
+    world: &mut World,
+    idx: usize,
+    wrongdoer: Option<EntityId>,
+) -> Option<MemorialId> {
+    let deceased = world.humans.ids[idx];
+    let name = known_as(world, idx);
+    let tick = world.current_tick;
+    world.legacy.record(tick, format!("{} died", name));
+    world.identities.record_death(deceased, tick);
+
+    let renown = mourners(world, deceased).len() as f32 * RENOWN_PER_MOURNER;
+    if renown < NOTABLE_RENOWN {
+        return None;
+    }
+    let position = world.humans.positions[idx];
+    Some(
+        world
+            .legacy
+            .raise_memorial(deceased, name, position, renown, wrongdoer, tick),
+    )
+}
+
+/// What one day of legacy upkeep did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LegacyReport {
+    pub remembered: usize,
+    pub visits: usize,
+    pub grudges: usize,
+    pub settled: usize,
+}
+
+/// Run one day of legacy upkeep
+pub fn run_legacy(world: &mut World) -> LegacyReport {
+    let mut report = LegacyReport::default();
+    let tick = world.current_tick;
+
+    for m in 0..world.legacy.memorials.len() {
+        let memorial = world.legacy.memorials[m].clone();
+        let grievance = memorial.grievance();
+        let days = tick.saturating_sub(memorial.died_tick) / TICKS_PER_DAY;
+        let anniversary = days > 0 && days.is_multiple_of(VISIT_INTERVAL_DAYS);
+
+        for (i, closeness) in mourners(world, memorial.deceased) {
+            let pos = world.humans.positions[i];
+            if pos.distance(&memorial.position) <= REMEMBRANCE_RANGE {
+                // Graveside: pride in the dead, or grief while their killer walks free
+                let (valence, concept) = match grievance {
+                    Some(_) => (Valence::Negative, "grief"),
+                    None => (Valence::Positive, "remembrance"),
+                };
+                world.humans.thoughts[i].add(Thought::new(
+                    valence,
+                    (memorial.renown * closeness).max(0.3),
+                    concept,
+                    format!("remembered {}", memorial.name),
+                    CauseType::Entity,
+                    tick,
+                ));
+                report.remembered += 1;
+            } else if anniversary {
+                let idle = world.humans.task_queues[i]
+                    .current()
+                    .map(|t| matches!(t.action, ActionId::IdleWander | ActionId::IdleObserve))
+                    .unwrap_or(true);
+                if idle {
+                    world.humans.task_queues[i].clear();
+                    let task = Task::new(ActionId::MoveTo, TaskPriority::Normal, tick)
+                        .with_position(memorial.position);
+                    world.humans.task_queues[i].push(task);
+                    report.visits += 1;
+                }
+            }
+
+            if let Some(wrongdoer) = grievance {
+                report.grudges += nurse_grudge(world, i, closeness, wrongdoer);
+            }
+        }
+
+        if let Some(wrongdoer) = grievance {
+            if feud_targets(world, wrongdoer).is_empty() {
+                world.legacy.memorials[m].settled = true;
+                world
+                    .legacy
+                    .record(tick, format!("The death of {} is settled", memorial.name));
+                report.settled += 1;
+            }
+        }
+    }
+
+    report
+}
+
+/// Everyone alive who answers for a wrongdoer's deed, with how much of it
+/// they carry: the wrongdoer in full, their descendants halved per generation
+fn feud_targets(world: &World, wrongdoer: EntityId) -> Vec<(EntityId, f32)> {
+    let mut targets = Vec::new();
+    let wrongdoer_alive = world
+        .humans
+        .index_of(wrongdoer)
+        .map(|i| world.humans.alive[i])
+        .or_else(|| world.orcs.index_of(wrongdoer).map(|i| world.orcs.alive[i]))
+        .unwrap_or(false);
+    if wrongdoer_alive {
+        targets.push((wrongdoer, 1.0));
+    }
+    for i in world.humans.iter_living() {
+        let id = world.humans.ids[i];
+        if let Some(g) = world.legacy.generations_between(id, wrongdoer) {
+            targets.push((id, 0.5f32.powi(g as i32)));
+        }
+    }
+    targets
+}
+
+/// Record a day's grudge against the wrongdoer and their line. Returns the
+/// number of grudges recorded.
+fn nurse_grudge(world: &mut World, mourner: usize, closeness: f32, wrongdoer: EntityId) -> usize {
+    let tick = world.current_tick;
+    let mut grudges = 0;
+    for (target, guilt) in feud_targets(world, wrongdoer) {
+        if target == world.humans.ids[mourner] {
+            continue;
+        }
+        world.humans.social_memories[mourner].record_encounter(
+            target,
+            EventType::HarmReceived,
+            FEUD_INTENSITY * closeness * guilt,
+            tick,
+        );
+        grudges += 1;
+    }
+    grudges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Vec2;
+
+    /// A founder with a child and grandchild, plus a friend who knew them
+    fn family(world: &mut World) -> (EntityId, EntityId, EntityId, EntityId) {
+        let founder = world.spawn_human("Founder".into());
+        let child = world.spawn_human("Child".into());
+        let grandchild = world.spawn_human("Grandchild".into());
+        let friend = world.spawn_human("Friend".into());
+        world.legacy.record_birth(child, &[founder]);
+        world.legacy.record_birth(grandchild, &[child]);
+        world.humans.social_memories[3].record_encounter(founder, EventType::AidReceived, 0.9, 0);
+        (founder, child, grandchild, friend)
+    }
+
+    #[test]
+    fn test_memorial_mourners_remember_and_visit() {
+        let mut world = World::new();
+        let (founder, child, _, _) = family(&mut world);
+        world.humans.positions[0] = Vec2::new(50.0, 50.0);
+        world.humans.alive[0] = false;
+
+        let memorial = record_death(&mut world, 0, None).unwrap();
+        assert_eq!(world.legacy.memorials[0].id, memorial);
+        assert!((world.legacy.memorials[0].renown - 0.3).abs() < 1e-6);
+        assert!(world.legacy.standing(child) > 0.0);
+        assert_eq!(world.legacy.chronicle.len(), 1);
+
+        // Child at the grave remembers; the rest are far away
+        world.humans.positions[1] = Vec2::new(51.0, 50.0);
+        world.current_tick = TICKS_PER_DAY;
+        let report = run_legacy(&mut world);
+        assert_eq!(report.remembered, 1);
+        assert_eq!(report.visits, 0);
+        assert!(world.humans.thoughts[1]
+            .iter()
+            .any(|t| t.concept_category == "remembrance"));
+
+        // On the anniversary, the idle grandchild and friend walk to the grave
+        world.current_tick = TICKS_PER_DAY * VISIT_INTERVAL_DAYS;
+        let report = run_legacy(&mut world);
+        assert_eq!(report.visits, 2);
+        let task = world.humans.task_queues[2].current().unwrap();
+        assert_eq!(task.action, ActionId::MoveTo);
+        assert!(world.legacy.memorial_of(founder).is_some());
+    }
+
+    #[test]
+    fn test_murder_feuds_with_killer_line_until_settled() {
+        let mut world = World::new();
+        let (_, _, _, _) = family(&mut world);
+        let killer = world.spawn_human("Killer".into());
+        let killer_son = world.spawn_human("Killer's son".into());
+        world.legacy.record_birth(killer_son, &[killer]);
+        world.humans.alive[0] = false;
+        record_death(&mut world, 0, Some(killer)).unwrap();
+
+        for day in 1..=3 {
+            world.current_tick = TICKS_PER_DAY * day;
+            run_legacy(&mut world);
+        }
+        // The victim's child hates the killer and distrusts his son
+        assert_eq!(
+            world.humans.social_memories[1].get_disposition(killer),
+            Disposition::Hostile
+        );
+        assert_ne!(
+            world.humans.social_memories[1].get_disposition(killer_son),
+            Disposition::Unknown
+        );
+
+        // With the killer's whole line dead, the feud ends
+        world.humans.alive[4] = false;
+        world.humans.alive[5] = false;
+        world.current_tick = TICKS_PER_DAY * 4;
+        let report = run_legacy(&mut world);
+        assert_eq!(report.settled, 1);
+        assert_eq!(report.grudges, 0);
+        assert!(world.legacy.memorials[0].grievance().is_none());
+    }
+}
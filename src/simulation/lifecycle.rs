@@ -0,0 +1,199 @@
+//! Lifecycle system
+//!
+//! Everyone grows a day older each day. Past their elder years the body
+//! wears: frailty builds, capping health and making work more tiring, and
+//! each day brings a chance (rising with frailty) of dying in their sleep.
+//! Nobody outlives full frailty. The dead give up their house to the living.
+//!
+//! Births stay gated by housing and food (see population). A newborn starts
+//! at age zero in its parents' home, with values inherited from both of them
+//! and no skills yet, taking the slot of someone dead a day or more if there
+//! is one.
+
+use rand::Rng;
+
+use crate::core::astronomy::YEAR_LENGTH;
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::genetics::values::inherit_human_values;
+use crate::simulation::legacy::record_death;
+use crate::skills::ChunkLibrary;
+
+/// Age (years) from which people can start a family
+pub const ADULT_AGE: u32 = 16;
+
+/// Age (years) after which people no longer have children
+pub const FERTILE_UNTIL: u32 = 45;
+
+/// Age (years) at which the body starts to wear
+pub const ELDER_AGE: u32 = 60;
+
+/// Frailty gained per day past `ELDER_AGE` (full frailty thirty years on)
+pub const FRAILTY_PER_DAY: f32 = 1.0 / (30 * YEAR_LENGTH as u32) as f32;
+
+/// Daily chance of dying of old age at full frailty (scaled by frailty squared)
+pub const OLD_AGE_DEATH_CHANCE: f32 = 0.002;
+
+/// Whole years in an age given in days
+pub fn age_in_years(days: u32) -> u32 {
+    days / YEAR_LENGTH as u32
+}
+
+/// Whether someone is of an age to have children
+pub fn is_fertile(world: &World, i: usize) -> bool {
+    (ADULT_AGE..FERTILE_UNTIL).contains(&age_in_years(world.humans.ages[i]))
+}
+
+/// What one day of the lifecycle did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LifecycleReport {
+    /// Died of old age
+    pub died: Vec<EntityId>,
+}
+
+/// Run one day of aging and natural death
+pub fn run_lifecycle<R: Rng>(world: &mut World, rng: &mut R) -> LifecycleReport {
+    let mut report = LifecycleReport::default();
+    let living: Vec<usize> = world.humans.iter_living().collect();
+
+    for i in living {
+        world.humans.ages[i] += 1;
+        if age_in_years(world.humans.ages[i]) < ELDER_AGE {
+            continue;
+        }
+
+        let body = &mut world.humans.body_states[i];
+        body.add_frailty(FRAILTY_PER_DAY);
+        let frailty = body.frailty;
+        if frailty >= 1.0 || rng.gen::<f32>() < frailty * frailty * OLD_AGE_DEATH_CHANCE {
+            report.died.push(world.humans.ids[i]);
+            die_of_old_age(world, i);
+        }
+    }
+    report
+}
+
+/// Die peacefully: leave the house and any work behind, and be remembered
+pub fn die_of_old_age(world: &mut World, i: usize) {
+    world.humans.alive[i] = false;
+    world.humans.assigned_houses[i] = None;
+    world.humans.task_queues[i].clear();
+    record_death(world, i, None);
+}
+
+/// A newborn to `parents` (one or two living humans)
+pub fn bear_child<R: Rng>(world: &mut World, parents: &[EntityId], rng: &mut R) -> EntityId {
+    let tick = world.current_tick;
+    let name = format!("Newborn {}", tick);
+    let child = world.spawn_human_in_freed_slot(name);
+    let c = world.humans.index_of(child).unwrap();
+    world.humans.ages[c] = 0;
+    world.humans.birth_ticks[c] = tick;
+    world.humans.chunk_libraries[c] = ChunkLibrary::new();
+
+    let homes: Vec<usize> = parents
+        .iter()
+        .filter_map(|&p| world.humans.index_of(p))
+        .collect();
+    if let Some(&mother) = homes.first() {
+        world.humans.positions[c] = world.humans.positions[mother];
+        world.humans.assigned_houses[c] = world.humans.assigned_houses[mother];
+    }
+    if let [a, b] = homes[..] {
+        world.humans.values[c] =
+            inherit_human_values(&world.humans.values[a], &world.humans.values[b], rng);
+    }
+
+    world.legacy.record_birth(child, parents);
+    child
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::building::BuildingId;
+    use crate::core::time::TICKS_PER_DAY;
+    use crate::core::types::Species;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_elders_grow_frail_and_die() {
+        let mut world = World::with_seed(8);
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        let young = world.spawn_human("Ada".into());
+        let old = world.spawn_human("Bram".into());
+        world.humans.ages[1] = (ELDER_AGE + 29) * YEAR_LENGTH as u32;
+        world.humans.assigned_houses[1] = Some(BuildingId::new());
+
+        run_lifecycle(&mut world, &mut rng);
+        assert_eq!(world.humans.ages[0], 25 * YEAR_LENGTH as u32 + 1);
+        assert_eq!(world.humans.body_states[0].frailty, 0.0);
+        assert!(world.humans.body_states[1].frailty > 0.0);
+        assert!(world.humans.body_states[1].overall_health < 1.0);
+
+        // Full frailty is certain death
+        world.humans.body_states[1].frailty = 1.0;
+        let report = run_lifecycle(&mut world, &mut rng);
+        assert_eq!(report.died, vec![old]);
+        assert!(!world.humans.alive[1]);
+        assert_eq!(world.humans.assigned_houses[1], None);
+        assert!(world.humans.alive[world.humans.index_of(young).unwrap()]);
+        assert!(world.legacy.chronicle.last().unwrap().text.contains("Bram"));
+    }
+
+    #[test]
+    fn test_newborns_start_young_at_home_with_inherited_values() {
+        let mut world = World::with_seed(9);
+        let mut rng = ChaCha8Rng::seed_from_u64(9);
+        let mother = world.spawn_human("Ada".into());
+        let father = world.spawn_human("Bram".into());
+        let house = BuildingId::new();
+        for i in 0..2 {
+            world.humans.assigned_houses[i] = Some(house);
+            world.humans.values[i].honor = 0.9;
+        }
+
+        let child = bear_child(&mut world, &[mother, father], &mut rng);
+        let c = world.humans.index_of(child).unwrap();
+        assert_eq!(world.humans.ages[c], 0);
+        assert!(!is_fertile(&world, c));
+        assert_eq!(world.humans.assigned_houses[c], Some(house));
+        assert!(world.humans.values[c].honor >= 0.8);
+        assert!(world.humans.chunk_libraries[c].chunks().is_empty());
+        assert_eq!(world.legacy.parents_of(child), &[mother, father]);
+    }
+
+    #[test]
+    fn test_the_dead_free_their_slots_for_newborns() {
+        let mut world = World::with_seed(10);
+        let mut rng = ChaCha8Rng::seed_from_u64(10);
+        let parents = [
+            world.spawn_human("Ada".into()),
+            world.spawn_human("Bram".into()),
+        ];
+
+        // Generation after generation lives and dies in the same slot
+        let mut buried = Vec::new();
+        for _ in 0..50 {
+            let child = bear_child(&mut world, &parents, &mut rng);
+            let c = world.humans.index_of(child).unwrap();
+            assert_eq!(world.get_entity_info(child), Some((Species::Human, c)));
+            die_of_old_age(&mut world, c);
+            buried.push(child);
+            world.current_tick += TICKS_PER_DAY;
+        }
+        assert_eq!(world.humans.count(), 3);
+        assert!(buried[..49]
+            .iter()
+            .all(|&id| world.get_entity_info(id).is_none()));
+
+        // Someone dead less than a day keeps their slot
+        let child = bear_child(&mut world, &parents, &mut rng);
+        let c = world.humans.index_of(child).unwrap();
+        die_of_old_age(&mut world, c);
+        bear_child(&mut world, &parents, &mut rng);
+        assert_eq!(world.humans.count(), 4);
+        assert!(world.humans.index_of(child).is_some());
+    }
+}
@@ -0,0 +1,149 @@
+//! Lighting system
+//!
+//! Every tick, carried lights follow their carriers, workers caught in the
+//! dark pick up torches, and lit sources burn wood from the stockpile. Once a
+//! day, every burning light rolls its fire risk against nearby buildings.
+
+use rand::Rng;
+
+use crate::actions::catalog::ActionCategory;
+use crate::city::building::BuildingState;
+use crate::ecs::world::World;
+use crate::simulation::resource_zone::ResourceType;
+use crate::world::lighting::{LightKind, FIRE_RANGE, LIT_BELOW_AMBIENT};
+
+/// Workers leave this much wood in the stockpile rather than take a torch
+pub const TORCH_WOOD_RESERVE: u32 = 20;
+
+/// Run one step of lighting upkeep. Returns the wood burned.
+pub fn tend_lights(world: &mut World) -> u32 {
+    follow_carriers(world);
+    let ambient = world.astronomy.light_level;
+    if ambient < LIT_BELOW_AMBIENT {
+        equip_workers(world);
+    }
+
+    let mut wood = world.stockpile.get(ResourceType::Wood);
+    let span = world.time_scale.span() as f32;
+    let used = world.lights.burn_over(ambient, &mut wood, span);
+    world.stockpile.remove(ResourceType::Wood, used);
+    used
+}
+
+/// Move carried lights to their carriers; drop lights whose carrier is gone
+fn follow_carriers(world: &mut World) {
+    let humans = &world.humans;
+    world.lights.sources.retain_mut(|source| {
+        let Some(carrier) = source.carrier else {
+            return true;
+        };
+        match humans.index_of(carrier) {
+            Some(idx) if humans.alive[idx] => {
+                source.position = humans.positions[idx];
+                true
+            }
+            _ => false,
+        }
+    });
+}
+
+/// Give a torch to every worker without a light, while wood allows
+fn equip_workers(world: &mut World) -> usize {
+    let mut equipped = 0;
+    let workers: Vec<usize> = world
+        .humans
+        .iter_living()
+        .filter(|&i| {
+            world.humans.task_queues[i]
+                .current()
+                .map(|t| t.action.category() == ActionCategory::Work)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    for i in workers {
+        if world.stockpile.get(ResourceType::Wood) <= TORCH_WOOD_RESERVE {
+            break;
+        }
+        let id = world.humans.ids[i];
+        if world.give_light(id, LightKind::Torch).is_some() {
+            equipped += 1;
+        }
+    }
+    equipped
+}
+
+/// Roll every burning light against complete buildings within `FIRE_RANGE`.
+/// Run once a day; returns number of buildings set alight.
+///
+/// A building that catches fire is left `Damaged` and needs repair.
+pub fn check_fires<R: Rng>(world: &mut World, rng: &mut R) -> usize {
+    let mut fires = 0;
+    for source in world.lights.sources.iter().filter(|s| s.lit) {
+        let near: Vec<usize> = world
+            .buildings
+            .iter_complete()
+            .filter(|&b| world.buildings.positions[b].distance(&source.position) <= FIRE_RANGE)
+            .collect();
+        for b in near {
+            if rng.gen::<f32>() < source.kind.fire_risk() {
+                world.buildings.states[b] = BuildingState::Damaged;
+                tracing::debug!("Light {:?} set building {} alight", source.id, b);
+                fires += 1;
+            }
+        }
+    }
+    fires
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::catalog::ActionId;
+    use crate::city::building::BuildingType;
+    use crate::core::types::Vec2;
+    use crate::entity::tasks::{Task, TaskPriority};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_workers_take_torches_after_dark() {
+        let mut world = World::new();
+        let worker = world.spawn_human("Worker".into());
+        world.spawn_human("Idler".into());
+        world.humans.task_queues[0].push(Task::new(ActionId::Build, TaskPriority::Normal, 0));
+        world
+            .stockpile
+            .add(ResourceType::Wood, TORCH_WOOD_RESERVE + 5);
+
+        world.astronomy.light_level = 0.0;
+        assert_eq!(tend_lights(&mut world), 1);
+        assert_eq!(world.lights.sources.len(), 1);
+        assert!(world.lights.carried_by(worker).unwrap().lit);
+
+        // The torch follows its carrier
+        world.humans.positions[0] = Vec2::new(30.0, 0.0);
+        tend_lights(&mut world);
+        assert!(world.lights.light_at(Vec2::new(31.0, 0.0), 0.0) > 0.5);
+
+        // Dead carriers drop their light
+        world.humans.alive[0] = false;
+        tend_lights(&mut world);
+        assert!(world.lights.sources.is_empty());
+    }
+
+    #[test]
+    fn test_campfire_can_burn_nearby_building() {
+        let mut world = World::new();
+        world.spawn_building(BuildingType::House, Vec2::new(2.0, 0.0));
+        world.buildings.states[0] = BuildingState::Complete;
+        world.place_light(LightKind::Campfire, Vec2::new(0.0, 0.0));
+        world.stockpile.add(ResourceType::Wood, 10);
+        world.astronomy.light_level = 0.0;
+        tend_lights(&mut world);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!((0..1000).any(|_| check_fires(&mut world, &mut rng) > 0));
+        assert_eq!(world.buildings.states[0], BuildingState::Damaged);
+    }
+}
@@ -32,24 +32,71 @@
 
 pub mod action_execute;
 pub mod action_select;
+pub mod aftermath;
+pub mod almanac;
+pub mod ambience;
+pub mod aspirations;
+pub mod avatar;
+pub mod barter;
+pub mod commanders;
 pub mod consumption;
+pub mod crossings;
+pub mod decision_trace;
+pub mod despair;
+pub mod drill;
+pub mod economy;
+pub mod epithets;
+pub mod eras;
+pub mod exhaustion;
+pub mod exile;
+pub mod happiness;
 pub mod expectation_formation;
+pub mod headless;
 pub mod housing;
+pub mod husbandry;
+pub mod hygiene;
+pub mod items;
+pub mod jobs;
+pub mod kin_temperament;
+pub mod legacy;
+pub mod lifecycle;
+pub mod lighting;
+pub mod oaths;
+pub mod omens;
+pub mod orc_temperament;
+pub mod parallel;
 pub mod perception;
+pub mod places;
 pub mod population;
+pub mod regrowth;
+pub mod replay;
+pub mod requests;
+pub mod rescue;
 pub mod resource_zone;
+pub mod roads;
 pub mod rule_eval;
+pub mod scheduler;
+pub mod sites;
+pub mod situation;
+pub mod soak;
 pub mod thought_gen;
 pub mod tick;
+pub mod time_scale;
+pub mod time_sync;
+pub mod transformations;
+pub mod unrest;
 pub mod value_dynamics;
 pub mod violation_detection;
+pub mod weather_effects;
 
 pub use action_select::select_action_with_rules;
+pub use economy::{Prices, StockpileTrade};
 pub use expectation_formation::{
     infer_patterns_from_action, process_observations, record_observation,
 };
 pub use resource_zone::{ResourceType, ResourceZone};
 pub use rule_eval::{evaluate_action_rules, select_idle_behavior};
+pub use scheduler::{Frequency, ScheduleError, Scheduler, Share, SystemId, SystemTiming};
 pub use tick::{check_win_condition, GameOutcome, SimulationEvent};
 pub use value_dynamics::{apply_event, apply_tick_dynamics};
 pub use violation_detection::{
@@ -0,0 +1,473 @@
+//! Oath system
+//!
+//! Once a day every open oath is checked. A delivery is kept once the
+//! stockpile has gained the promised amount since the oath was sworn; a
+//! defence is kept if the ward is alive at the deadline and broken the day
+//! the ward dies. Keeping an oath earns the swearer honor and the
+//! beneficiary's gratitude. Breaking one shames the swearer, leaves the
+//! beneficiary (and the kin of a fallen ward) with a grudge, and brings the
+//! swearer before the most just person in the settlement, who orders
+//! restitution of any unpaid delivery. One who flouts that order is exiled
+//! by the judge who gave it. With no one fit to judge, the justice-minded
+//! are left outraged.
+//!
+//! Honorable humans also swear oaths of their own accord: to defend a close
+//! friend for a month, going to their side whenever they are idle and far
+//! away.
+
+use crate::actions::catalog::ActionId;
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::social::{Disposition, EventType};
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::exile::exile;
+use crate::simulation::legacy::mourners;
+use crate::simulation::tick::TICKS_PER_DAY;
+use crate::world::{Oath, OathId, OathStatus, OathTerms};
+
+/// Honor gained by the swearer for keeping an oath
+pub const HONOR_GAIN: f32 = 0.02;
+
+/// Honor lost by the swearer for breaking an oath
+pub const HONOR_LOSS: f32 = 0.05;
+
+/// Encounter intensity of a kept oath for the beneficiary
+pub const GRATITUDE_INTENSITY: f32 = 0.6;
+
+/// Encounter intensity of a broken oath for the beneficiary
+pub const BETRAYAL_INTENSITY: f32 = 0.8;
+
+/// Justice needed to sit in judgment over a broken oath
+pub const ARBITER_JUSTICE: f32 = 0.6;
+
+/// Justice above which an unjudged breach provokes outrage
+pub const OUTRAGE_JUSTICE: f32 = 0.7;
+
+/// Intensity of the outrage (strong enough to move the justice-minded to act)
+pub const OUTRAGE_INTENSITY: f32 = 0.8;
+
+/// Days given to make restitution ordered at a hearing
+pub const RESTITUTION_DAYS: u32 = 10;
+
+/// Honor above which humans swear to defend their friends
+pub const OATH_HONOR: f32 = 0.7;
+
+/// Length of a self-sworn defence oath
+pub const DEFEND_DAYS: u32 = 30;
+
+/// Distance from their ward beyond which an idle defender goes to them
+pub const GUARD_RANGE: f32 = 10.0;
+
+/// What one day of oath upkeep did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OathReport {
+    pub sworn: usize,
+    pub kept: Vec<OathId>,
+    pub broken: Vec<OathId>,
+    pub released: usize,
+    pub hearings: usize,
+    pub guard_trips: usize,
+}
+
+/// Run one day of oath upkeep
+pub fn run_oaths(world: &mut World) -> OathReport {
+    let mut report = OathReport::default();
+    let tick = world.current_tick;
+
+    for o in 0..world.oaths.oaths.len() {
+        let oath = world.oaths.oaths[o].clone();
+        if !oath.is_sworn() {
+            continue;
+        }
+        if !is_alive(world, oath.swearer) {
+            world.oaths.oaths[o].status = OathStatus::Released;
+            report.released += 1;
+            continue;
+        }
+
+        let kept = match oath.terms {
+            OathTerms::Deliver { resource, amount } => {
+                let delivered = world.stockpile.get(resource).saturating_sub(oath.baseline);
+                if delivered >= amount {
+                    Some(true)
+                } else if tick >= oath.deadline_tick {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            OathTerms::Defend { ward } => {
+                if !is_alive(world, ward) {
+                    Some(false)
+                } else if tick >= oath.deadline_tick {
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+        };
+
+        match kept {
+            Some(true) => {
+                world.oaths.oaths[o].status = OathStatus::Kept;
+                keep_oath(world, &oath);
+                report.kept.push(oath.id);
+            }
+            Some(false) => {
+                world.oaths.oaths[o].status = OathStatus::Broken;
+                if break_oath(world, &oath) {
+                    report.hearings += 1;
+                }
+                report.broken.push(oath.id);
+            }
+            None => {}
+        }
+    }
+
+    report.sworn = swear_defence_oaths(world);
+    report.guard_trips = send_defenders(world);
+    report
+}
+
+fn is_alive(world: &World, id: EntityId) -> bool {
+    world
+        .humans
+        .index_of(id)
+        .map(|i| world.humans.alive[i])
+        .unwrap_or(false)
+}
+
+fn living_index(world: &World, id: EntityId) -> Option<usize> {
+    world.humans.index_of(id).filter(|&i| world.humans.alive[i])
+}
+
+fn keep_oath(world: &mut World, oath: &Oath) {
+    let tick = world.current_tick;
+    if let Some(s) = living_index(world, oath.swearer) {
+        let honor = &mut world.humans.values[s].honor;
+        *honor = (*honor + HONOR_GAIN).min(1.0);
+        world.humans.thoughts[s].add(Thought::new(
+            Valence::Positive,
+            0.5,
+            "pride",
+            "kept an oath",
+            CauseType::Action,
+            tick,
+        ));
+    }
+    if let Some(b) = oath.beneficiary.and_then(|id| living_index(world, id)) {
+        world.humans.social_memories[b].record_encounter(
+            oath.swearer,
+            EventType::PromiseKept,
+            GRATITUDE_INTENSITY,
+            tick,
+        );
+    }
+}
+
+/// Shame the swearer, aggrieve those wronged, and see justice done. Returns
+/// whether a hearing was held (or the swearer exiled for defying one).
+fn break_oath(world: &mut World, oath: &Oath) -> bool {
+    let tick = world.current_tick;
+    let swearer_idx = living_index(world, oath.swearer);
+    if let Some(s) = swearer_idx {
+        let honor = &mut world.humans.values[s].honor;
+        *honor = (*honor - HONOR_LOSS).max(0.0);
+        world.humans.thoughts[s].add(Thought::new(
+            Valence::Negative,
+            0.6,
+            "shame",
+            "broke an oath",
+            CauseType::Action,
+            tick,
+        ));
+    }
+
+    // The beneficiary feels betrayed, as do the kin and friends of a fallen ward
+    let mut aggrieved: Vec<(usize, f32)> = oath
+        .beneficiary
+        .and_then(|id| living_index(world, id))
+        .map(|b| vec![(b, 1.0)])
+        .unwrap_or_default();
+    if let OathTerms::Defend { ward } = oath.terms {
+        for (i, closeness) in mourners(world, ward) {
+            if !aggrieved.iter().any(|&(a, _)| a == i) {
+                aggrieved.push((i, closeness));
+            }
+        }
+    }
+    for &(i, closeness) in &aggrieved {
+        if world.humans.ids[i] == oath.swearer {
+            continue;
+        }
+        world.humans.social_memories[i].record_encounter(
+            oath.swearer,
+            EventType::PromiseBroken,
+            BETRAYAL_INTENSITY * closeness,
+            tick,
+        );
+        let mut thought = Thought::new(
+            Valence::Negative,
+            BETRAYAL_INTENSITY * closeness,
+            "BETRAYAL",
+            "a sworn oath was broken",
+            CauseType::Entity,
+            tick,
+        );
+        thought.cause_entity = Some(oath.swearer);
+        world.humans.thoughts[i].add(thought);
+    }
+
+    // A first breach goes before an arbiter, and a flouted restitution order
+    // brings exile; a breach with no one fit to judge is an injustice
+    let arbiter = if oath.ordered_by.is_none() {
+        let parties: Vec<usize> = aggrieved.iter().map(|&(i, _)| i).collect();
+        find_arbiter(world, oath, &parties)
+    } else {
+        None
+    };
+    match (arbiter, swearer_idx) {
+        (Some(arbiter), Some(s)) => {
+            hold_hearing(world, oath, arbiter, s);
+            true
+        }
+        (None, Some(s)) if oath.ordered_by.is_some_and(|judge| is_alive(world, judge)) => {
+            let reason = "flouted an order to make restitution";
+            exile(world, s, reason, oath.ordered_by).is_ok()
+        }
+        _ => {
+            for i in world.humans.iter_living().collect::<Vec<_>>() {
+                if world.humans.ids[i] != oath.swearer
+                    && world.humans.values[i].justice > OUTRAGE_JUSTICE
+                {
+                    let mut thought = Thought::new(
+                        Valence::Negative,
+                        OUTRAGE_INTENSITY,
+                        "injustice",
+                        "an oathbreaker goes unpunished",
+                        CauseType::Entity,
+                        tick,
+                    );
+                    thought.cause_entity = Some(oath.swearer);
+                    world.humans.thoughts[i].add(thought);
+                }
+            }
+            false
+        }
+    }
+}
+
+/// The most just living human who is neither the swearer nor one of the
+/// wronged parties
+fn find_arbiter(world: &World, oath: &Oath, parties: &[usize]) -> Option<usize> {
+    world
+        .humans
+        .iter_living()
+        .filter(|&i| {
+            world.humans.ids[i] != oath.swearer
+                && !parties.contains(&i)
+                && world.humans.values[i].justice >= ARBITER_JUSTICE
+        })
+        .max_by(|&a, &b| {
+            world.humans.values[a]
+                .justice
+                .total_cmp(&world.humans.values[b].justice)
+        })
+}
+
+/// Judge an oathbreaker: chronicle the ruling and order any unpaid
+/// delivery made good
+fn hold_hearing(world: &mut World, oath: &Oath, arbiter: usize, swearer: usize) {
+    let tick = world.current_tick;
+    let arbiter_id = world.humans.ids[arbiter];
+    let ruling = match oath.terms {
+        OathTerms::Deliver { resource, amount } => {
+            let delivered = world.stockpile.get(resource).saturating_sub(oath.baseline);
+            let owed = amount.saturating_sub(delivered);
+            let deadline = tick + RESTITUTION_DAYS as u64 * TICKS_PER_DAY;
+            let baseline = world.stockpile.get(resource);
+            let order = world.oaths.swear(
+                oath.swearer,
+                oath.beneficiary,
+                OathTerms::Deliver {
+                    resource,
+                    amount: owed,
+                },
+                tick,
+                deadline,
+            );
+            order.baseline = baseline;
+            order.ordered_by = Some(arbiter_id);
+            format!(
+                "to deliver {} {:?} within {} days",
+                owed, resource, RESTITUTION_DAYS
+            )
+        }
+        OathTerms::Defend { .. } => "guilty of failing their ward".to_string(),
+    };
+    world.legacy.record(
+        tick,
+        format!(
+            "{} judged {} for breaking an oath: {}",
+            world.humans.names[arbiter], world.humans.names[swearer], ruling
+        ),
+    );
+}
+
+/// Honorable humans with no open oath swear to defend a close friend.
+/// Returns the number of oaths sworn.
+fn swear_defence_oaths(world: &mut World) -> usize {
+    let mut sworn = 0;
+    for i in world.humans.iter_living().collect::<Vec<_>>() {
+        let id = world.humans.ids[i];
+        if world.humans.values[i].honor <= OATH_HONOR || world.oaths.sworn_by(id).next().is_some() {
+            continue;
+        }
+        let friend = world.humans.social_memories[i]
+            .slots
+            .iter()
+            .map(|slot| slot.target_id)
+            .find(|&target| {
+                world.humans.social_memories[i].get_disposition(target) == Disposition::Favorable
+                    && is_alive(world, target)
+            });
+        if let Some(ward) = friend {
+            world.swear_oath(id, Some(ward), OathTerms::Defend { ward }, DEFEND_DAYS);
+            sworn += 1;
+        }
+    }
+    sworn
+}
+
+/// Send idle defenders to wards who are out of reach. Returns the number of
+/// trips assigned.
+fn send_defenders(world: &mut World) -> usize {
+    let tick = world.current_tick;
+    let duties: Vec<(EntityId, EntityId)> = world
+        .oaths
+        .sworn()
+        .filter_map(|o| match o.terms {
+            OathTerms::Defend { ward } => Some((o.swearer, ward)),
+            OathTerms::Deliver { .. } => None,
+        })
+        .collect();
+
+    let mut trips = 0;
+    for (swearer, ward) in duties {
+        let (Some(s), Some(w)) = (living_index(world, swearer), living_index(world, ward)) else {
+            continue;
+        };
+        let ward_pos = world.humans.positions[w];
+        let idle = world.humans.task_queues[s]
+            .current()
+            .map(|t| matches!(t.action, ActionId::IdleWander | ActionId::IdleObserve))
+            .unwrap_or(true);
+        if idle
+            && !world.humans.body_states[s].is_overworked()
+            && world.humans.positions[s].distance(&ward_pos) > GUARD_RANGE
+        {
+            world.humans.task_queues[s].clear();
+            let task =
+                Task::new(ActionId::MoveTo, TaskPriority::Normal, tick).with_position(ward_pos);
+            world.humans.task_queues[s].push(task);
+            trips += 1;
+        }
+    }
+    trips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Vec2;
+    use crate::simulation::resource_zone::ResourceType;
+
+    #[test]
+    fn test_delivery_kept_or_judged_and_restituted() {
+        let mut world = World::new();
+        let smith = world.spawn_human("Smith".into());
+        let elder = world.spawn_human("Elder".into());
+        let judge = world.spawn_human("Judge".into());
+        world.humans.values[2].justice = 0.9;
+        let honor = world.humans.values[0].honor;
+
+        // Kept: the stockpile gains the promised wood
+        let terms = OathTerms::Deliver {
+            resource: ResourceType::Wood,
+            amount: 20,
+        };
+        let kept = world
+            .swear_oath(smith, Some(elder), terms.clone(), 5)
+            .unwrap();
+        world.stockpile.add(ResourceType::Wood, 25);
+        world.current_tick = TICKS_PER_DAY;
+        let report = run_oaths(&mut world);
+        assert_eq!(report.kept, vec![kept]);
+        assert!(world.humans.values[0].honor > honor);
+        assert_eq!(world.oaths.reputation(smith), 1.0);
+
+        // Broken: nothing delivered by the deadline, so the judge orders it made good
+        let broken = world.swear_oath(smith, Some(elder), terms, 5).unwrap();
+        world.stockpile.add(ResourceType::Wood, 5);
+        world.current_tick = TICKS_PER_DAY * 6;
+        let report = run_oaths(&mut world);
+        assert_eq!(report.broken, vec![broken]);
+        assert_eq!(report.hearings, 1);
+        assert_eq!(world.oaths.reputation(smith), 0.0);
+        let order = world.oaths.sworn_by(smith).next().unwrap();
+        assert_eq!(order.ordered_by, Some(judge));
+        assert_eq!(
+            order.terms,
+            OathTerms::Deliver {
+                resource: ResourceType::Wood,
+                amount: 15
+            }
+        );
+        assert!(world
+            .legacy
+            .chronicle
+            .iter()
+            .any(|e| e.text.starts_with("Judge judged Smith")));
+        assert!(world.humans.thoughts[1]
+            .iter()
+            .any(|t| t.concept_category == "BETRAYAL"));
+    }
+
+    #[test]
+    fn test_fallen_ward_breaks_defence_oath() {
+        let mut world = World::new();
+        let guard = world.spawn_human("Guard".into());
+        let ward = world.spawn_human("Ward".into());
+        let child = world.spawn_human("Child".into());
+        world.legacy.record_birth(child, &[ward]);
+        world.humans.values[0].honor = 0.9;
+        world.humans.values[2].justice = 0.8;
+        world.humans.social_memories[0].record_encounter(ward, EventType::AidReceived, 0.9, 0);
+        world.humans.positions[1] = Vec2::new(50.0, 50.0);
+
+        // The honorable guard swears to defend their friend and goes to them
+        world.current_tick = TICKS_PER_DAY;
+        let report = run_oaths(&mut world);
+        assert_eq!(report.sworn, 1);
+        assert_eq!(report.guard_trips, 1);
+        let task = world.humans.task_queues[0].current().unwrap();
+        assert_eq!(task.action, ActionId::MoveTo);
+
+        // The ward dies: the oath is broken and the child bears a grudge;
+        // the child is a party, so no one is left to judge
+        world.humans.alive[1] = false;
+        world.current_tick = TICKS_PER_DAY * 2;
+        let report = run_oaths(&mut world);
+        assert_eq!(report.broken.len(), 1);
+        assert_eq!(report.hearings, 0);
+        assert_eq!(report.sworn, 0);
+        assert_ne!(
+            world.humans.social_memories[2].get_disposition(guard),
+            Disposition::Unknown
+        );
+        assert!(world.humans.thoughts[2]
+            .iter()
+            .any(|t| t.concept_category == "injustice"));
+    }
+}
@@ -0,0 +1,396 @@
+//! Omens - the settlement's culture reading the sky, and what comes of it
+//!
+//! Once a day `run_omens` looks up the day's celestial events in the
+//! culture's table, loaded from `data/omens.toml`. Each event it lists
+//! becomes an omen: every settler dwells on it, the more pious the more
+//! deeply, and it holds for the days the table gives. While it holds it may
+//! keep settlers from fighting (`heed_omens` turns their attacks into
+//! seeking safety, and the executor refuses orders to fight) or slow their
+//! work.
+//!
+//! A dire omen presses the leader to hold a rite (`hold_rite`). The rite
+//! calms the settlers, lifts the omen's hold on them, and wins their trust;
+//! an omen that passes with its rite unheld costs the leader loyalty.
+//!
+//! A settlement founded from world history inherits its faith
+//! (`inherit_faith`): the religion's omen culture becomes the one the sky is
+//! read by, and settlers founded on a holy site start more pious.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::actions::catalog::{ActionCategory, ActionId};
+use crate::aggregate::religion::SettlementFaith;
+use crate::core::astronomy::CelestialEvent;
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskSource};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::happiness::in_settlement;
+use crate::world::omens::{Omen, DEFAULT_CULTURE};
+
+/// Loyalty each settler gains when the leader holds a rite
+pub const RITE_LOYALTY: f32 = 0.03;
+
+/// Loyalty each settler loses when an omen passes with its rite unheld
+pub const UNHEEDED_LOYALTY: f32 = 0.06;
+
+/// Piety settlers gain when their settlement stands on a holy site
+pub const HOLY_SITE_PIETY: f32 = 0.15;
+
+/// Errors from loading omen tables
+#[derive(Error, Debug)]
+pub enum OmenError {
+    /// Reading the file failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The file is not valid omen TOML
+    #[error("Parse error: {0}")]
+    ParseError(#[from] toml::de::Error),
+}
+
+/// Why a rite could not be held
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum RiteError {
+    #[error("No omen is waiting for a rite")]
+    NothingToAnswer,
+}
+
+/// How one culture reads one celestial event
+#[derive(Debug, Clone, Deserialize)]
+pub struct OmenRule {
+    pub reading: String,
+    pub valence: Valence,
+    /// Strength of the thought it gives, before piety
+    pub intensity: f32,
+    /// Days it holds
+    pub days: u32,
+    #[serde(default)]
+    pub refuse_fighting: bool,
+    #[serde(default = "no_effect")]
+    pub work_pace: f32,
+    /// The rite the leader is pressed to hold, if any
+    #[serde(default)]
+    pub rite: Option<String>,
+}
+
+fn no_effect() -> f32 {
+    1.0
+}
+
+/// The omens one culture sees, by event name ("BloodEclipse")
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CultureOmens {
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub omens: HashMap<String, OmenRule>,
+}
+
+/// Every culture's omen table
+#[derive(Debug, Clone, Deserialize)]
+pub struct OmenRules {
+    #[serde(default = "default_culture")]
+    pub default_culture: String,
+    #[serde(default)]
+    pub cultures: HashMap<String, CultureOmens>,
+}
+
+fn default_culture() -> String {
+    DEFAULT_CULTURE.to_string()
+}
+
+impl Default for OmenRules {
+    fn default() -> Self {
+        Self {
+            default_culture: default_culture(),
+            cultures: HashMap::new(),
+        }
+    }
+}
+
+impl OmenRules {
+    /// No cultures: the sky means nothing to anyone
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(content: &str) -> Result<Self, OmenError> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, OmenError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// How `culture` reads `event`, if it takes it as an omen at all
+    pub fn reading(&self, culture: &str, event: CelestialEvent) -> Option<&OmenRule> {
+        self.cultures
+            .get(culture)?
+            .omens
+            .get(&format!("{:?}", event))
+    }
+}
+
+/// Load omen tables from `data/omens.toml`
+pub(crate) fn load_default_omen_rules() -> OmenRules {
+    OmenRules::load("data/omens.toml").unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load omen tables: {}", e);
+        OmenRules::new()
+    })
+}
+
+/// Omens seen today, and rites left unheld as their omens passed
+#[derive(Debug, Default)]
+pub struct OmenReport {
+    pub seen: Vec<String>,
+    pub unheeded: Vec<String>,
+}
+
+/// Give every settler a thought about an omen (deeper for the pious), and
+/// shift their loyalty
+fn stir(world: &mut World, valence: Valence, intensity: f32, cause: &str, loyalty: f32) {
+    let tick = world.current_tick;
+    for i in world.humans.iter_living().collect::<Vec<_>>() {
+        if !in_settlement(world, i) {
+            continue;
+        }
+        let values = &mut world.humans.values[i];
+        values.loyalty = (values.loyalty + loyalty).clamp(0.0, 1.0);
+        let felt = (intensity * (0.5 + values.piety)).min(1.0);
+        world.humans.thoughts[i].add(Thought::new(
+            valence,
+            felt,
+            "omen",
+            cause,
+            CauseType::Event,
+            tick,
+        ));
+    }
+}
+
+/// Let passing omens go and read today's sky
+pub fn run_omens(world: &mut World) -> OmenReport {
+    let mut report = OmenReport::default();
+    let today = world.astronomy.current_day;
+    let tick = world.current_tick;
+
+    let (passed, held): (Vec<Omen>, Vec<Omen>) = std::mem::take(&mut world.omens.active)
+        .into_iter()
+        .partition(|omen| omen.until_day <= today);
+    world.omens.active = held;
+    for rite in passed.into_iter().filter_map(|omen| omen.awaits_rite) {
+        stir(
+            world,
+            Valence::Negative,
+            0.4,
+            &format!("the leader let the omen pass without {}", rite),
+            -UNHEEDED_LOYALTY,
+        );
+        world.legacy.record(
+            tick,
+            format!(
+                "The omen passed with no {} held, and the settlers remember it",
+                rite
+            ),
+        );
+        report.unheeded.push(rite);
+    }
+
+    for event in world.astronomy.active_events.clone() {
+        let Some(rule) = world.omen_rules.reading(&world.omens.culture, event) else {
+            continue;
+        };
+        if world.omens.holds(event) {
+            continue;
+        }
+        let rule = rule.clone();
+        world.omens.active.push(Omen {
+            event,
+            reading: rule.reading.clone(),
+            seen_day: today,
+            until_day: today + rule.days,
+            refuse_fighting: rule.refuse_fighting,
+            work_pace: rule.work_pace,
+            awaits_rite: rule.rite.clone(),
+        });
+        stir(world, rule.valence, rule.intensity, &rule.reading, 0.0);
+        world.legacy.record(tick, rule.reading.clone());
+        report.seen.push(rule.reading);
+    }
+    report
+}
+
+/// The leader answers every omen waiting for a rite; returns how many
+pub fn hold_rite(world: &mut World) -> Result<usize, RiteError> {
+    let mut rites = Vec::new();
+    for omen in &mut world.omens.active {
+        if let Some(rite) = omen.awaits_rite.take() {
+            omen.refuse_fighting = false;
+            omen.work_pace = 1.0;
+            rites.push(rite);
+        }
+    }
+    if rites.is_empty() {
+        return Err(RiteError::NothingToAnswer);
+    }
+    let tick = world.current_tick;
+    for rite in &rites {
+        stir(
+            world,
+            Valence::Positive,
+            0.4,
+            &format!("the leader held {}", rite),
+            RITE_LOYALTY,
+        );
+        world
+            .legacy
+            .record(tick, format!("The leader held {} to answer the omen", rite));
+    }
+    Ok(rites.len())
+}
+
+/// Found the settlement in an inherited faith: read the sky as its culture
+/// does, if the omen tables know it, and on a holy site deepen every
+/// settler's piety
+pub fn inherit_faith(world: &mut World, faith: &SettlementFaith) {
+    if world.omen_rules.cultures.contains_key(&faith.omen_culture) {
+        world.omens.culture = faith.omen_culture.clone();
+    }
+    world.omens.faith = Some(faith.religion.clone());
+    if faith.holy_site {
+        for i in world.humans.iter_living().collect::<Vec<_>>() {
+            let values = &mut world.humans.values[i];
+            values.piety = (values.piety + HOLY_SITE_PIETY).min(1.0);
+        }
+    }
+    let tick = world.current_tick;
+    world.legacy.record(
+        tick,
+        format!(
+            "The settlement was founded in the {}, under {}{}",
+            faith.religion,
+            faith.patron,
+            if faith.holy_site {
+                ", on ground it holds holy"
+            } else {
+                ""
+            }
+        ),
+    );
+}
+
+/// Why settlers will not fight today, if an omen forbids it
+pub fn fighting_forbidden(world: &World) -> Option<String> {
+    world.omens.forbidding_fighting().map(|omen| {
+        format!(
+            "The settlers will not fight while the omen holds: {}",
+            omen.reading
+        )
+    })
+}
+
+/// Under an omen that forbids fighting, a settler who would fight of their
+/// own accord seeks safety instead; orders are refused by the executor
+pub fn heed_omens(world: &World, task: &mut Task) {
+    if task.action.category() == ActionCategory::Combat
+        && task.source != TaskSource::PlayerCommand
+        && world.omens.forbidding_fighting().is_some()
+    {
+        task.action = ActionId::SeekSafety;
+        task.target_entity = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::tasks::TaskPriority;
+
+    fn blood_eclipse(world: &mut World) {
+        world.astronomy.active_events = vec![CelestialEvent::BloodEclipse];
+        run_omens(world);
+    }
+
+    #[test]
+    fn test_cultures_read_the_same_sky_differently() {
+        let rules = OmenRules::load("data/omens.toml").unwrap();
+        let hearth = rules
+            .reading("hearthfolk", CelestialEvent::BloodEclipse)
+            .unwrap();
+        let blood = rules
+            .reading("bloodsworn", CelestialEvent::BloodEclipse)
+            .unwrap();
+        assert_eq!(hearth.valence, Valence::Negative);
+        assert!(hearth.refuse_fighting && hearth.rite.is_some());
+        assert_eq!(blood.valence, Valence::Positive);
+        assert!(!blood.refuse_fighting);
+        assert!(rules
+            .reading("hearthfolk", CelestialEvent::FullArgent)
+            .is_none());
+        assert!(rules.cultures.contains_key(&rules.default_culture));
+    }
+
+    #[test]
+    fn test_blood_eclipse_stops_fighting_until_a_rite() {
+        let mut world = World::with_seed(62);
+        world.spawn_human("Thomas".into());
+        world.humans.values[0].loyalty = 0.5;
+        blood_eclipse(&mut world);
+
+        assert!(world.humans.thoughts[0]
+            .iter()
+            .any(|t| t.concept_category == "omen" && t.valence == Valence::Negative));
+        assert!(fighting_forbidden(&world).is_some());
+        let mut attack = Task::new(ActionId::Attack, TaskPriority::Critical, 0);
+        heed_omens(&world, &mut attack);
+        assert_eq!(attack.action, ActionId::SeekSafety);
+        // Seen once, however many days the eclipse is in the sky
+        run_omens(&mut world);
+        assert_eq!(world.omens.active.len(), 1);
+
+        assert_eq!(hold_rite(&mut world), Ok(1));
+        assert_eq!(hold_rite(&mut world), Err(RiteError::NothingToAnswer));
+        assert!(fighting_forbidden(&world).is_none());
+        assert!((world.humans.values[0].loyalty - 0.5 - RITE_LOYALTY).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_inherited_faith_reads_the_sky() {
+        let mut world = World::with_seed(64);
+        world.spawn_human("Brannoc".into());
+        world.humans.values[0].piety = 0.5;
+        let faith = SettlementFaith {
+            religion: "Faith of Morek".into(),
+            patron: "Morek".into(),
+            deities: vec!["Morek".into()],
+            founding_event: CelestialEvent::BloodEclipse,
+            omen_culture: "bloodsworn".into(),
+            holy_site: true,
+        };
+        inherit_faith(&mut world, &faith);
+
+        assert_eq!(world.omens.culture, "bloodsworn");
+        assert_eq!(world.omens.faith.as_deref(), Some("Faith of Morek"));
+        assert!((world.humans.values[0].piety - 0.5 - HOLY_SITE_PIETY).abs() < 1e-6);
+        blood_eclipse(&mut world);
+        assert!(fighting_forbidden(&world).is_none());
+    }
+
+    #[test]
+    fn test_an_unheeded_omen_costs_loyalty() {
+        let mut world = World::with_seed(63);
+        world.spawn_human("Elena".into());
+        world.humans.values[0].loyalty = 0.5;
+        blood_eclipse(&mut world);
+
+        world.astronomy.active_events.clear();
+        world.astronomy.current_day += 3;
+        let report = run_omens(&mut world);
+        assert_eq!(report.unheeded, vec!["a rite of cleansing".to_string()]);
+        assert!(world.omens.active.is_empty());
+        assert!((world.humans.values[0].loyalty - 0.5 + UNHEEDED_LOYALTY).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,180 @@
+//! Orc temperament
+//!
+//! Orc values move with what happens to them, as human values move with
+//! their thoughts. Each tick rage cools (never below a simmer) and blood
+//! debts fade, per the `value_dynamics` in `species/orc.toml`. Being wounded,
+//! killing what they were attacking, and seeing a clan-mate fall nearby push
+//! values the other way, per its `value_events` - and each leaves a thought.
+//!
+//! Action selection then reads the changed values: a bloodied, debt-laden
+//! orc goes looking for revenge where a calm one would have wandered.
+
+use crate::actions::catalog::ActionCategory;
+use crate::core::types::{EntityId, Species};
+use crate::ecs::world::World;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::value_dynamics::{apply_event, apply_tick_dynamics_over};
+
+/// Fatigue gained in one tick that can only have come from a wound
+pub const WOUND_FATIGUE: f32 = 0.05;
+
+/// Distance within which an orc sees a clan-mate fall
+pub const CLAN_RANGE: f32 = 30.0;
+
+/// How the orcs stood before this tick's fighting
+#[derive(Debug, Clone, Default)]
+pub struct OrcCondition {
+    fatigue: Vec<f32>,
+    alive: Vec<bool>,
+    /// Living target of each orc's attack, if attacking
+    prey: Vec<Option<EntityId>>,
+}
+
+fn is_alive(world: &World, id: EntityId) -> bool {
+    if let Some(i) = world.humans.index_of(id) {
+        return world.humans.alive[i];
+    }
+    world
+        .orcs
+        .index_of(id)
+        .map(|i| world.orcs.alive[i])
+        .unwrap_or(false)
+}
+
+/// Note how each orc stands, to compare against after the fighting
+pub fn orc_condition(world: &World) -> OrcCondition {
+    let orcs = &world.orcs;
+    OrcCondition {
+        fatigue: orcs.body_states.iter().map(|b| b.fatigue).collect(),
+        alive: orcs.alive.clone(),
+        prey: orcs
+            .task_queues
+            .iter()
+            .map(|queue| {
+                queue
+                    .current()
+                    .filter(|task| task.action.category() == ActionCategory::Combat)
+                    .and_then(|task| task.target_entity)
+                    .filter(|&target| is_alive(world, target))
+            })
+            .collect(),
+    }
+}
+
+fn feel(world: &mut World, i: usize, event: &str, valence: Valence, description: String) {
+    apply_event(
+        &mut world.orcs.values[i],
+        event,
+        &world.species_dynamics,
+        Species::Orc,
+    );
+    let tick = world.current_tick;
+    world.orcs.thoughts[i].add(Thought::new(
+        valence,
+        0.7,
+        event,
+        description,
+        CauseType::Event,
+        tick,
+    ));
+}
+
+/// Move orc values with this tick's fighting and the passing of time
+pub fn update_orc_temperament(world: &mut World, before: &OrcCondition) {
+    let fallen: Vec<usize> = (0..before.alive.len())
+        .filter(|&i| before.alive[i] && !world.orcs.alive[i])
+        .collect();
+    let living: Vec<usize> = world.orcs.iter_living().collect();
+    let span = world.time_scale.span() as f32;
+
+    for i in living {
+        let deltas = world.species_dynamics.get_tick_deltas(Species::Orc);
+        apply_tick_dynamics_over(&mut world.orcs.values[i], deltas, span);
+
+        // Spawned this tick: nothing to compare against
+        if i >= before.alive.len() {
+            continue;
+        }
+
+        if world.orcs.body_states[i].fatigue - before.fatigue[i] >= WOUND_FATIGUE {
+            feel(world, i, "wounded", Valence::Negative, "was wounded".into());
+        }
+
+        if let Some(prey) = before.prey[i].filter(|&p| !is_alive(world, p)) {
+            let name = world
+                .humans
+                .index_of(prey)
+                .map(|h| world.humans.names[h].clone())
+                .or_else(|| {
+                    world
+                        .orcs
+                        .index_of(prey)
+                        .map(|o| world.orcs.names[o].clone())
+                })
+                .unwrap_or_default();
+            feel(
+                world,
+                i,
+                "combat_victory",
+                Valence::Positive,
+                format!("killed {}", name),
+            );
+        }
+
+        for &f in &fallen {
+            if world.orcs.positions[i].distance(&world.orcs.positions[f]) <= CLAN_RANGE {
+                let name = world.orcs.names[f].clone();
+                feel(
+                    world,
+                    i,
+                    "clan_death",
+                    Valence::Negative,
+                    format!("saw {} fall", name),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::catalog::ActionId;
+    use crate::core::types::Vec2;
+    use crate::entity::species::orc::OrcValues;
+    use crate::entity::tasks::{Task, TaskPriority};
+
+    #[test]
+    fn test_orcs_grow_vengeful_when_the_clan_bleeds() {
+        let mut world = World::with_seed(15);
+        world.spawn_orc("Grusk".into());
+        world.spawn_orc("Mog".into());
+        world.spawn_orc("Far".into());
+        let ada = world.spawn_human("Ada".into());
+        world.orcs.positions[2] = Vec2::new(100.0, 0.0);
+        world.orcs.task_queues[0]
+            .push(Task::new(ActionId::Attack, TaskPriority::High, 0).with_entity(ada));
+
+        let before = orc_condition(&world);
+        world.orcs.alive[1] = false;
+        world.orcs.body_states[0].fatigue += 0.2;
+        world.humans.alive[0] = false;
+        let calm_rage = world.orcs.values[2].rage;
+        update_orc_temperament(&mut world, &before);
+
+        let grusk = &world.orcs.values[0];
+        assert!(grusk.blood_debt > 0.2);
+        assert!(grusk.rage > calm_rage);
+        assert!(grusk.dominance > OrcValues::default().dominance);
+        assert!(world.orcs.thoughts[0]
+            .iter()
+            .any(|t| t.cause_description == "saw Mog fall"));
+        assert!(world.orcs.thoughts[0]
+            .iter()
+            .any(|t| t.cause_description == "killed Ada"));
+
+        // Too far away to see, so only the tick's cooling applies
+        assert_eq!(world.orcs.values[2].blood_debt, 0.0);
+        assert!(world.orcs.values[2].rage < calm_rage);
+    }
+}
@@ -0,0 +1,83 @@
+//! Parallel stages over archetype arrays
+//!
+//! The per-entity stages of the tick - needs decay, attention refresh,
+//! thought decay, and perception - touch nothing but the entity's own slot
+//! in each SoA array (and read the rest of the world), so they run across
+//! rayon's thread pool once a population is large enough to pay for it.
+//!
+//! Each helper returns only when every entity is done: that is the barrier
+//! between a parallel stage and the sequential mutation phase after it.
+//! Anything that reaches across entities (thoughts about someone else,
+//! social memories, tasks that target another entity, combat) stays in the
+//! sequential phase. Results do not depend on how the work was split, so a
+//! world ticks the same whether or not a stage ran in parallel.
+
+use rayon::prelude::*;
+
+/// Entities below which the parallel stages run sequentially
+///
+/// Below this, thread overhead costs more than it saves.
+pub const PARALLEL_THRESHOLD: usize = 1000;
+
+/// Update every living entity's slot of `data`
+pub fn for_each_living<T: Send>(
+    alive: &[bool],
+    data: &mut [T],
+    update: impl Fn(usize, &mut T) + Send + Sync,
+) {
+    for_each_mut(data, |i, item| {
+        if alive[i] {
+            update(i, item)
+        }
+    });
+}
+
+/// Update every slot of `data`
+pub fn for_each_mut<T: Send>(data: &mut [T], update: impl Fn(usize, &mut T) + Send + Sync) {
+    if data.len() >= PARALLEL_THRESHOLD {
+        data.par_iter_mut()
+            .enumerate()
+            .for_each(|(i, item)| update(i, item));
+    } else {
+        data.iter_mut()
+            .enumerate()
+            .for_each(|(i, item)| update(i, item));
+    }
+}
+
+/// Map every item, keeping their order
+pub fn map<T: Sync, U: Send>(items: &[T], f: impl Fn(usize, &T) -> U + Send + Sync) -> Vec<U> {
+    if items.len() >= PARALLEL_THRESHOLD {
+        items
+            .par_iter()
+            .enumerate()
+            .map(|(i, item)| f(i, item))
+            .collect()
+    } else {
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| f(i, item))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stages_split_the_same_way_at_any_size() {
+        for count in [10, PARALLEL_THRESHOLD * 3] {
+            let alive: Vec<bool> = (0..count).map(|i| i % 3 != 0).collect();
+            let mut values = vec![1u64; count];
+            for_each_living(&alive, &mut values, |i, value| *value += i as u64);
+            for (i, &value) in values.iter().enumerate() {
+                assert_eq!(value, if alive[i] { 1 + i as u64 } else { 1 });
+            }
+
+            let doubled = map(&values, |_, &value| value * 2);
+            assert!(doubled.iter().zip(&values).all(|(&d, &v)| d == v * 2));
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Place naming - settlers name the places that come to matter to them
+//!
+//! `run_places` runs daily. The settlement is named once anyone lives
+//! there, and its label follows where they live. Every zone gets a
+//! district named for it, unless the player named the zone, in which case
+//! the district takes the player's name. A fight of at least
+//! `BATTLE_SITE_BLOWS` blows, once over, names the ground it was fought on
+//! ("Redford Field"), unless another battle site lies within
+//! `BATTLE_SITE_SPACING`. Names come from `place_name`, and are never
+//! reused.
+
+use rand::Rng;
+
+use crate::ecs::world::World;
+use crate::simulation::happiness::in_settlement;
+use crate::simulation::unrest::settlement_centre;
+use crate::world::situation::FIGHT_LULL_TICKS;
+use crate::world::{Place, PlaceKind, Places, ZoneKind};
+
+/// Blows a fight must see before the ground it was fought on is named
+pub const BATTLE_SITE_BLOWS: u32 = 20;
+
+/// Nearest two battle sites may be to each other
+pub const BATTLE_SITE_SPACING: f32 = 30.0;
+
+/// Random names tried before falling back to numbering one
+pub const NAME_TRIES: usize = 20;
+
+const STEMS: [&str; 16] = [
+    "Red", "Ash", "Oak", "Elm", "Thorn", "Stone", "Brack", "Wil", "Ald", "Har", "Hollow", "Mill",
+    "Black", "Green", "Cold", "Wolf",
+];
+const ENDS: [&str; 10] = [
+    "ford", "holm", "stead", "wick", "ton", "bury", "dale", "mere", "by", "worth",
+];
+
+/// What a district is called after the zone it grew around
+fn district_word(kind: ZoneKind) -> &'static str {
+    match kind {
+        ZoneKind::Stockpile => "Yard",
+        ZoneKind::Farm => "Fields",
+        ZoneKind::Dormitory => "Row",
+        ZoneKind::NoEntry => "Reach",
+        ZoneKind::Graveyard => "Rest",
+    }
+}
+
+/// A place name not yet taken, made by `form` from a random stem and
+/// ending ("Red" and "ford" make "Redford")
+pub fn place_name<R: Rng>(
+    places: &Places,
+    rng: &mut R,
+    form: impl Fn(&str, &str) -> String,
+) -> String {
+    let mut roll = || {
+        form(
+            STEMS[rng.gen_range(0..STEMS.len())],
+            ENDS[rng.gen_range(0..ENDS.len())],
+        )
+    };
+    let mut name = roll();
+    for _ in 1..NAME_TRIES {
+        if !places.is_taken(&name) {
+            return name;
+        }
+        name = roll();
+    }
+    (2..)
+        .map(|n| format!("{} {}", name, n))
+        .find(|numbered| !places.is_taken(numbered))
+        .expect("some number is free")
+}
+
+/// Name the settlement once anyone lives there, and keep its label over
+/// where they live
+fn name_settlement<R: Rng>(world: &mut World, rng: &mut R) {
+    if !world.humans.iter_living().any(|i| in_settlement(world, i)) {
+        return;
+    }
+    let centre = settlement_centre(world);
+    if let Some(place) = world
+        .places
+        .places
+        .iter_mut()
+        .find(|p| p.kind == PlaceKind::Settlement)
+    {
+        place.position = centre;
+        return;
+    }
+    let tick = world.current_tick;
+    let name = place_name(&world.places, rng, |stem, end| format!("{}{}", stem, end));
+    world
+        .legacy
+        .record(tick, format!("The settlers call their home {}", name));
+    world.places.places.push(Place {
+        name,
+        kind: PlaceKind::Settlement,
+        position: centre,
+        named: tick,
+    });
+}
+
+/// Give every zone a district, drop the districts of zones gone, and
+/// follow any name the player gave a zone
+fn name_districts<R: Rng>(world: &mut World, rng: &mut R) {
+    let tick = world.current_tick;
+    let zones = &world.zones;
+    world.places.places.retain(|p| match p.kind {
+        PlaceKind::District(zone) => zones.get(zone).is_some(),
+        _ => true,
+    });
+    for zone in world.zones.iter() {
+        let kind = PlaceKind::District(zone.id);
+        let position = zone.shape.center();
+        if let Some(place) = world.places.places.iter_mut().find(|p| p.kind == kind) {
+            place.position = position;
+            if let Some(name) = &zone.name {
+                place.name.clone_from(name);
+            }
+            continue;
+        }
+        let name = match &zone.name {
+            Some(name) => name.clone(),
+            None => place_name(&world.places, rng, |stem, _| {
+                format!("{} {}", stem, district_word(zone.kind))
+            }),
+        };
+        world.places.places.push(Place {
+            name,
+            kind,
+            position,
+            named: tick,
+        });
+    }
+}
+
+/// Name the ground of every hard fight that is over, unless a battle site
+/// is already there
+fn name_battle_sites<R: Rng>(world: &mut World, rng: &mut R) {
+    let tick = world.current_tick;
+    let fought: Vec<_> = world
+        .situation
+        .fights
+        .iter()
+        .filter(|f| f.blows >= BATTLE_SITE_BLOWS && tick > f.last_blow + FIGHT_LULL_TICKS)
+        .filter_map(|f| f.place)
+        .collect();
+    for at in fought {
+        let named = world.places.iter().any(|p| {
+            p.kind == PlaceKind::BattleSite && p.position.distance(&at) < BATTLE_SITE_SPACING
+        });
+        if named {
+            continue;
+        }
+        let name = place_name(&world.places, rng, |stem, end| {
+            format!("{}{} Field", stem, end)
+        });
+        world
+            .legacy
+            .record(tick, format!("The ground fought over is called {}", name));
+        world.places.places.push(Place {
+            name,
+            kind: PlaceKind::BattleSite,
+            position: at,
+            named: tick,
+        });
+    }
+}
+
+/// Name the settlement, its districts, and its battle sites; run daily
+pub fn run_places<R: Rng>(world: &mut World, rng: &mut R) {
+    name_settlement(world, rng);
+    name_districts(world, rng);
+    name_battle_sites(world, rng);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Vec2;
+    use crate::world::{ZoneDesignation, ZoneShape};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_settlement_and_districts_are_named() {
+        let mut world = World::with_seed(31);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        run_places(&mut world, &mut rng);
+        assert!(world.places.settlement().is_none());
+
+        world.spawn_human("Ada".into());
+        world.humans.positions[0] = Vec2::new(40.0, 40.0);
+        let farm = world.designate_zone(ZoneDesignation {
+            kind: ZoneKind::Farm,
+            shape: ZoneShape::Hex {
+                center: Vec2::new(60.0, 40.0),
+                radius: 5.0,
+            },
+            name: None,
+        });
+        run_places(&mut world, &mut rng);
+        let home = world.places.settlement().unwrap();
+        assert_eq!((home.position.x, home.position.y), (40.0, 40.0));
+        assert!(world
+            .legacy
+            .chronicle
+            .iter()
+            .any(|entry| entry.text == format!("The settlers call their home {}", home.name)));
+        let district = world.places.district(farm).unwrap();
+        assert!(district.name.ends_with(" Fields"));
+        assert_eq!((district.position.x, district.position.y), (60.0, 40.0));
+
+        // Names stick, and a removed zone takes its district with it
+        let name = home.name.clone();
+        world.humans.positions[0] = Vec2::new(50.0, 40.0);
+        run_places(&mut world, &mut rng);
+        let home = world.places.settlement().unwrap();
+        assert_eq!(home.name, name);
+        assert_eq!((home.position.x, home.position.y), (50.0, 40.0));
+        world.remove_zone(farm);
+        run_places(&mut world, &mut rng);
+        assert!(world.places.district(farm).is_none());
+    }
+
+    #[test]
+    fn test_hard_fights_name_their_battle_site_once() {
+        let mut world = World::with_seed(32);
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        world.situation.note_blows(10, BATTLE_SITE_BLOWS);
+        world.situation.note_place(Vec2::new(120.0, 80.0));
+        world.current_tick = 20;
+        run_places(&mut world, &mut rng);
+        assert!(world.places.iter().all(|p| p.kind != PlaceKind::BattleSite));
+
+        world.current_tick = 10 + FIGHT_LULL_TICKS + 1;
+        run_places(&mut world, &mut rng);
+        run_places(&mut world, &mut rng);
+        let sites: Vec<&Place> = world
+            .places
+            .iter()
+            .filter(|p| p.kind == PlaceKind::BattleSite)
+            .collect();
+        assert_eq!(sites.len(), 1);
+        assert!(sites[0].name.ends_with(" Field"));
+        assert_eq!((sites[0].position.x, sites[0].position.y), (120.0, 80.0));
+
+        // A skirmish leaves no name behind
+        world.situation.note_blows(500, 3);
+        world.situation.note_place(Vec2::new(10.0, 10.0));
+        world.current_tick = 1000;
+        run_places(&mut world, &mut rng);
+        assert_eq!(
+            world
+                .places
+                .iter()
+                .filter(|p| p.kind == PlaceKind::BattleSite)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_place_names_are_never_reused() {
+        let mut places = Places::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        for _ in 0..STEMS.len() + 2 {
+            let name = place_name(&places, &mut rng, |stem, _| format!("{} Yard", stem));
+            assert!(!places.is_taken(&name));
+            places.places.push(Place {
+                name,
+                kind: PlaceKind::BattleSite,
+                position: Vec2::new(0.0, 0.0),
+                named: 0,
+            });
+        }
+    }
+}
@@ -1,13 +1,17 @@
 //! Population growth system
 //!
-//! Entities reproduce when housing and food are available.
+//! Entities reproduce when housing and food are available. Housemates of an
+//! age to have children become the newborn's parents (see lifecycle).
 
 use crate::city::building::BuildingState;
+use crate::core::types::EntityId;
 use crate::ecs::world::World;
+use crate::simulation::lifecycle::{bear_child, is_fertile};
 use crate::simulation::resource_zone::ResourceType;
+use rand::seq::SliceRandom;
 use rand::Rng;
 
-/// Calculate current housing surplus (available - occupied)
+/// Calculate current housing surplus (available - occupied by the living)
 pub fn housing_surplus(world: &World) -> i32 {
     let mut total_capacity: i32 = 0;
 
@@ -20,20 +24,19 @@ pub fn housing_surplus(world: &World) -> i32 {
 
     let occupied = world
         .humans
-        .assigned_houses
-        .iter()
-        .filter(|h: &&Option<crate::city::BuildingId>| h.is_some())
+        .iter_living()
+        .filter(|&i| world.humans.assigned_houses[i].is_some())
         .count() as i32;
 
     total_capacity - occupied
 }
 
 /// Try to grow population based on conditions
-/// Returns true if new entity was spawned
-pub fn try_population_growth(world: &mut World) -> bool {
+/// Returns the newborn, if one was born
+pub fn try_population_growth<R: Rng>(world: &mut World, rng: &mut R) -> Option<EntityId> {
     // Check housing surplus
     if housing_surplus(world) <= 0 {
-        return false;
+        return None;
     }
 
     // Check food surplus (need food > population * 2)
@@ -42,21 +45,49 @@ pub fn try_population_growth(world: &mut World) -> bool {
     let food_threshold = living_count * 2;
 
     if food_available <= food_threshold {
-        return false;
+        return None;
     }
 
     // 5% chance per attempt
-    let mut rng = rand::thread_rng();
     let roll: f32 = rng.gen();
     if roll >= 0.05 {
-        return false;
+        return None;
+    }
+
+    // Only a couple sharing a house has children
+    let parents = choose_parents(world, rng);
+    if parents.is_empty() {
+        return None;
     }
+    Some(bear_child(world, &parents, rng))
+}
 
-    // Spawn new human
-    let name = format!("Newborn {}", world.current_tick);
-    world.spawn_human(name);
+/// Pick two living housemates of an age to have children to be a newborn's
+/// parents (none if no house holds two such people)
+pub fn choose_parents<R: Rng>(world: &World, rng: &mut R) -> Vec<EntityId> {
+    let households: Vec<Vec<usize>> = world
+        .buildings
+        .ids
+        .iter()
+        .map(|&house| {
+            world
+                .humans
+                .iter_living()
+                .filter(|&i| world.humans.assigned_houses[i] == Some(house))
+                .filter(|&i| is_fertile(world, i))
+                .collect::<Vec<_>>()
+        })
+        .filter(|members| members.len() >= 2)
+        .collect();
+    if households.is_empty() {
+        return Vec::new();
+    }
 
-    true
+    let members = &households[rng.gen_range(0..households.len())];
+    members
+        .choose_multiple(rng, 2)
+        .map(|&i| world.humans.ids[i])
+        .collect()
 }
 
 #[cfg(test)]
@@ -90,11 +121,16 @@ mod tests {
 
         // 4 capacity, 2 occupied = 2 surplus
         assert_eq!(housing_surplus(&world), 2);
+
+        // The dead leave their place free
+        world.humans.alive[1] = false;
+        assert_eq!(housing_surplus(&world), 3);
     }
 
     #[test]
     fn test_population_growth_requires_housing() {
         let mut world = World::new();
+        let mut rng = rand::thread_rng();
 
         // Plenty of food, no housing
         world.stockpile.add(ResourceType::Food, 1000);
@@ -104,7 +140,7 @@ mod tests {
 
         // Try growth many times - should never succeed
         for _ in 0..100 {
-            try_population_growth(&mut world);
+            try_population_growth(&mut world, &mut rng);
         }
 
         assert_eq!(
@@ -117,6 +153,7 @@ mod tests {
     #[test]
     fn test_population_growth_requires_food() {
         let mut world = World::new();
+        let mut rng = rand::thread_rng();
 
         // Housing available, no food
         world.spawn_building(BuildingType::House, Vec2::new(0.0, 0.0));
@@ -127,7 +164,7 @@ mod tests {
 
         // Try growth many times - should never succeed
         for _ in 0..100 {
-            try_population_growth(&mut world);
+            try_population_growth(&mut world, &mut rng);
         }
 
         assert_eq!(
@@ -140,19 +177,25 @@ mod tests {
     #[test]
     fn test_population_growth_when_conditions_met() {
         let mut world = World::new();
+        let mut rng = rand::thread_rng();
 
-        // Setup: 1 person, plenty of housing, plenty of food
+        // Setup: a couple sharing a house, plenty of room, plenty of food
         world.spawn_building(BuildingType::House, Vec2::new(0.0, 0.0));
+        world.spawn_building(BuildingType::House, Vec2::new(10.0, 0.0));
         world.buildings.states[0] = BuildingState::Complete;
+        world.buildings.states[1] = BuildingState::Complete;
         world.stockpile.add(ResourceType::Food, 1000);
         world.spawn_human("Adam".into());
+        world.spawn_human("Eve".into());
+        world.humans.assigned_houses[0] = Some(world.buildings.ids[0]);
+        world.humans.assigned_houses[1] = Some(world.buildings.ids[0]);
 
         let initial_count = world.humans.count();
 
         // With 5% chance, trying 200 times should almost certainly succeed
         let mut grew = false;
         for _ in 0..200 {
-            if try_population_growth(&mut world) {
+            if try_population_growth(&mut world, &mut rng).is_some() {
                 grew = true;
                 break;
             }
@@ -164,4 +207,42 @@ mod tests {
             "Population should have increased"
         );
     }
+
+    #[test]
+    fn test_no_birth_without_a_couple() {
+        let mut world = World::new();
+        let mut rng = rand::thread_rng();
+
+        // Room and food aplenty, but only one settler
+        world.spawn_building(BuildingType::House, Vec2::new(0.0, 0.0));
+        world.buildings.states[0] = BuildingState::Complete;
+        world.stockpile.add(ResourceType::Food, 1000);
+        world.spawn_human("Adam".into());
+        world.humans.assigned_houses[0] = Some(world.buildings.ids[0]);
+
+        for _ in 0..200 {
+            assert_eq!(try_population_growth(&mut world, &mut rng), None);
+        }
+        assert_eq!(world.humans.count(), 1);
+    }
+
+    #[test]
+    fn test_housemates_become_parents() {
+        let mut world = World::new();
+        let mut rng = rand::thread_rng();
+        world.spawn_building(BuildingType::House, Vec2::new(0.0, 0.0));
+        let alice = world.spawn_human("Alice".into());
+        let bob = world.spawn_human("Bob".into());
+        world.spawn_human("Drifter".into());
+
+        // Nobody shares a house yet
+        assert!(choose_parents(&world, &mut rng).is_empty());
+
+        let house_id = world.buildings.ids[0];
+        world.humans.assigned_houses[0] = Some(house_id);
+        world.humans.assigned_houses[1] = Some(house_id);
+        let mut parents = choose_parents(&world, &mut rng);
+        parents.sort_by_key(|id| world.humans.index_of(*id));
+        assert_eq!(parents, vec![alice, bob]);
+    }
 }
@@ -0,0 +1,169 @@
+//! Seasonal regrowth of food and resource zones
+//!
+//! Scarce food zones and resource zones grow back a little every tick, at a
+//! rate set by the calendar: berries come back in summer but not in winter,
+//! and a long dry spell (`WeatherState::dry_days`) slows everything living.
+//! The factors are data, loaded from `data/regrowth.toml`, so scarcity
+//! follows the year and a settlement has to store up for the lean seasons.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::core::astronomy::Season;
+use crate::ecs::world::World;
+
+/// Errors from loading regrowth rules
+#[derive(Error, Debug)]
+pub enum RegrowthError {
+    /// Reading the file failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The file is not valid regrowth TOML
+    #[error("Parse error: {0}")]
+    ParseError(#[from] toml::de::Error),
+}
+
+/// Regrowth factors for one kind of zone
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeasonalRegrowth {
+    pub spring: f32,
+    pub summer: f32,
+    pub autumn: f32,
+    pub winter: f32,
+    /// Extra factor during a drought
+    #[serde(default = "no_effect")]
+    pub drought: f32,
+}
+
+fn no_effect() -> f32 {
+    1.0
+}
+
+impl SeasonalRegrowth {
+    /// Factor on the base regrowth rate in this season
+    pub fn factor(&self, season: Season, drought: bool) -> f32 {
+        let seasonal = match season {
+            Season::Spring => self.spring,
+            Season::Summer => self.summer,
+            Season::Autumn => self.autumn,
+            Season::Winter => self.winter,
+        };
+        if drought {
+            seasonal * self.drought
+        } else {
+            seasonal
+        }
+    }
+}
+
+/// Regrowth factors by resource name (food zones use "food")
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RegrowthRules {
+    /// Days in a row without rain or snow that make a drought (0 = never)
+    #[serde(default)]
+    pub drought_days: u32,
+    #[serde(default)]
+    pub resources: HashMap<String, SeasonalRegrowth>,
+}
+
+impl RegrowthRules {
+    /// No seasons: everything regrows at its base rate all year
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(content: &str) -> Result<Self, RegrowthError> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RegrowthError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Whether this many dry days make a drought
+    pub fn is_drought(&self, dry_days: u32) -> bool {
+        self.drought_days > 0 && dry_days >= self.drought_days
+    }
+
+    /// Factor on the base regrowth rate of `resource` (case-insensitive)
+    pub fn factor(&self, resource: &str, season: Season, drought: bool) -> f32 {
+        self.resources
+            .get(&resource.to_lowercase())
+            .map(|rule| rule.factor(season, drought))
+            .unwrap_or(1.0)
+    }
+}
+
+/// Load regrowth rules from `data/regrowth.toml`
+pub(crate) fn load_default_regrowth_rules() -> RegrowthRules {
+    RegrowthRules::load("data/regrowth.toml").unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load regrowth rules: {}", e);
+        RegrowthRules::new()
+    })
+}
+
+/// Regrow every food and resource zone by one step's worth for the season
+pub fn regrow_zones(world: &mut World) {
+    let season = world.astronomy.season;
+    let drought = world.regrowth.is_drought(world.weather.dry_days);
+
+    let span = world.time_scale.span() as f32;
+    let food = world.regrowth.factor("food", season, drought);
+    for zone in &mut world.food_zones {
+        zone.regenerate_by(food * span);
+    }
+    for zone in &mut world.resource_zones {
+        let name = format!("{:?}", zone.resource_type);
+        zone.regenerate_by(world.regrowth.factor(&name, season, drought) * span);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Vec2;
+    use crate::ecs::world::Abundance;
+
+    #[test]
+    fn test_berries_regrow_in_summer_not_winter() {
+        let rules = RegrowthRules::load("data/regrowth.toml").unwrap();
+        assert_eq!(rules.factor("food", Season::Summer, false), 1.0);
+        assert_eq!(rules.factor("food", Season::Winter, false), 0.0);
+        assert_eq!(rules.factor("Food", Season::Summer, true), 0.5);
+        // Unlisted resources ignore the calendar
+        assert_eq!(rules.factor("stone", Season::Winter, true), 1.0);
+        assert!(!rules.is_drought(rules.drought_days - 1));
+        assert!(rules.is_drought(rules.drought_days));
+
+        let mut world = World::with_seed(3);
+        world.regrowth = rules;
+        world.add_food_zone(
+            Vec2::new(0.0, 0.0),
+            5.0,
+            Abundance::Scarce {
+                current: 0.0,
+                max: 10.0,
+                regen: 1.0,
+            },
+        );
+        let stock = |world: &World| match world.food_zones[0].abundance {
+            Abundance::Scarce { current, .. } => current,
+            Abundance::Unlimited => f32::INFINITY,
+        };
+
+        world.astronomy.season = Season::Winter;
+        regrow_zones(&mut world);
+        assert_eq!(stock(&world), 0.0);
+
+        world.astronomy.season = Season::Summer;
+        regrow_zones(&mut world);
+        assert_eq!(stock(&world), 1.0);
+
+        world.weather.dry_days = world.regrowth.drought_days;
+        regrow_zones(&mut world);
+        assert_eq!(stock(&world), 1.5);
+    }
+}
@@ -0,0 +1,311 @@
+//! Requests - settlers asking the player for things, and how it goes
+//!
+//! Once a day `run_requests` first settles the open requests: granted once
+//! the wish is met (the building stands, the asker has a house, the captive
+//! is free), lapsed at the deadline, withdrawn if the asker has died or left
+//! or the captive has died. A granted request gives the asker a happy
+//! thought and more loyalty; a lapsed one a bitter thought and less. For
+//! `REQUEST_MEMORY_DAYS` afterwards it also moves their opinion in the daily
+//! poll (`request_opinion`).
+//!
+//! Then at most one settler makes a new request, the most pressing one
+//! going: someone close to them held captive, having no home, a craft they
+//! aspire to master with no workshop to practise in, or a pious heart with
+//! no shrine. Nobody has two requests open, or asks again within
+//! `REQUEST_COOLDOWN_DAYS` of the last being answered.
+
+use crate::city::building::BuildingType;
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::aspirations::AspirationKind;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::happiness::in_settlement;
+use crate::simulation::legacy::closeness;
+use crate::simulation::tick::TICKS_PER_DAY;
+use crate::world::{Fate, Request, RequestId, RequestStatus, Wish};
+
+/// Days the player has to grant a request
+pub const REQUEST_DAYS: u64 = 10;
+
+/// Days after an answer before the same settler asks again
+pub const REQUEST_COOLDOWN_DAYS: u64 = 5;
+
+/// Days an answered request weighs on the asker's opinion
+pub const REQUEST_MEMORY_DAYS: u64 = 14;
+
+/// Opinion gained (or lost) for each request granted (or lapsed) lately
+pub const REQUEST_WEIGHT: f32 = 0.08;
+
+/// Most opinion that answered requests can move
+pub const REQUEST_CAP: f32 = 0.16;
+
+/// Loyalty gained when a request is granted, and lost when it lapses
+pub const LOYALTY_SHIFT: f32 = 0.05;
+
+/// Closeness to a captive that moves a settler to ask for them
+pub const CAPTIVE_CLOSENESS: f32 = 0.5;
+
+/// Piety that makes a settler want a shrine
+pub const PIOUS: f32 = 0.7;
+
+/// What happened to requests today
+#[derive(Debug, Default)]
+pub struct RequestReport {
+    pub made: Vec<RequestId>,
+    pub granted: Vec<RequestId>,
+    pub lapsed: Vec<RequestId>,
+}
+
+/// What is being asked for, e.g. "a shrine to pray at"
+pub fn describe_wish(world: &World, wish: Wish) -> String {
+    match wish {
+        Wish::Building(BuildingType::Workshop) => "a workshop to practise a craft in".to_string(),
+        Wish::Building(BuildingType::Shrine) => "a shrine to pray at".to_string(),
+        Wish::Building(kind) => format!("a {:?}", kind).to_lowercase(),
+        Wish::Home => "a home of their own".to_string(),
+        Wish::Freed { captive } => {
+            let name = world
+                .identities
+                .get(captive)
+                .map(|identity| identity.known_as())
+                .unwrap_or_else(|| "a captive".to_string());
+            format!("{} to be freed", name)
+        }
+    }
+}
+
+/// The request as the player reads it, e.g. "Thomas asks for a shrine to pray at"
+pub fn describe(world: &World, request: &Request) -> String {
+    let asker = world
+        .identities
+        .get(request.asker)
+        .map(|identity| identity.known_as())
+        .unwrap_or_default();
+    format!("{} asks for {}", asker, describe_wish(world, request.wish))
+}
+
+fn has_building(world: &World, kind: BuildingType) -> bool {
+    world
+        .buildings
+        .iter_complete()
+        .any(|b| world.buildings.building_types[b] == kind)
+}
+
+/// Whether a wish has come true, or can no longer (None while it may yet)
+fn settled(world: &World, asker: usize, wish: Wish) -> Option<RequestStatus> {
+    let met = match wish {
+        Wish::Building(kind) => has_building(world, kind),
+        Wish::Home => world.humans.assigned_houses[asker].is_some(),
+        Wish::Freed { captive } => match world.identities.get(captive).map(|c| &c.fate) {
+            Some(Fate::Captured { .. }) => false,
+            Some(Fate::Active) => true,
+            _ => return Some(RequestStatus::Withdrawn),
+        },
+    };
+    met.then_some(RequestStatus::Granted)
+}
+
+/// The most pressing thing human `i` would ask for, if anything
+fn wish_of(world: &World, i: usize, captives: &[EntityId]) -> Option<Wish> {
+    if let Some(&captive) = captives
+        .iter()
+        .find(|&&c| closeness(world, i, c) >= CAPTIVE_CLOSENESS)
+    {
+        return Some(Wish::Freed { captive });
+    }
+    if world.humans.assigned_houses[i].is_none() {
+        return Some(Wish::Home);
+    }
+    let aspires_to_craft = world.humans.aspirations[i]
+        .as_ref()
+        .and_then(|a| a.pursued())
+        .is_some_and(|kind| *kind == AspirationKind::MasterCraft);
+    if aspires_to_craft && !has_building(world, BuildingType::Workshop) {
+        return Some(Wish::Building(BuildingType::Workshop));
+    }
+    if world.humans.values[i].piety >= PIOUS && !has_building(world, BuildingType::Shrine) {
+        return Some(Wish::Building(BuildingType::Shrine));
+    }
+    None
+}
+
+/// Whether human `i` is free to make a new request
+fn may_ask(world: &World, i: usize) -> bool {
+    let since = world
+        .current_tick
+        .saturating_sub(REQUEST_COOLDOWN_DAYS * TICKS_PER_DAY);
+    world
+        .requests
+        .by(world.humans.ids[i])
+        .all(|r| !r.is_open() && r.closed_tick.is_some_and(|tick| tick < since))
+}
+
+/// Human `i`'s opinion moved by how their recent requests were answered
+pub fn request_opinion(world: &World, i: usize) -> f32 {
+    let since = world
+        .current_tick
+        .saturating_sub(REQUEST_MEMORY_DAYS * TICKS_PER_DAY);
+    let total: f32 = world
+        .requests
+        .by(world.humans.ids[i])
+        .filter(|r| r.closed_tick.is_some_and(|tick| tick >= since))
+        .map(|r| match r.status {
+            RequestStatus::Granted => REQUEST_WEIGHT,
+            RequestStatus::Lapsed => -REQUEST_WEIGHT,
+            _ => 0.0,
+        })
+        .sum();
+    total.clamp(-REQUEST_CAP, REQUEST_CAP)
+}
+
+/// Close a request; if it was granted or lapsed, the asker's thought and
+/// loyalty follow
+fn answer(world: &mut World, i: usize, request: &Request, status: RequestStatus) {
+    let tick = world.current_tick;
+    world.requests.close(request.id, status, tick);
+    let wish = describe_wish(world, request.wish);
+    let (valence, intensity, cause, shift) = match status {
+        RequestStatus::Granted => (
+            Valence::Positive,
+            0.6,
+            format!("was granted {}", wish),
+            LOYALTY_SHIFT,
+        ),
+        RequestStatus::Lapsed => (
+            Valence::Negative,
+            0.5,
+            format!("asked in vain for {}", wish),
+            -LOYALTY_SHIFT,
+        ),
+        _ => return,
+    };
+    world.humans.thoughts[i].add(Thought::new(
+        valence,
+        intensity,
+        "request",
+        cause,
+        CauseType::Event,
+        tick,
+    ));
+    let loyalty = &mut world.humans.values[i].loyalty;
+    *loyalty = (*loyalty + shift).clamp(0.0, 1.0);
+}
+
+/// Settle today's requests and take at most one new one
+pub fn run_requests(world: &mut World) -> RequestReport {
+    let mut report = RequestReport::default();
+    let tick = world.current_tick;
+
+    let open: Vec<Request> = world.requests.open().cloned().collect();
+    for request in open {
+        let asker = world
+            .humans
+            .index_of(request.asker)
+            .filter(|&i| world.humans.alive[i] && in_settlement(world, i));
+        let Some(i) = asker else {
+            world
+                .requests
+                .close(request.id, RequestStatus::Withdrawn, tick);
+            continue;
+        };
+        let status = settled(world, i, request.wish)
+            .or_else(|| (tick >= request.deadline_tick).then_some(RequestStatus::Lapsed));
+        match status {
+            Some(RequestStatus::Granted) => report.granted.push(request.id),
+            Some(RequestStatus::Lapsed) => report.lapsed.push(request.id),
+            _ => {}
+        }
+        if let Some(status) = status {
+            answer(world, i, &request, status);
+        }
+    }
+
+    let captives: Vec<EntityId> = world
+        .humans
+        .ids
+        .iter()
+        .copied()
+        .filter(|&id| {
+            world
+                .identities
+                .get(id)
+                .is_some_and(|identity| matches!(identity.fate, Fate::Captured { .. }))
+        })
+        .collect();
+    let asking = world
+        .humans
+        .iter_living()
+        .filter(|&i| in_settlement(world, i) && may_ask(world, i))
+        .filter_map(|i| wish_of(world, i, &captives).map(|wish| (i, wish)))
+        .min_by_key(|(_, wish)| match wish {
+            Wish::Freed { .. } => 0,
+            Wish::Home => 1,
+            Wish::Building(_) => 2,
+        });
+    if let Some((i, wish)) = asking {
+        let deadline = tick + REQUEST_DAYS * TICKS_PER_DAY;
+        report.made.push(
+            world
+                .requests
+                .make(world.humans.ids[i], wish, tick, deadline),
+        );
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::building::BuildingState;
+    use crate::core::types::Vec2;
+
+    #[test]
+    fn test_a_granted_request_raises_loyalty_and_opinion() {
+        let mut world = World::with_seed(59);
+        world.spawn_human("Thomas".into());
+        world.humans.values[0].piety = 0.9;
+        world.humans.values[0].loyalty = 0.5;
+        let house = world.spawn_building(BuildingType::House, Vec2::new(0.0, 0.0));
+        world.buildings.states[0] = BuildingState::Complete;
+        world.humans.assigned_houses[0] = Some(house);
+
+        let report = run_requests(&mut world);
+        let id = report.made[0];
+        let request = world.requests.get(id).unwrap().clone();
+        assert_eq!(request.wish, Wish::Building(BuildingType::Shrine));
+        assert_eq!(
+            describe(&world, &request),
+            "Thomas asks for a shrine to pray at"
+        );
+        // One open request at a time
+        assert!(run_requests(&mut world).made.is_empty());
+
+        world.spawn_building(BuildingType::Shrine, Vec2::new(5.0, 0.0));
+        world.buildings.states[1] = BuildingState::Complete;
+        world.current_tick += TICKS_PER_DAY;
+        let report = run_requests(&mut world);
+        assert_eq!(report.granted, vec![id]);
+        assert!((world.humans.values[0].loyalty - 0.55).abs() < 1e-6);
+        assert!((request_opinion(&world, 0) - REQUEST_WEIGHT).abs() < 1e-6);
+        assert!(world.humans.thoughts[0]
+            .iter()
+            .any(|t| t.cause_description == "was granted a shrine to pray at"));
+    }
+
+    #[test]
+    fn test_an_unanswered_request_lapses() {
+        let mut world = World::with_seed(60);
+        world.spawn_human("Elena".into());
+        world.humans.values[0].loyalty = 0.5;
+
+        let id = run_requests(&mut world).made[0];
+        assert_eq!(world.requests.get(id).unwrap().wish, Wish::Home);
+        world.current_tick += REQUEST_DAYS * TICKS_PER_DAY;
+        let report = run_requests(&mut world);
+        assert_eq!(report.lapsed, vec![id]);
+        assert!((world.humans.values[0].loyalty - 0.45).abs() < 1e-6);
+        assert!(request_opinion(&world, 0) < 0.0);
+        // Not asking again straight away
+        assert!(report.made.is_empty());
+    }
+}
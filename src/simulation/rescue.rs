@@ -0,0 +1,316 @@
+//! Carrying the wounded, the sick, and the dead
+//!
+//! Someone who cannot act - collapsed, in too much pain, or dead - can be
+//! picked up and carried: the living home to their house (or to a dormitory
+//! zone) to be cared for, the dead to the nearest graveyard zone for burial.
+//! Nobody is ordered to do it. Each tick, those who love the fallen (kin and
+//! friends, weighed by how much they value love) or feel loyal to a fellow
+//! settler drop what they are doing and come for them. A load slows the
+//! carrier by its weight; a grown body is dragged rather than carried.
+
+use crate::actions::catalog::ActionId;
+use crate::core::types::{EntityId, Vec2};
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::simulation::legacy::closeness;
+use crate::simulation::lifecycle::{age_in_years, ADULT_AGE};
+use crate::simulation::weather_effects::movement_multiplier;
+use crate::world::ZoneKind;
+
+/// How far away a rescuer will come from
+pub const RESCUE_RANGE: f32 = 30.0;
+
+/// Distance at which a body can be picked up
+pub const CARRY_REACH: f32 = 1.5;
+
+/// Distance from the destination at which a body is set down
+pub const DELIVERY_RANGE: f32 = 1.5;
+
+/// Drive needed to go to someone's rescue
+pub const RESCUE_DRIVE: f32 = 0.25;
+
+/// Weight of loyalty toward a fellow settler who is no kin or friend
+pub const COMRADE_DRIVE: f32 = 0.3;
+
+/// Walking speed when unladen
+pub const CARRY_BASE_SPEED: f32 = 2.0;
+
+/// Fraction of speed lost carrying a full-grown body
+pub const CARRY_WEIGHT_PENALTY: f32 = 0.6;
+
+/// Weight of human `j`'s body relative to a grown adult
+pub fn body_weight(world: &World, j: usize) -> f32 {
+    let years = age_in_years(world.humans.ages[j]) as f32;
+    (years / ADULT_AGE as f32).clamp(0.2, 1.0)
+}
+
+/// Walking speed while carrying a body of this weight
+pub fn carry_speed(weight: f32) -> f32 {
+    CARRY_BASE_SPEED * (1.0 - CARRY_WEIGHT_PENALTY * weight)
+}
+
+/// Whether human `j` can no longer get about on their own (exiles are
+/// off the map, not helpless)
+pub fn is_helpless(world: &World, j: usize) -> bool {
+    if world.exiles.is_away(world.humans.ids[j]) {
+        return false;
+    }
+    !world.humans.alive[j] || !world.humans.body_states[j].can_act()
+}
+
+/// Where human `j` should be carried, if anywhere
+///
+/// The dead go to the nearest graveyard, the living to their house or the
+/// nearest dormitory. None if there is nowhere to take them or they are
+/// already there.
+pub fn destination(world: &World, j: usize) -> Option<Vec2> {
+    let pos = world.humans.positions[j];
+    let target = if !world.humans.alive[j] {
+        if world.zones.contains(ZoneKind::Graveyard, pos) {
+            return None;
+        }
+        world
+            .zones
+            .nearest(ZoneKind::Graveyard, pos)?
+            .shape
+            .center()
+    } else if let Some(house) =
+        world.humans.assigned_houses[j].and_then(|house| world.buildings.index_of(house))
+    {
+        world.buildings.positions[house]
+    } else {
+        if world.zones.contains(ZoneKind::Dormitory, pos) {
+            return None;
+        }
+        world
+            .zones
+            .nearest(ZoneKind::Dormitory, pos)?
+            .shape
+            .center()
+    };
+    (pos.distance(&target) > DELIVERY_RANGE).then_some(target)
+}
+
+/// How strongly human `i` is moved to carry human `j`
+///
+/// Love for kin (either way) and friends, or loyalty to a fellow member of
+/// an allied faction.
+pub fn rescue_drive(world: &World, i: usize, j: usize) -> f32 {
+    let (rescuer, fallen) = (world.humans.ids[i], world.humans.ids[j]);
+    let parent = world
+        .legacy
+        .generations_between(fallen, rescuer)
+        .map(|g| 0.5f32.powi(g as i32 - 1))
+        .unwrap_or(0.0);
+    let love = closeness(world, i, fallen).max(parent) * world.humans.values[i].love;
+    let loyalty = if world.factions.are_allied(rescuer, fallen) {
+        world.humans.values[i].loyalty * COMRADE_DRIVE
+    } else {
+        0.0
+    };
+    love.max(loyalty)
+}
+
+/// The human carrying, or on the way to carry, this entity
+pub fn carrier_of(world: &World, carried: EntityId) -> Option<usize> {
+    world.humans.iter_living().find(|&i| {
+        world.humans.task_queues[i]
+            .current()
+            .is_some_and(|t| t.action == ActionId::Carry && t.target_entity == Some(carried))
+    })
+}
+
+/// Whether human `i` is free to drop what they are doing for a rescue
+fn can_answer(world: &World, i: usize) -> bool {
+    world.humans.body_states[i].can_act()
+        && world.humans.task_queues[i].current().is_none_or(|t| {
+            t.action.is_interruptible()
+                && t.action != ActionId::Carry
+                && t.priority != TaskPriority::Critical
+        })
+}
+
+/// Send the most devoted free human within range to each helpless body
+/// that has somewhere to go and nobody coming for it
+///
+/// Returns the number of rescues begun.
+pub fn run_rescue(world: &mut World) -> usize {
+    let mut begun = 0;
+    for j in 0..world.humans.ids.len() {
+        if !is_helpless(world, j) {
+            continue;
+        }
+        let Some(target) = destination(world, j) else {
+            continue;
+        };
+        if carrier_of(world, world.humans.ids[j]).is_some() {
+            continue;
+        }
+        let pos = world.humans.positions[j];
+        let rescuer = world
+            .humans
+            .iter_living()
+            .filter(|&i| i != j && can_answer(world, i))
+            .filter(|&i| world.humans.positions[i].distance(&pos) <= RESCUE_RANGE)
+            .map(|i| (i, rescue_drive(world, i, j)))
+            .filter(|&(_, drive)| drive >= RESCUE_DRIVE)
+            .max_by(|(a, drive_a), (b, drive_b)| {
+                drive_a.total_cmp(drive_b).then_with(|| {
+                    // Nearer first
+                    let (pa, pb) = (world.humans.positions[*a], world.humans.positions[*b]);
+                    pb.distance(&pos).total_cmp(&pa.distance(&pos))
+                })
+            })
+            .map(|(i, _)| i);
+        if let Some(i) = rescuer {
+            let task = Task::new(ActionId::Carry, TaskPriority::High, world.current_tick)
+                .with_entity(world.humans.ids[j])
+                .with_position(target);
+            world.humans.task_queues[i].clear();
+            world.humans.task_queues[i].push(task);
+            begun += 1;
+        }
+    }
+    begun
+}
+
+/// One tick of human `i` carrying: walk to the body, pick it up (progress
+/// 0.5 marks it in hand), and bring it to the task's destination
+///
+/// Returns true when the task is done, or there is no longer anyone to carry.
+pub fn carry_step(world: &mut World, i: usize, task: &mut Task) -> bool {
+    let (Some(carried), Some(target)) = (task.target_entity, task.target_position) else {
+        return true;
+    };
+    let Some(j) = world.humans.index_of(carried) else {
+        return true;
+    };
+    if !is_helpless(world, j) {
+        return true; // Back on their feet
+    }
+
+    let current = world.humans.positions[i];
+    let weather = movement_multiplier(world.weather.current_weather);
+    let span = world.time_scale.span() as f32;
+    if task.progress < 0.5 {
+        let body = world.humans.positions[j];
+        if current.distance(&body) > CARRY_REACH {
+            world.humans.positions[i] = world.paths.step_toward(
+                &world.blocked_cells,
+                current,
+                body,
+                CARRY_BASE_SPEED * weather * span,
+            );
+            return false;
+        }
+        task.progress = 0.5;
+        world.humans.task_queues[j].clear();
+    }
+
+    let speed = carry_speed(body_weight(world, j)) * weather * span;
+    world.humans.positions[i] =
+        world
+            .paths
+            .step_toward(&world.blocked_cells, current, target, speed);
+    world.humans.positions[j] = world.humans.positions[i];
+    if world.humans.positions[i].distance(&target) > DELIVERY_RANGE {
+        return false;
+    }
+
+    // Set down; the dead are buried where they are laid, and their
+    // memorial stands over the grave
+    if !world.humans.alive[j] {
+        let grave = world.humans.positions[j];
+        if let Some(memorial) = world.legacy.memorial_of_mut(carried) {
+            memorial.position = grave;
+        }
+    }
+    true
+}
+
+/// Keep every carried body with its carrier
+///
+/// Run after task execution, so a body carried by someone acted on earlier
+/// in the tick still ends up where its carrier is.
+pub fn hold_carried(world: &mut World) {
+    let living: Vec<usize> = world.humans.iter_living().collect();
+    for i in living {
+        let carried = world.humans.task_queues[i]
+            .current()
+            .filter(|t| t.action == ActionId::Carry && t.progress >= 0.5)
+            .and_then(|t| t.target_entity)
+            .and_then(|id| world.humans.index_of(id));
+        if let Some(j) = carried {
+            world.humans.positions[j] = world.humans.positions[i];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::tick::run_simulation_tick;
+    use crate::world::{ZoneDesignation, ZoneShape};
+
+    fn graveyard(world: &mut World, center: Vec2) {
+        world.designate_zone(ZoneDesignation {
+            kind: ZoneKind::Graveyard,
+            shape: ZoneShape::Hex {
+                center,
+                radius: 2.0,
+            },
+            name: None,
+        });
+    }
+
+    #[test]
+    fn test_loved_ones_carry_the_dead_to_the_graveyard() {
+        let mut world = World::with_seed(8);
+        let fallen = world.spawn_human("Ada".into());
+        world.spawn_human("Bram".into());
+        world.spawn_human("Cora".into());
+        world.humans.positions[0] = Vec2::new(0.0, 0.0);
+        world.humans.positions[1] = Vec2::new(5.0, 0.0);
+        world.humans.positions[2] = Vec2::new(3.0, 0.0);
+        graveyard(&mut world, Vec2::new(20.0, 0.0));
+        world.humans.alive[0] = false;
+        // Bram loved Ada; Cora, nearer, hardly knew them
+        world.humans.values[1].love = 1.0;
+        world.humans.values[2].love = 0.0;
+        world.humans.values[2].loyalty = 0.0;
+        world.humans.social_memories[1].record_encounter(
+            fallen,
+            crate::entity::social::EventType::AidReceived,
+            1.0,
+            0,
+        );
+
+        assert_eq!(run_rescue(&mut world), 1);
+        assert_eq!(carrier_of(&world, fallen), Some(1));
+        // Nobody else is sent for a body already being fetched
+        assert_eq!(run_rescue(&mut world), 0);
+
+        for _ in 0..200 {
+            run_simulation_tick(&mut world);
+            if carrier_of(&world, fallen).is_none() {
+                break;
+            }
+        }
+        assert!(world
+            .zones
+            .contains(ZoneKind::Graveyard, world.humans.positions[0]));
+        assert!(destination(&world, 0).is_none());
+    }
+
+    #[test]
+    fn test_carrying_a_grown_body_is_slower() {
+        let mut world = World::with_seed(9);
+        world.spawn_human("Ada".into());
+        world.humans.ages[0] = 0;
+        let child = body_weight(&world, 0);
+        world.humans.ages[0] = 30 * crate::core::astronomy::YEAR_LENGTH as u32;
+        let adult = body_weight(&world, 0);
+        assert!(carry_speed(adult) < carry_speed(child));
+        assert!(carry_speed(adult) < CARRY_BASE_SPEED);
+    }
+}
@@ -7,7 +7,7 @@ use crate::core::types::Vec2;
 use serde::{Deserialize, Serialize};
 
 /// Type of resource available in a zone
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ResourceType {
     Wood,
     Stone,
@@ -15,13 +15,64 @@ pub enum ResourceType {
     Iron,  // Processed from Ore
     Cloth, // From workshops
     Food,  // Explicit food resource
+    // Livestock products
+    Milk,
+    Eggs,
+    Meat,
+    Wool,
+    Leather,
+    Fodder, // Winter feed for livestock
 }
 
 impl ResourceType {
+    /// Every resource type
+    pub const ALL: [ResourceType; 12] = [
+        ResourceType::Wood,
+        ResourceType::Stone,
+        ResourceType::Ore,
+        ResourceType::Iron,
+        ResourceType::Cloth,
+        ResourceType::Food,
+        ResourceType::Milk,
+        ResourceType::Eggs,
+        ResourceType::Meat,
+        ResourceType::Wool,
+        ResourceType::Leather,
+        ResourceType::Fodder,
+    ];
+
     /// Whether this resource requires processing (can't be gathered directly)
     pub fn requires_processing(&self) -> bool {
         matches!(self, ResourceType::Iron | ResourceType::Cloth)
     }
+
+    /// Parse a resource from its data-file name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let resource = match name.to_lowercase().as_str() {
+            "wood" => ResourceType::Wood,
+            "stone" => ResourceType::Stone,
+            "ore" => ResourceType::Ore,
+            "iron" => ResourceType::Iron,
+            "cloth" => ResourceType::Cloth,
+            "food" => ResourceType::Food,
+            "milk" => ResourceType::Milk,
+            "eggs" => ResourceType::Eggs,
+            "meat" => ResourceType::Meat,
+            "wool" => ResourceType::Wool,
+            "leather" => ResourceType::Leather,
+            "fodder" => ResourceType::Fodder,
+            _ => return None,
+        };
+        Some(resource)
+    }
+
+    /// Whether entities can eat this resource when the stockpile runs low on Food
+    pub fn is_edible(&self) -> bool {
+        matches!(
+            self,
+            ResourceType::Food | ResourceType::Milk | ResourceType::Eggs | ResourceType::Meat
+        )
+    }
 }
 
 /// A zone where entities can gather resources
@@ -62,7 +113,12 @@ impl ResourceZone {
 
     /// Regenerate resources over time
     pub fn regenerate(&mut self) {
-        self.current = (self.current + self.regen_rate).min(self.max);
+        self.regenerate_by(1.0);
+    }
+
+    /// Regenerate at `factor` times the zone's own rate (seasons, drought)
+    pub fn regenerate_by(&mut self, factor: f32) {
+        self.current = (self.current + self.regen_rate * factor).min(self.max);
     }
 }
 
@@ -0,0 +1,129 @@
+//! Road upkeep and construction system
+//!
+//! Fades worn paths once a day and sends idle humans to build planned road
+//! cells. Road work is a Build task aimed at the cell's position; the task
+//! executor recognises the planned cell and applies construction work to it.
+
+use crate::actions::catalog::ActionId;
+use crate::city::road::RoadNetwork;
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority};
+use crate::simulation::sites::nearest_idle_worker;
+
+/// Run one day of road upkeep: fade unused paths, assign construction
+pub fn run_roads(world: &mut World) -> usize {
+    world.roads.decay_daily();
+    assign_road_tasks(world)
+}
+
+/// Send idle humans to planned road cells
+///
+/// Each cell gets at most one builder at a time. Returns number of tasks assigned.
+pub fn assign_road_tasks(world: &mut World) -> usize {
+    let mut assigned = 0;
+
+    let mut sites: Vec<(i32, i32)> = world.roads.sites.keys().copied().collect();
+    sites.sort_unstable();
+
+    for cell in sites {
+        let site_pos = RoadNetwork::cell_center(cell);
+        let already_worked = world.humans.iter_living().any(|i| {
+            world.humans.task_queues[i]
+                .current()
+                .map(|t| {
+                    t.action == ActionId::Build
+                        && t.target_building.is_none()
+                        && t.target_position
+                            .map(|pos| RoadNetwork::cell_of(pos) == cell)
+                            .unwrap_or(false)
+                })
+                .unwrap_or(false)
+        });
+        if already_worked {
+            continue;
+        }
+
+        let Some(i) = nearest_idle_worker(world, site_pos) else {
+            break; // Nobody left to assign
+        };
+        world.humans.task_queues[i].clear();
+        let task = Task::new(ActionId::Build, TaskPriority::Normal, world.current_tick)
+            .with_position(site_pos);
+        world.humans.task_queues[i].push(task);
+        assigned += 1;
+    }
+
+    assigned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::road::RoadGrade;
+    use crate::core::types::Vec2;
+
+    #[test]
+    fn test_assign_road_tasks_one_builder_per_cell() {
+        let mut world = World::new();
+        world.spawn_human("Builder A".into());
+        world.spawn_human("Builder B".into());
+        world.spawn_human("Builder C".into());
+
+        world.roads.plan_road(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            RoadGrade::DirtRoad,
+            &mut world.stockpile,
+        );
+        assert_eq!(world.roads.sites.len(), 2);
+
+        assert_eq!(assign_road_tasks(&mut world), 2);
+        let builders = world
+            .humans
+            .iter_living()
+            .filter(|&i| {
+                world.humans.task_queues[i]
+                    .current()
+                    .map(|t| t.action == ActionId::Build)
+                    .unwrap_or(false)
+            })
+            .count();
+        assert_eq!(builders, 2);
+
+        // Cells already being worked are not double-booked
+        assert_eq!(assign_road_tasks(&mut world), 0);
+    }
+
+    #[test]
+    fn test_overworked_humans_are_not_assigned() {
+        let mut world = World::new();
+        world.spawn_human("Worn Out".into());
+        world.humans.body_states[0].exhaustion = 0.8;
+
+        world.roads.plan_road(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            RoadGrade::Trail,
+            &mut world.stockpile,
+        );
+        assert_eq!(assign_road_tasks(&mut world), 0);
+
+        world.humans.body_states[0].exhaustion = 0.2;
+        assert_eq!(assign_road_tasks(&mut world), 1);
+    }
+
+    #[test]
+    fn test_run_roads_fades_paths() {
+        let mut world = World::new();
+        let pos = Vec2::new(3.0, 3.0);
+        for _ in 0..60 {
+            world.roads.record_traffic(pos);
+        }
+        assert_eq!(world.roads.grade_at(pos), Some(RoadGrade::Trail));
+
+        for _ in 0..30 {
+            run_roads(&mut world);
+        }
+        assert_eq!(world.roads.grade_at(pos), None);
+    }
+}
@@ -0,0 +1,417 @@
+//! System scheduler - per-system tick frequencies and timing
+//!
+//! Every system in `run_simulation_tick` runs under the world's `Scheduler`,
+//! which times it. The expensive social systems (expectation formation,
+//! violation detection, and consolidating thoughts into social memories) can
+//! also be slowed down, in one of two ways:
+//!
+//! - `Frequency::Every(n)`: the whole system runs once every `n` ticks
+//! - `Frequency::Amortized(n)`: the system runs every tick, but only over a
+//!   rotating `1/n` share of the population, so everyone is covered once
+//!   every `n` ticks and no single tick pays for all of them
+//!
+//! Under a compressed time scale these count steps, not ticks: the caller
+//! passes `current_tick / span`, so a share is never skipped over.
+//!
+//! Everything runs every tick by default. `Scheduler::large_settlement`
+//! spreads the social systems out for populations in the thousands, where
+//! running them for everyone every tick dominates the tick.
+//!
+//! The scheduler can also be given a tick budget. Each tick that runs over
+//! it lowers the `Fidelity` of the low-priority work one step; after
+//! `RECOVERY_TICKS` ticks comfortably inside it, fidelity comes back one step.
+//! A fast-forward through a crowded settlement slows its detail, not its pace.
+//! Fidelity follows the wall clock, so a recorded session logs the fidelity
+//! each tick ran at and a replay runs each tick at the fidelity logged.
+
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Each system timed by the scheduler
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemId {
+    /// Lights, needs, and attention
+    Needs,
+    Perception,
+    Thoughts,
+    /// Forming expectations from observed actions
+    Expectations,
+    /// Detecting violated expectations
+    Violations,
+    /// Turning intense thoughts into social memories
+    MemoryConsolidation,
+    ThoughtDecay,
+    ActionSelection,
+    TaskExecution,
+    /// Everything that runs once a day
+    Daily,
+}
+
+impl SystemId {
+    /// Every system, in tick order
+    pub const ALL: [SystemId; 10] = [
+        SystemId::Needs,
+        SystemId::Perception,
+        SystemId::Thoughts,
+        SystemId::Expectations,
+        SystemId::Violations,
+        SystemId::MemoryConsolidation,
+        SystemId::ThoughtDecay,
+        SystemId::ActionSelection,
+        SystemId::TaskExecution,
+        SystemId::Daily,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SystemId::Needs => "needs",
+            SystemId::Perception => "perception",
+            SystemId::Thoughts => "thoughts",
+            SystemId::Expectations => "expectations",
+            SystemId::Violations => "violations",
+            SystemId::MemoryConsolidation => "memory consolidation",
+            SystemId::ThoughtDecay => "thought decay",
+            SystemId::ActionSelection => "action selection",
+            SystemId::TaskExecution => "task execution",
+            SystemId::Daily => "daily systems",
+        }
+    }
+
+    /// Whether the system may run less often than every tick
+    ///
+    /// The rest drive everyone's moment-to-moment behavior (or already keep
+    /// their own daily cadence) and always run every tick.
+    pub fn is_schedulable(&self) -> bool {
+        matches!(
+            self,
+            SystemId::Expectations | SystemId::Violations | SystemId::MemoryConsolidation
+        )
+    }
+}
+
+/// How often a system runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Frequency {
+    #[default]
+    EveryTick,
+    /// Once every `n` ticks, for everyone
+    Every(u64),
+    /// Every tick, for a rotating `1/n` share of entities
+    Amortized(u64),
+}
+
+/// The share of entities a system covers this tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Share {
+    part: u64,
+    of: u64,
+}
+
+impl Share {
+    /// Everyone
+    pub const ALL: Share = Share { part: 0, of: 1 };
+
+    /// Whether the entity at `index` is in this share
+    pub fn includes(&self, index: usize) -> bool {
+        index as u64 % self.of == self.part
+    }
+}
+
+/// Why a frequency could not be set
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleError {
+    #[error("The {} system must run every tick", .0.name())]
+    EveryTickOnly(SystemId),
+    #[error("A system cannot run every 0 ticks")]
+    ZeroInterval,
+}
+
+/// How long a system has taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemTiming {
+    pub runs: u64,
+    pub total: Duration,
+    pub last: Duration,
+    pub max: Duration,
+}
+
+impl SystemTiming {
+    /// Mean time per run
+    pub fn mean(&self) -> Duration {
+        if self.runs == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.runs as u32
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.runs += 1;
+        self.total += elapsed;
+        self.last = elapsed;
+        self.max = self.max.max(elapsed);
+    }
+}
+
+/// Ticks in a row well inside the budget before fidelity recovers a step
+pub const RECOVERY_TICKS: u32 = 20;
+
+/// Share of the budget a tick must stay under to count toward recovery
+pub const RECOVERY_HEADROOM: f64 = 0.6;
+
+/// How much detail the low-priority work keeps while ticks run over budget
+///
+/// Each step keeps the cuts of the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum Fidelity {
+    #[default]
+    Full,
+    /// Sampled perception reports are no longer emitted
+    NoCosmeticEvents,
+    /// Thoughts decay every few ticks, a few ticks' worth at a time
+    CoarseThoughtDecay,
+    /// Perception only reaches nearby entities
+    NearPerceptionOnly,
+}
+
+impl Fidelity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Fidelity::Full => "full",
+            Fidelity::NoCosmeticEvents => "no cosmetic events",
+            Fidelity::CoarseThoughtDecay => "coarse thought decay",
+            Fidelity::NearPerceptionOnly => "near perception only",
+        }
+    }
+
+    fn lower(self) -> Self {
+        match self {
+            Fidelity::Full => Fidelity::NoCosmeticEvents,
+            Fidelity::NoCosmeticEvents => Fidelity::CoarseThoughtDecay,
+            _ => Fidelity::NearPerceptionOnly,
+        }
+    }
+
+    fn raise(self) -> Self {
+        match self {
+            Fidelity::NearPerceptionOnly => Fidelity::CoarseThoughtDecay,
+            Fidelity::CoarseThoughtDecay => Fidelity::NoCosmeticEvents,
+            _ => Fidelity::Full,
+        }
+    }
+}
+
+/// Per-system frequencies and timings, and the tick budget (runtime only,
+/// never saved)
+#[derive(Debug, Clone, Default)]
+pub struct Scheduler {
+    frequencies: AHashMap<SystemId, Frequency>,
+    timings: AHashMap<SystemId, SystemTiming>,
+    budget: Option<Duration>,
+    fidelity: Fidelity,
+    calm_ticks: u32,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Social systems spread out for a population in the thousands
+    pub fn large_settlement() -> Self {
+        let mut scheduler = Self::new();
+        scheduler.frequencies.extend([
+            (SystemId::Expectations, Frequency::Amortized(4)),
+            (SystemId::Violations, Frequency::Amortized(4)),
+            (SystemId::MemoryConsolidation, Frequency::Every(10)),
+        ]);
+        scheduler
+    }
+
+    pub fn frequency(&self, system: SystemId) -> Frequency {
+        self.frequencies.get(&system).copied().unwrap_or_default()
+    }
+
+    /// Change how often a schedulable system runs
+    pub fn set_frequency(
+        &mut self,
+        system: SystemId,
+        frequency: Frequency,
+    ) -> Result<(), ScheduleError> {
+        if frequency != Frequency::EveryTick && !system.is_schedulable() {
+            return Err(ScheduleError::EveryTickOnly(system));
+        }
+        if matches!(frequency, Frequency::Every(0) | Frequency::Amortized(0)) {
+            return Err(ScheduleError::ZeroInterval);
+        }
+        self.frequencies.insert(system, frequency);
+        Ok(())
+    }
+
+    /// Which share of entities a system covers at `tick`, if it runs at all
+    pub fn due(&self, system: SystemId, tick: u64) -> Option<Share> {
+        match self.frequency(system) {
+            Frequency::EveryTick => Some(Share::ALL),
+            Frequency::Every(n) => tick.is_multiple_of(n).then_some(Share::ALL),
+            Frequency::Amortized(n) => Some(Share {
+                part: tick % n,
+                of: n,
+            }),
+        }
+    }
+
+    /// Run a system that always runs, timing it
+    pub fn time<T>(&mut self, system: SystemId, run: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = run();
+        self.timings
+            .entry(system)
+            .or_default()
+            .record(start.elapsed());
+        result
+    }
+
+    /// Run a system over this tick's share if it is due, timing it
+    pub fn run<T>(
+        &mut self,
+        system: SystemId,
+        tick: u64,
+        run: impl FnOnce(Share) -> T,
+    ) -> Option<T> {
+        let share = self.due(system, tick)?;
+        Some(self.time(system, || run(share)))
+    }
+
+    pub fn timing(&self, system: SystemId) -> SystemTiming {
+        self.timings.get(&system).copied().unwrap_or_default()
+    }
+
+    /// Forget the timings so far
+    pub fn reset_timings(&mut self) {
+        self.timings.clear();
+    }
+
+    pub fn budget(&self) -> Option<Duration> {
+        self.budget
+    }
+
+    /// Set the time a whole tick may take, or None for no limit
+    ///
+    /// Without a budget fidelity returns to full straight away.
+    pub fn set_budget(&mut self, budget: Option<Duration>) {
+        self.budget = budget;
+        self.calm_ticks = 0;
+        if budget.is_none() {
+            self.fidelity = Fidelity::Full;
+        }
+    }
+
+    pub fn fidelity(&self) -> Fidelity {
+        self.fidelity
+    }
+
+    /// Run the next tick at `fidelity`, whatever the budget, as a replay
+    /// does with the fidelity its log recorded
+    pub fn set_fidelity(&mut self, fidelity: Fidelity) {
+        self.fidelity = fidelity;
+        self.calm_ticks = 0;
+    }
+
+    /// Record how long a whole tick took against the budget
+    ///
+    /// Returns the new fidelity if it changed.
+    pub fn end_tick(&mut self, elapsed: Duration) -> Option<Fidelity> {
+        let budget = self.budget?;
+        let before = self.fidelity;
+        if elapsed > budget {
+            self.calm_ticks = 0;
+            self.fidelity = self.fidelity.lower();
+        } else if elapsed.as_secs_f64() < budget.as_secs_f64() * RECOVERY_HEADROOM {
+            self.calm_ticks += 1;
+            if self.calm_ticks >= RECOVERY_TICKS {
+                self.calm_ticks = 0;
+                self.fidelity = self.fidelity.raise();
+            }
+        } else {
+            self.calm_ticks = 0;
+        }
+        (self.fidelity != before).then_some(self.fidelity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_systems_run_at_their_frequency() {
+        let mut scheduler = Scheduler::new();
+        assert_eq!(scheduler.due(SystemId::Violations, 7), Some(Share::ALL));
+
+        scheduler
+            .set_frequency(SystemId::MemoryConsolidation, Frequency::Every(10))
+            .unwrap();
+        let runs = (0..30)
+            .filter_map(|tick| scheduler.run(SystemId::MemoryConsolidation, tick, |_| ()))
+            .count();
+        assert_eq!(runs, 3);
+        assert_eq!(scheduler.timing(SystemId::MemoryConsolidation).runs, 3);
+
+        // Amortized: everyone is covered exactly once every n ticks
+        scheduler
+            .set_frequency(SystemId::Expectations, Frequency::Amortized(4))
+            .unwrap();
+        for entity in 0..10 {
+            let covered = (0..4)
+                .filter(|&tick| {
+                    scheduler
+                        .due(SystemId::Expectations, tick)
+                        .unwrap()
+                        .includes(entity)
+                })
+                .count();
+            assert_eq!(covered, 1);
+        }
+
+        assert_eq!(
+            scheduler.set_frequency(SystemId::TaskExecution, Frequency::Every(2)),
+            Err(ScheduleError::EveryTickOnly(SystemId::TaskExecution))
+        );
+        assert_eq!(
+            scheduler.set_frequency(SystemId::Violations, Frequency::Amortized(0)),
+            Err(ScheduleError::ZeroInterval)
+        );
+    }
+
+    #[test]
+    fn test_fidelity_degrades_over_budget_and_recovers() {
+        let mut scheduler = Scheduler::new();
+        let slow = Duration::from_millis(50);
+        let fast = Duration::from_millis(1);
+        assert_eq!(scheduler.end_tick(slow), None);
+
+        scheduler.set_budget(Some(Duration::from_millis(10)));
+        assert_eq!(scheduler.end_tick(slow), Some(Fidelity::NoCosmeticEvents));
+        scheduler.end_tick(slow);
+        scheduler.end_tick(slow);
+        assert_eq!(scheduler.end_tick(slow), None);
+        assert_eq!(scheduler.fidelity(), Fidelity::NearPerceptionOnly);
+
+        // One step back per run of calm ticks; a slow tick restarts the run
+        for _ in 0..RECOVERY_TICKS - 1 {
+            assert_eq!(scheduler.end_tick(fast), None);
+        }
+        assert_eq!(scheduler.end_tick(fast), Some(Fidelity::CoarseThoughtDecay));
+        for _ in 0..RECOVERY_TICKS - 1 {
+            scheduler.end_tick(fast);
+        }
+        scheduler.end_tick(Duration::from_millis(9));
+        scheduler.end_tick(fast);
+        assert_eq!(scheduler.fidelity(), Fidelity::CoarseThoughtDecay);
+
+        scheduler.set_budget(None);
+        assert_eq!(scheduler.fidelity(), Fidelity::Full);
+    }
+}
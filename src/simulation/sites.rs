@@ -0,0 +1,266 @@
+//! Construction site system
+//!
+//! Sites placed from blueprints post jobs on the job board: hauling while a
+//! site is short of materials the stockpile holds, building once its bill has
+//! arrived. Idle humans claim them as tasks aimed at the site's building; the
+//! task executor walks the worker there, then delivers a load from the
+//! stockpile or applies their work with `work_on_site`. A finished site
+//! completes its building.
+
+use std::path::Path;
+
+use crate::actions::catalog::ActionId;
+use crate::blueprints::BlueprintRegistry;
+use crate::city::building::{BuildingArchetype, BuildingId, BuildingState};
+use crate::city::site::{ConstructionSite, SiteError};
+use crate::core::types::{Tick, Vec2};
+use crate::ecs::world::World;
+use crate::entity::tasks::TaskPriority;
+use crate::simulation::jobs::Job;
+
+/// Workers within this distance of a site can haul to or build on it
+pub const SITE_WORK_RANGE: f32 = 3.0;
+
+/// Place a building blueprint from the blueprints on disk
+pub fn place_blueprint(
+    world: &mut World,
+    name: &str,
+    position: Vec2,
+) -> Result<BuildingId, SiteError> {
+    let mut registry = BlueprintRegistry::new();
+    registry.load_directory(Path::new("data/blueprints"))?;
+    world.place_blueprint(&registry, name, position)
+}
+
+/// The nearest living human who is idle or only idling, and not worn out,
+/// to take on work at `position`
+pub fn nearest_idle_worker(world: &World, position: Vec2) -> Option<usize> {
+    world
+        .humans
+        .iter_living()
+        .filter(|&i| {
+            !world.humans.body_states[i].is_overworked()
+                && world.humans.task_queues[i]
+                    .current()
+                    .map(|t| matches!(t.action, ActionId::IdleWander | ActionId::IdleObserve))
+                    .unwrap_or(true)
+        })
+        .min_by(|&a, &b| {
+            let da = world.humans.positions[a].distance(&position);
+            let db = world.humans.positions[b].distance(&position);
+            da.total_cmp(&db)
+        })
+}
+
+/// Index of the construction site for a building
+pub fn site_of(sites: &[ConstructionSite], building: BuildingId) -> Option<usize> {
+    sites.iter().position(|site| site.building == building)
+}
+
+/// Keep each unfinished site's jobs posted on the job board
+///
+/// A site wants as many workers as its blueprint's labor cap: haulers while
+/// it is short of materials the stockpile holds, builders once its bill has
+/// arrived. Jobs a site no longer needs are withdrawn. Returns number of jobs
+/// posted.
+pub fn post_site_jobs(world: &mut World) -> usize {
+    let mut posted = 0;
+
+    for site in &world.sites {
+        let wanted = if site.is_complete() {
+            None
+        } else if site.materials_ready() {
+            Some(ActionId::Build)
+        } else if site.can_haul_from(&world.stockpile) {
+            Some(ActionId::Haul)
+        } else {
+            None // Waiting on materials nobody has
+        };
+        let building = site.building;
+        world
+            .jobs
+            .retain(|job| job.building != Some(building) || Some(job.action) == wanted);
+
+        let Some(action) = wanted else {
+            continue;
+        };
+        let open = world
+            .jobs
+            .jobs()
+            .iter()
+            .filter(|job| job.building == Some(building))
+            .count() as u32;
+        for _ in open..site.labor_cap {
+            let job = Job::new(action, site.position, TaskPriority::Normal).with_building(building);
+            world.jobs.post(job, world.current_tick);
+            posted += 1;
+        }
+    }
+
+    posted
+}
+
+/// Apply a builder's work to a site, completing its building when the site
+/// is finished. Returns true when the builder's job is done.
+pub fn work_on_site(
+    site: &mut ConstructionSite,
+    buildings: &mut BuildingArchetype,
+    amount: f32,
+    current_tick: Tick,
+) -> bool {
+    if !site.materials_ready() {
+        return true; // Nothing to build with yet
+    }
+    let finished = site.apply_work(amount);
+    let Some(b) = buildings.index_of(site.building) else {
+        return true; // Building gone
+    };
+
+    // The building shows the site's progress on its own scale
+    buildings.construction_progress[b] =
+        site.instance.construction_progress * site.building_type.work_required();
+    if finished && buildings.states[b] == BuildingState::UnderConstruction {
+        buildings.states[b] = BuildingState::Complete;
+        buildings.completed_ticks[b] = current_tick;
+        tracing::debug!("Construction complete: {:?}", site.building);
+    }
+    finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::Abundance;
+    use crate::simulation::jobs::assign_jobs;
+    use crate::simulation::resource_zone::ResourceType;
+    use crate::simulation::tick::run_simulation_tick;
+    use crate::world::{ZoneDesignation, ZoneKind, ZoneShape};
+
+    #[test]
+    fn test_site_jobs_haul_then_build() {
+        let mut world = World::with_seed(3);
+        for n in 0..3 {
+            world.spawn_human(format!("Worker {}", n));
+        }
+        let building = place_blueprint(&mut world, "well", Vec2::new(5.0, 5.0)).unwrap();
+        let b = world.buildings.index_of(building).unwrap();
+        assert_eq!(world.buildings.states[b], BuildingState::UnderConstruction);
+
+        // No stone in the stockpile: nothing to haul yet
+        assert_eq!(post_site_jobs(&mut world), 0);
+
+        // Labor cap 2: two hauling jobs, claimed by two of the three workers
+        world.stockpile.add(ResourceType::Stone, 100);
+        assert_eq!(post_site_jobs(&mut world), 2);
+        assert_eq!(post_site_jobs(&mut world), 0);
+        assert_eq!(assign_jobs(&mut world), 2);
+        assert_eq!(assign_jobs(&mut world), 0);
+        let haulers = world
+            .humans
+            .iter_living()
+            .filter(|&i| {
+                world.humans.task_queues[i]
+                    .current()
+                    .is_some_and(|t| t.action == ActionId::Haul)
+            })
+            .count();
+        assert_eq!(haulers, 2);
+
+        // Builders wait for the stone
+        assert!(work_on_site(
+            &mut world.sites[0],
+            &mut world.buildings,
+            50.0,
+            1
+        ));
+        assert_eq!(world.buildings.construction_progress[b], 0.0);
+
+        // With the stone delivered, idle workers are sent to build
+        while world.sites[0].haul_from(&mut world.stockpile).is_some() {}
+        for i in 0..3 {
+            world.humans.task_queues[i].clear();
+        }
+        assert_eq!(post_site_jobs(&mut world), 2);
+        assert!(world
+            .jobs
+            .jobs()
+            .iter()
+            .all(|job| job.action == ActionId::Build));
+        assert_eq!(assign_jobs(&mut world), 2);
+        assert!(!work_on_site(
+            &mut world.sites[0],
+            &mut world.buildings,
+            150.0,
+            2
+        ));
+        // Half of a well's 60 work
+        assert_eq!(world.buildings.construction_progress[b], 30.0);
+        assert!(work_on_site(
+            &mut world.sites[0],
+            &mut world.buildings,
+            150.0,
+            3
+        ));
+        assert_eq!(world.buildings.states[b], BuildingState::Complete);
+        assert_eq!(world.buildings.completed_ticks[b], 3);
+        assert_eq!(post_site_jobs(&mut world), 0);
+        assert!(world.jobs.is_empty());
+
+        assert!(matches!(
+            place_blueprint(&mut world, "oak_tree", Vec2::new(0.0, 0.0)),
+            Err(SiteError::NotABuilding(_))
+        ));
+    }
+
+    #[test]
+    fn test_idle_humans_raise_placed_building() {
+        let mut world = World::with_seed(8);
+        world.add_food_zone(Vec2::new(10.0, 10.0), 10.0, Abundance::Unlimited);
+        world.stockpile.add(ResourceType::Stone, 100);
+        for name in ["Ada", "Bram"] {
+            world.spawn_human(name.into());
+        }
+        let building = place_blueprint(&mut world, "well", Vec2::new(12.0, 8.0)).unwrap();
+        let b = world.buildings.index_of(building).unwrap();
+
+        for _ in 0..2000 {
+            run_simulation_tick(&mut world);
+            if world.buildings.states[b] == BuildingState::Complete {
+                break;
+            }
+        }
+        assert_eq!(world.buildings.states[b], BuildingState::Complete);
+        assert_eq!(world.stockpile.get(ResourceType::Stone), 25);
+    }
+
+    #[test]
+    fn test_haulers_fetch_from_stockpile_zone() {
+        let mut world = World::with_seed(8);
+        world.add_food_zone(Vec2::new(10.0, 10.0), 10.0, Abundance::Unlimited);
+        world.stockpile.add(ResourceType::Stone, 100);
+        world.spawn_human("Ada".into());
+        world.humans.positions[0] = Vec2::new(10.0, 5.0);
+        let yard = ZoneShape::Rect {
+            min: Vec2::new(-24.0, 0.0),
+            max: Vec2::new(-20.0, 4.0),
+        };
+        world.designate_zone(ZoneDesignation {
+            kind: ZoneKind::Stockpile,
+            shape: yard,
+            name: None,
+        });
+        place_blueprint(&mut world, "well", Vec2::new(12.0, 8.0)).unwrap();
+
+        // The first load is carried from the yard, not conjured at the site
+        let mut visited_yard = false;
+        for _ in 0..1000 {
+            run_simulation_tick(&mut world);
+            visited_yard |= yard.contains(world.humans.positions[0]);
+            if world.stockpile.get(ResourceType::Stone) < 100 {
+                break;
+            }
+        }
+        assert!(world.stockpile.get(ResourceType::Stone) < 100);
+        assert!(visited_yard);
+    }
+}
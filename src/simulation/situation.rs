@@ -0,0 +1,209 @@
+//! Situation reports - what happened while the player was away
+//!
+//! After a load or a long fast-forward the player gets a report of what
+//! changed since the last one: who died and who was born, which buildings
+//! were finished, the fighting, and what the chronicle recorded besides the
+//! deaths (oaths judged, feuds settled, dreams fulfilled). The report is
+//! plain data for the UI; the LLM can narrate it (see `llm::narrator`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::city::building::{BuildingState, BuildingType};
+use crate::core::astronomy::TICKS_PER_DAY;
+use crate::core::types::Tick;
+use crate::ecs::world::World;
+use crate::world::{ChronicleEntry, Fight};
+
+/// Ticks run at once that count as a fast-forward worth a report
+pub const FAST_FORWARD_TICKS: u64 = TICKS_PER_DAY;
+
+/// What happened between two ticks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SituationReport {
+    pub since: Tick,
+    pub until: Tick,
+    /// Names of those who died
+    pub deaths: Vec<String>,
+    /// Names of those born
+    pub births: Vec<String>,
+    /// Buildings finished
+    pub constructions: Vec<BuildingType>,
+    /// Spells of fighting
+    pub fights: Vec<Fight>,
+    /// Chronicle entries other than deaths
+    pub notable: Vec<ChronicleEntry>,
+}
+
+impl SituationReport {
+    /// Whether nothing worth telling happened
+    pub fn is_quiet(&self) -> bool {
+        self.deaths.is_empty()
+            && self.births.is_empty()
+            && self.constructions.is_empty()
+            && self.fights.is_empty()
+            && self.notable.is_empty()
+    }
+
+    /// One line per section (and per notable event), for display
+    pub fn lines(&self) -> Vec<String> {
+        let days = (self.until - self.since) / TICKS_PER_DAY;
+        let mut lines = vec![format!(
+            "Ticks {}-{} ({} day(s)):",
+            self.since, self.until, days
+        )];
+        if self.is_quiet() {
+            lines.push("  Nothing of note happened.".to_string());
+            return lines;
+        }
+        if !self.deaths.is_empty() {
+            lines.push(format!("  Died: {}", self.deaths.join(", ")));
+        }
+        if !self.births.is_empty() {
+            lines.push(format!("  Born: {}", self.births.join(", ")));
+        }
+        if !self.constructions.is_empty() {
+            let built: Vec<String> = self
+                .constructions
+                .iter()
+                .map(|b| format!("{:?}", b))
+                .collect();
+            lines.push(format!("  Built: {}", built.join(", ")));
+        }
+        for fight in &self.fights {
+            lines.push(format!(
+                "  Fighting from tick {} to {}: {} blow(s) landed",
+                fight.started, fight.last_blow, fight.blows
+            ));
+        }
+        for entry in &self.notable {
+            lines.push(format!("  [{}] {}", entry.tick, entry.text));
+        }
+        lines
+    }
+}
+
+/// What has happened since the last report
+pub fn situation_report(world: &World) -> SituationReport {
+    let log = &world.situation;
+    let since = log.since;
+    let until = world.current_tick;
+
+    let deaths: Vec<String> = log
+        .living
+        .iter()
+        .filter_map(|&id| world.humans.index_of(id))
+        .filter(|&i| !world.humans.alive[i] && !world.exiles.is_away(world.humans.ids[i]))
+        .map(|i| world.humans.names[i].clone())
+        .collect();
+
+    // Newcomers young enough to have been born since (not spawned adults)
+    let days_passed = ((until - since) / TICKS_PER_DAY) as u32;
+    let births = world
+        .humans
+        .iter_living()
+        .filter(|&i| !log.living.contains(&world.humans.ids[i]))
+        .filter(|&i| world.humans.ages[i] <= days_passed)
+        .map(|i| world.humans.names[i].clone())
+        .collect();
+
+    let buildings = &world.buildings;
+    let constructions = (0..buildings.ids.len())
+        .filter(|&b| {
+            buildings.states[b] == BuildingState::Complete && buildings.completed_ticks[b] > since
+        })
+        .map(|b| buildings.building_types[b])
+        .collect();
+
+    let notable = world
+        .legacy
+        .chronicle
+        .iter()
+        .skip(log.chronicle_len)
+        .filter(|entry| {
+            !deaths
+                .iter()
+                .any(|name| entry.text == format!("{} died", name))
+        })
+        .cloned()
+        .collect();
+
+    SituationReport {
+        since,
+        until,
+        deaths,
+        births,
+        constructions,
+        fights: log.fights.clone(),
+        notable,
+    }
+}
+
+/// Start the next report from now
+pub fn mark_situation(world: &mut World) {
+    let living = world
+        .humans
+        .iter_living()
+        .map(|i| world.humans.ids[i])
+        .collect();
+    let chronicle_len = world.legacy.chronicle.len();
+    world
+        .situation
+        .mark(world.current_tick, living, chronicle_len);
+}
+
+/// Report what has happened since the last report, and start the next
+pub fn take_situation_report(world: &mut World) -> SituationReport {
+    let report = situation_report(world);
+    mark_situation(world);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::building::BuildingId;
+    use crate::core::types::Vec2;
+    use crate::simulation::legacy::record_death;
+    use crate::simulation::lifecycle::bear_child;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_report_covers_what_changed_since_the_mark() {
+        let mut world = World::with_seed(14);
+        let mut rng = ChaCha8Rng::seed_from_u64(14);
+        let ada = world.spawn_human("Ada".into());
+        world.spawn_human("Bram".into());
+        mark_situation(&mut world);
+        assert!(take_situation_report(&mut world).is_quiet());
+
+        world.current_tick += 3 * TICKS_PER_DAY;
+        world.humans.alive[1] = false;
+        record_death(&mut world, 1, None);
+        bear_child(&mut world, &[ada], &mut rng);
+        world.spawn_human("Cora".into());
+        world
+            .legacy
+            .record(world.current_tick, "Ada settled a feud");
+        let h = world.buildings.spawn(
+            BuildingId::new(),
+            BuildingType::House,
+            Vec2::new(0.0, 0.0),
+            0,
+        );
+        world.buildings.states[h] = BuildingState::Complete;
+        world.buildings.completed_ticks[h] = world.current_tick;
+        world.situation.note_blows(world.current_tick, 3);
+
+        let report = take_situation_report(&mut world);
+        assert_eq!(report.deaths, vec!["Bram".to_string()]);
+        assert_eq!(report.births.len(), 1);
+        assert_eq!(report.constructions, vec![BuildingType::House]);
+        assert_eq!(report.fights.len(), 1);
+        assert_eq!(report.notable.len(), 1);
+        assert!(report.lines().iter().any(|l| l.contains("feud")));
+
+        // The next report starts from here
+        assert!(situation_report(&world).is_quiet());
+    }
+}
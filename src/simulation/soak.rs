@@ -0,0 +1,303 @@
+//! Soak runs - a settlement left to live for generations
+//!
+//! `found_settlement` lays out a village that can keep itself: houses,
+//! worked farms, a stocked granary, food and wood nearby, and founding
+//! families of every age. `run_soak` lives it through `SoakConfig::years`
+//! years at a compressed time scale - births, deaths, seasons and all - with
+//! a warband of orcs coming to raid every `raid_every_years`, fought tick by
+//! tick, for `SOAK_RAID_DAYS` days before the raiders still standing go back
+//! to the wilds.
+//!
+//! Each year ends with a check: someone is still alive, no more live than
+//! the houses hold (with `SOAK_CROWDING` to spare), and a snapshot of the
+//! world takes no more than `SOAK_BYTES_PER_SETTLER` for each settler still
+//! living, so nothing piles up without bound. At the end the years must have
+//! left a chronicle and more than one generation.
+//! The first check to fail ends the run.
+
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use crate::actions::catalog::ActionId;
+use crate::city::building::{BuildingState, BuildingType};
+use crate::core::astronomy::YEAR_LENGTH;
+use crate::core::types::Vec2;
+use crate::ecs::persistence::{snapshot_to_string, PersistenceError};
+use crate::ecs::world::{Abundance, World};
+use crate::entity::tasks::{Task, TaskPriority, TaskSource};
+use crate::simulation::lifecycle::{age_in_years, ADULT_AGE};
+use crate::simulation::resource_zone::ResourceType;
+use crate::simulation::tick::{run_simulation_tick, SimulationEvent, TICKS_PER_DAY};
+use crate::simulation::time_scale::{TimeScale, TimeScaleError};
+use crate::simulation::unrest::settlement_centre;
+
+/// Houses in a founded settlement
+pub const SOAK_HOUSES: usize = 8;
+
+/// Worked farms in a founded settlement
+pub const SOAK_FARMS: usize = 3;
+
+/// Founding settlers
+pub const SOAK_FOUNDERS: usize = 16;
+
+/// Settlers beyond the houses' capacity a year may end with
+pub const SOAK_CROWDING: usize = 4;
+
+/// Snapshot bytes a year may end with for each living settler (after a
+/// century of raids, with everyone known to everyone, about 60 KB)
+pub const SOAK_BYTES_PER_SETTLER: usize = 128 * 1024;
+
+/// Orcs in a raiding warband
+pub const SOAK_RAIDERS: usize = 3;
+
+/// Days a warband raids before those still standing go back to the wilds
+pub const SOAK_RAID_DAYS: u64 = 5;
+
+/// How a settlement fell short during a soak run
+#[derive(Error, Debug)]
+pub enum SoakError {
+    #[error("Everyone was dead by year {year}")]
+    Extinct { year: u32 },
+    #[error("{population} living in year {year}, more than {limit} the houses can hold")]
+    Overcrowded {
+        year: u32,
+        population: usize,
+        limit: usize,
+    },
+    #[error("Snapshot grew to {bytes_per_settler} bytes a settler in year {year}, over {limit}")]
+    MemoryGrowth {
+        year: u32,
+        bytes_per_settler: usize,
+        limit: usize,
+    },
+    #[error("{years} years left no chronicle")]
+    NoHistory { years: u32 },
+    #[error("No child born in {years} years grew up")]
+    NoGeneration { years: u32 },
+    #[error("Snapshot failed: {0}")]
+    Snapshot(#[from] PersistenceError),
+    #[error("Bad time scale: {0}")]
+    TimeScale(#[from] TimeScaleError),
+}
+
+/// How long a soak runs, and how hard
+#[derive(Debug, Clone, Copy)]
+pub struct SoakConfig {
+    pub years: u32,
+    /// Simulation steps a day (see `TimeScale::compressed`)
+    pub steps_per_day: u64,
+    /// Years between orc raids; 0 for none
+    pub raid_every_years: u32,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            years: 100,
+            steps_per_day: 1,
+            raid_every_years: 10,
+        }
+    }
+}
+
+/// The settlement at the end of one year
+#[derive(Debug, Clone)]
+pub struct SoakYear {
+    pub year: u32,
+    pub population: usize,
+    /// Blows landed in fights this year, raids among them
+    pub blows: usize,
+    pub snapshot_bytes: usize,
+    pub chronicle_entries: usize,
+    pub elapsed: Duration,
+}
+
+/// What a soak run found, year by year
+#[derive(Debug, Clone, Default)]
+pub struct SoakReport {
+    pub years: Vec<SoakYear>,
+    pub raids: u32,
+    /// Everyone who ever lived in the settlement
+    pub settlers: usize,
+}
+
+impl SoakReport {
+    pub fn elapsed(&self) -> Duration {
+        self.years.iter().map(|y| y.elapsed).sum()
+    }
+
+    pub fn blows(&self) -> usize {
+        self.years.iter().map(|y| y.blows).sum()
+    }
+}
+
+/// Lay out a village that can keep itself going for generations
+pub fn found_settlement(seed: u64) -> World {
+    let mut world = World::with_seed(seed);
+
+    for i in 0..SOAK_HOUSES {
+        let at = Vec2::new(20.0 + (i % 4) as f32 * 10.0, 20.0 + (i / 4) as f32 * 10.0);
+        let id = world.spawn_building(BuildingType::House, at);
+        let idx = world.buildings.index_of(id).unwrap();
+        world.buildings.states[idx] = BuildingState::Complete;
+    }
+    for i in 0..SOAK_FARMS {
+        let at = Vec2::new(20.0 + i as f32 * 12.0, 50.0);
+        let id = world.spawn_building(BuildingType::Farm, at);
+        let idx = world.buildings.index_of(id).unwrap();
+        world.buildings.states[idx] = BuildingState::Complete;
+        world.buildings.start_production(idx, "farm_food".into());
+        world.buildings.production_workers[idx] = 2;
+    }
+
+    world.stockpile.set_capacity(ResourceType::Food, 5000);
+    world.stockpile.add(ResourceType::Food, 2000);
+    world.stockpile.add(ResourceType::Wood, 100);
+    world.add_food_zone(Vec2::new(10.0, 40.0), 15.0, Abundance::Unlimited);
+    world.add_food_zone(Vec2::new(60.0, 10.0), 15.0, Abundance::Unlimited);
+
+    for n in 0..SOAK_FOUNDERS {
+        world.spawn_human(format!("Founder {}", n + 1));
+        let i = world.humans.count() - 1;
+        // Children, parents, and a few elders
+        let age = [6, 20, 24, 30, 38, 52, 64, 12][n % 8];
+        world.humans.ages[i] = age * YEAR_LENGTH as u32;
+        world.humans.positions[i] = Vec2::new(25.0 + (n % 4) as f32 * 8.0, 30.0);
+    }
+    world
+}
+
+/// Send a warband out of the wilds to march on the settlement
+fn raid(world: &mut World, year: u32) {
+    let tick = world.current_tick;
+    let centre = settlement_centre(world);
+    for n in 0..SOAK_RAIDERS {
+        world.spawn_orc(format!("Raider {}-{}", year, n + 1));
+        let i = world.orcs.count() - 1;
+        world.orcs.positions[i] = Vec2::new(90.0, 80.0 + n as f32 * 2.0);
+        let mut approach =
+            Task::new(ActionId::MoveTo, TaskPriority::High, tick).with_position(centre);
+        approach.source = TaskSource::Reaction;
+        world.orcs.task_queues[i].push(approach);
+    }
+    world.legacy.record(
+        tick,
+        "A warband of orcs comes out of the wilds to raid".to_string(),
+    );
+}
+
+/// Send the raiders still standing back to the wilds, off the map
+fn withdraw(world: &mut World) {
+    let raiders: Vec<usize> = world.orcs.iter_living().collect();
+    if raiders.is_empty() {
+        return;
+    }
+    for &i in &raiders {
+        let id = world.orcs.ids[i];
+        world.orcs.task_queues[i].clear();
+        world.orcs.positions[i] = Vec2::new(90.0, 80.0);
+        world.orcs.alive[i] = false;
+        world.factions.leave(id);
+        world.components.remove_entity(id);
+    }
+    world.legacy.record(
+        world.current_tick,
+        format!("{} raiders go back to the wilds", raiders.len()),
+    );
+}
+
+/// Check the settlement at the end of `year`
+fn check_year(world: &World, year: &SoakYear) -> Result<(), SoakError> {
+    if year.population == 0 {
+        return Err(SoakError::Extinct { year: year.year });
+    }
+    let housing: usize = (0..world.buildings.count())
+        .filter(|&i| world.buildings.states[i] == BuildingState::Complete)
+        .map(|i| world.buildings.building_types[i].housing_capacity() as usize)
+        .sum();
+    let limit = housing + SOAK_CROWDING;
+    if year.population > limit {
+        return Err(SoakError::Overcrowded {
+            year: year.year,
+            population: year.population,
+            limit,
+        });
+    }
+    let bytes_per_settler = year.snapshot_bytes / year.population;
+    if bytes_per_settler > SOAK_BYTES_PER_SETTLER {
+        return Err(SoakError::MemoryGrowth {
+            year: year.year,
+            bytes_per_settler,
+            limit: SOAK_BYTES_PER_SETTLER,
+        });
+    }
+    Ok(())
+}
+
+/// Live `world` through `config.years` years, checking it after each, and
+/// calling `on_year` with each year's record
+pub fn run_soak(
+    world: &mut World,
+    config: SoakConfig,
+    mut on_year: impl FnMut(&SoakYear),
+) -> Result<SoakReport, SoakError> {
+    world.set_time_scale(TimeScale::compressed(config.steps_per_day)?);
+    let founders = world.humans.ids.clone();
+    let mut report = SoakReport::default();
+    let mut born = 0;
+
+    for year in 1..=config.years {
+        let start = Instant::now();
+        let mut blows = 0;
+        let mut raid_over = None;
+        if config.raid_every_years > 0 && year % config.raid_every_years == 0 {
+            raid(world, year);
+            report.raids += 1;
+            raid_over = Some(world.current_tick + SOAK_RAID_DAYS * TICKS_PER_DAY);
+        }
+        // Counted in ticks: raids are fought tick by tick
+        let year_end = world.current_tick + TICKS_PER_DAY * YEAR_LENGTH as u64;
+        while world.current_tick < year_end {
+            for event in run_simulation_tick(world) {
+                match event {
+                    SimulationEvent::Born { .. } => born += 1,
+                    SimulationEvent::CombatHit { .. } => blows += 1,
+                    _ => {}
+                }
+            }
+            if raid_over.is_some_and(|tick| world.current_tick >= tick) {
+                withdraw(world);
+                raid_over = None;
+            }
+        }
+
+        let record = SoakYear {
+            year,
+            population: world.humans.iter_living().count(),
+            blows,
+            snapshot_bytes: snapshot_to_string(world)?.len(),
+            chronicle_entries: world.legacy.chronicle.len(),
+            elapsed: start.elapsed(),
+        };
+        on_year(&record);
+        check_year(world, &record)?;
+        report.years.push(record);
+    }
+
+    report.settlers = founders.len() + born;
+    if world.legacy.chronicle.is_empty() {
+        return Err(SoakError::NoHistory {
+            years: config.years,
+        });
+    }
+    let grown = (0..world.humans.count()).any(|i| {
+        !founders.contains(&world.humans.ids[i]) && age_in_years(world.humans.ages[i]) >= ADULT_AGE
+    });
+    if config.years > ADULT_AGE && !grown {
+        return Err(SoakError::NoGeneration {
+            years: config.years,
+        });
+    }
+    Ok(report)
+}
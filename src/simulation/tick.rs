@@ -5,9 +5,12 @@
 //!
 //! Each tick advances the simulation one step, processing all entities.
 //!
-//! Uses rayon for parallel processing where safe.
+//! Uses rayon for parallel processing where safe: needs decay, attention
+//! refresh, thought decay, and perception run in parallel over the archetype
+//! arrays (see `parallel`), and finish before anything that reaches across
+//! entities runs.
 
-use crate::actions::catalog::ActionId;
+use crate::actions::catalog::{ActionCategory, ActionId};
 
 /// Events generated during simulation tick
 ///
@@ -113,6 +116,53 @@ pub enum SimulationEvent {
         /// What triggered the change
         trigger: String,
     },
+    /// An oath came due and was kept
+    OathKept { swearer: String, tick: u64 },
+    /// An oath was broken
+    OathBroken { swearer: String, tick: u64 },
+    /// A settler asked the player for something
+    RequestMade {
+        asker: String,
+        wish: String,
+        tick: u64,
+    },
+    /// A request was granted
+    RequestGranted {
+        asker: String,
+        wish: String,
+        tick: u64,
+    },
+    /// A request lapsed unanswered
+    RequestLapsed {
+        asker: String,
+        wish: String,
+        tick: u64,
+    },
+    /// The settlement read a celestial event as an omen
+    OmenSeen { reading: String, tick: u64 },
+    /// An omen passed without the rite it called for
+    OmenUnheeded { rite: String, tick: u64 },
+    /// A milestone was reached and a new era began
+    EraBegan { era: String, tick: u64 },
+    /// A child was born
+    Born { name: String, tick: u64 },
+    /// Someone died of old age
+    DiedOfOldAge { name: String, tick: u64 },
+    /// An attack on a neutral faction's member started a war
+    WarDeclared {
+        faction: String,
+        against: String,
+        tick: u64,
+    },
+    /// A step of a multi-step player order began, finished, or failed
+    PlanProgress {
+        tick: u64,
+        plan: PlanId,
+        /// The step (0-based) and how many there are
+        step: usize,
+        steps: usize,
+        message: String,
+    },
     /// Game over event - signals end of simulation
     GameOver {
         tick: u64,
@@ -133,6 +183,10 @@ pub enum GameOutcome {
     },
     /// Mutual destruction
     Draw,
+    /// The settlement met its happiness goal (see `happiness::HappinessGoal`)
+    Contented { happiness: f32 },
+    /// The settlement stayed too unhappy for too long
+    Overthrown { happiness: f32 },
     /// Simulation still in progress
     InProgress,
 }
@@ -150,72 +204,233 @@ enum CombatTarget {
 use crate::city::construction::{
     apply_construction_work, calculate_worker_contribution, ContributionResult,
 };
-use crate::city::production::tick_production;
+use crate::city::crossing::CrossingState;
+use crate::city::production::tick_production_over;
 use crate::city::recipe::RecipeCatalog;
+use crate::city::road::{RoadNetwork, ROAD_CELL_SIZE};
 use crate::combat::{
     resolve_exchange, ArmorProperties, CombatSkill, CombatStance, Combatant, WeaponProperties,
     WoundSeverity,
 };
+use crate::command::plan::{advance_plans, PlanId};
+use crate::core::types::{EntityId, Species, Vec2};
 use crate::ecs::world::World;
 use crate::entity::needs::NeedType;
-use crate::entity::social::{Disposition, EventType};
-use crate::entity::tasks::Task;
+use crate::entity::social::{Disposition, EventType, SocialMemory};
+use crate::entity::tasks::{Task, TaskQueue};
 use crate::entity::thoughts::{CauseType, Thought, Valence};
 use crate::simulation::action_select::{
-    select_action_dwarf, select_action_elf, select_action_human, select_action_orc,
-    DwarfSelectionContext, ElfSelectionContext, OrcSelectionContext, SelectionContext,
+    evaluate_human, select_action_dwarf, select_action_elf, select_action_human,
+    select_action_orc, Candidate, DwarfSelectionContext, ElfSelectionContext, OrcSelectionContext,
+    SelectionContext,
 };
+use crate::simulation::almanac::run_weather;
+use crate::simulation::aspirations::run_aspirations;
+use crate::simulation::barter::{conclude_trade, run_market};
 use crate::simulation::consumption::consume_food;
+use crate::simulation::crossings::{run_crossings, CROSSING_WORK_RANGE};
+use crate::simulation::decision_trace::record_decision;
+use crate::simulation::economy::{judge_deal, trade_with_stockpile};
+use crate::simulation::epithets::{perform, track_famine};
+use crate::simulation::eras::run_eras;
+use crate::simulation::exhaustion::update_exhaustion;
+use crate::simulation::exile::run_exiles;
 use crate::simulation::expectation_formation::process_observations;
 use crate::simulation::housing::assign_housing;
+use crate::simulation::husbandry::run_husbandry;
+use crate::simulation::hygiene::{
+    disgust_thought, run_hygiene, social_quality, wash_people, DISGUST_RANGE, FILTHY_THRESHOLD,
+};
+use crate::simulation::items::{deliver_crafted, use_items, PICKUP_RANGE};
+use crate::simulation::kin_temperament::{kin_condition, update_kin_temperament};
+use crate::simulation::legacy::{record_death, run_legacy, STANDING_BONUS};
+use crate::simulation::lifecycle::run_lifecycle;
+use crate::simulation::lighting::{check_fires, tend_lights};
+use crate::simulation::oaths::run_oaths;
+use crate::simulation::omens::{heed_omens, run_omens, OmenReport};
+use crate::simulation::orc_temperament::{orc_condition, update_orc_temperament};
 use crate::simulation::perception::{
     find_nearest_building_site, find_nearest_food_zone, perception_system, RelationshipType,
 };
 use crate::simulation::population::try_population_growth;
+use crate::simulation::regrowth::regrow_zones;
+use crate::simulation::requests::{describe_wish, run_requests, RequestReport};
+use crate::simulation::rescue::{carry_step, hold_carried, run_rescue};
+use crate::simulation::roads::run_roads;
+use crate::simulation::time_scale::hold_for_fighting;
+use crate::simulation::jobs::assign_jobs;
+use crate::simulation::sites::{post_site_jobs, site_of, work_on_site, SITE_WORK_RANGE};
+use crate::simulation::ambience::react_to_surroundings;
+use crate::simulation::despair::{run_despair, withdraw_in_despair};
+use crate::simulation::drill::{finish_drill, run_drill};
+use crate::simulation::happiness::run_polling;
+use crate::simulation::places::run_places;
+use crate::simulation::transformations::{note_head_wound, run_transformations};
+use crate::simulation::unrest::{resent_feast_work, run_unrest, work_pace};
+use crate::simulation::weather_effects::{
+    current_chill, movement_multiplier, need_decay_multiplier, outdoor_work_multiplier,
+    react_to_weather,
+};
+use crate::world::lighting::{visibility, work_efficiency};
+use crate::world::{Feat, OathId, RequestId, ZoneKind};
+use crate::simulation::parallel::{for_each_living, for_each_mut, map, PARALLEL_THRESHOLD};
+use crate::simulation::scheduler::{Fidelity, Share, SystemId};
 use crate::simulation::violation_detection::process_violations;
 use crate::skills::{
     record_action_experience, refresh_attention, skill_check, spend_attention, SkillFailure,
 };
+use crate::spatial::pathfinding::step_away;
 use crate::spatial::sparse_hash::SparseHashGrid;
+use rand::Rng;
 use rayon::prelude::*;
+use std::time::Instant;
 
 /// Run a single simulation tick
 ///
-/// This is the main entry point that orchestrates all simulation systems:
-/// 1. Update needs (decay over time)
-/// 2. Run perception (entities observe their surroundings)
+/// This is the main entry point that orchestrates all simulation systems, each
+/// timed by the world's scheduler (which may run steps 4-6 less often, or over
+/// a share of the population at a time). When ticks run over the scheduler's
+/// budget, sampled perception reports are dropped, thoughts decay in coarser
+/// steps, and perception stops reaching distant entities, until ticks are fast
+/// again:
+/// 1. Tend lights and update needs (lights follow carriers and burn fuel, needs decay)
+/// 2. Run perception (entities observe their surroundings; darkness hides unlit entities)
 /// 3. Generate thoughts (reactions to perceptions)
 /// 4. Process observations (form expectations from observed actions)
 /// 5. Process violations (detect expectation violations and generate thoughts)
 /// 6. Convert intense thoughts to memories (thoughts about entities become social memories)
 /// 7. Decay thoughts (thoughts fade over time)
 /// 8. Select actions (decide what to do based on needs, thoughts, values)
-/// 9. Execute tasks (progress current tasks, satisfy needs)
-/// 10. Regenerate food zones (scarce zones recover over time)
+/// 9. Execute tasks (progress current tasks, satisfy needs; walkers route around blocked
+///    cells; anyone idle near water washes; finished trades swap goods, with a partner or
+///    the stockpile, and lopsided deals are thought well or badly of; items are picked
+///    up, dropped, and equipped (equipped weapons and armor are what combat uses), and a
+///    meal away from food zones comes out of the pack;
+///    blows landed are tallied for the situation report; orc values move with the
+///    fighting and cool with time; members of hostile factions count as threats, allies
+///    will not attack each other, and striking a neutral faction's member starts a war;
+///    the fallen are marked dead in the identity registry)
+/// 10. Regenerate food zones (scarce zones recover over time), run workshop
+///     production (crafted items go to whoever is crafting there, else the stockpile),
+///     post hauling and building jobs for blueprint sites, and let idle humans claim
+///     jobs from the job board by priority, skill, distance, and values
 /// 11. Advance tick counter
-/// 12. Run daily systems (once per day: housing assignment, food consumption, population growth)
+/// 12. Run daily systems (once per day: weather and storm damage to crops, overwork
+///     exhaustion, illness and wash trips, housing assignment, livestock husbandry, road
+///     upkeep, bridge and ramp upkeep, fire risk from lights, remembrance and feuds over the
+///     dead, oaths coming due, market days, aspirations, food consumption, aging and death of
+///     old age, population growth)
 /// 13. Decay social memories (once per day, after tick advances)
 /// 14. Decay expectations (once per day, after tick advances)
 ///
 /// Returns a list of events that occurred during this tick for UI display.
 pub fn run_simulation_tick(world: &mut World) -> Vec<SimulationEvent> {
+    let mut rng = world.tick_rng();
+    run_simulation_tick_with_rng(world, &mut rng)
+}
+
+/// The step the scheduler counts in
+///
+/// A compressed step advances the tick by the scale's span, so frequencies
+/// and amortized shares go by steps; by raw ticks a share could be skipped.
+fn schedule_step(world: &World) -> u64 {
+    world.current_tick / world.time_scale.span()
+}
+
+/// Run a single simulation tick, taking every random roll from `rng`
+///
+/// `run_simulation_tick` seeds `rng` from the world seed and tick; replays
+/// pass the seed recorded in their log instead.
+pub fn run_simulation_tick_with_rng<R: Rng>(
+    world: &mut World,
+    rng: &mut R,
+) -> Vec<SimulationEvent> {
     let mut events = Vec::new();
 
-    // Advance astronomical state (time, moons, celestial events)
-    world.astronomy.advance_tick();
-
-    update_needs(world);
-    refresh_all_attention(world);
-    let (perceptions, perception_ranges) = run_perception_with_ranges(world);
-    emit_perception_events(world, &perceptions, &perception_ranges, &mut events);
-    generate_thoughts_with_events(world, &perceptions, &mut events);
-    process_observations(world, &perceptions);
-    process_violations(world, &perceptions);
-    convert_thoughts_to_memories_with_events(world, &mut events);
-    decay_thoughts(world);
-    select_actions(world, &mut events);
-    execute_tasks(world, &mut events);
-    regenerate_food_zones(world);
+    // The settlement stands still while another layer holds the clock
+    if world.clock.is_held() {
+        return events;
+    }
+
+    // Fights are stepped tick by tick, whatever the time scale
+    hold_for_fighting(world);
+
+    // Advance astronomical state (time, moons, celestial events) by one step
+    let span = world.time_scale.span();
+    world.astronomy.advance_ticks(span);
+
+    // Systems run under the scheduler, which times them and spreads the
+    // expensive social ones over several ticks
+    let mut scheduler = std::mem::take(&mut world.scheduler);
+    let tick = world.current_tick;
+    let step = schedule_step(world);
+    world.paths.begin_tick(tick);
+    let tick_start = Instant::now();
+    let fidelity = scheduler.fidelity();
+    let max_perception_range = if fidelity >= Fidelity::NearPerceptionOnly {
+        NEAR_PERCEPTION_RANGE
+    } else {
+        f32::INFINITY
+    };
+
+    scheduler.time(SystemId::Needs, || {
+        tend_lights(world);
+        update_needs(world);
+        refresh_all_attention(world);
+    });
+    let perceptions = scheduler.time(SystemId::Perception, || {
+        let (perceptions, ranges) = run_perception_with_ranges(world, max_perception_range);
+        if fidelity < Fidelity::NoCosmeticEvents {
+            emit_perception_events(world, &perceptions, &ranges, &mut events);
+        }
+        perceptions
+    });
+    scheduler.time(SystemId::Thoughts, || {
+        generate_thoughts_with_events(world, &perceptions, &mut events);
+        react_to_surroundings(world);
+    });
+    scheduler.run(SystemId::Expectations, step, |share| {
+        process_observations(world, &perceptions, share)
+    });
+    scheduler.run(SystemId::Violations, step, |share| {
+        process_violations(world, &perceptions, share)
+    });
+    scheduler.run(SystemId::MemoryConsolidation, step, |share| {
+        convert_thoughts_to_memories_with_events(world, &mut events, share)
+    });
+    if fidelity < Fidelity::CoarseThoughtDecay {
+        scheduler.time(SystemId::ThoughtDecay, || {
+            decay_thoughts(world, span as u32)
+        });
+    } else if step.is_multiple_of(COARSE_DECAY_TICKS) {
+        scheduler.time(SystemId::ThoughtDecay, || {
+            decay_thoughts(world, (COARSE_DECAY_TICKS * span) as u32)
+        });
+    }
+    scheduler.time(SystemId::ActionSelection, || {
+        select_actions(world, &mut events)
+    });
+    scheduler.time(SystemId::TaskExecution, || {
+        let orcs_before = orc_condition(world);
+        let kin_before = kin_condition(world);
+        execute_tasks(world, &mut events, rng);
+        hold_carried(world);
+        update_orc_temperament(world, &orcs_before);
+        update_kin_temperament(world, &kin_before);
+        world.record_deaths();
+        wash_people(world);
+    });
+    // Scarce food and resource zones grow back with the season
+    regrow_zones(world);
+
+    let blows = events
+        .iter()
+        .filter(|e| matches!(e, SimulationEvent::CombatHit { .. }))
+        .count();
+    world.situation.note_blows(world.current_tick, blows as u32);
+    if let Some(at) = first_blow_at(world, &events) {
+        world.situation.note_place(at);
+    }
 
     // Check win condition after combat resolution
     let outcome = check_win_condition(world);
@@ -228,7 +443,12 @@ pub fn run_simulation_tick(world: &mut World) -> Vec<SimulationEvent> {
 
     // Run production tick for buildings
     let recipes = RecipeCatalog::with_defaults(); // TODO: Load from config
-    let production_results = tick_production(&mut world.buildings, &recipes, &mut world.stockpile);
+    let production_results = tick_production_over(
+        &mut world.buildings,
+        &recipes,
+        &mut world.stockpile,
+        span as f32,
+    );
 
     // Log production completions and generate events
     for result in production_results {
@@ -241,23 +461,203 @@ pub fn run_simulation_tick(world: &mut World) -> Vec<SimulationEvent> {
             building_idx: result.building_idx,
             recipe: result.recipe_id.clone(),
         });
+        deliver_crafted(world, result.building_idx, result.items);
+    }
+
+    // Construction sites post hauling and building jobs; idle humans claim
+    // jobs from the board
+    post_site_jobs(world);
+    assign_jobs(world);
+    // Those who love the fallen come to carry them
+    run_rescue(world);
+    // Multi-step orders move on once the step under way is done
+    for progress in advance_plans(world) {
+        events.push(SimulationEvent::PlanProgress {
+            tick,
+            plan: progress.plan,
+            step: progress.step,
+            steps: progress.steps,
+            message: progress.message,
+        });
     }
+    resent_feast_work(world);
 
     world.tick();
 
     // Daily systems (run once per day)
-    if world.current_tick % TICKS_PER_DAY == 0 {
-        assign_housing(world);
-        consume_food(world);
-        try_population_growth(world);
+    if world.time_scale.new_day(world.current_tick) {
+        scheduler.time(SystemId::Daily, || {
+            run_daily_systems(world, rng, &mut events)
+        });
     }
 
     decay_social_memories(world);
     decay_expectations(world);
 
+    // Over budget, the low-priority work loses detail until ticks are fast again
+    if let Some(fidelity) = scheduler.end_tick(tick_start.elapsed()) {
+        tracing::info!(
+            "Tick {} over budget: fidelity now {}",
+            tick,
+            fidelity.name()
+        );
+    }
+    world.scheduler = scheduler;
     events
 }
 
+/// Everything that runs once a day
+fn run_daily_systems<R: Rng>(world: &mut World, rng: &mut R, events: &mut Vec<SimulationEvent>) {
+    run_weather(world);
+    react_to_weather(world);
+    let omens = run_omens(world);
+    emit_omen_events(world, omens, events);
+    if let Some(era) = run_eras(world) {
+        let tick = world.current_tick;
+        events.push(SimulationEvent::EraBegan { era, tick });
+    }
+    update_exhaustion(world, rng);
+    run_hygiene(world, rng);
+    assign_housing(world);
+    run_husbandry(world, rng);
+    run_roads(world);
+    run_drill(world);
+    run_crossings(world, rng);
+    check_fires(world, rng);
+    run_legacy(world);
+    run_exiles(world, rng);
+    let oaths = run_oaths(world);
+    emit_oath_events(world, &oaths.kept, &oaths.broken, events);
+    run_market(world);
+    run_aspirations(world);
+    let requests = run_requests(world);
+    emit_request_events(world, &requests, events);
+    let hungry = consume_food(world);
+    track_famine(world, hungry);
+    let lifecycle = run_lifecycle(world, rng);
+    let born = try_population_growth(world, rng);
+    emit_lifecycle_events(world, &lifecycle.died, born, events);
+    run_polling(world);
+    run_unrest(world);
+    run_despair(world, rng);
+    run_transformations(world, rng);
+    run_places(world, rng);
+}
+
+/// Where the first blow struck this tick fell: wherever its defender stands
+fn first_blow_at(world: &World, events: &[SimulationEvent]) -> Option<Vec2> {
+    events.iter().find_map(|event| {
+        let SimulationEvent::CombatHit { defender, .. } = event else {
+            return None;
+        };
+        world.archetypes().into_iter().find_map(|archetype| {
+            let parts = archetype.components();
+            let i = parts.names.iter().position(|name| name == defender)?;
+            Some(parts.positions[i])
+        })
+    })
+}
+
+/// Report deaths of old age and births today
+fn emit_lifecycle_events(
+    world: &World,
+    died: &[EntityId],
+    born: Option<EntityId>,
+    events: &mut Vec<SimulationEvent>,
+) {
+    let name_of = |id: EntityId| {
+        world
+            .humans
+            .index_of(id)
+            .map(|i| world.humans.names[i].clone())
+            .unwrap_or_default()
+    };
+    let tick = world.current_tick;
+    for &id in died {
+        events.push(SimulationEvent::DiedOfOldAge {
+            name: name_of(id),
+            tick,
+        });
+    }
+    if let Some(id) = born {
+        events.push(SimulationEvent::Born {
+            name: name_of(id),
+            tick,
+        });
+    }
+}
+
+/// Report omens seen today, and those that passed unheeded
+fn emit_omen_events(world: &World, report: OmenReport, events: &mut Vec<SimulationEvent>) {
+    let tick = world.current_tick;
+    for reading in report.seen {
+        events.push(SimulationEvent::OmenSeen { reading, tick });
+    }
+    for rite in report.unheeded {
+        events.push(SimulationEvent::OmenUnheeded { rite, tick });
+    }
+}
+
+/// Report requests made, granted, and lapsed today
+fn emit_request_events(
+    world: &World,
+    report: &RequestReport,
+    events: &mut Vec<SimulationEvent>,
+) {
+    let tick = world.current_tick;
+    let describe = |id: &RequestId| {
+        let request = world.requests.get(*id).expect("reported requests exist");
+        let asker = world
+            .humans
+            .index_of(request.asker)
+            .map(|i| world.humans.names[i].clone())
+            .unwrap_or_default();
+        (asker, describe_wish(world, request.wish))
+    };
+    for id in &report.made {
+        let (asker, wish) = describe(id);
+        events.push(SimulationEvent::RequestMade { asker, wish, tick });
+    }
+    for id in &report.granted {
+        let (asker, wish) = describe(id);
+        events.push(SimulationEvent::RequestGranted { asker, wish, tick });
+    }
+    for id in &report.lapsed {
+        let (asker, wish) = describe(id);
+        events.push(SimulationEvent::RequestLapsed { asker, wish, tick });
+    }
+}
+
+/// Report oaths that came due today
+fn emit_oath_events(
+    world: &World,
+    kept: &[OathId],
+    broken: &[OathId],
+    events: &mut Vec<SimulationEvent>,
+) {
+    let swearer_name = |id: &OathId| {
+        world
+            .oaths
+            .get(*id)
+            .and_then(|oath| world.humans.index_of(oath.swearer))
+            .map(|i| world.humans.names[i].clone())
+            .unwrap_or_default()
+    };
+    let tick = world.current_tick;
+    for id in kept {
+        events.push(SimulationEvent::OathKept {
+            swearer: swearer_name(id),
+            tick,
+        });
+    }
+    for id in broken {
+        events.push(SimulationEvent::OathBroken {
+            swearer: swearer_name(id),
+            tick,
+        });
+    }
+}
+
 /// Update all entity needs based on time passage
 ///
 /// Needs decay (increase) over time:
@@ -265,58 +665,58 @@ pub fn run_simulation_tick(world: &mut World) -> Vec<SimulationEvent> {
 /// - Food increases steadily
 /// - Social and purpose increase slowly
 /// - Safety decreases naturally when no threats present
+/// - Cold (season and weather) speeds human rest and food, less under a roof
 fn update_needs(world: &mut World) {
-    // Process humans
-    let living_indices: Vec<usize> = world.humans.iter_living().collect();
-    for i in living_indices {
-        let is_restful = world.humans.task_queues[i]
+    // Each entity's needs depend only on its own task and housing, so every
+    // archetype is updated in parallel
+    let chill = current_chill(world);
+    let span = world.time_scale.span();
+    let humans = &mut world.humans;
+    for_each_living(&humans.alive, &mut humans.needs, |i, needs| {
+        let is_restful = humans.task_queues[i]
             .current()
             .map(|t| t.action.is_restful())
             .unwrap_or(true);
         let is_active = !is_restful;
 
         // Homeless entities have accelerated need decay
-        let is_homeless = world.humans.assigned_houses[i].is_none();
+        let is_homeless = humans.assigned_houses[i].is_none();
         let homeless_mult = if is_homeless { 1.5 } else { 1.0 };
-        let dt = 1.0 * homeless_mult;
-
-        world.humans.needs[i].decay(dt, is_active);
-    }
-
-    // Process orcs
-    let orc_indices: Vec<usize> = world.orcs.iter_living().collect();
-    for i in orc_indices {
-        let is_restful = world.orcs.task_queues[i]
-            .current()
-            .map(|t| t.action.is_restful())
-            .unwrap_or(true);
-        let is_active = !is_restful;
-        let dt = 1.0;
-        world.orcs.needs[i].decay(dt, is_active);
-    }
-
-    // Process dwarves
-    let dwarf_indices: Vec<usize> = world.dwarves.iter_living().collect();
-    for i in dwarf_indices {
-        let is_restful = world.dwarves.task_queues[i]
+        let dt = span as f32 * homeless_mult;
+
+        // The cold wears on the body: rest and food needs climb faster
+        let (rest, food) = (needs.rest, needs.food);
+        needs.decay(dt, is_active);
+        let cold = need_decay_multiplier(chill, !is_homeless);
+        needs.rest = (rest + (needs.rest - rest) * cold).min(1.0);
+        needs.food = (food + (needs.food - food) * cold).min(1.0);
+    });
+
+    // Count work toward the day's exhaustion tally
+    for_each_living(&humans.alive, &mut humans.body_states, |i, body| {
+        let is_working = humans.task_queues[i]
             .current()
-            .map(|t| t.action.is_restful())
-            .unwrap_or(true);
-        let is_active = !is_restful;
-        let dt = 1.0;
-        world.dwarves.needs[i].decay(dt, is_active);
-    }
+            .map(|t| t.action.category() == crate::actions::catalog::ActionCategory::Work)
+            .unwrap_or(false);
+        if is_working {
+            body.work_ticks_today += span as u32;
+        }
+    });
 
-    // Process elves
-    let elf_indices: Vec<usize> = world.elves.iter_living().collect();
-    for i in elf_indices {
-        let is_restful = world.elves.task_queues[i]
-            .current()
-            .map(|t| t.action.is_restful())
-            .unwrap_or(true);
-        let is_active = !is_restful;
-        let dt = 1.0;
-        world.elves.needs[i].decay(dt, is_active);
+    // Every other species: needs decay by task alone
+    for archetype in world.archetypes_mut() {
+        if archetype.species() == Species::Human {
+            continue;
+        }
+        let parts = archetype.components_mut();
+        let task_queues: &[TaskQueue] = parts.task_queues;
+        for_each_living(parts.alive, parts.needs, |i, needs| {
+            let is_restful = task_queues[i]
+                .current()
+                .map(|t| t.action.is_restful())
+                .unwrap_or(true);
+            needs.decay(span as f32, !is_restful);
+        });
     }
 }
 
@@ -324,16 +724,15 @@ fn update_needs(world: &mut World) {
 ///
 /// Called at start of tick to reset attention for new decision period.
 fn refresh_all_attention(world: &mut World) {
-    // Process humans
-    let living_indices: Vec<usize> = world.humans.iter_living().collect();
-    for i in living_indices {
-        let fatigue = world.humans.body_states[i].fatigue;
-        let pain = world.humans.body_states[i].pain;
+    let humans = &mut world.humans;
+    for_each_living(&humans.alive, &mut humans.chunk_libraries, |i, library| {
+        let fatigue = humans.body_states[i].fatigue;
+        let pain = humans.body_states[i].pain;
         // Use 0.0 for stress until stress system is added
         let stress = 0.0;
 
-        refresh_attention(&mut world.humans.chunk_libraries[i], fatigue, pain, stress);
-    }
+        refresh_attention(library, fatigue, pain, stress);
+    });
 
     // TODO: Add refresh for other species when they have chunk_libraries
 }
@@ -341,37 +740,47 @@ fn refresh_all_attention(world: &mut World) {
 /// Run perception and return both perceptions and the ranges used (for event logging)
 ///
 /// This function now includes cross-species perception:
-/// - Humans can perceive both other humans AND orcs
+/// - Humans can perceive anyone of any species in the world
 /// - Orcs are always perceived as threats (threat_level = 0.9)
 /// - Disposition from social memory affects threat_level (hostile = 0.7)
 fn run_perception_with_ranges(
     world: &World,
+    max_range: f32,
 ) -> (Vec<crate::simulation::perception::Perception>, Vec<f32>) {
     use crate::simulation::perception::{PerceivedEntity, Perception};
     use crate::entity::social::Disposition;
 
     let mut grid = SparseHashGrid::new(10.0);
 
-    // Collect positions and IDs for spatial queries - INCLUDE BOTH HUMANS AND ORCS
+    // Humans observe; anyone of any species can be observed
     let human_positions: Vec<_> = world.humans.positions.iter().cloned().collect();
     let human_ids: Vec<_> = world.humans.ids.iter().cloned().collect();
-    let orc_positions: Vec<_> = world.orcs.positions.iter().cloned().collect();
-    let orc_ids: Vec<_> = world.orcs.ids.iter().cloned().collect();
-
-    // Combine all entities into single spatial grid for cross-species perception
-    let all_entities: Vec<_> = human_ids.iter().cloned().zip(human_positions.iter().cloned())
-        .chain(orc_ids.iter().cloned().zip(orc_positions.iter().cloned()))
+    let targets: Vec<(EntityId, Vec2, Species)> = world
+        .archetypes()
+        .into_iter()
+        .flat_map(|archetype| {
+            let species = archetype.species();
+            let parts = archetype.components();
+            parts
+                .ids
+                .iter()
+                .zip(parts.positions)
+                .map(move |(&id, &pos)| (id, pos, species))
+        })
         .collect();
-    grid.rebuild(all_entities.into_iter());
+
+    // Every species shares one spatial grid for cross-species perception
+    grid.rebuild(targets.iter().map(|&(id, pos, _)| (id, pos)));
 
     // Collect social memories for perception lookup
     let social_memories: Vec<_> = world.humans.social_memories.iter().cloned().collect();
 
-    // Build lookups for entity type and position
-    let id_to_human_idx: ahash::AHashMap<crate::core::types::EntityId, usize> =
-        human_ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
-    let id_to_orc_idx: ahash::AHashMap<crate::core::types::EntityId, usize> =
-        orc_ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+    // Build lookup from entity to its target entry
+    let id_to_target: ahash::AHashMap<EntityId, usize> = targets
+        .iter()
+        .enumerate()
+        .map(|(t, &(id, _, _))| (id, t))
+        .collect();
 
     // Compute per-entity perception ranges
     // IdleObserve grants 1.5x perception range
@@ -396,81 +805,82 @@ fn run_perception_with_ranges(
             // Apply fatigue penalty: 20% reduction when fatigue > 0.7
             let fatigue = world.humans.body_states[i].fatigue;
             crate::simulation::perception::effective_visual_range(base, fatigue, 1.0, 1.0)
+                .min(max_range)
         })
         .collect();
 
-    // Build perceptions with threat level computation
-    let mut perceptions: Vec<Perception> = human_ids
+    // Darkness hides entities: each target is seen out to the observer's
+    // range scaled by the light it stands in, so a torch-bearer stands out
+    let ambient = world.astronomy.light_level;
+    let target_visibility: Vec<f32> = targets
         .iter()
-        .enumerate()
-        .map(|(i, &observer_id)| {
-            let observer_pos = human_positions[i];
-            let observer_memory = &social_memories[i];
-            let perception_range = perception_ranges[i];
+        .map(|&(_, pos, _)| visibility(world.lights.light_at(pos, ambient)))
+        .collect();
 
-            let nearby: Vec<_> = grid
-                .query_neighbors(observer_pos)
-                .filter(|&e| e != observer_id)
-                .collect();
+    // Build perceptions with threat level computation (in parallel: each
+    // observer only reads the world)
+    let mut perceptions: Vec<Perception> = map(&human_ids, |i, &observer_id| {
+        let observer_pos = human_positions[i];
+        let observer_memory = &social_memories[i];
+        let perception_range = perception_ranges[i];
 
-            let perceived_entities: Vec<_> = nearby
-                .iter()
-                .filter_map(|&entity| {
-                    // Get entity position from appropriate archetype
-                    let (entity_pos, is_orc) = if let Some(&idx) = id_to_human_idx.get(&entity) {
-                        (human_positions[idx], false)
-                    } else if let Some(&idx) = id_to_orc_idx.get(&entity) {
-                        (orc_positions[idx], true)
-                    } else {
-                        return None;
-                    };
+        let nearby: Vec<_> = grid
+            .query_neighbors(observer_pos)
+            .filter(|&e| e != observer_id)
+            .collect();
 
-                    let distance = observer_pos.distance(&entity_pos);
+        let perceived_entities: Vec<_> = nearby
+            .iter()
+            .filter_map(|&entity| {
+                let &t = id_to_target.get(&entity)?;
+                let (_, entity_pos, species) = targets[t];
+                let entity_visibility = target_visibility[t];
 
-                    if distance <= perception_range {
-                        // Look up disposition from social memory
-                        let disposition = observer_memory.get_disposition(entity);
+                let distance = observer_pos.distance(&entity_pos);
 
-                        // Compute threat level based on species and disposition
-                        let threat_level = if is_orc {
-                            // Orcs are always high threat to humans
-                            0.9
-                        } else {
-                            // Disposition-based threat for humans
-                            match disposition {
-                                Disposition::Hostile => 0.7,
-                                Disposition::Suspicious => 0.3,
-                                _ => 0.0,
-                            }
-                        };
+                if distance <= perception_range * entity_visibility {
+                    // Look up disposition from social memory
+                    let disposition = observer_memory.get_disposition(entity);
 
-                        Some(PerceivedEntity {
-                            entity,
-                            distance,
-                            relationship: crate::simulation::perception::RelationshipType::Unknown,
-                            disposition,
-                            threat_level,
-                            notable_features: vec![],
-                        })
+                    // Compute threat level based on species and disposition
+                    let threat_level = if species == Species::Orc {
+                        // Orcs are always high threat to humans
+                        0.9
                     } else {
-                        None
-                    }
-                })
-                .collect();
+                        // Disposition-based threat for everyone else
+                        match disposition {
+                            Disposition::Hostile => 0.7,
+                            Disposition::Suspicious => 0.3,
+                            _ => 0.0,
+                        }
+                    };
 
-            Perception {
-                observer: observer_id,
-                perceived_entities,
-                perceived_objects: vec![],
-                perceived_events: vec![],
-                nearest_food_zone: None,
-                nearest_building_site: None,
-            }
-        })
-        .collect();
+                    Some(PerceivedEntity {
+                        entity,
+                        distance,
+                        relationship: crate::simulation::perception::RelationshipType::Unknown,
+                        disposition,
+                        threat_level,
+                        notable_features: vec![],
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Perception {
+            observer: observer_id,
+            perceived_entities,
+            perceived_objects: vec![],
+            perceived_events: vec![],
+            nearest_food_zone: None,
+            nearest_building_site: None,
+        }
+    });
 
     // Populate nearest_food_zone, nearest_building_site, and perceived_objects
-    for (i, perception) in perceptions.iter_mut().enumerate() {
+    for_each_mut(&mut perceptions, |i, perception| {
         perception.nearest_food_zone =
             find_nearest_food_zone(human_positions[i], perception_ranges[i], &world.food_zones);
         perception.nearest_building_site =
@@ -519,7 +929,7 @@ fn run_perception_with_ranges(
                 }
             })
             .collect();
-    }
+    });
 
     (perceptions, perception_ranges)
 }
@@ -856,6 +1266,19 @@ fn generate_thoughts_with_events(
                     (world.humans.needs[idx].safety + perceived.threat_level * 0.3).min(1.0);
             }
 
+            // Filthy neighbours close by are hard to ignore
+            if perceived.distance <= DISGUST_RANGE {
+                if let Some(&other) = id_to_idx.get(&perceived.entity) {
+                    let hygiene = world.humans.needs[other].hygiene;
+                    if hygiene >= FILTHY_THRESHOLD {
+                        let mut thought =
+                            disgust_thought(&world.humans.names[other], hygiene, current_tick);
+                        thought.cause_entity = Some(perceived.entity);
+                        world.humans.thoughts[idx].add(thought);
+                    }
+                }
+            }
+
             // E1: Generate positive thoughts from friendly entities
             if perceived.relationship == RelationshipType::Ally
                 || matches!(
@@ -1063,9 +1486,14 @@ fn generate_thoughts(world: &mut World, perceptions: &[crate::simulation::percep
 /// - Intense (intensity >= THOUGHT_MEMORY_THRESHOLD)
 /// - About another entity (cause_entity is Some)
 ///
-/// These thoughts are converted to memories via record_encounter().
+/// These thoughts are converted to memories via record_encounter(), for the
+/// humans in `share`.
 /// Emits SocialMemoryUpdate and DispositionChange events (sampled to avoid log spam).
-fn convert_thoughts_to_memories_with_events(world: &mut World, events: &mut Vec<SimulationEvent>) {
+fn convert_thoughts_to_memories_with_events(
+    world: &mut World,
+    events: &mut Vec<SimulationEvent>,
+    share: Share,
+) {
     const THOUGHT_MEMORY_THRESHOLD: f32 = 0.7;
 
     let current_tick = world.current_tick;
@@ -1074,7 +1502,11 @@ fn convert_thoughts_to_memories_with_events(world: &mut World, events: &mut Vec<
     let mut memory_log_count = 0;
     const MAX_MEMORY_LOGS: usize = 30;
 
-    let living_indices: Vec<usize> = world.humans.iter_living().collect();
+    let living_indices: Vec<usize> = world
+        .humans
+        .iter_living()
+        .filter(|&i| share.includes(i))
+        .collect();
 
     // Build ID -> name lookup for target names
     let id_to_idx: ahash::AHashMap<crate::core::types::EntityId, usize> = world
@@ -1172,28 +1604,55 @@ fn convert_thoughts_to_memories_with_events(world: &mut World, events: &mut Vec<
 /// Decay all thoughts over time
 ///
 /// Thoughts naturally fade, and faded thoughts are removed from the buffer.
-fn decay_thoughts(world: &mut World) {
-    // Decay human thoughts
-    let living_indices: Vec<usize> = world.humans.iter_living().collect();
-    for i in living_indices {
-        world.humans.thoughts[i].decay_all();
-    }
-
-    // Decay orc thoughts
-    let orc_indices: Vec<usize> = world.orcs.iter_living().collect();
-    for i in orc_indices {
-        world.orcs.thoughts[i].decay_all();
+fn decay_thoughts(world: &mut World, ticks: u32) {
+    for archetype in world.archetypes_mut() {
+        let parts = archetype.components_mut();
+        for_each_living(parts.alive, parts.thoughts, |_, thoughts| {
+            thoughts.decay_all_over(ticks)
+        });
     }
 }
 
-/// Threshold for parallelization (below this, sequential is faster due to thread overhead)
-const PARALLEL_THRESHOLD: usize = 1000;
+// Number of simulation ticks per day, from the clock every layer shares.
+// Used for time-based systems like memory decay that should run once per day.
+pub use crate::core::time::TICKS_PER_DAY;
+
+/// Perception range while the tick budget is cutting distant perception
+const NEAR_PERCEPTION_RANGE: f32 = 20.0;
+
+/// Ticks between thought decay passes while the tick budget is coarsening it
+const COARSE_DECAY_TICKS: u64 = 4;
+
+/// Movement cost of walking through blocked ground (wading a stream, climbing a ledge)
+const WADING_COST: f32 = 4.0;
+
+/// A human's chosen task, whether it interrupts idling, and the candidates it
+/// was weighed against
+type SelectedAction = (usize, Option<Task>, bool, Option<Vec<Candidate>>);
 
-/// Number of simulation ticks per day
+/// How close a sleeper must get to their bed before resting
+const ZONE_ARRIVAL_RANGE: f32 = 1.0;
+
+/// Send a human with nowhere to sleep to the nearest dormitory zone
 ///
-/// Used for time-based systems like memory decay that should run once per day.
-/// At 1000 ticks per day, daily events happen roughly every 1000 ticks.
-pub const TICKS_PER_DAY: u64 = 1000;
+/// Only rest with no place of its own is redirected, and only for the
+/// homeless: those with a house, or already in a dormitory, rest where
+/// they are.
+fn bed_down_in_dormitory(world: &World, i: usize, task: &mut Task) {
+    if task.action != ActionId::Rest
+        || task.target_position.is_some()
+        || world.humans.assigned_houses[i].is_some()
+    {
+        return;
+    }
+    let pos = world.humans.positions[i];
+    if world.zones.contains(ZoneKind::Dormitory, pos) {
+        return;
+    }
+    if let Some(dormitory) = world.zones.nearest(ZoneKind::Dormitory, pos) {
+        task.target_position = Some(dormitory.shape.center());
+    }
+}
 
 /// Select actions for entities without current tasks (PARALLEL when beneficial)
 ///
@@ -1205,7 +1664,13 @@ pub const TICKS_PER_DAY: u64 = 1000;
 ///
 /// Generates TaskStarted events for new tasks.
 fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
-    let living_indices: Vec<usize> = world.humans.iter_living().collect();
+    // The player decides for whoever they are embodying
+    let avatar = world.avatar.as_ref().map(|a| a.entity);
+    let living_indices: Vec<usize> = world
+        .humans
+        .iter_living()
+        .filter(|&i| Some(world.humans.ids[i]) != avatar)
+        .collect();
     let current_tick = world.current_tick;
 
     // Build spatial grid for nearby entity queries
@@ -1234,7 +1699,7 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
     if living_indices.len() >= PARALLEL_THRESHOLD {
         // PARALLEL path for large entity counts
-        let selected_actions: Vec<(usize, Option<Task>, bool)> = living_indices
+        let selected_actions: Vec<SelectedAction> = living_indices
             .par_iter()
             .filter_map(|&i| {
                 // Check if entity has a task that should NOT be interrupted
@@ -1252,7 +1717,8 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 let pos = world.humans.positions[i];
                 let observer_id = world.humans.ids[i];
 
-                // Early threat detection - check for nearby orcs BEFORE skip condition
+                // Early threat detection - check for nearby orcs and members of hostile
+                // factions BEFORE skip condition
                 // This ensures entities can react to threats even during idle tasks
                 let threat_detected = grid
                     .query_neighbors(pos)
@@ -1266,6 +1732,10 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
                             } else {
                                 false
                             }
+                        } else if world.factions.are_hostile(observer_id, entity) {
+                            id_to_human_idx.get(&entity).is_some_and(|&idx| {
+                                pos.distance(&human_positions[idx]) <= perception_range
+                            })
                         } else {
                             false
                         }
@@ -1318,13 +1788,17 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 None
                             }
                         } else {
-                            // Human entity - use social memory
+                            // Human entity - hostile faction, else social memory
                             let entity_idx = *id_to_human_idx.get(&entity)?;
                             let entity_pos = human_positions[entity_idx];
                             let distance = pos.distance(&entity_pos);
                             if distance <= perception_range {
-                                let disposition =
-                                    world.humans.social_memories[i].get_disposition(entity);
+                                let hostile = world.factions.are_hostile(observer_id, entity);
+                                let disposition = if hostile {
+                                    Disposition::Hostile
+                                } else {
+                                    world.humans.social_memories[i].get_disposition(entity)
+                                };
                                 Some((entity, disposition))
                             } else {
                                 None
@@ -1353,15 +1827,29 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
                     perceived_dispositions,
                     building_skill: world.humans.building_skills[i],
                     nearest_building_site,
+                    aspiration: world.humans.aspirations[i]
+                        .as_ref()
+                        .and_then(|a| a.pursued()),
                 };
                 // Flag indicating we should clear an existing task before adding new one
                 let should_clear_idle = has_task && has_critical_need;
-                Some((i, select_action_human(&ctx), should_clear_idle))
+                let candidates = world
+                    .decision_trace
+                    .as_ref()
+                    .filter(|trace| trace.is_watched(observer_id))
+                    .map(|_| evaluate_human(&ctx));
+                Some((i, select_action_human(&ctx), should_clear_idle, candidates))
             })
             .collect();
 
-        for (i, task_opt, should_clear_idle) in selected_actions {
-            if let Some(task) = task_opt {
+        for (i, task_opt, should_clear_idle, candidates) in selected_actions {
+            if let Some(candidates) = candidates {
+                record_decision(world, i, candidates, task_opt.as_ref().map(|t| t.action));
+            }
+            if let Some(mut task) = task_opt {
+                bed_down_in_dormitory(world, i, &mut task);
+                heed_omens(world, &mut task);
+                withdraw_in_despair(world, i, &mut task);
                 // Clear existing idle task if interrupting for critical need
                 if should_clear_idle {
                     world.humans.task_queues[i].clear();
@@ -1399,7 +1887,8 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
             let pos = world.humans.positions[i];
             let observer_id = world.humans.ids[i];
 
-            // Early threat detection - check for nearby orcs BEFORE skip condition
+            // Early threat detection - check for nearby orcs and members of hostile
+            // factions BEFORE skip condition
             // This ensures entities can react to threats even during idle tasks
             let threat_detected = grid
                 .query_neighbors(pos)
@@ -1413,6 +1902,10 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
                         } else {
                             false
                         }
+                    } else if world.factions.are_hostile(observer_id, entity) {
+                        id_to_human_idx.get(&entity).is_some_and(|&idx| {
+                            pos.distance(&human_positions[idx]) <= perception_range
+                        })
                     } else {
                         false
                     }
@@ -1452,12 +1945,17 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
                             None
                         }
                     } else {
-                        // Human entity - use social memory
+                        // Human entity - hostile faction, else social memory
                         let entity_idx = *id_to_human_idx.get(&entity)?;
                         let entity_pos = human_positions[entity_idx];
                         let distance = pos.distance(&entity_pos);
                         if distance <= perception_range {
-                            let disposition = world.humans.social_memories[i].get_disposition(entity);
+                            let hostile = world.factions.are_hostile(observer_id, entity);
+                            let disposition = if hostile {
+                                Disposition::Hostile
+                            } else {
+                                world.humans.social_memories[i].get_disposition(entity)
+                            };
                             Some((entity, disposition))
                         } else {
                             None
@@ -1485,8 +1983,23 @@ fn select_actions(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 perceived_dispositions,
                 building_skill: world.humans.building_skills[i],
                 nearest_building_site,
+                aspiration: world.humans.aspirations[i]
+                    .as_ref()
+                    .and_then(|a| a.pursued()),
             };
-            if let Some(task) = select_action_human(&ctx) {
+            let candidates = world
+                .decision_trace
+                .as_ref()
+                .filter(|trace| trace.is_watched(observer_id))
+                .map(|_| evaluate_human(&ctx));
+            let selected = select_action_human(&ctx);
+            if let Some(candidates) = candidates {
+                record_decision(world, i, candidates, selected.as_ref().map(|t| t.action));
+            }
+            if let Some(mut task) = selected {
+                bed_down_in_dormitory(world, i, &mut task);
+                heed_omens(world, &mut task);
+                withdraw_in_despair(world, i, &mut task);
                 // Clear existing idle task if interrupting for critical need
                 if has_idle_task && has_critical_need {
                     world.humans.task_queues[i].clear();
@@ -1524,9 +2037,12 @@ fn select_orc_actions(world: &mut World, current_tick: u64) {
     let human_positions: Vec<_> = world.humans.positions.iter().cloned().collect();
     let human_ids: Vec<_> = world.humans.ids.iter().cloned().collect();
 
-    // Combine all entities into the grid
+    // Combine all entities into the grid; dwarves and elves are in it for
+    // each other's sake, orcs pass them over below
     let all_entities: Vec<_> = orc_ids.iter().cloned().zip(orc_positions.iter().cloned())
         .chain(human_ids.iter().cloned().zip(human_positions.iter().cloned()))
+        .chain(world.dwarves.ids.iter().cloned().zip(world.dwarves.positions.iter().cloned()))
+        .chain(world.elves.ids.iter().cloned().zip(world.elves.positions.iter().cloned()))
         .collect();
     grid.rebuild(all_entities.into_iter());
 
@@ -1601,188 +2117,128 @@ fn select_orc_actions(world: &mut World, current_tick: u64) {
         }
     }
 
-    // Action selection for dwarves
-    let dwarf_indices: Vec<usize> = world.dwarves.iter_living().collect();
-    let dwarf_positions: Vec<_> = world.dwarves.positions.iter().cloned().collect();
-    let dwarf_ids: Vec<_> = world.dwarves.ids.iter().cloned().collect();
-    let dwarf_id_to_idx: ahash::AHashMap<crate::core::types::EntityId, usize> =
-        dwarf_ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+    // Dwarves and elves choose by their own values, seeing and regarding
+    // others by their species rules
+    select_species_actions(world, &grid, Species::Dwarf, |world, i, seen| {
+        select_action_dwarf(&DwarfSelectionContext {
+            body: &world.dwarves.body_states[i],
+            needs: &world.dwarves.needs[i],
+            thoughts: &world.dwarves.thoughts[i],
+            values: &world.dwarves.values[i],
+            has_current_task: false,
+            threat_nearby: world.dwarves.needs[i].safety > 0.5,
+            food_available: seen.food_available,
+            safe_location: world.dwarves.needs[i].safety < 0.3,
+            entity_nearby: !seen.perceived_dispositions.is_empty(),
+            current_tick,
+            nearest_food_zone: seen.nearest_food_zone,
+            perceived_dispositions: seen.perceived_dispositions,
+        })
+    });
+    select_species_actions(world, &grid, Species::Elf, |world, i, seen| {
+        select_action_elf(&ElfSelectionContext {
+            body: &world.elves.body_states[i],
+            needs: &world.elves.needs[i],
+            thoughts: &world.elves.thoughts[i],
+            values: &world.elves.values[i],
+            has_current_task: false,
+            threat_nearby: world.elves.needs[i].safety > 0.5,
+            food_available: seen.food_available,
+            safe_location: world.elves.needs[i].safety < 0.3,
+            entity_nearby: !seen.perceived_dispositions.is_empty(),
+            current_tick,
+            nearest_food_zone: seen.nearest_food_zone,
+            perceived_dispositions: seen.perceived_dispositions,
+        })
+    });
+}
+
+/// What one entity sees before choosing a task
+struct Surroundings {
+    food_available: bool,
+    nearest_food_zone: Option<(u32, Vec2, f32)>,
+    perceived_dispositions: Vec<(EntityId, Disposition)>,
+}
+
+/// Choose tasks for the living of a species, each with `select`
+///
+/// Those with no task choose, and so do those only idling: anything more
+/// pressing replaces the idling, as for humans. Each sees as far as its
+/// species' `[perception]` rules say, passes over its own kind, and regards
+/// the rest as those rules say or else by its memory of each one.
+fn select_species_actions(
+    world: &mut World,
+    grid: &SparseHashGrid,
+    species: Species,
+    select: impl Fn(&World, usize, Surroundings) -> Option<Task>,
+) {
+    let Some(parts) = world.archetype(species).map(|a| a.components()) else {
+        return;
+    };
+    let range = world.species_rules.perception_range(species);
 
-    for &i in &dwarf_indices {
-        if world.dwarves.task_queues[i].current().is_some() {
+    let mut chosen = Vec::new();
+    for i in parts.living() {
+        let idling = parts.task_queues[i]
+            .current()
+            .map(|t| t.action.category() == ActionCategory::Idle);
+        if idling == Some(false) {
             continue;
         }
+        let pos = parts.positions[i];
 
-        let pos = dwarf_positions[i];
-        let perception_range = 50.0;
-
-        // Check for nearby food zones
-        let (food_available, nearest_food_zone) = {
-            let mut nearest: Option<(u32, crate::core::types::Vec2, f32)> = None;
-            let mut food_avail = false;
+        let food_available = world
+            .food_zones
+            .iter()
+            .any(|zone| zone.position.distance(&pos) <= zone.radius);
+        let nearest_food_zone = world
+            .food_zones
+            .iter()
+            .map(|zone| (zone.id, zone.position, zone.position.distance(&pos)))
+            .filter(|&(_, _, distance)| distance <= range)
+            .min_by(|a, b| a.2.total_cmp(&b.2));
 
-            for zone in &world.food_zones {
-                let dist = zone.position.distance(&pos);
-                if dist <= zone.radius {
-                    food_avail = true;
-                }
-                if dist <= perception_range {
-                    if nearest.is_none() || dist < nearest.unwrap().2 {
-                        nearest = Some((zone.id, zone.position, dist));
-                    }
-                }
-            }
-
-            (food_avail, nearest)
-        };
-
-        // Build perceived dispositions - similar to orcs but dwarves are less aggressive
-        let nearby_entities: Vec<_> = grid
+        let perceived_dispositions = grid
             .query_neighbors(pos)
-            .filter(|&e| !dwarf_ids.contains(&e))
-            .collect();
-
-        let perceived_dispositions: Vec<(crate::core::types::EntityId, crate::entity::social::Disposition)> = nearby_entities
-            .iter()
-            .filter_map(|&entity| {
-                if let Some(&entity_idx) = orc_id_to_idx.get(&entity) {
-                    let entity_pos = orc_positions[entity_idx];
-                    let distance = pos.distance(&entity_pos);
-                    if distance <= perception_range {
-                        // Dwarves view orcs as hostile
-                        return Some((entity, crate::entity::social::social_memory::Disposition::Hostile));
-                    }
-                }
-                if let Some(&entity_idx) = human_id_to_idx.get(&entity) {
-                    let entity_pos = human_positions[entity_idx];
-                    let distance = pos.distance(&entity_pos);
-                    if distance <= perception_range {
-                        let disposition = world.dwarves.social_memories[i].get_disposition(entity);
-                        return Some((entity, disposition));
-                    }
+            .filter_map(|entity| {
+                let (other, j) = world.get_entity_info(entity)?;
+                if other == species {
+                    return None;
                 }
-                if let Some(&entity_idx) = dwarf_id_to_idx.get(&entity) {
-                    let entity_pos = dwarf_positions[entity_idx];
-                    let distance = pos.distance(&entity_pos);
-                    if distance <= perception_range {
-                        // Dwarves view other dwarves favorably
-                        return Some((entity, crate::entity::social::social_memory::Disposition::Favorable));
-                    }
+                let other_pos = world.archetype(other)?.components().positions[j];
+                if pos.distance(&other_pos) > range {
+                    return None;
                 }
-                None
+                let disposition = world
+                    .species_rules
+                    .sighted_disposition(species, other)
+                    .unwrap_or_else(|| parts.social_memories[i].get_disposition(entity));
+                Some((entity, disposition))
             })
             .collect();
 
-        let ctx = DwarfSelectionContext {
-            body: &world.dwarves.body_states[i],
-            needs: &world.dwarves.needs[i],
-            thoughts: &world.dwarves.thoughts[i],
-            values: &world.dwarves.values[i],
-            has_current_task: false,
-            threat_nearby: world.dwarves.needs[i].safety > 0.5,
+        let seen = Surroundings {
             food_available,
-            safe_location: world.dwarves.needs[i].safety < 0.3,
-            entity_nearby: !perceived_dispositions.is_empty(),
-            current_tick,
             nearest_food_zone,
             perceived_dispositions,
         };
-
-        if let Some(task) = select_action_dwarf(&ctx) {
-            world.dwarves.task_queues[i].push(task);
+        if let Some(task) = select(world, i, seen) {
+            if idling == Some(true) && task.action.category() == ActionCategory::Idle {
+                continue;
+            }
+            chosen.push((i, idling.is_some(), task));
         }
     }
 
-    // Action selection for elves
-    let elf_indices: Vec<usize> = world.elves.iter_living().collect();
-    let elf_positions: Vec<_> = world.elves.positions.iter().cloned().collect();
-    let elf_ids: Vec<_> = world.elves.ids.iter().cloned().collect();
-    let elf_id_to_idx: ahash::AHashMap<crate::core::types::EntityId, usize> =
-        elf_ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
-
-    for &i in &elf_indices {
-        if world.elves.task_queues[i].current().is_some() {
-            continue;
-        }
-
-        let pos = elf_positions[i];
-        let perception_range = 60.0; // Elves have better perception
-
-        // Check for nearby food zones
-        let (food_available, nearest_food_zone) = {
-            let mut nearest: Option<(u32, crate::core::types::Vec2, f32)> = None;
-            let mut food_avail = false;
-
-            for zone in &world.food_zones {
-                let dist = zone.position.distance(&pos);
-                if dist <= zone.radius {
-                    food_avail = true;
-                }
-                if dist <= perception_range {
-                    if nearest.is_none() || dist < nearest.unwrap().2 {
-                        nearest = Some((zone.id, zone.position, dist));
-                    }
-                }
-            }
-
-            (food_avail, nearest)
-        };
-
-        // Build perceived dispositions - elves are aloof
-        let nearby_entities: Vec<_> = grid
-            .query_neighbors(pos)
-            .filter(|&e| !elf_ids.contains(&e))
-            .collect();
-
-        let perceived_dispositions: Vec<(crate::core::types::EntityId, crate::entity::social::Disposition)> = nearby_entities
-            .iter()
-            .filter_map(|&entity| {
-                if let Some(&entity_idx) = orc_id_to_idx.get(&entity) {
-                    let entity_pos = orc_positions[entity_idx];
-                    let distance = pos.distance(&entity_pos);
-                    if distance <= perception_range {
-                        // Elves view orcs as hostile
-                        return Some((entity, crate::entity::social::social_memory::Disposition::Hostile));
-                    }
-                }
-                if let Some(&entity_idx) = human_id_to_idx.get(&entity) {
-                    let entity_pos = human_positions[entity_idx];
-                    let distance = pos.distance(&entity_pos);
-                    if distance <= perception_range {
-                        let disposition = world.elves.social_memories[i].get_disposition(entity);
-                        return Some((entity, disposition));
-                    }
-                }
-                if let Some(&entity_idx) = elf_id_to_idx.get(&entity) {
-                    let entity_pos = elf_positions[entity_idx];
-                    let distance = pos.distance(&entity_pos);
-                    if distance <= perception_range {
-                        // Elves view other elves favorably
-                        return Some((entity, crate::entity::social::social_memory::Disposition::Favorable));
-                    }
-                }
-                None
-            })
-            .collect();
-
-        let ctx = ElfSelectionContext {
-            body: &world.elves.body_states[i],
-            needs: &world.elves.needs[i],
-            thoughts: &world.elves.thoughts[i],
-            values: &world.elves.values[i],
-            has_current_task: false,
-            threat_nearby: world.elves.needs[i].safety > 0.5,
-            food_available,
-            safe_location: world.elves.needs[i].safety < 0.3,
-            entity_nearby: !perceived_dispositions.is_empty(),
-            current_tick,
-            nearest_food_zone,
-            perceived_dispositions,
-        };
-
-        if let Some(task) = select_action_elf(&ctx) {
-            world.elves.task_queues[i].push(task);
+    let Some(archetype) = world.archetype_mut(species) else {
+        return;
+    };
+    let task_queues = archetype.components_mut().task_queues;
+    for (i, was_idling, task) in chosen {
+        if was_idling {
+            task_queues[i].clear();
         }
+        task_queues[i].push(task);
     }
 }
 
@@ -1807,7 +2263,9 @@ fn select_orc_actions(world: &mut World, current_tick: u64) {
 /// - Eat action: consumes from food zone, satisfies at `consumed * 0.5`
 ///
 /// Generates TaskCompleted and CombatHit events.
-fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
+fn execute_tasks<R: Rng>(world: &mut World, events: &mut Vec<SimulationEvent>, rng: &mut R) {
+    // Per-tick speeds and work are applied once per step
+    let span = world.time_scale.span() as f32;
     // Collect indices first to avoid borrow conflicts
     let living_indices: Vec<usize> = world.humans.iter_living().collect();
     for i in living_indices {
@@ -1878,7 +2336,12 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
         let combat_target_info: Option<(crate::core::types::EntityId, CombatTarget)> = {
             if let Some(task) = world.humans.task_queues[i].current() {
                 if task.action == ActionId::Attack {
-                    if let Some(target_id) = task.target_entity {
+                    // Members of allied factions will not come to blows
+                    let attacker_id = world.humans.ids[i];
+                    if let Some(target_id) = task
+                        .target_entity
+                        .filter(|&target| !world.factions.are_allied(attacker_id, target))
+                    {
                         // Check humans first
                         if let Some(defender_idx) = world.humans.index_of(target_id) {
                             Some((target_id, CombatTarget::Human(defender_idx)))
@@ -1900,11 +2363,29 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
             }
         };
 
+        // Carrying moves the carried body as well as the carrier, so it is
+        // stepped here with the whole world to hand
+        let carry_done: Option<bool> = match world.humans.task_queues[i].current() {
+            Some(task) if task.action == ActionId::Carry => {
+                let mut task = task.clone();
+                let done = carry_step(world, i, &mut task);
+                if let Some(current) = world.humans.task_queues[i].current_mut() {
+                    current.progress = task.progress;
+                }
+                Some(done)
+            }
+            _ => None,
+        };
+
         // Get task info and execute based on action category
         // Note: Helper functions cannot be extracted due to Rust's borrowing rules -
         // the closure captures world mutably for task access, so we dispatch inline.
         use crate::actions::catalog::ActionCategory;
 
+        // Those at a slowdown, or under a dire omen, put less into their work;
+        // the era sets the pace for everyone
+        let pace = work_pace(world, i) * world.omens.work_pace() * world.eras.work_pace;
+        let mut slew_orc = false;
         let task_info = world.humans.task_queues[i].current_mut().map(|task| {
             let action = task.action;
             let target_pos = task.target_position;
@@ -1932,9 +2413,22 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
                         if let Some(target) = target_pos {
                             let current = world.humans.positions[i];
-                            let direction = (target - current).normalize();
-                            let base_speed = 2.0;
-                            let actual_speed = base_speed * speed_modifier;
+                            let base_speed = 2.0 * span;
+                            // Ramps slow the climb; impassable ground means wading or scrambling
+                            let terrain_cost = world
+                                .blocked_cells
+                                .position_movement_cost(glam::Vec2::new(current.x, current.y))
+                                .min(WADING_COST);
+                            // Leg wounds slow every step
+                            let actual_speed = base_speed
+                                * speed_modifier
+                                * world.roads.speed_multiplier_at(current)
+                                * movement_multiplier(world.weather.current_weather)
+                                * world.humans.combat_states[i].gait().speed_multiplier()
+                                / terrain_cost;
+
+                            // Every step wears the ground toward a trail
+                            world.roads.record_traffic(current);
 
                             let distance = current.distance(&target);
                             if distance < actual_speed {
@@ -1952,9 +2446,13 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
                                 true // Arrived
                             } else {
-                                if direction.length() > 0.0 {
-                                    world.humans.positions[i] = current + direction * actual_speed;
-                                }
+                                // Route around anything standing in the way
+                                world.humans.positions[i] = world.paths.step_toward(
+                                    &world.blocked_cells,
+                                    current,
+                                    target,
+                                    actual_speed,
+                                );
                                 false // Still moving
                             }
                         } else {
@@ -1978,13 +2476,15 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
                         if let Some(threat_pos) = target_pos {
                             let current = world.humans.positions[i];
-                            let away = (current - threat_pos).normalize(); // Move AWAY from target
-                            let base_speed = 3.0; // Higher base speed for fleeing (adrenaline)
-                            let actual_speed = base_speed * speed_modifier;
+                            let base_speed = 3.0 * span; // Higher base speed for fleeing (adrenaline)
+                            let actual_speed = base_speed
+                                * speed_modifier
+                                * movement_multiplier(world.weather.current_weather)
+                                * world.humans.combat_states[i].gait().speed_multiplier();
 
-                            if away.length() > 0.0 {
-                                world.humans.positions[i] = current + away * actual_speed;
-                            }
+                            // Move AWAY from target, veering off anything in the way
+                            world.humans.positions[i] =
+                                step_away(&world.blocked_cells, current, threat_pos, actual_speed);
 
                             // Record fleeing experience
                             if !skill_result.chunks_used.is_empty() {
@@ -2017,8 +2517,10 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                             if target_exists {
                                 let current = world.humans.positions[i];
                                 let direction = (target_pos - current).normalize();
-                                let base_speed = 2.0;
-                                let actual_speed = base_speed * speed_modifier;
+                                let base_speed = 2.0 * span;
+                                let actual_speed = base_speed
+                                    * speed_modifier
+                                    * movement_multiplier(world.weather.current_weather);
 
                                 if direction.length() > 0.0 {
                                     world.humans.positions[i] = current + direction * actual_speed;
@@ -2051,11 +2553,9 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                         ActionId::SeekSafety => {
                             if let Some(threat_pos) = target_pos {
                                 let current = world.humans.positions[i];
-                                let away = (current - threat_pos).normalize();
-                                let speed = 3.0;
-                                if away.length() > 0.0 {
-                                    world.humans.positions[i] = current + away * speed;
-                                }
+                                let speed = 3.0 * span;
+                                world.humans.positions[i] =
+                                    step_away(&world.blocked_cells, current, threat_pos, speed);
                                 let distance = world.humans.positions[i].distance(&threat_pos);
                                 distance > 20.0
                             } else {
@@ -2063,11 +2563,27 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                             }
                         }
                         ActionId::Rest => {
-                            world.humans.body_states[i].fatigue =
-                                (world.humans.body_states[i].fatigue - 0.01).max(0.0);
-                            let duration = task.action.base_duration();
-                            task.progress += 1.0 / duration as f32;
-                            task.progress >= 1.0
+                            // Walk to the bed (a dormitory zone) before resting
+                            let current = world.humans.positions[i];
+                            match target_pos {
+                                Some(bed) if current.distance(&bed) > ZONE_ARRIVAL_RANGE => {
+                                    world.humans.positions[i] = world.paths.step_toward(
+                                        &world.blocked_cells,
+                                        current,
+                                        bed,
+                                        2.0 * span,
+                                    );
+                                    false
+                                }
+                                _ => {
+                                    world.humans.body_states[i].fatigue =
+                                        (world.humans.body_states[i].fatigue - 0.01 * span)
+                                            .max(0.0);
+                                    let duration = task.action.base_duration();
+                                    task.progress += span / duration as f32;
+                                    task.progress >= 1.0
+                                }
+                            }
                         }
                         ActionId::Eat => {
                             // Eat consumption is handled after the closure
@@ -2077,7 +2593,7 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 1..=60 => 0.05,
                                 _ => 0.02,
                             };
-                            task.progress += progress_rate;
+                            task.progress += progress_rate * span;
                             duration > 0 && task.progress >= 1.0
                         }
                         _ => false,
@@ -2112,7 +2628,7 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
                         if distance > SOCIAL_RANGE {
                             let direction = (target_pos - current).normalize();
-                            let speed = 2.0;
+                            let speed = 2.0 * span;
                             if direction.length() > 0.0 {
                                 world.humans.positions[i] = current + direction * speed;
                             }
@@ -2129,8 +2645,8 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
                             // Apply skill modifier to social effectiveness
                             // skill_modifier ranges from ~0.3 (unskilled) to ~1.0+ (expert)
-                            let social_amount = base_social * skill_result.skill_modifier;
-                            let purpose_amount = base_purpose * skill_result.skill_modifier;
+                            let social_amount = base_social * skill_result.skill_modifier * span;
+                            let purpose_amount = base_purpose * skill_result.skill_modifier * span;
 
                             // For Trade: skill affects deal quality
                             // 0.3 = bad deals, 0.7 = fair, 0.9+ = advantageous
@@ -2142,8 +2658,14 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 0.0
                             };
 
-                            world.humans.needs[i].satisfy(NeedType::Social, social_amount);
-                            world.humans.needs[target_idx].satisfy(NeedType::Social, social_amount);
+                            // Grubby company is less satisfying
+                            let actor_quality = social_quality(world.humans.needs[i].hygiene);
+                            let target_quality =
+                                social_quality(world.humans.needs[target_idx].hygiene);
+                            world.humans.needs[i]
+                                .satisfy(NeedType::Social, social_amount * target_quality);
+                            world.humans.needs[target_idx]
+                                .satisfy(NeedType::Social, social_amount * actor_quality);
 
                             if purpose_amount > 0.0 || trade_bonus > 0.0 {
                                 world.humans.needs[i]
@@ -2166,11 +2688,15 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 let base_intensity = 0.5;
                                 let relationship_bonus = skill_result.skill_modifier * 0.1;
                                 let intensity = base_intensity + relationship_bonus;
+                                // Partners from a renowned line are more memorable
+                                let target_standing =
+                                    world.legacy.standing(target_entity.unwrap());
+                                let actor_standing = world.legacy.standing(actor_id);
 
                                 world.humans.social_memories[i].record_encounter(
                                     target_entity.unwrap(),
                                     event_type,
-                                    intensity,
+                                    intensity + target_standing * STANDING_BONUS,
                                     world.current_tick,
                                 );
                                 let target_event = match action {
@@ -2180,14 +2706,14 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 world.humans.social_memories[target_idx].record_encounter(
                                     actor_id,
                                     target_event,
-                                    intensity,
+                                    intensity + actor_standing * STANDING_BONUS,
                                     world.current_tick,
                                 );
                             }
 
                             let duration = action.base_duration() as f32;
                             let progress_rate = if duration > 0.0 { 1.0 / duration } else { 0.1 };
-                            task.progress += progress_rate;
+                            task.progress += progress_rate * span;
                             let is_complete = task.progress >= 1.0;
 
                             // Record experience on completion
@@ -2203,12 +2729,13 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                             is_complete
                         }
                     } else {
-                        // Target not found - complete early
-                        true
+                        // A carry finishes when stepped; otherwise the target
+                        // was not found - complete early
+                        carry_done.unwrap_or(true)
                     }
                 }
 
-                // =========== WORK ACTIONS (Gather, Build, Craft, Repair) ===========
+                // =========== WORK ACTIONS (Gather, Build, Craft, Repair, Haul) ===========
                 ActionCategory::Work => {
                     match action {
                         ActionId::Gather => {
@@ -2228,6 +2755,21 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 0.5
                             };
 
+                            // Work is slower in the dark without a light or in foul
+                            // weather, and an overworked body fumbles and wastes the
+                            // tick's effort
+                            let fumbled = rng.gen::<f32>()
+                                < world.humans.body_states[i].fumble_chance();
+                            let work_factor = if fumbled {
+                                0.0
+                            } else {
+                                work_efficiency(world.lights.light_at(
+                                    world.humans.positions[i],
+                                    world.astronomy.light_level,
+                                )) * outdoor_work_multiplier(world.weather.current_weather)
+                                    * pace
+                            };
+
                             let is_complete = if let Some(zone_pos) = target_pos {
                                 let current = world.humans.positions[i];
                                 let zone_idx = world
@@ -2239,16 +2781,19 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                     let distance = current.distance(&zone_pos);
 
                                     if distance > 2.0 {
-                                        let direction = (zone_pos - current).normalize();
-                                        let speed = 2.0;
-                                        if direction.length() > 0.0 {
-                                            world.humans.positions[i] = current + direction * speed;
-                                        }
+                                        let speed = 2.0 * span;
+                                        world.humans.positions[i] = world.paths.step_toward(
+                                            &world.blocked_cells,
+                                            current,
+                                            zone_pos,
+                                            speed,
+                                        );
                                         false
                                     } else {
                                         // Apply skill modifier to gather rate
-                                        let base_gather = 0.02;
-                                        let modified_gather = base_gather * effective_skill;
+                                        let base_gather = 0.02 * span;
+                                        let modified_gather =
+                                            base_gather * effective_skill * work_factor;
                                         let gathered =
                                             world.resource_zones[zone_idx].gather(modified_gather);
                                         if gathered > 0.0 {
@@ -2258,7 +2803,13 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                         let duration = action.base_duration() as f32;
                                         let progress_rate =
                                             if duration > 0.0 { 1.0 / duration } else { 0.1 };
-                                        task.progress += progress_rate;
+                                        task.progress += progress_rate * span;
+                                        if task.progress >= 1.0 {
+                                            // The gatherer keeps a share for themselves
+                                            let resource =
+                                                world.resource_zones[zone_idx].resource_type;
+                                            world.humans.possessions[i].add(resource, 1);
+                                        }
                                         task.progress >= 1.0
                                             || world.resource_zones[zone_idx].current <= 0.0
                                     }
@@ -2296,8 +2847,55 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 0.5
                             };
 
+                            // Work is slower in the dark without a light or in foul
+                            // weather, and an overworked body fumbles and wastes the
+                            // tick's effort
+                            let fumbled = rng.gen::<f32>()
+                                < world.humans.body_states[i].fumble_chance();
+                            let work_factor = if fumbled {
+                                0.0
+                            } else {
+                                work_efficiency(world.lights.light_at(
+                                    world.humans.positions[i],
+                                    world.astronomy.light_level,
+                                )) * outdoor_work_multiplier(world.weather.current_weather)
+                                    * pace
+                                    * span
+                            };
+
                             // Check for building target - use construction system if present
-                            let is_complete = if let Some(building_id) = task.target_building {
+                            let site =
+                                task.target_building.and_then(|b| site_of(&world.sites, b));
+                            let is_complete = if let Some(s) = site {
+                                // Building placed from a blueprint
+                                let current = world.humans.positions[i];
+                                let site_pos = world.sites[s].position;
+                                if current.distance(&site_pos) > SITE_WORK_RANGE {
+                                    world.humans.positions[i] = world.paths.step_toward(
+                                        &world.blocked_cells,
+                                        current,
+                                        site_pos,
+                                        2.0 * span,
+                                    );
+                                    false
+                                } else {
+                                    let contribution = calculate_worker_contribution(
+                                        world.humans.building_skills[i],
+                                        world.humans.body_states[i].fatigue,
+                                    ) * work_factor;
+                                    let done = work_on_site(
+                                        &mut world.sites[s],
+                                        &mut world.buildings,
+                                        contribution,
+                                        world.current_tick,
+                                    );
+                                    if done && world.sites[s].is_complete() {
+                                        world.humans.building_skills[i] =
+                                            (world.humans.building_skills[i] + 0.01).min(1.0);
+                                    }
+                                    done
+                                }
+                            } else if let Some(building_id) = task.target_building {
                                 if let Some(building_idx) = world.buildings.index_of(building_id) {
                                     // Get worker skill and fatigue
                                     let building_skill = world.humans.building_skills[i];
@@ -2305,7 +2903,8 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
                                     // Calculate contribution - skill already factored into base calculation
                                     let contribution =
-                                        calculate_worker_contribution(building_skill, fatigue);
+                                        calculate_worker_contribution(building_skill, fatigue)
+                                            * work_factor;
 
                                     // Apply to building
                                     let result = apply_construction_work(
@@ -2329,6 +2928,52 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 } else {
                                     true // Building not found, complete task
                                 }
+                            } else if let Some(c) = target_pos.and_then(|pos| {
+                                world.crossings.iter().position(|c| {
+                                    c.state == CrossingState::UnderConstruction
+                                        && c.position.distance(&pos) <= CROSSING_WORK_RANGE
+                                })
+                            }) {
+                                // Bridge or ramp construction
+                                let current = world.humans.positions[i];
+                                let site_pos = world.crossings[c].position;
+                                if current.distance(&site_pos) > CROSSING_WORK_RANGE {
+                                    let direction = (site_pos - current).normalize();
+                                    let speed = 2.0 * span;
+                                    if direction.length() > 0.0 {
+                                        world.humans.positions[i] = current + direction * speed;
+                                    }
+                                    false
+                                } else {
+                                    let building_skill = world.humans.building_skills[i];
+                                    let fatigue = world.humans.body_states[i].fatigue;
+                                    let contribution =
+                                        calculate_worker_contribution(building_skill, fatigue)
+                                            * work_factor;
+                                    world.crossings[c]
+                                        .apply_work(contribution, &mut world.blocked_cells)
+                                }
+                            } else if let Some(cell) = target_pos
+                                .and_then(|pos| world.roads.site_near(pos, ROAD_CELL_SIZE))
+                            {
+                                // Road construction at a planned cell
+                                let current = world.humans.positions[i];
+                                let site_pos = RoadNetwork::cell_center(cell);
+                                if current.distance(&site_pos) > ROAD_CELL_SIZE {
+                                    let direction = (site_pos - current).normalize();
+                                    let speed = 2.0 * span;
+                                    if direction.length() > 0.0 {
+                                        world.humans.positions[i] = current + direction * speed;
+                                    }
+                                    false
+                                } else {
+                                    let building_skill = world.humans.building_skills[i];
+                                    let fatigue = world.humans.body_states[i].fatigue;
+                                    let contribution =
+                                        calculate_worker_contribution(building_skill, fatigue)
+                                            * work_factor;
+                                    world.roads.apply_work(cell, contribution)
+                                }
                             } else {
                                 // Legacy progress-based logic (no building target)
                                 let duration = task.action.base_duration();
@@ -2338,7 +2983,8 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                     _ => 0.02,
                                 };
                                 // Apply skill modifier to progress rate
-                                let progress_rate = base_progress_rate * effective_skill;
+                                let progress_rate =
+                                    base_progress_rate * effective_skill * work_factor;
                                 task.progress += progress_rate;
                                 duration > 0 && task.progress >= 1.0
                             };
@@ -2353,6 +2999,75 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
                             is_complete
                         }
+                        ActionId::TendLivestock => {
+                            let skill_result = skill_check(
+                                &world.humans.chunk_libraries[i],
+                                ActionId::TendLivestock,
+                            );
+
+                            // Work actions always proceed
+                            let effective_skill = if skill_result.can_execute {
+                                spend_attention(
+                                    &mut world.humans.chunk_libraries[i],
+                                    skill_result.attention_cost,
+                                );
+                                skill_result.skill_modifier
+                            } else {
+                                // Exhausted: work at reduced efficiency
+                                0.5
+                            };
+
+                            let is_complete = if let Some(pasture_pos) = target_pos {
+                                let current = world.humans.positions[i];
+                                let pasture_idx = world
+                                    .pastures
+                                    .iter()
+                                    .position(|p| p.contains(pasture_pos));
+
+                                if let Some(pasture_idx) = pasture_idx {
+                                    if !world.pastures[pasture_idx].contains(current) {
+                                        let direction = (pasture_pos - current).normalize();
+                                        let speed = 2.0 * span;
+                                        if direction.length() > 0.0 {
+                                            world.humans.positions[i] = current + direction * speed;
+                                        }
+                                        false
+                                    } else {
+                                        let duration = action.base_duration() as f32;
+                                        task.progress += effective_skill * span / duration;
+                                        if task.progress >= 1.0 {
+                                            let result = world.livestock.tend_pasture(
+                                                &mut world.pastures[pasture_idx],
+                                                &mut world.stockpile,
+                                                effective_skill,
+                                            );
+                                            if result.slaughtered > 0 || !result.collected.is_empty()
+                                            {
+                                                world.humans.needs[i]
+                                                    .satisfy(NeedType::Purpose, 0.2);
+                                            }
+                                            true
+                                        } else {
+                                            false
+                                        }
+                                    }
+                                } else {
+                                    true // Pasture not found, complete task
+                                }
+                            } else {
+                                true // No target, complete task
+                            };
+
+                            // Record experience (husbandry always teaches)
+                            record_action_experience(
+                                &mut world.humans.chunk_libraries[i],
+                                &skill_result.chunks_used,
+                                true,
+                                world.current_tick,
+                            );
+
+                            is_complete
+                        }
                         ActionId::Craft => {
                             // Skill check before crafting
                             let skill_result =
@@ -2370,16 +3085,37 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 0.5
                             };
 
-                            let duration = task.action.base_duration();
-                            let base_progress_rate = match duration {
-                                0 => 0.1,
-                                1..=60 => 0.05,
-                                _ => 0.02,
+                            // Crafting at a vehicle site builds the vehicle
+                            let vehicle_site =
+                                target_pos.and_then(|pos| world.vehicles.site_near(pos, 2.0));
+
+                            let is_complete = if let Some(v) = vehicle_site {
+                                let current = world.humans.positions[i];
+                                let site = world.vehicles.positions[v];
+                                if current.distance(&site) > 2.0 {
+                                    let direction = (site - current).normalize();
+                                    world.humans.positions[i] = current + direction * 2.0 * span;
+                                    false
+                                } else {
+                                    let contribution = calculate_worker_contribution(
+                                        world.humans.building_skills[i],
+                                        world.humans.body_states[i].fatigue,
+                                    ) * effective_skill
+                                        * span;
+                                    world.vehicles.apply_work(v, contribution)
+                                }
+                            } else {
+                                let duration = task.action.base_duration();
+                                let base_progress_rate = match duration {
+                                    0 => 0.1,
+                                    1..=60 => 0.05,
+                                    _ => 0.02,
+                                };
+                                // Apply skill modifier to progress rate
+                                let progress_rate = base_progress_rate * effective_skill;
+                                task.progress += progress_rate * span;
+                                duration > 0 && task.progress >= 1.0
                             };
-                            // Apply skill modifier to progress rate
-                            let progress_rate = base_progress_rate * effective_skill;
-                            task.progress += progress_rate;
-                            let is_complete = duration > 0 && task.progress >= 1.0;
 
                             // Record experience (crafting always teaches)
                             record_action_experience(
@@ -2408,16 +3144,32 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                 0.5
                             };
 
-                            let duration = task.action.base_duration();
-                            let base_progress_rate = match duration {
-                                0 => 0.1,
-                                1..=60 => 0.05,
-                                _ => 0.02,
+                            // Repairing near a worn vehicle maintains it
+                            let worn_vehicle =
+                                target_pos.and_then(|pos| world.vehicles.worn_near(pos, 2.0));
+
+                            let is_complete = if let Some(v) = worn_vehicle {
+                                let current = world.humans.positions[i];
+                                let vehicle_pos = world.vehicles.positions[v];
+                                if current.distance(&vehicle_pos) > 2.0 {
+                                    let direction = (vehicle_pos - current).normalize();
+                                    world.humans.positions[i] = current + direction * 2.0 * span;
+                                    false
+                                } else {
+                                    world.vehicles.repair(v, 0.02 * effective_skill * span)
+                                }
+                            } else {
+                                let duration = task.action.base_duration();
+                                let base_progress_rate = match duration {
+                                    0 => 0.1,
+                                    1..=60 => 0.05,
+                                    _ => 0.02,
+                                };
+                                // Apply skill modifier to progress rate
+                                let progress_rate = base_progress_rate * effective_skill;
+                                task.progress += progress_rate * span;
+                                duration > 0 && task.progress >= 1.0
                             };
-                            // Apply skill modifier to progress rate
-                            let progress_rate = base_progress_rate * effective_skill;
-                            task.progress += progress_rate;
-                            let is_complete = duration > 0 && task.progress >= 1.0;
 
                             // Record experience (repairing always teaches)
                             record_action_experience(
@@ -2429,6 +3181,86 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
                             is_complete
                         }
+                        ActionId::Haul => {
+                            // Carry one load from the stockpile to a construction site,
+                            // fetching it from the nearest stockpile zone if any is
+                            // designated (progress 0.5 marks the load in hand)
+                            match task.target_building.and_then(|b| site_of(&world.sites, b)) {
+                                Some(s) if !world.sites[s].materials_ready() => {
+                                    let current = world.humans.positions[i];
+                                    let site_pos = world.sites[s].position;
+                                    let pickup = (task.progress < 0.5)
+                                        .then(|| world.zones.nearest(ZoneKind::Stockpile, current))
+                                        .flatten()
+                                        .map(|zone| zone.shape);
+                                    if let Some(zone) =
+                                        pickup.filter(|zone| !zone.contains(current))
+                                    {
+                                        world.humans.positions[i] = world.paths.step_toward(
+                                            &world.blocked_cells,
+                                            current,
+                                            zone.center(),
+                                            2.0 * span,
+                                        );
+                                        false
+                                    } else if current.distance(&site_pos) > SITE_WORK_RANGE {
+                                        task.progress = 0.5;
+                                        world.humans.positions[i] = world.paths.step_toward(
+                                            &world.blocked_cells,
+                                            current,
+                                            site_pos,
+                                            2.0 * span,
+                                        );
+                                        false
+                                    } else {
+                                        world.sites[s].haul_from(&mut world.stockpile);
+                                        true
+                                    }
+                                }
+                                _ => true, // Site gone or fully stocked, complete task
+                            }
+                        }
+                        ActionId::Drill => {
+                            let skill_result =
+                                skill_check(&world.humans.chunk_libraries[i], ActionId::Drill);
+                            if skill_result.can_execute {
+                                spend_attention(
+                                    &mut world.humans.chunk_libraries[i],
+                                    skill_result.attention_cost,
+                                );
+                            }
+
+                            // Walk to the drill ground, then drill out the session
+                            // (the rating is earned once it is finished, below)
+                            let is_complete = if let Some(ground) = target_pos {
+                                let current = world.humans.positions[i];
+                                if current.distance(&ground) > 2.0 {
+                                    world.humans.positions[i] = world.paths.step_toward(
+                                        &world.blocked_cells,
+                                        current,
+                                        ground,
+                                        2.0 * span,
+                                    );
+                                    false
+                                } else {
+                                    let duration = action.base_duration() as f32;
+                                    task.progress += span / duration;
+                                    task.progress >= 1.0
+                                }
+                            } else {
+                                true // No drill ground, complete task
+                            };
+
+                            // Record experience (drill always teaches)
+                            record_action_experience(
+                                &mut world.humans.chunk_libraries[i],
+                                &skill_result.chunks_used,
+                                true,
+                                world.current_tick,
+                            );
+
+                            is_complete
+                        }
                         _ => false,
                     }
                 }
@@ -2442,23 +3274,28 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                             .unwrap_or(true);
 
                         if needs_new_target {
-                            use rand::Rng;
-                            let mut rng = rand::thread_rng();
                             let angle = rng.gen::<f32>() * std::f32::consts::TAU;
                             let distance = rng.gen::<f32>() * 10.0;
                             let offset = crate::core::types::Vec2::new(
                                 angle.cos() * distance,
                                 angle.sin() * distance,
                             );
-                            task.target_position = Some(current + offset);
+                            let target = current + offset;
+                            // Nobody wanders into a wall; pick again next tick
+                            let blocked = world
+                                .blocked_cells
+                                .is_position_blocked(glam::Vec2::new(target.x, target.y));
+                            task.target_position = Some(if blocked { current } else { target });
                         }
 
                         if let Some(target) = task.target_position {
-                            let direction = (target - current).normalize();
-                            let speed = 1.0;
-                            if direction.length() > 0.0 {
-                                world.humans.positions[i] = current + direction * speed;
-                            }
+                            let speed = 1.0 * span;
+                            world.humans.positions[i] = world.paths.step_toward(
+                                &world.blocked_cells,
+                                current,
+                                target,
+                                speed,
+                            );
                         }
                         false
                     }
@@ -2466,6 +3303,23 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                     _ => false,
                 },
 
+                // =========== ITEM ACTIONS (PickUp, Drop, Equip) ===========
+                // Picking up walks to the item first; the rest is instant.
+                // The items change hands once the task completes.
+                ActionCategory::Items => match (action, target_pos) {
+                    (ActionId::PickUp, Some(target)) => {
+                        let current = world.humans.positions[i];
+                        world.humans.positions[i] = world.paths.step_toward(
+                            &world.blocked_cells,
+                            current,
+                            target,
+                            2.0 * span,
+                        );
+                        world.humans.positions[i].distance(&target) <= PICKUP_RANGE
+                    }
+                    _ => true,
+                },
+
                 // =========== COMBAT ACTIONS ===========
                 ActionCategory::Combat => {
                     match action {
@@ -2507,6 +3361,7 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                         armor: attacker_combat_state.armor.clone(),
                                         stance: CombatStance::Pressing,
                                         skill: attacker_skill,
+                                        aim: None,
                                     };
 
                                     // Build defender based on target type
@@ -2521,6 +3376,7 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                                 armor: defender_combat_state.armor.clone(),
                                                 stance: CombatStance::Neutral,
                                                 skill: defender_skill,
+                                                aim: None,
                                             };
 
                                             let exchange = resolve_exchange(&attacker, &defender);
@@ -2540,6 +3396,12 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                                         (world.humans.body_states[defender_idx].fatigue
                                                             + fatigue_increase)
                                                             .min(1.0);
+                                                    note_head_wound(
+                                                        &mut world.transformations,
+                                                        world.humans.ids[defender_idx],
+                                                        wound,
+                                                        world.current_tick,
+                                                    );
                                                 }
                                             }
 
@@ -2558,6 +3420,12 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                                         (world.humans.body_states[i].fatigue
                                                             + fatigue_increase)
                                                             .min(1.0);
+                                                    note_head_wound(
+                                                        &mut world.transformations,
+                                                        world.humans.ids[i],
+                                                        wound,
+                                                        world.current_tick,
+                                                    );
                                                 }
                                             }
 
@@ -2572,6 +3440,7 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                                 armor: ArmorProperties::default(),   // no armor
                                                 stance: CombatStance::Neutral,
                                                 skill: defender_skill,
+                                                aim: None,
                                             };
 
                                             let exchange = resolve_exchange(&attacker, &defender);
@@ -2595,6 +3464,7 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                                     // Kill orc if fatigue reaches 1.0 (exhausted)
                                                     if world.orcs.body_states[defender_idx].fatigue >= 1.0 {
                                                         world.orcs.alive[defender_idx] = false;
+                                                        slew_orc = true;
                                                     }
                                                 }
                                             }
@@ -2614,6 +3484,12 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                                                         (world.humans.body_states[i].fatigue
                                                             + fatigue_increase)
                                                             .min(1.0);
+                                                    note_head_wound(
+                                                        &mut world.transformations,
+                                                        world.humans.ids[i],
+                                                        wound,
+                                                        world.current_tick,
+                                                    );
                                                 }
                                             }
 
@@ -2727,10 +3603,13 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
         let Some((action, target_entity, is_complete)) = task_info else {
             continue;
         };
+        if slew_orc {
+            perform(world, i, Feat::SlewBeast);
+        }
 
         // Generate CombatHit event if this was an attack action that hit (cross-species)
         if action == ActionId::Attack && is_complete {
-            if let Some((_, target)) = combat_target_info {
+            if let Some((target_id, target)) = combat_target_info {
                 // Attack completed with a target - generate combat hit event
                 let defender_name = match target {
                     CombatTarget::Human(defender_idx) => world.humans.names[defender_idx].clone(),
@@ -2740,29 +3619,54 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                     attacker: world.humans.names[i].clone(),
                     defender: defender_name,
                 });
+
+                // Striking a neutral faction's member is an act of war
+                if let Some((faction, against)) =
+                    world.factions.provoke(world.humans.ids[i], target_id)
+                {
+                    let name = |id| world.factions.get(id).map(|f| f.name.clone());
+                    events.push(SimulationEvent::WarDeclared {
+                        faction: name(against).unwrap_or_default(),
+                        against: name(faction).unwrap_or_default(),
+                        tick: world.current_tick,
+                    });
+                }
             }
         }
 
+        // A finished drill session raises the driller's rating
+        if action == ActionId::Drill && is_complete {
+            finish_drill(world, i);
+        }
+
         // Handle Eat action specially: consume from food zone
         if action == ActionId::Eat {
             let pos = world.humans.positions[i];
             // Find food zone entity is standing in and consume from it
+            let mut in_zone = false;
             for zone in &mut world.food_zones {
                 if zone.contains(pos) {
-                    let consumed = zone.consume(0.1); // Consume rate per tick
+                    in_zone = true;
+                    let consumed = zone.consume(0.1 * span); // Consume rate per tick
                     if consumed > 0.0 {
                         world.humans.needs[i].satisfy(NeedType::Food, consumed * 0.5);
                     }
                     break;
                 }
             }
+            // Away from food zones, a finished meal comes out of the pack
+            if !in_zone && is_complete {
+                if let Some(nourishment) = world.humans.inventories[i].take_food() {
+                    world.humans.needs[i].satisfy(NeedType::Food, nourishment);
+                }
+            }
         } else {
             // SATISFACTION MULTIPLIER: 0.05
             // Actions apply a fraction of their nominal satisfaction each tick.
             // This accumulates over the task duration to total satisfaction.
             // Without this, entities would fully satisfy needs in one tick.
             for (need, amount) in action.satisfies_needs() {
-                world.humans.needs[i].satisfy(need, amount * 0.05);
+                world.humans.needs[i].satisfy(need, amount * 0.05 * span);
             }
         }
 
@@ -2775,7 +3679,7 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 // Check current task progress to see if this is the first tick of interaction
                 let first_tick_of_interaction = world.humans.task_queues[i]
                     .current()
-                    .map(|t| t.progress > 0.0 && t.progress < 0.1)
+                    .map(|t| t.progress > 0.0 && t.progress < 0.1 * span)
                     .unwrap_or(false);
 
                 if first_tick_of_interaction {
@@ -2810,6 +3714,15 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
             if let Some(target_id) = target_entity {
                 create_social_memory_from_task(world, i, action, target_id, world.current_tick);
 
+                // A finished trade hands the goods over
+                if action == ActionId::Trade {
+                    if let Some(target_idx) = world.humans.index_of(target_id) {
+                        if let Some(deal) = conclude_trade(world, i, target_idx) {
+                            judge_deal(world, i, target_idx, &deal);
+                        }
+                    }
+                }
+
                 // Emit social memory event for completed social actions
                 if matches!(action, ActionId::TalkTo | ActionId::Help | ActionId::Trade) {
                     let new_disposition = world.humans.social_memories[i].get_disposition(target_id);
@@ -2835,6 +3748,13 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                         }
                     }
                 }
+            } else if action == ActionId::Trade {
+                // A trade with no partner is a trade with the stockpile
+                trade_with_stockpile(world, i);
+            }
+
+            if action.category() == ActionCategory::Items {
+                use_items(world, i, action);
             }
 
             // Generate TaskCompleted event
@@ -2850,15 +3770,14 @@ fn execute_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
     // Execute orc tasks
     execute_orc_tasks(world, events);
 
-    // Execute dwarf tasks
-    execute_dwarf_tasks(world, events);
-
-    // Execute elf tasks
-    execute_elf_tasks(world, events);
+    // Dwarves and elves go about their tasks at their own pace
+    execute_species_tasks(world, Species::Dwarf, &DWARF_PACE);
+    execute_species_tasks(world, Species::Elf, &ELF_PACE);
 }
 
 /// Execute current tasks for orc entities
 fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
+    let span = world.time_scale.span() as f32;
     let orc_indices: Vec<usize> = world.orcs.iter_living().collect();
 
     // Collect combat actions to process after the main loop
@@ -2884,11 +3803,11 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 ActionId::MoveTo => {
                     if let Some(target) = target_pos {
                         let current = world.orcs.positions[i];
-                        let direction = (target - current).normalize();
-                        let speed = 2.0;
-                        if direction.length() > 0.0 {
-                            world.orcs.positions[i] = current + direction * speed;
-                        }
+                        let speed = 2.0 * span;
+                        world.orcs.positions[i] =
+                            world
+                                .paths
+                                .step_toward(&world.blocked_cells, current, target, speed);
                         world.orcs.positions[i].distance(&target) < 2.0
                     } else {
                         true
@@ -2897,22 +3816,18 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 ActionId::Flee => {
                     if let Some(threat_pos) = target_pos {
                         let current = world.orcs.positions[i];
-                        let away = (current - threat_pos).normalize();
-                        let speed = 3.0;
-                        if away.length() > 0.0 {
-                            world.orcs.positions[i] = current + away * speed;
-                        }
+                        let speed = 3.0 * span;
+                        world.orcs.positions[i] =
+                            step_away(&world.blocked_cells, current, threat_pos, speed);
                     }
                     false
                 }
                 ActionId::SeekSafety => {
                     if let Some(threat_pos) = target_pos {
                         let current = world.orcs.positions[i];
-                        let away = (current - threat_pos).normalize();
-                        let speed = 3.0;
-                        if away.length() > 0.0 {
-                            world.orcs.positions[i] = current + away * speed;
-                        }
+                        let speed = 3.0 * span;
+                        world.orcs.positions[i] =
+                            step_away(&world.blocked_cells, current, threat_pos, speed);
                         let distance = world.orcs.positions[i].distance(&threat_pos);
                         distance > 20.0
                     } else {
@@ -2921,14 +3836,14 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 }
                 ActionId::Rest => {
                     world.orcs.body_states[i].fatigue =
-                        (world.orcs.body_states[i].fatigue - 0.01).max(0.0);
+                        (world.orcs.body_states[i].fatigue - 0.01 * span).max(0.0);
                     let duration = task.action.base_duration();
-                    task.progress += 1.0 / duration as f32;
+                    task.progress += span / duration as f32;
                     task.progress >= 1.0
                 }
                 ActionId::Attack => {
                     // Progress attack action
-                    task.progress += 0.1;
+                    task.progress += 0.1 * span;
                     task.progress >= 1.0
                 }
                 _ => false,
@@ -2946,7 +3861,7 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                     1..=60 => 0.05,
                     _ => 0.02,
                 };
-                task.progress += progress_rate;
+                task.progress += progress_rate * span;
             }
 
             let duration = task.action.base_duration();
@@ -2961,7 +3876,7 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 let pos = world.orcs.positions[i];
                 for zone in &mut world.food_zones {
                     if zone.contains(pos) {
-                        let consumed = zone.consume(0.1);
+                        let consumed = zone.consume(0.1 * span);
                         if consumed > 0.0 {
                             world.orcs.needs[i].satisfy(NeedType::Food, consumed * 0.5);
                         }
@@ -2970,7 +3885,7 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 }
             } else {
                 for (need, amount) in action.satisfies_needs() {
-                    world.orcs.needs[i].satisfy(need, amount * 0.05);
+                    world.orcs.needs[i].satisfy(need, amount * 0.05 * span);
                 }
             }
 
@@ -2982,15 +3897,26 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
 
     // Process orc attacks against targets (cross-species combat)
     for (attacker_idx, target_id) in orc_attacks {
-        // Find target - could be human or orc
+        // Find target - could be human or orc, and only while it lives
         let target_info: Option<CombatTarget> = if let Some(idx) = world.humans.index_of(target_id) {
-            Some(CombatTarget::Human(idx))
+            world.humans.alive[idx].then_some(CombatTarget::Human(idx))
         } else if let Some(idx) = world.orcs.index_of(target_id) {
-            Some(CombatTarget::Orc(idx))
+            world.orcs.alive[idx].then_some(CombatTarget::Orc(idx))
         } else {
             None
         };
 
+        // The dead are past harming: give up the attack and choose anew
+        if target_info.is_none() {
+            let queue = &mut world.orcs.task_queues[attacker_idx];
+            if queue.current().is_some_and(|task| {
+                task.action == ActionId::Attack && task.target_entity == Some(target_id)
+            }) {
+                queue.complete_current();
+            }
+            continue;
+        }
+
         if let Some(target) = target_info {
             // Get orc attacker stats (orcs use axes by default)
             let attacker = Combatant {
@@ -2998,6 +3924,7 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                 armor: ArmorProperties::none(),
                 skill: CombatSkill::novice(),
                 stance: CombatStance::Pressing,
+                aim: None,
             };
 
             // Get defender stats based on target type
@@ -3007,12 +3934,14 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                     armor: world.humans.combat_states[idx].armor.clone(),
                     skill: CombatSkill::novice(),
                     stance: CombatStance::Neutral,
+                    aim: None,
                 },
                 CombatTarget::Orc(_idx) => Combatant {
                     weapon: WeaponProperties::axe(),
                     armor: ArmorProperties::none(),
                     skill: CombatSkill::novice(),
                     stance: CombatStance::Neutral,
+                    aim: None,
                 },
             };
 
@@ -3048,9 +3977,17 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                         CombatTarget::Human(idx) => {
                             world.humans.body_states[idx].fatigue =
                                 (world.humans.body_states[idx].fatigue + fatigue_increase).min(1.0);
+                            note_head_wound(
+                                &mut world.transformations,
+                                world.humans.ids[idx],
+                                wound,
+                                world.current_tick,
+                            );
                             // Kill human if fatigue reaches 1.0
                             if world.humans.body_states[idx].fatigue >= 1.0 {
                                 world.humans.alive[idx] = false;
+                                let killer = world.orcs.ids[attacker_idx];
+                                record_death(world, idx, Some(killer));
                             }
                         }
                         CombatTarget::Orc(idx) => {
@@ -3081,212 +4018,144 @@ fn execute_orc_tasks(world: &mut World, events: &mut Vec<SimulationEvent>) {
                     // Kill orc if fatigue reaches 1.0
                     if world.orcs.body_states[attacker_idx].fatigue >= 1.0 {
                         world.orcs.alive[attacker_idx] = false;
-                    }
-                }
-            }
-        }
-    }
-}
-
-/// Execute current tasks for dwarf entities
-fn execute_dwarf_tasks(world: &mut World, _events: &mut Vec<SimulationEvent>) {
-    let dwarf_indices: Vec<usize> = world.dwarves.iter_living().collect();
-
-    for i in dwarf_indices.iter().cloned() {
-        // Get task info
-        let task_info = world.dwarves.task_queues[i].current_mut().map(|task| {
-            let action = task.action;
-            let target_pos = task.target_position;
-
-            // Handle movement actions
-            let movement_complete = match action {
-                ActionId::MoveTo => {
-                    if let Some(target) = target_pos {
-                        let current = world.dwarves.positions[i];
-                        let direction = (target - current).normalize();
-                        let speed = 1.8; // Dwarves are slightly slower
-                        if direction.length() > 0.0 {
-                            world.dwarves.positions[i] = current + direction * speed;
-                        }
-                        world.dwarves.positions[i].distance(&target) < 2.0
-                    } else {
-                        true
-                    }
-                }
-                ActionId::Flee | ActionId::SeekSafety => {
-                    if let Some(threat_pos) = target_pos {
-                        let current = world.dwarves.positions[i];
-                        let away = (current - threat_pos).normalize();
-                        let speed = 2.5;
-                        if away.length() > 0.0 {
-                            world.dwarves.positions[i] = current + away * speed;
+                        if let CombatTarget::Human(idx) = target {
+                            perform(world, idx, Feat::SlewBeast);
                         }
-                        let distance = world.dwarves.positions[i].distance(&threat_pos);
-                        distance > 20.0
-                    } else {
-                        true
-                    }
-                }
-                ActionId::Rest => {
-                    world.dwarves.body_states[i].fatigue =
-                        (world.dwarves.body_states[i].fatigue - 0.01).max(0.0);
-                    let duration = task.action.base_duration();
-                    task.progress += 1.0 / duration as f32;
-                    task.progress >= 1.0
-                }
-                _ => false,
-            };
-
-            // Progress for non-movement actions
-            let is_special = matches!(
-                action,
-                ActionId::MoveTo | ActionId::Flee | ActionId::SeekSafety | ActionId::Rest
-            );
-            if !is_special {
-                let duration = task.action.base_duration();
-                let progress_rate = match duration {
-                    0 => 0.1,
-                    1..=60 => 0.05,
-                    _ => 0.02,
-                };
-                task.progress += progress_rate;
-            }
-
-            let duration = task.action.base_duration();
-            let progress_complete = duration > 0 && task.progress >= 1.0;
-            let is_complete = movement_complete || progress_complete;
-            (action, is_complete)
-        });
-
-        if let Some((action, is_complete)) = task_info {
-            // Handle Eat action
-            if action == ActionId::Eat {
-                let pos = world.dwarves.positions[i];
-                for zone in &mut world.food_zones {
-                    if zone.contains(pos) {
-                        let consumed = zone.consume(0.1);
-                        if consumed > 0.0 {
-                            world.dwarves.needs[i].satisfy(NeedType::Food, consumed * 0.5);
-                        }
-                        break;
-                    }
-                }
-            } else {
-                for (need, amount) in action.satisfies_needs() {
-                    world.dwarves.needs[i].satisfy(need, amount * 0.05);
+                    }
                 }
             }
-
-            if is_complete {
-                world.dwarves.task_queues[i].complete_current();
-            }
         }
     }
 }
 
-/// Execute current tasks for elf entities
-fn execute_elf_tasks(world: &mut World, _events: &mut Vec<SimulationEvent>) {
-    let elf_indices: Vec<usize> = world.elves.iter_living().collect();
+/// How a species goes about its tasks
+struct TaskPace {
+    /// Walking speed, per tick
+    walk: f32,
+    /// Fleeing speed, per tick
+    flee: f32,
+    /// How far from a threat a flight ends
+    safe_distance: f32,
+    /// Food taken from a zone per tick of eating
+    bite: f32,
+    /// How much of the hunger need a unit of food satisfies
+    nourishment: f32,
+}
 
-    for i in elf_indices.iter().cloned() {
-        // Get task info
-        let task_info = world.elves.task_queues[i].current_mut().map(|task| {
-            let action = task.action;
-            let target_pos = task.target_position;
+/// Dwarves are slightly slower than humans
+const DWARF_PACE: TaskPace = TaskPace {
+    walk: 1.8,
+    flee: 2.5,
+    safe_distance: 20.0,
+    bite: 0.1,
+    nourishment: 0.5,
+};
 
-            // Handle movement actions
-            let movement_complete = match action {
-                ActionId::MoveTo => {
-                    if let Some(target) = target_pos {
-                        let current = world.elves.positions[i];
-                        let direction = (target - current).normalize();
-                        let speed = 2.2; // Elves are faster and more graceful
-                        if direction.length() > 0.0 {
-                            world.elves.positions[i] = current + direction * speed;
-                        }
-                        world.elves.positions[i].distance(&target) < 2.0
-                    } else {
-                        true
-                    }
-                }
-                ActionId::Flee | ActionId::SeekSafety => {
-                    if let Some(threat_pos) = target_pos {
-                        let current = world.elves.positions[i];
-                        let away = (current - threat_pos).normalize();
-                        let speed = 3.5; // Elves flee quickly
-                        if away.length() > 0.0 {
-                            world.elves.positions[i] = current + away * speed;
-                        }
-                        let distance = world.elves.positions[i].distance(&threat_pos);
-                        distance > 25.0 // Elves seek more distance
-                    } else {
-                        true
-                    }
+/// Elves are faster and more graceful, flee further, and eat less
+const ELF_PACE: TaskPace = TaskPace {
+    walk: 2.2,
+    flee: 3.5,
+    safe_distance: 25.0,
+    bite: 0.08,
+    nourishment: 0.6,
+};
+
+/// Execute the current tasks of a species' living at its `pace`
+///
+/// Movement, rest, and eating are carried out here; any other task simply
+/// progresses, satisfying the needs its action meets.
+fn execute_species_tasks(world: &mut World, species: Species, pace: &TaskPace) {
+    let span = world.time_scale.span() as f32;
+    let Some(living) = world
+        .archetype(species)
+        .map(|a| a.components().living().collect::<Vec<_>>())
+    else {
+        return;
+    };
+
+    for i in living {
+        let Some(parts) = world.archetype(species).map(|a| a.components()) else {
+            return;
+        };
+        let Some(task) = parts.task_queues[i].current() else {
+            continue;
+        };
+        let (action, target_pos, mut progress) = (task.action, task.target_position, task.progress);
+        let mut position = parts.positions[i];
+        let duration = action.base_duration();
+
+        // Movement ends on arrival or escape; resting and other work progress
+        let mut rested = false;
+        let movement_complete = match action {
+            ActionId::MoveTo => match target_pos {
+                Some(target) => {
+                    position = world.paths.step_toward(
+                        &world.blocked_cells,
+                        position,
+                        target,
+                        pace.walk * span,
+                    );
+                    position.distance(&target) < 2.0
                 }
-                ActionId::Rest => {
-                    world.elves.body_states[i].fatigue =
-                        (world.elves.body_states[i].fatigue - 0.01).max(0.0);
-                    let duration = task.action.base_duration();
-                    task.progress += 1.0 / duration as f32;
-                    task.progress >= 1.0
+                None => true,
+            },
+            ActionId::Flee | ActionId::SeekSafety => match target_pos {
+                Some(threat) => {
+                    position = step_away(&world.blocked_cells, position, threat, pace.flee * span);
+                    position.distance(&threat) > pace.safe_distance
                 }
-                _ => false,
-            };
-
-            // Progress for non-movement actions
-            let is_special = matches!(
-                action,
-                ActionId::MoveTo | ActionId::Flee | ActionId::SeekSafety | ActionId::Rest
-            );
-            if !is_special {
-                let duration = task.action.base_duration();
+                None => true,
+            },
+            ActionId::Rest => {
+                rested = true;
+                progress += span / duration as f32;
+                progress >= 1.0
+            }
+            _ => {
                 let progress_rate = match duration {
                     0 => 0.1,
                     1..=60 => 0.05,
                     _ => 0.02,
                 };
-                task.progress += progress_rate;
+                progress += progress_rate * span;
+                false
             }
+        };
+        let is_complete = movement_complete || (duration > 0 && progress >= 1.0);
+
+        let eaten = match action {
+            ActionId::Eat => world
+                .food_zones
+                .iter_mut()
+                .find(|zone| zone.contains(position))
+                .map(|zone| zone.consume(pace.bite * span)),
+            _ => None,
+        };
 
-            let duration = task.action.base_duration();
-            let progress_complete = duration > 0 && task.progress >= 1.0;
-            let is_complete = movement_complete || progress_complete;
-            (action, is_complete)
-        });
-
-        if let Some((action, is_complete)) = task_info {
-            // Handle Eat action
-            if action == ActionId::Eat {
-                let pos = world.elves.positions[i];
-                for zone in &mut world.food_zones {
-                    if zone.contains(pos) {
-                        let consumed = zone.consume(0.08); // Elves eat less
-                        if consumed > 0.0 {
-                            world.elves.needs[i].satisfy(NeedType::Food, consumed * 0.6);
-                        }
-                        break;
-                    }
-                }
-            } else {
-                for (need, amount) in action.satisfies_needs() {
-                    world.elves.needs[i].satisfy(need, amount * 0.05);
-                }
+        let Some(archetype) = world.archetype_mut(species) else {
+            return;
+        };
+        let parts = archetype.components_mut();
+        parts.positions[i] = position;
+        if rested {
+            let fatigue = &mut parts.body_states[i].fatigue;
+            *fatigue = (*fatigue - 0.01 * span).max(0.0);
+        }
+        if let Some(task) = parts.task_queues[i].current_mut() {
+            task.progress = progress;
+        }
+        if action == ActionId::Eat {
+            if let Some(consumed) = eaten.filter(|&consumed| consumed > 0.0) {
+                parts.needs[i].satisfy(NeedType::Food, consumed * pace.nourishment);
             }
-
-            if is_complete {
-                world.elves.task_queues[i].complete_current();
+        } else {
+            for (need, amount) in action.satisfies_needs() {
+                parts.needs[i].satisfy(need, amount * 0.05 * span);
             }
         }
-    }
-}
-
-/// Regenerate food for scarce zones
-///
-/// Scarce zones slowly replenish over time, up to their maximum capacity.
-/// This creates dynamic resource availability and strategic considerations.
-fn regenerate_food_zones(world: &mut World) {
-    for zone in &mut world.food_zones {
-        zone.regenerate();
+        if is_complete {
+            parts.task_queues[i].complete_current();
+        }
     }
 }
 
@@ -3334,25 +4203,18 @@ pub fn check_win_condition(world: &World) -> GameOutcome {
 /// - Remove near-zero encounters
 ///
 /// Called at the END of run_simulation_tick, AFTER world.tick() has advanced
-/// the counter, so we check (current_tick - 1) to see if we just completed a day.
+/// the counter, so we check whether the step just crossed into a new day.
 fn decay_social_memories(world: &mut World) {
     // Only decay once per day
-    if world.current_tick % TICKS_PER_DAY != 0 {
+    if !world.time_scale.new_day(world.current_tick) {
         return;
     }
 
     let current_tick = world.current_tick;
 
-    // Decay human social memories
-    let living_indices: Vec<usize> = world.humans.iter_living().collect();
-    for &idx in &living_indices {
-        world.humans.social_memories[idx].apply_decay(current_tick);
-    }
-
-    // Decay orc social memories
-    let orc_indices: Vec<usize> = world.orcs.iter_living().collect();
-    for &idx in &orc_indices {
-        world.orcs.social_memories[idx].apply_decay(current_tick);
+    // Every living entity of every species
+    for memory in world.query::<&mut SocialMemory>() {
+        memory.apply_decay(current_tick);
     }
 }
 
@@ -3364,27 +4226,17 @@ fn decay_social_memories(world: &mut World) {
 /// - Remove near-zero expectations (below SALIENCE_FLOOR)
 ///
 /// Called at the END of run_simulation_tick, AFTER world.tick() has advanced
-/// the counter, so we check when current_tick % TICKS_PER_DAY == 0.
+/// the counter, so we check whether the step just crossed into a new day.
 fn decay_expectations(world: &mut World) {
     // Only decay once per day
-    if world.current_tick % TICKS_PER_DAY != 0 {
+    if !world.time_scale.new_day(world.current_tick) {
         return;
     }
 
     const EXPECTATION_DECAY_RATE: f32 = 0.05;
 
-    // Decay human expectations
-    let living_indices: Vec<usize> = world.humans.iter_living().collect();
-    for idx in living_indices {
-        for slot in &mut world.humans.social_memories[idx].slots {
-            slot.decay_expectations(EXPECTATION_DECAY_RATE);
-        }
-    }
-
-    // Decay orc expectations
-    let orc_indices: Vec<usize> = world.orcs.iter_living().collect();
-    for idx in orc_indices {
-        for slot in &mut world.orcs.social_memories[idx].slots {
+    for memory in world.query::<&mut SocialMemory>() {
+        for slot in &mut memory.slots {
             slot.decay_expectations(EXPECTATION_DECAY_RATE);
         }
     }
@@ -3444,6 +4296,12 @@ fn create_social_memory_from_task(
 mod tests {
     use super::*;
 
+    /// Start the clock just after 11:00 so work runs in full daylight
+    fn start_at_midday(world: &mut World) {
+        world.astronomy.tick = TICKS_PER_DAY * 11 / 24;
+        world.astronomy.advance_tick();
+    }
+
     #[test]
     fn test_tick_advances_astronomy() {
         let mut world = World::new();
@@ -4323,6 +5181,7 @@ mod tests {
         use crate::skills::ChunkLibrary;
 
         let mut world = World::new();
+        start_at_midday(&mut world);
 
         // Create nearly depleted resource zone
         let mut zone = ResourceZone::new(Vec2::new(10.0, 0.0), ResourceType::Stone, 5.0);
@@ -4689,6 +5548,7 @@ mod tests {
         use crate::skills::ChunkLibrary;
 
         let mut world = World::new();
+        start_at_midday(&mut world);
         let entity = world.spawn_human("Builder".into());
 
         // Spawn a building
@@ -4733,6 +5593,7 @@ mod tests {
         use crate::skills::ChunkLibrary;
 
         let mut world = World::new();
+        start_at_midday(&mut world);
         let entity = world.spawn_human("Builder".into());
 
         // Spawn a wall (80 work required)
@@ -4780,6 +5641,7 @@ mod tests {
         use crate::skills::ChunkLibrary;
 
         let mut world = World::new();
+        start_at_midday(&mut world);
         let entity = world.spawn_human("Builder".into());
 
         // Spawn a house (100 work required)
@@ -4820,6 +5682,317 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_action_at_planned_road_cell() {
+        use crate::actions::catalog::ActionId;
+        use crate::city::road::RoadGrade;
+        use crate::core::types::Vec2;
+        use crate::entity::tasks::{Task, TaskPriority};
+        use crate::skills::ChunkLibrary;
+
+        let mut world = World::new();
+        let entity = world.spawn_human("Roadworker".into());
+        let idx = world.humans.index_of(entity).unwrap();
+        world.humans.positions[idx] = Vec2::new(20.0, 21.0);
+        world.humans.building_skills[idx] = 1.0;
+        world.humans.chunk_libraries[idx] = ChunkLibrary::trained_worker(world.current_tick);
+
+        // One dirt road cell (25 work, 1.0 per tick at full skill)
+        let planned = world.roads.plan_road(
+            Vec2::new(21.0, 21.0),
+            Vec2::new(21.0, 21.0),
+            RoadGrade::DirtRoad,
+            &mut world.stockpile,
+        );
+        assert_eq!(planned, 1);
+
+        let task =
+            Task::new(ActionId::Build, TaskPriority::Normal, 0).with_position(Vec2::new(21.0, 21.0));
+        world.humans.task_queues[idx].push(task);
+
+        for _ in 0..60 {
+            run_simulation_tick(&mut world);
+        }
+
+        assert!(world.roads.sites.is_empty());
+        assert_eq!(
+            world.roads.grade_at(Vec2::new(21.0, 21.0)),
+            Some(RoadGrade::DirtRoad)
+        );
+    }
+
+    #[test]
+    fn test_build_action_opens_bridge() {
+        use crate::actions::catalog::ActionId;
+        use crate::blueprints::BlueprintRegistry;
+        use crate::core::types::Vec2;
+        use crate::entity::tasks::{Task, TaskPriority};
+        use crate::simulation::resource_zone::ResourceType;
+        use crate::skills::ChunkLibrary;
+        use crate::world::TerrainFeatureKind;
+
+        let mut registry = BlueprintRegistry::new();
+        registry
+            .load_directory(std::path::Path::new("data/blueprints"))
+            .unwrap();
+
+        let mut world = World::new();
+        world.add_terrain_feature(
+            TerrainFeatureKind::Stream,
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 20.0),
+            2.0,
+            1.0,
+        );
+        world.stockpile.add(ResourceType::Wood, 100);
+        world.stockpile.add(ResourceType::Stone, 100);
+        world
+            .plan_crossing(&registry, Vec2::new(10.0, 10.0), 1.0)
+            .unwrap();
+
+        let entity = world.spawn_human("Bridgewright".into());
+        let idx = world.humans.index_of(entity).unwrap();
+        world.humans.positions[idx] = Vec2::new(6.0, 10.0);
+        world.humans.building_skills[idx] = 1.0;
+        world.humans.chunk_libraries[idx] = ChunkLibrary::trained_worker(world.current_tick);
+
+        // span 2 x width 1: 30 work
+        let task = Task::new(ActionId::Build, TaskPriority::Normal, 0)
+            .with_position(Vec2::new(10.0, 10.0));
+        world.humans.task_queues[idx].push(task);
+
+        let on_bridge = glam::Vec2::new(10.0, 10.0);
+        assert!(world.blocked_cells.is_position_blocked(on_bridge));
+        for _ in 0..60 {
+            run_simulation_tick(&mut world);
+        }
+
+        assert_eq!(world.crossings[0].state, CrossingState::Complete);
+        assert!(!world.blocked_cells.is_position_blocked(on_bridge));
+    }
+
+    #[test]
+    fn test_move_to_is_faster_on_roads_and_wears_paths() {
+        use crate::actions::catalog::ActionId;
+        use crate::city::road::RoadGrade;
+        use crate::core::types::Vec2;
+        use crate::entity::tasks::{Task, TaskPriority};
+        use crate::simulation::resource_zone::ResourceType;
+
+        let distance_after_one_tick = |world: &mut World| {
+            let entity = world.spawn_human("Walker".into());
+            let idx = world.humans.index_of(entity).unwrap();
+            world.humans.positions[idx] = Vec2::new(1.0, 1.0);
+            let task = Task::new(ActionId::MoveTo, TaskPriority::High, 0)
+                .with_position(Vec2::new(41.0, 1.0));
+            world.humans.task_queues[idx].push(task);
+            run_simulation_tick(world);
+            world.humans.positions[idx].x - 1.0
+        };
+
+        let mut plain = World::new();
+        let off_road = distance_after_one_tick(&mut plain);
+        assert!(plain.roads.cells.contains_key(&(0, 0)));
+
+        let mut paved = World::new();
+        paved.stockpile.add(ResourceType::Stone, 100);
+        paved.roads.plan_road(
+            Vec2::new(0.0, 1.0),
+            Vec2::new(40.0, 1.0),
+            RoadGrade::StoneRoad,
+            &mut paved.stockpile,
+        );
+        let cells: Vec<_> = paved.roads.sites.keys().copied().collect();
+        for cell in cells {
+            paved.roads.apply_work(cell, 100.0);
+        }
+        let on_road = distance_after_one_tick(&mut paved);
+
+        assert!(
+            on_road > off_road,
+            "Road should speed movement: {} vs {}",
+            on_road,
+            off_road
+        );
+    }
+
+    #[test]
+    fn test_scheduler_times_and_spreads_systems() {
+        use crate::simulation::scheduler::{Scheduler, SystemId};
+
+        let mut world = World::with_seed(3);
+        for name in ["Ada", "Bram", "Cora"] {
+            world.spawn_human(name.into());
+        }
+        world.scheduler = Scheduler::large_settlement();
+
+        for _ in 0..10 {
+            run_simulation_tick(&mut world);
+        }
+
+        let scheduler = &world.scheduler;
+        assert_eq!(scheduler.timing(SystemId::Perception).runs, 10);
+        // Amortized systems still run every tick, over a share of people
+        assert_eq!(scheduler.timing(SystemId::Expectations).runs, 10);
+        // Memory consolidation runs every 10 ticks
+        assert_eq!(scheduler.timing(SystemId::MemoryConsolidation).runs, 1);
+        assert_eq!(scheduler.timing(SystemId::Daily).runs, 0);
+    }
+
+    #[test]
+    fn test_scheduler_visits_every_share_under_compressed_time() {
+        use crate::simulation::scheduler::{Scheduler, SystemId};
+        use crate::simulation::time_scale::TimeScale;
+
+        let mut world = World::with_seed(3);
+        world.time_scale = TimeScale::compressed(100).unwrap();
+        world.scheduler = Scheduler::large_settlement();
+
+        // Amortized(4) over four steps of ten ticks each covers everyone
+        let mut covered = [0; 4];
+        for _ in 0..4 {
+            let share = world
+                .scheduler
+                .due(SystemId::Expectations, schedule_step(&world))
+                .unwrap();
+            for (entity, count) in covered.iter_mut().enumerate() {
+                *count += share.includes(entity) as u32;
+            }
+            run_simulation_tick(&mut world);
+        }
+        assert_eq!(world.current_tick, 40);
+        assert_eq!(covered, [1; 4]);
+
+        // Memory consolidation every 10 steps, not every 10 ticks
+        for _ in 0..6 {
+            run_simulation_tick(&mut world);
+        }
+        assert_eq!(
+            world.scheduler.timing(SystemId::MemoryConsolidation).runs,
+            1
+        );
+    }
+
+    #[test]
+    fn test_dwarves_and_elves_walk_at_their_own_pace() {
+        use crate::actions::catalog::ActionId;
+        use crate::core::types::Vec2;
+        use crate::entity::tasks::{Task, TaskPriority};
+
+        let mut world = World::with_seed(3);
+        world.spawn_dwarf("Thrain".into());
+        world.spawn_elf("Lirael".into());
+        let walk =
+            Task::new(ActionId::MoveTo, TaskPriority::High, 0).with_position(Vec2::new(100.5, 0.5));
+        world.dwarves.positions[0] = Vec2::new(0.5, 0.5);
+        world.dwarves.task_queues[0].push(walk.clone());
+        world.elves.positions[0] = Vec2::new(0.5, 0.5);
+        world.elves.task_queues[0].push(walk);
+
+        run_simulation_tick(&mut world);
+        let walked = |pos: Vec2| pos.distance(&Vec2::new(0.5, 0.5));
+        assert!((walked(world.dwarves.positions[0]) - DWARF_PACE.walk).abs() < 1e-3);
+        assert!((walked(world.elves.positions[0]) - ELF_PACE.walk).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_move_to_walks_around_blocked_cells() {
+        use crate::actions::catalog::ActionId;
+        use crate::core::types::Vec2;
+        use crate::entity::tasks::{Task, TaskPriority};
+
+        let mut world = World::with_seed(3);
+        for y in -10..=10 {
+            world.blocked_cells.block(10, y);
+        }
+        let entity = world.spawn_human("Walker".into());
+        let idx = world.humans.index_of(entity).unwrap();
+        world.humans.positions[idx] = Vec2::new(2.5, 0.5);
+        let task = Task::new(ActionId::MoveTo, TaskPriority::High, 0)
+            .with_position(Vec2::new(20.5, 0.5));
+        world.humans.task_queues[idx].push(task);
+
+        for _ in 0..40 {
+            run_simulation_tick(&mut world);
+            let pos = world.humans.positions[idx];
+            assert!(!world
+                .blocked_cells
+                .is_position_blocked(glam::Vec2::new(pos.x, pos.y)));
+        }
+        assert!(world.humans.positions[idx].x > 11.0);
+    }
+
+    #[test]
+    fn test_work_ticks_count_toward_exhaustion() {
+        use crate::actions::catalog::ActionId;
+        use crate::entity::tasks::{Task, TaskPriority};
+
+        let mut world = World::new();
+        world.spawn_human("Worker".into());
+        world.spawn_human("Idler".into());
+        world.humans.task_queues[0].push(Task::new(ActionId::Build, TaskPriority::High, 0));
+
+        for _ in 0..5 {
+            run_simulation_tick(&mut world);
+        }
+        assert_eq!(world.humans.body_states[0].work_ticks_today, 5);
+        assert_eq!(world.humans.body_states[1].work_ticks_today, 0);
+    }
+
+    #[test]
+    fn test_filthy_neighbour_causes_disgust() {
+        use crate::core::types::Vec2;
+
+        let mut world = World::new();
+        world.spawn_human("Neat".into());
+        world.spawn_human("Reeking".into());
+        world.humans.positions[0] = Vec2::new(0.0, 0.0);
+        world.humans.positions[1] = Vec2::new(2.0, 0.0);
+        world.humans.needs[1].hygiene = 0.95;
+
+        let (perceptions, _) = run_perception_with_ranges(&world, f32::INFINITY);
+        generate_thoughts_with_events(&mut world, &perceptions, &mut Vec::new());
+
+        let disgusted = |i: usize, world: &World| {
+            world.humans.thoughts[i]
+                .iter()
+                .any(|t| t.concept_category == "disgust")
+        };
+        assert!(disgusted(0, &world));
+        assert!(!disgusted(1, &world));
+    }
+
+    #[test]
+    fn test_darkness_hides_entities_without_light() {
+        use crate::core::types::Vec2;
+        use crate::simulation::resource_zone::ResourceType;
+        use crate::world::LightKind;
+
+        let mut world = World::new();
+        let observer = world.spawn_human("Watcher".into());
+        let target = world.spawn_human("Prowler".into());
+        world.humans.positions[0] = Vec2::new(0.0, 0.0);
+        world.humans.positions[1] = Vec2::new(15.0, 0.0);
+        world.astronomy.light_level = 0.0;
+
+        let sees_target = |world: &World| {
+            run_perception_with_ranges(world, f32::INFINITY)
+                .0
+                .iter()
+                .find(|p| p.observer == observer)
+                .unwrap()
+                .perceived_entities
+                .iter()
+                .any(|e| e.entity == target)
+        };
+        assert!(!sees_target(&world), "Unlit prowler should stay hidden");
+
+        world.stockpile.add(ResourceType::Wood, 1);
+        world.give_light(target, LightKind::Torch).unwrap();
+        tend_lights(&mut world);
+        assert!(sees_target(&world), "Torch should give the prowler away");
+    }
+
     #[test]
     fn test_perception_includes_nearby_building_site() {
         use crate::city::building::BuildingType;
@@ -5046,6 +6219,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_attacks_follow_faction_diplomacy() {
+        use crate::core::types::Vec2;
+        use crate::entity::tasks::{Task, TaskPriority};
+        use crate::world::Stance;
+
+        let mut world = World::with_seed(7);
+        let settler = world.spawn_human("Settler".into());
+        let stranger = world.spawn_human("Stranger".into());
+        let riverfolk = world.factions.found("Riverfolk".into());
+        world.factions.join(stranger, riverfolk).unwrap();
+        let settlement = world.factions.settlement().unwrap();
+        let (settler_idx, stranger_idx) = (0, 1);
+        world.humans.positions[settler_idx] = Vec2::new(0.0, 0.0);
+        world.humans.positions[stranger_idx] = Vec2::new(1.0, 0.0);
+
+        let attack = |world: &mut World| {
+            let task = Task::new(ActionId::Attack, TaskPriority::Critical, 0).with_entity(stranger);
+            world.humans.task_queues[settler_idx].push(task);
+            (0..10)
+                .flat_map(|_| run_simulation_tick(world))
+                .collect::<Vec<_>>()
+        };
+
+        // Allies will not come to blows
+        world
+            .factions
+            .set_stance(settlement, riverfolk, Stance::Allied)
+            .unwrap();
+        let events = attack(&mut world);
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, SimulationEvent::CombatHit { .. })));
+
+        // Striking a neutral faction's member starts a war
+        world
+            .factions
+            .set_stance(settlement, riverfolk, Stance::Neutral)
+            .unwrap();
+        world.humans.task_queues[settler_idx] = Default::default();
+        let events = attack(&mut world);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SimulationEvent::WarDeclared { faction, .. } if faction == "Riverfolk"
+        )));
+        assert!(world.factions.are_hostile(stranger, settler));
+    }
+
     #[test]
     fn test_unarmored_vs_armored_combat() {
         use crate::combat::{combat_state_for_role, Coverage, Edge, Rigidity};
@@ -5141,4 +6362,59 @@ mod tests {
         // Should still be in progress because dwarves are alive
         assert_eq!(outcome, GameOutcome::InProgress);
     }
+
+    #[test]
+    fn test_homeless_rest_in_nearest_dormitory() {
+        use crate::entity::tasks::TaskPriority;
+        use crate::world::{ZoneDesignation, ZoneShape};
+
+        let mut world = World::with_seed(5);
+        world.spawn_human("Ada".into());
+        world.humans.positions[0] = crate::core::types::Vec2::new(0.0, 0.0);
+        let longhouse = ZoneShape::Hex {
+            center: crate::core::types::Vec2::new(20.0, 0.0),
+            radius: 4.0,
+        };
+        world.designate_zone(ZoneDesignation {
+            kind: ZoneKind::Dormitory,
+            shape: longhouse,
+            name: None,
+        });
+
+        let mut rest = Task::new(ActionId::Rest, TaskPriority::High, 0);
+        bed_down_in_dormitory(&world, 0, &mut rest);
+        assert!(rest.target_position.is_some_and(|bed| bed.x == 20.0));
+        world.humans.task_queues[0].push(rest);
+
+        for _ in 0..15 {
+            run_simulation_tick(&mut world);
+        }
+        assert!(longhouse.contains(world.humans.positions[0]));
+
+        // Someone with a house of their own sleeps where they are
+        world.humans.positions[0] = crate::core::types::Vec2::new(0.0, 0.0);
+        world.humans.assigned_houses[0] = Some(crate::city::building::BuildingId::new());
+        let mut rest = Task::new(ActionId::Rest, TaskPriority::High, 0);
+        bed_down_in_dormitory(&world, 0, &mut rest);
+        assert!(rest.target_position.is_none());
+    }
+
+    #[test]
+    fn test_orcs_leave_the_dead_alone() {
+        use crate::entity::tasks::TaskPriority;
+
+        let mut world = World::with_seed(5);
+        let victim = world.spawn_human("Ada".into());
+        world.spawn_orc("Grukk".into());
+        world.humans.alive[0] = false;
+        world.humans.body_states[0].fatigue = 1.0;
+        world.orcs.task_queues[0]
+            .push(Task::new(ActionId::Attack, TaskPriority::High, 0).with_entity(victim));
+
+        let mut events = Vec::new();
+        execute_orc_tasks(&mut world, &mut events);
+        assert!(world.orcs.task_queues[0].current().is_none());
+        assert!(world.legacy.chronicle.is_empty());
+        assert!(events.is_empty());
+    }
 }
@@ -0,0 +1,257 @@
+//! Time scale - how much time one simulation step covers
+//!
+//! In detailed play a step is one tick, `TICKS_PER_DAY` to the day. A
+//! compressed scale runs fewer, longer steps a day for fast-forwarding
+//! through quiet seasons: each step covers `span()` ticks, and the tick
+//! counter, calendar, and sky advance by that much.
+//!
+//! Every rate applied once per step is rescaled so a day plays out the same
+//! either way. Linear rates (need decay, thought fading, task progress,
+//! walking, eating, regrowth, fuel, production, orc temperament) are
+//! multiplied by the span, which is exact. Daily systems run whenever a step
+//! crosses into a new day. Detail finer than a step is lost: a walker
+//! arrives, or a task finishes, within the step rather than on the exact
+//! tick; and a fumble spoils a whole step's work (as often, on average, as
+//! tick by tick).
+//!
+//! Blows in a fight land once per step and cannot be rescaled, so compressed
+//! time is refused while anyone is fighting: `hold_for_fighting` drops the
+//! world to detailed steps from the step after a combat task is taken up or
+//! a blow is struck, and goes back to the compressed scale once the fight
+//! has lulled and the tick lines up with a compressed step again.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::actions::catalog::ActionCategory;
+use crate::ecs::world::World;
+use crate::simulation::tick::TICKS_PER_DAY;
+use crate::world::FIGHT_LULL_TICKS;
+
+/// Steps a day in the default compressed scale
+pub const COMPRESSED_TICKS_PER_DAY: u64 = 100;
+
+/// Why a time scale could not be set
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TimeScaleError {
+    #[error("{0} steps a day does not divide the {TICKS_PER_DAY} ticks of a day")]
+    Uneven(u64),
+}
+
+/// How many steps the simulation takes to a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimeScale {
+    /// One tick a step
+    #[default]
+    Detailed,
+    /// Fewer, longer steps
+    Compressed { ticks_per_day: u64 },
+}
+
+impl TimeScale {
+    /// A compressed scale of `ticks_per_day` steps a day, which must divide
+    /// the day evenly
+    pub fn compressed(ticks_per_day: u64) -> Result<Self, TimeScaleError> {
+        if ticks_per_day == 0 || !TICKS_PER_DAY.is_multiple_of(ticks_per_day) {
+            return Err(TimeScaleError::Uneven(ticks_per_day));
+        }
+        Ok(match ticks_per_day {
+            TICKS_PER_DAY => TimeScale::Detailed,
+            ticks_per_day => TimeScale::Compressed { ticks_per_day },
+        })
+    }
+
+    /// Steps a day
+    pub fn ticks_per_day(&self) -> u64 {
+        match self {
+            TimeScale::Detailed => TICKS_PER_DAY,
+            TimeScale::Compressed { ticks_per_day } => *ticks_per_day,
+        }
+    }
+
+    /// Ticks one step covers
+    pub fn span(&self) -> u64 {
+        TICKS_PER_DAY / self.ticks_per_day()
+    }
+
+    /// A per-tick amount over one step
+    pub fn rate(&self, per_tick: f32) -> f32 {
+        per_tick * self.span() as f32
+    }
+
+    /// Whether the step that just ended at `tick` crossed into a new day
+    pub fn new_day(&self, tick: u64) -> bool {
+        tick % TICKS_PER_DAY < self.span()
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            TimeScale::Detailed => "detailed".to_string(),
+            TimeScale::Compressed { ticks_per_day } => {
+                format!("compressed ({} steps a day)", ticks_per_day)
+            }
+        }
+    }
+}
+
+/// Whether anyone in the world has a combat task under way, or the last
+/// fight has yet to lull (a compressed step can take up and finish an attack
+/// within itself, so a blow struck in the last step counts)
+pub fn fighting(world: &World) -> bool {
+    let lull = FIGHT_LULL_TICKS.max(world.time_scale.span());
+    let blows_lately = world
+        .situation
+        .fights
+        .last()
+        .is_some_and(|fight| world.current_tick.saturating_sub(fight.last_blow) <= lull);
+    blows_lately
+        || world.archetypes().iter().any(|archetype| {
+            let parts = archetype.components();
+            parts.living().any(|i| {
+                parts.task_queues[i]
+                    .current()
+                    .is_some_and(|task| task.action.category() == ActionCategory::Combat)
+            })
+        })
+}
+
+/// Put off compressed time while anyone is fighting, and take it up again
+/// once the fight is over and the tick lines up with a compressed step
+pub fn hold_for_fighting(world: &mut World) {
+    if fighting(world) {
+        if world.time_scale != TimeScale::Detailed {
+            world.held_time_scale = Some(world.time_scale);
+            world.time_scale = TimeScale::Detailed;
+        }
+    } else if let Some(held) = world.held_time_scale {
+        if world.current_tick.is_multiple_of(held.span()) {
+            world.time_scale = held;
+            world.held_time_scale = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::catalog::ActionId;
+    use crate::core::types::Vec2;
+    use crate::ecs::world::Abundance;
+    use crate::entity::tasks::{Task, TaskPriority};
+    use crate::simulation::tick::{run_simulation_tick, SimulationEvent};
+
+    #[test]
+    fn test_scales_must_divide_the_day() {
+        assert_eq!(TimeScale::compressed(1000), Ok(TimeScale::Detailed));
+        let scale = TimeScale::compressed(COMPRESSED_TICKS_PER_DAY).unwrap();
+        assert_eq!(scale.span(), 10);
+        assert_eq!(scale.rate(0.5), 5.0);
+        assert_eq!(TimeScale::compressed(300), Err(TimeScaleError::Uneven(300)));
+        assert!(scale.new_day(1005));
+        assert!(!scale.new_day(1010));
+        assert!(TimeScale::Detailed.new_day(2000));
+        assert!(!TimeScale::Detailed.new_day(2001));
+    }
+
+    #[test]
+    fn test_a_compressed_day_matches_a_detailed_one() {
+        let run_day = |scale: TimeScale, settle: fn(&mut World)| {
+            let mut world = World::with_seed(57);
+            settle(&mut world);
+            world.time_scale = scale;
+            for _ in 0..scale.ticks_per_day() {
+                run_simulation_tick(&mut world);
+            }
+            world
+        };
+        let compressed_scale = TimeScale::compressed(COMPRESSED_TICKS_PER_DAY).unwrap();
+        let human = |world: &mut World| {
+            world.spawn_human("Ada".into());
+        };
+        // A hungry elf at a food zone eats through the day
+        let elf = |world: &mut World| {
+            world.spawn_elf("Lirael".into());
+            world.elves.positions[0] = Vec2::new(40.0, 40.0);
+            world.elves.needs[0].food = 0.9;
+            world.add_food_zone(Vec2::new(40.0, 40.0), 5.0, Abundance::Unlimited);
+        };
+        let detailed = run_day(TimeScale::Detailed, human);
+        let compressed = run_day(compressed_scale, human);
+        let detailed_elf = run_day(TimeScale::Detailed, elf);
+        let compressed_elf = run_day(compressed_scale, elf);
+
+        assert_eq!(compressed.current_tick, detailed.current_tick);
+        assert_eq!(
+            compressed.astronomy.current_day,
+            detailed.astronomy.current_day
+        );
+        for (a, b) in [
+            (&detailed.humans.needs[0], &compressed.humans.needs[0]),
+            (&detailed_elf.elves.needs[0], &compressed_elf.elves.needs[0]),
+        ] {
+            for (x, y) in [
+                (a.rest, b.rest),
+                (a.food, b.food),
+                (a.social, b.social),
+                (a.purpose, b.purpose),
+                (a.hygiene, b.hygiene),
+            ] {
+                assert!((x - y).abs() < 1e-3, "{} vs {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fighting_holds_compressed_time() {
+        let mut world = World::with_seed(5);
+        let victim = world.spawn_human("Ada".into());
+        world.spawn_orc("Grashnak".into());
+        world.orcs.positions[0] = Vec2::new(500.0, 500.0);
+        world.set_time_scale(TimeScale::compressed(COMPRESSED_TICKS_PER_DAY).unwrap());
+
+        world.orcs.task_queues[0]
+            .push(Task::new(ActionId::Attack, TaskPriority::Critical, 0).with_entity(victim));
+        assert!(fighting(&world));
+        run_simulation_tick(&mut world);
+        assert_eq!(world.current_tick, 1);
+        assert_eq!(world.time_scale, TimeScale::Detailed);
+
+        // Once the fight has lulled after the blow struck at tick 0,
+        // compressed steps resume on a step boundary
+        world.orcs.task_queues[0].clear();
+        let resumed = FIGHT_LULL_TICKS + 10 - FIGHT_LULL_TICKS % 10;
+        while world.current_tick < resumed {
+            run_simulation_tick(&mut world);
+            assert_eq!(world.time_scale, TimeScale::Detailed);
+        }
+        run_simulation_tick(&mut world);
+        assert_eq!(world.current_tick, resumed + 10);
+        assert_eq!(world.held_time_scale, None);
+    }
+
+    #[test]
+    fn test_a_blow_struck_within_a_compressed_step_holds_compressed_time() {
+        let mut world = World::with_seed(6);
+        world.spawn_human("Ada".into());
+        world.spawn_orc("Grashnak".into());
+        world.orcs.positions[0] = Vec2::new(1.0, 0.0);
+        world.set_time_scale(TimeScale::compressed(1).unwrap());
+
+        // The orc takes up an attack and lands it inside one day-long step
+        let mut struck = false;
+        for _ in 0..20 {
+            let events = run_simulation_tick(&mut world);
+            struck = events
+                .iter()
+                .any(|e| matches!(e, SimulationEvent::CombatHit { .. }));
+            if struck {
+                break;
+            }
+        }
+        assert!(struck);
+        let tick = world.current_tick;
+        run_simulation_tick(&mut world);
+        assert_eq!(world.current_tick, tick + 1);
+        assert_eq!(world.time_scale, TimeScale::Detailed);
+    }
+}
@@ -0,0 +1,112 @@
+//! Time sync - the settlement's side of the shared world clock
+//!
+//! `enter_battle` stops the settlement's clock as a battle begins, and
+//! `run_simulation_tick` leaves the settlement standing while it is fought.
+//! `leave_battle` takes the battle's length in battle ticks and has the
+//! settlement live through the same span of its own time, step by step, so
+//! needs, work, and the sky come out as if it had run alongside.
+//!
+//! Lines from the battle go on the chronicle through `record_battle_event`,
+//! stamped with the instant they happened rather than the battle's end.
+//! `catch_up_campaign` brings a campaign's day up to the world's, so every
+//! layer reads the same date.
+
+use crate::campaign::route::{campaign_tick, CampaignEvent, CampaignState};
+use crate::core::time::{battle_ticks_to_ticks, ClockError, Timestamp};
+use crate::ecs::world::World;
+use crate::simulation::tick::{run_simulation_tick, SimulationEvent};
+use crate::world::identity::Layer;
+
+/// Stop the settlement's clock for a battle; returns the instant it began
+pub fn enter_battle(world: &mut World) -> Result<Timestamp, ClockError> {
+    let now = world.now();
+    world.clock.hold(Layer::Battle, now)?;
+    Ok(now)
+}
+
+/// End the battle after `battle_ticks`, running the settlement through the
+/// time it took; returns what happened in the settlement meanwhile
+pub fn leave_battle(
+    world: &mut World,
+    battle_ticks: u64,
+) -> Result<Vec<SimulationEvent>, ClockError> {
+    let until = world
+        .clock
+        .release(Layer::Battle, battle_ticks_to_ticks(battle_ticks))?;
+    let mut events = Vec::new();
+    while world.current_tick < until.tick() {
+        events.extend(run_simulation_tick(world));
+    }
+    Ok(events)
+}
+
+/// Put a line from the battle under way on the chronicle, at the instant
+/// `battle_tick` ticks into it
+pub fn record_battle_event(
+    world: &mut World,
+    battle_tick: u64,
+    text: impl Into<String>,
+) -> Result<Timestamp, ClockError> {
+    let Some((Layer::Battle, began)) = world.clock.held() else {
+        return Err(ClockError::NotHeld(Layer::Battle));
+    };
+    let at = began.at_battle_tick(battle_tick);
+    world.legacy.record(at.tick(), text);
+    Ok(at)
+}
+
+/// Advance a campaign to the day `now` falls in, if it is behind
+pub fn catch_up_campaign(campaign: &mut CampaignState, now: Timestamp) -> Vec<CampaignEvent> {
+    let behind = now.campaign_day() - campaign.current_day;
+    if behind <= 0.0 {
+        return Vec::new();
+    }
+    campaign_tick(campaign, behind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaign::map::CampaignMap;
+
+    #[test]
+    fn test_settlement_waits_out_a_battle_then_lives_through_it() {
+        let mut world = World::with_seed(65);
+        world.spawn_human("Marcus".into());
+        for _ in 0..10 {
+            run_simulation_tick(&mut world);
+        }
+
+        let began = enter_battle(&mut world).unwrap();
+        assert_eq!(
+            enter_battle(&mut world),
+            Err(ClockError::AlreadyHeld(Layer::Battle))
+        );
+        // Held: the settlement does not move
+        run_simulation_tick(&mut world);
+        assert_eq!(world.now(), began);
+
+        let stamped = record_battle_event(&mut world, 200, "The line broke at the ford").unwrap();
+        assert_eq!(stamped, began.at_battle_tick(200));
+        assert_eq!(world.legacy.chronicle.last().unwrap().tick, stamped.tick());
+
+        // Five minutes of fighting is four settlement ticks
+        leave_battle(&mut world, 300).unwrap();
+        assert_eq!(world.now(), began.after(4));
+        assert_eq!(world.astronomy.tick, world.current_tick);
+        assert!(matches!(
+            leave_battle(&mut world, 300),
+            Err(ClockError::NotHeld(Layer::Battle))
+        ));
+    }
+
+    #[test]
+    fn test_campaign_catches_up_to_the_world_day() {
+        let mut campaign = CampaignState::new(CampaignMap::generate_simple(5, 5, 42));
+        catch_up_campaign(&mut campaign, Timestamp(2500));
+        assert_eq!(campaign.current_day, 2.5);
+        // Already there: nothing to do
+        assert!(catch_up_campaign(&mut campaign, Timestamp(2000)).is_empty());
+        assert_eq!(campaign.current_day, 2.5);
+    }
+}
@@ -0,0 +1,336 @@
+//! Transformations - rare turns that reshape what a settler knows and wants
+//!
+//! The transformation table is read from `data/transformations.toml` into
+//! `world.transformation_rules`. Turns in a settler's life are noted as they
+//! happen (`note_head_wound` in fights, and by `simulation::despair` and
+//! `simulation::aspirations`), and once a day `run_transformations` weighs
+//! them: each transformation set off by the turn gets its chance, in the
+//! table's order, and the first to come up changes the settler for good.
+//!
+//! - Each domain it names has its chunks' encoding depth scaled (faded by
+//!   a factor below one, sharpened by one above, never past mastery) or
+//!   its chunks forgotten outright.
+//! - Each value it names is shifted, within 0.0-1.0.
+//! - The chronicle records it.
+//!
+//! Nobody undergoes the same transformation twice, and turns of those who
+//! died or left before the end of the day come to nothing.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use rand::Rng;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::combat::{BodyZone, Wound, WoundSeverity};
+use crate::core::types::{EntityId, Tick};
+use crate::ecs::world::World;
+use crate::entity::species::human::HumanValues;
+use crate::entity::species::value_access::ValueAccessor;
+use crate::simulation::epithets::known_as;
+use crate::simulation::happiness::in_settlement;
+use crate::skills::ChunkDomain;
+use crate::world::{TransformationTrigger, Transformations, Transformed};
+
+/// Errors from loading the transformation table
+#[derive(Error, Debug)]
+pub enum TransformationError {
+    /// Reading the file failed
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The file is not valid transformation TOML
+    #[error("Parse error: {0}")]
+    ParseError(#[from] toml::de::Error),
+    /// A transformation shifts a value settlers do not have
+    #[error("Transformation '{transformation}' names unknown value '{value}'")]
+    UnknownValue {
+        transformation: String,
+        value: String,
+    },
+}
+
+/// What a transformation does to what someone knows of one domain
+#[derive(Debug, Clone, Deserialize)]
+pub struct DomainChange {
+    pub domain: ChunkDomain,
+    /// Factor on the encoding depth of the domain's chunks
+    #[serde(default)]
+    pub depth: Option<f32>,
+    /// Forget the domain's chunks entirely
+    #[serde(default)]
+    pub forget: bool,
+}
+
+/// One transformation in the table
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransformationRule {
+    pub name: String,
+    pub trigger: TransformationTrigger,
+    /// Chance it follows each time its trigger happens
+    pub chance: f32,
+    /// What the chronicle records ("{name}" is the settler)
+    pub chronicle: String,
+    #[serde(default)]
+    pub domains: Vec<DomainChange>,
+    /// Shifts to values, by name
+    #[serde(default)]
+    pub values: BTreeMap<String, f32>,
+}
+
+/// The transformation table
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TransformationRules {
+    #[serde(default)]
+    pub transformations: Vec<TransformationRule>,
+}
+
+impl TransformationRules {
+    /// No transformations: settlers stay who they are
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(content: &str) -> Result<Self, TransformationError> {
+        let rules: Self = toml::from_str(content)?;
+        for rule in &rules.transformations {
+            if let Some(value) = rule
+                .values
+                .keys()
+                .find(|v| !HumanValues::field_names().contains(&v.as_str()))
+            {
+                return Err(TransformationError::UnknownValue {
+                    transformation: rule.name.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TransformationError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// The transformations a trigger can set off, in the table's order
+    pub fn set_off_by(
+        &self,
+        trigger: TransformationTrigger,
+    ) -> impl Iterator<Item = &TransformationRule> {
+        self.transformations
+            .iter()
+            .filter(move |t| t.trigger == trigger)
+    }
+}
+
+/// Load the transformation table from `data/transformations.toml`
+pub(crate) fn load_default_transformation_rules() -> TransformationRules {
+    TransformationRules::load("data/transformations.toml").unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load transformations: {}", e);
+        TransformationRules::new()
+    })
+}
+
+/// Note a wound someone took; a wound to the head is a turn in their life
+pub fn note_head_wound(
+    transformations: &mut Transformations,
+    id: EntityId,
+    wound: &Wound,
+    tick: Tick,
+) {
+    if wound.zone == BodyZone::Head && wound.severity != WoundSeverity::None {
+        transformations.note(id, TransformationTrigger::HeadWound, tick);
+    }
+}
+
+/// Change human `i` as a transformation says, and chronicle it
+pub fn transform(world: &mut World, i: usize, rule: &TransformationRule) {
+    let tick = world.current_tick;
+    let library = &mut world.humans.chunk_libraries[i];
+    for change in &rule.domains {
+        if change.forget {
+            library
+                .chunks_mut()
+                .retain(|id, _| id.domain() != change.domain);
+            continue;
+        }
+        let Some(factor) = change.depth else {
+            continue;
+        };
+        for (_, chunk) in library
+            .chunks_mut()
+            .iter_mut()
+            .filter(|(id, _)| id.domain() == change.domain)
+        {
+            chunk.encoding_depth = (chunk.encoding_depth * factor).clamp(0.0, 1.0);
+        }
+    }
+    let values = &mut world.humans.values[i];
+    for (value, shift) in &rule.values {
+        if let Some(current) = values.get_value(value) {
+            values.set_value(value, (current + shift).clamp(0.0, 1.0));
+        }
+    }
+
+    let id = world.humans.ids[i];
+    world.transformations.undergone.push(Transformed {
+        id,
+        name: rule.name.clone(),
+        tick,
+    });
+    let text = rule.chronicle.replace("{name}", &known_as(world, i));
+    world.legacy.record(tick, text);
+}
+
+/// Weigh the day's turns in settlers' lives; returns how many changed
+/// someone. Run daily.
+pub fn run_transformations<R: Rng>(world: &mut World, rng: &mut R) -> usize {
+    let turns = std::mem::take(&mut world.transformations.pending);
+    let mut changed = 0;
+    for turn in turns {
+        let Some(i) = world.humans.index_of(turn.id) else {
+            continue;
+        };
+        if !world.humans.alive[i] || !in_settlement(world, i) {
+            continue;
+        }
+        let rule = world
+            .transformation_rules
+            .set_off_by(turn.trigger)
+            .filter(|t| !world.transformations.has_undergone(turn.id, &t.name))
+            .find(|t| rng.gen::<f32>() < t.chance)
+            .cloned();
+        if let Some(rule) = rule {
+            transform(world, i, &rule);
+            changed += 1;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::{ChunkId, PersonalChunkState};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const TABLE: &str = r#"
+[[transformations]]
+name = "a blow to the head"
+trigger = "HeadWound"
+chance = 1.0
+chronicle = "{name} took a blow to the head"
+domains = [{ domain = "Combat", depth = 0.5 }]
+
+[[transformations]]
+name = "a crisis of faith"
+trigger = "SpiritualCrisis"
+chance = 1.0
+chronicle = "{name} came back a different person"
+domains = [{ domain = "Social", forget = true }]
+values = { curiosity = 0.3 }
+"#;
+
+    fn settler(world: &mut World) -> usize {
+        world.spawn_human("Ada".into());
+        let library = &mut world.humans.chunk_libraries[0];
+        for id in [ChunkId::BasicSwing, ChunkId::SocialActiveListening] {
+            let mut state = PersonalChunkState::new(0);
+            state.encoding_depth = 0.8;
+            library.set_chunk(id, state);
+        }
+        world.humans.values[0].curiosity = 0.2;
+        0
+    }
+
+    #[test]
+    fn test_table_parses_and_rejects_unknown_values() {
+        let rules = TransformationRules::parse(TABLE).unwrap();
+        assert_eq!(
+            rules
+                .set_off_by(TransformationTrigger::HeadWound)
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            ["a blow to the head"]
+        );
+        let bad = TABLE.replace("curiosity", "wanderlust");
+        assert!(matches!(
+            TransformationRules::parse(&bad),
+            Err(TransformationError::UnknownValue { .. })
+        ));
+        assert!(!load_default_transformation_rules()
+            .transformations
+            .is_empty());
+    }
+
+    #[test]
+    fn test_turns_transform_settlers_once() {
+        let mut world = World::with_seed(41);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        world.transformation_rules = TransformationRules::parse(TABLE).unwrap();
+        let i = settler(&mut world);
+        let id = world.humans.ids[i];
+
+        world
+            .transformations
+            .note(id, TransformationTrigger::SpiritualCrisis, 0);
+        assert_eq!(run_transformations(&mut world, &mut rng), 1);
+        let library = &world.humans.chunk_libraries[i];
+        assert!(!library.has_chunk(ChunkId::SocialActiveListening));
+        assert_eq!(
+            library
+                .get_chunk(ChunkId::BasicSwing)
+                .unwrap()
+                .encoding_depth,
+            0.8
+        );
+        assert!((world.humans.values[i].curiosity - 0.5).abs() < 1e-6);
+        assert!(world
+            .legacy
+            .chronicle
+            .iter()
+            .any(|e| e.text == "Ada came back a different person"));
+
+        // The same turn again changes nothing
+        world
+            .transformations
+            .note(id, TransformationTrigger::SpiritualCrisis, 1);
+        assert_eq!(run_transformations(&mut world, &mut rng), 0);
+        assert!(world.transformations.pending.is_empty());
+        assert_eq!(world.transformations.of(id).count(), 1);
+    }
+
+    #[test]
+    fn test_head_wounds_fade_what_was_learned() {
+        let mut world = World::with_seed(42);
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        world.transformation_rules = TransformationRules::parse(TABLE).unwrap();
+        let i = settler(&mut world);
+
+        let mut arm = Wound::none(BodyZone::ArmLeft);
+        arm.severity = WoundSeverity::Minor;
+        let id = world.humans.ids[i];
+        note_head_wound(&mut world.transformations, id, &arm, 0);
+        assert!(world.transformations.pending.is_empty());
+
+        let mut head = Wound::none(BodyZone::Head);
+        head.severity = WoundSeverity::Minor;
+        note_head_wound(&mut world.transformations, id, &head, 0);
+        assert_eq!(run_transformations(&mut world, &mut rng), 1);
+        let library = &world.humans.chunk_libraries[i];
+        assert!(
+            (library
+                .get_chunk(ChunkId::BasicSwing)
+                .unwrap()
+                .encoding_depth
+                - 0.4)
+                .abs()
+                < 1e-6
+        );
+        assert!(library.has_chunk(ChunkId::SocialActiveListening));
+        assert!(world
+            .transformations
+            .has_undergone(id, "a blow to the head"));
+    }
+}
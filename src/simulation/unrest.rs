@@ -0,0 +1,417 @@
+//! Unrest - what the discontented do about it
+//!
+//! While the settlement is in unrest (see `happiness::run_polling`), every
+//! settler whose opinion is below `DISCONTENT_THRESHOLD` works at a
+//! slowdown. When they are at least `PROTEST_SHARE` of the settlement they
+//! down tools and gather in protest at its centre, going back to work
+//! afterwards. If the misery lasts, a ringleader emerges: an ambitious one
+//! challenges the player's leadership, and orders go unheeded for a while;
+//! otherwise they lead the disloyal away as a splinter faction of their own.
+//!
+//! Leadership can also break the settlement's norms. Settlers ordered to
+//! work through a feast day resent it, the more so the more pious or
+//! just-minded they are.
+
+use crate::actions::catalog::{ActionCategory, ActionId};
+use crate::core::astronomy::{CelestialEvent, TICKS_PER_DAY};
+use crate::core::types::{EntityId, Vec2};
+use crate::ecs::world::World;
+use crate::entity::tasks::{Task, TaskPriority, TaskSource};
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+use crate::simulation::happiness::{in_settlement, opinions};
+use crate::world::{Challenge, Protest};
+
+/// Opinion below which a settler is discontented
+pub const DISCONTENT_THRESHOLD: f32 = 0.35;
+
+/// Pace of work for settlers at a slowdown
+pub const SLOWDOWN_PACE: f32 = 0.5;
+
+/// Share of settlers who must be discontented for a protest
+pub const PROTEST_SHARE: f32 = 0.5;
+
+/// Fewest settlers that make a protest
+pub const MIN_PROTESTERS: usize = 3;
+
+/// Happiness below which lasting unrest brings forth a ringleader
+pub const EXTREME_THRESHOLD: f32 = 0.2;
+
+/// Days below `EXTREME_THRESHOLD` before a ringleader emerges
+pub const EXTREME_DAYS: usize = 7;
+
+/// Days a leadership challenge lasts, unless the unrest ends first
+pub const CHALLENGE_DAYS: u64 = 5;
+
+/// Ambition above which a ringleader challenges rather than leaves
+pub const CHALLENGE_AMBITION: f32 = 0.5;
+
+/// Loyalty below which the discontented follow a ringleader away
+pub const SPLINTER_LOYALTY: f32 = 0.5;
+
+/// How far from the settlement a splinter group goes
+pub const SPLINTER_DISTANCE: f32 = 80.0;
+
+/// Celestial events kept as feast days
+pub const FEAST_DAYS: [CelestialEvent; 3] = [
+    CelestialEvent::FullArgent,
+    CelestialEvent::NearDoubleFull,
+    CelestialEvent::PerfectDoubleFull,
+];
+
+/// Whether today is a feast day
+pub fn is_feast_day(world: &World) -> bool {
+    FEAST_DAYS
+        .iter()
+        .any(|&event| world.astronomy.has_event(event))
+}
+
+/// How fast human `i` works: slower while at a slowdown
+pub fn work_pace(world: &World, i: usize) -> f32 {
+    let slowing = &world.happiness.discontent.slowing;
+    if slowing.contains(&world.humans.ids[i]) {
+        SLOWDOWN_PACE
+    } else {
+        1.0
+    }
+}
+
+/// Why the player's orders go unheeded, if they do
+pub fn orders_refused(world: &World) -> Option<String> {
+    let challenge = world.happiness.discontent.challenge?;
+    let name = world
+        .humans
+        .index_of(challenge.challenger)
+        .map(|i| world.humans.names[i].as_str())
+        .unwrap_or("A settler");
+    Some(format!(
+        "{} has challenged your leadership; the settlement will not heed orders until the unrest ends",
+        name
+    ))
+}
+
+/// Settlers made to work through a feast day resent it, once a day each
+pub fn resent_feast_work(world: &mut World) {
+    if !is_feast_day(world) {
+        return;
+    }
+    let tick = world.current_tick;
+    let day_start = tick - tick % TICKS_PER_DAY;
+    let mut aggrieved = 0;
+    for i in world.humans.iter_living().collect::<Vec<_>>() {
+        let ordered_to_work = world.humans.task_queues[i].current().is_some_and(|task| {
+            task.source == TaskSource::PlayerCommand
+                && task.action.category() == ActionCategory::Work
+        });
+        let already = world.humans.thoughts[i]
+            .iter()
+            .any(|t| t.concept_category == "feast_work" && t.created_tick >= day_start);
+        if !ordered_to_work || already {
+            continue;
+        }
+        let values = &world.humans.values[i];
+        let intensity = (0.2 + 0.3 * values.piety + 0.3 * values.justice).min(1.0);
+        world.humans.thoughts[i].add(Thought::new(
+            Valence::Negative,
+            intensity,
+            "feast_work",
+            "was made to work through the feast",
+            CauseType::Event,
+            tick,
+        ));
+        aggrieved += 1;
+    }
+    let day = world.astronomy.current_day;
+    let discontent = &mut world.happiness.discontent;
+    if aggrieved > 0 && discontent.feast_grievance_day != Some(day) {
+        discontent.feast_grievance_day = Some(day);
+        world.legacy.record(
+            tick,
+            "Settlers grumble at being made to work through the feast",
+        );
+    }
+}
+
+/// Where the living settlers are on average
+pub(crate) fn settlement_centre(world: &World) -> Vec2 {
+    let settlers: Vec<Vec2> = world
+        .humans
+        .iter_living()
+        .filter(|&i| in_settlement(world, i))
+        .map(|i| world.humans.positions[i])
+        .collect();
+    let sum = settlers.iter().fold(Vec2::new(0.0, 0.0), |sum, &p| sum + p);
+    sum * (1.0 / settlers.len().max(1) as f32)
+}
+
+/// Put a task ahead of whatever human `i` was doing, keeping the rest
+fn interrupt(world: &mut World, i: usize, tasks: Vec<Task>) {
+    let queue = &mut world.humans.task_queues[i];
+    let kept: Vec<Task> = queue.iter().cloned().collect();
+    queue.clear();
+    for task in tasks.into_iter().chain(kept) {
+        queue.push(task);
+    }
+}
+
+/// The discontented down tools and gather at the settlement's centre; those
+/// still standing from an earlier day stay where they are
+fn protest(world: &mut World, protesters: &[usize]) {
+    let tick = world.current_tick;
+    let day = world.astronomy.current_day;
+    let place = settlement_centre(world);
+    let standing =
+        |task: &Task| task.action == ActionId::IdleObserve && task.source == TaskSource::Reaction;
+    for &i in protesters {
+        if world.humans.task_queues[i].iter().any(standing) {
+            continue;
+        }
+        let mut gather = Task::new(ActionId::MoveTo, TaskPriority::High, tick).with_position(place);
+        gather.source = TaskSource::Reaction;
+        let mut stand = Task::new(ActionId::IdleObserve, TaskPriority::High, tick);
+        stand.source = TaskSource::Reaction;
+        interrupt(world, i, vec![gather, stand]);
+    }
+    let over = world
+        .happiness
+        .latest
+        .as_ref()
+        .and_then(|p| p.worst_factor());
+    let discontent = &mut world.happiness.discontent;
+    let continuing = discontent
+        .protest
+        .as_ref()
+        .is_some_and(|p| p.day + 1 == day);
+    discontent.protest = Some(Protest {
+        day,
+        place,
+        protesters: protesters.iter().map(|&i| world.humans.ids[i]).collect(),
+        over,
+    });
+    if !continuing {
+        let cause = over
+            .map(|factor| format!(" over {}", factor.name()))
+            .unwrap_or_default();
+        world.legacy.record(
+            tick,
+            format!("{} settlers gather in protest{}", protesters.len(), cause),
+        );
+    }
+}
+
+/// The ringleader and the disloyal leave to found a faction of their own
+fn splinter(world: &mut World, ringleader: usize, discontented: &[usize]) {
+    let tick = world.current_tick;
+    let name = world.humans.names[ringleader].clone();
+    let faction = world.factions.found(format!("{}'s Band", name));
+    let centre = settlement_centre(world);
+    let away = world.humans.positions[ringleader] - centre;
+    let direction = if away.length() > 0.0 {
+        away.normalize()
+    } else {
+        Vec2::new(1.0, 0.0)
+    };
+    let destination = centre + direction * SPLINTER_DISTANCE;
+
+    let leaving: Vec<usize> = discontented
+        .iter()
+        .copied()
+        .filter(|&i| i == ringleader || world.humans.values[i].loyalty < SPLINTER_LOYALTY)
+        .collect();
+    for &i in &leaving {
+        let id: EntityId = world.humans.ids[i];
+        world
+            .factions
+            .join(id, faction)
+            .expect("faction was just founded");
+        world.humans.assigned_houses[i] = None;
+        let queue = &mut world.humans.task_queues[i];
+        queue.clear();
+        let mut leave =
+            Task::new(ActionId::MoveTo, TaskPriority::Critical, tick).with_position(destination);
+        leave.source = TaskSource::Reaction;
+        queue.push(leave);
+    }
+    let text = match leaving.len() - 1 {
+        0 => format!("{} leaves the settlement to found {}'s Band", name, name),
+        followers => format!(
+            "{} leads {} discontented settlers away to found {}'s Band",
+            name, followers, name
+        ),
+    };
+    world.legacy.record(tick, text);
+}
+
+/// Slowdowns, protests, and what comes of lasting misery; run daily after
+/// the settlement is polled
+pub fn run_unrest(world: &mut World) {
+    let tick = world.current_tick;
+    if !world.happiness.unrest {
+        let discontent = &mut world.happiness.discontent;
+        discontent.slowing.clear();
+        discontent.splintered = false;
+        if discontent.challenge.take().is_some() {
+            world
+                .legacy
+                .record(tick, "The challenge to your leadership comes to nothing");
+        }
+        return;
+    }
+
+    let opinions = opinions(world);
+    let mut discontented: Vec<(usize, f32)> = opinions
+        .iter()
+        .copied()
+        .filter(|&(_, opinion)| opinion < DISCONTENT_THRESHOLD)
+        .collect();
+    // Most discontented first
+    discontented.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let discontented: Vec<usize> = discontented.into_iter().map(|(i, _)| i).collect();
+    world.happiness.discontent.slowing =
+        discontented.iter().map(|&i| world.humans.ids[i]).collect();
+
+    if world
+        .happiness
+        .discontent
+        .challenge
+        .is_some_and(|challenge| tick >= challenge.until)
+    {
+        world.happiness.discontent.challenge = None;
+        world
+            .legacy
+            .record(tick, "The challenge to your leadership runs its course");
+    }
+
+    let share = discontented.len() as f32 / opinions.len().max(1) as f32;
+    if discontented.len() >= MIN_PROTESTERS && share >= PROTEST_SHARE {
+        protest(world, &discontented);
+    }
+
+    let discontent = &world.happiness.discontent;
+    let extreme = world
+        .happiness
+        .held_for(EXTREME_DAYS, |h| h < EXTREME_THRESHOLD);
+    if !extreme || discontent.challenge.is_some() || discontent.splintered {
+        return;
+    }
+    // The ringleader is the discontented settler with the most ambition
+    let Some(&ringleader) = discontented.iter().max_by(|&&a, &&b| {
+        let ambition = |i: usize| world.humans.values[i].ambition;
+        ambition(a).total_cmp(&ambition(b))
+    }) else {
+        return;
+    };
+    let name = world.humans.names[ringleader].clone();
+    if world.humans.values[ringleader].ambition > CHALLENGE_AMBITION {
+        world.happiness.discontent.challenge = Some(Challenge {
+            challenger: world.humans.ids[ringleader],
+            until: tick + CHALLENGE_DAYS * TICKS_PER_DAY,
+        });
+        world
+            .legacy
+            .record(tick, format!("{} challenges your leadership", name));
+    } else {
+        world.happiness.discontent.splintered = true;
+        splinter(world, ringleader, &discontented);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::happiness::run_polling;
+
+    /// A settlement of four, all wretched
+    fn miserable_world(seed: u64) -> World {
+        let mut world = World::with_seed(seed);
+        for name in ["Ada", "Bram", "Cora", "Dov"] {
+            world.spawn_human(name.into());
+        }
+        for i in 0..4 {
+            let needs = &mut world.humans.needs[i];
+            (needs.food, needs.rest, needs.safety) = (1.0, 1.0, 1.0);
+            world.humans.thoughts[i].add(Thought::new(
+                Valence::Negative,
+                1.0,
+                "grief",
+                "lost everything",
+                CauseType::Event,
+                0,
+            ));
+        }
+        world
+    }
+
+    fn run_days(world: &mut World, days: usize) {
+        for _ in 0..days {
+            run_polling(world);
+            run_unrest(world);
+        }
+    }
+
+    #[test]
+    fn test_unrest_brings_slowdown_and_protest() {
+        let mut world = miserable_world(11);
+        let ada = 0;
+        world.humans.task_queues[ada]
+            .push(Task::new(ActionId::Build, TaskPriority::Normal, 0).from_player());
+
+        run_days(&mut world, 2);
+        assert!(world.happiness.discontent.slowing.is_empty());
+        assert_eq!(work_pace(&world, ada), 1.0);
+
+        run_days(&mut world, 1);
+        assert!(world.happiness.unrest);
+        assert_eq!(work_pace(&world, ada), SLOWDOWN_PACE);
+        let protest = world.happiness.discontent.protest.as_ref().unwrap();
+        assert_eq!(protest.protesters.len(), 4);
+        // Ada walks to the protest, and her orders wait behind it
+        let tasks: Vec<ActionId> = world.humans.task_queues[ada]
+            .iter()
+            .map(|t| t.action)
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![ActionId::MoveTo, ActionId::IdleObserve, ActionId::Build]
+        );
+        assert!(world
+            .legacy
+            .chronicle
+            .iter()
+            .any(|entry| entry.text.starts_with("4 settlers gather in protest")));
+
+        // Protesting on, she doesn't pile up another walk and stand a day
+        run_days(&mut world, 1);
+        assert_eq!(world.humans.task_queues[ada].iter().count(), 3);
+    }
+
+    #[test]
+    fn test_lasting_misery_brings_a_challenge_or_a_splinter() {
+        let mut world = miserable_world(12);
+        for i in 0..4 {
+            world.humans.values[i].ambition = 0.1;
+            world.humans.values[i].loyalty = 0.8;
+        }
+        world.humans.values[2].ambition = 0.9;
+        run_days(&mut world, EXTREME_DAYS);
+        let challenge = world.happiness.discontent.challenge.unwrap();
+        assert_eq!(challenge.challenger, world.humans.ids[2]);
+        assert!(orders_refused(&world)
+            .unwrap()
+            .starts_with("Cora has challenged"));
+
+        let mut world = miserable_world(13);
+        for i in 0..4 {
+            world.humans.values[i].ambition = 0.3;
+            world.humans.values[i].loyalty = if i == 3 { 0.9 } else { 0.2 };
+        }
+        world.humans.values[1].ambition = 0.4;
+        run_days(&mut world, EXTREME_DAYS);
+        assert!(world.happiness.discontent.splintered);
+        let band = world.factions.faction_of(world.humans.ids[1]).unwrap();
+        assert_ne!(Some(band), world.factions.settlement());
+        assert_eq!(world.factions.members(band).count(), 3);
+        // The loyal one stays, and now speaks for the whole settlement
+        assert!(in_settlement(&world, 3));
+        assert_eq!(opinions(&world).len(), 1);
+    }
+}
@@ -6,9 +6,18 @@ use crate::rules::value_dynamics::{TickDelta, ValueDynamicsRules};
 
 /// Apply per-tick value changes to an entity
 pub fn apply_tick_dynamics<V: ValueAccessor>(values: &mut V, deltas: &[TickDelta]) {
+    apply_tick_dynamics_over(values, deltas, 1.0);
+}
+
+/// Apply `ticks` ticks' worth of value changes at once
+pub fn apply_tick_dynamics_over<V: ValueAccessor>(
+    values: &mut V,
+    deltas: &[TickDelta],
+    ticks: f32,
+) {
     for delta in deltas {
         if let Some(current) = values.get_value(&delta.value_name) {
-            let new_value = (current + delta.delta).clamp(delta.min, delta.max);
+            let new_value = (current + delta.delta * ticks).clamp(delta.min, delta.max);
             values.set_value(&delta.value_name, new_value);
         }
     }
@@ -9,6 +9,7 @@ use crate::ecs::world::World;
 use crate::entity::social::{BehaviorPattern, PatternType, TraitIndicator, SALIENCE_THRESHOLD};
 use crate::entity::thoughts::{CauseType, Thought, Valence};
 use crate::simulation::perception::Perception;
+use crate::simulation::scheduler::Share;
 
 /// Types of violations that can occur
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -144,11 +145,11 @@ pub fn check_pattern_violation(
     }
 }
 
-/// Process violation checks for all perceptions
+/// Process violation checks for perceptions
 ///
-/// Iterates through perceptions and checks for violations based on
-/// the current actions of observed entities.
-pub fn process_violations(world: &mut World, perceptions: &[Perception]) {
+/// Iterates through the perceptions of observers in `share` and checks for
+/// violations based on the current actions of observed entities.
+pub fn process_violations(world: &mut World, perceptions: &[Perception], share: Share) {
     let current_tick = world.current_tick;
 
     // Build O(1) lookup map once instead of O(N) index_of per perceived entity
@@ -161,6 +162,9 @@ pub fn process_violations(world: &mut World, perceptions: &[Perception]) {
         .collect();
 
     for (observer_idx, perception) in perceptions.iter().enumerate() {
+        if !share.includes(observer_idx) {
+            continue;
+        }
         for perceived in &perception.perceived_entities {
             // O(1) lookup instead of O(N) index_of
             if let Some(&observed_idx) = id_to_idx.get(&perceived.entity) {
@@ -590,7 +594,7 @@ mod tests {
 
         // Process violations
         world.current_tick = 600;
-        process_violations(&mut world, &perceptions);
+        process_violations(&mut world, &perceptions, Share::ALL);
 
         let final_thoughts = world.humans.thoughts[observer_idx].iter().count();
         assert!(
@@ -0,0 +1,162 @@
+//! Weather felt by individuals
+//!
+//! `world.weather` is rolled once a day from the season (see `almanac`).
+//! Here it reaches the people living under it: the cold of the season and
+//! the weather makes rest and food needs climb faster (less so for those
+//! with a roof of their own), rain, snow, and fog slow walkers and outdoor
+//! work, and each day's weather gives everyone something to think about.
+
+use crate::campaign::weather::Weather;
+use crate::core::astronomy::Season;
+use crate::ecs::world::World;
+use crate::entity::thoughts::{CauseType, Thought, Valence};
+
+/// Fraction of the chill that reaches someone with a house
+pub const SHELTERED_CHILL: f32 = 0.5;
+
+/// Extra rest and food need decay at full chill (1.0 = twice as fast)
+pub const CHILL_NEED_DECAY: f32 = 0.5;
+
+/// How cold it is (0.0 mild to 1.0 bitter), from the season and the weather
+pub fn chill(season: Season, weather: Weather) -> f32 {
+    let season_chill: f32 = match season {
+        Season::Winter => 0.5,
+        Season::Autumn | Season::Spring => 0.15,
+        Season::Summer => 0.0,
+    };
+    let weather_chill = match weather {
+        Weather::Blizzard => 0.5,
+        Weather::Snow => 0.3,
+        Weather::HeavyRain => 0.2,
+        Weather::Rain => 0.1,
+        _ => 0.0,
+    };
+    (season_chill + weather_chill).min(1.0)
+}
+
+/// The world's chill today
+pub fn current_chill(world: &World) -> f32 {
+    chill(world.astronomy.season, world.weather.current_weather)
+}
+
+/// Rate at which rest and food needs climb in the cold (1.0 = normal)
+pub fn need_decay_multiplier(chill: f32, sheltered: bool) -> f32 {
+    let felt = if sheltered {
+        chill * SHELTERED_CHILL
+    } else {
+        chill
+    };
+    1.0 + felt * CHILL_NEED_DECAY
+}
+
+/// Walking speed in this weather (1.0 = normal)
+pub fn movement_multiplier(weather: Weather) -> f32 {
+    weather.movement_modifier()
+}
+
+/// Efficiency of work done out of doors (gathering, building)
+pub fn outdoor_work_multiplier(weather: Weather) -> f32 {
+    match weather {
+        Weather::Clear | Weather::Cloudy => 1.0,
+        Weather::Fog => 0.9,
+        Weather::Rain => 0.85,
+        Weather::Snow => 0.75,
+        Weather::HeavyRain => 0.6,
+        Weather::Sandstorm => 0.4,
+        Weather::Blizzard => 0.3,
+    }
+}
+
+/// The thought a day of this weather gives: (valence, intensity, concept, cause)
+fn weather_thought(weather: Weather) -> Option<(Valence, f32, &'static str, &'static str)> {
+    match weather {
+        Weather::Clear => Some((
+            Valence::Positive,
+            0.2,
+            "fine_weather",
+            "enjoyed a clear day",
+        )),
+        Weather::Rain => Some((Valence::Negative, 0.2, "bad_weather", "was rained on")),
+        Weather::HeavyRain => Some((
+            Valence::Negative,
+            0.4,
+            "bad_weather",
+            "was soaked by a downpour",
+        )),
+        Weather::Snow => Some((Valence::Negative, 0.3, "cold", "shivered in the snow")),
+        Weather::Blizzard => Some((Valence::Negative, 0.6, "cold", "was caught in a blizzard")),
+        Weather::Sandstorm => Some((
+            Valence::Negative,
+            0.5,
+            "bad_weather",
+            "was scoured by a sandstorm",
+        )),
+        Weather::Cloudy | Weather::Fog => None,
+    }
+}
+
+/// Give everyone a thought about today's weather
+///
+/// Those who value comfort feel it more. Returns the number of thoughts.
+pub fn react_to_weather(world: &mut World) -> usize {
+    let Some((valence, intensity, concept, cause)) = weather_thought(world.weather.current_weather)
+    else {
+        return 0;
+    };
+    let living: Vec<usize> = world.humans.iter_living().collect();
+    for &i in &living {
+        let felt = intensity * (0.5 + world.humans.values[i].comfort);
+        world.humans.thoughts[i].add(Thought::new(
+            valence,
+            felt.min(1.0),
+            concept,
+            cause,
+            CauseType::Event,
+            world.current_tick,
+        ));
+    }
+    living.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cold_weather_raises_needs_and_slows_work() {
+        let bitter = chill(Season::Winter, Weather::Blizzard);
+        assert_eq!(bitter, 1.0);
+        assert_eq!(chill(Season::Summer, Weather::Clear), 0.0);
+        assert!(need_decay_multiplier(bitter, false) > need_decay_multiplier(bitter, true));
+        assert_eq!(need_decay_multiplier(0.0, false), 1.0);
+        assert!(movement_multiplier(Weather::Snow) < movement_multiplier(Weather::Clear));
+        assert!(
+            outdoor_work_multiplier(Weather::Blizzard) < outdoor_work_multiplier(Weather::Rain)
+        );
+    }
+
+    #[test]
+    fn test_weather_thoughts() {
+        let mut world = World::with_seed(2);
+        world.spawn_human("Ada".into());
+        world.spawn_human("Bram".into());
+        world.humans.values[0].comfort = 0.0;
+        world.humans.values[1].comfort = 1.0;
+
+        world.weather.current_weather = Weather::Fog;
+        assert_eq!(react_to_weather(&mut world), 0);
+
+        world.weather.current_weather = Weather::HeavyRain;
+        assert_eq!(react_to_weather(&mut world), 2);
+        let soaked = |i: usize| world.humans.thoughts[i].strongest().unwrap().intensity;
+        assert_eq!(
+            world.humans.thoughts[0]
+                .strongest()
+                .unwrap()
+                .concept_category,
+            "bad_weather"
+        );
+        // Bram, who loves comfort, takes the downpour harder
+        assert!(soaked(1) > soaked(0));
+    }
+}
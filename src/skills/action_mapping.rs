@@ -38,10 +38,17 @@ pub fn get_chunks_for_action(action: ActionId) -> &'static [ChunkId] {
         ],
         ActionId::Gather => &[ChunkId::PhysSustainedLabor],
         ActionId::Repair => &[ChunkId::CraftBasicMeasure, ChunkId::CraftBasicCut],
+        // Butchering shares the cutting chunk with crafting
+        ActionId::TendLivestock => &[ChunkId::PhysSustainedLabor, ChunkId::CraftBasicCut],
+        ActionId::Haul => &[ChunkId::PhysSustainedLabor],
+        // Drill is run by those who can give a clear order and place a formation
+        ActionId::Drill => &[ChunkId::LeadClearOrder, ChunkId::LeadDirectFormation],
 
         // === SOCIAL ===
         ActionId::TalkTo => &[ChunkId::SocialActiveListening, ChunkId::SocialBuildRapport],
         ActionId::Help => &[ChunkId::SocialActiveListening],
+        // Carrying a body is hard labour, not a social skill
+        ActionId::Carry => &[ChunkId::PhysSustainedLabor],
         ActionId::Trade => &[ChunkId::SocialNegotiateTerms, ChunkId::SocialReadReaction],
 
         // === COMBAT ===
@@ -53,6 +60,10 @@ pub fn get_chunks_for_action(action: ActionId) -> &'static [ChunkId] {
         // === IDLE ===
         // Idle actions require no skill
         ActionId::IdleWander | ActionId::IdleObserve => &[],
+
+        // === ITEMS ===
+        // Handling one's own things requires no skill
+        ActionId::PickUp | ActionId::Drop | ActionId::Equip => &[],
     }
 }
 
@@ -182,6 +182,19 @@ pub fn compute_combat(library: &ChunkLibrary, phenotype: &Phenotype) -> DisplayS
     DisplayStat::new("Combat", level, bar)
 }
 
+/// Every display stat, in character-sheet order
+pub fn compute_all(library: &ChunkLibrary, phenotype: &Phenotype) -> Vec<DisplayStat> {
+    vec![
+        compute_combat(library, phenotype),
+        compute_craftsmanship(library, phenotype),
+        compute_medicine(library, phenotype),
+        compute_leadership(library, phenotype),
+        compute_scholarship(library, phenotype),
+        compute_athleticism(library, phenotype),
+        compute_charisma(library, phenotype),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
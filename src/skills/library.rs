@@ -1,6 +1,6 @@
 //! Per-entity chunk state storage
 
-use crate::skills::ChunkId;
+use crate::skills::{ChunkDomain, ChunkId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -42,6 +42,28 @@ pub struct Experience {
     pub tick: u64,
 }
 
+/// What an entity has learned in one domain
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DomainSummary {
+    /// Chunks formed in the domain
+    pub chunk_count: usize,
+    /// Level of the highest chunk formed (0 if none)
+    pub highest_level: u8,
+    /// Sum of the chunks' encoding depths
+    pub total_encoding: f32,
+}
+
+impl DomainSummary {
+    /// Mean encoding depth of the domain's chunks (0.0 if none)
+    pub fn average_encoding(&self) -> f32 {
+        if self.chunk_count == 0 {
+            0.0
+        } else {
+            self.total_encoding / self.chunk_count as f32
+        }
+    }
+}
+
 /// Per-entity skill chunk library
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ChunkLibrary {
@@ -284,6 +306,20 @@ impl ChunkLibrary {
         self.pending_experiences.clear();
     }
 
+    /// Summarise the chunks formed in one domain
+    pub fn domain_summary(&self, domain: ChunkDomain) -> DomainSummary {
+        self.chunks
+            .iter()
+            .filter(|(id, _)| id.domain() == domain)
+            .fold(DomainSummary::default(), |summary, (id, state)| {
+                DomainSummary {
+                    chunk_count: summary.chunk_count + 1,
+                    highest_level: summary.highest_level.max(id.level()),
+                    total_encoding: summary.total_encoding + state.encoding_depth,
+                }
+            })
+    }
+
     /// Get best combat chunk (highest level with good depth)
     pub fn best_combat_chunk(&self) -> Option<(ChunkId, f32)> {
         self.chunks
@@ -301,6 +337,19 @@ impl ChunkLibrary {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_domain_summary() {
+        let lib = ChunkLibrary::trained_soldier(1000);
+        let combat = lib.domain_summary(ChunkDomain::Combat);
+        assert_eq!(combat.chunk_count, 4);
+        assert_eq!(combat.highest_level, 2);
+        assert!((combat.average_encoding() - 0.525).abs() < 1e-5);
+
+        let craft = lib.domain_summary(ChunkDomain::Craft);
+        assert_eq!(craft, DomainSummary::default());
+        assert_eq!(craft.average_encoding(), 0.0);
+    }
+
     #[test]
     fn test_empty_library() {
         let lib = ChunkLibrary::new();
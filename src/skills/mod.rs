@@ -11,7 +11,7 @@ pub mod attention;
 pub mod chunk_id;
 pub mod context;
 pub mod definitions;
-// display.rs - orphaned, depends on unimplemented ChunkLibrary::domain_summary
+pub mod display;
 pub mod domain;
 pub mod history;
 pub mod integration;
@@ -19,7 +19,7 @@ pub mod learning;
 pub mod library;
 pub mod resolution;
 pub mod spawn_loadouts;
-// species_mods.rs - orphaned, depends on unimplemented genetics::Phenotype
+pub mod species_mods;
 
 pub use action_mapping::{action_requires_skill, get_chunks_for_action};
 pub use attention::{
@@ -39,9 +39,10 @@ pub use integration::{
     SkillFailure,
 };
 pub use learning::{calculate_encoding_depth, process_learning};
-pub use library::{ChunkLibrary, Experience, PersonalChunkState};
+pub use library::{ChunkLibrary, DomainSummary, Experience, PersonalChunkState};
 pub use resolution::{
     find_best_chunk, resolve_attack, resolve_defense, resolve_riposte, ActionResult, ATTACK_CHUNKS,
     DEFENSE_CHUNKS, RIPOSTE_CHUNKS,
 };
 pub use spawn_loadouts::generate_spawn_chunks;
+pub use species_mods::{DomainModifier, SpeciesChunkModifiers};
@@ -2,7 +2,8 @@
 //!
 //! Different species learn different domains at different rates.
 
-use crate::skills::ChunkDomain;
+use crate::core::types::Species;
+use crate::skills::{ChunkDomain, ChunkLibrary};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -157,6 +158,29 @@ impl SpeciesChunkModifiers {
     pub fn get_domain(&self, domain: ChunkDomain) -> DomainModifier {
         self.domains.get(&domain).cloned().unwrap_or_default()
     }
+
+    /// Modifiers for a species (the human baseline for those without their own)
+    pub fn for_species(species: Species) -> Self {
+        match species {
+            Species::Dwarf => Self::dwarf(),
+            Species::Elf => Self::elf(),
+            Species::Orc => Self::orc(),
+            _ => Self::human(),
+        }
+    }
+
+    /// Reshape a library learned at the human rate to this species' rate
+    ///
+    /// Encoding gains diminish toward 1.0, so learning `rate` times as fast
+    /// leaves `1 - (1 - depth)^rate`, capped at the domain's ceiling.
+    pub fn shape(&self, library: &mut ChunkLibrary) {
+        for (id, state) in library.chunks_mut() {
+            let domain = self.get_domain(id.domain());
+            let rate = domain.formation_rate * self.base_learning_rate;
+            state.encoding_depth =
+                (1.0 - (1.0 - state.encoding_depth).powf(rate)).min(domain.max_encoding);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -190,4 +214,23 @@ mod tests {
         assert_eq!(combat.decay_rate, 0.05);
         assert_eq!(combat.max_encoding, 0.995);
     }
+
+    #[test]
+    fn test_shape_follows_species_rates() {
+        let mut library = ChunkLibrary::trained_worker(0);
+        let craft = |library: &ChunkLibrary| {
+            library
+                .domain_summary(ChunkDomain::Craft)
+                .average_encoding()
+        };
+        let human = craft(&library);
+
+        let mut dwarf = library.clone();
+        SpeciesChunkModifiers::for_species(Species::Dwarf).shape(&mut dwarf);
+        SpeciesChunkModifiers::for_species(Species::Elf).shape(&mut library);
+
+        assert!(craft(&dwarf) > human);
+        assert!(craft(&library) < human);
+        assert!(craft(&dwarf) <= 0.99);
+    }
 }
@@ -1 +1,254 @@
-//! Flow field navigation - stub for MVP
+//! Flow fields - one search shared by everyone heading for the same place
+//!
+//! When a crowd converges on one spot (a festival, a fire, a raid alarm),
+//! an A* search per walker repeats the same work dozens of times. A flow
+//! field runs a single Dijkstra search outward from the destination over
+//! the `BlockedCells` grid, recording each cell's cost to reach it; a walker
+//! anywhere in the field just steps to its cheapest neighbour. The search
+//! uses the same rules as A* (eight neighbours, no cutting blocked corners,
+//! weighted by movement cost) and covers a square of `FLOW_FIELD_RADIUS`
+//! cells around the destination. Walkers outside it, or walled off from
+//! it, get no direction and fall back to their own route.
+
+use std::collections::BinaryHeap;
+
+use crate::core::types::Vec2;
+use crate::spatial::pathfinding::{cell_center, cell_of, line_is_clear, Cell};
+use crate::world::BlockedCells;
+
+/// Cells from the destination to the edge of its field, each way
+pub const FLOW_FIELD_RADIUS: i32 = 64;
+
+/// Cells ahead a walker looks for the furthest one in sight
+const LOOKAHEAD_CELLS: usize = 8;
+
+const NEIGHBOURS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Node in the Dijkstra frontier
+#[derive(Debug, Clone, Copy)]
+struct FrontierNode {
+    cell: Cell,
+    cost: f32,
+}
+
+impl PartialEq for FrontierNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cell == other.cell
+    }
+}
+
+impl Eq for FrontierNode {}
+
+impl Ord for FrontierNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reverse order for min-heap
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+impl PartialOrd for FrontierNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Cost to reach one destination cell from every cell around it
+#[derive(Debug, Clone)]
+pub struct FlowField {
+    goal: Cell,
+    /// Cost to the goal, row by row from the field's corner (infinite where
+    /// unreachable)
+    costs: Vec<f32>,
+}
+
+impl FlowField {
+    /// Search outward from `goal` over everything within the radius
+    pub fn build(blocked: &BlockedCells, goal: Cell) -> Self {
+        let side = (2 * FLOW_FIELD_RADIUS + 1) as usize;
+        let mut field = Self {
+            goal,
+            costs: vec![f32::INFINITY; side * side],
+        };
+        let passable = |cell: Cell| cell == goal || !blocked.is_blocked(cell.0, cell.1);
+
+        let mut frontier = BinaryHeap::new();
+        // The goal sits in the middle of the square
+        field.costs[side * side / 2] = 0.0;
+        frontier.push(FrontierNode {
+            cell: goal,
+            cost: 0.0,
+        });
+
+        while let Some(current) = frontier.pop() {
+            let Some(i) = field.index(current.cell) else {
+                continue;
+            };
+            if current.cost > field.costs[i] {
+                continue;
+            }
+            // Stepping into `current` costs its own movement cost
+            let step = blocked.movement_cost(current.cell.0, current.cell.1);
+            let step = if step.is_finite() { step } else { 1.0 };
+            let (x, y) = current.cell;
+
+            for (dx, dy) in NEIGHBOURS {
+                let neighbor = (x + dx, y + dy);
+                if !passable(neighbor) {
+                    continue;
+                }
+                let diagonal = dx != 0 && dy != 0;
+                if diagonal && (!passable((x + dx, y)) || !passable((x, y + dy))) {
+                    continue;
+                }
+                let Some(j) = field.index(neighbor) else {
+                    continue;
+                };
+                let distance = if diagonal {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.0
+                };
+                let cost = current.cost + distance * step;
+                if cost < field.costs[j] {
+                    field.costs[j] = cost;
+                    frontier.push(FrontierNode {
+                        cell: neighbor,
+                        cost,
+                    });
+                }
+            }
+        }
+        field
+    }
+
+    /// The destination cell
+    pub fn goal(&self) -> Cell {
+        self.goal
+    }
+
+    fn index(&self, (x, y): Cell) -> Option<usize> {
+        let (dx, dy) = (x - self.goal.0, y - self.goal.1);
+        if dx.abs() > FLOW_FIELD_RADIUS || dy.abs() > FLOW_FIELD_RADIUS {
+            return None;
+        }
+        let side = 2 * FLOW_FIELD_RADIUS + 1;
+        Some(((dy + FLOW_FIELD_RADIUS) * side + dx + FLOW_FIELD_RADIUS) as usize)
+    }
+
+    /// Cost from `cell` to the destination, if it can be reached from there
+    pub fn cost_at(&self, cell: Cell) -> Option<f32> {
+        self.index(cell)
+            .map(|i| self.costs[i])
+            .filter(|cost| cost.is_finite())
+    }
+
+    /// The neighbour of `cell` on its cheapest way to the destination
+    pub fn next_cell(&self, blocked: &BlockedCells, cell: Cell) -> Option<Cell> {
+        self.cost_at(cell)?;
+        if cell == self.goal {
+            return None;
+        }
+        let passable = |c: Cell| c == self.goal || !blocked.is_blocked(c.0, c.1);
+        let (x, y) = cell;
+        let mut best: Option<(Cell, f32)> = None;
+        for (dx, dy) in NEIGHBOURS {
+            let neighbor = (x + dx, y + dy);
+            let Some(cost) = self.cost_at(neighbor) else {
+                continue;
+            };
+            let diagonal = dx != 0 && dy != 0;
+            if diagonal && (!passable((x + dx, y)) || !passable((x, y + dy))) {
+                continue;
+            }
+            let distance = if diagonal {
+                std::f32::consts::SQRT_2
+            } else {
+                1.0
+            };
+            let step = blocked.movement_cost(neighbor.0, neighbor.1);
+            let step = if step.is_finite() { step } else { 1.0 };
+            let total = cost + distance * step;
+            if best.is_none_or(|(_, b)| total < b) {
+                best = Some((neighbor, total));
+            }
+        }
+        best.map(|(n, _)| n)
+    }
+
+    /// Where to head next from `from` on the way to `to` (a point in the
+    /// destination cell): the furthest cell ahead along the field that is in
+    /// sight, or `to` itself once it is. None outside the field.
+    pub fn waypoint(&self, blocked: &BlockedCells, from: Vec2, to: Vec2) -> Option<Vec2> {
+        let mut cell = cell_of(blocked, from);
+        self.cost_at(cell)?;
+        let mut best = None;
+        for _ in 0..LOOKAHEAD_CELLS {
+            let Some(next) = self.next_cell(blocked, cell) else {
+                break;
+            };
+            cell = next;
+            let waypoint = if cell == self.goal {
+                to
+            } else {
+                cell_center(blocked, cell)
+            };
+            if best.is_some() && !line_is_clear(blocked, from, waypoint) {
+                break;
+            }
+            best = Some(waypoint);
+            if cell == self.goal {
+                break;
+            }
+        }
+        best.or(Some(to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_leads_round_a_wall_to_the_goal() {
+        let mut blocked = BlockedCells::new();
+        for y in -5..=5 {
+            blocked.block(5, y);
+        }
+        let to = Vec2::new(10.5, 0.5);
+        let field = FlowField::build(&blocked, cell_of(&blocked, to));
+        assert_eq!(field.cost_at((10, 0)), Some(0.0));
+        assert_eq!(field.cost_at((5, 0)), None);
+        assert_eq!(field.cost_at((200, 0)), None);
+
+        for start in [
+            Vec2::new(0.5, 0.5),
+            Vec2::new(0.5, -3.5),
+            Vec2::new(2.5, 4.5),
+        ] {
+            let mut pos = start;
+            for _ in 0..60 {
+                let waypoint = field.waypoint(&blocked, pos, to).unwrap();
+                let distance = pos.distance(&waypoint);
+                pos = if distance <= 1.0 {
+                    waypoint
+                } else {
+                    pos + (waypoint - pos).normalize()
+                };
+                assert!(!blocked.is_position_blocked(glam::Vec2::new(pos.x, pos.y)));
+            }
+            assert!(pos.distance(&to) < 0.01);
+        }
+    }
+}
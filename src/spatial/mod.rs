@@ -1,5 +1,7 @@
 pub mod flow_field;
 pub mod geometry_schema;
 pub mod grid;
+pub mod path_cache;
+pub mod pathfinding;
 pub mod sparse_hash;
 pub mod validation;
@@ -0,0 +1,239 @@
+//! Shared routes - flow fields for popular destinations, cached A* for the rest
+//!
+//! `PathCache` sits in front of `pathfinding::step_toward`. Walkers whose
+//! straight line is clear never touch it. Otherwise:
+//!
+//! - a destination cell that `CROWD_SIZE` walkers need a way round to in
+//!   one tick gets a `FlowField`, which everyone heading there then follows
+//!   until nobody has for `FLOW_FIELD_IDLE_TICKS`;
+//! - anyone else gets an A* route, cached under every cell along it, so the
+//!   walker (and anyone starting on the same line) finds it again next tick.
+//!
+//! Both are thrown away whenever `BlockedCells::revision` moves on: a wall
+//! going up or a zone being lifted invalidates every route.
+
+use std::sync::Arc;
+
+use ahash::AHashMap;
+
+use crate::core::types::Vec2;
+use crate::spatial::flow_field::FlowField;
+use crate::spatial::pathfinding::{cell_of, find_path, line_is_clear, Cell};
+use crate::world::BlockedCells;
+
+/// Walkers needing a way round to one cell in a tick before it gets a field
+pub const CROWD_SIZE: usize = 8;
+
+/// Ticks a flow field is kept without anyone following it
+pub const FLOW_FIELD_IDLE_TICKS: u64 = 100;
+
+/// Cached route entries kept before the cache starts over
+pub const PATH_CACHE_CAPACITY: usize = 16_384;
+
+/// A cached A* result: the route, and where along it this cell sits
+type CachedRoute = Option<(Arc<[Vec2]>, usize)>;
+
+#[derive(Debug, Clone)]
+struct CachedField {
+    field: Arc<FlowField>,
+    last_used: u64,
+}
+
+/// Flow fields and A* routes shared across walkers and ticks (runtime only)
+#[derive(Debug, Clone, Default)]
+pub struct PathCache {
+    revision: u64,
+    tick: u64,
+    fields: AHashMap<Cell, CachedField>,
+    /// Keyed by (cell on the route, destination cell); None where there is
+    /// no way round
+    routes: AHashMap<(Cell, Cell), CachedRoute>,
+    /// Walkers asking for a way round to each destination this tick
+    demand: AHashMap<Cell, usize>,
+}
+
+impl PathCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start counting demand afresh and drop fields nobody is following
+    pub fn begin_tick(&mut self, tick: u64) {
+        self.tick = tick;
+        self.demand.clear();
+        self.fields
+            .retain(|_, cached| tick.saturating_sub(cached.last_used) <= FLOW_FIELD_IDLE_TICKS);
+    }
+
+    /// Number of live flow fields
+    pub fn field_count(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Whether a flow field leads to the cell containing `to`
+    pub fn has_field_to(&self, blocked: &BlockedCells, to: Vec2) -> bool {
+        self.fields.contains_key(&cell_of(blocked, to))
+    }
+
+    /// Forget everything if the blocked cells have changed since last time
+    fn refresh(&mut self, blocked: &BlockedCells) {
+        if blocked.revision() != self.revision {
+            self.revision = blocked.revision();
+            self.fields.clear();
+            self.routes.clear();
+        }
+    }
+
+    /// Where to head next on the way from `from` to `to`
+    ///
+    /// Same answer as `pathfinding::next_waypoint`, from a shared field or a
+    /// cached route when there is one.
+    pub fn next_waypoint(&mut self, blocked: &BlockedCells, from: Vec2, to: Vec2) -> Vec2 {
+        if line_is_clear(blocked, from, to) {
+            return to;
+        }
+        self.refresh(blocked);
+        let goal = cell_of(blocked, to);
+
+        let demand = self.demand.entry(goal).or_insert(0);
+        *demand += 1;
+        if *demand >= CROWD_SIZE && !self.fields.contains_key(&goal) {
+            self.fields.insert(
+                goal,
+                CachedField {
+                    field: Arc::new(FlowField::build(blocked, goal)),
+                    last_used: self.tick,
+                },
+            );
+        }
+        if let Some(cached) = self.fields.get_mut(&goal) {
+            cached.last_used = self.tick;
+            if let Some(waypoint) = cached.field.waypoint(blocked, from, to) {
+                return waypoint;
+            }
+        }
+
+        let Some((path, offset)) = self.route(blocked, from, to, goal) else {
+            return to;
+        };
+        let last = path.len() - 1;
+        path[offset..]
+            .iter()
+            .enumerate()
+            .map(|(i, &waypoint)| if offset + i == last { to } else { waypoint })
+            .take_while(|&waypoint| line_is_clear(blocked, from, waypoint))
+            .last()
+            .unwrap_or(if offset == last { to } else { path[offset] })
+    }
+
+    /// The cached A* route from `from`'s cell, searching if there is none
+    fn route(&mut self, blocked: &BlockedCells, from: Vec2, to: Vec2, goal: Cell) -> CachedRoute {
+        let start = cell_of(blocked, from);
+        if let Some(cached) = self.routes.get(&(start, goal)) {
+            return cached.clone();
+        }
+        if self.routes.len() >= PATH_CACHE_CAPACITY {
+            self.routes.clear();
+        }
+
+        let Some(path) = find_path(blocked, from, to) else {
+            self.routes.insert((start, goal), None);
+            return None;
+        };
+        let path: Arc<[Vec2]> = path.into();
+        // Whoever stands on a waypoint's cell heads on from the next one
+        for (i, &waypoint) in path.iter().enumerate().take(path.len() - 1) {
+            self.routes
+                .entry((cell_of(blocked, waypoint), goal))
+                .or_insert_with(|| Some((path.clone(), i + 1)));
+        }
+        let route = Some((path, 0));
+        self.routes.insert((start, goal), route.clone());
+        route
+    }
+
+    /// Position after moving up to `speed` toward `to`, routing around
+    /// blocked cells
+    pub fn step_toward(
+        &mut self,
+        blocked: &BlockedCells,
+        from: Vec2,
+        to: Vec2,
+        speed: f32,
+    ) -> Vec2 {
+        let waypoint = self.next_waypoint(blocked, from, to);
+        let distance = from.distance(&waypoint);
+        if distance <= speed {
+            return waypoint;
+        }
+        from + (waypoint - from).normalize() * speed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A wall across x = 5, from y = -20 to y = 20
+    fn wall() -> BlockedCells {
+        let mut blocked = BlockedCells::new();
+        for y in -20..=20 {
+            blocked.block(5, y);
+        }
+        blocked
+    }
+
+    #[test]
+    fn test_crowd_shares_a_field_and_arrives() {
+        let blocked = wall();
+        let mut cache = PathCache::new();
+        let to = Vec2::new(10.5, 0.5);
+        let mut walkers: Vec<Vec2> = (0..CROWD_SIZE * 2)
+            .map(|i| Vec2::new(0.5, i as f32 - 8.0 + 0.5))
+            .collect();
+
+        for tick in 0..80 {
+            cache.begin_tick(tick);
+            for pos in &mut walkers {
+                *pos = cache.step_toward(&blocked, *pos, to, 1.0);
+                assert!(!blocked.is_position_blocked(glam::Vec2::new(pos.x, pos.y)));
+            }
+            if tick == 0 {
+                assert!(cache.has_field_to(&blocked, to));
+            }
+        }
+        assert!(walkers.iter().all(|pos| pos.distance(&to) < 0.01));
+        assert_eq!(cache.field_count(), 1);
+
+        // Idle fields are dropped
+        cache.begin_tick(80 + FLOW_FIELD_IDLE_TICKS + 1);
+        assert_eq!(cache.field_count(), 0);
+    }
+
+    #[test]
+    fn test_cached_route_goes_stale_when_the_wall_changes() {
+        let mut blocked = wall();
+        let mut cache = PathCache::new();
+        let from = Vec2::new(0.5, 0.5);
+        let to = Vec2::new(10.5, 0.5);
+
+        let mut pos = from;
+        for tick in 0..80 {
+            cache.begin_tick(tick);
+            pos = cache.step_toward(&blocked, pos, to, 1.0);
+            assert!(!blocked.is_position_blocked(glam::Vec2::new(pos.x, pos.y)));
+        }
+        assert!(pos.distance(&to) < 0.01);
+        assert_eq!(cache.field_count(), 0);
+
+        // A gap opens in the wall: the old detour is forgotten
+        let revision = blocked.revision();
+        blocked.unblock(5, 0);
+        assert!(blocked.revision() > revision);
+        let from = Vec2::new(0.5, 3.5);
+        let to = Vec2::new(10.5, 3.5);
+        let waypoint = cache.next_waypoint(&blocked, from, to);
+        assert!(waypoint.y.abs() < 5.0);
+        assert_eq!(cache.revision, blocked.revision());
+    }
+}
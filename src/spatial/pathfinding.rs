@@ -0,0 +1,336 @@
+//! A* pathfinding around blocked cells for individual movement
+//!
+//! Entities walk straight at their target when nothing is in the way. When a
+//! placed object, a building, or impassable ground sits on the line, they
+//! route around it over the `BlockedCells` grid: A* over the eight
+//! neighbouring cells (no cutting corners past a blocked cell), weighted by
+//! each cell's movement cost, then heading for the furthest waypoint they
+//! can see so the path does not zig-zag from cell centre to cell centre.
+//!
+//! The search is bounded; when no way round is found within it, movement
+//! falls back to the straight line (wading or scrambling, as before).
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use ahash::AHashMap;
+
+use crate::core::types::Vec2;
+use crate::world::BlockedCells;
+
+/// Cells the search may expand before giving up on a way round
+pub const MAX_SEARCH_CELLS: usize = 4096;
+
+/// Directions tried when fleeing straight away is blocked, in degrees
+const FLEE_DEVIATIONS: [f32; 6] = [45.0, -45.0, 90.0, -90.0, 135.0, -135.0];
+
+pub(crate) type Cell = (i32, i32);
+
+/// Node in the A* open set
+#[derive(Debug, Clone, Copy)]
+struct PathNode {
+    cell: Cell,
+    f_cost: f32, // g_cost + heuristic
+}
+
+impl PartialEq for PathNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cell == other.cell
+    }
+}
+
+impl Eq for PathNode {}
+
+impl Ord for PathNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse order for min-heap
+        other
+            .f_cost
+            .partial_cmp(&self.f_cost)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for PathNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub(crate) fn cell_of(blocked: &BlockedCells, pos: Vec2) -> Cell {
+    blocked.world_to_cell(glam::Vec2::new(pos.x, pos.y))
+}
+
+pub(crate) fn cell_center(blocked: &BlockedCells, (x, y): Cell) -> Vec2 {
+    let size = blocked.cell_size();
+    Vec2::new((x as f32 + 0.5) * size, (y as f32 + 0.5) * size)
+}
+
+/// Octile distance between cells (in cells)
+fn heuristic(a: Cell, b: Cell) -> f32 {
+    let dx = (a.0 - b.0).abs() as f32;
+    let dy = (a.1 - b.1).abs() as f32;
+    dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy)
+}
+
+/// Whether the straight line between two points crosses no blocked cell
+///
+/// Walks every cell the line passes through (a line through the exact
+/// corner of a blocked cell counts as crossing it). The cells the line
+/// starts and ends in are not checked: someone standing in (or heading for)
+/// a blocked cell can still step out of (or into) it.
+pub fn line_is_clear(blocked: &BlockedCells, from: Vec2, to: Vec2) -> bool {
+    if blocked.is_empty() {
+        return true;
+    }
+    let size = blocked.cell_size();
+    let (mut x, mut y) = cell_of(blocked, from);
+    let goal = cell_of(blocked, to);
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+
+    // Distance along the line (as a fraction of it) to the next cell edge
+    let axis = |d: f32, pos: f32, cell: i32| -> (i32, f32, f32) {
+        if d > 0.0 {
+            (1, ((cell + 1) as f32 * size - pos) / d, size / d)
+        } else if d < 0.0 {
+            (-1, (cell as f32 * size - pos) / d, -size / d)
+        } else {
+            (0, f32::INFINITY, f32::INFINITY)
+        }
+    };
+    let (step_x, mut t_max_x, t_delta_x) = axis(dx, from.x, x);
+    let (step_y, mut t_max_y, t_delta_y) = axis(dy, from.y, y);
+
+    let open = |cell: Cell| cell == goal || !blocked.is_blocked(cell.0, cell.1);
+    let cells = (goal.0 - x).abs() + (goal.1 - y).abs();
+    for _ in 0..cells {
+        if (x, y) == goal {
+            break;
+        }
+        if t_max_x < t_max_y {
+            x += step_x;
+            t_max_x += t_delta_x;
+        } else if t_max_y < t_max_x {
+            y += step_y;
+            t_max_y += t_delta_y;
+        } else {
+            // Through a corner: both cells beside it must be open
+            if !open((x + step_x, y)) || !open((x, y + step_y)) {
+                return false;
+            }
+            x += step_x;
+            y += step_y;
+            t_max_x += t_delta_x;
+            t_max_y += t_delta_y;
+        }
+        if !open((x, y)) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Waypoints from `from` to `to` around blocked cells, ending at `to`
+///
+/// Returns None if there is no way round within `MAX_SEARCH_CELLS`.
+pub fn find_path(blocked: &BlockedCells, from: Vec2, to: Vec2) -> Option<Vec<Vec2>> {
+    let start = cell_of(blocked, from);
+    let goal = cell_of(blocked, to);
+    if start == goal {
+        return Some(vec![to]);
+    }
+
+    let passable = |cell: Cell| cell == goal || !blocked.is_blocked(cell.0, cell.1);
+
+    let mut open_set = BinaryHeap::new();
+    let mut came_from: AHashMap<Cell, Cell> = AHashMap::new();
+    let mut g_scores: AHashMap<Cell, f32> = AHashMap::new();
+    let mut expanded = 0;
+
+    g_scores.insert(start, 0.0);
+    open_set.push(PathNode {
+        cell: start,
+        f_cost: heuristic(start, goal),
+    });
+
+    while let Some(current) = open_set.pop() {
+        if current.cell == goal {
+            return Some(reconstruct_path(blocked, &came_from, goal, to));
+        }
+        expanded += 1;
+        if expanded > MAX_SEARCH_CELLS {
+            return None;
+        }
+
+        let current_g = *g_scores.get(&current.cell).unwrap_or(&f32::INFINITY);
+        let (x, y) = current.cell;
+
+        for (dx, dy) in [
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (1, -1),
+            (-1, 1),
+            (-1, -1),
+        ] {
+            let neighbor = (x + dx, y + dy);
+            if !passable(neighbor) {
+                continue;
+            }
+            // No squeezing diagonally between two blocked cells' corners
+            let diagonal = dx != 0 && dy != 0;
+            if diagonal && (!passable((x + dx, y)) || !passable((x, y + dy))) {
+                continue;
+            }
+
+            let distance = if diagonal {
+                std::f32::consts::SQRT_2
+            } else {
+                1.0
+            };
+            let cost = blocked.movement_cost(neighbor.0, neighbor.1);
+            let tentative_g = current_g + distance * if cost.is_finite() { cost } else { 1.0 };
+            let neighbor_g = *g_scores.get(&neighbor).unwrap_or(&f32::INFINITY);
+
+            if tentative_g < neighbor_g {
+                came_from.insert(neighbor, current.cell);
+                g_scores.insert(neighbor, tentative_g);
+                open_set.push(PathNode {
+                    cell: neighbor,
+                    f_cost: tentative_g + heuristic(neighbor, goal),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Reconstruct waypoints (cell centres, then the exact target) from came_from
+fn reconstruct_path(
+    blocked: &BlockedCells,
+    came_from: &AHashMap<Cell, Cell>,
+    goal: Cell,
+    to: Vec2,
+) -> Vec<Vec2> {
+    let mut path = vec![to];
+    let mut current = goal;
+    while let Some(&prev) = came_from.get(&current) {
+        if came_from.contains_key(&prev) {
+            path.push(cell_center(blocked, prev));
+        }
+        current = prev;
+    }
+    path.reverse();
+    path
+}
+
+/// Where to head next on the way from `from` to `to`
+///
+/// The target itself when the line to it is clear (or when there is no way
+/// round); otherwise the furthest waypoint in sight along the path round.
+pub fn next_waypoint(blocked: &BlockedCells, from: Vec2, to: Vec2) -> Vec2 {
+    if line_is_clear(blocked, from, to) {
+        return to;
+    }
+    let Some(path) = find_path(blocked, from, to) else {
+        return to;
+    };
+    path.iter()
+        .take_while(|&&waypoint| line_is_clear(blocked, from, waypoint))
+        .last()
+        .or(path.first())
+        .copied()
+        .unwrap_or(to)
+}
+
+/// Position after moving up to `speed` toward `to`, routing around blocked cells
+pub fn step_toward(blocked: &BlockedCells, from: Vec2, to: Vec2, speed: f32) -> Vec2 {
+    let waypoint = next_waypoint(blocked, from, to);
+    let distance = from.distance(&waypoint);
+    if distance <= speed {
+        return waypoint;
+    }
+    from + (waypoint - from).normalize() * speed
+}
+
+/// Position after fleeing `speed` away from `threat`
+///
+/// Veers off the straight line away when it is blocked; a cornered entity
+/// (every way out blocked) stays where it is.
+pub fn step_away(blocked: &BlockedCells, from: Vec2, threat: Vec2, speed: f32) -> Vec2 {
+    let away = (from - threat).normalize();
+    if away.length() == 0.0 {
+        return from;
+    }
+    let rotated = |degrees: f32| {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Vec2::new(away.x * cos - away.y * sin, away.x * sin + away.y * cos)
+    };
+    std::iter::once(away)
+        .chain(FLEE_DEVIATIONS.iter().map(|&d| rotated(d)))
+        .map(|direction| from + direction * speed)
+        .find(|&to| {
+            let (x, y) = cell_of(blocked, to);
+            !blocked.is_blocked(x, y) && line_is_clear(blocked, from, to)
+        })
+        .unwrap_or(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A wall across x = 5, from y = -5 to y = 5
+    fn wall() -> BlockedCells {
+        let mut blocked = BlockedCells::new();
+        for y in -5..=5 {
+            blocked.block(5, y);
+        }
+        blocked
+    }
+
+    #[test]
+    fn test_walks_straight_when_nothing_is_in_the_way() {
+        let blocked = wall();
+        let from = Vec2::new(0.5, 10.5);
+        let to = Vec2::new(10.5, 10.5);
+        assert!(line_is_clear(&blocked, from, to));
+        assert_eq!(next_waypoint(&blocked, from, to).x, to.x);
+    }
+
+    #[test]
+    fn test_routes_around_a_wall() {
+        let blocked = wall();
+        let from = Vec2::new(0.5, 0.5);
+        let to = Vec2::new(10.5, 0.5);
+        assert!(!line_is_clear(&blocked, from, to));
+
+        let path = find_path(&blocked, from, to).unwrap();
+        assert!(path
+            .iter()
+            .all(|&p| !blocked.is_position_blocked(glam::Vec2::new(p.x, p.y))));
+        assert_eq!(path.last().unwrap().x, to.x);
+
+        // Walking the route never sets foot in the wall, and arrives
+        let mut pos = from;
+        for _ in 0..40 {
+            pos = step_toward(&blocked, pos, to, 1.0);
+            assert!(!blocked.is_position_blocked(glam::Vec2::new(pos.x, pos.y)));
+        }
+        assert!(pos.distance(&to) < 0.01);
+    }
+
+    #[test]
+    fn test_fleeing_veers_off_a_wall() {
+        let blocked = wall();
+        let from = Vec2::new(4.5, 0.5);
+        let threat = Vec2::new(0.5, 0.5);
+        let to = step_away(&blocked, from, threat, 1.0);
+        assert!(!blocked.is_position_blocked(glam::Vec2::new(to.x, to.y)));
+        assert!(to.distance(&threat) > from.distance(&threat) - 0.5);
+        assert!(to.distance(&from) > 0.5);
+    }
+}
@@ -0,0 +1,307 @@
+//! Entity inspector - everything about one settler, for the side panel
+//!
+//! `Inspection::of` reads a snapshot of the selected human from the world
+//! (needs, values, thoughts, skills, relationships, task queue, and any
+//! request to the player) with no egui involved, so it can be tested
+//! headless; `show` lays it out in an egui panel, one collapsible section per
+//! part.
+
+use crate::core::types::EntityId;
+use crate::ecs::world::World;
+use crate::entity::social::Disposition;
+use crate::entity::tasks::{TaskPriority, TaskSource};
+use crate::entity::thoughts::Valence;
+use crate::genetics::Phenotype;
+use crate::simulation::lifecycle::age_in_years;
+use crate::simulation::requests::describe_wish;
+use crate::skills::display::{compute_all, DisplayStat};
+
+/// Most relationships listed, strongest bonds first
+const MAX_RELATIONSHIPS: usize = 8;
+
+/// A thought as the inspector shows it
+#[derive(Debug, Clone)]
+pub struct ThoughtLine {
+    pub positive: bool,
+    pub intensity: f32,
+    pub about: String,
+}
+
+/// Someone the inspected entity remembers
+#[derive(Debug, Clone)]
+pub struct RelationshipLine {
+    pub name: String,
+    pub disposition: Disposition,
+    pub interactions: u32,
+}
+
+/// One task in the queue, the current one first
+#[derive(Debug, Clone)]
+pub struct TaskLine {
+    pub action: String,
+    pub priority: TaskPriority,
+    pub progress: f32,
+    /// Ordered by the player rather than chosen by the settler
+    pub ordered: bool,
+}
+
+/// A snapshot of one human for the inspector panel
+#[derive(Debug, Clone)]
+pub struct Inspection {
+    pub id: EntityId,
+    pub name: String,
+    pub age: u32,
+    pub fatigue: f32,
+    pub aspiration: Option<String>,
+    /// What they are asking of the player, if anything
+    pub request: Option<String>,
+    pub needs: Vec<(&'static str, f32)>,
+    pub values: Vec<(&'static str, f32)>,
+    pub thoughts: Vec<ThoughtLine>,
+    pub skills: Vec<DisplayStat>,
+    pub relationships: Vec<RelationshipLine>,
+    pub tasks: Vec<TaskLine>,
+}
+
+impl Inspection {
+    /// Inspect a living human; None for anyone else
+    pub fn of(world: &World, id: EntityId) -> Option<Self> {
+        let i = world.humans.index_of(id)?;
+        if !world.humans.alive[i] {
+            return None;
+        }
+        let name_of = |id: EntityId| {
+            world
+                .identities
+                .get(id)
+                .map(|identity| identity.known_as())
+                .unwrap_or_else(|| "someone unknown".to_string())
+        };
+
+        let needs = &world.humans.needs[i];
+        let values = &world.humans.values[i];
+        let mut value_list = vec![
+            ("Honor", values.honor),
+            ("Beauty", values.beauty),
+            ("Comfort", values.comfort),
+            ("Ambition", values.ambition),
+            ("Loyalty", values.loyalty),
+            ("Love", values.love),
+            ("Justice", values.justice),
+            ("Curiosity", values.curiosity),
+            ("Safety", values.safety),
+            ("Piety", values.piety),
+        ];
+        value_list.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut thoughts: Vec<ThoughtLine> = world.humans.thoughts[i]
+            .iter()
+            .map(|thought| ThoughtLine {
+                positive: thought.valence == Valence::Positive,
+                intensity: thought.intensity,
+                about: thought.cause_description.clone(),
+            })
+            .collect();
+        thoughts.sort_by(|a, b| b.intensity.total_cmp(&a.intensity));
+
+        let mut slots: Vec<_> = world.humans.social_memories[i].slots.iter().collect();
+        slots.sort_by_key(|slot| std::cmp::Reverse(slot.interaction_count));
+        let relationships = slots
+            .into_iter()
+            .take(MAX_RELATIONSHIPS)
+            .map(|slot| RelationshipLine {
+                name: name_of(slot.target_id),
+                disposition: slot.get_disposition(),
+                interactions: slot.interaction_count,
+            })
+            .collect();
+
+        let tasks = world.humans.task_queues[i]
+            .iter()
+            .map(|task| TaskLine {
+                action: format!("{:?}", task.action),
+                priority: task.priority,
+                progress: task.progress,
+                ordered: task.source == TaskSource::PlayerCommand,
+            })
+            .collect();
+
+        let aspiration = world.humans.aspirations[i].as_ref().map(|a| {
+            if a.achieved {
+                format!("{} (fulfilled)", a.kind.describe())
+            } else if a.days_stalled > 0 {
+                format!(
+                    "{} ({:.0}%, stalled {} days)",
+                    a.kind.describe(),
+                    a.progress * 100.0,
+                    a.days_stalled
+                )
+            } else {
+                format!("{} ({:.0}%)", a.kind.describe(), a.progress * 100.0)
+            }
+        });
+
+        let request = world
+            .requests
+            .by(id)
+            .find(|r| r.is_open())
+            .map(|r| describe_wish(world, r.wish));
+
+        Some(Self {
+            id,
+            name: name_of(id),
+            age: age_in_years(world.humans.ages[i]),
+            fatigue: world.humans.body_states[i].fatigue,
+            aspiration,
+            request,
+            needs: vec![
+                ("Food", needs.food),
+                ("Rest", needs.rest),
+                ("Safety", needs.safety),
+                ("Social", needs.social),
+                ("Purpose", needs.purpose),
+                ("Hygiene", needs.hygiene),
+            ],
+            values: value_list,
+            thoughts,
+            // Genomes are not expressed yet, so everyone has the same body
+            skills: compute_all(&world.humans.chunk_libraries[i], &Phenotype::default()),
+            relationships,
+            tasks,
+        })
+    }
+}
+
+fn bar(ui: &mut egui::Ui, label: &str, fill: f32, text: String) {
+    ui.horizontal(|row| {
+        row.label(label);
+        row.add(egui::ProgressBar::new(fill.clamp(0.0, 1.0)).text(text));
+    });
+}
+
+/// Lay out an inspection in a panel
+pub fn show(ui: &mut egui::Ui, inspection: &Inspection) {
+    ui.heading(&inspection.name);
+    ui.label(format!(
+        "Age {}, fatigue {:.0}%",
+        inspection.age,
+        inspection.fatigue * 100.0
+    ));
+    ui.label(match &inspection.aspiration {
+        Some(aspiration) => format!("Aspiration: {}", aspiration),
+        None => "Aspiration: none".to_string(),
+    });
+    if let Some(request) = &inspection.request {
+        ui.label(format!("Asking for {}", request));
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::CollapsingHeader::new("Needs")
+            .default_open(true)
+            .show(ui, |ui| {
+                for (name, level) in &inspection.needs {
+                    bar(ui, name, *level, format!("{:.0}%", level * 100.0));
+                }
+            });
+
+        egui::CollapsingHeader::new("Task queue")
+            .default_open(true)
+            .show(ui, |ui| {
+                if inspection.tasks.is_empty() {
+                    ui.label("Idle");
+                }
+                for (n, task) in inspection.tasks.iter().enumerate() {
+                    let mut line = format!("{} ({:?}", task.action, task.priority);
+                    if task.ordered {
+                        line.push_str(", ordered");
+                    }
+                    line.push(')');
+                    if n == 0 {
+                        bar(ui, "Now:", task.progress, line);
+                    } else {
+                        ui.label(format!("{}. {}", n, line));
+                    }
+                }
+            });
+
+        egui::CollapsingHeader::new("Values").show(ui, |ui| {
+            for (name, weight) in &inspection.values {
+                bar(ui, name, *weight, format!("{:.2}", weight));
+            }
+        });
+
+        egui::CollapsingHeader::new("Skills").show(ui, |ui| {
+            for stat in &inspection.skills {
+                bar(ui, stat.name, stat.bar_fill, stat.level.name().to_string());
+            }
+        });
+
+        egui::CollapsingHeader::new(format!("Thoughts ({})", inspection.thoughts.len())).show(
+            ui,
+            |ui| {
+                for thought in &inspection.thoughts {
+                    let color = if thought.positive {
+                        egui::Color32::LIGHT_GREEN
+                    } else {
+                        egui::Color32::LIGHT_RED
+                    };
+                    ui.colored_label(
+                        color,
+                        format!("{} ({:.0}%)", thought.about, thought.intensity * 100.0),
+                    );
+                }
+            },
+        );
+
+        egui::CollapsingHeader::new("Relationships").show(ui, |ui| {
+            if inspection.relationships.is_empty() {
+                ui.label("Knows no one yet");
+            }
+            for relation in &inspection.relationships {
+                ui.label(format!(
+                    "{}: {:?}, {} meetings",
+                    relation.name, relation.disposition, relation.interactions
+                ));
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::catalog::ActionId;
+    use crate::entity::tasks::Task;
+    use crate::entity::thoughts::{CauseType, Thought};
+
+    #[test]
+    fn test_inspection_reads_the_selected_settler() {
+        let mut world = World::with_seed(58);
+        let marcus = world.spawn_human("Marcus".into());
+        let elena = world.spawn_human("Elena".into());
+        world.humans.values[0].piety = 0.95;
+        world.humans.thoughts[0].add(Thought::new(
+            Valence::Negative,
+            0.8,
+            "danger",
+            "saw a wolf",
+            CauseType::Event,
+            0,
+        ));
+        world.humans.task_queues[0]
+            .push(Task::new(ActionId::Rest, TaskPriority::High, 0).from_player());
+        world.humans.social_memories[0].promote_encounter(elena, 0);
+
+        let inspection = Inspection::of(&world, marcus).unwrap();
+        assert_eq!(inspection.name, "Marcus");
+        assert_eq!(inspection.values[0], ("Piety", 0.95));
+        assert_eq!(inspection.skills.len(), 7);
+        assert_eq!(inspection.thoughts[0].about, "saw a wolf");
+        assert!(!inspection.thoughts[0].positive);
+        assert_eq!(inspection.tasks[0].action, "Rest");
+        assert!(inspection.tasks[0].ordered);
+
+        let rat = world.spawn_orc("Grak".into());
+        assert!(Inspection::of(&world, rat).is_none());
+    }
+}
@@ -2,7 +2,11 @@
 
 pub mod display;
 pub mod input;
+pub mod inspector;
+pub mod planner;
 pub mod state;
 pub mod terminal;
 
-pub use state::{GameUI, LogCategory, LogEntry};
+pub use inspector::Inspection;
+pub use planner::{PlanDraft, PlanError, PlanTool};
+pub use state::{GameUI, LogCategory, LogEntry, SelectionEvent};
@@ -0,0 +1,703 @@
+//! Battle planner - author a `BattlePlan` before the battle starts
+//!
+//! `PlanDraft` holds the plan being drafted and how the player is drafting
+//! it: the selected unit, the tool, and the behaviour, pace and go-code that
+//! new waypoints take. A click on the map deploys the selected unit, extends
+//! its path, or marks a hex for a contingency, depending on the tool.
+//! `validate` checks the draft against the army and the map and hands back
+//! the finished plan or every problem with it; `install` puts a plan into a
+//! battle. None of this needs egui; `show` lays out the planning panel.
+
+use thiserror::Error;
+
+use crate::battle::battle_map::BattleMap;
+use crate::battle::execution::BattleState;
+use crate::battle::hex::{BattleHexCoord, HexDirection};
+use crate::battle::planning::{
+    BattlePlan, Contingency, ContingencyResponse, ContingencyTrigger, EngagementRule, GoCode,
+    GoCodeId, GoCodeTrigger, MovementPace, UnitDeployment, WaitCondition, Waypoint,
+    WaypointBehavior, WaypointPlan,
+};
+use crate::battle::units::{Army, UnitId, UnitStance};
+use crate::core::types::Tick;
+
+/// What a click on the map does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlanTool {
+    /// Deploy the selected unit on the hex
+    #[default]
+    Deploy,
+    /// Add the hex to the selected unit's path
+    Path,
+    /// Mark the hex for a contingency's rally point or retreat
+    Mark,
+}
+
+/// Something wrong with a drafted plan
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PlanError {
+    #[error("unit {0:?} is not in the army")]
+    UnknownUnit(UnitId),
+    #[error("({}, {}) is off the map", .0.q, .0.r)]
+    OffMap(BattleHexCoord),
+    #[error("({}, {}) cannot be crossed", .0.q, .0.r)]
+    Impassable(BattleHexCoord),
+    #[error("two units are deployed to ({}, {})", .0.q, .0.r)]
+    SharedHex(BattleHexCoord),
+    #[error("unit {0:?} is deployed more than once")]
+    DeployedTwice(UnitId),
+    #[error("go-code needs a name")]
+    UnnamedGoCode,
+    #[error("go-code {0} is defined more than once")]
+    DuplicateGoCode(String),
+    #[error("go-code {0:?} is not in the plan")]
+    UnknownGoCode(GoCodeId),
+    #[error("there is no go-code called {0}")]
+    NoSuchGoCode(String),
+    #[error("select a unit first")]
+    NoUnitSelected,
+    #[error("mark a hex first")]
+    NoHexMarked,
+    #[error("casualty threshold {0} is not between 0 and 1")]
+    BadThreshold(f32),
+    #[error("a retreat needs a route")]
+    EmptyRetreat,
+}
+
+/// Contingency trigger as chosen in the panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TriggerKind {
+    #[default]
+    UnitBreaks,
+    CommanderDies,
+    PositionLost,
+    EnemyFlanking,
+    CasualtiesExceed,
+}
+
+/// Contingency response as chosen in the panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseKind {
+    #[default]
+    Rally,
+    Retreat,
+    Signal,
+}
+
+/// The contingency being filled in
+#[derive(Debug, Clone, Default)]
+pub struct ContingencyForm {
+    pub trigger: TriggerKind,
+    pub response: ResponseKind,
+    /// Casualties, 0 to 1, for `CasualtiesExceed`
+    pub threshold: f32,
+    /// Go-code a `Signal` fires, by name
+    pub signal: String,
+    pub priority: u8,
+}
+
+/// A battle plan in the drafting
+#[derive(Debug, Clone, Default)]
+pub struct PlanDraft {
+    pub plan: BattlePlan,
+    pub selected: Option<UnitId>,
+    pub tool: PlanTool,
+    /// Facing of units deployed from here on
+    pub facing: HexDirection,
+    pub behavior: WaypointBehavior,
+    pub pace: MovementPace,
+    /// Go-code new waypoints wait on, by name
+    pub wait_on: Option<String>,
+    /// Hex marked for contingencies
+    pub marked: Option<BattleHexCoord>,
+    /// Name and tick for the next go-code; no tick means manual
+    pub go_code_name: String,
+    pub go_code_tick: Option<Tick>,
+    pub contingency: ContingencyForm,
+    /// Problems found by the last `validate`
+    pub errors: Vec<PlanError>,
+}
+
+impl PlanDraft {
+    /// A draft with every unit deployed where it stands
+    pub fn for_army(army: &Army) -> Self {
+        let mut draft = Self::default();
+        for unit in army.formations.iter().flat_map(|f| f.units.iter()) {
+            draft.deploy(unit.id, unit.position, unit.facing);
+        }
+        draft.selected = draft.plan.deployments.first().map(|d| d.unit_id);
+        draft
+    }
+
+    /// Apply a click on `hex` with the current tool
+    pub fn click_hex(&mut self, hex: BattleHexCoord) {
+        if self.tool == PlanTool::Mark {
+            self.marked = Some(hex);
+            return;
+        }
+        let Some(unit) = self.selected else {
+            return;
+        };
+        match self.tool {
+            PlanTool::Deploy => self.deploy(unit, hex, self.facing),
+            PlanTool::Path => self.add_waypoint(unit, hex),
+            PlanTool::Mark => {}
+        }
+    }
+
+    /// Deploy `unit` on `hex`, replacing where it was deployed before
+    pub fn deploy(&mut self, unit: UnitId, hex: BattleHexCoord, facing: HexDirection) {
+        self.plan.deployments.retain(|d| d.unit_id != unit);
+        self.plan.deployments.push(UnitDeployment {
+            unit_id: unit,
+            position: hex,
+            facing,
+            initial_stance: UnitStance::Formed,
+        });
+    }
+
+    /// Add a waypoint on `hex` to the end of `unit`'s path
+    pub fn add_waypoint(&mut self, unit: UnitId, hex: BattleHexCoord) {
+        let mut waypoint = Waypoint::new(hex, self.behavior).with_pace(self.pace);
+        if let Some(go_code) = self
+            .wait_on
+            .as_deref()
+            .and_then(|name| self.plan.get_go_code_mut(name))
+        {
+            go_code.subscribe(unit);
+            waypoint = waypoint.with_wait(WaitCondition::GoCode(go_code.id));
+        }
+        match self
+            .plan
+            .waypoint_plans
+            .iter_mut()
+            .find(|p| p.unit_id == unit)
+        {
+            Some(plan) => plan.add_waypoint(waypoint),
+            None => {
+                let mut plan = WaypointPlan::new(unit);
+                plan.add_waypoint(waypoint);
+                self.plan.waypoint_plans.push(plan);
+            }
+        }
+    }
+
+    /// Drop the last waypoint of `unit`'s path
+    pub fn undo_waypoint(&mut self, unit: UnitId) {
+        if let Some(plan) = self
+            .plan
+            .waypoint_plans
+            .iter_mut()
+            .find(|p| p.unit_id == unit)
+        {
+            plan.waypoints.pop();
+        }
+        self.plan.waypoint_plans.retain(|p| !p.waypoints.is_empty());
+    }
+
+    pub fn set_engagement(&mut self, unit: UnitId, rule: EngagementRule) {
+        self.plan.engagement_rules.retain(|(id, _)| *id != unit);
+        self.plan.engagement_rules.push((unit, rule));
+    }
+
+    /// Add a go-code; names must be unique
+    pub fn add_go_code(
+        &mut self,
+        name: &str,
+        trigger: GoCodeTrigger,
+    ) -> Result<GoCodeId, PlanError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(PlanError::UnnamedGoCode);
+        }
+        if self.plan.get_go_code(name).is_some() {
+            return Err(PlanError::DuplicateGoCode(name.to_string()));
+        }
+        let go_code = GoCode::new(name.to_string(), trigger);
+        let id = go_code.id;
+        self.plan.go_codes.push(go_code);
+        Ok(id)
+    }
+
+    /// Add the contingency described by the form
+    pub fn add_contingency(&mut self) -> Result<(), PlanError> {
+        let form = &self.contingency;
+        let marked = self.marked.ok_or(PlanError::NoHexMarked);
+        let trigger = match form.trigger {
+            TriggerKind::UnitBreaks => {
+                ContingencyTrigger::UnitBreaks(self.selected.ok_or(PlanError::NoUnitSelected)?)
+            }
+            TriggerKind::CommanderDies => ContingencyTrigger::CommanderDies,
+            TriggerKind::PositionLost => ContingencyTrigger::PositionLost(marked.clone()?),
+            TriggerKind::EnemyFlanking => ContingencyTrigger::EnemyFlanking,
+            TriggerKind::CasualtiesExceed => ContingencyTrigger::CasualtiesExceed(form.threshold),
+        };
+        let response = match form.response {
+            ResponseKind::Rally => ContingencyResponse::Rally(marked?),
+            ResponseKind::Retreat => ContingencyResponse::Retreat(vec![marked?]),
+            ResponseKind::Signal => ContingencyResponse::Signal(
+                self.plan
+                    .get_go_code(form.signal.trim())
+                    .ok_or_else(|| PlanError::NoSuchGoCode(form.signal.trim().to_string()))?
+                    .id,
+            ),
+        };
+        self.plan
+            .contingencies
+            .push(Contingency::new(trigger, response).with_priority(form.priority));
+        Ok(())
+    }
+
+    /// The finished plan, or everything wrong with the draft
+    pub fn validate(&self, army: &Army, map: &BattleMap) -> Result<BattlePlan, Vec<PlanError>> {
+        let mut errors = Vec::new();
+        let plan = &self.plan;
+        let unit = |id: UnitId, errors: &mut Vec<PlanError>| {
+            if army.get_unit(id).is_none() {
+                errors.push(PlanError::UnknownUnit(id));
+            }
+        };
+        let hex = |at: BattleHexCoord, errors: &mut Vec<PlanError>| match map.get_hex(at) {
+            None => errors.push(PlanError::OffMap(at)),
+            Some(h) if h.terrain.impassable_for_infantry() => {
+                errors.push(PlanError::Impassable(at))
+            }
+            Some(_) => {}
+        };
+        let go_code = |id: GoCodeId, errors: &mut Vec<PlanError>| {
+            if !plan.go_codes.iter().any(|g| g.id == id) {
+                errors.push(PlanError::UnknownGoCode(id));
+            }
+        };
+
+        for (i, deployment) in plan.deployments.iter().enumerate() {
+            unit(deployment.unit_id, &mut errors);
+            hex(deployment.position, &mut errors);
+            let earlier = &plan.deployments[..i];
+            if earlier.iter().any(|d| d.unit_id == deployment.unit_id) {
+                errors.push(PlanError::DeployedTwice(deployment.unit_id));
+            } else if earlier.iter().any(|d| d.position == deployment.position) {
+                errors.push(PlanError::SharedHex(deployment.position));
+            }
+        }
+        for path in &plan.waypoint_plans {
+            unit(path.unit_id, &mut errors);
+            for waypoint in &path.waypoints {
+                hex(waypoint.position, &mut errors);
+                match &waypoint.wait_condition {
+                    Some(WaitCondition::GoCode(id)) => go_code(*id, &mut errors),
+                    Some(WaitCondition::UnitArrives(other)) => unit(*other, &mut errors),
+                    _ => {}
+                }
+            }
+        }
+        for (id, _) in &plan.engagement_rules {
+            unit(*id, &mut errors);
+        }
+        for (i, code) in plan.go_codes.iter().enumerate() {
+            if code.name.trim().is_empty() {
+                errors.push(PlanError::UnnamedGoCode);
+            } else if plan.go_codes[..i].iter().any(|g| g.name == code.name) {
+                errors.push(PlanError::DuplicateGoCode(code.name.clone()));
+            }
+            for subscriber in &code.subscribers {
+                unit(*subscriber, &mut errors);
+            }
+            if let GoCodeTrigger::UnitPosition { unit: id, position } = &code.trigger {
+                unit(*id, &mut errors);
+                hex(*position, &mut errors);
+            }
+        }
+        for contingency in &plan.contingencies {
+            match &contingency.trigger {
+                ContingencyTrigger::UnitBreaks(id) => unit(*id, &mut errors),
+                ContingencyTrigger::PositionLost(at) => hex(*at, &mut errors),
+                ContingencyTrigger::CasualtiesExceed(threshold)
+                    if !(0.0..=1.0).contains(threshold) =>
+                {
+                    errors.push(PlanError::BadThreshold(*threshold))
+                }
+                _ => {}
+            }
+            match &contingency.response {
+                ContingencyResponse::ExecutePlan(id) => unit(*id, &mut errors),
+                ContingencyResponse::Retreat(route) if route.is_empty() => {
+                    errors.push(PlanError::EmptyRetreat)
+                }
+                ContingencyResponse::Retreat(route) => {
+                    for at in route {
+                        hex(*at, &mut errors);
+                    }
+                }
+                ContingencyResponse::Rally(at) => hex(*at, &mut errors),
+                ContingencyResponse::Signal(id) => go_code(*id, &mut errors),
+                ContingencyResponse::Unknown(_) => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(plan.clone())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Put `plan` into the battle as the friendly plan, its units where it
+/// deploys them
+pub fn install(plan: BattlePlan, battle: &mut BattleState) {
+    for deployment in &plan.deployments {
+        if let Some(unit) = battle.friendly_army.get_unit_mut(deployment.unit_id) {
+            unit.position = deployment.position;
+            unit.facing = deployment.facing;
+            unit.stance = deployment.initial_stance;
+        }
+    }
+    battle.friendly_plan = plan;
+}
+
+fn unit_name(army: &Army, id: UnitId) -> String {
+    let units = army.formations.iter().flat_map(|f| f.units.iter());
+    units.enumerate().find(|(_, u)| u.id == id).map_or_else(
+        || "unknown unit".to_string(),
+        |(n, u)| format!("{:?} {}", u.unit_type, n + 1),
+    )
+}
+
+/// Lay out the planning panel; the validated plan once Start is pressed
+pub fn show(
+    ui: &mut egui::Ui,
+    draft: &mut PlanDraft,
+    army: &Army,
+    map: &BattleMap,
+) -> Option<BattlePlan> {
+    ui.heading("Battle plan");
+
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut draft.tool, PlanTool::Deploy, "Deploy");
+        ui.selectable_value(&mut draft.tool, PlanTool::Path, "Path");
+        ui.selectable_value(&mut draft.tool, PlanTool::Mark, "Mark");
+    });
+    if let Some(hex) = draft.marked {
+        ui.label(format!("Marked ({}, {})", hex.q, hex.r));
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::CollapsingHeader::new("Units")
+            .default_open(true)
+            .show(ui, |ui| {
+                for unit in army.formations.iter().flat_map(|f| f.units.iter()) {
+                    let waypoints = draft
+                        .plan
+                        .get_waypoint_plan(unit.id)
+                        .map_or(0, |p| p.waypoints.len());
+                    let text = format!("{} - {} waypoints", unit_name(army, unit.id), waypoints);
+                    if ui
+                        .selectable_label(draft.selected == Some(unit.id), text)
+                        .clicked()
+                    {
+                        draft.selected = Some(unit.id);
+                    }
+                }
+            });
+
+        if let Some(unit) = draft.selected {
+            egui::CollapsingHeader::new(unit_name(army, unit))
+                .default_open(true)
+                .show(ui, |ui| {
+                    egui::ComboBox::from_label("Facing")
+                        .selected_text(format!("{:?}", draft.facing))
+                        .show_ui(ui, |ui| {
+                            for facing in HexDirection::all() {
+                                ui.selectable_value(
+                                    &mut draft.facing,
+                                    facing,
+                                    format!("{:?}", facing),
+                                );
+                            }
+                        });
+                    egui::ComboBox::from_label("Waypoint")
+                        .selected_text(format!("{:?}", draft.behavior))
+                        .show_ui(ui, |ui| {
+                            for behavior in [
+                                WaypointBehavior::MoveTo,
+                                WaypointBehavior::HoldAt,
+                                WaypointBehavior::AttackFrom,
+                                WaypointBehavior::ScanFrom,
+                                WaypointBehavior::RallyAt,
+                            ] {
+                                ui.selectable_value(
+                                    &mut draft.behavior,
+                                    behavior,
+                                    format!("{:?}", behavior),
+                                );
+                            }
+                        });
+                    egui::ComboBox::from_label("Pace")
+                        .selected_text(format!("{:?}", draft.pace))
+                        .show_ui(ui, |ui| {
+                            for pace in [
+                                MovementPace::Walk,
+                                MovementPace::Quick,
+                                MovementPace::Run,
+                                MovementPace::Charge,
+                            ] {
+                                ui.selectable_value(&mut draft.pace, pace, format!("{:?}", pace));
+                            }
+                        });
+                    egui::ComboBox::from_label("Wait on")
+                        .selected_text(draft.wait_on.as_deref().unwrap_or("nothing"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut draft.wait_on, None, "nothing");
+                            for code in &draft.plan.go_codes {
+                                ui.selectable_value(
+                                    &mut draft.wait_on,
+                                    Some(code.name.clone()),
+                                    &code.name,
+                                );
+                            }
+                        });
+
+                    let rule = draft.plan.get_engagement_rule(unit);
+                    ui.horizontal(|ui| {
+                        for choice in [
+                            EngagementRule::Aggressive,
+                            EngagementRule::Defensive,
+                            EngagementRule::HoldFire,
+                            EngagementRule::Skirmish,
+                        ] {
+                            let chosen =
+                                std::mem::discriminant(&rule) == std::mem::discriminant(&choice);
+                            if ui
+                                .selectable_label(chosen, format!("{:?}", choice))
+                                .clicked()
+                            {
+                                draft.set_engagement(unit, choice);
+                            }
+                        }
+                    });
+                    if ui.button("Undo waypoint").clicked() {
+                        draft.undo_waypoint(unit);
+                    }
+                });
+        }
+
+        egui::CollapsingHeader::new("Go-codes")
+            .default_open(true)
+            .show(ui, |ui| {
+                for code in &draft.plan.go_codes {
+                    let when = match &code.trigger {
+                        GoCodeTrigger::Time(tick) => format!("at tick {}", tick),
+                        _ => "on command".to_string(),
+                    };
+                    ui.label(format!(
+                        "{} {}, {} waiting",
+                        code.name,
+                        when,
+                        code.subscribers.len()
+                    ));
+                }
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut draft.go_code_name);
+                    let mut timed = draft.go_code_tick.is_some();
+                    ui.checkbox(&mut timed, "at tick");
+                    let mut tick = draft.go_code_tick.unwrap_or(0);
+                    if timed {
+                        ui.add(egui::DragValue::new(&mut tick));
+                    }
+                    draft.go_code_tick = timed.then_some(tick);
+                });
+                if ui.button("Add go-code").clicked() {
+                    let trigger = draft
+                        .go_code_tick
+                        .map_or(GoCodeTrigger::Manual, GoCodeTrigger::Time);
+                    let name = std::mem::take(&mut draft.go_code_name);
+                    draft.errors = draft
+                        .add_go_code(&name, trigger)
+                        .err()
+                        .into_iter()
+                        .collect();
+                }
+            });
+
+        egui::CollapsingHeader::new("Contingencies")
+            .default_open(true)
+            .show(ui, |ui| {
+                for contingency in &draft.plan.contingencies {
+                    ui.label(format!(
+                        "{:?} -> {:?} (priority {})",
+                        contingency.trigger, contingency.response, contingency.priority
+                    ));
+                }
+                let form = &mut draft.contingency;
+                egui::ComboBox::from_label("When")
+                    .selected_text(format!("{:?}", form.trigger))
+                    .show_ui(ui, |ui| {
+                        for trigger in [
+                            TriggerKind::UnitBreaks,
+                            TriggerKind::CommanderDies,
+                            TriggerKind::PositionLost,
+                            TriggerKind::EnemyFlanking,
+                            TriggerKind::CasualtiesExceed,
+                        ] {
+                            ui.selectable_value(
+                                &mut form.trigger,
+                                trigger,
+                                format!("{:?}", trigger),
+                            );
+                        }
+                    });
+                if form.trigger == TriggerKind::CasualtiesExceed {
+                    ui.add(egui::Slider::new(&mut form.threshold, 0.0..=1.0).text("casualties"));
+                }
+                egui::ComboBox::from_label("Then")
+                    .selected_text(format!("{:?}", form.response))
+                    .show_ui(ui, |ui| {
+                        for response in [
+                            ResponseKind::Rally,
+                            ResponseKind::Retreat,
+                            ResponseKind::Signal,
+                        ] {
+                            ui.selectable_value(
+                                &mut form.response,
+                                response,
+                                format!("{:?}", response),
+                            );
+                        }
+                    });
+                if form.response == ResponseKind::Signal {
+                    ui.horizontal(|ui| {
+                        ui.label("Go-code");
+                        ui.text_edit_singleline(&mut form.signal);
+                    });
+                }
+                ui.add(egui::Slider::new(&mut form.priority, 0..=9).text("priority"));
+                if ui.button("Add contingency").clicked() {
+                    draft.errors = draft.add_contingency().err().into_iter().collect();
+                }
+            });
+    });
+
+    for error in &draft.errors {
+        ui.colored_label(egui::Color32::LIGHT_RED, error.to_string());
+    }
+    if ui.button("Start battle").clicked() {
+        match draft.validate(army, map) {
+            Ok(plan) => {
+                draft.errors.clear();
+                return Some(plan);
+            }
+            Err(errors) => draft.errors = errors,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::terrain::BattleTerrain;
+    use crate::battle::unit_type::UnitType;
+    use crate::battle::units::{ArmyId, BattleFormation, BattleUnit, FormationId};
+    use crate::core::types::EntityId;
+
+    fn army_of(count: i32) -> Army {
+        let mut army = Army::new(ArmyId::new(), EntityId::new());
+        let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+        for i in 0..count {
+            let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+            unit.position = BattleHexCoord::new(2, 2 + i * 2);
+            formation.units.push(unit);
+        }
+        army.formations.push(formation);
+        army
+    }
+
+    fn unit_ids(army: &Army) -> Vec<UnitId> {
+        army.formations[0].units.iter().map(|u| u.id).collect()
+    }
+
+    #[test]
+    fn test_drafted_plan_validates_and_installs() {
+        let army = army_of(2);
+        let map = BattleMap::new(20, 20);
+        let [first, second] = unit_ids(&army)[..] else {
+            panic!("two units");
+        };
+        let mut draft = PlanDraft::for_army(&army);
+        draft.add_go_code("HAMMER", GoCodeTrigger::Manual).unwrap();
+        assert_eq!(
+            draft.add_go_code("HAMMER", GoCodeTrigger::Manual),
+            Err(PlanError::DuplicateGoCode("HAMMER".into()))
+        );
+
+        draft.selected = Some(second);
+        draft.facing = HexDirection::West;
+        draft.click_hex(BattleHexCoord::new(5, 5));
+        draft.tool = PlanTool::Path;
+        draft.wait_on = Some("HAMMER".into());
+        draft.click_hex(BattleHexCoord::new(10, 5));
+        draft.wait_on = None;
+        draft.click_hex(BattleHexCoord::new(12, 5));
+        draft.tool = PlanTool::Mark;
+        draft.click_hex(BattleHexCoord::new(1, 1));
+        draft.contingency.response = ResponseKind::Rally;
+        draft.add_contingency().unwrap();
+
+        let plan = draft.validate(&army, &map).unwrap();
+        let path = plan.get_waypoint_plan(second).unwrap();
+        assert_eq!(path.waypoints.len(), 2);
+        let hammer = plan.get_go_code("HAMMER").unwrap();
+        assert!(matches!(
+            path.waypoints[0].wait_condition,
+            Some(WaitCondition::GoCode(id)) if id == hammer.id
+        ));
+        assert_eq!(hammer.subscribers, vec![second]);
+        assert!(matches!(
+            plan.contingencies[0].trigger,
+            ContingencyTrigger::UnitBreaks(id) if id == second
+        ));
+
+        let mut battle = BattleState::new(map, army, army_of(1));
+        install(plan, &mut battle);
+        let unit = battle.friendly_army.get_unit(second).unwrap();
+        assert_eq!(unit.position, BattleHexCoord::new(5, 5));
+        assert_eq!(unit.facing, HexDirection::West);
+        assert_eq!(
+            battle.friendly_army.get_unit(first).unwrap().position,
+            BattleHexCoord::new(2, 2)
+        );
+        assert!(battle.friendly_plan.get_waypoint_plan(second).is_some());
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let army = army_of(2);
+        let mut map = BattleMap::new(20, 20);
+        map.get_hex_mut(BattleHexCoord::new(6, 6)).unwrap().terrain = BattleTerrain::DeepWater;
+        let ids = unit_ids(&army);
+        let mut draft = PlanDraft::for_army(&army);
+
+        draft.deploy(ids[1], BattleHexCoord::new(2, 2), HexDirection::East);
+        draft.add_waypoint(ids[0], BattleHexCoord::new(6, 6));
+        draft.add_waypoint(ids[0], BattleHexCoord::new(50, 2));
+        draft.set_engagement(UnitId::new(), EngagementRule::HoldFire);
+        draft.plan.contingencies.push(Contingency::new(
+            ContingencyTrigger::CasualtiesExceed(30.0),
+            ContingencyResponse::Signal(GoCodeId::new()),
+        ));
+
+        let errors = draft.validate(&army, &map).unwrap_err();
+        assert!(errors.contains(&PlanError::SharedHex(BattleHexCoord::new(2, 2))));
+        assert!(errors.contains(&PlanError::Impassable(BattleHexCoord::new(6, 6))));
+        assert!(errors.contains(&PlanError::OffMap(BattleHexCoord::new(50, 2))));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, PlanError::UnknownUnit(_))));
+        assert!(errors.contains(&PlanError::BadThreshold(30.0)));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, PlanError::UnknownGoCode(_))));
+        assert_eq!(errors.len(), 6);
+    }
+}
@@ -1,6 +1,7 @@
 //! UI state management for live simulation
 
 use crate::core::types::EntityId;
+use crate::renderer::state::Selection;
 use std::collections::VecDeque;
 
 /// Maximum action log entries to keep
@@ -9,14 +10,22 @@ const MAX_LOG_ENTRIES: usize = 50;
 /// Game UI state
 #[derive(Debug, Default)]
 pub struct GameUI {
-    /// Currently selected entity (if any)
-    pub selected_entity: Option<EntityId>,
+    /// Currently selected entity or building (if any)
+    pub selection: Option<Selection>,
     /// Action log entries
     pub action_log: VecDeque<LogEntry>,
     /// Whether to show entity panel
     pub show_entity_panel: bool,
     /// Whether to show action log
     pub show_action_log: bool,
+    /// Whether to show the almanac
+    pub show_almanac: bool,
+    /// Whether to show what settlers are asking for
+    pub show_requests: bool,
+    /// Whether the camera follows the selection
+    pub following: bool,
+    /// Whether each settler's action and fatigue is drawn under them
+    pub show_debug_labels: bool,
     /// Command input buffer
     pub command_input: String,
     /// Whether command input is focused
@@ -31,6 +40,13 @@ pub struct LogEntry {
     pub category: LogCategory,
 }
 
+/// How a click changed the selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionEvent {
+    Selected(Selection),
+    Deselected(Selection),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogCategory {
     Action,
@@ -42,10 +58,14 @@ pub enum LogCategory {
 impl GameUI {
     pub fn new() -> Self {
         Self {
-            selected_entity: None,
+            selection: None,
             action_log: VecDeque::with_capacity(MAX_LOG_ENTRIES),
             show_entity_panel: true,
             show_action_log: true,
+            show_almanac: false,
+            show_requests: true,
+            following: false,
+            show_debug_labels: false,
             command_input: String::new(),
             command_focused: false,
         }
@@ -63,22 +83,80 @@ impl GameUI {
         });
     }
 
-    /// Select an entity by ID
-    pub fn select(&mut self, entity_id: EntityId) {
-        self.selected_entity = Some(entity_id);
+    /// The selected entity, if the selection is one
+    pub fn selected_entity(&self) -> Option<EntityId> {
+        match self.selection {
+            Some(Selection::Entity(id)) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Select an entity or building
+    pub fn select(&mut self, selection: impl Into<Selection>) {
+        self.selection = Some(selection.into());
     }
 
     /// Clear selection
     pub fn deselect(&mut self) {
-        self.selected_entity = None;
+        self.selection = None;
     }
 
     /// Toggle selection
-    pub fn toggle_select(&mut self, entity_id: EntityId) {
-        if self.selected_entity == Some(entity_id) {
+    pub fn toggle_select(&mut self, selection: impl Into<Selection>) {
+        let selection = selection.into();
+        if self.selection == Some(selection) {
             self.deselect();
         } else {
-            self.select(entity_id);
+            self.select(selection);
         }
     }
+
+    /// Apply a click in the world view given what it picked: select it, or
+    /// clear the selection when clicking it again or clicking empty ground
+    pub fn click(&mut self, picked: Option<Selection>) -> Option<SelectionEvent> {
+        let before = self.selection;
+        match picked {
+            Some(selection) => self.toggle_select(selection),
+            None => self.deselect(),
+        }
+        match (before, self.selection) {
+            (_, Some(now)) if before != Some(now) => Some(SelectionEvent::Selected(now)),
+            (Some(was), None) => Some(SelectionEvent::Deselected(was)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city::building::BuildingId;
+
+    #[test]
+    fn test_click_selects_toggles_and_clears() {
+        let mut ui = GameUI::new();
+        let marcus_id = EntityId::new();
+        let marcus = Selection::Entity(marcus_id);
+        let forge = Selection::Building(BuildingId::new());
+
+        assert_eq!(ui.click(None), None);
+        assert_eq!(
+            ui.click(Some(marcus)),
+            Some(SelectionEvent::Selected(marcus))
+        );
+        assert_eq!(ui.selected_entity(), Some(marcus_id));
+        assert_eq!(ui.click(Some(forge)), Some(SelectionEvent::Selected(forge)));
+        assert_eq!(ui.selected_entity(), None);
+        // Clicking the selection again clears it
+        assert_eq!(
+            ui.click(Some(forge)),
+            Some(SelectionEvent::Deselected(forge))
+        );
+        assert_eq!(
+            ui.click(Some(marcus)),
+            Some(SelectionEvent::Selected(marcus))
+        );
+        assert_eq!(ui.click(None), Some(SelectionEvent::Deselected(marcus)));
+        assert_eq!(ui.selection, None);
+    }
 }
@@ -0,0 +1,61 @@
+//! Avatar - the one entity the player is embodying, if any
+//!
+//! While embodied, an entity takes no autonomous decisions: the player moves
+//! it, picks its actions, and chooses what it says. Everything it does is
+//! kept as a deed so that, once handed back, it remembers what it did.
+
+use serde::{Deserialize, Serialize};
+
+use crate::actions::catalog::ActionId;
+use crate::core::types::{EntityId, Tick};
+
+/// What an embodied entity can say to someone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogueChoice {
+    Chat,
+    Compliment,
+    Insult,
+}
+
+impl DialogueChoice {
+    pub const ALL: [DialogueChoice; 3] = [
+        DialogueChoice::Chat,
+        DialogueChoice::Compliment,
+        DialogueChoice::Insult,
+    ];
+
+    /// Look up a choice by name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|choice| format!("{:?}", choice).eq_ignore_ascii_case(name))
+    }
+}
+
+/// Something done under the player's control
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deed {
+    pub tick: Tick,
+    pub action: ActionId,
+    pub target: Option<EntityId>,
+    /// What was said, for conversations
+    pub said: Option<DialogueChoice>,
+}
+
+/// The entity under the player's control
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Avatar {
+    pub entity: EntityId,
+    pub since: Tick,
+    pub deeds: Vec<Deed>,
+}
+
+impl Avatar {
+    pub fn new(entity: EntityId, since: Tick) -> Self {
+        Self {
+            entity,
+            since,
+            deeds: Vec::new(),
+        }
+    }
+}
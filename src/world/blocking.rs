@@ -3,8 +3,9 @@
 //! Provides cell-based blocking for pathfinding and movement systems.
 //! Uses a HashSet-based approach for O(1) lookup of blocked cells.
 
-use ahash::AHashSet;
+use ahash::{AHashMap, AHashSet};
 use glam::Vec2;
+use serde::{Deserialize, Serialize};
 
 /// State of a blocking object (for future breach/permeable handling)
 #[derive(Debug, Clone, PartialEq)]
@@ -48,10 +49,21 @@ impl Default for BlockingState {
 }
 
 /// Set of blocked grid cells for pathfinding
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockedCells {
+    #[serde(with = "crate::ecs::persistence::sorted_set")]
     cells: AHashSet<(i32, i32)>,
+    /// Movement cost multipliers for passable cells that are not plain ground
+    #[serde(with = "crate::ecs::persistence::sorted_map")]
+    costs: AHashMap<(i32, i32), f32>,
+    /// Cells in no-entry zones: blocked like walls, but kept apart so lifting
+    /// a zone never clears a cell something else blocks
+    #[serde(default, with = "crate::ecs::persistence::sorted_set")]
+    restricted: AHashSet<(i32, i32)>,
     cell_size: f32,
+    /// Bumped on every change, so cached routes know when they are stale
+    #[serde(skip)]
+    revision: u64,
 }
 
 impl BlockedCells {
@@ -59,7 +71,10 @@ impl BlockedCells {
     pub fn new() -> Self {
         Self {
             cells: AHashSet::new(),
+            costs: AHashMap::new(),
+            restricted: AHashSet::new(),
             cell_size: 1.0,
+            revision: 0,
         }
     }
 
@@ -67,23 +82,44 @@ impl BlockedCells {
     pub fn with_cell_size(cell_size: f32) -> Self {
         Self {
             cells: AHashSet::new(),
+            costs: AHashMap::new(),
+            restricted: AHashSet::new(),
             cell_size,
+            revision: 0,
         }
     }
 
     /// Block a cell at grid coordinates
     pub fn block(&mut self, x: i32, y: i32) {
-        self.cells.insert((x, y));
+        if self.cells.insert((x, y)) {
+            self.revision += 1;
+        }
     }
 
     /// Unblock a cell at grid coordinates
     pub fn unblock(&mut self, x: i32, y: i32) {
-        self.cells.remove(&(x, y));
+        if self.cells.remove(&(x, y)) {
+            self.revision += 1;
+        }
     }
 
-    /// Check if a cell at grid coordinates is blocked
+    /// Check if a cell at grid coordinates is blocked (or restricted)
     pub fn is_blocked(&self, x: i32, y: i32) -> bool {
-        self.cells.contains(&(x, y))
+        self.cells.contains(&(x, y)) || self.restricted.contains(&(x, y))
+    }
+
+    /// Replace the restricted cells (those in no-entry zones)
+    pub fn set_restricted(&mut self, cells: impl IntoIterator<Item = (i32, i32)>) {
+        let restricted: AHashSet<(i32, i32)> = cells.into_iter().collect();
+        if restricted != self.restricted {
+            self.restricted = restricted;
+            self.revision += 1;
+        }
+    }
+
+    /// Check if a cell lies in a no-entry zone
+    pub fn is_restricted(&self, x: i32, y: i32) -> bool {
+        self.restricted.contains(&(x, y))
     }
 
     /// Check if a world position is blocked
@@ -92,6 +128,43 @@ impl BlockedCells {
         self.is_blocked(cx, cy)
     }
 
+    /// Set the movement cost multiplier of a cell (1.0 clears it)
+    pub fn set_movement_cost(&mut self, x: i32, y: i32, cost: f32) {
+        let previous = if cost == 1.0 {
+            self.costs.remove(&(x, y))
+        } else {
+            self.costs.insert((x, y), cost)
+        };
+        if previous.unwrap_or(1.0) != cost {
+            self.revision += 1;
+        }
+    }
+
+    /// Movement cost multiplier of a cell (infinite if blocked)
+    pub fn movement_cost(&self, x: i32, y: i32) -> f32 {
+        if self.is_blocked(x, y) {
+            return f32::INFINITY;
+        }
+        self.costs.get(&(x, y)).copied().unwrap_or(1.0)
+    }
+
+    /// Movement cost multiplier at a world position (infinite if blocked)
+    pub fn position_movement_cost(&self, pos: Vec2) -> f32 {
+        let (cx, cy) = self.world_to_cell(pos);
+        self.movement_cost(cx, cy)
+    }
+
+    /// Counter bumped whenever a cell is blocked, unblocked, restricted,
+    /// or has its movement cost changed (not saved)
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Side length of one grid cell in world units
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
     /// Convert world position to cell coordinates
     pub fn world_to_cell(&self, pos: Vec2) -> (i32, i32) {
         let x = (pos.x / self.cell_size).floor() as i32;
@@ -143,19 +216,27 @@ impl BlockedCells {
         }
     }
 
-    /// Clear all blocked cells
+    /// Clear all blocked and restricted cells and movement costs
     pub fn clear(&mut self) {
         self.cells.clear();
+        self.costs.clear();
+        self.restricted.clear();
+        self.revision += 1;
     }
 
-    /// Get the number of blocked cells
+    /// Get the number of blocked cells (restricted ones included)
     pub fn len(&self) -> usize {
         self.cells.len()
+            + self
+                .restricted
+                .iter()
+                .filter(|cell| !self.cells.contains(cell))
+                .count()
     }
 
     /// Check if there are no blocked cells
     pub fn is_empty(&self) -> bool {
-        self.cells.is_empty()
+        self.cells.is_empty() && self.restricted.is_empty()
     }
 }
 
@@ -302,6 +383,20 @@ mod tests {
         assert!(!blocked.is_position_blocked(Vec2::new(5.0, 25.0)));
     }
 
+    #[test]
+    fn test_movement_cost() {
+        let mut blocked = BlockedCells::new();
+        blocked.set_movement_cost(3, 3, 1.5);
+        blocked.block(4, 4);
+
+        assert_eq!(blocked.movement_cost(3, 3), 1.5);
+        assert_eq!(blocked.position_movement_cost(Vec2::new(0.5, 0.5)), 1.0);
+        assert_eq!(blocked.movement_cost(4, 4), f32::INFINITY);
+
+        blocked.set_movement_cost(3, 3, 1.0);
+        assert_eq!(blocked.movement_cost(3, 3), 1.0);
+    }
+
     #[test]
     fn test_clear() {
         let mut blocked = BlockedCells::new();
@@ -313,6 +408,21 @@ mod tests {
         assert!(blocked.is_empty());
     }
 
+    #[test]
+    fn test_restricted_cells_block_without_touching_walls() {
+        let mut blocked = BlockedCells::new();
+        blocked.block(1, 1);
+        blocked.set_restricted([(1, 1), (2, 1)]);
+        assert!(blocked.is_blocked(2, 1));
+        assert!(blocked.is_restricted(2, 1));
+        assert_eq!(blocked.len(), 2);
+
+        // Lifting the restriction leaves the wall standing
+        blocked.set_restricted([]);
+        assert!(blocked.is_blocked(1, 1));
+        assert!(!blocked.is_blocked(2, 1));
+    }
+
     #[test]
     fn test_point_in_polygon_square() {
         let square = vec![
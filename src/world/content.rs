@@ -0,0 +1,31 @@
+//! Content settings - how much of settlers' darker lives the simulation shows
+//!
+//! Some of what settlers go through is hard to watch. These settings are
+//! the player's to choose, and everything they gate is off by default.
+
+use serde::{Deserialize, Serialize};
+
+/// How far despair may take a settler (see `simulation::despair`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DespairContent {
+    /// Misery stays a matter of opinion and unrest
+    #[default]
+    Off,
+    /// The despairing withdraw, refuse food, and may leave the settlement
+    Withdrawal,
+    /// As `Withdrawal`, and some reach a crisis that can end their life
+    Full,
+}
+
+impl DespairContent {
+    /// Whether despair shows at all
+    pub fn enabled(self) -> bool {
+        self != DespairContent::Off
+    }
+}
+
+/// The player's content settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentSettings {
+    pub despair: DespairContent,
+}
@@ -0,0 +1,74 @@
+//! Despair - settlers whose misery has lasted too long
+//!
+//! Only tracked while the content settings allow it (see
+//! `simulation::despair`). A settler in the depths for long enough
+//! withdraws from everyone, then stops eating, and at last either leaves
+//! or, where the settings allow, reaches a crisis. Friends and healers can
+//! reach them at any stage.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{EntityId, Tick};
+
+/// How far into despair a settler has gone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DespairStage {
+    /// In the depths, but still going about their days
+    Low,
+    /// Keeping away from everyone
+    Withdrawn,
+    /// Keeping away, and refusing food
+    RefusingFood,
+    /// At the breaking point; without someone to reach them today, they die
+    Crisis,
+}
+
+/// A settler in the depths
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Despairing {
+    pub id: EntityId,
+    /// When their mood first sank this low
+    pub since: Tick,
+    /// Days in a row spent in the depths
+    pub days: u32,
+    pub stage: DespairStage,
+}
+
+/// Everyone in the depths now
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Despair {
+    pub despairing: Vec<Despairing>,
+}
+
+impl Despair {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Someone's despair, if they are in the depths
+    pub fn get(&self, id: EntityId) -> Option<&Despairing> {
+        self.despairing.iter().find(|d| d.id == id)
+    }
+
+    /// How far into despair someone has gone, if at all
+    pub fn stage(&self, id: EntityId) -> Option<DespairStage> {
+        self.get(id).map(|d| d.stage)
+    }
+
+    /// Whether someone is keeping away from everyone
+    pub fn is_withdrawn(&self, id: EntityId) -> bool {
+        self.stage(id).is_some_and(|s| s >= DespairStage::Withdrawn)
+    }
+
+    /// Whether someone is refusing food
+    pub fn refuses_food(&self, id: EntityId) -> bool {
+        self.stage(id)
+            .is_some_and(|s| s >= DespairStage::RefusingFood)
+    }
+
+    /// Let someone's despair go, returning it if they were in the depths
+    pub fn lift(&mut self, id: EntityId) -> Option<Despairing> {
+        let at = self.despairing.iter().position(|d| d.id == id)?;
+        Some(self.despairing.remove(at))
+    }
+}
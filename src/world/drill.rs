@@ -0,0 +1,73 @@
+//! Drill - how well settlers have been trained to fight in formation
+//!
+//! Settlers earn a drill rating on the drill ground, in sessions run by
+//! those who know how to give orders and place a formation (see
+//! `simulation::drill`). It fades again without practice. A battle unit
+//! raised from settlers takes the mean rating of its soldiers, so a
+//! standing company answers orders faster and holds together better than
+//! a levy called in from the fields.
+
+use serde::{Deserialize, Serialize};
+
+use crate::battle::units::BattleUnit;
+use crate::core::types::{EntityId, Tick, Vec2};
+
+/// One settler's drill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Drilled {
+    pub id: EntityId,
+    /// 0.0 (raw levy) to 1.0 (drilled to perfection)
+    pub rating: f32,
+    /// When they last finished a session
+    pub last_drilled: Tick,
+}
+
+/// The drill ground and everyone who has trained on it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Drill {
+    /// Where the settlement drills; no one drills without one
+    pub ground: Option<Vec2>,
+    pub drilled: Vec<Drilled>,
+}
+
+impl Drill {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Someone's drill rating; 0.0 if they have never drilled
+    pub fn rating(&self, id: EntityId) -> f32 {
+        self.drilled
+            .iter()
+            .find(|d| d.id == id)
+            .map_or(0.0, |d| d.rating)
+    }
+
+    /// Raise someone's rating after a session at `tick`
+    pub fn practise(&mut self, id: EntityId, gain: f32, tick: Tick) {
+        match self.drilled.iter_mut().find(|d| d.id == id) {
+            Some(drilled) => {
+                drilled.rating = (drilled.rating + gain).min(1.0);
+                drilled.last_drilled = tick;
+            }
+            None => self.drilled.push(Drilled {
+                id,
+                rating: gain.min(1.0),
+                last_drilled: tick,
+            }),
+        }
+    }
+
+    /// Mean rating of a battle unit's soldiers; strangers count as raw
+    pub fn unit_rating(&self, unit: &BattleUnit) -> f32 {
+        let soldiers: Vec<EntityId> = unit
+            .elements
+            .iter()
+            .flat_map(|element| element.entities.iter().copied())
+            .collect();
+        if soldiers.is_empty() {
+            return 0.0;
+        }
+        soldiers.iter().map(|&id| self.rating(id)).sum::<f32>() / soldiers.len() as f32
+    }
+}
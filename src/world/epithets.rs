@@ -0,0 +1,64 @@
+//! Epithets - nicknames earned by notable feats
+//!
+//! Slaying an orc, crafting a masterwork, or living through a famine earns
+//! a settler an epithet ("Ada Orcbane"), kept on their identity and used
+//! wherever they are named in the chronicle. Only the first feat names
+//! them; every feat is remembered here.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{EntityId, Tick};
+
+/// A feat notable enough to be named for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Feat {
+    /// Killed an orc
+    SlewBeast,
+    /// Lived through a famine
+    SurvivedFamine,
+    /// Crafted a masterwork
+    Masterwork,
+}
+
+impl Feat {
+    /// The epithet it earns
+    pub fn epithet(&self) -> &'static str {
+        match self {
+            Feat::SlewBeast => "Orcbane",
+            Feat::SurvivedFamine => "the Lean",
+            Feat::Masterwork => "Goldhand",
+        }
+    }
+
+    /// What was done, e.g. "slew an orc"
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Feat::SlewBeast => "slew an orc",
+            Feat::SurvivedFamine => "lived through the famine",
+            Feat::Masterwork => "crafted a masterwork",
+        }
+    }
+}
+
+/// Feats performed, and the famine under way if any
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Feats {
+    /// Every feat, oldest first
+    pub record: Vec<(Tick, EntityId, Feat)>,
+    /// Tick the current run of hungry days began
+    pub famine_since: Option<Tick>,
+}
+
+impl Feats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feats someone has performed, oldest first
+    pub fn of(&self, id: EntityId) -> impl Iterator<Item = Feat> + '_ {
+        self.record
+            .iter()
+            .filter(move |(_, who, _)| *who == id)
+            .map(|&(_, _, feat)| feat)
+    }
+}
@@ -0,0 +1,89 @@
+//! Eras - the chapters of the settlement's story
+//!
+//! A scenario's campaign arc is a list of eras (see `data/eras.toml` and
+//! `simulation::eras`), each begun when a milestone is reached: a century
+//! survived, a rival faction wiped out. The settlement gathers for a
+//! ceremony, the chronicle starts a new chapter, and the era's rules take
+//! hold: blueprints it unlocks can be built, and work may go at a new pace.
+
+use serde::{Deserialize, Serialize};
+
+/// When an era began
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EraBegun {
+    pub name: String,
+    pub tick: u64,
+    pub day: u32,
+}
+
+/// The era the settlement is in, and what the eras so far have changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Eras {
+    /// Every era begun, oldest first (empty before the first milestone)
+    pub begun: Vec<EraBegun>,
+    /// Blueprints unlocked by the eras so far
+    pub unlocked: Vec<String>,
+    /// Factor on the pace of work set by the current era
+    pub work_pace: f32,
+}
+
+impl Default for Eras {
+    fn default() -> Self {
+        Self {
+            begun: Vec::new(),
+            unlocked: Vec::new(),
+            work_pace: 1.0,
+        }
+    }
+}
+
+impl Eras {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The era under way, if any has begun
+    pub fn current(&self) -> Option<&EraBegun> {
+        self.begun.last()
+    }
+
+    /// Whether the era of this name has begun
+    pub fn has_begun(&self, name: &str) -> bool {
+        self.begun.iter().any(|era| era.name == name)
+    }
+
+    /// Whether an era has unlocked this blueprint
+    pub fn has_unlocked(&self, blueprint: &str) -> bool {
+        self.unlocked.iter().any(|b| b == blueprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eras_accumulate() {
+        let mut eras = Eras::new();
+        assert!(eras.current().is_none());
+        assert_eq!(eras.work_pace, 1.0);
+
+        eras.begun.push(EraBegun {
+            name: "The Founding".into(),
+            tick: 0,
+            day: 0,
+        });
+        eras.begun.push(EraBegun {
+            name: "The Long Peace".into(),
+            tick: 100,
+            day: 1,
+        });
+        eras.unlocked.push("watchtower".into());
+
+        assert_eq!(eras.current().unwrap().name, "The Long Peace");
+        assert!(eras.has_begun("The Founding"));
+        assert!(!eras.has_begun("The Fall"));
+        assert!(eras.has_unlocked("watchtower"));
+        assert!(!eras.has_unlocked("shrine"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Exiles - those banished from the settlement, and what became of them
+//!
+//! An exile leaves the map with what they own and lives on off-screen
+//! (see `simulation::exile`): life away is hard or kind to them, their
+//! resentment at being sent away grows or fades, and some die abroad. Those
+//! who live come back in time as raiders, traders, or penitents.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::types::{EntityId, Tick, Vec2};
+use crate::world::identity::IdentityError;
+
+/// What an exile came back as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReturnRole {
+    /// Bent on revenge, at the head of a hostile band
+    Raider,
+    /// Prospered away and come to trade
+    Trader,
+    /// Humbled, asking to be taken back
+    Penitent,
+}
+
+/// Where an exile stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExileStatus {
+    Away,
+    Returned { role: ReturnRole, tick: Tick },
+    DiedAbroad { tick: Tick },
+}
+
+/// Why someone could not be exiled
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExileError {
+    #[error("{0:?} is not among the living settlers")]
+    NotLiving(EntityId),
+    #[error(transparent)]
+    Identity(#[from] IdentityError),
+}
+
+/// Someone banished from the settlement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exile {
+    pub id: EntityId,
+    pub name: String,
+    pub reason: String,
+    /// Who banished them (None when the player did)
+    pub banished_by: Option<EntityId>,
+    pub since: Tick,
+    /// When they come back, if they live that long
+    pub returns_at: Tick,
+    /// Where they left the map, and will come back
+    pub edge: Vec2,
+    /// Resentment at being sent away (0.0-1.0)
+    pub bitterness: f32,
+    /// How hard life away has been (0.0-1.0)
+    pub hardship: f32,
+    pub status: ExileStatus,
+}
+
+/// Everyone ever exiled
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Exiles {
+    pub exiles: Vec<Exile>,
+}
+
+impl Exiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The latest exile of someone
+    pub fn get(&self, id: EntityId) -> Option<&Exile> {
+        self.exiles.iter().rev().find(|exile| exile.id == id)
+    }
+
+    /// Whether someone is away in exile
+    pub fn is_away(&self, id: EntityId) -> bool {
+        self.get(id)
+            .is_some_and(|exile| exile.status == ExileStatus::Away)
+    }
+
+    /// Those away in exile
+    pub fn away(&self) -> impl Iterator<Item = &Exile> {
+        self.exiles
+            .iter()
+            .filter(|exile| exile.status == ExileStatus::Away)
+    }
+}
@@ -0,0 +1,257 @@
+//! Factions - who everyone answers to, and how factions stand with each other
+//!
+//! Every settler, dwarf, and elf starts out in the settlement's own faction,
+//! and every orc in a warband hostile to it. More factions can be founded (a
+//! rival village, a band of outlaws) and people moved between them. Each
+//! faction keeps its own stockpile and a diplomatic stance toward each other
+//! faction; the settlement's own goods stay in `World::stockpile`.
+//!
+//! Action selection treats members of hostile factions as threats. In combat,
+//! allies will not come to blows, and an attack on a member of a neutral
+//! faction is an act of war: their faction turns hostile.
+
+use ahash::AHashMap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::city::stockpile::Stockpile;
+use crate::core::types::EntityId;
+
+/// Unique identifier for factions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FactionId(pub u32);
+
+/// How one faction stands with another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Stance {
+    Allied,
+    #[default]
+    Neutral,
+    Hostile,
+}
+
+/// A group whose members act together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Faction {
+    pub id: FactionId,
+    pub name: String,
+    /// Goods held in common (unused for the settlement's own faction)
+    pub stockpile: Stockpile,
+    /// Stance toward each other faction (neutral unless set)
+    stances: AHashMap<FactionId, Stance>,
+}
+
+impl Faction {
+    pub fn stance_toward(&self, other: FactionId) -> Stance {
+        self.stances.get(&other).copied().unwrap_or_default()
+    }
+}
+
+/// Why a faction change could not be made
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactionError {
+    #[error("No faction {0:?}")]
+    Unknown(FactionId),
+    #[error("A faction cannot take a stance toward itself")]
+    SelfStance,
+}
+
+/// Every faction and who belongs to which
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Factions {
+    factions: Vec<Faction>,
+    members: AHashMap<EntityId, FactionId>,
+    next_id: u32,
+}
+
+impl Factions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The settlement's own faction and an orc warband at war with it
+    pub fn with_settlement() -> Self {
+        let mut factions = Self::new();
+        let settlement = factions.found("Settlement".into());
+        let warband = factions.found("Orc Warband".into());
+        factions
+            .set_stance(settlement, warband, Stance::Hostile)
+            .expect("both factions were just founded");
+        factions
+    }
+
+    /// Found a new faction, neutral toward everyone
+    pub fn found(&mut self, name: String) -> FactionId {
+        let id = FactionId(self.next_id);
+        self.next_id += 1;
+        self.factions.push(Faction {
+            id,
+            name,
+            stockpile: Stockpile::new(),
+            stances: AHashMap::new(),
+        });
+        id
+    }
+
+    /// The settlement's own faction (the first one founded)
+    pub fn settlement(&self) -> Option<FactionId> {
+        self.factions.first().map(|f| f.id)
+    }
+
+    /// The orc warband (the second one founded by `with_settlement`)
+    pub fn warband(&self) -> Option<FactionId> {
+        self.factions.get(1).map(|f| f.id)
+    }
+
+    pub fn get(&self, id: FactionId) -> Option<&Faction> {
+        self.factions.iter().find(|f| f.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: FactionId) -> Option<&mut Faction> {
+        self.factions.iter_mut().find(|f| f.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Faction> {
+        self.factions.iter()
+    }
+
+    /// Put someone in a faction (leaving any other)
+    pub fn join(&mut self, entity: EntityId, faction: FactionId) -> Result<(), FactionError> {
+        if self.get(faction).is_none() {
+            return Err(FactionError::Unknown(faction));
+        }
+        self.members.insert(entity, faction);
+        Ok(())
+    }
+
+    pub fn leave(&mut self, entity: EntityId) {
+        self.members.remove(&entity);
+    }
+
+    pub fn faction_of(&self, entity: EntityId) -> Option<FactionId> {
+        self.members.get(&entity).copied()
+    }
+
+    pub fn members(&self, faction: FactionId) -> impl Iterator<Item = EntityId> + '_ {
+        self.members
+            .iter()
+            .filter(move |(_, &f)| f == faction)
+            .map(|(&id, _)| id)
+    }
+
+    /// Set how two factions stand with each other (both ways)
+    pub fn set_stance(
+        &mut self,
+        a: FactionId,
+        b: FactionId,
+        stance: Stance,
+    ) -> Result<(), FactionError> {
+        if a == b {
+            return Err(FactionError::SelfStance);
+        }
+        for (from, to) in [(a, b), (b, a)] {
+            self.get_mut(from)
+                .ok_or(FactionError::Unknown(from))?
+                .stances
+                .insert(to, stance);
+        }
+        Ok(())
+    }
+
+    /// How two factions stand (a faction is its own ally)
+    pub fn stance(&self, a: FactionId, b: FactionId) -> Stance {
+        if a == b {
+            return Stance::Allied;
+        }
+        self.get(a).map(|f| f.stance_toward(b)).unwrap_or_default()
+    }
+
+    /// The stance between two people's factions, if both belong to one
+    fn stance_between(&self, a: EntityId, b: EntityId) -> Option<Stance> {
+        Some(self.stance(self.faction_of(a)?, self.faction_of(b)?))
+    }
+
+    /// Whether two people's factions are at war
+    pub fn are_hostile(&self, a: EntityId, b: EntityId) -> bool {
+        self.stance_between(a, b) == Some(Stance::Hostile)
+    }
+
+    /// Whether two people belong to different factions that are allied
+    ///
+    /// Members of the same faction are not counted: a quarrel within a
+    /// faction (a feud, a brawl) is its own affair.
+    pub fn are_allied(&self, a: EntityId, b: EntityId) -> bool {
+        self.faction_of(a) != self.faction_of(b)
+            && self.stance_between(a, b) == Some(Stance::Allied)
+    }
+
+    /// `attacker` struck `victim`: if their factions were neutral, the
+    /// victim's faction now counts the attacker's as hostile. Returns the
+    /// (attacker's, victim's) factions when this started a war.
+    pub fn provoke(
+        &mut self,
+        attacker: EntityId,
+        victim: EntityId,
+    ) -> Option<(FactionId, FactionId)> {
+        let (a, b) = (self.faction_of(attacker)?, self.faction_of(victim)?);
+        if a == b || self.stance(a, b) != Stance::Neutral {
+            return None;
+        }
+        self.set_stance(a, b, Stance::Hostile).ok()?;
+        Some((a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factions_and_diplomacy() {
+        let mut factions = Factions::with_settlement();
+        let settlement = factions.settlement().unwrap();
+        let warband = factions.warband().unwrap();
+        let riverfolk = factions.found("Riverfolk".into());
+
+        let (settler, neighbor, orc, rival) = (
+            EntityId::new(),
+            EntityId::new(),
+            EntityId::new(),
+            EntityId::new(),
+        );
+        factions.join(settler, settlement).unwrap();
+        factions.join(neighbor, settlement).unwrap();
+        factions.join(orc, warband).unwrap();
+        factions.join(rival, riverfolk).unwrap();
+        assert_eq!(factions.members(settlement).count(), 2);
+
+        assert!(factions.are_hostile(settler, orc));
+        assert!(!factions.are_hostile(settler, neighbor));
+        assert!(!factions.are_allied(settler, neighbor));
+        assert!(!factions.are_hostile(settler, rival));
+
+        factions
+            .set_stance(settlement, riverfolk, Stance::Allied)
+            .unwrap();
+        assert!(factions.are_allied(rival, settler));
+        assert_eq!(factions.provoke(rival, settler), None);
+
+        // An unprovoked attack on a neutral faction starts a war
+        let outlaws = factions.found("Outlaws".into());
+        factions.join(rival, outlaws).unwrap();
+        assert_eq!(
+            factions.provoke(rival, settler),
+            Some((outlaws, settlement))
+        );
+        assert!(factions.are_hostile(settler, rival));
+
+        assert_eq!(
+            factions.set_stance(settlement, settlement, Stance::Hostile),
+            Err(FactionError::SelfStance)
+        );
+        assert_eq!(
+            factions.join(settler, FactionId(99)),
+            Err(FactionError::Unknown(FactionId(99)))
+        );
+    }
+}
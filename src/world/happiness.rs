@@ -0,0 +1,180 @@
+//! Settlement happiness - the latest opinion poll and how it has trended
+//!
+//! Once a day every living settler is asked how things are (see
+//! `simulation::happiness`). The answers are summed up as a `Poll`: the
+//! settlement's happiness, how many approve, and what is pulling opinion up
+//! or down. The last few weeks of daily happiness are kept so scenarios and
+//! unrest can ask how long the mood has lasted. What the discontented are
+//! doing about it (see `simulation::unrest`) is kept alongside.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{EntityId, Tick, Vec2};
+
+/// Days of daily happiness kept
+pub const HAPPINESS_HISTORY_DAYS: usize = 30;
+
+/// Something that moves settlers' opinion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HappinessFactor {
+    /// How their thoughts lately have left them
+    Mood,
+    /// How hungry they are
+    Hunger,
+    /// Rest, safety, company, and purpose going unmet
+    Needs,
+    /// No house, or a house with more people than room
+    Overcrowding,
+    /// The recently dead, most of all those they were close to
+    RecentDeaths,
+    /// Celebrations and festivals they took part in
+    Festivals,
+    /// Whether what they asked of the player lately was granted
+    Requests,
+}
+
+impl HappinessFactor {
+    pub const ALL: [HappinessFactor; 7] = [
+        HappinessFactor::Mood,
+        HappinessFactor::Hunger,
+        HappinessFactor::Needs,
+        HappinessFactor::Overcrowding,
+        HappinessFactor::RecentDeaths,
+        HappinessFactor::Festivals,
+        HappinessFactor::Requests,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HappinessFactor::Mood => "mood",
+            HappinessFactor::Hunger => "hunger",
+            HappinessFactor::Needs => "needs",
+            HappinessFactor::Overcrowding => "overcrowding",
+            HappinessFactor::RecentDeaths => "recent deaths",
+            HappinessFactor::Festivals => "festivals",
+            HappinessFactor::Requests => "requests",
+        }
+    }
+}
+
+/// One day's poll of the settlement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Poll {
+    pub tick: Tick,
+    /// Settlers asked
+    pub respondents: usize,
+    /// Mean opinion (0.0 = wretched, 0.5 = indifferent, 1.0 = delighted)
+    pub happiness: f32,
+    /// Fraction of settlers whose opinion is at least indifferent
+    pub approval: f32,
+    /// Mean contribution of each factor to opinion, in `HappinessFactor::ALL` order
+    pub factors: Vec<(HappinessFactor, f32)>,
+}
+
+impl Poll {
+    /// Mean contribution of one factor
+    pub fn factor(&self, factor: HappinessFactor) -> f32 {
+        self.factors
+            .iter()
+            .find(|(f, _)| *f == factor)
+            .map(|&(_, value)| value)
+            .unwrap_or(0.0)
+    }
+
+    /// The factor pulling opinion down the most, if any is
+    pub fn worst_factor(&self) -> Option<HappinessFactor> {
+        self.factors
+            .iter()
+            .filter(|(_, value)| *value < 0.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|&(factor, _)| factor)
+    }
+
+    /// One-line summary for display
+    pub fn summary(&self) -> String {
+        let factors: Vec<String> = self
+            .factors
+            .iter()
+            .filter(|(_, value)| value.abs() >= 0.005)
+            .map(|(factor, value)| format!("{} {:+.0}%", factor.name(), value * 100.0))
+            .collect();
+        format!(
+            "Happiness {:.0}% (approval {:.0}%){}{}",
+            self.happiness * 100.0,
+            self.approval * 100.0,
+            if factors.is_empty() { "" } else { ": " },
+            factors.join(", ")
+        )
+    }
+}
+
+/// A gathering of the discontented
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Protest {
+    pub day: u32,
+    /// Where they gathered
+    pub place: Vec2,
+    pub protesters: Vec<EntityId>,
+    /// What they were protesting, if one thing stood out
+    pub over: Option<HappinessFactor>,
+}
+
+/// A settler contesting the player's right to give orders
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Challenge {
+    pub challenger: EntityId,
+    /// Orders go unheeded until this tick, unless the unrest ends first
+    pub until: Tick,
+}
+
+/// What the discontented are doing about it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Discontent {
+    /// Settlers working at a slowdown
+    pub slowing: Vec<EntityId>,
+    /// The latest protest
+    pub protest: Option<Protest>,
+    /// A challenge to the player's leadership under way
+    pub challenge: Option<Challenge>,
+    /// Whether a splinter group has left during this unrest
+    pub splintered: bool,
+    /// The last day on which settlers resented being ordered to work
+    /// through a feast
+    pub feast_grievance_day: Option<u32>,
+}
+
+/// The latest poll, recent daily happiness, and whether there is unrest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Happiness {
+    pub latest: Option<Poll>,
+    /// Daily happiness, oldest first
+    pub history: VecDeque<f32>,
+    /// Whether the settlement is in unrest
+    pub unrest: bool,
+    pub discontent: Discontent,
+}
+
+impl Happiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a day's poll
+    pub fn record(&mut self, poll: Poll) {
+        self.history.push_back(poll.happiness);
+        while self.history.len() > HAPPINESS_HISTORY_DAYS {
+            self.history.pop_front();
+        }
+        self.latest = Some(poll);
+    }
+
+    /// Whether each of the last `days` polls satisfied `test` (false if
+    /// there have not been that many)
+    pub fn held_for(&self, days: usize, test: impl Fn(f32) -> bool) -> bool {
+        days > 0
+            && self.history.len() >= days
+            && self.history.iter().rev().take(days).all(|&h| test(h))
+    }
+}
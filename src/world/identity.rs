@@ -0,0 +1,428 @@
+//! Identity registry - who someone is, and where, across every layer
+//!
+//! The same person can be a settler going about their day, a soldier in a
+//! battle unit, part of a campaign army on the march, or sitting at a ruler's
+//! court. The registry keeps one entry per `EntityId` naming the layer they
+//! are in now, their role there, and their fate (active, captured, or dead),
+//! so any system can ask "where is Marcus right now?" without searching
+//! every layer - and so nobody moves, commands, or counts on someone who has
+//! died or been taken.
+//!
+//! Every transition is logged as an `IdentityChange`; systems that care (the
+//! UI, the chronicle) drain the log to react to arrivals, captures, and deaths.
+
+use ahash::AHashMap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::battle::hex::BattleHexCoord;
+use crate::battle::units::{BattleUnit, UnitId};
+use crate::campaign::map::HexCoord;
+use crate::campaign::route::ArmyId;
+use crate::core::types::{EntityId, PolityId, RulerId, Species, Tick};
+
+/// The simulation layer someone is in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Layer {
+    Settlement,
+    Battle,
+    Campaign,
+    Aggregate,
+}
+
+/// What someone does in their current layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Role {
+    Civilian,
+    Soldier,
+    /// Leads a battle unit or campaign army
+    Leader,
+    Courier,
+    /// Rules a polity in the aggregate history
+    Ruler(RulerId),
+}
+
+/// Where someone is, within their layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Whereabouts {
+    /// In the settlement (their position is kept by their archetype)
+    Settlement,
+    Battle {
+        unit: UnitId,
+        hex: BattleHexCoord,
+    },
+    Campaign {
+        army: ArmyId,
+        hex: HexCoord,
+    },
+    /// At a polity's court
+    Court {
+        polity: PolityId,
+    },
+}
+
+impl Whereabouts {
+    pub fn layer(&self) -> Layer {
+        match self {
+            Whereabouts::Settlement => Layer::Settlement,
+            Whereabouts::Battle { .. } => Layer::Battle,
+            Whereabouts::Campaign { .. } => Layer::Campaign,
+            Whereabouts::Court { .. } => Layer::Aggregate,
+        }
+    }
+}
+
+/// Whether someone can still be called on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Fate {
+    Active,
+    Captured {
+        captor: Option<PolityId>,
+        since: Tick,
+    },
+    Dead {
+        tick: Tick,
+    },
+    /// Banished from the settlement
+    Exiled {
+        since: Tick,
+    },
+}
+
+/// One person's entry in the registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Identity {
+    pub name: String,
+    pub species: Species,
+    pub role: Role,
+    /// Last known whereabouts (where they were taken, or where they fell)
+    pub whereabouts: Whereabouts,
+    pub fate: Fate,
+    /// Earned nickname ("Wolfsbane", "the Lean"), if any
+    #[serde(default)]
+    pub epithet: Option<String>,
+}
+
+impl Identity {
+    /// Name and epithet together, e.g. "Sarah Wolfsbane"
+    pub fn known_as(&self) -> String {
+        match &self.epithet {
+            Some(epithet) => format!("{} {}", self.name, epithet),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn layer(&self) -> Layer {
+        self.whereabouts.layer()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.fate == Fate::Active
+    }
+}
+
+/// A transition in someone's identity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdentityChange {
+    Registered,
+    Moved { from: Layer, to: Layer },
+    Captured { captor: Option<PolityId> },
+    Freed,
+    Exiled,
+    Returned,
+    Died,
+}
+
+/// Why an identity could not be changed
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityError {
+    #[error("No one is registered as {0:?}")]
+    Unknown(EntityId),
+    #[error("{0:?} is dead")]
+    Dead(EntityId),
+    #[error("{0:?} is held captive")]
+    Captive(EntityId),
+    #[error("{0:?} is in exile")]
+    Exiled(EntityId),
+}
+
+/// Every known person, by id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdentityRegistry {
+    identities: AHashMap<EntityId, Identity>,
+    /// Transitions not yet drained, oldest first
+    changes: Vec<(Tick, EntityId, IdentityChange)>,
+}
+
+impl IdentityRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a newcomer to the settlement
+    pub fn register(&mut self, id: EntityId, name: String, species: Species, tick: Tick) {
+        self.identities.insert(
+            id,
+            Identity {
+                name,
+                species,
+                role: Role::Civilian,
+                whereabouts: Whereabouts::Settlement,
+                fate: Fate::Active,
+                epithet: None,
+            },
+        );
+        self.changes.push((tick, id, IdentityChange::Registered));
+    }
+
+    pub fn get(&self, id: EntityId) -> Option<&Identity> {
+        self.identities.get(&id)
+    }
+
+    /// Whether someone is alive and free to be called on
+    pub fn is_active(&self, id: EntityId) -> bool {
+        self.get(id).is_some_and(Identity::is_active)
+    }
+
+    /// Give someone a new name
+    pub fn rename(&mut self, id: EntityId, name: String) -> Result<(), IdentityError> {
+        let identity = self
+            .identities
+            .get_mut(&id)
+            .ok_or(IdentityError::Unknown(id))?;
+        identity.name = name;
+        Ok(())
+    }
+
+    /// Give someone an epithet unless they already have one; returns
+    /// whether it was given
+    pub fn grant_epithet(&mut self, id: EntityId, epithet: &str) -> Result<bool, IdentityError> {
+        let identity = self.active_mut(id)?;
+        if identity.epithet.is_some() {
+            return Ok(false);
+        }
+        identity.epithet = Some(epithet.to_string());
+        Ok(true)
+    }
+
+    /// Everyone with a name, in no particular order
+    pub fn find_by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = EntityId> + 'a {
+        self.identities
+            .iter()
+            .filter(move |(_, identity)| identity.name.eq_ignore_ascii_case(name))
+            .map(|(&id, _)| id)
+    }
+
+    /// Everyone active in a layer
+    pub fn active_in(&self, layer: Layer) -> impl Iterator<Item = EntityId> + '_ {
+        self.identities
+            .iter()
+            .filter(move |(_, identity)| identity.is_active() && identity.layer() == layer)
+            .map(|(&id, _)| id)
+    }
+
+    fn active_mut(&mut self, id: EntityId) -> Result<&mut Identity, IdentityError> {
+        let identity = self
+            .identities
+            .get_mut(&id)
+            .ok_or(IdentityError::Unknown(id))?;
+        match identity.fate {
+            Fate::Active => Ok(identity),
+            Fate::Captured { .. } => Err(IdentityError::Captive(id)),
+            Fate::Dead { .. } => Err(IdentityError::Dead(id)),
+            Fate::Exiled { .. } => Err(IdentityError::Exiled(id)),
+        }
+    }
+
+    /// Move someone into a layer and role (only the living and free can go)
+    pub fn move_to(
+        &mut self,
+        id: EntityId,
+        whereabouts: Whereabouts,
+        role: Role,
+        tick: Tick,
+    ) -> Result<(), IdentityError> {
+        let identity = self.active_mut(id)?;
+        let from = identity.layer();
+        identity.whereabouts = whereabouts;
+        identity.role = role;
+        let to = identity.layer();
+        if from != to {
+            self.changes
+                .push((tick, id, IdentityChange::Moved { from, to }));
+        }
+        Ok(())
+    }
+
+    /// Send everyone registered in a battle unit to it (its leader as leader)
+    ///
+    /// Anonymous soldiers raised for the battle are not registered and are
+    /// skipped. Returns how many people were deployed.
+    pub fn deploy(&mut self, unit: &BattleUnit, tick: Tick) -> usize {
+        let whereabouts = Whereabouts::Battle {
+            unit: unit.id,
+            hex: unit.position,
+        };
+        let leader = unit.leader.map(|id| (id, Role::Leader));
+        let soldiers = unit
+            .elements
+            .iter()
+            .flat_map(|element| element.entities.iter().map(|&id| (id, Role::Soldier)));
+        leader
+            .into_iter()
+            .chain(soldiers)
+            .filter(|&(id, role)| self.move_to(id, whereabouts, role, tick).is_ok())
+            .count()
+    }
+
+    /// Bring someone home to the settlement
+    pub fn recall(&mut self, id: EntityId, tick: Tick) -> Result<(), IdentityError> {
+        self.move_to(id, Whereabouts::Settlement, Role::Civilian, tick)
+    }
+
+    /// Someone has been taken where they stand
+    pub fn capture(
+        &mut self,
+        id: EntityId,
+        captor: Option<PolityId>,
+        tick: Tick,
+    ) -> Result<(), IdentityError> {
+        let identity = self.active_mut(id)?;
+        identity.fate = Fate::Captured {
+            captor,
+            since: tick,
+        };
+        self.changes
+            .push((tick, id, IdentityChange::Captured { captor }));
+        Ok(())
+    }
+
+    /// A captive is released, back home
+    pub fn free(&mut self, id: EntityId, tick: Tick) -> Result<(), IdentityError> {
+        let identity = self
+            .identities
+            .get_mut(&id)
+            .ok_or(IdentityError::Unknown(id))?;
+        match identity.fate {
+            Fate::Captured { .. } => {
+                identity.fate = Fate::Active;
+                identity.whereabouts = Whereabouts::Settlement;
+                identity.role = Role::Civilian;
+                self.changes.push((tick, id, IdentityChange::Freed));
+                Ok(())
+            }
+            Fate::Dead { .. } => Err(IdentityError::Dead(id)),
+            Fate::Exiled { .. } => Err(IdentityError::Exiled(id)),
+            Fate::Active => Ok(()),
+        }
+    }
+
+    /// Someone is banished from the settlement
+    pub fn exile(&mut self, id: EntityId, tick: Tick) -> Result<(), IdentityError> {
+        let identity = self.active_mut(id)?;
+        identity.fate = Fate::Exiled { since: tick };
+        identity.whereabouts = Whereabouts::Settlement;
+        identity.role = Role::Civilian;
+        self.changes.push((tick, id, IdentityChange::Exiled));
+        Ok(())
+    }
+
+    /// An exile comes back to the settlement
+    pub fn end_exile(&mut self, id: EntityId, tick: Tick) -> Result<(), IdentityError> {
+        let identity = self
+            .identities
+            .get_mut(&id)
+            .ok_or(IdentityError::Unknown(id))?;
+        match identity.fate {
+            Fate::Exiled { .. } => {
+                identity.fate = Fate::Active;
+                self.changes.push((tick, id, IdentityChange::Returned));
+                Ok(())
+            }
+            Fate::Dead { .. } => Err(IdentityError::Dead(id)),
+            Fate::Captured { .. } => Err(IdentityError::Captive(id)),
+            Fate::Active => Ok(()),
+        }
+    }
+
+    /// Someone has died; they keep their last whereabouts. Returns whether
+    /// this was news (they were not already recorded dead).
+    pub fn record_death(&mut self, id: EntityId, tick: Tick) -> bool {
+        match self.identities.get_mut(&id) {
+            Some(identity) if !matches!(identity.fate, Fate::Dead { .. }) => {
+                identity.fate = Fate::Dead { tick };
+                self.changes.push((tick, id, IdentityChange::Died));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Take the transitions logged since the last drain
+    pub fn drain_changes(&mut self) -> Vec<(Tick, EntityId, IdentityChange)> {
+        std::mem::take(&mut self.changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::unit_type::UnitType;
+    use crate::battle::units::Element;
+
+    #[test]
+    fn test_identity_follows_someone_across_layers() {
+        let mut registry = IdentityRegistry::new();
+        let marcus = EntityId::new();
+        registry.register(marcus, "Marcus".into(), Species::Human, 0);
+        assert_eq!(registry.find_by_name("marcus").next(), Some(marcus));
+
+        let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        unit.leader = Some(marcus);
+        unit.elements
+            .push(Element::new(vec![EntityId::new(), EntityId::new()]));
+        assert_eq!(registry.deploy(&unit, 5), 1);
+        let identity = registry.get(marcus).unwrap();
+        assert_eq!(identity.layer(), Layer::Battle);
+        assert_eq!(identity.role, Role::Leader);
+        assert_eq!(registry.active_in(Layer::Battle).count(), 1);
+
+        registry.capture(marcus, Some(PolityId(3)), 8).unwrap();
+        assert!(!registry.is_active(marcus));
+        assert_eq!(
+            registry.recall(marcus, 9),
+            Err(IdentityError::Captive(marcus))
+        );
+        registry.free(marcus, 10).unwrap();
+        assert_eq!(registry.get(marcus).unwrap().layer(), Layer::Settlement);
+
+        assert!(registry.record_death(marcus, 12));
+        assert!(!registry.record_death(marcus, 13));
+        assert_eq!(
+            registry.recall(marcus, 14),
+            Err(IdentityError::Dead(marcus))
+        );
+
+        let changes: Vec<IdentityChange> = registry
+            .drain_changes()
+            .into_iter()
+            .map(|(_, _, change)| change)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                IdentityChange::Registered,
+                IdentityChange::Moved {
+                    from: Layer::Settlement,
+                    to: Layer::Battle
+                },
+                IdentityChange::Captured {
+                    captor: Some(PolityId(3))
+                },
+                IdentityChange::Freed,
+                IdentityChange::Died,
+            ]
+        );
+        assert!(registry.drain_changes().is_empty());
+    }
+}
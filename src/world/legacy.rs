@@ -0,0 +1,249 @@
+//! Lineage, memorials, and the chronicle of the dead
+//!
+//! Births record parents so that descent can be traced a few generations up
+//! or down. Notable dead get a memorial where they fell, carrying their
+//! renown and, if they were killed, the wrongdoer whose debt is still owed.
+//! The chronicle keeps one line for every death, every settled feud, and
+//! every battle fought, in chapters opened as the settlement enters a new
+//! era. Battles are kept whole as well, told in phases and turning points.
+
+use ahash::AHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::battle::chronicle::BattleChronicle;
+use crate::core::types::{EntityId, Vec2};
+
+/// How many generations descent is traced through
+pub const MAX_GENERATIONS: u32 = 4;
+
+/// Unique identifier for a memorial
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MemorialId(pub u32);
+
+/// A grave or memorial stone for one of the notable dead
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Memorial {
+    pub id: MemorialId,
+    pub deceased: EntityId,
+    pub name: String,
+    pub position: Vec2,
+    pub died_tick: u64,
+    /// How widely the deceased was known and loved (0.0-1.0)
+    pub renown: f32,
+    /// Whoever killed them, if anyone
+    pub wrongdoer: Option<EntityId>,
+    /// Whether the debt against the wrongdoer has been settled
+    pub settled: bool,
+}
+
+impl Memorial {
+    /// Wrongdoer whose debt is still owed, if any
+    pub fn grievance(&self) -> Option<EntityId> {
+        if self.settled {
+            None
+        } else {
+            self.wrongdoer
+        }
+    }
+}
+
+/// One line of the chronicle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChronicleEntry {
+    pub tick: u64,
+    pub text: String,
+}
+
+/// A chapter of the chronicle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChronicleChapter {
+    pub title: String,
+    pub tick: u64,
+    /// Index of its first line in the chronicle
+    pub first_entry: usize,
+}
+
+/// Family trees, memorials, and the chronicle
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Legacy {
+    parents: AHashMap<EntityId, Vec<EntityId>>,
+    pub memorials: Vec<Memorial>,
+    pub chronicle: Vec<ChronicleEntry>,
+    /// Chapter breaks, oldest first; lines before the first have no chapter
+    #[serde(default)]
+    pub chapters: Vec<ChronicleChapter>,
+    /// Every battle fought, oldest first
+    #[serde(default)]
+    pub battles: Vec<BattleChronicle>,
+    next_memorial_id: u32,
+}
+
+impl Legacy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a child's parents
+    pub fn record_birth(&mut self, child: EntityId, parents: &[EntityId]) {
+        if !parents.is_empty() {
+            self.parents.insert(child, parents.to_vec());
+        }
+    }
+
+    pub fn parents_of(&self, child: EntityId) -> &[EntityId] {
+        self.parents.get(&child).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Ancestors within `MAX_GENERATIONS`, with their generation (1 = parent)
+    pub fn ancestors(&self, id: EntityId) -> Vec<(EntityId, u32)> {
+        let mut found = Vec::new();
+        let mut frontier = vec![id];
+        for generation in 1..=MAX_GENERATIONS {
+            frontier = frontier
+                .iter()
+                .flat_map(|&child| self.parents_of(child).iter().copied())
+                .collect();
+            if frontier.is_empty() {
+                break;
+            }
+            found.extend(frontier.iter().map(|&a| (a, generation)));
+        }
+        found
+    }
+
+    /// Generations between a descendant and an ancestor (1 = child), if related
+    pub fn generations_between(&self, descendant: EntityId, ancestor: EntityId) -> Option<u32> {
+        self.ancestors(descendant)
+            .into_iter()
+            .find(|&(a, _)| a == ancestor)
+            .map(|(_, generation)| generation)
+    }
+
+    /// Raise a memorial to the dead
+    pub fn raise_memorial(
+        &mut self,
+        deceased: EntityId,
+        name: String,
+        position: Vec2,
+        renown: f32,
+        wrongdoer: Option<EntityId>,
+        tick: u64,
+    ) -> MemorialId {
+        let id = MemorialId(self.next_memorial_id);
+        self.next_memorial_id += 1;
+        self.memorials.push(Memorial {
+            id,
+            deceased,
+            name,
+            position,
+            died_tick: tick,
+            renown: renown.clamp(0.0, 1.0),
+            wrongdoer,
+            settled: false,
+        });
+        id
+    }
+
+    pub fn memorial_of(&self, deceased: EntityId) -> Option<&Memorial> {
+        self.memorials.iter().find(|m| m.deceased == deceased)
+    }
+
+    pub fn memorial_of_mut(&mut self, deceased: EntityId) -> Option<&mut Memorial> {
+        self.memorials.iter_mut().find(|m| m.deceased == deceased)
+    }
+
+    /// Social standing inherited from memorialized ancestors (0.0-1.0)
+    ///
+    /// Each ancestor contributes their renown, halved per generation back.
+    pub fn standing(&self, id: EntityId) -> f32 {
+        self.ancestors(id)
+            .into_iter()
+            .filter_map(|(ancestor, generation)| {
+                self.memorial_of(ancestor)
+                    .map(|m| m.renown * 0.5f32.powi(generation as i32 - 1))
+            })
+            .sum::<f32>()
+            .min(1.0)
+    }
+
+    /// Add a line to the chronicle
+    pub fn record(&mut self, tick: u64, text: impl Into<String>) {
+        self.chronicle.push(ChronicleEntry {
+            tick,
+            text: text.into(),
+        });
+    }
+
+    /// Keep a battle's chronicle, and its summary as a line of the chronicle
+    pub fn record_battle(&mut self, tick: u64, battle: BattleChronicle) {
+        self.record(tick, battle.summary());
+        self.battles.push(battle);
+    }
+
+    /// The most recent battle of that name
+    pub fn battle(&self, name: &str) -> Option<&BattleChronicle> {
+        self.battles.iter().rev().find(|b| b.name == name)
+    }
+
+    /// Start a new chapter of the chronicle with the next line
+    pub fn begin_chapter(&mut self, tick: u64, title: impl Into<String>) {
+        self.chapters.push(ChronicleChapter {
+            title: title.into(),
+            tick,
+            first_entry: self.chronicle.len(),
+        });
+    }
+
+    /// The lines of one chapter
+    pub fn chapter_entries(&self, chapter: usize) -> &[ChronicleEntry] {
+        let Some(start) = self.chapters.get(chapter).map(|c| c.first_entry) else {
+            return &[];
+        };
+        let end = self
+            .chapters
+            .get(chapter + 1)
+            .map_or(self.chronicle.len(), |c| c.first_entry);
+        &self.chronicle[start..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descent_and_inherited_standing() {
+        let mut legacy = Legacy::new();
+        let founder = EntityId::new();
+        let spouse = EntityId::new();
+        let child = EntityId::new();
+        let grandchild = EntityId::new();
+        let stranger = EntityId::new();
+        legacy.record_birth(child, &[founder, spouse]);
+        legacy.record_birth(grandchild, &[child]);
+
+        assert_eq!(legacy.generations_between(grandchild, founder), Some(2));
+        assert_eq!(legacy.generations_between(child, spouse), Some(1));
+        assert_eq!(legacy.generations_between(stranger, founder), None);
+
+        legacy.raise_memorial(founder, "Founder".into(), Vec2::new(0.0, 0.0), 0.8, None, 0);
+        assert_eq!(legacy.standing(child), 0.8);
+        assert_eq!(legacy.standing(grandchild), 0.4);
+        assert_eq!(legacy.standing(stranger), 0.0);
+    }
+
+    #[test]
+    fn test_chronicle_chapters() {
+        let mut legacy = Legacy::new();
+        legacy.record(0, "The settlement was founded");
+        legacy.begin_chapter(10, "The First Century");
+        legacy.record(10, "A jubilee was held");
+        legacy.record(11, "Thomas died");
+        legacy.begin_chapter(20, "The Long Peace");
+
+        assert_eq!(legacy.chapter_entries(0).len(), 2);
+        assert_eq!(legacy.chapter_entries(0)[0].text, "A jubilee was held");
+        assert!(legacy.chapter_entries(1).is_empty());
+        assert!(legacy.chapter_entries(2).is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+//! Light sources - torches, lanterns, braziers, and campfires
+//!
+//! Lights are lit whenever ambient light falls below `LIT_BELOW_AMBIENT` and
+//! burn wood while they are lit. Each one lights a circle around itself;
+//! `light_at` combines those circles with the ambient light from astronomy.
+//! Carried lights follow their carrier and are moved by the lighting system.
+
+use crate::core::types::{EntityId, Vec2};
+use serde::{Deserialize, Serialize};
+
+/// Lights are lit when ambient light is below this level
+pub const LIT_BELOW_AMBIENT: f32 = 0.5;
+
+/// Fraction of normal sight range left in total darkness
+pub const DARK_VISIBILITY: f32 = 0.2;
+
+/// Fraction of normal work rate left in total darkness
+pub const DARK_WORK_EFFICIENCY: f32 = 0.5;
+
+/// Buildings within this distance of a burning light can catch fire
+pub const FIRE_RANGE: f32 = 3.0;
+
+/// Unique identifier for a light source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LightId(pub u32);
+
+/// Kind of light
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LightKind {
+    /// Hand-held burning brand
+    Torch,
+    /// Enclosed flame, carried or hung
+    Lantern,
+    /// Standing fire bowl
+    Brazier,
+    /// Open fire on the ground
+    Campfire,
+}
+
+impl LightKind {
+    /// Distance the light reaches
+    pub fn radius(&self) -> f32 {
+        match self {
+            LightKind::Torch => 6.0,
+            LightKind::Lantern => 8.0,
+            LightKind::Brazier => 10.0,
+            LightKind::Campfire => 12.0,
+        }
+    }
+
+    /// Light level at the centre of the light
+    pub fn intensity(&self) -> f32 {
+        match self {
+            LightKind::Torch => 0.7,
+            LightKind::Lantern => 0.8,
+            LightKind::Brazier => 0.9,
+            LightKind::Campfire => 1.0,
+        }
+    }
+
+    /// Ticks of burning one unit of wood provides
+    pub fn burn_ticks_per_wood(&self) -> f32 {
+        match self {
+            LightKind::Torch => 200.0,
+            LightKind::Lantern => 600.0,
+            LightKind::Brazier => 300.0,
+            LightKind::Campfire => 150.0,
+        }
+    }
+
+    /// Daily chance of setting a nearby building alight while lit
+    pub fn fire_risk(&self) -> f32 {
+        match self {
+            LightKind::Torch => 0.02,
+            LightKind::Lantern => 0.005,
+            LightKind::Brazier => 0.01,
+            LightKind::Campfire => 0.03,
+        }
+    }
+
+    /// Whether the light can be carried by a person
+    pub fn carriable(&self) -> bool {
+        matches!(self, LightKind::Torch | LightKind::Lantern)
+    }
+}
+
+/// A placed or carried light
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSource {
+    pub id: LightId,
+    pub kind: LightKind,
+    pub position: Vec2,
+    /// Ticks of burning left before more wood is needed
+    pub fuel: f32,
+    pub lit: bool,
+    /// Entity carrying the light, if any
+    pub carrier: Option<EntityId>,
+}
+
+impl LightSource {
+    /// Light this source casts at a position (0.0 when out of reach or unlit)
+    pub fn light_at(&self, pos: Vec2) -> f32 {
+        if !self.lit {
+            return 0.0;
+        }
+        let distance = self.position.distance(&pos);
+        let radius = self.kind.radius();
+        if distance >= radius {
+            return 0.0;
+        }
+        self.kind.intensity() * (1.0 - distance / radius)
+    }
+}
+
+/// All light sources on the local map
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LightSources {
+    pub sources: Vec<LightSource>,
+    next_id: u32,
+}
+
+impl LightSources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, kind: LightKind, position: Vec2, carrier: Option<EntityId>) -> LightId {
+        let id = LightId(self.next_id);
+        self.next_id += 1;
+        self.sources.push(LightSource {
+            id,
+            kind,
+            position,
+            fuel: 0.0,
+            lit: false,
+            carrier,
+        });
+        id
+    }
+
+    /// Place a fixed light
+    pub fn place(&mut self, kind: LightKind, position: Vec2) -> LightId {
+        self.push(kind, position, None)
+    }
+
+    /// Hand a light to an entity. Returns None if the light cannot be carried
+    /// or the entity already carries one.
+    pub fn give(&mut self, kind: LightKind, carrier: EntityId, position: Vec2) -> Option<LightId> {
+        if !kind.carriable() || self.carried_by(carrier).is_some() {
+            return None;
+        }
+        Some(self.push(kind, position, Some(carrier)))
+    }
+
+    pub fn get(&self, id: LightId) -> Option<&LightSource> {
+        self.sources.iter().find(|s| s.id == id)
+    }
+
+    /// Light carried by an entity, if any
+    pub fn carried_by(&self, carrier: EntityId) -> Option<&LightSource> {
+        self.sources.iter().find(|s| s.carrier == Some(carrier))
+    }
+
+    /// Total light at a position: the brighter of ambient light and the
+    /// strongest light source reaching it
+    pub fn light_at(&self, pos: Vec2, ambient: f32) -> f32 {
+        self.sources
+            .iter()
+            .map(|s| s.light_at(pos))
+            .fold(ambient, f32::max)
+            .clamp(0.0, 1.0)
+    }
+
+    /// Light, snuff, and burn every source for one tick
+    ///
+    /// A source that runs dry takes one unit of wood from `wood`; if none is
+    /// left it goes out. Returns the wood used.
+    pub fn burn(&mut self, ambient: f32, wood: &mut u32) -> u32 {
+        self.burn_over(ambient, wood, 1.0)
+    }
+
+    /// Burn every source for `ticks` ticks at once, refuelling as often as
+    /// burning tick by tick would
+    pub fn burn_over(&mut self, ambient: f32, wood: &mut u32, ticks: f32) -> u32 {
+        let wanted = ambient < LIT_BELOW_AMBIENT;
+        let mut used = 0;
+        for source in &mut self.sources {
+            if !wanted {
+                source.lit = false;
+                continue;
+            }
+            while source.fuel <= ticks - 1.0 && *wood > 0 {
+                *wood -= 1;
+                used += 1;
+                source.fuel += source.kind.burn_ticks_per_wood();
+            }
+            if source.fuel <= 0.0 {
+                source.lit = false;
+                continue;
+            }
+            source.lit = true;
+            source.fuel -= ticks;
+        }
+        used
+    }
+}
+
+/// Share of normal sight range left at a light level
+pub fn visibility(light: f32) -> f32 {
+    DARK_VISIBILITY + (1.0 - DARK_VISIBILITY) * light.clamp(0.0, 1.0)
+}
+
+/// Share of normal work rate left at a light level
+pub fn work_efficiency(light: f32) -> f32 {
+    DARK_WORK_EFFICIENCY + (1.0 - DARK_WORK_EFFICIENCY) * light.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lights_burn_wood_only_in_the_dark() {
+        let mut lights = LightSources::new();
+        let pos = Vec2::new(0.0, 0.0);
+        lights.place(LightKind::Campfire, pos);
+        let mut wood = 2;
+
+        // Daylight: nothing lit, nothing burned
+        assert_eq!(lights.burn(1.0, &mut wood), 0);
+        assert_eq!(lights.light_at(Vec2::new(3.0, 0.0), 0.0), 0.0);
+
+        // Night: one wood keeps a campfire going for 150 ticks
+        for _ in 0..150 {
+            lights.burn(0.0, &mut wood);
+        }
+        assert_eq!(wood, 1);
+        assert!(lights.light_at(Vec2::new(3.0, 0.0), 0.0) > 0.7);
+        assert_eq!(lights.light_at(Vec2::new(20.0, 0.0), 0.1), 0.1);
+
+        // Out of wood: the fire goes out
+        for _ in 0..151 {
+            lights.burn(0.0, &mut wood);
+        }
+        assert_eq!(wood, 0);
+        assert_eq!(lights.light_at(pos, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_only_hand_lights_can_be_carried() {
+        let mut lights = LightSources::new();
+        let carrier = EntityId::new();
+        let pos = Vec2::new(0.0, 0.0);
+
+        assert!(lights.give(LightKind::Brazier, carrier, pos).is_none());
+        let torch = lights.give(LightKind::Torch, carrier, pos).unwrap();
+        assert_eq!(lights.carried_by(carrier).map(|s| s.id), Some(torch));
+        // One light per hand
+        assert!(lights.give(LightKind::Lantern, carrier, pos).is_none());
+
+        assert!(visibility(0.0) < visibility(1.0));
+        assert_eq!(work_efficiency(1.0), 1.0);
+    }
+}
@@ -1,11 +1,13 @@
 //! Load world objects from JSON placement files
 //!
 //! This module provides `PlacementLoader` which converts JSON placement files
-//! (from worldgen) into `WorldObjects` containing instantiated blueprints.
+//! (from worldgen) into `WorldObjects` containing instantiated blueprints,
+//! along with any zones the file designates.
 
 use crate::blueprints::BlueprintRegistry;
 use crate::world::objects::WorldObjects;
 use crate::world::placement::{ObjectState, PlacementFile};
+use crate::world::zones::ZoneDesignation;
 use glam::Vec2;
 use std::path::Path;
 use thiserror::Error;
@@ -44,6 +46,16 @@ impl<'a> PlacementLoader<'a> {
         self.load_from_placements(&file)
     }
 
+    /// Load placements and the zones designated alongside them
+    pub fn load_with_zones_from_json(
+        &self,
+        json: &str,
+    ) -> Result<(WorldObjects, Vec<ZoneDesignation>), LoadError> {
+        let file: PlacementFile = serde_json::from_str(json)?;
+        let objects = self.load_from_placements(&file)?;
+        Ok((objects, file.zones))
+    }
+
     /// Load placements from a JSON file on disk
     pub fn load_from_file(&self, path: &Path) -> Result<WorldObjects, LoadError> {
         let content = std::fs::read_to_string(path)?;
@@ -120,6 +132,7 @@ impl<'a> PlacementLoader<'a> {
 mod tests {
     use super::*;
     use crate::blueprints::BlueprintRegistry;
+    use crate::world::zones::{ZoneKind, ZoneShape};
     use std::path::Path;
 
     fn setup_registry() -> BlueprintRegistry {
@@ -402,4 +415,28 @@ mod tests {
 
         assert!(objects.is_empty());
     }
+
+    #[test]
+    fn test_load_zones() {
+        let registry = setup_registry();
+
+        let json = r#"{
+            "version": 1,
+            "placements": [],
+            "zones": [
+                { "kind": "stockpile", "shape": "rect", "min": { "x": 0.0, "y": 0.0 }, "max": { "x": 8.0, "y": 4.0 } },
+                { "kind": "no_entry", "shape": "hex", "center": { "x": 30.0, "y": 30.0 }, "radius": 6.0, "name": "Shrine" }
+            ]
+        }"#;
+
+        let loader = PlacementLoader::new(&registry);
+        let (objects, zones) = loader.load_with_zones_from_json(json).unwrap();
+
+        assert!(objects.is_empty());
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].kind, ZoneKind::Stockpile);
+        assert_eq!(zones[1].kind, ZoneKind::NoEntry);
+        assert_eq!(zones[1].name.as_deref(), Some("Shrine"));
+        assert!(matches!(zones[1].shape, ZoneShape::Hex { radius, .. } if radius == 6.0));
+    }
 }
@@ -1,13 +1,57 @@
 //! World objects and spatial identification
 
+pub mod avatar;
 pub mod blocking;
+pub mod content;
+pub mod despair;
+pub mod drill;
+pub mod epithets;
+pub mod eras;
+pub mod exile;
+pub mod faction;
+pub mod happiness;
+pub mod identity;
+pub mod legacy;
+pub mod lighting;
 pub mod loader;
+pub mod oaths;
 pub mod objects;
+pub mod omens;
 pub mod placement;
+pub mod places;
+pub mod requests;
+pub mod situation;
 pub mod spatial_id;
+pub mod terrain;
+pub mod transformations;
+pub mod zones;
 
+pub use avatar::{Avatar, Deed, DialogueChoice};
 pub use blocking::{BlockedCells, BlockingState};
+pub use content::{ContentSettings, DespairContent};
+pub use despair::{Despair, DespairStage, Despairing};
+pub use drill::{Drill, Drilled};
+pub use epithets::{Feat, Feats};
+pub use eras::{EraBegun, Eras};
+pub use exile::{Exile, ExileError, ExileStatus, Exiles, ReturnRole};
+pub use faction::{Faction, FactionError, FactionId, Factions, Stance};
+pub use happiness::{
+    Challenge, Discontent, Happiness, HappinessFactor, Poll, Protest, HAPPINESS_HISTORY_DAYS,
+};
+pub use identity::{
+    Fate, Identity, IdentityChange, IdentityError, IdentityRegistry, Layer, Role, Whereabouts,
+};
+pub use legacy::{ChronicleChapter, ChronicleEntry, Legacy, Memorial, MemorialId};
+pub use lighting::{LightId, LightKind, LightSource, LightSources};
 pub use loader::{LoadError, PlacementLoader};
+pub use oaths::{Oath, OathId, OathStatus, OathTerms, Oaths};
 pub use objects::WorldObjects;
+pub use omens::{Omen, Omens, DEFAULT_CULTURE};
 pub use placement::{ObjectState, PlacedByJson, Placement, PlacementFile, PlacementMetadata};
+pub use places::{Place, PlaceKind, Places};
+pub use requests::{Request, RequestId, RequestStatus, Requests, Wish};
+pub use situation::{Fight, SituationLog, FIGHT_LULL_TICKS};
 pub use spatial_id::SpatialId;
+pub use terrain::{FeatureId, TerrainFeature, TerrainFeatureKind, TerrainFeatures};
+pub use transformations::{Transformations, Transformed, TransformationTrigger, Turn};
+pub use zones::{Zone, ZoneDesignation, ZoneId, ZoneKind, ZoneShape, Zones};
@@ -0,0 +1,159 @@
+//! Oaths - promises and contracts with deadlines
+//!
+//! An oath binds a swearer to terms ("deliver 20 wood", "defend Elena")
+//! until a deadline, either to another person or to the settlement as a
+//! whole. Every oath ends kept, broken, or released (when the swearer dies
+//! with it still open), and each swearer's record of kept and broken oaths
+//! makes up their reputation.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::EntityId;
+use crate::simulation::resource_zone::ResourceType;
+
+/// Unique identifier for an oath
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OathId(pub u32);
+
+/// What an oath binds the swearer to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OathTerms {
+    /// Bring `amount` of a resource into the stockpile
+    Deliver { resource: ResourceType, amount: u32 },
+    /// Keep someone alive
+    Defend { ward: EntityId },
+}
+
+/// Where an oath stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OathStatus {
+    Sworn,
+    Kept,
+    Broken,
+    /// The swearer died before the oath came due
+    Released,
+}
+
+/// A promise with a deadline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Oath {
+    pub id: OathId,
+    pub swearer: EntityId,
+    /// Who the oath was sworn to (None = the settlement)
+    pub beneficiary: Option<EntityId>,
+    pub terms: OathTerms,
+    pub sworn_tick: u64,
+    pub deadline_tick: u64,
+    /// Stockpile amount of the promised resource when the oath was sworn
+    pub baseline: u32,
+    /// Arbiter who ordered this oath as restitution, if any
+    pub ordered_by: Option<EntityId>,
+    pub status: OathStatus,
+}
+
+impl Oath {
+    pub fn is_sworn(&self) -> bool {
+        self.status == OathStatus::Sworn
+    }
+}
+
+/// Every oath ever sworn
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Oaths {
+    pub oaths: Vec<Oath>,
+    next_oath_id: u32,
+}
+
+impl Oaths {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new oath, with no baseline and no arbiter behind it
+    pub fn swear(
+        &mut self,
+        swearer: EntityId,
+        beneficiary: Option<EntityId>,
+        terms: OathTerms,
+        sworn_tick: u64,
+        deadline_tick: u64,
+    ) -> &mut Oath {
+        let id = OathId(self.next_oath_id);
+        self.next_oath_id += 1;
+        self.oaths.push(Oath {
+            id,
+            swearer,
+            beneficiary,
+            terms,
+            sworn_tick,
+            deadline_tick,
+            baseline: 0,
+            ordered_by: None,
+            status: OathStatus::Sworn,
+        });
+        self.oaths.last_mut().unwrap()
+    }
+
+    pub fn get(&self, id: OathId) -> Option<&Oath> {
+        self.oaths.iter().find(|o| o.id == id)
+    }
+
+    /// Oaths still open
+    pub fn sworn(&self) -> impl Iterator<Item = &Oath> {
+        self.oaths.iter().filter(|o| o.is_sworn())
+    }
+
+    /// Open oaths sworn by someone
+    pub fn sworn_by(&self, swearer: EntityId) -> impl Iterator<Item = &Oath> {
+        self.sworn().filter(move |o| o.swearer == swearer)
+    }
+
+    /// Reputation for keeping one's word (-1.0 to 1.0, 0.0 with no record)
+    pub fn reputation(&self, swearer: EntityId) -> f32 {
+        let (kept, broken) =
+            self.oaths
+                .iter()
+                .filter(|o| o.swearer == swearer)
+                .fold((0, 0), |(kept, broken), o| match o.status {
+                    OathStatus::Kept => (kept + 1, broken),
+                    OathStatus::Broken => (kept, broken + 1),
+                    _ => (kept, broken),
+                });
+        if kept + broken == 0 {
+            0.0
+        } else {
+            (kept as f32 - broken as f32) / (kept + broken) as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reputation_follows_record() {
+        let mut oaths = Oaths::new();
+        let swearer = EntityId::new();
+        let ward = EntityId::new();
+        let a = oaths
+            .swear(swearer, None, OathTerms::Defend { ward }, 0, 100)
+            .id;
+        let b = oaths
+            .swear(swearer, Some(ward), OathTerms::Defend { ward }, 0, 100)
+            .id;
+        oaths.swear(swearer, None, OathTerms::Defend { ward }, 0, 100);
+
+        assert_eq!(oaths.reputation(swearer), 0.0);
+        assert_eq!(oaths.sworn_by(swearer).count(), 3);
+
+        oaths.oaths[a.0 as usize].status = OathStatus::Kept;
+        oaths.oaths[b.0 as usize].status = OathStatus::Kept;
+        assert_eq!(oaths.reputation(swearer), 1.0);
+
+        oaths.oaths[2].status = OathStatus::Broken;
+        assert!((oaths.reputation(swearer) - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(oaths.sworn_by(swearer).count(), 0);
+        assert_eq!(oaths.reputation(ward), 0.0);
+    }
+}
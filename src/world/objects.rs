@@ -3,8 +3,10 @@
 use crate::blueprints::{BlueprintInstance, InstanceId};
 use ahash::AHashMap;
 use glam::Vec2;
+use serde::{Deserialize, Serialize};
 
 /// Storage for all world objects (walls, trees, buildings, etc.)
+#[derive(Serialize, Deserialize)]
 pub struct WorldObjects {
     /// All instances by ID
     instances: AHashMap<InstanceId, BlueprintInstance>,
@@ -0,0 +1,117 @@
+//! Omens - how the settlement's culture reads the sky
+//!
+//! Each culture reads some celestial events as omens (see `data/omens.toml`
+//! and `simulation::omens`). An omen holds for a few days: settlers dwell on
+//! it, and while it holds it may keep them from fighting or slow their work.
+//! A dire omen presses the leader to answer it with a rite; one left
+//! unanswered when it passes costs them the settlers' trust.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::astronomy::CelestialEvent;
+
+/// Culture a world starts with when none is chosen
+pub const DEFAULT_CULTURE: &str = "hearthfolk";
+
+/// An omen the settlement has seen and is living under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Omen {
+    pub event: CelestialEvent,
+    /// What the culture takes it to mean
+    pub reading: String,
+    /// Day it was seen
+    pub seen_day: u32,
+    /// Day it passes
+    pub until_day: u32,
+    /// Settlers will not fight while it holds
+    pub refuse_fighting: bool,
+    /// Factor on the pace of work while it holds
+    pub work_pace: f32,
+    /// The rite the leader is pressed to hold, until it is held
+    pub awaits_rite: Option<String>,
+}
+
+/// The settlement's culture and the omens it is living under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Omens {
+    /// Name of the culture whose table reads the sky (see `data/omens.toml`)
+    pub culture: String,
+    /// The religion the settlement was founded in, if it inherited one
+    #[serde(default)]
+    pub faith: Option<String>,
+    pub active: Vec<Omen>,
+}
+
+impl Default for Omens {
+    fn default() -> Self {
+        Self {
+            culture: DEFAULT_CULTURE.to_string(),
+            faith: None,
+            active: Vec::new(),
+        }
+    }
+}
+
+impl Omens {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether an omen of this event is already held
+    pub fn holds(&self, event: CelestialEvent) -> bool {
+        self.active.iter().any(|omen| omen.event == event)
+    }
+
+    /// The omen keeping settlers from fighting, if any
+    pub fn forbidding_fighting(&self) -> Option<&Omen> {
+        self.active.iter().find(|omen| omen.refuse_fighting)
+    }
+
+    /// Pace of work under every omen held (1.0 with none)
+    pub fn work_pace(&self) -> f32 {
+        self.active.iter().map(|omen| omen.work_pace).product()
+    }
+
+    /// Omens still waiting for the leader's rite
+    pub fn awaiting_rite(&self) -> impl Iterator<Item = &Omen> {
+        self.active.iter().filter(|omen| omen.awaits_rite.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_held_omens_combine() {
+        let mut omens = Omens::new();
+        assert_eq!(omens.work_pace(), 1.0);
+        omens.active.push(Omen {
+            event: CelestialEvent::BloodEclipse,
+            reading: "blood spilt now curses the spiller".into(),
+            seen_day: 10,
+            until_day: 13,
+            refuse_fighting: true,
+            work_pace: 0.8,
+            awaits_rite: Some("a rite of cleansing".into()),
+        });
+        omens.active.push(Omen {
+            event: CelestialEvent::SilverEclipse,
+            reading: "the harvest-mother turns away".into(),
+            seen_day: 10,
+            until_day: 12,
+            refuse_fighting: false,
+            work_pace: 0.5,
+            awaits_rite: None,
+        });
+
+        assert!(omens.holds(CelestialEvent::BloodEclipse));
+        assert!(!omens.holds(CelestialEvent::DoubleEclipse));
+        assert_eq!(
+            omens.forbidding_fighting().unwrap().event,
+            CelestialEvent::BloodEclipse
+        );
+        assert!((omens.work_pace() - 0.4).abs() < 1e-6);
+        assert_eq!(omens.awaiting_rite().count(), 1);
+    }
+}
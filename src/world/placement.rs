@@ -4,6 +4,7 @@
 //! from the Python worldgen pipeline. The placements are stored as JSON
 //! files containing positioned blueprint instances with metadata.
 
+use crate::world::zones::ZoneDesignation;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -17,6 +18,9 @@ pub struct PlacementFile {
     pub metadata: Option<PlacementMetadata>,
     /// List of placed objects
     pub placements: Vec<Placement>,
+    /// Designated zones (stockpiles, farms, dormitories, no-entry areas)
+    #[serde(default)]
+    pub zones: Vec<ZoneDesignation>,
 }
 
 /// Optional metadata for the placement file
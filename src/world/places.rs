@@ -0,0 +1,128 @@
+//! Places - the names settlers give the settlement and the ground around it
+//!
+//! Named automatically as they come to matter (see `simulation::places`):
+//! the settlement once someone lives there, a district for each zone, and
+//! a battle site where a hard fight was fought. Players and the LLM refer
+//! to them by name, and the map labels them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{Tick, Vec2};
+use crate::world::zones::ZoneId;
+
+/// What sort of place a name belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlaceKind {
+    /// The settlement itself
+    Settlement,
+    /// The district around a zone
+    District(ZoneId),
+    /// Where a hard fight was fought
+    BattleSite,
+}
+
+impl PlaceKind {
+    /// How the kind of place is described to the player and the LLM
+    pub fn describe(self) -> &'static str {
+        match self {
+            PlaceKind::Settlement => "settlement",
+            PlaceKind::District(_) => "district",
+            PlaceKind::BattleSite => "battle site",
+        }
+    }
+}
+
+/// A named place
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Place {
+    pub name: String,
+    pub kind: PlaceKind,
+    pub position: Vec2,
+    /// When it was named
+    pub named: Tick,
+}
+
+/// Every place named so far
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Places {
+    pub places: Vec<Place>,
+}
+
+impl Places {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The settlement, once it is named
+    pub fn settlement(&self) -> Option<&Place> {
+        self.places.iter().find(|p| p.kind == PlaceKind::Settlement)
+    }
+
+    /// The district named for a zone
+    pub fn district(&self, zone: ZoneId) -> Option<&Place> {
+        self.places
+            .iter()
+            .find(|p| p.kind == PlaceKind::District(zone))
+    }
+
+    /// A place by name (case-insensitive; "the" before it is ignored)
+    pub fn find(&self, name: &str) -> Option<&Place> {
+        let name = name.trim().to_lowercase();
+        let name = name.strip_prefix("the ").unwrap_or(&name);
+        self.places.iter().find(|p| p.name.to_lowercase() == name)
+    }
+
+    /// The first place whose name appears in `text` (case-insensitive),
+    /// longest names first so "Redford Field" wins over "Redford"
+    pub fn mentioned_in(&self, text: &str) -> Option<&Place> {
+        let text = text.to_lowercase();
+        self.places
+            .iter()
+            .filter(|p| text.contains(&p.name.to_lowercase()))
+            .max_by_key(|p| p.name.len())
+    }
+
+    /// Whether a name is already taken
+    pub fn is_taken(&self, name: &str) -> bool {
+        self.find(name).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Place> {
+        self.places.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_places_are_found_by_name_or_mention() {
+        let mut places = Places::new();
+        for (name, kind) in [
+            ("Redford", PlaceKind::Settlement),
+            ("Redford Field", PlaceKind::BattleSite),
+        ] {
+            places.places.push(Place {
+                name: name.into(),
+                kind,
+                position: Vec2::new(1.0, 2.0),
+                named: 0,
+            });
+        }
+        assert_eq!(places.settlement().unwrap().name, "Redford");
+        assert_eq!(
+            places.find("the redford field").unwrap().kind,
+            PlaceKind::BattleSite
+        );
+        assert_eq!(
+            places
+                .mentioned_in("send them to Redford Field")
+                .unwrap()
+                .name,
+            "Redford Field"
+        );
+        assert!(places.mentioned_in("the east gate").is_none());
+        assert!(places.is_taken("REDFORD"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Requests - what settlers ask of the player
+//!
+//! A settler with something on their mind (a craft with nowhere to practise
+//! it, no home, a friend held captive) asks the player for it. A request
+//! stays open until it is granted, lapses unanswered at its deadline, or is
+//! withdrawn when the asker dies or leaves. How their requests were answered
+//! moves settlers' mood, loyalty, and opinion of the settlement (see
+//! `simulation::requests`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::city::building::BuildingType;
+use crate::core::types::{EntityId, Tick};
+
+/// Unique identifier for a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RequestId(pub u32);
+
+/// What a settler is asking for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Wish {
+    /// A finished building of this kind in the settlement
+    Building(BuildingType),
+    /// A house to live in
+    Home,
+    /// Someone close to them freed from captivity
+    Freed { captive: EntityId },
+}
+
+/// Where a request stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RequestStatus {
+    Open,
+    Granted,
+    /// The deadline passed with the wish unmet
+    Lapsed,
+    /// The asker died or left, or what they asked became impossible
+    Withdrawn,
+}
+
+/// Something a settler has asked the player for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Request {
+    pub id: RequestId,
+    pub asker: EntityId,
+    pub wish: Wish,
+    pub made_tick: Tick,
+    pub deadline_tick: Tick,
+    pub status: RequestStatus,
+    /// When it was granted, lapsed, or withdrawn
+    pub closed_tick: Option<Tick>,
+}
+
+impl Request {
+    pub fn is_open(&self) -> bool {
+        self.status == RequestStatus::Open
+    }
+}
+
+/// Every request ever made
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Requests {
+    pub requests: Vec<Request>,
+    next_request_id: u32,
+}
+
+impl Requests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new request
+    pub fn make(
+        &mut self,
+        asker: EntityId,
+        wish: Wish,
+        made_tick: Tick,
+        deadline_tick: Tick,
+    ) -> RequestId {
+        let id = RequestId(self.next_request_id);
+        self.next_request_id += 1;
+        self.requests.push(Request {
+            id,
+            asker,
+            wish,
+            made_tick,
+            deadline_tick,
+            status: RequestStatus::Open,
+            closed_tick: None,
+        });
+        id
+    }
+
+    pub fn get(&self, id: RequestId) -> Option<&Request> {
+        self.requests.iter().find(|r| r.id == id)
+    }
+
+    /// Close an open request; false if it was not open
+    pub fn close(&mut self, id: RequestId, status: RequestStatus, tick: Tick) -> bool {
+        match self.requests.iter_mut().find(|r| r.id == id) {
+            Some(request) if request.is_open() => {
+                request.status = status;
+                request.closed_tick = Some(tick);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Requests still awaiting an answer
+    pub fn open(&self) -> impl Iterator<Item = &Request> {
+        self.requests.iter().filter(|r| r.is_open())
+    }
+
+    /// Requests someone has made, oldest first
+    pub fn by(&self, asker: EntityId) -> impl Iterator<Item = &Request> {
+        self.requests.iter().filter(move |r| r.asker == asker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_close_once() {
+        let mut requests = Requests::new();
+        let asker = EntityId::new();
+        let home = requests.make(asker, Wish::Home, 0, 100);
+        let shrine = requests.make(asker, Wish::Building(BuildingType::Shrine), 0, 100);
+        assert_eq!(requests.open().count(), 2);
+
+        assert!(requests.close(home, RequestStatus::Granted, 50));
+        assert!(!requests.close(home, RequestStatus::Lapsed, 100));
+        assert_eq!(requests.get(home).unwrap().status, RequestStatus::Granted);
+        assert_eq!(requests.get(home).unwrap().closed_tick, Some(50));
+        assert_eq!(requests.open().next().unwrap().id, shrine);
+        assert_eq!(requests.by(asker).count(), 2);
+    }
+}
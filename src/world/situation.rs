@@ -0,0 +1,102 @@
+//! Situation log - what has happened since the player last looked
+//!
+//! Keeps the settlement as it stood at the last situation report (who was
+//! alive, how long the chronicle was) and a tally of the fighting since, so
+//! a report can be drawn up after a load or a long fast-forward.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{EntityId, Tick, Vec2};
+
+/// Ticks without a blow after which a fight is over
+pub const FIGHT_LULL_TICKS: Tick = 50;
+
+/// A spell of fighting: blows struck with no long lull between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fight {
+    pub started: Tick,
+    pub last_blow: Tick,
+    pub blows: u32,
+    /// Where the first blow we know the place of fell
+    #[serde(default)]
+    pub place: Option<Vec2>,
+}
+
+/// The settlement at the last report, and the fighting since
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SituationLog {
+    /// Tick of the last report
+    pub since: Tick,
+    /// Humans alive at the last report
+    pub living: Vec<EntityId>,
+    /// Chronicle length at the last report
+    pub chronicle_len: usize,
+    /// Fights since the last report, oldest first
+    pub fights: Vec<Fight>,
+}
+
+impl SituationLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tally blows struck this tick, joining the current fight unless it has lulled
+    pub fn note_blows(&mut self, tick: Tick, blows: u32) {
+        if blows == 0 {
+            return;
+        }
+        match self.fights.last_mut() {
+            Some(fight) if tick.saturating_sub(fight.last_blow) <= FIGHT_LULL_TICKS => {
+                fight.last_blow = tick;
+                fight.blows += blows;
+            }
+            _ => self.fights.push(Fight {
+                started: tick,
+                last_blow: tick,
+                blows,
+                place: None,
+            }),
+        }
+    }
+
+    /// Note where a blow of the current fight fell, if its place is not yet known
+    pub fn note_place(&mut self, at: Vec2) {
+        if let Some(fight) = self.fights.last_mut() {
+            fight.place.get_or_insert(at);
+        }
+    }
+
+    /// Start afresh from the settlement as it stands now
+    pub fn mark(&mut self, tick: Tick, living: Vec<EntityId>, chronicle_len: usize) {
+        self.since = tick;
+        self.living = living;
+        self.chronicle_len = chronicle_len;
+        self.fights.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blows_group_into_fights() {
+        let mut log = SituationLog::new();
+        log.note_blows(10, 2);
+        log.note_place(Vec2::new(5.0, 5.0));
+        log.note_blows(20, 0);
+        log.note_blows(30, 1);
+        log.note_place(Vec2::new(9.0, 9.0));
+        log.note_blows(30 + FIGHT_LULL_TICKS + 1, 4);
+        assert_eq!(log.fights.len(), 2);
+        assert_eq!(log.fights[0].blows, 3);
+        assert_eq!(log.fights[0].last_blow, 30);
+        assert_eq!(log.fights[0].place.map(|p| (p.x, p.y)), Some((5.0, 5.0)));
+        assert_eq!(log.fights[1].blows, 4);
+        assert!(log.fights[1].place.is_none());
+
+        log.mark(200, Vec::new(), 0);
+        assert!(log.fights.is_empty());
+        assert_eq!(log.since, 200);
+    }
+}
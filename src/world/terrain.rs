@@ -0,0 +1,169 @@
+//! Local terrain features - streams, gullies, and ledges
+//!
+//! Features are laid down as straight bands across the local map. Every
+//! cell a feature covers is blocked in `BlockedCells`; the only way across
+//! is a constructed crossing (see `city::crossing`).
+
+use crate::core::types::Vec2;
+use crate::world::BlockedCells;
+use serde::{Deserialize, Serialize};
+
+/// Unique identifier for a terrain feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FeatureId(pub u32);
+
+/// Kind of terrain obstacle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TerrainFeatureKind {
+    /// Running water, crossed by a bridge
+    Stream,
+    /// Dry cut in the ground, crossed by a bridge
+    Gully,
+    /// Sudden rise in elevation, climbed by a ramp
+    Ledge,
+}
+
+/// A terrain obstacle on the local map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerrainFeature {
+    pub id: FeatureId,
+    pub kind: TerrainFeatureKind,
+    /// Centre line of the feature
+    pub from: Vec2,
+    pub to: Vec2,
+    /// Distance across the feature (what a bridge must span)
+    pub width: f32,
+    /// Depth of a stream or gully, or height of a ledge
+    pub elevation: f32,
+    /// Blocked cells the feature covers
+    pub cells: Vec<(i32, i32)>,
+    /// Grid cell size the cells were laid out on
+    pub cell_size: f32,
+}
+
+impl TerrainFeature {
+    /// Unit direction along the feature's centre line
+    pub fn direction(&self) -> Vec2 {
+        (self.to - self.from).normalize()
+    }
+
+    /// Cells within `reach` of a line crossing the feature at `position`
+    pub fn cells_across(&self, position: Vec2, reach: f32) -> Vec<(i32, i32)> {
+        let along = self.direction();
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&(x, y)| {
+                let center = Vec2::new(
+                    (x as f32 + 0.5) * self.cell_size,
+                    (y as f32 + 0.5) * self.cell_size,
+                );
+                let offset = center - position;
+                (offset.x * along.x + offset.y * along.y).abs() <= reach
+            })
+            .collect()
+    }
+}
+
+/// All terrain features on the local map
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TerrainFeatures {
+    pub features: Vec<TerrainFeature>,
+    next_id: u32,
+}
+
+impl TerrainFeatures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lay down a feature and block every cell it covers
+    pub fn add(
+        &mut self,
+        kind: TerrainFeatureKind,
+        from: Vec2,
+        to: Vec2,
+        width: f32,
+        elevation: f32,
+        blocked: &mut BlockedCells,
+    ) -> FeatureId {
+        let id = FeatureId(self.next_id);
+        self.next_id += 1;
+
+        let length = from.distance(&to);
+        let along = (to - from).normalize();
+        let across = Vec2::new(-along.y, along.x);
+        let step = blocked.cell_size() * 0.5;
+        let along_steps = (length / step).ceil().max(1.0) as i32;
+        let across_steps = (width / step).ceil().max(1.0) as i32;
+
+        let mut cells = Vec::new();
+        for a in 0..=along_steps {
+            for c in 0..=across_steps {
+                let offset = c as f32 * step - width * 0.5;
+                let pos = from + along * (a as f32 * step).min(length) + across * offset;
+                let cell = blocked.world_to_cell(glam::Vec2::new(pos.x, pos.y));
+                if !cells.contains(&cell) {
+                    cells.push(cell);
+                }
+            }
+        }
+        for &(x, y) in &cells {
+            blocked.block(x, y);
+        }
+
+        self.features.push(TerrainFeature {
+            id,
+            kind,
+            from,
+            to,
+            width,
+            elevation,
+            cells,
+            cell_size: blocked.cell_size(),
+        });
+        id
+    }
+
+    pub fn get(&self, id: FeatureId) -> Option<&TerrainFeature> {
+        self.features.iter().find(|f| f.id == id)
+    }
+
+    /// Feature covering a position, if any
+    pub fn feature_at(&self, pos: Vec2, blocked: &BlockedCells) -> Option<FeatureId> {
+        let cell = blocked.world_to_cell(glam::Vec2::new(pos.x, pos.y));
+        self.features
+            .iter()
+            .find(|f| f.cells.contains(&cell))
+            .map(|f| f.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_blocks_its_cells() {
+        let mut blocked = BlockedCells::new();
+        let mut features = TerrainFeatures::new();
+
+        let stream = features.add(
+            TerrainFeatureKind::Stream,
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 20.0),
+            3.0,
+            1.0,
+            &mut blocked,
+        );
+
+        assert!(blocked.is_position_blocked(glam::Vec2::new(10.0, 10.0)));
+        assert!(blocked.is_position_blocked(glam::Vec2::new(9.0, 10.0)));
+        assert!(!blocked.is_position_blocked(glam::Vec2::new(5.0, 10.0)));
+        assert_eq!(
+            features.feature_at(Vec2::new(10.5, 5.0), &blocked),
+            Some(stream)
+        );
+        assert_eq!(features.feature_at(Vec2::new(0.0, 5.0), &blocked), None);
+    }
+}
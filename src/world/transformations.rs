@@ -0,0 +1,67 @@
+//! Transformations - rare turns in a life that reshape what someone knows
+//!
+//! A head wound, coming back from a crisis of despair, or giving up on a
+//! dream of mastery can each, now and then, change a settler for good:
+//! what they had learned fades or is lost, and what drives them shifts
+//! (see `data/transformations.toml` and `simulation::transformations`).
+//! Turns are noted as they happen and weighed at the end of the day.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{EntityId, Tick};
+
+/// What can set off a transformation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransformationTrigger {
+    /// A wound to the head that did not kill
+    HeadWound,
+    /// Reached and brought back from a crisis of despair
+    SpiritualCrisis,
+    /// A dream of mastering a craft gone stale
+    Retraining,
+}
+
+/// A turn in someone's life, waiting to be weighed at the end of the day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Turn {
+    pub id: EntityId,
+    pub trigger: TransformationTrigger,
+    pub tick: Tick,
+}
+
+/// A transformation someone has undergone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transformed {
+    pub id: EntityId,
+    /// Name of the transformation
+    pub name: String,
+    pub tick: Tick,
+}
+
+/// Turns not yet weighed, and every transformation so far
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Transformations {
+    pub pending: Vec<Turn>,
+    pub undergone: Vec<Transformed>,
+}
+
+impl Transformations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note a turn in someone's life
+    pub fn note(&mut self, id: EntityId, trigger: TransformationTrigger, tick: Tick) {
+        self.pending.push(Turn { id, trigger, tick });
+    }
+
+    /// Whether someone has undergone the transformation of this name
+    pub fn has_undergone(&self, id: EntityId, name: &str) -> bool {
+        self.undergone.iter().any(|t| t.id == id && t.name == name)
+    }
+
+    /// The transformations someone has undergone, oldest first
+    pub fn of(&self, id: EntityId) -> impl Iterator<Item = &Transformed> {
+        self.undergone.iter().filter(move |t| t.id == id)
+    }
+}
@@ -0,0 +1,296 @@
+//! Player-designated zones - stockpiles, farms, dormitories, graveyards,
+//! no-entry areas
+//!
+//! A zone is a rectangle or hex the player marks out for a purpose. Zones
+//! steer behavior rather than place anything: haulers fetch their loads from
+//! the nearest stockpile zone, the homeless bed down in the nearest
+//! dormitory, the dead are carried to a graveyard, and no-entry zones are restricted cells in `BlockedCells`, so
+//! pathfinding routes around them. Zones are saved with the world and can be
+//! read from (and written back to) a placement file.
+
+use crate::core::types::Vec2;
+use crate::world::BlockedCells;
+use serde::{Deserialize, Serialize};
+
+/// Unique identifier for a zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ZoneId(pub u32);
+
+/// What a zone is designated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneKind {
+    /// Where hauled goods are picked up from
+    Stockpile,
+    /// Land set aside for crops
+    Farm,
+    /// Where those without a house sleep
+    Dormitory,
+    /// Nobody walks here
+    NoEntry,
+    /// Where the dead are buried
+    Graveyard,
+}
+
+/// The area a zone covers
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+pub enum ZoneShape {
+    /// Axis-aligned rectangle between two corners
+    Rect { min: Vec2, max: Vec2 },
+    /// Flat-topped hexagon; `radius` is centre to corner
+    Hex { center: Vec2, radius: f32 },
+}
+
+impl ZoneShape {
+    pub fn contains(&self, pos: Vec2) -> bool {
+        match *self {
+            ZoneShape::Rect { min, max } => {
+                pos.x >= min.x && pos.x <= max.x && pos.y >= min.y && pos.y <= max.y
+            }
+            ZoneShape::Hex { center, radius } => {
+                let dx = (pos.x - center.x).abs();
+                let dy = (pos.y - center.y).abs();
+                let sqrt3 = 3f32.sqrt();
+                dy <= sqrt3 / 2.0 * radius && sqrt3 * dx + dy <= sqrt3 * radius
+            }
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        match *self {
+            ZoneShape::Rect { min, max } => Vec2::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0),
+            ZoneShape::Hex { center, .. } => center,
+        }
+    }
+
+    /// Corners of the bounding box (min, max)
+    fn bounds(&self) -> (Vec2, Vec2) {
+        match *self {
+            ZoneShape::Rect { min, max } => (min, max),
+            ZoneShape::Hex { center, radius } => (
+                Vec2::new(center.x - radius, center.y - radius),
+                Vec2::new(center.x + radius, center.y + radius),
+            ),
+        }
+    }
+
+    /// Grid cells whose centres lie in the shape
+    pub fn cells(&self, blocked: &BlockedCells) -> Vec<(i32, i32)> {
+        let size = blocked.cell_size();
+        let (min, max) = self.bounds();
+        let (start_x, start_y) = blocked.world_to_cell(glam::Vec2::new(min.x, min.y));
+        let (end_x, end_y) = blocked.world_to_cell(glam::Vec2::new(max.x, max.y));
+        let mut cells = Vec::new();
+        for y in start_y..=end_y {
+            for x in start_x..=end_x {
+                let center = Vec2::new((x as f32 + 0.5) * size, (y as f32 + 0.5) * size);
+                if self.contains(center) {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// A zone as written in a placement file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneDesignation {
+    pub kind: ZoneKind,
+    #[serde(flatten)]
+    pub shape: ZoneShape,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// A designated zone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Zone {
+    pub id: ZoneId,
+    pub kind: ZoneKind,
+    pub shape: ZoneShape,
+    pub name: Option<String>,
+}
+
+/// Every zone the player has designated
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Zones {
+    zones: Vec<Zone>,
+    next_id: u32,
+}
+
+impl Zones {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Designate a zone, restricting its cells if it is no-entry
+    pub fn designate(
+        &mut self,
+        designation: ZoneDesignation,
+        blocked: &mut BlockedCells,
+    ) -> ZoneId {
+        let id = ZoneId(self.next_id);
+        self.next_id += 1;
+        let restricts = designation.kind == ZoneKind::NoEntry;
+        self.zones.push(Zone {
+            id,
+            kind: designation.kind,
+            shape: designation.shape,
+            name: designation.name,
+        });
+        if restricts {
+            self.restrict(blocked);
+        }
+        id
+    }
+
+    /// Lift a zone, reopening its cells if it was no-entry
+    pub fn remove(&mut self, id: ZoneId, blocked: &mut BlockedCells) -> Option<Zone> {
+        let index = self.zones.iter().position(|zone| zone.id == id)?;
+        let zone = self.zones.remove(index);
+        if zone.kind == ZoneKind::NoEntry {
+            self.restrict(blocked);
+        }
+        Some(zone)
+    }
+
+    /// Restrict exactly the cells of the no-entry zones
+    pub fn restrict(&self, blocked: &mut BlockedCells) {
+        let cells: Vec<(i32, i32)> = self
+            .of_kind(ZoneKind::NoEntry)
+            .flat_map(|zone| zone.shape.cells(blocked))
+            .collect();
+        blocked.set_restricted(cells);
+    }
+
+    pub fn get(&self, id: ZoneId) -> Option<&Zone> {
+        self.zones.iter().find(|zone| zone.id == id)
+    }
+
+    /// Zones in the order they were designated
+    pub fn iter(&self) -> impl Iterator<Item = &Zone> {
+        self.zones.iter()
+    }
+
+    pub fn of_kind(&self, kind: ZoneKind) -> impl Iterator<Item = &Zone> {
+        self.zones.iter().filter(move |zone| zone.kind == kind)
+    }
+
+    /// The first zone covering a position
+    pub fn zone_at(&self, pos: Vec2) -> Option<&Zone> {
+        self.zones.iter().find(|zone| zone.shape.contains(pos))
+    }
+
+    /// Whether a zone of this kind covers a position
+    pub fn contains(&self, kind: ZoneKind, pos: Vec2) -> bool {
+        self.of_kind(kind).any(|zone| zone.shape.contains(pos))
+    }
+
+    /// The zone of this kind whose centre is closest to a position
+    pub fn nearest(&self, kind: ZoneKind, from: Vec2) -> Option<&Zone> {
+        self.of_kind(kind).min_by(|a, b| {
+            from.distance(&a.shape.center())
+                .total_cmp(&from.distance(&b.shape.center()))
+        })
+    }
+
+    /// The zones as they would be written to a placement file
+    pub fn designations(&self) -> Vec<ZoneDesignation> {
+        self.zones
+            .iter()
+            .map(|zone| ZoneDesignation {
+                kind: zone.kind,
+                shape: zone.shape,
+                name: zone.name.clone(),
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(kind: ZoneKind, min: (f32, f32), max: (f32, f32)) -> ZoneDesignation {
+        ZoneDesignation {
+            kind,
+            shape: ZoneShape::Rect {
+                min: Vec2::new(min.0, min.1),
+                max: Vec2::new(max.0, max.1),
+            },
+            name: None,
+        }
+    }
+
+    #[test]
+    fn test_zone_shapes_and_lookup() {
+        let mut blocked = BlockedCells::new();
+        let mut zones = Zones::new();
+        zones.designate(
+            rect(ZoneKind::Stockpile, (0.0, 0.0), (4.0, 4.0)),
+            &mut blocked,
+        );
+        let far = zones.designate(
+            rect(ZoneKind::Stockpile, (40.0, 0.0), (44.0, 4.0)),
+            &mut blocked,
+        );
+        let hex = ZoneShape::Hex {
+            center: Vec2::new(20.0, 20.0),
+            radius: 5.0,
+        };
+        zones.designate(
+            ZoneDesignation {
+                kind: ZoneKind::Dormitory,
+                shape: hex,
+                name: Some("Longhouse".into()),
+            },
+            &mut blocked,
+        );
+
+        assert!(hex.contains(Vec2::new(24.9, 20.0)));
+        assert!(!hex.contains(Vec2::new(20.0, 24.9))); // Past the flat top
+        assert_eq!(
+            zones
+                .nearest(ZoneKind::Stockpile, Vec2::new(35.0, 0.0))
+                .unwrap()
+                .id,
+            far
+        );
+        assert!(zones.contains(ZoneKind::Dormitory, Vec2::new(21.0, 19.0)));
+        assert_eq!(
+            zones.zone_at(Vec2::new(2.0, 2.0)).unwrap().kind,
+            ZoneKind::Stockpile
+        );
+        assert!(zones.nearest(ZoneKind::Farm, Vec2::new(0.0, 0.0)).is_none());
+        // Only no-entry zones touch pathfinding
+        assert!(blocked.is_empty());
+    }
+
+    #[test]
+    fn test_no_entry_zones_restrict_cells_until_lifted() {
+        let mut blocked = BlockedCells::new();
+        blocked.block(2, 2);
+        let mut zones = Zones::new();
+        let no_entry = zones.designate(
+            rect(ZoneKind::NoEntry, (0.0, 0.0), (4.0, 4.0)),
+            &mut blocked,
+        );
+        assert!(blocked.is_blocked(3, 3));
+        assert!(!blocked.is_blocked(5, 5));
+
+        assert!(zones.remove(no_entry, &mut blocked).is_some());
+        assert!(!blocked.is_blocked(3, 3));
+        assert!(blocked.is_blocked(2, 2));
+        assert!(zones.is_empty());
+    }
+}
@@ -0,0 +1,470 @@
+//! Instantiation - a polity of the aggregate history founds a playable site
+//!
+//! The aggregate history deals in regions and polities; the live simulation
+//! in settlers, buildings, and stockpiles. `instantiate` is the bridge: given
+//! the region a settlement is founded in, the polity founding it, and the
+//! `FoundingModifiers` of the day it was founded, it lays out a starting
+//! `World` that matches the history.
+//!
+//! - Settlers are of the polity's species, as many as the region's fitness
+//!   for them and the founding's population multiplier give, capped by what
+//!   the region holds.
+//! - Houses shelter them all; farms feed them, more of them on arable land.
+//! - A prepared or harvest-minded founding builds a granary, a defensive
+//!   one walls and a gate, a martial one a watchtower, a pious or blessed
+//!   one a shrine, and a trading one a market square.
+//! - Stockpiles scale with the founding's stockpile efficiency and harvest
+//!   storage, and raw materials with what the region yields.
+//! - Food and resource zones follow the region's terrain and resources.
+//!
+//! `instantiate_from_history` founds in a region of a `WorldSnapshot`, under
+//! whoever holds it, and has the settlement inherit their faith.
+
+use thiserror::Error;
+
+use crate::aggregate::output::WorldSnapshot;
+use crate::aggregate::polity::Polity;
+use crate::aggregate::region::{Region, ResourceType as RegionResource, Terrain};
+use crate::city::building::{BuildingState, BuildingType};
+use crate::core::astronomy::{FoundingModifiers, YEAR_LENGTH};
+use crate::core::types::{Species, Vec2};
+use crate::ecs::world::{Abundance, World};
+use crate::simulation::omens::inherit_faith;
+use crate::simulation::resource_zone::{ResourceType, ResourceZone};
+
+/// Settlers a founding sends out before fitness and founding modifiers
+pub const BASE_SETTLERS: f32 = 12.0;
+
+/// Fewest settlers a founding sends out
+pub const MIN_SETTLERS: usize = 4;
+
+/// Most settlers a founding sends out
+pub const MAX_SETTLERS: usize = 40;
+
+/// Settlers one worked farm feeds
+pub const SETTLERS_PER_FARM: usize = 6;
+
+/// Workers at each founding farm
+pub const FARM_WORKERS: u32 = 2;
+
+/// Food capacity of a founded stockpile before its efficiency
+pub const BASE_FOOD_CAPACITY: f32 = 2000.0;
+
+/// Food capacity a granary adds
+pub const GRANARY_CAPACITY: f32 = 2000.0;
+
+/// Food the founders bring per settler
+pub const FOOD_PER_SETTLER: f32 = 100.0;
+
+/// Wood and stone the founders bring before what the region yields
+pub const BASE_MATERIALS: f32 = 100.0;
+
+/// Where the settlement is laid out around
+pub const SITE_CENTRE: Vec2 = Vec2 { x: 50.0, y: 50.0 };
+
+/// Why a settlement could not be founded
+#[derive(Error, Debug)]
+pub enum InstantiateError {
+    #[error("The history has no region {0}")]
+    UnknownRegion(u32),
+    #[error("No polity holds {0}")]
+    Unheld(String),
+    #[error("{region} is held by another polity than {polity}")]
+    HeldByAnother { region: String, polity: String },
+    #[error("{0} has fallen and founds nothing")]
+    Fallen(String),
+    #[error("{0:?} settlements cannot be played")]
+    UnplayableSpecies(Species),
+}
+
+/// Found a settlement of `polity` in `region` under `modifiers`
+pub fn instantiate(
+    region: &Region,
+    polity: &Polity,
+    modifiers: &FoundingModifiers,
+    seed: u64,
+) -> Result<World, InstantiateError> {
+    if !polity.alive {
+        return Err(InstantiateError::Fallen(polity.name.clone()));
+    }
+    if region.controller.is_some_and(|c| c != polity.id.0) {
+        return Err(InstantiateError::HeldByAnother {
+            region: region.name.clone(),
+            polity: polity.name.clone(),
+        });
+    }
+    if !matches!(
+        polity.species,
+        Species::Human | Species::Dwarf | Species::Elf | Species::Orc
+    ) {
+        return Err(InstantiateError::UnplayableSpecies(polity.species));
+    }
+
+    let mut world = World::with_seed(seed);
+    let settlers = settlers(region, polity, modifiers);
+
+    let houses = settlers.div_ceil(BuildingType::House.housing_capacity() as usize);
+    for i in 0..houses {
+        let at = Vec2::new(
+            SITE_CENTRE.x - 15.0 + (i % 4) as f32 * 10.0,
+            SITE_CENTRE.y - 10.0 + (i / 4) as f32 * 10.0,
+        );
+        build(&mut world, BuildingType::House, at);
+    }
+    for i in 0..farms(region, modifiers, settlers) {
+        let at = Vec2::new(SITE_CENTRE.x - 20.0 + i as f32 * 12.0, SITE_CENTRE.y + 30.0);
+        let idx = build(&mut world, BuildingType::Farm, at);
+        world.buildings.start_production(idx, "farm_food".into());
+        world.buildings.production_workers[idx] = FARM_WORKERS;
+    }
+
+    let granary = modifiers.preparation_trait || modifiers.harvest_storage > 0.0;
+    let civic = [
+        (granary, BuildingType::Granary),
+        (
+            modifiers.blessed || modifiers.theocratic_tendency > 0.0 || polity.religion.is_some(),
+            BuildingType::Shrine,
+        ),
+        (
+            modifiers.trade_infrastructure > 0.0,
+            BuildingType::MarketSquare,
+        ),
+        (modifiers.martial_culture > 0.0, BuildingType::Watchtower),
+    ];
+    for (i, (_, building)) in civic.iter().filter(|(wanted, _)| *wanted).enumerate() {
+        let at = Vec2::new(SITE_CENTRE.x - 15.0 + i as f32 * 10.0, SITE_CENTRE.y - 25.0);
+        build(&mut world, *building, at);
+    }
+    if modifiers.siege_mentality || modifiers.defensive_weight > 0.0 {
+        for (dx, dy) in [(-35.0, -35.0), (35.0, -35.0), (-35.0, 45.0), (35.0, 45.0)] {
+            build(
+                &mut world,
+                BuildingType::Wall,
+                Vec2::new(SITE_CENTRE.x + dx, SITE_CENTRE.y + dy),
+            );
+        }
+        build(
+            &mut world,
+            BuildingType::Gate,
+            Vec2::new(SITE_CENTRE.x, SITE_CENTRE.y - 35.0),
+        );
+    }
+
+    stock(&mut world, region, modifiers, settlers, granary);
+    lay_zones(&mut world, region);
+
+    for n in 0..settlers {
+        settle(&mut world, polity.species, n);
+    }
+    if polity.species == Species::Human {
+        for i in world.humans.iter_living().collect::<Vec<_>>() {
+            let values = &mut world.humans.values[i];
+            values.piety = (values.piety + modifiers.theocratic_tendency).clamp(0.0, 1.0);
+            values.honor = (values.honor + modifiers.martial_culture).clamp(0.0, 1.0);
+        }
+    }
+
+    let tick = world.current_tick;
+    world.legacy.record(
+        tick,
+        format!(
+            "{} settlers of {} founded a village in {}",
+            settlers, polity.name, region.name
+        ),
+    );
+    if !modifiers.flavor_text.is_empty() {
+        world.legacy.record(tick, modifiers.flavor_text.clone());
+    }
+    Ok(world)
+}
+
+/// Found a settlement in region `region` of the history, under whoever holds
+/// it, in their faith
+pub fn instantiate_from_history(
+    snapshot: &WorldSnapshot,
+    region: u32,
+    modifiers: &FoundingModifiers,
+    seed: u64,
+) -> Result<World, InstantiateError> {
+    let site = snapshot
+        .regions
+        .iter()
+        .find(|r| r.id == region)
+        .ok_or(InstantiateError::UnknownRegion(region))?;
+    let polity = site
+        .controller
+        .and_then(|c| snapshot.polities.iter().find(|p| p.id.0 == c))
+        .ok_or_else(|| InstantiateError::Unheld(site.name.clone()))?;
+
+    let mut world = instantiate(site, polity, modifiers, seed)?;
+    if let Some(faith) = snapshot.settlement_faith(region) {
+        inherit_faith(&mut world, &faith);
+    }
+    Ok(world)
+}
+
+/// How many settlers a founding sends out
+fn settlers(region: &Region, polity: &Polity, modifiers: &FoundingModifiers) -> usize {
+    let fitness = region.fitness.get(&polity.species).copied().unwrap_or(0.5);
+    let settlers = (BASE_SETTLERS * modifiers.initial_population_mult * (0.5 + fitness)).round();
+    (settlers.max(0.0) as usize)
+        .clamp(MIN_SETTLERS, MAX_SETTLERS)
+        .min(region.max_population.max(1) as usize)
+        .min(polity.population.max(1) as usize)
+}
+
+/// How many farms feed the founders, one more on arable land
+fn farms(region: &Region, modifiers: &FoundingModifiers, settlers: usize) -> usize {
+    let arable = matches!(region.terrain, Terrain::Plains | Terrain::River)
+        || region.resources == RegionResource::Grain
+        || modifiers.bias_tags.iter().any(|t| t == "agricultural");
+    settlers.div_ceil(SETTLERS_PER_FARM).max(1) + usize::from(arable)
+}
+
+/// Raise a finished building, returning its index
+fn build(world: &mut World, building: BuildingType, at: Vec2) -> usize {
+    let id = world.spawn_building(building, at);
+    let idx = world.buildings.index_of(id).unwrap();
+    world.buildings.states[idx] = BuildingState::Complete;
+    idx
+}
+
+/// Fill the stockpile with what the founders brought
+fn stock(
+    world: &mut World,
+    region: &Region,
+    modifiers: &FoundingModifiers,
+    settlers: usize,
+    granary: bool,
+) {
+    let storage = 1.0 + modifiers.harvest_storage;
+    let mut capacity = BASE_FOOD_CAPACITY * modifiers.stockpile_efficiency * storage;
+    if granary {
+        capacity += GRANARY_CAPACITY;
+    }
+    let mut food = settlers as f32 * FOOD_PER_SETTLER * storage;
+    if modifiers.preparation_trait {
+        food *= 2.0;
+    }
+    world
+        .stockpile
+        .set_capacity(ResourceType::Food, capacity as u32);
+    world.stockpile.add(ResourceType::Food, food as u32);
+
+    let yield_of = |resource: RegionResource| {
+        let rich = if region.resources == resource {
+            2.0
+        } else {
+            1.0
+        };
+        (BASE_MATERIALS * rich * (1.0 + modifiers.resource_efficiency)) as u32
+    };
+    world
+        .stockpile
+        .add(ResourceType::Wood, yield_of(RegionResource::Timber));
+    world
+        .stockpile
+        .add(ResourceType::Stone, yield_of(RegionResource::Stone));
+    if region.resources == RegionResource::Iron {
+        world
+            .stockpile
+            .add(ResourceType::Ore, yield_of(RegionResource::Iron) / 2);
+    }
+}
+
+/// Lay food and resource zones for the region's terrain and resources
+fn lay_zones(world: &mut World, region: &Region) {
+    let wild = match region.terrain {
+        Terrain::Plains | Terrain::River | Terrain::Coast => Abundance::Unlimited,
+        Terrain::Forest | Terrain::Hills | Terrain::Marsh => Abundance::Scarce {
+            current: 200.0,
+            max: 200.0,
+            regen: 1.0,
+        },
+        Terrain::Mountain | Terrain::Desert => Abundance::Scarce {
+            current: 100.0,
+            max: 100.0,
+            regen: 0.5,
+        },
+    };
+    world.add_food_zone(Vec2::new(10.0, 40.0), 15.0, wild.clone());
+    world.add_food_zone(Vec2::new(90.0, 40.0), 15.0, wild);
+    if matches!(
+        region.resources,
+        RegionResource::Fish | RegionResource::Grain
+    ) {
+        world.add_food_zone(Vec2::new(50.0, 95.0), 15.0, Abundance::Unlimited);
+    }
+
+    let mut gathered = match region.terrain {
+        Terrain::Forest => vec![ResourceType::Wood, ResourceType::Wood],
+        Terrain::Mountain | Terrain::Hills => vec![ResourceType::Stone, ResourceType::Ore],
+        _ => vec![ResourceType::Wood],
+    };
+    match region.resources {
+        RegionResource::Timber => gathered.push(ResourceType::Wood),
+        RegionResource::Stone => gathered.push(ResourceType::Stone),
+        RegionResource::Iron | RegionResource::Gold | RegionResource::Gems => {
+            gathered.push(ResourceType::Ore)
+        }
+        _ => {}
+    }
+    for (i, resource) in gathered.into_iter().enumerate() {
+        let at = Vec2::new(10.0 + i as f32 * 25.0, 5.0);
+        world
+            .resource_zones
+            .push(ResourceZone::new(at, resource, 10.0));
+    }
+}
+
+/// Spawn the `n`th settler of a species among the houses
+fn settle(world: &mut World, species: Species, n: usize) {
+    let name = format!("Settler {}", n + 1);
+    match species {
+        Species::Dwarf => world.spawn_dwarf(name),
+        Species::Elf => world.spawn_elf(name),
+        Species::Orc => world.spawn_orc(name),
+        _ => world.spawn_human(name),
+    };
+    let at = Vec2::new(
+        SITE_CENTRE.x - 12.0 + (n % 4) as f32 * 8.0,
+        SITE_CENTRE.y + 5.0 + (n / 4) as f32 * 2.0,
+    );
+    if let Some(archetype) = world
+        .archetypes_mut()
+        .into_iter()
+        .find(|a| a.species() == species)
+    {
+        let components = archetype.components_mut();
+        let i = components.positions.len() - 1;
+        components.positions[i] = at;
+    }
+    if species == Species::Human {
+        let i = world.humans.count() - 1;
+        // Children, parents, and a few elders
+        let age = [6, 20, 24, 30, 38, 52, 64, 12][n % 8];
+        world.humans.ages[i] = age * YEAR_LENGTH as u32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::polity::{
+        CulturalDrift, FoundingConditions, HumanCulturalDrift, HumanState, PolityType, SpeciesState,
+    };
+    use crate::core::astronomy::{CelestialEvent, Season};
+    use crate::core::types::{GovernmentType, PolityId, PolityTier, RulerId};
+    use std::collections::HashMap;
+
+    fn region(terrain: Terrain, resources: RegionResource) -> Region {
+        Region {
+            id: 0,
+            name: "Greenmere".to_string(),
+            terrain,
+            resources,
+            neighbors: Vec::new(),
+            fitness: Region::calculate_fitness(terrain),
+            controller: Some(1),
+            contested_by: Vec::new(),
+            max_population: 1000,
+        }
+    }
+
+    fn polity(species: Species) -> Polity {
+        Polity {
+            id: PolityId(1),
+            name: "Kingdom of Aldoria".to_string(),
+            species,
+            polity_type: PolityType::Kingdom,
+            tier: PolityTier::Kingdom,
+            government: GovernmentType::Autocracy,
+            parent: None,
+            rulers: vec![RulerId(1)],
+            council_roles: HashMap::new(),
+            capital: 0,
+            population: 10000,
+            military_strength: 100.0,
+            economic_strength: 100.0,
+            founding_conditions: FoundingConditions::default(),
+            cultural_drift: CulturalDrift::Human(HumanCulturalDrift::default()),
+            relations: HashMap::new(),
+            species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
+            alive: true,
+        }
+    }
+
+    fn count(world: &World, building: BuildingType) -> usize {
+        world
+            .buildings
+            .building_types
+            .iter()
+            .filter(|&&b| b == building)
+            .count()
+    }
+
+    #[test]
+    fn test_founding_houses_and_feeds_its_settlers() {
+        let modifiers = FoundingModifiers::calculate(20, Season::Spring, &[]);
+        let world = instantiate(
+            &region(Terrain::Plains, RegionResource::Grain),
+            &polity(Species::Human),
+            &modifiers,
+            7,
+        )
+        .unwrap();
+
+        let settlers = world.humans.count();
+        assert!((MIN_SETTLERS..=MAX_SETTLERS).contains(&settlers));
+        let housing = count(&world, BuildingType::House) * 4;
+        assert!(housing >= settlers);
+        assert!(count(&world, BuildingType::Farm) > settlers / SETTLERS_PER_FARM);
+        assert!(world.stockpile.get(ResourceType::Food) > 0);
+        assert!(world
+            .food_zones
+            .iter()
+            .any(|z| matches!(z.abundance, Abundance::Unlimited)));
+        assert!(!world.legacy.chronicle.is_empty());
+    }
+
+    #[test]
+    fn test_founding_matches_its_day() {
+        let site = region(Terrain::Mountain, RegionResource::Iron);
+        let summer = FoundingModifiers::calculate(150, Season::Summer, &[]);
+        let winter = FoundingModifiers::calculate(320, Season::Winter, &[]);
+        let dwarves = polity(Species::Dwarf);
+
+        let open = instantiate(&site, &dwarves, &summer, 7).unwrap();
+        let walled = instantiate(&site, &dwarves, &winter, 7).unwrap();
+
+        assert!(open.dwarves.count() > walled.dwarves.count());
+        assert_eq!(count(&open, BuildingType::MarketSquare), 1);
+        assert_eq!(count(&open, BuildingType::Wall), 0);
+        assert!(count(&walled, BuildingType::Wall) > 0);
+        assert!(walled.stockpile.get(ResourceType::Ore) > 0);
+
+        let blessed =
+            FoundingModifiers::calculate(150, Season::Summer, &[CelestialEvent::PerfectDoubleFull]);
+        let holy = instantiate(&site, &dwarves, &blessed, 7).unwrap();
+        assert_eq!(count(&holy, BuildingType::Shrine), 1);
+    }
+
+    #[test]
+    fn test_founding_refuses_what_the_history_forbids() {
+        let modifiers = FoundingModifiers::calculate(20, Season::Spring, &[]);
+        let mut site = region(Terrain::Plains, RegionResource::None);
+        site.controller = Some(2);
+        assert!(matches!(
+            instantiate(&site, &polity(Species::Human), &modifiers, 7),
+            Err(InstantiateError::HeldByAnother { .. })
+        ));
+
+        site.controller = None;
+        assert!(matches!(
+            instantiate(&site, &polity(Species::Kobold), &modifiers, 7),
+            Err(InstantiateError::UnplayableSpecies(Species::Kobold))
+        ));
+    }
+}
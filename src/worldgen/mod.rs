@@ -0,0 +1,11 @@
+//! World generation - founding playable sites from the aggregate history
+//!
+//! The aggregate history generates regions and polities; this module turns
+//! a moment of that history into a live `World` to play.
+
+pub mod instantiate;
+
+pub use instantiate::{
+    instantiate, instantiate_from_history, InstantiateError, BASE_SETTLERS, MAX_SETTLERS,
+    MIN_SETTLERS, SETTLERS_PER_FARM,
+};
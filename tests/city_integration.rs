@@ -19,7 +19,13 @@ use arc_citadel::core::types::Vec2;
 use arc_citadel::ecs::world::World;
 use arc_citadel::entity::tasks::{Task, TaskPriority};
 use arc_citadel::simulation::resource_zone::ResourceType;
-use arc_citadel::simulation::tick::run_simulation_tick;
+use arc_citadel::simulation::tick::{run_simulation_tick, TICKS_PER_DAY};
+
+/// Start the clock just after 11:00 so construction runs in full daylight
+fn start_at_midday(world: &mut World) {
+    world.astronomy.tick = TICKS_PER_DAY * 11 / 24;
+    world.astronomy.advance_tick();
+}
 
 // ============================================================================
 // Construction Workflow Integration Tests
@@ -159,6 +165,7 @@ fn test_multiple_builders_faster() {
 #[test]
 fn test_construction_progress_tracking() {
     let mut world = World::new();
+    start_at_midday(&mut world);
 
     // Spawn builder with max skill
     let builder = world.spawn_human("Builder".into());
@@ -498,6 +505,7 @@ fn test_full_city_workflow_build_then_produce() {
 #[test]
 fn test_workshop_iron_smelting_chain() {
     let mut world = World::new();
+    start_at_midday(&mut world);
 
     // Spawn builder
     let builder = world.spawn_human("Smith".into());
@@ -468,6 +468,7 @@ fn test_reach_advantage_cascade() {
         armor: ArmorProperties::none(),
         stance: CombatStance::Pressing,
         skill: arc_citadel::combat::CombatSkill::veteran(),
+        aim: None,
     };
 
     // At grapple range, the dagger fighter has the advantage
@@ -1634,3 +1634,35 @@ fn test_founding_conditions_integration() {
         modifiers.defensive_weight
     );
 }
+
+// ============================================================================
+// Snapshot Diff Tests
+// ============================================================================
+
+#[test]
+fn test_snapshot_diff_shows_settlers_taking_up_tasks() {
+    use arc_citadel::debug::snapshot::{diff, Change, WorldSnapshot};
+
+    let mut world = World::new();
+    let alice = world.spawn_human("Alice".into());
+    world.spawn_human("Bob".into());
+    let before = WorldSnapshot::capture(&world);
+
+    for _ in 0..100 {
+        run_simulation_tick(&mut world);
+    }
+    let changes = diff(&before, &WorldSnapshot::capture(&world));
+
+    assert_eq!(changes.to_tick, 100);
+    assert!(
+        changes.for_settler(alice).iter().any(|c| matches!(
+            c,
+            Change::Task {
+                from: None,
+                to: Some(_)
+            }
+        )),
+        "Alice should have taken up a task:\n{}",
+        changes
+    );
+}
@@ -0,0 +1,301 @@
+//! Golden-master tests for emergent behavior
+//!
+//! Each scenario runs a fixed-seed world (or battle) for a set number of ticks
+//! and boils the result down to a few metrics: population, deaths, tasks
+//! completed, relationships formed, battle outcome. The metrics are compared
+//! against expectations stored in `tests/golden/<scenario>.toml`, each number
+//! with a tolerance, so a refactor of action selection or combat that shifts
+//! emergent dynamics fails here instead of slipping through.
+//!
+//! When a change is meant to move the dynamics, regenerate the expectations
+//! and review the diff:
+//!
+//! ```bash
+//! UPDATE_GOLDEN=1 cargo test --test golden_master
+//! ```
+//!
+//! Tolerances are set by hand in the golden files; regenerating keeps each
+//! metric's tolerance and replaces its value.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use arc_citadel::battle::ai::{load_personality, AiCommander};
+use arc_citadel::battle::hex::BattleHexCoord;
+use arc_citadel::battle::unit_type::UnitType;
+use arc_citadel::battle::units::{
+    Army, ArmyId, BattleFormation, BattleUnit, Element, FormationId, UnitId, UnitStance,
+};
+use arc_citadel::battle::{BattleMap, BattleState};
+use arc_citadel::core::types::{EntityId, Vec2};
+use arc_citadel::ecs::world::{Abundance, World};
+use arc_citadel::simulation::tick::{run_simulation_tick, SimulationEvent};
+
+/// An expected metric: a number within a tolerance, or an exact label
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Expected {
+    Within { value: f64, tolerance: f64 },
+    Exact(String),
+}
+
+/// A measured metric
+#[derive(Debug, Clone)]
+enum Metric {
+    Number(f64),
+    Label(String),
+}
+
+impl Metric {
+    fn matches(&self, expected: &Expected) -> bool {
+        match (self, expected) {
+            (Metric::Number(n), Expected::Within { value, tolerance }) => {
+                (n - value).abs() <= *tolerance
+            }
+            (Metric::Label(label), Expected::Exact(expected)) => label == expected,
+            _ => false,
+        }
+    }
+
+    /// This metric as an expectation, keeping an existing tolerance
+    fn to_expected(&self, previous: Option<&Expected>) -> Expected {
+        match self {
+            Metric::Number(n) => {
+                let tolerance = match previous {
+                    Some(Expected::Within { tolerance, .. }) => *tolerance,
+                    _ => 0.0,
+                };
+                Expected::Within {
+                    value: (n * 1000.0).round() / 1000.0,
+                    tolerance,
+                }
+            }
+            Metric::Label(label) => Expected::Exact(label.clone()),
+        }
+    }
+}
+
+impl std::fmt::Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metric::Number(n) => write!(f, "{}", n),
+            Metric::Label(label) => write!(f, "{:?}", label),
+        }
+    }
+}
+
+/// The stored expectations for one scenario
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Golden {
+    metrics: BTreeMap<String, Expected>,
+}
+
+fn golden_path(scenario: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.toml", scenario))
+}
+
+/// Compare a scenario's metrics with its golden file, or rewrite the file
+/// when `UPDATE_GOLDEN` is set
+fn check_golden(scenario: &str, metrics: BTreeMap<&str, Metric>) {
+    let path = golden_path(scenario);
+    let stored: Golden = std::fs::read_to_string(&path)
+        .ok()
+        .map(|text| toml::from_str(&text).expect("golden file should parse"))
+        .unwrap_or_default();
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let updated = Golden {
+            metrics: metrics
+                .iter()
+                .map(|(&name, metric)| {
+                    (
+                        name.to_string(),
+                        metric.to_expected(stored.metrics.get(name)),
+                    )
+                })
+                .collect(),
+        };
+        let text = format!(
+            "# Golden master for the {} scenario (tests/golden_master.rs)\n\
+             # Regenerate with: UPDATE_GOLDEN=1 cargo test --test golden_master\n\
+             # Tolerances are set by hand and kept when regenerating\n\n{}",
+            scenario,
+            toml::to_string(&updated).unwrap()
+        );
+        std::fs::write(&path, text).unwrap();
+        return;
+    }
+
+    let mut failures = Vec::new();
+    for (&name, metric) in &metrics {
+        match stored.metrics.get(name) {
+            Some(expected) if metric.matches(expected) => {}
+            Some(expected) => failures.push(format!(
+                "  {}: got {}, expected {:?}",
+                name, metric, expected
+            )),
+            None => failures.push(format!("  {}: got {}, no expectation stored", name, metric)),
+        }
+    }
+    for name in stored.metrics.keys() {
+        if !metrics.contains_key(name.as_str()) {
+            failures.push(format!("  {}: expected but not measured", name));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} scenario drifted from {}:\n{}\n\
+         If the change is intended, run UPDATE_GOLDEN=1 cargo test --test golden_master",
+        scenario,
+        path.display(),
+        failures.join("\n")
+    );
+}
+
+/// Run a world for `ticks` ticks, measuring what the settlement got up to
+fn run_world(world: &mut World, ticks: u64) -> BTreeMap<&'static str, Metric> {
+    let population = |world: &World| {
+        world.humans.iter_living().count()
+            + world.orcs.iter_living().count()
+            + world.dwarves.iter_living().count()
+            + world.elves.iter_living().count()
+    };
+    let starting = population(world);
+    let (mut tasks_completed, mut blows, mut births) = (0, 0, 0);
+
+    for _ in 0..ticks {
+        for event in run_simulation_tick(world) {
+            match event {
+                SimulationEvent::TaskCompleted { .. } => tasks_completed += 1,
+                SimulationEvent::CombatHit { .. } => blows += 1,
+                SimulationEvent::Born { .. } => births += 1,
+                _ => {}
+            }
+        }
+    }
+
+    let living = population(world);
+    let relationships: usize = world
+        .humans
+        .iter_living()
+        .map(|i| world.humans.social_memories[i].slots.len())
+        .sum();
+    let humans = world.humans.iter_living().count();
+    let mean_food = world
+        .humans
+        .iter_living()
+        .map(|i| world.humans.needs[i].food as f64)
+        .sum::<f64>()
+        / humans.max(1) as f64;
+
+    BTreeMap::from([
+        ("population", Metric::Number(living as f64)),
+        ("humans", Metric::Number(humans as f64)),
+        (
+            "deaths",
+            Metric::Number((starting + births - living) as f64),
+        ),
+        ("births", Metric::Number(births as f64)),
+        ("tasks_completed", Metric::Number(tasks_completed as f64)),
+        ("combat_hits", Metric::Number(blows as f64)),
+        ("relationships", Metric::Number(relationships as f64)),
+        ("mean_food_need", Metric::Number(mean_food)),
+    ])
+}
+
+#[test]
+fn golden_village() {
+    let mut world = World::with_seed(7);
+    world.add_food_zone(Vec2::new(20.0, 20.0), 10.0, Abundance::Unlimited);
+    world.add_food_zone(
+        Vec2::new(60.0, 40.0),
+        8.0,
+        Abundance::Scarce {
+            current: 50.0,
+            max: 50.0,
+            regen: 0.1,
+        },
+    );
+    for n in 0..10 {
+        world.spawn_human(format!("Villager {}", n));
+        world.humans.positions[n] = Vec2::new(15.0 + 3.0 * n as f32, 25.0);
+    }
+
+    let metrics = run_world(&mut world, 1500);
+    check_golden("village", metrics);
+}
+
+#[test]
+fn golden_orc_raid() {
+    let mut world = World::with_seed(11);
+    world.add_food_zone(Vec2::new(20.0, 20.0), 10.0, Abundance::Unlimited);
+    for n in 0..8 {
+        world.spawn_human(format!("Defender {}", n));
+        world.humans.positions[n] = Vec2::new(18.0 + 2.0 * n as f32, 20.0);
+    }
+    for n in 0..4 {
+        world.spawn_orc(format!("Raider {}", n));
+        world.orcs.positions[n] = Vec2::new(20.0 + 3.0 * n as f32, 30.0);
+    }
+
+    let metrics = run_world(&mut world, 600);
+    check_golden("orc_raid", metrics);
+}
+
+fn battle_army(q: i32, rows: &[i32]) -> Army {
+    let mut army = Army::new(ArmyId::new(), EntityId::new());
+    army.hq_position = BattleHexCoord::new(q, 20);
+    army.courier_pool = vec![EntityId::new(); 6];
+    let mut formation = BattleFormation::new(FormationId::new(), EntityId::new());
+    for &r in rows {
+        let mut unit = BattleUnit::new(UnitId::new(), UnitType::Infantry);
+        unit.elements
+            .push(Element::new((0..60).map(|_| EntityId::new()).collect()));
+        unit.position = BattleHexCoord::new(q, r);
+        unit.stance = UnitStance::Formed;
+        formation.units.push(unit);
+    }
+    army.formations.push(formation);
+    army
+}
+
+#[test]
+fn golden_field_battle() {
+    let friendly = battle_army(10, &[15, 18, 21]);
+    let enemy = battle_army(11, &[15, 18]);
+    let mut state = BattleState::with_seed(BattleMap::new(40, 40), friendly, enemy, 3);
+    let personality = load_personality("aggressive").expect("aggressive personality");
+    state.set_enemy_ai(Some(Box::new(AiCommander::new(personality))));
+    state.start_battle();
+
+    let mut ticks = 0;
+    while !state.is_finished() && ticks < 400 {
+        state.run_tick();
+        ticks += 1;
+    }
+
+    let casualties = |army: &Army| -> u32 {
+        army.formations
+            .iter()
+            .flat_map(|f| &f.units)
+            .map(|u| u.casualties)
+            .sum()
+    };
+    let metrics = BTreeMap::from([
+        ("outcome", Metric::Label(format!("{:?}", state.outcome))),
+        ("ticks", Metric::Number(ticks as f64)),
+        (
+            "friendly_casualties",
+            Metric::Number(casualties(&state.friendly_army) as f64),
+        ),
+        (
+            "enemy_casualties",
+            Metric::Number(casualties(&state.enemy_army) as f64),
+        ),
+    ]);
+    check_golden("field_battle", metrics);
+}
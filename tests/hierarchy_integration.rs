@@ -53,6 +53,8 @@ fn create_test_hierarchy() -> (HashMap<PolityId, Polity>, HashMap<RulerId, Ruler
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         },
     );
@@ -89,6 +91,8 @@ fn create_test_hierarchy() -> (HashMap<PolityId, Polity>, HashMap<RulerId, Ruler
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         },
     );
@@ -125,6 +129,8 @@ fn create_test_hierarchy() -> (HashMap<PolityId, Polity>, HashMap<RulerId, Ruler
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         },
     );
@@ -150,6 +156,8 @@ fn create_test_hierarchy() -> (HashMap<PolityId, Polity>, HashMap<RulerId, Ruler
             cultural_drift: CulturalDrift::default(),
             relations: HashMap::new(),
             species_state: SpeciesState::Human(HumanState::default()),
+            religion: None,
+            technologies: Vec::new(),
             alive: true,
         },
     );
@@ -0,0 +1,68 @@
+//! Mixed-species settlements - humans, dwarves, and elves living together
+//!
+//! Dwarves and elves are spawned by the same commands as humans, act on
+//! their own values, learn at their species' rate, eat from the settlement's
+//! stores, and survive a save and load.
+
+use arc_citadel::command::run_script;
+use arc_citadel::core::types::Vec2;
+use arc_citadel::ecs::persistence::{snapshot_from_str, snapshot_to_string};
+use arc_citadel::ecs::world::{Abundance, World};
+use arc_citadel::simulation::resource_zone::ResourceType;
+use arc_citadel::simulation::tick::run_simulation_tick;
+use arc_citadel::simulation::time_scale::TimeScale;
+use arc_citadel::skills::{ChunkDomain, ChunkLibrary};
+
+fn mixed_settlement() -> World {
+    let mut world = World::with_seed(31);
+    world.time_scale = TimeScale::compressed(25).unwrap();
+    world.add_food_zone(Vec2::new(50.0, 50.0), 20.0, Abundance::Unlimited);
+    world.stockpile.add(ResourceType::Food, 500);
+    run_script(
+        &mut world,
+        "spawn Marcus\nspawn Elena\nspawn dwarf Thorin\nspawn dwarf Gimra\nspawn elf Aelwen\nspawn elf Celeborn",
+    )
+    .unwrap();
+    for (i, pos) in world.humans.positions.iter_mut().enumerate() {
+        *pos = Vec2::new(45.0 + i as f32 * 5.0, 45.0);
+    }
+    for (i, pos) in world.dwarves.positions.iter_mut().enumerate() {
+        *pos = Vec2::new(45.0 + i as f32 * 5.0, 50.0);
+    }
+    for (i, pos) in world.elves.positions.iter_mut().enumerate() {
+        *pos = Vec2::new(45.0 + i as f32 * 5.0, 55.0);
+    }
+    world
+}
+
+#[test]
+fn test_dwarves_and_elves_settle_alongside_humans() {
+    let mut world = mixed_settlement();
+    assert_eq!(world.dwarves.count(), 2);
+    assert_eq!(world.elves.count(), 2);
+
+    // A dwarf smith's craft runs deeper than an elf scholar's
+    let craft = |library: &ChunkLibrary| library.domain_summary(ChunkDomain::Craft).total_encoding;
+    assert!(craft(&world.dwarves.chunk_libraries[0]) > craft(&world.elves.chunk_libraries[0]));
+
+    let food_before = world.stockpile.get(ResourceType::Food);
+    // Five days at 25 steps a day
+    for _ in 0..125 {
+        run_simulation_tick(&mut world);
+    }
+
+    assert_eq!(world.dwarves.iter_living().count(), 2);
+    assert_eq!(world.elves.iter_living().count(), 2);
+    assert!(world.dwarves.needs.iter().all(|needs| needs.food < 0.9));
+    assert!(world.elves.needs.iter().all(|needs| needs.food < 0.9));
+    // All six ate from the stores each day, not just the humans
+    assert_eq!(food_before - world.stockpile.get(ResourceType::Food), 6 * 5);
+
+    let loaded = snapshot_from_str(&snapshot_to_string(&world).unwrap()).unwrap();
+    assert_eq!(loaded.dwarves.names, world.dwarves.names);
+    assert_eq!(loaded.elves.chunk_libraries.len(), 2);
+    assert_eq!(
+        loaded.dwarves.values[0].grudge,
+        world.dwarves.values[0].grudge
+    );
+}
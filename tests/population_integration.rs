@@ -14,7 +14,7 @@ use arc_citadel::simulation::tick::run_simulation_tick;
 
 #[test]
 fn test_population_lifecycle() {
-    let mut world = World::new();
+    let mut world = World::with_seed(4);
 
     // Setup initial settlement
     // 2 houses = 8 capacity
@@ -23,7 +23,9 @@ fn test_population_lifecycle() {
     world.buildings.states[0] = BuildingState::Complete;
     world.buildings.states[1] = BuildingState::Complete;
 
-    // Abundant food
+    // Abundant food, in a granary big enough to hold it (the default
+    // capacity of 100 runs out in fifty days, and births stop with it)
+    world.stockpile.set_capacity(ResourceType::Food, 10000);
     world.stockpile.add(ResourceType::Food, 10000);
 
     // Start with 2 humans
@@ -35,7 +37,6 @@ fn test_population_lifecycle() {
 
     // Run for several in-game days (TICKS_PER_DAY = 1000)
     // Running 100000 ticks = 100 days, with 5% growth chance per day
-    // P(no growth in 100 days) = 0.95^100 ~ 0.006 (very unlikely)
     for _ in 0..100000 {
         run_simulation_tick(&mut world);
     }
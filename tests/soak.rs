@@ -0,0 +1,53 @@
+//! Soak tests - a founded settlement left to live for years on end
+//!
+//! The century run is the regression net for the whole crate: births,
+//! deaths, seasons, eras, and raids for a hundred years at one step a day,
+//! with no panic, the settlement alive and within its houses, memory per
+//! living settler bounded, and a chronicle left behind. It takes a few
+//! minutes unoptimized, so it only runs when asked for:
+//!
+//! ```text
+//! cargo test --release --test soak -- --ignored
+//! ```
+
+use arc_citadel::simulation::soak::{found_settlement, run_soak, SoakConfig};
+
+#[test]
+fn test_settlement_lives_through_a_raid() {
+    let mut world = found_settlement(7);
+    let config = SoakConfig {
+        years: 2,
+        steps_per_day: 1,
+        raid_every_years: 1,
+    };
+    let report = run_soak(&mut world, config, |_| {}).expect("settlement should hold up");
+
+    assert_eq!(report.years.len(), 2);
+    assert_eq!(report.raids, 2);
+    assert!(
+        report.years.iter().all(|year| year.blows > 0),
+        "every raid should come to blows"
+    );
+    assert!(report.years.iter().all(|year| year.population > 0));
+    assert!(!world.legacy.chronicle.is_empty());
+}
+
+#[test]
+#[ignore = "a simulated century; run with --ignored, preferably --release"]
+fn test_hundred_year_soak() {
+    let mut world = found_settlement(42);
+    let report = run_soak(&mut world, SoakConfig::default(), |year| {
+        println!(
+            "Year {}: {} living, {} KB, {} chronicle lines",
+            year.year,
+            year.population,
+            year.snapshot_bytes / 1024,
+            year.chronicle_entries
+        );
+    })
+    .unwrap_or_else(|e| panic!("soak failed: {}", e));
+
+    assert_eq!(report.years.len(), 100);
+    assert!(report.settlers > world.humans.iter_living().count());
+    assert!(!world.eras.begun.is_empty());
+}
@@ -35,6 +35,12 @@ class FoundingContext(BaseModel):
     secrecy_trait: bool = Field(default=False)
     siege_mentality: bool = Field(default=False)
 
+    # Faith inherited from the region's holder in world history
+    religion: Optional[str] = Field(default=None, description="Religion the settlement is founded in")
+    patron: Optional[str] = Field(default=None, description="Patron god of that religion")
+    deities: list[str] = Field(default_factory=list, description="The religion's pantheon, patron first")
+    holy_site: bool = Field(default=False, description="The region is one of the religion's holy sites")
+
 
 class TaggedHex(BaseModel):
     """A hex with tag-based composition (100m scale)."""